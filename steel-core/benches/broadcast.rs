@@ -0,0 +1,97 @@
+#![expect(missing_docs, reason = "benchmarks")]
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use steel_core::player::outbound_queue::{
+    self, OutboundPacket, OutboundQueue, OutboundReceiver, QueueLimits,
+};
+use steel_protocol::packet_traits::{CompressionInfo, EncodedPacket};
+use steel_protocol::packets::game::{BossBarColor, BossBarOverlay, BossEventOperation, CBossEvent};
+use steel_protocol::utils::ConnectionProtocol;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+const PLAYER_COUNTS: [usize; 3] = [10, 100, 1000];
+
+const LIMITS: QueueLimits = QueueLimits {
+    low_priority_bytes: usize::MAX,
+    max_bytes: usize::MAX,
+};
+
+/// A packet large enough to be compressed with the default threshold.
+fn packet() -> CBossEvent {
+    CBossEvent {
+        id: Uuid::nil(),
+        operation: BossEventOperation::Add {
+            name: TextComponent::plain("The Ender Dragon ".repeat(32)),
+            progress: 1.0,
+            color: BossBarColor::Pink,
+            overlay: BossBarOverlay::Progress,
+            flags: CBossEvent::DARKEN_SCREEN,
+        },
+    }
+}
+
+/// One queue per player; the receivers are kept so the queues stay open.
+fn connections(players: usize) -> Vec<(OutboundQueue, OutboundReceiver)> {
+    (0..players)
+        .map(|_| outbound_queue::channel(LIMITS))
+        .collect()
+}
+
+fn bench_broadcast(c: &mut Criterion) {
+    let compression = Some(CompressionInfo::default());
+    let mut group = c.benchmark_group("broadcast");
+
+    for players in PLAYER_COUNTS {
+        group.bench_with_input(
+            BenchmarkId::new("encode_per_player", players),
+            &players,
+            |b, &players| {
+                b.iter_batched(
+                    || connections(players),
+                    |connections| {
+                        let packet = packet();
+                        for (queue, _) in &connections {
+                            let encoded = EncodedPacket::from_bare(
+                                packet.clone(),
+                                compression,
+                                ConnectionProtocol::Play,
+                            )
+                            .expect("packet encodes");
+                            let _ = queue.push(OutboundPacket::Encoded(encoded));
+                        }
+                        black_box(connections)
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("encode_once", players),
+            &players,
+            |b, &players| {
+                b.iter_batched(
+                    || connections(players),
+                    |connections| {
+                        let encoded = EncodedPacket::from_bare(
+                            packet(),
+                            compression,
+                            ConnectionProtocol::Play,
+                        )
+                        .expect("packet encodes");
+                        for (queue, _) in &connections {
+                            let _ = queue.push(OutboundPacket::Encoded(encoded.clone()));
+                        }
+                        black_box(connections)
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_broadcast);
+criterion_main!(benches);
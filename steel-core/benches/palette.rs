@@ -0,0 +1,70 @@
+#![expect(missing_docs, reason = "benchmarks")]
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::sync::Once;
+use steel_core::chunk::paletted_container::BlockPalette;
+use steel_registry::{REGISTRY, Registry};
+use steel_utils::BlockStateId;
+
+static INIT: Once = Once::new();
+
+fn ensure_registry() {
+    INIT.call_once(|| {
+        let mut registry = Registry::new_vanilla();
+        registry.freeze();
+        let _ = REGISTRY.init(registry);
+    });
+}
+
+/// A section with `distinct` different block states, spread evenly.
+fn section(distinct: u16) -> BlockPalette {
+    let mut palette = BlockPalette::Homogeneous(BlockStateId(0));
+    let mut next = 0;
+    for y in 0..16 {
+        for z in 0..16 {
+            for x in 0..16 {
+                palette.set(x, y, z, BlockStateId(next % distinct));
+                next += 1;
+            }
+        }
+    }
+    palette
+}
+
+/// Writes sections from single-value to global palettes. The throughput is
+/// the serialized size of the section.
+fn bench_write(c: &mut Criterion) {
+    ensure_registry();
+    let mut group = c.benchmark_group("palette_write");
+
+    for distinct in [1, 4, 16, 64, 512] {
+        let palette = section(distinct);
+        let mut buf = Vec::new();
+        palette.write(&mut buf).expect("write to vec");
+        group.throughput(Throughput::Bytes(buf.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(distinct),
+            &palette,
+            |b, palette| {
+                let mut buf = Vec::with_capacity(16 * 1024);
+                b.iter(|| {
+                    buf.clear();
+                    black_box(palette).write(&mut buf).expect("write to vec");
+                    black_box(&buf);
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_set(c: &mut Criterion) {
+    c.bench_function("palette_fill_16_states", |b| {
+        b.iter(|| black_box(section(black_box(16))));
+    });
+}
+
+criterion_group!(benches, bench_write, bench_set);
+criterion_main!(benches);
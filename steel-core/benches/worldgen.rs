@@ -67,11 +67,13 @@ fn sample_chunk_biomes(
     min_section_y: i32,
     section_count: i32,
 ) {
-    for lx in 0..4i32 {
-        for lz in 0..4i32 {
-            for section_index in 0..section_count {
-                let section_y = min_section_y + section_index;
-                for ly in 0..4i32 {
+    // Same order as `create_biomes`: Section(Y) → X → Y → Z
+    for section_index in 0..section_count {
+        let section_y = min_section_y + section_index;
+        sampler.prepare_section(chunk_x * 4, section_y * 4, chunk_z * 4);
+        for lx in 0..4i32 {
+            for ly in 0..4i32 {
+                for lz in 0..4i32 {
                     let qx = chunk_x * 4 + lx;
                     let qy = section_y * 4 + ly;
                     let qz = chunk_z * 4 + lz;
@@ -146,7 +148,7 @@ fn bench_overworld_noise(c: &mut Criterion) {
     c.bench_function("overworld_fill_from_noise", |b| {
         b.iter(|| {
             let chunk = make_proto_chunk(black_box(0), black_box(0), dim);
-            generator.fill_from_noise(&chunk);
+            generator.fill_from_noise(&chunk, &[], None);
         });
     });
 }
@@ -160,7 +162,7 @@ fn bench_nether_noise(c: &mut Criterion) {
     c.bench_function("nether_fill_from_noise", |b| {
         b.iter(|| {
             let chunk = make_proto_chunk(black_box(0), black_box(0), dim);
-            generator.fill_from_noise(&chunk);
+            generator.fill_from_noise(&chunk, &[], None);
         });
     });
 }
@@ -174,7 +176,7 @@ fn bench_end_noise(c: &mut Criterion) {
     c.bench_function("end_fill_from_noise", |b| {
         b.iter(|| {
             let chunk = make_proto_chunk(black_box(0), black_box(0), dim);
-            generator.fill_from_noise(&chunk);
+            generator.fill_from_noise(&chunk, &[], None);
         });
     });
 }
@@ -191,13 +193,13 @@ fn bench_overworld_surface(c: &mut Criterion) {
         b.iter_batched(
             || {
                 let chunk = make_proto_chunk(0, 0, dim);
-                generator.create_biomes(&chunk);
-                generator.fill_from_noise(&chunk);
+                generator.create_biomes(&chunk, None);
+                generator.fill_from_noise(&chunk, &[], None);
                 chunk
             },
             |chunk| {
                 let neighbor_biomes = self_neighbor_biomes(&chunk);
-                generator.build_surface(black_box(&chunk), &neighbor_biomes);
+                generator.build_surface(black_box(&chunk), &neighbor_biomes, None);
             },
             criterion::BatchSize::SmallInput,
         );
@@ -214,13 +216,13 @@ fn bench_nether_surface(c: &mut Criterion) {
         b.iter_batched(
             || {
                 let chunk = make_proto_chunk(0, 0, dim);
-                generator.create_biomes(&chunk);
-                generator.fill_from_noise(&chunk);
+                generator.create_biomes(&chunk, None);
+                generator.fill_from_noise(&chunk, &[], None);
                 chunk
             },
             |chunk| {
                 let neighbor_biomes = self_neighbor_biomes(&chunk);
-                generator.build_surface(black_box(&chunk), &neighbor_biomes);
+                generator.build_surface(black_box(&chunk), &neighbor_biomes, None);
             },
             criterion::BatchSize::SmallInput,
         );
@@ -237,13 +239,13 @@ fn bench_end_surface(c: &mut Criterion) {
         b.iter_batched(
             || {
                 let chunk = make_proto_chunk(0, 0, dim);
-                generator.create_biomes(&chunk);
-                generator.fill_from_noise(&chunk);
+                generator.create_biomes(&chunk, None);
+                generator.fill_from_noise(&chunk, &[], None);
                 chunk
             },
             |chunk| {
                 let neighbor_biomes = self_neighbor_biomes(&chunk);
-                generator.build_surface(black_box(&chunk), &neighbor_biomes);
+                generator.build_surface(black_box(&chunk), &neighbor_biomes, None);
             },
             criterion::BatchSize::SmallInput,
         );
@@ -13,7 +13,9 @@ use syn::Ident;
 
 mod blocks;
 mod common;
+mod flammables;
 mod items;
+mod map_colors;
 mod strippables;
 mod waxables;
 mod weathering;
@@ -48,6 +50,10 @@ pub fn main() {
         .expect("Failed to write weathering.rs");
     fs::write(format!("{out_dir}/strippables.rs"), strippables::build())
         .expect("Failed to write strippables.rs");
+    fs::write(format!("{out_dir}/flammables.rs"), flammables::build())
+        .expect("Failed to write flammables.rs");
+    fs::write(format!("{out_dir}/map_colors.rs"), map_colors::build())
+        .expect("Failed to write map_colors.rs");
 
     println!("cargo:rerun-if-changed={manifest_dir}/build/classes.json");
     println!("cargo:rerun-if-changed={manifest_dir}/src/behavior/blocks");
@@ -82,6 +82,7 @@ const KNOWN_REGISTRIES: &[&str] = &[
     "vanilla_blocks",
     "vanilla_items",
     "vanilla_fluids",
+    "vanilla_entities",
     "sound_events",
 ];
 
@@ -242,6 +243,8 @@ pub(crate) fn generate_arg(
                 let field_ident = to_item_ident(name);
                 quote! { vanilla_items::ITEMS.#field_ident }
             } else {
+                // Entity types are referenced by their description id (entity.minecraft.oak_boat)
+                let name = name.strip_prefix("entity.minecraft.").unwrap_or(name);
                 let module_ident = Ident::new(module, Span::call_site());
                 let const_ident = to_block_ident(name);
                 quote! { #module_ident::#const_ident }
@@ -0,0 +1,62 @@
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs};
+
+use quote::quote;
+
+use crate::to_block_ident;
+
+#[derive(Deserialize)]
+struct Flammability {
+    ignite_odds: i32,
+    burn_odds: i32,
+}
+
+pub fn build() -> String {
+    println!("cargo:rerun-if-changed=build/flammables.json");
+
+    let flammables_json =
+        fs::read_to_string("build/flammables.json").expect("Failed to read flammables.json");
+    let flammables_entries: BTreeMap<String, Flammability> =
+        serde_json::from_str(&flammables_json).expect("Failed to parse flammables.json");
+
+    let ignite_odds: Vec<proc_macro2::TokenStream> = flammables_entries
+        .iter()
+        .map(|(name, flammability)| (to_block_ident(name), flammability.ignite_odds))
+        .map(|(block, odds)| quote! { b if b == vanilla_blocks::#block => #odds , })
+        .collect();
+
+    let burn_odds: Vec<proc_macro2::TokenStream> = flammables_entries
+        .iter()
+        .map(|(name, flammability)| (to_block_ident(name), flammability.burn_odds))
+        .map(|(block, odds)| quote! { b if b == vanilla_blocks::#block => #odds , })
+        .collect();
+
+    let output = quote! {
+        //! Generated flammability table, matching vanilla's `FireBlock.bootStrap`.
+
+        use steel_registry::vanilla_blocks;
+        use steel_registry::blocks::BlockRef;
+
+        /// Returns the chance of fire spreading onto this block, or 0 if it is not flammable.
+        #[must_use]
+        #[inline]
+        pub fn get_ignite_odds(block: BlockRef) -> i32 {
+            match block {
+                #(#ignite_odds)*
+                _ => 0
+            }
+        }
+
+        /// Returns the chance of fire burning this block away, or 0 if it is not flammable.
+        #[must_use]
+        #[inline]
+        pub fn get_burn_odds(block: BlockRef) -> i32 {
+            match block {
+                #(#burn_odds)*
+                _ => 0
+            }
+        }
+    };
+
+    output.to_string()
+}
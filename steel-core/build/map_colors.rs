@@ -0,0 +1,97 @@
+use heck::ToUpperCamelCase;
+use proc_macro2::{Span, TokenStream};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs};
+use syn::Ident;
+
+use quote::quote;
+
+use crate::to_block_ident;
+
+/// The map color of a block, which may depend on its state.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BlockMapColor {
+    Fixed(String),
+    /// Logs: `top` when the log stands upright, `side` otherwise.
+    Pillar {
+        top: String,
+        side: String,
+    },
+    /// Beds: the pillow half is always wool colored.
+    Bed {
+        foot: String,
+        head: String,
+    },
+}
+
+fn to_color_ident(name: &str) -> Ident {
+    Ident::new(&name.to_upper_camel_case(), Span::call_site())
+}
+
+pub fn build() -> String {
+    println!("cargo:rerun-if-changed=build/map_colors.json");
+
+    let map_colors_json =
+        fs::read_to_string("build/map_colors.json").expect("Failed to read map_colors.json");
+    let map_colors_entries: BTreeMap<String, BlockMapColor> =
+        serde_json::from_str(&map_colors_json).expect("Failed to parse map_colors.json");
+
+    let arms: Vec<TokenStream> = map_colors_entries
+        .iter()
+        .map(|(name, color)| {
+            let block = to_block_ident(name);
+            let color = match color {
+                BlockMapColor::Fixed(color) => {
+                    let color = to_color_ident(color);
+                    quote! { MapColor::#color }
+                }
+                BlockMapColor::Pillar { top, side } => {
+                    let (top, side) = (to_color_ident(top), to_color_ident(side));
+                    quote! {
+                        if state.get_value(&BlockStateProperties::AXIS) == Axis::Y {
+                            MapColor::#top
+                        } else {
+                            MapColor::#side
+                        }
+                    }
+                }
+                BlockMapColor::Bed { foot, head } => {
+                    let (foot, head) = (to_color_ident(foot), to_color_ident(head));
+                    quote! {
+                        if state.get_value(&BlockStateProperties::BED_PART) == BedPart::Foot {
+                            MapColor::#foot
+                        } else {
+                            MapColor::#head
+                        }
+                    }
+                }
+            };
+            quote! { b if b == vanilla_blocks::#block => #color , }
+        })
+        .collect();
+
+    let output = quote! {
+        //! Generated map color table, matching the `mapColor` of vanilla's block properties.
+
+        use steel_registry::vanilla_blocks;
+        use steel_registry::blocks::BlockRef;
+        use steel_registry::blocks::block_state_ext::BlockStateExt;
+        use steel_registry::blocks::properties::{BedPart, BlockStateProperties};
+        use steel_utils::BlockStateId;
+        use steel_utils::math::Axis;
+
+        use crate::map::MapColor;
+
+        /// Returns the color `state` of `block` is drawn with on maps.
+        #[must_use]
+        pub fn get_map_color(block: BlockRef, state: BlockStateId) -> MapColor {
+            match block {
+                #(#arms)*
+                _ => MapColor::None
+            }
+        }
+    };
+
+    output.to_string()
+}
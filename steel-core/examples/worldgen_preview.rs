@@ -0,0 +1,401 @@
+//! Renders previews of Steel's world generation and dumps biome hashes.
+//!
+//! Useful for checking parity with vanilla and for creating new regression
+//! fixtures for `tests/biome_hashes.rs`. Regions are given in chunk
+//! coordinates, both corners inclusive.
+//!
+//! ```text
+//! cargo run --release -p steel-core --example worldgen_preview -- <command> ...
+//!
+//! biomes  <dimension> <seed> <x1> <z1> <x2> <z2> <out.ppm> [block_y]
+//! heights <dimension> <seed> <x1> <z1> <x2> <z2> <out.pgm>
+//! hashes  <seed> <x1> <z1> <x2> <z2> <out.json>
+//! ```
+//!
+//! `biomes` writes one pixel per quart at `block_y` (sea level by default) and
+//! prints which color each biome got. `heights` generates the terrain and
+//! writes the `WORLD_SURFACE_WG` heightmap, one pixel per block. `hashes`
+//! writes every dimension's per-chunk biome hashes in the format of
+//! `test_assets/biome_hashes.json`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::process;
+use std::str::FromStr;
+use std::thread;
+
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use steel_core::chunk::chunk_access::ChunkAccess;
+use steel_core::chunk::chunk_generator::ChunkGenerator;
+use steel_core::chunk::heightmap::HeightmapType;
+use steel_core::chunk::proto_chunk::ProtoChunk;
+use steel_core::chunk::section::{ChunkSection, Sections};
+use steel_core::chunk::world_gen_context::{
+    ChunkGeneratorType, EndGenerator, NetherGenerator, OverworldGenerator,
+};
+use steel_core::worldgen::{BiomeSourceKind, ChunkBiomeSampler};
+use steel_registry::biome::BiomeRef;
+use steel_registry::dimension_type::DimensionType;
+use steel_registry::{REGISTRY, Registry, vanilla_dimension_types};
+use steel_utils::ChunkPos;
+
+const USAGE: &str = "\
+Usage:
+  worldgen_preview biomes <dimension> <seed> <x1> <z1> <x2> <z2> <out.ppm> [block_y]
+  worldgen_preview heights <dimension> <seed> <x1> <z1> <x2> <z2> <out.pgm>
+  worldgen_preview hashes <seed> <x1> <z1> <x2> <z2> <out.json>";
+
+/// The vanilla dimensions Steel can generate.
+#[derive(Clone, Copy)]
+enum Dimension {
+    Overworld,
+    Nether,
+    End,
+}
+
+impl Dimension {
+    const ALL: [Self; 3] = [Self::Overworld, Self::Nether, Self::End];
+
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.strip_prefix("minecraft:").unwrap_or(name) {
+            "overworld" => Ok(Self::Overworld),
+            "the_nether" => Ok(Self::Nether),
+            "the_end" => Ok(Self::End),
+            _ => Err(format!("Unknown dimension {name}")),
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Overworld => "overworld",
+            Self::Nether => "the_nether",
+            Self::End => "the_end",
+        }
+    }
+
+    const fn dimension_type(self) -> &'static DimensionType {
+        match self {
+            Self::Overworld => vanilla_dimension_types::OVERWORLD,
+            Self::Nether => vanilla_dimension_types::THE_NETHER,
+            Self::End => vanilla_dimension_types::THE_END,
+        }
+    }
+
+    fn biome_source(self, seed: u64) -> BiomeSourceKind {
+        match self {
+            Self::Overworld => BiomeSourceKind::overworld(seed),
+            Self::Nether => BiomeSourceKind::nether(seed),
+            Self::End => BiomeSourceKind::end(seed),
+        }
+    }
+
+    fn generator(self, seed: u64) -> ChunkGeneratorType {
+        let source = self.biome_source(seed);
+        match self {
+            Self::Overworld => ChunkGeneratorType::Overworld(OverworldGenerator::new(source, seed)),
+            Self::Nether => ChunkGeneratorType::Nether(NetherGenerator::new(source, seed)),
+            Self::End => ChunkGeneratorType::End(EndGenerator::new(source, seed)),
+        }
+    }
+
+    /// The lowest and highest section Y, inclusive.
+    const fn section_range(self) -> (i32, i32) {
+        let dim = self.dimension_type();
+        (dim.min_y >> 4, ((dim.min_y + dim.height) >> 4) - 1)
+    }
+}
+
+/// A rectangle of chunks, both corners inclusive.
+#[derive(Clone, Copy)]
+struct Region {
+    min_x: i32,
+    min_z: i32,
+    max_x: i32,
+    max_z: i32,
+}
+
+impl Region {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let x1: i32 = parse_arg(&args[0], "chunk coordinate")?;
+        let z1: i32 = parse_arg(&args[1], "chunk coordinate")?;
+        let x2: i32 = parse_arg(&args[2], "chunk coordinate")?;
+        let z2: i32 = parse_arg(&args[3], "chunk coordinate")?;
+        Ok(Self {
+            min_x: x1.min(x2),
+            min_z: z1.min(z2),
+            max_x: x1.max(x2),
+            max_z: z1.max(z2),
+        })
+    }
+
+    const fn width(self) -> usize {
+        (self.max_x - self.min_x + 1) as usize
+    }
+
+    const fn depth(self) -> usize {
+        (self.max_z - self.min_z + 1) as usize
+    }
+
+    /// Every chunk in the region, in X then Z order.
+    fn chunks(self) -> Vec<(i32, i32)> {
+        (self.min_x..=self.max_x)
+            .flat_map(|x| (self.min_z..=self.max_z).map(move |z| (x, z)))
+            .collect()
+    }
+}
+
+fn parse_arg<T: FromStr>(arg: &str, what: &str) -> Result<T, String> {
+    arg.parse().map_err(|_| format!("Invalid {what}: {arg}"))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    // Climate sampler initialization has deep recursion; needs a large stack.
+    let result = thread::Builder::new()
+        .stack_size(16 * 1024 * 1024)
+        .spawn(move || run(&args))
+        .map_err(|e| e.to_string())
+        .and_then(|handle| {
+            handle
+                .join()
+                .map_err(|_| "Worker thread panicked".to_owned())
+        })
+        .and_then(|result| result);
+
+    if let Err(error) = result {
+        eprintln!("{error}\n\n{USAGE}");
+        process::exit(2);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("biomes") if args.len() == 8 || args.len() == 9 => {
+            let dimension = Dimension::parse(&args[1])?;
+            let seed = parse_arg(&args[2], "seed")?;
+            let region = Region::parse(&args[3..7])?;
+            let block_y = args.get(8).map_or(Ok(63), |y| parse_arg(y, "block Y"))?;
+            let source = dimension.biome_source(seed);
+            let (image, legend) = render_biomes(&source, region, block_y);
+            fs::write(&args[7], image).map_err(|e| e.to_string())?;
+            print!("{legend}");
+            Ok(())
+        }
+        Some("heights") if args.len() == 8 => {
+            let dimension = Dimension::parse(&args[1])?;
+            let seed = parse_arg(&args[2], "seed")?;
+            let region = Region::parse(&args[3..7])?;
+            let image = render_heights(dimension, seed, region);
+            fs::write(&args[7], image).map_err(|e| e.to_string())
+        }
+        Some("hashes") if args.len() == 7 => {
+            let seed = parse_arg(&args[1], "seed")?;
+            let region = Region::parse(&args[2..6])?;
+            let json = dump_hashes(seed, region)?;
+            fs::write(&args[6], json).map_err(|e| e.to_string())
+        }
+        _ => Err("Invalid arguments".to_owned()),
+    }
+}
+
+// ── Biome maps ──────────────────────────────────────────────────────────────
+
+/// A stable color for a biome, so maps of different regions line up.
+fn biome_color(biome: BiomeRef) -> [u8; 3] {
+    let digest = md5::compute(biome.key.path.as_bytes());
+    [digest[0], digest[1], digest[2]]
+}
+
+/// Renders the biomes at `block_y` as a binary PPM, one pixel per quart.
+///
+/// Returns the image and a legend of the colors used.
+fn render_biomes(source: &BiomeSourceKind, region: Region, block_y: i32) -> (Vec<u8>, String) {
+    let width = region.width() * 4;
+    let depth = region.depth() * 4;
+    let quart_y = block_y >> 2;
+
+    let columns: Vec<((i32, i32), Vec<BiomeRef>)> = region
+        .chunks()
+        .into_par_iter()
+        .map(|(chunk_x, chunk_z)| {
+            let mut sampler = source.chunk_sampler();
+            let mut biomes = Vec::with_capacity(16);
+            for z in 0..4 {
+                for x in 0..4 {
+                    biomes.push(sampler.sample(chunk_x * 4 + x, quart_y, chunk_z * 4 + z));
+                }
+            }
+            ((chunk_x, chunk_z), biomes)
+        })
+        .collect();
+
+    let mut pixels = vec![0u8; width * depth * 3];
+    let mut legend: FxHashMap<&'static str, [u8; 3]> = FxHashMap::default();
+    for ((chunk_x, chunk_z), biomes) in columns {
+        let base_x = (chunk_x - region.min_x) as usize * 4;
+        let base_z = (chunk_z - region.min_z) as usize * 4;
+        for (i, biome) in biomes.into_iter().enumerate() {
+            let color = biome_color(biome);
+            legend.insert(biome.key.path.as_ref(), color);
+            let pixel = ((base_z + i / 4) * width + base_x + i % 4) * 3;
+            pixels[pixel..pixel + 3].copy_from_slice(&color);
+        }
+    }
+
+    let mut legend: Vec<_> = legend.into_iter().collect();
+    legend.sort_unstable();
+    let mut text = String::new();
+    for (name, [r, g, b]) in legend {
+        let _ = writeln!(text, "#{r:02x}{g:02x}{b:02x} {name}");
+    }
+
+    let mut image = format!("P6\n{width} {depth}\n255\n").into_bytes();
+    image.extend(pixels);
+    (image, text)
+}
+
+// ── Heightmaps ──────────────────────────────────────────────────────────────
+
+fn init_registry() {
+    let mut registry = Registry::new_vanilla();
+    registry.freeze();
+    let _ = REGISTRY.init(registry);
+}
+
+fn make_proto_chunk(chunk_x: i32, chunk_z: i32, dim: &DimensionType) -> ChunkAccess {
+    let section_count = (dim.height / 16) as usize;
+    let sections: Box<[ChunkSection]> = (0..section_count)
+        .map(|_| ChunkSection::new_empty())
+        .collect();
+    let sections = Sections::from_owned(sections);
+    let pos = ChunkPos::new(chunk_x, chunk_z);
+    ChunkAccess::Proto(ProtoChunk::new(sections, pos, dim.min_y, dim.height))
+}
+
+/// Generates the terrain of each chunk and renders its `WORLD_SURFACE_WG`
+/// heightmap as a binary PGM, one pixel per block, black at the bottom of the
+/// world and white at the top.
+fn render_heights(dimension: Dimension, seed: u64, region: Region) -> Vec<u8> {
+    init_registry();
+    let dim = dimension.dimension_type();
+    let generator = dimension.generator(seed);
+    let width = region.width() * 16;
+    let depth = region.depth() * 16;
+
+    let columns: Vec<((i32, i32), Vec<i32>)> = region
+        .chunks()
+        .into_par_iter()
+        .map(|(chunk_x, chunk_z)| {
+            let chunk = make_proto_chunk(chunk_x, chunk_z, dim);
+            generator.create_biomes(&chunk, None);
+            generator.fill_from_noise(&chunk, &[], None);
+            chunk.prime_worldgen_heightmaps();
+            let heightmaps = chunk.proto_heightmaps();
+            let heights =
+                heightmaps
+                    .get(HeightmapType::WorldSurfaceWg)
+                    .map_or_else(Vec::new, |heightmap| {
+                        (0..256)
+                            .map(|i| heightmap.get_first_available(i % 16, i / 16))
+                            .collect()
+                    });
+            ((chunk_x, chunk_z), heights)
+        })
+        .collect();
+
+    let mut pixels = vec![0u8; width * depth];
+    for ((chunk_x, chunk_z), heights) in columns {
+        let base_x = (chunk_x - region.min_x) as usize * 16;
+        let base_z = (chunk_z - region.min_z) as usize * 16;
+        for (i, height) in heights.into_iter().enumerate() {
+            let shade = (height - dim.min_y) * 255 / dim.height;
+            pixels[(base_z + i / 16) * width + base_x + i % 16] = shade.clamp(0, 255) as u8;
+        }
+    }
+
+    let mut image = format!("P5\n{width} {depth}\n255\n").into_bytes();
+    image.extend(pixels);
+    image
+}
+
+// ── Biome hashes ────────────────────────────────────────────────────────────
+
+/// Compute a biome MD5 hash for a chunk, matching `tests/biome_hashes.rs`.
+///
+/// Samples biomes in vanilla's generation iteration order (section → X → Y → Z),
+/// then hashes in deterministic Y → Z → X order with `section_y` markers.
+fn chunk_biome_hash(
+    sampler: &mut ChunkBiomeSampler<'_>,
+    chunk_x: i32,
+    chunk_z: i32,
+    min_section_y: i32,
+    max_section_y: i32,
+) -> String {
+    let mut biomes: FxHashMap<(i32, i32, i32, i32), BiomeRef> = FxHashMap::default();
+
+    for section_y in min_section_y..=max_section_y {
+        sampler.prepare_section(chunk_x * 4, section_y * 4, chunk_z * 4);
+        for x in 0..4i32 {
+            for y in 0..4i32 {
+                for z in 0..4i32 {
+                    let quart_x = chunk_x * 4 + x;
+                    let quart_y = section_y * 4 + y;
+                    let quart_z = chunk_z * 4 + z;
+
+                    let biome = sampler.sample(quart_x, quart_y, quart_z);
+                    biomes.insert((section_y, x, y, z), biome);
+                }
+            }
+        }
+    }
+
+    let mut ctx = md5::Context::new();
+    for section_y in min_section_y..=max_section_y {
+        ctx.consume([section_y as u8]);
+        for y in 0..4i32 {
+            for z in 0..4i32 {
+                for x in 0..4i32 {
+                    let biome = biomes[&(section_y, x, y, z)];
+                    ctx.consume(biome.key.path.as_bytes());
+                }
+            }
+        }
+    }
+
+    format!("{:x}", ctx.finalize())
+}
+
+/// Hashes every chunk of the region in all three dimensions, as the JSON read
+/// by `tests/biome_hashes.rs`.
+fn dump_hashes(seed: u64, region: Region) -> Result<String, String> {
+    let chunks = region.chunks();
+    let mut root = json!({
+        "seed": seed,
+        "num_chunks": chunks.len(),
+    });
+
+    for dimension in Dimension::ALL {
+        let source = dimension.biome_source(seed);
+        let (min_section_y, max_section_y) = dimension.section_range();
+        let hashes: Vec<(i32, i32, String)> = chunks
+            .par_iter()
+            .map(|&(chunk_x, chunk_z)| {
+                let mut sampler = source.chunk_sampler();
+                let hash =
+                    chunk_biome_hash(&mut sampler, chunk_x, chunk_z, min_section_y, max_section_y);
+                (chunk_x, chunk_z, hash)
+            })
+            .collect();
+        root[dimension.name()] = json!({
+            "min_section_y": min_section_y,
+            "max_section_y": max_section_y,
+            "hashes": hashes,
+        });
+    }
+
+    serde_json::to_string_pretty(&root).map_err(|e| e.to_string())
+}
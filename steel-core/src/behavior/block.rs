@@ -8,13 +8,15 @@ use steel_registry::blocks::properties::{BlockStateProperties, Direction};
 use steel_registry::fluid::{FluidRef, FluidState};
 use steel_registry::item_stack::ItemStack;
 use steel_registry::items::ItemRef;
-use steel_registry::{REGISTRY, RegistryEntry, RegistryExt};
+use steel_registry::sound_types::SoundType;
+use steel_registry::{REGISTRY, RegistryEntry, RegistryExt, vanilla_damage_types};
 use steel_utils::types::{InteractionHand, UpdateFlags};
 use steel_utils::{BlockPos, BlockStateId};
 
 use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
 use crate::block_entity::SharedBlockEntity;
 use crate::entity::Entity;
+use crate::entity::damage::DamageSource;
 use crate::fluid::is_water_fluid;
 use crate::player::Player;
 use crate::world::World;
@@ -86,6 +88,22 @@ pub trait BlockBehavior: Send + Sync {
     /// Returns the block state to use when placing this block.
     fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId>;
 
+    /// Returns whether a block being placed may replace this one.
+    ///
+    /// Vanilla parity: `BlockBehavior.canBeReplaced(BlockState, BlockPlaceContext)`.
+    ///
+    /// Checked against the clicked block (to place into it instead of next to
+    /// it) and against the block at the target position. The default uses the
+    /// block's `replaceable` flag; override for blocks that stack with their own
+    /// item (slabs, candles, snow layers, ...).
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores the context"
+    )]
+    fn can_be_replaced(&self, state: BlockStateId, context: &BlockPlaceContext<'_>) -> bool {
+        state.get_block().config.replaceable
+    }
+
     /// Called when this block is placed in the world.
     ///
     /// # Arguments
@@ -109,6 +127,41 @@ pub trait BlockBehavior: Send + Sync {
         // Default: no-op
     }
 
+    /// Called after a player placed this block with an item.
+    ///
+    /// Runs after the block is in the world. Used by multi-block structures like
+    /// doors to place their other half. Vanilla equivalent: `Block.setPlacedBy()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn set_placed_by(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+    ) {
+        // Default: no-op
+    }
+
+    /// Called before a player destroys this block, while it's still in the world.
+    ///
+    /// Vanilla equivalent: `Block.playerWillDestroy()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn player_will_destroy(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+    ) {
+        // Default: no-op
+    }
+
     /// Called after this block is removed from the world, to affect neighbors.
     ///
     /// This is used for things like rails notifying neighbors when removed.
@@ -274,6 +327,33 @@ pub trait BlockBehavior: Send + Sync {
         // Default: no-op
     }
 
+    /// Called when a block event queued with `World::block_event` is run.
+    ///
+    /// Block events run at the end of the world tick, after scheduled ticks.
+    /// Returning `true` forwards the event to nearby clients so they can play
+    /// the matching animation (piston arms, note blocks, chest lids, bells).
+    ///
+    /// # Arguments
+    /// * `state` - The current block state
+    /// * `world` - The world the block is in
+    /// * `pos` - The position of the block
+    /// * `action_id` - The action ID (block-specific meaning)
+    /// * `action_param` - The action parameter (block-specific meaning)
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn trigger_event(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        action_id: u8,
+        action_param: u8,
+    ) -> bool {
+        false
+    }
+
     /// Called when an entity is inside this block's collision area.
     ///
     /// Used by cactus (damage), fire (ignite), sweet berry bush (slow + damage), etc.
@@ -297,6 +377,87 @@ pub trait BlockBehavior: Send + Sync {
         // Default: no-op
     }
 
+    /// Called when an entity lands on this block after falling.
+    ///
+    /// Applies full fall damage by default. Overridden by farmland (trampling),
+    /// hay bales and beds (reduced damage), etc.
+    /// Vanilla equivalent: `Block.fallOn()`.
+    ///
+    /// # Arguments
+    /// * `state` - The block state the entity landed on
+    /// * `world` - The world
+    /// * `pos` - The position of the block
+    /// * `entity` - The entity that landed
+    /// * `fall_distance` - How far the entity fell, in blocks
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation only looks at the entity"
+    )]
+    fn fall_on(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        entity: &dyn Entity,
+        fall_distance: f64,
+    ) {
+        entity.cause_fall_damage(
+            fall_distance,
+            1.0,
+            &DamageSource::environment(vanilla_damage_types::FALL),
+        );
+    }
+
+    /// Gets the sounds played when this block is placed, broken, stepped on, etc.
+    ///
+    /// Defaults to the block's configured sound type. Vanilla equivalent:
+    /// `BlockBehaviour.getSoundType()`.
+    fn get_sound_type(&self, state: BlockStateId) -> SoundType {
+        state.get_block().config.sound_type
+    }
+
+    // === Bonemeal Methods ===
+
+    /// Returns whether bone meal can be used on this block.
+    ///
+    /// Vanilla equivalent: `BonemealableBlock.isValidBonemealTarget()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn is_valid_bonemeal_target(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> bool {
+        false
+    }
+
+    /// Returns whether a bone meal use should actually grow this block.
+    ///
+    /// Bone meal is consumed either way. Only called if
+    /// `is_valid_bonemeal_target()` returns `true`.
+    /// Vanilla equivalent: `BonemealableBlock.isBonemealSuccess()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn is_bonemeal_success(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        true
+    }
+
+    /// Grows this block after a successful bone meal use.
+    ///
+    /// Vanilla equivalent: `BonemealableBlock.performBonemeal()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn perform_bonemeal(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        // Default: no-op
+    }
+
     // === Block Entity Methods ===
 
     /// Returns whether this block has an associated block entity.
@@ -379,6 +540,42 @@ pub trait BlockBehavior: Send + Sync {
         0
     }
 
+    /// Returns the weak redstone signal (0-15) this block emits.
+    ///
+    /// `direction` points from the receiving block towards this block.
+    /// Vanilla equivalent: `BlockBehaviour.getSignal()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn get_signal(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+    ) -> i32 {
+        0
+    }
+
+    /// Returns the strong redstone signal (0-15) this block emits.
+    ///
+    /// Unlike a weak signal, a strong signal also powers through the receiving
+    /// block to its other neighbors. Vanilla equivalent: `BlockBehaviour.getDirectSignal()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait implementation ignores all params"
+    )]
+    fn get_direct_signal(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+    ) -> i32 {
+        0
+    }
+
     // === Fluid Methods ===
 
     /// Returns the fluid state for this block state.
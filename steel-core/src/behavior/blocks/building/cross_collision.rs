@@ -0,0 +1,72 @@
+//! Shared connection logic for fences, walls, iron bars and glass panes.
+//!
+//! Vanilla equivalent: `CrossCollisionBlock` and `Block.isExceptionForConnection()`.
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, BoolProperty, Direction};
+use steel_registry::vanilla_block_tags::{
+    BARS_TAG, C_GLASS_PANES_TAG, FENCE_GATES_TAG, LEAVES_TAG, SHULKER_BOXES_TAG,
+};
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_blocks};
+use steel_utils::BlockStateId;
+
+/// The four horizontal directions, in the order connection properties are computed.
+pub const HORIZONTAL_DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+/// Gets the boolean connection property for a horizontal direction.
+///
+/// Returns `None` for `Up` and `Down`.
+pub const fn connection_property(direction: Direction) -> Option<BoolProperty> {
+    match direction {
+        Direction::North => Some(BlockStateProperties::NORTH),
+        Direction::East => Some(BlockStateProperties::EAST),
+        Direction::South => Some(BlockStateProperties::SOUTH),
+        Direction::West => Some(BlockStateProperties::WEST),
+        Direction::Up | Direction::Down => None,
+    }
+}
+
+/// Checks if a block never connects even though it has sturdy faces.
+///
+/// Vanilla equivalent: `Block.isExceptionForConnection()`.
+pub fn is_exception_for_connection(state: BlockStateId) -> bool {
+    let block = state.get_block();
+    REGISTRY.blocks.is_in_tag(block, &LEAVES_TAG)
+        || REGISTRY.blocks.is_in_tag(block, &SHULKER_BOXES_TAG)
+        || block == vanilla_blocks::BARRIER
+        || block == vanilla_blocks::CARVED_PUMPKIN
+        || block == vanilla_blocks::JACK_O_LANTERN
+        || block == vanilla_blocks::MELON
+        || block == vanilla_blocks::PUMPKIN
+}
+
+/// Checks if a block is iron bars, copper bars or a glass pane.
+///
+/// Vanilla checks `instanceof IronBarsBlock`, which covers all of these.
+pub fn is_bars_or_pane(block: BlockRef) -> bool {
+    REGISTRY.blocks.is_in_tag(block, &BARS_TAG)
+        || REGISTRY.blocks.is_in_tag(block, &C_GLASS_PANES_TAG)
+}
+
+/// Checks if `state` is a fence gate whose sides face `direction`.
+///
+/// Vanilla equivalent: `block instanceof FenceGateBlock && FenceGateBlock.connectsToDirection()`.
+pub fn is_gate_connecting(state: BlockStateId, direction: Direction) -> bool {
+    if !REGISTRY
+        .blocks
+        .is_in_tag(state.get_block(), &FENCE_GATES_TAG)
+    {
+        return false;
+    }
+    state
+        .try_get_value(&BlockStateProperties::HORIZONTAL_FACING)
+        .is_some_and(|facing: Direction| {
+            facing.get_axis() == direction.rotate_y_clockwise().get_axis()
+        })
+}
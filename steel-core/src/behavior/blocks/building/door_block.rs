@@ -0,0 +1,270 @@
+//! Door block behavior implementation.
+//!
+//! Doors are two blocks tall. The lower half is placed by the item and places
+//! the upper half itself; each half keeps the other in sync through shape updates.
+//!
+//! Vanilla equivalent: `DoorBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{
+    BlockStateProperties, Direction, DoorHingeSide, DoubleBlockHalf,
+};
+use steel_registry::blocks::shapes::is_shape_full_block;
+use steel_registry::vanilla_block_tags::DOORS_TAG;
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_blocks};
+use steel_utils::math::Axis;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for all door variants.
+///
+/// Iron and copper doors cannot be opened by hand, only by redstone.
+#[block_behavior]
+pub struct DoorBlock {
+    block: BlockRef,
+    #[json_arg(value, json = "type_can_open_by_hand")]
+    can_open_by_hand: bool,
+    #[json_arg(sound_events, json = "type_door_open")]
+    sound_open: i32,
+    #[json_arg(sound_events, json = "type_door_close")]
+    sound_close: i32,
+}
+
+impl DoorBlock {
+    /// Creates a new door block behavior.
+    ///
+    /// Parameters are provided by the build system from `classes.json`.
+    #[must_use]
+    pub const fn new(
+        block: BlockRef,
+        can_open_by_hand: bool,
+        sound_open: i32,
+        sound_close: i32,
+    ) -> Self {
+        Self {
+            block,
+            can_open_by_hand,
+            sound_open,
+            sound_close,
+        }
+    }
+
+    /// Checks if a state is the lower half of any door.
+    fn is_lower_door(state: BlockStateId) -> bool {
+        REGISTRY.blocks.is_in_tag(state.get_block(), &DOORS_TAG)
+            && state.get_value(&BlockStateProperties::DOUBLE_BLOCK_HALF) == DoubleBlockHalf::Lower
+    }
+
+    /// Checks if the block at `pos` has a full-block collision shape.
+    fn is_full_block(world: &Arc<World>, pos: BlockPos) -> bool {
+        is_shape_full_block(world.get_block_state(pos).get_collision_shape())
+    }
+
+    /// Picks the hinge side so the door opens against a wall or away from a
+    /// neighboring door, falling back to the side of the block the player clicked.
+    ///
+    /// Vanilla equivalent: `DoorBlock.getHinge()`.
+    fn get_hinge(context: &BlockPlaceContext<'_>) -> DoorHingeSide {
+        let world = context.world;
+        let pos = context.relative_pos;
+        let facing = context.horizontal_direction;
+        let left = facing.rotate_y_counter_clockwise();
+        let right = facing.rotate_y_clockwise();
+
+        let solid_balance = -i32::from(Self::is_full_block(world, pos.relative(left)))
+            - i32::from(Self::is_full_block(world, pos.above().relative(left)))
+            + i32::from(Self::is_full_block(world, pos.relative(right)))
+            + i32::from(Self::is_full_block(world, pos.above().relative(right)));
+        let door_left = Self::is_lower_door(world.get_block_state(pos.relative(left)));
+        let door_right = Self::is_lower_door(world.get_block_state(pos.relative(right)));
+
+        if (door_left && !door_right) || solid_balance > 0 {
+            return DoorHingeSide::Right;
+        }
+        if (door_right && !door_left) || solid_balance < 0 {
+            return DoorHingeSide::Left;
+        }
+
+        let (step_x, _, step_z) = facing.offset();
+        let click_x = context.click_location.x - f64::from(pos.x());
+        let click_z = context.click_location.z - f64::from(pos.z());
+        let clicked_left = (step_x >= 0 || click_z >= 0.5)
+            && (step_x <= 0 || click_z <= 0.5)
+            && (step_z >= 0 || click_x <= 0.5)
+            && (step_z <= 0 || click_x >= 0.5);
+        if clicked_left {
+            DoorHingeSide::Left
+        } else {
+            DoorHingeSide::Right
+        }
+    }
+
+    /// Plays the open or close sound.
+    ///
+    /// `exclude` is the player who toggled the door, since their client plays it already.
+    fn play_sound(&self, world: &World, pos: BlockPos, open: bool, exclude: Option<i32>) {
+        let sound = if open {
+            self.sound_open
+        } else {
+            self.sound_close
+        };
+        let pitch = rand::random::<f32>() * 0.1 + 0.9;
+        world.play_block_sound(sound, pos, 1.0, pitch, exclude);
+        // TODO: GameEvent.BLOCK_OPEN / BLOCK_CLOSE when game event system exists
+    }
+}
+
+impl BlockBehavior for DoorBlock {
+    fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        let below_pos = pos.below();
+        let below = world.get_block_state(below_pos);
+        if state.get_value(&BlockStateProperties::DOUBLE_BLOCK_HALF) == DoubleBlockHalf::Lower {
+            below.is_face_sturdy(Direction::Up)
+        } else {
+            below.get_block() == self.block
+        }
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let world = context.world;
+        let pos = context.relative_pos;
+        if pos.y() >= world.get_max_y() || !world.get_block_state(pos.above()).is_replaceable() {
+            return None;
+        }
+
+        let state = self.block.default_state().set_value(
+            &BlockStateProperties::DOUBLE_BLOCK_HALF,
+            DoubleBlockHalf::Lower,
+        );
+        if !self.can_survive(state, world, pos) {
+            return None;
+        }
+
+        let powered = world.has_neighbor_signal(pos) || world.has_neighbor_signal(pos.above());
+        Some(
+            state
+                .set_value(
+                    &BlockStateProperties::HORIZONTAL_FACING,
+                    context.horizontal_direction,
+                )
+                .set_value(&BlockStateProperties::DOOR_HINGE, Self::get_hinge(context))
+                .set_value(&BlockStateProperties::POWERED, powered)
+                .set_value(&BlockStateProperties::OPEN, powered),
+        )
+    }
+
+    fn set_placed_by(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _player: &Player,
+    ) {
+        let upper = state.set_value(
+            &BlockStateProperties::DOUBLE_BLOCK_HALF,
+            DoubleBlockHalf::Upper,
+        );
+        world.set_block(pos.above(), upper, UpdateFlags::UPDATE_ALL);
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        let air = REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+        let half: DoubleBlockHalf = state.get_value(&BlockStateProperties::DOUBLE_BLOCK_HALF);
+
+        // The other half changed: copy its state, or break if it is gone
+        if direction.get_axis() == Axis::Y
+            && (half == DoubleBlockHalf::Lower) == (direction == Direction::Up)
+        {
+            let is_other_half = neighbor_state.get_block() == self.block
+                && neighbor_state.get_value(&BlockStateProperties::DOUBLE_BLOCK_HALF) != half;
+            return if is_other_half {
+                neighbor_state.set_value(&BlockStateProperties::DOUBLE_BLOCK_HALF, half)
+            } else {
+                air
+            };
+        }
+
+        if half == DoubleBlockHalf::Lower
+            && direction == Direction::Down
+            && !self.can_survive(state, world, pos)
+        {
+            return air;
+        }
+        state
+    }
+
+    fn use_without_item(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        if !self.can_open_by_hand {
+            return InteractionResult::Pass;
+        }
+        let open = !state.get_value(&BlockStateProperties::OPEN);
+        let new_state = state.set_value(&BlockStateProperties::OPEN, open);
+        world.set_block(
+            pos,
+            new_state,
+            UpdateFlags::UPDATE_CLIENTS | UpdateFlags::UPDATE_IMMEDIATE,
+        );
+        self.play_sound(world, pos, open, Some(player.id));
+        InteractionResult::Success
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        // Ignore updates caused by the door itself (e.g. the other half toggling)
+        if source_block == self.block {
+            return;
+        }
+        let other_half = if state.get_value(&BlockStateProperties::DOUBLE_BLOCK_HALF)
+            == DoubleBlockHalf::Lower
+        {
+            pos.above()
+        } else {
+            pos.below()
+        };
+        let powered = world.has_neighbor_signal(pos) || world.has_neighbor_signal(other_half);
+        if powered == state.get_value(&BlockStateProperties::POWERED) {
+            return;
+        }
+
+        if powered != state.get_value(&BlockStateProperties::OPEN) {
+            self.play_sound(world, pos, powered, None);
+        }
+        world.set_block(
+            pos,
+            state
+                .set_value(&BlockStateProperties::POWERED, powered)
+                .set_value(&BlockStateProperties::OPEN, powered),
+            UpdateFlags::UPDATE_CLIENTS,
+        );
+    }
+}
@@ -5,7 +5,12 @@
 use std::sync::Arc;
 
 use crate::behavior::block::BlockBehavior;
-use crate::behavior::context::BlockPlaceContext;
+use crate::behavior::blocks::building::cross_collision::{
+    is_exception_for_connection, is_gate_connecting,
+};
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::entity::leash;
+use crate::player::Player;
 use crate::world::World;
 use steel_macros::block_behavior;
 use steel_registry::REGISTRY;
@@ -13,7 +18,7 @@ use steel_registry::TaggedRegistryExt;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::{BlockStateProperties, BoolProperty, Direction};
-use steel_registry::vanilla_block_tags::{FENCE_GATES_TAG, FENCES_TAG};
+use steel_registry::vanilla_block_tags::{FENCES_TAG, WOODEN_FENCES_TAG};
 use steel_utils::{BlockPos, BlockStateId};
 
 /// Behavior for fence blocks.
@@ -47,48 +52,25 @@ impl FenceBlock {
     }
 
     /// Checks if this fence should connect to the given neighbor state.
-    fn connects_to(neighbor_state: BlockStateId, direction: Direction) -> bool {
+    ///
+    /// `direction` points from this fence towards the neighbor.
+    ///
+    /// Vanilla equivalent: `FenceBlock.connectsTo()`.
+    fn connects_to(&self, neighbor_state: BlockStateId, direction: Direction) -> bool {
+        // Wooden fences don't connect to nether brick fences and vice versa
         let neighbor_block = neighbor_state.get_block();
-
-        // Check if it's a fence (same tag)
-        if REGISTRY.blocks.is_in_tag(neighbor_block, &FENCES_TAG) {
+        let is_same_fence = REGISTRY.blocks.is_in_tag(neighbor_block, &FENCES_TAG)
+            && REGISTRY
+                .blocks
+                .is_in_tag(neighbor_block, &WOODEN_FENCES_TAG)
+                == REGISTRY.blocks.is_in_tag(self.block, &WOODEN_FENCES_TAG);
+        if is_same_fence || is_gate_connecting(neighbor_state, direction) {
             return true;
         }
 
-        // Check if it's a fence gate facing the right direction
-        if REGISTRY.blocks.is_in_tag(neighbor_block, &FENCE_GATES_TAG) {
-            // Fence gates connect perpendicular to their facing direction
-            // A gate facing north/south connects to fences to its east/west
-            // A gate facing east/west connects to fences to its north/south
-            if let Some(gate_facing) =
-                neighbor_state.try_get_value(&BlockStateProperties::HORIZONTAL_FACING)
-            {
-                // Gate connects perpendicular to its facing
-                let connects = match (gate_facing, direction) {
-                    // Gate facing N/S connects to blocks on E/W sides,
-                    // Gate facing E/W connects to blocks on N/S sides
-                    (Direction::North | Direction::South, Direction::East | Direction::West)
-                    | (Direction::East | Direction::West, Direction::North | Direction::South) => {
-                        true
-                    }
-                    _ => false,
-                };
-                if connects {
-                    return true;
-                }
-            }
-        }
-
-        // Check if the neighbor has a sturdy face on the opposite side
-        let opposite = match direction {
-            Direction::North => Direction::South,
-            Direction::South => Direction::North,
-            Direction::East => Direction::West,
-            Direction::West => Direction::East,
-            Direction::Up => Direction::Down,
-            Direction::Down => Direction::Up,
-        };
-        neighbor_state.is_face_sturdy(opposite)
+        // Otherwise connect to any sturdy face on the opposite side
+        !is_exception_for_connection(neighbor_state)
+            && neighbor_state.is_face_sturdy(direction.opposite())
     }
 
     /// Gets the connection state for a position by checking all 4 horizontal neighbors.
@@ -98,25 +80,25 @@ impl FenceBlock {
         // Check north
         let north_pos = Direction::North.relative(pos);
         let north_state = world.get_block_state(north_pos);
-        let connects_north = Self::connects_to(north_state, Direction::North);
+        let connects_north = self.connects_to(north_state, Direction::North);
         state = state.set_value(&Self::NORTH, connects_north);
 
         // Check east
         let east_pos = Direction::East.relative(pos);
         let east_state = world.get_block_state(east_pos);
-        let connects_east = Self::connects_to(east_state, Direction::East);
+        let connects_east = self.connects_to(east_state, Direction::East);
         state = state.set_value(&Self::EAST, connects_east);
 
         // Check south
         let south_pos = Direction::South.relative(pos);
         let south_state = world.get_block_state(south_pos);
-        let connects_south = Self::connects_to(south_state, Direction::South);
+        let connects_south = self.connects_to(south_state, Direction::South);
         state = state.set_value(&Self::SOUTH, connects_south);
 
         // Check west
         let west_pos = Direction::West.relative(pos);
         let west_state = world.get_block_state(west_pos);
-        let connects_west = Self::connects_to(west_state, Direction::West);
+        let connects_west = self.connects_to(west_state, Direction::West);
         state = state.set_value(&Self::WEST, connects_west);
 
         state
@@ -124,6 +106,20 @@ impl FenceBlock {
 }
 
 impl BlockBehavior for FenceBlock {
+    /// Ties the entities the player holds to the fence.
+    ///
+    /// Vanilla: `FenceBlock.useWithoutItem()`.
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        leash::bind_player_mobs(world, player, pos)
+    }
+
     fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
         log::debug!(
             "FenceBlock::get_state_for_placement called for {:?} at {:?}",
@@ -148,19 +144,19 @@ impl BlockBehavior for FenceBlock {
         // Only update for horizontal directions
         match direction {
             Direction::North => {
-                let connects = Self::connects_to(neighbor_state, Direction::North);
+                let connects = self.connects_to(neighbor_state, Direction::North);
                 state.set_value(&Self::NORTH, connects)
             }
             Direction::East => {
-                let connects = Self::connects_to(neighbor_state, Direction::East);
+                let connects = self.connects_to(neighbor_state, Direction::East);
                 state.set_value(&Self::EAST, connects)
             }
             Direction::South => {
-                let connects = Self::connects_to(neighbor_state, Direction::South);
+                let connects = self.connects_to(neighbor_state, Direction::South);
                 state.set_value(&Self::SOUTH, connects)
             }
             Direction::West => {
-                let connects = Self::connects_to(neighbor_state, Direction::West);
+                let connects = self.connects_to(neighbor_state, Direction::West);
                 state.set_value(&Self::WEST, connects)
             }
             // Vertical directions don't affect fence connections
@@ -0,0 +1,169 @@
+//! Fence gate block behavior implementation.
+//!
+//! Vanilla equivalent: `FenceGateBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::vanilla_block_tags::WALLS_TAG;
+use steel_registry::{REGISTRY, TaggedRegistryExt};
+use steel_utils::math::Axis;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for all fence gate variants.
+///
+/// Gates open away from the player who uses them, and sit lower (`in_wall`)
+/// when placed between two walls.
+#[block_behavior]
+pub struct FenceGateBlock {
+    block: BlockRef,
+    #[json_arg(sound_events, json = "type_fence_gate_open")]
+    sound_open: i32,
+    #[json_arg(sound_events, json = "type_fence_gate_close")]
+    sound_close: i32,
+}
+
+impl FenceGateBlock {
+    /// Creates a new fence gate block behavior.
+    ///
+    /// Parameters are provided by the build system from `classes.json`.
+    #[must_use]
+    pub const fn new(block: BlockRef, sound_open: i32, sound_close: i32) -> Self {
+        Self {
+            block,
+            sound_open,
+            sound_close,
+        }
+    }
+
+    fn is_wall(state: BlockStateId) -> bool {
+        REGISTRY.blocks.is_in_tag(state.get_block(), &WALLS_TAG)
+    }
+
+    /// Plays the open or close sound.
+    ///
+    /// `exclude` is the player who toggled the gate, since their client plays it already.
+    fn play_sound(&self, world: &World, pos: BlockPos, open: bool, exclude: Option<i32>) {
+        let sound = if open {
+            self.sound_open
+        } else {
+            self.sound_close
+        };
+        let pitch = rand::random::<f32>() * 0.1 + 0.9;
+        world.play_block_sound(sound, pos, 1.0, pitch, exclude);
+        // TODO: GameEvent.BLOCK_OPEN / BLOCK_CLOSE when game event system exists
+    }
+}
+
+impl BlockBehavior for FenceGateBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let world = context.world;
+        let pos = context.relative_pos;
+        let facing = context.horizontal_direction;
+        let powered = world.has_neighbor_signal(pos);
+
+        // The gate's sides are perpendicular to the way it faces
+        let in_wall = if facing.get_axis() == Axis::Z {
+            Self::is_wall(world.get_block_state(pos.relative(Direction::West)))
+                || Self::is_wall(world.get_block_state(pos.relative(Direction::East)))
+        } else {
+            Self::is_wall(world.get_block_state(pos.relative(Direction::North)))
+                || Self::is_wall(world.get_block_state(pos.relative(Direction::South)))
+        };
+
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::HORIZONTAL_FACING, facing)
+                .set_value(&BlockStateProperties::OPEN, powered)
+                .set_value(&BlockStateProperties::POWERED, powered)
+                .set_value(&BlockStateProperties::IN_WALL, in_wall),
+        )
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        let facing: Direction = state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+        if facing.rotate_y_clockwise().get_axis() != direction.get_axis() {
+            return state;
+        }
+        let opposite = world.get_block_state(pos.relative(direction.opposite()));
+        let in_wall = Self::is_wall(neighbor_state) || Self::is_wall(opposite);
+        state.set_value(&BlockStateProperties::IN_WALL, in_wall)
+    }
+
+    fn use_without_item(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let open: bool = state.get_value(&BlockStateProperties::OPEN);
+        let new_state = if open {
+            state.set_value(&BlockStateProperties::OPEN, false)
+        } else {
+            // Swing the gate away from the player
+            let (yaw, _) = player.rotation.load();
+            let player_dir = Direction::from_yaw(yaw);
+            let facing: Direction = state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+            let state = if facing == player_dir.opposite() {
+                state.set_value(&BlockStateProperties::HORIZONTAL_FACING, player_dir)
+            } else {
+                state
+            };
+            state.set_value(&BlockStateProperties::OPEN, true)
+        };
+
+        world.set_block(
+            pos,
+            new_state,
+            UpdateFlags::UPDATE_CLIENTS | UpdateFlags::UPDATE_IMMEDIATE,
+        );
+        self.play_sound(world, pos, !open, Some(player.id));
+        InteractionResult::Success
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        let powered = world.has_neighbor_signal(pos);
+        if powered == state.get_value(&BlockStateProperties::POWERED) {
+            return;
+        }
+
+        let open: bool = state.get_value(&BlockStateProperties::OPEN);
+        if open != powered {
+            self.play_sound(world, pos, powered, None);
+        }
+        world.set_block(
+            pos,
+            state
+                .set_value(&BlockStateProperties::POWERED, powered)
+                .set_value(&BlockStateProperties::OPEN, powered),
+            UpdateFlags::UPDATE_CLIENTS,
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Iron bars and glass pane block behavior implementation.
+//!
+//! Bars and panes connect to each other, to walls, and to sturdy faces.
+//!
+//! Vanilla equivalent: `IronBarsBlock`, `StainedGlassPaneBlock` and
+//! `WeatheringCopperBarsBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::vanilla_block_tags::WALLS_TAG;
+use steel_registry::{REGISTRY, TaggedRegistryExt};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::blocks::building::cross_collision::{
+    HORIZONTAL_DIRECTIONS, connection_property, is_bars_or_pane, is_exception_for_connection,
+};
+use crate::behavior::blocks::building::{WeatherState, WeatheringCopper};
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+
+/// Behavior for iron bars, waxed copper bars and plain glass panes.
+#[block_behavior]
+pub struct IronBarsBlock {
+    block: BlockRef,
+}
+
+impl IronBarsBlock {
+    /// Creates a new iron bars block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Checks if bars connect to the given neighbor state.
+    ///
+    /// `direction` points from the bars towards the neighbor.
+    ///
+    /// Vanilla equivalent: `IronBarsBlock.attachsTo()`.
+    fn attaches_to(neighbor_state: BlockStateId, direction: Direction) -> bool {
+        let neighbor_block = neighbor_state.get_block();
+        is_bars_or_pane(neighbor_block)
+            || REGISTRY.blocks.is_in_tag(neighbor_block, &WALLS_TAG)
+            || (!is_exception_for_connection(neighbor_state)
+                && neighbor_state.is_face_sturdy(direction.opposite()))
+    }
+}
+
+impl BlockBehavior for IronBarsBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let world = context.world;
+        let pos = context.relative_pos;
+        let mut state = self.block.default_state().set_value(
+            &BlockStateProperties::WATERLOGGED,
+            context.is_water_source(),
+        );
+        for direction in HORIZONTAL_DIRECTIONS {
+            if let Some(property) = connection_property(direction) {
+                let neighbor_state = world.get_block_state(pos.relative(direction));
+                state = state.set_value(&property, Self::attaches_to(neighbor_state, direction));
+            }
+        }
+        Some(state)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        match connection_property(direction) {
+            Some(property) => {
+                state.set_value(&property, Self::attaches_to(neighbor_state, direction))
+            }
+            None => state,
+        }
+    }
+}
+
+/// Behavior for stained glass panes.
+///
+/// Connects exactly like [`IronBarsBlock`]; the color only matters for beacons.
+#[block_behavior]
+pub struct StainedGlassPaneBlock {
+    bars: IronBarsBlock,
+}
+
+impl StainedGlassPaneBlock {
+    /// Creates a new stained glass pane block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self {
+            bars: IronBarsBlock::new(block),
+        }
+    }
+}
+
+impl BlockBehavior for StainedGlassPaneBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        self.bars.get_state_for_placement(context)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        self.bars
+            .update_shape(state, world, pos, direction, neighbor_pos, neighbor_state)
+    }
+}
+
+/// Behavior for unwaxed copper bars, which oxidize over time.
+///
+/// See [`WeatherState`]
+#[block_behavior]
+pub struct WeatheringCopperBarsBlock {
+    bars: IronBarsBlock,
+    #[json_arg(r#enum = "WeatherState", json = "weather_state")]
+    weathering: WeatheringCopper,
+}
+
+impl WeatheringCopperBarsBlock {
+    /// Creates a new `WeatheringCopperBarsBlock` behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef, weather_state: WeatherState) -> Self {
+        Self {
+            bars: IronBarsBlock::new(block),
+            weathering: WeatheringCopper::new(weather_state),
+        }
+    }
+}
+
+impl BlockBehavior for WeatheringCopperBarsBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        self.bars.get_state_for_placement(context)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        self.bars
+            .update_shape(state, world, pos, direction, neighbor_pos, neighbor_state)
+    }
+
+    fn is_randomly_ticking(&self, _state: BlockStateId) -> bool {
+        self.weathering.is_randomly_ticking()
+    }
+
+    fn random_tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.weathering.change_over_time(state, world, pos);
+    }
+}
@@ -1,7 +1,18 @@
+mod cross_collision;
+mod door_block;
 mod fence_block;
+mod fence_gate_block;
+mod iron_bars_block;
 mod rotated_pillar_block;
+mod trapdoor_block;
+mod wall_block;
 mod weathering_block;
 
+pub use door_block::DoorBlock;
 pub use fence_block::FenceBlock;
+pub use fence_gate_block::FenceGateBlock;
+pub use iron_bars_block::{IronBarsBlock, StainedGlassPaneBlock, WeatheringCopperBarsBlock};
 pub use rotated_pillar_block::RotatedPillarBlock;
+pub use trapdoor_block::TrapDoorBlock;
+pub use wall_block::WallBlock;
 pub use weathering_block::{WeatherState, WeatheringCopper, WeatheringCopperFullBlock};
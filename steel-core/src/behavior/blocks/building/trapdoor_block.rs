@@ -0,0 +1,162 @@
+//! Trapdoor block behavior implementation.
+//!
+//! Vanilla equivalent: `TrapDoorBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, Half};
+use steel_registry::vanilla_fluids;
+use steel_utils::math::Axis;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::FLUID_BEHAVIORS;
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for all trapdoor variants.
+///
+/// Iron and copper trapdoors cannot be opened by hand, only by redstone.
+#[block_behavior]
+pub struct TrapDoorBlock {
+    block: BlockRef,
+    #[json_arg(value, json = "type_can_open_by_hand")]
+    can_open_by_hand: bool,
+    #[json_arg(sound_events, json = "type_trapdoor_open")]
+    sound_open: i32,
+    #[json_arg(sound_events, json = "type_trapdoor_close")]
+    sound_close: i32,
+}
+
+impl TrapDoorBlock {
+    /// Creates a new trapdoor block behavior.
+    ///
+    /// Parameters are provided by the build system from `classes.json`.
+    #[must_use]
+    pub const fn new(
+        block: BlockRef,
+        can_open_by_hand: bool,
+        sound_open: i32,
+        sound_close: i32,
+    ) -> Self {
+        Self {
+            block,
+            can_open_by_hand,
+            sound_open,
+            sound_close,
+        }
+    }
+
+    /// Sets the new state and lets trapped water flow again.
+    fn set_state(world: &Arc<World>, pos: BlockPos, state: BlockStateId, flags: UpdateFlags) {
+        world.set_block(pos, state, flags);
+        if state.get_value(&BlockStateProperties::WATERLOGGED) {
+            let delay = FLUID_BEHAVIORS
+                .get_behavior(&vanilla_fluids::WATER)
+                .tick_delay(world);
+            world.schedule_fluid_tick_default(pos, &vanilla_fluids::WATER, delay);
+        }
+    }
+
+    /// Plays the open or close sound.
+    ///
+    /// `exclude` is the player who toggled the trapdoor, since their client plays it already.
+    fn play_sound(&self, world: &World, pos: BlockPos, open: bool, exclude: Option<i32>) {
+        let sound = if open {
+            self.sound_open
+        } else {
+            self.sound_close
+        };
+        let pitch = rand::random::<f32>() * 0.1 + 0.9;
+        world.play_block_sound(sound, pos, 1.0, pitch, exclude);
+        // TODO: GameEvent.BLOCK_OPEN / BLOCK_CLOSE when game event system exists
+    }
+}
+
+impl BlockBehavior for TrapDoorBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let clicked_face = context.clicked_face;
+        let state = if !context.replace_clicked && clicked_face.get_axis() != Axis::Y {
+            // Placed against a wall: hinge on that wall, top or bottom by click height
+            let click_y = context.click_location.y - f64::from(context.relative_pos.y());
+            let half = if click_y > 0.5 {
+                Half::Top
+            } else {
+                Half::Bottom
+            };
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::HORIZONTAL_FACING, clicked_face)
+                .set_value(&BlockStateProperties::HALF, half)
+        } else {
+            let half = if clicked_face == Direction::Up {
+                Half::Bottom
+            } else {
+                Half::Top
+            };
+            self.block
+                .default_state()
+                .set_value(
+                    &BlockStateProperties::HORIZONTAL_FACING,
+                    context.horizontal_direction.opposite(),
+                )
+                .set_value(&BlockStateProperties::HALF, half)
+        };
+
+        let powered = context.world.has_neighbor_signal(context.relative_pos);
+        Some(
+            state
+                .set_value(&BlockStateProperties::OPEN, powered)
+                .set_value(&BlockStateProperties::POWERED, powered)
+                .set_value(
+                    &BlockStateProperties::WATERLOGGED,
+                    context.is_water_source(),
+                ),
+        )
+    }
+
+    fn use_without_item(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        if !self.can_open_by_hand {
+            return InteractionResult::Pass;
+        }
+        let open = !state.get_value(&BlockStateProperties::OPEN);
+        let new_state = state.set_value(&BlockStateProperties::OPEN, open);
+        Self::set_state(world, pos, new_state, UpdateFlags::UPDATE_CLIENTS);
+        self.play_sound(world, pos, open, Some(player.id));
+        InteractionResult::Success
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        let powered = world.has_neighbor_signal(pos);
+        if powered == state.get_value(&BlockStateProperties::POWERED) {
+            return;
+        }
+
+        let mut new_state = state.set_value(&BlockStateProperties::POWERED, powered);
+        let open: bool = state.get_value(&BlockStateProperties::OPEN);
+        if open != powered {
+            new_state = new_state.set_value(&BlockStateProperties::OPEN, powered);
+            self.play_sound(world, pos, powered, None);
+        }
+        Self::set_state(world, pos, new_state, UpdateFlags::UPDATE_CLIENTS);
+    }
+}
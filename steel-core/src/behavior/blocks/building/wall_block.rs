@@ -0,0 +1,212 @@
+//! Wall block behavior implementation.
+//!
+//! Walls connect like fences, but each side is low or tall depending on the
+//! block above, and the center post is raised unless the wall runs straight.
+//!
+//! Vanilla equivalent: `WallBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, EnumProperty, WallSide};
+use steel_registry::blocks::shapes::{AABB, VoxelShape};
+use steel_registry::vanilla_block_tags::{WALL_POST_OVERRIDE_TAG, WALLS_TAG};
+use steel_registry::{REGISTRY, TaggedRegistryExt};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::blocks::building::cross_collision::{
+    HORIZONTAL_DIRECTIONS, is_bars_or_pane, is_exception_for_connection, is_gate_connecting,
+};
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+
+/// Footprint the block above must cover for the center post to stay low.
+const TEST_SHAPE_POST: AABB = AABB::new(0.4375, 0.0, 0.4375, 0.5625, 1.0, 0.5625);
+/// Footprint the block above must cover for the north side to be tall.
+const TEST_SHAPE_NORTH: AABB = AABB::new(0.4375, 0.0, 0.0, 0.5625, 1.0, 0.5625);
+/// Footprint the block above must cover for the south side to be tall.
+const TEST_SHAPE_SOUTH: AABB = AABB::new(0.4375, 0.0, 0.4375, 0.5625, 1.0, 1.0);
+/// Footprint the block above must cover for the west side to be tall.
+const TEST_SHAPE_WEST: AABB = AABB::new(0.0, 0.0, 0.4375, 0.5625, 1.0, 0.5625);
+/// Footprint the block above must cover for the east side to be tall.
+const TEST_SHAPE_EAST: AABB = AABB::new(0.4375, 0.0, 0.4375, 1.0, 1.0, 0.5625);
+
+/// Behavior for all wall variants.
+#[block_behavior]
+pub struct WallBlock {
+    block: BlockRef,
+}
+
+impl WallBlock {
+    /// Creates a new wall block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Gets the side property and tall test shape for a horizontal direction.
+    const fn side(direction: Direction) -> Option<(EnumProperty<WallSide>, AABB)> {
+        match direction {
+            Direction::North => Some((BlockStateProperties::NORTH_WALL, TEST_SHAPE_NORTH)),
+            Direction::East => Some((BlockStateProperties::EAST_WALL, TEST_SHAPE_EAST)),
+            Direction::South => Some((BlockStateProperties::SOUTH_WALL, TEST_SHAPE_SOUTH)),
+            Direction::West => Some((BlockStateProperties::WEST_WALL, TEST_SHAPE_WEST)),
+            Direction::Up | Direction::Down => None,
+        }
+    }
+
+    /// Checks if a wall connects to the given neighbor state.
+    ///
+    /// `direction` points from this wall towards the neighbor.
+    ///
+    /// Vanilla equivalent: `WallBlock.connectsTo()`.
+    fn connects_to(neighbor_state: BlockStateId, direction: Direction) -> bool {
+        let neighbor_block = neighbor_state.get_block();
+        REGISTRY.blocks.is_in_tag(neighbor_block, &WALLS_TAG)
+            || is_bars_or_pane(neighbor_block)
+            || is_gate_connecting(neighbor_state, direction)
+            || (!is_exception_for_connection(neighbor_state)
+                && neighbor_state.is_face_sturdy(direction.opposite()))
+    }
+
+    /// Checks if the bottom face of the shape above covers the footprint of `test`.
+    ///
+    /// Vanilla uses voxel arithmetic on the face shape; like the other face checks this
+    /// only looks for a single box that covers it.
+    fn is_covered(above_shape: VoxelShape, test: &AABB) -> bool {
+        above_shape.iter().any(|aabb| {
+            aabb.min_y <= 0.0
+                && aabb.min_x <= test.min_x
+                && aabb.max_x >= test.max_x
+                && aabb.min_z <= test.min_z
+                && aabb.max_z >= test.max_z
+        })
+    }
+
+    /// Sets each side to none, low or tall, then decides whether to raise the post.
+    ///
+    /// `connections` is indexed like [`HORIZONTAL_DIRECTIONS`].
+    ///
+    /// Vanilla equivalent: `WallBlock.updateShape(LevelReader, BlockState, BlockPos, ...)`.
+    fn update_state(
+        state: BlockStateId,
+        connections: [bool; 4],
+        above_state: BlockStateId,
+    ) -> BlockStateId {
+        let above_shape = above_state.get_collision_shape();
+        let mut state = state;
+        for (direction, connected) in HORIZONTAL_DIRECTIONS.into_iter().zip(connections) {
+            let Some((property, test)) = Self::side(direction) else {
+                continue;
+            };
+            let side = if !connected {
+                WallSide::None
+            } else if Self::is_covered(above_shape, &test) {
+                WallSide::Tall
+            } else {
+                WallSide::Low
+            };
+            state = state.set_value(&property, side);
+        }
+        let up = Self::should_raise_post(state, above_state, above_shape);
+        state.set_value(&BlockStateProperties::UP, up)
+    }
+
+    /// Vanilla equivalent: `WallBlock.shouldRaisePost()`.
+    fn should_raise_post(
+        state: BlockStateId,
+        above_state: BlockStateId,
+        above_shape: VoxelShape,
+    ) -> bool {
+        let above_block = above_state.get_block();
+        if REGISTRY.blocks.is_in_tag(above_block, &WALLS_TAG)
+            && above_state.try_get_value(&BlockStateProperties::UP) == Some(true)
+        {
+            return true;
+        }
+
+        let north: WallSide = state.get_value(&BlockStateProperties::NORTH_WALL);
+        let east: WallSide = state.get_value(&BlockStateProperties::EAST_WALL);
+        let south: WallSide = state.get_value(&BlockStateProperties::SOUTH_WALL);
+        let west: WallSide = state.get_value(&BlockStateProperties::WEST_WALL);
+
+        // Ends, corners, T-junctions and lone posts always show the post
+        let north_none = north == WallSide::None;
+        let east_none = east == WallSide::None;
+        let south_none = south == WallSide::None;
+        let west_none = west == WallSide::None;
+        let has_corner = (north_none && east_none && south_none && west_none)
+            || north_none != south_none
+            || east_none != west_none;
+        if has_corner {
+            return true;
+        }
+
+        let has_high_wall = (north == WallSide::Tall && south == WallSide::Tall)
+            || (east == WallSide::Tall && west == WallSide::Tall);
+        if has_high_wall {
+            return false;
+        }
+
+        REGISTRY
+            .blocks
+            .is_in_tag(above_block, &WALL_POST_OVERRIDE_TAG)
+            || Self::is_covered(above_shape, &TEST_SHAPE_POST)
+    }
+
+    /// Reads the current connections from the side properties.
+    fn current_connections(state: BlockStateId) -> [bool; 4] {
+        HORIZONTAL_DIRECTIONS.map(|direction| {
+            Self::side(direction)
+                .is_some_and(|(property, _)| state.get_value(&property) != WallSide::None)
+        })
+    }
+}
+
+impl BlockBehavior for WallBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        let world = context.world;
+        let pos = context.relative_pos;
+        let connections = HORIZONTAL_DIRECTIONS.map(|direction| {
+            Self::connects_to(world.get_block_state(pos.relative(direction)), direction)
+        });
+        let state = self.block.default_state().set_value(
+            &BlockStateProperties::WATERLOGGED,
+            context.is_water_source(),
+        );
+        Some(Self::update_state(
+            state,
+            connections,
+            world.get_block_state(pos.above()),
+        ))
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        match direction {
+            Direction::Down => state,
+            Direction::Up => {
+                Self::update_state(state, Self::current_connections(state), neighbor_state)
+            }
+            _ => {
+                let mut connections = Self::current_connections(state);
+                for (connected, side) in connections.iter_mut().zip(HORIZONTAL_DIRECTIONS) {
+                    if side == direction {
+                        *connected = Self::connects_to(neighbor_state, direction);
+                    }
+                }
+                Self::update_state(state, connections, world.get_block_state(pos.above()))
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Anvil block behavior implementation.
+//!
+//! Opens the anvil menu when right-clicked, where players rename, repair
+//! and combine items. Anvils fall like sand.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::blocks::FallingBlock;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::AnvilMenuProvider;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for the anvil, chipped anvil and damaged anvil blocks.
+///
+/// Vanilla equivalent: `AnvilBlock`.
+#[block_behavior]
+pub struct AnvilBlock {
+    block: BlockRef,
+}
+
+impl AnvilBlock {
+    /// Creates a new anvil block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for AnvilBlock {
+    /// Turns the anvil sideways to the player.
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state().set_value(
+            &BlockStateProperties::HORIZONTAL_FACING,
+            context.horizontal_direction.rotate_y_clockwise(),
+        ))
+    }
+
+    fn on_place(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _old_state: BlockStateId,
+        _moved_by_piston: bool,
+    ) {
+        FallingBlock::schedule_fall_check(world, pos, self.block);
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        FallingBlock::schedule_fall_check(world, pos, self.block);
+        state
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        // TODO: Damage falling anvils and hurt the entities they land on
+        FallingBlock::try_fall(state, world, pos);
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        player.open_menu(&AnvilMenuProvider::new(
+            player.inventory.clone(),
+            world.clone(),
+            pos,
+            TextComponent::translated(translations::CONTAINER_REPAIR.msg()),
+        ));
+
+        // TODO: Award stat INTERACT_WITH_ANVIL
+
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,80 @@
+//! Beacon block behavior implementation.
+//!
+//! Opens the beacon menu when right-clicked, where players pay to choose
+//! the effects the beacon gives.
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::entities::BeaconBlockEntity;
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::beacon_menu::BeaconMenuProvider;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for the beacon block.
+#[block_behavior]
+pub struct BeaconBlock {
+    block: BlockRef,
+}
+
+impl BeaconBlock {
+    /// Creates a new beacon block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for BeaconBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(block_entity) = world.get_block_entity(pos) else {
+            return InteractionResult::Pass;
+        };
+        if !block_entity.lock().as_any().is::<BeaconBlockEntity>() {
+            return InteractionResult::Pass;
+        }
+
+        // TODO: Use the beacon's custom name and check its container lock
+        player.open_menu(&BeaconMenuProvider::new(
+            player.inventory.clone(),
+            block_entity,
+            TextComponent::translated(translations::CONTAINER_BEACON.msg()),
+        ));
+
+        // TODO: Award stat INTERACT_WITH_BEACON
+
+        InteractionResult::Success
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::BEACON, level, pos, state)
+    }
+}
@@ -0,0 +1,222 @@
+//! What dispensers and droppers do with the items they dispense.
+//!
+//! Vanilla equivalent: `DispenseItemBehavior` and the behaviors registered in
+//! `DispenseItemBehavior.bootStrap()`.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{Axis, BlockStateProperties, Direction};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::{level_events, vanilla_blocks, vanilla_items};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::behavior::items::empty_bucket_contents;
+use crate::block_entity::SharedBlockEntity;
+use crate::block_entity::entities::DispenserBlockEntity;
+use crate::world::{World, triangle_random};
+
+/// The dispenser or dropper that is dispensing an item.
+///
+/// Vanilla equivalent: `BlockSource`.
+pub struct BlockSource<'a> {
+    /// The world the block is in.
+    pub world: &'a Arc<World>,
+    /// Position of the block.
+    pub pos: BlockPos,
+    /// State of the block.
+    pub state: BlockStateId,
+    /// The block entity holding the items.
+    pub block_entity: SharedBlockEntity,
+}
+
+impl BlockSource<'_> {
+    /// Returns the direction the block faces.
+    #[must_use]
+    pub fn facing(&self) -> Direction {
+        self.state.get_value(&BlockStateProperties::FACING)
+    }
+
+    /// Returns the point items leave the block from, just inside its front face.
+    ///
+    /// Vanilla equivalent: `DispenserBlock.getDispensePosition()`.
+    #[must_use]
+    pub fn dispense_position(&self) -> DVec3 {
+        let (x, y, z) = self.facing().offset();
+        DVec3::new(
+            f64::from(self.pos.x()) + 0.5 + 0.7 * f64::from(x),
+            f64::from(self.pos.y()) + 0.5 + 0.7 * f64::from(y),
+            f64::from(self.pos.z()) + 0.5 + 0.7 * f64::from(z),
+        )
+    }
+
+    /// Puts `stack` back into the block, or ejects it if it doesn't fit.
+    ///
+    /// Vanilla equivalent: `DefaultDispenseItemBehavior.addToInventoryOrDispense()`.
+    fn add_to_inventory_or_dispense(&self, stack: ItemStack) {
+        let remainder = {
+            let mut guard = self.block_entity.lock();
+            match guard.as_any_mut().downcast_mut::<DispenserBlockEntity>() {
+                Some(dispenser) => dispenser.insert_item(stack),
+                None => stack,
+            }
+        };
+        if !remainder.is_empty() {
+            let facing = self.facing();
+            spawn_item(self.world, remainder, 6, facing, self.dispense_position());
+            play_dispense_sound(self);
+            play_animation(self, facing);
+        }
+    }
+}
+
+/// How an item is dispensed.
+#[derive(Clone, Copy)]
+pub enum DispenseItemBehavior {
+    /// Throws the item out of the block.
+    ///
+    /// Vanilla equivalent: `DefaultDispenseItemBehavior`.
+    Default,
+    /// Fills the bucket from the fluid in front of the block.
+    EmptyBucket,
+    /// Places the bucket's fluid block in front of the block.
+    FilledBucket(BlockRef),
+}
+
+impl DispenseItemBehavior {
+    /// Returns how a dispenser dispenses `stack`.
+    ///
+    /// Vanilla equivalent: `DispenserBlock.getDispenseMethod()`.
+    #[must_use]
+    pub fn for_item(stack: &ItemStack) -> Self {
+        // TODO: Projectiles (arrows, snowballs, eggs, ...) once projectile entities exist,
+        // until then they are thrown out as items
+        if stack.is(&vanilla_items::ITEMS.bucket) {
+            Self::EmptyBucket
+        } else if stack.is(&vanilla_items::ITEMS.water_bucket) {
+            Self::FilledBucket(vanilla_blocks::WATER)
+        } else if stack.is(&vanilla_items::ITEMS.lava_bucket) {
+            Self::FilledBucket(vanilla_blocks::LAVA)
+        } else {
+            Self::Default
+        }
+    }
+
+    /// Dispenses `stack` and returns what is left of it.
+    ///
+    /// Vanilla equivalent: `DefaultDispenseItemBehavior.dispense()`.
+    #[must_use]
+    pub fn dispense(self, source: &BlockSource<'_>, stack: ItemStack) -> ItemStack {
+        let result = self.execute(source, stack);
+        play_dispense_sound(source);
+        play_animation(source, source.facing());
+        result
+    }
+
+    fn execute(self, source: &BlockSource<'_>, mut stack: ItemStack) -> ItemStack {
+        let target = source.pos.relative(source.facing());
+        match self {
+            Self::Default => {
+                let item = stack.split(1);
+                spawn_item(
+                    source.world,
+                    item,
+                    6,
+                    source.facing(),
+                    source.dispense_position(),
+                );
+                stack
+            }
+            Self::EmptyBucket => {
+                let target_state = source.world.get_block_state(target);
+                let Some(result) = BLOCK_BEHAVIORS
+                    .get_behavior(target_state.get_block())
+                    .pickup_block(source.world, target, target_state, None)
+                else {
+                    return Self::Default.execute(source, stack);
+                };
+                // TODO: GameEvent.FLUID_PICKUP when game event system exists
+                consume_with_remainder(source, stack, ItemStack::new(result.filled_bucket))
+            }
+            Self::FilledBucket(fluid_block) => {
+                if empty_bucket_contents(source.world, target, fluid_block) {
+                    consume_with_remainder(
+                        source,
+                        stack,
+                        ItemStack::new(&vanilla_items::ITEMS.bucket),
+                    )
+                } else {
+                    Self::Default.dispense(source, stack)
+                }
+            }
+        }
+    }
+}
+
+/// Uses up one item of `stack` and hands out `remainder` in exchange.
+///
+/// Vanilla equivalent: `DefaultDispenseItemBehavior.consumeWithRemainder()`.
+fn consume_with_remainder(
+    source: &BlockSource<'_>,
+    mut stack: ItemStack,
+    remainder: ItemStack,
+) -> ItemStack {
+    stack.shrink(1);
+    if stack.is_empty() {
+        return remainder;
+    }
+    source.add_to_inventory_or_dispense(remainder);
+    stack
+}
+
+/// Throws `stack` out of a dispenser facing `direction`.
+///
+/// `accuracy` scales the random spread of the throw.
+///
+/// Vanilla equivalent: `DefaultDispenseItemBehavior.spawnItem()`.
+fn spawn_item(
+    world: &Arc<World>,
+    stack: ItemStack,
+    accuracy: i32,
+    direction: Direction,
+    position: DVec3,
+) {
+    let y_offset = if direction.get_axis() == Axis::Y {
+        0.125
+    } else {
+        0.156_25
+    };
+    let spawn_pos = DVec3::new(position.x, position.y - y_offset, position.z);
+
+    let power = rand::random::<f64>() * 0.1 + 0.2;
+    let spread = 0.017_227_5 * f64::from(accuracy);
+    let (step_x, _, step_z) = direction.offset();
+    let velocity = DVec3::new(
+        triangle_random(f64::from(step_x) * power, spread),
+        triangle_random(0.2, spread),
+        triangle_random(f64::from(step_z) * power, spread),
+    );
+    if let Some(item_entity) = world.spawn_item_with_velocity(spawn_pos, stack, velocity) {
+        item_entity.set_no_pickup_delay();
+    }
+}
+
+/// Vanilla equivalent: `DefaultDispenseItemBehavior.playSound()`.
+fn play_dispense_sound(source: &BlockSource<'_>) {
+    source
+        .world
+        .level_event(level_events::SOUND_DISPENSER_DISPENSE, source.pos, 0, None);
+}
+
+/// Vanilla equivalent: `DefaultDispenseItemBehavior.playAnimation()`.
+fn play_animation(source: &BlockSource<'_>, direction: Direction) {
+    source.world.level_event(
+        level_events::PARTICLES_SHOOT_SMOKE,
+        source.pos,
+        direction.get_3d_data_value(),
+        None,
+    );
+}
@@ -0,0 +1,234 @@
+//! Dispenser block behavior implementation.
+//!
+//! A dispenser dispenses one item from a random slot 4 ticks after it gets
+//! powered. Most items are thrown out, some have special behaviors (see
+//! [`DispenseItemBehavior`]).
+//!
+//! Vanilla equivalent: `DispenserBlock`.
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::{level_events, vanilla_block_entity_types};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use super::dispense_item_behavior::{BlockSource, DispenseItemBehavior};
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::entities::DispenserBlockEntity;
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::container::{Container, calculate_redstone_signal_from_container};
+use crate::inventory::dispenser_menu::DispenserMenuProvider;
+use crate::inventory::lock::ContainerRef;
+use crate::player::Player;
+use crate::world::World;
+
+/// Ticks between a dispenser getting powered and dispensing.
+const TRIGGER_DURATION: i32 = 4;
+
+/// Behavior for dispensers.
+#[block_behavior]
+pub struct DispenserBlock {
+    block: BlockRef,
+}
+
+impl DispenserBlock {
+    /// Creates a new dispenser block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Dispenses one item from a random slot.
+    ///
+    /// Vanilla equivalent: `DispenserBlock.dispenseFrom()`.
+    fn dispense_from(state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        let Some(source) = block_source(world, pos, state) else {
+            return;
+        };
+        let Some((slot, stack)) = pick_random_item(&source) else {
+            world.level_event(level_events::SOUND_DISPENSER_FAIL, pos, 0, None);
+            // TODO: GameEvent.BLOCK_ACTIVATE when game event system exists
+            return;
+        };
+        // TODO: Skip items that are disabled by feature flags (vanilla's DispenseItemBehavior.NOOP)
+        let remaining = DispenseItemBehavior::for_item(&stack).dispense(&source, stack);
+        set_dispensed_item(&source, slot, remaining);
+    }
+}
+
+impl BlockBehavior for DispenserBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(place_facing_player(self.block, context))
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        open_menu(
+            world,
+            pos,
+            player,
+            TextComponent::translated(translations::CONTAINER_DISPENSER.msg()),
+        );
+        // TODO: Award stat INSPECT_DISPENSER
+        InteractionResult::Success
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        update_triggered(self.block, state, world, pos);
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        Self::dispense_from(state, world, pos);
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::DISPENSER, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> i32 {
+        container_signal(world, pos)
+    }
+}
+
+/// Faces the dispenser towards the player.
+#[must_use]
+pub(super) fn place_facing_player(
+    block: BlockRef,
+    context: &BlockPlaceContext<'_>,
+) -> BlockStateId {
+    block.default_state().set_value(
+        &BlockStateProperties::FACING,
+        context.get_nearest_looking_direction().opposite(),
+    )
+}
+
+/// Opens the 3x3 menu of the dispenser or dropper at `pos`.
+pub(super) fn open_menu(world: &Arc<World>, pos: BlockPos, player: &Player, title: TextComponent) {
+    let Some(container_ref) = world
+        .get_block_entity(pos)
+        .and_then(ContainerRef::from_block_entity)
+    else {
+        return;
+    };
+    player.open_menu(&DispenserMenuProvider::new(
+        player.inventory.clone(),
+        container_ref,
+        title,
+    ));
+}
+
+/// Schedules dispensing when the block gets powered and tracks the power in `TRIGGERED`.
+///
+/// Like pistons, dispensers are also powered through the block above them.
+pub(super) fn update_triggered(
+    block: BlockRef,
+    state: BlockStateId,
+    world: &Arc<World>,
+    pos: BlockPos,
+) {
+    let has_signal = world.has_neighbor_signal(pos) || world.has_neighbor_signal(pos.above());
+    let triggered = state.get_value(&BlockStateProperties::TRIGGERED);
+    if has_signal && !triggered {
+        world.schedule_block_tick_default(pos, block, TRIGGER_DURATION);
+        world.set_block(
+            pos,
+            state.set_value(&BlockStateProperties::TRIGGERED, true),
+            UpdateFlags::UPDATE_CLIENTS,
+        );
+    } else if !has_signal && triggered {
+        world.set_block(
+            pos,
+            state.set_value(&BlockStateProperties::TRIGGERED, false),
+            UpdateFlags::UPDATE_CLIENTS,
+        );
+    }
+}
+
+/// Returns the comparator output of the dispenser or dropper at `pos`.
+#[must_use]
+pub(super) fn container_signal(world: &Arc<World>, pos: BlockPos) -> i32 {
+    world.get_block_entity(pos).map_or(0, |be| {
+        let guard = be.lock();
+        if let Some(container) = guard.as_container() {
+            calculate_redstone_signal_from_container(container)
+        } else {
+            0
+        }
+    })
+}
+
+/// Returns the dispensing block at `pos`, or `None` if its block entity is missing.
+#[must_use]
+pub(super) fn block_source(
+    world: &Arc<World>,
+    pos: BlockPos,
+    state: BlockStateId,
+) -> Option<BlockSource<'_>> {
+    let Some(block_entity) = world.get_block_entity(pos) else {
+        log::warn!("Ignoring dispensing attempt for block at {pos:?} without a block entity");
+        return None;
+    };
+    Some(BlockSource {
+        world,
+        pos,
+        state,
+        block_entity,
+    })
+}
+
+/// Picks a random non-empty slot and returns it with a copy of its item.
+///
+/// The item stays in the slot while it is dispensed, so that leftovers put back
+/// into the block can't end up in the same slot.
+#[must_use]
+pub(super) fn pick_random_item(source: &BlockSource<'_>) -> Option<(usize, ItemStack)> {
+    let guard = source.block_entity.lock();
+    let dispenser = guard.as_any().downcast_ref::<DispenserBlockEntity>()?;
+    let slot = dispenser.get_random_slot()?;
+    Some((slot, dispenser.get_item(slot).clone()))
+}
+
+/// Replaces the item in `slot` with what is left after dispensing.
+pub(super) fn set_dispensed_item(source: &BlockSource<'_>, slot: usize, stack: ItemStack) {
+    if let Some(container) = source.block_entity.lock().as_container_mut() {
+        container.set_item(slot, stack);
+    }
+}
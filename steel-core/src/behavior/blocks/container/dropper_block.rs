@@ -0,0 +1,135 @@
+//! Dropper block behavior implementation.
+//!
+//! Works like a dispenser, but always throws items out as they are, or puts
+//! them into the container it faces.
+//!
+//! Vanilla equivalent: `DropperBlock`.
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::{level_events, vanilla_block_entity_types};
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use super::dispense_item_behavior::DispenseItemBehavior;
+use super::dispenser_block::{
+    block_source, container_signal, open_menu, pick_random_item, place_facing_player,
+    set_dispensed_item, update_triggered,
+};
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::entities::{HopperBlockEntity, get_container_at};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for droppers.
+#[block_behavior]
+pub struct DropperBlock {
+    block: BlockRef,
+}
+
+impl DropperBlock {
+    /// Creates a new dropper block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Drops one item from a random slot.
+    ///
+    /// Vanilla equivalent: `DropperBlock.dispenseFrom()`.
+    fn dispense_from(state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        let Some(source) = block_source(world, pos, state) else {
+            return;
+        };
+        let Some((slot, stack)) = pick_random_item(&source) else {
+            world.level_event(level_events::SOUND_DISPENSER_FAIL, pos, 0, None);
+            return;
+        };
+
+        let remaining = match get_container_at(world, pos.relative(source.facing())) {
+            Some(target) => {
+                let rest = HopperBlockEntity::add_item(
+                    None,
+                    &mut *target.lock(),
+                    stack.copy_with_count(1),
+                );
+                let mut remaining = stack;
+                if rest.is_empty() {
+                    remaining.shrink(1);
+                }
+                remaining
+            }
+            None => DispenseItemBehavior::Default.dispense(&source, stack),
+        };
+        set_dispensed_item(&source, slot, remaining);
+    }
+}
+
+impl BlockBehavior for DropperBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(place_facing_player(self.block, context))
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        open_menu(
+            world,
+            pos,
+            player,
+            TextComponent::translated(translations::CONTAINER_DROPPER.msg()),
+        );
+        // TODO: Award stat INSPECT_DROPPER
+        InteractionResult::Success
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        update_triggered(self.block, state, world, pos);
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        Self::dispense_from(state, world, pos);
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::DROPPER, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> i32 {
+        container_signal(world, pos)
+    }
+}
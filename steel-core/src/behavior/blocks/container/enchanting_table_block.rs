@@ -0,0 +1,60 @@
+//! Enchanting table block behavior implementation.
+//!
+//! Opens the enchanting table menu when right-clicked, where players spend
+//! lapis lazuli and levels on one of three enchantment offers.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::inventory::EnchantmentMenuProvider;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for the enchanting table block.
+///
+/// Vanilla equivalent: `EnchantingTableBlock`.
+#[block_behavior]
+pub struct EnchantingTableBlock {
+    block: BlockRef,
+}
+
+impl EnchantingTableBlock {
+    /// Creates a new enchanting table block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for EnchantingTableBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        // TODO: Add the enchanting table block entity for the book animation
+        // and use its custom name as the title
+        let enchantment_seed = player.experience.lock().enchantment_seed;
+        player.open_menu(&EnchantmentMenuProvider::new(
+            player.inventory.clone(),
+            world.clone(),
+            pos,
+            enchantment_seed,
+            TextComponent::translated(translations::CONTAINER_ENCHANT.msg()),
+        ));
+        InteractionResult::Success
+    }
+}
@@ -0,0 +1,121 @@
+//! Ender chest block behavior implementation.
+//!
+//! Opens the player's personal 27-slot ender inventory when right-clicked.
+//! Every ender chest shows the same items to the same player.
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::entities::EnderChestBlockEntity;
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::chest_menu::ChestMenuProvider;
+use crate::inventory::container::Container;
+use crate::inventory::lock::ContainerRef;
+use crate::player::Player;
+use crate::world::World;
+use crate::world::redstone::is_redstone_conductor;
+
+/// Behavior for the ender chest block.
+#[block_behavior]
+pub struct EnderChestBlock {
+    block: BlockRef,
+}
+
+impl EnderChestBlock {
+    /// Creates a new ender chest block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for EnderChestBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(
+            self.block
+                .default_state()
+                .set_value(
+                    &BlockStateProperties::HORIZONTAL_FACING,
+                    context.horizontal_direction.opposite(),
+                )
+                .set_value(
+                    &BlockStateProperties::WATERLOGGED,
+                    context.is_water_source(),
+                ),
+        )
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(block_entity) = world.get_block_entity(pos) else {
+            return InteractionResult::Pass;
+        };
+        if !block_entity.lock().as_any().is::<EnderChestBlockEntity>() {
+            return InteractionResult::Pass;
+        }
+
+        // A solid block on top keeps the lid shut
+        if is_redstone_conductor(world.get_block_state(pos.above())) {
+            return InteractionResult::Success;
+        }
+
+        player.open_menu(&ChestMenuProvider::three_rows(
+            player.inventory.clone(),
+            ContainerRef::Other(player.ender_chest_inventory.clone()),
+            TextComponent::translated(translations::CONTAINER_ENDERCHEST.msg()),
+        ));
+
+        // The menu calls `stop_open` when it's closed, which also forgets the chest
+        {
+            let mut ender_chest = player.ender_chest_inventory.lock();
+            ender_chest.set_active_chest(block_entity);
+            ender_chest.start_open(player);
+        }
+
+        // TODO: Award stat OPEN_ENDERCHEST
+        // TODO: Anger nearby piglins (PiglinAi.angerNearbyPiglins)
+
+        InteractionResult::Success
+    }
+
+    fn trigger_event(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        action_id: u8,
+        action_param: u8,
+    ) -> bool {
+        world
+            .get_block_entity(pos)
+            .is_some_and(|be| be.lock().trigger_event(action_id, action_param))
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::ENDER_CHEST, level, pos, state)
+    }
+}
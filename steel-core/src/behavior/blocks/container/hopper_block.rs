@@ -0,0 +1,159 @@
+//! Hopper block behavior implementation.
+//!
+//! Hoppers move items between containers, see [`HopperBlockEntity`]. A powered
+//! hopper is disabled and stops moving items.
+//!
+//! Vanilla equivalent: `HopperBlock`.
+//!
+//! [`HopperBlockEntity`]: crate::block_entity::entities::HopperBlockEntity
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{Axis, BlockStateProperties, Direction};
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::inventory::hopper_menu::HopperMenuProvider;
+use crate::inventory::lock::ContainerRef;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for hoppers.
+#[block_behavior]
+pub struct HopperBlock {
+    block: BlockRef,
+}
+
+impl HopperBlock {
+    /// Creates a new hopper block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Disables the hopper while it is powered.
+    ///
+    /// Vanilla equivalent: `HopperBlock.checkPoweredState()`.
+    fn check_powered_state(state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        let should_be_enabled = !world.has_neighbor_signal(pos);
+        if should_be_enabled != state.get_value(&BlockStateProperties::ENABLED) {
+            world.set_block(
+                pos,
+                state.set_value(&BlockStateProperties::ENABLED, should_be_enabled),
+                UpdateFlags::UPDATE_CLIENTS,
+            );
+        }
+    }
+}
+
+impl BlockBehavior for HopperBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Hoppers point away from the clicked face, but never up
+        let direction = context.clicked_face.opposite();
+        let facing = if direction.get_axis() == Axis::Y {
+            Direction::Down
+        } else {
+            direction
+        };
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::FACING_HOPPER, facing)
+                .set_value(&BlockStateProperties::ENABLED, true),
+        )
+    }
+
+    fn on_place(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        old_state: BlockStateId,
+        _moved_by_piston: bool,
+    ) {
+        if old_state.get_block() != self.block {
+            Self::check_powered_state(state, world, pos);
+        }
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        Self::check_powered_state(state, world, pos);
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(container_ref) = world
+            .get_block_entity(pos)
+            .and_then(ContainerRef::from_block_entity)
+        else {
+            return InteractionResult::Pass;
+        };
+
+        player.open_menu(&HopperMenuProvider::new(
+            player.inventory.clone(),
+            container_ref,
+            TextComponent::translated(translations::CONTAINER_HOPPER.msg()),
+        ));
+
+        // TODO: Award stat INSPECT_HOPPER
+        InteractionResult::Success
+    }
+
+    // TODO: Pick up items falling into the bowl right away (vanilla's entityInside),
+    // until then they are picked up on the next transfer
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::HOPPER, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> i32 {
+        world.get_block_entity(pos).map_or(0, |be| {
+            let guard = be.lock();
+            if let Some(container) = guard.as_container() {
+                calculate_redstone_signal_from_container(container)
+            } else {
+                0
+            }
+        })
+    }
+}
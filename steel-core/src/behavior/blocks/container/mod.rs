@@ -1,5 +1,22 @@
+mod anvil_block;
 mod barrel_block;
+mod beacon_block;
 mod crafting_table_block;
+mod dispense_item_behavior;
+mod dispenser_block;
+mod dropper_block;
+mod enchanting_table_block;
+mod ender_chest_block;
+mod hopper_block;
+mod shulker_box_block;
 
+pub use anvil_block::AnvilBlock;
 pub use barrel_block::BarrelBlock;
+pub use beacon_block::BeaconBlock;
 pub use crafting_table_block::CraftingTableBlock;
+pub use dispenser_block::DispenserBlock;
+pub use dropper_block::DropperBlock;
+pub use enchanting_table_block::EnchantingTableBlock;
+pub use ender_chest_block::EnderChestBlock;
+pub use hopper_block::HopperBlock;
+pub use shulker_box_block::ShulkerBoxBlock;
@@ -0,0 +1,214 @@
+//! Shulker box block behavior implementation.
+//!
+//! Opens a 27-slot menu when right-clicked, as long as the lid has room to
+//! open. Broken shulker boxes keep their contents on the dropped item.
+
+use std::sync::{Arc, Weak};
+
+use glam::DVec3;
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::{REGISTRY, RegistryExt, vanilla_block_entity_types};
+use steel_utils::{BlockPos, BlockStateId, translations};
+use text_components::TextComponent;
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::entities::{AnimationStatus, ShulkerBoxBlockEntity};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::inventory::container::calculate_redstone_signal_from_container;
+use crate::inventory::lock::ContainerRef;
+use crate::inventory::shulker_box_menu::ShulkerBoxMenuProvider;
+use crate::physics::{CollisionWorld, WorldCollisionProvider};
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for shulker boxes of every color.
+#[block_behavior]
+pub struct ShulkerBoxBlock {
+    block: BlockRef,
+}
+
+impl ShulkerBoxBlock {
+    /// Creates a new shulker box block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+
+    /// Returns whether the box can be opened: an already moving lid always
+    /// can, a closed one needs the space in front of it to be free.
+    ///
+    /// Vanilla equivalent: `ShulkerBoxBlock.canOpen()`.
+    fn can_open(
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        status: AnimationStatus,
+    ) -> bool {
+        if status != AnimationStatus::Closed {
+            return true;
+        }
+
+        let facing: Direction = state.get_value(&BlockStateProperties::FACING);
+        let (dx, dy, dz) = facing.offset();
+        // The half block the lid rises into
+        let span = |start: i32, offset: i32| -> (f64, f64) {
+            let start = f64::from(start);
+            match offset {
+                1 => (start + 1.0, start + 1.5),
+                -1 => (start - 0.5, start),
+                _ => (start, start + 1.0),
+            }
+        };
+        let (min_x, max_x) = span(pos.x(), dx);
+        let (min_y, max_y) = span(pos.y(), dy);
+        let (min_z, max_z) = span(pos.z(), dz);
+        let lid = AABBd::new(min_x, min_y, min_z, max_x, max_y, max_z).deflate(1.0E-6);
+
+        // TODO: Also check entity collisions (boats, shulkers) like `Level.noCollision`
+        WorldCollisionProvider::new(world)
+            .get_block_collisions(&lid)
+            .is_empty()
+    }
+}
+
+impl BlockBehavior for ShulkerBoxBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(
+            self.block
+                .default_state()
+                .set_value(&BlockStateProperties::FACING, context.clicked_face),
+        )
+    }
+
+    fn use_without_item(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let Some(block_entity) = world.get_block_entity(pos) else {
+            return InteractionResult::Pass;
+        };
+
+        let status = {
+            let guard = block_entity.lock();
+            let Some(shulker_box) = guard.as_any().downcast_ref::<ShulkerBoxBlockEntity>() else {
+                return InteractionResult::Pass;
+            };
+            shulker_box.animation_status()
+        };
+        if !Self::can_open(state, world, pos, status) {
+            return InteractionResult::Success;
+        }
+
+        let Some(container_ref) = ContainerRef::from_block_entity(block_entity.clone()) else {
+            return InteractionResult::Pass;
+        };
+
+        player.open_menu(&ShulkerBoxMenuProvider::new(
+            player.inventory.clone(),
+            container_ref,
+            TextComponent::translated(translations::CONTAINER_SHULKER_BOX.msg()),
+        ));
+
+        // The menu calls `stop_open` when it's closed
+        if let Some(container) = block_entity.lock().as_container_mut() {
+            container.start_open(player);
+        }
+
+        // TODO: Award stat OPEN_SHULKER_BOX
+        // TODO: Anger nearby piglins (PiglinAi.angerNearbyPiglins)
+
+        InteractionResult::Success
+    }
+
+    fn trigger_event(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        action_id: u8,
+        action_param: u8,
+    ) -> bool {
+        world
+            .get_block_entity(pos)
+            .is_some_and(|be| be.lock().trigger_event(action_id, action_param))
+    }
+
+    /// Creative players don't get block drops, so the box is dropped here to
+    /// keep its contents.
+    fn player_will_destroy(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+    ) {
+        if !player.has_infinite_materials() {
+            return;
+        }
+        let Some(block_entity) = world.get_block_entity(pos) else {
+            return;
+        };
+        let components = {
+            let guard = block_entity.lock();
+            match guard.as_container() {
+                Some(container) if !container.is_empty() => guard.collect_components(),
+                _ => return,
+            }
+        };
+        let Some(item) = REGISTRY.items.by_key(&self.block.key) else {
+            return;
+        };
+
+        let mut stack = ItemStack::new(item);
+        stack.apply_components(&components);
+        let center = DVec3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()) + 0.5,
+            f64::from(pos.z()) + 0.5,
+        );
+        world.spawn_item(center, stack);
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(vanilla_block_entity_types::SHULKER_BOX, level, pos, state)
+    }
+
+    fn has_analog_output_signal(&self, _state: BlockStateId) -> bool {
+        true
+    }
+
+    fn get_analog_output_signal(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> i32 {
+        world.get_block_entity(pos).map_or(0, |be| {
+            let guard = be.lock();
+            if let Some(container) = guard.as_container() {
+                calculate_redstone_signal_from_container(container)
+            } else {
+                0
+            }
+        })
+    }
+}
@@ -7,19 +7,24 @@ use std::f64::consts::PI;
 use std::sync::{Arc, Weak};
 
 use steel_macros::block_behavior;
+use steel_protocol::packets::game::SoundSource;
 use steel_registry::REGISTRY;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::{BlockStateProperties, Direction};
 use steel_registry::blocks::shapes::SupportType;
-use steel_registry::vanilla_blocks;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::loot_table::DyeColor;
+use steel_registry::{level_events, sound_events, vanilla_blocks, vanilla_items};
 use steel_utils::locks::SyncMutex;
+use steel_utils::types::InteractionHand;
 use steel_utils::{BlockPos, BlockStateId};
 
 use crate::behavior::block::BlockBehavior;
 use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
-use crate::block_entity::SharedBlockEntity;
-use crate::block_entity::entities::SignBlockEntity;
+use crate::block_entity::entities::{SignBlockEntity, SignText, dye_color_from_str};
+use crate::block_entity::{BlockEntity, SharedBlockEntity};
+use crate::command::sender::CommandSender;
 use crate::player::Player;
 use crate::world::World;
 
@@ -195,80 +200,198 @@ fn can_wall_hanging_sign_survive(world: &Arc<World>, pos: BlockPos, facing: Dire
     can_attach_clockwise || can_attach_counter
 }
 
-// TODO: Implement sign applicators (use_with_item):
-// - Dye items: Change sign text color (front or back based on player facing)
-//   - Check if sign is not waxed
-//   - Get the SignText for the side player is facing
-//   - If color differs from dye color, update it and consume the dye
-//   - Play DYE_USE sound
-// - Glow Ink Sac: Make sign text glow
-//   - Check if sign is not waxed
-//   - If text is not already glowing, set has_glowing_text = true
-//   - Consume the ink sac
-//   - Play GLOW_INK_SAC_USE sound
-// - Ink Sac: Remove glow from sign text
-//   - Check if sign is not waxed
-//   - If text is glowing, set has_glowing_text = false
-//   - Consume the ink sac
-//   - Play INK_SAC_USE sound
-// - Honeycomb: Wax the sign (prevents future edits)
-//   - If sign is not already waxed, set is_waxed = true
-//   - Consume the honeycomb
-//   - Play HONEYCOMB_WAX_ON sound
-//   - Spawn WAX_ON particles
-
-/// Attempts to open the sign editor for a player.
+/// An item that changes a sign when used on it, like vanilla's `SignApplicator`.
+#[derive(Debug, Clone, Copy)]
+enum SignApplicator {
+    /// Recolors the text on the clicked side.
+    Dye(DyeColor),
+    /// Makes the text on the clicked side glow.
+    GlowInkSac,
+    /// Removes the glow from the text on the clicked side.
+    InkSac,
+    /// Waxes the sign so it can no longer be edited.
+    Honeycomb,
+}
+
+impl SignApplicator {
+    /// Gets the applicator for an item, if it is one.
+    fn from_item(item_stack: &ItemStack) -> Option<Self> {
+        if item_stack.is(&vanilla_items::ITEMS.glow_ink_sac) {
+            return Some(Self::GlowInkSac);
+        }
+        if item_stack.is(&vanilla_items::ITEMS.ink_sac) {
+            return Some(Self::InkSac);
+        }
+        if item_stack.is(&vanilla_items::ITEMS.honeycomb) {
+            return Some(Self::Honeycomb);
+        }
+        let key = &item_stack.item.key;
+        if key.namespace != "minecraft" {
+            return None;
+        }
+        key.path
+            .strip_suffix("_dye")
+            .and_then(dye_color_from_str)
+            .map(Self::Dye)
+    }
+
+    /// Checks if the applicator can be used on a side with the given text.
+    ///
+    /// Dyes and ink only make sense on text; wax applies to the whole sign.
+    fn can_apply_to(self, text: &SignText) -> bool {
+        matches!(self, Self::Honeycomb) || text.has_message()
+    }
+
+    /// Applies the applicator to one side of the sign.
+    ///
+    /// Returns `false` if the sign already looked like this.
+    fn apply(self, sign: &mut SignBlockEntity, is_front_text: bool) -> bool {
+        let text = sign.get_text_mut(is_front_text);
+        match self {
+            Self::Dye(color) if text.color != color => text.color = color,
+            Self::GlowInkSac if !text.has_glowing_text => text.has_glowing_text = true,
+            Self::InkSac if text.has_glowing_text => text.has_glowing_text = false,
+            Self::Honeycomb if !sign.is_waxed => sign.is_waxed = true,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Plays the sound (and particles) for a successful application.
+    fn play_effects(self, world: &World, pos: BlockPos) {
+        let sound = match self {
+            Self::Dye(_) => sound_events::ITEM_DYE_USE,
+            Self::GlowInkSac => sound_events::ITEM_GLOW_INK_SAC_USE,
+            Self::InkSac => sound_events::ITEM_INK_SAC_USE,
+            Self::Honeycomb => {
+                world.level_event(level_events::PARTICLES_AND_SOUND_WAX_ON, pos, 0, None);
+                return;
+            }
+        };
+        world.play_sound(sound, SoundSource::Players, pos, 1.0, 1.0, None);
+    }
+}
+
+/// Runs the `run_command` click events on the side of the sign the player clicked.
 ///
-/// Checks all conditions required by vanilla:
-/// 1. Block entity exists and is a sign
-/// 2. Sign is not waxed
-/// 3. No other player is currently editing
-/// 4. Player has build permission (`may_build`)
+/// Must be called without the sign locked, since the commands may touch it.
+fn execute_click_commands(world: &World, player: &Player, commands: Vec<String>) {
+    let Some(server) = player.server.upgrade() else {
+        return;
+    };
+    let Some(player) = world.players.get_by_uuid(&player.gameprofile.id) else {
+        return;
+    };
+    // TODO: Vanilla runs these from the sign's position with permission level 2,
+    // not as the player. Needs a block command source.
+    let dispatcher = server.command_dispatcher.read();
+    for command in commands {
+        let command = command.strip_prefix('/').unwrap_or(&command).to_string();
+        dispatcher.handle_command(CommandSender::Player(player.clone()), command, &server);
+    }
+}
+
+/// Uses an item on a sign, applying dyes, ink sacs and honeycomb.
+///
+/// Mirrors vanilla's `SignBlock.useItemOn`. Anything that is not a sign
+/// applicator falls through to the empty hand interaction.
+fn use_item_on_sign(
+    item_stack: &ItemStack,
+    state: BlockStateId,
+    world: &Arc<World>,
+    pos: BlockPos,
+    player: &Player,
+    hand: InteractionHand,
+) -> InteractionResult {
+    // TODO: Also require player.may_build() once it exists
+    let Some(applicator) = SignApplicator::from_item(item_stack) else {
+        return InteractionResult::TryEmptyHandInteraction;
+    };
+    let Some(block_entity) = world.get_block_entity(pos) else {
+        return InteractionResult::TryEmptyHandInteraction;
+    };
+
+    let mut guard = block_entity.lock();
+    let Some(sign) = guard.as_any_mut().downcast_mut::<SignBlockEntity>() else {
+        return InteractionResult::TryEmptyHandInteraction;
+    };
+
+    let is_front_text = is_facing_front_text(state, pos, player);
+    if sign.is_waxed
+        || !applicator.can_apply_to(sign.get_text(is_front_text))
+        || sign.is_other_player_editing(player.gameprofile.id)
+    {
+        return InteractionResult::TryEmptyHandInteraction;
+    }
+    if !applicator.apply(sign, is_front_text) {
+        return InteractionResult::Fail;
+    }
+
+    sign.mark_updated();
+    let commands = sign.get_text(is_front_text).click_commands();
+    drop(guard);
+
+    applicator.play_effects(world, pos);
+    execute_click_commands(world, player, commands);
+    // TODO: Award ITEM_USED stat and dispatch GameEvent::BLOCK_CHANGE
+    if !player.has_infinite_materials() {
+        player.inventory.lock().get_item_in_hand_mut(hand).shrink(1);
+    }
+    InteractionResult::Success
+}
+
+/// Handles a player clicking a sign with an empty hand.
 ///
-/// Returns `Success` if the editor was opened, `Pass` otherwise.
-fn try_open_sign_editor(
+/// Mirrors vanilla's `SignBlock.useWithoutItem`:
+/// 1. Click commands on the facing side run first
+/// 2. Waxed signs only play the interaction failed sound
+/// 3. Otherwise the editor opens, if no other player is editing the sign and
+///    all of its lines are plain text
+fn use_sign_without_item(
     state: BlockStateId,
     world: &Arc<World>,
     pos: BlockPos,
     player: &Player,
 ) -> InteractionResult {
-    // Get the block entity
     let Some(block_entity) = world.get_block_entity(pos) else {
         return InteractionResult::Pass;
     };
 
+    let is_front_text = is_facing_front_text(state, pos, player);
+    let commands = {
+        let guard = block_entity.lock();
+        let Some(sign) = guard.as_any().downcast_ref::<SignBlockEntity>() else {
+            return InteractionResult::Pass;
+        };
+        sign.get_text(is_front_text).click_commands()
+    };
+    let has_click_command = !commands.is_empty();
+    execute_click_commands(world, player, commands);
+
     let mut guard = block_entity.lock();
     let Some(sign) = guard.as_any_mut().downcast_mut::<SignBlockEntity>() else {
         return InteractionResult::Pass;
     };
 
-    // Check 1: Is the sign waxed?
     if sign.is_waxed {
-        // TODO: Play waxed sign interaction fail sound
-        return InteractionResult::Success; // Vanilla returns SUCCESS even when waxed
+        world.play_block_sound(sign.interaction_failed_sound(), pos, 1.0, 1.0, None);
+        return InteractionResult::Success;
+    }
+    if has_click_command {
+        return InteractionResult::Success;
     }
 
-    // Check 2: Is another player editing?
-    if sign.is_other_player_editing(player.gameprofile.id) {
+    // TODO: Also require player.may_build() once it exists
+    if sign.is_other_player_editing(player.gameprofile.id)
+        || !sign.get_text(is_front_text).is_editable()
+    {
         return InteractionResult::Pass;
     }
 
-    // Check 3: Player must have build permission
-    // TODO: Implement may_build check properly
-    // if !player.may_build() {
-    //     return InteractionResult::Pass;
-    // }
-
-    // Determine which side the player is facing
-    let is_front_text = is_facing_front_text(state, pos, player);
-
-    // Set the editing player lock
+    // Set the editing player lock, then release it before calling the player
     sign.set_player_who_may_edit(Some(player.gameprofile.id));
-
-    // Release lock before calling player method
     drop(guard);
 
-    // Open the editor
     player.open_sign_editor(pos, is_front_text);
     InteractionResult::Success
 }
@@ -341,6 +464,19 @@ impl BlockBehavior for StandingSignBlock {
         false
     }
 
+    fn use_item_on(
+        &self,
+        item_stack: &ItemStack,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        hand: InteractionHand,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        use_item_on_sign(item_stack, state, world, pos, player, hand)
+    }
+
     fn use_without_item(
         &self,
         state: BlockStateId,
@@ -349,7 +485,7 @@ impl BlockBehavior for StandingSignBlock {
         player: &Player,
         _hit_result: &BlockHitResult,
     ) -> InteractionResult {
-        try_open_sign_editor(state, world, pos, player)
+        use_sign_without_item(state, world, pos, player)
     }
 }
 
@@ -429,6 +565,19 @@ impl BlockBehavior for WallSignBlock {
         false
     }
 
+    fn use_item_on(
+        &self,
+        item_stack: &ItemStack,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        hand: InteractionHand,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        use_item_on_sign(item_stack, state, world, pos, player, hand)
+    }
+
     fn use_without_item(
         &self,
         state: BlockStateId,
@@ -437,7 +586,7 @@ impl BlockBehavior for WallSignBlock {
         player: &Player,
         _hit_result: &BlockHitResult,
     ) -> InteractionResult {
-        try_open_sign_editor(state, world, pos, player)
+        use_sign_without_item(state, world, pos, player)
     }
 }
 
@@ -551,6 +700,19 @@ impl BlockBehavior for CeilingHangingSignBlock {
         false
     }
 
+    fn use_item_on(
+        &self,
+        item_stack: &ItemStack,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        hand: InteractionHand,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        use_item_on_sign(item_stack, state, world, pos, player, hand)
+    }
+
     fn use_without_item(
         &self,
         state: BlockStateId,
@@ -559,7 +721,7 @@ impl BlockBehavior for CeilingHangingSignBlock {
         player: &Player,
         _hit_result: &BlockHitResult,
     ) -> InteractionResult {
-        try_open_sign_editor(state, world, pos, player)
+        use_sign_without_item(state, world, pos, player)
     }
 }
 
@@ -657,6 +819,19 @@ impl BlockBehavior for WallHangingSignBlock {
         false
     }
 
+    fn use_item_on(
+        &self,
+        item_stack: &ItemStack,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+        hand: InteractionHand,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        use_item_on_sign(item_stack, state, world, pos, player, hand)
+    }
+
     fn use_without_item(
         &self,
         state: BlockStateId,
@@ -665,6 +840,6 @@ impl BlockBehavior for WallHangingSignBlock {
         player: &Player,
         _hit_result: &BlockHitResult,
     ) -> InteractionResult {
-        try_open_sign_editor(state, world, pos, player)
+        use_sign_without_item(state, world, pos, player)
     }
 }
@@ -0,0 +1,152 @@
+//! Gravity-affected blocks like sand and gravel.
+//!
+//! Vanilla equivalent: `FallingBlock`, `ColoredFallingBlock` and `SandBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::vanilla_block_tags::FIRE_TAG;
+use steel_registry::{REGISTRY, TaggedRegistryExt};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::BlockPlaceContext;
+use crate::entity::entities::FallingBlockEntity;
+use crate::world::World;
+
+/// Shared logic of blocks that fall when the block below them is free.
+///
+/// The check runs in a scheduled tick two ticks after the block is placed or
+/// one of its neighbors changes, so a block placed in mid-air falls right away.
+pub struct FallingBlock;
+
+impl FallingBlock {
+    /// Ticks between a placement or neighbor change and the fall check.
+    pub const DELAY_AFTER_PLACE: i32 = 2;
+
+    /// Returns whether a falling block can fall into (or through) this state.
+    ///
+    /// Vanilla equivalent: `FallingBlock.isFree()`.
+    #[must_use]
+    pub fn is_free(state: BlockStateId) -> bool {
+        let block = state.get_block();
+        state.is_air()
+            || REGISTRY.blocks.is_in_tag(block, &FIRE_TAG)
+            || block.config.liquid
+            || state.is_replaceable()
+    }
+
+    /// Schedules the fall check for the block at `pos`.
+    pub fn schedule_fall_check(world: &World, pos: BlockPos, block: BlockRef) {
+        world.schedule_block_tick_default(pos, block, Self::DELAY_AFTER_PLACE);
+    }
+
+    /// Turns the block into a falling block entity if nothing supports it.
+    pub fn try_fall(state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        if Self::is_free(world.get_block_state(pos.below())) && pos.y() >= world.get_min_y() {
+            FallingBlockEntity::fall(world, pos, state);
+        }
+    }
+}
+
+/// Behavior for gravel and other plain falling blocks.
+#[block_behavior]
+pub struct ColoredFallingBlock {
+    block: BlockRef,
+}
+
+impl ColoredFallingBlock {
+    /// Creates a new falling block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for ColoredFallingBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn on_place(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _old_state: BlockStateId,
+        _moved_by_piston: bool,
+    ) {
+        FallingBlock::schedule_fall_check(world, pos, self.block);
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        FallingBlock::schedule_fall_check(world, pos, self.block);
+        state
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        FallingBlock::try_fall(state, world, pos);
+    }
+}
+
+/// Behavior for sand and red sand.
+#[block_behavior]
+pub struct SandBlock {
+    falling: ColoredFallingBlock,
+}
+
+impl SandBlock {
+    /// Creates a new sand block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self {
+            falling: ColoredFallingBlock::new(block),
+        }
+    }
+}
+
+impl BlockBehavior for SandBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        self.falling.get_state_for_placement(context)
+    }
+
+    fn on_place(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        old_state: BlockStateId,
+        moved_by_piston: bool,
+    ) {
+        self.falling
+            .on_place(state, world, pos, old_state, moved_by_piston);
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        self.falling
+            .update_shape(state, world, pos, direction, neighbor_pos, neighbor_state)
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.falling.tick(state, world, pos);
+    }
+}
@@ -0,0 +1,3 @@
+mod falling_block;
+
+pub use falling_block::{ColoredFallingBlock, FallingBlock, SandBlock};
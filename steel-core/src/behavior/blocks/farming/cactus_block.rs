@@ -205,7 +205,7 @@ impl BlockBehavior for CactusBlock {
         _pos: BlockPos,
         entity: &dyn Entity,
     ) {
-        entity.hurt(
+        entity.take_damage(
             &DamageSource::environment(vanilla_damage_types::CACTUS),
             1.0,
         );
@@ -5,8 +5,10 @@ use std::sync::Arc;
 use steel_macros::block_behavior;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_registry::blocks::properties::{BlockStateProperties, IntProperty};
-use steel_registry::{TaggedRegistryExt, vanilla_block_tags};
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, IntProperty};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::items::ItemRef;
+use steel_registry::{TaggedRegistryExt, vanilla_block_tags, vanilla_blocks, vanilla_items};
 use steel_utils::{BlockPos, BlockStateId, types::UpdateFlags};
 
 use crate::behavior::block::BlockBehavior;
@@ -22,26 +24,35 @@ pub struct CropBlock {
     block: BlockRef,
     age_property: IntProperty,
     max_age: u8,
+    seed: ItemRef,
 }
 
 impl CropBlock {
-    /// Creates a new crop block behavior with the default age property (0-7).
+    /// Creates a new crop block behavior with the default age property (0-7),
+    /// planted from wheat seeds.
     #[must_use]
-    pub const fn new(block: BlockRef) -> Self {
-        Self {
+    pub fn new(block: BlockRef) -> Self {
+        Self::with_age(
             block,
-            age_property: BlockStateProperties::AGE_7,
-            max_age: 7,
-        }
+            BlockStateProperties::AGE_7,
+            7,
+            &vanilla_items::ITEMS.wheat_seeds,
+        )
     }
 
-    /// Creates a new crop block behavior with a custom age property.
+    /// Creates a new crop block behavior with a custom age property and seed item.
     #[must_use]
-    pub const fn with_age(block: BlockRef, age_property: IntProperty, max_age: u8) -> Self {
+    pub const fn with_age(
+        block: BlockRef,
+        age_property: IntProperty,
+        max_age: u8,
+        seed: ItemRef,
+    ) -> Self {
         Self {
             block,
             age_property,
             max_age,
+            seed,
         }
     }
 
@@ -62,6 +73,28 @@ impl CropBlock {
         self.get_age(state) >= self.max_age
     }
 
+    /// Returns a random number of ages to grow when bone meal is applied.
+    ///
+    /// Vanilla equivalent: `CropBlock.getBonemealAgeIncrease()`.
+    fn get_bonemeal_age_increase() -> u8 {
+        rand::random_range(2..=5)
+    }
+
+    /// Grows the crop by `increase` ages, capped at the max age.
+    ///
+    /// Vanilla equivalent: `CropBlock.growCrops()`.
+    fn grow_crops(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos, increase: u8) {
+        let age = self
+            .get_age(state)
+            .saturating_add(increase)
+            .min(self.max_age);
+        world.set_block(
+            pos,
+            self.get_state_for_age(age),
+            UpdateFlags::UPDATE_CLIENTS,
+        );
+    }
+
     /// Helper to check if a block matches this crop type using pointer equality.
     fn is_same_block(&self, other: BlockRef) -> bool {
         self.block == other
@@ -143,9 +176,45 @@ impl CropBlock {
 }
 
 impl BlockBehavior for CropBlock {
-    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+    /// Crops can only stay on farmland.
+    ///
+    /// Vanilla equivalent: `CropBlock.canSurvive()` and `CropBlock.mayPlaceOn()`.
+    fn can_survive(&self, _state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        // TODO: Also require light level >= 8 or sky access when the light engine is implemented
+        world.get_block_state(pos.below()).get_block() == vanilla_blocks::FARMLAND
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
         // Crops are placed at age 0
-        Some(self.get_state_for_age(0))
+        let state = self.get_state_for_age(0);
+        self.can_survive(state, context.world, context.relative_pos)
+            .then_some(state)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        // Vanilla: BushBlock.updateShape() pops the crop when it loses its farmland
+        if self.can_survive(state, world, pos) {
+            state
+        } else {
+            vanilla_blocks::AIR.default_state()
+        }
+    }
+
+    fn get_clone_item_stack(
+        &self,
+        _block: BlockRef,
+        _state: BlockStateId,
+        _include_data: bool,
+    ) -> Option<ItemStack> {
+        Some(ItemStack::new(self.seed))
     }
 
     fn is_randomly_ticking(&self, state: BlockStateId) -> bool {
@@ -171,4 +240,247 @@ impl BlockBehavior for CropBlock {
             }
         }
     }
+
+    fn is_valid_bonemeal_target(
+        &self,
+        state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+    ) -> bool {
+        !self.is_max_age(state)
+    }
+
+    fn perform_bonemeal(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.grow_crops(state, world, pos, Self::get_bonemeal_age_increase());
+    }
+}
+
+/// Behavior for carrots, which grow like wheat and are planted from carrots.
+#[block_behavior]
+pub struct CarrotBlock {
+    crop: CropBlock,
+}
+
+impl CarrotBlock {
+    /// Creates a new carrot block behavior.
+    #[must_use]
+    pub fn new(block: BlockRef) -> Self {
+        Self {
+            crop: CropBlock::with_age(
+                block,
+                BlockStateProperties::AGE_7,
+                7,
+                &vanilla_items::ITEMS.carrot,
+            ),
+        }
+    }
+}
+
+impl BlockBehavior for CarrotBlock {
+    fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        self.crop.can_survive(state, world, pos)
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        self.crop.get_state_for_placement(context)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        self.crop
+            .update_shape(state, world, pos, direction, neighbor_pos, neighbor_state)
+    }
+
+    fn get_clone_item_stack(
+        &self,
+        block: BlockRef,
+        state: BlockStateId,
+        include_data: bool,
+    ) -> Option<ItemStack> {
+        self.crop.get_clone_item_stack(block, state, include_data)
+    }
+
+    fn is_randomly_ticking(&self, state: BlockStateId) -> bool {
+        self.crop.is_randomly_ticking(state)
+    }
+
+    fn random_tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.crop.random_tick(state, world, pos);
+    }
+
+    fn is_valid_bonemeal_target(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> bool {
+        self.crop.is_valid_bonemeal_target(state, world, pos)
+    }
+
+    fn perform_bonemeal(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.crop.perform_bonemeal(state, world, pos);
+    }
+}
+
+/// Behavior for potatoes, which grow like wheat and are planted from potatoes.
+#[block_behavior]
+pub struct PotatoBlock {
+    crop: CropBlock,
+}
+
+impl PotatoBlock {
+    /// Creates a new potato block behavior.
+    #[must_use]
+    pub fn new(block: BlockRef) -> Self {
+        Self {
+            crop: CropBlock::with_age(
+                block,
+                BlockStateProperties::AGE_7,
+                7,
+                &vanilla_items::ITEMS.potato,
+            ),
+        }
+    }
+}
+
+impl BlockBehavior for PotatoBlock {
+    fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        self.crop.can_survive(state, world, pos)
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        self.crop.get_state_for_placement(context)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        self.crop
+            .update_shape(state, world, pos, direction, neighbor_pos, neighbor_state)
+    }
+
+    fn get_clone_item_stack(
+        &self,
+        block: BlockRef,
+        state: BlockStateId,
+        include_data: bool,
+    ) -> Option<ItemStack> {
+        self.crop.get_clone_item_stack(block, state, include_data)
+    }
+
+    fn is_randomly_ticking(&self, state: BlockStateId) -> bool {
+        self.crop.is_randomly_ticking(state)
+    }
+
+    fn random_tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.crop.random_tick(state, world, pos);
+    }
+
+    fn is_valid_bonemeal_target(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> bool {
+        self.crop.is_valid_bonemeal_target(state, world, pos)
+    }
+
+    fn perform_bonemeal(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        self.crop.perform_bonemeal(state, world, pos);
+    }
+}
+
+/// Behavior for beetroots.
+///
+/// Beetroots only have four ages, grow a third as often as other crops, and
+/// advance less per bone meal.
+#[block_behavior]
+pub struct BeetrootBlock {
+    crop: CropBlock,
+}
+
+impl BeetrootBlock {
+    /// Creates a new beetroot block behavior.
+    #[must_use]
+    pub fn new(block: BlockRef) -> Self {
+        Self {
+            crop: CropBlock::with_age(
+                block,
+                BlockStateProperties::AGE_3,
+                3,
+                &vanilla_items::ITEMS.beetroot_seeds,
+            ),
+        }
+    }
+}
+
+impl BlockBehavior for BeetrootBlock {
+    fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        self.crop.can_survive(state, world, pos)
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        self.crop.get_state_for_placement(context)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        self.crop
+            .update_shape(state, world, pos, direction, neighbor_pos, neighbor_state)
+    }
+
+    fn get_clone_item_stack(
+        &self,
+        block: BlockRef,
+        state: BlockStateId,
+        include_data: bool,
+    ) -> Option<ItemStack> {
+        self.crop.get_clone_item_stack(block, state, include_data)
+    }
+
+    fn is_randomly_ticking(&self, state: BlockStateId) -> bool {
+        self.crop.is_randomly_ticking(state)
+    }
+
+    fn random_tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        // Vanilla: BeetrootBlock only passes 2 in 3 random ticks on to the crop
+        if !rand::random::<u32>().is_multiple_of(3) {
+            self.crop.random_tick(state, world, pos);
+        }
+    }
+
+    fn is_valid_bonemeal_target(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+    ) -> bool {
+        self.crop.is_valid_bonemeal_target(state, world, pos)
+    }
+
+    fn perform_bonemeal(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        // Vanilla: BeetrootBlock.getBonemealAgeIncrease() divides the crop increase by 3
+        let increase = CropBlock::get_bonemeal_age_increase() / 3;
+        self.crop.grow_crops(state, world, pos, increase);
+    }
 }
@@ -2,15 +2,23 @@
 
 use std::sync::Arc;
 
+use glam::DVec3;
 use steel_macros::block_behavior;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_registry::blocks::properties::BlockStateProperties;
-use steel_registry::vanilla_blocks;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::vanilla_block_tags::{FENCE_GATES_TAG, MAINTAINS_FARMLAND_TAG};
+use steel_registry::vanilla_game_rules::MOB_GRIEFING;
+use steel_registry::{
+    REGISTRY, TaggedRegistryExt, vanilla_blocks, vanilla_damage_types, vanilla_entities,
+};
 use steel_utils::{BlockPos, BlockStateId, types::UpdateFlags};
 
 use crate::behavior::block::BlockBehavior;
 use crate::behavior::context::BlockPlaceContext;
+use crate::entity::Entity;
+use crate::entity::damage::DamageSource;
 use crate::world::World;
 
 /// Maximum moisture level for farmland.
@@ -22,6 +30,7 @@ const MAX_MOISTURE: u8 = 7;
 /// - Moisture increases to max (7) when near water
 /// - Moisture decreases by 1 each random tick when not near water
 /// - Farmland turns back to dirt when moisture reaches 0 and no crop is planted
+/// - Farmland turns back to dirt when trampled or covered by a solid block
 #[block_behavior]
 pub struct FarmlandBlock {
     block: BlockRef,
@@ -68,37 +77,123 @@ impl FarmlandBlock {
     /// This prevents farmland from turning to dirt when crops are planted.
     fn should_maintain_farmland(world: &Arc<World>, pos: BlockPos) -> bool {
         let above = world.get_block_state(pos.above());
-        let block = above.get_block();
-
-        // Check for crops that maintain farmland
-        // In vanilla this uses the MAINTAINS_FARMLAND tag
-        block == vanilla_blocks::WHEAT
-            || block == vanilla_blocks::CARROTS
-            || block == vanilla_blocks::POTATOES
-            || block == vanilla_blocks::BEETROOTS
-            || block == vanilla_blocks::MELON_STEM
-            || block == vanilla_blocks::PUMPKIN_STEM
-            || block == vanilla_blocks::ATTACHED_MELON_STEM
-            || block == vanilla_blocks::ATTACHED_PUMPKIN_STEM
-            || block == vanilla_blocks::TORCHFLOWER_CROP
-            || block == vanilla_blocks::PITCHER_CROP
+        REGISTRY
+            .blocks
+            .is_in_tag(above.get_block(), &MAINTAINS_FARMLAND_TAG)
     }
 
-    /// Turns the farmland into dirt.
+    /// Turns the farmland into dirt, lifting entities standing on it onto the
+    /// taller block.
+    ///
+    /// Vanilla equivalent: `FarmlandBlock.turnToDirt()`.
     fn turn_to_dirt(world: &Arc<World>, pos: BlockPos) {
+        Self::push_entities_up(world, pos);
         let dirt_state = vanilla_blocks::DIRT.default_state();
         world.set_block(pos, dirt_state, UpdateFlags::UPDATE_ALL);
+        // TODO: Emit GameEvent::BLOCK_CHANGE
+    }
+
+    /// Moves entities standing inside the block space up onto a full block.
+    ///
+    /// Farmland is 15/16 tall, so entities on it would otherwise end up inside
+    /// the dirt. Vanilla equivalent: `Block.pushEntitiesUp()`.
+    fn push_entities_up(world: &Arc<World>, pos: BlockPos) {
+        let top = f64::from(pos.y()) + 1.0;
+        let space = AABBd::new(
+            f64::from(pos.x()),
+            f64::from(pos.y()),
+            f64::from(pos.z()),
+            f64::from(pos.x()) + 1.0,
+            top,
+            f64::from(pos.z()) + 1.0,
+        );
+        for entity in world.get_entities_in_aabb(&space) {
+            let position = entity.position();
+            if position.y >= top {
+                continue;
+            }
+            if let Some(player) = Arc::clone(&entity).as_player() {
+                let (yaw, pitch) = player.rotation();
+                player.teleport(position.x, top, position.z, yaw, pitch);
+            } else {
+                entity.set_position(DVec3::new(position.x, top, position.z));
+            }
+        }
     }
 }
 
 impl BlockBehavior for FarmlandBlock {
-    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
-        // Farmland is placed with moisture 0
-        Some(
-            self.block
-                .default_state()
-                .set_value(&BlockStateProperties::MOISTURE, 0u8),
-        )
+    /// Farmland needs open space above it; only fence gates (and moving
+    /// pistons) may sit on top.
+    fn can_survive(&self, _state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        let above = world.get_block_state(pos.above());
+        !above.is_solid()
+            || REGISTRY
+                .blocks
+                .is_in_tag(above.get_block(), &FENCE_GATES_TAG)
+            || above.get_block() == vanilla_blocks::MOVING_PISTON
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        // Farmland is placed with moisture 0, or as dirt if something solid is above
+        let state = self
+            .block
+            .default_state()
+            .set_value(&BlockStateProperties::MOISTURE, 0u8);
+        if self.can_survive(state, context.world, context.relative_pos) {
+            Some(state)
+        } else {
+            Some(vanilla_blocks::DIRT.default_state())
+        }
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        if direction == Direction::Up && !self.can_survive(state, world, pos) {
+            world.schedule_block_tick_default(pos, self.block, 1);
+        }
+        state
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        if !self.can_survive(state, world, pos) {
+            Self::turn_to_dirt(world, pos);
+        }
+    }
+
+    /// Trampling: a heavy enough entity falling on farmland may turn it into dirt.
+    fn fall_on(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        entity: &dyn Entity,
+        fall_distance: f64,
+    ) {
+        if f64::from(rand::random::<f32>()) < fall_distance - 0.5 {
+            // TODO: Vanilla only lets living entities trample, check it once other entities fall
+            let may_trample = entity.entity_type().key == vanilla_entities::PLAYER.key
+                || world.get_game_rule(MOB_GRIEFING).as_bool().unwrap_or(true);
+            let bb = entity.bounding_box();
+            let width = bb.max_x - bb.min_x;
+            let height = bb.max_y - bb.min_y;
+            if may_trample && width * width * height > 0.512 {
+                Self::turn_to_dirt(world, pos);
+            }
+        }
+
+        entity.cause_fall_damage(
+            fall_distance,
+            1.0,
+            &DamageSource::environment(vanilla_damage_types::FALL),
+        );
     }
 
     fn is_randomly_ticking(&self, _state: BlockStateId) -> bool {
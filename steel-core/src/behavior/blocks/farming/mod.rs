@@ -5,5 +5,5 @@ mod farmland_block;
 
 pub use cactus_block::CactusBlock;
 pub use cactus_flower_block::CactusFlowerBlock;
-pub use crop_block::CropBlock;
+pub use crop_block::{BeetrootBlock, CarrotBlock, CropBlock, PotatoBlock};
 pub use farmland_block::FarmlandBlock;
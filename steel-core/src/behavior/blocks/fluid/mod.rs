@@ -1,3 +1,5 @@
 mod liquid_block;
+mod powder_snow_block;
 
 pub use liquid_block::LiquidBlock;
+pub use powder_snow_block::PowderSnowBlock;
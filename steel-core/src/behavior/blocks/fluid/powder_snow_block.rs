@@ -0,0 +1,59 @@
+//! Powder snow block behavior.
+//!
+//! Powder snow isn't a fluid, but like one it is placed from and picked up
+//! into a bucket.
+//!
+//! Based on vanilla's PowderSnowBlock.java.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks, vanilla_items};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::{BlockBehavior, PickupResult};
+use crate::behavior::context::BlockPlaceContext;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for powder snow.
+#[block_behavior]
+pub struct PowderSnowBlock {
+    block: BlockRef,
+}
+
+impl PowderSnowBlock {
+    /// Creates a new powder snow block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for PowderSnowBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    // TODO: Slow down and freeze entities inside (entityInside)
+
+    fn pickup_block(
+        &self,
+        world: &Arc<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+        _player: Option<&Player>,
+    ) -> Option<PickupResult> {
+        let air = REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+        world.set_block(pos, air, UpdateFlags::UPDATE_ALL_IMMEDIATE);
+        world.destroy_block_effect(pos, u32::from(state.0), None);
+
+        Some(PickupResult {
+            filled_bucket: &vanilla_items::ITEMS.powder_snow_bucket,
+            sound: Some(sound_events::ITEM_BUCKET_FILL_POWDER_SNOW),
+        })
+    }
+}
@@ -0,0 +1,3 @@
+mod structure_block;
+
+pub use structure_block::StructureBlock;
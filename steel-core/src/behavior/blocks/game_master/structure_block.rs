@@ -0,0 +1,87 @@
+//! Structure block behavior implementation.
+//!
+//! Structure blocks in load mode place their template when powered.
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::block_entity::entities::{StructureBlockEntity, StructureMode};
+use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
+use crate::world::World;
+
+/// Behavior for structure blocks.
+///
+/// Vanilla: `StructureBlock`.
+// TODO: Open the edit screen for operators in creative mode
+// TODO: Set the author to the player who placed the block
+#[block_behavior]
+pub struct StructureBlock {
+    block: BlockRef,
+}
+
+impl StructureBlock {
+    /// Creates a new structure block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for StructureBlock {
+    fn handle_neighbor_changed(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        let Some(block_entity) = world.get_block_entity(pos) else {
+            return;
+        };
+        let mut guard = block_entity.lock();
+        let Some(structure_block) = guard.as_any_mut().downcast_mut::<StructureBlockEntity>()
+        else {
+            return;
+        };
+
+        let should_trigger = world.has_neighbor_signal(pos);
+        if should_trigger && !structure_block.powered {
+            structure_block.powered = true;
+            match structure_block.mode {
+                StructureMode::Load => {
+                    structure_block.place_structure_if_same_size(world);
+                }
+                // TODO: Save the area as a template
+                StructureMode::Save => {}
+                StructureMode::Corner => structure_block.unload_structure(),
+                StructureMode::Data => {}
+            }
+        } else if !should_trigger && structure_block.powered {
+            structure_block.powered = false;
+        }
+    }
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        BLOCK_ENTITIES.create(
+            vanilla_block_entity_types::STRUCTURE_BLOCK,
+            level,
+            pos,
+            state,
+        )
+    }
+}
@@ -6,20 +6,35 @@
 mod building;
 mod container;
 mod decoration;
+mod falling;
 mod farming;
 mod fluid;
+mod game_master;
 mod portal;
 mod redstone;
 
 pub use building::{
-    FenceBlock, RotatedPillarBlock, WeatherState, WeatheringCopper, WeatheringCopperFullBlock,
+    DoorBlock, FenceBlock, FenceGateBlock, IronBarsBlock, RotatedPillarBlock,
+    StainedGlassPaneBlock, TrapDoorBlock, WallBlock, WeatherState, WeatheringCopper,
+    WeatheringCopperBarsBlock, WeatheringCopperFullBlock,
+};
+pub use container::{
+    AnvilBlock, BarrelBlock, BeaconBlock, CraftingTableBlock, DispenserBlock, DropperBlock,
+    EnchantingTableBlock, EnderChestBlock, HopperBlock, ShulkerBoxBlock,
 };
-pub use container::{BarrelBlock, CraftingTableBlock};
 pub use decoration::{
     CandleBlock, CeilingHangingSignBlock, StandingSignBlock, TorchBlock, WallHangingSignBlock,
     WallSignBlock, WallTorchBlock,
 };
-pub use farming::{CactusBlock, CactusFlowerBlock, CropBlock, FarmlandBlock};
-pub use fluid::LiquidBlock;
+pub use falling::{ColoredFallingBlock, FallingBlock, SandBlock};
+pub use farming::{
+    BeetrootBlock, CactusBlock, CactusFlowerBlock, CarrotBlock, CropBlock, FarmlandBlock,
+    PotatoBlock,
+};
+pub use fluid::{LiquidBlock, PowderSnowBlock};
+pub use game_master::StructureBlock;
 pub use portal::{EndPortalFrameBlock, FireBlock, NetherPortalBlock};
-pub use redstone::{ButtonBlock, RedstoneTorchBlock, RedstoneWallTorchBlock};
+pub use redstone::{
+    ButtonBlock, LeverBlock, MovingPistonBlock, PistonBaseBlock, PistonHeadBlock,
+    RedstoneTorchBlock, RedstoneWallTorchBlock,
+};
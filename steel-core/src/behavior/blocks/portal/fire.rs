@@ -1,23 +1,33 @@
 //! Fire block behavior implementation.
 //!
-//! Vanilla splits fire into `BaseFireBlock` (portal logic, placement checks) and `FireBlock`
-//! (spreading, aging). This combines the portal-relevant parts from `BaseFireBlock`.
+//! Vanilla splits fire into `BaseFireBlock` (portal logic, placement checks, entity
+//! damage) and `FireBlock` (spreading, aging). This combines both.
 
 use std::sync::Arc;
 use steel_macros::block_behavior;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_registry::vanilla_blocks;
-use steel_registry::vanilla_dimension_types;
+use steel_registry::blocks::properties::{BlockStateProperties, BoolProperty};
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_block_tags, vanilla_damage_types};
+use steel_registry::{vanilla_blocks, vanilla_dimension_types, vanilla_entities};
 use steel_utils::math::Axis;
 use steel_utils::types::UpdateFlags;
-use steel_utils::{BlockPos, BlockStateId, Direction};
+use steel_utils::{BlockPos, BlockStateId, Direction, Identifier};
 
 use crate::behavior::block::BlockBehavior;
 use crate::behavior::context::BlockPlaceContext;
+use crate::behavior::flammables::{get_burn_odds, get_ignite_odds};
+use crate::entity::Entity;
+use crate::entity::damage::DamageSource;
 use crate::portal::portal_shape::{PortalShape, nether_portal_config};
 use crate::world::World;
 
+/// Damage dealt per tick to entities standing in fire (vanilla: `FireBlock` passes 1.0).
+const FIRE_DAMAGE: f32 = 1.0;
+
+/// Highest value of the `age` property.
+const MAX_AGE: u8 = 15;
+
 /// Behavior for fire blocks.
 #[block_behavior]
 pub struct FireBlock {
@@ -56,7 +66,187 @@ impl FireBlock {
         world
             .get_block_state(pos.below())
             .is_face_sturdy(Direction::Up)
-        // TODO: || is_valid_fire_location (check adjacent flammable blocks once flammability exists)
+            || Self::is_valid_fire_location(world, pos)
+    }
+
+    /// Matches vanilla's `FireBlock.isValidFireLocation`: any neighbor can burn.
+    fn is_valid_fire_location(world: &Arc<World>, pos: BlockPos) -> bool {
+        Direction::ALL
+            .iter()
+            .any(|&dir| Self::can_burn(world.get_block_state(pos.relative(dir))))
+    }
+
+    /// Returns the chance of fire spreading onto `state`; waterlogged blocks never catch fire.
+    fn ignite_odds(state: BlockStateId) -> i32 {
+        if Self::is_waterlogged(state) {
+            0
+        } else {
+            get_ignite_odds(state.get_block())
+        }
+    }
+
+    /// Returns the chance of fire burning `state` away; waterlogged blocks never burn.
+    fn burn_odds(state: BlockStateId) -> i32 {
+        if Self::is_waterlogged(state) {
+            0
+        } else {
+            get_burn_odds(state.get_block())
+        }
+    }
+
+    fn is_waterlogged(state: BlockStateId) -> bool {
+        state
+            .try_get_value(&BlockStateProperties::WATERLOGGED)
+            .unwrap_or(false)
+    }
+
+    /// Matches vanilla's `FireBlock.canBurn`.
+    fn can_burn(state: BlockStateId) -> bool {
+        Self::ignite_odds(state) > 0
+    }
+
+    /// Matches vanilla's `FireBlock.getIgniteOdds(LevelReader, BlockPos)`: the best ignite
+    /// odds of the neighbors of an empty position, or 0 if the position is occupied.
+    fn ignite_odds_at(world: &Arc<World>, pos: BlockPos) -> i32 {
+        if !world.get_block_state(pos).is_air() {
+            return 0;
+        }
+        Direction::ALL
+            .iter()
+            .map(|&dir| Self::ignite_odds(world.get_block_state(pos.relative(dir))))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the side property attaching fire to the neighbor in `direction`.
+    const fn side_property(direction: Direction) -> Option<BoolProperty> {
+        match direction {
+            Direction::North => Some(BlockStateProperties::NORTH),
+            Direction::East => Some(BlockStateProperties::EAST),
+            Direction::South => Some(BlockStateProperties::SOUTH),
+            Direction::West => Some(BlockStateProperties::WEST),
+            Direction::Up => Some(BlockStateProperties::UP),
+            Direction::Down => None,
+        }
+    }
+
+    /// Matches vanilla's `BaseFireBlock.getState`: the fire state that fits at `pos`.
+    pub(crate) fn get_state(world: &Arc<World>, pos: BlockPos) -> BlockStateId {
+        Self::new(vanilla_blocks::FIRE).state_with_age(world, pos, 0)
+    }
+
+    /// Matches vanilla's `FireBlock.getStateForPlacement(BlockGetter, BlockPos)`: fire that is
+    /// not resting on a burnable or sturdy block clings to its flammable neighbors.
+    fn state_for_position(&self, world: &Arc<World>, pos: BlockPos) -> BlockStateId {
+        let below = world.get_block_state(pos.below());
+        let mut state = self.block.default_state();
+        if Self::can_burn(below) || below.is_face_sturdy(Direction::Up) {
+            return state;
+        }
+        for dir in Direction::ALL {
+            if let Some(property) = Self::side_property(dir) {
+                let burnable = Self::can_burn(world.get_block_state(pos.relative(dir)));
+                state = state.set_value(&property, burnable);
+            }
+        }
+        state
+    }
+
+    /// Matches vanilla's `FireBlock.getStateWithAge`: soul fire on soul blocks, otherwise
+    /// fire with the given age.
+    fn state_with_age(&self, world: &Arc<World>, pos: BlockPos, age: u8) -> BlockStateId {
+        let below = world.get_block_state(pos.below()).get_block();
+        if REGISTRY
+            .blocks
+            .is_in_tag(below, &vanilla_block_tags::SOUL_FIRE_BASE_BLOCKS_TAG)
+        {
+            return vanilla_blocks::SOUL_FIRE.default_state();
+        }
+        self.state_for_position(world, pos)
+            .set_value(&BlockStateProperties::AGE_15, age)
+    }
+
+    /// Matches vanilla's `FireBlock.getFireTickDelay`.
+    fn fire_tick_delay() -> i32 {
+        30 + rand::random_range(0..10)
+    }
+
+    /// Matches vanilla's `FireBlock.isNearRain`: rain falls on the position or a horizontal
+    /// neighbor.
+    fn is_near_rain(world: &World, pos: BlockPos) -> bool {
+        world.is_raining_at(pos)
+            || world.is_raining_at(pos.west())
+            || world.is_raining_at(pos.east())
+            || world.is_raining_at(pos.north())
+            || world.is_raining_at(pos.south())
+    }
+
+    /// Returns true if the block below burns forever (`infiniburn` tag of the dimension).
+    fn is_infiniburn(world: &World, pos: BlockPos) -> bool {
+        let below = world.get_block_state(pos.below()).get_block();
+        world
+            .dimension
+            .infiniburn
+            .strip_prefix('#')
+            .and_then(|tag| tag.parse::<Identifier>().ok())
+            .is_some_and(|tag| REGISTRY.blocks.is_in_tag(below, &tag))
+    }
+
+    /// Matches vanilla's `Level.removeBlock`.
+    fn remove_block(world: &Arc<World>, pos: BlockPos) {
+        world.set_block(
+            pos,
+            vanilla_blocks::AIR.default_state(),
+            UpdateFlags::UPDATE_ALL,
+        );
+    }
+
+    /// Matches vanilla's `FireBlock.checkBurnOut`: may burn the block at `pos` away,
+    /// sometimes leaving fire in its place.
+    fn check_burn_out(&self, world: &Arc<World>, pos: BlockPos, chance: i32, age: u8) {
+        let state = world.get_block_state(pos);
+        if rand::random_range(0..chance) >= Self::burn_odds(state) {
+            return;
+        }
+
+        if rand::random_range(0..i32::from(age) + 10) < 5 && !world.is_raining_at(pos) {
+            let new_age = (age + rand::random_range(0..5u8) / 4).min(MAX_AGE);
+            world.set_block(
+                pos,
+                self.state_with_age(world, pos, new_age),
+                UpdateFlags::UPDATE_ALL,
+            );
+        } else {
+            Self::remove_block(world, pos);
+        }
+
+        // TODO: Prime the block if it was TNT (`TntBlock.prime`) once primed TNT exists
+    }
+
+    /// Matches vanilla's `BaseFireBlock.onPlace`: lights a portal or removes unsupported fire.
+    fn base_on_place(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        old_state: BlockStateId,
+    ) {
+        // Only attempt portal creation when fire is newly placed, not when replacing itself
+        if old_state.get_block() == state.get_block() {
+            return;
+        }
+
+        if Self::in_portal_dimension(world)
+            && let Some(shape) =
+                PortalShape::find_empty_portal_shape(world, pos, &nether_portal_config())
+        {
+            shape.place_portal_blocks(world);
+            return;
+        }
+
+        if !self.can_survive(state, world, pos) {
+            Self::remove_block(world, pos);
+        }
     }
 
     /// Matches vanilla's `BaseFireBlock.isPortal`: checks if placing fire here could form a portal.
@@ -88,8 +278,24 @@ impl FireBlock {
 }
 
 impl BlockBehavior for FireBlock {
-    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
-        Some(self.block.default_state())
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.state_for_position(context.world, context.relative_pos))
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        if self.can_survive(state, world, pos) {
+            self.state_with_age(world, pos, state.get_value(&BlockStateProperties::AGE_15))
+        } else {
+            vanilla_blocks::AIR.default_state()
+        }
     }
 
     fn can_survive(&self, _state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
@@ -104,25 +310,127 @@ impl BlockBehavior for FireBlock {
         old_state: BlockStateId,
         _moved_by_piston: bool,
     ) {
-        // Only attempt portal creation when fire is newly placed, not when replacing itself
-        if old_state.get_block() == state.get_block() {
+        self.base_on_place(state, world, pos, old_state);
+        world.schedule_block_tick_default(pos, self.block, Self::fire_tick_delay());
+    }
+
+    fn tick(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        world.schedule_block_tick_default(pos, self.block, Self::fire_tick_delay());
+        if !world.can_spread_fire_around(pos) {
             return;
         }
 
-        if Self::in_portal_dimension(world)
-            && let Some(shape) =
-                PortalShape::find_empty_portal_shape(world, pos, &nether_portal_config())
+        // Vanilla keeps going after removing unsupported fire.
+        if !self.can_survive(state, world, pos) {
+            Self::remove_block(world, pos);
+        }
+
+        let infiniburn = Self::is_infiniburn(world, pos);
+        let age: u8 = state.get_value(&BlockStateProperties::AGE_15);
+        if !infiniburn
+            && world.is_raining()
+            && Self::is_near_rain(world, pos)
+            && rand::random::<f32>() < 0.2 + f32::from(age) * 0.03
         {
-            shape.place_portal_blocks(world);
+            Self::remove_block(world, pos);
             return;
         }
 
-        if !self.can_survive(state, world, pos) {
+        let new_age = (age + rand::random_range(0..3u8) / 2).min(MAX_AGE);
+        if age != new_age {
             world.set_block(
                 pos,
-                vanilla_blocks::AIR.default_state(),
-                UpdateFlags::UPDATE_ALL,
+                state.set_value(&BlockStateProperties::AGE_15, new_age),
+                UpdateFlags::UPDATE_NONE,
             );
         }
+
+        if !infiniburn {
+            if !Self::is_valid_fire_location(world, pos) {
+                let below = world.get_block_state(pos.below());
+                if !below.is_face_sturdy(Direction::Up) || age > 3 {
+                    Self::remove_block(world, pos);
+                }
+                return;
+            }
+
+            if age == MAX_AGE
+                && rand::random_range(0..4) == 0
+                && !Self::can_burn(world.get_block_state(pos.below()))
+            {
+                Self::remove_block(world, pos);
+                return;
+            }
+        }
+
+        // TODO: Check the `increased_fire_burnout` biome tag once biome tags are loaded
+        let increased_burnout = false;
+        let burnout_modifier = if increased_burnout { -50 } else { 0 };
+        self.check_burn_out(world, pos.east(), 300 + burnout_modifier, age);
+        self.check_burn_out(world, pos.west(), 300 + burnout_modifier, age);
+        self.check_burn_out(world, pos.below(), 250 + burnout_modifier, age);
+        self.check_burn_out(world, pos.above(), 250 + burnout_modifier, age);
+        self.check_burn_out(world, pos.north(), 300 + burnout_modifier, age);
+        self.check_burn_out(world, pos.south(), 300 + burnout_modifier, age);
+
+        let difficulty = i32::from(world.level_data.read().difficulty().id());
+        for dx in -1..=1 {
+            for dz in -1..=1 {
+                for dy in -1..=4 {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    let rate = if dy > 1 { 100 + (dy - 1) * 100 } else { 100 };
+                    let test_pos = pos.offset(dx, dy, dz);
+                    let ignite_odds = Self::ignite_odds_at(world, test_pos);
+                    if ignite_odds <= 0 {
+                        continue;
+                    }
+
+                    let mut odds = (ignite_odds + 40 + difficulty * 7) / (i32::from(age) + 30);
+                    if increased_burnout {
+                        odds /= 2;
+                    }
+                    if odds > 0
+                        && rand::random_range(0..rate) <= odds
+                        && (!world.is_raining() || !Self::is_near_rain(world, test_pos))
+                    {
+                        let spread_age = (age + rand::random_range(0..5u8) / 4).min(MAX_AGE);
+                        world.set_block(
+                            test_pos,
+                            self.state_with_age(world, test_pos, spread_age),
+                            UpdateFlags::UPDATE_ALL,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Matches vanilla's `BaseFireBlock.entityInside`/`fireIgnite`: sets the entity on
+    /// fire and deals `in_fire` damage.
+    fn entity_inside(
+        &self,
+        _state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        entity: &dyn Entity,
+    ) {
+        if !entity.fire_immune() {
+            let remaining = entity.remaining_fire_ticks();
+            if remaining < 0 {
+                entity.set_remaining_fire_ticks(remaining + 1);
+            } else if entity.entity_type().key == vanilla_entities::PLAYER.key {
+                entity.set_remaining_fire_ticks(remaining + rand::random_range(1..3));
+            }
+            if entity.remaining_fire_ticks() >= 0 {
+                entity.ignite_for_seconds(8.0);
+            }
+        }
+
+        entity.take_damage(
+            &DamageSource::environment(vanilla_damage_types::IN_FIRE),
+            FIRE_DAMAGE,
+        );
     }
 }
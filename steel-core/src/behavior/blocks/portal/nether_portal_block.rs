@@ -2,14 +2,19 @@
 
 use crate::behavior::block::BlockBehavior;
 use crate::behavior::context::BlockPlaceContext;
+use crate::entity::Entity;
+use crate::portal::portal_forcer::find_closest_portal_position;
 use crate::portal::portal_shape::{PortalShape, nether_portal_config};
+use crate::server::Server;
 use crate::world::World;
+use glam::DVec3;
 use std::sync::Arc;
 use steel_macros::block_behavior;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::BlockStateProperties;
 use steel_registry::vanilla_blocks::AIR;
+use steel_registry::vanilla_dimension_types::THE_NETHER;
 use steel_utils::math::Axis;
 use steel_utils::{BlockPos, BlockStateId, Direction};
 
@@ -24,6 +29,30 @@ impl NetherPortalBlock {
     pub const fn new(block: BlockRef) -> Self {
         Self { block }
     }
+
+    /// Finds the world an entity at `pos` in `world` travels to, and the portal it
+    /// comes out of there. `None` if that world doesn't exist or has no portal in range.
+    ///
+    /// Vanilla: `NetherPortalBlock.getPortalDestination()`.
+    // TODO: Create a portal when none is found (PortalForcer.createPortal)
+    // TODO: Clamp the exit position to the destination's world border
+    #[must_use]
+    pub fn portal_destination(
+        server: &Server,
+        world: &World,
+        pos: DVec3,
+    ) -> Option<(Arc<World>, BlockPos)> {
+        let to_nether = world.dimension.key != THE_NETHER.key;
+        let destination = if to_nether {
+            server.nether()?
+        } else {
+            server.overworld()
+        };
+        let scale = world.dimension.coordinate_scale / destination.dimension.coordinate_scale;
+        let exit = BlockPos::containing(pos.x * scale, pos.y, pos.z * scale);
+        let portal = find_closest_portal_position(destination, exit, to_nether)?;
+        Some((destination.clone(), portal))
+    }
 }
 
 impl BlockBehavior for NetherPortalBlock {
@@ -50,6 +79,19 @@ impl BlockBehavior for NetherPortalBlock {
         state
     }
 
+    /// Vanilla: `NetherPortalBlock.entityInside()`.
+    fn entity_inside(
+        &self,
+        _state: BlockStateId,
+        _world: &Arc<World>,
+        pos: BlockPos,
+        entity: &dyn Entity,
+    ) {
+        if !entity.is_passenger() && !entity.is_removed() {
+            entity.set_as_inside_portal(pos);
+        }
+    }
+
     fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
         None // TODO: add this functionality but has low priority
     }
@@ -11,16 +11,17 @@ use steel_macros::block_behavior;
 use steel_registry::REGISTRY;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_registry::blocks::properties::{AttachFace, BlockStateProperties, Direction};
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
 use steel_registry::vanilla_blocks;
-use steel_utils::math::Axis;
 use steel_utils::types::UpdateFlags;
 use steel_utils::{BlockPos, BlockStateId};
 
 use crate::behavior::block::BlockBehavior;
+use crate::behavior::blocks::redstone::face_attached;
 use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
 use crate::player::Player;
 use crate::world::World;
+use crate::world::redstone::MAX_SIGNAL;
 
 /// Behavior for all button block variants.
 ///
@@ -56,34 +57,12 @@ impl ButtonBlock {
         }
     }
 
-    /// Returns the outward direction the button faces (away from the support block).
-    ///
-    /// Vanilla equivalent: `FaceAttachedHorizontalDirectionalBlock.getConnectedDirection()`.
-    fn get_connected_direction(state: BlockStateId) -> Direction {
-        let face: AttachFace = state.get_value(&BlockStateProperties::ATTACH_FACE);
-        match face {
-            AttachFace::Floor => Direction::Up,
-            AttachFace::Ceiling => Direction::Down,
-            AttachFace::Wall => state.get_value(&BlockStateProperties::HORIZONTAL_FACING),
-        }
-    }
-
-    /// Updates neighbors at both the button position and the support block position.
-    ///
-    /// Vanilla equivalent: `ButtonBlock.updateNeighbors()`.
-    fn update_button_neighbors(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
-        world.update_neighbors_at(pos, self.block);
-        let support_dir = Self::get_connected_direction(state).opposite();
-        let support_pos = support_dir.relative(pos);
-        world.update_neighbors_at(support_pos, self.block);
-    }
-
     /// Presses the button: sets POWERED=true, updates neighbors, schedules unpress tick,
     /// and plays the click sound.
     fn press(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos, player: &Player) {
         let powered_state = state.set_value(&BlockStateProperties::POWERED, true);
         world.set_block(pos, powered_state, UpdateFlags::UPDATE_ALL);
-        self.update_button_neighbors(powered_state, world, pos);
+        face_attached::update_neighbors(self.block, powered_state, world, pos);
         world.schedule_block_tick_default(pos, self.block, self.ticks_to_stay_pressed);
         world.play_block_sound(self.sound_click_on, pos, 1.0, 1.0, Some(player.id));
         // TODO: GameEvent.BLOCK_ACTIVATE when game event system exists
@@ -93,42 +72,11 @@ impl ButtonBlock {
 impl BlockBehavior for ButtonBlock {
     /// Checks if a button with the given state can survive at the given position.
     fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
-        let support_dir = Self::get_connected_direction(state).opposite();
-        let support_pos = support_dir.relative(pos);
-        let support_state = world.get_block_state(support_pos);
-        support_state.is_face_sturdy(support_dir.opposite())
+        face_attached::can_survive(state, world, pos)
     }
 
     fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
-        for direction in context.get_nearest_looking_directions() {
-            let state = if direction.get_axis() == Axis::Y {
-                let face = if direction == Direction::Up {
-                    AttachFace::Ceiling
-                } else {
-                    AttachFace::Floor
-                };
-                self.block
-                    .default_state()
-                    .set_value(&BlockStateProperties::ATTACH_FACE, face)
-                    .set_value(
-                        &BlockStateProperties::HORIZONTAL_FACING,
-                        context.horizontal_direction,
-                    )
-            } else {
-                self.block
-                    .default_state()
-                    .set_value(&BlockStateProperties::ATTACH_FACE, AttachFace::Wall)
-                    .set_value(
-                        &BlockStateProperties::HORIZONTAL_FACING,
-                        direction.opposite(),
-                    )
-            };
-
-            if self.can_survive(state, context.world, context.relative_pos) {
-                return Some(state);
-            }
-        }
-        None
+        face_attached::get_state_for_placement(self.block, context)
     }
 
     fn update_shape(
@@ -140,7 +88,7 @@ impl BlockBehavior for ButtonBlock {
         _neighbor_pos: BlockPos,
         _neighbor_state: BlockStateId,
     ) -> BlockStateId {
-        let support_dir = Self::get_connected_direction(state).opposite();
+        let support_dir = face_attached::get_connected_direction(state).opposite();
         if direction == support_dir && !self.can_survive(state, world, pos) {
             return REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
         }
@@ -176,11 +124,32 @@ impl BlockBehavior for ButtonBlock {
         // Unpress the button
         let unpowered_state = state.set_value(&BlockStateProperties::POWERED, false);
         world.set_block(pos, unpowered_state, UpdateFlags::UPDATE_ALL);
-        self.update_button_neighbors(state, world, pos);
+        face_attached::update_neighbors(self.block, state, world, pos);
         world.play_block_sound(self.sound_click_off, pos, 1.0, 1.0, None);
         // TODO: GameEvent.BLOCK_DEACTIVATE when game event system exists
     }
 
+    fn get_signal(
+        &self,
+        state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        _direction: Direction,
+    ) -> i32 {
+        let powered: bool = state.get_value(&BlockStateProperties::POWERED);
+        if powered { MAX_SIGNAL } else { 0 }
+    }
+
+    fn get_direct_signal(
+        &self,
+        state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        direction: Direction,
+    ) -> i32 {
+        face_attached::get_direct_signal(state, direction)
+    }
+
     fn affect_neighbors_after_removal(
         &self,
         state: BlockStateId,
@@ -195,6 +164,6 @@ impl BlockBehavior for ButtonBlock {
         if !powered {
             return;
         }
-        self.update_button_neighbors(state, world, pos);
+        face_attached::update_neighbors(self.block, state, world, pos);
     }
 }
@@ -0,0 +1,93 @@
+//! Shared logic for blocks attached to a face of their support block.
+//!
+//! Vanilla equivalent: `FaceAttachedHorizontalDirectionalBlock`, the parent of
+//! buttons and levers.
+
+use std::sync::Arc;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{AttachFace, BlockStateProperties, Direction};
+use steel_utils::math::Axis;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+use crate::world::redstone::MAX_SIGNAL;
+
+/// Returns the outward direction the block faces (away from the support block).
+///
+/// Vanilla equivalent: `FaceAttachedHorizontalDirectionalBlock.getConnectedDirection()`.
+pub fn get_connected_direction(state: BlockStateId) -> Direction {
+    let face: AttachFace = state.get_value(&BlockStateProperties::ATTACH_FACE);
+    match face {
+        AttachFace::Floor => Direction::Up,
+        AttachFace::Ceiling => Direction::Down,
+        AttachFace::Wall => state.get_value(&BlockStateProperties::HORIZONTAL_FACING),
+    }
+}
+
+/// Checks if the block the state is attached to has a sturdy face to hold it.
+pub fn can_survive(state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+    let support_dir = get_connected_direction(state).opposite();
+    let support_pos = support_dir.relative(pos);
+    let support_state = world.get_block_state(support_pos);
+    support_state.is_face_sturdy(support_dir.opposite())
+}
+
+/// Picks the first face the player is looking at that can hold the block.
+///
+/// Vanilla equivalent: `FaceAttachedHorizontalDirectionalBlock.getStateForPlacement()`.
+pub fn get_state_for_placement(
+    block: BlockRef,
+    context: &BlockPlaceContext<'_>,
+) -> Option<BlockStateId> {
+    for direction in context.get_nearest_looking_directions() {
+        let state = if direction.get_axis() == Axis::Y {
+            let face = if direction == Direction::Up {
+                AttachFace::Ceiling
+            } else {
+                AttachFace::Floor
+            };
+            block
+                .default_state()
+                .set_value(&BlockStateProperties::ATTACH_FACE, face)
+                .set_value(
+                    &BlockStateProperties::HORIZONTAL_FACING,
+                    context.horizontal_direction,
+                )
+        } else {
+            block
+                .default_state()
+                .set_value(&BlockStateProperties::ATTACH_FACE, AttachFace::Wall)
+                .set_value(
+                    &BlockStateProperties::HORIZONTAL_FACING,
+                    direction.opposite(),
+                )
+        };
+
+        if can_survive(state, context.world, context.relative_pos) {
+            return Some(state);
+        }
+    }
+    None
+}
+
+/// Updates neighbors at both the block position and the support block position,
+/// so the support block passes on the strong signal.
+pub fn update_neighbors(block: BlockRef, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+    world.update_neighbors_at(pos, block);
+    let support_pos = get_connected_direction(state).opposite().relative(pos);
+    world.update_neighbors_at(support_pos, block);
+}
+
+/// Signal emitted by a powered face-attached block: weak in every direction,
+/// strong only into its support block.
+pub fn get_direct_signal(state: BlockStateId, direction: Direction) -> i32 {
+    let powered: bool = state.get_value(&BlockStateProperties::POWERED);
+    if powered && get_connected_direction(state) == direction {
+        MAX_SIGNAL
+    } else {
+        0
+    }
+}
@@ -0,0 +1,115 @@
+//! Lever block behavior.
+//!
+//! Levers are face-attached blocks that toggle a redstone signal on use.
+//!
+//! Vanilla equivalent: `LeverBlock` + `FaceAttachedHorizontalDirectionalBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::blocks::redstone::face_attached;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::player::Player;
+use crate::world::World;
+use crate::world::redstone::MAX_SIGNAL;
+
+/// Behavior for the lever block.
+#[block_behavior]
+pub struct LeverBlock {
+    block: BlockRef,
+}
+
+impl LeverBlock {
+    /// Creates a new lever block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for LeverBlock {
+    fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        face_attached::can_survive(state, world, pos)
+    }
+
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        face_attached::get_state_for_placement(self.block, context)
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        let support_dir = face_attached::get_connected_direction(state).opposite();
+        if direction == support_dir && !self.can_survive(state, world, pos) {
+            return REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+        }
+        state
+    }
+
+    fn use_without_item(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        let powered = !state.get_value(&BlockStateProperties::POWERED);
+        let new_state = state.set_value(&BlockStateProperties::POWERED, powered);
+        world.set_block(pos, new_state, UpdateFlags::UPDATE_ALL);
+        face_attached::update_neighbors(self.block, new_state, world, pos);
+
+        let pitch = if powered { 0.6 } else { 0.5 };
+        world.play_block_sound(sound_events::BLOCK_LEVER_CLICK, pos, 0.3, pitch, None);
+        // TODO: GameEvent.BLOCK_ACTIVATE / BLOCK_DEACTIVATE when game event system exists
+        InteractionResult::Success
+    }
+
+    fn get_signal(
+        &self,
+        state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        _direction: Direction,
+    ) -> i32 {
+        let powered: bool = state.get_value(&BlockStateProperties::POWERED);
+        if powered { MAX_SIGNAL } else { 0 }
+    }
+
+    fn get_direct_signal(
+        &self,
+        state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        direction: Direction,
+    ) -> i32 {
+        face_attached::get_direct_signal(state, direction)
+    }
+
+    fn affect_neighbors_after_removal(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        moved_by_piston: bool,
+    ) {
+        let powered: bool = state.get_value(&BlockStateProperties::POWERED);
+        if !moved_by_piston && powered {
+            face_attached::update_neighbors(self.block, state, world, pos);
+        }
+    }
+}
@@ -1,5 +1,15 @@
 mod button_block;
+mod face_attached;
+mod lever_block;
+mod moving_piston_block;
+mod piston_base_block;
+mod piston_head_block;
+mod piston_structure_resolver;
 mod redstone_torch_block;
 
 pub use button_block::ButtonBlock;
+pub use lever_block::LeverBlock;
+pub use moving_piston_block::MovingPistonBlock;
+pub use piston_base_block::PistonBaseBlock;
+pub use piston_head_block::PistonHeadBlock;
 pub use redstone_torch_block::{RedstoneTorchBlock, RedstoneWallTorchBlock};
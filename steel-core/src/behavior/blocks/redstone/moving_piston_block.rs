@@ -0,0 +1,87 @@
+//! Moving piston block behavior.
+//!
+//! Placeholder block for anything a piston is moving. Its block entity holds
+//! the moved block and places it again once the movement is done.
+//!
+//! Vanilla equivalent: `MovingPistonBlock`.
+
+use std::sync::{Arc, Weak};
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::item_stack::ItemStack;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::{BlockHitResult, BlockPlaceContext, InteractionResult};
+use crate::block_entity::SharedBlockEntity;
+use crate::block_entity::entities::PistonMovingBlockEntity;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for the moving piston block.
+#[block_behavior]
+pub struct MovingPistonBlock {
+    block: BlockRef,
+}
+
+impl MovingPistonBlock {
+    /// Creates a new moving piston block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+impl BlockBehavior for MovingPistonBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn tick(&self, _state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        // Scheduled by the block entity once the block arrived
+        PistonMovingBlockEntity::finish_movement(world, pos);
+    }
+
+    fn use_without_item(
+        &self,
+        _state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _player: &Player,
+        _hit_result: &BlockHitResult,
+    ) -> InteractionResult {
+        // A moving piston without its block entity is stuck forever
+        if world.get_block_entity(pos).is_none() {
+            world.remove_block(pos, false);
+            return InteractionResult::Success;
+        }
+        InteractionResult::Pass
+    }
+
+    fn get_clone_item_stack(
+        &self,
+        _block: BlockRef,
+        _state: BlockStateId,
+        _include_data: bool,
+    ) -> Option<ItemStack> {
+        None
+    }
+
+    // TODO: Drop the moved block when broken (vanilla's getDrops) and remove an extended
+    // base behind it when a player breaks it (vanilla's destroy)
+
+    fn has_block_entity(&self) -> bool {
+        true
+    }
+
+    fn new_block_entity(
+        &self,
+        _level: Weak<World>,
+        _pos: BlockPos,
+        _state: BlockStateId,
+    ) -> Option<SharedBlockEntity> {
+        // The piston adds the block entity itself, together with the block it moves
+        None
+    }
+}
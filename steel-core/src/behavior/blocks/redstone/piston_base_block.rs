@@ -0,0 +1,531 @@
+//! Piston and sticky piston block behavior.
+//!
+//! A powered piston pushes up to 12 blocks in front of it and places its head
+//! in the freed space. When it loses power it pulls the head back, and a
+//! sticky piston also pulls the block in front of the head along. Blocks move
+//! through `moving_piston` blocks whose block entities animate them.
+//!
+//! Vanilla equivalent: `PistonBaseBlock`.
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::behavior::PushReaction;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, PistonType};
+use steel_registry::{REGISTRY, sound_events, vanilla_blocks};
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use super::piston_structure_resolver::PistonStructureResolver;
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::BlockPlaceContext;
+use crate::block_entity::entities::PistonMovingBlockEntity;
+use crate::player::Player;
+use crate::world::World;
+
+/// Block event that extends the piston.
+const TRIGGER_EXTEND: u8 = 0;
+/// Block event that retracts the piston.
+const TRIGGER_CONTRACT: u8 = 1;
+/// Block event that retracts the piston while the blocks it pushed are still moving.
+const TRIGGER_DROP: u8 = 2;
+
+/// Behavior for pistons and sticky pistons.
+#[block_behavior]
+pub struct PistonBaseBlock {
+    block: BlockRef,
+    #[json_arg(value)]
+    is_sticky: bool,
+}
+
+impl PistonBaseBlock {
+    /// Creates a new piston block behavior.
+    ///
+    /// Parameters are provided by the build system from `classes.json`.
+    #[must_use]
+    pub const fn new(block: BlockRef, is_sticky: bool) -> Self {
+        Self { block, is_sticky }
+    }
+
+    const fn piston_type(&self) -> PistonType {
+        if self.is_sticky {
+            PistonType::Sticky
+        } else {
+            PistonType::Normal
+        }
+    }
+
+    /// Queues the block event that extends or retracts the piston if its power changed.
+    fn check_if_extend(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) {
+        let direction: Direction = state.get_value(&BlockStateProperties::FACING);
+        let should_extend = get_neighbor_signal(world, pos, direction);
+        let extended = state.get_value(&BlockStateProperties::EXTENDED);
+
+        if should_extend && !extended {
+            if PistonStructureResolver::new(world, pos, direction, true).resolve() {
+                world.block_event(pos, self.block, TRIGGER_EXTEND, direction_param(direction));
+            }
+        } else if !should_extend && extended {
+            let two_pos = pos.relative_n(direction, 2);
+            let two_state = world.get_block_state(two_pos);
+            let mut action = TRIGGER_CONTRACT;
+            // The blocks in front of the head are still moving out: leave them where they are
+            if two_state.get_block() == vanilla_blocks::MOVING_PISTON
+                && two_state.get_value(&BlockStateProperties::FACING) == direction
+                && let Some(block_entity) = world.get_block_entity(two_pos)
+            {
+                let game_time = world.level_data.read().game_time();
+                // TODO: Also when called while scheduled ticks run (vanilla's `isHandlingTick`)
+                let still_moving = block_entity
+                    .lock()
+                    .as_any()
+                    .downcast_ref::<PistonMovingBlockEntity>()
+                    .is_some_and(|moving| {
+                        moving.is_extending()
+                            && (moving.progress(0.0) < 0.5 || game_time == moving.last_ticked())
+                    });
+                if still_moving {
+                    action = TRIGGER_DROP;
+                }
+            }
+            world.block_event(pos, self.block, action, direction_param(direction));
+        }
+    }
+
+    /// Replaces the blocks the piston moves with moving pistons.
+    ///
+    /// Returns `false` if the blocks can't be moved.
+    ///
+    /// Vanilla equivalent: `PistonBaseBlock.moveBlocks()`.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "mirrors vanilla's moveBlocks step by step"
+    )]
+    fn move_blocks(
+        &self,
+        world: &Arc<World>,
+        piston_pos: BlockPos,
+        direction: Direction,
+        extending: bool,
+    ) -> bool {
+        let arm_pos = piston_pos.relative(direction);
+        if !extending && world.get_block_state(arm_pos).get_block() == vanilla_blocks::PISTON_HEAD {
+            world.set_block(
+                arm_pos,
+                REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR),
+                UpdateFlags::UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS
+                    | UpdateFlags::UPDATE_KNOWN_SHAPE
+                    | UpdateFlags::UPDATE_INVISIBLE,
+            );
+        }
+
+        let mut resolver = PistonStructureResolver::new(world, piston_pos, direction, extending);
+        if !resolver.resolve() {
+            return false;
+        }
+        let to_push = resolver.to_push();
+        let to_destroy = resolver.to_destroy();
+
+        let mut delete_after_move: FxHashMap<BlockPos, BlockStateId> = FxHashMap::default();
+        let to_push_states: Vec<BlockStateId> = to_push
+            .iter()
+            .map(|&pos| {
+                let state = world.get_block_state(pos);
+                delete_after_move.insert(pos, state);
+                state
+            })
+            .collect();
+        let mut to_update = Vec::with_capacity(to_push.len() + to_destroy.len());
+        let push_direction = if extending {
+            direction
+        } else {
+            direction.opposite()
+        };
+
+        for &pos in to_destroy.iter().rev() {
+            let state = world.get_block_state(pos);
+            // TODO: Drop the contents of block entities
+            world.drop_resources(state, pos);
+            let block = state.get_block();
+            if block != vanilla_blocks::FIRE && block != vanilla_blocks::SOUL_FIRE {
+                world.destroy_block_effect(pos, u32::from(state.0), None);
+            }
+            world.set_block(
+                pos,
+                REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR),
+                UpdateFlags::UPDATE_KNOWN_SHAPE | UpdateFlags::UPDATE_CLIENTS,
+            );
+            // TODO: Fire GameEvent.BLOCK_DESTROY
+            to_update.push(state);
+        }
+
+        let moving_state = vanilla_blocks::MOVING_PISTON
+            .default_state()
+            .set_value(&BlockStateProperties::FACING, direction);
+        let moving_flags = UpdateFlags::UPDATE_MOVE_BY_PISTON | UpdateFlags::UPDATE_INVISIBLE;
+        for (i, &pos) in to_push.iter().enumerate().rev() {
+            let state = world.get_block_state(pos);
+            let target = pos.relative(push_direction);
+            delete_after_move.remove(&target);
+            world.set_block(target, moving_state, moving_flags);
+            set_moving_block_entity(
+                world,
+                target,
+                moving_state,
+                to_push_states[i],
+                direction,
+                extending,
+                false,
+            );
+            to_update.push(state);
+        }
+
+        if extending {
+            let head_state = vanilla_blocks::PISTON_HEAD
+                .default_state()
+                .set_value(&BlockStateProperties::FACING, direction)
+                .set_value(&BlockStateProperties::PISTON_TYPE, self.piston_type());
+            let arm_state = self.moving_piston_state(direction);
+            delete_after_move.remove(&arm_pos);
+            world.set_block(arm_pos, arm_state, moving_flags);
+            set_moving_block_entity(world, arm_pos, arm_state, head_state, direction, true, true);
+        }
+
+        let air = REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+        for &pos in delete_after_move.keys() {
+            world.set_block(
+                pos,
+                air,
+                UpdateFlags::UPDATE_MOVE_BY_PISTON
+                    | UpdateFlags::UPDATE_KNOWN_SHAPE
+                    | UpdateFlags::UPDATE_CLIENTS,
+            );
+        }
+        for &pos in delete_after_move.keys() {
+            // TODO: updateIndirectNeighbourShapes for the old state and air
+            // once redstone wire exists
+            world.update_neighbor_shapes(
+                pos,
+                air,
+                UpdateFlags::UPDATE_CLIENTS,
+                World::UPDATE_LIMIT,
+            );
+        }
+
+        let mut to_update = to_update.into_iter();
+        for &pos in to_destroy.iter().rev() {
+            let Some(state) = to_update.next() else {
+                break;
+            };
+            BLOCK_BEHAVIORS
+                .get_behavior(state.get_block())
+                .affect_neighbors_after_removal(state, world, pos, false);
+            world.update_neighbors_at(pos, state.get_block());
+        }
+        for &pos in to_push.iter().rev() {
+            let Some(state) = to_update.next() else {
+                break;
+            };
+            world.update_neighbors_at(pos, state.get_block());
+        }
+        if extending {
+            world.update_neighbors_at(arm_pos, vanilla_blocks::PISTON_HEAD);
+        }
+        true
+    }
+
+    /// Pulls the block in front of the head back while a sticky piston retracts.
+    fn pull_block(&self, world: &Arc<World>, pos: BlockPos, direction: Direction, action_id: u8) {
+        let arm_pos = pos.relative(direction);
+        let two_pos = pos.relative_n(direction, 2);
+        let two_state = world.get_block_state(two_pos);
+
+        // A block that is still being pushed out stops where it is instead
+        if two_state.get_block() == vanilla_blocks::MOVING_PISTON
+            && let Some(block_entity) = world.get_block_entity(two_pos)
+        {
+            let pushed_out = block_entity
+                .lock()
+                .as_any()
+                .downcast_ref::<PistonMovingBlockEntity>()
+                .is_some_and(|moving| moving.direction() == direction && moving.is_extending());
+            if pushed_out {
+                PistonMovingBlockEntity::final_tick(world, two_pos);
+                return;
+            }
+        }
+
+        let two_block = two_state.get_block();
+        let is_piston =
+            two_block == vanilla_blocks::PISTON || two_block == vanilla_blocks::STICKY_PISTON;
+        if action_id != TRIGGER_CONTRACT
+            || two_state.is_air()
+            || !is_pushable(
+                two_state,
+                world,
+                two_pos,
+                direction.opposite(),
+                false,
+                direction,
+            )
+            || (!matches!(two_block.config.push_reaction, PushReaction::Normal) && !is_piston)
+        {
+            world.remove_block(arm_pos, false);
+        } else {
+            self.move_blocks(world, pos, direction, false);
+        }
+    }
+
+    /// Returns the moving piston block that moves this piston's base or head.
+    fn moving_piston_state(&self, direction: Direction) -> BlockStateId {
+        vanilla_blocks::MOVING_PISTON
+            .default_state()
+            .set_value(&BlockStateProperties::FACING, direction)
+            .set_value(&BlockStateProperties::PISTON_TYPE, self.piston_type())
+    }
+}
+
+impl BlockBehavior for PistonBaseBlock {
+    fn get_state_for_placement(&self, context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(
+            self.block
+                .default_state()
+                .set_value(
+                    &BlockStateProperties::FACING,
+                    context.get_nearest_looking_direction().opposite(),
+                )
+                .set_value(&BlockStateProperties::EXTENDED, false),
+        )
+    }
+
+    fn set_placed_by(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _player: &Player,
+    ) {
+        self.check_if_extend(state, world, pos);
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        self.check_if_extend(state, world, pos);
+    }
+
+    fn on_place(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        old_state: BlockStateId,
+        _moved_by_piston: bool,
+    ) {
+        if old_state.get_block() != self.block && world.get_block_entity(pos).is_none() {
+            self.check_if_extend(state, world, pos);
+        }
+    }
+
+    fn trigger_event(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        action_id: u8,
+        action_param: u8,
+    ) -> bool {
+        let direction: Direction = state.get_value(&BlockStateProperties::FACING);
+        let extended_state = state.set_value(&BlockStateProperties::EXTENDED, true);
+
+        // The power may have changed back since the event was queued
+        let should_extend = get_neighbor_signal(world, pos, direction);
+        if should_extend && (action_id == TRIGGER_CONTRACT || action_id == TRIGGER_DROP) {
+            world.set_block(pos, extended_state, UpdateFlags::UPDATE_CLIENTS);
+            return false;
+        }
+        if !should_extend && action_id == TRIGGER_EXTEND {
+            return false;
+        }
+
+        if action_id == TRIGGER_EXTEND {
+            if !self.move_blocks(world, pos, direction, true) {
+                return false;
+            }
+            world.set_block(
+                pos,
+                extended_state,
+                UpdateFlags::UPDATE_MOVE_BY_PISTON | UpdateFlags::UPDATE_ALL,
+            );
+            let pitch = rand::random::<f32>() * 0.25 + 0.6;
+            world.play_block_sound(sound_events::BLOCK_PISTON_EXTEND, pos, 0.5, pitch, None);
+            // TODO: GameEvent.BLOCK_ACTIVATE when game event system exists
+        } else if action_id == TRIGGER_CONTRACT || action_id == TRIGGER_DROP {
+            let arm_pos = pos.relative(direction);
+            PistonMovingBlockEntity::final_tick(world, arm_pos);
+
+            let moving_state = self.moving_piston_state(direction);
+            world.set_block(
+                pos,
+                moving_state,
+                UpdateFlags::UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS
+                    | UpdateFlags::UPDATE_KNOWN_SHAPE
+                    | UpdateFlags::UPDATE_INVISIBLE,
+            );
+            let retracted_state = self.block.default_state().set_value(
+                &BlockStateProperties::FACING,
+                Direction::from_3d_data_value(i32::from(action_param & 7)),
+            );
+            set_moving_block_entity(
+                world,
+                pos,
+                moving_state,
+                retracted_state,
+                direction,
+                false,
+                true,
+            );
+            world.update_neighbors_at(pos, vanilla_blocks::MOVING_PISTON);
+            world.update_neighbor_shapes(
+                pos,
+                moving_state,
+                UpdateFlags::UPDATE_CLIENTS,
+                World::UPDATE_LIMIT,
+            );
+
+            if self.is_sticky {
+                self.pull_block(world, pos, direction, action_id);
+            } else {
+                world.remove_block(arm_pos, false);
+            }
+
+            let pitch = rand::random::<f32>() * 0.15 + 0.6;
+            world.play_block_sound(sound_events::BLOCK_PISTON_CONTRACT, pos, 0.5, pitch, None);
+            // TODO: GameEvent.BLOCK_DEACTIVATE when game event system exists
+        }
+        true
+    }
+}
+
+/// Checks whether the piston at `pos` is powered.
+///
+/// Besides its direct neighbors (except the one it faces), a piston is also
+/// powered by the neighbors of the block above it (quasi-connectivity).
+fn get_neighbor_signal(world: &Arc<World>, pos: BlockPos, push_direction: Direction) -> bool {
+    for direction in Direction::VALUES {
+        if direction != push_direction && world.get_signal(pos.relative(direction), direction) > 0 {
+            return true;
+        }
+    }
+    if world.get_signal(pos, Direction::Down) > 0 {
+        return true;
+    }
+    let above = pos.above();
+    Direction::VALUES.into_iter().any(|direction| {
+        direction != Direction::Down && world.get_signal(above.relative(direction), direction) > 0
+    })
+}
+
+/// Checks whether a piston can move the block at `pos`.
+///
+/// `allow_destroyable` lets blocks that break when pushed count as movable,
+/// `connection_direction` is the direction the block is reached from, which
+/// matters for blocks that can only be pushed (glazed terracotta).
+///
+/// Vanilla equivalent: `PistonBaseBlock.isPushable()`.
+pub fn is_pushable(
+    state: BlockStateId,
+    world: &Arc<World>,
+    pos: BlockPos,
+    direction: Direction,
+    allow_destroyable: bool,
+    connection_direction: Direction,
+) -> bool {
+    // TODO: Check the world border once it exists
+    if pos.y() < world.get_min_y() || pos.y() > world.get_max_y() {
+        return false;
+    }
+    if state.is_air() {
+        return true;
+    }
+
+    let block = state.get_block();
+    if block == vanilla_blocks::OBSIDIAN
+        || block == vanilla_blocks::CRYING_OBSIDIAN
+        || block == vanilla_blocks::RESPAWN_ANCHOR
+        || block == vanilla_blocks::REINFORCED_DEEPSLATE
+    {
+        return false;
+    }
+    if (direction == Direction::Down && pos.y() == world.get_min_y())
+        || (direction == Direction::Up && pos.y() == world.get_max_y())
+    {
+        return false;
+    }
+
+    if block == vanilla_blocks::PISTON || block == vanilla_blocks::STICKY_PISTON {
+        if state.get_value(&BlockStateProperties::EXTENDED) {
+            return false;
+        }
+    } else {
+        #[expect(
+            clippy::float_cmp,
+            reason = "unbreakable blocks have a destroy time of exactly -1"
+        )]
+        let unbreakable = block.config.destroy_time == -1.0;
+        if unbreakable {
+            return false;
+        }
+        match block.config.push_reaction {
+            PushReaction::Block => return false,
+            PushReaction::Destroy => return allow_destroyable,
+            PushReaction::PushOnly => return direction == connection_direction,
+            PushReaction::Normal | PushReaction::Ignore => {}
+        }
+    }
+
+    !BLOCK_BEHAVIORS.get_behavior(block).has_block_entity()
+}
+
+/// Encodes the piston facing as the block event parameter.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "3D data values are always in 0..6"
+)]
+const fn direction_param(direction: Direction) -> u8 {
+    direction.get_3d_data_value() as u8
+}
+
+/// Adds the block entity of a moving piston that was just placed at `pos`.
+fn set_moving_block_entity(
+    world: &Arc<World>,
+    pos: BlockPos,
+    state: BlockStateId,
+    moved_state: BlockStateId,
+    direction: Direction,
+    extending: bool,
+    is_source_piston: bool,
+) {
+    world.set_block_entity(Arc::new(SyncMutex::new(
+        PistonMovingBlockEntity::new_moving(
+            Arc::downgrade(world),
+            pos,
+            state,
+            moved_state,
+            direction,
+            extending,
+            is_source_piston,
+        ),
+    )));
+}
@@ -0,0 +1,119 @@
+//! Piston head block behavior.
+//!
+//! The head of an extended piston. It only survives attached to its base and
+//! breaks the base when it is removed.
+//!
+//! Vanilla equivalent: `PistonHeadBlock`.
+
+use std::sync::Arc;
+
+use steel_macros::block_behavior;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction, PistonType};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::{REGISTRY, vanilla_blocks, vanilla_items};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::block::BlockBehavior;
+use crate::behavior::context::BlockPlaceContext;
+use crate::world::World;
+
+/// Behavior for the piston head block.
+#[block_behavior]
+pub struct PistonHeadBlock {
+    block: BlockRef,
+}
+
+impl PistonHeadBlock {
+    /// Creates a new piston head block behavior.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self { block }
+    }
+}
+
+/// Checks whether `base_state` is the extended piston this head belongs to.
+fn is_fitting_base(head_state: BlockStateId, base_state: BlockStateId) -> bool {
+    let base_block = match head_state.get_value(&BlockStateProperties::PISTON_TYPE) {
+        PistonType::Normal => vanilla_blocks::PISTON,
+        PistonType::Sticky => vanilla_blocks::STICKY_PISTON,
+    };
+    base_state.get_block() == base_block
+        && base_state.get_value(&BlockStateProperties::EXTENDED)
+        && facing(base_state) == facing(head_state)
+}
+
+fn facing(state: BlockStateId) -> Direction {
+    state.get_value(&BlockStateProperties::FACING)
+}
+
+impl BlockBehavior for PistonHeadBlock {
+    fn get_state_for_placement(&self, _context: &BlockPlaceContext<'_>) -> Option<BlockStateId> {
+        Some(self.block.default_state())
+    }
+
+    fn update_shape(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        direction: Direction,
+        _neighbor_pos: BlockPos,
+        _neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        if direction.opposite() == facing(state) && !self.can_survive(state, world, pos) {
+            return REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+        }
+        state
+    }
+
+    fn can_survive(&self, state: BlockStateId, world: &Arc<World>, pos: BlockPos) -> bool {
+        let behind_state = world.get_block_state(pos.relative(facing(state).opposite()));
+        is_fitting_base(state, behind_state)
+            || (behind_state.get_block() == vanilla_blocks::MOVING_PISTON
+                && facing(behind_state) == facing(state))
+    }
+
+    fn handle_neighbor_changed(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        source_block: BlockRef,
+        _moved_by_piston: bool,
+    ) {
+        // Let the base react to power next to the head
+        if self.can_survive(state, world, pos) {
+            world.neighbor_changed(pos.relative(facing(state).opposite()), source_block, false);
+        }
+    }
+
+    fn affect_neighbors_after_removal(
+        &self,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        _moved_by_piston: bool,
+    ) {
+        // TODO: Break the base without drops when a creative player breaks the head
+        // (vanilla's playerWillDestroy)
+        let base_pos = pos.relative(facing(state).opposite());
+        if is_fitting_base(state, world.get_block_state(base_pos)) {
+            world.destroy_block(base_pos, true);
+        }
+    }
+
+    fn get_clone_item_stack(
+        &self,
+        _block: BlockRef,
+        state: BlockStateId,
+        _include_data: bool,
+    ) -> Option<ItemStack> {
+        let item = match state.get_value(&BlockStateProperties::PISTON_TYPE) {
+            PistonType::Normal => &vanilla_items::ITEMS.piston,
+            PistonType::Sticky => &vanilla_items::ITEMS.sticky_piston,
+        };
+        Some(ItemStack::new(item))
+    }
+}
@@ -0,0 +1,268 @@
+//! Works out which blocks a piston moves and which it breaks.
+//!
+//! Vanilla equivalent: `PistonStructureResolver`.
+
+use std::sync::Arc;
+
+use steel_registry::blocks::behavior::PushReaction;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::vanilla_blocks;
+use steel_utils::{BlockPos, BlockStateId};
+
+use super::piston_base_block::is_pushable;
+use crate::world::World;
+
+/// The maximum number of blocks a piston can push.
+pub const MAX_PUSH_DEPTH: usize = 12;
+
+/// Collects the blocks moved and destroyed by a piston extending or retracting.
+pub struct PistonStructureResolver<'a> {
+    world: &'a Arc<World>,
+    piston_pos: BlockPos,
+    extending: bool,
+    start_pos: BlockPos,
+    push_direction: Direction,
+    piston_direction: Direction,
+    to_push: Vec<BlockPos>,
+    to_destroy: Vec<BlockPos>,
+}
+
+impl<'a> PistonStructureResolver<'a> {
+    /// Creates a resolver for the piston at `piston_pos` facing `piston_direction`.
+    #[must_use]
+    pub fn new(
+        world: &'a Arc<World>,
+        piston_pos: BlockPos,
+        piston_direction: Direction,
+        extending: bool,
+    ) -> Self {
+        let (push_direction, start_pos) = if extending {
+            (piston_direction, piston_pos.relative(piston_direction))
+        } else {
+            (
+                piston_direction.opposite(),
+                piston_pos.relative_n(piston_direction, 2),
+            )
+        };
+        Self {
+            world,
+            piston_pos,
+            extending,
+            start_pos,
+            push_direction,
+            piston_direction,
+            to_push: Vec::new(),
+            to_destroy: Vec::new(),
+        }
+    }
+
+    /// Collects the blocks to move. Returns `false` if the piston can't move them.
+    pub fn resolve(&mut self) -> bool {
+        self.to_push.clear();
+        self.to_destroy.clear();
+
+        let state = self.world.get_block_state(self.start_pos);
+        if !is_pushable(
+            state,
+            self.world,
+            self.start_pos,
+            self.push_direction,
+            false,
+            self.piston_direction,
+        ) {
+            if self.extending
+                && matches!(
+                    state.get_block().config.push_reaction,
+                    PushReaction::Destroy
+                )
+            {
+                self.to_destroy.push(self.start_pos);
+                return true;
+            }
+            return false;
+        }
+
+        if !self.add_block_line(self.start_pos, self.push_direction) {
+            return false;
+        }
+
+        // `add_branching_blocks` grows the list while it is being walked
+        let mut i = 0;
+        while i < self.to_push.len() {
+            let pos = self.to_push[i];
+            if is_sticky(self.world.get_block_state(pos)) && !self.add_branching_blocks(pos) {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns the blocks to move, ordered from the piston outwards per line.
+    #[must_use]
+    pub fn to_push(&self) -> &[BlockPos] {
+        &self.to_push
+    }
+
+    /// Returns the blocks the piston breaks.
+    #[must_use]
+    pub fn to_destroy(&self) -> &[BlockPos] {
+        &self.to_destroy
+    }
+
+    fn add_block_line(&mut self, start: BlockPos, direction: Direction) -> bool {
+        let mut state = self.world.get_block_state(start);
+        if state.is_air()
+            || !is_pushable(
+                state,
+                self.world,
+                start,
+                self.push_direction,
+                false,
+                direction,
+            )
+            || start == self.piston_pos
+            || self.to_push.contains(&start)
+        {
+            return true;
+        }
+
+        let mut block_count = 1;
+        if block_count + self.to_push.len() > MAX_PUSH_DEPTH {
+            return false;
+        }
+
+        // Sticky blocks drag the blocks behind them along
+        let pull_direction = self.push_direction.opposite();
+        while is_sticky(state) {
+            let pos = relative_n(start, pull_direction, block_count);
+            let previous_state = state;
+            state = self.world.get_block_state(pos);
+            if state.is_air()
+                || !can_stick_to_each_other(previous_state, state)
+                || !is_pushable(
+                    state,
+                    self.world,
+                    pos,
+                    self.push_direction,
+                    false,
+                    pull_direction,
+                )
+                || pos == self.piston_pos
+            {
+                break;
+            }
+            block_count += 1;
+            if block_count + self.to_push.len() > MAX_PUSH_DEPTH {
+                return false;
+            }
+        }
+
+        let mut blocks_added = 0;
+        for i in (0..block_count).rev() {
+            self.to_push.push(relative_n(start, pull_direction, i));
+            blocks_added += 1;
+        }
+
+        let mut blocks_pushed = 1;
+        loop {
+            let pos = relative_n(start, self.push_direction, blocks_pushed);
+            if let Some(collision_index) = self.to_push.iter().position(|&p| p == pos) {
+                self.reorder_list_at_collision(blocks_added, collision_index);
+                for i in 0..=collision_index + blocks_added {
+                    let pos = self.to_push[i];
+                    if is_sticky(self.world.get_block_state(pos)) && !self.add_branching_blocks(pos)
+                    {
+                        return false;
+                    }
+                }
+                return true;
+            }
+
+            state = self.world.get_block_state(pos);
+            if state.is_air() {
+                return true;
+            }
+            if !is_pushable(
+                state,
+                self.world,
+                pos,
+                self.push_direction,
+                true,
+                self.push_direction,
+            ) || pos == self.piston_pos
+            {
+                return false;
+            }
+            if matches!(
+                state.get_block().config.push_reaction,
+                PushReaction::Destroy
+            ) {
+                self.to_destroy.push(pos);
+                return true;
+            }
+            if self.to_push.len() >= MAX_PUSH_DEPTH {
+                return false;
+            }
+
+            self.to_push.push(pos);
+            blocks_added += 1;
+            blocks_pushed += 1;
+        }
+    }
+
+    /// Moves the line added last in front of the line it collided with, so that
+    /// blocks are always moved before the blocks behind them.
+    fn reorder_list_at_collision(&mut self, blocks_added: usize, collision_index: usize) {
+        let last_line_start = self.to_push.len() - blocks_added;
+        let mut last_line = self.to_push.split_off(last_line_start);
+        let mut collision_to_line = self.to_push.split_off(collision_index);
+        self.to_push.append(&mut last_line);
+        self.to_push.append(&mut collision_to_line);
+    }
+
+    fn add_branching_blocks(&mut self, from_pos: BlockPos) -> bool {
+        let from_state = self.world.get_block_state(from_pos);
+        for direction in Direction::VALUES {
+            if direction.get_axis() == self.push_direction.get_axis() {
+                continue;
+            }
+            let neighbor_pos = from_pos.relative(direction);
+            let neighbor_state = self.world.get_block_state(neighbor_pos);
+            if can_stick_to_each_other(neighbor_state, from_state)
+                && !self.add_block_line(neighbor_pos, direction)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn is_sticky(state: BlockStateId) -> bool {
+    let block = state.get_block();
+    block == vanilla_blocks::SLIME_BLOCK || block == vanilla_blocks::HONEY_BLOCK
+}
+
+fn can_stick_to_each_other(state: BlockStateId, neighbor_state: BlockStateId) -> bool {
+    let block = state.get_block();
+    let neighbor = neighbor_state.get_block();
+    // Slime and honey don't stick to each other
+    if (block == vanilla_blocks::HONEY_BLOCK && neighbor == vanilla_blocks::SLIME_BLOCK)
+        || (block == vanilla_blocks::SLIME_BLOCK && neighbor == vanilla_blocks::HONEY_BLOCK)
+    {
+        return false;
+    }
+    is_sticky(state) || is_sticky(neighbor_state)
+}
+
+/// [`BlockPos::relative_n`] for distances bounded by [`MAX_PUSH_DEPTH`].
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    reason = "distances are at most MAX_PUSH_DEPTH"
+)]
+const fn relative_n(pos: BlockPos, direction: Direction, n: usize) -> BlockPos {
+    pos.relative_n(direction, n as i32)
+}
@@ -2,12 +2,13 @@
 
 use glam::DVec3;
 use std::sync::Arc;
-use steel_registry::REGISTRY;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::Direction;
 use steel_registry::item_stack::ItemStack;
-use steel_utils::BlockPos;
-use steel_utils::types::InteractionHand;
+use steel_utils::types::{InteractionHand, UpdateFlags};
+use steel_utils::{BlockPos, BlockStateId};
 
+use crate::behavior::BLOCK_BEHAVIORS;
 use crate::fluid::FluidStateExt;
 use crate::inventory::lock::{ContainerId, ContainerLockGuard};
 use crate::player::Player;
@@ -57,6 +58,10 @@ pub struct BlockPlaceContext<'a> {
     pub rotation: f32,
     /// The player's pitch (vertical look angle).
     pub pitch: f32,
+    /// The item being placed.
+    pub item_in_hand: ItemStack,
+    /// Whether the player is sneaking, which some blocks use to skip stacking.
+    pub secondary_use_active: bool,
     /// The world where the block is being placed.
     pub world: &'a Arc<World>,
 }
@@ -96,6 +101,20 @@ impl BlockPlaceContext<'_> {
         directions
     }
 
+    /// Returns whether the block can go at [`Self::relative_pos`].
+    ///
+    /// Vanilla: `BlockPlaceContext.canPlace()`.
+    #[must_use]
+    pub fn can_place(&self) -> bool {
+        if self.replace_clicked {
+            return true;
+        }
+        let existing = self.world.get_block_state(self.relative_pos);
+        BLOCK_BEHAVIORS
+            .get_behavior(existing.get_block())
+            .can_be_replaced(existing, self)
+    }
+
     /// Returns true if the block at the relative position is a water source
     #[must_use]
     pub fn is_water_source(&self) -> bool {
@@ -192,45 +211,113 @@ impl<'a> UseOnContext<'a> {
 
     /// Builds a [`BlockPlaceContext`] from this interaction context.
     ///
-    /// Returns `None` if placement is invalid (out of bounds or non-replaceable target).
+    /// The block goes into the clicked block if that can be replaced, otherwise
+    /// next to it on the clicked face. Returns `None` if placement is invalid
+    /// (out of bounds or non-replaceable target).
     /// This is the common prefix of vanilla's `BlockItem.useOn`.
     #[must_use]
-    pub fn build_place_context(&self) -> Option<BlockPlaceContext<'a>> {
+    pub fn build_place_context(&mut self) -> Option<BlockPlaceContext<'a>> {
         let clicked_pos = self.hit_result.block_pos;
-        let clicked_state = self.world.get_block_state(clicked_pos);
-        let clicked_block = REGISTRY.blocks.by_state_id(clicked_state);
-        let clicked_replaceable = clicked_block.is_some_and(|b| b.config.replaceable);
-
-        let (place_pos, replace_clicked) = if clicked_replaceable {
-            (clicked_pos, true)
-        } else {
-            (self.hit_result.direction.relative(clicked_pos), false)
-        };
-
-        if !self.world.is_in_valid_bounds(place_pos) {
-            return None;
-        }
-
-        let existing_state = self.world.get_block_state(place_pos);
-        let existing_block = REGISTRY.blocks.by_state_id(existing_state);
-        if !existing_block.is_some_and(|b| b.config.replaceable) {
-            return None;
-        }
-
         let (yaw, pitch) = self.player.rotation.load();
 
-        Some(BlockPlaceContext {
+        // Vanilla: the BlockPlaceContext constructor asks the clicked block whether it
+        // can be replaced by this context
+        let mut context = BlockPlaceContext {
             clicked_pos,
             clicked_face: self.hit_result.direction,
             click_location: self.hit_result.location,
             inside: self.hit_result.inside,
-            relative_pos: place_pos,
-            replace_clicked,
+            relative_pos: self.hit_result.direction.relative(clicked_pos),
+            replace_clicked: false,
             horizontal_direction: Direction::from_yaw(yaw),
             rotation: yaw,
             pitch,
+            item_in_hand: self.inv.item().clone(),
+            secondary_use_active: self.player.is_secondary_use_active(),
             world: self.world,
-        })
+        };
+
+        let clicked_state = self.world.get_block_state(clicked_pos);
+        if BLOCK_BEHAVIORS
+            .get_behavior(clicked_state.get_block())
+            .can_be_replaced(clicked_state, &context)
+        {
+            context.relative_pos = clicked_pos;
+            context.replace_clicked = true;
+        }
+
+        if !self.world.is_in_valid_bounds(context.relative_pos) || !context.can_place() {
+            return None;
+        }
+
+        Some(context)
+    }
+
+    /// Places `state` for a block item and finishes the placement.
+    ///
+    /// This is the shared tail of vanilla's `BlockItem.place()`:
+    /// 1. Fail if an entity is standing in the block's collision shape
+    /// 2. Set the block, hand the item's components to its block entity and
+    ///    run the behavior's `set_placed_by`
+    /// 3. Update the shape of all 6 neighbors against the final state
+    /// 4. Play the place sound for everyone but the placer
+    /// 5. Take one item, unless the player has infinite materials
+    pub fn place_block(
+        &mut self,
+        place_context: &BlockPlaceContext<'_>,
+        state: BlockStateId,
+    ) -> InteractionResult {
+        let pos = place_context.relative_pos;
+        if !self.world.is_unobstructed(state.get_collision_shape(), pos) {
+            return InteractionResult::Fail;
+        }
+
+        // Neighbor shapes are updated below, after `set_placed_by` may have changed the block
+        if !self.world.set_block(
+            pos,
+            state,
+            UpdateFlags::UPDATE_ALL_IMMEDIATE | UpdateFlags::UPDATE_KNOWN_SHAPE,
+        ) {
+            return InteractionResult::Fail;
+        }
+
+        // Vanilla: BlockItem.updateBlockEntityComponents()
+        if let Some(block_entity) = self.world.get_block_entity(pos) {
+            let mut block_entity = block_entity.lock();
+            block_entity.apply_implicit_components(self.inv.item());
+            block_entity.set_changed();
+        }
+
+        let block = state.get_block();
+        BLOCK_BEHAVIORS
+            .get_behavior(block)
+            .set_placed_by(state, self.world, pos, self.player);
+
+        let placed_state = self.world.get_block_state(pos);
+        self.world.update_neighbor_shapes(
+            pos,
+            placed_state,
+            UpdateFlags::UPDATE_ALL_IMMEDIATE,
+            World::UPDATE_LIMIT,
+        );
+
+        // Vanilla: BlockItem.place() uses the placed state's sounds
+        let sound_type = BLOCK_BEHAVIORS
+            .get_behavior(placed_state.get_block())
+            .get_sound_type(placed_state);
+        self.world.play_block_sound(
+            sound_type.place_sound,
+            pos,
+            f32::midpoint(sound_type.volume, 1.0),
+            sound_type.pitch * 0.8,
+            Some(self.player.id),
+        );
+
+        if !self.player.has_infinite_materials() {
+            self.inv.item().shrink(1);
+        }
+
+        InteractionResult::Success
     }
 }
 
@@ -1,10 +1,19 @@
 //! Item behavior trait and registry.
 
+use std::sync::Arc;
+
+use steel_registry::item_stack::ItemStack;
 use steel_registry::items::ItemRef;
 use steel_registry::{REGISTRY, RegistryEntry, RegistryExt};
+use steel_utils::{BlockPos, BlockStateId};
 
 use crate::behavior::items::DefaultItemBehavior;
 use crate::behavior::{InteractionResult, UseItemContext, UseOnContext};
+use crate::entity::SharedEntity;
+use crate::inventory::lock::ContainerLockGuard;
+use crate::inventory::slot::SlotType;
+use crate::player::Player;
+use crate::world::World;
 
 /// Trait defining the behavior of an item.
 ///
@@ -22,6 +31,71 @@ pub trait ItemBehavior: Send + Sync {
     fn use_item(&self, _context: &mut UseItemContext) -> InteractionResult {
         InteractionResult::Pass
     }
+
+    /// Called when a player right-clicks a living entity with this item and the
+    /// entity itself did not handle the click.
+    ///
+    /// Vanilla: `Item.interactLivingEntity()`.
+    fn interact_living_entity(
+        &self,
+        _context: &mut UseItemContext,
+        _target: &SharedEntity,
+    ) -> InteractionResult {
+        InteractionResult::Pass
+    }
+
+    /// Called before a player destroys a block while holding this item in the main hand.
+    ///
+    /// Returning false cancels the break. By default, tools that can't destroy
+    /// blocks in creative (e.g. swords) are blocked for players with instabuild.
+    fn can_destroy_block(
+        &self,
+        item: &mut ItemStack,
+        _state: BlockStateId,
+        _world: &Arc<World>,
+        _pos: BlockPos,
+        player: &Player,
+    ) -> bool {
+        item.get_tool()
+            .is_none_or(|tool| tool.can_destroy_blocks_in_creative)
+            || !player.abilities.lock().instabuild
+    }
+
+    /// Called when a player clicks a slot while carrying this item.
+    ///
+    /// Returning true means the click was handled and the normal pickup logic
+    /// is skipped. `button` is 0 for a primary and 1 for a secondary click.
+    ///
+    /// Vanilla: `Item.overrideStackedOnOther()`.
+    fn override_stacked_on_other(
+        &self,
+        _stack: &mut ItemStack,
+        _slot: &SlotType,
+        _guard: &mut ContainerLockGuard,
+        _button: i8,
+        _player: &Player,
+    ) -> bool {
+        false
+    }
+
+    /// Called when a player clicks this item's slot, carrying `other`.
+    ///
+    /// `stack` is written back to the slot when this returns true, which also
+    /// skips the normal pickup logic. `button` is 0 for a primary and 1 for a
+    /// secondary click.
+    ///
+    /// Vanilla: `Item.overrideOtherStackedOnMe()`.
+    fn override_other_stacked_on_me(
+        &self,
+        _stack: &mut ItemStack,
+        _other: &mut ItemStack,
+        _slot: &SlotType,
+        _guard: &mut ContainerLockGuard,
+        _button: i8,
+        _player: &Player,
+    ) -> bool {
+        false
+    }
 }
 
 /// Registry for item behaviors.
@@ -2,8 +2,6 @@
 
 use steel_macros::item_behavior;
 use steel_registry::blocks::BlockRef;
-use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_utils::types::UpdateFlags;
 
 use crate::behavior::context::{InteractionResult, UseOnContext};
 use crate::behavior::{BLOCK_BEHAVIORS, ItemBehavior};
@@ -29,40 +27,13 @@ impl ItemBehavior for BlockItem {
         let Some(place_context) = context.build_place_context() else {
             return InteractionResult::Fail;
         };
-        let place_pos = place_context.relative_pos;
 
         let behavior = BLOCK_BEHAVIORS.get_behavior(self.block);
         let Some(new_state) = behavior.get_state_for_placement(&place_context) else {
             return InteractionResult::Fail;
         };
 
-        // Check if the block placement would intersect with any entity (vanilla: Level.isUnobstructed)
-        let collision_shape = new_state.get_collision_shape();
-        if !context.world.is_unobstructed(collision_shape, place_pos) {
-            return InteractionResult::Fail;
-        }
-
-        if !context
-            .world
-            .set_block(place_pos, new_state, UpdateFlags::UPDATE_ALL_IMMEDIATE)
-        {
-            return InteractionResult::Fail;
-        }
-
-        // Play place sound (exclude the placing player, they hear it client-side)
-        let sound_type = &self.block.config.sound_type;
-        context.world.play_block_sound(
-            sound_type.place_sound,
-            place_pos,
-            sound_type.volume,
-            sound_type.pitch,
-            Some(context.player.id),
-        );
-
-        context.inv.item().shrink(1);
-
-        // TODO: Call behavior.on_place() — triggers neighbor updates (redstone, etc.)
-        InteractionResult::Success
+        context.place_block(&place_context, new_state)
     }
 }
 
@@ -93,7 +64,8 @@ impl DoubleHighBlockItem {
 
 impl ItemBehavior for DoubleHighBlockItem {
     fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
-        // TODO: Implement vanilla's double-high placement (place upper half block above)
+        // The upper half is placed by the block itself in `set_placed_by` (e.g. `DoorBlock`).
+        // TODO: Vanilla first clears the space above (air, or water if it was a source)
         self.base.use_on(context)
     }
 }
@@ -0,0 +1,126 @@
+//! Boat item behavior implementation.
+//!
+//! Places a boat (or raft) where the player looks, on water or on land.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_macros::item_behavior;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_utils::BlockPos;
+
+use crate::behavior::context::{InteractionResult, UseItemContext};
+use crate::behavior::{BlockStateBehaviorExt, ItemBehavior};
+use crate::entity::{ENTITIES, Entity, next_entity_id};
+use crate::fluid::get_height;
+use crate::physics::{CollisionWorld, WorldCollisionProvider};
+use crate::world::{RaytraceAction, World};
+
+/// How far in front of the player entities can block placing a boat.
+const ENTITY_CHECK_RANGE: f64 = 5.0;
+
+/// Behavior for every boat and raft item.
+///
+/// Mirrors vanilla's `BoatItem`.
+#[item_behavior]
+pub struct BoatItem {
+    #[json_arg(vanilla_entities, json = "entity_type_description_id")]
+    entity_type: EntityTypeRef,
+}
+
+impl BoatItem {
+    /// Creates a new boat item behavior placing `entity_type`.
+    #[must_use]
+    pub const fn new(entity_type: EntityTypeRef) -> Self {
+        Self { entity_type }
+    }
+}
+
+impl ItemBehavior for BoatItem {
+    fn use_item(&self, context: &mut UseItemContext) -> InteractionResult {
+        let (start, end) = context.player.get_ray_endpoints();
+
+        // Vanilla: ClipContext.Fluid.ANY, water and lava both stop the ray
+        let (hit_block, _) = context.world.raytrace(start, end, |pos, world| {
+            let state = world.get_block_state(pos);
+            if !state.get_fluid_state().is_empty() {
+                return RaytraceAction::ImmediateHit;
+            }
+            if state.is_air() {
+                return RaytraceAction::Pass;
+            }
+            RaytraceAction::CheckShape
+        });
+        let Some(hit_pos) = hit_block else {
+            return InteractionResult::Pass;
+        };
+
+        // Don't place boats into entities right in front of the player
+        let view = (end - start).normalize_or_zero() * ENTITY_CHECK_RANGE;
+        let player_box = context.player.bounding_box();
+        let search_box = AABBd::new(
+            player_box.min_x + view.x.min(0.0),
+            player_box.min_y + view.y.min(0.0),
+            player_box.min_z + view.z.min(0.0),
+            player_box.max_x + view.x.max(0.0),
+            player_box.max_y + view.y.max(0.0),
+            player_box.max_z + view.z.max(0.0),
+        )
+        .inflate(1.0);
+        let eye_blocked = context
+            .world
+            .get_entities_in_aabb(&search_box)
+            .iter()
+            .filter(|entity| entity.id() != context.player.id())
+            .any(|entity| {
+                let bounds = entity.bounding_box();
+                (bounds.min_x..=bounds.max_x).contains(&start.x)
+                    && (bounds.min_y..=bounds.max_y).contains(&start.y)
+                    && (bounds.min_z..=bounds.max_z).contains(&start.z)
+            });
+        if eye_blocked {
+            return InteractionResult::Pass;
+        }
+
+        let location = hit_location(context.world, hit_pos, start, end);
+        let Some(boat) = ENTITIES.create(
+            self.entity_type,
+            next_entity_id(),
+            location,
+            Arc::downgrade(context.world),
+        ) else {
+            return InteractionResult::Fail;
+        };
+        boat.set_rotation((context.player.rotation().0, 0.0));
+        boat.set_custom_name(context.inv.item().get_custom_name().cloned());
+
+        // TODO: Also check for colliding entities
+        if !WorldCollisionProvider::new(context.world)
+            .get_block_collisions(&boat.bounding_box())
+            .is_empty()
+        {
+            return InteractionResult::Fail;
+        }
+
+        context.world.add_entity(boat);
+        if !context.player.has_infinite_materials() {
+            context.inv.item().shrink(1);
+        }
+        InteractionResult::Success
+    }
+}
+
+/// Gets where the ray from `start` to `end` enters the block or fluid at `pos`.
+fn hit_location(world: &Arc<World>, pos: BlockPos, start: DVec3, end: DVec3) -> DVec3 {
+    let fluid = world.get_block_state(pos).get_fluid_state();
+    let clip = if fluid.is_empty() {
+        world.ray_outline_clip(pos, start, end)
+    } else {
+        let min = DVec3::new(f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+        let height = f64::from(get_height(world, pos, fluid));
+        World::intersects_aabb_with_t(start, end, min, min + DVec3::new(1.0, height, 1.0))
+    };
+    clip.map_or(start, |(t, _)| start + (end - start) * t)
+}
@@ -0,0 +1,44 @@
+//! Bone meal item behavior implementation.
+
+use steel_macros::item_behavior;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::level_events;
+
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::{BLOCK_BEHAVIORS, ItemBehavior};
+
+/// Particle count sent with [`level_events::PARTICLES_AND_SOUND_PLANT_GROWTH`].
+const GROWTH_PARTICLE_COUNT: i32 = 15;
+
+/// Behavior for the bone meal item.
+///
+/// Grows crops and other bonemealable blocks it is used on.
+#[item_behavior]
+pub struct BoneMealItem;
+
+impl ItemBehavior for BoneMealItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let pos = context.hit_result.block_pos;
+        let state = context.world.get_block_state(pos);
+        let behavior = BLOCK_BEHAVIORS.get_behavior(state.get_block());
+
+        // TODO: Grow seagrass and coral on sturdy faces underwater (`growWaterPlant`)
+        if !behavior.is_valid_bonemeal_target(state, context.world, pos) {
+            return InteractionResult::Pass;
+        }
+
+        if behavior.is_bonemeal_success(state, context.world, pos) {
+            behavior.perform_bonemeal(state, context.world, pos);
+        }
+        context.inv.item().shrink(1);
+
+        // TODO: Emit GameEvent::ITEM_INTERACT_FINISH
+        context.world.level_event(
+            level_events::PARTICLES_AND_SOUND_PLANT_GROWTH,
+            pos,
+            GROWTH_PARTICLE_COUNT,
+            None,
+        );
+        InteractionResult::Success
+    }
+}
@@ -1,19 +1,23 @@
 //! Bucket item behavior implementation.
 //!
-//! Handles water buckets, lava buckets, and empty buckets.
+//! Handles water buckets, lava buckets, empty buckets, and buckets holding a
+//! solid block like powder snow.
 //!
 //! Mirrors vanilla's `BucketItem(Fluid fluid)`: `fluid_block = None` = empty bucket,
 //! `Some(block)` = filled bucket. Logic is dispatched in `use_item`.
 //!
 // TODO: Spawn particles
 
-use crate::behavior::context::InteractionResult;
+use std::sync::Arc;
+
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::items::BlockItem;
 use crate::behavior::{
     BLOCK_BEHAVIORS, BlockStateBehaviorExt, FLUID_BEHAVIORS, ItemBehavior, UseItemContext,
 };
 use crate::fluid::FluidStateExt;
 use crate::inventory::lock::ContainerId;
-use crate::world::RaytraceAction;
+use crate::world::{RaytraceAction, World};
 use steel_macros::item_behavior;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
@@ -53,6 +57,39 @@ impl ItemBehavior for BucketItem {
     }
 }
 
+/// Handles buckets that place a block instead of a fluid (powder snow).
+///
+/// The `_block` field is read by the build script via `#[json_arg]` to generate constructor
+/// calls from `classes.json`. The actual value is forwarded into `base`.
+#[item_behavior]
+pub struct SolidBucketItem {
+    #[json_arg(vanilla_blocks, json = "block")]
+    _block: BlockRef,
+    base: BlockItem,
+}
+
+impl SolidBucketItem {
+    /// Creates a new solid bucket behavior placing `block`.
+    #[must_use]
+    pub const fn new(block: BlockRef) -> Self {
+        Self {
+            _block: block,
+            base: BlockItem::new(block),
+        }
+    }
+}
+
+impl ItemBehavior for SolidBucketItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        // TODO: Vanilla plays the item's `place_sound` instead of the block's place sound
+        let result = self.base.use_on(context);
+        if result.consumes_action() && !context.player.has_infinite_materials() {
+            *context.inv.item() = ItemStack::new(&vanilla_items::ITEMS.bucket);
+        }
+        result
+    }
+}
+
 /// Consumes one bucket from the player's hand, replacing it with `result_item`.
 ///
 /// Vanilla parity: `ItemUtils.createFilledResult` with `limitCreativeStackSize = true`.
@@ -156,11 +193,6 @@ fn use_empty_bucket(context: &mut UseItemContext) -> InteractionResult {
     InteractionResult::Fail
 }
 
-// TODO: Refactor into smaller helpers once all bucket types are implemented
-#[expect(
-    clippy::too_many_lines,
-    reason = "mirrors vanilla's emptyContents flow; splitting would obscure the sequential placement logic"
-)]
 fn use_filled_bucket(fluid_block: BlockRef, context: &mut UseItemContext) -> InteractionResult {
     // Raytrace to find target block
     let (start, end) = context.player.get_ray_endpoints();
@@ -200,90 +232,17 @@ fn use_filled_bucket(fluid_block: BlockRef, context: &mut UseItemContext) -> Int
             return None;
         }
 
-        let state = context.world.get_block_state(pos);
-        let fluid_state = state.get_fluid_state();
-
-        // TODO: Nether water evaporation (vanilla uses EnvironmentAttributes.WATER_EVAPORATES)
-        // If the dimension evaporates water and we are placing WATER, play FIRE_EXTINGUISH
-        // sound, spawn LARGE_SMOKE particles, and consume the bucket without placing.
-
         // Vanilla parity (bl4): when sneaking, only air allows placement at this position.
         // Non-air blocks redirect to the neighbor — handled by the secondary call.
         // The secondary call bypasses this check (hitResult == null in vanilla).
+        let state = context.world.get_block_state(pos);
         if check_sneak && is_sneaking && !state.get_block().config.is_air {
             return None;
         }
 
-        // 1. Try Waterlogging via LiquidBlockContainer (only if Water bucket)
-        let is_water_bucket = fluid_block == vanilla_blocks::WATER;
-
-        if is_water_bucket {
-            let source_water = FluidState::source(&vanilla_fluids::WATER);
-            let behavior = BLOCK_BEHAVIORS.get_behavior(state.get_block());
-            if behavior.can_place_liquid(state, source_water.fluid_id) {
-                behavior.place_liquid(context.world, pos, state, source_water);
-                context.world.play_block_sound(
-                    sound_events::ITEM_BUCKET_EMPTY,
-                    pos,
-                    1.0,
-                    1.0,
-                    None,
-                );
-                consume_bucket(context, &vanilla_items::ITEMS.bucket);
-                return Some(InteractionResult::Success);
-            }
-        }
-
-        // 2. Try Standard Placement (Replaceable block)
-        if state.can_be_replaced_by_fluid(fluid_block) {
-            // If same fluid already exists and is source, just consume bucket (parity)
-            let is_same_fluid = if is_water_bucket {
-                fluid_state.is_water()
-            } else {
-                fluid_state.is_lava()
-            };
-
-            if is_same_fluid && fluid_state.is_source() {
-                consume_bucket(context, &vanilla_items::ITEMS.bucket);
-                return Some(InteractionResult::Success);
-            }
-
-            // Vanilla parity: destroy non-liquid replaceable blocks first so they
-            // drop their items (e.g. tall grass, flowers, snow layers).
-            if !state.get_block().config.liquid && !state.get_block().config.is_air {
-                context.player.world.destroy_block(pos, true);
-            }
-
-            // Place fluid block
-            let fluid_state_to_place = fluid_block.default_state();
-            if context
-                .world
-                .set_block(pos, fluid_state_to_place, UpdateFlags::UPDATE_ALL_IMMEDIATE)
-            {
-                let fluid_ref = if is_water_bucket {
-                    &vanilla_fluids::WATER
-                } else {
-                    &vanilla_fluids::LAVA
-                };
-                let tick_delay = FLUID_BEHAVIORS
-                    .get_behavior(fluid_ref)
-                    .tick_delay(context.world);
-                context
-                    .world
-                    .schedule_fluid_tick_default(pos, fluid_ref, tick_delay);
-
-                let sound_id = if is_water_bucket {
-                    sound_events::ITEM_BUCKET_EMPTY
-                } else {
-                    sound_events::ITEM_BUCKET_EMPTY_LAVA
-                };
-                context
-                    .world
-                    .play_block_sound(sound_id, pos, 1.0, 1.0, None);
-
-                consume_bucket(context, &vanilla_items::ITEMS.bucket);
-                return Some(InteractionResult::Success);
-            }
+        if empty_bucket_contents(context.world, pos, fluid_block) {
+            consume_bucket(context, &vanilla_items::ITEMS.bucket);
+            return Some(InteractionResult::Success);
         }
         None
     };
@@ -319,3 +278,81 @@ fn use_filled_bucket(fluid_block: BlockRef, context: &mut UseItemContext) -> Int
 
     InteractionResult::Fail
 }
+
+/// Places the fluid of a filled bucket at `pos`, waterlogging the block there
+/// if possible. Returns `false` if the fluid can't be placed.
+///
+/// Also used by dispensers, so this doesn't depend on a player.
+///
+/// Vanilla equivalent: `BucketItem.emptyContents()`.
+pub fn empty_bucket_contents(world: &Arc<World>, pos: BlockPos, fluid_block: BlockRef) -> bool {
+    if !world.is_in_valid_bounds(pos) {
+        return false;
+    }
+
+    let state = world.get_block_state(pos);
+    let fluid_state = state.get_fluid_state();
+
+    // TODO: Nether water evaporation (vanilla uses EnvironmentAttributes.WATER_EVAPORATES)
+    // If the dimension evaporates water and we are placing WATER, play FIRE_EXTINGUISH
+    // sound, spawn LARGE_SMOKE particles, and consume the bucket without placing.
+
+    // 1. Try Waterlogging via LiquidBlockContainer (only if Water bucket)
+    let is_water_bucket = fluid_block == vanilla_blocks::WATER;
+
+    if is_water_bucket {
+        let source_water = FluidState::source(&vanilla_fluids::WATER);
+        let behavior = BLOCK_BEHAVIORS.get_behavior(state.get_block());
+        if behavior.can_place_liquid(state, source_water.fluid_id) {
+            behavior.place_liquid(world, pos, state, source_water);
+            world.play_block_sound(sound_events::ITEM_BUCKET_EMPTY, pos, 1.0, 1.0, None);
+            return true;
+        }
+    }
+
+    // 2. Try Standard Placement (Replaceable block)
+    if !state.can_be_replaced_by_fluid(fluid_block) {
+        return false;
+    }
+
+    // If same fluid already exists and is source, just consume bucket (parity)
+    let is_same_fluid = if is_water_bucket {
+        fluid_state.is_water()
+    } else {
+        fluid_state.is_lava()
+    };
+    if is_same_fluid && fluid_state.is_source() {
+        return true;
+    }
+
+    // Vanilla parity: destroy non-liquid replaceable blocks first so they
+    // drop their items (e.g. tall grass, flowers, snow layers).
+    if !state.get_block().config.liquid && !state.get_block().config.is_air {
+        world.destroy_block(pos, true);
+    }
+
+    // Place fluid block
+    if !world.set_block(
+        pos,
+        fluid_block.default_state(),
+        UpdateFlags::UPDATE_ALL_IMMEDIATE,
+    ) {
+        return false;
+    }
+
+    let fluid_ref = if is_water_bucket {
+        &vanilla_fluids::WATER
+    } else {
+        &vanilla_fluids::LAVA
+    };
+    let tick_delay = FLUID_BEHAVIORS.get_behavior(fluid_ref).tick_delay(world);
+    world.schedule_fluid_tick_default(pos, fluid_ref, tick_delay);
+
+    let sound_id = if is_water_bucket {
+        sound_events::ITEM_BUCKET_EMPTY
+    } else {
+        sound_events::ITEM_BUCKET_EMPTY_LAVA
+    };
+    world.play_block_sound(sound_id, pos, 1.0, 1.0, None);
+    true
+}
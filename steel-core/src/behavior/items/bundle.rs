@@ -0,0 +1,134 @@
+//! Bundle item behavior for moving items in and out of bundles in menus.
+
+use steel_macros::item_behavior;
+use steel_registry::data_components::vanilla_components::{BUNDLE_CONTENTS, BundleContents};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::sound_events;
+
+use crate::behavior::item::ItemBehavior;
+use crate::entity::Entity;
+use crate::inventory::lock::ContainerLockGuard;
+use crate::inventory::slot::{Slot, SlotType};
+use crate::player::Player;
+
+/// Primary (left) click button.
+const PRIMARY: i8 = 0;
+/// Secondary (right) click button.
+const SECONDARY: i8 = 1;
+
+/// Behavior for the bundle and its dyed variants.
+///
+/// Clicking an item with a bundle (or a bundle with an item) stores it, and
+/// clicking with the secondary button takes the selected item back out.
+// TODO: Drop the contents when the bundle is used in the air (`BundleItem.use`)
+#[item_behavior]
+pub struct BundleItem;
+
+impl BundleItem {
+    fn play_insert_sound(player: &Player) {
+        let pitch = 0.8 + rand::random::<f32>() * 0.4;
+        player.play_sound(sound_events::ITEM_BUNDLE_INSERT, 0.8, pitch);
+    }
+
+    fn play_insert_fail_sound(player: &Player) {
+        player.play_sound(sound_events::ITEM_BUNDLE_INSERT_FAIL, 1.0, 1.0);
+    }
+
+    fn play_remove_one_sound(player: &Player) {
+        let pitch = 0.8 + rand::random::<f32>() * 0.4;
+        player.play_sound(sound_events::ITEM_BUNDLE_REMOVE_ONE, 0.8, pitch);
+    }
+
+    /// Clears the selected item, like vanilla does on clicks the bundle ignores.
+    fn reset_selected_item(stack: &mut ItemStack) {
+        if let Some(mut contents) = stack.get(BUNDLE_CONTENTS).cloned() {
+            contents.toggle_selected_item(-1);
+            stack.set(BUNDLE_CONTENTS, contents);
+        }
+    }
+}
+
+impl ItemBehavior for BundleItem {
+    /// Vanilla: `BundleItem.overrideStackedOnOther()`.
+    fn override_stacked_on_other(
+        &self,
+        stack: &mut ItemStack,
+        slot: &SlotType,
+        guard: &mut ContainerLockGuard,
+        button: i8,
+        player: &Player,
+    ) -> bool {
+        let Some(mut contents) = stack.get(BUNDLE_CONTENTS).cloned() else {
+            return false;
+        };
+        let other = slot.get_item(guard).clone();
+
+        if button == PRIMARY && !other.is_empty() {
+            let mut taken = if BundleContents::can_item_be_in_bundle(&other) {
+                let max_amount = contents.max_amount_to_add(&other);
+                slot.safe_take(guard, other.count(), max_amount, player)
+            } else {
+                ItemStack::empty()
+            };
+            if contents.try_insert(&mut taken) > 0 {
+                Self::play_insert_sound(player);
+            } else {
+                Self::play_insert_fail_sound(player);
+            }
+        } else if button == SECONDARY && other.is_empty() {
+            if let Some(mut removed) = contents.remove_one() {
+                slot.safe_insert(guard, &mut removed, i32::MAX);
+                if removed.is_empty() {
+                    Self::play_remove_one_sound(player);
+                } else {
+                    contents.try_insert(&mut removed);
+                }
+            }
+        } else {
+            return false;
+        }
+
+        stack.set(BUNDLE_CONTENTS, contents);
+        true
+    }
+
+    /// Vanilla: `BundleItem.overrideOtherStackedOnMe()`.
+    fn override_other_stacked_on_me(
+        &self,
+        stack: &mut ItemStack,
+        other: &mut ItemStack,
+        slot: &SlotType,
+        guard: &mut ContainerLockGuard,
+        button: i8,
+        player: &Player,
+    ) -> bool {
+        if button == PRIMARY && other.is_empty() {
+            Self::reset_selected_item(stack);
+            return false;
+        }
+        let Some(mut contents) = stack.get(BUNDLE_CONTENTS).cloned() else {
+            return false;
+        };
+
+        if button == PRIMARY {
+            if slot.allow_modification(guard, player) && contents.try_insert(other) > 0 {
+                Self::play_insert_sound(player);
+            } else {
+                Self::play_insert_fail_sound(player);
+            }
+        } else if button == SECONDARY && other.is_empty() {
+            if slot.allow_modification(guard, player)
+                && let Some(removed) = contents.remove_one()
+            {
+                Self::play_remove_one_sound(player);
+                *other = removed;
+            }
+        } else {
+            Self::reset_selected_item(stack);
+            return false;
+        }
+
+        stack.set(BUNDLE_CONTENTS, contents);
+        true
+    }
+}
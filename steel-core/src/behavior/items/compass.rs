@@ -0,0 +1,56 @@
+//! Compass item behavior.
+//!
+//! Using a compass on a lodestone binds it to that lodestone. Whether an
+//! unbound compass points at the world spawn is decided by the client from
+//! the default spawn position the server sends.
+
+use steel_macros::item_behavior;
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::data_components::vanilla_components::{LODESTONE_TRACKER, LodestoneTracker};
+use steel_registry::entity_data::GlobalPos;
+use steel_registry::{sound_events, vanilla_blocks};
+
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::item::ItemBehavior;
+
+/// Behavior for compasses.
+#[item_behavior]
+pub struct CompassItem;
+
+impl ItemBehavior for CompassItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let pos = context.hit_result.block_pos;
+        if context.world.get_block_state(pos).get_block() != vanilla_blocks::LODESTONE {
+            return InteractionResult::Pass;
+        }
+
+        context.world.play_sound(
+            sound_events::ITEM_LODESTONE_COMPASS_LOCK,
+            SoundSource::Players,
+            pos,
+            1.0,
+            1.0,
+            None,
+        );
+
+        let tracker =
+            LodestoneTracker::new(GlobalPos::new(context.world.dimension.key.clone(), pos));
+        let player = context.player;
+        let consume = !player.has_infinite_materials();
+        let item = context.inv.item();
+        if consume && item.count() == 1 {
+            item.set(LODESTONE_TRACKER, tracker);
+            return InteractionResult::Success;
+        }
+
+        // Bind a single compass out of the stack, leaving the rest unbound
+        let mut compass = item.copy_with_count(1);
+        compass.set(LODESTONE_TRACKER, tracker);
+        if consume {
+            item.shrink(1);
+        }
+        player.add_item_or_drop_with_guard(context.inv.guard(), compass);
+        InteractionResult::Success
+    }
+}
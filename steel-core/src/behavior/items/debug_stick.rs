@@ -0,0 +1,161 @@
+//! Debug stick item behavior for selecting and cycling block state properties.
+
+use std::sync::Arc;
+
+use steel_macros::item_behavior;
+use steel_registry::REGISTRY;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::DynProperty;
+use steel_registry::data_components::vanilla_components::DEBUG_STICK_STATE;
+use steel_registry::item_stack::ItemStack;
+use steel_utils::translations;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+use text_components::TextComponent;
+
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::item::ItemBehavior;
+use crate::player::Player;
+use crate::world::World;
+
+/// Behavior for the debug stick.
+///
+/// Attacking a block selects its next property, using the stick on a block
+/// cycles the selected property's value. Sneaking reverses both directions.
+/// Only players that can use game master blocks get any effect.
+#[item_behavior]
+pub struct DebugStickItem;
+
+impl DebugStickItem {
+    /// Selects (`cycle == false`) or cycles (`cycle == true`) a property of the block at `pos`.
+    ///
+    /// Returns false if nothing happened.
+    fn handle_interaction(
+        player: &Player,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        cycle: bool,
+        item: &mut ItemStack,
+    ) -> bool {
+        if !player.can_use_game_master_blocks() {
+            return false;
+        }
+
+        let block = state.get_block();
+        if block.properties.is_empty() {
+            player.send_overlay_message(
+                &translations::ITEM_MINECRAFT_DEBUG_STICK_EMPTY
+                    .message([TextComponent::from(block.key.to_string())])
+                    .into(),
+            );
+            return false;
+        }
+
+        let Some(selected) = item
+            .get(DEBUG_STICK_STATE)
+            .map(|debug_state| debug_state.get(&block.key))
+        else {
+            return false;
+        };
+        let backwards = player.is_secondary_use_active();
+
+        if cycle {
+            let property = selected.unwrap_or_else(|| block.properties[0].get_name());
+            let Some(new_state) = REGISTRY.blocks.cycle_property(state, property, backwards) else {
+                return false;
+            };
+            world.set_block(
+                pos,
+                new_state,
+                UpdateFlags::UPDATE_CLIENTS | UpdateFlags::UPDATE_KNOWN_SHAPE,
+            );
+            player.send_overlay_message(
+                &translations::ITEM_MINECRAFT_DEBUG_STICK_UPDATE
+                    .message([
+                        TextComponent::from(property),
+                        TextComponent::from(Self::value_name(new_state, property)),
+                    ])
+                    .into(),
+            );
+        } else {
+            let property = Self::relative_property(block.properties, selected, backwards);
+            let mut debug_state = item.get(DEBUG_STICK_STATE).cloned().unwrap_or_default();
+            debug_state.set(block.key.clone(), property);
+            item.set(DEBUG_STICK_STATE, debug_state);
+            player.send_overlay_message(
+                &translations::ITEM_MINECRAFT_DEBUG_STICK_SELECT
+                    .message([
+                        TextComponent::from(property),
+                        TextComponent::from(Self::value_name(state, property)),
+                    ])
+                    .into(),
+            );
+        }
+
+        true
+    }
+
+    /// Returns the property after (or before) `current`, wrapping around.
+    ///
+    /// With nothing selected yet, this is the first property (or the last when
+    /// going backwards), matching vanilla's `Util.findNextInIterable` /
+    /// `Util.findPreviousInIterable`.
+    fn relative_property(
+        properties: &[&'static dyn DynProperty],
+        current: Option<&str>,
+        backwards: bool,
+    ) -> &'static str {
+        let count = properties.len();
+        let index =
+            match current.and_then(|name| properties.iter().position(|p| p.get_name() == name)) {
+                Some(index) if backwards => (index + count - 1) % count,
+                Some(index) => (index + 1) % count,
+                None if backwards => count - 1,
+                None => 0,
+            };
+        properties[index].get_name()
+    }
+
+    /// Returns the value of `property` in `state` as shown to the player.
+    fn value_name(state: BlockStateId, property: &str) -> &'static str {
+        REGISTRY
+            .blocks
+            .get_properties(state)
+            .into_iter()
+            .find_map(|(name, value)| (name == property).then_some(value))
+            .unwrap_or_default()
+    }
+}
+
+impl ItemBehavior for DebugStickItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let pos = context.hit_result.block_pos;
+        let state = context.world.get_block_state(pos);
+
+        if Self::handle_interaction(
+            context.player,
+            state,
+            context.world,
+            pos,
+            true,
+            context.inv.item(),
+        ) {
+            InteractionResult::Success
+        } else {
+            InteractionResult::Fail
+        }
+    }
+
+    fn can_destroy_block(
+        &self,
+        item: &mut ItemStack,
+        state: BlockStateId,
+        world: &Arc<World>,
+        pos: BlockPos,
+        player: &Player,
+    ) -> bool {
+        Self::handle_interaction(player, state, world, pos, false, item);
+        false
+    }
+}
@@ -0,0 +1,60 @@
+//! Empty map item behavior implementation.
+
+use steel_macros::item_behavior;
+use steel_registry::data_components::vanilla_components::MAP_ID;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::sound_events::UI_CARTOGRAPHY_TABLE_TAKE_RESULT;
+use steel_registry::vanilla_items;
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseItemContext};
+use crate::entity::Entity;
+use crate::map::MapItemSavedData;
+
+/// Behavior for the empty map.
+///
+/// Using it creates a new map centered on the grid cell the player stands
+/// in and hands out the filled map.
+#[item_behavior]
+pub struct EmptyMapItem;
+
+impl ItemBehavior for EmptyMapItem {
+    fn use_item(&self, context: &mut UseItemContext) -> InteractionResult {
+        let player = context.player;
+        let Some(server) = player.server.upgrade() else {
+            return InteractionResult::Pass;
+        };
+
+        if !player.has_infinite_materials() {
+            context.inv.item().shrink(1);
+        }
+        // TODO: Award the item used stat
+        context.world.play_entity_sound(
+            player,
+            UI_CARTOGRAPHY_TABLE_TAKE_RESULT,
+            player.sound_source(),
+            1.0,
+            1.0,
+            None,
+        );
+
+        let pos = player.position();
+        let data = MapItemSavedData::create_fresh(
+            pos.x.floor(),
+            pos.z.floor(),
+            0,
+            true,
+            false,
+            context.world.dimension.key.clone(),
+        );
+        let mut map = ItemStack::new(&vanilla_items::ITEMS.filled_map);
+        map.set(MAP_ID, server.maps.create(data));
+
+        if context.inv.item().is_empty() {
+            *context.inv.item() = map;
+        } else {
+            player.add_item_or_drop_with_guard(context.inv.guard(), map);
+        }
+        InteractionResult::Success
+    }
+}
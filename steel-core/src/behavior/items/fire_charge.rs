@@ -0,0 +1,60 @@
+//! Fire charge item behavior.
+//!
+//! Used on a block, a fire charge works like a single-use flint and steel.
+
+use crate::behavior::blocks::FireBlock;
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::item::ItemBehavior;
+use crate::behavior::items::flint_and_steel::can_light;
+use steel_macros::item_behavior;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::sound_events;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, Direction};
+
+/// Behavior for fire charges.
+#[item_behavior]
+pub struct FireChargeItem;
+
+impl FireChargeItem {
+    fn play_sound(context: &UseOnContext, pos: BlockPos) {
+        let pitch = (rand::random::<f32>() - rand::random::<f32>()) * 0.2 + 1.0;
+        context
+            .world
+            .play_block_sound(sound_events::ITEM_FIRECHARGE_USE, pos, 1.0, pitch, None);
+    }
+}
+
+impl ItemBehavior for FireChargeItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let click_pos = context.hit_result.block_pos;
+        let click_state = context.world.get_block_state(click_pos);
+
+        if can_light(click_state) {
+            Self::play_sound(context, click_pos);
+            context.world.set_block(
+                click_pos,
+                click_state.set_value(&BlockStateProperties::LIT, true),
+                UpdateFlags::UPDATE_ALL,
+            );
+            // TODO: Emit GameEvent::BLOCK_CHANGE
+        } else {
+            let fire_pos = click_pos.relative(context.hit_result.direction);
+            let (yaw, _) = context.player.rotation.load();
+            if !FireBlock::can_be_placed_at(context.world, fire_pos, Direction::from_yaw(yaw)) {
+                return InteractionResult::Fail;
+            }
+
+            Self::play_sound(context, fire_pos);
+            let fire_state = FireBlock::get_state(context.world, fire_pos);
+            context
+                .world
+                .set_block(fire_pos, fire_state, UpdateFlags::UPDATE_ALL);
+            // TODO: Emit GameEvent::BLOCK_PLACE
+        }
+
+        context.inv.item().shrink(1);
+        InteractionResult::Success
+    }
+}
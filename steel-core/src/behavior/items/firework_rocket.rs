@@ -0,0 +1,63 @@
+//! Firework rocket item behavior.
+//!
+//! Launches a rocket from the clicked block face, or boosts a gliding player
+//! when used in the air.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_macros::item_behavior;
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseItemContext, UseOnContext};
+use crate::entity::entities::FireworkRocketEntity;
+use crate::entity::projectile::Projectile;
+use crate::entity::{Entity, next_entity_id};
+
+/// How far in front of the clicked face rockets are launched from.
+const FACE_OFFSET: f64 = 0.15;
+
+/// Behavior for firework rockets.
+///
+/// Mirrors vanilla's `FireworkRocketItem`.
+#[item_behavior]
+pub struct FireworkRocketItem;
+
+impl ItemBehavior for FireworkRocketItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let (dx, dy, dz) = context.hit_result.direction.offset();
+        let position = context.hit_result.location
+            + DVec3::new(f64::from(dx), f64::from(dy), f64::from(dz)) * FACE_OFFSET;
+
+        let rocket = FireworkRocketEntity::launched(
+            next_entity_id(),
+            position,
+            Arc::downgrade(context.world),
+            context.inv.item(),
+        );
+        rocket.set_owner(Some(context.player.uuid()));
+        context.world.add_entity(Arc::new(rocket));
+
+        context.inv.item().shrink(1);
+        InteractionResult::Success
+    }
+
+    fn use_item(&self, context: &mut UseItemContext) -> InteractionResult {
+        if !context.player.is_fall_flying() {
+            return InteractionResult::Pass;
+        }
+
+        // TODO: Drop all leash connections and play the lead break sound
+        let rocket = FireworkRocketEntity::attached(
+            next_entity_id(),
+            context.player,
+            Arc::downgrade(context.world),
+            context.inv.item(),
+        );
+        context.world.add_entity(Arc::new(rocket));
+
+        context.inv.item().shrink(1);
+        // TODO: Award stat ITEM_USED
+        InteractionResult::Success
+    }
+}
@@ -1,23 +1,72 @@
 //! Flint and steel item behavior with portal ignition.
+//!
+//! Lights campfires, candles and candle cakes, or places fire in front of the
+//! clicked face. Fire placed inside an obsidian frame turns into a nether portal
+//! (see [`FireBlock`]'s `on_place`).
 
 use crate::behavior::blocks::FireBlock;
 use crate::behavior::context::{InteractionResult, UseOnContext};
 use crate::behavior::item::ItemBehavior;
 use steel_macros::item_behavior;
-use steel_registry::sound_events;
-use steel_registry::vanilla_blocks::FIRE;
-use steel_utils::Direction;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::{REGISTRY, TaggedRegistryExt, sound_events, vanilla_block_tags};
 use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockStateId, Direction};
 
 /// Behavior for flint and steel items.
 #[item_behavior]
 pub struct FlintAndSteelItem;
 
+/// Returns true if `state` is an unlit campfire, candle or candle cake that
+/// can be lit.
+///
+/// Vanilla equivalent: `CampfireBlock.canLight()`, `CandleBlock.canLight()` and
+/// `CandleCakeBlock.canLight()`.
+pub(crate) fn can_light(state: BlockStateId) -> bool {
+    let block = state.get_block();
+    let lightable = REGISTRY
+        .blocks
+        .is_in_tag(block, &vanilla_block_tags::CAMPFIRES_TAG)
+        || REGISTRY
+            .blocks
+            .is_in_tag(block, &vanilla_block_tags::CANDLES_TAG)
+        || REGISTRY
+            .blocks
+            .is_in_tag(block, &vanilla_block_tags::CANDLE_CAKES_TAG);
+
+    lightable
+        && state.try_get_value(&BlockStateProperties::LIT) == Some(false)
+        && state.try_get_value(&BlockStateProperties::WATERLOGGED) != Some(true)
+}
+
 impl ItemBehavior for FlintAndSteelItem {
     fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
-        // TODO: light campfires, candles, and candle cakes (set LIT=true) before fire placement
-
         let click_pos = context.hit_result.block_pos;
+        let click_state = context.world.get_block_state(click_pos);
+        let pitch = rand::random::<f32>() * 0.4 + 0.8;
+
+        if can_light(click_state) {
+            context.world.play_block_sound(
+                sound_events::ITEM_FLINTANDSTEEL_USE,
+                click_pos,
+                1.0,
+                pitch,
+                Some(context.player.id),
+            );
+            context.world.set_block(
+                click_pos,
+                click_state.set_value(&BlockStateProperties::LIT, true),
+                UpdateFlags::UPDATE_ALL_IMMEDIATE,
+            );
+            // TODO: Emit GameEvent::BLOCK_CHANGE
+
+            let has_infinite_materials = context.player.has_infinite_materials();
+            context.inv.item().hurt_and_break(1, has_infinite_materials);
+
+            return InteractionResult::Success;
+        }
+
         let fire_pos = click_pos.relative(context.hit_result.direction);
         let (yaw, _) = context.player.rotation.load();
         let forward_dir = Direction::from_yaw(yaw);
@@ -30,14 +79,16 @@ impl ItemBehavior for FlintAndSteelItem {
             sound_events::ITEM_FLINTANDSTEEL_USE,
             fire_pos,
             1.0,
-            rand::random::<f32>() * 0.4 + 0.8,
+            pitch,
             Some(context.player.id),
         );
 
-        // TODO: use BaseFireBlock.getState() equivalent to select soul fire vs regular fire
+        // Soul fire on soul sand and soil, regular fire everywhere else
+        let fire_state = FireBlock::get_state(context.world, fire_pos);
         context
             .world
-            .set_block(fire_pos, FIRE.default_state(), UpdateFlags::UPDATE_ALL);
+            .set_block(fire_pos, fire_state, UpdateFlags::UPDATE_ALL_IMMEDIATE);
+        // TODO: Emit GameEvent::BLOCK_PLACE
 
         let has_infinite_materials = context.player.has_infinite_materials();
         context.inv.item().hurt_and_break(1, has_infinite_materials);
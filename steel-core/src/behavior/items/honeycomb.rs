@@ -2,14 +2,11 @@ use steel_macros::item_behavior;
 use steel_registry::{REGISTRY, blocks::block_state_ext::BlockStateExt, level_events};
 use steel_utils::types::UpdateFlags;
 
-use crate::{
-    behavior::{
-        InteractionResult, ItemBehavior, UseOnContext, waxables::get_waxed_from_normal_variant,
-    },
-    block_entity::{BlockEntity, entities::SignBlockEntity},
+use crate::behavior::{
+    InteractionResult, ItemBehavior, UseOnContext, waxables::get_waxed_from_normal_variant,
 };
 
-/// Behavior for the honeycomb item. Waxes copper blocks and signs.
+/// Behavior for the honeycomb item. Waxes copper blocks.
 #[item_behavior]
 pub struct HoneycombItem;
 
@@ -39,29 +36,7 @@ impl ItemBehavior for HoneycombItem {
             return InteractionResult::Success;
         }
 
-        // Fall through to sign waxing
-        let Some(block_entity) = context.world.get_block_entity(pos) else {
-            return InteractionResult::Pass;
-        };
-
-        let mut guard = block_entity.lock();
-        let Some(sign) = guard.as_any_mut().downcast_mut::<SignBlockEntity>() else {
-            return InteractionResult::Pass;
-        };
-
-        if sign.is_waxed {
-            return InteractionResult::Pass;
-        }
-
-        sign.is_waxed = true;
-        sign.set_changed();
-        context.inv.item().shrink(1);
-        context.world.level_event(
-            level_events::PARTICLES_AND_SOUND_WAX_ON,
-            pos,
-            0,
-            Some(context.player.id),
-        );
-        InteractionResult::Success
+        // Signs are waxed by the sign block itself, see `SignApplicator`
+        InteractionResult::Pass
     }
 }
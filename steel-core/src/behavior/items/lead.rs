@@ -0,0 +1,25 @@
+//! Lead item behavior for tying leashed entities to fences.
+
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::behavior::item::ItemBehavior;
+use crate::entity::leash;
+use steel_macros::item_behavior;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::vanilla_block_tags::FENCES_TAG;
+use steel_registry::{REGISTRY, TaggedRegistryExt};
+
+/// Behavior for the lead item.
+#[item_behavior]
+pub struct LeadItem;
+
+impl ItemBehavior for LeadItem {
+    /// Vanilla: `LeadItem.useOn()`.
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let pos = context.hit_result.block_pos;
+        let state = context.world.get_block_state(pos);
+        if !REGISTRY.blocks.is_in_tag(state.get_block(), &FENCES_TAG) {
+            return InteractionResult::Pass;
+        }
+        leash::bind_player_mobs(context.world, context.player, pos)
+    }
+}
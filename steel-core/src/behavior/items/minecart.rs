@@ -0,0 +1,80 @@
+//! Minecart item behavior implementation.
+//!
+//! Places a minecart on the rail the player clicked.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_macros::item_behavior;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, RailShape};
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_block_tags::RAILS_TAG;
+use steel_registry::{REGISTRY, TaggedRegistryExt};
+
+use crate::behavior::ItemBehavior;
+use crate::behavior::context::{InteractionResult, UseOnContext};
+use crate::entity::{ENTITIES, next_entity_id};
+
+/// Behavior for every minecart item.
+///
+/// Mirrors vanilla's `MinecartItem`.
+#[item_behavior]
+pub struct MinecartItem {
+    #[json_arg(vanilla_entities, json = "type_description_id")]
+    entity_type: EntityTypeRef,
+}
+
+impl MinecartItem {
+    /// Creates a new minecart item behavior placing `entity_type`.
+    #[must_use]
+    pub const fn new(entity_type: EntityTypeRef) -> Self {
+        Self { entity_type }
+    }
+}
+
+impl ItemBehavior for MinecartItem {
+    fn use_on(&self, context: &mut UseOnContext) -> InteractionResult {
+        let pos = context.hit_result.block_pos;
+        let state = context.world.get_block_state(pos);
+        if !REGISTRY.blocks.is_in_tag(state.get_block(), &RAILS_TAG) {
+            return InteractionResult::Fail;
+        }
+
+        let shape = state
+            .try_get_value(&BlockStateProperties::RAIL_SHAPE)
+            .or_else(|| state.try_get_value(&BlockStateProperties::RAIL_SHAPE_STRAIGHT))
+            .unwrap_or(RailShape::NorthSouth);
+        let offset = if matches!(
+            shape,
+            RailShape::AscendingEast
+                | RailShape::AscendingWest
+                | RailShape::AscendingNorth
+                | RailShape::AscendingSouth
+        ) {
+            0.5
+        } else {
+            0.0
+        };
+
+        let location = DVec3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()) + 0.0625 + offset,
+            f64::from(pos.z()) + 0.5,
+        );
+        // TODO: Chest, furnace, hopper, TNT and command block minecarts have no entity yet
+        let Some(minecart) = ENTITIES.create(
+            self.entity_type,
+            next_entity_id(),
+            location,
+            Arc::downgrade(context.world),
+        ) else {
+            return InteractionResult::Fail;
+        };
+
+        minecart.set_custom_name(context.inv.item().get_custom_name().cloned());
+        context.world.add_entity(minecart);
+        context.inv.item().shrink(1);
+        InteractionResult::Success
+    }
+}
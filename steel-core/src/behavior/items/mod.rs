@@ -5,11 +5,22 @@
 
 mod axe;
 mod block_item;
+mod boat;
+mod bone_meal;
 mod bucket;
+mod bundle;
+mod compass;
+mod debug_stick;
 mod default;
+mod empty_map;
 mod ender_eye;
+mod fire_charge;
+mod firework_rocket;
 mod hoe;
 mod honeycomb;
+mod lead;
+mod minecart;
+mod name_tag;
 mod shovel;
 mod sign_item;
 mod standing_and_wall_block_item;
@@ -18,12 +29,23 @@ mod flint_and_steel;
 
 pub use axe::AxeItem;
 pub use block_item::{BlockItem, DoubleHighBlockItem};
-pub use bucket::BucketItem;
+pub use boat::BoatItem;
+pub use bone_meal::BoneMealItem;
+pub use bucket::{BucketItem, SolidBucketItem, empty_bucket_contents};
+pub use bundle::BundleItem;
+pub use compass::CompassItem;
+pub use debug_stick::DebugStickItem;
 pub use default::DefaultItemBehavior;
+pub use empty_map::EmptyMapItem;
 pub use ender_eye::EnderEyeItem;
+pub use fire_charge::FireChargeItem;
+pub use firework_rocket::FireworkRocketItem;
 pub use flint_and_steel::FlintAndSteelItem;
 pub use hoe::HoeItem;
 pub use honeycomb::HoneycombItem;
+pub use lead::LeadItem;
+pub use minecart::MinecartItem;
+pub use name_tag::NameTagItem;
 pub use shovel::ShovelItem;
 pub use sign_item::{HangingSignItem, SignItem};
 pub use standing_and_wall_block_item::StandingAndWallBlockItem;
@@ -0,0 +1,37 @@
+//! Name tag item behavior.
+
+use crate::behavior::context::{InteractionResult, UseItemContext};
+use crate::behavior::item::ItemBehavior;
+use crate::entity::SharedEntity;
+use steel_macros::item_behavior;
+
+/// Behavior for the name tag item.
+#[item_behavior]
+pub struct NameTagItem;
+
+impl ItemBehavior for NameTagItem {
+    /// Vanilla: `NameTagItem.interactLivingEntity()`.
+    fn interact_living_entity(
+        &self,
+        context: &mut UseItemContext,
+        target: &SharedEntity,
+    ) -> InteractionResult {
+        let Some(name) = context.inv.item().get_custom_name().cloned() else {
+            return InteractionResult::Pass;
+        };
+        // Players can't be renamed, vanilla checks `EntityType.canSerialize()`
+        if !target.entity_type().can_serialize {
+            return InteractionResult::Pass;
+        }
+
+        let mob = target.clone().as_mob();
+        if !target.is_removed() && mob.as_ref().is_none_or(|mob| mob.is_alive()) {
+            target.set_custom_name(Some(name));
+            if let Some(mob) = mob {
+                mob.set_persistence_required(true);
+            }
+            context.inv.item().shrink(1);
+        }
+        InteractionResult::Success
+    }
+}
@@ -6,7 +6,7 @@ use steel_registry::{
         block_state_ext::BlockStateExt,
         properties::{BlockStateProperties, BoolProperty},
     },
-    vanilla_block_tags, vanilla_blocks,
+    level_events, sound_events, vanilla_block_tags, vanilla_blocks,
 };
 use steel_utils::Direction;
 use steel_utils::types::UpdateFlags;
@@ -46,7 +46,13 @@ impl ItemBehavior for ShovelItem {
             {
                 return InteractionResult::Pass;
             }
-            // TODO: Play SoundEvents.SHOVEL_FLATTEN
+            context.world.play_block_sound(
+                sound_events::ITEM_SHOVEL_FLATTEN,
+                context.hit_result.block_pos,
+                1.0,
+                1.0,
+                Some(context.player.id),
+            );
             let infinite_materials = context.player.has_infinite_materials();
             context.inv.item().hurt_and_break(1, infinite_materials);
             context.world.set_block(
@@ -66,14 +72,20 @@ impl ItemBehavior for ShovelItem {
             if !block_state.get_value(&LIT_PROPERTY) {
                 return InteractionResult::Pass;
             }
-            // TODO: level_event(1009, pos, 0) — extinguish particle/sound
+            context.world.level_event(
+                level_events::SOUND_EXTINGUISH_FIRE,
+                context.hit_result.block_pos,
+                0,
+                None,
+            );
             // TODO: CampfireBlock::dowse() — eject cooking items
             context.world.set_block(
                 context.hit_result.block_pos,
                 block_state.set_value(&LIT_PROPERTY, false),
                 UpdateFlags::UPDATE_ALL_IMMEDIATE,
             );
-            // TODO: hurt_and_break(1, ...) — shovels take durability damage
+            let infinite_materials = context.player.has_infinite_materials();
+            context.inv.item().hurt_and_break(1, infinite_materials);
             // TODO: Emit GameEvent::BLOCK_CHANGE
             return InteractionResult::Success;
         }
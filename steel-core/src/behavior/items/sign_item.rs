@@ -14,7 +14,6 @@ use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::{BlockStateProperties, Direction};
 use steel_registry::blocks::shapes::SupportType;
 use steel_registry::{TaggedRegistryExt, vanilla_block_tags};
-use steel_utils::types::UpdateFlags;
 use steel_utils::{BlockPos, BlockStateId};
 
 use super::standing_and_wall_block_item::StandingAndWallBlockItem;
@@ -74,25 +73,11 @@ impl ItemBehavior for SignItem {
             return InteractionResult::Fail;
         };
 
-        if !context
-            .world
-            .set_block(place_pos, new_state, UpdateFlags::UPDATE_ALL_IMMEDIATE)
-        {
-            return InteractionResult::Fail;
+        let result = context.place_block(&place_context, new_state);
+        if result != InteractionResult::Success {
+            return result;
         }
 
-        let block = self.inner.get_block_for_state(new_state);
-        let sound_type = &block.config.sound_type;
-        context.world.play_block_sound(
-            sound_type.place_sound,
-            place_pos,
-            sound_type.volume,
-            sound_type.pitch,
-            Some(context.player.id),
-        );
-
-        context.inv.item().shrink(1);
-
         // Sign-specific: Open the sign editor for the player (front text by default)
         context.player.open_sign_editor(place_pos, true);
 
@@ -216,7 +201,6 @@ impl ItemBehavior for HangingSignItem {
         };
 
         let mut new_state = None;
-        let mut placed_block = None;
         for block in blocks_to_try {
             let behavior = block_behaviors.get_behavior(block);
             let Some(state) = behavior.get_state_for_placement(&place_context) else {
@@ -231,7 +215,6 @@ impl ItemBehavior for HangingSignItem {
             let collision_shape = state.get_collision_shape();
             if context.world.is_unobstructed(collision_shape, place_pos) {
                 new_state = Some(state);
-                placed_block = Some(block);
                 break;
             }
         }
@@ -240,26 +223,11 @@ impl ItemBehavior for HangingSignItem {
             return InteractionResult::Fail;
         };
 
-        if !context
-            .world
-            .set_block(place_pos, state, UpdateFlags::UPDATE_ALL_IMMEDIATE)
-        {
-            return InteractionResult::Fail;
-        }
-
-        if let Some(block) = placed_block {
-            let sound_type = &block.config.sound_type;
-            context.world.play_block_sound(
-                sound_type.place_sound,
-                place_pos,
-                sound_type.volume,
-                sound_type.pitch,
-                Some(context.player.id),
-            );
+        let result = context.place_block(&place_context, state);
+        if result != InteractionResult::Success {
+            return result;
         }
 
-        context.inv.item().shrink(1);
-
         // Sign-specific: Open the sign editor for the player (front text by default)
         context.player.open_sign_editor(place_pos, true);
 
@@ -14,7 +14,6 @@ use steel_registry::REGISTRY;
 use steel_registry::blocks::BlockRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::Direction;
-use steel_utils::types::UpdateFlags;
 
 use crate::behavior::context::{BlockPlaceContext, InteractionResult, UseOnContext};
 use crate::behavior::{BLOCK_BEHAVIORS, ItemBehavior};
@@ -142,31 +141,11 @@ impl ItemBehavior for StandingAndWallBlockItem {
         let Some(place_context) = context.build_place_context() else {
             return InteractionResult::Fail;
         };
-        let place_pos = place_context.relative_pos;
 
         let Some(new_state) = self.get_placement_state(&place_context) else {
             return InteractionResult::Fail;
         };
 
-        if !context
-            .world
-            .set_block(place_pos, new_state, UpdateFlags::UPDATE_ALL_IMMEDIATE)
-        {
-            return InteractionResult::Fail;
-        }
-
-        let block = self.get_block_for_state(new_state);
-        let sound_type = &block.config.sound_type;
-        context.world.play_block_sound(
-            sound_type.place_sound,
-            place_pos,
-            sound_type.volume,
-            sound_type.pitch,
-            Some(context.player.id),
-        );
-
-        context.inv.item().shrink(1);
-
-        InteractionResult::Success
+        context.place_block(&place_context, new_state)
     }
 }
@@ -36,11 +36,21 @@ pub mod items;
 #[path = "generated/blocks.rs"]
 pub mod block_behaviors;
 
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/flammables.rs"]
+pub mod flammables;
+
 #[expect(warnings)]
 #[rustfmt::skip]
 #[path = "generated/items.rs"]
 pub mod item_behaviors;
 
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/map_colors.rs"]
+pub mod map_colors;
+
 #[expect(warnings)]
 #[rustfmt::skip]
 #[path = "generated/strippables.rs"]
@@ -0,0 +1,426 @@
+//! Beacon block entity implementation.
+//!
+//! Beacons need a clear view of the sky and a pyramid of beacon base blocks
+//! below them. Every 80 ticks a powered beacon gives its selected effects to
+//! the players around it, with a range and duration that grow with the
+//! number of pyramid layers.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::NbtCompound;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::{AABBd, is_shape_full_block};
+use steel_registry::mob_effect::{MobEffect, MobEffectRef};
+use steel_registry::vanilla_block_tags::BEACON_BASE_BLOCKS_TAG;
+use steel_registry::vanilla_mob_effects::{
+    HASTE, JUMP_BOOST, REGENERATION, RESISTANCE, SPEED, STRENGTH,
+};
+use steel_registry::{
+    REGISTRY, RegistryExt, TaggedRegistryExt, sound_events, vanilla_block_entity_types,
+    vanilla_blocks,
+};
+use steel_utils::types::GameType;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::block_entity::BlockEntity;
+use crate::chunk::heightmap::HeightmapType;
+use crate::entity::Entity;
+use crate::entity::effect::MobEffectInstance;
+use crate::player::Player;
+use crate::world::World;
+
+/// The effects a beacon can give, by the pyramid level they unlock at.
+///
+/// Vanilla: `BeaconBlockEntity.BEACON_EFFECTS`.
+pub const BEACON_EFFECTS: [&[MobEffectRef]; 4] = [
+    &[SPEED, HASTE],
+    &[RESISTANCE, JUMP_BOOST],
+    &[STRENGTH],
+    &[REGENERATION],
+];
+
+/// The highest number of pyramid layers a beacon counts.
+pub const MAX_LEVELS: i32 = 4;
+
+/// Ticks between two pyramid checks and effect applications.
+const UPDATE_INTERVAL: i64 = 80;
+
+/// How many blocks of the beam are checked each tick.
+const BEAM_BLOCKS_PER_TICK: i32 = 10;
+
+/// Beacon block entity.
+pub struct BeaconBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// Number of complete pyramid layers below the beacon, from 0 to 4.
+    levels: i32,
+    /// Whether the last finished beam check reached the sky.
+    has_beam: bool,
+    /// Whether the beam check in progress hasn't been blocked yet.
+    checking_beam: bool,
+    /// The height the beam check continues from.
+    last_check_y: i32,
+    /// The effect chosen in the first column of the menu.
+    primary_power: Option<MobEffectRef>,
+    /// The effect chosen for a full pyramid, regeneration or a stronger primary.
+    secondary_power: Option<MobEffectRef>,
+}
+
+impl BeaconBlockEntity {
+    /// Creates a new beacon block entity.
+    #[must_use]
+    pub const fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            levels: 0,
+            has_beam: false,
+            checking_beam: false,
+            // Below the beacon, so the first tick starts a new beam check
+            last_check_y: i32::MIN,
+            primary_power: None,
+            secondary_power: None,
+        }
+    }
+
+    /// Returns the number of complete pyramid layers below the beacon.
+    #[must_use]
+    pub const fn levels(&self) -> i32 {
+        self.levels
+    }
+
+    /// Returns the primary effect.
+    #[must_use]
+    pub const fn primary_power(&self) -> Option<MobEffectRef> {
+        self.primary_power
+    }
+
+    /// Returns the secondary effect.
+    #[must_use]
+    pub const fn secondary_power(&self) -> Option<MobEffectRef> {
+        self.secondary_power
+    }
+
+    /// Sets the primary effect, dropping effects a beacon can't give.
+    ///
+    /// Plays the power select sound when the beacon has a beam.
+    pub fn set_primary_power(&mut self, effect: Option<MobEffectRef>) {
+        if self.has_beam
+            && let Some(world) = self.level.upgrade()
+        {
+            world.play_block_sound(
+                sound_events::BLOCK_BEACON_POWER_SELECT,
+                self.pos,
+                1.0,
+                1.0,
+                None,
+            );
+        }
+        self.primary_power = effect.filter(|effect| Self::is_valid_effect(effect));
+        self.set_changed();
+    }
+
+    /// Sets the secondary effect, dropping effects a beacon can't give.
+    pub fn set_secondary_power(&mut self, effect: Option<MobEffectRef>) {
+        self.secondary_power = effect.filter(|effect| Self::is_valid_effect(effect));
+        self.set_changed();
+    }
+
+    /// Returns true if a beacon can give `effect` at any level.
+    ///
+    /// Vanilla: `BeaconBlockEntity.filterEffect()`.
+    #[must_use]
+    pub fn is_valid_effect(effect: &MobEffect) -> bool {
+        BEACON_EFFECTS
+            .iter()
+            .flat_map(|effects| effects.iter())
+            .any(|valid| valid.key == effect.key)
+    }
+
+    /// Continues the check for a clear path from the beacon to the sky and
+    /// returns the height the check ends at.
+    ///
+    /// Vanilla tracks colored beam sections here, but their colors only matter
+    /// to the client. The server only needs to know whether an opaque block
+    /// stops the beam, which stained glass and the beacon itself never do.
+    fn update_beam(&mut self, world: &World) -> i32 {
+        let (x, y, z) = (self.pos.x(), self.pos.y(), self.pos.z());
+        let mut check_y = if self.last_check_y < y {
+            self.checking_beam = true;
+            self.last_check_y = y - 1;
+            y
+        } else {
+            self.last_check_y + 1
+        };
+
+        let last_set_block = world.get_height(HeightmapType::WorldSurface, self.pos);
+        for _ in 0..BEAM_BLOCKS_PER_TICK {
+            if check_y > last_set_block {
+                break;
+            }
+            let state = world.get_block_state(BlockPos::new(x, check_y, z));
+            if Self::blocks_beam(state) {
+                self.checking_beam = false;
+                self.last_check_y = last_set_block;
+                break;
+            }
+            check_y += 1;
+            self.last_check_y += 1;
+        }
+        last_set_block
+    }
+
+    /// Returns true if the block stops a beacon beam.
+    ///
+    /// Vanilla checks `getLightBlock() >= 15`, which is true for blocks that
+    /// occlude and fill the whole block space, and for tinted glass. Bedrock
+    /// never blocks the beam.
+    fn blocks_beam(state: BlockStateId) -> bool {
+        let block = state.get_block();
+        if block == vanilla_blocks::TINTED_GLASS {
+            return true;
+        }
+        block.config.can_occlude
+            && is_shape_full_block(state.get_outline_shape())
+            && block != vanilla_blocks::BEDROCK
+    }
+
+    /// Counts the complete pyramid layers of beacon base blocks below the
+    /// beacon.
+    ///
+    /// Vanilla: `BeaconBlockEntity.updateBase()`.
+    fn update_base(world: &World, pos: BlockPos) -> i32 {
+        let (x, y, z) = (pos.x(), pos.y(), pos.z());
+        let mut levels = 0;
+        for step in 1..=MAX_LEVELS {
+            let layer_y = y - step;
+            if layer_y < world.get_min_y() {
+                break;
+            }
+            let complete = (x - step..=x + step).all(|layer_x| {
+                (z - step..=z + step).all(|layer_z| {
+                    let state = world.get_block_state(BlockPos::new(layer_x, layer_y, layer_z));
+                    REGISTRY
+                        .blocks
+                        .is_in_tag(state.get_block(), &BEACON_BASE_BLOCKS_TAG)
+                })
+            });
+            if !complete {
+                break;
+            }
+            levels = step;
+        }
+        levels
+    }
+
+    /// Gives the selected effects to every player in range.
+    ///
+    /// The range reaches up to the top of the world.
+    ///
+    /// Vanilla: `BeaconBlockEntity.applyEffects()`.
+    fn apply_effects(&self, world: &World) {
+        let Some(primary) = self.primary_power else {
+            return;
+        };
+
+        let range = f64::from(self.levels * 10 + 10);
+        let amplifier = u8::from(
+            self.levels >= MAX_LEVELS
+                && self
+                    .secondary_power
+                    .is_some_and(|secondary| secondary.key == primary.key),
+        );
+        let duration = (9 + self.levels * 2) * 20;
+        let area = AABBd::new(
+            f64::from(self.pos.x()),
+            f64::from(self.pos.y()),
+            f64::from(self.pos.z()),
+            f64::from(self.pos.x() + 1),
+            f64::from(self.pos.y() + 1 + world.dimension.height),
+            f64::from(self.pos.z() + 1),
+        )
+        .inflate(range);
+
+        let mut players: Vec<Arc<Player>> = Vec::new();
+        world.players.iter_players(|_, player| {
+            if player.game_mode.load() != GameType::Spectator
+                && player.bounding_box().intersects(&area)
+            {
+                players.push(player.clone());
+            }
+            true
+        });
+
+        let secondary = self
+            .secondary_power
+            .filter(|secondary| self.levels >= MAX_LEVELS && secondary.key != primary.key);
+        for player in &players {
+            player.add_effect(Self::effect_instance(primary, amplifier, duration));
+            if let Some(secondary) = secondary {
+                player.add_effect(Self::effect_instance(secondary, 0, duration));
+            }
+        }
+    }
+
+    /// Creates an ambient effect like the ones beacons give.
+    fn effect_instance(effect: MobEffectRef, amplifier: u8, duration: i32) -> MobEffectInstance {
+        let mut instance = MobEffectInstance::new(effect.key.clone(), amplifier, duration);
+        instance.ambient = true;
+        instance
+    }
+
+    /// Saves an effect by its registry key.
+    fn store_effect(nbt: &mut NbtCompound, key: &str, effect: Option<MobEffectRef>) {
+        if let Some(effect) = effect {
+            nbt.insert(key, effect.key.to_string());
+        }
+    }
+
+    /// Loads an effect saved by [`Self::store_effect`], ignoring effects a
+    /// beacon can't give.
+    fn load_effect(nbt: &NbtCompoundView<'_, '_>, key: &str) -> Option<MobEffectRef> {
+        let key = nbt.string(key)?.to_str().parse().ok()?;
+        REGISTRY
+            .mob_effects
+            .by_key(&key)
+            .filter(|effect| Self::is_valid_effect(effect))
+    }
+}
+
+impl BlockEntity for BeaconBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::BEACON
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    /// Vanilla plays the deactivate sound whenever a beacon is removed, even
+    /// when its chunk unloads.
+    fn set_removed(&mut self) {
+        if let Some(world) = self.level.upgrade() {
+            world.play_block_sound(
+                sound_events::BLOCK_BEACON_DEACTIVATE,
+                self.pos,
+                1.0,
+                1.0,
+                None,
+            );
+        }
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    // TODO: Load and save CustomName and the container lock
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+        self.primary_power = Self::load_effect(&nbt_view, "primary_effect");
+        self.secondary_power = Self::load_effect(&nbt_view, "secondary_effect");
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        Self::store_effect(nbt, "primary_effect", self.primary_power);
+        Self::store_effect(nbt, "secondary_effect", self.secondary_power);
+        nbt.insert("Levels", self.levels);
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    /// Checks the beam, and every 80 ticks the pyramid, then gives the
+    /// effects to nearby players.
+    ///
+    /// Vanilla: `BeaconBlockEntity.tick()`.
+    fn tick(&mut self, world: &Arc<World>) {
+        let previous_levels = self.levels;
+        let last_set_block = self.update_beam(world);
+
+        if world.level_data.read().game_time() % UPDATE_INTERVAL == 0 {
+            if self.has_beam {
+                self.levels = Self::update_base(world, self.pos);
+            }
+            if self.levels > 0 && self.has_beam {
+                self.apply_effects(world);
+                world.play_block_sound(
+                    sound_events::BLOCK_BEACON_AMBIENT,
+                    self.pos,
+                    1.0,
+                    1.0,
+                    None,
+                );
+            }
+        }
+
+        if self.last_check_y >= last_set_block {
+            self.last_check_y = world.get_min_y() - 1;
+            self.has_beam = self.checking_beam;
+
+            let was_active = previous_levels > 0;
+            let is_active = self.levels > 0;
+            if !was_active && is_active {
+                world.play_block_sound(
+                    sound_events::BLOCK_BEACON_ACTIVATE,
+                    self.pos,
+                    1.0,
+                    1.0,
+                    None,
+                );
+                // TODO: Trigger the CONSTRUCT_BEACON advancement criterion
+            } else if was_active && !is_active {
+                world.play_block_sound(
+                    sound_events::BLOCK_BEACON_DEACTIVATE,
+                    self.pos,
+                    1.0,
+                    1.0,
+                    None,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Dispenser and dropper block entity implementation.
+//!
+//! Both blocks store 9 slots (3x3 grid) and pick a random non-empty slot each
+//! time they are activated.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::block_entity::BlockEntity;
+use crate::inventory::container::Container;
+use crate::world::World;
+
+/// Number of slots in a dispenser or dropper.
+pub const DISPENSER_SLOTS: usize = 9;
+
+/// Dispenser block entity, also used by droppers.
+///
+/// Vanilla equivalent: `DispenserBlockEntity` and `DropperBlockEntity`.
+pub struct DispenserBlockEntity {
+    /// The block entity type (dispenser or dropper).
+    block_entity_type: BlockEntityTypeRef,
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The 9 item slots.
+    items: Vec<ItemStack>,
+}
+
+impl DispenserBlockEntity {
+    /// Creates a new dispenser block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self::with_type(level, vanilla_block_entity_types::DISPENSER, pos, state)
+    }
+
+    /// Creates a new dropper block entity.
+    #[must_use]
+    pub fn new_dropper(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self::with_type(level, vanilla_block_entity_types::DROPPER, pos, state)
+    }
+
+    /// Creates a dispenser block entity with a specific type.
+    #[must_use]
+    pub fn with_type(
+        level: Weak<World>,
+        block_entity_type: BlockEntityTypeRef,
+        pos: BlockPos,
+        state: BlockStateId,
+    ) -> Self {
+        Self {
+            block_entity_type,
+            level,
+            pos,
+            state,
+            removed: false,
+            items: vec![ItemStack::empty(); DISPENSER_SLOTS],
+        }
+    }
+
+    /// Picks a uniformly random non-empty slot, or `None` if all slots are empty.
+    ///
+    /// Vanilla equivalent: `DispenserBlockEntity.getRandomSlot()`.
+    #[must_use]
+    pub fn get_random_slot(&self) -> Option<usize> {
+        let mut slot = None;
+        let mut odds = 1u32;
+        for (i, item) in self.items.iter().enumerate() {
+            if item.is_empty() {
+                continue;
+            }
+            if rand::random_range(0..odds) == 0 {
+                slot = Some(i);
+            }
+            odds += 1;
+        }
+        slot
+    }
+
+    /// Puts as much of `stack` into the slots as fits and returns the rest.
+    ///
+    /// Used for items left over by dispensing, like the empty bucket of a
+    /// dispensed water bucket.
+    ///
+    /// Vanilla equivalent: `DispenserBlockEntity.insertItem()`.
+    pub fn insert_item(&mut self, mut stack: ItemStack) -> ItemStack {
+        let max_stack_size = self.get_max_stack_size_for_item(&stack);
+        for slot in 0..DISPENSER_SLOTS {
+            let target = &mut self.items[slot];
+            if !target.is_empty() && !ItemStack::is_same_item_same_components(&stack, target) {
+                continue;
+            }
+            let count = stack.count().min(max_stack_size - target.count());
+            if count > 0 {
+                if target.is_empty() {
+                    *target = stack.split(count);
+                } else {
+                    stack.shrink(count);
+                    target.grow(count);
+                }
+                self.set_changed();
+            }
+            if stack.is_empty() {
+                break;
+            }
+        }
+        stack
+    }
+}
+
+impl BlockEntity for DispenserBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        self.block_entity_type
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn pre_remove_side_effects(&mut self, pos: BlockPos, _state: BlockStateId) {
+        if let Some(world) = self.level.upgrade() {
+            for item in self.items.drain(..) {
+                world.drop_item_stack(pos, item);
+            }
+        }
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(items_list) = nbt_view.list("Items")
+            && let Some(compounds) = items_list.compounds()
+        {
+            for compound in compounds {
+                if let Some(slot) = compound.byte("Slot") {
+                    let slot = slot as usize;
+                    if slot < DISPENSER_SLOTS
+                        && let Some(item) = ItemStack::from_borrowed_compound(&compound)
+                    {
+                        self.items[slot] = item;
+                    }
+                }
+            }
+        }
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        let mut items: Vec<NbtCompound> = Vec::new();
+        for (slot, item) in self.items.iter().enumerate() {
+            if !item.is_empty()
+                && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+            {
+                item_nbt.insert("Slot", slot as i8);
+                items.push(item_nbt);
+            }
+        }
+        nbt.insert("Items", NbtList::Compound(items));
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        None
+    }
+
+    fn as_container(&self) -> Option<&(dyn Container + 'static)> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut (dyn Container + 'static)> {
+        Some(self)
+    }
+}
+
+impl Container for DispenserBlockEntity {
+    fn get_container_size(&self) -> usize {
+        DISPENSER_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < DISPENSER_SLOTS {
+            self.items[slot] = stack;
+            self.set_changed();
+        }
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        BlockEntity::set_changed(self);
+    }
+}
@@ -0,0 +1,162 @@
+//! Ender chest block entity implementation.
+//!
+//! Ender chests hold no items themselves; every player sees their own
+//! [`PlayerEnderChestContainer`](crate::inventory::ender_chest_container::PlayerEnderChestContainer).
+//! The block entity only tracks how many players are looking inside so the
+//! lid animates and the open/close sounds play.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::borrow::BaseNbtCompound as BorrowedNbtCompound;
+use simdnbt::owned::NbtCompound;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::{sound_events, vanilla_block_entity_types};
+use steel_utils::types::GameType;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::block_entity::BlockEntity;
+use crate::player::Player;
+use crate::world::World;
+
+/// Block event id that carries the number of players looking inside.
+const EVENT_SET_OPEN_COUNT: u8 = 1;
+
+/// Ender chest block entity.
+pub struct EnderChestBlockEntity {
+    /// Weak reference to the world for block events and sounds.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// Number of players with this chest open.
+    open_count: i32,
+}
+
+impl EnderChestBlockEntity {
+    /// Creates a new ender chest block entity.
+    #[must_use]
+    pub const fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            open_count: 0,
+        }
+    }
+
+    /// Returns the number of players with this chest open.
+    #[must_use]
+    pub const fn open_count(&self) -> i32 {
+        self.open_count
+    }
+
+    /// Called when a player opens their ender inventory through this chest.
+    ///
+    /// Vanilla equivalent: `EnderChestBlockEntity.startOpen()`.
+    pub fn start_open(&mut self, player: &Player) {
+        if self.removed || player.game_mode.load() == GameType::Spectator {
+            return;
+        }
+        let Some(world) = self.level.upgrade() else {
+            return;
+        };
+        self.open_count = self.open_count.max(0) + 1;
+        let sound = (self.open_count == 1).then_some(sound_events::BLOCK_ENDER_CHEST_OPEN);
+        self.update_open_count(&world, sound);
+    }
+
+    /// Called when a player closes their ender inventory opened through this chest.
+    ///
+    /// Vanilla equivalent: `EnderChestBlockEntity.stopOpen()`.
+    pub fn stop_open(&mut self, player: &Player) {
+        if self.removed || player.game_mode.load() == GameType::Spectator {
+            return;
+        }
+        let Some(world) = self.level.upgrade() else {
+            return;
+        };
+        self.open_count -= 1;
+        let sound = (self.open_count <= 0).then_some(sound_events::BLOCK_ENDER_CHEST_CLOSE);
+        self.update_open_count(&world, sound);
+    }
+
+    /// Tells the clients how many players have the chest open and plays the
+    /// open or close sound when that count leaves or reaches zero.
+    fn update_open_count(&self, world: &World, sound: Option<i32>) {
+        world.block_event(
+            self.pos,
+            self.state.get_block(),
+            EVENT_SET_OPEN_COUNT,
+            self.open_count.clamp(0, i32::from(u8::MAX)) as u8,
+        );
+        // TODO: Emit CONTAINER_OPEN/CONTAINER_CLOSE game events once sculk sensors exist
+        if let Some(sound) = sound {
+            let pitch = rand::random::<f32>() * 0.1 + 0.9;
+            world.play_block_sound(sound, self.pos, 0.5, pitch, None);
+        }
+    }
+}
+
+impl BlockEntity for EnderChestBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::ENDER_CHEST
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn trigger_event(&mut self, action_id: u8, action_param: u8) -> bool {
+        if action_id != EVENT_SET_OPEN_COUNT {
+            return false;
+        }
+        self.open_count = i32::from(action_param);
+        true
+    }
+
+    fn load_additional(&mut self, _nbt: &BorrowedNbtCompound<'_>) {
+        // Ender chests have no saved data
+    }
+
+    fn save_additional(&self, _nbt: &mut NbtCompound) {
+        // Ender chests have no saved data
+    }
+}
@@ -0,0 +1,446 @@
+//! Hopper block entity implementation.
+//!
+//! Hoppers hold 5 slots. Every 8 ticks a hopper pushes one item into the
+//! container it faces and pulls one item from the container above it, or picks
+//! up item entities resting on top of it when there is no container.
+
+use std::any::Any;
+use std::mem;
+use std::sync::{Arc, Weak};
+
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::blocks::shapes::{AABBd, is_shape_full_block};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_block_tags::DOES_NOT_BLOCK_HOPPERS_TAG;
+use steel_registry::{REGISTRY, vanilla_block_entity_types};
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::block_entity::{BlockEntity, SharedBlockEntity};
+use crate::entity::entities::ItemEntity;
+use crate::entity::{Entity, RemovalReason};
+use crate::inventory::container::Container;
+use crate::world::World;
+
+/// Number of slots in a hopper.
+pub const HOPPER_SLOTS: usize = 5;
+
+/// Ticks a hopper waits after moving an item.
+const MOVE_ITEM_SPEED: i32 = 8;
+
+/// Hopper block entity.
+///
+/// Vanilla equivalent: `HopperBlockEntity`.
+pub struct HopperBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The 5 item slots.
+    items: Vec<ItemStack>,
+    /// Ticks until the hopper moves items again.
+    cooldown_time: i32,
+    /// Game time of the last tick.
+    ticked_game_time: i64,
+}
+
+impl HopperBlockEntity {
+    /// Creates a new hopper block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            items: vec![ItemStack::empty(); HOPPER_SLOTS],
+            cooldown_time: -1,
+            ticked_game_time: 0,
+        }
+    }
+
+    const fn is_on_cooldown(&self) -> bool {
+        self.cooldown_time > 0
+    }
+
+    /// A cooldown longer than a normal transfer was set by something else and is kept.
+    const fn is_on_custom_cooldown(&self) -> bool {
+        self.cooldown_time > MOVE_ITEM_SPEED
+    }
+
+    fn inventory_full(&self) -> bool {
+        self.items
+            .iter()
+            .all(|item| !item.is_empty() && item.count() == item.max_stack_size())
+    }
+
+    /// Pushes an item out and pulls one in, then starts the cooldown if anything moved.
+    ///
+    /// Vanilla equivalent: `HopperBlockEntity.tryMoveItems()`.
+    fn try_move_items(&mut self, world: &Arc<World>) -> bool {
+        if self.is_on_cooldown() || !self.state.get_value(&BlockStateProperties::ENABLED) {
+            return false;
+        }
+
+        let mut changed = false;
+        if !self.is_empty() {
+            changed = self.eject_items(world);
+        }
+        if !self.inventory_full() {
+            changed |= self.suck_in_items(world);
+        }
+
+        if changed {
+            self.cooldown_time = MOVE_ITEM_SPEED;
+            BlockEntity::set_changed(self);
+        }
+        changed
+    }
+
+    /// Moves one item into the container the hopper faces.
+    ///
+    /// Vanilla equivalent: `HopperBlockEntity.ejectItems()`.
+    fn eject_items(&mut self, world: &Arc<World>) -> bool {
+        let facing: Direction = self.state.get_value(&BlockStateProperties::FACING_HOPPER);
+        let Some(target) = get_container_at(world, self.pos.relative(facing)) else {
+            return false;
+        };
+        let mut target = target.lock();
+        if target.as_container().is_none_or(is_full_container) {
+            return false;
+        }
+
+        for slot in 0..HOPPER_SLOTS {
+            if self.items[slot].is_empty() {
+                continue;
+            }
+            let original = self.items[slot].clone();
+            let item = self.items[slot].split(1);
+            if Self::add_item(Some(self.ticked_game_time), &mut *target, item).is_empty() {
+                return true;
+            }
+            self.items[slot] = original;
+        }
+        false
+    }
+
+    /// Pulls one item from the container above, or picks up item entities on top.
+    ///
+    /// Vanilla equivalent: `HopperBlockEntity.suckInItems()`.
+    fn suck_in_items(&mut self, world: &Arc<World>) -> bool {
+        let above = self.pos.above();
+        if let Some(source) = get_container_at(world, above) {
+            let mut source = source.lock();
+            let from_ticked_game_time = source
+                .as_any()
+                .downcast_ref::<Self>()
+                .map(|hopper| hopper.ticked_game_time);
+            let Some(container) = source.as_container_mut() else {
+                return false;
+            };
+            // TODO: Respect the faces of sided containers (vanilla's `WorldlyContainer`)
+            return (0..container.get_container_size()).any(|slot| {
+                self.try_take_in_item_from_slot(container, slot, from_ticked_game_time)
+            });
+        }
+
+        let above_state = world.get_block_state(above);
+        let blocked_above = is_shape_full_block(above_state.get_collision_shape())
+            && !REGISTRY
+                .blocks
+                .is_in_tag(above_state.get_block(), &DOES_NOT_BLOCK_HOPPERS_TAG);
+        if blocked_above {
+            return false;
+        }
+
+        // The hopper bowl and the block above it (vanilla's `Hopper.SUCK_AABB`)
+        let (x, y, z) = (
+            f64::from(self.pos.x()),
+            f64::from(self.pos.y()),
+            f64::from(self.pos.z()),
+        );
+        let suck_area = AABBd::new(x, y + 0.6875, z, x + 1.0, y + 2.0, z + 1.0);
+        world
+            .get_entities_in_aabb(&suck_area)
+            .into_iter()
+            .filter(|entity| !entity.is_removed())
+            .filter_map(|entity| entity.as_item_entity())
+            .any(|item_entity| self.add_item_entity(&item_entity))
+    }
+
+    /// Moves one item from `slot` of `source` into this hopper.
+    ///
+    /// Vanilla equivalent: `HopperBlockEntity.tryTakeInItemFromSlot()`.
+    fn try_take_in_item_from_slot(
+        &mut self,
+        source: &mut dyn Container,
+        slot: usize,
+        from_ticked_game_time: Option<i64>,
+    ) -> bool {
+        let item = source.get_item(slot);
+        if item.is_empty() || !source.can_take_item(slot, item) {
+            return false;
+        }
+
+        let original = item.clone();
+        let item = source.remove_item(slot, 1);
+        if Self::add_item(from_ticked_game_time, self, item).is_empty() {
+            source.set_changed();
+            return true;
+        }
+        source.set_item(slot, original);
+        false
+    }
+
+    /// Picks up as much of an item entity as fits. Returns `true` only if all of it fit.
+    ///
+    /// Vanilla equivalent: `HopperBlockEntity.addItem(Container, ItemEntity)`.
+    fn add_item_entity(&mut self, item_entity: &ItemEntity) -> bool {
+        let remaining = Self::add_item(None, self, item_entity.get_item());
+        if remaining.is_empty() {
+            item_entity.set_item(ItemStack::empty());
+            item_entity.set_removed(RemovalReason::Discarded);
+            return true;
+        }
+        item_entity.set_item(remaining);
+        false
+    }
+
+    /// Inserts `stack` into the container block entity `to`, filling existing
+    /// stacks and empty slots in slot order. Returns what didn't fit.
+    ///
+    /// `from_ticked_game_time` is the last tick of the hopper the items come
+    /// from, if any. A hopper that receives items while empty waits a full
+    /// cooldown before passing them on, minus one tick if it ticks after the
+    /// source hopper in the same game tick, so that hopper chains move items
+    /// at a steady rate.
+    ///
+    /// Vanilla equivalent: `HopperBlockEntity.addItem(Container, Container, ItemStack, Direction)`.
+    pub fn add_item(
+        from_ticked_game_time: Option<i64>,
+        to: &mut dyn BlockEntity,
+        mut stack: ItemStack,
+    ) -> ItemStack {
+        // TODO: Respect the faces of sided containers (vanilla's `WorldlyContainer`)
+        let size = to.as_container().map_or(0, Container::get_container_size);
+        for slot in 0..size {
+            if stack.is_empty() {
+                break;
+            }
+            stack = Self::try_move_in_item(from_ticked_game_time, to, stack, slot);
+        }
+        stack
+    }
+
+    /// Vanilla equivalent: `HopperBlockEntity.tryMoveInItem()`.
+    fn try_move_in_item(
+        from_ticked_game_time: Option<i64>,
+        to: &mut dyn BlockEntity,
+        mut stack: ItemStack,
+        slot: usize,
+    ) -> ItemStack {
+        let Some(container) = to.as_container_mut() else {
+            return stack;
+        };
+        if !container.can_place_item(slot, &stack) {
+            return stack;
+        }
+
+        let was_empty = container.is_empty();
+        let current = container.get_item_mut(slot);
+        let success = if current.is_empty() {
+            container.set_item(slot, mem::take(&mut stack));
+            true
+        } else if can_merge_items(current, &stack) {
+            let space = stack.max_stack_size() - current.count();
+            let count = stack.count().min(space);
+            stack.shrink(count);
+            current.grow(count);
+            count > 0
+        } else {
+            false
+        };
+        if !success {
+            return stack;
+        }
+
+        container.set_changed();
+        if was_empty
+            && let Some(hopper) = to.as_any_mut().downcast_mut::<Self>()
+            && !hopper.is_on_custom_cooldown()
+        {
+            let skew = from_ticked_game_time.is_some_and(|from| hopper.ticked_game_time >= from);
+            hopper.cooldown_time = MOVE_ITEM_SPEED - i32::from(skew);
+        }
+        stack
+    }
+}
+
+/// Returns the block entity with an inventory at `pos`, if there is one.
+///
+/// Vanilla equivalent: `HopperBlockEntity.getContainerAt()`.
+#[must_use]
+pub fn get_container_at(world: &Arc<World>, pos: BlockPos) -> Option<SharedBlockEntity> {
+    // TODO: Containers without a block entity (composters) and container entities (minecarts)
+    world
+        .get_block_entity(pos)
+        .filter(|block_entity| block_entity.lock().as_container().is_some())
+}
+
+fn is_full_container<C: Container + ?Sized>(container: &C) -> bool {
+    (0..container.get_container_size()).all(|slot| {
+        let item = container.get_item(slot);
+        item.count() >= item.max_stack_size()
+    })
+}
+
+fn can_merge_items(existing: &ItemStack, stack: &ItemStack) -> bool {
+    existing.count() <= existing.max_stack_size()
+        && ItemStack::is_same_item_same_components(existing, stack)
+}
+
+impl BlockEntity for HopperBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::HOPPER
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn pre_remove_side_effects(&mut self, pos: BlockPos, _state: BlockStateId) {
+        if let Some(world) = self.level.upgrade() {
+            for item in self.items.drain(..) {
+                world.drop_item_stack(pos, item);
+            }
+        }
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(items_list) = nbt_view.list("Items")
+            && let Some(compounds) = items_list.compounds()
+        {
+            for compound in compounds {
+                if let Some(slot) = compound.byte("Slot") {
+                    let slot = slot as usize;
+                    if slot < HOPPER_SLOTS
+                        && let Some(item) = ItemStack::from_borrowed_compound(&compound)
+                    {
+                        self.items[slot] = item;
+                    }
+                }
+            }
+        }
+        self.cooldown_time = nbt_view.int("TransferCooldown").unwrap_or(-1);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        let mut items: Vec<NbtCompound> = Vec::new();
+        for (slot, item) in self.items.iter().enumerate() {
+            if !item.is_empty()
+                && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+            {
+                item_nbt.insert("Slot", slot as i8);
+                items.push(item_nbt);
+            }
+        }
+        nbt.insert("Items", NbtList::Compound(items));
+        nbt.insert("TransferCooldown", self.cooldown_time);
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        None
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, world: &Arc<World>) {
+        self.cooldown_time -= 1;
+        self.ticked_game_time = world.level_data.read().game_time();
+        if !self.is_on_cooldown() {
+            self.cooldown_time = 0;
+            self.try_move_items(world);
+        }
+    }
+
+    fn as_container(&self) -> Option<&(dyn Container + 'static)> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut (dyn Container + 'static)> {
+        Some(self)
+    }
+}
+
+impl Container for HopperBlockEntity {
+    fn get_container_size(&self) -> usize {
+        HOPPER_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < HOPPER_SLOTS {
+            self.items[slot] = stack;
+            self.set_changed();
+        }
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        BlockEntity::set_changed(self);
+    }
+}
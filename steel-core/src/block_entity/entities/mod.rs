@@ -1,7 +1,23 @@
 //! Block entity implementations.
 
 mod barrel;
+mod beacon;
+mod dispenser;
+mod ender_chest;
+mod hopper;
+mod piston;
+mod shulker_box;
 mod sign;
+mod structure_block;
 
 pub use barrel::{BARREL_SLOTS, BarrelBlockEntity};
-pub use sign::{SIGN_LINES, SignBlockEntity, SignText};
+pub use beacon::{BEACON_EFFECTS, BeaconBlockEntity, MAX_LEVELS};
+pub use dispenser::{DISPENSER_SLOTS, DispenserBlockEntity};
+pub use ender_chest::EnderChestBlockEntity;
+pub use hopper::{HOPPER_SLOTS, HopperBlockEntity, get_container_at};
+pub use piston::PistonMovingBlockEntity;
+pub use shulker_box::{AnimationStatus, SHULKER_BOX_SLOTS, ShulkerBoxBlockEntity};
+pub use sign::{SIGN_LINES, SignBlockEntity, SignText, dye_color_from_str};
+pub use structure_block::{
+    MAX_OFFSET_PER_AXIS, MAX_SIZE_PER_AXIS, StructureBlockEntity, StructureMode,
+};
@@ -0,0 +1,364 @@
+//! Moving piston block entity implementation.
+//!
+//! While a piston extends or retracts, every moved block (and the piston arm
+//! itself) is replaced by a `moving_piston` block whose block entity remembers
+//! the moved block and animates it over 2 ticks before placing it again.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::NbtCompound;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
+use steel_registry::{REGISTRY, vanilla_block_entity_types, vanilla_blocks};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use crate::block_entity::BlockEntity;
+use crate::world::World;
+
+/// How far a moving block travels each tick, as a fraction of a block.
+const PROGRESS_PER_TICK: f32 = 0.5;
+
+/// Block entity of a `moving_piston` block.
+///
+/// Vanilla equivalent: `PistonMovingBlockEntity`.
+pub struct PistonMovingBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The block being moved.
+    moved_state: BlockStateId,
+    /// The facing of the piston that moves the block.
+    direction: Direction,
+    /// Whether the piston is extending (`true`) or retracting (`false`).
+    extending: bool,
+    /// Whether the moved block is the piston base itself (retracting from its arm).
+    is_source_piston: bool,
+    /// Movement progress from 0 to 1.
+    progress: f32,
+    /// Movement progress of the previous tick.
+    progress_o: f32,
+    /// Game time of the last tick.
+    last_ticked: i64,
+}
+
+impl PistonMovingBlockEntity {
+    /// Creates an empty moving piston block entity, used when loading from disk.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self::new_moving(
+            level,
+            pos,
+            state,
+            REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR),
+            Direction::Down,
+            false,
+            false,
+        )
+    }
+
+    /// Creates a moving piston block entity that moves `moved_state`.
+    ///
+    /// Vanilla equivalent: `MovingPistonBlock.newMovingBlockEntity()`.
+    #[must_use]
+    pub const fn new_moving(
+        level: Weak<World>,
+        pos: BlockPos,
+        state: BlockStateId,
+        moved_state: BlockStateId,
+        direction: Direction,
+        extending: bool,
+        is_source_piston: bool,
+    ) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            moved_state,
+            direction,
+            extending,
+            is_source_piston,
+            progress: 0.0,
+            progress_o: 0.0,
+            last_ticked: 0,
+        }
+    }
+
+    /// Returns the block being moved.
+    #[must_use]
+    pub const fn moved_state(&self) -> BlockStateId {
+        self.moved_state
+    }
+
+    /// Returns the facing of the piston that moves the block.
+    #[must_use]
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns whether the piston is extending.
+    #[must_use]
+    pub const fn is_extending(&self) -> bool {
+        self.extending
+    }
+
+    /// Returns whether the moved block is the piston base itself.
+    #[must_use]
+    pub const fn is_source_piston(&self) -> bool {
+        self.is_source_piston
+    }
+
+    /// Returns the direction the block is travelling in.
+    #[must_use]
+    pub const fn moving_direction(&self) -> Direction {
+        if self.extending {
+            self.direction
+        } else {
+            self.direction.opposite()
+        }
+    }
+
+    /// Returns the movement progress, interpolated between the last two ticks.
+    ///
+    /// Vanilla equivalent: `PistonMovingBlockEntity.getProgress()`.
+    #[must_use]
+    pub fn progress(&self, partial_tick: f32) -> f32 {
+        let partial_tick = partial_tick.min(1.0);
+        self.progress_o + (self.progress - self.progress_o) * partial_tick
+    }
+
+    /// Returns the game time this block entity was last ticked at.
+    #[must_use]
+    pub const fn last_ticked(&self) -> i64 {
+        self.last_ticked
+    }
+
+    /// Places the moved block of the finished moving piston at `pos`.
+    ///
+    /// Does nothing if the block there is still moving.
+    ///
+    /// Vanilla equivalent: the `progressO >= 1` branch of `PistonMovingBlockEntity.tick()`.
+    pub fn finish_movement(world: &Arc<World>, pos: BlockPos) {
+        let Some((moved_state, _)) =
+            remove_moving_piston(world, pos, |piston| piston.progress >= 1.0)
+        else {
+            return;
+        };
+        if world.get_block_state(pos).get_block() != vanilla_blocks::MOVING_PISTON {
+            return;
+        }
+
+        let state = world.update_from_neighbor_shapes(moved_state, pos);
+        if state.is_air() {
+            // The moved block can't survive here: place it so it drops like a broken block
+            world.set_block(
+                pos,
+                moved_state,
+                UpdateFlags::UPDATE_MOVE_BY_PISTON
+                    | UpdateFlags::UPDATE_KNOWN_SHAPE
+                    | UpdateFlags::UPDATE_INVISIBLE,
+            );
+            world.destroy_block(pos, true);
+        } else {
+            let state = if state.try_get_value(&BlockStateProperties::WATERLOGGED) == Some(true) {
+                state.set_value(&BlockStateProperties::WATERLOGGED, false)
+            } else {
+                state
+            };
+            world.set_block(
+                pos,
+                state,
+                UpdateFlags::UPDATE_MOVE_BY_PISTON | UpdateFlags::UPDATE_ALL,
+            );
+            world.neighbor_changed(pos, state.get_block(), false);
+        }
+    }
+
+    /// Immediately finishes an unfinished moving piston at `pos`.
+    ///
+    /// Used when a piston changes direction before the blocks in front of it
+    /// stopped moving. The piston arm itself is removed, any other block is placed.
+    ///
+    /// Vanilla equivalent: `PistonMovingBlockEntity.finalTick()`.
+    pub fn final_tick(world: &Arc<World>, pos: BlockPos) {
+        let Some((moved_state, is_source_piston)) =
+            remove_moving_piston(world, pos, |piston| piston.progress_o < 1.0)
+        else {
+            return;
+        };
+        if world.get_block_state(pos).get_block() != vanilla_blocks::MOVING_PISTON {
+            return;
+        }
+
+        let state = if is_source_piston {
+            REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR)
+        } else {
+            world.update_from_neighbor_shapes(moved_state, pos)
+        };
+        world.set_block(pos, state, UpdateFlags::UPDATE_ALL);
+        world.neighbor_changed(pos, state.get_block(), false);
+    }
+}
+
+impl BlockEntity for PistonMovingBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::PISTON
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+
+        // Vanilla falls back to air for a missing or unknown block state
+        self.moved_state = nbt
+            .compound("blockState")
+            .and_then(|block_state| read_block_state(&block_state))
+            .unwrap_or_else(|| REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR));
+        self.direction = Direction::from_3d_data_value(nbt.int("facing").unwrap_or(0));
+        self.progress = nbt.float("progress").unwrap_or(0.0);
+        self.progress_o = self.progress;
+        self.extending = nbt.byte("extending").is_some_and(|b| b != 0);
+        self.is_source_piston = nbt.byte("source").is_some_and(|b| b != 0);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        nbt.insert("blockState", write_block_state(self.moved_state));
+        nbt.insert("facing", self.direction.get_3d_data_value());
+        nbt.insert("progress", self.progress_o);
+        nbt.insert("extending", i8::from(self.extending));
+        nbt.insert("source", i8::from(self.is_source_piston));
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        // The client needs the moved block to render the animation
+        let mut nbt = NbtCompound::new();
+        self.save_additional(&mut nbt);
+        Some(nbt)
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, world: &Arc<World>) {
+        self.last_ticked = world.level_data.read().game_time();
+        self.progress_o = self.progress;
+        if self.progress_o < 1.0 {
+            // TODO: Push and pull entities (moveCollidedEntities / moveStuckEntities)
+            self.progress = (self.progress + PROGRESS_PER_TICK).min(1.0);
+        }
+        if self.progress >= 1.0 {
+            // Placing the moved block replaces this block entity, which can't happen while
+            // it is being ticked. The moving piston's scheduled tick does it instead.
+            world.schedule_block_tick_default(self.pos, vanilla_blocks::MOVING_PISTON, 1);
+        }
+    }
+}
+
+/// Completes the moving piston at `pos` if `condition` holds and removes its block entity.
+///
+/// Returns the moved block and whether it is the piston base.
+fn remove_moving_piston(
+    world: &Arc<World>,
+    pos: BlockPos,
+    condition: impl FnOnce(&PistonMovingBlockEntity) -> bool,
+) -> Option<(BlockStateId, bool)> {
+    let block_entity = world.get_block_entity(pos)?;
+    let moved = {
+        let mut guard = block_entity.lock();
+        let piston = guard
+            .as_any_mut()
+            .downcast_mut::<PistonMovingBlockEntity>()?;
+        if !condition(piston) {
+            return None;
+        }
+        piston.progress = 1.0;
+        piston.progress_o = 1.0;
+        (piston.moved_state, piston.is_source_piston)
+    };
+    world.remove_block_entity(pos);
+    Some(moved)
+}
+
+/// Writes a block state as `{Name, Properties}`. Vanilla: `NbtUtils.writeBlockState()`.
+fn write_block_state(state: BlockStateId) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    nbt.insert("Name", state.get_block().key.to_string());
+    let properties = REGISTRY.blocks.get_properties(state);
+    if !properties.is_empty() {
+        let mut props = NbtCompound::new();
+        for (name, value) in properties {
+            props.insert(name, value);
+        }
+        nbt.insert("Properties", props);
+    }
+    nbt
+}
+
+/// Reads a block state written by [`write_block_state`]. Vanilla: `NbtUtils.readBlockState()`.
+fn read_block_state(nbt: &NbtCompoundView<'_, '_>) -> Option<BlockStateId> {
+    let key: Identifier = nbt.string("Name")?.to_str().parse().ok()?;
+    let properties: Vec<(String, String)> = nbt
+        .compound("Properties")
+        .map(|props| {
+            props
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((
+                        name.to_str().into_owned(),
+                        value.string()?.to_str().into_owned(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let properties: Vec<(&str, &str)> = properties
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    REGISTRY.blocks.state_id_from_properties(&key, &properties)
+}
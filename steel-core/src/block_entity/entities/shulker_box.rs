@@ -0,0 +1,318 @@
+//! Shulker box block entity implementation.
+//!
+//! Shulker boxes are 27-slot containers that keep their contents when broken,
+//! storing them on the dropped item's `minecraft:container` component.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::data_components::DataComponentPatch;
+use steel_registry::data_components::vanilla_components::{CONTAINER, ItemContainerContents};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::{sound_events, vanilla_block_entity_types};
+use steel_utils::types::GameType;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::block_entity::BlockEntity;
+use crate::inventory::container::Container;
+use crate::player::Player;
+use crate::world::World;
+
+/// Number of slots in a shulker box (3 rows of 9).
+pub const SHULKER_BOX_SLOTS: usize = 27;
+
+/// Block event id that carries the number of players looking inside.
+const EVENT_SET_OPEN_COUNT: u8 = 1;
+
+/// How much the lid moves each tick, so it opens in 10 ticks.
+const PROGRESS_PER_TICK: f32 = 0.1;
+
+/// Where the lid is in its open/close animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationStatus {
+    /// The lid is shut.
+    Closed,
+    /// The lid is rising.
+    Opening,
+    /// The lid is fully open.
+    Opened,
+    /// The lid is coming back down.
+    Closing,
+}
+
+/// Shulker box block entity.
+pub struct ShulkerBoxBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The 27 item slots.
+    items: Vec<ItemStack>,
+    /// Number of players with the box open.
+    open_count: i32,
+    /// Current lid animation.
+    animation_status: AnimationStatus,
+    /// How far the lid is open, from 0.0 to 1.0.
+    progress: f32,
+}
+
+impl ShulkerBoxBlockEntity {
+    /// Creates a new shulker box block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            items: vec![ItemStack::empty(); SHULKER_BOX_SLOTS],
+            open_count: 0,
+            animation_status: AnimationStatus::Closed,
+            progress: 0.0,
+        }
+    }
+
+    /// Returns the current lid animation.
+    #[must_use]
+    pub const fn animation_status(&self) -> AnimationStatus {
+        self.animation_status
+    }
+
+    /// Returns how far the lid is open, from 0.0 to 1.0.
+    #[must_use]
+    pub const fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Tells the clients how many players have the box open and plays the
+    /// open or close sound when that count leaves or reaches zero.
+    fn update_open_count(&self, world: &World, sound: Option<i32>) {
+        world.block_event(
+            self.pos,
+            self.state.get_block(),
+            EVENT_SET_OPEN_COUNT,
+            self.open_count.clamp(0, i32::from(u8::MAX)) as u8,
+        );
+        // TODO: Emit CONTAINER_OPEN/CONTAINER_CLOSE game events once sculk sensors exist
+        if let Some(sound) = sound {
+            let pitch = rand::random::<f32>() * 0.1 + 0.9;
+            world.play_block_sound(sound, self.pos, 0.5, pitch, None);
+        }
+    }
+}
+
+impl BlockEntity for ShulkerBoxBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::SHULKER_BOX
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn pre_remove_side_effects(&mut self, _pos: BlockPos, _state: BlockStateId) {
+        // The contents leave with the dropped item instead of spilling out
+    }
+
+    fn trigger_event(&mut self, action_id: u8, action_param: u8) -> bool {
+        if action_id != EVENT_SET_OPEN_COUNT {
+            return false;
+        }
+        self.open_count = i32::from(action_param);
+        match action_param {
+            0 => self.animation_status = AnimationStatus::Closing,
+            1 => self.animation_status = AnimationStatus::Opening,
+            _ => {}
+        }
+        true
+    }
+
+    fn apply_implicit_components(&mut self, stack: &ItemStack) {
+        if let Some(contents) = stack.get(CONTAINER) {
+            contents.copy_into(&mut self.items);
+        }
+    }
+
+    fn collect_implicit_components(&self, components: &mut DataComponentPatch) {
+        components.set(CONTAINER, ItemContainerContents::from_items(&self.items));
+    }
+
+    fn remove_components_from_tag(&self, tag: &mut NbtCompound) {
+        tag.remove("Items");
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt_view: NbtCompoundView<'_, '_> = nbt.into();
+
+        // TODO: Support LootTable/LootTableSeed for unopened generated boxes
+        if let Some(items_list) = nbt_view.list("Items")
+            && let Some(compounds) = items_list.compounds()
+        {
+            for compound in compounds {
+                if let Some(slot) = compound.byte("Slot") {
+                    let slot = slot as usize;
+                    if slot < SHULKER_BOX_SLOTS
+                        && let Some(item) = ItemStack::from_borrowed_compound(&compound)
+                    {
+                        self.items[slot] = item;
+                    }
+                }
+            }
+        }
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        let mut items: Vec<NbtCompound> = Vec::new();
+        for (slot, item) in self.items.iter().enumerate() {
+            if !item.is_empty()
+                && let NbtTag::Compound(mut item_nbt) = item.clone().to_nbt_tag()
+            {
+                item_nbt.insert("Slot", slot as i8);
+                items.push(item_nbt);
+            }
+        }
+        nbt.insert("Items", NbtList::Compound(items));
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        None
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    /// Advances the lid animation.
+    ///
+    /// Vanilla also pushes entities out of the way of the lid and updates
+    /// neighbors when the lid starts or stops moving.
+    fn tick(&mut self, _world: &Arc<World>) {
+        // TODO: Move collided entities and update neighbors once that can be
+        //       done without holding this block entity's lock.
+        match self.animation_status {
+            AnimationStatus::Closed => self.progress = 0.0,
+            AnimationStatus::Opening => {
+                self.progress += PROGRESS_PER_TICK;
+                if self.progress >= 1.0 {
+                    self.animation_status = AnimationStatus::Opened;
+                    self.progress = 1.0;
+                }
+            }
+            AnimationStatus::Closing => {
+                self.progress -= PROGRESS_PER_TICK;
+                if self.progress <= 0.0 {
+                    self.animation_status = AnimationStatus::Closed;
+                    self.progress = 0.0;
+                }
+            }
+            AnimationStatus::Opened => self.progress = 1.0,
+        }
+    }
+
+    fn as_container(&self) -> Option<&(dyn Container + 'static)> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut (dyn Container + 'static)> {
+        Some(self)
+    }
+}
+
+impl Container for ShulkerBoxBlockEntity {
+    fn get_container_size(&self) -> usize {
+        SHULKER_BOX_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < SHULKER_BOX_SLOTS {
+            self.items[slot] = stack;
+            self.set_changed();
+        }
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        BlockEntity::set_changed(self);
+    }
+
+    /// Shulker boxes and other item containers can't go inside.
+    fn can_place_item(&self, _slot: usize, stack: &ItemStack) -> bool {
+        stack.can_fit_inside_container_items()
+    }
+
+    fn start_open(&mut self, player: &Player) {
+        if self.removed || player.game_mode.load() == GameType::Spectator {
+            return;
+        }
+        let Some(world) = self.level.upgrade() else {
+            return;
+        };
+        self.open_count = self.open_count.max(0) + 1;
+        let sound = (self.open_count == 1).then_some(sound_events::BLOCK_SHULKER_BOX_OPEN);
+        self.update_open_count(&world, sound);
+    }
+
+    fn stop_open(&mut self, player: &Player) {
+        if self.removed || player.game_mode.load() == GameType::Spectator {
+            return;
+        }
+        let Some(world) = self.level.upgrade() else {
+            return;
+        };
+        self.open_count -= 1;
+        let sound = (self.open_count <= 0).then_some(sound_events::BLOCK_SHULKER_BOX_CLOSE);
+        self.update_open_count(&world, sound);
+    }
+}
@@ -14,9 +14,10 @@ use simdnbt::borrow::{
 use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
 use steel_registry::block_entity_type::BlockEntityTypeRef;
 use steel_registry::loot_table::DyeColor;
+use steel_registry::sound_events;
 use steel_registry::vanilla_block_entity_types;
 use steel_utils::{BlockPos, BlockStateId};
-use text_components::{TextComponent, content::Content};
+use text_components::{TextComponent, content::Content, interactivity::ClickEvent};
 use uuid::Uuid;
 
 use crate::block_entity::BlockEntity;
@@ -82,6 +83,29 @@ impl SignText {
         })
     }
 
+    /// Checks if every line is plain text, so the sign editor can show it.
+    ///
+    /// The editor only edits literal strings; lines with translations or other
+    /// content would be flattened, so vanilla refuses to open it for them.
+    #[must_use]
+    pub fn is_editable(&self) -> bool {
+        self.messages
+            .iter()
+            .all(|msg| matches!(msg.content, Content::Text { .. }))
+    }
+
+    /// Gets the commands of all `run_command` click events, in line order.
+    #[must_use]
+    pub fn click_commands(&self) -> Vec<String> {
+        self.messages
+            .iter()
+            .filter_map(|msg| match &msg.interactions.click {
+                Some(ClickEvent::RunCommand { command }) => Some(command.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Loads sign text from borrowed NBT.
     pub fn load(&mut self, nbt: BorrowedNbtCompoundView<'_, '_>) {
         // Load messages - they are stored as a list of compounds (text components)
@@ -98,7 +122,7 @@ impl SignText {
 
         // Load color
         if let Some(color_str) = nbt.string("color") {
-            self.color = dye_color_from_str(&color_str.to_str());
+            self.color = dye_color_from_str(&color_str.to_str()).unwrap_or(DyeColor::Black);
         }
 
         // Load glow
@@ -221,6 +245,16 @@ impl SignBlockEntity {
         }
     }
 
+    /// Gets the sound played when a player interacts with the sign while it is waxed.
+    #[must_use]
+    pub fn interaction_failed_sound(&self) -> i32 {
+        if self.block_entity_type.key == vanilla_block_entity_types::HANGING_SIGN.key {
+            sound_events::BLOCK_HANGING_SIGN_WAXED_INTERACT_FAIL
+        } else {
+            sound_events::BLOCK_SIGN_WAXED_INTERACT_FAIL
+        }
+    }
+
     /// Sets the text for a side.
     pub fn set_text(&mut self, text: SignText, front: bool) {
         if front {
@@ -365,8 +399,9 @@ const fn dye_color_to_str(color: DyeColor) -> &'static str {
 }
 
 /// Parses a dye color from its string representation.
-fn dye_color_from_str(s: &str) -> DyeColor {
-    match s {
+#[must_use]
+pub fn dye_color_from_str(s: &str) -> Option<DyeColor> {
+    Some(match s {
         "white" => DyeColor::White,
         "orange" => DyeColor::Orange,
         "magenta" => DyeColor::Magenta,
@@ -382,6 +417,7 @@ fn dye_color_from_str(s: &str) -> DyeColor {
         "brown" => DyeColor::Brown,
         "green" => DyeColor::Green,
         "red" => DyeColor::Red,
-        _ => DyeColor::Black,
-    }
+        "black" => DyeColor::Black,
+        _ => return None,
+    })
 }
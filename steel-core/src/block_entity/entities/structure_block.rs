@@ -0,0 +1,352 @@
+//! Structure block entity implementation.
+//!
+//! Structure blocks load `.nbt` structure templates into the world. They keep
+//! the template name, its offset and size, and how it is placed.
+
+use std::any::Any;
+use std::sync::{Arc, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use simdnbt::borrow::BaseNbtCompound as BorrowedNbtCompound;
+use simdnbt::owned::NbtCompound;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::vanilla_block_entity_types;
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier, Mirror, Rotation};
+
+use crate::block_entity::BlockEntity;
+use crate::world::World;
+use crate::worldgen::{
+    STRUCTURE_TEMPLATES, StructurePlaceSettings, StructureProcessor, StructureTemplate,
+};
+
+/// How far the structure can be from the block on each axis.
+pub const MAX_OFFSET_PER_AXIS: i32 = 48;
+/// The largest structure a structure block handles on each axis.
+pub const MAX_SIZE_PER_AXIS: i32 = 48;
+
+/// What a structure block does when powered.
+///
+/// Vanilla: `StructureMode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StructureMode {
+    /// Saves the blocks in its area as a template.
+    Save,
+    /// Places a template.
+    Load,
+    /// Marks a corner for the save mode's size detection.
+    Corner,
+    /// Marks a spot for structure code to replace.
+    #[default]
+    Data,
+}
+
+impl StructureMode {
+    /// Gets the name used in block states.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Save => "save",
+            Self::Load => "load",
+            Self::Corner => "corner",
+            Self::Data => "data",
+        }
+    }
+
+    /// Gets the name the block entity saves the mode with.
+    #[must_use]
+    pub const fn nbt_name(self) -> &'static str {
+        match self {
+            Self::Save => "SAVE",
+            Self::Load => "LOAD",
+            Self::Corner => "CORNER",
+            Self::Data => "DATA",
+        }
+    }
+
+    /// Gets the mode with the given NBT name.
+    #[must_use]
+    pub fn by_nbt_name(name: &str) -> Option<Self> {
+        [Self::Save, Self::Load, Self::Corner, Self::Data]
+            .into_iter()
+            .find(|mode| mode.nbt_name() == name)
+    }
+}
+
+/// Structure block entity.
+///
+/// Vanilla: `StructureBlockEntity`.
+pub struct StructureBlockEntity {
+    /// Weak reference to the world for marking chunks dirty.
+    level: Weak<World>,
+    /// Position in the world.
+    pos: BlockPos,
+    /// Current block state.
+    state: BlockStateId,
+    /// Whether this entity has been marked for removal.
+    removed: bool,
+    /// The template this block saves or loads.
+    pub name: Option<Identifier>,
+    /// Who saved the template.
+    pub author: String,
+    /// The data mode's marker text.
+    pub metadata: String,
+    /// Offset of the structure from this block.
+    pub structure_pos: BlockPos,
+    /// Size of the structure.
+    pub structure_size: BlockPos,
+    /// Rotation the template is placed with.
+    pub rotation: Rotation,
+    /// Mirror the template is placed with.
+    pub mirror: Mirror,
+    /// What this block does when powered.
+    pub mode: StructureMode,
+    /// Whether entities are left out.
+    pub ignore_entities: bool,
+    /// Whether blocks are placed without updating their shapes.
+    pub strict: bool,
+    /// Whether the block was powered at the last neighbor update.
+    pub powered: bool,
+    /// Whether the client draws air blocks in the area.
+    pub show_air: bool,
+    /// Whether the client draws the area's outline.
+    pub show_bounding_box: bool,
+    /// The chance of each block being placed, from 0 to 1.
+    pub integrity: f32,
+    /// The seed of the integrity roll, or 0 for a random one.
+    pub seed: i64,
+}
+
+impl StructureBlockEntity {
+    /// Creates a new structure block entity.
+    #[must_use]
+    pub fn new(level: Weak<World>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            level,
+            pos,
+            state,
+            removed: false,
+            name: None,
+            author: String::new(),
+            metadata: String::new(),
+            structure_pos: BlockPos::new(0, 1, 0),
+            structure_size: BlockPos::ZERO,
+            rotation: Rotation::None,
+            mirror: Mirror::None,
+            mode: StructureMode::Data,
+            ignore_entities: true,
+            strict: false,
+            powered: false,
+            show_air: false,
+            show_bounding_box: true,
+            integrity: 1.0,
+            seed: 0,
+        }
+    }
+
+    /// Places the template if its size still matches the one saved in this
+    /// block. Otherwise only the size is updated.
+    ///
+    /// Vanilla: `StructureBlockEntity.placeStructureIfSameSize()`.
+    pub fn place_structure_if_same_size(&mut self, world: &Arc<World>) -> bool {
+        if self.mode != StructureMode::Load {
+            return false;
+        }
+        let Some(template) = self
+            .name
+            .as_ref()
+            .and_then(|name| STRUCTURE_TEMPLATES.get(name))
+        else {
+            return false;
+        };
+        if template.size() == self.structure_size {
+            self.place_structure(world, &template);
+            true
+        } else {
+            self.load_structure_info(&template);
+            false
+        }
+    }
+
+    /// Places the template at this block's offset.
+    ///
+    /// Vanilla: `StructureBlockEntity.placeStructure()`.
+    pub fn place_structure(&mut self, world: &Arc<World>, template: &StructureTemplate) {
+        self.load_structure_info(template);
+        let seed = Self::random_seed(self.seed);
+        let mut settings = StructurePlaceSettings {
+            mirror: self.mirror,
+            rotation: self.rotation,
+            ignore_entities: self.ignore_entities,
+            known_shape: self.strict,
+            ..StructurePlaceSettings::default()
+        };
+        if self.integrity < 1.0 {
+            settings.processors = vec![StructureProcessor::BlockRot {
+                integrity: self.integrity.clamp(0.0, 1.0),
+                rottable_blocks: None,
+            }];
+            settings.random_seed = Some(seed);
+        }
+        let mut flags = UpdateFlags::UPDATE_CLIENTS;
+        if self.strict {
+            flags |= UpdateFlags::UPDATE_KNOWN_SHAPE
+                | UpdateFlags::UPDATE_SUPPRESS_DROPS
+                | UpdateFlags::UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS
+                | UpdateFlags::UPDATE_SKIP_ON_PLACE;
+        }
+        let pos = self.pos.offset(
+            self.structure_pos.x(),
+            self.structure_pos.y(),
+            self.structure_pos.z(),
+        );
+        template.place_in_world(
+            world,
+            pos,
+            pos,
+            &settings,
+            &mut LegacyRandom::from_seed(seed as u64),
+            flags,
+        );
+    }
+
+    /// Drops the cached template, so it is read again on the next load.
+    ///
+    /// Vanilla: `StructureBlockEntity.unloadStructure()`.
+    pub fn unload_structure(&self) {
+        if let Some(name) = &self.name {
+            STRUCTURE_TEMPLATES.remove(name);
+        }
+    }
+
+    /// Vanilla: `StructureBlockEntity.loadStructureInfo()`.
+    fn load_structure_info(&mut self, template: &StructureTemplate) {
+        self.structure_size = template.size();
+        self.set_changed();
+    }
+
+    /// Gets the seed to place with. A seed of 0 picks one from the clock.
+    ///
+    /// Vanilla: `StructureBlockEntity.createRandom()`.
+    #[must_use]
+    pub fn random_seed(seed: i64) -> i64 {
+        if seed != 0 {
+            return seed;
+        }
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as i64)
+    }
+
+    fn save_data(&self, nbt: &mut NbtCompound) {
+        nbt.insert(
+            "name",
+            self.name
+                .as_ref()
+                .map_or_else(String::new, ToString::to_string),
+        );
+        nbt.insert("author", self.author.as_str());
+        nbt.insert("metadata", self.metadata.as_str());
+        nbt.insert("posX", self.structure_pos.x());
+        nbt.insert("posY", self.structure_pos.y());
+        nbt.insert("posZ", self.structure_pos.z());
+        nbt.insert("sizeX", self.structure_size.x());
+        nbt.insert("sizeY", self.structure_size.y());
+        nbt.insert("sizeZ", self.structure_size.z());
+        nbt.insert("rotation", self.rotation.nbt_name());
+        nbt.insert("mirror", self.mirror.nbt_name());
+        nbt.insert("mode", self.mode.nbt_name());
+        nbt.insert("ignoreEntities", i8::from(self.ignore_entities));
+        nbt.insert("strict", i8::from(self.strict));
+        nbt.insert("powered", i8::from(self.powered));
+        nbt.insert("showair", i8::from(self.show_air));
+        nbt.insert("showboundingbox", i8::from(self.show_bounding_box));
+        nbt.insert("integrity", self.integrity);
+        nbt.insert("seed", self.seed);
+    }
+}
+
+impl BlockEntity for StructureBlockEntity {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_type(&self) -> BlockEntityTypeRef {
+        vanilla_block_entity_types::STRUCTURE_BLOCK
+    }
+
+    fn get_block_pos(&self) -> BlockPos {
+        self.pos
+    }
+
+    fn get_block_state(&self) -> BlockStateId {
+        self.state
+    }
+
+    fn set_block_state(&mut self, state: BlockStateId) {
+        self.state = state;
+    }
+
+    fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    fn set_removed(&mut self) {
+        self.removed = true;
+    }
+
+    fn clear_removed(&mut self) {
+        self.removed = false;
+    }
+
+    fn get_level(&self) -> Option<Arc<World>> {
+        self.level.upgrade()
+    }
+
+    fn load_additional(&mut self, nbt: &BorrowedNbtCompound<'_>) {
+        let string = |key| {
+            nbt.string(key)
+                .map(|value| value.to_str().into_owned())
+                .unwrap_or_default()
+        };
+        let offset = |key| {
+            nbt.int(key)
+                .unwrap_or(0)
+                .clamp(-MAX_OFFSET_PER_AXIS, MAX_OFFSET_PER_AXIS)
+        };
+        let size = |key| nbt.int(key).unwrap_or(0).clamp(0, MAX_SIZE_PER_AXIS);
+        let flag = |key, default| nbt.byte(key).map_or(default, |value| value != 0);
+
+        self.name = string("name").parse().ok();
+        self.author = string("author");
+        self.metadata = string("metadata");
+        self.structure_pos = BlockPos::new(offset("posX"), offset("posY"), offset("posZ"));
+        self.structure_size = BlockPos::new(size("sizeX"), size("sizeY"), size("sizeZ"));
+        self.rotation = Rotation::by_nbt_name(&string("rotation")).unwrap_or_default();
+        self.mirror = Mirror::by_nbt_name(&string("mirror")).unwrap_or_default();
+        self.mode = StructureMode::by_nbt_name(&string("mode")).unwrap_or_default();
+        self.ignore_entities = flag("ignoreEntities", true);
+        self.strict = flag("strict", false);
+        self.powered = flag("powered", false);
+        self.show_air = flag("showair", false);
+        self.show_bounding_box = flag("showboundingbox", true);
+        self.integrity = nbt.float("integrity").unwrap_or(1.0);
+        self.seed = nbt.long("seed").unwrap_or(0);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        self.save_data(nbt);
+    }
+
+    fn get_update_tag(&self) -> Option<NbtCompound> {
+        // The client shows the name, area and settings in its edit screen
+        let mut nbt = NbtCompound::new();
+        self.save_data(&mut nbt);
+        Some(nbt)
+    }
+}
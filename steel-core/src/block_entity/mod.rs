@@ -33,6 +33,8 @@ use std::sync::Arc;
 use simdnbt::borrow::BaseNbtCompound as BorrowedNbtCompound;
 use simdnbt::owned::NbtCompound;
 use steel_registry::block_entity_type::BlockEntityTypeRef;
+use steel_registry::data_components::DataComponentPatch;
+use steel_registry::item_stack::ItemStack;
 use steel_utils::{BlockPos, BlockStateId, locks::SyncMutex};
 
 pub use registry::{BLOCK_ENTITIES, BlockEntityFactory, BlockEntityRegistry, init_block_entities};
@@ -94,6 +96,21 @@ pub trait BlockEntity: Send + Sync {
         }
     }
 
+    /// Called when data the client renders changes.
+    ///
+    /// Marks the chunk as dirty like [`Self::set_changed`] and queues the block
+    /// for the next chunk broadcast, which resends [`Self::get_update_tag`] to
+    /// every player tracking the chunk.
+    ///
+    /// Vanilla: `SignBlockEntity.markUpdated()`.
+    fn mark_updated(&self) {
+        if let Some(world) = self.get_level() {
+            let pos = self.get_block_pos();
+            world.block_entity_changed(pos);
+            world.send_block_updated(pos);
+        }
+    }
+
     /// Gets the world reference if still valid.
     ///
     /// Block entities receive a `Weak<World>` at construction time.
@@ -114,6 +131,65 @@ pub trait BlockEntity: Send + Sync {
         // Default: no side effects
     }
 
+    /// Handles a block event forwarded by the block's behavior.
+    ///
+    /// Returns `true` if the event was handled and should be sent to clients.
+    /// Vanilla: `BlockEntity.triggerEvent()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameters used by overrides"
+    )]
+    fn trigger_event(&mut self, action_id: u8, action_param: u8) -> bool {
+        false
+    }
+
+    // === Item Components ===
+
+    /// Reads the data this block entity keeps on its item, when it's placed
+    /// from an item stack.
+    ///
+    /// Vanilla: `BlockEntity.applyImplicitComponents()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameter used by overrides"
+    )]
+    fn apply_implicit_components(&mut self, stack: &ItemStack) {
+        // Default: nothing is kept on the item
+    }
+
+    /// Writes the data this block entity keeps on its item, like a shulker
+    /// box's contents.
+    ///
+    /// Vanilla: `BlockEntity.collectImplicitComponents()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameter used by overrides"
+    )]
+    fn collect_implicit_components(&self, components: &mut DataComponentPatch) {
+        // Default: nothing is kept on the item
+    }
+
+    /// Returns the components to put on the item dropped for this block entity.
+    ///
+    /// Vanilla: `BlockEntity.collectComponents()`.
+    fn collect_components(&self) -> DataComponentPatch {
+        let mut components = DataComponentPatch::new();
+        self.collect_implicit_components(&mut components);
+        components
+    }
+
+    /// Removes the data that [`Self::collect_implicit_components`] already
+    /// keeps on the item from a saved tag, so it isn't stored twice.
+    ///
+    /// Vanilla: `BlockEntity.removeComponentsFromTag()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameter used by overrides"
+    )]
+    fn remove_components_from_tag(&self, tag: &mut NbtCompound) {
+        // Default: nothing is kept on the item
+    }
+
     // === Persistence (disk) ===
 
     /// Loads additional data from NBT.
@@ -129,10 +205,11 @@ pub trait BlockEntity: Send + Sync {
 
     // === Client Sync ===
 
-    /// Returns the NBT data to send to clients for initial sync.
+    /// Returns the NBT data to send to clients.
     ///
-    /// This is included in the chunk data packet when the chunk is first sent.
-    /// Return `None` if no client sync is needed.
+    /// This is included in the chunk data packet when the chunk is first sent,
+    /// and sent as `CBlockEntityData` when the block is broadcast again, see
+    /// [`Self::mark_updated`]. Return `None` if no client sync is needed.
     fn get_update_tag(&self) -> Option<NbtCompound> {
         None
     }
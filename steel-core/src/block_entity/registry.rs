@@ -11,7 +11,11 @@ use steel_utils::locks::SyncMutex;
 use steel_utils::{BlockPos, BlockStateId};
 
 use super::SharedBlockEntity;
-use super::entities::{BarrelBlockEntity, SignBlockEntity};
+use super::entities::{
+    BarrelBlockEntity, BeaconBlockEntity, DispenserBlockEntity, EnderChestBlockEntity,
+    HopperBlockEntity, PistonMovingBlockEntity, ShulkerBoxBlockEntity, SignBlockEntity,
+    StructureBlockEntity,
+};
 use crate::world::World;
 
 /// Factory function type for creating block entities.
@@ -151,6 +155,62 @@ pub fn init_block_entities() {
         Arc::new(SyncMutex::new(BarrelBlockEntity::new(level, pos, state)))
     });
 
+    // Register dispenser block entity factory
+    registry.register(
+        vanilla_block_entity_types::DISPENSER,
+        |level, pos, state| Arc::new(SyncMutex::new(DispenserBlockEntity::new(level, pos, state))),
+    );
+
+    // Register dropper block entity factory
+    registry.register(vanilla_block_entity_types::DROPPER, |level, pos, state| {
+        Arc::new(SyncMutex::new(DispenserBlockEntity::new_dropper(
+            level, pos, state,
+        )))
+    });
+
+    // Register hopper block entity factory
+    registry.register(vanilla_block_entity_types::HOPPER, |level, pos, state| {
+        Arc::new(SyncMutex::new(HopperBlockEntity::new(level, pos, state)))
+    });
+
+    // Register beacon block entity factory
+    registry.register(vanilla_block_entity_types::BEACON, |level, pos, state| {
+        Arc::new(SyncMutex::new(BeaconBlockEntity::new(level, pos, state)))
+    });
+
+    // Register ender chest block entity factory
+    registry.register(
+        vanilla_block_entity_types::ENDER_CHEST,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(EnderChestBlockEntity::new(
+                level, pos, state,
+            )))
+        },
+    );
+
+    // Register shulker box block entity factory
+    registry.register(
+        vanilla_block_entity_types::SHULKER_BOX,
+        |level, pos, state| {
+            Arc::new(SyncMutex::new(ShulkerBoxBlockEntity::new(
+                level, pos, state,
+            )))
+        },
+    );
+
+    // Register moving piston block entity factory
+    registry.register(vanilla_block_entity_types::PISTON, |level, pos, state| {
+        Arc::new(SyncMutex::new(PistonMovingBlockEntity::new(
+            level, pos, state,
+        )))
+    });
+
+    // Register structure block entity factory
+    registry.register(
+        vanilla_block_entity_types::STRUCTURE_BLOCK,
+        |level, pos, state| Arc::new(SyncMutex::new(StructureBlockEntity::new(level, pos, state))),
+    );
+
     assert!(
         BLOCK_ENTITIES.set(registry).is_ok(),
         "Block entity registry already initialized"
@@ -7,19 +7,21 @@
 
 use std::sync::LazyLock;
 
+use serde::Deserialize;
+use steel_utils::BoundingBox;
 use steel_utils::math::map_clamped;
-use steel_utils::{BoundingBox, Identifier};
 
-use crate::world::structure::StructureStartMap;
+use crate::world::structure::StructureStart;
+use crate::worldgen::{JigsawPiece, Projection, Structure};
 
 /// How a structure modifies the surrounding terrain.
 ///
 /// Corresponds to vanilla's `TerrainAdjustment` enum.
-// TODO: This should be data-driven from the structure registry, not hardcoded.
-// In vanilla, `TerrainAdjustment` is a codec field on `Structure.StructureSettings`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TerrainAdjustment {
     /// No terrain adaptation.
+    #[default]
     None,
     /// Fill in terrain around and above the structure (e.g. ancient city).
     Bury,
@@ -31,26 +33,6 @@ pub enum TerrainAdjustment {
     Encapsulate,
 }
 
-impl TerrainAdjustment {
-    /// Look up the terrain adjustment for a vanilla structure identifier.
-    // TODO: Replace with registry lookup once structures are data-driven.
-    #[must_use]
-    pub fn for_structure(id: &Identifier) -> Self {
-        if id.namespace != Identifier::VANILLA_NAMESPACE {
-            return Self::None;
-        }
-        match id.path.as_ref() {
-            "village" | "pillager_outpost" | "desert_pyramid" | "jungle_temple" | "swamp_hut"
-            | "igloo" | "shipwreck" | "shipwreck_beached" | "ocean_ruin_cold"
-            | "ocean_ruin_warm" => Self::BeardThin,
-            "bastion_remnant" => Self::BeardBox,
-            "ancient_city" | "trail_ruins" | "ocean_monument" => Self::Bury,
-            "trial_chambers" => Self::Encapsulate,
-            _ => Self::None,
-        }
-    }
-}
-
 /// A rigid structure piece that modifies terrain density.
 #[derive(Debug)]
 struct Rigid {
@@ -60,7 +42,7 @@ struct Rigid {
 }
 
 /// A jigsaw junction point that creates a small terrain beard.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct JigsawJunction {
     /// World X coordinate of the junction source.
     pub source_x: i32,
@@ -68,6 +50,10 @@ pub struct JigsawJunction {
     pub source_ground_y: i32,
     /// World Z coordinate of the junction source.
     pub source_z: i32,
+    /// Y offset from the source jigsaw to the jigsaw it connects to.
+    pub delta_y: i32,
+    /// Projection of the piece on the other side of the junction.
+    pub dest_proj: Projection,
 }
 
 const KERNEL_RADIUS: i32 = 12;
@@ -156,18 +142,30 @@ impl Beardifier {
     /// Collect rigid pieces and junctions from structure starts that affect this chunk.
     ///
     /// `chunk_x` and `chunk_z` are chunk coordinates (not block coordinates).
+    /// `structure_starts` are the starts the chunk references.
     #[must_use]
     pub fn for_structures_in_chunk(
-        structure_starts: &StructureStartMap,
+        structure_starts: &[StructureStart],
         chunk_x: i32,
         chunk_z: i32,
     ) -> Self {
         let mut rigids = Vec::new();
-        let junctions = Vec::new();
+        let mut junctions = Vec::new();
         let mut encompassing: Option<BoundingBox> = None;
+        let mut encompass = |bb: &BoundingBox| {
+            encompassing = Some(match encompassing {
+                Some(enc) => BoundingBox::encapsulating(&enc, bb),
+                None => *bb,
+            });
+        };
+        let chunk_start_x = chunk_x * 16;
+        let chunk_start_z = chunk_z * 16;
 
-        for (structure_id, start) in structure_starts {
-            let terrain_adj = TerrainAdjustment::for_structure(structure_id);
+        for start in structure_starts {
+            let terrain_adj = Structure::by_id(&start.structure)
+                .map_or(TerrainAdjustment::None, |structure| {
+                    structure.terrain_adaptation
+                });
             if terrain_adj == TerrainAdjustment::None {
                 continue;
             }
@@ -181,27 +179,45 @@ impl Beardifier {
                     continue;
                 }
 
-                // For jigsaw pieces with RIGID projection, we'd check projection.
-                // Since we store nbt_data opaquely, we treat all pieces as rigid
-                // (non-rigid pieces don't generate terrain adaptation in vanilla
-                // unless they're PoolElementStructurePiece with RIGID projection).
-                // For now, treat all pieces as rigid with ground_level_delta = 0.
-                // TODO: Parse ground_level_delta from jigsaw piece NBT when available.
-                let ground_level_delta = 0;
-
-                encompassing = Some(match encompassing {
-                    Some(enc) => BoundingBox::encapsulating(&enc, bb),
-                    None => *bb,
-                });
-
-                rigids.push(Rigid {
-                    bounding_box: *bb,
-                    terrain_adjustment: terrain_adj,
-                    ground_level_delta,
-                });
+                let Some(jigsaw) = JigsawPiece::from_piece(piece) else {
+                    encompass(bb);
+                    rigids.push(Rigid {
+                        bounding_box: *bb,
+                        terrain_adjustment: terrain_adj,
+                        ground_level_delta: 0,
+                    });
+                    continue;
+                };
+
+                // Only rigid jigsaw pieces adapt the terrain; terrain matching ones
+                // already follow it
+                if jigsaw.element.projection() == Projection::Rigid {
+                    encompass(bb);
+                    rigids.push(Rigid {
+                        bounding_box: *bb,
+                        terrain_adjustment: terrain_adj,
+                        ground_level_delta: jigsaw.ground_level_delta,
+                    });
+                }
 
-                // TODO: Parse and collect jigsaw junctions from piece NBT
-                // Junctions contribute getBeardContribution * 0.4
+                for junction in jigsaw.junctions {
+                    let (jx, jz) = (junction.source_x, junction.source_z);
+                    if jx > chunk_start_x - 12
+                        && jz > chunk_start_z - 12
+                        && jx < chunk_start_x + 15 + 12
+                        && jz < chunk_start_z + 15 + 12
+                    {
+                        encompass(&BoundingBox::new(
+                            jx,
+                            junction.source_ground_y,
+                            jz,
+                            jx,
+                            junction.source_ground_y,
+                            jz,
+                        ));
+                        junctions.push(junction);
+                    }
+                }
             }
         }
 
@@ -0,0 +1,145 @@
+//! Finding the chunks from older generation that a generated chunk blends into.
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use steel_registry::blocks::{Block, block_state_ext::BlockStateExt};
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_block_tags, vanilla_blocks};
+use steel_utils::density::blending::{DENSITY_BLENDING_RANGE_CHUNKS, HEIGHT_BLENDING_RANGE_CHUNKS};
+use steel_utils::density::{Blender, BlendingData, BlendingSource};
+use steel_utils::{BlockPos, BlockStateId, ChunkPos};
+
+use crate::chunk::chunk_access::{ChunkAccess, ChunkStatus};
+use crate::chunk::chunk_generation_task::StaticCache2D;
+use crate::chunk::chunk_holder::ChunkHolder;
+
+/// Blocks the surface of old terrain is measured by.
+const SURFACE_BLOCKS: [&Block; 11] = [
+    vanilla_blocks::PODZOL,
+    vanilla_blocks::GRAVEL,
+    vanilla_blocks::GRASS_BLOCK,
+    vanilla_blocks::STONE,
+    vanilla_blocks::COARSE_DIRT,
+    vanilla_blocks::SAND,
+    vanilla_blocks::RED_SAND,
+    vanilla_blocks::MYCELIUM,
+    vanilla_blocks::SNOW_BLOCK,
+    vanilla_blocks::TERRACOTTA,
+    vanilla_blocks::DIRT,
+];
+
+/// Creates the blender for the chunk at `center`, or `None` if there is no
+/// chunk from older generation close enough to blend with.
+///
+/// `cache` must hold the chunks up to `HEIGHT_BLENDING_RANGE_CHUNKS + 1` away.
+/// Vanilla: `Blender.of()`.
+#[must_use]
+pub fn blender_for(
+    cache: &StaticCache2D<Arc<ChunkHolder>>,
+    center: ChunkPos,
+) -> Option<Arc<Blender>> {
+    let mut height_and_biome_data = FxHashMap::default();
+    let mut density_data = FxHashMap::default();
+    let max_distance_sq = (HEIGHT_BLENDING_RANGE_CHUNKS + 1).pow(2);
+
+    for dx in -HEIGHT_BLENDING_RANGE_CHUNKS..=HEIGHT_BLENDING_RANGE_CHUNKS {
+        for dz in -HEIGHT_BLENDING_RANGE_CHUNKS..=HEIGHT_BLENDING_RANGE_CHUNKS {
+            if dx * dx + dz * dz > max_distance_sq {
+                continue;
+            }
+            let chunk_x = center.0.x + dx;
+            let chunk_z = center.0.y + dz;
+            let Some(data) = updated_blending_data(cache, chunk_x, chunk_z) else {
+                continue;
+            };
+            if dx.abs() <= DENSITY_BLENDING_RANGE_CHUNKS
+                && dz.abs() <= DENSITY_BLENDING_RANGE_CHUNKS
+            {
+                density_data.insert((chunk_x, chunk_z), Arc::clone(&data));
+            }
+            height_and_biome_data.insert((chunk_x, chunk_z), data);
+        }
+    }
+
+    if height_and_biome_data.is_empty() {
+        return None;
+    }
+    Some(Arc::new(Blender::new(height_and_biome_data, density_data)))
+}
+
+/// Gets the blending data of an old chunk, sampling its borders on first use.
+///
+/// Vanilla: `BlendingData.getOrUpdateBlendingData()`.
+fn updated_blending_data(
+    cache: &StaticCache2D<Arc<ChunkHolder>>,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Option<Arc<BlendingData>> {
+    let chunk = cache.get(chunk_x, chunk_z).try_chunk(ChunkStatus::Biomes)?;
+    let data = chunk.blending_data()?;
+    if data.has_calculated_data() {
+        return Some(data);
+    }
+
+    // Check the neighbours before locking this chunk's data, so two old chunks
+    // next to each other never wait on each other's lock.
+    let mut new_sides = [[false; 3]; 3];
+    for (dx, row) in (-1..=1).zip(&mut new_sides) {
+        for (dz, is_new) in (-1..=1).zip(row) {
+            *is_new = cache
+                .get(chunk_x + dx, chunk_z + dz)
+                .try_chunk(ChunkStatus::Empty)
+                .is_none_or(|neighbor| !neighbor.is_old_noise_generation());
+        }
+    }
+
+    let mut guard = chunk.blending_data_lock().lock();
+    let data = guard.as_mut()?;
+    Arc::make_mut(data).calculate_data(&OldChunk(&chunk), |dx, dz| {
+        new_sides[(dx + 1) as usize][(dz + 1) as usize]
+    });
+    Some(Arc::clone(data))
+}
+
+/// Reads an old chunk's blocks and biomes for sampling its blending data.
+struct OldChunk<'a>(&'a ChunkAccess);
+
+impl OldChunk<'_> {
+    fn block_state(&self, x: i32, y: i32, z: i32) -> BlockStateId {
+        let pos = self.0.pos();
+        self.0
+            .get_block_state(BlockPos::new(pos.0.x * 16 + x, y, pos.0.y * 16 + z))
+    }
+}
+
+impl BlendingSource for OldChunk<'_> {
+    fn is_surface_block(&self, x: i32, y: i32, z: i32) -> bool {
+        SURFACE_BLOCKS.contains(&self.block_state(x, y, z).get_block())
+    }
+
+    fn is_ground(&self, x: i32, y: i32, z: i32) -> bool {
+        let state = self.block_state(x, y, z);
+        let block = state.get_block();
+        !state.is_air()
+            && !REGISTRY
+                .blocks
+                .is_in_tag(block, &vanilla_block_tags::LEAVES_TAG)
+            && !REGISTRY
+                .blocks
+                .is_in_tag(block, &vanilla_block_tags::LOGS_TAG)
+            && block != vanilla_blocks::BROWN_MUSHROOM_BLOCK
+            && block != vanilla_blocks::RED_MUSHROOM_BLOCK
+            && !state.get_collision_shape().is_empty()
+    }
+
+    fn noise_biome(&self, quart_x: i32, quart_y: i32, quart_z: i32) -> u16 {
+        let sections = &self.0.sections().sections;
+        let min_quart_y = self.0.min_y() >> 2;
+        let quart_y = (quart_y - min_quart_y).clamp(0, sections.len() as i32 * 4 - 1);
+        sections[(quart_y >> 2) as usize].read().biomes.get(
+            (quart_x & 3) as usize,
+            (quart_y & 3) as usize,
+            (quart_z & 3) as usize,
+        )
+    }
+}
@@ -1,5 +1,9 @@
 //! This module contains the `ChunkAccess` enum, which is used to access chunks in different states.
-use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+use steel_utils::density::BlendingData;
+use steel_utils::locks::SyncMutex;
 use steel_utils::{BlockPos, BlockStateId, ChunkPos, types::UpdateFlags};
 use wincode::{SchemaRead, SchemaWrite};
 
@@ -8,9 +12,13 @@ use parking_lot::{RwLockReadGuard, RwLockWriteGuard};
 use crate::chunk::{
     heightmap::HeightmapType, level_chunk::LevelChunk, proto_chunk::ProtoChunk, section::Sections,
 };
+use crate::entity::activation::EntityActivation;
 use crate::world::structure::{StructureReferenceMap, StructureStartMap};
 use crate::world::tick_scheduler::{BlockTick, FluidTick};
 
+/// How many ticks of inhabited time accumulate before the chunk is marked for saving (one minute).
+pub const INHABITED_TIME_SAVE_INTERVAL: i64 = 1200;
+
 /// The status of a chunk.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, SchemaWrite, SchemaRead)]
 pub enum ChunkStatus {
@@ -229,6 +237,69 @@ impl ChunkAccess {
         }
     }
 
+    /// Returns the total ticks players have spent near this chunk.
+    #[must_use]
+    pub fn inhabited_time(&self) -> i64 {
+        self.inhabited_time_atomic().load(Ordering::Relaxed)
+    }
+
+    /// Sets the inhabited time and marks the chunk as dirty.
+    pub fn set_inhabited_time(&self, ticks: i64) {
+        self.inhabited_time_atomic().store(ticks, Ordering::Relaxed);
+        self.mark_dirty();
+    }
+
+    /// Adds `delta` ticks to the inhabited time.
+    ///
+    /// The chunk is only marked dirty whenever the total crosses a multiple of
+    /// [`INHABITED_TIME_SAVE_INTERVAL`], so an occupied chunk is not re-saved every tick.
+    pub fn increment_inhabited_time(&self, delta: i64) {
+        let previous = self
+            .inhabited_time_atomic()
+            .fetch_add(delta, Ordering::Relaxed);
+        if previous / INHABITED_TIME_SAVE_INTERVAL
+            != (previous + delta) / INHABITED_TIME_SAVE_INTERVAL
+        {
+            self.mark_dirty();
+        }
+    }
+
+    const fn inhabited_time_atomic(&self) -> &AtomicI64 {
+        match self {
+            Self::Full(chunk) => &chunk.inhabited_time,
+            Self::Proto(proto_chunk) => &proto_chunk.inhabited_time,
+            Self::Unloaded => unreachable!(),
+        }
+    }
+
+    /// Returns the blending data of this chunk, if it is from older generation.
+    #[must_use]
+    pub fn blending_data(&self) -> Option<Arc<BlendingData>> {
+        self.blending_data_lock().lock().clone()
+    }
+
+    /// Sets the blending data of this chunk and marks it as dirty.
+    pub fn set_blending_data(&self, blending_data: Option<Arc<BlendingData>>) {
+        *self.blending_data_lock().lock() = blending_data;
+        self.mark_dirty();
+    }
+
+    /// Whether this chunk's terrain comes from older generation that new chunks blend into.
+    ///
+    /// Vanilla: `ChunkAccess.isOldNoiseGeneration()`.
+    #[must_use]
+    pub fn is_old_noise_generation(&self) -> bool {
+        self.blending_data_lock().lock().is_some()
+    }
+
+    pub(crate) const fn blending_data_lock(&self) -> &SyncMutex<Option<Arc<BlendingData>>> {
+        match self {
+            Self::Full(chunk) => &chunk.blending_data,
+            Self::Proto(proto_chunk) => &proto_chunk.blending_data,
+            Self::Unloaded => unreachable!(),
+        }
+    }
+
     /// Returns the minimum Y coordinate of the world this chunk belongs to.
     #[must_use]
     pub const fn min_y(&self) -> i32 {
@@ -365,13 +436,16 @@ impl ChunkAccess {
     /// Ticks the chunk if it's a full chunk.
     ///
     /// Drains ready scheduled ticks into the provided vecs, then processes
-    /// block entities, entities, and random ticks.
+    /// block entities, entities, and random ticks. Adds the time spent ticking
+    /// entities to `entity_time`, if set. `activation` decides which entities tick.
     pub fn tick(
         &self,
         random_tick_speed: u32,
         tick_count: i32,
         ready_block_ticks: &mut Vec<BlockTick>,
         ready_fluid_ticks: &mut Vec<FluidTick>,
+        entity_time: Option<&mut Duration>,
+        activation: &mut EntityActivation,
     ) {
         if let Self::Full(chunk) = self {
             chunk.tick(
@@ -379,6 +453,8 @@ impl ChunkAccess {
                 tick_count,
                 ready_block_ticks,
                 ready_fluid_ticks,
+                entity_time,
+                activation,
             );
         }
     }
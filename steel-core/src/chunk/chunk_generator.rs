@@ -1,7 +1,11 @@
 //! This module contains the `ChunkGenerator` trait, which is used to generate chunks.
 
+use std::sync::Arc;
+
 use crate::chunk::chunk_access::ChunkAccess;
+use crate::world::structure::StructureStart;
 use enum_dispatch::enum_dispatch;
+use steel_utils::density::Blender;
 
 /// A trait for generating chunks.
 #[enum_dispatch]
@@ -10,21 +14,37 @@ pub trait ChunkGenerator: Send + Sync {
     fn create_structures(&self, chunk: &ChunkAccess);
 
     /// Creates the biomes in a chunk.
-    fn create_biomes(&self, chunk: &ChunkAccess);
+    ///
+    /// `blender` carries the biomes of nearby chunks from older generation, if any.
+    fn create_biomes(&self, chunk: &ChunkAccess, blender: Option<&Arc<Blender>>);
 
-    /// Fills the chunk with noise.
-    fn fill_from_noise(&self, chunk: &ChunkAccess);
+    /// Fills the chunk with noise, blended into nearby chunks from older generation.
+    ///
+    /// `structures` are the structure starts the chunk references; the terrain
+    /// adapts around them.
+    fn fill_from_noise(
+        &self,
+        chunk: &ChunkAccess,
+        structures: &[StructureStart],
+        blender: Option<&Arc<Blender>>,
+    );
 
     /// Builds the surface of the chunk.
     ///
     /// `neighbor_biomes` maps `(quart_x, quart_y, quart_z)` to a biome palette ID,
     /// reading from neighbor chunk palettes for out-of-chunk biome lookups (matching
     /// vanilla's `WorldGenRegion.getNoiseBiome`).
-    fn build_surface(&self, chunk: &ChunkAccess, neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16);
+    fn build_surface(
+        &self,
+        chunk: &ChunkAccess,
+        neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16,
+        blender: Option<&Arc<Blender>>,
+    );
 
     /// Applies carvers to the chunk.
     fn apply_carvers(&self, chunk: &ChunkAccess);
 
-    /// Applies biome decorations to the chunk.
-    fn apply_biome_decorations(&self, chunk: &ChunkAccess);
+    /// Applies biome decorations to the chunk, placing the parts of `structures`
+    /// inside it first.
+    fn apply_biome_decorations(&self, chunk: &ChunkAccess, structures: &[StructureStart]);
 }
@@ -0,0 +1,187 @@
+//! Per-chunk entity and block entity caps.
+//!
+//! The caps and overflow policies come from [`ChunkLimitsConfig`]. Every chunk
+//! counts how often it hit them so admins can find grief machines and spawner
+//! farms with `/steel chunks`.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use steel_utils::ChunkPos;
+
+use crate::config::{BlockEntityOverflowPolicy, ChunkLimitsConfig, EntityOverflowPolicy};
+
+/// What to do with a new entity in a chunk holding `count` capped entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityAdmission {
+    /// The chunk is below its cap.
+    Allow,
+    /// The chunk is full but the policy lets the entity in with a warning.
+    Warn,
+    /// Remove this many of the chunk's oldest item entities first.
+    DropItems(usize),
+    /// Refuse the entity.
+    Refuse,
+}
+
+/// Decides what happens to a new entity in a chunk holding `count` capped entities.
+#[must_use]
+pub fn admit_entity(config: &ChunkLimitsConfig, count: usize) -> EntityAdmission {
+    let Some(max) = config.max_entities else {
+        return EntityAdmission::Allow;
+    };
+    let max = max as usize;
+    if count < max {
+        return EntityAdmission::Allow;
+    }
+    match config.entity_overflow {
+        EntityOverflowPolicy::DropOldestItems => EntityAdmission::DropItems(count + 1 - max),
+        EntityOverflowPolicy::RefuseNew => EntityAdmission::Refuse,
+        EntityOverflowPolicy::Warn => EntityAdmission::Warn,
+    }
+}
+
+/// Decides whether a new block entity fits into a chunk holding `count` block entities.
+///
+/// Returns `None` if the chunk is below its cap, or the overflow policy otherwise.
+#[must_use]
+pub fn block_entity_overflow(
+    config: &ChunkLimitsConfig,
+    count: usize,
+) -> Option<BlockEntityOverflowPolicy> {
+    let max = config.max_block_entities? as usize;
+    (count >= max).then_some(config.block_entity_overflow)
+}
+
+/// Counts how often a chunk hit its caps since it was loaded.
+#[derive(Debug, Default)]
+pub struct ChunkLimitStats {
+    refused_entities: AtomicU32,
+    dropped_items: AtomicU32,
+    refused_block_entities: AtomicU32,
+    warnings: AtomicU32,
+}
+
+impl ChunkLimitStats {
+    /// Records a refused entity.
+    pub fn record_refused_entity(&self) {
+        self.refused_entities.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records item entities removed to make room.
+    pub fn record_dropped_items(&self, count: u32) {
+        self.dropped_items.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Records a refused block entity.
+    pub fn record_refused_block_entity(&self) {
+        self.refused_block_entities.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records an overflow that was let through.
+    ///
+    /// Returns `true` for the first one so only that one is logged as a warning.
+    pub fn record_warning(&self) -> bool {
+        self.warnings.fetch_add(1, Ordering::Relaxed) == 0
+    }
+}
+
+/// Entity counts and cap counters of one chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkLimitSnapshot {
+    /// The chunk's position.
+    pub pos: ChunkPos,
+    /// Entities counted against the cap.
+    pub entities: usize,
+    /// Block entities in the chunk.
+    pub block_entities: usize,
+    /// Entities refused since the chunk was loaded.
+    pub refused_entities: u32,
+    /// Item entities removed to make room.
+    pub dropped_items: u32,
+    /// Block entities refused.
+    pub refused_block_entities: u32,
+    /// Overflows let through by the `warn` policy.
+    pub warnings: u32,
+}
+
+impl ChunkLimitSnapshot {
+    /// Takes a snapshot of `stats` for a chunk with the given counts.
+    #[must_use]
+    pub fn new(
+        pos: ChunkPos,
+        entities: usize,
+        block_entities: usize,
+        stats: &ChunkLimitStats,
+    ) -> Self {
+        Self {
+            pos,
+            entities,
+            block_entities,
+            refused_entities: stats.refused_entities.load(Ordering::Relaxed),
+            dropped_items: stats.dropped_items.load(Ordering::Relaxed),
+            refused_block_entities: stats.refused_block_entities.load(Ordering::Relaxed),
+            warnings: stats.warnings.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(
+        max_entities: Option<u32>,
+        entity_overflow: EntityOverflowPolicy,
+    ) -> ChunkLimitsConfig {
+        ChunkLimitsConfig {
+            max_entities,
+            entity_overflow,
+            max_block_entities: Some(2),
+            block_entity_overflow: BlockEntityOverflowPolicy::RefuseNew,
+        }
+    }
+
+    #[test]
+    fn test_admit_entity() {
+        let unlimited = config(None, EntityOverflowPolicy::RefuseNew);
+        assert_eq!(admit_entity(&unlimited, 10_000), EntityAdmission::Allow);
+
+        let refuse = config(Some(4), EntityOverflowPolicy::RefuseNew);
+        assert_eq!(admit_entity(&refuse, 3), EntityAdmission::Allow);
+        assert_eq!(admit_entity(&refuse, 4), EntityAdmission::Refuse);
+
+        let warn = config(Some(4), EntityOverflowPolicy::Warn);
+        assert_eq!(admit_entity(&warn, 4), EntityAdmission::Warn);
+
+        // Chunks loaded above the cap drop enough items to get back under it
+        let drop = config(Some(4), EntityOverflowPolicy::DropOldestItems);
+        assert_eq!(admit_entity(&drop, 4), EntityAdmission::DropItems(1));
+        assert_eq!(admit_entity(&drop, 6), EntityAdmission::DropItems(3));
+    }
+
+    #[test]
+    fn test_block_entity_overflow() {
+        let limits = config(None, EntityOverflowPolicy::Warn);
+        assert_eq!(block_entity_overflow(&limits, 1), None);
+        assert_eq!(
+            block_entity_overflow(&limits, 2),
+            Some(BlockEntityOverflowPolicy::RefuseNew)
+        );
+        assert_eq!(
+            block_entity_overflow(&ChunkLimitsConfig::default(), usize::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stats_warn_once() {
+        let stats = ChunkLimitStats::default();
+        assert!(stats.record_warning());
+        assert!(!stats.record_warning());
+        stats.record_dropped_items(3);
+
+        let snapshot = ChunkLimitSnapshot::new(ChunkPos::new(1, 2), 5, 0, &stats);
+        assert_eq!(snapshot.warnings, 2);
+        assert_eq!(snapshot.dropped_items, 3);
+    }
+}
@@ -2,45 +2,51 @@ use rayon::{
     ThreadPool,
     iter::{IntoParallelIterator, ParallelIterator},
 };
-use rustc_hash::FxBuildHasher;
+use rustc_hash::{FxBuildHasher, FxHashSet};
 use std::{
+    collections::VecDeque,
     io, mem,
     sync::{
         Arc, Weak,
         atomic::{AtomicUsize, Ordering},
     },
+    thread,
     time::{Duration, Instant},
 };
 use steel_protocol::packet_traits::EncodedPacket;
 use steel_protocol::packets::game::{
-    BlockChange, CBlockUpdate, CSectionBlocksUpdate, CSetChunkCenter,
+    BlockChange, CBlockEntityData, CBlockUpdate, CSectionBlocksUpdate, CSetChunkCenter,
 };
 use steel_protocol::utils::ConnectionProtocol;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::dimension_type::DimensionTypeRef;
+use steel_utils::serial::OptionalNbt;
 use steel_utils::{BlockPos, ChunkPos, SectionPos, locks::SyncMutex};
 use tokio::runtime::Runtime;
+use tokio::task::spawn_blocking;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 use tracing::instrument;
 
 use crate::behavior::BlockStateBehaviorExt;
 use crate::behavior::{BLOCK_BEHAVIORS, FLUID_BEHAVIORS};
+use crate::chunk::chunk_access::ChunkAccess;
 use crate::chunk::chunk_holder::ChunkHolder;
 use crate::chunk::chunk_ticket_manager::{
     ChunkTicketManager, LevelChange, MAX_VIEW_DISTANCE, is_full,
 };
 use crate::chunk::player_chunk_view::PlayerChunkView;
 use crate::chunk::world_gen_context::ChunkGeneratorType;
-use crate::chunk::{chunk_access::ChunkAccess, chunk_ticket_manager::is_ticked};
 use crate::chunk::{
     chunk_access::ChunkStatus, chunk_generation_task::ChunkGenerationTask,
     world_gen_context::WorldGenContext,
 };
-use crate::chunk_saver::ChunkStorage;
+use crate::chunk_saver::{ChunkSaveMetrics, ChunkStorage};
 use crate::config::STEEL_CONFIG;
+use crate::entity::activation::EntityActivation;
 use crate::player::Player;
 use crate::player::connection::NetworkConnection;
+use crate::server::tick_profiler::TICK_PROFILER;
 use crate::world::World;
 use crate::world::tick_scheduler::{BlockTick, FluidTick};
 
@@ -61,12 +67,26 @@ pub struct ChunkMapTickTimings {
     pub broadcast_changes: Duration,
     /// Time spent processing chunk unloads.
     pub process_unloads: Duration,
+    /// Time spent snapshotting chunks for autosave.
+    pub autosave: Duration,
     /// Time spent collecting tickable chunks.
     pub collect_tickable: Duration,
-    /// Time spent ticking chunks (random ticks, etc.).
+    /// Time spent ticking chunks (block entities, entities and random ticks).
     pub tick_chunks: Duration,
+    /// The part of `tick_chunks` spent ticking entities. Only measured while the
+    /// tick profiler runs.
+    pub entity_tick: Duration,
+    /// Time spent running scheduled block and fluid ticks.
+    pub scheduled_ticks: Duration,
+    /// How long each chunk took to tick. Only measured while the tick profiler
+    /// runs.
+    pub chunk_times: Vec<(ChunkPos, Duration)>,
     /// Number of chunks that were ticked.
     pub tickable_count: usize,
+    /// Number of entities that ticked.
+    pub active_entities: usize,
+    /// Number of entities skipped because they were out of activation range.
+    pub inactive_entities: usize,
     /// Total number of loaded chunks.
     pub total_chunks: usize,
 }
@@ -83,6 +103,12 @@ pub struct ChunkMap {
     pub task_tracker: TaskTracker,
     /// Manager for chunk distances and tickets.
     pub chunk_tickets: SyncMutex<ChunkTicketManager>,
+    /// Tickets for the chunks within simulation distance of a player, and of
+    /// other simulating tickets like the dragon fight's. Chunks whose level here
+    /// is full get block ticks, random ticks and entity ticks.
+    ///
+    /// Vanilla: `DistanceManager.simulationChunkTracker`.
+    pub simulation_tickets: SyncMutex<ChunkTicketManager>,
     /// The world generation context.
     pub world_gen_context: Arc<WorldGenContext>,
     /// The thread pool to use for chunk generation (throughput-oriented).
@@ -97,6 +123,12 @@ pub struct ChunkMap {
     pub chunks_to_broadcast: SyncMutex<Vec<Arc<ChunkHolder>>>,
     /// Last length of `tickable_chunks` to pre-allocate with appropriate capacity.
     last_tickable_len: AtomicUsize,
+    /// Chunks left to save in the current autosave.
+    autosave_queue: SyncMutex<VecDeque<ChunkPos>>,
+    /// Chunks whose save is compressing or writing on the chunk runtime.
+    saves_in_flight: SyncMutex<FxHashSet<ChunkPos>>,
+    /// Counters for the chunks saved while the server runs.
+    pub save_metrics: ChunkSaveMetrics,
     /// Parent cancellation token for all generation tasks.
     /// Child tokens are created per-task; cancelling this cancels everything.
     pub cancel_token: CancellationToken,
@@ -121,12 +153,16 @@ impl ChunkMap {
             pending_generation_tasks: SyncMutex::new(Vec::new()),
             task_tracker: TaskTracker::new(),
             chunk_tickets: SyncMutex::new(ChunkTicketManager::new()),
+            simulation_tickets: SyncMutex::new(ChunkTicketManager::new()),
             world_gen_context: Arc::new(WorldGenContext::new(generator, world)),
             generation_pool,
             chunk_runtime,
             storage,
             chunks_to_broadcast: SyncMutex::new(Vec::new()),
             last_tickable_len: AtomicUsize::new(0),
+            autosave_queue: SyncMutex::new(VecDeque::new()),
+            saves_in_flight: SyncMutex::new(FxHashSet::default()),
+            save_metrics: ChunkSaveMetrics::default(),
             cancel_token: CancellationToken::new(),
         }
     }
@@ -227,6 +263,7 @@ impl ChunkMap {
                             player.connection.send_encoded(encoded.clone());
                         }
                     }
+                    Self::broadcast_block_entity(&world, block_pos, &tracking_players);
                 } else {
                     // Multiple block changes - use CSectionBlocksUpdate
                     let changes: Vec<BlockChange> = changed_positions
@@ -267,11 +304,47 @@ impl ChunkMap {
                             player.connection.send_encoded(encoded.clone());
                         }
                     }
+                    for &packed in &changed_positions {
+                        let block_pos = section_pos.relative_to_block_pos(packed);
+                        Self::broadcast_block_entity(&world, block_pos, &tracking_players);
+                    }
                 }
             }
         }
     }
 
+    /// Sends the client data of the block entity at `pos`, if it has any.
+    ///
+    /// Vanilla: `ChunkHolder.broadcastBlockEntityIfNeeded()`.
+    fn broadcast_block_entity(world: &World, pos: BlockPos, tracking_players: &[i32]) {
+        let Some(block_entity) = world.get_block_entity(pos) else {
+            return;
+        };
+        let (block_entity_type, update_tag) = {
+            let guard = block_entity.lock();
+            (guard.get_type(), guard.get_update_tag())
+        };
+        let Some(nbt) = update_tag else {
+            return;
+        };
+        let packet = CBlockEntityData {
+            pos,
+            block_entity_type: block_entity_type.id() as i32,
+            nbt: OptionalNbt(Some(nbt)),
+        };
+        let Ok(encoded) =
+            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
+        else {
+            log::warn!("Failed to encode block entity data packet");
+            return;
+        };
+        for entity_id in tracking_players {
+            if let Some(player) = world.players.get_by_entity_id(*entity_id) {
+                player.connection.send_encoded(encoded.clone());
+            }
+        }
+    }
+
     /// Schedules a new generation task.
     #[inline]
     #[instrument(level = "trace", skip(self), fields(chunk = ?pos, target = ?target_status))]
@@ -474,6 +547,13 @@ impl ChunkMap {
             timings.process_unloads = start.elapsed();
         }
 
+        {
+            let _span = tracing::trace_span!("autosave").entered();
+            let start = Instant::now();
+            self.autosave(tick_count);
+            timings.autosave = start.elapsed();
+        }
+
         if tick_count.is_multiple_of(100) {
             tracing::debug!(
                 chunks = self.chunks.len(),
@@ -490,17 +570,14 @@ impl ChunkMap {
         {
             let _span = tracing::trace_span!("collect_tickable").entered();
             let start = Instant::now();
-            let mut total_chunks = 0;
+            let total_chunks = self.chunks.len();
             let last_len = self.last_tickable_len.load(Ordering::Relaxed);
             let mut tickable_chunks = Vec::with_capacity(last_len);
-            self.chunks.iter_sync(|_, holder| {
-                total_chunks += 1;
-                let level = holder.ticket_level.load(Ordering::Relaxed);
-                if is_ticked(level) {
-                    tickable_chunks.push(holder.clone());
+            for pos in self.simulated_positions() {
+                if let Some(holder) = self.chunks.read_sync(&pos, |_, holder| holder.clone()) {
+                    tickable_chunks.push(holder);
                 }
-                true
-            });
+            }
             self.last_tickable_len
                 .store(tickable_chunks.len(), Ordering::Relaxed);
             timings.collect_tickable = start.elapsed();
@@ -515,23 +592,40 @@ impl ChunkMap {
                 )
                 .entered();
                 let start = Instant::now();
+                let profiling = TICK_PROFILER.is_running();
+                let mut activation = EntityActivation::new(world, tick_count as i32);
                 // TODO: In the future we might want to tick different regions/islands in parallel
                 for holder in &tickable_chunks {
                     if let Some(chunk_guard) = holder.try_chunk(ChunkStatus::Full) {
+                        let chunk_start = profiling.then(Instant::now);
+                        if world.is_player_close_enough_for_spawning(chunk_guard.pos()) {
+                            chunk_guard.increment_inhabited_time(1);
+                        }
                         chunk_guard.tick(
                             random_tick_speed,
                             tick_count as i32,
                             &mut ready_block_ticks,
                             &mut ready_fluid_ticks,
+                            profiling.then_some(&mut timings.entity_tick),
+                            &mut activation,
                         );
+                        if let Some(chunk_start) = chunk_start {
+                            timings
+                                .chunk_times
+                                .push((holder.get_pos(), chunk_start.elapsed()));
+                        }
                     }
                 }
                 timings.tick_chunks = start.elapsed();
+                timings.active_entities = activation.active;
+                timings.inactive_entities = activation.inactive;
             }
         }
 
         // Execute scheduled ticks collected during chunk ticking
+        let start = Instant::now();
         Self::execute_scheduled_ticks(world, ready_block_ticks, ready_fluid_ticks);
+        timings.scheduled_ticks = start.elapsed();
 
         timings
     }
@@ -587,46 +681,109 @@ impl ChunkMap {
         }
     }
 
-    /// Saves a chunk to disk. Does not remove from `unloading_chunks`.
+    /// Snapshots a chunk on the tick thread, then packs, compresses and
+    /// writes it on the chunk runtime. Does not remove from `unloading_chunks`.
+    ///
+    /// Returns whether a save started; not if the chunk is clean, at the Empty
+    /// stage or still being written by an earlier save.
     #[instrument(level = "trace", skip(self, chunk_holder), fields(chunk = ?chunk_holder.get_pos()))]
-    async fn save_chunk(&self, chunk_holder: &Arc<ChunkHolder>) {
-        // Prepare chunk data while holding the lock, then release before async I/O
-        let prepared = {
+    fn save_in_background(self: &Arc<Self>, chunk_holder: &Arc<ChunkHolder>) -> bool {
+        let pos = chunk_holder.get_pos();
+        if self.saves_in_flight.lock().contains(&pos) {
+            return false;
+        }
+
+        let (snapshot, status) = {
             let Some(chunk_guard) = chunk_holder.try_chunk(ChunkStatus::StructureStarts) else {
                 // Chunk was at Empty stage so no need to save it
-                return;
+                return false;
             };
 
             let status = chunk_holder
                 .persisted_status()
                 .expect("The check above confirmed it exists");
 
-            let prepared = ChunkStorage::prepare_chunk_save(&chunk_guard);
-
-            // Clear dirty flag while we still have the lock (only if we're actually saving)
-            if prepared.is_some() {
-                chunk_guard.clear_dirty();
-            }
-
-            (prepared, status)
+            let start = Instant::now();
+            let Some(snapshot) = ChunkStorage::snapshot_chunk(&chunk_guard) else {
+                return false;
+            };
+            // Clear dirty flag while we still have the lock
+            chunk_guard.clear_dirty();
+            self.save_metrics.record_snapshot(start.elapsed());
+            (snapshot, status)
         }; // chunk_guard dropped here
 
-        let (prepared, status) = prepared;
+        self.saves_in_flight.lock().insert(pos);
+        let map = self.clone();
+        // Holding the holder keeps an unloading chunk until it's written
+        let holder = chunk_holder.clone();
+        self.task_tracker.spawn_on(
+            async move {
+                let start = Instant::now();
+                let result = match spawn_blocking(move || snapshot.into_prepared()).await {
+                    Ok(prepared) => map.storage.save_chunk_data(prepared, status).await,
+                    Err(e) => Err(io::Error::other(e)),
+                };
+                match result {
+                    Ok(_) => map.save_metrics.record_saved(start.elapsed()),
+                    Err(e) => {
+                        tracing::error!(?pos, "Error saving chunk: {e}");
+                        map.save_metrics.record_failed();
+                    }
+                }
+                map.saves_in_flight.lock().remove(&pos);
+                drop(holder);
+            },
+            self.chunk_runtime.handle(),
+        );
+        true
+    }
+
+    /// Whether fewer saves than `autosave.max_in_flight` are running.
+    fn can_start_save(&self) -> bool {
+        self.saves_in_flight.lock().len() < STEEL_CONFIG.autosave.max_in_flight
+    }
 
-        // Save chunk data if dirty
-        if let Some(prepared) = prepared {
-            let result = self.storage.save_chunk_data(prepared, status).await;
+    /// Queues every loaded chunk once per autosave interval, then saves queued
+    /// chunks while the in-flight budget allows. A big autosave is spread over
+    /// several ticks rather than stalling one.
+    fn autosave(self: &Arc<Self>, tick_count: u64) {
+        let interval = STEEL_CONFIG.autosave.interval;
+        let mut queue = self.autosave_queue.lock();
+        if interval > 0 && tick_count.is_multiple_of(interval) && queue.is_empty() {
+            self.chunks.iter_sync(|pos, _| {
+                queue.push_back(*pos);
+                true
+            });
+        }
 
-            if let Err(e) = result {
-                tracing::error!("Error saving chunk: {e}");
+        while self.can_start_save()
+            && let Some(pos) = queue.pop_front()
+        {
+            // Chunks that unloaded meanwhile are saved by `process_unloads`
+            if let Some(holder) = self.chunks.read_sync(&pos, |_, holder| holder.clone()) {
+                self.save_in_background(&holder);
             }
         }
     }
 
+    /// How many chunk saves are compressing or writing right now.
+    #[must_use]
+    pub fn saves_in_flight(&self) -> usize {
+        self.saves_in_flight.lock().len()
+    }
+
+    /// How many chunks are left to check in the current autosave.
+    #[must_use]
+    pub fn autosave_queue_len(&self) -> usize {
+        self.autosave_queue.lock().len()
+    }
+
     /// Processes chunks that are pending unload.
     ///
     /// Iterates over `unloading_chunks`. For each chunk with `strong_count == 1`:
-    /// - If dirty: spawn save task (keep until saved and clean)
+    /// - If dirty: start a save if the in-flight budget allows (keep until saved
+    ///   and clean)
     /// - If not dirty: release region handle and remove
     #[instrument(level = "trace", skip(self))]
     pub fn process_unloads(self: &Arc<Self>) {
@@ -638,12 +795,11 @@ impl ChunkMap {
                     .is_some_and(|chunk| chunk.is_dirty());
 
                 if is_dirty {
-                    // Save the chunk, keep until next tick when it's clean
-                    let holder_clone = holder.clone();
-                    let map_clone = self.clone();
-                    self.task_tracker.spawn(async move {
-                        map_clone.save_chunk(&holder_clone).await;
-                    });
+                    // Save the chunk, keep until it's written and clean. Over
+                    // budget, the save waits for a later tick.
+                    if self.can_start_save() {
+                        self.save_in_background(holder);
+                    }
                     true // keep until clean
                 } else {
                     // Clean and no refs - release region handle and remove
@@ -667,6 +823,8 @@ impl ChunkMap {
         let current_chunk_pos = *player.last_chunk_pos.lock();
         let view_distance = player.view_distance();
 
+        self.update_simulation_ticket(player, current_chunk_pos);
+
         let new_view = PlayerChunkView::new(current_chunk_pos, view_distance);
         let mut last_view_guard = player.last_tracking_view.lock();
 
@@ -758,8 +916,104 @@ impl ChunkMap {
         }
     }
 
+    /// Moves the player's simulation ticket to `center` with their current
+    /// simulation distance, if either changed.
+    fn update_simulation_ticket(&self, player: &Player, center: ChunkPos) {
+        let new_view = PlayerChunkView::new(center, player.simulation_distance());
+        let mut last_view_guard = player.last_simulation_view.lock();
+        if last_view_guard.as_ref() == Some(&new_view) {
+            return;
+        }
+
+        let mut simulation_tickets = self.simulation_tickets.lock();
+        if let Some(last_view) = last_view_guard.as_ref() {
+            simulation_tickets.remove_ticket(
+                last_view.center,
+                MAX_VIEW_DISTANCE.saturating_sub(last_view.view_distance),
+            );
+        }
+        simulation_tickets.add_ticket(
+            new_view.center,
+            MAX_VIEW_DISTANCE.saturating_sub(new_view.view_distance),
+        );
+        *last_view_guard = Some(new_view);
+    }
+
+    /// Adds a ticket that loads the chunks around `pos` and ticks them, without
+    /// a player nearby.
+    ///
+    /// Vanilla: `DistanceManager.addTicket()` with a simulating ticket type.
+    pub fn add_simulating_ticket(&self, pos: ChunkPos, level: u8) {
+        self.chunk_tickets.lock().add_ticket(pos, level);
+        self.simulation_tickets.lock().add_ticket(pos, level);
+    }
+
+    /// Removes a ticket added by [`Self::add_simulating_ticket`].
+    ///
+    /// Vanilla: `DistanceManager.removeTicket()` with a simulating ticket type.
+    pub fn remove_simulating_ticket(&self, pos: ChunkPos, level: u8) {
+        self.chunk_tickets.lock().remove_ticket(pos, level);
+        self.simulation_tickets.lock().remove_ticket(pos, level);
+    }
+
+    /// Loads or generates the chunk at `pos` up to full status, blocking until
+    /// it's ready, and returns whether it got there. A full ticket keeps the
+    /// chunk loaded until [`Self::remove_full_ticket`] removes it.
+    ///
+    /// Vanilla: `ServerChunkCache.getChunk()` with `load = true`, which runs
+    /// the queued chunk tasks while it waits, as this runs the generation tasks.
+    pub fn load_full_chunk_blocking(self: &Arc<Self>, pos: ChunkPos) -> bool {
+        self.chunk_tickets.lock().add_ticket(pos, MAX_VIEW_DISTANCE);
+        let changes = self.chunk_tickets.lock().run_all_updates().to_vec();
+        for change in changes {
+            if let Some(holder) = self.update_chunk_level(change.pos, change.new_level)
+                && change.new_level.is_some_and(is_full)
+            {
+                holder.schedule_chunk_generation_task_b(ChunkStatus::Full, self);
+            }
+        }
+
+        let Some(holder) = self.chunks.read_sync(&pos, |_, holder| holder.clone()) else {
+            return false;
+        };
+        while holder.try_chunk(ChunkStatus::Full).is_none() {
+            if holder.is_status_disallowed(ChunkStatus::Full) || self.cancel_token.is_cancelled() {
+                return false;
+            }
+            // Neighbours needed by the generation are queued as new tasks
+            self.run_generation_tasks_b();
+            thread::sleep(Duration::from_millis(1));
+        }
+        true
+    }
+
+    /// Removes a ticket added by [`Self::load_full_chunk_blocking`].
+    pub fn remove_full_ticket(&self, pos: ChunkPos) {
+        self.chunk_tickets
+            .lock()
+            .remove_ticket(pos, MAX_VIEW_DISTANCE);
+    }
+
+    /// Returns the positions of the chunks to tick, whose simulation level is full.
+    fn simulated_positions(&self) -> Vec<ChunkPos> {
+        let mut simulation_tickets = self.simulation_tickets.lock();
+        simulation_tickets.run_all_updates();
+        simulation_tickets
+            .iter_levels()
+            .filter(|&(_, level)| is_full(level))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
     /// Removes a player from the chunk map.
     pub fn remove_player(&self, player: &Player) {
+        if let Some(last_view) = player.last_simulation_view.lock().take() {
+            self.simulation_tickets.lock().remove_ticket(
+                last_view.center,
+                MAX_VIEW_DISTANCE.saturating_sub(last_view.view_distance),
+            );
+        }
+
         // Okay to lock sync lock here cause it has low contention
         let mut last_view_guard = player.last_tracking_view.lock();
         if let Some(last_view) = last_view_guard.take() {
@@ -784,6 +1038,8 @@ impl ChunkMap {
     #[instrument(level = "info", skip(self), name = "save_all_chunks")]
     pub async fn save_all_chunks(self: &Arc<Self>) -> io::Result<usize> {
         let mut saved_count = 0;
+        // Everything is saved below anyway
+        self.autosave_queue.lock().clear();
 
         // Collect all chunks from both maps
         let all_chunks: Vec<Arc<ChunkHolder>> = {
@@ -841,3 +1097,52 @@ impl ChunkMap {
         Ok(saved_count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rayon::ThreadPoolBuilder;
+    use steel_registry::vanilla_dimension_types;
+
+    use super::*;
+    use crate::chunk::empty_chunk_generator::EmptyChunkGenerator;
+    use crate::chunk_saver::RamOnlyStorage;
+
+    fn test_chunk_map() -> ChunkMap {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime builds");
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .expect("thread pool builds");
+        ChunkMap::new_with_storage(
+            Arc::new(runtime),
+            Weak::new(),
+            vanilla_dimension_types::OVERWORLD,
+            Arc::new(ChunkStorage::RamOnly(RamOnlyStorage::empty_world())),
+            Arc::new(ChunkGeneratorType::Empty(EmptyChunkGenerator::new())),
+            Arc::new(pool),
+        )
+    }
+
+    #[test]
+    fn simulating_tickets_tick_their_chunks() {
+        let chunk_map = test_chunk_map();
+        let (pos, level) = (ChunkPos::new(0, 0), MAX_VIEW_DISTANCE - 2);
+        chunk_map.add_simulating_ticket(pos, level);
+
+        let ticking: FxHashSet<ChunkPos> = chunk_map.simulated_positions().into_iter().collect();
+        assert!(ticking.contains(&pos));
+        assert!(ticking.contains(&ChunkPos::new(2, -2)));
+        assert!(!ticking.contains(&ChunkPos::new(3, 0)));
+        assert_eq!(
+            chunk_map.chunk_tickets.lock().get_ticket(pos),
+            Some(level),
+            "the chunks are loaded too"
+        );
+
+        chunk_map.remove_simulating_ticket(pos, level);
+        assert!(chunk_map.simulated_positions().is_empty());
+        assert_eq!(chunk_map.chunk_tickets.lock().get_ticket(pos), None);
+    }
+}
@@ -6,6 +6,7 @@
 use std::sync::Arc;
 
 use crate::chunk::{
+    blending::blender_for,
     chunk_access::{ChunkAccess, ChunkStatus},
     chunk_generation_task::StaticCache2D,
     chunk_generator::ChunkGenerator,
@@ -15,6 +16,9 @@ use crate::chunk::{
     section::{ChunkSection, Sections},
     world_gen_context::WorldGenContext,
 };
+use crate::event::{ChunkLoadEvent, EVENTS};
+use crate::world::structure::StructureStart;
+use crate::worldgen::Structure;
 
 pub struct ChunkStatusTasks;
 
@@ -48,19 +52,64 @@ impl ChunkStatusTasks {
     /// # Panics
     /// Panics if the chunk is not at `ChunkStatus::Empty` or higher.
     pub fn generate_structure_starts(
-        _context: Arc<WorldGenContext>,
+        context: Arc<WorldGenContext>,
         _step: &ChunkStep,
         _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
-        _holder: Arc<ChunkHolder>,
+        holder: Arc<ChunkHolder>,
     ) {
+        let chunk = holder
+            .try_chunk(ChunkStatus::Empty)
+            .expect("Chunk not found at status Empty");
+        context.generator.create_structures(&chunk);
     }
 
+    /// Records which nearby structure starts reach into the chunk.
+    ///
+    /// Vanilla: `ChunkGenerator.createReferences()`.
+    ///
+    /// # Panics
+    /// Panics if the chunk or a neighbor within 8 chunks is not at
+    /// `ChunkStatus::StructureStarts` or higher.
     pub fn generate_structure_references(
         _context: Arc<WorldGenContext>,
         _step: &ChunkStep,
-        _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
-        _holder: Arc<ChunkHolder>,
+        cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
+        holder: Arc<ChunkHolder>,
     ) {
+        let chunk = holder
+            .try_chunk(ChunkStatus::StructureStarts)
+            .expect("Chunk not found at status StructureStarts");
+        let pos = chunk.pos();
+        let min_block_x = pos.0.x * 16;
+        let min_block_z = pos.0.y * 16;
+
+        for x in pos.0.x - 8..=pos.0.x + 8 {
+            for z in pos.0.y - 8..=pos.0.y + 8 {
+                let source = cache.get(x, z);
+                let source_chunk = source
+                    .try_chunk(ChunkStatus::StructureStarts)
+                    .expect("Neighbor not at StructureStarts status");
+                let source_pos = source_chunk.pos();
+                for (structure, start) in source_chunk.structure_starts().iter() {
+                    let reaches_chunk = start.bounding_box().is_some_and(|bounding_box| {
+                        bounding_box.intersects_xz(
+                            min_block_x,
+                            min_block_z,
+                            min_block_x + 15,
+                            min_block_z + 15,
+                        )
+                    });
+                    if !reaches_chunk {
+                        continue;
+                    }
+                    let mut references = chunk.structure_references_mut();
+                    let sources = references.entry(structure.clone()).or_default();
+                    if !sources.contains(&source_pos) {
+                        sources.push(source_pos);
+                    }
+                }
+            }
+        }
     }
 
     pub fn load_structure_starts(
@@ -76,14 +125,15 @@ impl ChunkStatusTasks {
     pub fn generate_biomes(
         context: Arc<WorldGenContext>,
         _step: &ChunkStep,
-        _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
+        cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
         holder: Arc<ChunkHolder>,
     ) {
         let chunk = holder
             .try_chunk(ChunkStatus::StructureReferences)
             .expect("Chunk not found at status StructureReferences");
 
-        context.generator.create_biomes(&chunk);
+        let blender = blender_for(cache, chunk.pos());
+        context.generator.create_biomes(&chunk, blender.as_ref());
     }
 
     #[expect(
@@ -93,13 +143,19 @@ impl ChunkStatusTasks {
     pub fn generate_noise(
         context: Arc<WorldGenContext>,
         _step: &ChunkStep,
-        _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
+        cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
         holder: Arc<ChunkHolder>,
     ) {
         let chunk = holder
             .try_chunk(ChunkStatus::Biomes)
             .expect("Chunk not found at status Biomes");
-        context.generator.fill_from_noise(&chunk);
+        let blender = blender_for(cache, chunk.pos());
+        let structures = referenced_starts(cache, &chunk);
+        context
+            .generator
+            .fill_from_noise(&chunk, &structures, blender.as_ref());
+        // TODO: Mask carvers around old chunks (Blender.addAroundOldChunksCarvingMaskFilter)
+        // TODO: Schedule border ticks between old and new chunks (Blender.generateBorderTicks)
     }
 
     /// # Panics
@@ -140,7 +196,10 @@ impl ChunkStatusTasks {
                 .get(local_qx, local_qy, local_qz)
         };
 
-        context.generator.build_surface(&chunk, &neighbor_biomes);
+        let blender = blender_for(cache, chunk.pos());
+        context
+            .generator
+            .build_surface(&chunk, &neighbor_biomes, blender.as_ref());
     }
 
     // TODO: Wire up to context.generator.apply_carvers() once carver generation is implemented
@@ -152,13 +211,21 @@ impl ChunkStatusTasks {
     ) {
     }
 
-    // TODO: Wire up to context.generator.apply_biome_decorations() once feature generation is implemented
+    /// # Panics
+    /// Panics if the chunk has not reached `ChunkStatus::Carvers`.
     pub fn generate_features(
-        _context: Arc<WorldGenContext>,
+        context: Arc<WorldGenContext>,
         _step: &ChunkStep,
-        _cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
-        _holder: Arc<ChunkHolder>,
+        cache: &Arc<StaticCache2D<Arc<ChunkHolder>>>,
+        holder: Arc<ChunkHolder>,
     ) {
+        let chunk = holder
+            .try_chunk(ChunkStatus::Carvers)
+            .expect("Chunk not found at status Carvers");
+        let structures = referenced_starts(cache, &chunk);
+        context
+            .generator
+            .apply_biome_decorations(&chunk, &structures);
     }
 
     pub fn initialize_light(
@@ -193,5 +260,41 @@ impl ChunkStatusTasks {
     ) {
         //log::info!("Chunk {:?} upgraded to full", holder.get_pos());
         holder.upgrade_to_full(context.weak_world());
+        if EVENTS.has_listeners::<ChunkLoadEvent>()
+            && let Some(world) = context.weak_world().upgrade()
+        {
+            EVENTS.post(&mut ChunkLoadEvent {
+                world,
+                pos: holder.get_pos(),
+            });
+        }
+    }
+}
+
+/// Gets the structure starts `chunk` references, in structure registry order.
+///
+/// Vanilla: `StructureManager.startsForStructure()`.
+fn referenced_starts(
+    cache: &StaticCache2D<Arc<ChunkHolder>>,
+    chunk: &ChunkAccess,
+) -> Vec<StructureStart> {
+    let references = chunk.structure_references();
+    let mut starts = Vec::new();
+    for structure in Structure::all() {
+        let Some(sources) = references.get(&structure.id) else {
+            continue;
+        };
+        for source in sources {
+            let Some(source_chunk) = cache
+                .get(source.0.x, source.0.y)
+                .try_chunk(ChunkStatus::StructureStarts)
+            else {
+                continue;
+            };
+            if let Some(start) = source_chunk.structure_starts().get(&structure.id) {
+                starts.push(start.clone());
+            }
+        }
     }
+    starts
 }
@@ -1,4 +1,9 @@
+use std::sync::Arc;
+
+use steel_utils::density::Blender;
+
 use crate::chunk::{chunk_access::ChunkAccess, chunk_generator::ChunkGenerator};
+use crate::world::structure::StructureStart;
 
 /// A chunk generator that generates an empty world.
 #[derive(Default)]
@@ -15,14 +20,25 @@ impl EmptyChunkGenerator {
 impl ChunkGenerator for EmptyChunkGenerator {
     fn create_structures(&self, _chunk: &ChunkAccess) {}
 
-    fn create_biomes(&self, _chunk: &ChunkAccess) {}
+    fn create_biomes(&self, _chunk: &ChunkAccess, _blender: Option<&Arc<Blender>>) {}
 
-    fn fill_from_noise(&self, _chunk: &ChunkAccess) {}
+    fn fill_from_noise(
+        &self,
+        _chunk: &ChunkAccess,
+        _structures: &[StructureStart],
+        _blender: Option<&Arc<Blender>>,
+    ) {
+    }
 
-    fn build_surface(&self, _chunk: &ChunkAccess, _neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16) {
+    fn build_surface(
+        &self,
+        _chunk: &ChunkAccess,
+        _neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16,
+        _blender: Option<&Arc<Blender>>,
+    ) {
     }
 
     fn apply_carvers(&self, _chunk: &ChunkAccess) {}
 
-    fn apply_biome_decorations(&self, _chunk: &ChunkAccess) {}
+    fn apply_biome_decorations(&self, _chunk: &ChunkAccess, _structures: &[StructureStart]) {}
 }
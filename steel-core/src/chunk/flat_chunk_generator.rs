@@ -1,7 +1,11 @@
+use std::sync::Arc;
+
 use steel_registry::{REGISTRY, RegistryExt};
+use steel_utils::density::Blender;
 use steel_utils::{BlockStateId, Identifier};
 
 use crate::chunk::{chunk_access::ChunkAccess, chunk_generator::ChunkGenerator};
+use crate::world::structure::StructureStart;
 
 /// A chunk generator that generates a flat world.
 ///
@@ -39,7 +43,7 @@ impl FlatChunkGenerator {
 impl ChunkGenerator for FlatChunkGenerator {
     fn create_structures(&self, _chunk: &ChunkAccess) {}
 
-    fn create_biomes(&self, chunk: &ChunkAccess) {
+    fn create_biomes(&self, chunk: &ChunkAccess, _blender: Option<&Arc<Blender>>) {
         let section_count = chunk.sections().sections.len();
 
         for section_index in 0..section_count {
@@ -64,7 +68,12 @@ impl ChunkGenerator for FlatChunkGenerator {
         chunk.mark_dirty();
     }
 
-    fn fill_from_noise(&self, chunk: &ChunkAccess) {
+    fn fill_from_noise(
+        &self,
+        chunk: &ChunkAccess,
+        _structures: &[StructureStart],
+        _blender: Option<&Arc<Blender>>,
+    ) {
         // Layers:
         // 0: Bedrock
         // 1-2: Dirt
@@ -89,10 +98,15 @@ impl ChunkGenerator for FlatChunkGenerator {
         }
     }
 
-    fn build_surface(&self, _chunk: &ChunkAccess, _neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16) {
+    fn build_surface(
+        &self,
+        _chunk: &ChunkAccess,
+        _neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16,
+        _blender: Option<&Arc<Blender>>,
+    ) {
     }
 
     fn apply_carvers(&self, _chunk: &ChunkAccess) {}
 
-    fn apply_biome_decorations(&self, _chunk: &ChunkAccess) {}
+    fn apply_biome_decorations(&self, _chunk: &ChunkAccess, _structures: &[StructureStart]) {}
 }
@@ -50,6 +50,31 @@ impl HeightmapType {
         ]
     }
 
+    /// Gets the name data packs and saves use, like `WORLD_SURFACE_WG`.
+    ///
+    /// Vanilla: `Heightmap.Types.getSerializationKey()`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::WorldSurface => "WORLD_SURFACE",
+            Self::MotionBlocking => "MOTION_BLOCKING",
+            Self::MotionBlockingNoLeaves => "MOTION_BLOCKING_NO_LEAVES",
+            Self::OceanFloor => "OCEAN_FLOOR",
+            Self::WorldSurfaceWg => "WORLD_SURFACE_WG",
+            Self::OceanFloorWg => "OCEAN_FLOOR_WG",
+        }
+    }
+
+    /// Gets the heightmap type with the given name.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::worldgen_types()
+            .iter()
+            .chain(Self::final_types())
+            .copied()
+            .find(|heightmap_type| heightmap_type.name() == name)
+    }
+
     /// Returns whether a block is "opaque" for this heightmap type.
     /// This determines whether the block counts towards the heightmap.
     ///
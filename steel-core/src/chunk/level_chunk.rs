@@ -3,8 +3,9 @@ use std::{
     io::Cursor,
     sync::{
         Arc, Weak,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicI64, Ordering},
     },
+    time::{Duration, Instant},
 };
 
 use rand::RngExt;
@@ -16,8 +17,8 @@ use steel_registry::{
     REGISTRY, RegistryEntry, blocks::block_state_ext::BlockStateExt, vanilla_blocks,
 };
 use steel_utils::{
-    BlockPos, BlockStateId, ChunkPos, SectionPos, codec::BitSet, locks::SyncRwLock,
-    types::UpdateFlags,
+    BlockPos, BlockStateId, ChunkPos, SectionPos, codec::BitSet, density::BlendingData,
+    locks::SyncRwLock, nbt::with_borrowed, types::UpdateFlags,
 };
 
 use steel_utils::locks::SyncMutex;
@@ -25,11 +26,14 @@ use steel_utils::locks::SyncMutex;
 use crate::behavior::BLOCK_BEHAVIORS;
 use crate::block_entity::{BlockEntityStorage, SharedBlockEntity};
 use crate::chunk::{
+    chunk_limits::{self, ChunkLimitSnapshot, ChunkLimitStats, EntityAdmission},
     heightmap::{ChunkHeightmaps, HeightmapType},
     proto_chunk::ProtoChunk,
     section::Sections,
 };
-use crate::entity::{EntityStorage, SharedEntity};
+use crate::config::{BlockEntityOverflowPolicy, STEEL_CONFIG};
+use crate::entity::activation::EntityActivation;
+use crate::entity::{ENTITIES, Entity, EntityStorage, RemovalReason, SharedEntity, next_entity_id};
 use crate::world::World;
 use crate::world::structure::{StructureReferenceMap, StructureStartMap};
 use crate::world::tick_scheduler::{BlockTick, BlockTickList, FluidTick, FluidTickList};
@@ -66,6 +70,12 @@ pub struct LevelChunk {
     pub structure_starts: SyncRwLock<StructureStartMap>,
     /// References to structures from nearby origin chunks (carried from proto).
     pub structure_references: SyncRwLock<StructureReferenceMap>,
+    /// Total ticks players have spent near this chunk (vanilla `InhabitedTime`).
+    pub inhabited_time: AtomicI64,
+    /// Border samples new terrain is blended into, if this chunk is from older generation.
+    pub blending_data: SyncMutex<Option<Arc<BlendingData>>>,
+    /// How often this chunk hit its entity and block entity caps.
+    pub limit_stats: ChunkLimitStats,
 }
 
 impl LevelChunk {
@@ -79,6 +89,8 @@ impl LevelChunk {
     /// * `random_tick_speed` - Number of random blocks to tick per section per tick.
     ///   This is controlled by the `randomTickSpeed` game rule.
     /// * `tick_count` - Current server tick count (for entity sync timing).
+    /// * `entity_time` - Adds the time spent ticking entities to this, if set.
+    /// * `activation` - Decides which entities tick and counts them.
     ///
     /// # Panics
     /// Panics if the block behavior registry has not been initialized.
@@ -88,6 +100,8 @@ impl LevelChunk {
         tick_count: i32,
         ready_block_ticks: &mut Vec<BlockTick>,
         ready_fluid_ticks: &mut Vec<FluidTick>,
+        entity_time: Option<&mut Duration>,
+        activation: &mut EntityActivation,
     ) {
         // Drain ready scheduled ticks (decrement delays, collect those at 0)
         ready_block_ticks.extend(self.block_ticks.lock().drain_ready());
@@ -98,7 +112,11 @@ impl LevelChunk {
 
         // Tick entities in this chunk
         if let Some(world) = self.get_level() {
-            let ticked_entities = self.entities.tick(&world, self.pos, tick_count);
+            let start = entity_time.is_some().then(Instant::now);
+            let ticked_entities = self.entities.tick(&world, self.pos, tick_count, activation);
+            if let (Some(entity_time), Some(start)) = (entity_time, start) {
+                *entity_time += start.elapsed();
+            }
             if ticked_entities {
                 // Mark chunk dirty since entity state may have changed
                 self.dirty.store(true, Ordering::Release);
@@ -195,10 +213,14 @@ impl LevelChunk {
 
         let structure_starts = proto_chunk.structure_starts.into_inner();
         let structure_references = proto_chunk.structure_references.into_inner();
+        let inhabited_time = proto_chunk.inhabited_time.into_inner();
+        let proto_entities = proto_chunk.entities.into_inner();
+        let proto_block_entities = proto_chunk.block_entities.into_inner();
+        let blending_data = proto_chunk.blending_data.into_inner();
 
         Self::populate_poi(&level, &proto_chunk.sections, proto_chunk.pos, min_y);
 
-        Self {
+        let chunk = Self {
             sections: proto_chunk.sections,
             pos: proto_chunk.pos,
             dirty: AtomicBool::new(proto_chunk.dirty.load(Ordering::Acquire)),
@@ -212,7 +234,40 @@ impl LevelChunk {
             fluid_ticks: SyncMutex::new(FluidTickList::new()),
             structure_starts: SyncRwLock::new(structure_starts),
             structure_references: SyncRwLock::new(structure_references),
+            inhabited_time: AtomicI64::new(inhabited_time),
+            blending_data: SyncMutex::new(blending_data),
+            limit_stats: ChunkLimitStats::default(),
+        };
+
+        // Vanilla: LevelChunk.promotePendingBlockEntity()
+        for (pos, nbt) in proto_block_entities {
+            let state = chunk.get_block_state(pos);
+            let behavior = BLOCK_BEHAVIORS.get_behavior(state.get_block());
+            if !behavior.has_block_entity() {
+                continue;
+            }
+            let Some(block_entity) = behavior.new_block_entity(chunk.level.clone(), pos, state)
+            else {
+                continue;
+            };
+            with_borrowed(&nbt, |base| block_entity.lock().load_additional(base)).ok();
+            chunk.add_and_register_block_entity(block_entity);
+        }
+
+        // Vanilla: ChunkStatusTasks.postLoadProtoChunk()
+        for proto_entity in proto_entities {
+            if let Some(entity) = ENTITIES.create(
+                proto_entity.entity_type,
+                next_entity_id(),
+                proto_entity.position,
+                chunk.level.clone(),
+            ) {
+                entity.set_rotation(proto_entity.rotation);
+                chunk.add_and_register_entity(entity);
+            }
         }
+
+        chunk
     }
 
     /// Creates a new `LevelChunk` that was loaded from disk (not dirty).
@@ -269,6 +324,9 @@ impl LevelChunk {
             fluid_ticks: SyncMutex::new(fluid_ticks),
             structure_starts: SyncRwLock::new(structure_starts),
             structure_references: SyncRwLock::new(structure_references),
+            inhabited_time: AtomicI64::new(0),
+            blending_data: SyncMutex::new(None),
+            limit_stats: ChunkLimitStats::default(),
         }
     }
 
@@ -398,6 +456,82 @@ impl LevelChunk {
         true
     }
 
+    /// Adds a newly spawned entity, applying the chunk's entity cap.
+    ///
+    /// Depending on the overflow policy a full chunk removes its oldest item
+    /// entities, refuses the entity, or lets it in with a warning. Players are
+    /// never refused. Returns `false` if the entity was not added.
+    pub fn spawn_entity(&self, entity: SharedEntity) -> bool {
+        if entity.clone().as_player().is_none() {
+            let limits = &STEEL_CONFIG.chunk_limits;
+            match chunk_limits::admit_entity(limits, self.entities.capped_len()) {
+                EntityAdmission::Allow => {}
+                EntityAdmission::Warn => {
+                    if self.limit_stats.record_warning() {
+                        log::warn!(
+                            "Chunk {:?} reached its cap of {:?} entities",
+                            self.pos,
+                            limits.max_entities
+                        );
+                    }
+                }
+                EntityAdmission::DropItems(count) => {
+                    let items = self.entities.oldest_items(count);
+                    if items.len() < count {
+                        self.limit_stats.record_refused_entity();
+                        return false;
+                    }
+                    for item in &items {
+                        item.set_removed(RemovalReason::Discarded);
+                    }
+                    self.limit_stats.record_dropped_items(count as u32);
+                }
+                EntityAdmission::Refuse => {
+                    self.limit_stats.record_refused_entity();
+                    return false;
+                }
+            }
+        }
+        self.add_and_register_entity(entity)
+    }
+
+    /// Checks the chunk's block entity cap before creating a block entity at `pos`.
+    fn admit_block_entity(&self, pos: BlockPos) -> bool {
+        let limits = &STEEL_CONFIG.chunk_limits;
+        match chunk_limits::block_entity_overflow(limits, self.block_entities.len()) {
+            None => true,
+            Some(BlockEntityOverflowPolicy::Warn) => {
+                if self.limit_stats.record_warning() {
+                    log::warn!(
+                        "Chunk {:?} reached its cap of {:?} block entities",
+                        self.pos,
+                        limits.max_block_entities
+                    );
+                }
+                true
+            }
+            Some(BlockEntityOverflowPolicy::RefuseNew) => {
+                log::debug!(
+                    "Refused block entity at {pos:?}, chunk {:?} is full",
+                    self.pos
+                );
+                self.limit_stats.record_refused_block_entity();
+                false
+            }
+        }
+    }
+
+    /// Returns the chunk's entity counts and how often it hit its caps.
+    #[must_use]
+    pub fn limit_snapshot(&self) -> ChunkLimitSnapshot {
+        ChunkLimitSnapshot::new(
+            self.pos,
+            self.entities.capped_len(),
+            self.block_entities.len(),
+            &self.limit_stats,
+        )
+    }
+
     /// Updates the ticking status of a block entity.
     ///
     /// Call this when a block entity's ticking status may have changed
@@ -587,8 +721,9 @@ impl LevelChunk {
                     self.update_block_entity_ticker(&existing);
                 } else {
                     // Create new block entity
-                    if let Some(entity) =
-                        new_behavior.new_block_entity(self.level.clone(), pos, state)
+                    if self.admit_block_entity(pos)
+                        && let Some(entity) =
+                            new_behavior.new_block_entity(self.level.clone(), pos, state)
                     {
                         self.add_and_register_block_entity(entity);
                     }
@@ -625,6 +760,36 @@ impl LevelChunk {
         section_guard.states.get(local_x, local_y, local_z)
     }
 
+    /// Gets the first free Y coordinate above the given heightmap at the column of `pos`.
+    #[must_use]
+    pub fn get_height(&self, heightmap_type: HeightmapType, pos: BlockPos) -> i32 {
+        let local_x = (pos.0.x & 15) as usize;
+        let local_z = (pos.0.z & 15) as usize;
+        self.heightmaps
+            .read()
+            .get(heightmap_type)
+            .get_first_available(local_x, local_z)
+    }
+
+    /// Gets the biome id stored in the 4x4x4 biome cell containing `pos`.
+    ///
+    /// Positions outside the build height are clamped to the bottom or top section.
+    #[must_use]
+    pub fn get_noise_biome(&self, pos: BlockPos) -> u16 {
+        let max_y = self.min_y + self.height - 1;
+        let y = pos.0.y.clamp(self.min_y, max_y);
+        let section_index = self.get_section_index(y);
+
+        let quart_x = ((pos.0.x >> 2) & 3) as usize;
+        let quart_y = ((y >> 2) & 3) as usize;
+        let quart_z = ((pos.0.z >> 2) & 3) as usize;
+
+        self.sections.sections[section_index]
+            .read()
+            .biomes
+            .get(quart_x, quart_y, quart_z)
+    }
+
     /// Extracts the chunk data for sending to the client.
     #[must_use]
     pub fn extract_chunk_data(&self) -> ChunkPacketData {
@@ -634,6 +799,10 @@ impl LevelChunk {
         self.sections.sections.iter().for_each(|section| {
             section.read().write(&mut cursor);
         });
+        crate::profiling::record_chunk_serialized(
+            cursor.get_ref().len(),
+            cursor.get_ref().capacity(),
+        );
 
         let heightmaps_guard = self.heightmaps.read();
 
@@ -4,10 +4,14 @@
 pub mod aquifer;
 /// Terrain density modification around structure pieces.
 pub mod beardifier;
+/// Blending generated terrain into chunks from older generation.
+pub mod blending;
 pub mod chunk_access;
 pub mod chunk_generation_task;
 pub mod chunk_generator;
 pub mod chunk_holder;
+/// Per-chunk entity and block entity caps.
+pub mod chunk_limits;
 /// The chunk map manages chunk loading, generation, and lifecycle.
 pub mod chunk_map;
 pub mod chunk_pyramid;
@@ -276,4 +276,98 @@ impl<N: DimensionNoises> NoiseChunk<N> {
             }
         }
     }
+
+    /// Walks the block column at `block_x`, `block_z` from the top down, giving
+    /// `visit` each Y and its final density until it returns `true`. Returns the Y
+    /// it stopped at. No beardifier or blender is applied.
+    ///
+    /// Vanilla: `NoiseBasedChunkGenerator.iterateNoiseColumn()`.
+    pub fn iterate_column<F>(
+        noises: &N,
+        cache: &mut N::ColumnCache,
+        block_x: i32,
+        block_z: i32,
+        mut visit: F,
+    ) -> Option<i32>
+    where
+        F: FnMut(i32, f64) -> bool,
+    {
+        let cell_width = N::Settings::CELL_WIDTH;
+        let cell_height = N::Settings::CELL_HEIGHT;
+        let cell_min_y = N::Settings::MIN_Y.div_euclid(cell_height);
+        let cell_count_y = (N::Settings::HEIGHT / cell_height) as usize;
+        let corners_y = cell_count_y + 1;
+        let interp_count = N::interpolated_count();
+
+        let cell_x = block_x.div_euclid(cell_width);
+        let cell_z = block_z.div_euclid(cell_width);
+        let factor_x = f64::from(block_x.rem_euclid(cell_width)) / f64::from(cell_width);
+        let factor_z = f64::from(block_z.rem_euclid(cell_width)) / f64::from(cell_width);
+
+        // The four corner columns of the cell, in (x, z) order 00, 01, 10, 11
+        let mut corners = vec![0.0f64; 4 * corners_y * interp_count];
+        for (column, (dx, dz)) in [(0, 0), (0, 1), (1, 0), (1, 1)].into_iter().enumerate() {
+            let x = (cell_x + dx) * cell_width;
+            let z = (cell_z + dz) * cell_width;
+            cache.ensure(x, z, noises);
+            for cy in 0..corners_y {
+                let y = (cell_min_y + cy as i32) * cell_height;
+                let start = (column * corners_y + cy) * interp_count;
+                noises.fill_cell_corner_densities(
+                    cache,
+                    x,
+                    y,
+                    z,
+                    &mut corners[start..start + interp_count],
+                );
+            }
+        }
+        let corner = |column: usize, cy: usize, ch: usize| {
+            corners[(column * corners_y + cy) * interp_count + ch]
+        };
+
+        let mut interpolated = [0.0f64; MAX_INTERP];
+        for cell_y_idx in (0..cell_count_y).rev() {
+            for y_in_cell in (0..cell_height).rev() {
+                let factor_y = f64::from(y_in_cell) / f64::from(cell_height);
+                let world_y = (cell_min_y + cell_y_idx as i32) * cell_height + y_in_cell;
+                for (ch, value) in interpolated[..interp_count].iter_mut().enumerate() {
+                    let d00 = lerp(
+                        factor_y,
+                        corner(0, cell_y_idx, ch),
+                        corner(0, cell_y_idx + 1, ch),
+                    );
+                    let d01 = lerp(
+                        factor_y,
+                        corner(1, cell_y_idx, ch),
+                        corner(1, cell_y_idx + 1, ch),
+                    );
+                    let d10 = lerp(
+                        factor_y,
+                        corner(2, cell_y_idx, ch),
+                        corner(2, cell_y_idx + 1, ch),
+                    );
+                    let d11 = lerp(
+                        factor_y,
+                        corner(3, cell_y_idx, ch),
+                        corner(3, cell_y_idx + 1, ch),
+                    );
+                    let d0 = lerp(factor_x, d00, d10);
+                    let d1 = lerp(factor_x, d01, d11);
+                    *value = lerp(factor_z, d0, d1);
+                }
+                let density = noises.combine_interpolated(
+                    cache,
+                    &interpolated[..interp_count],
+                    0,
+                    world_y,
+                    0,
+                );
+                if visit(world_y, density) {
+                    return Some(world_y);
+                }
+            }
+        }
+        None
+    }
 }
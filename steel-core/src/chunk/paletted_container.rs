@@ -1,4 +1,9 @@
-//! A paletted container is a container that can be either homogeneous or heterogeneous.
+//! A paletted container stores a cube of values, picking how to store them by
+//! how many different values it holds.
+//!
+//! A single value is stored once, a few values are stored as packed indices into
+//! a small palette, and many values are stored directly. Setting values promotes
+//! or demotes the container between these as needed.
 use std::{
     fmt::Debug,
     hash::Hash,
@@ -6,6 +11,7 @@ use std::{
 };
 
 use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::{REGISTRY, RegistryExt};
 use steel_utils::{BlockStateId, codec::VarInt, serial::WriteTo};
 
 /// A trait for converting a value to a global ID.
@@ -29,6 +35,212 @@ impl ToGlobalId for u16 {
 /// 3d array indexed by y,z,x
 type Cube<T, const DIM: usize> = [[[T; DIM]; DIM]; DIM];
 
+/// The most values a linear palette holds, vanilla's linear palette limit
+/// (4 bits for blocks, 3 bits for biomes).
+const fn max_linear_entries(dim: usize) -> usize {
+    if dim == 16 { 16 } else { 8 }
+}
+
+/// Bits per entry for a palette of `len` values. Vanilla never uses less than
+/// 4 bits for block palettes.
+const fn palette_bits(dim: usize, len: usize) -> u8 {
+    let bits = ceil_log2(len);
+    if dim == 16 && bits < 4 {
+        4
+    } else if bits == 0 {
+        1
+    } else {
+        bits
+    }
+}
+
+/// Vanilla: `Mth.ceillog2()`.
+const fn ceil_log2(value: usize) -> u8 {
+    if value <= 1 {
+        0
+    } else {
+        (usize::BITS - (value - 1).leading_zeros()) as u8
+    }
+}
+
+/// Small unsigned values packed into longs, without any value spanning two
+/// longs.
+///
+/// Vanilla: `SimpleBitStorage`.
+#[derive(Debug, Clone)]
+pub struct BitStorage {
+    bits: u8,
+    data: Box<[u64]>,
+}
+
+impl BitStorage {
+    fn new(bits: u8, size: usize) -> Self {
+        let values_per_long = 64 / bits as usize;
+        Self {
+            bits,
+            data: vec![0; size.div_ceil(values_per_long)].into_boxed_slice(),
+        }
+    }
+
+    const fn values_per_long(&self) -> usize {
+        64 / self.bits as usize
+    }
+
+    const fn mask(&self) -> u64 {
+        (1 << self.bits) - 1
+    }
+
+    fn get(&self, index: usize) -> u32 {
+        let values_per_long = self.values_per_long();
+        let offset = (index % values_per_long) * self.bits as usize;
+        ((self.data[index / values_per_long] >> offset) & self.mask()) as u32
+    }
+
+    fn set(&mut self, index: usize, value: u32) -> u32 {
+        debug_assert!(u64::from(value) <= self.mask());
+        let values_per_long = self.values_per_long();
+        let offset = (index % values_per_long) * self.bits as usize;
+        let mask = self.mask();
+        let long = &mut self.data[index / values_per_long];
+        let old = ((*long >> offset) & mask) as u32;
+        *long = (*long & !(mask << offset)) | (u64::from(value) << offset);
+        old
+    }
+
+    /// Copies the values into a storage with `bits` bits per entry.
+    fn resized(&self, bits: u8, size: usize) -> Self {
+        let mut storage = Self::new(bits, size);
+        for index in 0..size {
+            storage.set(index, self.get(index));
+        }
+        storage
+    }
+
+    /// The bits each value takes.
+    #[must_use]
+    pub const fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// The packed longs, in the layout sent to the client.
+    #[must_use]
+    pub fn raw(&self) -> &[u64] {
+        &self.data
+    }
+}
+
+/// A container of a few different values, stored as indices into a palette.
+///
+/// Palette entries that are no longer used keep their index and are reused by
+/// the next new value, so stored indices never have to be remapped.
+///
+/// Vanilla: `LinearPalette`.
+#[derive(Debug, Clone)]
+pub struct LinearPalette<V: Hash + Eq + Copy, const DIM: usize> {
+    // (value, count) like `HeterogeneousPalette`, a count of 0 marks a free entry.
+    pub(crate) palette: Vec<(V, u16)>,
+    pub(crate) storage: BitStorage,
+}
+
+impl<V: Hash + Eq + Copy, const DIM: usize> LinearPalette<V, DIM> {
+    const fn index(x: usize, y: usize, z: usize) -> usize {
+        (y * DIM + z) * DIM + x
+    }
+
+    /// Builds the palette from a cube whose value counts are already known.
+    fn from_cube(cube: &Cube<V, DIM>, palette: Vec<(V, u16)>) -> Self {
+        let mut storage = BitStorage::new(palette_bits(DIM, palette.len()), DIM * DIM * DIM);
+        for (index, value) in cube.iter().flatten().flatten().enumerate() {
+            let palette_index = palette
+                .iter()
+                .position(|(v, _)| v == value)
+                .expect("palette was built from the cube's own values");
+            storage.set(index, palette_index as u32);
+        }
+        Self { palette, storage }
+    }
+
+    fn get(&self, x: usize, y: usize, z: usize) -> V {
+        debug_assert!(x < DIM);
+        debug_assert!(y < DIM);
+        debug_assert!(z < DIM);
+
+        self.value_at(Self::index(x, y, z))
+    }
+
+    fn value_at(&self, index: usize) -> V {
+        self.palette[self.storage.get(index) as usize].0
+    }
+
+    /// Returns an iterator over all values in y, z, x order.
+    pub fn iter_values(&self) -> impl Iterator<Item = V> + '_ {
+        (0..DIM * DIM * DIM).map(|index| self.value_at(index))
+    }
+
+    /// Returns the only value left, if every other one was overwritten.
+    fn single_value(&self) -> Option<V> {
+        let mut live = self.palette.iter().filter(|(_, count)| *count > 0);
+        let (value, _) = live.next()?;
+        live.next().is_none().then_some(*value)
+    }
+
+    /// Sets a value and returns the old one, or `None` if the value is new and
+    /// the palette is full.
+    fn set(&mut self, x: usize, y: usize, z: usize, value: V) -> Option<V> {
+        debug_assert!(x < DIM);
+        debug_assert!(y < DIM);
+        debug_assert!(z < DIM);
+
+        let new_index = self.index_for(value)?;
+        let old_index = self.storage.set(Self::index(x, y, z), new_index as u32) as usize;
+        self.palette[new_index].1 += 1;
+        self.palette[old_index].1 -= 1;
+        Some(self.palette[old_index].0)
+    }
+
+    fn index_for(&mut self, value: V) -> Option<usize> {
+        if let Some(index) = self
+            .palette
+            .iter()
+            .position(|(v, count)| *v == value && *count > 0)
+        {
+            return Some(index);
+        }
+        if let Some(index) = self.palette.iter().position(|(_, count)| *count == 0) {
+            self.palette[index].0 = value;
+            return Some(index);
+        }
+        if self.palette.len() >= max_linear_entries(DIM) {
+            return None;
+        }
+
+        self.palette.push((value, 0));
+        let bits = palette_bits(DIM, self.palette.len());
+        if bits != self.storage.bits {
+            self.storage = self.storage.resized(bits, DIM * DIM * DIM);
+        }
+        Some(self.palette.len() - 1)
+    }
+
+    fn to_heterogeneous(&self) -> HeterogeneousPalette<V, DIM> {
+        let mut values = self.iter_values();
+        let cube = Box::new(std::array::from_fn(|_| {
+            std::array::from_fn(|_| {
+                std::array::from_fn(|_| values.next().expect("storage holds every value"))
+            })
+        }));
+        HeterogeneousPalette {
+            cube,
+            palette: self
+                .palette
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .copied()
+                .collect(),
+        }
+    }
+}
+
 /// A heterogeneous palette container.
 #[derive(Debug, Clone)]
 pub struct HeterogeneousPalette<V: Hash + Eq + Copy, const DIM: usize> {
@@ -83,10 +295,14 @@ impl<V: Hash + Eq + Copy, const DIM: usize> HeterogeneousPalette<V, DIM> {
 }
 
 /// A paletted container.
+///
+/// Vanilla: `PalettedContainer`.
 #[derive(Debug, Clone)]
 pub enum PalettedContainer<V: Hash + Eq + Copy + Default, const DIM: usize> {
     /// A homogeneous container, where all values are the same.
     Homogeneous(V),
+    /// A container of a few different values, stored as packed palette indices.
+    Linear(LinearPalette<V, DIM>),
     /// A heterogeneous container, where values can be different.
     Heterogeneous(HeterogeneousPalette<V, DIM>),
 }
@@ -119,6 +335,8 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
 
         if palette.len() == 1 {
             Self::Homogeneous(palette[0].0)
+        } else if palette.len() <= max_linear_entries(DIM) {
+            Self::Linear(LinearPalette::from_cube(&cube, palette))
         } else {
             Self::Heterogeneous(HeterogeneousPalette { cube, palette })
         }
@@ -128,6 +346,7 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
     pub fn get(&self, x: usize, y: usize, z: usize) -> V {
         match self {
             Self::Homogeneous(value) => *value,
+            Self::Linear(data) => data.get(x, y, z),
             Self::Heterogeneous(data) => data.get(x, y, z),
         }
     }
@@ -137,10 +356,44 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
     pub fn collect_values(&self) -> Vec<V> {
         match self {
             Self::Homogeneous(value) => vec![*value; Self::VOLUME],
+            Self::Linear(data) => data.iter_values().collect(),
             Self::Heterogeneous(data) => data.iter_values().copied().collect(),
         }
     }
 
+    /// Returns the distinct values in the container and, for every position
+    /// in y, z, x order, the index of its value. Returns `None` for a
+    /// homogeneous container.
+    #[must_use]
+    pub fn palette_and_indices(&self) -> Option<(Vec<V>, Vec<u32>)> {
+        match self {
+            Self::Homogeneous(_) => None,
+            Self::Linear(data) => {
+                // Leave out free entries, shifting the indices after them down
+                let mut remap = Vec::with_capacity(data.palette.len());
+                let mut values = Vec::with_capacity(data.palette.len());
+                for (value, count) in &data.palette {
+                    remap.push(values.len() as u32);
+                    if *count > 0 {
+                        values.push(*value);
+                    }
+                }
+                let indices = (0..Self::VOLUME)
+                    .map(|index| remap[data.storage.get(index) as usize])
+                    .collect();
+                Some((values, indices))
+            }
+            Self::Heterogeneous(data) => {
+                let values: Vec<V> = data.palette.iter().map(|(value, _)| *value).collect();
+                let indices = data
+                    .iter_values()
+                    .map(|value| values.iter().position(|v| v == value).unwrap_or(0) as u32)
+                    .collect();
+                Some((values, indices))
+            }
+        }
+    }
+
     /// Sets the value at the given coordinates.
     pub fn set(&mut self, x: usize, y: usize, z: usize, value: V) -> V {
         debug_assert!(x < Self::SIZE);
@@ -151,22 +404,85 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
             Self::Homogeneous(original) => {
                 let original = *original;
                 if value != original {
-                    let mut cube = Box::new([[[original; DIM]; DIM]; DIM]);
-                    cube[y][z][x] = value;
-                    *self = Self::from_cube(cube);
+                    let mut data = LinearPalette {
+                        palette: vec![(original, Self::VOLUME as u16)],
+                        storage: BitStorage::new(palette_bits(DIM, 1), Self::VOLUME),
+                    };
+                    data.set(x, y, z, value);
+                    *self = Self::Linear(data);
                 }
                 original
             }
+            Self::Linear(data) => {
+                if let Some(original) = data.set(x, y, z, value) {
+                    if let Some(single) = data.single_value() {
+                        *self = Self::Homogeneous(single);
+                    }
+                    original
+                } else {
+                    // A new value that doesn't fit the palette anymore
+                    let mut data = data.to_heterogeneous();
+                    let original = data.set(x, y, z, value);
+                    *self = Self::Heterogeneous(data);
+                    original
+                }
+            }
             Self::Heterogeneous(data) => {
                 let original = data.set(x, y, z, value);
                 if data.palette.len() == 1 {
                     *self = Self::Homogeneous(data.palette[0].0);
+                } else if data.palette.len() <= max_linear_entries(DIM) / 2 {
+                    // Only demote well below the limit, so a section changing
+                    // around it isn't repacked on every set
+                    *self = Self::Linear(LinearPalette::from_cube(
+                        &data.cube,
+                        std::mem::take(&mut data.palette),
+                    ));
                 }
                 original
             }
         }
     }
 
+    /// Re-palettes the container into its most compact form: drops free
+    /// palette entries and stops storing values directly once there are few
+    /// of them. Returns true if the container changed.
+    pub fn compact(&mut self) -> bool {
+        let compacted = match self {
+            Self::Homogeneous(_) => return false,
+            Self::Linear(data) => {
+                if data.palette.iter().all(|(_, count)| *count > 0) {
+                    return false;
+                }
+                let HeterogeneousPalette { cube, palette } = data.to_heterogeneous();
+                LinearPalette::from_cube(&cube, palette)
+            }
+            Self::Heterogeneous(data) => {
+                if data.palette.len() > max_linear_entries(DIM) {
+                    return false;
+                }
+                LinearPalette::from_cube(&data.cube, data.palette.clone())
+            }
+        };
+        *self = Self::Linear(compacted);
+        true
+    }
+
+    /// The bytes the container keeps on the heap.
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        match self {
+            Self::Homogeneous(_) => 0,
+            Self::Linear(data) => {
+                data.palette.capacity() * size_of::<(V, u16)>()
+                    + data.storage.data.len() * size_of::<u64>()
+            }
+            Self::Heterogeneous(data) => {
+                size_of::<Cube<V, DIM>>() + data.palette.capacity() * size_of::<(V, u16)>()
+            }
+        }
+    }
+
     /// Writes the container to the given writer.
     ///
     /// # Errors
@@ -188,6 +504,17 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
                 VarInt(value.to_global_id() as i32).write(writer)?;
                 // writeFixedSizeLongArray(new long[0]) writes nothing
             }
+            Self::Linear(data) => {
+                data.storage.bits.write(writer)?;
+                VarInt(data.palette.len() as i32).write(writer)?;
+                for (val, _) in &data.palette {
+                    VarInt(val.to_global_id() as i32).write(writer)?;
+                }
+                // Already packed the way the client reads it
+                for long in data.storage.raw() {
+                    long.write(writer)?;
+                }
+            }
             Self::Heterogeneous(data) => {
                 let (bits, mode) = Self::calculate_strategy(data.palette.len());
 
@@ -231,26 +558,24 @@ impl<V: Hash + Eq + Copy + Default + Debug, const DIM: usize> PalettedContainer<
         Ok(())
     }
 
+    /// Vanilla: `PalettedContainer.Strategy.SECTION_STATES` and `SECTION_BIOMES`.
     fn calculate_strategy(count: usize) -> (u8, PaletteMode) {
+        let bits = palette_bits(DIM, count);
         if DIM == 16 {
             // Block states
-            match count {
-                0..=1 => unreachable!("Homogeneous handled separately"),
-                2..=16 => (4, PaletteMode::Linear),
-                17..=32 => (5, PaletteMode::Hash),
-                33..=64 => (6, PaletteMode::Hash),
-                65..=128 => (7, PaletteMode::Hash),
-                129..=256 => (8, PaletteMode::Hash),
-                _ => (15, PaletteMode::Global), // ceil(log2(max_block_state_id)) approx 15
+            match bits {
+                0..=4 => (bits, PaletteMode::Linear),
+                5..=8 => (bits, PaletteMode::Hash),
+                _ => (
+                    ceil_log2(REGISTRY.blocks.state_to_block_lookup.len()),
+                    PaletteMode::Global,
+                ),
             }
         } else {
             // Biomes
-            match count {
-                0..=1 => unreachable!("Homogeneous handled separately"),
-                2 => (1, PaletteMode::Linear),
-                3..=4 => (2, PaletteMode::Linear),
-                5..=8 => (3, PaletteMode::Hash),
-                _ => (6, PaletteMode::Global), // ceil(log2(max_biome_id)) approx 6
+            match bits {
+                0..=3 => (bits, PaletteMode::Linear),
+                _ => (ceil_log2(REGISTRY.biomes.len()), PaletteMode::Global),
             }
         }
     }
@@ -276,12 +601,13 @@ pub type BlockPalette = PalettedContainer<BlockStateId, 16>;
 pub type BiomePalette = PalettedContainer<u16, 4>;
 
 impl BlockPalette {
-    /// Gets the number of non-empty blocks in the container.
+    /// Gets the number of non-air blocks in the container. Cave air and void
+    /// air count as air.
     #[must_use]
     pub fn non_empty_block_count(&self) -> u16 {
         match self {
             Self::Homogeneous(v) => {
-                if v.0 == 0 {
+                if v.is_air() {
                     0
                 } else {
                     #[expect(
@@ -293,10 +619,11 @@ impl BlockPalette {
                     }
                 }
             }
-            Self::Heterogeneous(data) => {
+            Self::Linear(LinearPalette { palette, .. })
+            | Self::Heterogeneous(HeterogeneousPalette { palette, .. }) => {
                 let mut count = 0;
-                for (v, c) in &data.palette {
-                    if v.0 != 0 {
+                for (v, c) in palette {
+                    if !v.is_air() {
                         count += c;
                     }
                 }
@@ -308,10 +635,75 @@ impl BlockPalette {
     /// Returns `true` if this palette contains only air blocks.
     #[must_use]
     pub fn has_only_air(&self) -> bool {
-        match self {
-            Self::Homogeneous(v) => v.is_air(),
-            //TODO: Use a nonEmpty counter?
-            Self::Heterogeneous(_data) => false,
+        self.non_empty_block_count() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_promotes_and_demotes() {
+        let mut palette = BlockPalette::Homogeneous(BlockStateId(0));
+        palette.set(1, 2, 3, BlockStateId(1));
+        assert!(matches!(palette, PalettedContainer::Linear(_)));
+        assert_eq!(palette.get(1, 2, 3), BlockStateId(1));
+
+        for x in 0..16 {
+            palette.set(x, 0, 0, BlockStateId(x as u16 + 2));
         }
+        assert!(matches!(palette, PalettedContainer::Heterogeneous(_)));
+        assert_eq!(palette.get(15, 0, 0), BlockStateId(17));
+
+        for x in 0..16 {
+            palette.set(x, 0, 0, BlockStateId(0));
+        }
+        assert!(matches!(palette, PalettedContainer::Linear(_)));
+
+        palette.set(1, 2, 3, BlockStateId(0));
+        assert!(matches!(
+            palette,
+            PalettedContainer::Homogeneous(BlockStateId(0))
+        ));
+    }
+
+    #[test]
+    fn test_compact_drops_free_entries() {
+        let mut palette = BiomePalette::Homogeneous(0);
+        palette.set(0, 0, 0, 1);
+        palette.set(1, 0, 0, 2);
+        palette.set(2, 0, 0, 3);
+        palette.set(0, 0, 0, 0);
+        assert!(palette.compact());
+        assert!(!palette.compact());
+
+        let PalettedContainer::Linear(data) = &palette else {
+            panic!("expected a linear palette");
+        };
+        assert_eq!(data.palette.len(), 3);
+        assert_eq!(palette.get(2, 0, 0), 3);
+    }
+
+    #[test]
+    fn test_linear_reuses_free_entries() {
+        let mut palette = BiomePalette::Homogeneous(0);
+        palette.set(0, 0, 0, 1);
+        palette.set(1, 0, 0, 2);
+        palette.set(0, 0, 0, 0);
+        palette.set(2, 0, 0, 3);
+
+        let PalettedContainer::Linear(data) = &palette else {
+            panic!("expected a linear palette");
+        };
+        assert_eq!(data.palette.len(), 3);
+        assert_eq!(data.storage.bits(), 2);
+
+        let (values, indices) = palette
+            .palette_and_indices()
+            .expect("palette has several values");
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[indices[2] as usize], 3);
+        assert_eq!(palette.collect_values()[1], 2);
     }
 }
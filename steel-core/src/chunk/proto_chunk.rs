@@ -1,14 +1,35 @@
 //! A proto chunk is a chunk that is still being generated.
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 
 use crossbeam::atomic::AtomicCell;
+use glam::DVec3;
 use rustc_hash::FxHashMap;
+use simdnbt::owned::NbtCompound;
+use steel_registry::entity_types::EntityTypeRef;
 use steel_registry::{REGISTRY, blocks::block_state_ext::BlockStateExt, vanilla_blocks};
-use steel_utils::{BlockPos, BlockStateId, ChunkPos, locks::SyncRwLock, types::UpdateFlags};
+use steel_utils::density::BlendingData;
+use steel_utils::locks::{SyncMutex, SyncRwLock};
+use steel_utils::{BlockPos, BlockStateId, ChunkPos, types::UpdateFlags};
 
-use crate::chunk::{chunk_access::ChunkStatus, heightmap::ProtoHeightmaps, section::Sections};
+use crate::chunk::{
+    chunk_access::ChunkStatus,
+    heightmap::{HeightmapType, ProtoHeightmaps},
+    section::Sections,
+};
 use crate::world::structure::{StructureReferenceMap, StructureStartMap};
 
+/// An entity placed by world generation, spawned once its chunk is full.
+#[derive(Debug, Clone, Copy)]
+pub struct ProtoEntity {
+    /// The type of the entity.
+    pub entity_type: EntityTypeRef,
+    /// The position of the entity.
+    pub position: DVec3,
+    /// The rotation of the entity as (yaw, pitch) in degrees.
+    pub rotation: (f32, f32),
+}
+
 /// A chunk that is still being generated.
 #[derive(Debug)]
 pub struct ProtoChunk {
@@ -32,6 +53,16 @@ pub struct ProtoChunk {
     pub structure_starts: SyncRwLock<StructureStartMap>,
     /// References to structures from nearby origin chunks.
     pub structure_references: SyncRwLock<StructureReferenceMap>,
+    /// Total ticks players have spent near this chunk (vanilla `InhabitedTime`).
+    pub inhabited_time: AtomicI64,
+    /// Entities placed by world generation.
+    // TODO: Save these with the proto chunk like vanilla's `ProtoChunk.entities`
+    pub entities: SyncMutex<Vec<ProtoEntity>>,
+    /// Block entity data placed by world generation, loaded once the chunk is full.
+    // TODO: Save these with the proto chunk like vanilla's `ProtoChunk.pendingBlockEntities`
+    pub block_entities: SyncMutex<FxHashMap<BlockPos, NbtCompound>>,
+    /// Border samples new terrain is blended into, if this chunk is from older generation.
+    pub blending_data: SyncMutex<Option<Arc<BlendingData>>>,
 }
 
 impl ProtoChunk {
@@ -48,6 +79,10 @@ impl ProtoChunk {
             height,
             structure_starts: SyncRwLock::new(FxHashMap::default()),
             structure_references: SyncRwLock::new(FxHashMap::default()),
+            inhabited_time: AtomicI64::new(0),
+            entities: SyncMutex::new(Vec::new()),
+            block_entities: SyncMutex::new(FxHashMap::default()),
+            blending_data: SyncMutex::new(None),
         }
     }
 
@@ -73,7 +108,51 @@ impl ProtoChunk {
             height,
             structure_starts: SyncRwLock::new(structure_starts),
             structure_references: SyncRwLock::new(structure_references),
+            inhabited_time: AtomicI64::new(0),
+            entities: SyncMutex::new(Vec::new()),
+            block_entities: SyncMutex::new(FxHashMap::default()),
+            blending_data: SyncMutex::new(None),
+        }
+    }
+
+    /// Queues an entity to be spawned when the chunk becomes full.
+    ///
+    /// Vanilla: `ProtoChunk.addEntity()`.
+    pub fn add_entity(&self, entity: ProtoEntity) {
+        self.entities.lock().push(entity);
+        self.mark_unsaved();
+    }
+
+    /// Stores the data of a block entity to load when the chunk becomes full.
+    ///
+    /// Vanilla: `ChunkAccess.setBlockEntityNbt()`.
+    pub fn set_block_entity_nbt(&self, pos: BlockPos, nbt: NbtCompound) {
+        self.block_entities.lock().insert(pos, nbt);
+        self.mark_unsaved();
+    }
+
+    /// Gets the first free Y above the heightmap's blocks at `x`, `z`, priming the
+    /// heightmap if the chunk doesn't track it yet.
+    ///
+    /// Vanilla: `ChunkAccess.getHeight()`.
+    #[must_use]
+    pub fn get_height(&self, heightmap_type: HeightmapType, x: i32, z: i32) -> i32 {
+        let (local_x, local_z) = ((x & 15) as usize, (z & 15) as usize);
+        if let Some(heightmap) = self.heightmaps.read().get(heightmap_type) {
+            return heightmap.get_first_available(local_x, local_z);
         }
+        let mut heightmaps = self.heightmaps.write();
+        heightmaps.prime_from_sections(
+            &[heightmap_type],
+            self.min_y,
+            self.height,
+            &self.sections.sections,
+        );
+        heightmaps
+            .get(heightmap_type)
+            .map_or(self.min_y, |heightmap| {
+                heightmap.get_first_available(local_x, local_z)
+            })
     }
 
     /// Returns the minimum Y coordinate of the world.
@@ -171,6 +171,28 @@ impl Sections {
         }
     }
 
+    /// Compacts the sections that changed a lot since they were last
+    /// compacted. Returns how many sections changed.
+    pub fn compact(&self) -> usize {
+        let mut compacted = 0;
+        for holder in &self.sections {
+            if !holder.read().needs_compaction() {
+                continue;
+            }
+            if holder.write().compact() {
+                compacted += 1;
+            }
+        }
+        compacted
+    }
+
+    /// Adds the memory used by these sections to `memory`.
+    pub fn memory_usage(&self, memory: &mut ChunkMemory) {
+        for holder in &self.sections {
+            memory.add_section(&holder.read());
+        }
+    }
+
     /// Sets a block at a relative position in the chunk.
     pub fn set_relative_block(
         &self,
@@ -191,6 +213,45 @@ impl Sections {
     }
 }
 
+/// How many changed blocks make a section worth compacting.
+const COMPACTION_EDIT_THRESHOLD: u16 = 256;
+
+/// How much memory a set of chunks uses, by how their sections store blocks.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ChunkMemory {
+    /// The number of chunks.
+    pub chunks: usize,
+    /// The number of sections.
+    pub sections: usize,
+    /// Sections with only air.
+    pub empty_sections: usize,
+    /// Sections with a single block state.
+    pub single_value_sections: usize,
+    /// Sections storing packed palette indices.
+    pub linear_sections: usize,
+    /// Sections storing block states directly.
+    pub direct_sections: usize,
+    /// The bytes used by the sections, including their palettes.
+    pub bytes: usize,
+}
+
+impl ChunkMemory {
+    /// Counts a section.
+    pub fn add_section(&mut self, section: &ChunkSection) {
+        self.sections += 1;
+        if section.states.has_only_air() {
+            self.empty_sections += 1;
+        }
+        match section.states {
+            BlockPalette::Homogeneous(_) => self.single_value_sections += 1,
+            BlockPalette::Linear(_) => self.linear_sections += 1,
+            BlockPalette::Heterogeneous(_) => self.direct_sections += 1,
+        }
+        self.bytes +=
+            size_of::<SectionHolder>() + section.states.heap_size() + section.biomes.heap_size();
+    }
+}
+
 /// A chunk section.
 ///
 /// Contains a 16x16x16 cube of block states and biomes, along with cached
@@ -209,6 +270,8 @@ pub struct ChunkSection {
     fluid_count: u16,
     /// Number of randomly-ticking blocks in this section (0-4096).
     pub ticking_block_count: u16,
+    /// Blocks changed since the section was last compacted.
+    edits_since_compaction: u16,
 }
 
 impl ChunkSection {
@@ -224,6 +287,7 @@ impl ChunkSection {
             non_empty_block_count: 0,
             fluid_count: 0,
             ticking_block_count: 0,
+            edits_since_compaction: 0,
         }
     }
 
@@ -237,6 +301,7 @@ impl ChunkSection {
             non_empty_block_count: 0,
             fluid_count: 0,
             ticking_block_count: 0,
+            edits_since_compaction: 0,
         }
     }
 
@@ -347,6 +412,8 @@ impl ChunkSection {
         let old_state = self.states.set(x, y, z, new_state);
 
         if old_state != new_state {
+            self.edits_since_compaction = self.edits_since_compaction.saturating_add(1);
+
             // Update non-empty count
             let old_is_air = old_state.is_air();
             let new_is_air = new_state.is_air();
@@ -387,6 +454,31 @@ impl ChunkSection {
         old_state
     }
 
+    /// Returns true if enough blocks changed since the last compaction, for
+    /// example by a fill command or an explosion, to compact the section.
+    #[must_use]
+    pub const fn needs_compaction(&self) -> bool {
+        self.edits_since_compaction >= COMPACTION_EDIT_THRESHOLD
+    }
+
+    /// Re-palettes the section, and releases the block storage of a section
+    /// that only holds air. Returns true if anything changed.
+    ///
+    /// Like vanilla's `hasOnlyAir()`, cave air and void air count as air, so a
+    /// section of only those becomes plain air.
+    pub fn compact(&mut self) -> bool {
+        self.edits_since_compaction = 0;
+        let biomes_changed = self.biomes.compact();
+        if self.states.has_only_air() {
+            if matches!(self.states, BlockPalette::Homogeneous(_)) {
+                return biomes_changed;
+            }
+            self.states = BlockPalette::Homogeneous(BlockStateId(0));
+            return true;
+        }
+        self.states.compact() || biomes_changed
+    }
+
     /// Writes the chunk section to a writer.
     ///
     /// # Panics
@@ -405,3 +497,47 @@ impl ChunkSection {
         self.biomes.write(writer).expect("Failed to write biomes");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Once;
+
+    use steel_registry::{REGISTRY, Registry, vanilla_blocks};
+
+    use super::*;
+
+    static INIT: Once = Once::new();
+
+    fn ensure_registry() {
+        INIT.call_once(|| {
+            let mut registry = Registry::new_vanilla();
+            registry.freeze();
+            let _ = REGISTRY.init(registry);
+        });
+    }
+
+    #[test]
+    fn air_and_cave_air_compact_to_air() {
+        ensure_registry();
+        let cave_air = REGISTRY.blocks.get_base_state_id(vanilla_blocks::CAVE_AIR);
+        let stone = REGISTRY.blocks.get_base_state_id(vanilla_blocks::STONE);
+        let mut states = BlockPalette::Homogeneous(BlockStateId(0));
+        for x in 0..16 {
+            states.set(x, 0, 0, cave_air);
+        }
+        assert_eq!(states.non_empty_block_count(), 0);
+        assert!(states.has_only_air());
+
+        let mut section = ChunkSection::new_with_biomes(states, BiomePalette::Homogeneous(0));
+        assert!(section.compact());
+        assert!(matches!(
+            section.states,
+            BlockPalette::Homogeneous(BlockStateId(0))
+        ));
+
+        section.states.set(0, 0, 0, cave_air);
+        section.states.set(1, 0, 0, stone);
+        assert_eq!(section.states.non_empty_block_count(), 1);
+        assert!(!section.states.has_only_air());
+    }
+}
@@ -1,17 +1,19 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use sha2::{Digest, Sha256};
 use steel_registry::RegistryEntry;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::noise_parameters::get_noise_parameters;
 use steel_registry::vanilla_biomes;
-use steel_utils::BlockStateId;
-use steel_utils::density::{ColumnCache, DimensionNoises, NoiseSettings};
+use steel_utils::density::{Blender, ColumnCache, DimensionNoises, NoiseSettings};
 use steel_utils::math::noise_math::lerp2;
 use steel_utils::random::{
-    Random, RandomSplitter, legacy_random::LegacyRandom, xoroshiro::Xoroshiro,
+    Random, RandomSource, RandomSplitter, legacy_random::LegacyRandom,
+    worldgen_random::WorldgenRandom, xoroshiro::Xoroshiro,
 };
 use steel_utils::surface::SurfaceRuleContext;
+use steel_utils::{BlockStateId, BoundingBox};
 
 use crate::chunk::aquifer::{Aquifer, AquiferResult, preliminary_surface_level};
 use crate::chunk::beardifier::Beardifier;
@@ -21,7 +23,16 @@ use crate::chunk::heightmap::HeightmapType;
 use crate::chunk::noise_chunk::NoiseChunk;
 use crate::chunk::ore_veinifier::OreVeinifier;
 use crate::chunk::surface_system::SurfaceSystem;
-use crate::worldgen::BiomeSourceKind;
+use crate::world::structure::StructureStart;
+use crate::worldgen::{
+    BiomeSourceKind, DECORATION_STEPS, EndSpike, GenerationContext, SPIKE_COUNT, Structure,
+    StructureSelectionEntry, StructureSet, end_spikes, place_end_spike,
+};
+
+/// Vanilla's `GenerationStep.Decoration.SURFACE_STRUCTURES` step.
+const SURFACE_STRUCTURES_STEP: usize = 4;
+/// Index of `end_spike` among the End's surface structure features.
+const END_SPIKE_FEATURE_INDEX: i32 = 0;
 
 /// A chunk generator for vanilla (normal) world generation.
 ///
@@ -47,6 +58,12 @@ pub struct VanillaGenerator<N: DimensionNoises> {
     default_block_id: BlockStateId,
     /// Obfuscated seed for `BiomeManager` biome zoom fuzzing.
     biome_zoom_seed: i64,
+    /// The world seed, used to seed decoration.
+    seed: i64,
+    /// The obsidian spikes, if this generates the End.
+    end_spikes: Option<[EndSpike; SPIKE_COUNT]>,
+    /// The structure sets with a structure that can start in this dimension's biomes.
+    structure_sets: Vec<&'static StructureSet>,
     _phantom: PhantomData<N>,
 }
 
@@ -90,6 +107,10 @@ impl<N: DimensionNoises> VanillaGenerator<N> {
             i64::from_le_bytes(result[0..8].try_into().expect("SHA-256 produces 32 bytes"))
         };
 
+        let end_spikes =
+            matches!(biome_source, BiomeSourceKind::End(_)).then(|| end_spikes(seed as i64));
+        let structure_sets = StructureSet::possible(&biome_source);
+
         Self {
             biome_source,
             noises: Box::new(noises),
@@ -98,15 +119,126 @@ impl<N: DimensionNoises> VanillaGenerator<N> {
             surface_system,
             default_block_id,
             biome_zoom_seed,
+            seed: seed as i64,
+            end_spikes,
+            structure_sets,
             _phantom: PhantomData,
         }
     }
+
+    /// Returns the biome source this generator samples biomes from.
+    #[must_use]
+    pub const fn biome_source(&self) -> &BiomeSourceKind {
+        &self.biome_source
+    }
+
+    /// Gets the first free Y above the noise terrain at `x`, `z`, before surfaces,
+    /// carvers and features.
+    ///
+    /// Vanilla: `NoiseBasedChunkGenerator.getBaseHeight()`.
+    #[must_use]
+    pub fn base_height(&self, x: i32, z: i32, heightmap: HeightmapType) -> i32 {
+        let noises = &*self.noises;
+        let cell_width = N::Settings::CELL_WIDTH;
+        let mut column_cache = N::ColumnCache::default();
+        column_cache.init_grid(
+            x.div_euclid(cell_width) * cell_width,
+            z.div_euclid(cell_width) * cell_width,
+            noises,
+        );
+        let mut aquifer = Aquifer::<N>::new(
+            (x >> 4) << 4,
+            (z >> 4) << 4,
+            N::Settings::MIN_Y,
+            N::Settings::HEIGHT,
+            &self.splitter,
+            noises,
+            column_cache.clone(),
+        );
+        NoiseChunk::<N>::iterate_column(noises, &mut column_cache, x, z, |y, density| {
+            let state = match aquifer.compute_substance(noises, x, y, z, density) {
+                AquiferResult::Solid => self.default_block_id,
+                AquiferResult::Fluid(id) => id,
+                AquiferResult::Air => return false,
+            };
+            heightmap.is_opaque(state)
+        })
+        .map_or(N::Settings::MIN_Y, |y| y + 1)
+    }
 }
 
 impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
-    fn create_structures(&self, _chunk: &ChunkAccess) {}
+    /// Vanilla: `ChunkGenerator.createStructures()`.
+    fn create_structures(&self, chunk: &ChunkAccess) {
+        let pos = chunk.pos();
+        let base_height = |x, z, heightmap| self.base_height(x, z, heightmap);
+        let try_generate = |structure: &StructureSelectionEntry| {
+            let Some(structure) = Structure::by_id(&structure.structure) else {
+                return false;
+            };
+            let mut context = GenerationContext::new(
+                pos,
+                self.seed,
+                N::Settings::MIN_Y,
+                N::Settings::HEIGHT,
+                &base_height,
+            );
+            let Some(start) = structure.generate(&mut context, &self.biome_source) else {
+                return false;
+            };
+            chunk
+                .structure_starts_mut()
+                .insert(structure.id.clone(), start);
+            chunk.mark_dirty();
+            true
+        };
 
-    fn create_biomes(&self, chunk: &ChunkAccess) {
+        for set in &self.structure_sets {
+            {
+                let starts = chunk.structure_starts();
+                if set
+                    .structures
+                    .iter()
+                    .any(|entry| starts.contains_key(&entry.structure))
+                {
+                    continue;
+                }
+            }
+            if !set
+                .placement
+                .is_structure_chunk(self.seed, pos.0.x, pos.0.y)
+            {
+                continue;
+            }
+            if let [entry] = set.structures.as_slice() {
+                try_generate(entry);
+                continue;
+            }
+
+            let mut options = set.structures.clone();
+            let mut random = WorldgenRandom::new(RandomSource::Legacy(LegacyRandom::from_seed(0)));
+            random.set_large_feature_seed(self.seed, pos.0.x, pos.0.y);
+            let mut total: i32 = options.iter().map(|entry| entry.weight).sum();
+            while !options.is_empty() {
+                let mut choice = random.next_i32_bounded(total);
+                let mut selected = 0;
+                for entry in &options {
+                    choice -= entry.weight;
+                    if choice < 0 {
+                        break;
+                    }
+                    selected += 1;
+                }
+                let entry = options.remove(selected);
+                if try_generate(&entry) {
+                    break;
+                }
+                total -= entry.weight;
+            }
+        }
+    }
+
+    fn create_biomes(&self, chunk: &ChunkAccess, blender: Option<&Arc<Blender>>) {
         let pos = chunk.pos();
         let min_y = chunk.min_y();
         let section_count = chunk.sections().sections.len();
@@ -125,6 +257,10 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
             let section = &chunk.sections().sections[section_index];
             let mut section_guard = section.write();
 
+            // Evaluate the section's climate a column at a time up front; the
+            // lookups below still run in vanilla's order.
+            sampler.prepare_section(chunk_x * 4, section_y * 4, chunk_z * 4);
+
             for local_quart_x in 0..4i32 {
                 let quart_x = chunk_x * 4 + local_quart_x;
 
@@ -134,8 +270,12 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
                     for local_quart_z in 0..4i32 {
                         let quart_z = chunk_z * 4 + local_quart_z;
 
-                        let biome = sampler.sample(quart_x, quart_y, quart_z);
-                        let biome_id = biome.id() as u16;
+                        // Vanilla: Blender.getBiomeResolver()
+                        let biome_id = blender
+                            .and_then(|b| b.blend_biome(quart_x, quart_y, quart_z))
+                            .unwrap_or_else(|| {
+                                sampler.sample(quart_x, quart_y, quart_z).id() as u16
+                            });
 
                         section_guard.biomes.set(
                             local_quart_x as usize,
@@ -151,7 +291,12 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
         chunk.mark_dirty();
     }
 
-    fn fill_from_noise(&self, chunk: &ChunkAccess) {
+    fn fill_from_noise(
+        &self,
+        chunk: &ChunkAccess,
+        structures: &[StructureStart],
+        blender: Option<&Arc<Blender>>,
+    ) {
         let pos = chunk.pos();
         let chunk_min_x = pos.0.x * 16;
         let chunk_min_z = pos.0.y * 16;
@@ -163,6 +308,7 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
         let noises = &*self.noises;
 
         let mut column_cache = N::ColumnCache::default();
+        column_cache.set_blender(blender.cloned());
         column_cache.init_grid(chunk_min_x, chunk_min_z, noises);
 
         let default_block_id = self.default_block_id;
@@ -178,8 +324,7 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
             column_cache.clone(),
         );
 
-        let structure_starts = chunk.structure_starts();
-        let beardifier = Beardifier::for_structures_in_chunk(&structure_starts, pos.0.x, pos.0.y);
+        let beardifier = Beardifier::for_structures_in_chunk(structures, pos.0.x, pos.0.y);
         let beard_opt = if beardifier.is_empty() {
             None
         } else {
@@ -244,7 +389,12 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
     }
 
     #[expect(clippy::too_many_lines, reason = "splitting would hurt readability")]
-    fn build_surface(&self, chunk: &ChunkAccess, neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16) {
+    fn build_surface(
+        &self,
+        chunk: &ChunkAccess,
+        neighbor_biomes: &dyn Fn(i32, i32, i32) -> u16,
+        blender: Option<&Arc<Blender>>,
+    ) {
         let min_y = N::Settings::MIN_Y;
         let pos = chunk.pos();
         let chunk_min_x = pos.0.x * 16;
@@ -261,6 +411,7 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
         // Pre-compute the 4 preliminary surface level corners for the 16-block cell.
         // Vanilla uses bilinear interpolation across these 4 corners (SurfaceRules.Context).
         let mut psl_cache = N::ColumnCache::default();
+        psl_cache.set_blender(blender.cloned());
         let p00 = preliminary_surface_level::<N>(noises, &mut psl_cache, chunk_min_x, chunk_min_z);
         let p10 =
             preliminary_surface_level::<N>(noises, &mut psl_cache, chunk_min_x + 16, chunk_min_z);
@@ -475,7 +626,48 @@ impl<N: DimensionNoises> ChunkGenerator for VanillaGenerator<N> {
 
     fn apply_carvers(&self, _chunk: &ChunkAccess) {}
 
-    fn apply_biome_decorations(&self, _chunk: &ChunkAccess) {}
+    /// Vanilla: `ChunkGenerator.applyBiomeDecoration()`.
+    // TODO: Place biome features; only structures and the End spikes exist so far
+    fn apply_biome_decorations(&self, chunk: &ChunkAccess, structures: &[StructureStart]) {
+        let pos = chunk.pos();
+        let min_x = pos.0.x * 16;
+        let min_z = pos.0.y * 16;
+        let mut random = WorldgenRandom::new(RandomSource::Xoroshiro(Xoroshiro::from_seed(0)));
+        let decoration_seed = random.set_decoration_seed(self.seed, min_x, min_z);
+        // Vanilla: `ChunkGenerator.getWritableArea()`
+        let writable_area = BoundingBox {
+            min_x,
+            min_y: N::Settings::MIN_Y + 1,
+            min_z,
+            max_x: min_x + 15,
+            max_y: N::Settings::MIN_Y + N::Settings::HEIGHT - 1,
+            max_z: min_z + 15,
+        };
+
+        for step in 0..DECORATION_STEPS.len() {
+            let step_structures = Structure::all()
+                .iter()
+                .filter(|structure| structure.step == step);
+            for (index, structure) in step_structures.enumerate() {
+                random.set_feature_seed(decoration_seed, index as i32, step as i32);
+                for start in structures
+                    .iter()
+                    .filter(|start| start.structure == structure.id)
+                {
+                    start.place_in_chunk(chunk, &mut random, writable_area);
+                }
+            }
+
+            if step == SURFACE_STRUCTURES_STEP
+                && let Some(spikes) = &self.end_spikes
+            {
+                random.set_feature_seed(decoration_seed, END_SPIKE_FEATURE_INDEX, step as i32);
+                for spike in spikes {
+                    place_end_spike(chunk, spike, &mut random);
+                }
+            }
+        }
+    }
 }
 
 // ── BiomeManager biome zoom helpers ──────────────────────────────────────────
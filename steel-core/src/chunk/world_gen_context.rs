@@ -13,6 +13,7 @@ use crate::chunk::{
     vanilla_generator::VanillaGenerator,
 };
 use crate::world::World;
+use crate::worldgen::BiomeSourceKind;
 
 /// Type alias for overworld generator.
 pub type OverworldGenerator = VanillaGenerator<OverworldNoises>;
@@ -37,6 +38,21 @@ pub enum ChunkGeneratorType {
     //Custom(Box<dyn ChunkGenerator>),
 }
 
+impl ChunkGeneratorType {
+    /// Returns the biome source of generators that sample biomes from noise.
+    ///
+    /// Flat and empty generators have no biome source.
+    #[must_use]
+    pub fn biome_source(&self) -> Option<&BiomeSourceKind> {
+        match self {
+            Self::Overworld(generator) => Some(generator.biome_source()),
+            Self::Nether(generator) => Some(generator.biome_source()),
+            Self::End(generator) => Some(generator.biome_source()),
+            Self::Flat(_) | Self::Empty(_) => None,
+        }
+    }
+}
+
 /// Context for world generation.
 ///
 /// Similar to vanilla's `WorldGenContext`, this provides access to the level/dimension
@@ -42,7 +42,13 @@ pub const REGION_MAGIC: [u8; 4] = *b"STLR";
 /// v5: Added heightmap persistence (`PersistentHeightmap`).
 /// v6: Added structure start and structure reference persistence.
 /// v7: Added POI persistence (`PersistentPoi`).
-pub const FORMAT_VERSION: u16 = 7;
+/// v8: Added chunk inhabited time.
+/// v9: Added blending data for chunks from older generation.
+/// v10: Added the chunk data version.
+///
+/// Regions from v9 on are rewritten in the current layout when opened, see
+/// [`super::upgrade`].
+pub const FORMAT_VERSION: u16 = 10;
 
 /// Number of chunks per region side (32×32 = 1024 chunks per region).
 pub const REGION_SIZE: usize = 32;
@@ -293,6 +299,8 @@ pub struct PersistentHeightmap {
 /// self-contained. Sections reference indices into these chunk-level palettes.
 #[derive(SchemaWrite, SchemaRead)]
 pub struct PersistentChunk {
+    /// Version of the saved data, see [`super::upgrade::CHUNK_DATA_VERSION`].
+    pub data_version: i32,
     /// Unix timestamp of last modification.
     pub last_modified: u32,
     /// Block states used in this chunk. Sections reference indices into this.
@@ -317,6 +325,24 @@ pub struct PersistentChunk {
     pub structure_references: Vec<PersistentStructureReference>,
     /// POI occupancy data (ticket state for beds, workstations, etc.).
     pub pois: Vec<PersistentPoi>,
+    /// Total ticks players have spent near this chunk (vanilla `InhabitedTime`).
+    pub inhabited_time: i64,
+    /// Blending data, if the chunk is from older generation.
+    pub blending_data: Option<PersistentBlendingData>,
+}
+
+/// Blending data of a chunk from older generation.
+///
+/// Only the sampled heights are kept; densities and biomes are sampled again
+/// after loading, like vanilla's `BlendingData.Packed`.
+#[derive(SchemaWrite, SchemaRead)]
+pub struct PersistentBlendingData {
+    /// Lowest section of the old generation.
+    pub min_section: i32,
+    /// Section above the highest section of the old generation.
+    pub max_section: i32,
+    /// Sampled border heights, empty if none have been sampled yet.
+    pub heights: Vec<f64>,
 }
 
 /// A 16×16×16 section of a chunk.
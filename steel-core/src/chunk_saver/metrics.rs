@@ -0,0 +1,90 @@
+//! Counters for the chunk saves of a world.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Counts chunk saves and how long their parts took.
+///
+/// Snapshots are taken on the tick thread, so their time is what saving adds
+/// to MSPT. Writes include packing, compression and IO on the chunk runtime.
+#[derive(Debug, Default)]
+pub struct ChunkSaveMetrics {
+    saved: AtomicU64,
+    failed: AtomicU64,
+    snapshots: AtomicU64,
+    snapshot_nanos: AtomicU64,
+    write_nanos: AtomicU64,
+}
+
+impl ChunkSaveMetrics {
+    /// Records a chunk snapshot taken on the tick thread.
+    pub fn record_snapshot(&self, time: Duration) {
+        self.snapshots.fetch_add(1, Ordering::Relaxed);
+        self.snapshot_nanos
+            .fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Records a chunk that finished writing.
+    pub fn record_saved(&self, time: Duration) {
+        self.saved.fetch_add(1, Ordering::Relaxed);
+        self.write_nanos
+            .fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Records a chunk that failed to save.
+    pub fn record_failed(&self) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// How many chunks were saved.
+    #[must_use]
+    pub fn saved(&self) -> u64 {
+        self.saved.load(Ordering::Relaxed)
+    }
+
+    /// How many chunks failed to save.
+    #[must_use]
+    pub fn failed(&self) -> u64 {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// The average time a snapshot took on the tick thread.
+    #[must_use]
+    pub fn average_snapshot(&self) -> Duration {
+        average(
+            self.snapshot_nanos.load(Ordering::Relaxed),
+            self.snapshots.load(Ordering::Relaxed),
+        )
+    }
+
+    /// The average time from a snapshot to the chunk being written.
+    #[must_use]
+    pub fn average_write(&self) -> Duration {
+        average(self.write_nanos.load(Ordering::Relaxed), self.saved())
+    }
+}
+
+fn average(total_nanos: u64, count: u64) -> Duration {
+    Duration::from_nanos(total_nanos.checked_div(count).unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_averages() {
+        let metrics = ChunkSaveMetrics::default();
+        assert_eq!(metrics.average_snapshot(), Duration::ZERO);
+
+        metrics.record_snapshot(Duration::from_micros(100));
+        metrics.record_snapshot(Duration::from_micros(300));
+        metrics.record_saved(Duration::from_millis(4));
+        metrics.record_failed();
+
+        assert_eq!(metrics.average_snapshot(), Duration::from_micros(200));
+        assert_eq!(metrics.average_write(), Duration::from_millis(4));
+        assert_eq!(metrics.saved(), 1);
+        assert_eq!(metrics.failed(), 1);
+    }
+}
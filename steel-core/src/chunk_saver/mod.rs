@@ -29,14 +29,19 @@
 //! - **Power-of-2 bit packing** for efficient storage (1, 2, 4, 8, 16 bits)
 //! - **Homogeneous section optimization** (single block type = no bit array)
 //! - **zstd compression** per-chunk for good compression ratios
+//! - **Upgrades** of regions and chunks saved by older revisions
 
 mod bit_pack;
 mod format;
+mod metrics;
 mod ram_only;
 mod region_manager;
 mod storage;
+mod upgrade;
 
 pub use format::*;
+pub use metrics::*;
 pub use ram_only::*;
 pub use region_manager::*;
 pub use storage::*;
+pub use upgrade::*;
@@ -15,17 +15,19 @@ use steel_utils::{ChunkPos, locks::AsyncRwLock};
 use tokio::{
     fs::{self, File, OpenOptions},
     io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    task::spawn_blocking,
 };
 
 use crate::chunk::chunk_access::{ChunkAccess, ChunkStatus};
 use crate::world::World;
 
 use super::{
-    ChunkStorage, PersistentChunk,
+    ChunkStorage, OLDEST_UPGRADABLE_FORMAT, PersistentChunk, decode_chunk,
     format::{
-        CHUNK_TABLE_SIZE, FILE_HEADER_SIZE, FIRST_DATA_SECTOR, FORMAT_VERSION, MAX_CHUNK_SIZE,
-        REGION_MAGIC, RegionHeader, RegionPos, SECTOR_SIZE,
+        CHUNK_TABLE_SIZE, ChunkEntry, FILE_HEADER_SIZE, FIRST_DATA_SECTOR, FORMAT_VERSION,
+        MAX_CHUNK_SIZE, REGION_MAGIC, RegionHeader, RegionPos, SECTOR_SIZE,
     },
+    upgrade_chunk,
 };
 
 /// Manages region files with seek-based chunk access.
@@ -108,8 +110,21 @@ impl RegionManager {
         }
 
         let version = u16::from_le_bytes([header_bytes[4], header_bytes[5]]);
+        if version > FORMAT_VERSION {
+            // Written by a newer revision, don't touch it
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Region file {} has version {version}, newer than {FORMAT_VERSION}",
+                    path.display()
+                ),
+            ));
+        }
+        if version >= OLDEST_UPGRADABLE_FORMAT && version < FORMAT_VERSION {
+            return self.upgrade_region(pos, file, version).await;
+        }
         if version != FORMAT_VERSION {
-            // Version mismatch — backup the old file and create a fresh region.
+            // Too old to upgrade — backup the old file and create a fresh region.
             drop(file);
             let backup_path = path.with_extension(format!("srg.v{version}.bak"));
             tracing::warn!(
@@ -139,6 +154,57 @@ impl RegionManager {
         })
     }
 
+    /// Rewrites a region file from an older layout in the current one, keeping
+    /// the old file as a backup.
+    async fn upgrade_region(
+        &self,
+        pos: RegionPos,
+        mut file: File,
+        version: u16,
+    ) -> io::Result<RegionHandle> {
+        let path = self.region_path(pos);
+        let mut table_bytes = vec![0u8; CHUNK_TABLE_SIZE];
+        file.read_exact(&mut table_bytes).await?;
+        let old_header = RegionHeader::from_bytes(&table_bytes);
+
+        let mut chunks = Vec::new();
+        for (index, entry) in old_header.entries.iter().enumerate() {
+            if !entry.exists() {
+                continue;
+            }
+            let compressed =
+                Self::read_chunk_data(&mut file, entry.sector_offset, entry.size_bytes).await?;
+            let data = zstd::decode_all(&compressed[..])?;
+            let persistent = decode_chunk(version, &data)?;
+            chunks.push((index, entry.status, Self::encode_chunk(&persistent)?));
+        }
+        drop(file);
+
+        let backup_path = path.with_extension(format!("srg.v{version}.bak"));
+        tracing::info!(
+            "Upgrading region file {} from version {version} to {FORMAT_VERSION}, backing up to {}",
+            path.display(),
+            backup_path.display()
+        );
+        fs::rename(&path, &backup_path).await?;
+
+        let mut handle = self.create_region(pos).await?;
+        for (index, status, compressed) in chunks {
+            let sector_offset = handle.file_sectors;
+            Self::write_chunk_data(
+                &mut handle.file,
+                sector_offset,
+                &compressed,
+                &mut handle.file_sectors,
+            )
+            .await?;
+            handle.header.entries[index] =
+                ChunkEntry::new(sector_offset, compressed.len() as u32, status);
+        }
+        Self::write_header(&mut handle.file, &handle.header).await?;
+        Ok(handle)
+    }
+
     /// Creates a new empty region file.
     async fn create_region(&self, pos: RegionPos) -> io::Result<RegionHandle> {
         fs::create_dir_all(&self.base_path).await?;
@@ -225,6 +291,9 @@ impl RegionManager {
 
     /// Saves prepared chunk data to disk. This is the async part that doesn't
     /// need to hold the chunk lock.
+    ///
+    /// The chunk is encoded and compressed on the blocking pool before the
+    /// regions are locked, so other saves and loads only wait for the write.
     #[expect(
         clippy::missing_panics_doc,
         reason = "panic on `just inserted` is unreachable"
@@ -239,6 +308,8 @@ impl RegionManager {
         let (local_x, local_z) = RegionPos::local_chunk_pos(pos.0.x, pos.0.y);
         let index = RegionHeader::chunk_index(local_x, local_z);
 
+        let compressed = Self::encode_chunk_checked(prepared.persistent).await?;
+
         let mut regions = self.regions.write().await;
 
         // Track if we opened the region (so we can close it after)
@@ -253,29 +324,6 @@ impl RegionManager {
             regions.get_mut(&region_pos).expect("just inserted")
         };
 
-        // Serialize the prepared data
-        let persistent = prepared.persistent;
-        let data = wincode::serialize(&persistent)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-
-        // Compress with zstd
-        let compressed = zstd::encode_all(&data[..], 3)?;
-
-        if compressed.len() > MAX_CHUNK_SIZE {
-            // Clean up if we opened the region
-            if we_opened_region {
-                regions.remove(&region_pos);
-            }
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "Chunk too large: {} bytes (max {})",
-                    compressed.len(),
-                    MAX_CHUNK_SIZE
-                ),
-            ));
-        }
-
         // Find space for the chunk
         let sectors_needed = compressed.len().div_ceil(SECTOR_SIZE) as u32;
         let old_entry = handle.header.entries[index];
@@ -300,7 +348,7 @@ impl RegionManager {
 
         // Update header entry
         handle.header.entries[index] =
-            super::format::ChunkEntry::new(sector_offset, compressed.len() as u32, status);
+            ChunkEntry::new(sector_offset, compressed.len() as u32, status);
 
         // If we opened this region and no chunks are loaded from it,
         // write the header and close it immediately
@@ -314,6 +362,31 @@ impl RegionManager {
         Ok(true)
     }
 
+    /// Encodes a chunk on the blocking pool, failing if it doesn't fit in a region.
+    async fn encode_chunk_checked(persistent: PersistentChunk) -> io::Result<Vec<u8>> {
+        let compressed = spawn_blocking(move || Self::encode_chunk(&persistent))
+            .await
+            .map_err(io::Error::other)??;
+        if compressed.len() > MAX_CHUNK_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Chunk too large: {} bytes (max {})",
+                    compressed.len(),
+                    MAX_CHUNK_SIZE
+                ),
+            ));
+        }
+        Ok(compressed)
+    }
+
+    /// Serializes a chunk and compresses it with zstd.
+    fn encode_chunk(persistent: &PersistentChunk) -> io::Result<Vec<u8>> {
+        let data = wincode::serialize(persistent)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        zstd::encode_all(&data[..], 3)
+    }
+
     /// Loads a chunk from the appropriate region.
     ///
     /// Automatically opens the region if not already open. The region's reference
@@ -360,14 +433,18 @@ impl RegionManager {
         // Decompress
         let data = zstd::decode_all(&compressed[..])?;
 
-        // Deserialize
-        let persistent: PersistentChunk = wincode::deserialize(&data)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        // Deserialize and bring the data up to date
+        let mut persistent = decode_chunk(FORMAT_VERSION, &data)?;
+        let upgraded = upgrade_chunk(&mut persistent)?;
 
         // Convert to runtime format (persistent is dropped after this - no duplication!)
         let status = entry.status;
         let chunk =
             ChunkStorage::persistent_to_chunk(&persistent, pos, status, min_y, height, level);
+        if upgraded {
+            // Save the upgraded data so it only runs once
+            chunk.mark_dirty();
+        }
 
         Ok(Some((chunk, status)))
     }
@@ -2,9 +2,9 @@ use crate::block_entity::{BLOCK_ENTITIES, SharedBlockEntity};
 use crate::chunk::chunk_access::{ChunkAccess, ChunkStatus};
 use crate::chunk::heightmap::{ChunkHeightmaps, Heightmap, HeightmapType};
 use crate::chunk::level_chunk::LevelChunk;
-use crate::chunk::paletted_container::PalettedContainer;
+use crate::chunk::paletted_container::{BiomePalette, BlockPalette, PalettedContainer};
 use crate::chunk::proto_chunk::ProtoChunk;
-use crate::chunk::section::{ChunkSection, SectionHolder, Sections};
+use crate::chunk::section::{ChunkSection, Sections};
 use crate::chunk_saver::bit_pack::{bits_for_palette_len, pack_indices, unpack_indices};
 use crate::entity::{ENTITIES, SharedEntity};
 use crate::world::World;
@@ -12,10 +12,12 @@ use crate::world::tick_scheduler::{BlockTickList, FluidTickList, ScheduledTick,
 use simdnbt::borrow::read_compound as read_borrowed_compound;
 use simdnbt::owned::NbtCompound;
 use std::io::Cursor;
+use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{io, sync::Weak};
 use steel_registry::{REGISTRY, Registry, RegistryEntry, RegistryExt, vanilla_biomes};
+use steel_utils::density::BlendingData;
 use steel_utils::{BlockPos, BlockStateId, ChunkPos, Direction, Identifier};
 
 use crate::world::structure::{
@@ -48,10 +50,10 @@ const fn direction_from_2d(value: i8) -> Option<Direction> {
 use super::ram_only::RamOnlyStorage;
 use super::region_manager::RegionManager;
 use super::{
-    PersistentBiomeData, PersistentBlockEntity, PersistentBlockState, PersistentChunk,
-    PersistentEntity, PersistentHeightmap, PersistentPoi, PersistentSection,
-    PersistentStructurePiece, PersistentStructureReference, PersistentStructureStart,
-    PersistentTick, PreparedChunkSave,
+    CHUNK_DATA_VERSION, PersistentBiomeData, PersistentBlendingData, PersistentBlockEntity,
+    PersistentBlockState, PersistentChunk, PersistentEntity, PersistentHeightmap, PersistentPoi,
+    PersistentSection, PersistentStructurePiece, PersistentStructureReference,
+    PersistentStructureStart, PersistentTick, PreparedChunkSave,
 };
 
 /// Builder for creating a persistent chunk with its own palettes.
@@ -116,6 +118,39 @@ impl<'a> ChunkBuilder<'a> {
     }
 }
 
+/// A copy of a chunk's data, taken by [`ChunkStorage::snapshot_chunk`].
+///
+/// Entities, block entities and ticks are already in persistent form; the
+/// sections are plain copies of the palettes, packed by [`Self::into_prepared`].
+pub struct ChunkSnapshot {
+    pos: ChunkPos,
+    sections: Vec<(BlockPalette, BiomePalette)>,
+    /// Everything but the sections and their palettes.
+    persistent: PersistentChunk,
+}
+
+impl ChunkSnapshot {
+    /// Packs the sections, finishing the chunk for `save_chunk_data`.
+    #[must_use]
+    pub fn into_prepared(self) -> PreparedChunkSave {
+        let mut persistent = self.persistent;
+        let mut builder = ChunkBuilder::new(&REGISTRY);
+        persistent.sections = self
+            .sections
+            .iter()
+            .map(|(states, biomes)| {
+                ChunkStorage::section_to_persistent(states, biomes, &mut builder)
+            })
+            .collect();
+        persistent.block_states = builder.block_states;
+        persistent.biomes = builder.biomes;
+        PreparedChunkSave {
+            pos: self.pos,
+            persistent,
+        }
+    }
+}
+
 /// Chunk storage backend.
 ///
 /// This enum provides persistence for chunks, either to disk (region files)
@@ -204,22 +239,26 @@ impl ChunkStorage {
         }
     }
 
-    /// Saves a chunk to the appropriate region.
-    ///
-    /// The chunk is serialized, compressed, and written to disk immediately.
-    /// If the region was already open (has loaded chunks), the header update is
-    /// deferred. If this call opened the region, it will be closed after saving.
-    ///
-    /// If the chunk is not dirty, this is a no-op and returns `Ok(false)`.
-    /// Returns `Ok(true)` if the chunk was saved.
     /// Prepares chunk data for saving. Call this while holding the chunk lock,
     /// then pass the result to `save_chunk_data` after releasing the lock.
+    ///
+    /// Returns `None` if the chunk is not dirty.
+    #[must_use]
+    pub fn prepare_chunk_save(chunk: &ChunkAccess) -> Option<PreparedChunkSave> {
+        Self::snapshot_chunk(chunk).map(ChunkSnapshot::into_prepared)
+    }
+
+    /// Copies what needs saving out of a chunk, cheap enough for the tick
+    /// thread. Packing the sections is left to [`ChunkSnapshot::into_prepared`],
+    /// which can run anywhere since the snapshot owns its data.
+    ///
+    /// Returns `None` if the chunk is not dirty.
     #[must_use]
     #[expect(
         clippy::similar_names,
         reason = "`pois` vs `pos` are semantically distinct"
     )]
-    pub fn prepare_chunk_save(chunk: &ChunkAccess) -> Option<PreparedChunkSave> {
+    pub fn snapshot_chunk(chunk: &ChunkAccess) -> Option<ChunkSnapshot> {
         if !chunk.is_dirty() {
             return None;
         }
@@ -265,8 +304,17 @@ impl ChunkStorage {
             .map(|c| Self::pois_to_persistent(c, pos))
             .unwrap_or_default();
 
+        let sections = chunk
+            .sections()
+            .sections
+            .iter()
+            .map(|section| {
+                let section = section.read();
+                (section.states.clone(), section.biomes.clone())
+            })
+            .collect();
+
         let persistent = Self::to_persistent(
-            chunk.sections(),
             &block_entities,
             &entities,
             block_ticks,
@@ -275,20 +323,28 @@ impl ChunkStorage {
             structure_starts,
             structure_references,
             pois,
+            chunk.inhabited_time(),
+            chunk
+                .blending_data()
+                .map(|data| Self::blending_data_to_persistent(&data)),
             pos,
         );
 
-        Some(PreparedChunkSave { pos, persistent })
+        Some(ChunkSnapshot {
+            pos,
+            sections,
+            persistent,
+        })
     }
 
-    /// Converts chunk data to persistent format.
+    /// Converts chunk data to persistent format, leaving out the sections and
+    /// their palettes.
     #[expect(
         clippy::too_many_arguments,
         clippy::similar_names,
         reason = "chunk serialization requires all fields; `block_ticks`/`fluid_ticks` are distinct"
     )]
     fn to_persistent(
-        sections: &Sections,
         block_entities: &[SharedBlockEntity],
         entities: &[SharedEntity],
         block_ticks: Vec<PersistentTick>,
@@ -297,16 +353,10 @@ impl ChunkStorage {
         structure_starts: Vec<PersistentStructureStart>,
         structure_references: Vec<PersistentStructureReference>,
         pois: Vec<PersistentPoi>,
+        inhabited_time: i64,
+        blending_data: Option<PersistentBlendingData>,
         chunk_pos: ChunkPos,
     ) -> PersistentChunk {
-        let mut builder = ChunkBuilder::new(&REGISTRY);
-
-        let persistent_sections = sections
-            .sections
-            .iter()
-            .map(|section| Self::section_to_persistent(section, &mut builder))
-            .collect();
-
         // Serialize block entities
         let persistent_block_entities: Vec<PersistentBlockEntity> = block_entities
             .iter()
@@ -350,7 +400,7 @@ impl ChunkStorage {
 
                 // Serialize type-specific NBT data
                 let mut nbt = NbtCompound::new();
-                entity.save_additional(&mut nbt);
+                entity.save_data(&mut nbt);
                 let mut nbt_bytes = Vec::new();
                 nbt.write(&mut nbt_bytes);
 
@@ -367,12 +417,13 @@ impl ChunkStorage {
             .collect();
 
         PersistentChunk {
+            data_version: CHUNK_DATA_VERSION,
             last_modified: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .map_or(0, |d| d.as_secs() as u32),
-            block_states: builder.block_states,
-            biomes: builder.biomes,
-            sections: persistent_sections,
+            block_states: Vec::new(),
+            biomes: Vec::new(),
+            sections: Vec::new(),
             block_entities: persistent_block_entities,
             entities: persistent_entities,
             block_ticks,
@@ -381,18 +432,20 @@ impl ChunkStorage {
             structure_starts,
             structure_references,
             pois,
+            inhabited_time,
+            blending_data,
         }
     }
 
     /// Converts a runtime section to persistent format.
     fn section_to_persistent(
-        section: &SectionHolder,
+        states: &BlockPalette,
+        biomes: &BiomePalette,
         builder: &mut ChunkBuilder,
     ) -> PersistentSection {
-        let section = section.read();
-        let biomes = Self::biomes_to_persistent(&section.biomes, builder);
+        let biomes = Self::biomes_to_persistent(biomes, builder);
 
-        match &section.states {
+        match states {
             PalettedContainer::Homogeneous(block_id) => {
                 let block_idx = builder.ensure_block_state(*block_id);
                 PersistentSection::Homogeneous {
@@ -400,29 +453,19 @@ impl ChunkStorage {
                     biomes,
                 }
             }
-            PalettedContainer::Heterogeneous(data) => {
+            states => {
+                let (values, indices) = states
+                    .palette_and_indices()
+                    .expect("Only homogeneous sections have no palette");
                 // Build section-local palette (indices into chunk's block_states)
-                let palette: Vec<u16> = data
-                    .palette
+                let palette: Vec<u16> = values
                     .iter()
-                    .map(|(block_id, _)| builder.ensure_block_state(*block_id))
+                    .map(|block_id| builder.ensure_block_state(*block_id))
                     .collect();
 
                 // Pack block indices (indices into section-local palette)
                 let bits = bits_for_palette_len(palette.len())
                     .expect("Heterogeneous section should have palette length >= 2");
-                let indices: Vec<u32> = data
-                    .cube
-                    .iter()
-                    .flatten()
-                    .flatten()
-                    .map(|block_id| {
-                        data.palette
-                            .iter()
-                            .position(|(v, _)| v == block_id)
-                            .unwrap_or(0) as u32
-                    })
-                    .collect();
 
                 let block_data = pack_indices(&indices, bits);
 
@@ -446,28 +489,18 @@ impl ChunkStorage {
                 let biome_idx = builder.ensure_biome(*biome_id);
                 PersistentBiomeData::Homogeneous { biome: biome_idx }
             }
-            PalettedContainer::Heterogeneous(data) => {
+            biomes => {
+                let (values, indices) = biomes
+                    .palette_and_indices()
+                    .expect("Only homogeneous biome data has no palette");
                 // Build section-local palette (indices into chunk's biomes)
-                let palette: Vec<u16> = data
-                    .palette
+                let palette: Vec<u16> = values
                     .iter()
-                    .map(|(biome_id, _)| builder.ensure_biome(*biome_id))
+                    .map(|biome_id| builder.ensure_biome(*biome_id))
                     .collect();
 
                 let bits = bits_for_palette_len(palette.len())
                     .expect("Heterogeneous biome data should have palette length >= 2");
-                let indices: Vec<u32> = data
-                    .cube
-                    .iter()
-                    .flatten()
-                    .flatten()
-                    .map(|biome_id| {
-                        data.palette
-                            .iter()
-                            .position(|(v, _)| v == biome_id)
-                            .unwrap_or(0) as u32
-                    })
-                    .collect();
 
                 let biome_data = pack_indices(&indices, bits);
 
@@ -531,6 +564,11 @@ impl ChunkStorage {
                     structure_starts,
                     structure_references,
                 );
+                chunk
+                    .inhabited_time
+                    .store(persistent.inhabited_time, Ordering::Relaxed);
+                *chunk.blending_data.lock() =
+                    Self::persistent_to_blending_data(persistent.blending_data.as_ref());
 
                 // Load block entities
                 for persistent_be in &persistent.block_entities {
@@ -573,15 +611,23 @@ impl ChunkStorage {
 
                 ChunkAccess::Full(chunk)
             }
-            _ => ChunkAccess::Proto(ProtoChunk::from_disk(
-                Sections::from_owned(sections.into_boxed_slice()),
-                pos,
-                status,
-                min_y,
-                height,
-                structure_starts,
-                structure_references,
-            )),
+            _ => {
+                let proto_chunk = ProtoChunk::from_disk(
+                    Sections::from_owned(sections.into_boxed_slice()),
+                    pos,
+                    status,
+                    min_y,
+                    height,
+                    structure_starts,
+                    structure_references,
+                );
+                proto_chunk
+                    .inhabited_time
+                    .store(persistent.inhabited_time, Ordering::Relaxed);
+                *proto_chunk.blending_data.lock() =
+                    Self::persistent_to_blending_data(persistent.blending_data.as_ref());
+                ChunkAccess::Proto(proto_chunk)
+            }
         }
     }
 
@@ -932,6 +978,28 @@ impl ChunkStorage {
             .collect()
     }
 
+    /// Converts blending data to persistent format, keeping only the sampled heights.
+    fn blending_data_to_persistent(data: &BlendingData) -> PersistentBlendingData {
+        PersistentBlendingData {
+            min_section: data.min_section(),
+            max_section: data.max_section(),
+            heights: data.heights().map(|h| h.to_vec()).unwrap_or_default(),
+        }
+    }
+
+    /// Reconstructs blending data from persistent data.
+    fn persistent_to_blending_data(
+        persistent: Option<&PersistentBlendingData>,
+    ) -> Option<Arc<BlendingData>> {
+        let persistent = persistent?;
+        let heights = persistent.heights.as_slice().try_into().ok();
+        Some(Arc::new(BlendingData::new(
+            persistent.min_section,
+            persistent.max_section,
+            heights,
+        )))
+    }
+
     /// Collects POI occupancy data from the world's POI storage for this chunk.
     fn pois_to_persistent(chunk: &LevelChunk, chunk_pos: ChunkPos) -> Vec<PersistentPoi> {
         let Some(world) = chunk.get_level() else {
@@ -0,0 +1,220 @@
+//! Upgrading chunks saved by older Steel revisions.
+//!
+//! Two versions describe a saved chunk:
+//! - The region layout version ([`FORMAT_VERSION`]) in the region file header,
+//!   which changes when the binary layout of [`PersistentChunk`] changes.
+//! - The chunk data version ([`CHUNK_DATA_VERSION`]) stored with every chunk,
+//!   which changes when the meaning of saved data changes (renamed blocks,
+//!   moved NBT keys, ...).
+//!
+//! Regions in an older layout are decoded with the layout they were written in
+//! and rewritten in the current one. Chunks with an older data version then run
+//! through [`CHUNK_UPGRADES`] when they are loaded.
+
+use std::io;
+
+use steel_utils::Identifier;
+use thiserror::Error;
+use wincode::{SchemaRead, SchemaWrite};
+
+use super::format::{
+    FORMAT_VERSION, PersistentBlendingData, PersistentBlockEntity, PersistentBlockState,
+    PersistentChunk, PersistentEntity, PersistentHeightmap, PersistentPoi, PersistentSection,
+    PersistentStructureReference, PersistentStructureStart, PersistentTick,
+};
+
+/// Current chunk data version. Increment and add an entry to [`CHUNK_UPGRADES`]
+/// when saved chunk data needs migrating.
+/// v1: Chunks carry a data version.
+pub const CHUNK_DATA_VERSION: i32 = 1;
+
+/// Data version of chunks saved before chunks carried one.
+pub const UNVERSIONED_CHUNK_DATA: i32 = 0;
+
+/// The lowest vanilla `DataVersion` (15w32a). Chunks imported from vanilla
+/// worlds keep their vanilla data version, which never overlaps Steel's.
+pub const MIN_VANILLA_DATA_VERSION: i32 = 100;
+
+/// Oldest region layout that can still be read and rewritten.
+pub const OLDEST_UPGRADABLE_FORMAT: u16 = 9;
+
+/// A migration of chunk data from one data version to the next.
+pub struct ChunkUpgrade {
+    /// The data version the upgrade starts from.
+    pub from: i32,
+    /// Rewrites the chunk so it matches data version `from + 1`.
+    pub upgrade: fn(&mut PersistentChunk),
+}
+
+/// The chunk upgrades, ordered by the version they start from. Versions without
+/// an entry need no changes to their data.
+pub static CHUNK_UPGRADES: &[ChunkUpgrade] = &[];
+
+/// Why a saved chunk couldn't be upgraded.
+#[derive(Debug, Error)]
+pub enum ChunkUpgradeError {
+    /// The chunk was saved by a newer Steel revision.
+    #[error("chunk data version {0} is newer than supported version {CHUNK_DATA_VERSION}")]
+    TooNew(i32),
+    /// The chunk was imported from a vanilla world.
+    // TODO: Port vanilla's `DataFixers` for the chunk format to import vanilla worlds
+    #[error("chunk has vanilla data version {0}, which can't be upgraded yet")]
+    Vanilla(i32),
+}
+
+impl From<ChunkUpgradeError> for io::Error {
+    fn from(err: ChunkUpgradeError) -> Self {
+        Self::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Runs the upgrades a chunk needs to reach [`CHUNK_DATA_VERSION`].
+///
+/// Returns whether the chunk changed, so callers can save it again.
+///
+/// # Errors
+/// Returns an error if the chunk was saved by a newer revision or by vanilla.
+pub fn upgrade_chunk(chunk: &mut PersistentChunk) -> Result<bool, ChunkUpgradeError> {
+    let version = chunk.data_version;
+    if version >= MIN_VANILLA_DATA_VERSION {
+        return Err(ChunkUpgradeError::Vanilla(version));
+    }
+    if version > CHUNK_DATA_VERSION {
+        return Err(ChunkUpgradeError::TooNew(version));
+    }
+    if version == CHUNK_DATA_VERSION {
+        return Ok(false);
+    }
+    for upgrade in CHUNK_UPGRADES
+        .iter()
+        .filter(|upgrade| upgrade.from >= version)
+    {
+        (upgrade.upgrade)(chunk);
+    }
+    chunk.data_version = CHUNK_DATA_VERSION;
+    Ok(true)
+}
+
+/// Decodes a chunk written in the given region layout.
+///
+/// # Errors
+/// Returns an error if the data doesn't match the layout, or the layout is
+/// older than [`OLDEST_UPGRADABLE_FORMAT`].
+pub fn decode_chunk(format_version: u16, data: &[u8]) -> io::Result<PersistentChunk> {
+    match format_version {
+        FORMAT_VERSION => wincode::deserialize(data).map_err(invalid_data),
+        9 => wincode::deserialize::<PersistentChunkV9>(data)
+            .map(PersistentChunk::from)
+            .map_err(invalid_data),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Region layout version {format_version} can't be upgraded"),
+        )),
+    }
+}
+
+fn invalid_data(err: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+/// Layout v9 of [`PersistentChunk`], before chunks carried a data version.
+#[derive(SchemaWrite, SchemaRead)]
+struct PersistentChunkV9 {
+    last_modified: u32,
+    block_states: Vec<PersistentBlockState>,
+    biomes: Vec<Identifier>,
+    sections: Vec<PersistentSection>,
+    block_entities: Vec<PersistentBlockEntity>,
+    entities: Vec<PersistentEntity>,
+    block_ticks: Vec<PersistentTick>,
+    fluid_ticks: Vec<PersistentTick>,
+    heightmaps: Vec<PersistentHeightmap>,
+    structure_starts: Vec<PersistentStructureStart>,
+    structure_references: Vec<PersistentStructureReference>,
+    pois: Vec<PersistentPoi>,
+    inhabited_time: i64,
+    blending_data: Option<PersistentBlendingData>,
+}
+
+impl From<PersistentChunkV9> for PersistentChunk {
+    fn from(chunk: PersistentChunkV9) -> Self {
+        Self {
+            data_version: UNVERSIONED_CHUNK_DATA,
+            last_modified: chunk.last_modified,
+            block_states: chunk.block_states,
+            biomes: chunk.biomes,
+            sections: chunk.sections,
+            block_entities: chunk.block_entities,
+            entities: chunk.entities,
+            block_ticks: chunk.block_ticks,
+            fluid_ticks: chunk.fluid_ticks,
+            heightmaps: chunk.heightmaps,
+            structure_starts: chunk.structure_starts,
+            structure_references: chunk.structure_references,
+            pois: chunk.pois,
+            inhabited_time: chunk.inhabited_time,
+            blending_data: chunk.blending_data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_v9() -> PersistentChunkV9 {
+        PersistentChunkV9 {
+            last_modified: 7,
+            block_states: Vec::new(),
+            biomes: Vec::new(),
+            sections: Vec::new(),
+            block_entities: Vec::new(),
+            entities: Vec::new(),
+            block_ticks: Vec::new(),
+            fluid_ticks: Vec::new(),
+            heightmaps: Vec::new(),
+            structure_starts: Vec::new(),
+            structure_references: Vec::new(),
+            pois: Vec::new(),
+            inhabited_time: 42,
+            blending_data: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_v9_chunk() {
+        let data = wincode::serialize(&empty_v9()).expect("serializes");
+        let chunk = decode_chunk(9, &data).expect("decodes");
+        assert_eq!(chunk.data_version, UNVERSIONED_CHUNK_DATA);
+        assert_eq!(chunk.last_modified, 7);
+        assert_eq!(chunk.inhabited_time, 42);
+    }
+
+    #[test]
+    fn test_decode_unsupported_layout() {
+        assert!(decode_chunk(OLDEST_UPGRADABLE_FORMAT - 1, &[]).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_chunk() {
+        let mut chunk = PersistentChunk::from(empty_v9());
+        assert!(upgrade_chunk(&mut chunk).expect("upgrades"));
+        assert_eq!(chunk.data_version, CHUNK_DATA_VERSION);
+        assert!(!upgrade_chunk(&mut chunk).expect("already current"));
+    }
+
+    #[test]
+    fn test_upgrade_rejects_newer_and_vanilla() {
+        let mut chunk = PersistentChunk::from(empty_v9());
+        chunk.data_version = CHUNK_DATA_VERSION + 1;
+        assert!(matches!(
+            upgrade_chunk(&mut chunk),
+            Err(ChunkUpgradeError::TooNew(_))
+        ));
+        chunk.data_version = 4_325;
+        assert!(matches!(
+            upgrade_chunk(&mut chunk),
+            Err(ChunkUpgradeError::Vanilla(4_325))
+        ));
+    }
+}
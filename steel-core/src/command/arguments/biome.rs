@@ -0,0 +1,57 @@
+//! A biome argument
+use steel_protocol::packets::game::{ArgumentType, SuggestionEntry, SuggestionType};
+use steel_registry::{REGISTRY, RegistryExt, biome::BiomeRef};
+use steel_utils::Identifier;
+
+use crate::command::{
+    arguments::{CommandArgument, SuggestionContext},
+    context::CommandContext,
+};
+
+/// A biome argument that resolves to a `BiomeRef`.
+///
+/// The client parses this as a resource or tag, but biome tags are not supported yet.
+// TODO: Accept `#tag` once the biome registry has tags
+pub struct BiomeArgument;
+
+impl CommandArgument for BiomeArgument {
+    type Output = BiomeRef;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+        let key = s.strip_prefix("minecraft:").unwrap_or(s).to_owned();
+
+        REGISTRY
+            .biomes
+            .by_key(&Identifier::vanilla(key))
+            .map(|biome| (&arg[1..], biome))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::ResourceOrTag {
+                identifier: "minecraft:worldgen/biome",
+            },
+            Some(SuggestionType::AskServer),
+        )
+    }
+
+    fn suggest(&self, prefix: &str, _suggestion_ctx: &SuggestionContext) -> Vec<SuggestionEntry> {
+        let stripped_prefix = prefix.strip_prefix("minecraft:").unwrap_or(prefix);
+        REGISTRY
+            .biomes
+            .iter()
+            .map(|(_, biome)| SuggestionEntry::new(biome.key.to_string()))
+            .filter(|s| {
+                s.text
+                    .strip_prefix("minecraft:")
+                    .unwrap_or(&s.text)
+                    .starts_with(stripped_prefix)
+            })
+            .collect()
+    }
+}
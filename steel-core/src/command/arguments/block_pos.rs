@@ -0,0 +1,46 @@
+//! A block position argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_utils::BlockPos;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A block position argument, e.g. `1 64 -3` or `~ ~-1 ~`.
+pub struct BlockPosArgument;
+
+impl BlockPosArgument {
+    /// Parses one integer coordinate, relative to `origin` when prefixed with `~`.
+    fn parse_coordinate(s: &str, origin: f64) -> Option<i32> {
+        if let Some(offset) = s.strip_prefix('~') {
+            let offset: f64 = if offset.is_empty() {
+                0.0
+            } else {
+                offset.parse().ok()?
+            };
+            let value = (origin + offset).floor();
+            (value >= f64::from(i32::MIN) && value <= f64::from(i32::MAX)).then_some(value as i32)
+        } else {
+            s.parse().ok()
+        }
+    }
+}
+
+impl CommandArgument for BlockPosArgument {
+    type Output = BlockPos;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let x = Self::parse_coordinate(arg.first()?, context.position.x)?;
+        let y = Self::parse_coordinate(arg.get(1)?, context.position.y)?;
+        let z = Self::parse_coordinate(arg.get(2)?, context.position.z)?;
+
+        Some((&arg[3..], BlockPos::new(x, y, z)))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::BlockPos, None)
+    }
+}
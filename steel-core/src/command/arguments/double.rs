@@ -0,0 +1,73 @@
+//! A double argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A double argument that parses a 64-bit floating point number.
+///
+/// Can optionally have minimum and maximum bounds.
+pub struct DoubleArgument {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl DoubleArgument {
+    /// Creates a new unbounded double argument.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Creates a new double argument with bounds.
+    #[must_use]
+    pub const fn bounded(min: Option<f64>, max: Option<f64>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl Default for DoubleArgument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandArgument for DoubleArgument {
+    type Output = f64;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+        let value: f64 = s.parse().ok()?;
+
+        // Check bounds
+        if let Some(min) = self.min
+            && value < min
+        {
+            return None;
+        }
+        if let Some(max) = self.max
+            && value > max
+        {
+            return None;
+        }
+
+        Some((&arg[1..], value))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::Double {
+                min: self.min,
+                max: self.max,
+            },
+            None,
+        )
+    }
+}
@@ -45,6 +45,10 @@ impl CommandArgument for FloatArgument {
     ) -> Option<(&'a [&'a str], Self::Output)> {
         let s = arg.first()?;
         let value: f32 = s.parse().ok()?;
+        // Vanilla's reader doesn't take NaN or infinity
+        if !value.is_finite() {
+            return None;
+        }
 
         // Check bounds
         if let Some(min) = self.min
@@ -0,0 +1,51 @@
+//! A game profile argument, naming players that may be offline.
+use crate::command::arguments::CommandArgument;
+use crate::command::arguments::SuggestionContext;
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::context::CommandContext;
+use steel_protocol::packets::game::{ArgumentType, SuggestionEntry, SuggestionType};
+use uuid::Uuid;
+
+/// A game profile argument. Selectors match online players, names are also
+/// looked up in the profile cache.
+///
+/// Vanilla: `GameProfileArgument`.
+pub struct GameProfileArgument;
+
+impl CommandArgument for GameProfileArgument {
+    /// The names and UUIDs of the matched players.
+    type Output = Vec<(String, Uuid)>;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let name = *arg.first()?;
+        if !name.starts_with('@')
+            && let Some(profile) = context
+                .server
+                .get_players()
+                .iter()
+                .find(|player| player.gameprofile.name.eq_ignore_ascii_case(name))
+                .map(|player| (player.gameprofile.name.clone(), player.gameprofile.id))
+                .or_else(|| context.server.profile_cache.get_by_name(name))
+        {
+            return Some((&arg[1..], vec![profile]));
+        }
+        let (rest, players) = PlayerArgument::multiple().parse(arg, context)?;
+        let profiles = players
+            .iter()
+            .map(|player| (player.gameprofile.name.clone(), player.gameprofile.id))
+            .collect();
+        Some((rest, profiles))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::GameProfile, Some(SuggestionType::AskServer))
+    }
+
+    fn suggest(&self, prefix: &str, suggestion_ctx: &SuggestionContext) -> Vec<SuggestionEntry> {
+        PlayerArgument::multiple().suggest(prefix, suggestion_ctx)
+    }
+}
@@ -0,0 +1,74 @@
+//! An integer range argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// An inclusive range of integers where either end may be open.
+///
+/// Vanilla equivalent: `MinMaxBounds.Ints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntRange {
+    /// The lowest value in the range, or `None` if unbounded.
+    pub min: Option<i32>,
+    /// The highest value in the range, or `None` if unbounded.
+    pub max: Option<i32>,
+}
+
+impl IntRange {
+    /// Parses a range like `5`, `1..6`, `..6` or `1..`.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let parse_end = |end: &str| -> Option<Option<i32>> {
+            if end.is_empty() {
+                Some(None)
+            } else {
+                end.parse().ok().map(Some)
+            }
+        };
+        let range = match s.split_once("..") {
+            Some((min, max)) => Self {
+                min: parse_end(min)?,
+                max: parse_end(max)?,
+            },
+            None => {
+                let value = s.parse().ok()?;
+                Self {
+                    min: Some(value),
+                    max: Some(value),
+                }
+            }
+        };
+        match range {
+            Self {
+                min: None,
+                max: None,
+            } => None,
+            Self {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => None,
+            range => Some(range),
+        }
+    }
+}
+
+/// An integer range argument, e.g. `1..6`.
+pub struct IntRangeArgument;
+
+impl CommandArgument for IntRangeArgument {
+    type Output = IntRange;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let range = IntRange::parse(arg.first()?)?;
+        Some((&arg[1..], range))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::IntRange, None)
+    }
+}
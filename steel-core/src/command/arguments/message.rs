@@ -0,0 +1,127 @@
+//! A message argument, which players sign like chat messages.
+use std::sync::Arc;
+
+use steel_protocol::packets::game::{
+    ArgumentType, CDisguisedChat, ChatTypeBound, FilterType, SuggestionType,
+};
+use text_components::TextComponent;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+use crate::player::Player;
+use crate::player::chat_filter;
+use crate::player::message_chain::PlayerChatMessage;
+use crate::server::Server;
+
+/// A message taking the rest of the command. Clients sign it if they have a
+/// chat session, so the output is only the text; use [`MessageArgument::resolve`]
+/// to get the message to send.
+///
+/// Vanilla: `MessageArgument`.
+pub struct MessageArgument;
+
+impl CommandArgument for MessageArgument {
+    type Output = String;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        // TODO: Replace entity selectors in the message with the names they match
+        (!arg.is_empty()).then(|| (&[] as &[&str], arg.join(" ")))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::Message, None)
+    }
+}
+
+impl MessageArgument {
+    /// Gets the message argument `name` of the running command: the player's
+    /// signed message if they signed it, otherwise the plain `text`.
+    ///
+    /// Vanilla: `MessageArgument.resolveChatMessage()`.
+    #[must_use]
+    pub fn resolve(context: &CommandContext, name: &str, text: String) -> OutgoingChatMessage {
+        match (context.signing.argument(name), context.sender.get_player()) {
+            (Some(message), Some(sender)) => OutgoingChatMessage::Player {
+                message: message.clone(),
+                filter_mask: chat_filter::filter_message(sender, message.content()),
+                sender: Arc::clone(sender),
+            },
+            _ => OutgoingChatMessage::Disguised(TextComponent::plain(text)),
+        }
+    }
+}
+
+/// A message from a command, as sent to other players.
+///
+/// Vanilla: `OutgoingChatMessage`.
+pub enum OutgoingChatMessage {
+    /// A chat message from a player, shown like their chat.
+    Player {
+        /// The message, signed if the player signed the argument.
+        message: PlayerChatMessage,
+        /// The parts of the message hidden by the chat filter.
+        filter_mask: FilterType,
+        /// The player who sent it.
+        sender: Arc<Player>,
+    },
+    /// Plain text from a sender that didn't sign it, such as the console.
+    Disguised(TextComponent),
+}
+
+impl OutgoingChatMessage {
+    /// Sends the message to `recipient`. Returns `false` if the chat filter hid
+    /// it from them entirely.
+    ///
+    /// Vanilla: `OutgoingChatMessage.sendToPlayer()`.
+    pub fn send_to(&self, recipient: &Player, chat_type: ChatTypeBound) -> bool {
+        match self {
+            Self::Player {
+                message,
+                filter_mask,
+                sender,
+            } => {
+                let Some(filter_type) = sender.filter_mask_for(recipient, filter_mask) else {
+                    return false;
+                };
+                recipient.send_chat_message(message, None, filter_type, chat_type);
+            }
+            Self::Disguised(content) => {
+                recipient.send_packet(CDisguisedChat::new(content, chat_type, recipient));
+            }
+        }
+        true
+    }
+
+    /// Sends the message to every player.
+    ///
+    /// Vanilla: `PlayerList.broadcastChatMessage()`.
+    pub fn broadcast(&self, server: &Server, chat_type: &ChatTypeBound) {
+        match self {
+            Self::Player {
+                message,
+                filter_mask,
+                sender,
+            } => server.broadcast_chat_message(message, sender, None, filter_mask, chat_type),
+            Self::Disguised(_) => {
+                for player in server.get_players() {
+                    self.send_to(&player, chat_type.clone());
+                }
+            }
+        }
+    }
+
+    /// The text of the message.
+    ///
+    /// Vanilla: `OutgoingChatMessage.content()`.
+    #[must_use]
+    pub fn content(&self) -> TextComponent {
+        match self {
+            Self::Player { message, .. } => TextComponent::plain(message.content().to_owned()),
+            Self::Disguised(content) => content.clone(),
+        }
+    }
+}
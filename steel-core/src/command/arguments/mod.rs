@@ -1,14 +1,25 @@
 //! This module contains types and utilities for parsing command arguments.
 pub mod anchor;
+pub mod biome;
+pub mod block_pos;
 pub mod bool;
+pub mod double;
 pub mod enchantment;
 pub mod entity;
 pub mod float;
+pub mod game_profile;
 pub mod gamemode;
+pub mod int_range;
 pub mod integer;
 pub mod item;
+pub mod message;
+pub mod nbt;
 pub mod player;
+pub mod resource_location;
 pub mod rotation;
+pub mod sound;
+pub mod string;
+pub mod template;
 pub mod text_component;
 pub mod time;
 pub mod vector2;
@@ -0,0 +1,107 @@
+//! SNBT and NBT path arguments.
+use std::fmt::Display;
+
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_utils::nbt::NbtPath;
+use steel_utils::nbt::snbt::parse_snbt_prefix;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// Parses one SNBT value from the start of `arg`, returning the unconsumed arguments.
+fn parse_tag<'a>(arg: &'a [&'a str]) -> Option<(&'a [&'a str], NbtTag)> {
+    parse_joined(arg, parse_snbt_prefix)
+}
+
+/// Runs `parser` on the joined arguments, returning the unconsumed arguments.
+///
+/// The value may span several arguments since the input is split on spaces, but it
+/// has to end at an argument boundary.
+fn parse_joined<'a, T, E: Display>(
+    arg: &'a [&'a str],
+    parser: impl FnOnce(&str) -> Result<(T, usize), E>,
+) -> Option<(&'a [&'a str], T)> {
+    let joined = arg.join(" ");
+    let (value, end) = match parser(&joined) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            log::debug!("Invalid NBT argument: {e}");
+            return None;
+        }
+    };
+
+    let mut boundary = 0;
+    for (i, part) in arg.iter().enumerate() {
+        boundary += part.len();
+        if boundary == end {
+            return Some((&arg[i + 1..], value));
+        }
+        if boundary > end {
+            return None;
+        }
+        // The space that joined this argument to the next one
+        boundary += 1;
+    }
+    None
+}
+
+/// A compound tag argument, e.g. `{CustomName: "Steel"}`.
+pub struct CompoundTagArgument;
+
+impl CommandArgument for CompoundTagArgument {
+    type Output = NbtCompound;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        match parse_tag(arg)? {
+            (rest, NbtTag::Compound(compound)) => Some((rest, compound)),
+            _ => None,
+        }
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::Nbt, None)
+    }
+}
+
+/// An argument accepting any tag, e.g. `5b`, `"text"` or `[1, 2, 3]`.
+pub struct NbtTagArgument;
+
+impl CommandArgument for NbtTagArgument {
+    type Output = NbtTag;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        parse_tag(arg)
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::NbtTag, None)
+    }
+}
+
+/// An NBT path argument, e.g. `Inventory[0].id`.
+pub struct NbtPathArgument;
+
+impl CommandArgument for NbtPathArgument {
+    type Output = NbtPath;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        parse_joined(arg, NbtPath::parse_prefix)
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::NbtPath, None)
+    }
+}
@@ -0,0 +1,35 @@
+//! A resource location argument.
+use std::str::FromStr;
+
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_utils::Identifier;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A resource location argument, e.g. `minecraft:foo` or `foo`.
+///
+/// The namespace defaults to `minecraft` when omitted.
+pub struct ResourceLocationArgument;
+
+impl CommandArgument for ResourceLocationArgument {
+    type Output = Identifier;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let s = arg.first()?;
+        let identifier = if s.contains(':') {
+            Identifier::from_str(s).ok()?
+        } else {
+            Identifier::validate_path(s).then(|| Identifier::vanilla((*s).to_owned()))?
+        };
+        Some((&arg[1..], identifier))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::ResourceLocation, None)
+    }
+}
@@ -0,0 +1,32 @@
+//! A sound event argument.
+use steel_protocol::packets::game::{ArgumentType, SuggestionType};
+use steel_utils::Identifier;
+
+use crate::command::arguments::CommandArgument;
+use crate::command::arguments::resource_location::ResourceLocationArgument;
+use crate::command::context::CommandContext;
+
+/// The name of a sound event, e.g. `entity.pig.ambient`.
+///
+/// Any name is accepted, so sounds added by resource packs can be played. The
+/// client suggests the sounds it knows.
+pub struct SoundArgument;
+
+impl CommandArgument for SoundArgument {
+    type Output = Identifier;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        ResourceLocationArgument.parse(arg, context)
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::ResourceLocation,
+            Some(SuggestionType::AvailableSounds),
+        )
+    }
+}
@@ -0,0 +1,73 @@
+//! A string argument.
+use steel_protocol::packets::game::{ArgumentStringTypeBehavior, ArgumentType, SuggestionType};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A string argument, a single word or a phrase in double quotes.
+///
+/// Vanilla: `StringArgumentType`.
+pub struct StringArgument {
+    behavior: ArgumentStringTypeBehavior,
+}
+
+impl StringArgument {
+    /// Creates an argument taking a single word.
+    #[must_use]
+    pub const fn word() -> Self {
+        Self {
+            behavior: ArgumentStringTypeBehavior::SingleWord,
+        }
+    }
+
+    /// Creates an argument taking a single word, or a phrase in double quotes.
+    ///
+    /// The client only accepts letters, digits and `_-.+` in unquoted words,
+    /// so other text has to be quoted.
+    #[must_use]
+    pub const fn quotable() -> Self {
+        Self {
+            behavior: ArgumentStringTypeBehavior::QuotablePhrase,
+        }
+    }
+}
+
+impl CommandArgument for StringArgument {
+    type Output = String;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let first = arg.first()?;
+        if !matches!(self.behavior, ArgumentStringTypeBehavior::QuotablePhrase)
+            || !first.starts_with('"')
+        {
+            return Some((&arg[1..], (*first).to_owned()));
+        }
+
+        // The words were split on spaces, so join them back up to the closing quote
+        // TODO: Handle escaped quotes and backslashes
+        let mut phrase = String::new();
+        for (index, word) in arg.iter().enumerate() {
+            if index > 0 {
+                phrase.push(' ');
+            }
+            phrase.push_str(word);
+            if phrase.len() > 1 && phrase.ends_with('"') {
+                return Some((&arg[index + 1..], phrase[1..phrase.len() - 1].to_owned()));
+            }
+        }
+        None
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (
+            ArgumentType::String {
+                behavior: self.behavior,
+            },
+            None,
+        )
+    }
+}
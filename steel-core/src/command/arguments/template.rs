@@ -0,0 +1,72 @@
+//! Structure template rotation and mirror arguments.
+use steel_protocol::packets::game::{ArgumentType, SuggestionEntry, SuggestionType};
+use steel_utils::{Mirror, Rotation};
+
+use crate::command::arguments::CommandArgument;
+use crate::command::context::CommandContext;
+
+/// A template rotation argument, like `clockwise_90`.
+pub struct TemplateRotationArgument;
+
+impl CommandArgument for TemplateRotationArgument {
+    type Output = Rotation;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let rotation = Rotation::by_name(arg.first()?)?;
+        Some((&arg[1..], rotation))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::TemplateRotation, None)
+    }
+
+    fn suggest(
+        &self,
+        prefix: &str,
+        _suggestion_ctx: &super::SuggestionContext,
+    ) -> Vec<SuggestionEntry> {
+        Rotation::VALUES
+            .into_iter()
+            .map(Rotation::as_str)
+            .filter(|name| name.starts_with(prefix))
+            .map(SuggestionEntry::new)
+            .collect()
+    }
+}
+
+/// A template mirror argument, like `front_back`.
+pub struct TemplateMirrorArgument;
+
+impl CommandArgument for TemplateMirrorArgument {
+    type Output = Mirror;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        _context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let mirror = Mirror::by_name(arg.first()?)?;
+        Some((&arg[1..], mirror))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        (ArgumentType::TemplateMirror, None)
+    }
+
+    fn suggest(
+        &self,
+        prefix: &str,
+        _suggestion_ctx: &super::SuggestionContext,
+    ) -> Vec<SuggestionEntry> {
+        Mirror::VALUES
+            .into_iter()
+            .map(Mirror::as_str)
+            .filter(|name| name.starts_with(prefix))
+            .map(SuggestionEntry::new)
+            .collect()
+    }
+}
@@ -4,8 +4,33 @@ use steel_protocol::packets::game::{ArgumentType, SuggestionEntry, SuggestionTyp
 use crate::command::arguments::CommandArgument;
 use crate::command::context::CommandContext;
 
-/// A time argument.
-pub struct TimeArgument;
+/// A time argument, a number of ticks, seconds (`s`) or days (`d`) converted
+/// to ticks.
+///
+/// Vanilla: `TimeArgument`.
+pub struct TimeArgument {
+    min: i32,
+}
+
+impl TimeArgument {
+    /// Creates a time argument accepting any number of ticks from 0.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { min: 0 }
+    }
+
+    /// Creates a time argument accepting at least `min` ticks.
+    #[must_use]
+    pub const fn at_least(min: i32) -> Self {
+        Self { min }
+    }
+}
+
+impl Default for TimeArgument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl CommandArgument for TimeArgument {
     type Output = i32;
@@ -22,7 +47,8 @@ impl CommandArgument for TimeArgument {
             .map_or((*s, "t"), |pos| (&s[..pos], &s[pos..]));
 
         let number = number.parse::<f32>().ok()?;
-        if number < 0.0 {
+        // Vanilla's reader doesn't take NaN or infinity
+        if !number.is_finite() || number < 0.0 {
             return None;
         }
 
@@ -33,11 +59,17 @@ impl CommandArgument for TimeArgument {
             _ => return None,
         };
 
-        Some((&arg[1..], ticks.round() as i32))
+        // Saturates like vanilla's Math.round
+        let ticks = ticks.round() as i32;
+        if ticks < self.min {
+            return None;
+        }
+
+        Some((&arg[1..], ticks))
     }
 
     fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
-        (ArgumentType::Time { min: 0 }, None)
+        (ArgumentType::Time { min: self.min }, None)
     }
 
     /// ONLY FOR THE CONSOLE\
@@ -0,0 +1,869 @@
+//! Handler for the "data" command.
+//!
+//! Mirrors `net.minecraft.server.commands.data.DataCommands`: every subcommand
+//! targets a block entity, an entity or a command storage, selected by the
+//! `block`, `entity` or `storage` literal in front of the target.
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use glam::DVec3;
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use steel_protocol::packets::game::{ArgumentType, SuggestionEntry, SuggestionType};
+use steel_utils::nbt::{NbtPath, NbtPathError, merge_compound, to_snbt, with_borrowed};
+use steel_utils::{BlockPos, Identifier, UuidExt, translations};
+use text_components::TextComponent;
+use text_components::translation::TranslatedMessage;
+
+use crate::block_entity::SharedBlockEntity;
+use crate::command::arguments::{
+    CommandArgument, ParsedValue, SuggestionContext,
+    block_pos::BlockPosArgument,
+    double::DoubleArgument,
+    entity::EntityArgument,
+    integer::IntegerArgument,
+    nbt::{CompoundTagArgument, NbtPathArgument, NbtTagArgument},
+    resource_location::ResourceLocationArgument,
+};
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, CommandParserArgumentBuilder,
+    CommandParserExecutor, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::entity::{Entity, LivingEntity};
+
+/// Handler for the "data" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["data"],
+        "Gets, merges, modifies and removes block entity, entity and storage data.",
+        "minecraft:command.data",
+    )
+    .then(
+        literal("merge")
+            .then(merge_branch(TargetKind::Block))
+            .then(merge_branch(TargetKind::Entity))
+            .then(merge_branch(TargetKind::Storage)),
+    )
+    .then(
+        literal("get")
+            .then(get_branch(TargetKind::Block))
+            .then(get_branch(TargetKind::Entity))
+            .then(get_branch(TargetKind::Storage)),
+    )
+    .then(
+        literal("remove")
+            .then(remove_branch(TargetKind::Block))
+            .then(remove_branch(TargetKind::Entity))
+            .then(remove_branch(TargetKind::Storage)),
+    )
+    .then(
+        literal("modify")
+            .then(modify_branch(TargetKind::Block))
+            .then(modify_branch(TargetKind::Entity))
+            .then(modify_branch(TargetKind::Storage)),
+    )
+}
+
+/// `<kind> <target> <nbt>`
+fn merge_branch(kind: TargetKind) -> impl CommandParserExecutor<()> {
+    literal(kind.literal()).then(
+        kind.argument(kind.target_name())
+            .then(argument("nbt", CompoundTagArgument).executes(MergeExecutor)),
+    )
+}
+
+/// `<kind> <target> [<path> [<scale>]]`
+fn get_branch(kind: TargetKind) -> impl CommandParserExecutor<()> {
+    literal(kind.literal()).then(
+        kind.argument(kind.target_name())
+            .executes(GetExecutor)
+            .then(
+                argument("path", NbtPathArgument)
+                    .executes(GetExecutor)
+                    .then(argument("scale", DoubleArgument::new()).executes(GetExecutor)),
+            ),
+    )
+}
+
+/// `<kind> <target> <path>`
+fn remove_branch(kind: TargetKind) -> impl CommandParserExecutor<()> {
+    literal(kind.literal()).then(
+        kind.argument(kind.target_name())
+            .then(argument("path", NbtPathArgument).executes(RemoveExecutor)),
+    )
+}
+
+/// `<kind> <target> <targetPath> <operation> <source>`
+fn modify_branch(kind: TargetKind) -> impl CommandParserExecutor<()> {
+    literal(kind.literal()).then(
+        kind.argument(kind.target_name()).then(
+            argument("targetPath", NbtPathArgument)
+                .then(
+                    // The placeholder index is replaced by the parsed one, see `ModifyTarget`
+                    literal("insert").then(
+                        argument("index", IntegerArgument::new())
+                            .then(from_source(ModifyOperation::Insert(0)))
+                            .then(value_source(ModifyOperation::Insert(0)))
+                            .then(string_source(ModifyOperation::Insert(0))),
+                    ),
+                )
+                .then(operation_branch("prepend", ModifyOperation::Insert(0)))
+                .then(operation_branch("append", ModifyOperation::Insert(-1)))
+                .then(operation_branch("set", ModifyOperation::Set))
+                .then(operation_branch("merge", ModifyOperation::Merge)),
+        ),
+    )
+}
+
+/// `<name> (from|value|string) ...`
+fn operation_branch<P: ModifyTarget>(
+    name: &'static str,
+    operation: ModifyOperation,
+) -> impl CommandParserExecutor<P> {
+    literal(name)
+        .then(from_source(operation))
+        .then(value_source(operation))
+        .then(string_source(operation))
+}
+
+/// `from <kind> <source> [<sourcePath>]`
+fn from_source<P: ModifyTarget>(operation: ModifyOperation) -> impl CommandParserExecutor<P> {
+    let branch = |kind: TargetKind| {
+        literal(kind.literal()).then(
+            kind.argument(kind.source_name())
+                .executes(FromExecutor(operation))
+                .then(argument("sourcePath", NbtPathArgument).executes(FromExecutor(operation))),
+        )
+    };
+
+    literal("from")
+        .then(branch(TargetKind::Block))
+        .then(branch(TargetKind::Entity))
+        .then(branch(TargetKind::Storage))
+}
+
+/// `value <value>`
+fn value_source<P: ModifyTarget>(operation: ModifyOperation) -> impl CommandParserExecutor<P> {
+    literal("value").then(argument("value", NbtTagArgument).executes(ValueExecutor(operation)))
+}
+
+/// `string <kind> <source> [<sourcePath> [<start> [<end>]]]`
+fn string_source<P: ModifyTarget>(operation: ModifyOperation) -> impl CommandParserExecutor<P> {
+    let branch = |kind: TargetKind| {
+        literal(kind.literal()).then(
+            kind.argument(kind.source_name())
+                .executes(StringExecutor(operation))
+                .then(
+                    argument("sourcePath", NbtPathArgument)
+                        .executes(StringExecutor(operation))
+                        .then(
+                            argument("start", IntegerArgument::new())
+                                .executes(StringExecutor(operation))
+                                .then(
+                                    argument("end", IntegerArgument::new())
+                                        .executes(StringExecutor(operation)),
+                                ),
+                        ),
+                ),
+        )
+    };
+
+    literal("string")
+        .then(branch(TargetKind::Block))
+        .then(branch(TargetKind::Entity))
+        .then(branch(TargetKind::Storage))
+}
+
+/// The kinds of objects `/data` can read from and write to.
+#[derive(Clone, Copy)]
+enum TargetKind {
+    Block,
+    Entity,
+    Storage,
+}
+
+impl TargetKind {
+    const fn literal(self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::Entity => "entity",
+            Self::Storage => "storage",
+        }
+    }
+
+    const fn target_name(self) -> &'static str {
+        match self {
+            Self::Block => "targetPos",
+            Self::Entity | Self::Storage => "target",
+        }
+    }
+
+    const fn source_name(self) -> &'static str {
+        match self {
+            Self::Block => "sourcePos",
+            Self::Entity | Self::Storage => "source",
+        }
+    }
+
+    /// Creates the argument selecting an object of this kind.
+    fn argument<S>(self, name: &'static str) -> CommandParserArgumentBuilder<S, DataSelector> {
+        match self {
+            Self::Block => argument(
+                name,
+                SelectorArgument {
+                    inner: BlockPosArgument,
+                    wrap: DataSelector::Block,
+                },
+            ),
+            Self::Entity => argument(
+                name,
+                SelectorArgument {
+                    inner: EntityArgument::one(),
+                    wrap: DataSelector::Entity,
+                },
+            ),
+            Self::Storage => argument(
+                name,
+                SelectorArgument {
+                    inner: ResourceLocationArgument,
+                    wrap: DataSelector::Storage,
+                },
+            ),
+        }
+    }
+}
+
+/// A parsed but not yet resolved target.
+#[derive(Clone)]
+enum DataSelector {
+    Block(BlockPos),
+    Entity(Vec<Arc<dyn LivingEntity + Send + Sync>>),
+    Storage(Identifier),
+}
+
+impl DataSelector {
+    fn resolve(self, context: &CommandContext) -> Result<DataTarget, CommandError> {
+        match self {
+            Self::Block(pos) => {
+                let block_entity = context
+                    .world
+                    .get_block_entity(pos)
+                    .ok_or_else(|| failure(translations::COMMANDS_DATA_BLOCK_INVALID.msg()))?;
+                Ok(DataTarget::Block { pos, block_entity })
+            }
+            Self::Entity(entities) => entities
+                .into_iter()
+                .next()
+                .map(DataTarget::Entity)
+                .ok_or_else(|| failure(translations::ARGUMENT_ENTITY_NOTFOUND_ENTITY.msg())),
+            Self::Storage(id) => Ok(DataTarget::Storage(id)),
+        }
+    }
+}
+
+/// Wraps the argument of a target kind so that all kinds parse to a [`DataSelector`].
+struct SelectorArgument<A: CommandArgument> {
+    inner: A,
+    wrap: fn(A::Output) -> DataSelector,
+}
+
+impl<A: CommandArgument> CommandArgument for SelectorArgument<A> {
+    type Output = DataSelector;
+
+    fn parse<'a>(
+        &self,
+        arg: &'a [&'a str],
+        context: &mut CommandContext,
+    ) -> Option<(&'a [&'a str], Self::Output)> {
+        let (rest, value) = self.inner.parse(arg, context)?;
+        Some((rest, (self.wrap)(value)))
+    }
+
+    fn usage(&self) -> (ArgumentType, Option<SuggestionType>) {
+        self.inner.usage()
+    }
+
+    fn suggest(&self, prefix: &str, suggestion_ctx: &SuggestionContext) -> Vec<SuggestionEntry> {
+        self.inner.suggest(prefix, suggestion_ctx)
+    }
+
+    fn parsed_value(&self, args: &[&str], context: &mut CommandContext) -> Option<ParsedValue> {
+        self.inner.parsed_value(args, context)
+    }
+}
+
+/// A resolved target, mirroring vanilla's `DataAccessor`.
+enum DataTarget {
+    Block {
+        pos: BlockPos,
+        block_entity: SharedBlockEntity,
+    },
+    Entity(Arc<dyn LivingEntity + Send + Sync>),
+    Storage(Identifier),
+}
+
+impl DataTarget {
+    /// Returns a copy of the target's data.
+    fn data(&self, context: &CommandContext) -> NbtCompound {
+        match self {
+            Self::Block { pos, block_entity } => {
+                let block_entity = block_entity.lock();
+                let mut nbt = NbtCompound::new();
+                nbt.insert(
+                    "id",
+                    NbtTag::String(block_entity.get_type().key.to_string().into()),
+                );
+                nbt.insert("x", NbtTag::Int(pos.x()));
+                nbt.insert("y", NbtTag::Int(pos.y()));
+                nbt.insert("z", NbtTag::Int(pos.z()));
+                block_entity.save_additional(&mut nbt);
+                nbt
+            }
+            Self::Entity(entity) => entity_data(&**entity),
+            Self::Storage(id) => context
+                .server
+                .overworld()
+                .level_data
+                .read()
+                .command_storage()
+                .get(id),
+        }
+    }
+
+    /// Replaces the target's data.
+    fn set_data(&self, data: &NbtCompound, context: &CommandContext) -> Result<(), CommandError> {
+        match self {
+            Self::Block { block_entity, .. } => {
+                let mut block_entity = block_entity.lock();
+                with_borrowed(data, |nbt| block_entity.load_additional(nbt))
+                    .map_err(read_failed)?;
+                block_entity.mark_updated();
+                Ok(())
+            }
+            Self::Entity(entity) => {
+                if Arc::clone(entity).as_player().is_some() {
+                    return Err(failure(translations::COMMANDS_DATA_ENTITY_INVALID.msg()));
+                }
+                if let Some(pos) = vec3(data, "Pos") {
+                    entity.set_position(pos);
+                }
+                if let Some(motion) = vec3(data, "Motion") {
+                    entity.set_velocity(motion);
+                }
+                if let Some(NbtTag::Byte(on_ground)) = data.get("OnGround") {
+                    entity.set_on_ground(*on_ground != 0);
+                }
+                // TODO: Apply "Rotation" once entities can be rotated
+                with_borrowed(data, |nbt| entity.load_data(nbt)).map_err(read_failed)
+            }
+            Self::Storage(id) => {
+                context
+                    .server
+                    .overworld()
+                    .level_data
+                    .write()
+                    .command_storage_mut()
+                    .set(id.clone(), data.clone());
+                Ok(())
+            }
+        }
+    }
+
+    fn modified_message(&self) -> TextComponent {
+        match self {
+            Self::Block { pos, .. } => translations::COMMANDS_DATA_BLOCK_MODIFIED
+                .message([
+                    TextComponent::plain(pos.x().to_string()),
+                    TextComponent::plain(pos.y().to_string()),
+                    TextComponent::plain(pos.z().to_string()),
+                ])
+                .into(),
+            Self::Entity(entity) => translations::COMMANDS_DATA_ENTITY_MODIFIED
+                .message([entity_name(entity)])
+                .into(),
+            Self::Storage(id) => translations::COMMANDS_DATA_STORAGE_MODIFIED
+                .message([TextComponent::plain(id.to_string())])
+                .into(),
+        }
+    }
+
+    // TODO: Print the tag as a colored component like vanilla's `NbtUtils.toPrettyComponent`
+    fn query_message(&self, tag: &NbtTag) -> TextComponent {
+        let tag = TextComponent::plain(to_snbt(tag));
+        match self {
+            Self::Block { pos, .. } => translations::COMMANDS_DATA_BLOCK_QUERY
+                .message([
+                    TextComponent::plain(pos.x().to_string()),
+                    TextComponent::plain(pos.y().to_string()),
+                    TextComponent::plain(pos.z().to_string()),
+                    tag,
+                ])
+                .into(),
+            Self::Entity(entity) => translations::COMMANDS_DATA_ENTITY_QUERY
+                .message([entity_name(entity), tag])
+                .into(),
+            Self::Storage(id) => translations::COMMANDS_DATA_STORAGE_QUERY
+                .message([TextComponent::plain(id.to_string()), tag])
+                .into(),
+        }
+    }
+
+    fn get_message(&self, path: &NbtPath, scale: f64, value: i32) -> TextComponent {
+        let path = TextComponent::plain(path.to_string());
+        let scale = TextComponent::plain(format!("{scale:.2}"));
+        let value = TextComponent::plain(value.to_string());
+        match self {
+            Self::Block { pos, .. } => translations::COMMANDS_DATA_BLOCK_GET
+                .message([
+                    path,
+                    TextComponent::plain(pos.x().to_string()),
+                    TextComponent::plain(pos.y().to_string()),
+                    TextComponent::plain(pos.z().to_string()),
+                    scale,
+                    value,
+                ])
+                .into(),
+            Self::Entity(entity) => translations::COMMANDS_DATA_ENTITY_GET
+                .message([path, entity_name(entity), scale, value])
+                .into(),
+            Self::Storage(id) => translations::COMMANDS_DATA_STORAGE_GET
+                .message([path, TextComponent::plain(id.to_string()), scale, value])
+                .into(),
+        }
+    }
+}
+
+/// Builds the data of an entity the way vanilla's `Entity.saveWithoutId` lays it out.
+fn entity_data(entity: &(dyn LivingEntity + Send + Sync)) -> NbtCompound {
+    let pos = entity.position();
+    let motion = entity.velocity();
+    let (yaw, pitch) = entity.rotation();
+
+    let mut nbt = NbtCompound::new();
+    nbt.insert(
+        "id",
+        NbtTag::String(entity.entity_type().key.to_string().into()),
+    );
+    nbt.insert(
+        "Pos",
+        NbtTag::List(NbtList::Double(vec![pos.x, pos.y, pos.z])),
+    );
+    nbt.insert(
+        "Motion",
+        NbtTag::List(NbtList::Double(vec![motion.x, motion.y, motion.z])),
+    );
+    nbt.insert("Rotation", NbtTag::List(NbtList::Float(vec![yaw, pitch])));
+    nbt.insert("OnGround", NbtTag::Byte(i8::from(entity.on_ground())));
+    nbt.insert(
+        "UUID",
+        NbtTag::IntArray(entity.uuid().to_int_array().to_vec()),
+    );
+    entity.save_data(&mut nbt);
+    nbt
+}
+
+/// The name commands show for an entity in their feedback.
+// TODO: use getDisplayName() (team formatting, hover event)
+pub(crate) fn entity_name<E: Entity + ?Sized>(entity: &Arc<E>) -> TextComponent {
+    if let Some(name) = entity.custom_name() {
+        return name;
+    }
+    if let Some(player) = Arc::clone(entity).as_player() {
+        return TextComponent::plain(player.gameprofile.name.clone());
+    }
+    let key = &entity.entity_type().key;
+    TextComponent::translated(TranslatedMessage {
+        key: Cow::Owned(format!("entity.{}.{}", key.namespace, key.path)),
+        args: None,
+        fallback: None,
+    })
+}
+
+/// Reads a list of three doubles, like `Pos` and `Motion`.
+fn vec3(data: &NbtCompound, key: &str) -> Option<DVec3> {
+    match data.get(key)? {
+        NbtTag::List(NbtList::Double(v)) if v.len() == 3 => Some(DVec3::new(v[0], v[1], v[2])),
+        _ => None,
+    }
+}
+
+fn failure(message: impl Into<TextComponent>) -> CommandError {
+    CommandError::CommandFailed(Box::new(message.into()))
+}
+
+fn read_failed(error: simdnbt::Error) -> CommandError {
+    failure(TextComponent::from(error.to_string()))
+}
+
+fn merge_failed() -> CommandError {
+    failure(translations::COMMANDS_DATA_MERGE_FAILED.msg())
+}
+
+fn path_error(error: NbtPathError) -> CommandError {
+    match error {
+        NbtPathError::Invalid { .. } => failure(translations::ARGUMENTS_NBTPATH_NODE_INVALID.msg()),
+        NbtPathError::NothingFound(path) => failure(
+            translations::ARGUMENTS_NBTPATH_NOTHING_FOUND.message([TextComponent::plain(path)]),
+        ),
+        NbtPathError::ExpectedList(found) => failure(
+            translations::COMMANDS_DATA_MODIFY_EXPECTED_LIST.message([TextComponent::plain(found)]),
+        ),
+        NbtPathError::ExpectedObject(found) => failure(
+            translations::COMMANDS_DATA_MODIFY_EXPECTED_OBJECT
+                .message([TextComponent::plain(found)]),
+        ),
+        NbtPathError::InvalidIndex(index) => failure(
+            translations::COMMANDS_DATA_MODIFY_INVALID_INDEX
+                .message([TextComponent::plain(index.to_string())]),
+        ),
+    }
+}
+
+/// Returns the only tag matched by `path`.
+fn single_tag(path: &NbtPath, data: NbtCompound) -> Result<NbtTag, CommandError> {
+    let mut tags = path.get(&NbtTag::Compound(data)).map_err(path_error)?;
+    if tags.len() > 1 {
+        return Err(failure(translations::COMMANDS_DATA_GET_MULTIPLE.msg()));
+    }
+    tags.pop()
+        .ok_or_else(|| path_error(NbtPathError::NothingFound(path.to_string())))
+}
+
+const fn numeric_value(tag: &NbtTag) -> Option<f64> {
+    match *tag {
+        NbtTag::Byte(v) => Some(v as f64),
+        NbtTag::Short(v) => Some(v as f64),
+        NbtTag::Int(v) => Some(v as f64),
+        NbtTag::Long(v) => Some(v as f64),
+        NbtTag::Float(v) => Some(v as f64),
+        NbtTag::Double(v) => Some(v),
+        _ => None,
+    }
+}
+
+struct GetExecutor;
+
+impl CommandExecutor<((), DataSelector)> for GetExecutor {
+    fn execute(
+        &self,
+        args: ((), DataSelector),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), target) = args;
+        let target = target.resolve(context)?;
+        let data = NbtTag::Compound(target.data(context));
+
+        context.sender.send_message(&target.query_message(&data));
+        Ok(())
+    }
+}
+
+impl CommandExecutor<(((), DataSelector), NbtPath)> for GetExecutor {
+    fn execute(
+        &self,
+        args: (((), DataSelector), NbtPath),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), target), path) = args;
+        let target = target.resolve(context)?;
+        let tag = single_tag(&path, target.data(context))?;
+
+        // TODO: Return the numeric value, size or length of the tag as the command result
+        // once /execute store and the scoreboard can consume it
+        context.sender.send_message(&target.query_message(&tag));
+        Ok(())
+    }
+}
+
+impl CommandExecutor<((((), DataSelector), NbtPath), f64)> for GetExecutor {
+    fn execute(
+        &self,
+        args: ((((), DataSelector), NbtPath), f64),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((((), target), path), scale) = args;
+        let target = target.resolve(context)?;
+        let tag = single_tag(&path, target.data(context))?;
+        let Some(value) = numeric_value(&tag) else {
+            return Err(failure(
+                translations::COMMANDS_DATA_GET_INVALID
+                    .message([TextComponent::plain(path.to_string())]),
+            ));
+        };
+        let result = (value * scale).floor() as i32;
+
+        // TODO: Return `result` as the command result once /execute store and the scoreboard
+        // can consume it
+        context
+            .sender
+            .send_message(&target.get_message(&path, scale, result));
+        Ok(())
+    }
+}
+
+struct MergeExecutor;
+
+impl CommandExecutor<(((), DataSelector), NbtCompound)> for MergeExecutor {
+    fn execute(
+        &self,
+        args: (((), DataSelector), NbtCompound),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), target), nbt) = args;
+        let target = target.resolve(context)?;
+        let original = target.data(context);
+        let mut merged = original.clone();
+        merge_compound(&mut merged, &nbt);
+        if merged == original {
+            return Err(merge_failed());
+        }
+
+        target.set_data(&merged, context)?;
+        context.sender.send_message(&target.modified_message());
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+impl CommandExecutor<(((), DataSelector), NbtPath)> for RemoveExecutor {
+    fn execute(
+        &self,
+        args: (((), DataSelector), NbtPath),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((), target), path) = args;
+        let target = target.resolve(context)?;
+        let mut root = NbtTag::Compound(target.data(context));
+        if path.remove(&mut root) == 0 {
+            return Err(merge_failed());
+        }
+        let NbtTag::Compound(data) = root else {
+            return Err(merge_failed());
+        };
+
+        target.set_data(&data, context)?;
+        context.sender.send_message(&target.modified_message());
+        Ok(())
+    }
+}
+
+/// How `/data modify` combines the source values with the target.
+#[derive(Clone, Copy)]
+enum ModifyOperation {
+    /// Inserts the values into the target lists; `-1` appends.
+    Insert(i32),
+    /// Merges the values, which must be compounds, into the target compounds.
+    Merge,
+    /// Replaces the targets with the last value.
+    Set,
+}
+
+/// The arguments parsed in front of a modification's source: `<target> <targetPath> [<index>]`.
+trait ModifyTarget: Clone {
+    /// Splits off the target and its path.
+    ///
+    /// `insert <index>` only knows its index once parsed, so it replaces `operation`.
+    fn split(self, operation: ModifyOperation) -> (DataSelector, NbtPath, ModifyOperation);
+}
+
+impl ModifyTarget for (((), DataSelector), NbtPath) {
+    fn split(self, operation: ModifyOperation) -> (DataSelector, NbtPath, ModifyOperation) {
+        let (((), target), path) = self;
+        (target, path, operation)
+    }
+}
+
+impl ModifyTarget for ((((), DataSelector), NbtPath), i32) {
+    fn split(self, _operation: ModifyOperation) -> (DataSelector, NbtPath, ModifyOperation) {
+        let ((((), target), path), index) = self;
+        (target, path, ModifyOperation::Insert(index))
+    }
+}
+
+/// Applies `operation` with `values` to the target and stores the result.
+fn modify(
+    target: impl ModifyTarget,
+    operation: ModifyOperation,
+    values: &[NbtTag],
+    context: &CommandContext,
+) -> Result<(), CommandError> {
+    let (target, path, operation) = target.split(operation);
+    let target = target.resolve(context)?;
+    let mut root = NbtTag::Compound(target.data(context));
+
+    let changed = match operation {
+        ModifyOperation::Insert(index) => path.insert(index, &mut root, values),
+        ModifyOperation::Merge => {
+            let mut merged = NbtCompound::new();
+            for value in values {
+                let NbtTag::Compound(compound) = value else {
+                    return Err(path_error(NbtPathError::ExpectedObject(to_snbt(value))));
+                };
+                merge_compound(&mut merged, compound);
+            }
+            path.merge(&mut root, &merged)
+        }
+        ModifyOperation::Set => {
+            let Some(value) = values.last() else {
+                return Err(merge_failed());
+            };
+            path.set(&mut root, value)
+        }
+    }
+    .map_err(path_error)?;
+
+    if changed == 0 {
+        return Err(merge_failed());
+    }
+    let NbtTag::Compound(data) = root else {
+        return Err(path_error(NbtPathError::ExpectedObject(to_snbt(&root))));
+    };
+
+    target.set_data(&data, context)?;
+    context.sender.send_message(&target.modified_message());
+    Ok(())
+}
+
+/// Reads the source values of `from` and `string`: the whole data, or the tags at `path`.
+fn source_values(
+    source: DataSelector,
+    path: Option<&NbtPath>,
+    context: &CommandContext,
+) -> Result<Vec<NbtTag>, CommandError> {
+    let data = NbtTag::Compound(source.resolve(context)?.data(context));
+    match path {
+        Some(path) => path.get(&data).map_err(path_error),
+        None => Ok(vec![data]),
+    }
+}
+
+/// Converts the values to strings and cuts them to `start..end`, where negative
+/// indices count from the end.
+fn substrings(
+    values: Vec<NbtTag>,
+    start: Option<i32>,
+    end: Option<i32>,
+) -> Result<Vec<NbtTag>, CommandError> {
+    values
+        .into_iter()
+        .map(|value| {
+            let text = match value {
+                NbtTag::String(text) => text.to_str().into_owned(),
+                other => to_snbt(&other),
+            };
+            let chars: Vec<char> = text.chars().collect();
+            let len = i32::try_from(chars.len()).unwrap_or(i32::MAX);
+            let offset = |index: i32| if index >= 0 { index } else { len + index };
+            let from = start.map_or(0, offset);
+            let to = end.map_or(len, offset);
+            if from < 0 || to > len || from > to {
+                return Err(failure(
+                    translations::COMMANDS_DATA_MODIFY_INVALID_SUBSTRING.message([
+                        TextComponent::plain(from.to_string()),
+                        TextComponent::plain(to.to_string()),
+                    ]),
+                ));
+            }
+            let substring: String = chars[from as usize..to as usize].iter().collect();
+            Ok(NbtTag::String(substring.into()))
+        })
+        .collect()
+}
+
+struct FromExecutor(ModifyOperation);
+
+impl<P: ModifyTarget> CommandExecutor<(P, DataSelector)> for FromExecutor {
+    fn execute(
+        &self,
+        args: (P, DataSelector),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (target, source) = args;
+        let values = source_values(source, None, context)?;
+        modify(target, self.0, &values, context)
+    }
+}
+
+impl<P: ModifyTarget> CommandExecutor<((P, DataSelector), NbtPath)> for FromExecutor {
+    fn execute(
+        &self,
+        args: ((P, DataSelector), NbtPath),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((target, source), path) = args;
+        let values = source_values(source, Some(&path), context)?;
+        modify(target, self.0, &values, context)
+    }
+}
+
+struct ValueExecutor(ModifyOperation);
+
+impl<P: ModifyTarget> CommandExecutor<(P, NbtTag)> for ValueExecutor {
+    fn execute(&self, args: (P, NbtTag), context: &mut CommandContext) -> Result<(), CommandError> {
+        let (target, value) = args;
+        modify(target, self.0, &[value], context)
+    }
+}
+
+struct StringExecutor(ModifyOperation);
+
+impl<P: ModifyTarget> CommandExecutor<(P, DataSelector)> for StringExecutor {
+    fn execute(
+        &self,
+        args: (P, DataSelector),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (target, source) = args;
+        let values = substrings(source_values(source, None, context)?, None, None)?;
+        modify(target, self.0, &values, context)
+    }
+}
+
+impl<P: ModifyTarget> CommandExecutor<((P, DataSelector), NbtPath)> for StringExecutor {
+    fn execute(
+        &self,
+        args: ((P, DataSelector), NbtPath),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((target, source), path) = args;
+        let values = substrings(source_values(source, Some(&path), context)?, None, None)?;
+        modify(target, self.0, &values, context)
+    }
+}
+
+impl<P: ModifyTarget> CommandExecutor<(((P, DataSelector), NbtPath), i32)> for StringExecutor {
+    fn execute(
+        &self,
+        args: (((P, DataSelector), NbtPath), i32),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((target, source), path), start) = args;
+        let values = substrings(
+            source_values(source, Some(&path), context)?,
+            Some(start),
+            None,
+        )?;
+        modify(target, self.0, &values, context)
+    }
+}
+
+impl<P: ModifyTarget> CommandExecutor<((((P, DataSelector), NbtPath), i32), i32)>
+    for StringExecutor
+{
+    fn execute(
+        &self,
+        args: ((((P, DataSelector), NbtPath), i32), i32),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((((target, source), path), start), end) = args;
+        let values = substrings(
+            source_values(source, Some(&path), context)?,
+            Some(start),
+            Some(end),
+        )?;
+        modify(target, self.0, &values, context)
+    }
+}
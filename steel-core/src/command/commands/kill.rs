@@ -11,8 +11,8 @@ use crate::command::commands::{
 };
 use crate::command::context::CommandContext;
 use crate::command::error::CommandError;
-use crate::entity::LivingEntity;
 use crate::entity::damage::DamageSource;
+use crate::entity::{Entity, LivingEntity};
 use crate::player::Player;
 use steel_registry::vanilla_damage_types;
 use steel_utils::translations;
@@ -27,7 +27,7 @@ pub fn command_handler() -> impl CommandHandlerDyn {
 
 /// `LivingEntity.kill()` — hurt with `genericKill` at `Float.MAX_VALUE`.
 fn kill_player(player: &Player) {
-    player.hurt(
+    player.take_damage(
         &DamageSource::environment(vanilla_damage_types::GENERIC_KILL),
         f32::MAX,
     );
@@ -0,0 +1,137 @@
+//! Handler for the "locate" command.
+use std::sync::Arc;
+use std::time::Instant;
+
+use steel_registry::biome::BiomeRef;
+use steel_utils::{BlockPos, translations};
+use text_components::format::Color;
+use text_components::interactivity::{ClickEvent, HoverEvent};
+use text_components::{Modifier, TextComponent};
+
+use crate::command::arguments::biome::BiomeArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::command::sender::CommandSender;
+use crate::worldgen::{
+    LOCATE_BIOME_HORIZONTAL_STEP, LOCATE_BIOME_RADIUS, LOCATE_BIOME_VERTICAL_STEP,
+};
+
+/// Handler for the "locate" command.
+// TODO: Add `structure` and `poi` once structures and POI types can be searched
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["locate"],
+        "Finds the nearest biome of the given type.",
+        "minecraft:command.locate",
+    )
+    .then(literal("biome").then(argument("biome", BiomeArgument).executes(LocateBiomeExecutor)))
+}
+
+struct LocateBiomeExecutor;
+
+impl CommandExecutor<((), BiomeRef)> for LocateBiomeExecutor {
+    fn execute(
+        &self,
+        args: ((), BiomeRef),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((), biome) = args;
+        let world = &context.world;
+        let generator = Arc::clone(&world.chunk_map.world_gen_context.generator);
+        if !generator
+            .biome_source()
+            .is_some_and(|source| source.can_generate(biome))
+        {
+            return Err(biome_not_found(biome));
+        }
+
+        let origin = BlockPos::new(
+            context.position.x.floor() as i32,
+            context.position.y.floor() as i32,
+            context.position.z.floor() as i32,
+        );
+        let y_range = world.get_min_y()..=world.get_max_y();
+        let pool = Arc::clone(&world.chunk_map.generation_pool);
+        let sender = context.sender.clone();
+
+        // Sampling up to 160k columns takes a while, so keep it off the tick thread
+        world.chunk_map.chunk_runtime.spawn_blocking(move || {
+            let Some(source) = generator.biome_source() else {
+                return;
+            };
+            let start = Instant::now();
+            let found = pool.install(|| {
+                source.find_closest_biome_3d(
+                    origin,
+                    LOCATE_BIOME_RADIUS,
+                    LOCATE_BIOME_HORIZONTAL_STEP,
+                    LOCATE_BIOME_VERTICAL_STEP,
+                    y_range,
+                    |candidate| candidate.key == biome.key,
+                )
+            });
+            log::debug!("Locating biome {} took {:?}", biome.key, start.elapsed());
+
+            match found {
+                Some((pos, _)) => send_locate_result(&sender, biome, origin, pos),
+                None => sender.send_message(&biome_not_found_message(biome).color(Color::Red)),
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Sends the success message with clickable coordinates.
+///
+/// Vanilla equivalent: `LocateCommand.showLocateResult()` with `includeY = true`.
+fn send_locate_result(sender: &CommandSender, biome: BiomeRef, origin: BlockPos, pos: BlockPos) {
+    let dx = f64::from(pos.x() - origin.x());
+    let dy = f64::from(pos.y() - origin.y());
+    let dz = f64::from(pos.z() - origin.z());
+    let distance = (dx * dx + dy * dy + dz * dz).sqrt().floor() as i32;
+
+    let coordinates = translations::CHAT_COORDINATES
+        .message([
+            TextComponent::from(pos.x().to_string()),
+            TextComponent::from(pos.y().to_string()),
+            TextComponent::from(pos.z().to_string()),
+        ])
+        .component();
+    let coordinates = translations::CHAT_SQUARE_BRACKETS
+        .message([coordinates])
+        .component()
+        .color(Color::Green)
+        .click_event(ClickEvent::suggest_command(format!(
+            "/tp @s {} {} {}",
+            pos.x(),
+            pos.y(),
+            pos.z()
+        )))
+        .hover_event(HoverEvent::show_text(
+            &translations::CHAT_COORDINATES_TOOLTIP,
+        ));
+
+    sender.send_message(
+        &translations::COMMANDS_LOCATE_BIOME_SUCCESS
+            .message([
+                TextComponent::from(biome.key.to_string()),
+                coordinates,
+                TextComponent::from(distance.to_string()),
+            ])
+            .component(),
+    );
+}
+
+fn biome_not_found_message(biome: BiomeRef) -> TextComponent {
+    translations::COMMANDS_LOCATE_BIOME_NOT_FOUND
+        .message([TextComponent::from(biome.key.to_string())])
+        .component()
+}
+
+fn biome_not_found(biome: BiomeRef) -> CommandError {
+    CommandError::CommandFailed(Box::new(biome_not_found_message(biome)))
+}
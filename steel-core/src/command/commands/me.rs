@@ -0,0 +1,24 @@
+//! Handler for the "me" command.
+use steel_registry::vanilla_chat_types::EMOTE_COMMAND;
+
+use crate::command::arguments::message::MessageArgument;
+use crate::command::commands::{CommandHandlerBuilder, CommandHandlerDyn, argument};
+use crate::command::context::CommandContext;
+
+/// Handler for the "me" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["me"],
+        "Displays a message about the sender.",
+        "minecraft:command.me",
+    )
+    .then(argument("action", MessageArgument).executes(
+        |((), text): ((), String), context: &mut CommandContext| {
+            let message = MessageArgument::resolve(context, "action", text);
+            let chat_type = context.sender.bind_chat_type(EMOTE_COMMAND, None);
+            message.broadcast(&context.server, &chat_type);
+            Ok(())
+        },
+    ))
+}
@@ -1,5 +1,6 @@
 //! This module contains the command building structs.
 pub mod clear;
+pub mod data;
 pub mod enchant;
 pub mod execute;
 pub mod fly;
@@ -7,12 +8,26 @@ pub mod gamemode;
 pub mod gamerule;
 pub mod give;
 pub mod kill;
+pub mod locate;
+pub mod me;
+pub mod msg;
+pub mod place;
+pub mod playsound;
+pub mod random;
+pub mod ride;
 pub mod seed;
+pub mod setworldspawn;
+pub mod spawnpoint;
+pub mod spectate;
+pub mod spreadplayers;
+pub mod steel;
 pub mod stop;
+pub mod stopsound;
 pub mod summon;
 pub mod tellraw;
 pub mod tick;
 pub mod time;
+pub mod title;
 pub mod tp;
 pub mod weather;
 pub mod xp;
@@ -22,11 +37,11 @@ use std::ops::Not;
 use std::sync::Arc;
 
 use steel_protocol::packets::game::{
-    CommandNode, CommandNodeInfo, SuggestionEntry, SuggestionType,
+    ArgumentType, CommandNode, CommandNodeInfo, SuggestionEntry, SuggestionType,
 };
 
 use crate::command::arguments::{CommandArgument, SuggestionContext};
-use crate::command::context::CommandContext;
+use crate::command::context::{CommandContext, CommandSigning};
 use crate::command::error::CommandError;
 use crate::command::sender::CommandSender;
 use crate::server::Server;
@@ -305,8 +320,12 @@ where
         _server: &Arc<Server>,
         _: &dyn CommandHandlerDyn,
     ) -> Option<Result<(), CommandError>> {
-        args.is_empty()
-            .then(|| self.executor.execute(parsed, context))
+        args.is_empty().then(|| {
+            if context.signing.is_collecting() {
+                return Ok(());
+            }
+            self.executor.execute(parsed, context)
+        })
     }
 
     fn usage(&self, _buffer: &mut Vec<CommandNode>, _: i32) -> CommandNodeInfo {
@@ -695,9 +714,26 @@ where
         server: &Arc<Server>,
         handler: &dyn CommandHandlerDyn,
     ) -> Option<Result<(), CommandError>> {
-        let (args, arg) = self.argument.parse(args, context)?;
-        self.executor
-            .execute(args, (parsed, arg), context, server, handler)
+        let (rest, arg) = self.argument.parse(args, context)?;
+        let mut collected = false;
+        if let CommandSigning::Collecting(arguments) = &mut context.signing
+            && matches!(self.argument.usage().0, ArgumentType::Message)
+        {
+            arguments.push((self.name, args[..args.len() - rest.len()].join(" ")));
+            collected = true;
+        }
+
+        let result = self
+            .executor
+            .execute(rest, (parsed, arg), context, server, handler);
+        // The argument isn't part of the command if this branch didn't match
+        if collected
+            && result.is_none()
+            && let CommandSigning::Collecting(arguments) = &mut context.signing
+        {
+            arguments.pop();
+        }
+        result
     }
 
     fn usage(&self, buffer: &mut Vec<CommandNode>, node_index: i32) -> CommandNodeInfo {
@@ -0,0 +1,60 @@
+//! Handler for the "msg" command.
+use std::sync::Arc;
+
+use steel_registry::vanilla_chat_types::{MSG_COMMAND_INCOMING, MSG_COMMAND_OUTGOING};
+use steel_utils::translations;
+
+use crate::command::arguments::message::{MessageArgument, OutgoingChatMessage};
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::commands::{CommandHandlerBuilder, CommandHandlerDyn, argument};
+use crate::command::context::CommandContext;
+use crate::command::sender::CommandSender;
+use crate::player::Player;
+
+/// Handler for the "msg" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["msg", "tell", "w"],
+        "Sends a private message to players.",
+        "minecraft:command.msg",
+    )
+    .then(argument("targets", PlayerArgument::multiple()).then(
+        argument("message", MessageArgument).executes(
+            |(((), targets), text): (((), Vec<Arc<Player>>), String),
+             context: &mut CommandContext| {
+                let message = MessageArgument::resolve(context, "message", text);
+                send_message(&context.sender, &targets, &message);
+                Ok(())
+            },
+        ),
+    ))
+}
+
+/// Sends the message to each target, with a copy to the sender.
+///
+/// Vanilla: `MsgCommand.sendMessage()`.
+fn send_message(sender: &CommandSender, targets: &[Arc<Player>], message: &OutgoingChatMessage) {
+    let incoming = sender.bind_chat_type(MSG_COMMAND_INCOMING, None);
+    let mut filtered_for_any = false;
+    for target in targets {
+        let target_name = target.display_name();
+        match sender {
+            CommandSender::Player(player) => {
+                message.send_to(
+                    player,
+                    sender.bind_chat_type(MSG_COMMAND_OUTGOING, Some(target_name)),
+                );
+            }
+            _ => sender.send_message(
+                &translations::COMMANDS_MESSAGE_DISPLAY_OUTGOING
+                    .message([target_name, message.content()])
+                    .into(),
+            ),
+        }
+        filtered_for_any |= !message.send_to(target, incoming.clone());
+    }
+    if filtered_for_any {
+        sender.send_message(&translations::CHAT_FILTERED_FULL.msg().into());
+    }
+}
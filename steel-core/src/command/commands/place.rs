@@ -0,0 +1,163 @@
+//! Handler for the "place" command.
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, Identifier, Mirror, Rotation, translations};
+use text_components::TextComponent;
+
+use crate::block_entity::entities::StructureBlockEntity;
+use crate::command::arguments::block_pos::BlockPosArgument;
+use crate::command::arguments::float::FloatArgument;
+use crate::command::arguments::integer::IntegerArgument;
+use crate::command::arguments::resource_location::ResourceLocationArgument;
+use crate::command::arguments::template::{TemplateMirrorArgument, TemplateRotationArgument};
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::worldgen::{STRUCTURE_TEMPLATES, StructurePlaceSettings, StructureProcessor};
+
+/// Handler for the "place" command.
+// TODO: Add `feature`, `jigsaw` and `structure` once they can be placed on their own
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["place"],
+        "Places a structure template.",
+        "minecraft:command.place",
+    )
+    .then(
+        literal("template").then(
+            argument("template", ResourceLocationArgument)
+                .executes(PlaceTemplateExecutor)
+                .then(
+                    argument("pos", BlockPosArgument)
+                        .executes(PlaceTemplateExecutor)
+                        .then(
+                            argument("rotation", TemplateRotationArgument)
+                                .executes(PlaceTemplateExecutor)
+                                .then(
+                                    argument("mirror", TemplateMirrorArgument)
+                                        .executes(PlaceTemplateExecutor)
+                                        .then(
+                                            argument(
+                                                "integrity",
+                                                FloatArgument::bounded(Some(0.0), Some(1.0)),
+                                            )
+                                            .executes(PlaceTemplateExecutor)
+                                            .then(
+                                                argument("seed", IntegerArgument::new())
+                                                    .executes(PlaceTemplateExecutor),
+                                            ),
+                                        ),
+                                ),
+                        ),
+                ),
+        ),
+    )
+}
+
+struct PlaceTemplateExecutor;
+
+type TemplateArgs = ((), Identifier);
+type PosArgs = (TemplateArgs, BlockPos);
+type RotationArgs = (PosArgs, Rotation);
+type MirrorArgs = (RotationArgs, Mirror);
+type IntegrityArgs = (MirrorArgs, f32);
+type SeedArgs = (IntegrityArgs, i32);
+
+impl CommandExecutor<TemplateArgs> for PlaceTemplateExecutor {
+    fn execute(
+        &self,
+        args: TemplateArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let pos = BlockPos::from(context.position);
+        self.execute((args, pos), context)
+    }
+}
+
+impl CommandExecutor<PosArgs> for PlaceTemplateExecutor {
+    fn execute(&self, args: PosArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, Rotation::None), context)
+    }
+}
+
+impl CommandExecutor<RotationArgs> for PlaceTemplateExecutor {
+    fn execute(
+        &self,
+        args: RotationArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute((args, Mirror::None), context)
+    }
+}
+
+impl CommandExecutor<MirrorArgs> for PlaceTemplateExecutor {
+    fn execute(&self, args: MirrorArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, 1.0), context)
+    }
+}
+
+impl CommandExecutor<IntegrityArgs> for PlaceTemplateExecutor {
+    fn execute(
+        &self,
+        args: IntegrityArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute((args, 0), context)
+    }
+}
+
+impl CommandExecutor<SeedArgs> for PlaceTemplateExecutor {
+    /// Vanilla: `PlaceCommand.placeTemplate()`.
+    // TODO: Fail with `argument.pos.unloaded` when the area isn't loaded
+    fn execute(&self, args: SeedArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        let (((((((), id), pos), rotation), mirror), integrity), seed) = args;
+        let Some(template) = STRUCTURE_TEMPLATES.get(&id) else {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_PLACE_TEMPLATE_INVALID
+                    .message([TextComponent::from(id.to_string())])
+                    .into(),
+            )));
+        };
+
+        let seed = StructureBlockEntity::random_seed(i64::from(seed));
+        let mut settings = StructurePlaceSettings {
+            mirror,
+            rotation,
+            ..StructurePlaceSettings::default()
+        };
+        if integrity < 1.0 {
+            settings.processors = vec![StructureProcessor::BlockRot {
+                integrity,
+                rottable_blocks: None,
+            }];
+            settings.random_seed = Some(seed);
+        }
+        if !template.place_in_world(
+            &context.world,
+            pos,
+            pos,
+            &settings,
+            &mut LegacyRandom::from_seed(seed as u64),
+            UpdateFlags::UPDATE_CLIENTS,
+        ) {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_PLACE_TEMPLATE_FAILED.msg().into(),
+            )));
+        }
+
+        context.sender.send_message(
+            &translations::COMMANDS_PLACE_TEMPLATE_SUCCESS
+                .message([
+                    TextComponent::from(id.to_string()),
+                    TextComponent::from(pos.x().to_string()),
+                    TextComponent::from(pos.y().to_string()),
+                    TextComponent::from(pos.z().to_string()),
+                ])
+                .into(),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+//! Handler for the "playsound" command.
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_protocol::packets::game::{CSound, SoundEvent, SoundSource};
+use steel_utils::{Identifier, translations};
+use text_components::TextComponent;
+
+use crate::command::arguments::float::FloatArgument;
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::arguments::sound::SoundArgument;
+use crate::command::arguments::vector3::Vector3Argument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, CommandParserExecutor, argument,
+    literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::entity::Entity;
+use crate::player::Player;
+use crate::world::sound_range;
+
+type SoundArgs = ((), Identifier);
+type TargetArgs = (SoundArgs, Vec<Arc<Player>>);
+type PosArgs = (TargetArgs, DVec3);
+type VolumeArgs = (PosArgs, f32);
+type PitchArgs = (VolumeArgs, f32);
+type MinVolumeArgs = (PitchArgs, f32);
+
+/// Handler for the "playsound" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["playsound"],
+        "Plays a sound to players.",
+        "minecraft:command.playsound",
+    )
+    .then(
+        argument("sound", SoundArgument)
+            .executes(PlaySoundExecutor {
+                source: SoundSource::Master,
+            })
+            .then(source(SoundSource::Master))
+            .then(source(SoundSource::Music))
+            .then(source(SoundSource::Records))
+            .then(source(SoundSource::Weather))
+            .then(source(SoundSource::Blocks))
+            .then(source(SoundSource::Hostile))
+            .then(source(SoundSource::Neutral))
+            .then(source(SoundSource::Players))
+            .then(source(SoundSource::Ambient))
+            .then(source(SoundSource::Voice))
+            .then(source(SoundSource::Ui)),
+    )
+}
+
+/// The branch playing the sound in `source`, e.g. `/playsound <sound> music`.
+fn source(source: SoundSource) -> impl CommandParserExecutor<SoundArgs> {
+    let executor = PlaySoundExecutor { source };
+    literal(source.name()).executes(executor).then(
+        argument("targets", PlayerArgument::multiple())
+            .executes(executor)
+            .then(
+                argument("pos", Vector3Argument).executes(executor).then(
+                    argument("volume", FloatArgument::bounded(Some(0.0), None))
+                        .executes(executor)
+                        .then(
+                            argument("pitch", FloatArgument::bounded(Some(0.0), Some(2.0)))
+                                .executes(executor)
+                                .then(
+                                    argument(
+                                        "minVolume",
+                                        FloatArgument::bounded(Some(0.0), Some(1.0)),
+                                    )
+                                    .executes(executor),
+                                ),
+                        ),
+                ),
+            ),
+    )
+}
+
+#[derive(Clone, Copy)]
+struct PlaySoundExecutor {
+    source: SoundSource,
+}
+
+impl CommandExecutor<SoundArgs> for PlaySoundExecutor {
+    fn execute(
+        &self,
+        ((), sound): SoundArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        // Without targets the sound plays to the player running the command
+        let targets: Vec<Arc<Player>> = context.player.clone().into_iter().collect();
+        self.execute((((), sound), targets), context)
+    }
+}
+
+impl CommandExecutor<TargetArgs> for PlaySoundExecutor {
+    fn execute(&self, args: TargetArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        let position = context.position;
+        self.execute((args, position), context)
+    }
+}
+
+impl CommandExecutor<PosArgs> for PlaySoundExecutor {
+    fn execute(&self, args: PosArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, 1.0), context)
+    }
+}
+
+impl CommandExecutor<VolumeArgs> for PlaySoundExecutor {
+    fn execute(&self, args: VolumeArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, 1.0), context)
+    }
+}
+
+impl CommandExecutor<PitchArgs> for PlaySoundExecutor {
+    fn execute(&self, args: PitchArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, 0.0), context)
+    }
+}
+
+impl CommandExecutor<MinVolumeArgs> for PlaySoundExecutor {
+    /// Vanilla: `PlaySoundCommand.playSound()`.
+    fn execute(
+        &self,
+        args: MinVolumeArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let ((((((), sound), targets), position), volume), pitch) = args.0;
+        let min_volume = args.1;
+
+        let sound_event = SoundEvent::Direct {
+            location: sound.clone(),
+            fixed_range: None,
+        };
+        let max_distance_sqr = sound_range(volume).powi(2);
+        let seed = rand::random::<i64>();
+
+        let mut played_for = Vec::new();
+        for player in &targets {
+            if !Arc::ptr_eq(&player.world, &context.world) {
+                continue;
+            }
+
+            let delta = position - player.position();
+            let distance_sqr = delta.length_squared();
+            let (position, volume) = if distance_sqr > max_distance_sqr {
+                // Too far to be heard, play it quietly right next to the player instead
+                if min_volume <= 0.0 {
+                    continue;
+                }
+                let direction = delta / distance_sqr.sqrt();
+                (player.position() + direction * 2.0, min_volume)
+            } else {
+                (position, volume)
+            };
+
+            player.send_packet(CSound::new(
+                sound_event.clone(),
+                self.source,
+                position.x,
+                position.y,
+                position.z,
+                volume,
+                pitch,
+                seed,
+            ));
+            played_for.push(player);
+        }
+
+        let message = match played_for[..] {
+            [] => {
+                return Err(CommandError::CommandFailed(Box::new(
+                    translations::COMMANDS_PLAYSOUND_FAILED.msg().into(),
+                )));
+            }
+            [player] => translations::COMMANDS_PLAYSOUND_SUCCESS_SINGLE.message([
+                TextComponent::from(sound.to_string()),
+                player.display_name(),
+            ]),
+            _ => translations::COMMANDS_PLAYSOUND_SUCCESS_MULTIPLE.message([
+                TextComponent::from(sound.to_string()),
+                TextComponent::from(played_for.len().to_string()),
+            ]),
+        };
+        context.sender.send_message(&message.into());
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+//! Handler for the "random" command.
+use steel_utils::{Identifier, translations};
+use text_components::TextComponent;
+
+use crate::command::arguments::bool::BoolArgument;
+use crate::command::arguments::int_range::{IntRange, IntRangeArgument};
+use crate::command::arguments::integer::IntegerArgument;
+use crate::command::arguments::resource_location::ResourceLocationArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+
+type SequenceArgs = ((), Identifier);
+type SeedArgs<T> = (T, i32);
+type WorldSeedArgs<T> = (SeedArgs<T>, bool);
+type SequenceIdArgs<T> = (WorldSeedArgs<T>, bool);
+
+/// Handler for the "random" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["random"],
+        "Draws a random value or controls random sequences.",
+        "minecraft:command.random",
+    )
+    .then(
+        literal("value").then(
+            argument("range", IntRangeArgument)
+                .executes(RandomSampleExecutor { roll: false })
+                .then(
+                    argument("sequence", ResourceLocationArgument)
+                        .executes(RandomSampleExecutor { roll: false }),
+                ),
+        ),
+    )
+    .then(
+        literal("roll").then(
+            argument("range", IntRangeArgument)
+                .executes(RandomSampleExecutor { roll: true })
+                .then(
+                    argument("sequence", ResourceLocationArgument)
+                        .executes(RandomSampleExecutor { roll: true }),
+                ),
+        ),
+    )
+    .then(
+        literal("reset")
+            .then(
+                literal("*").executes(ResetExecutor).then(
+                    argument("seed", IntegerArgument::new())
+                        .executes(ResetExecutor)
+                        .then(
+                            argument("includeWorldSeed", BoolArgument)
+                                .executes(ResetExecutor)
+                                .then(
+                                    argument("includeSequenceId", BoolArgument)
+                                        .executes(ResetExecutor),
+                                ),
+                        ),
+                ),
+            )
+            .then(
+                argument("sequence", ResourceLocationArgument)
+                    .executes(ResetExecutor)
+                    .then(
+                        argument("seed", IntegerArgument::new())
+                            .executes(ResetExecutor)
+                            .then(
+                                argument("includeWorldSeed", BoolArgument)
+                                    .executes(ResetExecutor)
+                                    .then(
+                                        argument("includeSequenceId", BoolArgument)
+                                            .executes(ResetExecutor),
+                                    ),
+                            ),
+                    ),
+            ),
+    )
+}
+
+/// Draws a value, either telling only the sender (`value`) or everyone (`roll`).
+struct RandomSampleExecutor {
+    roll: bool,
+}
+
+impl CommandExecutor<((), IntRange)> for RandomSampleExecutor {
+    fn execute(
+        &self,
+        ((), range): ((), IntRange),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.sample(range, None, context)
+    }
+}
+
+impl CommandExecutor<(((), IntRange), Identifier)> for RandomSampleExecutor {
+    fn execute(
+        &self,
+        (((), range), sequence): (((), IntRange), Identifier),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.sample(range, Some(&sequence), context)
+    }
+}
+
+impl RandomSampleExecutor {
+    /// Vanilla: `RandomCommand.randomSample()`.
+    fn sample(
+        &self,
+        range: IntRange,
+        sequence: Option<&Identifier>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let min = range.min.unwrap_or(i32::MIN);
+        let max = range.max.unwrap_or(i32::MAX);
+        let span = i64::from(max) - i64::from(min);
+        if span == 0 {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RANDOM_ERROR_RANGE_TOO_SMALL
+                    .msg()
+                    .into(),
+            )));
+        }
+        if span >= i64::from(i32::MAX) {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RANDOM_ERROR_RANGE_TOO_LARGE
+                    .msg()
+                    .into(),
+            )));
+        }
+
+        let value = match sequence {
+            Some(sequence) => {
+                let mut level_data = context.server.overworld().level_data.write();
+                let world_seed = level_data.seed();
+                level_data
+                    .random_sequences_mut()
+                    .next_i32_between(sequence, world_seed, min, max)
+            }
+            None => rand::random_range(min..=max),
+        };
+
+        if self.roll {
+            context.server.broadcast_system_message(
+                &translations::COMMANDS_RANDOM_ROLL
+                    .message([
+                        TextComponent::from(context.sender.to_string()),
+                        TextComponent::from(value.to_string()),
+                        TextComponent::from(min.to_string()),
+                        TextComponent::from(max.to_string()),
+                    ])
+                    .into(),
+            );
+        } else {
+            context.sender.send_message(
+                &translations::COMMANDS_RANDOM_SAMPLE_SUCCESS
+                    .message([TextComponent::from(value.to_string())])
+                    .into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Resets one sequence, or with `*` all of them.
+struct ResetExecutor;
+
+impl CommandExecutor<()> for ResetExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let count = context
+            .server
+            .overworld()
+            .level_data
+            .write()
+            .random_sequences_mut()
+            .clear();
+        send_reset_all(count, context);
+        Ok(())
+    }
+}
+
+impl CommandExecutor<SeedArgs<()>> for ResetExecutor {
+    fn execute(
+        &self,
+        args: SeedArgs<()>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute((args, true), context)
+    }
+}
+
+impl CommandExecutor<WorldSeedArgs<()>> for ResetExecutor {
+    fn execute(
+        &self,
+        args: WorldSeedArgs<()>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute((args, true), context)
+    }
+}
+
+impl CommandExecutor<SequenceIdArgs<()>> for ResetExecutor {
+    fn execute(
+        &self,
+        ((((), seed), include_world_seed), include_sequence_id): SequenceIdArgs<()>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let count = {
+            let mut level_data = context.server.overworld().level_data.write();
+            let sequences = level_data.random_sequences_mut();
+            let count = sequences.clear();
+            sequences.set_seed_defaults(seed, include_world_seed, include_sequence_id);
+            count
+        };
+        send_reset_all(count, context);
+        Ok(())
+    }
+}
+
+impl CommandExecutor<SequenceArgs> for ResetExecutor {
+    fn execute(
+        &self,
+        ((), sequence): SequenceArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        {
+            let mut level_data = context.server.overworld().level_data.write();
+            let world_seed = level_data.seed();
+            level_data
+                .random_sequences_mut()
+                .reset_to_defaults(sequence.clone(), world_seed);
+        }
+        send_reset(&sequence, context);
+        Ok(())
+    }
+}
+
+impl CommandExecutor<SeedArgs<SequenceArgs>> for ResetExecutor {
+    fn execute(
+        &self,
+        args: SeedArgs<SequenceArgs>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute(((args, true), true), context)
+    }
+}
+
+impl CommandExecutor<WorldSeedArgs<SequenceArgs>> for ResetExecutor {
+    fn execute(
+        &self,
+        args: WorldSeedArgs<SequenceArgs>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute((args, true), context)
+    }
+}
+
+impl CommandExecutor<SequenceIdArgs<SequenceArgs>> for ResetExecutor {
+    fn execute(
+        &self,
+        args: SequenceIdArgs<SequenceArgs>,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let (((((), sequence), seed), include_world_seed), include_sequence_id) = args;
+        {
+            let mut level_data = context.server.overworld().level_data.write();
+            let world_seed = level_data.seed();
+            level_data.random_sequences_mut().reset(
+                sequence.clone(),
+                world_seed,
+                seed,
+                include_world_seed,
+                include_sequence_id,
+            );
+        }
+        send_reset(&sequence, context);
+        Ok(())
+    }
+}
+
+fn send_reset(sequence: &Identifier, context: &CommandContext) {
+    context.sender.send_message(
+        &translations::COMMANDS_RANDOM_RESET_SUCCESS
+            .message([TextComponent::from(sequence.to_string())])
+            .into(),
+    );
+}
+
+fn send_reset_all(count: usize, context: &CommandContext) {
+    context.sender.send_message(
+        &translations::COMMANDS_RANDOM_RESET_ALL_SUCCESS
+            .message([TextComponent::from(count.to_string())])
+            .into(),
+    );
+}
@@ -0,0 +1,140 @@
+//! Handler for the "ride" command.
+//! Mirrors `net.minecraft.server.commands.RideCommand`.
+
+use std::sync::Arc;
+
+use steel_registry::vanilla_entities;
+use steel_utils::translations;
+use text_components::TextComponent;
+
+use crate::command::arguments::entity::EntityArgument;
+use crate::command::commands::data::entity_name;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::entity::{Entity, LivingEntity, SharedEntity, riding};
+
+type TargetArgs = ((), Vec<Arc<dyn LivingEntity + Send + Sync>>);
+type MountArgs = (TargetArgs, Vec<Arc<dyn LivingEntity + Send + Sync>>);
+
+/// Handler for the "ride" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["ride"],
+        "Makes an entity start or stop riding another entity.",
+        "minecraft:command.ride",
+    )
+    .then(
+        argument("target", EntityArgument::one())
+            .then(
+                literal("mount")
+                    .then(argument("vehicle", EntityArgument::one()).executes(MountExecutor)),
+            )
+            .then(literal("dismount").executes(DismountExecutor)),
+    )
+}
+
+struct MountExecutor;
+
+impl CommandExecutor<MountArgs> for MountExecutor {
+    fn execute(
+        &self,
+        (((), targets), vehicles): MountArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let target = targets.into_iter().next().ok_or_else(no_entity_found)?;
+        let vehicle = vehicles.into_iter().next().ok_or_else(no_entity_found)?;
+
+        if let Some(existing) = target.vehicle() {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RIDE_ALREADY_RIDING
+                    .message([entity_name(&target), entity_name(&existing)])
+                    .into(),
+            )));
+        }
+        if vehicle.entity_type().key == vanilla_entities::PLAYER.key {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RIDE_MOUNT_FAILURE_CANT_RIDE_PLAYERS
+                    .msg()
+                    .into(),
+            )));
+        }
+        if is_self_or_passenger(target.as_ref(), vehicle.id()) {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RIDE_MOUNT_FAILURE_LOOP.msg().into(),
+            )));
+        }
+        let same_world = match (target.level(), vehicle.level()) {
+            (Some(target_world), Some(vehicle_world)) => Arc::ptr_eq(&target_world, &vehicle_world),
+            _ => false,
+        };
+        if !same_world {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RIDE_MOUNT_FAILURE_WRONG_DIMENSION
+                    .msg()
+                    .into(),
+            )));
+        }
+        if !riding::start_riding(
+            &(target.clone() as SharedEntity),
+            &(vehicle.clone() as SharedEntity),
+            true,
+        ) {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RIDE_MOUNT_FAILURE_GENERIC
+                    .message([entity_name(&target), entity_name(&vehicle)])
+                    .into(),
+            )));
+        }
+
+        context.sender.send_message(
+            &translations::COMMANDS_RIDE_MOUNT_SUCCESS
+                .message([entity_name(&target), entity_name(&vehicle)])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+struct DismountExecutor;
+
+impl CommandExecutor<TargetArgs> for DismountExecutor {
+    fn execute(
+        &self,
+        ((), targets): TargetArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let target = targets.into_iter().next().ok_or_else(no_entity_found)?;
+        let Some(vehicle) = target.vehicle() else {
+            return Err(CommandError::CommandFailed(Box::new(
+                translations::COMMANDS_RIDE_NOT_RIDING
+                    .message([entity_name(&target)])
+                    .into(),
+            )));
+        };
+
+        riding::stop_riding(target.as_ref());
+        context.sender.send_message(
+            &translations::COMMANDS_RIDE_DISMOUNT_SUCCESS
+                .message([entity_name(&target), entity_name(&vehicle)])
+                .into(),
+        );
+        Ok(())
+    }
+}
+
+fn no_entity_found() -> CommandError {
+    CommandError::CommandFailed(Box::new(TextComponent::const_plain("No entity was found")))
+}
+
+/// Vanilla: `Entity.getSelfAndPassengers().anyMatch(...)`.
+fn is_self_or_passenger(entity: &dyn Entity, id: i32) -> bool {
+    entity.id() == id
+        || entity
+            .passengers()
+            .iter()
+            .any(|passenger| is_self_or_passenger(passenger.as_ref(), id))
+}
@@ -0,0 +1,79 @@
+//! Handler for the "setworldspawn" command.
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+
+use crate::command::arguments::block_pos::BlockPosArgument;
+use crate::command::arguments::rotation::RotationArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::level_data::SpawnPoint;
+
+/// Handler for the "setworldspawn" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["setworldspawn"],
+        "Sets the world spawn.",
+        "minecraft:command.setworldspawn",
+    )
+    .executes(SetWorldSpawnCommandExecutor)
+    .then(
+        argument("pos", BlockPosArgument)
+            .executes(SetWorldSpawnCommandExecutor)
+            .then(argument("rotation", RotationArgument).executes(SetWorldSpawnCommandExecutor)),
+    )
+}
+
+struct SetWorldSpawnCommandExecutor;
+
+impl CommandExecutor<()> for SetWorldSpawnCommandExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let pos = BlockPos::from(context.position);
+        self.execute(((), pos), context)
+    }
+}
+
+impl CommandExecutor<((), BlockPos)> for SetWorldSpawnCommandExecutor {
+    fn execute(
+        &self,
+        ((), pos): ((), BlockPos),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.execute((((), pos), (0.0, 0.0)), context)
+    }
+}
+
+impl CommandExecutor<(((), BlockPos), (f32, f32))> for SetWorldSpawnCommandExecutor {
+    fn execute(
+        &self,
+        (((), pos), (yaw, pitch)): (((), BlockPos), (f32, f32)),
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let dimension = context.world.dimension.key.clone();
+        context.server.set_respawn_data(SpawnPoint {
+            x: pos.x(),
+            y: pos.y(),
+            z: pos.z(),
+            angle: yaw,
+            pitch,
+            dimension: dimension.clone(),
+        });
+
+        context.sender.send_message(
+            &translations::COMMANDS_SETWORLDSPAWN_SUCCESS_NEW
+                .message([
+                    TextComponent::from(pos.x().to_string()),
+                    TextComponent::from(pos.y().to_string()),
+                    TextComponent::from(pos.z().to_string()),
+                    TextComponent::from(yaw.to_string()),
+                    TextComponent::from(pitch.to_string()),
+                    TextComponent::from(dimension.to_string()),
+                ])
+                .into(),
+        );
+        Ok(())
+    }
+}
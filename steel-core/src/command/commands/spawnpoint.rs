@@ -0,0 +1,116 @@
+//! Handler for the "spawnpoint" command.
+use std::sync::Arc;
+
+use steel_utils::{BlockPos, translations};
+use text_components::TextComponent;
+
+use crate::command::arguments::block_pos::BlockPosArgument;
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::arguments::rotation::RotationArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::player::{Player, RespawnConfig};
+
+type TargetsArgs = ((), Vec<Arc<Player>>);
+type PosArgs = (TargetsArgs, BlockPos);
+type RotationArgs = (PosArgs, (f32, f32));
+
+/// Handler for the "spawnpoint" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["spawnpoint"],
+        "Sets the spawn point of players.",
+        "minecraft:command.spawnpoint",
+    )
+    .executes(SpawnPointCommandExecutor)
+    .then(
+        argument("targets", PlayerArgument::multiple())
+            .executes(SpawnPointCommandExecutor)
+            .then(
+                argument("pos", BlockPosArgument)
+                    .executes(SpawnPointCommandExecutor)
+                    .then(
+                        argument("rotation", RotationArgument).executes(SpawnPointCommandExecutor),
+                    ),
+            ),
+    )
+}
+
+struct SpawnPointCommandExecutor;
+
+impl CommandExecutor<()> for SpawnPointCommandExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let player = context
+            .sender
+            .get_player()
+            .ok_or(CommandError::InvalidRequirement)?;
+        self.execute(((), vec![player.clone()]), context)
+    }
+}
+
+impl CommandExecutor<TargetsArgs> for SpawnPointCommandExecutor {
+    fn execute(&self, args: TargetsArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        let pos = BlockPos::from(context.position);
+        self.execute((args, pos), context)
+    }
+}
+
+impl CommandExecutor<PosArgs> for SpawnPointCommandExecutor {
+    fn execute(&self, args: PosArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        self.execute((args, (0.0, 0.0)), context)
+    }
+}
+
+impl CommandExecutor<RotationArgs> for SpawnPointCommandExecutor {
+    fn execute(
+        &self,
+        ((((), targets), pos), (yaw, pitch)): RotationArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let dimension = context.world.dimension.key.clone();
+
+        for target in &targets {
+            *target.respawn_config.lock() = Some(RespawnConfig {
+                dimension: dimension.clone(),
+                pos,
+                yaw,
+                pitch,
+                forced: true,
+            });
+        }
+
+        let x = TextComponent::from(pos.x().to_string());
+        let y = TextComponent::from(pos.y().to_string());
+        let z = TextComponent::from(pos.z().to_string());
+        let yaw = TextComponent::from(yaw.to_string());
+        let pitch = TextComponent::from(pitch.to_string());
+        let dimension = TextComponent::from(dimension.to_string());
+        let message = if let [target] = targets.as_slice() {
+            translations::COMMANDS_SPAWNPOINT_SUCCESS_SINGLE_NEW.message([
+                x,
+                y,
+                z,
+                yaw,
+                pitch,
+                dimension,
+                TextComponent::from(target.gameprofile.name.clone()),
+            ])
+        } else {
+            translations::COMMANDS_SPAWNPOINT_SUCCESS_MULTIPLE_NEW.message([
+                x,
+                y,
+                z,
+                yaw,
+                pitch,
+                dimension,
+                TextComponent::from(targets.len().to_string()),
+            ])
+        };
+        context.sender.send_message(&message.into());
+        Ok(())
+    }
+}
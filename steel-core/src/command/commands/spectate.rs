@@ -0,0 +1,125 @@
+//! Handler for the "spectate" command.
+//! Mirrors `net.minecraft.server.commands.SpectateCommand`.
+
+use std::sync::Arc;
+
+use steel_utils::translations;
+use steel_utils::types::GameType;
+use text_components::TextComponent;
+
+use crate::command::arguments::entity::EntityArgument;
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::commands::data::entity_name;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, argument,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::entity::{Entity, LivingEntity, SharedEntity};
+use crate::player::Player;
+
+type TargetArgs = ((), Vec<Arc<dyn LivingEntity + Send + Sync>>);
+type TargetPlayerArgs = (TargetArgs, Vec<Arc<Player>>);
+
+/// Handler for the "spectate" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["spectate"],
+        "Makes a spectator view the world through another entity.",
+        "minecraft:command.spectate",
+    )
+    .executes(SpectateCommandExecutor)
+    .then(
+        argument("target", EntityArgument::one())
+            .executes(SpectateCommandExecutor)
+            .then(argument("player", PlayerArgument::one()).executes(SpectateCommandExecutor)),
+    )
+}
+
+struct SpectateCommandExecutor;
+
+impl CommandExecutor<()> for SpectateCommandExecutor {
+    fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
+        let player = context
+            .sender
+            .get_player()
+            .ok_or(CommandError::InvalidRequirement)?
+            .clone();
+        spectate(None, &player, context)
+    }
+}
+
+impl CommandExecutor<TargetArgs> for SpectateCommandExecutor {
+    fn execute(&self, args: TargetArgs, context: &mut CommandContext) -> Result<(), CommandError> {
+        let player = context
+            .sender
+            .get_player()
+            .ok_or(CommandError::InvalidRequirement)?
+            .clone();
+        self.execute((args, vec![player]), context)
+    }
+}
+
+impl CommandExecutor<TargetPlayerArgs> for SpectateCommandExecutor {
+    fn execute(
+        &self,
+        (((), targets), players): TargetPlayerArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        let target = targets.into_iter().next().ok_or_else(no_entity_found)?;
+        let player = players.into_iter().next().ok_or_else(no_entity_found)?;
+        spectate(Some(target), &player, context)
+    }
+}
+
+fn no_entity_found() -> CommandError {
+    CommandError::CommandFailed(Box::new(TextComponent::const_plain("No entity was found")))
+}
+
+fn spectate(
+    target: Option<Arc<dyn LivingEntity + Send + Sync>>,
+    player: &Arc<Player>,
+    context: &mut CommandContext,
+) -> Result<(), CommandError> {
+    if target
+        .as_ref()
+        .is_some_and(|target| target.id() == player.id)
+    {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SPECTATE_SELF.msg().into(),
+        )));
+    }
+    if player.game_mode.load() != GameType::Spectator {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SPECTATE_NOT_SPECTATOR
+                .message([TextComponent::plain(player.gameprofile.name.clone())])
+                .into(),
+        )));
+    }
+
+    let Some(target) = target else {
+        player.set_camera(None);
+        context
+            .sender
+            .send_message(&translations::COMMANDS_SPECTATE_SUCCESS_STOPPED.msg().into());
+        return Ok(());
+    };
+
+    // Entities the client never tracks can't be viewed through
+    if target.entity_type().client_tracking_range == 0
+        || !player.set_camera(Some(target.clone() as SharedEntity))
+    {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SPECTATE_CANNOT_SPECTATE
+                .message([entity_name(&target)])
+                .into(),
+        )));
+    }
+    context.sender.send_message(
+        &translations::COMMANDS_SPECTATE_SUCCESS_STARTED
+            .message([entity_name(&target)])
+            .into(),
+    );
+    Ok(())
+}
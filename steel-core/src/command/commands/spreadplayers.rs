@@ -0,0 +1,413 @@
+//! Handler for the "spreadplayers" command.
+use std::sync::Arc;
+
+use glam::DVec2;
+use rustc_hash::FxHashSet;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::{REGISTRY, vanilla_block_tags};
+use steel_utils::{BlockPos, ChunkPos, SectionPos, translations};
+use text_components::TextComponent;
+
+use crate::chunk::heightmap::HeightmapType;
+use crate::command::arguments::bool::BoolArgument;
+use crate::command::arguments::float::FloatArgument;
+use crate::command::arguments::integer::IntegerArgument;
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::arguments::vector2::Vector2Argument;
+use crate::command::commands::{CommandHandlerBuilder, CommandHandlerDyn, argument, literal};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::entity::Entity;
+use crate::player::Player;
+use crate::world::World;
+
+/// How many rounds of pushing positions apart are tried before giving up.
+const MAX_ITERATIONS: usize = 10_000;
+
+type SpreadArgs = ((((((), DVec2), f32), f32), bool), Vec<Arc<Player>>);
+type SpreadUnderArgs = (((((((), DVec2), f32), f32), i32), bool), Vec<Arc<Player>>);
+
+/// Handler for the "spreadplayers" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["spreadplayers"],
+        "Teleports players to random surface locations around a center.",
+        "minecraft:command.spreadplayers",
+    )
+    .then(
+        argument("center", Vector2Argument).then(
+            argument("spreadDistance", FloatArgument::bounded(Some(0.0), None)).then(
+                argument("maxRange", FloatArgument::bounded(Some(1.0), None))
+                    .then(argument("respectTeams", BoolArgument).then(
+                        argument("targets", PlayerArgument::multiple()).executes(
+                            |args: SpreadArgs, context: &mut CommandContext| {
+                                let (((((), center), spread_distance), max_range), respect_teams) =
+                                    args.0;
+                                let max_height = context.world.get_max_y() + 1;
+                                spread_players(
+                                    center,
+                                    spread_distance,
+                                    max_range,
+                                    max_height,
+                                    respect_teams,
+                                    &args.1,
+                                    context,
+                                )
+                            },
+                        ),
+                    ))
+                    .then(literal("under").then(
+                        argument("maxHeight", IntegerArgument::new()).then(
+                            argument("respectTeams", BoolArgument).then(
+                                argument("targets", PlayerArgument::multiple()).executes(
+                                    |args: SpreadUnderArgs, context: &mut CommandContext| {
+                                        let (
+                                            (
+                                                ((((), center), spread_distance), max_range),
+                                                max_height,
+                                            ),
+                                            respect_teams,
+                                        ) = args.0;
+                                        spread_players(
+                                            center,
+                                            spread_distance,
+                                            max_range,
+                                            max_height,
+                                            respect_teams,
+                                            &args.1,
+                                            context,
+                                        )
+                                    },
+                                ),
+                            ),
+                        ),
+                    )),
+            ),
+        ),
+    )
+}
+
+/// Vanilla: `SpreadPlayersCommand.spreadPlayers()`.
+fn spread_players(
+    center: DVec2,
+    spread_distance: f32,
+    max_range: f32,
+    max_height: i32,
+    respect_teams: bool,
+    targets: &[Arc<Player>],
+    context: &mut CommandContext,
+) -> Result<(), CommandError> {
+    let world = &context.world;
+    let min_y = world.get_min_y();
+    if max_height < min_y {
+        return Err(CommandError::CommandFailed(Box::new(
+            translations::COMMANDS_SPREADPLAYERS_FAILED_INVALID_HEIGHT
+                .message([
+                    TextComponent::from(max_height.to_string()),
+                    TextComponent::from(min_y.to_string()),
+                ])
+                .into(),
+        )));
+    }
+
+    let bounds = Bounds {
+        min_x: center.x - f64::from(max_range),
+        min_z: center.y - f64::from(max_range),
+        max_x: center.x + f64::from(max_range),
+        max_z: center.y + f64::from(max_range),
+    };
+    // TODO: Group players by team once teams exist. Until then every player is
+    // team-less, and vanilla spreads all team-less entities as one team.
+    let count = if respect_teams { 1 } else { targets.len() };
+    let mut positions: Vec<Position> = (0..count).map(|_| bounds.random_position()).collect();
+    let mut chunks = LoadedChunks::new(world);
+
+    let (iterations, min_distance) = spread_positions(
+        f64::from(spread_distance),
+        &mut chunks,
+        &bounds,
+        max_height,
+        &mut positions,
+    );
+    if iterations >= MAX_ITERATIONS {
+        let error = if respect_teams {
+            translations::COMMANDS_SPREADPLAYERS_FAILED_TEAMS
+        } else {
+            translations::COMMANDS_SPREADPLAYERS_FAILED_ENTITIES
+        };
+        return Err(CommandError::CommandFailed(Box::new(
+            error
+                .message([
+                    TextComponent::from(positions.len().to_string()),
+                    TextComponent::from(center.x.to_string()),
+                    TextComponent::from(center.y.to_string()),
+                    TextComponent::from(format!("{min_distance:.2}")),
+                ])
+                .into(),
+        )));
+    }
+
+    let average_distance = set_player_positions(targets, &mut chunks, &positions, max_height);
+    let success = if respect_teams {
+        translations::COMMANDS_SPREADPLAYERS_SUCCESS_TEAMS
+    } else {
+        translations::COMMANDS_SPREADPLAYERS_SUCCESS_ENTITIES
+    };
+    context.sender.send_message(
+        &success
+            .message([
+                TextComponent::from(positions.len().to_string()),
+                TextComponent::from(center.x.to_string()),
+                TextComponent::from(center.y.to_string()),
+                TextComponent::from(format!("{average_distance:.2}")),
+            ])
+            .into(),
+    );
+    Ok(())
+}
+
+/// Pushes positions apart until they're at least `spread_distance` from each
+/// other and all stand on safe ground, returning the number of iterations
+/// taken and the smallest distance between two positions.
+///
+/// Vanilla: `SpreadPlayersCommand.spreadPositions()`.
+fn spread_positions(
+    spread_distance: f64,
+    chunks: &mut LoadedChunks,
+    bounds: &Bounds,
+    max_height: i32,
+    positions: &mut [Position],
+) -> (usize, f64) {
+    let mut has_collisions = true;
+    let mut min_distance = f64::from(f32::MAX);
+    let mut iteration = 0;
+    while iteration < MAX_ITERATIONS && has_collisions {
+        iteration += 1;
+        has_collisions = false;
+        min_distance = f64::from(f32::MAX);
+
+        for i in 0..positions.len() {
+            let position = positions[i];
+            let mut neighbour_count = 0;
+            let mut push = DVec2::ZERO;
+            for (j, neighbour) in positions.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let distance = position.0.distance(neighbour.0);
+                min_distance = min_distance.min(distance);
+                if distance < spread_distance {
+                    neighbour_count += 1;
+                    push += neighbour.0 - position.0;
+                }
+            }
+
+            let position = &mut positions[i];
+            if neighbour_count > 0 {
+                push /= f64::from(neighbour_count);
+                if push.length() > 0.0 {
+                    position.0 -= push.normalize();
+                } else {
+                    *position = bounds.random_position();
+                }
+                has_collisions = true;
+            }
+            if bounds.clamp(position) {
+                has_collisions = true;
+            }
+        }
+
+        if !has_collisions {
+            for position in positions.iter_mut() {
+                if !position.is_safe(chunks, max_height) {
+                    *position = bounds.random_position();
+                    has_collisions = true;
+                }
+            }
+        }
+    }
+
+    if min_distance == f64::from(f32::MAX) {
+        min_distance = 0.0;
+    }
+    (iteration, min_distance)
+}
+
+/// Teleports every target to its position, returning the average distance
+/// to the nearest other position.
+///
+/// Vanilla: `SpreadPlayersCommand.setPlayerPositions()`.
+fn set_player_positions(
+    targets: &[Arc<Player>],
+    chunks: &mut LoadedChunks,
+    positions: &[Position],
+    max_height: i32,
+) -> f64 {
+    let mut total_distance = 0.0;
+    for (i, player) in targets.iter().enumerate() {
+        // With fewer positions than targets, everyone shares the team-less one
+        let index = i.min(positions.len() - 1);
+        let position = positions[index];
+        let (yaw, pitch) = player.rotation();
+        player.teleport(
+            position.0.x.floor() + 0.5,
+            f64::from(position.spawn_y(chunks, max_height)),
+            position.0.y.floor() + 0.5,
+            yaw,
+            pitch,
+        );
+
+        let closest = positions
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != index)
+            .map(|(_, other)| position.0.distance(other.0))
+            .fold(f64::MAX, f64::min);
+        total_distance += closest;
+    }
+
+    // With a single position there's no other to be closest to
+    if targets.len() < 2 || positions.len() < 2 {
+        0.0
+    } else {
+        total_distance / targets.len() as f64
+    }
+}
+
+/// The area positions are spread within.
+struct Bounds {
+    min_x: f64,
+    min_z: f64,
+    max_x: f64,
+    max_z: f64,
+}
+
+impl Bounds {
+    /// Vanilla: `SpreadPlayersCommand.Position.randomize()`.
+    fn random_position(&self) -> Position {
+        Position(DVec2::new(
+            random_between(self.min_x, self.max_x),
+            random_between(self.min_z, self.max_z),
+        ))
+    }
+
+    /// Moves `position` back inside the bounds, returning whether it moved.
+    ///
+    /// Vanilla: `SpreadPlayersCommand.Position.clamp()`.
+    fn clamp(&self, position: &mut Position) -> bool {
+        let clamped = DVec2::new(
+            position.0.x.clamp(self.min_x, self.max_x),
+            position.0.y.clamp(self.min_z, self.max_z),
+        );
+        let changed = clamped != position.0;
+        position.0 = clamped;
+        changed
+    }
+}
+
+/// A candidate column to spread to, with `z` stored in the vector's `y`.
+#[derive(Clone, Copy)]
+struct Position(DVec2);
+
+impl Position {
+    fn block_column(self, y: i32) -> BlockPos {
+        BlockPos::new(self.0.x.floor() as i32, y, self.0.y.floor() as i32)
+    }
+
+    /// Returns the lowest Y at or below `max_height + 1` with two air blocks
+    /// on top of a non-air block, or `max_height + 1` if there is none.
+    ///
+    /// Vanilla: `SpreadPlayersCommand.Position.getSpawnY()`. Steel starts from
+    /// the world surface heightmap and only scans blocks when the surface is
+    /// above `max_height`.
+    fn spawn_y(self, chunks: &mut LoadedChunks, max_height: i32) -> i32 {
+        let world = chunks.load(self.block_column(max_height));
+        let min_y = world.get_min_y();
+        let surface = world.get_height(HeightmapType::WorldSurface, self.block_column(max_height));
+        if surface <= min_y {
+            return max_height + 1;
+        }
+        if surface <= max_height {
+            return surface;
+        }
+
+        let mut air_above_2 = world
+            .get_block_state(self.block_column(max_height + 1))
+            .is_air();
+        let mut air_above_1 = world
+            .get_block_state(self.block_column(max_height))
+            .is_air();
+        let mut y = max_height;
+        while y > min_y {
+            y -= 1;
+            let is_air = world.get_block_state(self.block_column(y)).is_air();
+            if !is_air && air_above_1 && air_above_2 {
+                return y + 1;
+            }
+            air_above_2 = air_above_1;
+            air_above_1 = is_air;
+        }
+        max_height + 1
+    }
+
+    /// Whether a player would land below `max_height` on something other than
+    /// a liquid or fire.
+    ///
+    /// Vanilla: `SpreadPlayersCommand.Position.isSafe()`.
+    fn is_safe(self, chunks: &mut LoadedChunks, max_height: i32) -> bool {
+        let ground_y = self.spawn_y(chunks, max_height) - 1;
+        let ground = self.block_column(ground_y);
+        let block = chunks.load(ground).get_block_state(ground).get_block();
+        ground_y < max_height
+            && !block.config.liquid
+            && !REGISTRY
+                .blocks
+                .is_in_tag(block, &vanilla_block_tags::FIRE_TAG)
+    }
+}
+
+/// The chunks loaded to check positions, which keep their tickets until the
+/// command is done.
+struct LoadedChunks<'a> {
+    world: &'a World,
+    chunks: FxHashSet<ChunkPos>,
+}
+
+impl<'a> LoadedChunks<'a> {
+    fn new(world: &'a World) -> Self {
+        Self {
+            world,
+            chunks: FxHashSet::default(),
+        }
+    }
+
+    /// Loads or generates the chunk holding `pos` if it wasn't yet.
+    fn load(&mut self, pos: BlockPos) -> &'a World {
+        let chunk = ChunkPos::new(
+            SectionPos::block_to_section_coord(pos.x()),
+            SectionPos::block_to_section_coord(pos.z()),
+        );
+        if self.chunks.insert(chunk) {
+            self.world.chunk_map.load_full_chunk_blocking(chunk);
+        }
+        self.world
+    }
+}
+
+impl Drop for LoadedChunks<'_> {
+    fn drop(&mut self) {
+        for &chunk in &self.chunks {
+            self.world.chunk_map.remove_full_ticket(chunk);
+        }
+    }
+}
+
+/// Vanilla: `Mth.nextDouble()`.
+fn random_between(min: f64, max: f64) -> f64 {
+    if min >= max {
+        min
+    } else {
+        rand::random::<f64>() * (max - min) + min
+    }
+}
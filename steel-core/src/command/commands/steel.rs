@@ -0,0 +1,378 @@
+//! Handler for the "steel" command.
+use std::cmp::Reverse;
+use std::path::Path;
+
+use steel_utils::logger::set_level;
+use text_components::TextComponent;
+use tracing::level_filters::LevelFilter;
+use uuid::Uuid;
+
+use crate::chunk::chunk_ticket_manager::MAX_VIEW_DISTANCE;
+use crate::command::arguments::game_profile::GameProfileArgument;
+use crate::command::arguments::integer::IntegerArgument;
+use crate::command::arguments::string::StringArgument;
+use crate::command::commands::{
+    CommandHandlerBuilder, CommandHandlerDyn, CommandParserExecutor, argument, literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::config::STEEL_CONFIG;
+#[cfg(feature = "scripting")]
+use crate::plugin::scripting::reload_scripts;
+use crate::server::chunk_distances::{CHUNK_DISTANCES, DistanceError, MIN_VIEW_DISTANCE};
+use crate::server::tick_profiler::{PROFILE_DIRECTORY, TICK_PROFILER};
+
+/// Handler for the "steel" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["steel"],
+        "Steel server administration.",
+        "steel:command.steel",
+    )
+    // /steel viewdistance [<distance>]
+    .then(
+        literal("viewdistance")
+            .executes(|(), ctx: &mut CommandContext| {
+                ctx.sender.send_message(&TextComponent::from(format!(
+                    "The view distance is {} chunks",
+                    CHUNK_DISTANCES.view_distance()
+                )));
+                Ok(())
+            })
+            .then(
+                argument(
+                    "distance",
+                    IntegerArgument::bounded(
+                        Some(i32::from(MIN_VIEW_DISTANCE)),
+                        Some(i32::from(MAX_VIEW_DISTANCE)),
+                    ),
+                )
+                .executes(|((), distance): ((), i32), ctx: &mut CommandContext| {
+                    let distance = to_distance(distance)?;
+                    ctx.server
+                        .set_view_distance(distance)
+                        .map_err(distance_error)?;
+                    ctx.sender.send_message(&TextComponent::from(format!(
+                        "Set the view distance to {distance} chunks (simulation distance: {})",
+                        CHUNK_DISTANCES.simulation_distance()
+                    )));
+                    Ok(())
+                }),
+            ),
+    )
+    // /steel simulationdistance [<distance>]
+    .then(
+        literal("simulationdistance")
+            .executes(|(), ctx: &mut CommandContext| {
+                ctx.sender.send_message(&TextComponent::from(format!(
+                    "The simulation distance is {} chunks",
+                    CHUNK_DISTANCES.simulation_distance()
+                )));
+                Ok(())
+            })
+            .then(
+                argument(
+                    "distance",
+                    IntegerArgument::bounded(Some(0), Some(i32::from(MAX_VIEW_DISTANCE))),
+                )
+                .executes(|((), distance): ((), i32), ctx: &mut CommandContext| {
+                    let distance = to_distance(distance)?;
+                    ctx.server
+                        .set_simulation_distance(distance)
+                        .map_err(distance_error)?;
+                    ctx.sender.send_message(&TextComponent::from(format!(
+                        "Set the simulation distance to {distance} chunks"
+                    )));
+                    Ok(())
+                }),
+            ),
+    )
+    // /steel worldsimulationdistance [<distance>|default]
+    .then(
+        literal("worldsimulationdistance")
+            .executes(|(), ctx: &mut CommandContext| {
+                let world = &ctx.world.dimension.key;
+                let message = match ctx.world.own_simulation_distance() {
+                    Some(_) => format!(
+                        "The simulation distance of {world} is {} chunks",
+                        ctx.world.simulation_distance()
+                    ),
+                    None => format!(
+                        "{world} uses the server simulation distance of {} chunks",
+                        ctx.world.simulation_distance()
+                    ),
+                };
+                ctx.sender.send_message(&TextComponent::from(message));
+                Ok(())
+            })
+            .then(literal("default").executes(|(), ctx: &mut CommandContext| {
+                ctx.world
+                    .set_simulation_distance(None)
+                    .map_err(distance_error)?;
+                ctx.sender.send_message(&TextComponent::from(format!(
+                    "{} uses the server simulation distance again",
+                    ctx.world.dimension.key
+                )));
+                Ok(())
+            }))
+            .then(
+                argument(
+                    "distance",
+                    IntegerArgument::bounded(Some(0), Some(i32::from(MAX_VIEW_DISTANCE))),
+                )
+                .executes(|((), distance): ((), i32), ctx: &mut CommandContext| {
+                    let distance = to_distance(distance)?;
+                    ctx.world
+                        .set_simulation_distance(Some(distance))
+                        .map_err(distance_error)?;
+                    ctx.sender.send_message(&TextComponent::from(format!(
+                        "Set the simulation distance of {} to {distance} chunks",
+                        ctx.world.dimension.key
+                    )));
+                    Ok(())
+                }),
+            ),
+    )
+    // /steel chunks
+    .then(literal("chunks").executes(|(), ctx: &mut CommandContext| {
+        list_crowded_chunks(ctx);
+        Ok(())
+    }))
+    // /steel memory
+    .then(literal("memory").executes(|(), ctx: &mut CommandContext| {
+        report_chunk_memory(ctx);
+        Ok(())
+    }))
+    // /steel saves
+    .then(literal("saves").executes(|(), ctx: &mut CommandContext| {
+        report_chunk_saves(ctx);
+        Ok(())
+    }))
+    // /steel profile (start|stop)
+    .then(
+        literal("profile")
+            .then(literal("start").executes(|(), ctx: &mut CommandContext| {
+                if !TICK_PROFILER.start() {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        "The profiler is already running".into(),
+                    )));
+                }
+                ctx.sender.send_message(&TextComponent::from(
+                    "Started profiling, stop with /steel profile stop",
+                ));
+                Ok(())
+            }))
+            .then(literal("stop").executes(|(), ctx: &mut CommandContext| stop_profiler(ctx))),
+    )
+    // /steel reload-config
+    .then(
+        literal("reload-config").executes(|(), ctx: &mut CommandContext| {
+            let changed = ctx.server.reload_config().map_err(|err| {
+                CommandError::CommandFailed(Box::new(
+                    format!("Failed to reload the config: {err}").into(),
+                ))
+            })?;
+            let message = if changed.is_empty() {
+                "Reloaded the config, no live settings changed".to_owned()
+            } else {
+                format!("Reloaded the config, changed {}", changed.join(", "))
+            };
+            ctx.sender.send_message(&TextComponent::from(message));
+            Ok(())
+        }),
+    )
+    // /steel log level <target> (off|error|warn|info|debug|trace)
+    .then(
+        literal("log").then(
+            literal("level").then(
+                argument("target", StringArgument::quotable())
+                    .then(log_level("off", LevelFilter::OFF))
+                    .then(log_level("error", LevelFilter::ERROR))
+                    .then(log_level("warn", LevelFilter::WARN))
+                    .then(log_level("info", LevelFilter::INFO))
+                    .then(log_level("debug", LevelFilter::DEBUG))
+                    .then(log_level("trace", LevelFilter::TRACE)),
+            ),
+        ),
+    )
+    // /steel uuid <player>
+    .then(
+        literal("uuid").then(argument("player", GameProfileArgument).executes(
+            |((), profiles): ((), Vec<(String, Uuid)>), ctx: &mut CommandContext| {
+                if profiles.is_empty() {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        "No player was found".into(),
+                    )));
+                }
+                for (name, id) in profiles {
+                    ctx.sender
+                        .send_message(&TextComponent::from(format!("The UUID of {name} is {id}")));
+                }
+                Ok(())
+            },
+        )),
+    )
+    // /steel scripts reload
+    .then(
+        literal("scripts").then(literal("reload").executes(|(), ctx: &mut CommandContext| {
+            reload(ctx)?;
+            ctx.sender
+                .send_message(&TextComponent::from("Reloaded the scripts"));
+            Ok(())
+        })),
+    )
+}
+
+#[cfg(feature = "scripting")]
+fn reload(ctx: &CommandContext) -> Result<(), CommandError> {
+    reload_scripts(&ctx.server).map_err(|err| {
+        CommandError::CommandFailed(Box::new(
+            format!("Failed to reload the scripts: {err}").into(),
+        ))
+    })
+}
+
+#[cfg(not(feature = "scripting"))]
+fn reload(_ctx: &CommandContext) -> Result<(), CommandError> {
+    Err(CommandError::CommandFailed(Box::new(
+        "The server was built without the scripting feature".into(),
+    )))
+}
+
+/// The target of `/steel log level` that changes the default level.
+const DEFAULT_LOG_TARGET: &str = "*";
+
+/// `<name>`, setting the log level of the target to `level`.
+fn log_level(name: &'static str, level: LevelFilter) -> impl CommandParserExecutor<((), String)> {
+    literal(name).executes(
+        move |((), target): ((), String), ctx: &mut CommandContext| {
+            let default = target == DEFAULT_LOG_TARGET;
+            set_level(if default { "" } else { &target }, level).map_err(|err| {
+                CommandError::CommandFailed(Box::new(
+                    format!("Failed to set the log level: {err}").into(),
+                ))
+            })?;
+            let message = if default {
+                format!("Set the default log level to {name}")
+            } else {
+                format!("Set the log level of {target} to {name}")
+            };
+            ctx.sender.send_message(&TextComponent::from(message));
+            Ok(())
+        },
+    )
+}
+
+/// Stops the profiler, writes its report and sends the sender a summary.
+fn stop_profiler(ctx: &CommandContext) -> Result<(), CommandError> {
+    let Some(report) = TICK_PROFILER.stop() else {
+        return Err(CommandError::CommandFailed(Box::new(
+            "The profiler is not running".into(),
+        )));
+    };
+    for line in report.summary() {
+        ctx.sender.send_message(&TextComponent::from(line));
+    }
+    match report.write_to(Path::new(PROFILE_DIRECTORY)) {
+        Ok(path) => ctx.sender.send_message(&TextComponent::from(format!(
+            "Wrote the full report to {}",
+            path.display()
+        ))),
+        Err(err) => {
+            log::error!("Failed to write the profile report: {err}");
+            ctx.sender.send_message(&TextComponent::from(
+                "Failed to write the full report, see the server log",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// How many chunks `/steel chunks` lists.
+const CROWDED_CHUNKS_SHOWN: usize = 10;
+
+/// Lists the chunks of the sender's world with the most entities and block entities,
+/// along with how often they hit their caps.
+fn list_crowded_chunks(ctx: &CommandContext) {
+    let mut snapshots = ctx.world.chunk_limit_snapshots();
+    snapshots.sort_unstable_by_key(|s| Reverse(s.entities + s.block_entities));
+
+    let limits = &STEEL_CONFIG.chunk_limits;
+    let cap = |max: Option<u32>| max.map_or_else(|| "-".to_owned(), |max| max.to_string());
+    ctx.sender.send_message(&TextComponent::from(format!(
+        "Most crowded of {} loaded chunks (caps: {} entities, {} block entities):",
+        snapshots.len(),
+        cap(limits.max_entities),
+        cap(limits.max_block_entities),
+    )));
+    for s in snapshots.iter().take(CROWDED_CHUNKS_SHOWN) {
+        ctx.sender.send_message(&TextComponent::from(format!(
+            "[{}, {}] {} entities, {} block entities, {} refused, {} items dropped, \
+             {} block entities refused, {} warnings",
+            s.pos.0.x,
+            s.pos.0.y,
+            s.entities,
+            s.block_entities,
+            s.refused_entities,
+            s.dropped_items,
+            s.refused_block_entities,
+            s.warnings,
+        )));
+    }
+}
+
+/// Sends the memory used by the loaded chunks of every world.
+fn report_chunk_memory(ctx: &CommandContext) {
+    for world in ctx.server.worlds.values() {
+        let memory = world.chunk_memory();
+        ctx.sender.send_message(&TextComponent::from(format!(
+            "{}: {:.1} MiB in {} chunks, {} sections ({} only air, {} single block, \
+             {} paletted, {} direct)",
+            world.dimension.key,
+            memory.bytes as f64 / (1024.0 * 1024.0),
+            memory.chunks,
+            memory.sections,
+            memory.empty_sections,
+            memory.single_value_sections,
+            memory.linear_sections,
+            memory.direct_sections,
+        )));
+    }
+}
+
+/// Sends how chunk saving is doing in every world.
+fn report_chunk_saves(ctx: &CommandContext) {
+    for world in ctx.server.worlds.values() {
+        let chunk_map = &world.chunk_map;
+        let metrics = &chunk_map.save_metrics;
+        ctx.sender.send_message(&TextComponent::from(format!(
+            "{}: {} chunks saved, {} failed, {} saving, {} queued for autosave, \
+             {:?} per snapshot, {:?} per write",
+            world.dimension.key,
+            metrics.saved(),
+            metrics.failed(),
+            chunk_map.saves_in_flight(),
+            chunk_map.autosave_queue_len(),
+            metrics.average_snapshot(),
+            metrics.average_write(),
+        )));
+    }
+}
+
+fn to_distance(distance: i32) -> Result<u8, CommandError> {
+    u8::try_from(distance).map_err(|_| CommandError::InvalidConsumption(Some(distance.to_string())))
+}
+
+fn distance_error(error: DistanceError) -> CommandError {
+    let message = match error {
+        DistanceError::ViewDistanceOutOfRange => {
+            format!("View distance must be between {MIN_VIEW_DISTANCE} and {MAX_VIEW_DISTANCE}")
+        }
+        DistanceError::SimulationExceedsView => format!(
+            "Simulation distance must not exceed the view distance ({})",
+            CHUNK_DISTANCES.view_distance()
+        ),
+    };
+    CommandError::CommandFailed(Box::new(message.into()))
+}
@@ -0,0 +1,112 @@
+//! Handler for the "stopsound" command.
+use std::sync::Arc;
+
+use steel_protocol::packets::game::SoundSource;
+use steel_utils::{Identifier, translations};
+use text_components::TextComponent;
+
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::arguments::sound::SoundArgument;
+use crate::command::commands::{
+    CommandExecutor, CommandHandlerBuilder, CommandHandlerDyn, CommandParserExecutor, argument,
+    literal,
+};
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::player::Player;
+
+type TargetArgs = ((), Vec<Arc<Player>>);
+type SoundArgs = (TargetArgs, Identifier);
+
+/// Handler for the "stopsound" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["stopsound"],
+        "Stops sounds playing for players.",
+        "minecraft:command.stopsound",
+    )
+    .then(
+        argument("targets", PlayerArgument::multiple())
+            .executes(StopSoundExecutor { source: None })
+            .then(literal("*").then(
+                argument("sound", SoundArgument).executes(StopSoundExecutor { source: None }),
+            ))
+            .then(source(SoundSource::Master))
+            .then(source(SoundSource::Music))
+            .then(source(SoundSource::Records))
+            .then(source(SoundSource::Weather))
+            .then(source(SoundSource::Blocks))
+            .then(source(SoundSource::Hostile))
+            .then(source(SoundSource::Neutral))
+            .then(source(SoundSource::Players))
+            .then(source(SoundSource::Ambient))
+            .then(source(SoundSource::Voice))
+            .then(source(SoundSource::Ui)),
+    )
+}
+
+/// The branch stopping sounds in `source`, e.g. `/stopsound @a music`.
+fn source(source: SoundSource) -> impl CommandParserExecutor<TargetArgs> {
+    let executor = StopSoundExecutor {
+        source: Some(source),
+    };
+    literal(source.name())
+        .executes(executor)
+        .then(argument("sound", SoundArgument).executes(executor))
+}
+
+#[derive(Clone, Copy)]
+struct StopSoundExecutor {
+    source: Option<SoundSource>,
+}
+
+impl CommandExecutor<TargetArgs> for StopSoundExecutor {
+    fn execute(
+        &self,
+        ((), targets): TargetArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.stop(&targets, None, context);
+        Ok(())
+    }
+}
+
+impl CommandExecutor<SoundArgs> for StopSoundExecutor {
+    fn execute(
+        &self,
+        (((), targets), sound): SoundArgs,
+        context: &mut CommandContext,
+    ) -> Result<(), CommandError> {
+        self.stop(&targets, Some(sound), context);
+        Ok(())
+    }
+}
+
+impl StopSoundExecutor {
+    /// Vanilla: `StopSoundCommand.stopSound()`.
+    fn stop(&self, targets: &[Arc<Player>], sound: Option<Identifier>, context: &CommandContext) {
+        for player in targets {
+            player.stop_sound(self.source, sound.clone());
+        }
+
+        let message: TextComponent = match (self.source, sound) {
+            (Some(source), Some(sound)) => translations::COMMANDS_STOPSOUND_SUCCESS_SOURCE_SOUND
+                .message([
+                    TextComponent::from(sound.to_string()),
+                    TextComponent::from(source.name()),
+                ])
+                .into(),
+            (Some(source), None) => translations::COMMANDS_STOPSOUND_SUCCESS_SOURCE_ANY
+                .message([TextComponent::from(source.name())])
+                .into(),
+            (None, Some(sound)) => translations::COMMANDS_STOPSOUND_SUCCESS_SOURCELESS_SOUND
+                .message([TextComponent::from(sound.to_string())])
+                .into(),
+            (None, None) => translations::COMMANDS_STOPSOUND_SUCCESS_SOURCELESS_ANY
+                .msg()
+                .into(),
+        };
+        context.sender.send_message(&message);
+    }
+}
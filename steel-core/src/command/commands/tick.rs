@@ -9,6 +9,7 @@ use crate::command::commands::{
 };
 use crate::command::context::CommandContext;
 use crate::command::error::CommandError;
+use crate::server::tick_rate_manager::{MAX_TICK_RATE, MIN_TICK_RATE};
 
 /// Handler for the "tick" command.
 #[must_use]
@@ -23,8 +24,11 @@ pub fn command_handler() -> impl CommandHandlerDyn {
     // /tick rate <rate>
     .then(
         literal("rate").then(
-            argument("rate", FloatArgument::bounded(Some(1.0), Some(10000.0)))
-                .executes(TickRateExecutor),
+            argument(
+                "rate",
+                FloatArgument::bounded(Some(MIN_TICK_RATE), Some(MAX_TICK_RATE)),
+            )
+            .executes(TickRateExecutor),
         ),
     )
     // /tick freeze
@@ -36,13 +40,13 @@ pub fn command_handler() -> impl CommandHandlerDyn {
         literal("step")
             .executes(TickStepDefaultExecutor)
             .then(literal("stop").executes(TickStepStopExecutor))
-            .then(argument("time", TimeArgument).executes(TickStepExecutor)),
+            .then(argument("time", TimeArgument::at_least(1)).executes(TickStepExecutor)),
     )
     // /tick sprint <time> | /tick sprint stop
     .then(
         literal("sprint")
             .then(literal("stop").executes(TickSprintStopExecutor))
-            .then(argument("time", TimeArgument).executes(TickSprintExecutor)),
+            .then(argument("time", TimeArgument::at_least(1)).executes(TickSprintExecutor)),
     )
 }
 
@@ -147,8 +151,8 @@ impl CommandExecutor<((), f32)> for TickRateExecutor {
     fn execute(&self, args: ((), f32), context: &mut CommandContext) -> Result<(), CommandError> {
         let ((), rate) = args;
 
-        context.server.broadcast_ticking_state();
         context.server.tick_rate_manager.write().set_tick_rate(rate);
+        context.server.broadcast_ticking_state();
 
         let rate_string = format!("{rate:.1}");
         context.sender.send_message(
@@ -167,19 +171,19 @@ impl CommandExecutor<()> for TickFreezeExecutor {
     fn execute(&self, _args: (), context: &mut CommandContext) -> Result<(), CommandError> {
         let mut tick_manager = context.server.tick_rate_manager.write();
 
-        // Stop sprinting if active (vanilla behavior)
-        if tick_manager.is_sprinting() {
-            tick_manager.stop_sprinting();
-        }
-
-        // Stop stepping if active (vanilla behavior)
-        if tick_manager.is_stepping_forward() {
-            tick_manager.stop_stepping();
-        }
+        // Stop sprinting and stepping if active (vanilla behavior)
+        let sprint_report = tick_manager.stop_sprinting();
+        let stopped_stepping = tick_manager.stop_stepping();
 
         tick_manager.set_frozen(true);
         drop(tick_manager);
 
+        if let Some(report) = sprint_report {
+            context.server.broadcast_sprint_report(&report);
+        }
+        if stopped_stepping {
+            context.server.broadcast_ticking_step();
+        }
         context.server.broadcast_ticking_state();
 
         context
@@ -300,18 +304,13 @@ impl CommandExecutor<()> for TickSprintStopExecutor {
         let report = context.server.tick_rate_manager.write().stop_sprinting();
 
         if let Some(report) = report {
-            // Broadcast state change (restored previous frozen state)
+            // Like a sprint running out, everyone gets the report
+            context.server.broadcast_sprint_report(&report);
             context.server.broadcast_ticking_state();
 
-            // Send sprint report
-            context.sender.send_message(
-                &translations::COMMANDS_TICK_SPRINT_REPORT
-                    .message([
-                        TextComponent::from(format!("{}", report.ticks_per_second)),
-                        TextComponent::from(format!("{:.2}", report.ms_per_tick)),
-                    ])
-                    .into(),
-            );
+            context
+                .sender
+                .send_message(&translations::COMMANDS_TICK_SPRINT_STOP_SUCCESS.msg().into());
             Ok(())
         } else {
             Err(CommandError::CommandFailed(Box::new(
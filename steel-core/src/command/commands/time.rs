@@ -31,9 +31,9 @@ pub fn command_handler() -> impl CommandHandlerDyn {
             .then(literal("midnight").executes(TimeConstSetExecutor::<18000>))
             .then(literal("night").executes(TimeConstSetExecutor::<13000>))
             .then(literal("noon").executes(TimeConstSetExecutor::<6000>))
-            .then(argument("time", TimeArgument).executes(TimeExecutor::Set)),
+            .then(argument("time", TimeArgument::new()).executes(TimeExecutor::Set)),
     )
-    .then(literal("add").then(argument("time", TimeArgument).executes(TimeExecutor::Add)))
+    .then(literal("add").then(argument("time", TimeArgument::new()).executes(TimeExecutor::Add)))
 }
 
 enum TimeQueryExecutor {
@@ -0,0 +1,143 @@
+//! Handler for the "title" command.
+use std::sync::Arc;
+
+use steel_utils::translations;
+use text_components::TextComponent;
+use text_components::translation::Translation;
+
+use crate::command::arguments::player::PlayerArgument;
+use crate::command::arguments::text_component::TextComponentArgument;
+use crate::command::arguments::time::TimeArgument;
+use crate::command::commands::{CommandHandlerBuilder, CommandHandlerDyn, argument, literal};
+use crate::command::context::CommandContext;
+use crate::player::Player;
+use crate::player::title::TitleTimes;
+
+type TargetArgs = ((), Vec<Arc<Player>>);
+type TextArgs = (TargetArgs, TextComponent);
+type TimesArgs = (((TargetArgs, i32), i32), i32);
+
+/// Handler for the "title" command.
+#[must_use]
+pub fn command_handler() -> impl CommandHandlerDyn {
+    CommandHandlerBuilder::new(
+        &["title"],
+        "Shows titles, subtitles and action bar messages to players.",
+        "minecraft:command.title",
+    )
+    .then(
+        argument("targets", PlayerArgument::multiple())
+            .then(literal("clear").executes(
+                |((), targets): TargetArgs, context: &mut CommandContext| {
+                    for player in &targets {
+                        player.clear_title();
+                    }
+                    send_success(
+                        &targets,
+                        &translations::COMMANDS_TITLE_CLEARED_SINGLE,
+                        &translations::COMMANDS_TITLE_CLEARED_MULTIPLE,
+                        context,
+                    );
+                    Ok(())
+                },
+            ))
+            .then(literal("reset").executes(
+                |((), targets): TargetArgs, context: &mut CommandContext| {
+                    for player in &targets {
+                        player.reset_title();
+                    }
+                    send_success(
+                        &targets,
+                        &translations::COMMANDS_TITLE_RESET_SINGLE,
+                        &translations::COMMANDS_TITLE_RESET_MULTIPLE,
+                        context,
+                    );
+                    Ok(())
+                },
+            ))
+            .then(
+                literal("title").then(argument("title", TextComponentArgument).executes(
+                    |(((), targets), title): TextArgs, context: &mut CommandContext| {
+                        for player in &targets {
+                            player.send_title(&title, None, None);
+                        }
+                        send_success(
+                            &targets,
+                            &translations::COMMANDS_TITLE_SHOW_TITLE_SINGLE,
+                            &translations::COMMANDS_TITLE_SHOW_TITLE_MULTIPLE,
+                            context,
+                        );
+                        Ok(())
+                    },
+                )),
+            )
+            .then(
+                literal("subtitle").then(argument("title", TextComponentArgument).executes(
+                    |(((), targets), subtitle): TextArgs, context: &mut CommandContext| {
+                        for player in &targets {
+                            player.send_subtitle(&subtitle);
+                        }
+                        send_success(
+                            &targets,
+                            &translations::COMMANDS_TITLE_SHOW_SUBTITLE_SINGLE,
+                            &translations::COMMANDS_TITLE_SHOW_SUBTITLE_MULTIPLE,
+                            context,
+                        );
+                        Ok(())
+                    },
+                )),
+            )
+            .then(
+                literal("actionbar").then(argument("title", TextComponentArgument).executes(
+                    |(((), targets), text): TextArgs, context: &mut CommandContext| {
+                        for player in &targets {
+                            player.send_action_bar(&text);
+                        }
+                        send_success(
+                            &targets,
+                            &translations::COMMANDS_TITLE_SHOW_ACTIONBAR_SINGLE,
+                            &translations::COMMANDS_TITLE_SHOW_ACTIONBAR_MULTIPLE,
+                            context,
+                        );
+                        Ok(())
+                    },
+                )),
+            )
+            .then(
+                literal("times").then(argument("fadeIn", TimeArgument::new()).then(
+                    argument("stay", TimeArgument::new()).then(
+                        argument("fadeOut", TimeArgument::new()).executes(
+                            |(((((), targets), fade_in), stay), fade_out): TimesArgs,
+                             context: &mut CommandContext| {
+                                let times = TitleTimes::new(fade_in, stay, fade_out);
+                                for player in &targets {
+                                    player.send_title_times(times);
+                                }
+                                send_success(
+                                    &targets,
+                                    &translations::COMMANDS_TITLE_TIMES_SINGLE,
+                                    &translations::COMMANDS_TITLE_TIMES_MULTIPLE,
+                                    context,
+                                );
+                                Ok(())
+                            },
+                        ),
+                    ),
+                )),
+            ),
+    )
+}
+
+/// Tells the sender which players were affected, by name for a single one.
+fn send_success(
+    targets: &[Arc<Player>],
+    single: &Translation<1>,
+    multiple: &Translation<1>,
+    context: &CommandContext,
+) {
+    let message = match targets {
+        [player] => single.message([player.display_name()]),
+        _ => multiple.message([TextComponent::from(targets.len().to_string())]),
+    };
+    context.sender.send_message(&message.into());
+}
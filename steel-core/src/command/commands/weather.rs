@@ -17,17 +17,19 @@ pub fn command_handler() -> impl CommandHandlerDyn {
     )
     .then(
         literal("rain")
-            .then(argument("duration", TimeArgument).executes(WeatherCommandExecutor::Rain))
+            .then(argument("duration", TimeArgument::new()).executes(WeatherCommandExecutor::Rain))
             .executes(WeatherCommandExecutor::Rain),
     )
     .then(
         literal("thunder")
-            .then(argument("duration", TimeArgument).executes(WeatherCommandExecutor::Thunder))
+            .then(
+                argument("duration", TimeArgument::new()).executes(WeatherCommandExecutor::Thunder),
+            )
             .executes(WeatherCommandExecutor::Thunder),
     )
     .then(
         literal("clear")
-            .then(argument("duration", TimeArgument).executes(WeatherCommandExecutor::Clear))
+            .then(argument("duration", TimeArgument::new()).executes(WeatherCommandExecutor::Clear))
             .executes(WeatherCommandExecutor::Clear),
     )
 }
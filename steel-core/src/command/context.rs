@@ -2,9 +2,11 @@
 use std::sync::Arc;
 
 use glam::DVec3;
+use rustc_hash::FxHashMap;
 
 use crate::command::sender::CommandSender;
 use crate::player::Player;
+use crate::player::message_chain::PlayerChatMessage;
 use crate::server::Server;
 use crate::world::World;
 
@@ -25,6 +27,8 @@ pub struct CommandContext {
     pub rotation: Option<(f32, f32)>,
     /// The anchor of the command.
     pub anchor: EntityAnchor,
+    /// The signed message arguments of the command.
+    pub signing: CommandSigning,
 }
 
 /// The position anchor to use for an entity.
@@ -37,6 +41,38 @@ pub enum EntityAnchor {
     Eyes,
 }
 
+/// How the message arguments of a command were signed.
+///
+/// Vanilla: `CommandSigningContext`.
+#[derive(Clone, Default)]
+pub enum CommandSigning {
+    /// Nothing is signed, as in commands from the console.
+    #[default]
+    Anonymous,
+    /// The command only gets parsed, collecting its message arguments as
+    /// (name, text) pairs so their signatures can be checked before it runs.
+    Collecting(Vec<(&'static str, String)>),
+    /// The verified message arguments by name.
+    Signed(FxHashMap<&'static str, PlayerChatMessage>),
+}
+
+impl CommandSigning {
+    /// Gets the verified message argument `name`.
+    #[must_use]
+    pub fn argument(&self, name: &str) -> Option<&PlayerChatMessage> {
+        match self {
+            Self::Signed(arguments) => arguments.get(name),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the command is only parsed, not run.
+    #[must_use]
+    pub const fn is_collecting(&self) -> bool {
+        matches!(self, Self::Collecting(_))
+    }
+}
+
 impl CommandContext {
     /// Creates a new command context.
     #[must_use]
@@ -67,6 +103,7 @@ impl CommandContext {
             position,
             rotation: None,
             anchor: EntityAnchor::default(),
+            signing: CommandSigning::default(),
         }
     }
 }
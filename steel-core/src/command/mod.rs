@@ -11,7 +11,7 @@ use steel_protocol::packets::game::{CCommandSuggestions, CCommands, CommandNode,
 use text_components::{Modifier, TextComponent, format::Color};
 
 use crate::command::commands::CommandHandlerDyn;
-use crate::command::context::CommandContext;
+use crate::command::context::{CommandContext, CommandSigning};
 use crate::command::error::CommandError;
 use crate::command::sender::CommandSender;
 use crate::player::Player;
@@ -30,6 +30,7 @@ impl CommandDispatcher {
     pub fn new() -> Self {
         let dispatcher = CommandDispatcher::new_empty();
         dispatcher.register(commands::clear::command_handler());
+        dispatcher.register(commands::data::command_handler());
         dispatcher.register(commands::enchant::command_handler());
         dispatcher.register(commands::execute::command_handler());
         dispatcher.register(commands::fly::command_handler());
@@ -37,12 +38,26 @@ impl CommandDispatcher {
         dispatcher.register(commands::gamerule::command_handler());
         dispatcher.register(commands::kill::command_handler());
         dispatcher.register(commands::give::command_handler());
+        dispatcher.register(commands::locate::command_handler());
+        dispatcher.register(commands::me::command_handler());
+        dispatcher.register(commands::msg::command_handler());
+        dispatcher.register(commands::place::command_handler());
+        dispatcher.register(commands::playsound::command_handler());
+        dispatcher.register(commands::random::command_handler());
+        dispatcher.register(commands::ride::command_handler());
         dispatcher.register(commands::seed::command_handler());
+        dispatcher.register(commands::setworldspawn::command_handler());
+        dispatcher.register(commands::spawnpoint::command_handler());
+        dispatcher.register(commands::spectate::command_handler());
+        dispatcher.register(commands::spreadplayers::command_handler());
+        dispatcher.register(commands::steel::command_handler());
         dispatcher.register(commands::stop::command_handler());
+        dispatcher.register(commands::stopsound::command_handler());
         dispatcher.register(commands::summon::command_handler());
         dispatcher.register(commands::tellraw::command_handler());
         dispatcher.register(commands::tick::command_handler());
         dispatcher.register(commands::time::command_handler());
+        dispatcher.register(commands::title::command_handler());
         dispatcher.register(commands::tp::command_handler());
         dispatcher.register(commands::weather::command_handler());
         dispatcher.register(commands::xp::command_handler());
@@ -59,7 +74,19 @@ impl CommandDispatcher {
 
     /// Executes a command.
     pub fn handle_command(&self, sender: CommandSender, command: String, server: &Arc<Server>) {
+        self.handle_signed_command(sender, command, server, CommandSigning::Anonymous);
+    }
+
+    /// Executes a command with the signed message arguments in `signing`.
+    pub fn handle_signed_command(
+        &self,
+        sender: CommandSender,
+        command: String,
+        server: &Arc<Server>,
+        signing: CommandSigning,
+    ) {
         let mut context = CommandContext::new(sender.clone(), server.clone());
+        context.signing = signing;
 
         if let Err(error) = Self::split_command(&command)
             .and_then(|(command, args)| self.execute(command, &args, &mut context, server))
@@ -91,6 +118,28 @@ impl CommandDispatcher {
         }
     }
 
+    /// Parses a command without running it, returning its message arguments as
+    /// (name, text) pairs. These are the arguments clients sign.
+    ///
+    /// Vanilla: `SignableCommand.of()`.
+    #[must_use]
+    pub fn signable_arguments(
+        &self,
+        sender: CommandSender,
+        command: &str,
+        server: &Arc<Server>,
+    ) -> Vec<(&'static str, String)> {
+        let mut context = CommandContext::new(sender, server.clone());
+        context.signing = CommandSigning::Collecting(Vec::new());
+        // Commands that fail to parse have no arguments to sign
+        let _ = Self::split_command(command)
+            .and_then(|(command, args)| self.execute(command, &args, &mut context, server));
+        match context.signing {
+            CommandSigning::Collecting(arguments) => arguments,
+            _ => Vec::new(),
+        }
+    }
+
     /// Executes a command.
     fn execute(
         &self,
@@ -1,8 +1,12 @@
 //! Module defining the sender of a command.
 use std::{fmt, sync::Arc};
+use steel_protocol::packets::game::ChatTypeBound;
+use steel_registry::RegistryEntry;
+use steel_registry::chat_type::ChatTypeRef;
 use text_components::TextComponent;
 
 use crate::player::Player;
+use crate::server::languages::LANGUAGES;
 
 /// The sender of a command.
 #[derive(Clone)]
@@ -29,11 +33,30 @@ impl CommandSender {
     pub fn send_message(&self, text: &TextComponent) {
         match self {
             Self::Player(player) => player.send_message(text),
-            Self::Console => log::info!("{:p}", *text),
+            Self::Console => log::info!("{:p}", LANGUAGES.localize_for_console(text)),
             // TODO: Implement Rcon message sending
             Self::Rcon => unimplemented!(),
         }
     }
+
+    /// Binds a chat type to this sender as the sender of a message.
+    ///
+    /// Vanilla: `ChatType.bind()`.
+    #[must_use]
+    pub fn bind_chat_type(
+        &self,
+        chat_type: ChatTypeRef,
+        target_name: Option<TextComponent>,
+    ) -> ChatTypeBound {
+        match self {
+            Self::Player(player) => player.bind_chat_type(chat_type, target_name),
+            Self::Console | Self::Rcon => ChatTypeBound {
+                registry_id: chat_type.id() as i32,
+                sender_name: TextComponent::plain(self.to_string()),
+                target_name,
+            },
+        }
+    }
 }
 
 impl fmt::Display for CommandSender {
@@ -3,15 +3,20 @@
 //! The `ServerConfig` struct is defined here, but loading is handled by the `steel` crate.
 //! Steel-core accesses config via `STEEL_CONFIG` after steel initializes it.
 
+use std::collections::BTreeMap;
 use std::ops::Deref;
+use std::str::FromStr;
 use std::sync::OnceLock;
 
 use serde::Deserialize;
 use steel_protocol::packet_traits::CompressionInfo;
 use steel_protocol::packets::config::{CServerLinks, Link, ServerLinksType};
+use steel_registry::entity_types::MobCategory;
 use steel_utils::codec::Or;
 use text_components::TextComponent;
 
+use crate::player::outbound_queue::QueueLimits;
+
 /// Reference to the server configuration.
 ///
 /// This is initialized by the `steel` crate during server startup.
@@ -20,6 +25,11 @@ pub struct ServerConfigRef;
 
 static CONFIG: OnceLock<&'static ServerConfig> = OnceLock::new();
 
+/// Loads the configuration from disk again, set by the `steel` crate.
+type Reloader = fn() -> Result<ServerConfig, String>;
+
+static RELOADER: OnceLock<Reloader> = OnceLock::new();
+
 impl ServerConfigRef {
     /// Initializes the configuration reference.
     ///
@@ -30,6 +40,25 @@ impl ServerConfigRef {
             .set(config)
             .expect("Server config already initialized");
     }
+
+    /// Sets how the configuration is loaded from disk again.
+    pub fn set_reloader(reloader: Reloader) {
+        if RELOADER.set(reloader).is_err() {
+            log::warn!("Server config reloader was already set");
+        }
+    }
+
+    /// Loads the configuration from disk again, without changing the one
+    /// `STEEL_CONFIG` points to. See `Server::reload_config` for applying it.
+    ///
+    /// # Errors
+    /// Returns a description of why the configuration couldn't be loaded.
+    pub fn reload(&self) -> Result<ServerConfig, String> {
+        let reloader = RELOADER
+            .get()
+            .ok_or_else(|| "The config can't be reloaded".to_owned())?;
+        reloader()
+    }
 }
 
 impl Deref for ServerConfigRef {
@@ -42,6 +71,27 @@ impl Deref for ServerConfigRef {
     }
 }
 
+/// Limits on neighbor and shape update cascades.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NeighborUpdateConfig {
+    /// How many updates one cascade may queue before the rest are skipped, no limit
+    /// if unset. Vanilla: `max-chained-neighbor-updates`.
+    pub max_chained: Option<u32>,
+    /// How many updates run per tick, no limit if unset. A cascade that runs out
+    /// of budget continues next tick.
+    pub tick_budget: Option<u32>,
+}
+
+impl Default for NeighborUpdateConfig {
+    fn default() -> Self {
+        Self {
+            max_chained: Some(1_000_000),
+            tick_budget: None,
+        }
+    }
+}
+
 /// The server configuration.
 ///
 /// Access via `STEEL_CONFIG` static after initialization by the steel crate.
@@ -133,6 +183,548 @@ pub enum WorldStorageConfig {
     RamOnly,
 }
 
+/// Configuration for player data storage.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerDataStorageConfig {
+    /// One gzip-compressed NBT file per player, like vanilla.
+    Files {
+        /// Path to the players directory (e.g., "players").
+        path: String,
+    },
+    /// A single SQLite database holding all players.
+    Sqlite {
+        /// Path to the database file (e.g., "players.db").
+        path: String,
+    },
+}
+
+impl Default for PlayerDataStorageConfig {
+    fn default() -> Self {
+        Self::Files {
+            path: "players".to_owned(),
+        }
+    }
+}
+
+impl FromStr for PlayerDataStorageConfig {
+    type Err = String;
+
+    /// Parses `files:<path>` or `sqlite:<path>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, path) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Expected <files|sqlite>:<path>, got '{s}'"))?;
+        let path = path.to_owned();
+        match kind {
+            "files" => Ok(Self::Files { path }),
+            "sqlite" => Ok(Self::Sqlite { path }),
+            _ => Err(format!("Unknown player data backend '{kind}'")),
+        }
+    }
+}
+
+/// Configuration for the server's cryptography.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CryptoConfig {
+    /// Where to cache the server's RSA key pair between restarts.
+    /// The key pair is regenerated on every start if unset.
+    pub key_cache_path: Option<String>,
+    /// How long a cached key pair is reused, in seconds.
+    pub key_cache_max_age: u64,
+    /// How many chat signatures may be verified at the same time.
+    pub max_concurrent_verifications: usize,
+}
+
+impl Default for CryptoConfig {
+    fn default() -> Self {
+        Self {
+            key_cache_path: None,
+            key_cache_max_age: 60 * 60,
+            max_concurrent_verifications: 4,
+        }
+    }
+}
+
+/// Configuration for the packets queued for each client.
+///
+/// A client that reads slower than the server sends builds up a queue. Past
+/// `low_priority_bytes` cosmetic packets like sounds are dropped, past
+/// `max_bytes` the client is disconnected.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct OutboundQueueConfig {
+    /// How many queued bytes make low priority packets get dropped.
+    pub low_priority_bytes: usize,
+    /// How many queued bytes get the client disconnected.
+    pub max_bytes: usize,
+}
+
+impl Default for OutboundQueueConfig {
+    fn default() -> Self {
+        Self {
+            low_priority_bytes: 1024 * 1024,
+            max_bytes: 32 * 1024 * 1024,
+        }
+    }
+}
+
+impl OutboundQueueConfig {
+    /// The limits for a new connection's queue.
+    #[must_use]
+    pub const fn limits(&self) -> QueueLimits {
+        QueueLimits {
+            low_priority_bytes: self.low_priority_bytes,
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+/// Configuration for kicking players who send chat messages or commands too quickly.
+///
+/// Every message adds its cost to a counter that drops by one each tick; the
+/// player is kicked once it reaches the threshold. The defaults match vanilla.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ChatSpamConfig {
+    /// Whether spamming players are kicked at all.
+    pub enabled: bool,
+    /// How much each chat message adds to the counter.
+    pub message_cost: u32,
+    /// How much each command adds to the counter.
+    pub command_cost: u32,
+    /// The counter value at which the player is kicked.
+    pub threshold: u32,
+}
+
+impl Default for ChatSpamConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            message_cost: 20,
+            command_cost: 20,
+            threshold: 200,
+        }
+    }
+}
+
+/// Configuration for the checks that reject impossible player movement.
+///
+/// Rejected moves are rolled back and logged. The defaults match vanilla.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MovementCheckConfig {
+    /// How far a player may move per packet beyond their velocity, squared.
+    pub max_speed: f64,
+    /// Same as `max_speed`, while flying with an elytra.
+    pub max_elytra_speed: f64,
+    /// Same as `max_speed`, for the vehicle a player steers.
+    pub max_vehicle_speed: f64,
+    /// How far the client's position may end up from where the server puts
+    /// the player, squared.
+    pub max_error: f64,
+    /// Whether players may float in the air without being allowed to fly.
+    ///
+    /// Vanilla: `allow-flight`.
+    pub allow_flight: bool,
+    /// How many ticks a player may float before being kicked, at normal gravity.
+    pub max_floating_ticks: u32,
+}
+
+impl Default for MovementCheckConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: 100.0,
+            max_elytra_speed: 300.0,
+            max_vehicle_speed: 100.0,
+            max_error: 0.0625,
+            allow_flight: false,
+            max_floating_ticks: 80,
+        }
+    }
+}
+
+/// Configuration for the built-in chat filter.
+///
+/// Filtered messages are hidden from players with text filtering enabled, or
+/// from everyone with `filter_everyone`. Senders always see their own message.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ChatFilterConfig {
+    /// Words hidden in chat messages, matched ignoring case.
+    pub blocked_words: Vec<String>,
+    /// Whether messages with a blocked word are hidden entirely instead of
+    /// having the word replaced with `#`.
+    pub block_messages: bool,
+    /// Whether messages are filtered for every player, not just those with text
+    /// filtering enabled.
+    pub filter_everyone: bool,
+}
+
+impl Default for ChatFilterConfig {
+    fn default() -> Self {
+        Self {
+            blocked_words: Vec::new(),
+            block_messages: false,
+            filter_everyone: true,
+        }
+    }
+}
+
+/// Configuration for the locator bar.
+///
+/// The `locator_bar` game rule can still turn it off per world.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WaypointConfig {
+    /// Whether players see each other (and custom waypoints) on the locator bar.
+    pub enabled: bool,
+}
+
+impl Default for WaypointConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// What happens to a new entity in a chunk that reached its entity cap.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityOverflowPolicy {
+    /// Removes the chunk's oldest item entities to make room.
+    /// The spawn is refused if there are not enough items to remove.
+    DropOldestItems,
+    /// Refuses the spawn.
+    RefuseNew,
+    /// Allows the spawn and logs a warning.
+    Warn,
+}
+
+/// What happens to a new block entity in a chunk that reached its block entity cap.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockEntityOverflowPolicy {
+    /// Places the block without its block entity.
+    RefuseNew,
+    /// Allows the block entity and logs a warning.
+    Warn,
+}
+
+/// Per-chunk caps that keep entity and block entity buildup from tanking the tick.
+///
+/// Only new spawns and placed blocks are capped. Players are never counted, and
+/// entities loaded from disk or walking in from a neighbouring chunk are let in.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ChunkLimitsConfig {
+    /// The maximum number of entities in a chunk, no limit if unset.
+    pub max_entities: Option<u32>,
+    /// What happens to a spawn in a full chunk.
+    pub entity_overflow: EntityOverflowPolicy,
+    /// The maximum number of block entities in a chunk, no limit if unset.
+    pub max_block_entities: Option<u32>,
+    /// What happens to a new block entity in a full chunk.
+    pub block_entity_overflow: BlockEntityOverflowPolicy,
+}
+
+impl Default for ChunkLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_entities: None,
+            entity_overflow: EntityOverflowPolicy::DropOldestItems,
+            max_block_entities: None,
+            block_entity_overflow: BlockEntityOverflowPolicy::RefuseNew,
+        }
+    }
+}
+
+/// Configuration for sandboxed WASM plugins.
+///
+/// Only used when the server is built with the `wasm-plugins` feature.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WasmPluginConfig {
+    /// The directory `.wasm` plugins are loaded from.
+    pub directory: String,
+    /// How much fuel (roughly one per WASM instruction) a plugin may use per call
+    /// into it before it is stopped and disabled.
+    pub fuel_per_call: u64,
+    /// The maximum size of a plugin's linear memory, in bytes.
+    pub max_memory: usize,
+}
+
+impl Default for WasmPluginConfig {
+    fn default() -> Self {
+        Self {
+            directory: "plugins".to_owned(),
+            fuel_per_call: 10_000_000,
+            max_memory: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Configuration for the server list entry, beyond the MOTD and favicon.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct StatusConfig {
+    /// Whether hovering the player count lists online players.
+    ///
+    /// Vanilla: `hide-online-players`, inverted.
+    pub show_players: bool,
+    /// How many online players are listed at most.
+    pub sample_size: usize,
+    /// Lines listed instead of the online players, if not empty.
+    pub custom_sample: Vec<String>,
+    /// Added to the online player count shown.
+    pub online_offset: i32,
+    /// If set, the max player count shown is this much above the online count
+    /// instead of `max_players`.
+    pub max_above_online: Option<u32>,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            show_players: true,
+            sample_size: 12,
+            custom_sample: Vec::new(),
+            online_offset: 0,
+            max_above_online: None,
+        }
+    }
+}
+
+/// Configuration for the requests to Mojang's authentication servers.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AuthenticationConfig {
+    /// The base URL of the session server, if not Mojang's, e.g. for an
+    /// authentication proxy.
+    pub session_server: Option<String>,
+    /// How many seconds connecting to the servers may take.
+    pub connect_timeout: u64,
+    /// How many seconds a whole request may take.
+    pub request_timeout: u64,
+    /// How many times a failed request is tried.
+    pub max_attempts: u32,
+    /// After how many failed logins in a row logins fail right away for
+    /// `breaker_cooldown` seconds, as the servers are likely down.
+    pub breaker_threshold: u32,
+    /// How many seconds logins fail right away after `breaker_threshold` failures.
+    pub breaker_cooldown: u64,
+}
+
+impl Default for AuthenticationConfig {
+    fn default() -> Self {
+        Self {
+            session_server: None,
+            connect_timeout: 5,
+            request_timeout: 10,
+            max_attempts: 3,
+            breaker_threshold: 5,
+            breaker_cooldown: 30,
+        }
+    }
+}
+
+/// Configuration for showing skins to players on a server in offline mode.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct OfflineSkinsConfig {
+    /// Whether offline players get the skin of the premium account with their
+    /// name, looked up with the Mojang API.
+    pub enabled: bool,
+    /// How many accounts may be looked up per minute. Mojang rate limits the API
+    /// per IP address.
+    pub lookups_per_minute: u32,
+}
+
+impl Default for OfflineSkinsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lookups_per_minute: 30,
+        }
+    }
+}
+
+/// Configuration for recording the packets players send.
+///
+/// Recordings can be replayed against a test server to reproduce desyncs and
+/// false positives of the movement checks.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PacketRecordingConfig {
+    /// Whether packets are recorded.
+    pub enabled: bool,
+    /// The directory recordings are written to, one file per connection.
+    pub directory: String,
+    /// The names of the players to record, or everyone if empty.
+    pub players: Vec<String>,
+}
+
+impl Default for PacketRecordingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "recordings".to_owned(),
+            players: Vec::new(),
+        }
+    }
+}
+
+/// Who translates the translatable messages the server sends to players.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationMode {
+    /// Messages stay translatable and the client translates them. Keys the
+    /// client doesn't know, e.g. ones added by plugins, are still translated by
+    /// the server.
+    Client,
+    /// The server translates every message into the player's language.
+    Server,
+}
+
+/// Configuration for translating messages on the server.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct TranslationConfig {
+    /// Who translates messages sent to players.
+    pub players: TranslationMode,
+    /// The language messages to the console are translated into.
+    pub console_language: String,
+    /// The directory language files (e.g. `de_de.json`) are loaded from, in
+    /// addition to the bundled `en_us` one.
+    pub directory: String,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            players: TranslationMode::Client,
+            console_language: "en_us".to_owned(),
+            directory: "lang".to_owned(),
+        }
+    }
+}
+
+/// Settings for a single world, overriding the server-wide ones.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WorldSettingsConfig {
+    /// The simulation distance of the world, or the server's if unset.
+    pub simulation_distance: Option<u8>,
+}
+
+/// Activation ranges for entity ticking, in blocks per mob category.
+///
+/// Entities further than their category's range from every player only tick
+/// once every `wake_up_interval` ticks, like Spigot's entity activation range.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ActivationRangeConfig {
+    /// Whether entities far from players tick less often. Off by default, since
+    /// it changes how farms behave.
+    pub enabled: bool,
+    /// How often inactive entities still tick, in ticks.
+    pub wake_up_interval: u32,
+    /// The range of hostile mobs.
+    pub monster: u32,
+    /// The range of passive land mobs.
+    pub creature: u32,
+    /// The range of bats.
+    pub ambient: u32,
+    /// The range of axolotls.
+    pub axolotls: u32,
+    /// The range of glow squids.
+    pub underground_water_creature: u32,
+    /// The range of squids and dolphins.
+    pub water_creature: u32,
+    /// The range of fish.
+    pub water_ambient: u32,
+    /// The range of everything else, like items, minecarts and projectiles.
+    pub misc: u32,
+}
+
+impl ActivationRangeConfig {
+    /// Returns the range of entities in `category`, in blocks.
+    #[must_use]
+    pub const fn range(&self, category: MobCategory) -> u32 {
+        match category {
+            MobCategory::Monster => self.monster,
+            MobCategory::Creature => self.creature,
+            MobCategory::Ambient => self.ambient,
+            MobCategory::Axolotls => self.axolotls,
+            MobCategory::UndergroundWaterCreature => self.underground_water_creature,
+            MobCategory::WaterCreature => self.water_creature,
+            MobCategory::WaterAmbient => self.water_ambient,
+            MobCategory::Misc => self.misc,
+        }
+    }
+}
+
+impl Default for ActivationRangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            wake_up_interval: 20,
+            monster: 32,
+            creature: 32,
+            ambient: 16,
+            axolotls: 16,
+            underground_water_creature: 16,
+            water_creature: 16,
+            water_ambient: 16,
+            misc: 16,
+        }
+    }
+}
+
+/// Configuration for saving chunks while the server runs.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AutosaveConfig {
+    /// How often every changed chunk is saved, in ticks. 0 turns autosaving
+    /// off, leaving only the saves of unloading chunks and at shutdown.
+    pub interval: u64,
+    /// How many chunk saves of a world may compress and write at once. Chunks
+    /// past this wait for a later tick, spreading an autosave out.
+    pub max_in_flight: usize,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            interval: 6000,
+            max_in_flight: 32,
+        }
+    }
+}
+
+/// Configuration for the watchdog that detects stalled ticks.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    /// Whether the watchdog runs.
+    pub enabled: bool,
+    /// How many seconds a single tick may take before it counts as stalled.
+    pub stall_threshold: u64,
+    /// Whether a stalled server shuts down, saving what it can, and exits with a
+    /// failure code so a process supervisor restarts it.
+    pub shutdown_on_stall: bool,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stall_threshold: 60,
+            shutdown_on_stall: false,
+        }
+    }
+}
+
 /// The server configuration.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
@@ -150,11 +742,14 @@ pub struct ServerConfig {
     pub view_distance: u8,
     /// The simulation distance of the server.
     pub simulation_distance: u8,
+    /// Settings for single worlds, keyed by dimension name (e.g. `the_nether`).
+    #[serde(default)]
+    pub worlds: BTreeMap<String, WorldSettingsConfig>,
     /// Whether the server is in online mode.
     pub online_mode: bool,
     /// Whether the server should use encryption.
     pub encryption: bool,
-    /// The message of the day.
+    /// The message of the day, as text with `§` formatting codes or as JSON text.
     pub motd: String,
     /// Whether to use a favicon.
     pub use_favicon: bool,
@@ -162,10 +757,64 @@ pub struct ServerConfig {
     pub favicon: String,
     /// Whether to enforce secure chat.
     pub enforce_secure_chat: bool,
+    /// Key caching and signature verification settings.
+    #[serde(default)]
+    pub crypto: CryptoConfig,
+    /// Limits on the packets queued for each client.
+    #[serde(default)]
+    pub outbound_queue: OutboundQueueConfig,
+    /// Chat and command spam limits.
+    #[serde(default)]
+    pub chat_spam: ChatSpamConfig,
+    /// Words filtered out of chat messages.
+    #[serde(default)]
+    pub chat_filter: ChatFilterConfig,
+    /// Speed, noclip and flight checks on player movement.
+    #[serde(default)]
+    pub movement_checks: MovementCheckConfig,
+    /// Locator bar settings.
+    #[serde(default)]
+    pub waypoints: WaypointConfig,
+    /// Per-chunk entity and block entity caps.
+    #[serde(default)]
+    pub chunk_limits: ChunkLimitsConfig,
+    /// Limits on neighbor and shape update cascades.
+    #[serde(default)]
+    pub neighbor_updates: NeighborUpdateConfig,
+    /// Reduced ticking of entities far from players.
+    #[serde(default)]
+    pub activation_range: ActivationRangeConfig,
+    /// Saving of chunks while the server runs.
+    #[serde(default)]
+    pub autosave: AutosaveConfig,
+    /// Limits for sandboxed WASM plugins.
+    #[serde(default)]
+    pub wasm_plugins: WasmPluginConfig,
+    /// Detection of stalled ticks.
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+    /// Recording of the packets players send, for debugging.
+    #[serde(default)]
+    pub packet_recording: PacketRecordingConfig,
+    /// The requests to Mojang's authentication servers.
+    #[serde(default)]
+    pub authentication: AuthenticationConfig,
+    /// Skins for players on a server in offline mode.
+    #[serde(default)]
+    pub offline_skins: OfflineSkinsConfig,
+    /// The player count and list shown in the server list.
+    #[serde(default)]
+    pub status: StatusConfig,
+    /// Translation of messages into the languages of players and the console.
+    #[serde(default)]
+    pub translation: TranslationConfig,
     /// Defines which generator should be used for the world.
     pub world_generator: WorldGeneratorTypes,
     /// Defines which storage format and storage option should be used for the world
     pub world_storage_config: WorldStorageConfig,
+    /// Defines where player data is stored
+    #[serde(default)]
+    pub player_data_storage: PlayerDataStorageConfig,
     /// The compression settings for the server.
     pub compression: Option<CompressionInfo>,
     /// All settings and configurations for server links
@@ -0,0 +1,108 @@
+//! Activation ranges: entities far from every player tick less often.
+//!
+//! Like Spigot's entity activation range, an entity further than its mob
+//! category's range from all players is inactive and only ticks once every
+//! `wake_up_interval` ticks. Some entities are always active: players, the
+//! ender dragon, entities with passengers and entities in the air, so nothing
+//! freezes mid-fall.
+
+use glam::DVec3;
+use steel_registry::vanilla_entities;
+use steel_utils::types::GameType;
+
+use super::Entity;
+use crate::config::{ActivationRangeConfig, STEEL_CONFIG};
+use crate::world::World;
+
+/// Decides which entities tick during one tick of a world, and counts them.
+pub struct EntityActivation {
+    /// The ranges, or `None` if every entity ticks.
+    config: Option<&'static ActivationRangeConfig>,
+    /// Where the players that keep entities active are.
+    players: Vec<DVec3>,
+    tick_count: i32,
+    /// Entities that ticked.
+    pub active: usize,
+    /// Entities skipped because no player was in range.
+    pub inactive: usize,
+}
+
+impl EntityActivation {
+    /// Collects the players of `world` for this tick.
+    #[must_use]
+    pub fn new(world: &World, tick_count: i32) -> Self {
+        let config = &STEEL_CONFIG.activation_range;
+        if !config.enabled {
+            return Self::everything_active(tick_count);
+        }
+        let mut players = Vec::new();
+        world.players.iter_players(|_, player| {
+            if player.game_mode.load() != GameType::Spectator {
+                players.push(player.position());
+            }
+            true
+        });
+        Self {
+            config: Some(config),
+            players,
+            tick_count,
+            active: 0,
+            inactive: 0,
+        }
+    }
+
+    /// Lets every entity tick, for when activation ranges are off.
+    #[must_use]
+    pub const fn everything_active(tick_count: i32) -> Self {
+        Self {
+            config: None,
+            players: Vec::new(),
+            tick_count,
+            active: 0,
+            inactive: 0,
+        }
+    }
+
+    /// Returns whether `entity` ticks this tick, and counts it.
+    pub fn should_tick(&mut self, entity: &dyn Entity) -> bool {
+        let active = self.config.is_none_or(|config| {
+            Self::is_immune(entity)
+                || self.player_in_range(entity, config)
+                || self.wakes_up(entity, config)
+        });
+        if active {
+            self.active += 1;
+        } else {
+            self.inactive += 1;
+        }
+        active
+    }
+
+    /// Entities that would break or look wrong when ticking less often.
+    fn is_immune(entity: &dyn Entity) -> bool {
+        let key = &entity.entity_type().key;
+        (!entity.on_ground() && entity.get_gravity() > 0.0)
+            || !entity.passengers().is_empty()
+            || *key == vanilla_entities::PLAYER.key
+            || *key == vanilla_entities::ENDER_DRAGON.key
+    }
+
+    /// Whether an inactive entity still ticks this tick. The wake ups are
+    /// spread over the interval by entity ID.
+    fn wakes_up(&self, entity: &dyn Entity, config: &ActivationRangeConfig) -> bool {
+        let interval = config.wake_up_interval.max(1) as i32;
+        self.tick_count
+            .wrapping_add(entity.id())
+            .rem_euclid(interval)
+            == 0
+    }
+
+    /// Whether a player is within the entity's range horizontally.
+    fn player_in_range(&self, entity: &dyn Entity, config: &ActivationRangeConfig) -> bool {
+        let range = f64::from(config.range(entity.entity_type().mob_category));
+        let pos = entity.position();
+        self.players
+            .iter()
+            .any(|player| (player.x - pos.x).abs() <= range && (player.z - pos.z).abs() <= range)
+    }
+}
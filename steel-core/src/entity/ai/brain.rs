@@ -0,0 +1,231 @@
+//! Memories and activity state of mobs that use a brain.
+
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_registry::entity_data::GlobalPos;
+use steel_utils::{BlockPos, Identifier};
+
+use super::navigation::WalkTarget;
+use super::schedule::{Activity, EMPTY, Schedule};
+
+/// A point of interest a mob can claim and remember.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoiMemory {
+    /// The bed the mob sleeps in. Vanilla: `MemoryModuleType.HOME`.
+    Home,
+    /// The workstation the mob works at. Vanilla: `MemoryModuleType.JOB_SITE`.
+    JobSite,
+    /// The bell the mob gathers at. Vanilla: `MemoryModuleType.MEETING_POINT`.
+    MeetingPoint,
+}
+
+impl PoiMemory {
+    /// All POI memories.
+    pub const ALL: [Self; 3] = [Self::Home, Self::JobSite, Self::MeetingPoint];
+
+    /// Gets the memory's key in saved brains.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Home => "minecraft:home",
+            Self::JobSite => "minecraft:job_site",
+            Self::MeetingPoint => "minecraft:meeting_point",
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::Home => 0,
+            Self::JobSite => 1,
+            Self::MeetingPoint => 2,
+        }
+    }
+}
+
+/// What a mob remembers and what it is busy with.
+///
+/// Only the memories villagers need for their daily routine are tracked; sensors,
+/// gossip and the remaining memory types are added with the mobs that use them.
+///
+/// Vanilla: `Brain`.
+#[derive(Debug)]
+pub struct Brain {
+    schedule: &'static Schedule,
+    active_activity: Activity,
+    pois: [Option<GlobalPos>; 3],
+    walk_target: Option<WalkTarget>,
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Self::new(&EMPTY)
+    }
+}
+
+impl Brain {
+    /// Creates an empty brain following `schedule`.
+    #[must_use]
+    pub const fn new(schedule: &'static Schedule) -> Self {
+        Self {
+            schedule,
+            active_activity: Activity::Idle,
+            pois: [None, None, None],
+            walk_target: None,
+        }
+    }
+
+    /// Gets the schedule the brain follows.
+    #[must_use]
+    pub const fn schedule(&self) -> &'static Schedule {
+        self.schedule
+    }
+
+    /// Sets the schedule the brain follows, e.g. when a baby grows up.
+    pub const fn set_schedule(&mut self, schedule: &'static Schedule) {
+        self.schedule = schedule;
+    }
+
+    /// Gets the activity the mob is busy with.
+    #[must_use]
+    pub const fn active_activity(&self) -> Activity {
+        self.active_activity
+    }
+
+    /// Switches to the scheduled activity for `day_time`.
+    ///
+    /// Returns the new activity if it changed. The walk target of the previous
+    /// activity is dropped so the mob doesn't keep walking to its old destination.
+    ///
+    /// Vanilla: `Brain.updateActivityFromSchedule()`.
+    pub fn update_activity_from_schedule(&mut self, day_time: i64) -> Option<Activity> {
+        // TODO: Keep panic and raid activities running once they exist
+        let activity = self.schedule.activity_at(day_time);
+        if activity == self.active_activity {
+            return None;
+        }
+        self.active_activity = activity;
+        self.walk_target = None;
+        Some(activity)
+    }
+
+    /// Gets a remembered point of interest.
+    #[must_use]
+    pub fn poi(&self, memory: PoiMemory) -> Option<&GlobalPos> {
+        self.pois[memory.index()].as_ref()
+    }
+
+    /// Remembers a point of interest.
+    pub fn set_poi(&mut self, memory: PoiMemory, pos: GlobalPos) {
+        self.pois[memory.index()] = Some(pos);
+    }
+
+    /// Forgets a point of interest and returns it.
+    pub const fn erase_poi(&mut self, memory: PoiMemory) -> Option<GlobalPos> {
+        self.pois[memory.index()].take()
+    }
+
+    /// Gets where the mob is walking to.
+    #[must_use]
+    pub const fn walk_target(&self) -> Option<&WalkTarget> {
+        self.walk_target.as_ref()
+    }
+
+    /// Sets where the mob walks to.
+    pub const fn set_walk_target(&mut self, target: WalkTarget) {
+        self.walk_target = Some(target);
+    }
+
+    /// Stops walking.
+    pub const fn erase_walk_target(&mut self) {
+        self.walk_target = None;
+    }
+
+    /// Saves the persistent memories in vanilla's `Brain` format.
+    #[must_use]
+    pub fn save(&self) -> NbtCompound {
+        let mut memories = NbtCompound::new();
+        for memory in PoiMemory::ALL {
+            let Some(global_pos) = self.poi(memory) else {
+                continue;
+            };
+            let mut value = NbtCompound::new();
+            value.insert("dimension", global_pos.dimension.to_string());
+            let pos = global_pos.pos;
+            value.insert("pos", NbtTag::IntArray(vec![pos.x(), pos.y(), pos.z()]));
+            let mut entry = NbtCompound::new();
+            entry.insert("value", NbtTag::Compound(value));
+            memories.insert(memory.key(), NbtTag::Compound(entry));
+        }
+        let mut brain = NbtCompound::new();
+        brain.insert("memories", NbtTag::Compound(memories));
+        brain
+    }
+
+    /// Loads the memories saved by [`Self::save`]. Invalid entries are skipped.
+    pub fn load(&mut self, nbt: &NbtCompoundView<'_, '_>) {
+        let Some(memories) = nbt.compound("memories") else {
+            return;
+        };
+        for memory in PoiMemory::ALL {
+            self.pois[memory.index()] = memories
+                .compound(memory.key())
+                .and_then(|entry| entry.compound("value"))
+                .and_then(|value| load_global_pos(&value));
+        }
+    }
+}
+
+fn load_global_pos(nbt: &NbtCompoundView<'_, '_>) -> Option<GlobalPos> {
+    let pos = nbt.int_array("pos")?;
+    let dimension: Identifier = nbt.string("dimension")?.to_str().parse().ok()?;
+    Some(GlobalPos::new(
+        dimension,
+        BlockPos::new(*pos.first()?, *pos.get(1)?, *pos.get(2)?),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use simdnbt::borrow::read_compound;
+
+    use super::*;
+    use crate::entity::ai::schedule::VILLAGER_DEFAULT;
+
+    #[test]
+    fn schedule_change_drops_walk_target() {
+        let mut brain = Brain::new(&VILLAGER_DEFAULT);
+        assert_eq!(
+            brain.update_activity_from_schedule(2500),
+            Some(Activity::Work)
+        );
+        brain.set_walk_target(WalkTarget::new(BlockPos::new(0, 64, 0), 0.5, 1));
+        assert_eq!(brain.update_activity_from_schedule(2600), None);
+        assert!(brain.walk_target().is_some());
+        assert_eq!(
+            brain.update_activity_from_schedule(9000),
+            Some(Activity::Meet)
+        );
+        assert!(brain.walk_target().is_none());
+    }
+
+    #[test]
+    fn memories_round_trip() {
+        let mut brain = Brain::new(&VILLAGER_DEFAULT);
+        let home = GlobalPos::new(
+            Identifier::vanilla_static("overworld"),
+            BlockPos::new(1, 2, 3),
+        );
+        brain.set_poi(PoiMemory::Home, home.clone());
+
+        let mut bytes = Vec::new();
+        brain.save().write(&mut bytes);
+        let nbt = read_compound(&mut Cursor::new(&bytes)).expect("valid nbt");
+
+        let mut loaded = Brain::new(&VILLAGER_DEFAULT);
+        loaded.load(&(&nbt).into());
+        assert_eq!(loaded.poi(PoiMemory::Home), Some(&home));
+        assert!(loaded.poi(PoiMemory::JobSite).is_none());
+    }
+}
@@ -0,0 +1,14 @@
+//! Brain-driven AI: schedules, memories and the behaviors built on them.
+//!
+//! Mirrors the parts of vanilla's `Brain` system that villagers use for their
+//! daily routine: picking an activity by time of day, claiming beds, job sites
+//! and meeting points, and walking to them.
+
+pub mod brain;
+pub mod navigation;
+pub mod poi;
+pub mod schedule;
+
+pub use brain::{Brain, PoiMemory};
+pub use navigation::{WalkTarget, walk_towards};
+pub use schedule::{Activity, Schedule};
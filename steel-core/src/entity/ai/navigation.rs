@@ -0,0 +1,152 @@
+//! Walking mobs towards a target block.
+
+use glam::DVec3;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::vanilla_attributes::MOVEMENT_SPEED;
+use steel_utils::BlockPos;
+
+use crate::entity::Mob;
+use crate::physics::{CollisionWorld, WorldCollisionProvider};
+
+/// Speed factor of ground movement. Vanilla: `LivingEntity.getFrictionInfluencedSpeed()`
+/// with the default block friction of 0.6.
+const GROUND_SPEED_FACTOR: f64 = 0.216 / (0.546 * 0.546 * 0.546);
+
+/// Speed factor of movement in the air. Vanilla: `LivingEntity.getFlyingSpeed()`.
+const AIR_SPEED_FACTOR: f64 = 0.02;
+
+/// How far ahead of the mob to look for an obstacle to jump over.
+const OBSTACLE_PROBE: f64 = 0.4;
+
+/// Upwards velocity of a jump. Vanilla: `LivingEntity.getJumpPower()` at the
+/// default jump strength.
+const JUMP_POWER: f64 = 0.42;
+
+/// A block a mob walks to.
+///
+/// Vanilla: `WalkTarget`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalkTarget {
+    /// The block to walk to.
+    pub pos: BlockPos,
+    /// Multiplier on the mob's movement speed attribute.
+    pub speed_modifier: f32,
+    /// How many blocks away from `pos` count as arrived.
+    pub close_enough_dist: i32,
+}
+
+impl WalkTarget {
+    /// Creates a walk target.
+    #[must_use]
+    pub const fn new(pos: BlockPos, speed_modifier: f32, close_enough_dist: i32) -> Self {
+        Self {
+            pos,
+            speed_modifier,
+            close_enough_dist,
+        }
+    }
+
+    /// Returns true if a mob at `position` has arrived.
+    ///
+    /// Vanilla: `MoveToTargetSink.reachedTarget()`, comparing block distances.
+    #[must_use]
+    pub fn is_reached(&self, position: DVec3) -> bool {
+        let block = BlockPos::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        );
+        distance_manhattan(block, self.pos) <= self.close_enough_dist
+    }
+}
+
+/// Manhattan distance between two blocks. Vanilla: `Vec3i.distManhattan()`.
+#[must_use]
+pub const fn distance_manhattan(a: BlockPos, b: BlockPos) -> i32 {
+    (a.x() - b.x()).abs() + (a.y() - b.y()).abs() + (a.z() - b.z()).abs()
+}
+
+/// Moves `mob` one tick towards `target` in a straight line.
+///
+/// Turns the mob to face the target, pushes it forward and jumps when a block is
+/// in the way. Returns true once the target is reached.
+///
+/// Vanilla: `MoveToTargetSink` and `MoveControl.tick()`.
+// TODO: Follow a path from the pathfinder instead of walking straight at the target
+pub fn walk_towards(mob: &dyn Mob, target: &WalkTarget) -> bool {
+    let position = mob.position();
+    if target.is_reached(position) {
+        return true;
+    }
+
+    let goal = DVec3::new(
+        f64::from(target.pos.x()) + 0.5,
+        f64::from(target.pos.y()),
+        f64::from(target.pos.z()) + 0.5,
+    );
+    let delta = goal - position;
+    let horizontal = DVec3::new(delta.x, 0.0, delta.z);
+    let Some(direction) = horizontal.try_normalize() else {
+        return false;
+    };
+
+    // Vanilla: MoveControl turns at most 90 degrees per tick, which always reaches the target
+    let yaw = (delta.z.atan2(delta.x).to_degrees() - 90.0) as f32;
+    mob.set_rotation((yaw, 0.0));
+
+    // Mob.setSpeed() also sets zza, so the forward input is the speed itself
+    let speed = mob.get_attribute_value(MOVEMENT_SPEED) * f64::from(target.speed_modifier);
+    mob.set_speed(speed as f32);
+    let on_ground = mob.on_ground();
+    let factor = if on_ground {
+        GROUND_SPEED_FACTOR
+    } else {
+        AIR_SPEED_FACTOR
+    };
+    let mut velocity = mob.velocity() + direction * (speed * speed * factor);
+
+    if on_ground && delta.y > -1.0 && is_blocked(mob, direction) {
+        velocity.y = JUMP_POWER;
+    }
+    mob.set_velocity(velocity);
+    false
+}
+
+/// Returns true if a block right in front of the mob's feet can be jumped onto.
+///
+/// Vanilla: the jump in `MoveControl.tick()` and `LivingEntity.aiStep()` on
+/// horizontal collision.
+fn is_blocked(mob: &dyn Mob, direction: DVec3) -> bool {
+    let Some(world) = mob.level() else {
+        return false;
+    };
+    let offset = direction * OBSTACLE_PROBE;
+    let aabb = mob.bounding_box();
+    let shifted = |dy: f64| {
+        AABBd::new(
+            aabb.min_x + offset.x,
+            aabb.min_y + dy,
+            aabb.min_z + offset.z,
+            aabb.max_x + offset.x,
+            aabb.max_y + dy,
+            aabb.max_z + offset.z,
+        )
+    };
+    let collisions = WorldCollisionProvider::new(&world);
+    // Nudged up a bit so the floor the mob stands on doesn't count as an obstacle
+    !collisions.get_block_collisions(&shifted(0.01)).is_empty()
+        && collisions.get_block_collisions(&shifted(1.01)).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reached_uses_block_distance() {
+        let target = WalkTarget::new(BlockPos::new(10, 64, 10), 0.5, 1);
+        assert!(target.is_reached(DVec3::new(10.9, 64.0, 11.2)));
+        assert!(!target.is_reached(DVec3::new(11.5, 64.0, 11.5)));
+        assert!(!target.is_reached(DVec3::new(10.5, 66.0, 10.5)));
+    }
+}
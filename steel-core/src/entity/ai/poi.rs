@@ -0,0 +1,92 @@
+//! Claiming, checking and releasing points of interest for brain memories.
+
+use steel_registry::entity_data::GlobalPos;
+use steel_utils::BlockPos;
+
+use super::brain::{Brain, PoiMemory};
+use crate::poi::OccupationStatus;
+use crate::poi::poi_storage::distance_sq;
+use crate::world::World;
+
+/// How far mobs look for a free POI to claim. Vanilla: `AcquirePoi.SCAN_RANGE`.
+pub const SCAN_RANGE: i32 = 48;
+
+/// How close a remembered POI has to be to get checked.
+///
+/// Vanilla: `ValidateNearbyPoi.MAX_DISTANCE`.
+pub const VALIDATE_DISTANCE: i32 = 16;
+
+/// Claims the nearest free POI that matches `predicate` and remembers it.
+///
+/// Does nothing if the memory is already set. Returns the claimed position.
+///
+/// Vanilla: `AcquirePoi`.
+pub fn acquire_poi(
+    world: &World,
+    brain: &mut Brain,
+    memory: PoiMemory,
+    pos: BlockPos,
+    predicate: impl Fn(usize) -> bool,
+) -> Option<(BlockPos, usize)> {
+    if brain.poi(memory).is_some() {
+        return None;
+    }
+    let mut storage = world.poi_storage.lock();
+    let (poi_pos, poi_type) =
+        storage.get_nearest(&predicate, pos, SCAN_RANGE, OccupationStatus::Free)?;
+    // TODO: Only claim POIs the mob can path to (AcquirePoi.findPathToPois)
+    if !storage.reserve_ticket(poi_pos) {
+        return None;
+    }
+    drop(storage);
+    brain.set_poi(memory, GlobalPos::new(world.dimension.key.clone(), poi_pos));
+    Some((poi_pos, poi_type))
+}
+
+/// Forgets a remembered POI and frees the ticket the mob held on it.
+///
+/// Vanilla: `Villager.releasePoi()`.
+pub fn release_poi(world: &World, brain: &mut Brain, memory: PoiMemory) {
+    let Some(global_pos) = brain.erase_poi(memory) else {
+        return;
+    };
+    // TODO: Release POIs in other dimensions through the server's worlds
+    if global_pos.dimension != world.dimension.key {
+        return;
+    }
+    if !world.poi_storage.lock().release_ticket(global_pos.pos) {
+        log::debug!("No ticket to release at {:?}", global_pos.pos);
+    }
+}
+
+/// Forgets a nearby remembered POI whose block is gone or no longer matches `predicate`.
+///
+/// The ticket went away together with the POI, so nothing is released. Returns true
+/// if the memory was erased.
+///
+/// Vanilla: `ValidateNearbyPoi`.
+pub fn validate_poi(
+    world: &World,
+    brain: &mut Brain,
+    memory: PoiMemory,
+    pos: BlockPos,
+    predicate: impl Fn(usize) -> bool,
+) -> bool {
+    let Some(global_pos) = brain.poi(memory) else {
+        return false;
+    };
+    if global_pos.dimension != world.dimension.key
+        || distance_sq(global_pos.pos, pos) >= i64::from(VALIDATE_DISTANCE).pow(2)
+    {
+        return false;
+    }
+    let valid = world
+        .poi_storage
+        .lock()
+        .get_type(global_pos.pos)
+        .is_some_and(predicate);
+    if !valid {
+        brain.erase_poi(memory);
+    }
+    !valid
+}
@@ -0,0 +1,136 @@
+//! Time-of-day schedules that pick a mob's activity.
+
+/// What a mob with a brain is currently busy with.
+///
+/// Vanilla: `Activity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Activity {
+    /// Always-running behaviors (waking up, validating POIs, ...).
+    Core,
+    /// Wandering around with nothing to do.
+    Idle,
+    /// Working at the job site.
+    Work,
+    /// Playing with other children.
+    Play,
+    /// Sleeping in the home bed.
+    Rest,
+    /// Gathering at the meeting point.
+    Meet,
+    /// Running away from danger.
+    Panic,
+    /// Hiding during a raid.
+    Raid,
+    /// Celebrating after a raid.
+    PreRaid,
+    /// Hiding from a raid.
+    Hide,
+}
+
+impl Activity {
+    /// Gets the activity's registry key path.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Core => "core",
+            Self::Idle => "idle",
+            Self::Work => "work",
+            Self::Play => "play",
+            Self::Rest => "rest",
+            Self::Meet => "meet",
+            Self::Panic => "panic",
+            Self::Raid => "raid",
+            Self::PreRaid => "pre_raid",
+            Self::Hide => "hide",
+        }
+    }
+}
+
+/// The activity that starts at a time of day.
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleEntry {
+    /// Time of day (0..24000) the activity starts at.
+    pub start: i64,
+    /// The activity.
+    pub activity: Activity,
+}
+
+/// A list of activities by time of day, repeated every day.
+///
+/// Vanilla: `Schedule`.
+#[derive(Debug)]
+pub struct Schedule {
+    /// Entries sorted by start time.
+    entries: &'static [ScheduleEntry],
+}
+
+impl Schedule {
+    /// Creates a schedule from entries sorted by start time.
+    #[must_use]
+    pub const fn new(entries: &'static [ScheduleEntry]) -> Self {
+        Self { entries }
+    }
+
+    /// Gets the activity at `day_time`.
+    ///
+    /// Before the first entry of the day, the last activity of the previous day
+    /// is still running.
+    ///
+    /// Vanilla: `Schedule.getActivityAt()`.
+    #[must_use]
+    pub fn activity_at(&self, day_time: i64) -> Activity {
+        let time = day_time.rem_euclid(24000);
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.start <= time)
+            .or_else(|| self.entries.last())
+            .map_or(Activity::Idle, |entry| entry.activity)
+    }
+}
+
+const fn entry(start: i64, activity: Activity) -> ScheduleEntry {
+    ScheduleEntry { start, activity }
+}
+
+/// The schedule of nothing but idling. Vanilla: `Schedule.EMPTY`.
+pub static EMPTY: Schedule = Schedule::new(&[entry(0, Activity::Idle)]);
+
+/// The schedule of grown-up villagers. Vanilla: `Schedule.VILLAGER_DEFAULT`.
+pub static VILLAGER_DEFAULT: Schedule = Schedule::new(&[
+    entry(10, Activity::Idle),
+    entry(2000, Activity::Work),
+    entry(9000, Activity::Meet),
+    entry(11000, Activity::Idle),
+    entry(12000, Activity::Rest),
+]);
+
+/// The schedule of baby villagers. Vanilla: `Schedule.VILLAGER_BABY`.
+pub static VILLAGER_BABY: Schedule = Schedule::new(&[
+    entry(10, Activity::Idle),
+    entry(3000, Activity::Play),
+    entry(6000, Activity::Idle),
+    entry(10000, Activity::Play),
+    entry(12000, Activity::Rest),
+]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn villager_day() {
+        assert_eq!(VILLAGER_DEFAULT.activity_at(1000), Activity::Idle);
+        assert_eq!(VILLAGER_DEFAULT.activity_at(2000), Activity::Work);
+        assert_eq!(VILLAGER_DEFAULT.activity_at(9500), Activity::Meet);
+        assert_eq!(VILLAGER_DEFAULT.activity_at(13000), Activity::Rest);
+    }
+
+    #[test]
+    fn night_carries_over_midnight() {
+        // Between 0 and 10 the previous day's rest is still going
+        assert_eq!(VILLAGER_DEFAULT.activity_at(5), Activity::Rest);
+        assert_eq!(VILLAGER_DEFAULT.activity_at(24_005), Activity::Rest);
+        assert_eq!(VILLAGER_BABY.activity_at(-1), Activity::Rest);
+    }
+}
@@ -0,0 +1,599 @@
+//! Entity attributes and their modifiers.
+//!
+//! Every living entity owns an [`AttributeMap`] holding one [`AttributeInstance`]
+//! per attribute its type supports. Each instance has a base value and a list of
+//! [`AttributeModifier`]s, which equipment, effects and commands add and remove by
+//! id. Changes to syncable attributes are collected and sent to clients with
+//! `CUpdateAttributes`.
+//!
+//! Mirrors vanilla's `AttributeMap`, `AttributeInstance`, `AttributeModifier` and
+//! `DefaultAttributes`.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_protocol::packets::game::{AttributeModifierSnapshot, AttributeSnapshot};
+use steel_registry::attribute::AttributeRef;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_attributes::{
+    ARMOR, ARMOR_TOUGHNESS, ATTACK_DAMAGE, ATTACK_KNOCKBACK, ATTACK_SPEED, BLOCK_BREAK_SPEED,
+    BLOCK_INTERACTION_RANGE, BURNING_TIME, CAMERA_DISTANCE, ENTITY_INTERACTION_RANGE,
+    EXPLOSION_KNOCKBACK_RESISTANCE, FALL_DAMAGE_MULTIPLIER, GRAVITY, JUMP_STRENGTH,
+    KNOCKBACK_RESISTANCE, LUCK, MAX_ABSORPTION, MAX_HEALTH, MINING_EFFICIENCY, MOVEMENT_EFFICIENCY,
+    MOVEMENT_SPEED, OXYGEN_BONUS, SAFE_FALL_DISTANCE, SCALE, SNEAKING_SPEED, STEP_HEIGHT,
+    SUBMERGED_MINING_SPEED, SWEEPING_DAMAGE_RATIO, WATER_MOVEMENT_EFFICIENCY,
+    WAYPOINT_RECEIVE_RANGE, WAYPOINT_TRANSMIT_RANGE,
+};
+use steel_registry::{REGISTRY, RegistryEntry, RegistryExt, vanilla_entities};
+use steel_utils::Identifier;
+
+/// The movement speed modifier of sprinting entities.
+///
+/// Vanilla: `LivingEntity.SPEED_MODIFIER_SPRINTING`.
+pub const SPEED_MODIFIER_SPRINTING: AttributeModifier = AttributeModifier::new(
+    Identifier::vanilla_static("sprinting"),
+    0.3_f32 as f64,
+    AttributeOperation::AddMultipliedTotal,
+);
+
+/// The block interaction range modifier of players in creative mode.
+///
+/// Vanilla: `Player.CREATIVE_BLOCK_INTERACTION_RANGE_MODIFIER`.
+pub const CREATIVE_BLOCK_INTERACTION_RANGE_MODIFIER: AttributeModifier = AttributeModifier::new(
+    Identifier::vanilla_static("creative_mode_block_range"),
+    0.5,
+    AttributeOperation::AddValue,
+);
+
+/// The entity interaction range modifier of players in creative mode.
+///
+/// Vanilla: `Player.CREATIVE_ENTITY_INTERACTION_RANGE_MODIFIER`.
+pub const CREATIVE_ENTITY_INTERACTION_RANGE_MODIFIER: AttributeModifier = AttributeModifier::new(
+    Identifier::vanilla_static("creative_mode_entity_range"),
+    2.0,
+    AttributeOperation::AddValue,
+);
+
+/// How a modifier is combined with the base value.
+///
+/// Vanilla equivalent: `AttributeModifier.Operation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOperation {
+    /// Adds the amount to the base value.
+    AddValue,
+    /// Adds the amount times the base value (after `AddValue` modifiers).
+    AddMultipliedBase,
+    /// Multiplies the result by one plus the amount.
+    AddMultipliedTotal,
+}
+
+impl AttributeOperation {
+    /// Returns the network id of the operation.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        match self {
+            Self::AddValue => 0,
+            Self::AddMultipliedBase => 1,
+            Self::AddMultipliedTotal => 2,
+        }
+    }
+
+    /// Returns the name used in NBT and commands.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::AddValue => "add_value",
+            Self::AddMultipliedBase => "add_multiplied_base",
+            Self::AddMultipliedTotal => "add_multiplied_total",
+        }
+    }
+
+    /// Parses an operation from its NBT name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "add_value" => Some(Self::AddValue),
+            "add_multiplied_base" => Some(Self::AddMultipliedBase),
+            "add_multiplied_total" => Some(Self::AddMultipliedTotal),
+            _ => None,
+        }
+    }
+}
+
+/// A change applied to an attribute, identified by `id`.
+///
+/// Vanilla equivalent: `AttributeModifier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeModifier {
+    /// The modifier id, unique within one attribute instance.
+    pub id: Identifier,
+    /// The amount the operation uses.
+    pub amount: f64,
+    /// How the amount is applied.
+    pub operation: AttributeOperation,
+}
+
+impl AttributeModifier {
+    /// Creates a new attribute modifier.
+    #[must_use]
+    pub const fn new(id: Identifier, amount: f64, operation: AttributeOperation) -> Self {
+        Self {
+            id,
+            amount,
+            operation,
+        }
+    }
+
+    fn save(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.insert("id", self.id.to_string());
+        nbt.insert("amount", self.amount);
+        nbt.insert("operation", self.operation.name().to_string());
+        nbt
+    }
+
+    fn load(nbt: &NbtCompoundView<'_, '_>) -> Option<Self> {
+        let id = nbt.string("id")?.to_str().parse().ok()?;
+        let amount = nbt.double("amount")?;
+        let operation = AttributeOperation::from_name(&nbt.string("operation")?.to_str())?;
+        Some(Self::new(id, amount, operation))
+    }
+}
+
+/// The value of one attribute of one entity.
+///
+/// Vanilla equivalent: `AttributeInstance`.
+#[derive(Debug, Clone)]
+pub struct AttributeInstance {
+    attribute: AttributeRef,
+    base_value: f64,
+    modifiers: Vec<AttributeModifier>,
+    /// Ids of the modifiers that are saved with the entity.
+    permanent_modifiers: FxHashSet<Identifier>,
+    dirty: bool,
+}
+
+impl AttributeInstance {
+    /// Creates an instance without modifiers.
+    #[must_use]
+    pub fn new(attribute: AttributeRef, base_value: f64) -> Self {
+        Self {
+            attribute,
+            base_value,
+            modifiers: Vec::new(),
+            permanent_modifiers: FxHashSet::default(),
+            dirty: false,
+        }
+    }
+
+    /// Returns the attribute this instance holds the value of.
+    #[must_use]
+    pub const fn attribute(&self) -> AttributeRef {
+        self.attribute
+    }
+
+    /// Returns the base value before modifiers.
+    #[must_use]
+    pub const fn base_value(&self) -> f64 {
+        self.base_value
+    }
+
+    /// Sets the base value.
+    #[expect(
+        clippy::float_cmp,
+        reason = "only skips exact no-op changes, like vanilla"
+    )]
+    pub fn set_base_value(&mut self, base_value: f64) {
+        if base_value != self.base_value {
+            self.base_value = base_value;
+            self.set_dirty();
+        }
+    }
+
+    /// Returns all modifiers, in the order they were added.
+    #[must_use]
+    pub fn modifiers(&self) -> &[AttributeModifier] {
+        &self.modifiers
+    }
+
+    /// Returns the modifier with `id`, if any.
+    #[must_use]
+    pub fn get_modifier(&self, id: &Identifier) -> Option<&AttributeModifier> {
+        self.modifiers.iter().find(|modifier| modifier.id == *id)
+    }
+
+    /// Returns true if a modifier with `id` is applied.
+    #[must_use]
+    pub fn has_modifier(&self, id: &Identifier) -> bool {
+        self.get_modifier(id).is_some()
+    }
+
+    /// Adds a modifier that isn't saved with the entity.
+    ///
+    /// Returns false without changing anything if a modifier with the same id is
+    /// already applied (vanilla throws instead).
+    pub fn add_transient_modifier(&mut self, modifier: AttributeModifier) -> bool {
+        if self.has_modifier(&modifier.id) {
+            return false;
+        }
+        self.modifiers.push(modifier);
+        self.set_dirty();
+        true
+    }
+
+    /// Adds a modifier that isn't saved with the entity, replacing one with the same id.
+    ///
+    /// Vanilla equivalent: `AttributeInstance.addOrUpdateTransientModifier()`.
+    pub fn add_or_update_transient_modifier(&mut self, modifier: AttributeModifier) {
+        self.remove_modifier(&modifier.id);
+        self.add_transient_modifier(modifier);
+    }
+
+    /// Adds a modifier that is saved with the entity.
+    ///
+    /// Returns false without changing anything if a modifier with the same id is
+    /// already applied (vanilla throws instead).
+    pub fn add_permanent_modifier(&mut self, modifier: AttributeModifier) -> bool {
+        let id = modifier.id.clone();
+        if !self.add_transient_modifier(modifier) {
+            return false;
+        }
+        self.permanent_modifiers.insert(id);
+        true
+    }
+
+    /// Adds a modifier that is saved with the entity, replacing one with the same id.
+    ///
+    /// Vanilla equivalent: `AttributeInstance.addOrReplacePermanentModifier()`.
+    pub fn add_or_replace_permanent_modifier(&mut self, modifier: AttributeModifier) {
+        self.remove_modifier(&modifier.id);
+        self.add_permanent_modifier(modifier);
+    }
+
+    /// Removes the modifier with `id`. Returns true if there was one.
+    pub fn remove_modifier(&mut self, id: &Identifier) -> bool {
+        let Some(index) = self
+            .modifiers
+            .iter()
+            .position(|modifier| modifier.id == *id)
+        else {
+            return false;
+        };
+        self.modifiers.remove(index);
+        self.permanent_modifiers.remove(id);
+        self.set_dirty();
+        true
+    }
+
+    /// Removes all modifiers.
+    pub fn remove_modifiers(&mut self) {
+        if !self.modifiers.is_empty() {
+            self.modifiers.clear();
+            self.permanent_modifiers.clear();
+            self.set_dirty();
+        }
+    }
+
+    /// Returns the value with all modifiers applied, clamped into the attribute's range.
+    ///
+    /// Vanilla equivalent: `AttributeInstance.calculateValue()`.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        let amounts = |operation| {
+            self.modifiers
+                .iter()
+                .filter(move |modifier| modifier.operation == operation)
+                .map(|modifier| modifier.amount)
+        };
+
+        let base = self.base_value + amounts(AttributeOperation::AddValue).sum::<f64>();
+        let mut value = base;
+        for amount in amounts(AttributeOperation::AddMultipliedBase) {
+            value += base * amount;
+        }
+        for amount in amounts(AttributeOperation::AddMultipliedTotal) {
+            value *= 1.0 + amount;
+        }
+        self.attribute.sanitize_value(value)
+    }
+
+    /// Copies the base value and permanent modifiers of `other`.
+    ///
+    /// Vanilla equivalent: `AttributeInstance.replaceFrom()`.
+    pub fn replace_from(&mut self, other: &Self) {
+        self.base_value = other.base_value;
+        self.modifiers.clone_from(&other.modifiers);
+        self.permanent_modifiers
+            .clone_from(&other.permanent_modifiers);
+        self.set_dirty();
+    }
+
+    fn set_dirty(&mut self) {
+        if self.attribute.syncable {
+            self.dirty = true;
+        }
+    }
+
+    fn snapshot(&self) -> AttributeSnapshot {
+        AttributeSnapshot {
+            attribute_id: self.attribute.id() as i32,
+            base: self.base_value,
+            modifiers: self
+                .modifiers
+                .iter()
+                .map(|modifier| AttributeModifierSnapshot {
+                    id: modifier.id.clone(),
+                    amount: modifier.amount,
+                    operation: modifier.operation.id(),
+                })
+                .collect(),
+        }
+    }
+
+    fn save(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.insert("id", self.attribute.key.to_string());
+        nbt.insert("base", self.base_value);
+        let modifiers: Vec<NbtCompound> = self
+            .modifiers
+            .iter()
+            .filter(|modifier| self.permanent_modifiers.contains(&modifier.id))
+            .map(AttributeModifier::save)
+            .collect();
+        if !modifiers.is_empty() {
+            nbt.insert("modifiers", NbtList::Compound(modifiers));
+        }
+        nbt
+    }
+
+    fn load(&mut self, nbt: &NbtCompoundView<'_, '_>) {
+        if let Some(base) = nbt.double("base") {
+            self.set_base_value(base);
+        }
+        // Transient modifiers are kept, vanilla only replaces the permanent ones
+        let permanent: Vec<Identifier> = self.permanent_modifiers.iter().cloned().collect();
+        for id in &permanent {
+            self.remove_modifier(id);
+        }
+        if let Some(modifiers) = nbt.list("modifiers").and_then(|list| list.compounds()) {
+            for modifier in modifiers {
+                if let Some(modifier) = AttributeModifier::load(&modifier) {
+                    self.add_or_replace_permanent_modifier(modifier);
+                }
+            }
+        }
+    }
+}
+
+/// All attributes of one entity.
+///
+/// Vanilla equivalent: `AttributeMap`.
+#[derive(Debug, Clone, Default)]
+pub struct AttributeMap {
+    attributes: FxHashMap<Identifier, AttributeInstance>,
+}
+
+impl AttributeMap {
+    /// Creates an empty attribute map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the map with `attribute` added at base value `base`.
+    ///
+    /// Vanilla equivalent: `AttributeSupplier.Builder.add()`.
+    #[must_use]
+    pub fn with(mut self, attribute: AttributeRef, base: f64) -> Self {
+        self.attributes.insert(
+            attribute.key.clone(),
+            AttributeInstance::new(attribute, base),
+        );
+        self
+    }
+
+    /// Returns the map with `attribute` added at its default value.
+    #[must_use]
+    pub fn with_default(self, attribute: AttributeRef) -> Self {
+        self.with(attribute, attribute.default_value)
+    }
+
+    /// Returns true if the entity has `attribute`.
+    #[must_use]
+    pub fn has_attribute(&self, attribute: AttributeRef) -> bool {
+        self.attributes.contains_key(&attribute.key)
+    }
+
+    /// Returns the instance of `attribute`, if the entity has it.
+    #[must_use]
+    pub fn get_instance(&self, attribute: AttributeRef) -> Option<&AttributeInstance> {
+        self.attributes.get(&attribute.key)
+    }
+
+    /// Returns the instance of `attribute` for changing it, if the entity has it.
+    pub fn get_instance_mut(&mut self, attribute: AttributeRef) -> Option<&mut AttributeInstance> {
+        self.attributes.get_mut(&attribute.key)
+    }
+
+    /// Returns the value of `attribute` with all modifiers applied.
+    ///
+    /// Falls back to the attribute's default value if the entity doesn't have it,
+    /// where vanilla throws.
+    #[must_use]
+    pub fn get_value(&self, attribute: AttributeRef) -> f64 {
+        self.get_instance(attribute)
+            .map_or(attribute.default_value, AttributeInstance::value)
+    }
+
+    /// Returns the base value of `attribute`, see [`Self::get_value`] for missing attributes.
+    #[must_use]
+    pub fn get_base_value(&self, attribute: AttributeRef) -> f64 {
+        self.get_instance(attribute)
+            .map_or(attribute.default_value, AttributeInstance::base_value)
+    }
+
+    /// Returns true if `attribute` has a modifier with `id`.
+    #[must_use]
+    pub fn has_modifier(&self, attribute: AttributeRef, id: &Identifier) -> bool {
+        self.get_instance(attribute)
+            .is_some_and(|instance| instance.has_modifier(id))
+    }
+
+    /// Returns the syncable attributes that changed since the last call and
+    /// clears their dirty flags.
+    ///
+    /// Vanilla equivalent: `AttributeMap.getAttributesToSync()`.
+    pub fn take_dirty_snapshots(&mut self) -> Vec<AttributeSnapshot> {
+        self.attributes
+            .values_mut()
+            .filter(|instance| instance.dirty)
+            .map(|instance| {
+                instance.dirty = false;
+                instance.snapshot()
+            })
+            .collect()
+    }
+
+    /// Returns all syncable attributes, for entities that start being tracked.
+    ///
+    /// Vanilla equivalent: `AttributeMap.getSyncableAttributes()`.
+    #[must_use]
+    pub fn syncable_snapshots(&self) -> Vec<AttributeSnapshot> {
+        self.attributes
+            .values()
+            .filter(|instance| instance.attribute.syncable)
+            .map(AttributeInstance::snapshot)
+            .collect()
+    }
+
+    /// Copies the base values and permanent modifiers of all attributes in `other`.
+    ///
+    /// Vanilla equivalent: `AttributeMap.assignAllValues()`.
+    pub fn assign_all_values(&mut self, other: &Self) {
+        for (key, instance) in &other.attributes {
+            if let Some(own) = self.attributes.get_mut(key) {
+                own.replace_from(instance);
+            }
+        }
+    }
+
+    /// Copies the base values of all attributes in `other`, dropping its modifiers.
+    ///
+    /// Used when a player respawns after dying. Vanilla equivalent:
+    /// `AttributeMap.assignBaseValues()`.
+    pub fn assign_base_values(&mut self, other: &Self) {
+        for (key, instance) in &other.attributes {
+            if let Some(own) = self.attributes.get_mut(key) {
+                own.set_base_value(instance.base_value);
+            }
+        }
+    }
+
+    /// Saves the attributes to a list in vanilla's `attributes` format.
+    #[must_use]
+    pub fn save(&self) -> NbtList {
+        NbtList::Compound(
+            self.attributes
+                .values()
+                .map(AttributeInstance::save)
+                .collect(),
+        )
+    }
+
+    /// Loads attributes saved by [`Self::save`]; attributes the entity doesn't have are ignored.
+    pub fn load(&mut self, nbt: &NbtCompoundView<'_, '_>) {
+        let Some(list) = nbt.list("attributes").and_then(|list| list.compounds()) else {
+            return;
+        };
+        for compound in list {
+            let Some(id) = compound
+                .string("id")
+                .and_then(|id| id.to_str().parse::<Identifier>().ok())
+            else {
+                continue;
+            };
+            let Some(attribute) = REGISTRY.attributes.by_key(&id) else {
+                log::warn!("Ignoring unknown attribute {id}");
+                continue;
+            };
+            if let Some(instance) = self.get_instance_mut(attribute) {
+                instance.load(&compound);
+            }
+        }
+    }
+}
+
+/// Attributes of every living entity.
+///
+/// Vanilla equivalent: `LivingEntity.createLivingAttributes()`.
+#[must_use]
+pub fn living_entity_attributes() -> AttributeMap {
+    [
+        MAX_HEALTH,
+        KNOCKBACK_RESISTANCE,
+        MOVEMENT_SPEED,
+        ARMOR,
+        ARMOR_TOUGHNESS,
+        MAX_ABSORPTION,
+        STEP_HEIGHT,
+        SCALE,
+        GRAVITY,
+        SAFE_FALL_DISTANCE,
+        FALL_DAMAGE_MULTIPLIER,
+        JUMP_STRENGTH,
+        OXYGEN_BONUS,
+        BURNING_TIME,
+        EXPLOSION_KNOCKBACK_RESISTANCE,
+        WATER_MOVEMENT_EFFICIENCY,
+        MOVEMENT_EFFICIENCY,
+        ATTACK_KNOCKBACK,
+        CAMERA_DISTANCE,
+        WAYPOINT_TRANSMIT_RANGE,
+    ]
+    .into_iter()
+    .fold(AttributeMap::new(), AttributeMap::with_default)
+}
+
+/// Attributes of players.
+///
+/// Vanilla equivalent: `Player.createAttributes()`.
+#[must_use]
+pub fn player_attributes() -> AttributeMap {
+    living_entity_attributes()
+        .with(ATTACK_DAMAGE, 1.0)
+        .with(MOVEMENT_SPEED, 0.1)
+        .with_default(ATTACK_SPEED)
+        .with_default(LUCK)
+        .with_default(BLOCK_INTERACTION_RANGE)
+        .with_default(ENTITY_INTERACTION_RANGE)
+        .with_default(BLOCK_BREAK_SPEED)
+        .with_default(SUBMERGED_MINING_SPEED)
+        .with_default(SNEAKING_SPEED)
+        .with_default(MINING_EFFICIENCY)
+        .with_default(SWEEPING_DAMAGE_RATIO)
+        .with(WAYPOINT_TRANSMIT_RANGE, 6.0E7)
+        .with(WAYPOINT_RECEIVE_RANGE, 6.0E7)
+}
+
+/// Returns the attributes a new entity of `entity_type` starts with.
+///
+/// Living entities get the attributes every living entity has, with the base values
+/// extracted from vanilla on top. Entity types without attributes (items, falling
+/// blocks, ...) get an empty map.
+///
+/// Vanilla equivalent: `DefaultAttributes.getSupplier()`.
+#[must_use]
+pub fn default_attributes(entity_type: EntityTypeRef) -> AttributeMap {
+    if entity_type.key == vanilla_entities::PLAYER.key {
+        return player_attributes();
+    }
+    if !entity_type.has_attributes() {
+        return AttributeMap::new();
+    }
+    entity_type
+        .default_attributes
+        .iter()
+        .fold(living_entity_attributes(), |map, &(attribute, base)| {
+            map.with(attribute, base)
+        })
+}
@@ -6,11 +6,14 @@
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::{Arc, Weak};
 
+use crossbeam::atomic::AtomicCell;
 use glam::DVec3;
 use steel_utils::locks::SyncMutex;
 use uuid::Uuid;
 
-use crate::entity::{EntityLevelCallback, NullEntityCallback, RemovalReason};
+use crate::entity::{
+    EntityLevelCallback, LeashState, NullEntityCallback, RemovalReason, RidingState,
+};
 use crate::world::World;
 
 /// Common fields and methods shared by all entities.
@@ -54,6 +57,16 @@ pub struct EntityBase {
     /// The server tick count when this entity was last ticked.
     /// Used to prevent double-ticking when moving between chunks.
     last_world_tick: AtomicI32,
+    /// Ticks the entity keeps burning for; negative while immune to re-ignition.
+    remaining_fire_ticks: AtomicI32,
+    /// The vehicle and passengers of this entity.
+    riding: SyncMutex<RidingState>,
+    /// The leash of this entity, only used by leashable entities.
+    leash: SyncMutex<LeashState>,
+    /// Distance moved so far, scaled by 0.6. Vanilla: `Entity.moveDist`.
+    move_dist: AtomicCell<f32>,
+    /// The `move_dist` at which the next step sound plays. Vanilla: `Entity.nextStep`.
+    next_step: AtomicCell<f32>,
 }
 
 impl EntityBase {
@@ -76,6 +89,11 @@ impl EntityBase {
             removed: AtomicBool::new(false),
             level_callback: SyncMutex::new(Arc::new(NullEntityCallback)),
             last_world_tick: AtomicI32::new(-1),
+            remaining_fire_ticks: AtomicI32::new(0),
+            riding: SyncMutex::new(RidingState::default()),
+            leash: SyncMutex::new(LeashState::default()),
+            move_dist: AtomicCell::new(0.0),
+            next_step: AtomicCell::new(1.0),
         }
     }
 
@@ -149,4 +167,42 @@ impl EntityBase {
     pub fn mark_ticked(&self, server_tick: i32) {
         self.last_world_tick.store(server_tick, Ordering::Release);
     }
+
+    /// Gets the number of ticks the entity keeps burning for.
+    #[inline]
+    pub fn remaining_fire_ticks(&self) -> i32 {
+        self.remaining_fire_ticks.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of ticks the entity keeps burning for.
+    #[inline]
+    pub fn set_remaining_fire_ticks(&self, ticks: i32) {
+        self.remaining_fire_ticks.store(ticks, Ordering::Relaxed);
+    }
+
+    /// Adds `distance` to the moved distance and returns true if a step sound is due.
+    ///
+    /// Steps are only taken while `can_step` is set (on the ground, on a solid block).
+    pub fn advance_steps(&self, distance: f32, can_step: bool) -> bool {
+        let move_dist = self.move_dist.load() + distance;
+        self.move_dist.store(move_dist);
+        if can_step && move_dist > self.next_step.load() {
+            // Vanilla: Entity.nextStep()
+            self.next_step.store(move_dist.floor() + 1.0);
+            return true;
+        }
+        false
+    }
+
+    /// Gets the vehicle and passengers of this entity.
+    #[inline]
+    pub const fn riding(&self) -> &SyncMutex<RidingState> {
+        &self.riding
+    }
+
+    /// Gets the leash of this entity.
+    #[inline]
+    pub const fn leash(&self) -> &SyncMutex<LeashState> {
+        &self.leash
+    }
 }
@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use rustc_hash::FxHashSet;
 use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityTypeRef;
 use steel_utils::SectionPos;
 use uuid::Uuid;
 
@@ -173,6 +174,21 @@ impl EntityCache {
             .unwrap_or_default()
     }
 
+    /// Gets all loaded entities of the given type.
+    #[must_use]
+    pub fn get_entities_of_type(&self, entity_type: EntityTypeRef) -> Vec<SharedEntity> {
+        let mut result = Vec::new();
+        self.by_id.iter_sync(|_, weak| {
+            if let Some(entity) = weak.upgrade()
+                && entity.entity_type().key == entity_type.key
+            {
+                result.push(entity);
+            }
+            true
+        });
+        result
+    }
+
     /// Returns the number of registered entities (includes potentially stale weak refs).
     #[must_use]
     pub fn count(&self) -> usize {
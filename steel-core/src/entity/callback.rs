@@ -22,6 +22,8 @@ pub enum RemovalReason {
     UnloadedToChunk,
     /// Entity changed dimension.
     ChangedDimension,
+    /// Entity left together with the player riding it or owning it, and is saved with them.
+    UnloadedWithPlayer,
 }
 
 impl RemovalReason {
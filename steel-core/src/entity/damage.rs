@@ -28,6 +28,32 @@ impl DamageSource {
         }
     }
 
+    /// Damage dealt directly by an entity, like a melee attack.
+    #[must_use]
+    pub const fn entity(damage_type: &'static DamageType, attacker_id: i32) -> Self {
+        Self {
+            damage_type,
+            causing_entity_id: Some(attacker_id),
+            direct_entity_id: Some(attacker_id),
+            source_position: None,
+        }
+    }
+
+    /// Damage dealt by a projectile on behalf of the entity that shot it.
+    #[must_use]
+    pub const fn projectile(
+        damage_type: &'static DamageType,
+        projectile_id: i32,
+        owner_id: Option<i32>,
+    ) -> Self {
+        Self {
+            damage_type,
+            causing_entity_id: owner_id,
+            direct_entity_id: Some(projectile_id),
+            source_position: None,
+        }
+    }
+
     /// Whether this damage bypasses creative/spectator invulnerability.
     /// TODO: use damage type tag query once `DamageTypeRegistry` supports tags
     #[must_use]
@@ -0,0 +1,174 @@
+//! Active mob effects.
+//!
+//! Only the bookkeeping exists so far: effects count down, show up on the client
+//! and are saved with their remaining duration, but don't change anything about
+//! the entity yet.
+//!
+//! Mirrors vanilla's `MobEffectInstance`.
+
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::NbtCompound;
+use steel_utils::Identifier;
+
+/// The duration of effects that never run out.
+///
+/// Vanilla: `MobEffectInstance.INFINITE_DURATION`.
+pub const INFINITE_DURATION: i32 = -1;
+
+/// An effect applied to an entity, such as speed or poison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MobEffectInstance {
+    /// The mob effect registry key.
+    pub effect: Identifier,
+    /// The effect level minus one.
+    pub amplifier: u8,
+    /// Remaining ticks, or [`INFINITE_DURATION`].
+    pub duration: i32,
+    /// Whether the effect comes from a beacon or conduit.
+    pub ambient: bool,
+    /// Whether the effect shows particles around the entity.
+    pub show_particles: bool,
+    /// Whether the effect shows an icon in the HUD.
+    pub show_icon: bool,
+}
+
+impl MobEffectInstance {
+    /// Creates a new effect instance with particles and an icon.
+    #[must_use]
+    pub const fn new(effect: Identifier, amplifier: u8, duration: i32) -> Self {
+        Self {
+            effect,
+            amplifier,
+            duration,
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+        }
+    }
+
+    /// Returns true if the effect never runs out.
+    #[must_use]
+    pub const fn is_infinite(&self) -> bool {
+        self.duration == INFINITE_DURATION
+    }
+
+    /// Returns true if this effect runs out before `other`.
+    ///
+    /// Vanilla: `MobEffectInstance.isShorterDurationThan()`.
+    #[must_use]
+    pub const fn is_shorter_duration_than(&self, other: &Self) -> bool {
+        !self.is_infinite() && (self.duration < other.duration || other.is_infinite())
+    }
+
+    /// Merges a new application of the same effect into this one.
+    ///
+    /// A stronger effect replaces this one, a longer effect of the same level
+    /// extends it. Returns true if anything changed.
+    ///
+    /// Vanilla: `MobEffectInstance.update()`.
+    // TODO: Keep the weaker effect around as hidden_effect when a shorter, stronger one takes over
+    pub const fn update(&mut self, other: &Self) -> bool {
+        let mut changed = false;
+        if other.amplifier > self.amplifier {
+            self.amplifier = other.amplifier;
+            self.duration = other.duration;
+            changed = true;
+        } else if self.is_shorter_duration_than(other) && other.amplifier == self.amplifier {
+            self.duration = other.duration;
+            changed = true;
+        }
+
+        if (!other.ambient && self.ambient) || changed {
+            self.ambient = other.ambient;
+            changed = true;
+        }
+        if other.show_particles != self.show_particles {
+            self.show_particles = other.show_particles;
+            changed = true;
+        }
+        if other.show_icon != self.show_icon {
+            self.show_icon = other.show_icon;
+            changed = true;
+        }
+        changed
+    }
+
+    /// Counts the effect down by one tick and returns true while it is still active.
+    ///
+    /// Vanilla: `MobEffectInstance.tickServer()`.
+    // TODO: Apply the effect itself (MobEffect.applyEffectTick) once effects are implemented
+    pub const fn tick(&mut self) -> bool {
+        if self.is_infinite() {
+            return true;
+        }
+        if self.duration > 0 {
+            self.duration -= 1;
+        }
+        self.duration > 0
+    }
+
+    /// Saves the effect in vanilla's `active_effects` entry format.
+    // TODO: hidden_effect (weaker effects hidden by a stronger one of the same type)
+    #[must_use]
+    pub fn save(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.insert("id", self.effect.to_string());
+        nbt.insert("amplifier", self.amplifier as i8);
+        nbt.insert("duration", self.duration);
+        nbt.insert("ambient", i8::from(self.ambient));
+        nbt.insert("show_particles", i8::from(self.show_particles));
+        nbt.insert("show_icon", i8::from(self.show_icon));
+        nbt
+    }
+
+    /// Loads an effect saved by [`Self::save`].
+    #[must_use]
+    pub fn load(nbt: &NbtCompoundView<'_, '_>) -> Option<Self> {
+        let effect = nbt.string("id")?.to_str().parse().ok()?;
+        let show_particles = nbt.byte("show_particles").is_none_or(|b| b != 0);
+        Some(Self {
+            effect,
+            amplifier: nbt.byte("amplifier").unwrap_or(0) as u8,
+            duration: nbt.int("duration").unwrap_or(0),
+            ambient: nbt.byte("ambient").is_some_and(|b| b != 0),
+            show_particles,
+            // Vanilla: the icon defaults to whether particles are shown
+            show_icon: nbt.byte("show_icon").map_or(show_particles, |b| b != 0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effects_run_out() {
+        let mut effect = MobEffectInstance::new(Identifier::vanilla_static("speed"), 0, 2);
+        assert!(effect.tick());
+        assert!(!effect.tick());
+
+        let mut infinite =
+            MobEffectInstance::new(Identifier::vanilla_static("speed"), 0, INFINITE_DURATION);
+        assert!(infinite.tick());
+        assert_eq!(infinite.duration, INFINITE_DURATION);
+    }
+
+    #[test]
+    fn stronger_or_longer_effects_take_over() {
+        let speed = Identifier::vanilla_static("speed");
+        let mut effect = MobEffectInstance::new(speed.clone(), 0, 100);
+
+        assert!(!effect.update(&MobEffectInstance::new(speed.clone(), 0, 50)));
+        assert_eq!(effect.duration, 100);
+
+        assert!(effect.update(&MobEffectInstance::new(speed.clone(), 0, 200)));
+        assert_eq!(effect.duration, 200);
+
+        assert!(effect.update(&MobEffectInstance::new(speed.clone(), 1, 20)));
+        assert_eq!((effect.amplifier, effect.duration), (1, 20));
+
+        assert!(!effect.update(&MobEffectInstance::new(speed, 0, 400)));
+        assert_eq!((effect.amplifier, effect.duration), (1, 20));
+    }
+}
@@ -0,0 +1,553 @@
+//! Arrow entity implementation.
+//!
+//! `ArrowEntity` is a plain arrow shot by a bow, dispenser or plugin. It flies
+//! with gravity and drag, damages the first entity it hits based on its speed
+//! and sticks into blocks until they're removed.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Weak};
+
+use crossbeam::atomic::AtomicCell;
+use glam::DVec3;
+use rustc_hash::FxHashSet;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{CEntityPositionSync, CSetEntityMotion};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entity_data::ArrowEntityData;
+use steel_registry::{sound_events, vanilla_damage_types, vanilla_entities};
+use steel_utils::locks::SyncMutex;
+use steel_utils::{ChunkPos, UuidExt};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::entity::damage::DamageSource;
+use crate::entity::projectile::{
+    ArrowModifiers, HitAction, Projectile, ProjectileHit, ProjectileHitHandler, expand_towards,
+    find_hit,
+};
+use crate::entity::{Entity, EntityBase, RemovalReason, SharedEntity};
+use crate::physics::{CollisionWorld, WorldCollisionProvider};
+use crate::world::World;
+
+/// Gravity applied per tick (blocks/tick^2). Vanilla: `AbstractArrow.getDefaultGravity()`
+const DEFAULT_GRAVITY: f64 = 0.05;
+
+/// Air drag multiplier per tick.
+const AIR_DRAG: f64 = 0.99;
+
+/// Ticks an arrow stays stuck in a block before it despawns.
+const DESPAWN_TIME: i32 = 1200;
+
+/// `id_flags` bit: the arrow was fully charged. Vanilla: `AbstractArrow.FLAG_CRIT`.
+const FLAG_CRIT: i8 = 1;
+
+/// An arrow.
+///
+/// Mirrors vanilla's `Arrow` / `AbstractArrow`:
+/// - Falls with gravity (0.05 per tick) and 0.99 drag
+/// - Deals `ceil(speed * damage)` to the first entity it hits, plus a random bonus when critical
+/// - Pierces through `PierceLevel` entities before stopping
+/// - Sticks into blocks and despawns after a minute in the ground
+pub struct ArrowEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Rotation as (yaw, pitch) in degrees, following the flight direction.
+    rotation: AtomicCell<(f32, f32)>,
+    /// Synced entity data (critical flag, pierce level, in ground).
+    entity_data: SyncMutex<ArrowEntityData>,
+    /// UUID of the entity that shot the arrow.
+    owner: SyncMutex<Option<Uuid>>,
+    /// Whether the arrow has moved away from its owner and may hit it.
+    left_owner: AtomicBool,
+    /// Damage per block/tick of speed.
+    base_damage: AtomicCell<f64>,
+    /// Ticks spent stuck in the ground.
+    life: AtomicI32,
+    /// Entities this arrow already pierced through.
+    pierced: SyncMutex<FxHashSet<i32>>,
+    /// Called before the arrow reacts to a hit.
+    hit_handler: SyncMutex<Option<ProjectileHitHandler>>,
+    /// Whether position/velocity needs to be synced to clients right away.
+    needs_sync: AtomicBool,
+}
+
+impl ArrowEntity {
+    /// Creates a new arrow that isn't moving.
+    ///
+    /// Use [`ProjectileLauncher`](crate::entity::projectile::ProjectileLauncher) to shoot one.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::from_parts(
+            EntityBase::new(id, position, world),
+            DVec3::ZERO,
+            (0.0, 0.0),
+        )
+    }
+
+    /// Creates an arrow from saved data with restored base state.
+    ///
+    /// Type-specific data (damage, owner, ...) is restored via
+    /// `load_additional()` after this constructor.
+    #[must_use]
+    pub fn from_saved(
+        id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        world: Weak<World>,
+    ) -> Self {
+        Self::from_parts(
+            EntityBase::with_uuid(id, uuid, position, world),
+            velocity,
+            rotation,
+        )
+    }
+
+    fn from_parts(base: EntityBase, velocity: DVec3, rotation: (f32, f32)) -> Self {
+        Self {
+            base,
+            velocity: SyncMutex::new(velocity),
+            rotation: AtomicCell::new(rotation),
+            entity_data: SyncMutex::new(ArrowEntityData::new()),
+            owner: SyncMutex::new(None),
+            left_owner: AtomicBool::new(false),
+            base_damage: AtomicCell::new(ArrowModifiers::default().base_damage),
+            life: AtomicI32::new(0),
+            pierced: SyncMutex::new(FxHashSet::default()),
+            hit_handler: SyncMutex::new(None),
+            needs_sync: AtomicBool::new(false),
+        }
+    }
+
+    /// Applies damage settings to the arrow.
+    pub fn set_modifiers(&self, modifiers: ArrowModifiers) {
+        self.base_damage.store(modifiers.base_damage);
+        self.set_critical(modifiers.critical);
+        self.set_pierce_level(modifiers.pierce_level);
+        if modifiers.fire_ticks > 0 {
+            self.ignite_for_ticks(modifiers.fire_ticks);
+        }
+    }
+
+    /// Sets the handler called before the arrow reacts to a hit.
+    pub fn set_hit_handler(&self, handler: Option<ProjectileHitHandler>) {
+        *self.hit_handler.lock() = handler;
+    }
+
+    /// Gets the damage per block/tick of speed.
+    #[must_use]
+    pub fn base_damage(&self) -> f64 {
+        self.base_damage.load()
+    }
+
+    /// Sets the damage per block/tick of speed.
+    pub fn set_base_damage(&self, damage: f64) {
+        self.base_damage.store(damage);
+    }
+
+    /// Returns true if the arrow was fully charged.
+    #[must_use]
+    pub fn is_critical(&self) -> bool {
+        *self.entity_data.lock().id_flags.get() & FLAG_CRIT != 0
+    }
+
+    /// Sets whether the arrow was fully charged.
+    pub fn set_critical(&self, critical: bool) {
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.id_flags.get();
+        let flags = if critical {
+            flags | FLAG_CRIT
+        } else {
+            flags & !FLAG_CRIT
+        };
+        entity_data.id_flags.set(flags);
+    }
+
+    /// Gets how many entities the arrow passes through.
+    #[must_use]
+    pub fn pierce_level(&self) -> u8 {
+        *self.entity_data.lock().pierce_level.get() as u8
+    }
+
+    /// Sets how many entities the arrow passes through.
+    pub fn set_pierce_level(&self, pierce_level: u8) {
+        self.entity_data.lock().pierce_level.set(pierce_level as i8);
+    }
+
+    /// Returns true if the arrow is stuck in a block.
+    #[must_use]
+    pub fn is_in_ground(&self) -> bool {
+        *self.entity_data.lock().in_ground.get()
+    }
+
+    fn set_in_ground(&self, in_ground: bool) {
+        self.entity_data.lock().in_ground.set(in_ground);
+        self.needs_sync.store(true, Ordering::Relaxed);
+    }
+
+    /// Turns the arrow to face along its velocity.
+    ///
+    /// Vanilla: `Projectile.updateRotation()` without the interpolation.
+    fn update_rotation(&self) {
+        let velocity = self.velocity();
+        let horizontal = velocity.x.hypot(velocity.z);
+        self.rotation.store((
+            velocity.x.atan2(velocity.z).to_degrees() as f32,
+            velocity.y.atan2(horizontal).to_degrees() as f32,
+        ));
+    }
+
+    /// Lets the arrow hit its owner once it no longer overlaps it.
+    ///
+    /// Vanilla: `Projectile.checkLeftOwner()`.
+    fn check_left_owner(&self) {
+        if self.left_owner.load(Ordering::Relaxed) {
+            return;
+        }
+        let path = expand_towards(&self.bounding_box(), self.velocity()).inflate(1.0);
+        let touches_owner = self
+            .owner_entity()
+            .is_some_and(|owner| owner.bounding_box().intersects(&path));
+        if !touches_owner {
+            self.left_owner.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns true if the arrow may hit `target`.
+    ///
+    /// Vanilla: `AbstractArrow.canHitEntity()`.
+    fn can_hit_entity(&self, target: &dyn Entity) -> bool {
+        if self.pierced.lock().contains(&target.id()) {
+            return false;
+        }
+        // TODO: Skip spectators and the owner's vehicle
+        self.left_owner.load(Ordering::Relaxed) || self.owner() != Some(target.uuid())
+    }
+
+    /// Runs the hit handler, if any.
+    fn run_hit_handler(&self, hit: &ProjectileHit) -> HitAction {
+        let handler = self.hit_handler.lock().clone();
+        handler.map_or(HitAction::Continue, |handler| handler(self, hit))
+    }
+
+    /// Damages the entity the arrow hit.
+    ///
+    /// Returns true if the arrow pierced through and keeps flying.
+    /// Mirrors vanilla's `AbstractArrow.onHitEntity()`.
+    fn on_hit_entity(&self, target: &SharedEntity) -> bool {
+        let speed = self.velocity().length();
+        let mut damage = (speed * self.base_damage())
+            .clamp(0.0, f64::from(i32::MAX))
+            .ceil() as i64;
+
+        let pierce_level = usize::from(self.pierce_level());
+        if pierce_level > 0 {
+            let mut pierced = self.pierced.lock();
+            if pierced.len() > pierce_level {
+                drop(pierced);
+                self.set_removed(RemovalReason::Discarded);
+                return false;
+            }
+            pierced.insert(target.id());
+        }
+
+        if self.is_critical() {
+            damage += rand::random_range(0..damage / 2 + 2);
+        }
+
+        let owner_id = self.owner_entity().map(|owner| owner.id());
+        let source = DamageSource::projectile(vanilla_damage_types::ARROW, self.id(), owner_id);
+        if self.is_on_fire() {
+            target.ignite_for_seconds(5.0);
+        }
+
+        if target.take_damage(&source, damage.min(i64::from(i32::MAX)) as f32) {
+            // TODO: Knockback, stuck arrow count and potion effects on living targets
+            self.play_hit_sound();
+            if pierce_level == 0 {
+                self.set_removed(RemovalReason::Discarded);
+                return false;
+            }
+            return true;
+        }
+
+        // Vanilla: AbstractArrow.deflect() bounces arrows off entities that weren't hurt
+        let velocity = self.velocity() * -0.1;
+        self.set_velocity(velocity);
+        let (yaw, pitch) = self.rotation.load();
+        self.rotation.store((yaw + 180.0, pitch));
+        self.needs_sync.store(true, Ordering::Relaxed);
+        if velocity.length_squared() < 1.0e-7 {
+            self.set_removed(RemovalReason::Discarded);
+        }
+        false
+    }
+
+    /// Sticks the arrow into the block it hit.
+    ///
+    /// Mirrors vanilla's `AbstractArrow.onHitBlock()`.
+    fn on_hit_block(&self, location: DVec3) {
+        // Pull the arrow back a little so it renders stuck in the surface
+        let offset = self.velocity().normalize_or_zero() * 0.05;
+        self.set_position(location - offset);
+        self.set_velocity(DVec3::ZERO);
+        self.set_in_ground(true);
+        self.set_critical(false);
+        self.set_pierce_level(0);
+        self.pierced.lock().clear();
+        self.life.store(0, Ordering::Relaxed);
+        self.play_hit_sound();
+        // TODO: Shake and Block.onProjectileHit (targets, bells, chorus flowers, ...)
+    }
+
+    fn play_hit_sound(&self) {
+        let pitch = 1.2 / (rand::random::<f32>() * 0.2 + 0.9);
+        self.play_sound(sound_events::ENTITY_ARROW_HIT, 1.0, pitch);
+    }
+}
+
+impl Projectile for ArrowEntity {
+    fn owner(&self) -> Option<Uuid> {
+        *self.owner.lock()
+    }
+
+    fn set_owner(&self, owner: Option<Uuid>) {
+        *self.owner.lock() = owner;
+        self.left_owner.store(owner.is_none(), Ordering::Relaxed);
+    }
+
+    fn shoot(&self, velocity: DVec3) {
+        self.set_velocity(velocity);
+        self.update_rotation();
+        self.life.store(0, Ordering::Relaxed);
+        self.needs_sync.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Entity for ArrowEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::ARROW
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    fn tick(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        self.tick_fire();
+        self.check_left_owner();
+
+        if self.is_in_ground() {
+            // Vanilla: AbstractArrow.shouldFall() once the block it's stuck in is gone
+            let collisions = WorldCollisionProvider::new(&world)
+                .get_block_collisions(&self.bounding_box().inflate(0.06));
+            if collisions.is_empty() {
+                self.set_in_ground(false);
+                self.life.store(0, Ordering::Relaxed);
+            } else {
+                if self.life.fetch_add(1, Ordering::Relaxed) + 1 >= DESPAWN_TIME {
+                    self.set_removed(RemovalReason::Discarded);
+                }
+                return;
+            }
+        }
+
+        let from = self.position();
+        let to = from + self.velocity();
+        // Hits cancelled by the handler or pierced this tick
+        let mut passed = Vec::new();
+        let mut stopped = false;
+        while !self.is_removed() {
+            let Some(hit) = find_hit(&world, self, from, to, |target| {
+                !passed.contains(&target.id()) && self.can_hit_entity(target)
+            }) else {
+                break;
+            };
+            let cancelled = self.run_hit_handler(&hit) == HitAction::Cancel;
+            match hit {
+                ProjectileHit::Entity { entity, .. } => {
+                    if cancelled || self.on_hit_entity(&entity) {
+                        passed.push(entity.id());
+                        continue;
+                    }
+                    stopped = true;
+                }
+                ProjectileHit::Block { location, .. } => {
+                    if !cancelled {
+                        self.on_hit_block(location);
+                        stopped = true;
+                    }
+                }
+            }
+            break;
+        }
+        if self.is_removed() || stopped {
+            return;
+        }
+
+        // TODO: Water drag (0.6), bubble particles and fluid pushing
+        self.set_position(to);
+        self.update_rotation();
+        self.set_velocity(self.velocity() * AIR_DRAG);
+        self.apply_gravity();
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        // The client simulates the flight itself; resync on the update interval
+        // and right away when the arrow hits something
+        let needs_sync = self.needs_sync.swap(false, Ordering::Relaxed);
+        if tick_count % self.entity_type().update_interval != 0 && !needs_sync {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let pos = self.position();
+        let velocity = self.velocity();
+        let (yaw, pitch) = self.rotation.load();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CSetEntityMotion::new(self.id(), velocity.x, velocity.y, velocity.z),
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: false,
+            },
+            None,
+        );
+    }
+
+    fn spawn_data(&self) -> i32 {
+        // Vanilla: Projectile.getAddEntityPacket() sends the owner's id
+        self.owner_entity().map_or(0, |owner| owner.id())
+    }
+
+    fn get_default_gravity(&self) -> f64 {
+        DEFAULT_GRAVITY
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        *self.entity_data.lock().no_gravity.get()
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn rotation(&self) -> (f32, f32) {
+        self.rotation.load()
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        // Match vanilla's AbstractArrow.addAdditionalSaveData
+        nbt.insert("life", self.life.load(Ordering::Relaxed) as i16);
+        nbt.insert("inGround", i8::from(self.is_in_ground()));
+        nbt.insert("damage", self.base_damage());
+        nbt.insert("crit", i8::from(self.is_critical()));
+        nbt.insert("PierceLevel", self.pierce_level() as i8);
+        // Projectile.addAdditionalSaveData
+        if let Some(owner) = self.owner() {
+            nbt.insert("Owner", NbtTag::IntArray(owner.to_int_array().to_vec()));
+        }
+        nbt.insert(
+            "LeftOwner",
+            i8::from(self.left_owner.load(Ordering::Relaxed)),
+        );
+        // TODO: Save pickup, item, weapon and sound event once arrows can be picked up
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(life) = nbt.short("life") {
+            self.life.store(i32::from(life), Ordering::Relaxed);
+        }
+        self.entity_data
+            .lock()
+            .in_ground
+            .set(nbt.byte("inGround").is_some_and(|in_ground| in_ground != 0));
+        if let Some(damage) = nbt.double("damage") {
+            self.set_base_damage(damage);
+        }
+        self.set_critical(nbt.byte("crit").is_some_and(|crit| crit != 0));
+        if let Some(pierce_level) = nbt.byte("PierceLevel") {
+            self.set_pierce_level(pierce_level as u8);
+        }
+        if let Some(owner) = nbt.int_array("Owner")
+            && let Some(uuid) = Uuid::from_int_array(&owner)
+        {
+            *self.owner.lock() = Some(uuid);
+        }
+        self.left_owner.store(
+            nbt.byte("LeftOwner").is_some_and(|left| left != 0),
+            Ordering::Relaxed,
+        );
+    }
+}
@@ -15,6 +15,7 @@ use steel_registry::vanilla_entities;
 use steel_registry::vanilla_entity_data::BlockDisplayEntityData;
 use steel_utils::BlockStateId;
 use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
 use uuid::Uuid;
 
 use crate::entity::{Entity, EntityBase};
@@ -106,6 +107,27 @@ impl Entity for BlockDisplayEntity {
         self.entity_data.lock().pack_all()
     }
 
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
     fn save_additional(&self, nbt: &mut NbtCompound) {
         // Save block state ID directly - these are deterministic in Minecraft
         let block_state_id = *self.entity_data.lock().block_state.get();
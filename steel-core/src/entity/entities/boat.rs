@@ -0,0 +1,679 @@
+//! Boat entity implementation.
+//!
+//! `BoatEntity` covers every boat, chest boat and raft. Players steer boats on
+//! their client and send the result with `SMoveVehicle`, which the server
+//! validates. Boats without a player in control float and drift on the server.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+use crossbeam::atomic::AtomicCell;
+use glam::{DVec3, IVec3};
+use steel_protocol::packets::game::{CEntityPositionSync, CSetEntityMotion};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_blocks;
+use steel_registry::vanilla_entity_data::OakBoatEntityData;
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::InteractionHand;
+use steel_utils::{BlockPos, ChunkPos};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::behavior::InteractionResult;
+use crate::entity::damage::DamageSource;
+use crate::entity::{
+    Entity, EntityBase, RemovalReason, SharedEntity, VehicleEntity, leash, riding,
+};
+use crate::fluid::{get_fluid_state, get_height, is_water_fluid};
+use crate::physics::{
+    CollisionWorld, MoverType, WorldCollisionProvider, join_is_not_empty, translate_shape,
+};
+use crate::player::Player;
+use crate::world::World;
+
+/// Gravity applied per tick (blocks/tick^2). Vanilla: `AbstractBoat.getDefaultGravity()`
+const DEFAULT_GRAVITY: f64 = 0.04;
+
+/// Ticks spent underwater after which a boat throws off its passengers.
+const TIME_TO_EJECT: f32 = 60.0;
+
+/// Vanilla: `AbstractBoat.getMaxPassengers()`.
+const MAX_PASSENGERS: usize = 2;
+
+/// Where a boat is floating, recomputed every tick.
+///
+/// Vanilla: `AbstractBoat.Status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    InWater,
+    UnderWater,
+    UnderFlowingWater,
+    OnLand,
+    InAir,
+}
+
+/// Server-side floating state, not synced to clients.
+struct BoatState {
+    status: Status,
+    old_status: Status,
+    /// The water surface the boat floats on.
+    water_level: f64,
+    /// The friction of the blocks below the boat while on land.
+    land_friction: f32,
+    /// The vertical velocity after the last move.
+    last_yd: f64,
+    /// Ticks spent underwater.
+    out_of_control_ticks: f32,
+}
+
+/// A boat, chest boat or raft.
+///
+/// Mirrors vanilla's `AbstractBoat` and `Boat`/`Raft`. All boat types share the
+/// same synced data layout, so they all use [`OakBoatEntityData`].
+pub struct BoatEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Which boat this is (`oak_boat`, `bamboo_chest_raft`, ...).
+    entity_type: EntityTypeRef,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Whether the entity is on the ground.
+    on_ground: AtomicBool,
+    /// Rotation (yaw, pitch).
+    rotation: AtomicCell<(f32, f32)>,
+    /// Synced entity data (damage wobble and paddles).
+    entity_data: SyncMutex<OakBoatEntityData>,
+    /// Floating state.
+    state: SyncMutex<BoatState>,
+}
+
+impl BoatEntity {
+    /// Creates a new boat of the given type.
+    #[must_use]
+    pub fn new(entity_type: EntityTypeRef, id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::with_base(
+            entity_type,
+            EntityBase::new(id, position, world),
+            DVec3::ZERO,
+            (0.0, 0.0),
+            false,
+        )
+    }
+
+    /// Creates a boat from saved data with restored base state.
+    #[must_use]
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "all fields are required to reconstruct a persisted entity"
+    )]
+    pub fn from_saved(
+        entity_type: EntityTypeRef,
+        id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        on_ground: bool,
+        world: Weak<World>,
+    ) -> Self {
+        Self::with_base(
+            entity_type,
+            EntityBase::with_uuid(id, uuid, position, world),
+            velocity,
+            rotation,
+            on_ground,
+        )
+    }
+
+    fn with_base(
+        entity_type: EntityTypeRef,
+        base: EntityBase,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        on_ground: bool,
+    ) -> Self {
+        Self {
+            base,
+            entity_type,
+            velocity: SyncMutex::new(velocity),
+            on_ground: AtomicBool::new(on_ground),
+            rotation: AtomicCell::new(rotation),
+            entity_data: SyncMutex::new(OakBoatEntityData::new()),
+            state: SyncMutex::new(BoatState {
+                status: Status::InAir,
+                old_status: Status::InAir,
+                water_level: 0.0,
+                land_friction: 0.0,
+                last_yd: 0.0,
+                out_of_control_ticks: 0.0,
+            }),
+        }
+    }
+
+    /// Returns true for rafts, which seat their passengers higher.
+    fn is_raft(&self) -> bool {
+        self.entity_type.key.path.ends_with("raft")
+    }
+
+    /// Returns true for chest boats and chest rafts.
+    // TODO: Chest boat inventories
+    fn has_chest(&self) -> bool {
+        self.entity_type.key.path.contains("chest")
+    }
+
+    /// Sets which paddles are moving, as sent by the steering player.
+    ///
+    /// Vanilla: `AbstractBoat.setPaddleState()`.
+    pub fn set_paddle_state(&self, left: bool, right: bool) {
+        let mut entity_data = self.entity_data.lock();
+        entity_data.id_paddle_left.set(left);
+        entity_data.id_paddle_right.set(right);
+    }
+
+    /// Computes where the boat is floating.
+    ///
+    /// Vanilla: `AbstractBoat.getStatus()`.
+    fn compute_status(&self, world: &Arc<World>, state: &mut BoatState) -> Status {
+        if let Some(status) = self.underwater_status(world) {
+            state.water_level = self.bounding_box().max_y;
+            return status;
+        }
+        if self.check_in_water(world, state) {
+            return Status::InWater;
+        }
+        let friction = self.ground_friction(world);
+        if friction > 0.0 {
+            state.land_friction = friction;
+            return Status::OnLand;
+        }
+        Status::InAir
+    }
+
+    /// Checks whether water covers the top of the boat.
+    ///
+    /// Vanilla: `AbstractBoat.isUnderwater()`.
+    fn underwater_status(&self, world: &Arc<World>) -> Option<Status> {
+        let aabb = self.bounding_box();
+        let max_y = aabb.max_y + 0.001;
+        let mut under_water = false;
+        for x in aabb.min_x.floor() as i32..aabb.max_x.ceil() as i32 {
+            for y in aabb.max_y.floor() as i32..max_y.ceil() as i32 {
+                for z in aabb.min_z.floor() as i32..aabb.max_z.ceil() as i32 {
+                    let pos = BlockPos::new(x, y, z);
+                    let fluid = get_fluid_state(world, pos);
+                    if is_water_fluid(fluid.fluid_id)
+                        && max_y < f64::from(y) + f64::from(get_height(world, pos, fluid))
+                    {
+                        if !fluid.is_source() {
+                            return Some(Status::UnderFlowingWater);
+                        }
+                        under_water = true;
+                    }
+                }
+            }
+        }
+        under_water.then_some(Status::UnderWater)
+    }
+
+    /// Checks whether the bottom of the boat is in water, updating the water level.
+    ///
+    /// Vanilla: `AbstractBoat.checkInWater()`.
+    fn check_in_water(&self, world: &Arc<World>, state: &mut BoatState) -> bool {
+        let aabb = self.bounding_box();
+        let mut in_water = false;
+        state.water_level = -f64::MAX;
+        for x in aabb.min_x.floor() as i32..aabb.max_x.ceil() as i32 {
+            for y in aabb.min_y.floor() as i32..(aabb.min_y + 0.001).ceil() as i32 {
+                for z in aabb.min_z.floor() as i32..aabb.max_z.ceil() as i32 {
+                    let pos = BlockPos::new(x, y, z);
+                    let fluid = get_fluid_state(world, pos);
+                    if is_water_fluid(fluid.fluid_id) {
+                        let height = f64::from(y) + f64::from(get_height(world, pos, fluid));
+                        state.water_level = state.water_level.max(height);
+                        in_water |= aabb.min_y < height;
+                    }
+                }
+            }
+        }
+        in_water
+    }
+
+    /// Gets the average friction of the blocks the boat rests on.
+    ///
+    /// Returns NaN if it rests on nothing, like vanilla. Lily pads are ignored.
+    ///
+    /// Vanilla: `AbstractBoat.getGroundFriction()`.
+    fn ground_friction(&self, world: &Arc<World>) -> f32 {
+        let aabb = self.bounding_box();
+        let test_box = AABBd::new(
+            aabb.min_x,
+            aabb.min_y - 0.001,
+            aabb.min_z,
+            aabb.max_x,
+            aabb.min_y,
+            aabb.max_z,
+        );
+        let x0 = test_box.min_x.floor() as i32 - 1;
+        let x1 = test_box.max_x.ceil() as i32 + 1;
+        let y0 = test_box.min_y.floor() as i32 - 1;
+        let y1 = test_box.max_y.ceil() as i32 + 1;
+        let z0 = test_box.min_z.floor() as i32 - 1;
+        let z1 = test_box.max_z.ceil() as i32 + 1;
+
+        let mut friction = 0.0;
+        let mut count = 0;
+        for x in x0..x1 {
+            for z in z0..z1 {
+                let edges = i32::from(x == x0 || x == x1 - 1) + i32::from(z == z0 || z == z1 - 1);
+                if edges == 2 {
+                    continue;
+                }
+                for y in y0..y1 {
+                    if edges > 0 && (y == y0 || y == y1 - 1) {
+                        continue;
+                    }
+                    let pos = BlockPos::new(x, y, z);
+                    let state = world.get_block_state(pos);
+                    if state.get_block() == vanilla_blocks::LILY_PAD {
+                        continue;
+                    }
+                    let collides = state.get_collision_shape().iter().any(|shape| {
+                        join_is_not_empty(&test_box, &translate_shape(shape, IVec3::new(x, y, z)))
+                    });
+                    if collides {
+                        friction += state.get_block().config.friction;
+                        count += 1;
+                    }
+                }
+            }
+        }
+        friction / count as f32
+    }
+
+    /// Gets the highest water surface above the boat that isn't covered by more water.
+    ///
+    /// Vanilla: `AbstractBoat.getWaterLevelAbove()`.
+    fn water_level_above(&self, world: &Arc<World>, last_yd: f64) -> f32 {
+        let aabb = self.bounding_box();
+        let x0 = aabb.min_x.floor() as i32;
+        let x1 = aabb.max_x.ceil() as i32;
+        let y0 = aabb.max_y.floor() as i32;
+        let y1 = (aabb.max_y - last_yd).ceil() as i32;
+        let z0 = aabb.min_z.floor() as i32;
+        let z1 = aabb.max_z.ceil() as i32;
+
+        'rows: for y in y0..y1 {
+            let mut block_height: f32 = 0.0;
+            for x in x0..x1 {
+                for z in z0..z1 {
+                    let pos = BlockPos::new(x, y, z);
+                    let fluid = get_fluid_state(world, pos);
+                    if is_water_fluid(fluid.fluid_id) {
+                        block_height = block_height.max(get_height(world, pos, fluid));
+                    }
+                    if block_height >= 1.0 {
+                        continue 'rows;
+                    }
+                }
+            }
+            return y as f32 + block_height;
+        }
+        (y1 + 1) as f32
+    }
+
+    /// Applies gravity, buoyancy and friction for the current status.
+    ///
+    /// Vanilla: `AbstractBoat.floatBoat()`.
+    fn float_boat(&self, world: &Arc<World>, state: &mut BoatState) {
+        let mut gravity = -self.get_gravity();
+        let mut buoyancy = 0.0;
+        let mut inv_friction: f32 = 0.05;
+
+        if state.old_status == Status::InAir
+            && state.status != Status::InAir
+            && state.status != Status::OnLand
+        {
+            let pos = self.position();
+            let height = f64::from(self.entity_type.dimensions.height);
+            state.water_level = pos.y + height;
+            let target_y = f64::from(self.water_level_above(world, state.last_yd)) - height + 0.101;
+            let aabb = self.bounding_box();
+            let moved = AABBd::new(
+                aabb.min_x,
+                aabb.min_y + target_y - pos.y,
+                aabb.min_z,
+                aabb.max_x,
+                aabb.max_y + target_y - pos.y,
+                aabb.max_z,
+            );
+            if WorldCollisionProvider::new(world)
+                .get_block_collisions(&moved)
+                .is_empty()
+            {
+                self.set_position(DVec3::new(pos.x, target_y, pos.z));
+                let velocity = self.velocity();
+                self.set_velocity(DVec3::new(velocity.x, 0.0, velocity.z));
+                state.last_yd = 0.0;
+            }
+            state.status = Status::InWater;
+            return;
+        }
+
+        match state.status {
+            Status::InWater => {
+                buoyancy = (state.water_level - self.position().y)
+                    / f64::from(self.entity_type.dimensions.height);
+                inv_friction = 0.9;
+            }
+            Status::UnderFlowingWater => {
+                gravity = -7.0E-4;
+                inv_friction = 0.9;
+            }
+            Status::UnderWater => {
+                buoyancy = 0.01;
+                inv_friction = 0.45;
+            }
+            Status::InAir => inv_friction = 0.9,
+            Status::OnLand => {
+                inv_friction = state.land_friction;
+                if self.controlled_by_player() {
+                    state.land_friction /= 2.0;
+                }
+            }
+        }
+
+        let inv_friction = f64::from(inv_friction);
+        let velocity = self.velocity();
+        let mut velocity = DVec3::new(
+            velocity.x * inv_friction,
+            velocity.y + gravity,
+            velocity.z * inv_friction,
+        );
+        if buoyancy > 0.0 {
+            velocity.y = (velocity.y + buoyancy * (DEFAULT_GRAVITY / 0.65)) * 0.75;
+        }
+        self.set_velocity(velocity);
+    }
+
+    /// Returns true if a player steers this boat.
+    fn controlled_by_player(&self) -> bool {
+        self.controlling_passenger()
+            .is_some_and(|passenger| passenger.as_player().is_some())
+    }
+
+    /// Returns true if the boat's eyes are below the water surface.
+    ///
+    /// Vanilla: `Entity.isEyeInFluid(FluidTags.WATER)`.
+    fn is_eye_in_water(&self, world: &Arc<World>) -> bool {
+        let pos = self.position();
+        let eye_y = self.get_eye_y() - 0.111_111_11;
+        let block = BlockPos::new(
+            pos.x.floor() as i32,
+            eye_y.floor() as i32,
+            pos.z.floor() as i32,
+        );
+        let fluid = get_fluid_state(world, block);
+        is_water_fluid(fluid.fluid_id)
+            && eye_y < f64::from(block.y()) + f64::from(get_height(world, block, fluid))
+    }
+}
+
+impl VehicleEntity for BoatEntity {
+    fn hurt_time(&self) -> i32 {
+        *self.entity_data.lock().id_hurt.get()
+    }
+
+    fn set_hurt_time(&self, hurt_time: i32) {
+        self.entity_data.lock().id_hurt.set(hurt_time);
+    }
+
+    fn hurt_dir(&self) -> i32 {
+        *self.entity_data.lock().id_hurtdir.get()
+    }
+
+    fn set_hurt_dir(&self, hurt_dir: i32) {
+        self.entity_data.lock().id_hurtdir.set(hurt_dir);
+    }
+
+    fn damage(&self) -> f32 {
+        *self.entity_data.lock().id_damage.get()
+    }
+
+    fn set_damage(&self, damage: f32) {
+        self.entity_data.lock().id_damage.set(damage);
+    }
+}
+
+impl Entity for BoatEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        self.entity_type
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type.dimensions;
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    fn as_boat(self: Arc<Self>) -> Option<Arc<BoatEntity>> {
+        Some(self)
+    }
+
+    fn tick(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let eject = {
+            let mut state = self.state.lock();
+            state.old_status = state.status;
+            let status = self.compute_status(&world, &mut state);
+            state.status = status;
+            if matches!(state.status, Status::UnderWater | Status::UnderFlowingWater) {
+                state.out_of_control_ticks += 1.0;
+            } else {
+                state.out_of_control_ticks = 0.0;
+            }
+            state.out_of_control_ticks >= TIME_TO_EJECT
+        };
+        if eject {
+            riding::eject_passengers(self);
+        }
+        self.tick_damage();
+
+        // The steering player's client simulates the boat and sends SMoveVehicle
+        if self.controlled_by_player() {
+            self.set_velocity(DVec3::ZERO);
+        } else {
+            self.set_paddle_state(false, false);
+            self.float_boat(&world, &mut self.state.lock());
+            self.do_move(MoverType::SelfMovement);
+            self.state.lock().last_yd = self.velocity().y;
+        }
+        // TODO: Bubble columns, block effects, pushing entities and picking up mobs
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        if tick_count % self.entity_type.update_interval != 0 {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let pos = self.position();
+        let velocity = self.velocity();
+        let (yaw, pitch) = self.rotation();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CSetEntityMotion::new(self.id(), velocity.x, velocity.y, velocity.z),
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: self.on_ground(),
+            },
+            None,
+        );
+    }
+
+    fn rotation(&self) -> (f32, f32) {
+        self.rotation.load()
+    }
+
+    fn set_rotation(&self, rotation: (f32, f32)) {
+        self.rotation.store(rotation);
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn on_ground(&self) -> bool {
+        self.on_ground.load(Ordering::Relaxed)
+    }
+
+    fn set_on_ground(&self, on_ground: bool) {
+        self.on_ground.store(on_ground, Ordering::Relaxed);
+    }
+
+    fn get_default_gravity(&self) -> f64 {
+        DEFAULT_GRAVITY
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        *self.entity_data.lock().no_gravity.get()
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        self.hurt_vehicle(source, amount)
+    }
+
+    /// Vanilla: `AbstractBoat.interact()`.
+    fn interact(
+        self: Arc<Self>,
+        player: &Arc<Player>,
+        _hand: InteractionHand,
+    ) -> InteractionResult {
+        if player.is_secondary_use_active()
+            || self.state.lock().out_of_control_ticks >= TIME_TO_EJECT
+        {
+            return InteractionResult::Pass;
+        }
+        let passenger: SharedEntity = player.clone();
+        let vehicle: SharedEntity = self;
+        if riding::start_riding(&passenger, &vehicle, false) {
+            InteractionResult::Success
+        } else {
+            InteractionResult::Pass
+        }
+    }
+
+    fn get_pick_result(&self) -> Option<ItemStack> {
+        self.drop_item()
+    }
+
+    fn can_be_leashed(&self) -> bool {
+        true
+    }
+
+    /// Vanilla: `AbstractBoat.remove()`.
+    fn set_removed(&self, reason: RemovalReason) {
+        if reason.should_destroy() {
+            leash::drop_leash(self, true);
+        }
+        self.base.set_removed(reason);
+    }
+
+    fn can_add_passenger(&self, _passenger: &dyn Entity) -> bool {
+        self.passengers().len() < MAX_PASSENGERS
+            && !self
+                .level()
+                .is_some_and(|world| self.is_eye_in_water(&world))
+    }
+
+    fn controlling_passenger(&self) -> Option<SharedEntity> {
+        self.passengers().into_iter().next()
+    }
+
+    /// Vanilla: `AbstractBoat.getPassengerAttachmentPoint()`.
+    fn passenger_attachment_point(&self, passenger: &dyn Entity) -> DVec3 {
+        let passengers = self.passengers();
+        let mut offset: f32 = if self.has_chest() { 0.15 } else { 0.0 };
+        if passengers.len() > 1 {
+            let index = passengers
+                .iter()
+                .position(|entity| entity.id() == passenger.id());
+            offset = if index == Some(0) { 0.2 } else { -0.6 };
+            // TODO: Animals sit 0.2 further forward
+        }
+        let height = self.entity_type.dimensions.height;
+        let ride_height = if self.is_raft() {
+            height * 0.888_888_9
+        } else {
+            height / 3.0
+        };
+        riding::rotate_y(
+            DVec3::new(0.0, f64::from(ride_height), f64::from(offset)),
+            -self.rotation().0.to_radians(),
+        )
+    }
+}
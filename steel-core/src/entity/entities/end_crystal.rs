@@ -0,0 +1,222 @@
+//! End crystal entity implementation.
+//!
+//! Crystals on the End spikes heal the dragon. Destroying one tells the dragon
+//! fight, which may hurt the dragon and makes it go after the player.
+
+use std::sync::Weak;
+
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_damage_type_tags::IS_EXPLOSION_TAG;
+use steel_registry::vanilla_entity_data::EndCrystalEntityData;
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_entities, vanilla_items};
+use steel_utils::BlockPos;
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::UpdateFlags;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::behavior::blocks::FireBlock;
+use crate::entity::damage::DamageSource;
+use crate::entity::{Entity, EntityBase, RemovalReason};
+use crate::world::World;
+
+/// An end crystal.
+///
+/// Mirrors vanilla's `EndCrystal`.
+pub struct EndCrystalEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Synced entity data (beam target, base plate).
+    entity_data: SyncMutex<EndCrystalEntityData>,
+}
+
+impl EndCrystalEntity {
+    /// Creates a new end crystal.
+    ///
+    /// The `id` should be obtained from `next_entity_id()`.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self {
+            base: EntityBase::new(id, position, world),
+            entity_data: SyncMutex::new(EndCrystalEntityData::new()),
+        }
+    }
+
+    /// Creates an end crystal from saved data.
+    ///
+    /// The beam target and base plate are restored via `load_additional()`.
+    #[must_use]
+    pub fn from_saved(id: i32, position: DVec3, uuid: Uuid, world: Weak<World>) -> Self {
+        Self {
+            base: EntityBase::with_uuid(id, uuid, position, world),
+            entity_data: SyncMutex::new(EndCrystalEntityData::new()),
+        }
+    }
+
+    /// Gets the block the crystal's beam points at.
+    #[must_use]
+    pub fn beam_target(&self) -> Option<BlockPos> {
+        *self.entity_data.lock().beam_target.get()
+    }
+
+    /// Points the crystal's beam at `target`, or removes the beam.
+    pub fn set_beam_target(&self, target: Option<BlockPos>) {
+        self.entity_data.lock().beam_target.set(target);
+    }
+
+    /// Returns true if the bedrock base plate is shown.
+    #[must_use]
+    pub fn shows_bottom(&self) -> bool {
+        *self.entity_data.lock().show_bottom.get()
+    }
+
+    /// Shows or hides the bedrock base plate.
+    pub fn set_show_bottom(&self, show_bottom: bool) {
+        self.entity_data.lock().show_bottom.set(show_bottom);
+    }
+}
+
+impl Entity for EndCrystalEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::END_CRYSTAL
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    /// Vanilla: `EndCrystal.tick()`, which keeps a fire burning under crystals
+    /// in the dragon's dimension.
+    fn tick(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        let pos = self.position();
+        let block_pos = BlockPos::containing(pos.x, pos.y, pos.z);
+        if world.dragon_fight().is_some() && world.get_block_state(block_pos).is_air() {
+            world.set_block(
+                block_pos,
+                FireBlock::get_state(&world, block_pos),
+                UpdateFlags::UPDATE_ALL,
+            );
+        }
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        true
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    /// Vanilla: `EndCrystal.hurtServer()`.
+    fn hurt(&self, source: &DamageSource, _amount: f32) -> bool {
+        let Some(world) = self.level() else {
+            return false;
+        };
+        let by_dragon = source
+            .causing_entity_id
+            .and_then(|id| world.get_entity_by_id(id))
+            .is_some_and(|entity| entity.entity_type().key == vanilla_entities::ENDER_DRAGON.key);
+        if by_dragon {
+            return false;
+        }
+
+        if !self.is_removed() {
+            self.set_removed(RemovalReason::Killed);
+            if !REGISTRY
+                .damage_types
+                .is_in_tag(source.damage_type, &IS_EXPLOSION_TAG)
+            {
+                // TODO: Explode with a power of 6 once explosions exist
+            }
+            // Vanilla: EndCrystal.onDestroyedBy()
+            let dragon = world
+                .dragon_fight()
+                .and_then(|fight| fight.lock().on_crystal_destroyed(&world));
+            if let Some(dragon) = dragon
+                .and_then(|uuid| world.get_entity_by_uuid(&uuid))
+                .and_then(|entity| entity.as_ender_dragon())
+            {
+                dragon.on_crystal_destroyed(self, source);
+            }
+        }
+        true
+    }
+
+    fn get_pick_result(&self) -> Option<ItemStack> {
+        Some(ItemStack::new(&vanilla_items::ITEMS.end_crystal))
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        // Match vanilla's EndCrystal.addAdditionalSaveData
+        let entity_data = self.entity_data.lock();
+        if let Some(target) = *entity_data.beam_target.get() {
+            nbt.insert(
+                "beam_target",
+                NbtTag::IntArray(vec![target.x(), target.y(), target.z()]),
+            );
+        }
+        nbt.insert("ShowBottom", i8::from(*entity_data.show_bottom.get()));
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+        let beam_target = nbt
+            .int_array("beam_target")
+            .and_then(|target| match target[..] {
+                [x, y, z] => Some(BlockPos::new(x, y, z)),
+                _ => None,
+            });
+        let mut entity_data = self.entity_data.lock();
+        entity_data.beam_target.set(beam_target);
+        entity_data
+            .show_bottom
+            .set(nbt.byte("ShowBottom").is_none_or(|show| show != 0));
+    }
+}
@@ -0,0 +1,1132 @@
+//! Ender dragon entity implementation.
+//!
+//! The dragon flies without physics along a fixed graph of nodes around the
+//! main End island, see [`path`], while its [`phase`] decides where to fly
+//! next. Its body is split into eight parts the client can hit separately;
+//! the parts' network ids directly follow the dragon's own id.
+
+mod path;
+mod phase;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+use crossbeam::atomic::AtomicCell;
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{
+    CEntityPositionSync, CRotateHead, CSetEntityMotion, to_angle_byte,
+};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_attributes::MAX_HEALTH;
+use steel_registry::vanilla_block_tags::{DRAGON_IMMUNE_TAG, DRAGON_TRANSPARENT_TAG};
+use steel_registry::vanilla_damage_type_tags::ALWAYS_HURTS_ENDER_DRAGONS_TAG;
+use steel_registry::vanilla_entities;
+use steel_registry::vanilla_entity_data::EnderDragonEntityData;
+use steel_registry::vanilla_game_rules::MOB_GRIEFING;
+use steel_registry::{REGISTRY, TaggedRegistryExt, level_events, vanilla_damage_types};
+use steel_utils::locks::SyncMutex;
+use steel_utils::math::wrap_degrees;
+use steel_utils::types::GameType;
+use steel_utils::{BlockPos, ChunkPos};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+pub use phase::DragonPhase;
+
+use self::path::DragonPathfinder;
+use self::phase::PhaseState;
+use crate::chunk::heightmap::HeightmapType;
+use crate::entity::attribute::{AttributeMap, default_attributes};
+use crate::entity::damage::DamageSource;
+use crate::entity::{
+    Entity, EntityBase, LivingEntity, LivingEntityBase, Mob, RemovalReason, SharedEntity,
+    reserve_entity_ids,
+};
+use crate::player::Player;
+use crate::world::{RaytraceAction, World};
+
+/// Number of parts the dragon's body is made of.
+pub const PART_COUNT: usize = 8;
+/// Index of the head part.
+pub const HEAD: usize = 0;
+/// Index of the neck part.
+pub const NECK: usize = 1;
+/// Index of the body part.
+pub const BODY: usize = 2;
+/// Index of the first tail part, the two others follow it.
+pub const TAIL: usize = 3;
+/// Index of the first wing part, the other one follows it.
+pub const WING: usize = 6;
+
+/// Width and height of each part. Vanilla: the `EnderDragonPart` sizes.
+const PART_SIZES: [(f64, f64); PART_COUNT] = [
+    (1.0, 1.0),
+    (3.0, 3.0),
+    (5.0, 3.0),
+    (2.0, 2.0),
+    (2.0, 2.0),
+    (2.0, 2.0),
+    (4.0, 2.0),
+    (4.0, 2.0),
+];
+
+/// Ticks the dying animation lasts before the dragon is removed.
+const DEATH_TIME: i32 = 200;
+/// Ticks a hit keeps the dragon invulnerable. Vanilla: `LivingEntity.invulnerableTime`.
+const INVULNERABLE_TICKS: i32 = 20;
+/// Ticks the dragon flinches after a hit. Vanilla: `LivingEntity.hurtTime`.
+const HURT_TICKS: i32 = 10;
+
+/// The dragon's recent heights and rotations, which the neck and tail trail behind.
+///
+/// Vanilla: `DragonFlightHistory`.
+#[derive(Debug)]
+struct FlightHistory {
+    samples: [(f64, f32); 64],
+    head: Option<usize>,
+}
+
+impl FlightHistory {
+    const fn new() -> Self {
+        Self {
+            samples: [(0.0, 0.0); 64],
+            head: None,
+        }
+    }
+
+    /// Records the current height and rotation.
+    fn record(&mut self, y: f64, y_rot: f32) {
+        let head = match self.head {
+            Some(head) => (head + 1) % self.samples.len(),
+            None => {
+                self.samples.fill((y, y_rot));
+                0
+            }
+        };
+        self.samples[head] = (y, y_rot);
+        self.head = Some(head);
+    }
+
+    /// Gets the height and rotation from `delay` ticks ago.
+    fn get(&self, delay: usize) -> (f64, f32) {
+        let len = self.samples.len();
+        let head = self.head.unwrap_or(0);
+        self.samples[(head + len - delay % len) % len]
+    }
+}
+
+/// The dragon's AI and movement state.
+struct DragonState {
+    /// The current phase.
+    phase: PhaseState,
+    /// The flight graph.
+    pathfinder: DragonPathfinder,
+    /// Recent heights and rotations.
+    flight_history: FlightHistory,
+    /// Position of each part.
+    parts: [DVec3; PART_COUNT],
+    /// Turning velocity.
+    y_rot_a: f32,
+    /// Whether the head, neck or body are stuck in blocks the dragon can't break.
+    in_wall: bool,
+    /// The end crystal healing the dragon.
+    nearest_crystal: Option<SharedEntity>,
+    /// Damage taken since the dragon landed, it takes off after a quarter of its health.
+    sitting_damage_received: f32,
+    /// Ticks since the dragon died.
+    death_time: i32,
+    /// Remaining ticks of flinching after a hit.
+    hurt_time: i32,
+    /// Ticks the dragon existed for.
+    tick_count: i32,
+    /// Whether this is the dragon of the world's dragon fight.
+    in_fight: bool,
+}
+
+impl DragonState {
+    /// Gets the bounding box of part `index`.
+    fn part_box(&self, index: usize) -> AABBd {
+        let pos = self.parts[index];
+        let (width, height) = PART_SIZES[index];
+        AABBd::entity_box(pos.x, pos.y, pos.z, width / 2.0, height)
+    }
+}
+
+/// The ender dragon.
+///
+/// Mirrors vanilla's `EnderDragon`:
+/// - Flies towards the target of its current phase, breaking blocks in its way
+///   unless `mobGriefing` is off
+/// - Heals from the nearest end crystal
+/// - Knocks back and hurts entities touching its wings, head and neck
+/// - Takes full damage on the head and a quarter elsewhere
+/// - Flies to the exit portal when killed and rises while dying
+pub struct EnderDragonEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Rotation as (yaw, pitch) in degrees.
+    rotation: AtomicCell<(f32, f32)>,
+    /// Synced entity data (health, phase, ...).
+    entity_data: SyncMutex<EnderDragonEntityData>,
+    /// Attributes (health, ...).
+    attributes: SyncMutex<AttributeMap>,
+    /// Shared living entity state (death, invulnerability).
+    living_base: SyncMutex<LivingEntityBase>,
+    /// Extra health from absorption.
+    absorption: AtomicCell<f32>,
+    /// Movement speed, unused since the dragon flies on its own.
+    speed: AtomicCell<f32>,
+    /// Whether the dragon never despawns.
+    persistence_required: AtomicBool,
+    /// Phase, path and part positions.
+    state: SyncMutex<DragonState>,
+}
+
+impl EnderDragonEntity {
+    /// Creates a new hovering dragon.
+    ///
+    /// Like vanilla, the dragon drops the given `id` and takes a block of
+    /// ids for itself and its parts.
+    #[must_use]
+    pub fn new(_id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::from_parts(
+            EntityBase::new(Self::reserve_ids(), position, world),
+            DVec3::ZERO,
+            (0.0, 0.0),
+        )
+    }
+
+    /// Creates a dragon from saved data with restored base state.
+    ///
+    /// Type-specific data (phase, health, ...) is restored via
+    /// `load_additional()` after this constructor.
+    #[must_use]
+    pub fn from_saved(
+        _id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        world: Weak<World>,
+    ) -> Self {
+        Self::from_parts(
+            EntityBase::with_uuid(Self::reserve_ids(), uuid, position, world),
+            velocity,
+            rotation,
+        )
+    }
+
+    fn reserve_ids() -> i32 {
+        reserve_entity_ids(PART_COUNT as i32 + 1)
+    }
+
+    fn from_parts(base: EntityBase, velocity: DVec3, rotation: (f32, f32)) -> Self {
+        let attributes = default_attributes(vanilla_entities::ENDER_DRAGON);
+        let mut entity_data = EnderDragonEntityData::new();
+        entity_data
+            .health
+            .set(attributes.get_value(MAX_HEALTH) as f32);
+        entity_data.phase.set(DragonPhase::Hovering.id());
+        let position = base.position();
+
+        Self {
+            base,
+            velocity: SyncMutex::new(velocity),
+            rotation: AtomicCell::new(rotation),
+            entity_data: SyncMutex::new(entity_data),
+            attributes: SyncMutex::new(attributes),
+            living_base: SyncMutex::new(LivingEntityBase::new()),
+            absorption: AtomicCell::new(0.0),
+            speed: AtomicCell::new(0.0),
+            persistence_required: AtomicBool::new(false),
+            state: SyncMutex::new(DragonState {
+                phase: PhaseState::new(DragonPhase::Hovering),
+                pathfinder: DragonPathfinder::default(),
+                flight_history: FlightHistory::new(),
+                parts: [position; PART_COUNT],
+                y_rot_a: 0.0,
+                in_wall: false,
+                nearest_crystal: None,
+                sitting_damage_received: 0.0,
+                death_time: 0,
+                hurt_time: 0,
+                tick_count: 0,
+                in_fight: false,
+            }),
+        }
+    }
+
+    /// Gets the dragon's current phase.
+    #[must_use]
+    pub fn phase(&self) -> DragonPhase {
+        self.state.lock().phase.phase
+    }
+
+    /// Switches the dragon to `phase`.
+    ///
+    /// Vanilla: `EnderDragonPhaseManager.setPhase()`.
+    pub fn set_dragon_phase(&self, phase: DragonPhase) {
+        let mut state = self.state.lock();
+        self.set_phase(&mut state, phase);
+    }
+
+    /// Gets the network id of part `index`.
+    #[must_use]
+    pub fn part_id(&self, index: usize) -> i32 {
+        self.id() + index as i32 + 1
+    }
+
+    /// Returns true if the dragon makes no sounds.
+    #[must_use]
+    pub fn is_silent(&self) -> bool {
+        *self.entity_data.lock().silent.get()
+    }
+
+    /// Gets the block the dragon's position is in.
+    fn block_position(&self) -> BlockPos {
+        let pos = self.position();
+        BlockPos::containing(pos.x, pos.y, pos.z)
+    }
+
+    /// Damages part `part` of the dragon.
+    ///
+    /// The head takes full damage, every other part a quarter plus up to one.
+    /// Only players and damage types tagged `always_hurts_ender_dragons` hurt
+    /// the dragon at all.
+    ///
+    /// Vanilla: `EnderDragon.hurt(ServerLevel, EnderDragonPart, DamageSource, float)`.
+    // TODO: Route player attacks on part ids here once attacks are handled
+    pub fn hurt_part(&self, part: usize, source: &DamageSource, amount: f32) -> bool {
+        let Some(world) = self.level() else {
+            return false;
+        };
+        let mut state = self.state.lock();
+        let phase = state.phase.phase;
+        if phase == DragonPhase::Dying {
+            return false;
+        }
+
+        let mut damage = amount;
+        // Vanilla: AbstractDragonSittingPhase.onHurt() burns arrows instead of taking damage
+        if matches!(
+            phase,
+            DragonPhase::SittingFlaming
+                | DragonPhase::SittingScanning
+                | DragonPhase::SittingAttacking
+        ) && let Some(arrow) = source
+            .direct_entity_id
+            .and_then(|id| world.get_entity_by_id(id))
+            .filter(|entity| is_arrow(entity.entity_type()))
+        {
+            arrow.ignite_for_seconds(1.0);
+            damage = 0.0;
+        }
+        if part != HEAD {
+            damage = damage / 4.0 + damage.min(1.0);
+        }
+        if damage < 0.01 {
+            return false;
+        }
+
+        let by_player = source
+            .causing_entity_id
+            .and_then(|id| world.get_entity_by_id(id))
+            .and_then(|entity| entity.as_player())
+            .is_some();
+        if by_player
+            || REGISTRY
+                .damage_types
+                .is_in_tag(source.damage_type, &ALWAYS_HURTS_ENDER_DRAGONS_TAG)
+        {
+            let health_before = self.get_health();
+            self.really_hurt(&mut state, damage);
+            if self.is_dead_or_dying() && !state.phase.phase.is_sitting() {
+                self.set_health(1.0);
+                self.set_phase(&mut state, DragonPhase::Dying);
+            }
+            if state.phase.phase.is_sitting() {
+                state.sitting_damage_received += health_before - self.get_health();
+                if state.sitting_damage_received > 0.25 * self.get_max_health() {
+                    state.sitting_damage_received = 0.0;
+                    self.set_phase(&mut state, DragonPhase::Takeoff);
+                }
+            }
+        }
+        true
+    }
+
+    /// Applies damage like any other living entity.
+    ///
+    /// Vanilla: `EnderDragon.reallyHurt()`, which is `LivingEntity.hurtServer()`.
+    fn really_hurt(&self, state: &mut DragonState, amount: f32) {
+        if self.is_removed() || self.is_dead_or_dying() {
+            return;
+        }
+        let damage = {
+            let mut living_base = self.living_base.lock();
+            if living_base.invulnerable_time > INVULNERABLE_TICKS / 2 {
+                if amount <= living_base.last_hurt {
+                    return;
+                }
+                // Only the part above the previous hit counts during invulnerability
+                let damage = amount - living_base.last_hurt;
+                living_base.last_hurt = amount;
+                damage
+            } else {
+                living_base.last_hurt = amount;
+                living_base.invulnerable_time = INVULNERABLE_TICKS;
+                amount
+            }
+        };
+        state.hurt_time = HURT_TICKS;
+        self.set_health(self.get_health() - damage);
+        if self.is_dead_or_dying() {
+            self.living_base.lock().dead = true;
+        }
+    }
+
+    /// Reacts to an end crystal of the fight being destroyed.
+    ///
+    /// Destroying the crystal that heals the dragon deals 10 damage to its head.
+    /// The player who destroyed it, or the nearest one, becomes the target.
+    ///
+    /// Vanilla: `EnderDragon.onCrystalDestroyed()`.
+    pub fn on_crystal_destroyed(&self, crystal: &dyn Entity, source: &DamageSource) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        let crystal_pos = crystal.position();
+        let player = source
+            .causing_entity_id
+            .and_then(|id| world.players.get_by_entity_id(id))
+            .or_else(|| {
+                let block = BlockPos::containing(crystal_pos.x, crystal_pos.y, crystal_pos.z);
+                let corner = DVec3::new(
+                    f64::from(block.x()),
+                    f64::from(block.y()),
+                    f64::from(block.z()),
+                );
+                self.nearest_player(&world, corner, None, false, |_| true)
+            });
+
+        let was_nearest = self
+            .state
+            .lock()
+            .nearest_crystal
+            .as_ref()
+            .is_some_and(|nearest| nearest.id() == crystal.id());
+        if was_nearest {
+            let damage_type = if player.is_some() {
+                vanilla_damage_types::PLAYER_EXPLOSION
+            } else {
+                vanilla_damage_types::EXPLOSION
+            };
+            let source = DamageSource {
+                damage_type,
+                causing_entity_id: player.as_ref().map(|player| player.id()),
+                direct_entity_id: Some(crystal.id()),
+                source_position: None,
+            };
+            self.hurt_part(HEAD, &source, 10.0);
+        }
+
+        let target = player
+            .filter(|player| can_attack(player))
+            .map(|player| player.id());
+        let mut state = self.state.lock();
+        self.on_crystal_destroyed_phase(&world, &mut state, target);
+    }
+
+    /// Gets the number of end crystals left, or `None` outside of the fight.
+    fn crystals_alive(&self, world: &World, state: &DragonState) -> Option<i32> {
+        if !state.in_fight {
+            return None;
+        }
+        world
+            .dragon_fight()
+            .map(|fight| fight.lock().crystals_alive())
+    }
+
+    /// Gets the nearest player to `pos` the dragon may attack.
+    ///
+    /// `range` and `line_of_sight` are checked from the dragon.
+    /// Vanilla: `ServerLevel.getNearestPlayer()` with `TargetingConditions.forCombat()`.
+    fn nearest_player(
+        &self,
+        world: &World,
+        pos: DVec3,
+        range: Option<f64>,
+        line_of_sight: bool,
+        filter: impl Fn(&Player) -> bool,
+    ) -> Option<Arc<Player>> {
+        let dragon_pos = self.position();
+        let mut nearest = None;
+        let mut nearest_dist = f64::MAX;
+        world.players.iter_players(|_, player| {
+            let dist = player.position().distance_squared(pos);
+            if dist < nearest_dist
+                && can_attack(player)
+                && range.is_none_or(|range| {
+                    dragon_pos.distance_squared(player.position()) <= range * range
+                })
+                && filter(player)
+                && (!line_of_sight || self.has_line_of_sight(world, &**player))
+            {
+                nearest = Some(player.clone());
+                nearest_dist = dist;
+            }
+            true
+        });
+        nearest
+    }
+
+    /// Returns true if no blocks are between the dragon's and `target`'s eyes.
+    ///
+    /// Vanilla: `LivingEntity.hasLineOfSight()`.
+    // TODO: Clip against collision shapes (ClipContext.Block.COLLIDER) instead of outlines
+    fn has_line_of_sight(&self, world: &World, target: &dyn Entity) -> bool {
+        let pos = self.position();
+        let target_pos = target.position();
+        let from = DVec3::new(pos.x, self.get_eye_y(), pos.z);
+        let to = DVec3::new(target_pos.x, target.get_eye_y(), target_pos.z);
+        if from.distance(to) > 128.0 {
+            return false;
+        }
+        let (hit, _) = world.raytrace(from, to, |pos, world| {
+            if world.get_block_state(pos).is_air() {
+                RaytraceAction::Pass
+            } else {
+                RaytraceAction::CheckShape
+            }
+        });
+        hit.is_none()
+    }
+
+    /// Gets where the dragon's head looks, tilted down while landing and sitting.
+    ///
+    /// Vanilla: `EnderDragon.getHeadLookVector()`.
+    fn head_look_vector(&self, world: &World, phase: DragonPhase) -> DVec3 {
+        let (yaw, pitch) = self.rotation();
+        let x_rot = if matches!(phase, DragonPhase::Landing | DragonPhase::Takeoff) {
+            let egg = Self::egg_pos(world, HeightmapType::MotionBlockingNoLeaves);
+            let dist =
+                (DVec3::from(egg.get_center()).distance(self.position()) as f32 / 4.0).max(1.0);
+            -(6.0 / dist) * 1.5 * 5.0
+        } else if phase.is_sitting() {
+            -45.0
+        } else {
+            pitch
+        };
+        // Vanilla: Entity.calculateViewVector()
+        let x_rot = x_rot.to_radians();
+        let y_rot = -yaw.to_radians();
+        DVec3::new(
+            f64::from(y_rot.sin() * x_rot.cos()),
+            f64::from(-x_rot.sin()),
+            f64::from(y_rot.cos() * x_rot.cos()),
+        )
+    }
+
+    /// Heals from the nearest end crystal and looks for a closer one now and then.
+    ///
+    /// Vanilla: `EnderDragon.checkCrystals()`.
+    fn check_crystals(&self, world: &World, state: &mut DragonState) {
+        if let Some(crystal) = &state.nearest_crystal {
+            if crystal.is_removed() {
+                state.nearest_crystal = None;
+            } else if state.tick_count % 10 == 0 && self.get_health() < self.get_max_health() {
+                self.set_health(self.get_health() + 1.0);
+            }
+        }
+
+        if rand::random_range(0..10) == 0 {
+            let pos = self.position();
+            state.nearest_crystal = world
+                .get_entities_in_aabb(&self.bounding_box().inflate(32.0))
+                .into_iter()
+                .filter(|entity| entity.entity_type().key == vanilla_entities::END_CRYSTAL.key)
+                .min_by(|a, b| {
+                    a.position()
+                        .distance_squared(pos)
+                        .total_cmp(&b.position().distance_squared(pos))
+                });
+        }
+    }
+
+    /// Flies towards the current phase's target.
+    ///
+    /// Vanilla: the movement part of `EnderDragon.aiStep()`.
+    fn fly_towards_target(&self, state: &mut DragonState) {
+        let Some(target) = state.phase.target_location else {
+            return;
+        };
+        let phase = state.phase.phase;
+        let pos = self.position();
+        let delta = target - pos;
+        let dist_to_target = delta.length_squared();
+        let max = f64::from(phase.fly_speed());
+        let horizontal_dist = delta.x.hypot(delta.z);
+        let ydd = if horizontal_dist > 0.0 {
+            (delta.y / horizontal_dist).clamp(-max, max)
+        } else {
+            delta.y
+        };
+        let mut velocity = self.velocity();
+        velocity.y += ydd * 0.01;
+
+        let (yaw, pitch) = self.rotation();
+        let mut yaw = wrap_degrees(yaw);
+        let aim = delta.normalize_or_zero();
+        let dir = DVec3::new(
+            f64::from(yaw.to_radians().sin()),
+            velocity.y,
+            -f64::from(yaw.to_radians().cos()),
+        )
+        .normalize_or_zero();
+        let dot = ((dir.dot(aim) as f32 + 0.5) / 1.5).max(0.0);
+        if delta.x.abs() > 1.0e-5 || delta.z.abs() > 1.0e-5 {
+            let y_rot_d = wrap_degrees(180.0 - delta.x.atan2(delta.z).to_degrees() as f32 - yaw)
+                .clamp(-50.0, 50.0);
+            state.y_rot_a *= 0.8;
+            state.y_rot_a += y_rot_d * phase.turn_speed(velocity);
+            yaw += state.y_rot_a * 0.1;
+        }
+
+        // Vanilla: Entity.moveRelative() with an input of (0, 0, -1)
+        let span = (2.0 / (dist_to_target + 1.0)) as f32;
+        let speed = 0.06 * (dot * span + (1.0 - span));
+        let yaw_rad = yaw.to_radians();
+        velocity += DVec3::new(
+            f64::from(speed * yaw_rad.sin()),
+            0.0,
+            f64::from(-speed * yaw_rad.cos()),
+        );
+
+        let movement = if state.in_wall {
+            velocity * 0.8
+        } else {
+            velocity
+        };
+        self.set_position(pos + movement);
+        let slide = 0.8 + 0.15 * (velocity.normalize_or_zero().dot(dir) + 1.0) / 2.0;
+        self.set_velocity(velocity * DVec3::new(slide, 0.91, slide));
+        self.set_rotation((yaw, pitch));
+    }
+
+    /// Moves the parts along with the dragon and lets the wings, head and neck hit entities.
+    ///
+    /// Vanilla: the part handling of `EnderDragon.aiStep()`.
+    fn tick_parts(&self, world: &Arc<World>, state: &mut DragonState) {
+        let pos = self.position();
+        let yaw = self.rotation().0;
+        let history = &state.flight_history;
+        let tilt = ((history.get(5).0 - history.get(10).0) as f32 * 10.0).to_radians();
+        let (ss_tilt, cc_tilt) = tilt.sin_cos();
+        let (ss1, cc1) = yaw.to_radians().sin_cos();
+        let offset = |x: f32, y: f64, z: f32| pos + DVec3::new(f64::from(x), y, f64::from(z));
+
+        state.parts[BODY] = offset(ss1 * 0.5, 0.0, -cc1 * 0.5);
+        state.parts[WING] = offset(cc1 * 4.5, 2.0, ss1 * 4.5);
+        state.parts[WING + 1] = offset(cc1 * -4.5, 2.0, ss1 * -4.5);
+        if state.hurt_time == 0 {
+            let body = state.parts[BODY];
+            let sitting = state.phase.phase.is_sitting();
+            for wing in [WING, WING + 1] {
+                let area = state.part_box(wing).inflate_xyz(4.0, 2.0, 4.0);
+                let area = AABBd::new(
+                    area.min_x,
+                    area.min_y - 2.0,
+                    area.min_z,
+                    area.max_x,
+                    area.max_y - 2.0,
+                    area.max_z,
+                );
+                self.knock_back(world, &area, body, sitting);
+            }
+            // The head and neck still have last tick's positions here, like in vanilla
+            self.hurt_entities(world, &state.part_box(HEAD).inflate(1.0));
+            self.hurt_entities(world, &state.part_box(NECK).inflate(1.0));
+        }
+
+        let history = &state.flight_history;
+        let (ss, cc) = (yaw.to_radians() - state.y_rot_a * 0.01).sin_cos();
+        let y_offset = if state.phase.phase.is_sitting() {
+            -1.0
+        } else {
+            history.get(5).0 - history.get(0).0
+        };
+        let head = offset(
+            ss * 6.5 * cc_tilt,
+            y_offset + f64::from(ss_tilt * 6.5),
+            -cc * 6.5 * cc_tilt,
+        );
+        let neck = offset(
+            ss * 5.5 * cc_tilt,
+            y_offset + f64::from(ss_tilt * 5.5),
+            -cc * 5.5 * cc_tilt,
+        );
+        let p1 = history.get(5);
+        let mut tails = [DVec3::ZERO; 3];
+        for (i, tail) in tails.iter_mut().enumerate() {
+            let p0 = history.get(12 + i * 2);
+            let rot = yaw.to_radians() + wrap_degrees(p0.1 - p1.1).to_radians();
+            let (ss2, cc2) = rot.sin_cos();
+            let dd = (i + 1) as f32 * 2.0;
+            *tail = offset(
+                -(ss1 * 1.5 + ss2 * dd) * cc_tilt,
+                p0.0 - p1.0 - f64::from((dd + 1.5) * ss_tilt) + 1.5,
+                (cc1 * 1.5 + cc2 * dd) * cc_tilt,
+            );
+        }
+        state.parts[HEAD] = head;
+        state.parts[NECK] = neck;
+        state.parts[TAIL..TAIL + 3].copy_from_slice(&tails);
+    }
+
+    /// Pushes living entities away from the body, hurting them unless the dragon sits.
+    ///
+    /// Vanilla: `EnderDragon.knockBack()`.
+    fn knock_back(&self, world: &World, area: &AABBd, body: DVec3, sitting: bool) {
+        for entity in self.living_targets(world, area) {
+            let pos = entity.position();
+            let xd = pos.x - body.x;
+            let zd = pos.z - body.z;
+            let dd = (xd * xd + zd * zd).max(0.1);
+            push(&entity, DVec3::new(xd / dd * 4.0, 0.2, zd / dd * 4.0));
+            if !sitting {
+                // TODO: Skip entities that were hurt by a mob in the last two ticks
+                let source = DamageSource::entity(vanilla_damage_types::MOB_ATTACK, self.id());
+                entity.take_damage(&source, 5.0);
+            }
+        }
+    }
+
+    /// Bites the living entities in `area`.
+    ///
+    /// Vanilla: `EnderDragon.hurt(ServerLevel, List<Entity>)`.
+    fn hurt_entities(&self, world: &World, area: &AABBd) {
+        for entity in self.living_targets(world, area) {
+            let source = DamageSource::entity(vanilla_damage_types::MOB_ATTACK, self.id());
+            entity.take_damage(&source, 10.0);
+        }
+    }
+
+    /// Gets the living entities in `area` other than the dragon, without
+    /// creative and spectator players.
+    ///
+    /// Vanilla: `EntitySelector.NO_CREATIVE_OR_SPECTATOR`.
+    fn living_targets(&self, world: &World, area: &AABBd) -> Vec<SharedEntity> {
+        world
+            .get_entities_in_aabb(area)
+            .into_iter()
+            .filter(|entity| entity.id() != self.id())
+            .filter(|entity| match entity.clone().as_player() {
+                Some(player) => !matches!(
+                    player.game_mode.load(),
+                    GameType::Creative | GameType::Spectator
+                ),
+                None => entity.clone().as_mob().is_some(),
+            })
+            .collect()
+    }
+
+    /// Breaks the blocks in `area` the dragon can fly through.
+    ///
+    /// Returns true if a block stopped the dragon.
+    /// Vanilla: `EnderDragon.checkWalls()`.
+    fn check_walls(&self, world: &Arc<World>, area: &AABBd) -> bool {
+        let min = BlockPos::containing(area.min_x, area.min_y, area.min_z);
+        let max = BlockPos::containing(area.max_x, area.max_y, area.max_z);
+        let mut hit_wall = false;
+        let mut destroyed_block = false;
+        for x in min.x()..=max.x() {
+            for y in min.y()..=max.y() {
+                for z in min.z()..=max.z() {
+                    let pos = BlockPos::new(x, y, z);
+                    let state = world.get_block_state(pos);
+                    let block = state.get_block();
+                    if state.is_air() || REGISTRY.blocks.is_in_tag(block, &DRAGON_TRANSPARENT_TAG) {
+                        continue;
+                    }
+                    if world.get_game_rule(MOB_GRIEFING).as_bool().unwrap_or(true)
+                        && !REGISTRY.blocks.is_in_tag(block, &DRAGON_IMMUNE_TAG)
+                    {
+                        destroyed_block = world.remove_block(pos, false) || destroyed_block;
+                    } else {
+                        hit_wall = true;
+                    }
+                }
+            }
+        }
+        if destroyed_block {
+            let pos = BlockPos::new(
+                rand::random_range(min.x()..=max.x()),
+                rand::random_range(min.y()..=max.y()),
+                rand::random_range(min.z()..=max.z()),
+            );
+            world.level_event(level_events::PARTICLES_DRAGON_BLOCK_BREAK, pos, 0, None);
+        }
+        hit_wall
+    }
+
+    /// Tells the dragon fight about the dragon's health and name.
+    fn update_fight(&self, world: &World) {
+        if self.state.lock().in_fight
+            && let Some(fight) = world.dragon_fight()
+        {
+            fight.lock().update_dragon(self);
+        }
+    }
+
+    /// Rises and spins for 10 seconds, then ends the fight.
+    ///
+    /// Vanilla: `EnderDragon.tickDeath()`.
+    fn tick_death(&self, world: &Arc<World>) {
+        self.update_fight(world);
+        let death_time = {
+            let mut state = self.state.lock();
+            state.death_time += 1;
+            let rise = DVec3::new(0.0, 0.1, 0.0);
+            for part in &mut state.parts {
+                *part += rise;
+            }
+            state.death_time
+        };
+        // TODO: Drop experience: 500, or 12000 for the first dragon, gated by mobDrops
+        if death_time == 1 && !self.is_silent() {
+            world.global_level_event(level_events::SOUND_DRAGON_DEATH, self.block_position(), 0);
+        }
+        self.set_position(self.position() + DVec3::new(0.0, 0.1, 0.0));
+        let (yaw, pitch) = self.rotation();
+        self.set_rotation((yaw + 20.0, pitch));
+
+        if death_time == DEATH_TIME {
+            if let Some(fight) = world.dragon_fight() {
+                fight.lock().set_dragon_killed(world, self.uuid());
+            }
+            self.set_removed(RemovalReason::Killed);
+        }
+    }
+}
+
+/// Returns true if the dragon may target `player`.
+///
+/// Vanilla: `TargetingConditions.forCombat()`, which skips creative and
+/// spectator players and dead ones.
+fn can_attack(player: &Player) -> bool {
+    !matches!(
+        player.game_mode.load(),
+        GameType::Creative | GameType::Spectator
+    ) && !player.is_dead_or_dying()
+}
+
+/// Returns true for the entity types vanilla's `AbstractArrow` covers.
+fn is_arrow(entity_type: EntityTypeRef) -> bool {
+    [
+        vanilla_entities::ARROW,
+        vanilla_entities::SPECTRAL_ARROW,
+        vanilla_entities::TRIDENT,
+    ]
+    .iter()
+    .any(|arrow| arrow.key == entity_type.key)
+}
+
+/// Adds `impulse` to the velocity of `entity`.
+///
+/// Vanilla: `Entity.push()`. Players move themselves, so they are sent their
+/// new velocity.
+fn push(entity: &SharedEntity, impulse: DVec3) {
+    let velocity = entity.velocity() + impulse;
+    if let Some(player) = entity.clone().as_player() {
+        player.send_packet(CSetEntityMotion::new(
+            player.id(),
+            velocity.x,
+            velocity.y,
+            velocity.z,
+        ));
+    } else {
+        entity.set_velocity(velocity);
+    }
+}
+
+impl Mob for EnderDragonEntity {
+    fn mob_flags(&self) -> i8 {
+        *self.entity_data.lock().mob_flags.get()
+    }
+
+    fn set_mob_flags(&self, flags: i8) {
+        self.entity_data.lock().mob_flags.set(flags);
+    }
+
+    fn is_persistence_required(&self) -> bool {
+        self.persistence_required.load(Ordering::Relaxed)
+    }
+
+    fn set_persistence_required(&self, persistence_required: bool) {
+        self.persistence_required
+            .store(persistence_required, Ordering::Relaxed);
+    }
+
+    /// Vanilla: `EnderDragon.aiStep()`, which replaces all of the usual mob movement.
+    fn ai_step(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        let mut state = self.state.lock();
+        if !state.in_fight {
+            state.in_fight = world
+                .dragon_fight()
+                .is_some_and(|fight| fight.lock().dragon_uuid() == Some(self.uuid()));
+        }
+        if self.is_dead_or_dying() {
+            return;
+        }
+
+        self.check_crystals(&world, &mut state);
+        let (yaw, pitch) = self.rotation();
+        self.set_rotation((wrap_degrees(yaw), pitch));
+        if self.is_no_ai() {
+            return;
+        }
+
+        let (yaw, _) = self.rotation();
+        state.flight_history.record(self.position().y, yaw);
+        let phase = state.phase.phase;
+        self.tick_phase(&world, &mut state);
+        if state.phase.phase != phase {
+            self.tick_phase(&world, &mut state);
+        }
+        self.fly_towards_target(&mut state);
+        self.tick_parts(&world, &mut state);
+
+        let head = state.part_box(HEAD);
+        let neck = state.part_box(NECK);
+        let body = state.part_box(BODY);
+        // Check all three boxes, every one of them breaks blocks
+        let hit_head = self.check_walls(&world, &head);
+        let hit_neck = self.check_walls(&world, &neck);
+        let hit_body = self.check_walls(&world, &body);
+        state.in_wall = hit_head || hit_neck || hit_body;
+        drop(state);
+        self.update_fight(&world);
+    }
+}
+
+impl LivingEntity for EnderDragonEntity {
+    fn get_health(&self) -> f32 {
+        *self.entity_data.lock().health.get()
+    }
+
+    fn set_health(&self, health: f32) {
+        let clamped = health.clamp(0.0, self.get_max_health());
+        self.entity_data.lock().health.set(clamped);
+    }
+
+    fn attributes(&self) -> &SyncMutex<AttributeMap> {
+        &self.attributes
+    }
+
+    fn living_base(&self) -> &SyncMutex<LivingEntityBase> {
+        &self.living_base
+    }
+
+    fn get_absorption_amount(&self) -> f32 {
+        self.absorption.load()
+    }
+
+    fn set_absorption_amount(&self, amount: f32) {
+        self.absorption.store(amount.max(0.0));
+    }
+
+    /// Vanilla: `EnderDragon.addEffect()` rejects all effects.
+    fn is_affected_by_potions(&self) -> bool {
+        false
+    }
+
+    fn set_sprinting(&self, _sprinting: bool) {}
+
+    fn get_speed(&self) -> f32 {
+        self.speed.load()
+    }
+
+    fn set_speed(&self, speed: f32) {
+        self.speed.store(speed);
+    }
+}
+
+impl Entity for EnderDragonEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::ENDER_DRAGON
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    fn tick(&self) {
+        {
+            let mut living_base = self.living_base.lock();
+            if living_base.invulnerable_time > 0 {
+                living_base.invulnerable_time -= 1;
+            }
+        }
+        {
+            let mut state = self.state.lock();
+            state.tick_count += 1;
+            if state.hurt_time > 0 {
+                state.hurt_time -= 1;
+            }
+        }
+        if self.is_dead_or_dying()
+            && let Some(world) = self.level()
+        {
+            self.tick_death(&world);
+        }
+        self.ai_step();
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        if tick_count % self.entity_type().update_interval != 0 {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        // TODO: Send relative moves and only when something changed (ServerEntity)
+        let pos = self.position();
+        let velocity = self.velocity();
+        let (yaw, pitch) = self.rotation.load();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: false,
+            },
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CRotateHead {
+                entity_id: self.id(),
+                head_y_rot: to_angle_byte(yaw),
+            },
+            None,
+        );
+    }
+
+    fn as_mob(self: Arc<Self>) -> Option<Arc<dyn Mob>> {
+        Some(self)
+    }
+
+    fn as_ender_dragon(self: Arc<Self>) -> Option<Arc<EnderDragonEntity>> {
+        Some(self)
+    }
+
+    /// Vanilla: `EnderDragon.hurtServer()`, which hits the body.
+    fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        self.hurt_part(BODY, source, amount)
+    }
+
+    fn rotation(&self) -> (f32, f32) {
+        self.rotation.load()
+    }
+
+    fn set_rotation(&self, rotation: (f32, f32)) {
+        self.rotation.store(rotation);
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        true
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        // Match vanilla's LivingEntity and EnderDragon.addAdditionalSaveData
+        nbt.insert("Health", self.get_health());
+        nbt.insert("attributes", self.attributes.lock().save());
+        self.save_mob_data(nbt);
+        let state = self.state.lock();
+        nbt.insert("DragonPhase", state.phase.phase.id());
+        nbt.insert("DragonDeathTime", state.death_time);
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        self.load_mob_data(nbt);
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+
+        self.attributes.lock().load(&nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.set_health(health);
+        }
+        let mut state = self.state.lock();
+        if let Some(phase) = nbt.int("DragonPhase").and_then(DragonPhase::from_id) {
+            self.set_phase(&mut state, phase);
+        }
+        state.death_time = nbt.int("DragonDeathTime").unwrap_or(0);
+    }
+}
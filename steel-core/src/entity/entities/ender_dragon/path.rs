@@ -0,0 +1,258 @@
+//! The fixed flight graph the ender dragon paths along.
+//!
+//! The graph has 24 nodes: 12 on an outer ring above the spikes, 8 on an inner
+//! ring and 4 close to the exit portal. Without end crystals left the outer
+//! ring is skipped.
+
+use std::array;
+use std::f32::consts::PI;
+
+use steel_utils::BlockPos;
+
+use crate::chunk::heightmap::HeightmapType;
+use crate::world::World;
+
+/// Number of nodes in the graph.
+pub const NODE_COUNT: usize = 24;
+/// Number of nodes on the outer ring, the ones only used while crystals remain.
+pub const OUTER_NODE_COUNT: usize = 12;
+
+/// Which nodes each node is connected to, as a bit set.
+///
+/// Vanilla: `EnderDragon.nodeAdjacency`.
+const NODE_ADJACENCY: [u32; NODE_COUNT] = [
+    6146, 8197, 8202, 16404, 32808, 32848, 65696, 131_392, 131_712, 263_424, 526_848, 525_313,
+    1_581_057, 3_166_214, 2_138_120, 6_373_424, 4_358_208, 12_910_976, 9_044_480, 9_706_496,
+    15_216_640, 13_688_832, 11_763_712, 8_257_536,
+];
+
+/// A path through the graph, possibly ending at an extra target outside it.
+///
+/// Vanilla: the `Path` built by `EnderDragon.reconstructPath()`.
+#[derive(Debug, Clone)]
+pub struct DragonPath {
+    nodes: Vec<BlockPos>,
+    next: usize,
+}
+
+impl DragonPath {
+    /// Returns true once every node was visited.
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.next >= self.nodes.len()
+    }
+
+    /// Moves on to the next node.
+    pub const fn advance(&mut self) {
+        self.next += 1;
+    }
+
+    /// Gets the node the dragon flies to next.
+    #[must_use]
+    pub fn next_node_pos(&self) -> Option<BlockPos> {
+        self.nodes.get(self.next).copied()
+    }
+
+    /// Skips all remaining nodes.
+    pub const fn finish(&mut self) {
+        self.next = self.nodes.len();
+    }
+}
+
+/// The dragon's flight graph.
+///
+/// The node heights follow the terrain, so the nodes are placed the first time
+/// the dragon needs them.
+#[derive(Debug, Default)]
+pub struct DragonPathfinder {
+    nodes: Option<[BlockPos; NODE_COUNT]>,
+}
+
+impl DragonPathfinder {
+    /// Places the nodes if that didn't happen yet.
+    ///
+    /// Vanilla: the node setup in `EnderDragon.findClosestNode()`.
+    fn nodes(&mut self, world: &World) -> &[BlockPos; NODE_COUNT] {
+        self.nodes.get_or_insert_with(|| {
+            array::from_fn(|i| {
+                let (radius, step, index, y_adjustment) = match i {
+                    0..12 => (60.0, PI / 12.0, i, 5),
+                    12..20 => (40.0, PI / 8.0, i - 12, 15),
+                    _ => (20.0, PI / 4.0, i - 20, 5),
+                };
+                let angle = 2.0 * (-PI + step * index as f32);
+                let x = (radius * angle.cos()).floor() as i32;
+                let z = (radius * angle.sin()).floor() as i32;
+                let surface = world.get_height(
+                    HeightmapType::MotionBlockingNoLeaves,
+                    BlockPos::new(x, 0, z),
+                );
+                BlockPos::new(x, (surface + y_adjustment).max(73), z)
+            })
+        })
+    }
+
+    /// Gets the index of the node closest to `pos`.
+    ///
+    /// The outer ring is only considered while crystals remain.
+    /// Vanilla: `EnderDragon.findClosestNode(double, double, double)`.
+    pub fn find_closest_node(
+        &mut self,
+        world: &World,
+        pos: BlockPos,
+        crystals_alive: bool,
+    ) -> usize {
+        let start = if crystals_alive { 0 } else { OUTER_NODE_COUNT };
+        let nodes = self.nodes(world);
+        let mut closest_dist = 10_000.0;
+        let mut closest = 0;
+        for (i, node) in nodes.iter().enumerate().skip(start) {
+            let dist = distance_sqr(*node, pos);
+            if dist < closest_dist {
+                closest_dist = dist;
+                closest = i;
+            }
+        }
+        closest
+    }
+
+    /// Finds the shortest path from node `start` to node `end` with A*, optionally
+    /// continuing to `final_pos` afterwards.
+    ///
+    /// If `end` can't be reached the path leads to the node closest to it, or is
+    /// `None` if no other node is reachable at all.
+    /// Vanilla: `EnderDragon.findPath()`.
+    pub fn find_path(
+        &mut self,
+        world: &World,
+        start: usize,
+        end: usize,
+        final_pos: Option<BlockPos>,
+        crystals_alive: bool,
+    ) -> Option<DragonPath> {
+        let nodes = *self.nodes(world);
+        let min_index = if crystals_alive { 0 } else { OUTER_NODE_COUNT };
+        let to = nodes[end];
+
+        let mut g = [0.0f32; NODE_COUNT];
+        let mut f = [0.0f32; NODE_COUNT];
+        let mut closed = [false; NODE_COUNT];
+        let mut came_from: [Option<usize>; NODE_COUNT] = [None; NODE_COUNT];
+        let mut open: Vec<usize> = vec![start];
+        f[start] = distance(nodes[start], to);
+
+        let mut closest = start;
+        while !open.is_empty() {
+            // Pop the open node with the lowest cost, the first one on ties
+            let mut best = 0;
+            for (slot, &node) in open.iter().enumerate() {
+                if f[node] < f[open[best]] {
+                    best = slot;
+                }
+            }
+            let current = open.remove(best);
+
+            if current == end {
+                return Some(Self::reconstruct(&nodes, &came_from, current, final_pos));
+            }
+            if distance(nodes[current], to) < distance(nodes[closest], to) {
+                closest = current;
+            }
+            closed[current] = true;
+
+            for next in min_index..NODE_COUNT {
+                if NODE_ADJACENCY[current] & (1 << next) == 0 || closed[next] {
+                    continue;
+                }
+                let tentative = g[current] + distance(nodes[current], nodes[next]);
+                let in_open = open.contains(&next);
+                if !in_open || tentative < g[next] {
+                    came_from[next] = Some(current);
+                    g[next] = tentative;
+                    f[next] = tentative + distance(nodes[next], to);
+                    if !in_open {
+                        open.push(next);
+                    }
+                }
+            }
+        }
+
+        if closest == start {
+            return None;
+        }
+        log::debug!("Failed to find dragon path from {start} to {end}");
+        Some(Self::reconstruct(&nodes, &came_from, closest, final_pos))
+    }
+
+    fn reconstruct(
+        nodes: &[BlockPos; NODE_COUNT],
+        came_from: &[Option<usize>; NODE_COUNT],
+        last: usize,
+        final_pos: Option<BlockPos>,
+    ) -> DragonPath {
+        let mut path = Vec::new();
+        let mut node = Some(last);
+        while let Some(index) = node {
+            path.push(nodes[index]);
+            node = came_from[index];
+        }
+        path.reverse();
+        path.extend(final_pos);
+        DragonPath {
+            nodes: path,
+            next: 0,
+        }
+    }
+}
+
+/// Vanilla: `Node.distanceToSqr()`, computed in floats.
+const fn distance_sqr(a: BlockPos, b: BlockPos) -> f32 {
+    let dx = (b.x() - a.x()) as f32;
+    let dy = (b.y() - a.y()) as f32;
+    let dz = (b.z() - a.z()) as f32;
+    dx * dx + dy * dy + dz * dz
+}
+
+/// Vanilla: `Node.distanceTo()`.
+fn distance(a: BlockPos, b: BlockPos) -> f32 {
+    distance_sqr(a, b).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacency_is_symmetric() {
+        for (a, edges) in NODE_ADJACENCY.iter().enumerate() {
+            for b in 0..NODE_COUNT {
+                if edges & (1 << b) != 0 {
+                    assert!(
+                        NODE_ADJACENCY[b] & (1 << a) != 0,
+                        "{a} -> {b} has no way back"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reconstruct_appends_final_pos() {
+        let mut nodes = [BlockPos::new(0, 0, 0); NODE_COUNT];
+        nodes[1] = BlockPos::new(1, 0, 0);
+        nodes[2] = BlockPos::new(2, 0, 0);
+        let mut came_from = [None; NODE_COUNT];
+        came_from[2] = Some(1);
+        came_from[1] = Some(0);
+        let final_pos = BlockPos::new(5, 5, 5);
+
+        let mut path = DragonPathfinder::reconstruct(&nodes, &came_from, 2, Some(final_pos));
+        let mut visited = Vec::new();
+        while let Some(pos) = path.next_node_pos() {
+            visited.push(pos);
+            path.advance();
+        }
+        assert!(path.is_done());
+        assert_eq!(visited, vec![nodes[0], nodes[1], nodes[2], final_pos]);
+    }
+}
@@ -0,0 +1,674 @@
+//! The ender dragon's phases.
+//!
+//! Each phase decides where the dragon flies to and when to switch to another
+//! phase. The dragon circles the island in the holding pattern, strafes players
+//! with fireballs, lands on the exit portal to breathe fire and charges at
+//! players after taking off again.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_registry::level_events;
+use steel_utils::BlockPos;
+use steel_utils::math::wrap_degrees;
+
+use super::path::{DragonPath, OUTER_NODE_COUNT};
+use super::{DragonState, EnderDragonEntity, HEAD};
+use crate::chunk::heightmap::HeightmapType;
+use crate::entity::{Entity, LivingEntity};
+use crate::world::EndDragonFight;
+use crate::world::World;
+
+/// Squared distance below which the dragon picks a new target.
+const TARGET_REACHED_DIST_SQ: f64 = 100.0;
+/// Squared distance above which the dragon gives up on a target.
+const TARGET_LOST_DIST_SQ: f64 = 22500.0;
+
+/// What the dragon is doing.
+///
+/// Vanilla: `EnderDragonPhase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragonPhase {
+    /// Circling the island along the outer nodes.
+    HoldingPattern,
+    /// Flying at a player to shoot a fireball.
+    StrafePlayer,
+    /// Flying towards the exit portal to land.
+    LandingApproach,
+    /// Descending onto the exit portal.
+    Landing,
+    /// Leaving the exit portal.
+    Takeoff,
+    /// Breathing fire on the exit portal.
+    SittingFlaming,
+    /// Looking for a player while sitting on the exit portal.
+    SittingScanning,
+    /// Roaring at a player before breathing fire.
+    SittingAttacking,
+    /// Charging at a player.
+    ChargingPlayer,
+    /// Flying to the exit portal to die.
+    Dying,
+    /// Staying in place, the phase of dragons outside of a fight.
+    Hovering,
+}
+
+impl DragonPhase {
+    /// All phases in id order.
+    const ALL: [Self; 11] = [
+        Self::HoldingPattern,
+        Self::StrafePlayer,
+        Self::LandingApproach,
+        Self::Landing,
+        Self::Takeoff,
+        Self::SittingFlaming,
+        Self::SittingScanning,
+        Self::SittingAttacking,
+        Self::ChargingPlayer,
+        Self::Dying,
+        Self::Hovering,
+    ];
+
+    /// Gets the id synced to clients and saved as `DragonPhase`.
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// Gets the phase with the given id.
+    #[must_use]
+    pub fn from_id(id: i32) -> Option<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|index| Self::ALL.get(index))
+            .copied()
+    }
+
+    /// Returns true if the dragon stays in place during this phase.
+    ///
+    /// Vanilla: `DragonPhaseInstance.isSitting()`.
+    #[must_use]
+    pub const fn is_sitting(self) -> bool {
+        matches!(
+            self,
+            Self::SittingFlaming | Self::SittingScanning | Self::SittingAttacking | Self::Hovering
+        )
+    }
+
+    /// Gets the maximum climb or descent rate towards the fly target.
+    ///
+    /// Vanilla: `DragonPhaseInstance.getFlySpeed()`.
+    #[must_use]
+    pub const fn fly_speed(self) -> f32 {
+        match self {
+            Self::Landing => 1.5,
+            Self::ChargingPlayer | Self::Dying => 3.0,
+            Self::Hovering => 1.0,
+            _ => 0.6,
+        }
+    }
+
+    /// Gets how quickly the dragon turns towards its fly target.
+    ///
+    /// Vanilla: `DragonPhaseInstance.getTurnSpeed()`.
+    #[must_use]
+    pub fn turn_speed(self, velocity: DVec3) -> f32 {
+        let rot_speed = velocity.x.hypot(velocity.z) as f32 + 1.0;
+        let dist = rot_speed.min(40.0);
+        if self == Self::Landing {
+            dist / rot_speed
+        } else {
+            0.7 / dist / rot_speed
+        }
+    }
+}
+
+/// The state of the current phase.
+///
+/// Vanilla keeps one instance per phase; since `begin()` resets nearly all of
+/// their fields, they share one set of fields here. Only the circling
+/// directions and the flame count survive a phase change.
+#[derive(Debug)]
+pub(super) struct PhaseState {
+    /// The current phase.
+    pub phase: DragonPhase,
+    /// The path the dragon follows.
+    pub path: Option<DragonPath>,
+    /// Where the dragon flies to.
+    pub target_location: Option<DVec3>,
+    /// Entity id of the player being strafed.
+    pub attack_target: Option<i32>,
+    /// Ticks the strafed player was in sight, a fireball is shot at 5.
+    pub fireball_charge: i32,
+    /// Ticks spent in the phase: scanning, attacking, flaming, charging or dying.
+    pub ticks: i32,
+    /// Whether the takeoff phase still has to pick its path.
+    pub first_tick: bool,
+    /// Circling direction of the holding pattern.
+    pub holding_clockwise: bool,
+    /// Circling direction while strafing.
+    pub strafe_clockwise: bool,
+    /// Times the dragon breathed fire since it landed.
+    pub flame_count: i32,
+}
+
+impl PhaseState {
+    /// Creates the state of a dragon in `phase`.
+    pub fn new(phase: DragonPhase) -> Self {
+        let mut state = Self {
+            phase,
+            path: None,
+            target_location: None,
+            attack_target: None,
+            fireball_charge: 0,
+            ticks: 0,
+            first_tick: true,
+            holding_clockwise: false,
+            strafe_clockwise: false,
+            flame_count: 0,
+        };
+        state.begin(phase);
+        state
+    }
+
+    /// Switches to `phase`, resetting its fields.
+    ///
+    /// Vanilla: `DragonPhaseInstance.begin()` of each phase.
+    fn begin(&mut self, phase: DragonPhase) {
+        self.phase = phase;
+        self.path = None;
+        self.target_location = None;
+        self.attack_target = None;
+        self.fireball_charge = 0;
+        self.ticks = 0;
+        self.first_tick = true;
+        if phase == DragonPhase::SittingFlaming {
+            self.flame_count += 1;
+        }
+    }
+
+    /// Returns true if the current target is reached or too far away.
+    fn needs_new_target(&self, pos: DVec3) -> bool {
+        let dist = self
+            .target_location
+            .map_or(0.0, |target| target.distance_squared(pos));
+        !(TARGET_REACHED_DIST_SQ..=TARGET_LOST_DIST_SQ).contains(&dist)
+    }
+
+    /// Flies to the next node of the path, somewhere up to 20 blocks above it.
+    ///
+    /// Vanilla: `navigateToNextPathNode()` of the flying phases.
+    fn navigate_to_next_node(&mut self) {
+        if let Some(path) = &mut self.path
+            && let Some(node) = path.next_node_pos()
+        {
+            path.advance();
+            self.target_location = Some(random_above(node));
+        }
+    }
+}
+
+/// Gets a random point up to 20 blocks above `node`.
+fn random_above(node: BlockPos) -> DVec3 {
+    DVec3::new(
+        f64::from(node.x()),
+        f64::from(node.y()) + f64::from(rand::random::<f32>() * 20.0),
+        f64::from(node.z()),
+    )
+}
+
+/// Gets the node the dragon circles to next, after `current`.
+///
+/// With crystals left the dragon stays on the outer ring, otherwise on the
+/// inner one.
+fn next_circling_node(current: usize, clockwise: &mut bool, outer_ring: bool) -> usize {
+    let mut target = current as i32;
+    if rand::random_range(0..8) == 0 {
+        *clockwise = !*clockwise;
+        target += 6;
+    }
+    target += if *clockwise { 1 } else { -1 };
+    wrap_to_ring(target, outer_ring)
+}
+
+/// Wraps a node index around the outer or the inner ring.
+fn wrap_to_ring(index: i32, outer_ring: bool) -> usize {
+    let ring = OUTER_NODE_COUNT as i32;
+    if outer_ring {
+        index.rem_euclid(ring) as usize
+    } else {
+        (((index - ring) & 7) + ring) as usize
+    }
+}
+
+/// Gets the minimum corner of `pos`, where vanilla looks for players near a block.
+fn corner(pos: BlockPos) -> DVec3 {
+    DVec3::new(f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()))
+}
+
+/// Gets the normalized horizontal direction the dragon faces at `yaw`.
+fn facing(yaw: f32) -> DVec3 {
+    let yaw = yaw.to_radians();
+    DVec3::new(f64::from(yaw.sin()), 0.0, -f64::from(yaw.cos())).normalize_or_zero()
+}
+
+/// Gets the angle in degrees between where the dragon faces and `target`.
+fn angle_to(dragon_pos: DVec3, yaw: f32, target: DVec3) -> f32 {
+    let aim = DVec3::new(target.x - dragon_pos.x, 0.0, target.z - dragon_pos.z).normalize_or_zero();
+    let dot = facing(yaw).dot(aim) as f32;
+    dot.acos().to_degrees() + 0.5
+}
+
+impl EnderDragonEntity {
+    /// Switches to `phase` unless the dragon is already in it.
+    ///
+    /// Vanilla: `EnderDragonPhaseManager.setPhase()`.
+    pub(super) fn set_phase(&self, state: &mut DragonState, phase: DragonPhase) {
+        if state.phase.phase == phase {
+            return;
+        }
+        // TODO: Discard the dragon breath cloud when leaving SittingFlaming
+        state.phase.begin(phase);
+        self.entity_data.lock().phase.set(phase.id());
+        log::debug!("Set dragon phase to {phase:?}");
+    }
+
+    /// Runs the current phase for one tick.
+    ///
+    /// Vanilla: `DragonPhaseInstance.doServerTick()`.
+    pub(super) fn tick_phase(&self, world: &Arc<World>, state: &mut DragonState) {
+        match state.phase.phase {
+            DragonPhase::HoldingPattern => self.tick_holding_pattern(world, state),
+            DragonPhase::StrafePlayer => self.tick_strafe_player(world, state),
+            DragonPhase::LandingApproach => self.tick_landing_approach(world, state),
+            DragonPhase::Landing => self.tick_landing(world, state),
+            DragonPhase::Takeoff => self.tick_takeoff(world, state),
+            DragonPhase::SittingFlaming => self.tick_sitting_flaming(state),
+            DragonPhase::SittingScanning => self.tick_sitting_scanning(world, state),
+            DragonPhase::SittingAttacking => {
+                state.phase.ticks += 1;
+                if state.phase.ticks >= 40 {
+                    self.set_phase(state, DragonPhase::SittingFlaming);
+                }
+            }
+            DragonPhase::ChargingPlayer => self.tick_charging_player(state),
+            DragonPhase::Dying => self.tick_dying(world, state),
+            DragonPhase::Hovering => {
+                if state.phase.target_location.is_none() {
+                    state.phase.target_location = Some(self.position());
+                }
+            }
+        }
+    }
+
+    /// Called when an end crystal of the fight was destroyed.
+    ///
+    /// Vanilla: `DragonPhaseInstance.onCrystalDestroyed()`, which only the
+    /// holding pattern reacts to.
+    pub(super) fn on_crystal_destroyed_phase(
+        &self,
+        world: &Arc<World>,
+        state: &mut DragonState,
+        player: Option<i32>,
+    ) {
+        if state.phase.phase == DragonPhase::HoldingPattern
+            && let Some(player) = player
+        {
+            self.strafe_player(world, state, player);
+        }
+    }
+
+    /// Gets the block on top of the exit portal.
+    ///
+    /// Vanilla: `level.getHeightmapPos(heightmap, EndPodiumFeature.getLocation(fightOrigin))`.
+    pub(crate) fn egg_pos(world: &World, heightmap: HeightmapType) -> BlockPos {
+        let origin = EndDragonFight::FIGHT_ORIGIN;
+        origin.at_y(world.get_height(heightmap, origin))
+    }
+
+    /// Gets the index of the node closest to `pos`, see [`DragonPathfinder::find_closest_node`].
+    ///
+    /// [`DragonPathfinder::find_closest_node`]: super::path::DragonPathfinder::find_closest_node
+    fn closest_node(&self, world: &World, state: &mut DragonState, pos: DVec3) -> usize {
+        let crystals_alive = self.crystals_alive(world, state).is_some_and(|c| c > 0);
+        let pos = BlockPos::containing(pos.x, pos.y, pos.z);
+        state
+            .pathfinder
+            .find_closest_node(world, pos, crystals_alive)
+    }
+
+    /// Finds a path between two nodes, see [`DragonPathfinder::find_path`].
+    ///
+    /// [`DragonPathfinder::find_path`]: super::path::DragonPathfinder::find_path
+    fn find_path(
+        &self,
+        world: &World,
+        state: &mut DragonState,
+        start: usize,
+        end: usize,
+        final_pos: Option<BlockPos>,
+    ) -> Option<DragonPath> {
+        let crystals_alive = self.crystals_alive(world, state).is_some_and(|c| c > 0);
+        state
+            .pathfinder
+            .find_path(world, start, end, final_pos, crystals_alive)
+    }
+
+    /// Vanilla: `DragonHoldingPatternPhase`.
+    fn tick_holding_pattern(&self, world: &Arc<World>, state: &mut DragonState) {
+        // TODO: Also pick a new target on collisions once the dragon tracks them
+        if !state.phase.needs_new_target(self.position()) {
+            return;
+        }
+
+        let crystals = self.crystals_alive(world, state);
+        if state.phase.path.as_ref().is_some_and(DragonPath::is_done) {
+            let egg = Self::egg_pos(world, HeightmapType::MotionBlockingNoLeaves);
+            let crystal_count = crystals.unwrap_or(0);
+            if rand::random_range(0..crystal_count + 3) == 0 {
+                self.set_phase(state, DragonPhase::LandingApproach);
+                return;
+            }
+            if let Some(player) = self.nearest_player(world, corner(egg), None, false, |_| true) {
+                let dist =
+                    DVec3::from(egg.get_center()).distance_squared(player.position()) / 512.0;
+                if rand::random_range(0..(dist + 2.0) as i32) == 0
+                    || rand::random_range(0..crystal_count + 2) == 0
+                {
+                    self.strafe_player(world, state, player.id());
+                    return;
+                }
+            }
+        }
+
+        if state.phase.path.as_ref().is_none_or(DragonPath::is_done) {
+            let current = self.closest_node(world, state, self.position());
+            let outer_ring = crystals.is_some_and(|c| c >= 0);
+            let target =
+                next_circling_node(current, &mut state.phase.holding_clockwise, outer_ring);
+            state.phase.path = self.find_path(world, state, current, target, None);
+            if let Some(path) = &mut state.phase.path {
+                path.advance();
+            }
+        }
+        state.phase.navigate_to_next_node();
+    }
+
+    /// Starts strafing the player with entity id `player`.
+    ///
+    /// Vanilla: `DragonHoldingPatternPhase.strafePlayer()` and
+    /// `DragonStrafePlayerPhase.setTarget()`.
+    fn strafe_player(&self, world: &Arc<World>, state: &mut DragonState, player: i32) {
+        self.set_phase(state, DragonPhase::StrafePlayer);
+        state.phase.attack_target = Some(player);
+        let Some(target) = world.get_entity_by_id(player) else {
+            return;
+        };
+        let target_pos = target.position();
+        let current = self.closest_node(world, state, self.position());
+        let target_node = self.closest_node(world, state, target_pos);
+
+        let block = BlockPos::containing(target_pos.x, target_pos.y, target_pos.z);
+        let pos = self.position();
+        let horizontal_dist = (f64::from(block.x()) - pos.x).hypot(f64::from(block.z()) - pos.z);
+        let height_offset = (0.4 + horizontal_dist / 80.0 - 1.0).min(10.0);
+        let final_pos = block.at_y((target_pos.y + height_offset).floor() as i32);
+
+        state.phase.path = self.find_path(world, state, current, target_node, Some(final_pos));
+        if let Some(path) = &mut state.phase.path {
+            path.advance();
+            state.phase.navigate_to_next_node();
+        }
+    }
+
+    /// Vanilla: `DragonStrafePlayerPhase`.
+    fn tick_strafe_player(&self, world: &Arc<World>, state: &mut DragonState) {
+        let Some(target) = state
+            .phase
+            .attack_target
+            .and_then(|id| world.get_entity_by_id(id))
+        else {
+            log::warn!("Skipping player strafe phase because no player was found");
+            self.set_phase(state, DragonPhase::HoldingPattern);
+            return;
+        };
+        let pos = self.position();
+        let target_pos = target.position();
+
+        if state.phase.path.as_ref().is_some_and(DragonPath::is_done) {
+            let horizontal_dist = (target_pos.x - pos.x).hypot(target_pos.z - pos.z);
+            let height_offset = (0.4 + horizontal_dist / 80.0 - 1.0).min(10.0);
+            state.phase.target_location = Some(DVec3::new(
+                target_pos.x,
+                target_pos.y + height_offset,
+                target_pos.z,
+            ));
+        }
+
+        if state.phase.needs_new_target(pos) {
+            if state.phase.path.as_ref().is_none_or(DragonPath::is_done) {
+                let current = self.closest_node(world, state, pos);
+                let outer_ring = self.crystals_alive(world, state).is_some_and(|c| c > 0);
+                let next =
+                    next_circling_node(current, &mut state.phase.strafe_clockwise, outer_ring);
+                state.phase.path = self.find_path(world, state, current, next, None);
+                if let Some(path) = &mut state.phase.path {
+                    path.advance();
+                }
+            }
+            state.phase.navigate_to_next_node();
+        }
+
+        if target_pos.distance_squared(pos) >= 64.0 * 64.0
+            || !self.has_line_of_sight(world, &*target)
+        {
+            if state.phase.fireball_charge > 0 {
+                state.phase.fireball_charge -= 1;
+            }
+            return;
+        }
+        state.phase.fireball_charge += 1;
+        let angle = angle_to(pos, self.rotation().0, target_pos);
+        if state.phase.fireball_charge >= 5 && (0.0..10.0).contains(&angle) {
+            if !self.is_silent() {
+                world.level_event(
+                    level_events::SOUND_DRAGON_FIREBALL,
+                    self.block_position(),
+                    0,
+                    None,
+                );
+            }
+            // TODO: Shoot a dragon fireball from the head at the target
+            state.phase.fireball_charge = 0;
+            if let Some(path) = &mut state.phase.path {
+                path.finish();
+            }
+            self.set_phase(state, DragonPhase::HoldingPattern);
+        }
+    }
+
+    /// Vanilla: `DragonLandingApproachPhase`.
+    fn tick_landing_approach(&self, world: &Arc<World>, state: &mut DragonState) {
+        // TODO: Also pick a new target on collisions once the dragon tracks them
+        if !state.phase.needs_new_target(self.position()) {
+            return;
+        }
+        if state.phase.path.as_ref().is_none_or(DragonPath::is_done) {
+            let current = self.closest_node(world, state, self.position());
+            let egg = Self::egg_pos(world, HeightmapType::MotionBlockingNoLeaves);
+            let target = match self.nearest_player(world, corner(egg), None, false, |_| true) {
+                Some(player) => {
+                    let player_pos = player.position();
+                    let aim = DVec3::new(player_pos.x, 0.0, player_pos.z).normalize_or_zero();
+                    DVec3::new(-aim.x * 40.0, 105.0, -aim.z * 40.0)
+                }
+                None => DVec3::new(40.0, f64::from(egg.y()), 0.0),
+            };
+            let target = self.closest_node(world, state, target);
+            state.phase.path = self.find_path(world, state, current, target, Some(egg));
+            if let Some(path) = &mut state.phase.path {
+                path.advance();
+            }
+        }
+        state.phase.navigate_to_next_node();
+        if state.phase.path.as_ref().is_some_and(DragonPath::is_done) {
+            self.set_phase(state, DragonPhase::Landing);
+        }
+    }
+
+    /// Vanilla: `DragonLandingPhase`.
+    fn tick_landing(&self, world: &World, state: &mut DragonState) {
+        let target = *state.phase.target_location.get_or_insert_with(|| {
+            DVec3::from(
+                Self::egg_pos(world, HeightmapType::MotionBlockingNoLeaves).get_bottom_center(),
+            )
+        });
+        if target.distance_squared(self.position()) < 1.0 {
+            state.phase.flame_count = 0;
+            self.set_phase(state, DragonPhase::SittingScanning);
+        }
+    }
+
+    /// Vanilla: `DragonTakeoffPhase`.
+    fn tick_takeoff(&self, world: &World, state: &mut DragonState) {
+        if !state.phase.first_tick && state.phase.path.is_some() {
+            let egg = Self::egg_pos(world, HeightmapType::MotionBlockingNoLeaves);
+            if DVec3::from(egg.get_center()).distance_squared(self.position()) >= 10.0 * 10.0 {
+                self.set_phase(state, DragonPhase::HoldingPattern);
+            }
+            return;
+        }
+        state.phase.first_tick = false;
+
+        let current = self.closest_node(world, state, self.position());
+        let look = self.head_look_vector(world, state.phase.phase);
+        let target = self.closest_node(
+            world,
+            state,
+            DVec3::new(-look.x * 40.0, 105.0, -look.z * 40.0),
+        );
+        let outer_ring = self.crystals_alive(world, state).is_some_and(|c| c > 0);
+        let target = wrap_to_ring(target as i32, outer_ring);
+        state.phase.path = self.find_path(world, state, current, target, None);
+        if let Some(path) = &mut state.phase.path {
+            path.advance();
+            state.phase.navigate_to_next_node();
+        }
+    }
+
+    /// Vanilla: `DragonSittingFlamingPhase`.
+    fn tick_sitting_flaming(&self, state: &mut DragonState) {
+        state.phase.ticks += 1;
+        if state.phase.ticks >= 200 {
+            let next = if state.phase.flame_count >= 4 {
+                DragonPhase::Takeoff
+            } else {
+                DragonPhase::SittingScanning
+            };
+            self.set_phase(state, next);
+        } else if state.phase.ticks == 10 {
+            // TODO: Spawn the dragon breath cloud in front of the head
+        }
+    }
+
+    /// Vanilla: `DragonSittingScanningPhase`.
+    fn tick_sitting_scanning(&self, world: &Arc<World>, state: &mut DragonState) {
+        state.phase.ticks += 1;
+        let pos = self.position();
+        let target = self.nearest_player(world, pos, Some(20.0), true, |player| {
+            (player.position().y - pos.y).abs() <= 10.0
+        });
+
+        if let Some(target) = target {
+            if state.phase.ticks > 25 {
+                self.set_phase(state, DragonPhase::SittingAttacking);
+                return;
+            }
+            let target_pos = target.position();
+            let (yaw, pitch) = self.rotation();
+            let angle = angle_to(pos, yaw, target_pos);
+            if (0.0..=10.0).contains(&angle) {
+                return;
+            }
+            let head = state.parts[HEAD];
+            let xdd = target_pos.x - head.x;
+            let zdd = target_pos.z - head.z;
+            let y_rot_d = f64::from(wrap_degrees(
+                (180.0 - xdd.atan2(zdd).to_degrees()) as f32 - yaw,
+            ))
+            .clamp(-100.0, 100.0);
+            state.y_rot_a *= 0.8;
+            let dist = xdd.hypot(zdd) as f32 + 1.0;
+            let rot_speed = dist.min(40.0);
+            state.y_rot_a += y_rot_d as f32 * (0.7 / rot_speed / dist);
+            self.set_rotation((yaw + state.y_rot_a, pitch));
+        } else if state.phase.ticks >= 100 {
+            let target = self.nearest_player(world, pos, Some(150.0), true, |_| true);
+            self.set_phase(state, DragonPhase::Takeoff);
+            if let Some(target) = target {
+                self.set_phase(state, DragonPhase::ChargingPlayer);
+                state.phase.target_location = Some(target.position());
+            }
+        }
+    }
+
+    /// Vanilla: `DragonChargePlayerPhase`.
+    fn tick_charging_player(&self, state: &mut DragonState) {
+        let Some(target) = state.phase.target_location else {
+            log::warn!("Aborting charge player as no target was set.");
+            self.set_phase(state, DragonPhase::HoldingPattern);
+            return;
+        };
+        if state.phase.ticks > 0 {
+            state.phase.ticks += 1;
+            if state.phase.ticks > 10 {
+                self.set_phase(state, DragonPhase::HoldingPattern);
+                return;
+            }
+        }
+        // TODO: Also count collisions once the dragon tracks them
+        let dist = target.distance_squared(self.position());
+        if !(TARGET_REACHED_DIST_SQ..=TARGET_LOST_DIST_SQ).contains(&dist) {
+            state.phase.ticks += 1;
+        }
+    }
+
+    /// Flies to the exit portal, then lets the dragon die.
+    ///
+    /// Vanilla: `DragonDeathPhase`.
+    fn tick_dying(&self, world: &World, state: &mut DragonState) {
+        state.phase.ticks += 1;
+        let target = *state.phase.target_location.get_or_insert_with(|| {
+            DVec3::from(Self::egg_pos(world, HeightmapType::MotionBlocking).get_bottom_center())
+        });
+        let dist = target.distance_squared(self.position());
+        if (TARGET_REACHED_DIST_SQ..=TARGET_LOST_DIST_SQ).contains(&dist) {
+            self.set_health(1.0);
+        } else {
+            self.set_health(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phase_ids_round_trip() {
+        for phase in DragonPhase::ALL {
+            assert_eq!(DragonPhase::from_id(phase.id()), Some(phase));
+        }
+        assert_eq!(DragonPhase::Hovering.id(), 10);
+        assert_eq!(DragonPhase::from_id(11), None);
+    }
+
+    #[test]
+    fn flame_count_survives_phase_changes() {
+        let mut state = PhaseState::new(DragonPhase::SittingFlaming);
+        state.begin(DragonPhase::SittingScanning);
+        state.begin(DragonPhase::SittingFlaming);
+        assert_eq!(state.flame_count, 2);
+        assert_eq!(state.ticks, 0);
+    }
+}
@@ -0,0 +1,471 @@
+//! Falling block entity implementation.
+//!
+//! `FallingBlockEntity` is a block that lost its support (sand, gravel, ...).
+//! It falls with gravity and places itself back as a block when it lands,
+//! or drops as an item if it can't be placed.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Weak};
+
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::{CEntityPositionSync, CSetEntityMotion};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::fluid::is_water_fluid;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::FallingBlockEntityData;
+use steel_registry::vanilla_game_rules::ENTITY_DROPS;
+use steel_registry::{REGISTRY, RegistryExt, vanilla_blocks, vanilla_entities};
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, BlockStateId, ChunkPos, Identifier, types::UpdateFlags};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::behavior::blocks::FallingBlock;
+use crate::behavior::{BLOCK_BEHAVIORS, BlockStateBehaviorExt};
+use crate::entity::{Entity, EntityBase, RemovalReason, next_entity_id};
+use crate::fluid::fluid_state_to_block;
+use crate::physics::MoverType;
+use crate::world::World;
+
+/// Gravity applied per tick (blocks/tick^2). Vanilla: `FallingBlockEntity.getDefaultGravity()`
+const DEFAULT_GRAVITY: f64 = 0.04;
+
+/// Air drag multiplier per tick.
+const AIR_DRAG: f64 = 0.98;
+
+/// Ticks after which a falling block outside the build height is discarded.
+const OUT_OF_WORLD_TIME: i32 = 100;
+
+/// Ticks after which a falling block that never landed is discarded.
+const MAX_FALL_TIME: i32 = 600;
+
+/// A block falling because nothing supports it.
+///
+/// Mirrors vanilla's `FallingBlockEntity`:
+/// - Falls with gravity (0.04 per tick) and 0.98 drag
+/// - Places its block where it lands if the block there can be replaced
+/// - Drops its block as an item otherwise (if `DropItem` and the `entity_drops` rule allow)
+/// - Is discarded after 30 seconds in the air
+pub struct FallingBlockEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Whether the entity is on the ground.
+    on_ground: AtomicBool,
+    /// Synced entity data (the start position, used by the client for rendering).
+    entity_data: SyncMutex<FallingBlockEntityData>,
+    /// The block that is falling.
+    block_state: SyncMutex<BlockStateId>,
+    /// Ticks since the block started falling.
+    time: AtomicI32,
+    /// Whether the block drops as an item when it can't be placed.
+    drop_item: AtomicBool,
+    /// Whether the block is removed without placing or dropping it when it lands.
+    cancel_drop: AtomicBool,
+}
+
+impl FallingBlockEntity {
+    /// Creates a new falling sand entity (vanilla's default block for `/summon`).
+    ///
+    /// Use `with_block_state()` or `set_block_state()` to pick another block.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::with_block_state(id, position, vanilla_blocks::SAND.default_state(), world)
+    }
+
+    /// Creates a new falling block entity for the given block state.
+    #[must_use]
+    pub fn with_block_state(
+        id: i32,
+        position: DVec3,
+        block_state: BlockStateId,
+        world: Weak<World>,
+    ) -> Self {
+        let mut entity_data = FallingBlockEntityData::new();
+        entity_data.start_pos.set(block_pos_of(position));
+
+        Self {
+            base: EntityBase::new(id, position, world),
+            velocity: SyncMutex::new(DVec3::ZERO),
+            on_ground: AtomicBool::new(false),
+            entity_data: SyncMutex::new(entity_data),
+            block_state: SyncMutex::new(block_state),
+            time: AtomicI32::new(0),
+            drop_item: AtomicBool::new(true),
+            cancel_drop: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates a falling block entity from saved data with restored base state.
+    ///
+    /// Type-specific data (block state, time, ...) is restored via
+    /// `load_additional()` after this constructor.
+    #[must_use]
+    pub fn from_saved(
+        id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        on_ground: bool,
+        world: Weak<World>,
+    ) -> Self {
+        let mut entity_data = FallingBlockEntityData::new();
+        entity_data.start_pos.set(block_pos_of(position));
+
+        Self {
+            base: EntityBase::with_uuid(id, uuid, position, world),
+            velocity: SyncMutex::new(velocity),
+            on_ground: AtomicBool::new(on_ground),
+            entity_data: SyncMutex::new(entity_data),
+            block_state: SyncMutex::new(vanilla_blocks::SAND.default_state()),
+            time: AtomicI32::new(0),
+            drop_item: AtomicBool::new(true),
+            cancel_drop: AtomicBool::new(false),
+        }
+    }
+
+    /// Turns the block at `pos` into a falling block entity.
+    ///
+    /// The block is replaced by its fluid (or air), and the entity is added to the world.
+    /// Mirrors vanilla's `FallingBlockEntity.fall()`.
+    pub fn fall(world: &Arc<World>, pos: BlockPos, state: BlockStateId) -> Arc<Self> {
+        // The falling block leaves its water behind
+        let falling_state = if state
+            .try_get_value(&BlockStateProperties::WATERLOGGED)
+            .is_some()
+        {
+            state.set_value(&BlockStateProperties::WATERLOGGED, false)
+        } else {
+            state
+        };
+
+        let position = DVec3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()),
+            f64::from(pos.z()) + 0.5,
+        );
+        let entity = Arc::new(Self::with_block_state(
+            next_entity_id(),
+            position,
+            falling_state,
+            Arc::downgrade(world),
+        ));
+
+        world.set_block(
+            pos,
+            fluid_state_to_block(state.get_fluid_state()),
+            UpdateFlags::UPDATE_ALL,
+        );
+        world.add_entity(entity.clone());
+        entity
+    }
+
+    /// Gets the block state that is falling.
+    #[must_use]
+    pub fn get_block_state(&self) -> BlockStateId {
+        *self.block_state.lock()
+    }
+
+    /// Sets the block state that is falling.
+    ///
+    /// Only takes effect for players that start tracking the entity afterwards.
+    pub fn set_block_state(&self, state: BlockStateId) {
+        *self.block_state.lock() = state;
+    }
+
+    /// Sets whether the block drops as an item when it can't be placed.
+    pub fn set_drop_item(&self, drop_item: bool) {
+        self.drop_item.store(drop_item, Ordering::Relaxed);
+    }
+
+    /// Makes the block vanish when it lands instead of being placed or dropped.
+    pub fn disable_drop(&self) {
+        self.cancel_drop.store(true, Ordering::Relaxed);
+    }
+
+    /// Drops the falling block as an item if allowed.
+    fn drop_as_item(&self, world: &Arc<World>, state: BlockStateId) {
+        // TODO: Call `Fallable.onBrokenAfterFall` (anvils play a sound)
+        if !self.drop_item.load(Ordering::Relaxed)
+            || !world.get_game_rule(ENTITY_DROPS).as_bool().unwrap_or(true)
+        {
+            return;
+        }
+        if let Some(item) = REGISTRY.items.by_key(&state.get_block().key) {
+            self.spawn_at_location(ItemStack::new(item), 0.0);
+        }
+    }
+
+    /// Tries to place the block where the entity landed, dropping it otherwise.
+    ///
+    /// Mirrors the landing branch of vanilla's `FallingBlockEntity.tick()`.
+    fn land(&self, world: &Arc<World>, pos: BlockPos) {
+        let mut state = self.get_block_state();
+        let current = world.get_block_state(pos);
+        if current.get_block() == vanilla_blocks::MOVING_PISTON {
+            return;
+        }
+
+        self.set_removed(RemovalReason::Discarded);
+        if self.cancel_drop.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // TODO: Check `canBeReplaced` with a directional place context once it exists
+        let can_be_replaced = current.is_replaceable();
+        let below_is_free = FallingBlock::is_free(world.get_block_state(pos.below()));
+        let can_survive = !below_is_free
+            && BLOCK_BEHAVIORS
+                .get_behavior(state.get_block())
+                .can_survive(state, world, pos);
+
+        if !can_be_replaced || !can_survive {
+            self.drop_as_item(world, state);
+            return;
+        }
+
+        if state
+            .try_get_value(&BlockStateProperties::WATERLOGGED)
+            .is_some()
+            && is_water_fluid(current.get_fluid_state().fluid_id)
+        {
+            state = state.set_value(&BlockStateProperties::WATERLOGGED, true);
+        }
+
+        if world.set_block(pos, state, UpdateFlags::UPDATE_ALL) {
+            // TODO: Call `Fallable.onLand` (concrete powder, anvils) and restore block entity data
+        } else {
+            self.drop_as_item(world, state);
+        }
+    }
+}
+
+/// Returns the block position containing `position`.
+fn block_pos_of(position: DVec3) -> BlockPos {
+    BlockPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    )
+}
+
+impl Entity for FallingBlockEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::FALLING_BLOCK
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        let half_width = f64::from(dims.width) / 2.0;
+        let height = f64::from(dims.height);
+        AABBd {
+            min_x: pos.x - half_width,
+            min_y: pos.y,
+            min_z: pos.z - half_width,
+            max_x: pos.x + half_width,
+            max_y: pos.y + height,
+            max_z: pos.z + half_width,
+        }
+    }
+
+    fn tick(&self) {
+        if self.get_block_state().is_air() {
+            self.set_removed(RemovalReason::Discarded);
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let time = self.time.fetch_add(1, Ordering::Relaxed) + 1;
+        self.apply_gravity();
+        self.do_move(MoverType::SelfMovement);
+        // TODO: Apply effects from blocks and handle portals
+
+        let pos = block_pos_of(self.position());
+        if self.on_ground() {
+            let velocity = self.velocity();
+            self.set_velocity(DVec3::new(
+                velocity.x * 0.7,
+                velocity.y * -0.5,
+                velocity.z * 0.7,
+            ));
+            self.land(&world, pos);
+        } else if (time > OUT_OF_WORLD_TIME
+            && (pos.y() <= world.get_min_y() || pos.y() > world.get_max_y()))
+            || time > MAX_FALL_TIME
+        {
+            self.drop_as_item(&world, self.get_block_state());
+            self.set_removed(RemovalReason::Discarded);
+        }
+
+        self.set_velocity(self.velocity() * AIR_DRAG);
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        // The client simulates the fall itself; only resync on the update interval
+        if tick_count % self.entity_type().update_interval != 0 {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let pos = self.position();
+        let velocity = self.velocity();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CSetEntityMotion::new(self.id(), velocity.x, velocity.y, velocity.z),
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw: 0.0,
+                pitch: 0.0,
+                on_ground: self.on_ground(),
+            },
+            None,
+        );
+    }
+
+    fn spawn_data(&self) -> i32 {
+        i32::from(self.get_block_state().0)
+    }
+
+    fn get_default_gravity(&self) -> f64 {
+        DEFAULT_GRAVITY
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        *self.entity_data.lock().no_gravity.get()
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn on_ground(&self) -> bool {
+        self.on_ground.load(Ordering::Relaxed)
+    }
+
+    fn set_on_ground(&self, on_ground: bool) {
+        self.on_ground.store(on_ground, Ordering::Relaxed);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        // Match vanilla's FallingBlockEntity.addAdditionalSaveData
+        let state = self.get_block_state();
+        let mut block_state = NbtCompound::new();
+        block_state.insert("Name", state.get_block().key.to_string());
+        let properties = REGISTRY.blocks.get_properties(state);
+        if !properties.is_empty() {
+            let mut props = NbtCompound::new();
+            for (name, value) in properties {
+                props.insert(name, value);
+            }
+            block_state.insert("Properties", props);
+        }
+        nbt.insert("BlockState", block_state);
+        nbt.insert("Time", self.time.load(Ordering::Relaxed));
+        nbt.insert("DropItem", i8::from(self.drop_item.load(Ordering::Relaxed)));
+        nbt.insert(
+            "CancelDrop",
+            i8::from(self.cancel_drop.load(Ordering::Relaxed)),
+        );
+        // TODO: Save HurtEntities, FallHurtMax, FallHurtAmount and TileEntityData
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+
+        let state = nbt.compound("BlockState").and_then(|block_state| {
+            let name = block_state.string("Name")?.to_str();
+            let key: Identifier = name.parse().ok()?;
+            let properties: Vec<(String, String)> = block_state
+                .compound("Properties")
+                .map(|props| {
+                    props
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            Some((
+                                name.to_str().into_owned(),
+                                value.string()?.to_str().into_owned(),
+                            ))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let properties: Vec<(&str, &str)> = properties
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            REGISTRY.blocks.state_id_from_properties(&key, &properties)
+        });
+        // Vanilla falls back to sand for a missing or unknown block state
+        self.set_block_state(state.unwrap_or_else(|| vanilla_blocks::SAND.default_state()));
+
+        if let Some(time) = nbt.int("Time") {
+            self.time.store(time, Ordering::Relaxed);
+        }
+        if let Some(drop_item) = nbt.byte("DropItem") {
+            self.drop_item.store(drop_item != 0, Ordering::Relaxed);
+        }
+        if let Some(cancel_drop) = nbt.byte("CancelDrop") {
+            self.cancel_drop.store(cancel_drop != 0, Ordering::Relaxed);
+        }
+    }
+}
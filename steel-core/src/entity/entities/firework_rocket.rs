@@ -0,0 +1,568 @@
+//! Firework rocket entity implementation.
+//!
+//! `FireworkRocketEntity` is a rocket launched from a firework item. It flies
+//! up (or along its launch direction when shot from a crossbow) and explodes
+//! when its lifetime runs out or it hits something. Rockets launched by a
+//! gliding player stay attached to them and boost their flight instead.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Weak};
+
+use crossbeam::atomic::AtomicCell;
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{CEntityEvent, CEntityPositionSync, CSetEntityMotion};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::data_components::vanilla_components::FIREWORKS;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entity_data::FireworkRocketEntityData;
+use steel_registry::{sound_events, vanilla_damage_types, vanilla_entities};
+use steel_utils::entity_events::EntityStatus;
+use steel_utils::locks::SyncMutex;
+use steel_utils::{ChunkPos, UuidExt};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::entity::damage::DamageSource;
+use crate::entity::projectile::{
+    Projectile, ProjectileHit, ProjectileLauncher, clip_blocks, expand_towards, find_hit,
+};
+use crate::entity::{Entity, EntityBase, RemovalReason, SharedEntity};
+use crate::physics::MoverType;
+use crate::world::World;
+
+/// Upwards acceleration per tick of a rocket that isn't shot at an angle.
+const UPWARDS_ACCELERATION: f64 = 0.04;
+
+/// Horizontal speed multiplier per tick of a rocket that isn't shot at an angle.
+const HORIZONTAL_ACCELERATION: f64 = 1.15;
+
+/// How far the explosion damages nearby entities.
+const EXPLOSION_RADIUS: f64 = 5.0;
+
+/// A firework rocket.
+///
+/// Mirrors vanilla's `FireworkRocketEntity`:
+/// - Flies for `10 * (1 + flight_duration)` plus up to 11 random ticks
+/// - Explodes on hitting an entity, or a block if it has any explosions
+/// - Deals `5 + 2 * explosions` damage to whoever it's attached to and less to
+///   living entities within 5 blocks
+/// - Boosts the gliding entity it's attached to towards where it's looking
+pub struct FireworkRocketEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Rotation as (yaw, pitch) in degrees, following the flight direction.
+    rotation: AtomicCell<(f32, f32)>,
+    /// Synced entity data (firework item, attached entity, shot at angle).
+    entity_data: SyncMutex<FireworkRocketEntityData>,
+    /// UUID of the entity that launched the rocket.
+    owner: SyncMutex<Option<Uuid>>,
+    /// Whether the rocket has moved away from its owner and may hit it.
+    left_owner: AtomicBool,
+    /// Ticks the rocket has been flying.
+    life: AtomicI32,
+    /// Ticks after which the rocket explodes.
+    lifetime: AtomicI32,
+    /// Whether the last move ran into a wall, which stops horizontal acceleration.
+    horizontal_collision: AtomicBool,
+    /// Whether position/velocity needs to be synced to clients right away.
+    needs_sync: AtomicBool,
+}
+
+impl FireworkRocketEntity {
+    /// Creates a rocket without any explosions that isn't moving.
+    ///
+    /// Use [`Self::set_item`] to give it the firework item it was launched from.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::from_parts(
+            EntityBase::new(id, position, world),
+            DVec3::ZERO,
+            (0.0, 0.0),
+        )
+    }
+
+    /// Creates a rocket from saved data with restored base state.
+    ///
+    /// Type-specific data (item, life, owner, ...) is restored via
+    /// `load_additional()` after this constructor.
+    #[must_use]
+    pub fn from_saved(
+        id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        world: Weak<World>,
+    ) -> Self {
+        Self::from_parts(
+            EntityBase::with_uuid(id, uuid, position, world),
+            velocity,
+            rotation,
+        )
+    }
+
+    fn from_parts(base: EntityBase, velocity: DVec3, rotation: (f32, f32)) -> Self {
+        let rocket = Self {
+            base,
+            velocity: SyncMutex::new(velocity),
+            rotation: AtomicCell::new(rotation),
+            entity_data: SyncMutex::new(FireworkRocketEntityData::new()),
+            owner: SyncMutex::new(None),
+            left_owner: AtomicBool::new(false),
+            life: AtomicI32::new(0),
+            lifetime: AtomicI32::new(0),
+            horizontal_collision: AtomicBool::new(false),
+            needs_sync: AtomicBool::new(false),
+        };
+        rocket.roll_lifetime(0);
+        rocket
+    }
+
+    /// Creates a rocket launched straight up from `position`, like one used on a block.
+    ///
+    /// Vanilla: `FireworkRocketEntity(Level, double, double, double, ItemStack)`.
+    #[must_use]
+    pub fn launched(id: i32, position: DVec3, world: Weak<World>, item: &ItemStack) -> Self {
+        let rocket = Self::new(id, position, world);
+        rocket.set_item(item);
+        let spread = || 0.002_297 * (rand::random::<f64>() - rand::random::<f64>());
+        rocket.set_velocity(DVec3::new(spread(), 0.05, spread()));
+        rocket
+    }
+
+    /// Creates a rocket attached to `target`, boosting it while it glides.
+    ///
+    /// The target also becomes the rocket's owner.
+    ///
+    /// Vanilla: `FireworkRocketEntity(Level, ItemStack, LivingEntity)`.
+    #[must_use]
+    pub fn attached(id: i32, target: &dyn Entity, world: Weak<World>, item: &ItemStack) -> Self {
+        let rocket = Self::launched(id, target.position(), world, item);
+        rocket.set_owner(Some(target.uuid()));
+        rocket
+            .entity_data
+            .lock()
+            .attached_to_target
+            .set(Some(target.id() as u32));
+        rocket
+    }
+
+    /// Sets the firework item the rocket was launched from and rolls a new
+    /// lifetime from its flight duration.
+    pub fn set_item(&self, item: &ItemStack) {
+        let flight_duration = item
+            .get(FIREWORKS)
+            .map_or(0, |fireworks| fireworks.flight_duration);
+        self.entity_data
+            .lock()
+            .fireworks_item
+            .set(item.copy_with_count(1));
+        self.roll_lifetime(flight_duration);
+    }
+
+    /// Returns the number of explosions on the rocket's firework item.
+    fn explosion_count(&self) -> usize {
+        self.entity_data
+            .lock()
+            .fireworks_item
+            .get()
+            .get(FIREWORKS)
+            .map_or(0, |fireworks| fireworks.explosions.len())
+    }
+
+    /// Vanilla: the `lifetime` assignment in the `FireworkRocketEntity` constructor.
+    fn roll_lifetime(&self, flight_duration: i32) {
+        let lifetime =
+            10 * (1 + flight_duration) + rand::random_range(0..6) + rand::random_range(0..7);
+        self.lifetime.store(lifetime, Ordering::Relaxed);
+    }
+
+    /// Returns true if the rocket flies along its launch direction instead of
+    /// accelerating upwards, like rockets shot from a crossbow.
+    #[must_use]
+    pub fn is_shot_at_angle(&self) -> bool {
+        *self.entity_data.lock().shot_at_angle.get()
+    }
+
+    /// Sets whether the rocket flies along its launch direction.
+    pub fn set_shot_at_angle(&self, shot_at_angle: bool) {
+        self.entity_data.lock().shot_at_angle.set(shot_at_angle);
+    }
+
+    /// Returns the id of the entity the rocket is attached to, if any.
+    fn attached_to(&self) -> Option<i32> {
+        self.entity_data
+            .lock()
+            .attached_to_target
+            .get()
+            .map(|id| id as i32)
+    }
+
+    /// Vanilla: `Projectile.updateRotation()` without the interpolation.
+    fn update_rotation(&self) {
+        let velocity = self.velocity();
+        let horizontal = velocity.x.hypot(velocity.z);
+        self.rotation.store((
+            velocity.x.atan2(velocity.z).to_degrees() as f32,
+            velocity.y.atan2(horizontal).to_degrees() as f32,
+        ));
+    }
+
+    /// Lets the rocket hit its owner once it no longer overlaps it.
+    ///
+    /// Vanilla: `Projectile.checkLeftOwner()`.
+    fn check_left_owner(&self) {
+        if self.left_owner.load(Ordering::Relaxed) {
+            return;
+        }
+        let path = expand_towards(&self.bounding_box(), self.velocity()).inflate(1.0);
+        let touches_owner = self
+            .owner_entity()
+            .is_some_and(|owner| owner.bounding_box().intersects(&path));
+        if !touches_owner {
+            self.left_owner.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Follows the attached entity and boosts it if it's gliding.
+    ///
+    /// Vanilla: the `attachedToEntity` branch of `FireworkRocketEntity.tick()`.
+    fn tick_attached(&self, world: &World, target_id: i32) {
+        // TODO: Boost other gliding living entities once they exist
+        let Some(target) = world
+            .get_entity_by_id(target_id)
+            .and_then(|entity| entity.as_player())
+        else {
+            return;
+        };
+
+        if target.is_fall_flying() {
+            let (yaw, pitch) = target.rotation();
+            let look = ProjectileLauncher::velocity_from_rotation(yaw, pitch, 1.0, 0.0);
+            let motion = target.get_delta_movement();
+            target.set_delta_movement(motion + look * 0.1 + (look * 1.5 - motion) * 0.5);
+            // The client applies the boost itself, this keeps movement checks happy
+            target.apply_impulse();
+        }
+        // TODO: Offset to the hand holding the rocket (getHandHoldingItemAngle)
+        self.set_position(target.position());
+        self.set_velocity(target.get_delta_movement());
+    }
+
+    /// Flies the rocket and explodes it if it hits something.
+    ///
+    /// Vanilla: the free-flying branch of `FireworkRocketEntity.tick()`.
+    fn tick_free(&self, world: &World) {
+        if !self.is_shot_at_angle() {
+            let horizontal = if self.horizontal_collision.load(Ordering::Relaxed) {
+                1.0
+            } else {
+                HORIZONTAL_ACCELERATION
+            };
+            let velocity = self.velocity() * DVec3::new(horizontal, 1.0, horizontal);
+            self.set_velocity(velocity + DVec3::new(0.0, UPWARDS_ACCELERATION, 0.0));
+        }
+
+        let velocity = self.velocity();
+        let from = self.position();
+        let hit = find_hit(world, self, from, from + velocity, |target| {
+            self.can_hit_entity(target)
+        });
+        match hit {
+            Some(ProjectileHit::Entity { location, .. }) => {
+                self.set_position(location);
+                self.explode(world);
+                return;
+            }
+            Some(ProjectileHit::Block { location, .. }) if self.explosion_count() > 0 => {
+                self.set_position(location);
+                self.explode(world);
+                return;
+            }
+            // TODO: Block.onProjectileHit (targets, bells, chorus flowers, ...)
+            _ => {}
+        }
+
+        if let Some(result) = self.move_by(MoverType::SelfMovement, velocity) {
+            self.horizontal_collision
+                .store(result.horizontal_collision, Ordering::Relaxed);
+        }
+        // Vanilla keeps the velocity from before the move, so rockets slide along walls
+        self.set_velocity(velocity);
+        self.update_rotation();
+    }
+
+    /// Returns true if the rocket may hit `target`.
+    ///
+    /// Vanilla: `Projectile.canHitEntity()`.
+    fn can_hit_entity(&self, target: &dyn Entity) -> bool {
+        // TODO: Skip spectators and the owner's vehicle
+        self.left_owner.load(Ordering::Relaxed) || self.owner() != Some(target.uuid())
+    }
+
+    /// Shows the explosion to nearby players, damages entities around it and
+    /// removes the rocket.
+    ///
+    /// Vanilla: `FireworkRocketEntity.explode()`.
+    fn explode(&self, world: &World) {
+        let pos = self.position();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityEvent {
+                entity_id: self.id(),
+                event: EntityStatus::FireworksExplode,
+            },
+            None,
+        );
+        // TODO: Emit GameEvent::EXPLODE
+        self.deal_explosion_damage(world);
+        self.set_removed(RemovalReason::Discarded);
+    }
+
+    /// Vanilla: `FireworkRocketEntity.dealExplosionDamage()`.
+    fn deal_explosion_damage(&self, world: &World) {
+        let explosions = self.explosion_count();
+        if explosions == 0 {
+            return;
+        }
+        let damage = 5.0 + explosions as f32 * 2.0;
+        let owner_id = self.owner_entity().map(|owner| owner.id());
+        let source = DamageSource::projectile(vanilla_damage_types::FIREWORKS, self.id(), owner_id);
+
+        let attached = self.attached_to();
+        if let Some(target) = attached.and_then(|id| world.get_entity_by_id(id)) {
+            target.take_damage(&source, damage);
+        }
+
+        let pos = self.position();
+        let search = self.bounding_box().inflate(EXPLOSION_RADIUS);
+        for entity in world.get_entities_in_aabb(&search) {
+            if Some(entity.id()) == attached || entity.id() == self.id() || !is_living(&entity) {
+                continue;
+            }
+            let distance = entity.position().distance(pos);
+            if distance > EXPLOSION_RADIUS {
+                continue;
+            }
+            // The explosion needs a clear line to the target's feet or middle
+            let target_pos = entity.position();
+            let height = entity.bounding_box().max_y - entity.bounding_box().min_y;
+            let visible = [0.0, 0.5].into_iter().any(|fraction| {
+                let to = DVec3::new(target_pos.x, target_pos.y + height * fraction, target_pos.z);
+                clip_blocks(world, pos, to).is_none()
+            });
+            if visible {
+                let scale = ((EXPLOSION_RADIUS - distance) / EXPLOSION_RADIUS).sqrt() as f32;
+                entity.take_damage(&source, damage * scale);
+            }
+        }
+    }
+}
+
+/// Returns true if `entity` is a living entity that explosions can hurt.
+fn is_living(entity: &SharedEntity) -> bool {
+    entity.clone().as_player().is_some() || entity.clone().as_mob().is_some()
+}
+
+impl Projectile for FireworkRocketEntity {
+    fn owner(&self) -> Option<Uuid> {
+        *self.owner.lock()
+    }
+
+    fn set_owner(&self, owner: Option<Uuid>) {
+        *self.owner.lock() = owner;
+        self.left_owner.store(owner.is_none(), Ordering::Relaxed);
+    }
+
+    fn shoot(&self, velocity: DVec3) {
+        self.set_velocity(velocity);
+        self.update_rotation();
+        self.needs_sync.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Entity for FireworkRocketEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::FIREWORK_ROCKET
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    fn tick(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        self.check_left_owner();
+
+        if let Some(target_id) = self.attached_to() {
+            self.tick_attached(&world, target_id);
+        } else {
+            self.tick_free(&world);
+            if self.is_removed() {
+                return;
+            }
+        }
+
+        let life = self.life.fetch_add(1, Ordering::Relaxed);
+        if life == 0 {
+            self.play_sound(sound_events::ENTITY_FIREWORK_ROCKET_LAUNCH, 3.0, 1.0);
+        }
+        if life + 1 > self.lifetime.load(Ordering::Relaxed) {
+            self.explode(&world);
+        }
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        // The client simulates the flight itself; resync on the update interval
+        // and right away when the rocket is shot
+        let needs_sync = self.needs_sync.swap(false, Ordering::Relaxed);
+        if tick_count % self.entity_type().update_interval != 0 && !needs_sync {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let pos = self.position();
+        let velocity = self.velocity();
+        let (yaw, pitch) = self.rotation.load();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CSetEntityMotion::new(self.id(), velocity.x, velocity.y, velocity.z),
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: false,
+            },
+            None,
+        );
+    }
+
+    fn spawn_data(&self) -> i32 {
+        // Vanilla: Projectile.getAddEntityPacket() sends the owner's id
+        self.owner_entity().map_or(0, |owner| owner.id())
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        *self.entity_data.lock().no_gravity.get()
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn rotation(&self) -> (f32, f32) {
+        self.rotation.load()
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        // Match vanilla's FireworkRocketEntity.addAdditionalSaveData
+        nbt.insert("Life", self.life.load(Ordering::Relaxed));
+        nbt.insert("LifeTime", self.lifetime.load(Ordering::Relaxed));
+        let item = self.entity_data.lock().fireworks_item.get().clone();
+        if !item.is_empty() {
+            nbt.insert("FireworksItem", item.to_nbt_tag());
+        }
+        nbt.insert("ShotAtAngle", i8::from(self.is_shot_at_angle()));
+        // Projectile.addAdditionalSaveData
+        if let Some(owner) = self.owner() {
+            nbt.insert("Owner", NbtTag::IntArray(owner.to_int_array().to_vec()));
+        }
+        nbt.insert(
+            "LeftOwner",
+            i8::from(self.left_owner.load(Ordering::Relaxed)),
+        );
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+
+        if let Some(item_tag) = nbt.compound("FireworksItem")
+            && let Some(item) = ItemStack::from_borrowed_compound(&item_tag)
+        {
+            self.set_item(&item);
+        }
+        if let Some(life) = nbt.int("Life") {
+            self.life.store(life, Ordering::Relaxed);
+        }
+        if let Some(lifetime) = nbt.int("LifeTime") {
+            self.lifetime.store(lifetime, Ordering::Relaxed);
+        }
+        self.set_shot_at_angle(nbt.byte("ShotAtAngle").is_some_and(|angle| angle != 0));
+        if let Some(owner) = nbt.int_array("Owner")
+            && let Some(uuid) = Uuid::from_int_array(&owner)
+        {
+            *self.owner.lock() = Some(uuid);
+        }
+        self.left_owner.store(
+            nbt.byte("LeftOwner").is_some_and(|left| left != 0),
+            Ordering::Relaxed,
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Interaction entity implementation.
+//!
+//! Interaction entities are invisible hitboxes map makers place to detect
+//! players clicking somewhere. They remember the last player who attacked and
+//! who interacted with them, which commands can read with `/data`.
+
+use std::sync::{Arc, Weak};
+
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_entities;
+use steel_registry::vanilla_entity_data::InteractionEntityData;
+use steel_utils::UuidExt;
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::InteractionHand;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::behavior::InteractionResult;
+use crate::entity::{Entity, EntityBase};
+use crate::player::Player;
+use crate::world::World;
+
+/// A player clicking an interaction entity.
+///
+/// Vanilla: `Interaction.PlayerAction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerAction {
+    /// The player who clicked.
+    pub player: Uuid,
+    /// The game time of the click.
+    pub timestamp: i64,
+}
+
+impl PlayerAction {
+    fn save(self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.insert(
+            "player",
+            NbtTag::IntArray(self.player.to_int_array().to_vec()),
+        );
+        nbt.insert("timestamp", self.timestamp);
+        nbt
+    }
+
+    fn load(nbt: &NbtCompoundView<'_, '_>) -> Option<Self> {
+        Some(Self {
+            player: Uuid::from_int_array(&nbt.int_array("player")?)?,
+            timestamp: nbt.long("timestamp")?,
+        })
+    }
+}
+
+/// An invisible, resizable hitbox that records clicks.
+///
+/// Mirrors vanilla's `Interaction`.
+pub struct InteractionEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Synced entity data (width, height, response).
+    entity_data: SyncMutex<InteractionEntityData>,
+    /// The last player who attacked this entity.
+    attack: SyncMutex<Option<PlayerAction>>,
+    /// The last player who interacted with this entity.
+    interaction: SyncMutex<Option<PlayerAction>>,
+}
+
+impl InteractionEntity {
+    /// Creates a new 1x1 interaction entity.
+    ///
+    /// The `id` should be obtained from `next_entity_id()`.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::from_base(EntityBase::new(id, position, world))
+    }
+
+    /// Creates an interaction entity from saved data.
+    ///
+    /// Type-specific data (size, recorded clicks) is restored via
+    /// `load_additional()` after this constructor.
+    #[must_use]
+    pub fn from_saved(id: i32, position: DVec3, uuid: Uuid, world: Weak<World>) -> Self {
+        Self::from_base(EntityBase::with_uuid(id, uuid, position, world))
+    }
+
+    fn from_base(base: EntityBase) -> Self {
+        Self {
+            base,
+            entity_data: SyncMutex::new(InteractionEntityData::new()),
+            attack: SyncMutex::new(None),
+            interaction: SyncMutex::new(None),
+        }
+    }
+
+    /// Gets the last player who interacted with this entity.
+    #[must_use]
+    pub fn interaction(&self) -> Option<PlayerAction> {
+        *self.interaction.lock()
+    }
+
+    /// Gets the last player who attacked this entity.
+    // TODO: Record attacks once attack packets are handled (Interaction.skipAttackInteraction)
+    #[must_use]
+    pub fn attack(&self) -> Option<PlayerAction> {
+        *self.attack.lock()
+    }
+}
+
+impl Entity for InteractionEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::INTERACTION
+    }
+
+    /// Vanilla: `Interaction.makeBoundingBox()`, scaled by the synced width and height.
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let (width, height) = {
+            let entity_data = self.entity_data.lock();
+            (*entity_data.width.get(), *entity_data.height.get())
+        };
+        let half_width = f64::from(width) / 2.0;
+        AABBd::new(
+            pos.x - half_width,
+            pos.y,
+            pos.z - half_width,
+            pos.x + half_width,
+            pos.y + f64::from(height),
+            pos.z + half_width,
+        )
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    /// Vanilla: `Interaction.interact()`.
+    fn interact(
+        self: Arc<Self>,
+        player: &Arc<Player>,
+        _hand: InteractionHand,
+    ) -> InteractionResult {
+        let Some(world) = self.level() else {
+            return InteractionResult::Pass;
+        };
+        let timestamp = world.level_data.read().game_time();
+        *self.interaction.lock() = Some(PlayerAction {
+            player: player.uuid(),
+            timestamp,
+        });
+        // TODO: Trigger the player_interacted_with_entity advancement criterion
+        InteractionResult::Success
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        {
+            let entity_data = self.entity_data.lock();
+            nbt.insert("width", *entity_data.width.get());
+            nbt.insert("height", *entity_data.height.get());
+            nbt.insert("response", i8::from(*entity_data.response.get()));
+        }
+        if let Some(attack) = *self.attack.lock() {
+            nbt.insert("attack", NbtTag::Compound(attack.save()));
+        }
+        if let Some(interaction) = *self.interaction.lock() {
+            nbt.insert("interaction", NbtTag::Compound(interaction.save()));
+        }
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+        {
+            let mut entity_data = self.entity_data.lock();
+            entity_data.width.set(nbt.float("width").unwrap_or(1.0));
+            entity_data.height.set(nbt.float("height").unwrap_or(1.0));
+            entity_data
+                .response
+                .set(nbt.byte("response").is_some_and(|response| response != 0));
+        }
+        *self.attack.lock() = nbt
+            .compound("attack")
+            .and_then(|attack| PlayerAction::load(&attack));
+        *self.interaction.lock() = nbt
+            .compound("interaction")
+            .and_then(|interaction| PlayerAction::load(&interaction));
+    }
+}
@@ -17,6 +17,7 @@ use steel_registry::vanilla_entities;
 use steel_registry::vanilla_entity_data::ItemEntityData;
 use steel_utils::UuidExt;
 use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
 use uuid::Uuid;
 
 use crate::entity::damage::DamageSource;
@@ -456,15 +457,10 @@ impl ItemEntity {
         from_item: &ItemEntity,
         from_stack: &ItemStack,
     ) {
-        // Calculate how many items to transfer
-        let max_count = to_stack.max_stack_size();
-        let space_available = max_count - to_stack.count();
-        let transfer_count = space_available.min(from_stack.count());
-
-        // Create new stacks
-        let new_to_stack = to_stack.copy_with_count(to_stack.count() + transfer_count);
+        // Move as many items as fit into the destination stack
+        let mut new_to_stack = to_stack.clone();
         let mut new_from_stack = from_stack.clone();
-        new_from_stack.shrink(transfer_count);
+        new_to_stack.merge_from(&mut new_from_stack, i32::MAX);
 
         // Update the destination item
         to_item.set_item(new_to_stack);
@@ -914,6 +910,27 @@ impl Entity for ItemEntity {
         self.entity_data.lock().pack_all()
     }
 
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
     fn rotation(&self) -> (f32, f32) {
         self.rotation.load()
     }
@@ -0,0 +1,235 @@
+//! Leash knot entity implementation.
+//!
+//! The knot a lead makes around a fence. Knots are never saved, the entities
+//! tied to them save the fence position and recreate the knot on load.
+
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Weak};
+
+use glam::DVec3;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::sound_events::{ITEM_LEAD_TIED, ITEM_LEAD_UNTIED};
+use steel_registry::vanilla_block_tags::FENCES_TAG;
+use steel_registry::vanilla_entity_data::LeashKnotEntityData;
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_entities, vanilla_items};
+use steel_utils::BlockPos;
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::InteractionHand;
+use text_components::TextComponent;
+
+use crate::behavior::InteractionResult;
+use crate::entity::damage::DamageSource;
+use crate::entity::{Entity, EntityBase, RemovalReason, SharedEntity, leash, next_entity_id};
+use crate::player::Player;
+use crate::world::World;
+
+/// Ticks between checks whether the fence is still there.
+///
+/// Vanilla: `BlockAttachedEntity.tick()`.
+const SURVIVAL_CHECK_INTERVAL: i32 = 100;
+
+/// A leash knot tied to a fence.
+///
+/// Mirrors vanilla's `LeashFenceKnotEntity`.
+pub struct LeashFenceKnotEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Synced entity data for network serialization.
+    entity_data: SyncMutex<LeashKnotEntityData>,
+    /// Ticks since the fence was last checked.
+    check_interval: AtomicI32,
+}
+
+impl LeashFenceKnotEntity {
+    /// Creates a new knot on the fence at `pos`.
+    ///
+    /// The `id` should be obtained from `next_entity_id()`.
+    #[must_use]
+    pub fn new(id: i32, pos: BlockPos, world: Weak<World>) -> Self {
+        // Vanilla: BlockAttachedEntity.recalculateBoundingBox()
+        let position = DVec3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()) + 0.375,
+            f64::from(pos.z()) + 0.5,
+        );
+        Self {
+            base: EntityBase::new(id, position, world),
+            entity_data: SyncMutex::new(LeashKnotEntityData::new()),
+            check_interval: AtomicI32::new(0),
+        }
+    }
+
+    /// Gets the position of the fence this knot is tied to.
+    #[must_use]
+    pub fn block_pos(&self) -> BlockPos {
+        let pos = self.position();
+        BlockPos::containing(pos.x, pos.y, pos.z)
+    }
+
+    /// Gets the knot on the fence at `pos`, placing a new one if there is none.
+    ///
+    /// Vanilla: `LeashFenceKnotEntity.getOrCreateKnot()`.
+    pub fn get_or_create(world: &Arc<World>, pos: BlockPos) -> SharedEntity {
+        let (x, y, z) = (f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+        let search = AABBd::new(x - 1.0, y - 1.0, z - 1.0, x + 1.0, y + 1.0, z + 1.0);
+        let existing = world
+            .get_entities_in_aabb(&search)
+            .into_iter()
+            .find(|entity| {
+                entity.entity_type().key == vanilla_entities::LEASH_KNOT.key
+                    && !entity.is_removed()
+                    && BlockPos::containing(
+                        entity.position().x,
+                        entity.position().y,
+                        entity.position().z,
+                    ) == pos
+            });
+        if let Some(knot) = existing {
+            return knot;
+        }
+
+        let knot: SharedEntity = Arc::new(Self::new(next_entity_id(), pos, Arc::downgrade(world)));
+        if !world.add_entity(knot.clone()) {
+            // Refused by the chunk's entity cap, the leashes drop on their next tick
+            knot.set_removed(RemovalReason::Discarded);
+        }
+        knot
+    }
+
+    /// Returns true if the knot is still tied to a fence.
+    ///
+    /// Vanilla: `LeashFenceKnotEntity.survives()`.
+    fn survives(&self, world: &World) -> bool {
+        let state = world.get_block_state(self.block_pos());
+        REGISTRY.blocks.is_in_tag(state.get_block(), &FENCES_TAG)
+    }
+
+    /// Removes the knot, letting the entities tied to it drop their leads.
+    ///
+    /// Vanilla: `BlockAttachedEntity.kill()` followed by `LeashFenceKnotEntity.dropItem()`.
+    fn break_knot(&self) {
+        self.set_removed(RemovalReason::Killed);
+        self.play_sound(ITEM_LEAD_UNTIED, 1.0, 1.0);
+    }
+}
+
+impl Entity for LeashFenceKnotEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::LEASH_KNOT
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dimensions = vanilla_entities::LEASH_KNOT.dimensions;
+        let half_width = f64::from(dimensions.width) / 2.0;
+        AABBd::new(
+            pos.x - half_width,
+            pos.y,
+            pos.z - half_width,
+            pos.x + half_width,
+            pos.y + f64::from(dimensions.height),
+            pos.z + half_width,
+        )
+    }
+
+    /// Vanilla: `BlockAttachedEntity.tick()`.
+    fn tick(&self) {
+        if self.check_interval.fetch_add(1, Ordering::Relaxed) != SURVIVAL_CHECK_INTERVAL {
+            return;
+        }
+        self.check_interval.store(0, Ordering::Relaxed);
+        if let Some(world) = self.level()
+            && !self.is_removed()
+            && !self.survives(&world)
+        {
+            self.break_knot();
+        }
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    /// Vanilla: `BlockAttachedEntity.hurtServer()`.
+    fn hurt(&self, _source: &DamageSource, _amount: f32) -> bool {
+        // TODO: Ignore mobs while the `mob_griefing` rule is off
+        if !self.is_removed() {
+            self.break_knot();
+        }
+        true
+    }
+
+    /// Ties the entities the player holds to this knot, or hands the entities tied
+    /// to it over to the player.
+    ///
+    /// Vanilla: `LeashFenceKnotEntity.interact()`.
+    fn interact(
+        self: Arc<Self>,
+        player: &Arc<Player>,
+        _hand: InteractionHand,
+    ) -> InteractionResult {
+        // TODO: Shears cut the leashes attached to the knot
+        let knot: SharedEntity = self.clone();
+        let mut attached = false;
+        for entity in leash::leashed_to(player.as_ref()) {
+            if leash::can_attach_to(entity.as_ref(), knot.as_ref()) {
+                leash::set_leashed_to(entity.as_ref(), &knot);
+                attached = true;
+            }
+        }
+
+        let mut handed_over = false;
+        if !attached && !player.is_secondary_use_active() {
+            let holder: SharedEntity = player.clone();
+            for entity in leash::leashed_to(knot.as_ref()) {
+                if leash::can_attach_to(entity.as_ref(), player.as_ref()) {
+                    leash::set_leashed_to(entity.as_ref(), &holder);
+                    handed_over = true;
+                }
+            }
+        }
+
+        if !attached && !handed_over {
+            return InteractionResult::Pass;
+        }
+        self.play_sound(ITEM_LEAD_TIED, 1.0, 1.0);
+        InteractionResult::Success
+    }
+
+    fn get_pick_result(&self) -> Option<ItemStack> {
+        Some(ItemStack::new(&vanilla_items::ITEMS.lead))
+    }
+}
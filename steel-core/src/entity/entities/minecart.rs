@@ -0,0 +1,689 @@
+//! Minecart entity implementation.
+//!
+//! `MinecartEntity` is the rideable minecart. It follows rails on the server
+//! using vanilla's original rail physics (`OldMinecartBehavior`); riders can
+//! only give it a gentle push.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+use crossbeam::atomic::AtomicCell;
+use glam::{DVec3, IVec3};
+use steel_protocol::packets::game::{CEntityPositionSync, CSetEntityMotion};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, RailShape};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::DataValue;
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_block_tags::RAILS_TAG;
+use steel_registry::vanilla_entity_data::MinecartEntityData;
+use steel_registry::{REGISTRY, TaggedRegistryExt, vanilla_blocks, vanilla_entities};
+use steel_utils::locks::SyncMutex;
+use steel_utils::math::wrap_degrees;
+use steel_utils::types::InteractionHand;
+use steel_utils::{BlockPos, BlockStateId, ChunkPos};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::behavior::InteractionResult;
+use crate::entity::damage::DamageSource;
+use crate::entity::{Entity, EntityBase, SharedEntity, VehicleEntity, riding};
+use crate::fluid::{get_fluid_state, get_height, is_water_fluid};
+use crate::physics::MoverType;
+use crate::player::Player;
+use crate::world::World;
+use crate::world::redstone::is_redstone_conductor;
+
+/// Gravity applied per tick (blocks/tick^2). Vanilla: `AbstractMinecart.getDefaultGravity()`
+const DEFAULT_GRAVITY: f64 = 0.04;
+
+/// Gravity applied per tick while in water.
+const WATER_GRAVITY: f64 = 0.005;
+
+/// How much a slope accelerates the minecart per tick.
+const SLOPE_SPEED: f64 = 0.007_812_5;
+
+/// Acceleration per tick on a powered rail.
+const POWERED_RAIL_BOOST: f64 = 0.06;
+
+/// Returns the two block offsets a rail of `shape` connects.
+///
+/// Vanilla: `AbstractMinecart.EXITS`.
+const fn exits(shape: &RailShape) -> (IVec3, IVec3) {
+    match shape {
+        RailShape::NorthSouth => (IVec3::new(0, 0, -1), IVec3::new(0, 0, 1)),
+        RailShape::EastWest => (IVec3::new(-1, 0, 0), IVec3::new(1, 0, 0)),
+        RailShape::AscendingEast => (IVec3::new(-1, -1, 0), IVec3::new(1, 0, 0)),
+        RailShape::AscendingWest => (IVec3::new(-1, 0, 0), IVec3::new(1, -1, 0)),
+        RailShape::AscendingNorth => (IVec3::new(0, 0, -1), IVec3::new(0, -1, 1)),
+        RailShape::AscendingSouth => (IVec3::new(0, -1, -1), IVec3::new(0, 0, 1)),
+        RailShape::SouthEast => (IVec3::new(0, 0, 1), IVec3::new(1, 0, 0)),
+        RailShape::SouthWest => (IVec3::new(0, 0, 1), IVec3::new(-1, 0, 0)),
+        RailShape::NorthWest => (IVec3::new(0, 0, -1), IVec3::new(-1, 0, 0)),
+        RailShape::NorthEast => (IVec3::new(0, 0, -1), IVec3::new(1, 0, 0)),
+    }
+}
+
+/// Gets the shape of a rail block, or `None` if `state` is not a rail.
+///
+/// Vanilla: `BaseRailBlock.isRail()` and `getShapeProperty()`.
+fn rail_shape(state: BlockStateId) -> Option<RailShape> {
+    if !REGISTRY.blocks.is_in_tag(state.get_block(), &RAILS_TAG) {
+        return None;
+    }
+    state
+        .try_get_value(&BlockStateProperties::RAIL_SHAPE)
+        .or_else(|| state.try_get_value(&BlockStateProperties::RAIL_SHAPE_STRAIGHT))
+}
+
+/// Returns the block position containing `position`.
+fn block_pos_of(position: DVec3) -> BlockPos {
+    BlockPos::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    )
+}
+
+/// Returns the length of the horizontal part of `vector`.
+fn horizontal_distance(vector: DVec3) -> f64 {
+    vector.x.hypot(vector.z)
+}
+
+/// A rideable minecart.
+///
+/// Mirrors vanilla's `Minecart` with `OldMinecartBehavior`:
+/// - Follows rails, speeds up on slopes and powered rails and brakes on unpowered ones
+/// - Rolls at most 0.4 blocks per tick (0.2 in water)
+/// - Carries a single passenger, whose input nudges a standing minecart
+pub struct MinecartEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Whether the entity is on the ground.
+    on_ground: AtomicBool,
+    /// Rotation (yaw, pitch).
+    rotation: AtomicCell<(f32, f32)>,
+    /// Synced entity data (damage wobble and display block).
+    entity_data: SyncMutex<MinecartEntityData>,
+    /// Whether the model is turned around relative to the direction of travel.
+    flipped: AtomicBool,
+}
+
+impl MinecartEntity {
+    /// Creates a new minecart.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::with_base(
+            EntityBase::new(id, position, world),
+            DVec3::ZERO,
+            (0.0, 0.0),
+            false,
+        )
+    }
+
+    /// Creates a minecart from saved data with restored base state.
+    #[must_use]
+    pub fn from_saved(
+        id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        on_ground: bool,
+        world: Weak<World>,
+    ) -> Self {
+        Self::with_base(
+            EntityBase::with_uuid(id, uuid, position, world),
+            velocity,
+            rotation,
+            on_ground,
+        )
+    }
+
+    fn with_base(base: EntityBase, velocity: DVec3, rotation: (f32, f32), on_ground: bool) -> Self {
+        Self {
+            base,
+            velocity: SyncMutex::new(velocity),
+            on_ground: AtomicBool::new(on_ground),
+            rotation: AtomicCell::new(rotation),
+            entity_data: SyncMutex::new(MinecartEntityData::new()),
+            flipped: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns true if water touches the minecart.
+    ///
+    /// Vanilla: `Entity.isInWater()`.
+    fn is_in_water(&self, world: &Arc<World>) -> bool {
+        let aabb = self.bounding_box().deflate(0.001);
+        for x in aabb.min_x.floor() as i32..aabb.max_x.ceil() as i32 {
+            for y in aabb.min_y.floor() as i32..aabb.max_y.ceil() as i32 {
+                for z in aabb.min_z.floor() as i32..aabb.max_z.ceil() as i32 {
+                    let pos = BlockPos::new(x, y, z);
+                    let fluid = get_fluid_state(world, pos);
+                    if is_water_fluid(fluid.fluid_id)
+                        && f64::from(y) + f64::from(get_height(world, pos, fluid)) >= aabb.min_y
+                    {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Gets the fastest the minecart rolls.
+    ///
+    /// Vanilla: `OldMinecartBehavior.getMaxSpeed()`.
+    fn max_speed(&self, world: &Arc<World>) -> f64 {
+        if self.is_in_water(world) { 0.2 } else { 0.4 }
+    }
+
+    /// Gets the rail block the minecart is on, which may be one block below it.
+    ///
+    /// Vanilla: `AbstractMinecart.getCurrentBlockPosOrRailBelow()`.
+    fn rail_pos(world: &Arc<World>, position: DVec3) -> BlockPos {
+        let pos = block_pos_of(position);
+        let below = pos.below();
+        if REGISTRY
+            .blocks
+            .is_in_tag(world.get_block_state(below).get_block(), &RAILS_TAG)
+        {
+            below
+        } else {
+            pos
+        }
+    }
+
+    /// Snaps `position` onto the rail it is on, or `None` if it isn't on a rail.
+    ///
+    /// Vanilla: `OldMinecartBehavior.getPos()`.
+    fn pos_on_rail(world: &Arc<World>, position: DVec3) -> Option<DVec3> {
+        let pos = Self::rail_pos(world, position);
+        let shape = rail_shape(world.get_block_state(pos))?;
+        let (exit0, exit1) = exits(&shape);
+        let (xt, yt, zt) = (f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+
+        let start = DVec3::new(
+            xt + 0.5 + f64::from(exit0.x) * 0.5,
+            yt + 0.0625 + f64::from(exit0.y) * 0.5,
+            zt + 0.5 + f64::from(exit0.z) * 0.5,
+        );
+        let end = DVec3::new(
+            xt + 0.5 + f64::from(exit1.x) * 0.5,
+            yt + 0.0625 + f64::from(exit1.y) * 0.5,
+            zt + 0.5 + f64::from(exit1.z) * 0.5,
+        );
+        let dir = DVec3::new(end.x - start.x, (end.y - start.y) * 2.0, end.z - start.z);
+
+        let progress = if dir.x == 0.0 {
+            position.z - zt
+        } else if dir.z == 0.0 {
+            position.x - xt
+        } else {
+            ((position.x - start.x) * dir.x + (position.z - start.z) * dir.z) * 2.0
+        };
+        let mut result = start + dir * progress;
+        if dir.y < 0.0 {
+            result.y += 1.0;
+        } else if dir.y > 0.0 {
+            result.y += 0.5;
+        }
+        Some(result)
+    }
+
+    /// Moves the minecart along the rail at `pos`.
+    ///
+    /// Vanilla: `OldMinecartBehavior.moveAlongTrack()`.
+    fn move_along_track(&self, world: &Arc<World>, pos: BlockPos, state: BlockStateId) {
+        let Some(shape) = rail_shape(state) else {
+            return;
+        };
+        let start = self.position();
+        let old_pos = Self::pos_on_rail(world, start);
+        let mut y = f64::from(pos.y());
+
+        let mut power_track = false;
+        let mut halt_track = false;
+        if state.get_block() == vanilla_blocks::POWERED_RAIL {
+            power_track = state.get_value(&BlockStateProperties::POWERED);
+            halt_track = !power_track;
+        }
+
+        let in_water = self.is_in_water(world);
+        let slide_speed = if in_water {
+            SLOPE_SPEED * 0.2
+        } else {
+            SLOPE_SPEED
+        };
+        let slope = match shape {
+            RailShape::AscendingEast => Some(DVec3::new(-slide_speed, 0.0, 0.0)),
+            RailShape::AscendingWest => Some(DVec3::new(slide_speed, 0.0, 0.0)),
+            RailShape::AscendingNorth => Some(DVec3::new(0.0, 0.0, slide_speed)),
+            RailShape::AscendingSouth => Some(DVec3::new(0.0, 0.0, -slide_speed)),
+            _ => None,
+        };
+        if let Some(slope) = slope {
+            self.set_velocity(self.velocity() + slope);
+            y += 1.0;
+        }
+
+        // Point the velocity along the rail
+        let (exit0, exit1) = exits(&shape);
+        let mut x_dir = f64::from(exit1.x - exit0.x);
+        let mut z_dir = f64::from(exit1.z - exit0.z);
+        let dir_length = x_dir.hypot(z_dir);
+        let velocity = self.velocity();
+        if velocity.x * x_dir + velocity.z * z_dir < 0.0 {
+            x_dir = -x_dir;
+            z_dir = -z_dir;
+        }
+        let speed = horizontal_distance(velocity).min(2.0);
+        self.set_velocity(DVec3::new(
+            speed * x_dir / dir_length,
+            velocity.y,
+            speed * z_dir / dir_length,
+        ));
+
+        // A rider can push a standing minecart
+        let rider_input = self
+            .passengers()
+            .into_iter()
+            .next()
+            .and_then(|passenger| passenger.as_player())
+            .map_or(DVec3::ZERO, |player| player.last_client_move_intent());
+        if rider_input.length_squared() > 0.0 {
+            let velocity = self.velocity();
+            if velocity.x * velocity.x + velocity.z * velocity.z < 0.01 {
+                self.set_velocity(
+                    velocity + DVec3::new(rider_input.x * 0.001, 0.0, rider_input.z * 0.001),
+                );
+                halt_track = false;
+            }
+        }
+
+        if halt_track {
+            let velocity = self.velocity();
+            if horizontal_distance(velocity) < 0.03 {
+                self.set_velocity(DVec3::ZERO);
+            } else {
+                self.set_velocity(DVec3::new(velocity.x * 0.5, 0.0, velocity.z * 0.5));
+            }
+        }
+
+        // Snap onto the rail
+        let (block_x, block_z) = (f64::from(pos.x()), f64::from(pos.z()));
+        let x0 = block_x + 0.5 + f64::from(exit0.x) * 0.5;
+        let z0 = block_z + 0.5 + f64::from(exit0.z) * 0.5;
+        let x1 = block_x + 0.5 + f64::from(exit1.x) * 0.5;
+        let z1 = block_z + 0.5 + f64::from(exit1.z) * 0.5;
+        let (x_dir, z_dir) = (x1 - x0, z1 - z0);
+        let progress = if x_dir == 0.0 {
+            start.z - block_z
+        } else if z_dir == 0.0 {
+            start.x - block_x
+        } else {
+            ((start.x - x0) * x_dir + (start.z - z0) * z_dir) * 2.0
+        };
+        self.set_position(DVec3::new(x0 + x_dir * progress, y, z0 + z_dir * progress));
+
+        let scale = if self.passengers().is_empty() {
+            1.0
+        } else {
+            0.75
+        };
+        let max_speed = self.max_speed(world);
+        let velocity = self.velocity();
+        self.move_by(
+            MoverType::SelfMovement,
+            DVec3::new(
+                (scale * velocity.x).clamp(-max_speed, max_speed),
+                0.0,
+                (scale * velocity.z).clamp(-max_speed, max_speed),
+            ),
+        );
+
+        // Climb onto the next block of a slope
+        let moved = self.position();
+        let (moved_x, moved_z) = (moved.x.floor() as i32, moved.z.floor() as i32);
+        if exit0.y != 0 && moved_x - pos.x() == exit0.x && moved_z - pos.z() == exit0.z {
+            self.set_position(moved + DVec3::new(0.0, f64::from(exit0.y), 0.0));
+        } else if exit1.y != 0 && moved_x - pos.x() == exit1.x && moved_z - pos.z() == exit1.z {
+            self.set_position(moved + DVec3::new(0.0, f64::from(exit1.y), 0.0));
+        }
+
+        // Vanilla: AbstractMinecart.applyNaturalSlowdown()
+        let slowdown = if self.passengers().is_empty() {
+            0.96
+        } else {
+            0.997
+        };
+        let velocity = self.velocity();
+        let mut velocity = DVec3::new(velocity.x * slowdown, 0.0, velocity.z * slowdown);
+        if in_water {
+            velocity *= 0.95;
+        }
+        self.set_velocity(velocity);
+
+        // Trade height for speed
+        let moved = self.position();
+        if let Some(new_pos) = Self::pos_on_rail(world, moved)
+            && let Some(old_pos) = old_pos
+        {
+            let speed_change = (old_pos.y - new_pos.y) * 0.05;
+            let velocity = self.velocity();
+            let speed = horizontal_distance(velocity);
+            if speed > 0.0 {
+                let factor = (speed + speed_change) / speed;
+                self.set_velocity(DVec3::new(
+                    velocity.x * factor,
+                    velocity.y,
+                    velocity.z * factor,
+                ));
+            }
+            self.set_position(DVec3::new(moved.x, new_pos.y, moved.z));
+        }
+
+        // Turn the velocity towards the block the minecart rolled into
+        let moved = self.position();
+        let (moved_x, moved_z) = (moved.x.floor() as i32, moved.z.floor() as i32);
+        if moved_x != pos.x() || moved_z != pos.z() {
+            let velocity = self.velocity();
+            let speed = horizontal_distance(velocity);
+            self.set_velocity(DVec3::new(
+                speed * f64::from(moved_x - pos.x()),
+                velocity.y,
+                speed * f64::from(moved_z - pos.z()),
+            ));
+        }
+
+        if power_track {
+            self.apply_powered_rail(world, pos, &shape);
+        }
+    }
+
+    /// Speeds the minecart up on a powered rail, or pushes it off a block next to it.
+    ///
+    /// Vanilla: the `powerTrack` branch of `OldMinecartBehavior.moveAlongTrack()`.
+    fn apply_powered_rail(&self, world: &Arc<World>, pos: BlockPos, shape: &RailShape) {
+        let velocity = self.velocity();
+        let speed = horizontal_distance(velocity);
+        if speed > 0.01 {
+            self.set_velocity(
+                velocity
+                    + DVec3::new(
+                        velocity.x / speed * POWERED_RAIL_BOOST,
+                        0.0,
+                        velocity.z / speed * POWERED_RAIL_BOOST,
+                    ),
+            );
+            return;
+        }
+
+        let conductor = |pos: BlockPos| is_redstone_conductor(world.get_block_state(pos));
+        let mut velocity = velocity;
+        match shape {
+            RailShape::EastWest => {
+                if conductor(pos.west()) {
+                    velocity.x = 0.02;
+                } else if conductor(pos.east()) {
+                    velocity.x = -0.02;
+                }
+            }
+            RailShape::NorthSouth => {
+                if conductor(pos.north()) {
+                    velocity.z = 0.02;
+                } else if conductor(pos.south()) {
+                    velocity.z = -0.02;
+                }
+            }
+            _ => return,
+        }
+        self.set_velocity(velocity);
+    }
+
+    /// Rolls freely while off the rails.
+    ///
+    /// Vanilla: `AbstractMinecart.comeOffTrack()`.
+    fn come_off_track(&self, world: &Arc<World>) {
+        let max_speed = self.max_speed(world);
+        let velocity = self.velocity();
+        let mut velocity = DVec3::new(
+            velocity.x.clamp(-max_speed, max_speed),
+            velocity.y,
+            velocity.z.clamp(-max_speed, max_speed),
+        );
+        if self.on_ground() {
+            velocity *= 0.5;
+        }
+        self.set_velocity(velocity);
+        self.do_move(MoverType::SelfMovement);
+        if !self.on_ground() {
+            self.set_velocity(self.velocity() * 0.95);
+        }
+    }
+
+    /// Turns the minecart to face where it is rolling.
+    ///
+    /// Vanilla: the rotation part of `OldMinecartBehavior.tick()`.
+    fn update_rotation(&self, start: DVec3, old_yaw: f32) {
+        let position = self.position();
+        let x_diff = start.x - position.x;
+        let z_diff = start.z - position.z;
+        let mut yaw = old_yaw;
+        let mut flipped = self.flipped.load(Ordering::Relaxed);
+        if x_diff * x_diff + z_diff * z_diff > 0.001 {
+            yaw = z_diff.atan2(x_diff).to_degrees() as f32;
+            if flipped {
+                yaw += 180.0;
+            }
+        }
+        let rot_diff = wrap_degrees(yaw - old_yaw);
+        if !(-170.0..170.0).contains(&rot_diff) {
+            yaw += 180.0;
+            flipped = !flipped;
+        }
+        self.flipped.store(flipped, Ordering::Relaxed);
+        self.set_rotation((yaw % 360.0, 0.0));
+    }
+}
+
+impl VehicleEntity for MinecartEntity {
+    fn hurt_time(&self) -> i32 {
+        *self.entity_data.lock().id_hurt.get()
+    }
+
+    fn set_hurt_time(&self, hurt_time: i32) {
+        self.entity_data.lock().id_hurt.set(hurt_time);
+    }
+
+    fn hurt_dir(&self) -> i32 {
+        *self.entity_data.lock().id_hurtdir.get()
+    }
+
+    fn set_hurt_dir(&self, hurt_dir: i32) {
+        self.entity_data.lock().id_hurtdir.set(hurt_dir);
+    }
+
+    fn damage(&self) -> f32 {
+        *self.entity_data.lock().id_damage.get()
+    }
+
+    fn set_damage(&self, damage: f32) {
+        self.entity_data.lock().id_damage.set(damage);
+    }
+}
+
+impl Entity for MinecartEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::MINECART
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    fn tick(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        self.tick_damage();
+        // TODO: Discard below the world, portals and lava
+
+        let start = self.position();
+        let (old_yaw, _) = self.rotation();
+        self.apply_gravity();
+
+        let pos = Self::rail_pos(&world, start);
+        let state = world.get_block_state(pos);
+        if rail_shape(state).is_some() {
+            self.move_along_track(&world, pos, state);
+            // TODO: Activator rails eject passengers
+        } else {
+            self.come_off_track(&world);
+        }
+        self.update_rotation(start, old_yaw);
+        // TODO: Push other minecarts and pick up mobs
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        if tick_count % self.entity_type().update_interval != 0 {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        let pos = self.position();
+        let velocity = self.velocity();
+        let (yaw, pitch) = self.rotation();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CSetEntityMotion::new(self.id(), velocity.x, velocity.y, velocity.z),
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: self.on_ground(),
+            },
+            None,
+        );
+    }
+
+    fn rotation(&self) -> (f32, f32) {
+        self.rotation.load()
+    }
+
+    fn set_rotation(&self, rotation: (f32, f32)) {
+        self.rotation.store(rotation);
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn on_ground(&self) -> bool {
+        self.on_ground.load(Ordering::Relaxed)
+    }
+
+    fn set_on_ground(&self, on_ground: bool) {
+        self.on_ground.store(on_ground, Ordering::Relaxed);
+    }
+
+    fn get_default_gravity(&self) -> f64 {
+        if self.level().is_some_and(|world| self.is_in_water(&world)) {
+            WATER_GRAVITY
+        } else {
+            DEFAULT_GRAVITY
+        }
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        *self.entity_data.lock().no_gravity.get()
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        self.hurt_vehicle(source, amount)
+    }
+
+    /// Vanilla: `Minecart.interact()`.
+    fn interact(
+        self: Arc<Self>,
+        player: &Arc<Player>,
+        _hand: InteractionHand,
+    ) -> InteractionResult {
+        if player.is_secondary_use_active() || !self.passengers().is_empty() {
+            return InteractionResult::Pass;
+        }
+        let passenger: SharedEntity = player.clone();
+        let vehicle: SharedEntity = self;
+        if riding::start_riding(&passenger, &vehicle, false) {
+            InteractionResult::Success
+        } else {
+            InteractionResult::Pass
+        }
+    }
+
+    fn get_pick_result(&self) -> Option<ItemStack> {
+        self.drop_item()
+    }
+}
@@ -1,7 +1,27 @@
 //! Concrete entity implementations.
 
+mod arrow;
 mod block_display;
+mod boat;
+mod end_crystal;
+mod ender_dragon;
+mod falling_block;
+mod firework_rocket;
+mod interaction;
 mod item;
+mod leash_knot;
+mod minecart;
+mod villager;
 
+pub use arrow::ArrowEntity;
 pub use block_display::BlockDisplayEntity;
+pub use boat::BoatEntity;
+pub use end_crystal::EndCrystalEntity;
+pub use ender_dragon::{DragonPhase, EnderDragonEntity};
+pub use falling_block::FallingBlockEntity;
+pub use firework_rocket::FireworkRocketEntity;
+pub use interaction::{InteractionEntity, PlayerAction};
 pub use item::ItemEntity;
+pub use leash_knot::LeashFenceKnotEntity;
+pub use minecart::MinecartEntity;
+pub use villager::{PROFESSIONS, VillagerEntity};
@@ -0,0 +1,745 @@
+//! Villager entity implementation.
+//!
+//! Villagers follow a daily schedule driven by their [`Brain`]: they claim a bed,
+//! a job site and a meeting point nearby, work during the day, gather at the bell
+//! in the afternoon and walk home to sleep at night.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Weak};
+
+use crossbeam::atomic::AtomicCell;
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound as BorrowedNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{CEntityPositionSync, CRotateHead, to_angle_byte};
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_data::{DataValue, EntityPose};
+use steel_registry::entity_types::EntityTypeRef;
+use steel_registry::vanilla_attributes::MAX_HEALTH;
+use steel_registry::vanilla_entity_data::VillagerEntityData;
+use steel_registry::vanilla_poi_type_tags::ACQUIRABLE_JOB_SITE_TAG;
+use steel_registry::{
+    REGISTRY, RegistryEntry, RegistryExt, TaggedRegistryExt, vanilla_entities, vanilla_poi_types,
+};
+use steel_utils::locks::SyncMutex;
+use steel_utils::{BlockPos, ChunkPos, Identifier};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::entity::ai::brain::{Brain, PoiMemory};
+use crate::entity::ai::navigation::{WalkTarget, walk_towards};
+use crate::entity::ai::poi::{acquire_poi, release_poi, validate_poi};
+use crate::entity::ai::schedule::{Activity, VILLAGER_BABY, VILLAGER_DEFAULT};
+use crate::entity::attribute::{AttributeMap, default_attributes};
+use crate::entity::damage::DamageSource;
+use crate::entity::{
+    DEATH_DURATION, Entity, EntityBase, LivingEntity, LivingEntityBase, Mob, RemovalReason,
+};
+use crate::world::World;
+
+/// Villager professions in registry order. Vanilla: `VillagerProfession`.
+///
+/// Every profession except `none` and `nitwit` has a job site POI type of the same name.
+pub const PROFESSIONS: [&str; 15] = [
+    "none",
+    "armorer",
+    "butcher",
+    "cartographer",
+    "cleric",
+    "farmer",
+    "fisherman",
+    "fletcher",
+    "leatherworker",
+    "librarian",
+    "mason",
+    "nitwit",
+    "shepherd",
+    "toolsmith",
+    "weaponsmith",
+];
+
+/// Profession id of villagers without a job.
+const PROFESSION_NONE: i32 = 0;
+/// Profession id of villagers that never take a job.
+const PROFESSION_NITWIT: i32 = 11;
+/// Villager type id of plains villagers. Vanilla: `VillagerType.PLAINS`.
+const TYPE_PLAINS: i32 = 2;
+
+/// Speed modifier for walking to POIs. Vanilla: `VillagerGoalPackages` uses 0.5.
+const WALK_SPEED: f32 = 0.5;
+/// How close to the job site counts as arrived.
+const JOB_SITE_CLOSE_ENOUGH: i32 = 1;
+/// How close to the bed counts as arrived.
+const HOME_CLOSE_ENOUGH: i32 = 1;
+/// How close to the meeting point counts as arrived.
+const MEETING_POINT_CLOSE_ENOUGH: i32 = 6;
+/// How close to the bed a villager has to be to lie down. Vanilla: `SleepInBed`.
+const SLEEP_DISTANCE_SQ: f64 = 2.0 * 2.0;
+/// Height of a lying villager above the bed block. Vanilla: `LivingEntity.setPosToBed()`.
+const BED_HEIGHT: f64 = 0.6875;
+
+/// Minimum ticks between POI searches. Vanilla: `AcquirePoi` retries randomly.
+const POI_SEARCH_INTERVAL: i32 = 20;
+
+/// Ticks a hit keeps the villager invulnerable. Vanilla: `LivingEntity.invulnerableTime`.
+const INVULNERABLE_TICKS: i32 = 20;
+
+/// A villager.
+///
+/// Mirrors vanilla's `Villager` brain setup:
+/// - Core: claims a bed, a meeting point and (for unemployed adults) a job site,
+///   taking the job site's profession, and forgets POIs whose blocks are gone
+/// - Work: walks to the job site
+/// - Meet: walks to the meeting point
+/// - Rest: walks home and sleeps in the bed
+///
+/// Claimed POIs are released when the villager dies or is discarded.
+pub struct VillagerEntity {
+    /// Common entity fields (id, uuid, position, etc.).
+    base: EntityBase,
+    /// Velocity in blocks per tick.
+    velocity: SyncMutex<DVec3>,
+    /// Rotation as (yaw, pitch) in degrees.
+    rotation: AtomicCell<(f32, f32)>,
+    /// Whether the villager is standing on something.
+    on_ground: AtomicBool,
+    /// Synced entity data (health, villager data, sleeping position, ...).
+    entity_data: SyncMutex<VillagerEntityData>,
+    /// Attributes (health, movement speed, ...).
+    attributes: SyncMutex<AttributeMap>,
+    /// Shared living entity state (death, invulnerability).
+    living_base: SyncMutex<LivingEntityBase>,
+    /// Extra health from absorption.
+    absorption: AtomicCell<f32>,
+    /// Current movement speed set by navigation.
+    speed: AtomicCell<f32>,
+    /// Memories, schedule and current activity.
+    brain: SyncMutex<Brain>,
+    /// Ticks until the next POI search.
+    poi_search_cooldown: AtomicI32,
+    /// Whether the villager never despawns.
+    persistence_required: AtomicBool,
+}
+
+impl VillagerEntity {
+    /// Creates a new unemployed plains villager.
+    #[must_use]
+    pub fn new(id: i32, position: DVec3, world: Weak<World>) -> Self {
+        Self::from_parts(
+            EntityBase::new(id, position, world),
+            DVec3::ZERO,
+            (0.0, 0.0),
+        )
+    }
+
+    /// Creates a villager from saved data with restored base state.
+    ///
+    /// Type-specific data (villager data, brain, health, ...) is restored via
+    /// `load_additional()` after this constructor.
+    #[must_use]
+    pub fn from_saved(
+        id: i32,
+        position: DVec3,
+        uuid: Uuid,
+        velocity: DVec3,
+        rotation: (f32, f32),
+        world: Weak<World>,
+    ) -> Self {
+        Self::from_parts(
+            EntityBase::with_uuid(id, uuid, position, world),
+            velocity,
+            rotation,
+        )
+    }
+
+    fn from_parts(base: EntityBase, velocity: DVec3, rotation: (f32, f32)) -> Self {
+        let attributes = default_attributes(vanilla_entities::VILLAGER);
+        let mut entity_data = VillagerEntityData::new();
+        entity_data
+            .health
+            .set(attributes.get_value(MAX_HEALTH) as f32);
+        let mut villager_data = *entity_data.villager_data.get();
+        villager_data.villager_type = TYPE_PLAINS;
+        entity_data.villager_data.set(villager_data);
+
+        Self {
+            base,
+            velocity: SyncMutex::new(velocity),
+            rotation: AtomicCell::new(rotation),
+            on_ground: AtomicBool::new(false),
+            entity_data: SyncMutex::new(entity_data),
+            attributes: SyncMutex::new(attributes),
+            living_base: SyncMutex::new(LivingEntityBase::new()),
+            absorption: AtomicCell::new(0.0),
+            speed: AtomicCell::new(0.0),
+            brain: SyncMutex::new(Brain::new(&VILLAGER_DEFAULT)),
+            poi_search_cooldown: AtomicI32::new(0),
+            persistence_required: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns true if the villager is a baby.
+    #[must_use]
+    pub fn is_baby(&self) -> bool {
+        *self.entity_data.lock().baby.get()
+    }
+
+    /// Sets whether the villager is a baby, switching to the matching schedule.
+    pub fn set_baby(&self, baby: bool) {
+        self.entity_data.lock().baby.set(baby);
+        self.brain.lock().set_schedule(if baby {
+            &VILLAGER_BABY
+        } else {
+            &VILLAGER_DEFAULT
+        });
+    }
+
+    /// Gets the villager's profession id. See [`PROFESSIONS`].
+    #[must_use]
+    pub fn profession(&self) -> i32 {
+        self.entity_data.lock().villager_data.get().profession
+    }
+
+    /// Sets the villager's profession id. See [`PROFESSIONS`].
+    pub fn set_profession(&self, profession: i32) {
+        let mut entity_data = self.entity_data.lock();
+        let mut villager_data = *entity_data.villager_data.get();
+        villager_data.profession = profession;
+        entity_data.villager_data.set(villager_data);
+    }
+
+    /// Gets the activity the villager is busy with.
+    #[must_use]
+    pub fn active_activity(&self) -> Activity {
+        self.brain.lock().active_activity()
+    }
+
+    /// Gets the remembered position of a claimed POI.
+    #[must_use]
+    pub fn poi(&self, memory: PoiMemory) -> Option<BlockPos> {
+        self.brain
+            .lock()
+            .poi(memory)
+            .map(|global_pos| global_pos.pos)
+    }
+
+    /// Gets the block the villager's feet are in.
+    fn block_position(&self) -> BlockPos {
+        let pos = self.position();
+        BlockPos::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        )
+    }
+
+    /// Returns true if POI type `poi_type` is a job site this villager may claim.
+    ///
+    /// Unemployed villagers take any job site, employed ones only their own.
+    /// Vanilla: `VillagerProfession.acquirableJobSite()`.
+    fn is_acquirable_job_site(profession: i32, poi_type: usize) -> bool {
+        let Some(poi) = REGISTRY.poi_types.by_id(poi_type) else {
+            return false;
+        };
+        match profession {
+            PROFESSION_NONE => REGISTRY.poi_types.is_in_tag(poi, &ACQUIRABLE_JOB_SITE_TAG),
+            PROFESSION_NITWIT => false,
+            _ => usize::try_from(profession)
+                .ok()
+                .and_then(|index| PROFESSIONS.get(index))
+                .is_some_and(|name| poi.key == Identifier::vanilla_static(*name)),
+        }
+    }
+
+    /// Gets the profession that works at POI type `poi_type`.
+    ///
+    /// Vanilla: `AssignProfessionFromJobSite`.
+    fn profession_for_job_site(poi_type: usize) -> Option<i32> {
+        let poi = REGISTRY.poi_types.by_id(poi_type)?;
+        if poi.key.namespace != Identifier::VANILLA_NAMESPACE {
+            return None;
+        }
+        PROFESSIONS
+            .iter()
+            .position(|name| *name == &*poi.key.path)
+            .and_then(|index| i32::try_from(index).ok())
+    }
+
+    /// Runs the behaviors that are active regardless of the schedule.
+    ///
+    /// Vanilla: `VillagerGoalPackages.getCorePackage()`.
+    fn tick_core(&self, world: &World, brain: &mut Brain) {
+        let pos = self.block_position();
+        let profession = self.profession();
+        let home = vanilla_poi_types::HOME.id();
+        let meeting = vanilla_poi_types::MEETING.id();
+
+        validate_poi(world, brain, PoiMemory::Home, pos, |id| id == home);
+        validate_poi(world, brain, PoiMemory::MeetingPoint, pos, |id| {
+            id == meeting
+        });
+        // TODO: Lose the profession again if the villager never traded (ResetProfession)
+        validate_poi(world, brain, PoiMemory::JobSite, pos, |id| {
+            Self::is_acquirable_job_site(profession, id)
+        });
+
+        if self.poi_search_cooldown.fetch_sub(1, Ordering::Relaxed) > 0 {
+            return;
+        }
+        self.poi_search_cooldown.store(
+            POI_SEARCH_INTERVAL + rand::random_range(0..POI_SEARCH_INTERVAL),
+            Ordering::Relaxed,
+        );
+
+        acquire_poi(world, brain, PoiMemory::Home, pos, |id| id == home);
+        acquire_poi(world, brain, PoiMemory::MeetingPoint, pos, |id| {
+            id == meeting
+        });
+        if !self.is_baby()
+            && let Some((_, poi_type)) = acquire_poi(world, brain, PoiMemory::JobSite, pos, |id| {
+                Self::is_acquirable_job_site(profession, id)
+            })
+            && profession == PROFESSION_NONE
+            && let Some(new_profession) = Self::profession_for_job_site(poi_type)
+        {
+            // TODO: Play the profession's work sound and refresh trades
+            self.set_profession(new_profession);
+        }
+    }
+
+    /// Sets the walk target to a remembered POI unless the villager is already there.
+    ///
+    /// Vanilla: `SetWalkTargetFromBlockMemory`.
+    fn walk_to_poi(&self, brain: &mut Brain, memory: PoiMemory, close_enough: i32) {
+        let Some(global_pos) = brain.poi(memory) else {
+            brain.erase_walk_target();
+            return;
+        };
+        let target = WalkTarget::new(global_pos.pos, WALK_SPEED, close_enough);
+        if target.is_reached(self.position()) {
+            brain.erase_walk_target();
+        } else {
+            brain.set_walk_target(target);
+        }
+    }
+
+    /// Lies down in the home bed once the villager is next to it.
+    ///
+    /// Vanilla: `SleepInBed`.
+    fn try_sleep(&self, brain: &Brain) {
+        if self.is_sleeping() {
+            return;
+        }
+        let Some(home) = brain.poi(PoiMemory::Home) else {
+            return;
+        };
+        let bed = home.pos;
+        let center = DVec3::new(
+            f64::from(bed.x()) + 0.5,
+            f64::from(bed.y()),
+            f64::from(bed.z()) + 0.5,
+        );
+        if self.position().distance_squared(center) > SLEEP_DISTANCE_SQ {
+            return;
+        }
+        // TODO: Check the bed isn't occupied and set its occupied block state
+        self.set_position(DVec3::new(center.x, center.y + BED_HEIGHT, center.z));
+        self.set_velocity(DVec3::ZERO);
+        let mut entity_data = self.entity_data.lock();
+        entity_data.sleeping_pos.set(Some(bed));
+        entity_data.pose.set(EntityPose::Sleeping);
+    }
+
+    /// Frees all claimed POIs so other villagers can take them.
+    ///
+    /// Vanilla: `Villager.releaseAllPois()`.
+    fn release_all_pois(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        let mut brain = self.brain.lock();
+        for memory in PoiMemory::ALL {
+            release_poi(&world, &mut brain, memory);
+        }
+    }
+
+    /// Marks the villager as dead and gives up its POIs.
+    ///
+    /// Vanilla: `Villager.die()`.
+    fn die(&self) {
+        {
+            let mut living_base = self.living_base.lock();
+            if living_base.dead {
+                return;
+            }
+            living_base.dead = true;
+        }
+        self.stop_sleeping();
+        self.release_all_pois();
+        // TODO: Death message for named villagers, loot and gossip to nearby villagers
+    }
+}
+
+impl Mob for VillagerEntity {
+    fn mob_flags(&self) -> i8 {
+        *self.entity_data.lock().mob_flags.get()
+    }
+
+    fn set_mob_flags(&self, flags: i8) {
+        self.entity_data.lock().mob_flags.set(flags);
+    }
+
+    fn is_persistence_required(&self) -> bool {
+        self.persistence_required.load(Ordering::Relaxed)
+    }
+
+    fn set_persistence_required(&self, persistence_required: bool) {
+        self.persistence_required
+            .store(persistence_required, Ordering::Relaxed);
+    }
+
+    /// Vanilla: `Villager.customServerAiStep()` ticking the brain.
+    fn server_ai_step(&self) {
+        let Some(world) = self.level() else {
+            return;
+        };
+        let day_time = world.level_data.read().day_time();
+        let mut brain = self.brain.lock();
+
+        if brain.update_activity_from_schedule(day_time).is_some()
+            && brain.active_activity() != Activity::Rest
+        {
+            // Vanilla: WakeUp in the core package
+            self.stop_sleeping();
+        }
+        self.tick_core(&world, &mut brain);
+
+        match brain.active_activity() {
+            Activity::Work => {
+                self.walk_to_poi(&mut brain, PoiMemory::JobSite, JOB_SITE_CLOSE_ENOUGH)
+            }
+            Activity::Meet => {
+                self.walk_to_poi(
+                    &mut brain,
+                    PoiMemory::MeetingPoint,
+                    MEETING_POINT_CLOSE_ENOUGH,
+                );
+            }
+            Activity::Rest => {
+                self.walk_to_poi(&mut brain, PoiMemory::Home, HOME_CLOSE_ENOUGH);
+                self.try_sleep(&brain);
+            }
+            // TODO: Random strolls, playing and socializing
+            _ => brain.erase_walk_target(),
+        }
+
+        if self.is_sleeping() {
+            return;
+        }
+        if let Some(target) = brain.walk_target().copied()
+            && walk_towards(self, &target)
+        {
+            brain.erase_walk_target();
+        }
+    }
+}
+
+impl LivingEntity for VillagerEntity {
+    fn get_health(&self) -> f32 {
+        *self.entity_data.lock().health.get()
+    }
+
+    fn set_health(&self, health: f32) {
+        let clamped = health.clamp(0.0, self.get_max_health());
+        self.entity_data.lock().health.set(clamped);
+    }
+
+    fn attributes(&self) -> &SyncMutex<AttributeMap> {
+        &self.attributes
+    }
+
+    fn living_base(&self) -> &SyncMutex<LivingEntityBase> {
+        &self.living_base
+    }
+
+    fn get_absorption_amount(&self) -> f32 {
+        self.absorption.load()
+    }
+
+    fn set_absorption_amount(&self, amount: f32) {
+        self.absorption.store(amount.max(0.0));
+    }
+
+    fn is_sleeping(&self) -> bool {
+        self.entity_data.lock().sleeping_pos.get().is_some()
+    }
+
+    /// Vanilla: `LivingEntity.stopSleeping()`.
+    fn stop_sleeping(&self) {
+        let mut entity_data = self.entity_data.lock();
+        if entity_data.sleeping_pos.get().is_none() {
+            return;
+        }
+        entity_data.sleeping_pos.set(None);
+        entity_data.pose.set(EntityPose::Standing);
+        // TODO: Move out of the bed to a free spot (BedBlock.findStandUpPosition)
+    }
+
+    fn set_sprinting(&self, _sprinting: bool) {}
+
+    fn get_speed(&self) -> f32 {
+        self.speed.load()
+    }
+
+    fn set_speed(&self, speed: f32) {
+        self.speed.store(speed);
+    }
+}
+
+impl Entity for VillagerEntity {
+    fn base(&self) -> Option<&EntityBase> {
+        Some(&self.base)
+    }
+
+    fn entity_type(&self) -> EntityTypeRef {
+        vanilla_entities::VILLAGER
+    }
+
+    fn bounding_box(&self) -> AABBd {
+        let pos = self.position();
+        let dims = self.entity_type().dimensions;
+        // TODO: Use the baby and sleeping dimensions
+        AABBd::entity_box(
+            pos.x,
+            pos.y,
+            pos.z,
+            f64::from(dims.width) / 2.0,
+            f64::from(dims.height),
+        )
+    }
+
+    fn tick(&self) {
+        self.tick_fire();
+        {
+            let mut living_base = self.living_base.lock();
+            if living_base.invulnerable_time > 0 {
+                living_base.invulnerable_time -= 1;
+            }
+        }
+
+        if self.is_dead_or_dying() {
+            // Vanilla: LivingEntity.tickDeath()
+            if self.living_base.lock().increment_death_time() >= DEATH_DURATION {
+                self.set_removed(RemovalReason::Killed);
+            }
+            return;
+        }
+        self.ai_step();
+    }
+
+    fn send_changes(&self, tick_count: i32) {
+        if tick_count % self.entity_type().update_interval != 0 {
+            return;
+        }
+        let Some(world) = self.level() else {
+            return;
+        };
+
+        // TODO: Send relative moves and only when something changed (ServerEntity)
+        let pos = self.position();
+        let velocity = self.velocity();
+        let (yaw, pitch) = self.rotation.load();
+        let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CEntityPositionSync {
+                entity_id: self.id(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity_x: velocity.x,
+                velocity_y: velocity.y,
+                velocity_z: velocity.z,
+                yaw,
+                pitch,
+                on_ground: self.on_ground(),
+            },
+            None,
+        );
+        world.broadcast_to_nearby(
+            chunk_pos,
+            CRotateHead {
+                entity_id: self.id(),
+                head_y_rot: to_angle_byte(yaw),
+            },
+            None,
+        );
+    }
+
+    fn set_removed(&self, reason: RemovalReason) {
+        if reason.should_destroy() {
+            self.release_all_pois();
+        }
+        self.base.set_removed(reason);
+    }
+
+    fn as_mob(self: Arc<Self>) -> Option<Arc<dyn Mob>> {
+        Some(self)
+    }
+
+    /// Vanilla: `LivingEntity.hurtServer()` without armor and effects.
+    fn hurt(&self, _source: &DamageSource, amount: f32) -> bool {
+        if self.is_removed() || self.is_dead_or_dying() {
+            return false;
+        }
+        let damage = {
+            let mut living_base = self.living_base.lock();
+            if living_base.invulnerable_time > INVULNERABLE_TICKS / 2 {
+                if amount <= living_base.last_hurt {
+                    return false;
+                }
+                // Only the part above the previous hit counts during invulnerability
+                let damage = amount - living_base.last_hurt;
+                living_base.last_hurt = amount;
+                damage
+            } else {
+                living_base.last_hurt = amount;
+                living_base.invulnerable_time = INVULNERABLE_TICKS;
+                amount
+            }
+        };
+        // TODO: Hurt animation, knockback, panic and gossip about the attacker
+        self.stop_sleeping();
+        self.set_health(self.get_health() - damage);
+        if self.is_dead_or_dying() {
+            self.die();
+        }
+        true
+    }
+
+    fn rotation(&self) -> (f32, f32) {
+        self.rotation.load()
+    }
+
+    fn set_rotation(&self, rotation: (f32, f32)) {
+        self.rotation.store(rotation);
+    }
+
+    fn velocity(&self) -> DVec3 {
+        *self.velocity.lock()
+    }
+
+    fn set_velocity(&self, velocity: DVec3) {
+        *self.velocity.lock() = velocity;
+    }
+
+    fn on_ground(&self) -> bool {
+        self.on_ground.load(Ordering::Relaxed)
+    }
+
+    fn set_on_ground(&self, on_ground: bool) {
+        self.on_ground.store(on_ground, Ordering::Relaxed);
+    }
+
+    fn is_no_gravity(&self) -> bool {
+        *self.entity_data.lock().no_gravity.get()
+    }
+
+    fn emits_step_sounds(&self) -> bool {
+        true
+    }
+
+    fn pack_dirty_entity_data(&self) -> Option<Vec<DataValue>> {
+        self.entity_data.lock().pack_dirty()
+    }
+
+    fn pack_all_entity_data(&self) -> Vec<DataValue> {
+        self.entity_data.lock().pack_all()
+    }
+
+    fn custom_name(&self) -> Option<TextComponent> {
+        self.entity_data
+            .lock()
+            .custom_name
+            .get()
+            .as_deref()
+            .cloned()
+    }
+
+    fn set_custom_name(&self, name: Option<TextComponent>) {
+        self.entity_data.lock().custom_name.set(name.map(Box::new));
+    }
+
+    fn is_custom_name_visible(&self) -> bool {
+        *self.entity_data.lock().custom_name_visible.get()
+    }
+
+    fn set_custom_name_visible(&self, visible: bool) {
+        self.entity_data.lock().custom_name_visible.set(visible);
+    }
+
+    fn save_additional(&self, nbt: &mut NbtCompound) {
+        // Match vanilla's LivingEntity and Villager.addAdditionalSaveData
+        nbt.insert("Health", self.get_health());
+        nbt.insert("attributes", self.attributes.lock().save());
+        nbt.insert("Brain", NbtTag::Compound(self.brain.lock().save()));
+        if let Some(sleeping_pos) = *self.entity_data.lock().sleeping_pos.get() {
+            nbt.insert(
+                "sleeping_pos",
+                NbtTag::IntArray(vec![sleeping_pos.x(), sleeping_pos.y(), sleeping_pos.z()]),
+            );
+        }
+        self.save_mob_data(nbt);
+
+        let villager_data = *self.entity_data.lock().villager_data.get();
+        let profession = usize::try_from(villager_data.profession)
+            .ok()
+            .and_then(|index| PROFESSIONS.get(index))
+            .unwrap_or(&"none");
+        let mut data = NbtCompound::new();
+        data.insert("level", villager_data.level);
+        data.insert(
+            "profession",
+            Identifier::vanilla_static(*profession).to_string(),
+        );
+        // TODO: Save the villager type by key once the villager type registry exists
+        data.insert("type", "minecraft:plains");
+        nbt.insert("VillagerData", NbtTag::Compound(data));
+        // Vanilla stores the age in AgeableMob; babies have a negative age
+        nbt.insert("Age", if self.is_baby() { -24000 } else { 0 });
+    }
+
+    fn load_additional(&self, nbt: &BorrowedNbtCompound<'_>) {
+        self.load_mob_data(nbt);
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+
+        self.attributes.lock().load(&nbt);
+        if let Some(health) = nbt.float("Health") {
+            self.set_health(health);
+        }
+        self.set_baby(nbt.int("Age").is_some_and(|age| age < 0));
+        if let Some(brain) = nbt.compound("Brain") {
+            self.brain.lock().load(&brain);
+        }
+        if let Some(pos) = nbt.int_array("sleeping_pos")
+            && let [x, y, z] = pos[..]
+        {
+            let mut entity_data = self.entity_data.lock();
+            entity_data.sleeping_pos.set(Some(BlockPos::new(x, y, z)));
+            entity_data.pose.set(EntityPose::Sleeping);
+        }
+        if let Some(data) = nbt.compound("VillagerData") {
+            let profession = data
+                .string("profession")
+                .and_then(|profession| profession.to_str().parse::<Identifier>().ok())
+                .and_then(|key| PROFESSIONS.iter().position(|name| *name == &*key.path))
+                .and_then(|index| i32::try_from(index).ok())
+                .unwrap_or(PROFESSION_NONE);
+            let mut entity_data = self.entity_data.lock();
+            let mut villager_data = *entity_data.villager_data.get();
+            villager_data.profession = profession;
+            villager_data.level = data.int("level").unwrap_or(1).max(1);
+            entity_data.villager_data.set(villager_data);
+        }
+    }
+}
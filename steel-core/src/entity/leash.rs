@@ -0,0 +1,462 @@
+//! Leashes between entities.
+//!
+//! Mirrors vanilla's `Leashable`: a leashable entity can be tied to a player, a
+//! leash knot on a fence or another entity. The leashed entity holds a weak
+//! reference to its holder and is pulled towards it while the leash is taut.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::CSetEntityLink;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::sound_events::{ITEM_LEAD_BREAK, ITEM_LEAD_TIED, ITEM_LEAD_UNTIED};
+use steel_registry::vanilla_game_rules::ENTITY_DROPS;
+use steel_registry::{vanilla_entities, vanilla_items};
+use steel_utils::types::InteractionHand;
+use steel_utils::{BlockPos, ChunkPos, UuidExt};
+use uuid::Uuid;
+
+use super::entities::LeashFenceKnotEntity;
+use super::{Entity, RemovalReason, SharedEntity, WeakEntity, riding};
+use crate::behavior::InteractionResult;
+use crate::player::Player;
+use crate::world::World;
+
+/// Distance at which a leash breaks. Vanilla: `Leashable.leashSnapDistance()`.
+pub const LEASH_SNAP_DISTANCE: f64 = 12.0;
+/// Distance from which a leash pulls its entity. Vanilla: `Leashable.leashElasticDistance()`.
+pub const LEASH_ELASTIC_DISTANCE: f64 = 6.0;
+/// Size of the box searched for leashed entities. Vanilla: `Leashable.leashableInArea()`.
+const LEASHABLE_SEARCH_SIZE: f64 = 32.0;
+/// Ticks a saved holder may take to load before the leash drops as an item.
+const DELAYED_HOLDER_TICKS: u32 = 100;
+
+/// A leash holder read from disk that isn't loaded yet.
+///
+/// Vanilla: `LeashData.delayedLeashInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DelayedHolder {
+    /// An entity, usually a player or another mob.
+    Entity(Uuid),
+    /// The leash knot on the fence at this position.
+    Knot(BlockPos),
+}
+
+/// The leash of an entity.
+#[derive(Default)]
+pub struct LeashState {
+    /// The entity holding the leash, if any.
+    holder: Option<WeakEntity>,
+    /// The saved holder, until it is loaded.
+    delayed: Option<DelayedHolder>,
+    /// Ticks spent waiting for `delayed`.
+    delayed_ticks: u32,
+}
+
+impl LeashState {
+    /// Returns the leash holder, if it is still loaded.
+    #[must_use]
+    pub fn holder(&self) -> Option<SharedEntity> {
+        self.holder.as_ref().and_then(WeakEntity::upgrade)
+    }
+
+    /// Saves the leash under `leash`.
+    ///
+    /// Knots are saved by their fence position, other holders by their UUID.
+    ///
+    /// Vanilla: `Leashable.writeLeashData()`.
+    pub fn save(&self, nbt: &mut NbtCompound) {
+        let saved = match self.holder() {
+            Some(holder) if holder.entity_type().key == vanilla_entities::LEASH_KNOT.key => {
+                let pos = holder.position();
+                Some(DelayedHolder::Knot(BlockPos::containing(
+                    pos.x, pos.y, pos.z,
+                )))
+            }
+            Some(holder) => Some(DelayedHolder::Entity(holder.uuid())),
+            None => self.delayed,
+        };
+
+        match saved {
+            Some(DelayedHolder::Entity(uuid)) => {
+                let mut holder = NbtCompound::new();
+                holder.insert("UUID", NbtTag::IntArray(uuid.to_int_array().to_vec()));
+                nbt.insert("leash", NbtTag::Compound(holder));
+            }
+            Some(DelayedHolder::Knot(pos)) => {
+                nbt.insert("leash", NbtTag::IntArray(vec![pos.x(), pos.y(), pos.z()]));
+            }
+            None => {}
+        }
+    }
+
+    /// Loads a leash saved by [`LeashState::save`], resolved later by [`tick_leash`].
+    ///
+    /// Vanilla: `Leashable.readLeashData()`.
+    pub fn load(&mut self, nbt: &NbtCompoundView<'_, '_>) {
+        let delayed = if let Some(holder) = nbt.compound("leash") {
+            holder
+                .int_array("UUID")
+                .and_then(|uuid| Uuid::from_int_array(&uuid))
+                .map(DelayedHolder::Entity)
+        } else if let Some(pos) = nbt.int_array("leash")
+            && let [x, y, z] = pos[..]
+        {
+            Some(DelayedHolder::Knot(BlockPos::new(x, y, z)))
+        } else {
+            None
+        };
+        if delayed.is_some() {
+            self.delayed = delayed;
+            self.delayed_ticks = 0;
+        }
+    }
+}
+
+/// Gets the entity holding the leash of `entity`, if any.
+///
+/// Vanilla: `Leashable.getLeashHolder()`.
+#[must_use]
+pub fn leash_holder(entity: &dyn Entity) -> Option<SharedEntity> {
+    entity.leash().and_then(|leash| leash.lock().holder())
+}
+
+/// Returns true if `entity` is tied to a holder.
+///
+/// Vanilla: `Leashable.isLeashed()`.
+#[must_use]
+pub fn is_leashed(entity: &dyn Entity) -> bool {
+    entity
+        .leash()
+        .is_some_and(|leash| leash.lock().holder.is_some())
+}
+
+fn is_held_by(entity: &dyn Entity, holder: &dyn Entity) -> bool {
+    leash_holder(entity).is_some_and(|current| current.id() == holder.id())
+}
+
+/// Gets the distance between the centers of two entities.
+///
+/// Vanilla: `Leashable.leashDistanceTo()`.
+fn leash_distance(entity: &dyn Entity, holder: &dyn Entity) -> f64 {
+    center(&entity.bounding_box()).distance(center(&holder.bounding_box()))
+}
+
+fn center(aabb: &AABBd) -> DVec3 {
+    DVec3::new(
+        (aabb.min_x + aabb.max_x) / 2.0,
+        (aabb.min_y + aabb.max_y) / 2.0,
+        (aabb.min_z + aabb.max_z) / 2.0,
+    )
+}
+
+/// Returns true if `entity` may be tied to `holder`.
+///
+/// Vanilla: `Leashable.canHaveALeashAttachedTo()`.
+#[must_use]
+pub fn can_attach_to(entity: &dyn Entity, holder: &dyn Entity) -> bool {
+    entity.id() != holder.id()
+        && entity.can_be_leashed()
+        && leash_distance(entity, holder) <= LEASH_SNAP_DISTANCE
+}
+
+/// Ties `entity` to `holder`, replacing any previous holder.
+///
+/// Vanilla: `Leashable.setLeashedTo()`.
+pub fn set_leashed_to(entity: &dyn Entity, holder: &SharedEntity) {
+    let Some(leash) = entity.leash() else {
+        return;
+    };
+    let previous = {
+        let mut leash = leash.lock();
+        leash.delayed = None;
+        leash.holder.replace(Arc::downgrade(holder))
+    };
+    if let Some(previous) = previous.and_then(|previous| previous.upgrade())
+        && previous.id() != holder.id()
+    {
+        notify_leashee_removed(&previous);
+    }
+
+    broadcast_link(entity, holder.id());
+    if entity.is_passenger() {
+        riding::stop_riding(entity);
+    }
+}
+
+/// Unties `entity`, dropping a lead if `drop_item` is set.
+///
+/// Vanilla: `Leashable.dropLeash()` and `Leashable.removeLeash()`.
+pub fn drop_leash(entity: &dyn Entity, drop_item: bool) {
+    let Some(leash) = entity.leash() else {
+        return;
+    };
+    let previous = {
+        let mut leash = leash.lock();
+        leash.delayed = None;
+        leash.holder.take()
+    };
+    let Some(previous) = previous else {
+        return;
+    };
+
+    if drop_item {
+        entity.spawn_at_location(ItemStack::new(&vanilla_items::ITEMS.lead), 0.0);
+    }
+    broadcast_link(entity, 0);
+    if let Some(previous) = previous.upgrade() {
+        notify_leashee_removed(&previous);
+    }
+}
+
+/// Removes leash knots that no longer hold anything.
+///
+/// Vanilla: `Entity.notifyLeasheeRemoved()`, overridden by `LeashFenceKnotEntity`.
+fn notify_leashee_removed(holder: &SharedEntity) {
+    if holder.entity_type().key == vanilla_entities::LEASH_KNOT.key
+        && leashed_to(holder.as_ref()).is_empty()
+    {
+        holder.set_removed(RemovalReason::Discarded);
+    }
+}
+
+/// Gets the leashable entities around `center` that match `filter`.
+///
+/// Vanilla: `Leashable.leashableInArea()`.
+#[must_use]
+pub fn leashable_in_area(
+    world: &World,
+    center: DVec3,
+    filter: impl Fn(&SharedEntity) -> bool,
+) -> Vec<SharedEntity> {
+    let half = LEASHABLE_SEARCH_SIZE / 2.0;
+    let area = AABBd::new(
+        center.x - half,
+        center.y - half,
+        center.z - half,
+        center.x + half,
+        center.y + half,
+        center.z + half,
+    );
+    world
+        .get_entities_in_aabb(&area)
+        .into_iter()
+        .filter(|entity| entity.leash().is_some() && filter(entity))
+        .collect()
+}
+
+/// Gets the entities tied to `holder`.
+///
+/// Vanilla: `Leashable.leashableLeashedTo()`.
+#[must_use]
+pub fn leashed_to(holder: &dyn Entity) -> Vec<SharedEntity> {
+    let Some(world) = holder.level() else {
+        return Vec::new();
+    };
+    leashable_in_area(&world, center(&holder.bounding_box()), |entity| {
+        is_held_by(entity.as_ref(), holder)
+    })
+}
+
+/// Ties every entity `player` holds to a leash knot on the fence at `pos`.
+///
+/// Vanilla: `LeadItem.bindPlayerMobs()`.
+pub fn bind_player_mobs(world: &Arc<World>, player: &Player, pos: BlockPos) -> InteractionResult {
+    let center = DVec3::new(
+        f64::from(pos.x()) + 0.5,
+        f64::from(pos.y()) + 0.5,
+        f64::from(pos.z()) + 0.5,
+    );
+    let leashed = leashable_in_area(world, center, |entity| is_held_by(entity.as_ref(), player));
+    if leashed.is_empty() {
+        return InteractionResult::Pass;
+    }
+
+    let knot = LeashFenceKnotEntity::get_or_create(world, pos);
+    knot.play_sound(ITEM_LEAD_TIED, 1.0, 1.0);
+    for entity in &leashed {
+        if can_attach_to(entity.as_ref(), knot.as_ref()) {
+            set_leashed_to(entity.as_ref(), &knot);
+        }
+    }
+    // TODO: Emit the BLOCK_ATTACH game event once game events exist
+    InteractionResult::Success
+}
+
+/// Handles the leash part of a player right-clicking `entity`.
+///
+/// A sneaking player ties the entities they hold to a leashable entity, clicking
+/// an entity the player holds unties it and clicking with a lead ties it.
+///
+/// Vanilla: the leash handling in `Entity.interact()`.
+pub fn interact(
+    entity: &SharedEntity,
+    player: &Arc<Player>,
+    hand: InteractionHand,
+) -> InteractionResult {
+    let alive = !entity.is_removed();
+    if player.is_secondary_use_active() && entity.can_be_leashed() && alive {
+        // TODO: Skip babies once there are ageable leashable entities
+        let leashed = leashed_to(player.as_ref());
+        let mut attached = false;
+        for leashed in &leashed {
+            if can_attach_to(leashed.as_ref(), entity.as_ref()) {
+                set_leashed_to(leashed.as_ref(), entity);
+                attached = true;
+            }
+        }
+        if attached {
+            entity.play_sound(ITEM_LEAD_TIED, 1.0, 1.0);
+            return InteractionResult::Success;
+        }
+    }
+
+    // TODO: Shears cut all leashes attached to the entity (Entity.shearOffAllLeashConnections)
+    if !alive || entity.leash().is_none() {
+        return InteractionResult::Pass;
+    }
+
+    if is_held_by(entity.as_ref(), player.as_ref()) {
+        drop_leash(entity.as_ref(), !player.has_infinite_materials());
+        entity.play_sound(ITEM_LEAD_UNTIED, 1.0, 1.0);
+        return InteractionResult::Success;
+    }
+
+    let holding_lead = player
+        .inventory
+        .lock()
+        .get_item_in_hand(hand)
+        .is(&vanilla_items::ITEMS.lead);
+    let held_by_player =
+        leash_holder(entity.as_ref()).is_some_and(|holder| holder.as_player().is_some());
+    if !holding_lead || held_by_player {
+        return InteractionResult::Pass;
+    }
+
+    let player_entity: SharedEntity = player.clone();
+    if can_attach_to(entity.as_ref(), player.as_ref()) {
+        if is_leashed(entity.as_ref()) {
+            drop_leash(entity.as_ref(), true);
+        }
+        set_leashed_to(entity.as_ref(), &player_entity);
+        entity.play_sound(ITEM_LEAD_TIED, 1.0, 1.0);
+        player.inventory.lock().get_item_in_hand_mut(hand).shrink(1);
+    }
+    InteractionResult::Success
+}
+
+/// Ticks the leash of `entity`: resolves a saved holder, breaks the leash when the
+/// holder is gone or too far away and pulls the entity towards its holder.
+///
+/// Vanilla: `Leashable.tickLeash()`.
+pub fn tick_leash(entity: &dyn Entity) {
+    let Some(leash) = entity.leash() else {
+        return;
+    };
+    let Some(world) = entity.level() else {
+        return;
+    };
+
+    let (holder, delayed) = {
+        let leash = leash.lock();
+        (leash.holder.clone(), leash.delayed)
+    };
+    if let Some(delayed) = delayed {
+        restore_holder(entity, &world, delayed);
+    }
+    let Some(holder) = holder else {
+        return;
+    };
+
+    let Some(holder) = holder.upgrade().filter(|holder| !holder.is_removed()) else {
+        let drops = world.get_game_rule(ENTITY_DROPS).as_bool().unwrap_or(true);
+        drop_leash(entity, drops);
+        return;
+    };
+
+    let distance = leash_distance(entity, holder.as_ref());
+    if distance > LEASH_SNAP_DISTANCE {
+        let pos = holder.position();
+        world.play_sound_at(ITEM_LEAD_BREAK, entity.sound_source(), pos, 1.0, 1.0, None);
+        drop_leash(entity, true);
+    } else if distance
+        > LEASH_ELASTIC_DISTANCE
+            - f64::from(holder.entity_type().dimensions.width)
+            - f64::from(entity.entity_type().dimensions.width)
+    {
+        // TODO: Replace with vanilla's momentum based leash physics (checkElasticInteractions)
+        let pull = (holder.position() - entity.position()) / distance;
+        let pull = DVec3::new(
+            (pull.x * pull.x * 0.4).copysign(pull.x),
+            (pull.y * pull.y * 0.4).copysign(pull.y),
+            (pull.z * pull.z * 0.4).copysign(pull.z),
+        );
+        entity.set_velocity(entity.velocity() + pull);
+    }
+}
+
+/// Ties `entity` to its saved holder once that is loaded, or gives up after a while.
+///
+/// Vanilla: `Leashable.restoreLeashFromSave()`.
+fn restore_holder(entity: &dyn Entity, world: &Arc<World>, delayed: DelayedHolder) {
+    let holder = match delayed {
+        DelayedHolder::Entity(uuid) => world.get_entity_by_uuid(&uuid).or_else(|| {
+            world
+                .players
+                .get_by_uuid(&uuid)
+                .map(|player| player as SharedEntity)
+        }),
+        DelayedHolder::Knot(pos) => Some(LeashFenceKnotEntity::get_or_create(world, pos)),
+    };
+    if let Some(holder) = holder {
+        set_leashed_to(entity, &holder);
+        return;
+    }
+
+    let Some(leash) = entity.leash() else {
+        return;
+    };
+    let give_up = {
+        let mut leash = leash.lock();
+        leash.delayed_ticks += 1;
+        leash.delayed_ticks > DELAYED_HOLDER_TICKS
+    };
+    if give_up {
+        entity.spawn_at_location(ItemStack::new(&vanilla_items::ITEMS.lead), 0.0);
+        let mut leash = leash.lock();
+        leash.delayed = None;
+        leash.delayed_ticks = 0;
+    }
+}
+
+/// Builds the packet that syncs the leash of `entity` to clients.
+///
+/// Returns `None` if the entity isn't leashed.
+#[must_use]
+pub fn link_packet(entity: &dyn Entity) -> Option<CSetEntityLink> {
+    leash_holder(entity).map(|holder| CSetEntityLink {
+        source_id: entity.id(),
+        dest_id: holder.id(),
+    })
+}
+
+/// Sends the leash of `entity` to every player tracking it, `0` removing it.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "world coordinates are always within i32 range in a valid Minecraft world"
+)]
+fn broadcast_link(entity: &dyn Entity, holder_id: i32) {
+    let Some(world) = entity.level() else {
+        return;
+    };
+    let pos = entity.position();
+    let chunk = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+    let packet = CSetEntityLink {
+        source_id: entity.id(),
+        dest_id: holder_id,
+    };
+    world.broadcast_to_nearby(chunk, packet, None);
+}
@@ -0,0 +1,294 @@
+//! Shared behavior for mobs, the living entities that think for themselves.
+//!
+//! Mirrors the parts of vanilla's `Mob` that don't depend on a specific mob:
+//! the synched `DATA_MOB_FLAGS_ID` byte and the `NoAI` flag it carries.
+
+use glam::DVec3;
+use simdnbt::borrow::{BaseNbtCompound, NbtCompound as NbtCompoundView};
+use simdnbt::owned::NbtCompound;
+
+use crate::entity::LivingEntity;
+use crate::physics::MoverType;
+
+/// Mob flag bit: the mob doesn't run its AI. Vanilla: `Mob.MOB_FLAG_NO_AI`.
+pub const MOB_FLAG_NO_AI: i8 = 1;
+/// Mob flag bit: the mob uses its left hand. Vanilla: `Mob.MOB_FLAG_LEFTHANDED`.
+pub const MOB_FLAG_LEFT_HANDED: i8 = 2;
+
+/// Horizontal drag while walking on a block with the default friction of 0.6.
+///
+/// Vanilla: `LivingEntity.travelInAir()`, `blockFriction * 0.91`.
+const GROUND_DRAG: f64 = 0.6 * 0.91;
+/// Horizontal drag while in the air.
+const AIR_DRAG: f64 = 0.91;
+/// Vertical drag. Vanilla: `LivingEntity.travelInAir()`.
+const VERTICAL_DRAG: f64 = 0.98;
+
+/// Returns `flags` with `flag` set or cleared.
+#[must_use]
+pub const fn with_mob_flag(flags: i8, flag: i8, value: bool) -> i8 {
+    if value { flags | flag } else { flags & !flag }
+}
+
+/// A living entity with AI.
+///
+/// Implementors store the flags in the `mob_flags_id` field of their generated
+/// entity data and call [`Mob::ai_step`] from [`Entity::tick`](super::Entity::tick).
+///
+/// Mirrors vanilla's `Mob`.
+pub trait Mob: LivingEntity {
+    /// Gets the synched mob flags byte.
+    fn mob_flags(&self) -> i8;
+
+    /// Sets the synched mob flags byte.
+    fn set_mob_flags(&self, flags: i8);
+
+    /// Returns true if the mob's AI is switched off.
+    ///
+    /// Vanilla: `Mob.isNoAi()`.
+    fn is_no_ai(&self) -> bool {
+        self.mob_flags() & MOB_FLAG_NO_AI != 0
+    }
+
+    /// Freezes (`true`) or unfreezes (`false`) the mob's AI.
+    ///
+    /// A frozen mob keeps being affected by gravity and other forces, it just
+    /// stops running goals and pathfinding.
+    ///
+    /// Vanilla: `Mob.setNoAi()`.
+    fn set_no_ai(&self, no_ai: bool) {
+        self.set_mob_flags(with_mob_flag(self.mob_flags(), MOB_FLAG_NO_AI, no_ai));
+    }
+
+    /// Returns true if the mob uses its left hand.
+    ///
+    /// Vanilla: `Mob.isLeftHanded()`.
+    fn is_left_handed(&self) -> bool {
+        self.mob_flags() & MOB_FLAG_LEFT_HANDED != 0
+    }
+
+    /// Sets whether the mob uses its left hand.
+    ///
+    /// Vanilla: `Mob.setLeftHanded()`.
+    fn set_left_handed(&self, left_handed: bool) {
+        self.set_mob_flags(with_mob_flag(
+            self.mob_flags(),
+            MOB_FLAG_LEFT_HANDED,
+            left_handed,
+        ));
+    }
+
+    /// Returns true if the mob never despawns, e.g. after being named.
+    ///
+    /// Vanilla: `Mob.isPersistenceRequired()`.
+    fn is_persistence_required(&self) -> bool;
+
+    /// Keeps the mob from despawning.
+    ///
+    /// Vanilla: `Mob.setPersistenceRequired()`.
+    fn set_persistence_required(&self, persistence_required: bool);
+
+    /// Returns true if the server should run this mob's AI.
+    ///
+    /// Vanilla: `Mob.isEffectiveAi()`.
+    fn is_effective_ai(&self) -> bool {
+        !self.is_no_ai()
+    }
+
+    /// Runs the mob's goals, target selection and pathfinding for one tick.
+    ///
+    /// Vanilla: `Mob.serverAiStep()`.
+    // TODO: Goal selectors, navigation, move/look/jump controls and sensing
+    fn server_ai_step(&self) {}
+
+    /// Ticks the mob's AI and movement.
+    ///
+    /// The AI is skipped while the mob is dead or has `NoAI` set, but the mob
+    /// still falls and gets moved by its velocity.
+    ///
+    /// Vanilla: `LivingEntity.aiStep()`.
+    fn ai_step(&self) {
+        if !self.is_dead_or_dying() && self.is_effective_ai() {
+            self.server_ai_step();
+        }
+        // TODO: Fluid movement, block friction and input-driven travel (LivingEntity.travel)
+        let on_ground = self.on_ground();
+        self.apply_gravity();
+        self.do_move(MoverType::SelfMovement);
+        let drag = if on_ground { GROUND_DRAG } else { AIR_DRAG };
+        let velocity = self.velocity();
+        self.set_velocity(DVec3::new(
+            velocity.x * drag,
+            velocity.y * VERTICAL_DRAG,
+            velocity.z * drag,
+        ));
+    }
+
+    /// Saves the mob data shared by all mobs.
+    ///
+    /// Call from [`Entity::save_additional`](super::Entity::save_additional).
+    /// Vanilla: `Mob.addAdditionalSaveData()`.
+    fn save_mob_data(&self, nbt: &mut NbtCompound) {
+        if self.is_no_ai() {
+            nbt.insert("NoAI", 1i8);
+        }
+        nbt.insert("LeftHanded", i8::from(self.is_left_handed()));
+        nbt.insert(
+            "PersistenceRequired",
+            i8::from(self.is_persistence_required()),
+        );
+        // TODO: Save CanPickUpLoot and equipment drop chances
+    }
+
+    /// Loads the mob data shared by all mobs.
+    ///
+    /// Call from [`Entity::load_additional`](super::Entity::load_additional).
+    /// Vanilla: `Mob.readAdditionalSaveData()`.
+    fn load_mob_data(&self, nbt: &BaseNbtCompound<'_>) {
+        let nbt: NbtCompoundView<'_, '_> = nbt.into();
+        self.set_no_ai(nbt.byte("NoAI").is_some_and(|no_ai| no_ai != 0));
+        self.set_left_handed(nbt.byte("LeftHanded").is_some_and(|left| left != 0));
+        self.set_persistence_required(
+            nbt.byte("PersistenceRequired")
+                .is_some_and(|persistent| persistent != 0),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::atomic::{AtomicBool, AtomicI8, AtomicU32, Ordering};
+
+    use simdnbt::borrow::read_compound;
+    use steel_registry::blocks::shapes::AABBd;
+    use steel_registry::entity_types::EntityTypeRef;
+    use steel_registry::vanilla_entities;
+    use steel_utils::locks::SyncMutex;
+
+    use super::*;
+    use crate::entity::attribute::AttributeMap;
+    use crate::entity::{Entity, LivingEntityBase};
+
+    /// A mob with its flags in atomics, counting how often its AI runs.
+    #[derive(Default)]
+    struct TestMob {
+        flags: AtomicI8,
+        persistence_required: AtomicBool,
+        velocity: SyncMutex<DVec3>,
+        ai_steps: AtomicU32,
+        attributes: SyncMutex<AttributeMap>,
+        living_base: SyncMutex<LivingEntityBase>,
+    }
+
+    impl Entity for TestMob {
+        fn entity_type(&self) -> EntityTypeRef {
+            vanilla_entities::ZOMBIE
+        }
+
+        fn bounding_box(&self) -> AABBd {
+            AABBd::entity_box(0.0, 0.0, 0.0, 0.3, 1.95)
+        }
+
+        fn velocity(&self) -> DVec3 {
+            *self.velocity.lock()
+        }
+
+        fn set_velocity(&self, velocity: DVec3) {
+            *self.velocity.lock() = velocity;
+        }
+
+        fn get_default_gravity(&self) -> f64 {
+            0.08
+        }
+    }
+
+    impl LivingEntity for TestMob {
+        fn get_health(&self) -> f32 {
+            20.0
+        }
+
+        fn set_health(&self, _health: f32) {}
+
+        fn attributes(&self) -> &SyncMutex<AttributeMap> {
+            &self.attributes
+        }
+
+        fn living_base(&self) -> &SyncMutex<LivingEntityBase> {
+            &self.living_base
+        }
+
+        fn get_absorption_amount(&self) -> f32 {
+            0.0
+        }
+
+        fn set_absorption_amount(&self, _amount: f32) {}
+
+        fn set_sprinting(&self, _sprinting: bool) {}
+
+        fn get_speed(&self) -> f32 {
+            0.0
+        }
+
+        fn set_speed(&self, _speed: f32) {}
+    }
+
+    impl Mob for TestMob {
+        fn mob_flags(&self) -> i8 {
+            self.flags.load(Ordering::Relaxed)
+        }
+
+        fn set_mob_flags(&self, flags: i8) {
+            self.flags.store(flags, Ordering::Relaxed);
+        }
+
+        fn is_persistence_required(&self) -> bool {
+            self.persistence_required.load(Ordering::Relaxed)
+        }
+
+        fn set_persistence_required(&self, persistence_required: bool) {
+            self.persistence_required
+                .store(persistence_required, Ordering::Relaxed);
+        }
+
+        fn server_ai_step(&self) {
+            self.ai_steps.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn no_ai_survives_save_and_load() {
+        let mob = TestMob::default();
+        mob.set_no_ai(true);
+        mob.set_left_handed(true);
+        let mut nbt = NbtCompound::new();
+        mob.save_mob_data(&mut nbt);
+
+        let mut bytes = Vec::new();
+        nbt.write(&mut bytes);
+        let nbt = read_compound(&mut Cursor::new(&bytes)).expect("saved mob data is valid NBT");
+        let loaded = TestMob::default();
+        loaded.load_mob_data(&nbt);
+        assert!(loaded.is_no_ai());
+        assert!(loaded.is_left_handed());
+        assert_eq!(loaded.mob_flags(), MOB_FLAG_NO_AI | MOB_FLAG_LEFT_HANDED);
+
+        loaded.set_no_ai(false);
+        let mut nbt = NbtCompound::new();
+        loaded.save_mob_data(&mut nbt);
+        assert!(nbt.get("NoAI").is_none());
+    }
+
+    #[test]
+    fn no_ai_skips_the_ai_but_not_gravity() {
+        let mob = TestMob::default();
+        mob.ai_step();
+        assert_eq!(mob.ai_steps.load(Ordering::Relaxed), 1);
+
+        mob.set_no_ai(true);
+        mob.set_velocity(DVec3::ZERO);
+        mob.ai_step();
+        assert_eq!(mob.ai_steps.load(Ordering::Relaxed), 1);
+        assert!(mob.velocity().y < 0.0);
+    }
+}
@@ -4,22 +4,33 @@ use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::{Arc, Weak};
 
 use glam::DVec3;
-use simdnbt::borrow::BaseNbtCompound;
+use simdnbt::ToNbtTag;
+use simdnbt::borrow::{BaseNbtCompound, NbtCompound as NbtCompoundView};
 use simdnbt::owned::NbtCompound;
+use steel_protocol::packets::game::SoundSource;
+use steel_registry::attribute::AttributeRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::shapes::AABBd;
 use steel_registry::entity_data::DataValue;
 use steel_registry::entity_types::EntityTypeRef;
 use steel_registry::item_stack::ItemStack;
+use steel_registry::{vanilla_attributes, vanilla_damage_types};
 use steel_utils::locks::SyncMutex;
+use steel_utils::types::InteractionHand;
+use steel_utils::{BlockPos, BlockStateId};
+use text_components::TextComponent;
 use uuid::Uuid;
 
+use crate::behavior::{BLOCK_BEHAVIORS, InteractionResult};
+use crate::event::{EVENTS, EntityDamageEvent};
 use crate::physics::{
     EntityPhysicsState, MoveResult, MoverType, WorldCollisionProvider, move_entity,
 };
 use crate::world::World;
 use crate::{entity::damage::DamageSource, player::Player};
 
-use entities::ItemEntity;
+use attribute::AttributeMap;
+use entities::{BoatEntity, EnderDragonEntity, ItemEntity};
 
 /// Global counter for allocating unique entity IDs.
 ///
@@ -36,15 +47,33 @@ pub fn next_entity_id() -> i32 {
     ENTITY_COUNTER.fetch_add(1, Ordering::Relaxed)
 }
 
+/// Allocates `count` consecutive entity IDs and returns the first one.
+///
+/// Used by entities made of several parts, like the ender dragon, whose part
+/// IDs the client derives from the entity's own ID.
+#[must_use]
+pub fn reserve_entity_ids(count: i32) -> i32 {
+    ENTITY_COUNTER.fetch_add(count, Ordering::Relaxed)
+}
+
+pub mod activation;
+pub mod ai;
+pub mod attribute;
 mod base;
 mod cache;
 mod callback;
 pub mod damage;
+pub mod effect;
 pub mod entities;
+pub mod leash;
 mod living_base;
+mod mob;
+pub mod projectile;
 mod registry;
+pub mod riding;
 mod storage;
 mod tracker;
+mod vehicle;
 
 pub use base::EntityBase;
 pub use cache::EntityCache;
@@ -52,10 +81,16 @@ pub use callback::{
     EntityChunkCallback, EntityLevelCallback, NullEntityCallback, PlayerEntityCallback,
     RemovalReason,
 };
+pub use leash::LeashState;
 pub use living_base::{DEATH_DURATION, LivingEntityBase};
-pub use registry::{ENTITIES, EntityRegistry, init_entities};
+pub use mob::{MOB_FLAG_LEFT_HANDED, MOB_FLAG_NO_AI, Mob, with_mob_flag};
+pub use registry::{
+    ENTITIES, EntityRegistry, init_entities, save_entity, saved_entity_position, spawn_saved_entity,
+};
+pub use riding::RidingState;
 pub use storage::EntityStorage;
 pub use tracker::EntityTracker;
+pub use vehicle::VehicleEntity;
 
 /// Type alias for a shared entity reference.
 pub type SharedEntity = Arc<dyn Entity>;
@@ -180,6 +215,21 @@ pub trait Entity: Send + Sync {
         None
     }
 
+    /// Gets the entity as a [`Mob`] if it is one.
+    fn as_mob(self: Arc<Self>) -> Option<Arc<dyn Mob>> {
+        None
+    }
+
+    /// Gets the entity as a [`BoatEntity`] if it is one.
+    fn as_boat(self: Arc<Self>) -> Option<Arc<BoatEntity>> {
+        None
+    }
+
+    /// Gets the entity as an [`EnderDragonEntity`] if it is one.
+    fn as_ender_dragon(self: Arc<Self>) -> Option<Arc<EnderDragonEntity>> {
+        None
+    }
+
     /// Gets the entity's rotation as (yaw, pitch) in degrees.
     ///
     /// Yaw is horizontal rotation (0-360), pitch is vertical (-90 to 90).
@@ -187,6 +237,9 @@ pub trait Entity: Send + Sync {
         (0.0, 0.0)
     }
 
+    /// Sets the entity's rotation as (yaw, pitch) in degrees.
+    fn set_rotation(&self, _rotation: (f32, f32)) {}
+
     /// Gets the eye height for this entity.
     ///
     /// Default implementation returns the eye height from the entity type dimensions.
@@ -266,17 +319,23 @@ pub trait Entity: Send + Sync {
         }
     }
 
-    /// Moves the entity with collision detection.
+    /// Moves the entity by its velocity with collision detection.
+    ///
+    /// Mirrors vanilla's `Entity.move(MoverType, getDeltaMovement())`.
+    fn do_move(&self, mover_type: MoverType) -> Option<MoveResult> {
+        self.move_by(mover_type, self.velocity())
+    }
+
+    /// Moves the entity by `delta` with collision detection.
     ///
     /// Mirrors vanilla's `Entity.move(MoverType, Vec3)`.
     /// Updates position, `on_ground`, velocity (on collision), and returns collision info.
-    fn do_move(&self, mover_type: MoverType) -> Option<MoveResult> {
+    fn move_by(&self, mover_type: MoverType, delta: DVec3) -> Option<MoveResult> {
         let world = self.level()?;
-        let velocity = self.velocity();
 
         // Build physics state
         let mut physics_state = EntityPhysicsState::new(self.position(), self.entity_type());
-        physics_state.velocity = velocity;
+        physics_state.velocity = self.velocity();
         physics_state.on_ground = self.on_ground();
         // Most entities don't step up; override for entities that do
         physics_state.max_up_step = 0.0;
@@ -284,12 +343,31 @@ pub trait Entity: Send + Sync {
 
         // Perform collision detection and movement
         let collision_world = WorldCollisionProvider::new(&world);
-        let result = move_entity(&physics_state, velocity, mover_type, &collision_world);
+        let result = move_entity(&physics_state, delta, mover_type, &collision_world);
 
         // Update entity state
+        let start = self.position();
         self.set_position(result.final_position);
         self.set_on_ground(result.on_ground);
 
+        // Vanilla: Entity.applyMovementEmissionAndPlaySound()
+        // TODO: Climbing and swimming sounds
+        if self.emits_step_sounds()
+            && let Some(base) = self.base()
+        {
+            let moved = result.final_position - start;
+            let distance = (moved.x * moved.x + moved.z * moved.z).sqrt() as f32 * 0.6;
+            let on_pos = BlockPos::new(
+                result.final_position.x.floor() as i32,
+                (result.final_position.y - 0.2).floor() as i32,
+                result.final_position.z.floor() as i32,
+            );
+            let on_state = world.get_block_state(on_pos);
+            if base.advance_steps(distance, result.on_ground && !on_state.is_air()) {
+                self.play_step_sound(on_state);
+            }
+        }
+
         // Vanilla: Entity.move() zeros velocity components on collision.
         // Horizontal collision zeros X/Z individually based on which axis collided.
         // Vertical collision calls Block.updateEntityMovementAfterFallOn which by default zeros Y.
@@ -328,9 +406,149 @@ pub trait Entity: Send + Sync {
         world.spawn_item(DVec3::new(pos.x, pos.y + y_offset, pos.z), item)
     }
 
+    /// Called when `player` right-clicks this entity with `hand`.
+    ///
+    /// Vanilla: `Entity.interact()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameters used by overrides"
+    )]
+    fn interact(self: Arc<Self>, player: &Arc<Player>, hand: InteractionHand) -> InteractionResult {
+        InteractionResult::Pass
+    }
+
+    /// Returns the item a player picks when middle-clicking this entity.
+    ///
+    /// Mirrors vanilla's `Entity.getPickResult()`; most entities have none.
+    fn get_pick_result(&self) -> Option<ItemStack> {
+        None
+    }
+
+    // === Sounds ===
+
+    /// Gets the category this entity's sounds play in.
+    ///
+    /// Vanilla: `Entity.getSoundSource()`.
+    fn sound_source(&self) -> SoundSource {
+        SoundSource::Neutral
+    }
+
+    /// Plays a sound at this entity's position for nearby players.
+    ///
+    /// Vanilla: `Entity.playSound()`.
+    // TODO: Skip silent entities
+    fn play_sound(&self, sound_id: i32, volume: f32, pitch: f32) {
+        if let Some(world) = self.level() {
+            world.play_sound_at(
+                sound_id,
+                self.sound_source(),
+                self.position(),
+                volume,
+                pitch,
+                None,
+            );
+        }
+    }
+
+    /// Returns true if walking plays step sounds.
+    ///
+    /// Vanilla: `Entity.getMovementEmission().emitsSounds()`.
+    fn emits_step_sounds(&self) -> bool {
+        false
+    }
+
+    /// Plays the step sound of the block the entity walks on.
+    ///
+    /// Vanilla: `Entity.playStepSound()`.
+    fn play_step_sound(&self, state: BlockStateId) {
+        let sound_type = BLOCK_BEHAVIORS
+            .get_behavior(state.get_block())
+            .get_sound_type(state);
+        self.play_sound(
+            sound_type.step_sound,
+            sound_type.volume * 0.15,
+            sound_type.pitch,
+        );
+    }
+
+    /// Returns the `data` field sent in the add entity packet.
+    ///
+    /// Mirrors vanilla's `Entity.getAddEntityPacket()`; falling blocks send their
+    /// block state, most entities send 0.
+    fn spawn_data(&self) -> i32 {
+        0
+    }
+
+    // === Custom Names ===
+
+    /// Gets the name given to this entity by a name tag or command.
+    ///
+    /// Vanilla: `Entity.getCustomName()`.
+    fn custom_name(&self) -> Option<TextComponent> {
+        None
+    }
+
+    /// Sets or clears the custom name.
+    ///
+    /// Vanilla: `Entity.setCustomName()`.
+    fn set_custom_name(&self, _name: Option<TextComponent>) {}
+
+    /// Returns true if the custom name is always rendered, not just when looked at.
+    ///
+    /// Vanilla: `Entity.isCustomNameVisible()`.
+    fn is_custom_name_visible(&self) -> bool {
+        false
+    }
+
+    /// Sets whether the custom name is always rendered.
+    ///
+    /// Vanilla: `Entity.setCustomNameVisible()`.
+    fn set_custom_name_visible(&self, _visible: bool) {}
+
     // === Persistence Methods ===
     // These mirror vanilla's Entity.addAdditionalSaveData/readAdditionalSaveData.
 
+    /// Saves the data shared by all entities, then the type-specific data.
+    ///
+    /// Base fields (pos, motion, rotation, uuid, `on_ground`) are handled by
+    /// the serialization layer.
+    ///
+    /// Mirrors the custom name part of vanilla's `Entity.saveWithoutId()`.
+    fn save_data(&self, nbt: &mut NbtCompound) {
+        if let Some(name) = self.custom_name() {
+            nbt.insert("CustomName", name.to_nbt_tag());
+        }
+        if self.is_custom_name_visible() {
+            nbt.insert("CustomNameVisible", 1i8);
+        }
+        if self.can_be_leashed()
+            && let Some(leash) = self.leash()
+        {
+            leash.lock().save(nbt);
+        }
+        self.save_additional(nbt);
+    }
+
+    /// Loads the data saved by [`Entity::save_data`].
+    ///
+    /// Mirrors the custom name part of vanilla's `Entity.load()`.
+    fn load_data(&self, nbt: &BaseNbtCompound<'_>) {
+        {
+            let view: NbtCompoundView<'_, '_> = nbt.into();
+            self.set_custom_name(
+                view.get("CustomName")
+                    .and_then(|name| TextComponent::from_nbt(&name.to_owned())),
+            );
+            self.set_custom_name_visible(view.byte("CustomNameVisible").is_some_and(|v| v != 0));
+            if self.can_be_leashed()
+                && let Some(leash) = self.leash()
+            {
+                leash.lock().load(&view);
+            }
+        }
+        self.load_additional(nbt);
+    }
+
     /// Saves type-specific entity data to NBT.
     ///
     /// Called during chunk serialization. Implementors should save all data
@@ -384,6 +602,218 @@ pub trait Entity: Send + Sync {
     fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
         false
     }
+
+    /// Damages this entity through [`Self::hurt`], after [`EntityDamageEvent`]
+    /// listeners had the chance to change or cancel the damage.
+    ///
+    /// All damage should go through here rather than calling `hurt` directly.
+    fn take_damage(&self, source: &DamageSource, amount: f32) -> bool {
+        if !EVENTS.has_listeners::<EntityDamageEvent>() {
+            return self.hurt(source, amount);
+        }
+        let mut event = EntityDamageEvent::new(
+            self.id(),
+            self.uuid(),
+            self.entity_type(),
+            source.clone(),
+            amount,
+        );
+        if !EVENTS.post_cancellable(&mut event) {
+            return false;
+        }
+        self.hurt(&event.source, event.amount)
+    }
+
+    /// Damages this entity for landing after falling `fall_distance` blocks.
+    ///
+    /// `multiplier` scales the damage, e.g. hay bales pass 0.2. Returns true if
+    /// the entity was hurt. Vanilla: `Entity.causeFallDamage()`, which does
+    /// nothing for non-living entities.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameters used by overrides"
+    )]
+    fn cause_fall_damage(&self, fall_distance: f64, multiplier: f32, source: &DamageSource) -> bool {
+        false
+    }
+
+    /// Returns true if fire and lava do not hurt this entity.
+    ///
+    /// Vanilla: `Entity.fireImmune()`.
+    fn fire_immune(&self) -> bool {
+        self.entity_type().fire_immune
+    }
+
+    /// Gets the number of ticks this entity keeps burning for.
+    ///
+    /// Negative values are a grace period during which fire blocks cannot ignite the entity.
+    fn remaining_fire_ticks(&self) -> i32 {
+        self.base().map_or(0, EntityBase::remaining_fire_ticks)
+    }
+
+    /// Sets the number of ticks this entity keeps burning for.
+    fn set_remaining_fire_ticks(&self, ticks: i32) {
+        if let Some(base) = self.base() {
+            base.set_remaining_fire_ticks(ticks);
+        }
+    }
+
+    /// Marks the entity as standing in the nether portal at `pos` this tick.
+    ///
+    /// Vanilla: `Entity.setAsInsidePortal()`.
+    // TODO: Let other entities travel through portals once they can change dimension
+    fn set_as_inside_portal(&self, _pos: BlockPos) {}
+
+    /// Returns true if the entity is burning.
+    ///
+    /// Vanilla: `Entity.isOnFire()` (server side).
+    fn is_on_fire(&self) -> bool {
+        !self.fire_immune() && self.remaining_fire_ticks() > 0
+    }
+
+    /// Sets the entity on fire for at least `ticks` ticks.
+    ///
+    /// Vanilla: `Entity.igniteForTicks()`.
+    fn ignite_for_ticks(&self, ticks: i32) {
+        if self.remaining_fire_ticks() < ticks {
+            self.set_remaining_fire_ticks(ticks);
+        }
+    }
+
+    /// Sets the entity on fire for at least `seconds` seconds.
+    ///
+    /// Vanilla: `Entity.igniteForSeconds()`.
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "fire durations are small enough to fit in an i32"
+    )]
+    fn ignite_for_seconds(&self, seconds: f32) {
+        self.ignite_for_ticks((seconds * 20.0).floor() as i32);
+    }
+
+    /// Extinguishes the entity, keeping any pending re-ignition grace period.
+    ///
+    /// Vanilla: `Entity.clearFire()`.
+    fn clear_fire(&self) {
+        self.set_remaining_fire_ticks(self.remaining_fire_ticks().min(0));
+    }
+
+    /// Burns the entity for one tick, dealing `on_fire` damage once per second.
+    ///
+    /// Vanilla: the fire section of `Entity.baseTick()`.
+    fn tick_fire(&self) {
+        let remaining = self.remaining_fire_ticks();
+        if remaining <= 0 {
+            return;
+        }
+        if self.fire_immune() {
+            self.clear_fire();
+            return;
+        }
+        // TODO: Skip the damage while in lava once lava contact is tracked
+        if remaining % 20 == 0 {
+            self.take_damage(
+                &DamageSource::environment(vanilla_damage_types::ON_FIRE),
+                1.0,
+            );
+        }
+        self.set_remaining_fire_ticks(remaining - 1);
+    }
+
+    /// Returns the vehicle and passengers of this entity.
+    ///
+    /// Entities returning `None` can neither ride nor be ridden.
+    fn riding(&self) -> Option<&SyncMutex<RidingState>> {
+        self.base().map(EntityBase::riding)
+    }
+
+    /// Returns the leash of this entity.
+    ///
+    /// Entities returning `None` can't be leashed, see also [`Entity::can_be_leashed`].
+    fn leash(&self) -> Option<&SyncMutex<LeashState>> {
+        self.base().map(EntityBase::leash)
+    }
+
+    /// Returns true if a lead can be tied to this entity.
+    ///
+    /// Vanilla: `Leashable.canBeLeashed()`; entities that aren't `Leashable` return false.
+    fn can_be_leashed(&self) -> bool {
+        false
+    }
+
+    /// Gets the entity this entity is riding, if any.
+    fn vehicle(&self) -> Option<SharedEntity> {
+        self.riding().and_then(|riding| riding.lock().vehicle())
+    }
+
+    /// Gets the entities riding this entity.
+    fn passengers(&self) -> Vec<SharedEntity> {
+        self.riding()
+            .map(|riding| riding.lock().passengers().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if this entity is riding another entity.
+    fn is_passenger(&self) -> bool {
+        self.vehicle().is_some()
+    }
+
+    /// Returns true if `passenger` may start riding this entity.
+    ///
+    /// Vanilla: `Entity.canAddPassenger()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameters used by overrides"
+    )]
+    fn can_add_passenger(&self, passenger: &dyn Entity) -> bool {
+        self.passengers().is_empty()
+    }
+
+    /// Returns true if this entity may start riding `vehicle`.
+    ///
+    /// Vanilla: `Entity.canRide()`.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameters used by overrides"
+    )]
+    fn can_ride(&self, vehicle: &dyn Entity) -> bool {
+        true
+    }
+
+    /// Gets the passenger that steers this entity, if any.
+    ///
+    /// Vanilla: `Entity.getControllingPassenger()`.
+    fn controlling_passenger(&self) -> Option<SharedEntity> {
+        None
+    }
+
+    /// Gets where `passenger` sits relative to this entity's position.
+    ///
+    /// Vanilla: `Entity.getPassengerAttachmentPoint()`, which uses the `PASSENGER`
+    /// attachment of the passenger's index, rotated with the vehicle.
+    fn passenger_attachment_point(&self, passenger: &dyn Entity) -> DVec3 {
+        let index = self
+            .passengers()
+            .iter()
+            .position(|entity| entity.id() == passenger.id())
+            .unwrap_or(0);
+        let point = self.entity_type().passenger_attachment(index);
+        riding::rotate_y(point.as_dvec3(), -self.rotation().0.to_radians())
+    }
+
+    /// Gets how far above its feet this entity is attached to a vehicle's seat.
+    ///
+    /// Vanilla: the `VEHICLE` entity attachment.
+    fn vehicle_attachment_y(&self) -> f64 {
+        0.0
+    }
+
+    /// Moves the entity to `pos`, e.g. when dismounting.
+    ///
+    /// Players override this to also sync the new position to their client.
+    fn teleport_to(&self, pos: DVec3) {
+        self.set_position(pos);
+    }
 }
 
 /// A trait for living entities that can take damage, heal, and die.
@@ -401,7 +831,33 @@ pub trait LivingEntity: Entity {
     fn set_health(&self, health: f32);
 
     /// Gets the maximum health of the entity.
-    fn get_max_health(&self) -> f32;
+    ///
+    /// Vanilla: `LivingEntity.getMaxHealth()`.
+    fn get_max_health(&self) -> f32 {
+        self.get_attribute_value(vanilla_attributes::MAX_HEALTH) as f32
+    }
+
+    /// Returns the entity's attributes.
+    fn attributes(&self) -> &SyncMutex<AttributeMap>;
+
+    /// Gets the value of `attribute` with all modifiers applied.
+    ///
+    /// Vanilla: `LivingEntity.getAttributeValue()`.
+    fn get_attribute_value(&self, attribute: AttributeRef) -> f64 {
+        self.attributes().lock().get_value(attribute)
+    }
+
+    /// Attacks `target` with this entity's attack damage.
+    ///
+    /// Returns true if the target was hurt.
+    ///
+    /// Vanilla: `Mob.doHurtTarget()`.
+    fn do_hurt_target(&self, target: &dyn Entity) -> bool {
+        let damage = self.get_attribute_value(vanilla_attributes::ATTACK_DAMAGE) as f32;
+        let source = DamageSource::entity(vanilla_damage_types::MOB_ATTACK, self.id());
+        // TODO: Weapon enchantments, knockback and setLastHurtMob
+        target.take_damage(&source, damage)
+    }
 
     /// Heals the entity by the specified amount.
     fn heal(&self, amount: f32) {
@@ -432,7 +888,12 @@ pub trait LivingEntity: Entity {
     fn set_absorption_amount(&self, amount: f32);
 
     /// Gets the entity's armor value.
-    fn get_armor_value(&self) -> i32;
+    ///
+    /// Vanilla: `LivingEntity.getArmorValue()`.
+    fn get_armor_value(&self) -> i32 {
+        // TODO: Add armor modifiers from equipment once item attribute modifiers are applied
+        self.get_attribute_value(vanilla_attributes::ARMOR).floor() as i32
+    }
 
     /// Checks if the entity can be affected by potions.
     fn is_affected_by_potions(&self) -> bool {
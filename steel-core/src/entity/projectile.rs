@@ -0,0 +1,304 @@
+//! Shared projectile plumbing: launching, hit detection and hit handlers.
+//!
+//! Item behaviors and plugins use [`ProjectileLauncher`] to shoot arrows or their
+//! own projectile entities without reimplementing the physics and tracking, and
+//! react to hits by attaching a [`ProjectileHitHandler`].
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::shapes::AABBd;
+use steel_utils::{BlockPos, Direction};
+use uuid::Uuid;
+
+use crate::entity::entities::ArrowEntity;
+use crate::entity::{Entity, SharedEntity, next_entity_id};
+use crate::world::{RaytraceAction, World, triangle_random};
+
+/// How far entity hit boxes are grown for projectile hit checks.
+///
+/// Vanilla: `Projectile.getPickRadius()` callers inflate by 0.3.
+const ENTITY_HIT_MARGIN: f64 = 0.3;
+
+/// Spread per point of inaccuracy. Vanilla: `Projectile.getMovementToShoot()`.
+const INACCURACY_SPREAD: f64 = 0.017_227_5;
+
+/// What a projectile ran into.
+///
+/// Vanilla equivalent: `BlockHitResult` / `EntityHitResult`.
+#[derive(Clone)]
+pub enum ProjectileHit {
+    /// The projectile hit the side of a block.
+    Block {
+        /// The block that was hit.
+        pos: BlockPos,
+        /// The face of the block that was hit.
+        face: Direction,
+        /// Where the projectile touched the block.
+        location: DVec3,
+    },
+    /// The projectile hit an entity.
+    Entity {
+        /// The entity that was hit.
+        entity: SharedEntity,
+        /// Where the projectile touched the entity's hit box.
+        location: DVec3,
+    },
+}
+
+impl ProjectileHit {
+    /// Returns where the projectile touched the block or entity.
+    #[must_use]
+    pub const fn location(&self) -> DVec3 {
+        match self {
+            Self::Block { location, .. } | Self::Entity { location, .. } => *location,
+        }
+    }
+}
+
+/// What a projectile should do after its hit handler ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitAction {
+    /// Run the projectile's own hit behavior (damage, sticking into blocks, ...).
+    Continue,
+    /// Ignore the hit; the projectile keeps flying as if nothing was there.
+    Cancel,
+}
+
+/// Called when a projectile hits something, before the projectile reacts to it.
+///
+/// Receives the projectile and the hit. Handlers can apply their own effects and
+/// return [`HitAction::Cancel`] to replace the projectile's default behavior.
+pub type ProjectileHitHandler = Arc<dyn Fn(&dyn Entity, &ProjectileHit) -> HitAction + Send + Sync>;
+
+/// Damage settings for arrows shot through [`ProjectileLauncher::launch_arrow`].
+#[derive(Debug, Clone, Copy)]
+pub struct ArrowModifiers {
+    /// Damage per block/tick of speed. Vanilla default is 2.0.
+    pub base_damage: f64,
+    /// Whether the arrow was fully charged, adding random bonus damage.
+    pub critical: bool,
+    /// How many entities the arrow passes through before stopping.
+    pub pierce_level: u8,
+    /// Ticks the arrow burns for, setting hit entities on fire.
+    pub fire_ticks: i32,
+}
+
+impl Default for ArrowModifiers {
+    fn default() -> Self {
+        Self {
+            base_damage: 2.0,
+            critical: false,
+            pierce_level: 0,
+            fire_ticks: 0,
+        }
+    }
+}
+
+/// An entity shot or thrown by another entity.
+///
+/// Mirrors vanilla's `Projectile`.
+pub trait Projectile: Entity {
+    /// Gets the UUID of the entity that shot this projectile.
+    fn owner(&self) -> Option<Uuid>;
+
+    /// Sets the entity that shot this projectile.
+    fn set_owner(&self, owner: Option<Uuid>);
+
+    /// Gets the entity that shot this projectile, if it is still loaded.
+    fn owner_entity(&self) -> Option<SharedEntity> {
+        self.level()?.get_entity_by_uuid(&self.owner()?)
+    }
+
+    /// Sets the projectile moving with `velocity`.
+    ///
+    /// Vanilla: `Projectile.shoot()`.
+    fn shoot(&self, velocity: DVec3) {
+        self.set_velocity(velocity);
+    }
+}
+
+/// Entry point for shooting projectiles.
+///
+/// Spawning, ownership and tracking are handled here so callers only have to
+/// pick a direction and what the projectile does on hit.
+pub struct ProjectileLauncher;
+
+impl ProjectileLauncher {
+    /// Gets the velocity of a projectile shot in the direction of `yaw`/`pitch`.
+    ///
+    /// `power` is the speed in blocks per tick and `inaccuracy` adds random spread
+    /// (vanilla bows use 1.0, dispensers 6.0).
+    ///
+    /// Vanilla: `Projectile.shootFromRotation()` and `Projectile.getMovementToShoot()`.
+    #[must_use]
+    pub fn velocity_from_rotation(yaw: f32, pitch: f32, power: f32, inaccuracy: f32) -> DVec3 {
+        let (yaw, pitch) = (f64::from(yaw.to_radians()), f64::from(pitch.to_radians()));
+        let direction = DVec3::new(
+            -yaw.sin() * pitch.cos(),
+            -pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        );
+        Self::velocity_from_direction(direction, power, inaccuracy)
+    }
+
+    /// Gets the velocity of a projectile shot along `direction`.
+    ///
+    /// Vanilla: `Projectile.getMovementToShoot()`.
+    #[must_use]
+    pub fn velocity_from_direction(direction: DVec3, power: f32, inaccuracy: f32) -> DVec3 {
+        let spread = INACCURACY_SPREAD * f64::from(inaccuracy);
+        let direction = direction.normalize_or_zero();
+        DVec3::new(
+            triangle_random(direction.x, spread),
+            triangle_random(direction.y, spread),
+            triangle_random(direction.z, spread),
+        ) * f64::from(power)
+    }
+
+    /// Shoots an arrow from `shooter`'s eyes with the given velocity.
+    ///
+    /// The shooter becomes the arrow's owner, so damage is attributed to it and
+    /// the arrow doesn't hit it right after leaving the bow.
+    ///
+    /// Returns `None` if the shooter is not in a world.
+    pub fn launch_arrow(
+        shooter: &dyn Entity,
+        velocity: DVec3,
+        modifiers: ArrowModifiers,
+    ) -> Option<Arc<ArrowEntity>> {
+        Self::launch_arrow_with_handler(shooter, velocity, modifiers, None)
+    }
+
+    /// Like [`Self::launch_arrow`], with a handler that runs when the arrow hits.
+    pub fn launch_arrow_with_handler(
+        shooter: &dyn Entity,
+        velocity: DVec3,
+        modifiers: ArrowModifiers,
+        on_hit: Option<ProjectileHitHandler>,
+    ) -> Option<Arc<ArrowEntity>> {
+        let world = shooter.level()?;
+        // Vanilla: AbstractArrow(type, owner, level, ...) spawns just below the eyes
+        let pos = shooter.position();
+        let position = DVec3::new(pos.x, shooter.get_eye_y() - 0.1, pos.z);
+
+        let arrow = Arc::new(ArrowEntity::new(
+            next_entity_id(),
+            position,
+            Arc::downgrade(&world),
+        ));
+        arrow.set_owner(Some(shooter.uuid()));
+        arrow.set_modifiers(modifiers);
+        arrow.set_hit_handler(on_hit);
+        arrow.shoot(velocity);
+        world.add_entity(arrow.clone());
+        Some(arrow)
+    }
+
+    /// Launches an already created projectile for `shooter`.
+    ///
+    /// The projectile keeps the position it was created with; this makes the
+    /// shooter its owner, shoots it with `velocity` and adds it to the shooter's
+    /// world. Custom projectiles run their own hit logic, usually through [`find_hit`].
+    ///
+    /// Returns false if the shooter is not in a world.
+    pub fn launch_custom<P: Projectile + 'static>(
+        shooter: &dyn Entity,
+        projectile: Arc<P>,
+        velocity: DVec3,
+    ) -> bool {
+        let Some(world) = shooter.level() else {
+            return false;
+        };
+        projectile.set_owner(Some(shooter.uuid()));
+        projectile.shoot(velocity);
+        world.add_entity(projectile);
+        true
+    }
+}
+
+/// Finds the first block or entity a projectile runs into while moving from
+/// `from` to `to`.
+///
+/// Only entities whose type can be hit by projectiles and that pass `can_hit`
+/// are considered. A hit entity is preferred over a block further along the path.
+///
+/// Vanilla: `ProjectileUtil.getHitResultOnMoveVector()`.
+#[must_use]
+pub fn find_hit(
+    world: &World,
+    projectile: &dyn Entity,
+    from: DVec3,
+    to: DVec3,
+    can_hit: impl Fn(&dyn Entity) -> bool,
+) -> Option<ProjectileHit> {
+    let block_hit = clip_blocks(world, from, to);
+    let end = block_hit.as_ref().map_or(to, ProjectileHit::location);
+
+    let movement = end - from;
+    let search = expand_towards(&projectile.bounding_box(), movement).inflate(1.0);
+
+    let mut closest: Option<(f64, SharedEntity, DVec3)> = None;
+    for entity in world.get_entities_in_aabb(&search) {
+        if entity.id() == projectile.id()
+            || entity.is_removed()
+            || !entity.entity_type().flags.can_be_hit_by_projectile
+            || !can_hit(entity.as_ref())
+        {
+            continue;
+        }
+        let hit_box = entity.bounding_box().inflate(ENTITY_HIT_MARGIN);
+        let min = DVec3::new(hit_box.min_x, hit_box.min_y, hit_box.min_z);
+        let max = DVec3::new(hit_box.max_x, hit_box.max_y, hit_box.max_z);
+        let t = if from.cmpge(min).all() && from.cmple(max).all() {
+            0.0
+        } else {
+            match World::intersects_aabb_with_t(from, end, min, max) {
+                Some((t, _)) if (0.0..=1.0).contains(&t) => t,
+                _ => continue,
+            }
+        };
+        if closest.as_ref().is_none_or(|(best, _, _)| t < *best) {
+            closest = Some((t, entity, from + movement * t));
+        }
+    }
+
+    match closest {
+        Some((_, entity, location)) => Some(ProjectileHit::Entity { entity, location }),
+        None => block_hit,
+    }
+}
+
+/// Finds the first block with a shape along the path.
+pub(crate) fn clip_blocks(world: &World, from: DVec3, to: DVec3) -> Option<ProjectileHit> {
+    // TODO: Clip against collision shapes and fluids (ClipContext) instead of outlines
+    let (pos, _) = world.raytrace(from, to, |pos, world| {
+        if world.get_block_state(pos).is_air() {
+            RaytraceAction::Pass
+        } else {
+            RaytraceAction::CheckShape
+        }
+    });
+    let pos = pos?;
+    let (t, face) = world.ray_outline_clip(pos, from, to)?;
+    Some(ProjectileHit::Block {
+        pos,
+        face,
+        location: from + (to - from) * t.max(0.0),
+    })
+}
+
+/// Grows `aabb` in the direction of `movement` so it covers the whole path.
+///
+/// Vanilla: `AABB.expandTowards()`.
+pub(crate) fn expand_towards(aabb: &AABBd, movement: DVec3) -> AABBd {
+    AABBd::new(
+        aabb.min_x + movement.x.min(0.0),
+        aabb.min_y + movement.y.min(0.0),
+        aabb.min_z + movement.z.min(0.0),
+        aabb.max_x + movement.x.max(0.0),
+        aabb.max_y + movement.y.max(0.0),
+        aabb.max_z + movement.z.max(0.0),
+    )
+}
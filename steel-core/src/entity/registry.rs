@@ -5,13 +5,21 @@ use std::sync::{Arc, OnceLock, Weak};
 
 use glam::DVec3;
 use simdnbt::borrow::BaseNbtCompound as BorrowedNbtCompound;
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
 use steel_registry::entity_types::EntityTypeRef;
 use steel_registry::{REGISTRY, RegistryEntry};
 use steel_registry::{RegistryExt, vanilla_entities};
+use steel_utils::nbt::with_borrowed;
+use steel_utils::{BlockPos, ChunkPos, Identifier, UuidExt};
 use uuid::Uuid;
 
-use super::entities::{BlockDisplayEntity, ItemEntity};
-use super::{SharedEntity, next_entity_id};
+use super::entities::{
+    ArrowEntity, BlockDisplayEntity, BoatEntity, EndCrystalEntity, EnderDragonEntity,
+    FallingBlockEntity, FireworkRocketEntity, InteractionEntity, ItemEntity, LeashFenceKnotEntity,
+    MinecartEntity, VillagerEntity,
+};
+use super::riding::start_riding;
+use super::{Entity, SharedEntity, next_entity_id};
 use crate::world::World;
 
 /// Factory function type for creating entities.
@@ -128,7 +136,7 @@ impl EntityRegistry {
 
         let entity_id = next_entity_id();
         let entity = load_factory(entity_id, pos, uuid, velocity, rotation, on_ground, world);
-        entity.load_additional(nbt);
+        entity.load_data(nbt);
         Some(entity)
     }
 
@@ -155,6 +163,115 @@ impl Default for EntityRegistry {
     }
 }
 
+/// Saves `entity` and its passengers in vanilla's entity format, with its type under `id`.
+///
+/// Players riding it are left out, they are saved with their own data. Returns `None`
+/// for entity types that are never saved.
+///
+/// Vanilla: `Entity.save()`.
+#[must_use]
+pub fn save_entity(entity: &dyn Entity) -> Option<NbtCompound> {
+    if !entity.entity_type().can_serialize {
+        return None;
+    }
+    let pos = entity.position();
+    let motion = entity.velocity();
+    let (yaw, pitch) = entity.rotation();
+
+    let mut nbt = NbtCompound::new();
+    nbt.insert("id", entity.entity_type().key.to_string());
+    nbt.insert("Pos", NbtList::Double(vec![pos.x, pos.y, pos.z]));
+    nbt.insert(
+        "Motion",
+        NbtList::Double(vec![motion.x, motion.y, motion.z]),
+    );
+    nbt.insert("Rotation", NbtList::Float(vec![yaw, pitch]));
+    nbt.insert("OnGround", i8::from(entity.on_ground()));
+    nbt.insert(
+        "UUID",
+        NbtTag::IntArray(entity.uuid().to_int_array().to_vec()),
+    );
+    entity.save_data(&mut nbt);
+
+    let passengers: Vec<NbtCompound> = entity
+        .passengers()
+        .iter()
+        .filter_map(|passenger| save_entity(passenger.as_ref()))
+        .collect();
+    if !passengers.is_empty() {
+        nbt.insert("Passengers", NbtList::Compound(passengers));
+    }
+    Some(nbt)
+}
+
+/// Reads the position of an entity saved by [`save_entity`].
+#[must_use]
+pub fn saved_entity_position(nbt: &NbtCompound) -> Option<DVec3> {
+    let pos = nbt.list("Pos")?.doubles()?;
+    let pos = DVec3::new(*pos.first()?, *pos.get(1)?, *pos.get(2)?);
+    pos.is_finite().then_some(pos)
+}
+
+/// Adds an entity saved by [`save_entity`] to `world`, together with its passengers.
+///
+/// Returns `None` if the data is invalid, the entity type can't be loaded yet or the
+/// entity's chunk isn't loaded.
+///
+/// Vanilla: `EntityType.loadEntityRecursive()`.
+pub fn spawn_saved_entity(world: &Arc<World>, nbt: &NbtCompound) -> Option<SharedEntity> {
+    let pos = saved_entity_position(nbt)?;
+    let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+    world.chunk_map.with_full_chunk(chunk_pos, |_| ())?;
+
+    let id = nbt.string("id")?.to_str().parse::<Identifier>().ok()?;
+    let entity_type = REGISTRY.entity_types.by_key(&id)?;
+    let velocity = nbt
+        .list("Motion")
+        .and_then(NbtList::doubles)
+        .and_then(|motion| {
+            Some(DVec3::new(
+                *motion.first()?,
+                *motion.get(1)?,
+                *motion.get(2)?,
+            ))
+        })
+        .unwrap_or(DVec3::ZERO);
+    let rotation = nbt
+        .list("Rotation")
+        .and_then(NbtList::floats)
+        .and_then(|rotation| Some((*rotation.first()?, *rotation.get(1)?)))
+        .unwrap_or((0.0, 0.0));
+    let uuid = nbt
+        .int_array("UUID")
+        .and_then(Uuid::from_int_array)
+        .unwrap_or_else(Uuid::new_v4);
+    let on_ground = nbt.byte("OnGround").is_some_and(|b| b != 0);
+
+    let entity = with_borrowed(nbt, |base| {
+        ENTITIES.create_and_load(
+            entity_type,
+            pos,
+            uuid,
+            velocity,
+            rotation,
+            on_ground,
+            Arc::downgrade(world),
+            base,
+        )
+    })
+    .ok()??;
+    world.add_entity(entity.clone());
+
+    if let Some(passengers) = nbt.list("Passengers").and_then(NbtList::compounds) {
+        for passenger_nbt in passengers {
+            if let Some(passenger) = spawn_saved_entity(world, passenger_nbt) {
+                start_riding(&passenger, &entity, true);
+            }
+        }
+    }
+    Some(entity)
+}
+
 /// Wrapper for the global entity registry that implements `Deref`.
 pub struct EntityRegistryLock(OnceLock<EntityRegistry>);
 
@@ -212,6 +329,161 @@ pub fn init_entities() {
         },
     );
 
+    // Register falling block entity factory
+    registry.register(vanilla_entities::FALLING_BLOCK, |id, pos, world| {
+        Arc::new(FallingBlockEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::FALLING_BLOCK,
+        |id, pos, uuid, velocity, _rotation, on_ground, world| {
+            Arc::new(FallingBlockEntity::from_saved(
+                id, pos, uuid, velocity, on_ground, world,
+            ))
+        },
+    );
+
+    // Register arrow entity factory
+    registry.register(vanilla_entities::ARROW, |id, pos, world| {
+        Arc::new(ArrowEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::ARROW,
+        |id, pos, uuid, velocity, rotation, _on_ground, world| {
+            Arc::new(ArrowEntity::from_saved(
+                id, pos, uuid, velocity, rotation, world,
+            ))
+        },
+    );
+
+    // Register firework rocket entity factory
+    registry.register(vanilla_entities::FIREWORK_ROCKET, |id, pos, world| {
+        Arc::new(FireworkRocketEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::FIREWORK_ROCKET,
+        |id, pos, uuid, velocity, rotation, _on_ground, world| {
+            Arc::new(FireworkRocketEntity::from_saved(
+                id, pos, uuid, velocity, rotation, world,
+            ))
+        },
+    );
+
+    // Register villager entity factory
+    registry.register(vanilla_entities::VILLAGER, |id, pos, world| {
+        Arc::new(VillagerEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::VILLAGER,
+        |id, pos, uuid, velocity, rotation, _on_ground, world| {
+            Arc::new(VillagerEntity::from_saved(
+                id, pos, uuid, velocity, rotation, world,
+            ))
+        },
+    );
+
+    // Register ender dragon entity factory
+    registry.register(vanilla_entities::ENDER_DRAGON, |id, pos, world| {
+        Arc::new(EnderDragonEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::ENDER_DRAGON,
+        |id, pos, uuid, velocity, rotation, _on_ground, world| {
+            Arc::new(EnderDragonEntity::from_saved(
+                id, pos, uuid, velocity, rotation, world,
+            ))
+        },
+    );
+
+    // Register end crystal entity factory
+    registry.register(vanilla_entities::END_CRYSTAL, |id, pos, world| {
+        Arc::new(EndCrystalEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::END_CRYSTAL,
+        |id, pos, uuid, _velocity, _rotation, _on_ground, world| {
+            Arc::new(EndCrystalEntity::from_saved(id, pos, uuid, world))
+        },
+    );
+
+    // Register boat entity factories, one per boat and raft type
+    macro_rules! register_boats {
+        ($($boat:ident),* $(,)?) => {$(
+            registry.register(vanilla_entities::$boat, |id, pos, world| {
+                Arc::new(BoatEntity::new(vanilla_entities::$boat, id, pos, world))
+            });
+            registry.register_load(
+                vanilla_entities::$boat,
+                |id, pos, uuid, velocity, rotation, on_ground, world| {
+                    Arc::new(BoatEntity::from_saved(
+                        vanilla_entities::$boat,
+                        id,
+                        pos,
+                        uuid,
+                        velocity,
+                        rotation,
+                        on_ground,
+                        world,
+                    ))
+                },
+            );
+        )*};
+    }
+    register_boats!(
+        OAK_BOAT,
+        SPRUCE_BOAT,
+        BIRCH_BOAT,
+        JUNGLE_BOAT,
+        ACACIA_BOAT,
+        CHERRY_BOAT,
+        DARK_OAK_BOAT,
+        PALE_OAK_BOAT,
+        MANGROVE_BOAT,
+        BAMBOO_RAFT,
+        OAK_CHEST_BOAT,
+        SPRUCE_CHEST_BOAT,
+        BIRCH_CHEST_BOAT,
+        JUNGLE_CHEST_BOAT,
+        ACACIA_CHEST_BOAT,
+        CHERRY_CHEST_BOAT,
+        DARK_OAK_CHEST_BOAT,
+        PALE_OAK_CHEST_BOAT,
+        MANGROVE_CHEST_BOAT,
+        BAMBOO_CHEST_RAFT,
+    );
+
+    // Register minecart entity factory
+    registry.register(vanilla_entities::MINECART, |id, pos, world| {
+        Arc::new(MinecartEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::MINECART,
+        |id, pos, uuid, velocity, rotation, on_ground, world| {
+            Arc::new(MinecartEntity::from_saved(
+                id, pos, uuid, velocity, rotation, on_ground, world,
+            ))
+        },
+    );
+
+    // Register interaction entity factory
+    registry.register(vanilla_entities::INTERACTION, |id, pos, world| {
+        Arc::new(InteractionEntity::new(id, pos, world))
+    });
+    registry.register_load(
+        vanilla_entities::INTERACTION,
+        |id, pos, uuid, _velocity, _rotation, _on_ground, world| {
+            Arc::new(InteractionEntity::from_saved(id, pos, uuid, world))
+        },
+    );
+
+    // Register leash knot entity factory; knots are never saved
+    registry.register(vanilla_entities::LEASH_KNOT, |id, pos, world| {
+        Arc::new(LeashFenceKnotEntity::new(
+            id,
+            BlockPos::containing(pos.x, pos.y, pos.z),
+            world,
+        ))
+    });
+
     assert!(
         ENTITIES.set(registry).is_ok(),
         "Entity registry already initialized"
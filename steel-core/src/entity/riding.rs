@@ -0,0 +1,210 @@
+//! Vehicles and passengers.
+//!
+//! Mirrors the riding parts of vanilla's `Entity`: every entity can have one
+//! vehicle and any number of passengers. The vehicle holds strong references to
+//! its passengers, passengers only hold a weak reference back to the vehicle.
+
+use std::sync::Arc;
+
+use glam::DVec3;
+use steel_protocol::packets::game::CSetPassengers;
+use steel_registry::vanilla_entities;
+use steel_utils::ChunkPos;
+
+use super::{Entity, SharedEntity, WeakEntity};
+
+/// The vehicle and passengers of an entity.
+#[derive(Default)]
+pub struct RidingState {
+    /// The entity this entity is riding, if any.
+    vehicle: Option<WeakEntity>,
+    /// The entities riding this entity, the first one being the controlling passenger.
+    passengers: Vec<SharedEntity>,
+}
+
+impl RidingState {
+    /// Returns the vehicle, if it is still loaded.
+    #[must_use]
+    pub fn vehicle(&self) -> Option<SharedEntity> {
+        self.vehicle.as_ref().and_then(WeakEntity::upgrade)
+    }
+
+    /// Returns the passengers of this entity.
+    #[must_use]
+    pub fn passengers(&self) -> &[SharedEntity] {
+        &self.passengers
+    }
+}
+
+fn is_player(entity: &dyn Entity) -> bool {
+    entity.entity_type().key == vanilla_entities::PLAYER.key
+}
+
+/// Makes `passenger` start riding `vehicle`, dismounting it from any previous vehicle.
+///
+/// Returns `false` if the passenger already rides the vehicle, the vehicle cannot be
+/// ridden, or riding it would create a loop. With `force` set, `Entity::can_ride` and
+/// `Entity::can_add_passenger` are not consulted.
+///
+/// Vanilla equivalent: `Entity.startRiding()`.
+pub fn start_riding(passenger: &SharedEntity, vehicle: &SharedEntity, force: bool) -> bool {
+    if passenger
+        .vehicle()
+        .is_some_and(|current| current.id() == vehicle.id())
+    {
+        return false;
+    }
+    // Entities that are never saved (players included) can't carry passengers
+    if !vehicle.entity_type().can_serialize
+        || passenger.riding().is_none()
+        || vehicle.riding().is_none()
+        || passenger.id() == vehicle.id()
+    {
+        return false;
+    }
+
+    let mut current = vehicle.vehicle();
+    while let Some(entity) = current {
+        if entity.id() == passenger.id() {
+            return false;
+        }
+        current = entity.vehicle();
+    }
+
+    if !force
+        && (!passenger.can_ride(vehicle.as_ref()) || !vehicle.can_add_passenger(passenger.as_ref()))
+    {
+        return false;
+    }
+
+    if passenger.is_passenger() {
+        stop_riding(passenger.as_ref());
+    }
+
+    if let Some(riding) = passenger.riding() {
+        riding.lock().vehicle = Some(Arc::downgrade(vehicle));
+    }
+    if let Some(riding) = vehicle.riding() {
+        let mut riding = riding.lock();
+        // Players always take control over other passengers
+        let first_is_player = riding
+            .passengers
+            .first()
+            .is_some_and(|first| is_player(first.as_ref()));
+        if is_player(passenger.as_ref()) && !first_is_player {
+            riding.passengers.insert(0, passenger.clone());
+        } else {
+            riding.passengers.push(passenger.clone());
+        }
+    }
+
+    position_rider(vehicle.as_ref(), passenger.as_ref());
+    broadcast_passengers(vehicle.as_ref());
+    true
+}
+
+/// Dismounts `passenger` from its vehicle and moves it on top of the vehicle.
+///
+/// Does nothing if the entity isn't riding anything.
+///
+/// Vanilla equivalent: `Entity.stopRiding()` / `Entity.removeVehicle()`.
+pub fn stop_riding(passenger: &dyn Entity) {
+    let Some(riding) = passenger.riding() else {
+        return;
+    };
+    let Some(vehicle) = riding.lock().vehicle.take() else {
+        return;
+    };
+    let Some(vehicle) = vehicle.upgrade() else {
+        return;
+    };
+
+    if let Some(riding) = vehicle.riding() {
+        riding
+            .lock()
+            .passengers
+            .retain(|entity| entity.id() != passenger.id());
+    }
+
+    // Vanilla: Entity.getDismountLocationForPassenger
+    let pos = vehicle.position();
+    passenger.teleport_to(DVec3::new(pos.x, vehicle.bounding_box().max_y, pos.z));
+    broadcast_passengers(vehicle.as_ref());
+}
+
+/// Dismounts every passenger of `vehicle`.
+///
+/// Vanilla equivalent: `Entity.ejectPassengers()`.
+pub fn eject_passengers(vehicle: &dyn Entity) {
+    for passenger in vehicle.passengers() {
+        stop_riding(passenger.as_ref());
+    }
+}
+
+/// Returns the lowest vehicle below `entity`, if it rides anything.
+///
+/// Vanilla equivalent: `Entity.getRootVehicle()`.
+#[must_use]
+pub fn root_vehicle(entity: &dyn Entity) -> Option<SharedEntity> {
+    let mut root = entity.vehicle()?;
+    while let Some(vehicle) = root.vehicle() {
+        root = vehicle;
+    }
+    Some(root)
+}
+
+/// Moves `passenger` to its seat on `vehicle`.
+///
+/// Vanilla equivalent: `Entity.positionRider()`.
+pub fn position_rider(vehicle: &dyn Entity, passenger: &dyn Entity) {
+    passenger.set_position(rider_position(vehicle, passenger));
+}
+
+/// Gets the position of the seat `passenger` occupies on `vehicle`.
+///
+/// Vanilla equivalent: `Entity.getPassengerRidingPosition()` minus the rider's vehicle attachment.
+#[must_use]
+pub fn rider_position(vehicle: &dyn Entity, passenger: &dyn Entity) -> DVec3 {
+    let seat = vehicle.position() + vehicle.passenger_attachment_point(passenger);
+    DVec3::new(seat.x, seat.y - passenger.vehicle_attachment_y(), seat.z)
+}
+
+/// Rotates `point` around the Y axis by `angle` radians.
+///
+/// Vanilla equivalent: `Vec3.yRot()`.
+#[must_use]
+pub fn rotate_y(point: DVec3, angle: f32) -> DVec3 {
+    let (sin, cos) = (f64::from(angle.sin()), f64::from(angle.cos()));
+    DVec3::new(
+        point.x * cos + point.z * sin,
+        point.y,
+        point.z * cos - point.x * sin,
+    )
+}
+
+/// Builds the packet that syncs the passengers of `vehicle` to clients.
+#[must_use]
+pub fn passengers_packet(vehicle: &dyn Entity) -> CSetPassengers {
+    CSetPassengers {
+        vehicle_id: vehicle.id(),
+        passenger_ids: vehicle
+            .passengers()
+            .iter()
+            .map(|passenger| passenger.id())
+            .collect(),
+    }
+}
+
+/// Sends the passengers of `vehicle` to every player tracking it.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "world coordinates are always within i32 range in a valid Minecraft world"
+)]
+pub fn broadcast_passengers(vehicle: &dyn Entity) {
+    let Some(world) = vehicle.level() else {
+        return;
+    };
+    let pos = vehicle.position();
+    let chunk = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+    world.broadcast_to_nearby(chunk, passengers_packet(vehicle), None);
+}
@@ -3,14 +3,18 @@
 //! Entities are stored in chunks similar to block entities.
 //! The chunk owns the `Arc<dyn Entity>` and is responsible for ticking.
 
+use std::cmp::Reverse;
 use std::sync::Arc;
 
+use glam::DVec3;
 use rustc_hash::FxHashMap;
-use steel_protocol::packets::game::CSetEntityData;
+use steel_protocol::packets::game::{CSetEntityData, CUpdateAttributes};
 use steel_utils::ChunkPos;
 use steel_utils::locks::SyncRwLock;
 
-use super::SharedEntity;
+use super::activation::EntityActivation;
+use super::entities::ItemEntity;
+use super::{LivingEntity, SharedEntity, leash, riding};
 use crate::world::World;
 
 /// Storage for entities in a chunk.
@@ -68,6 +72,33 @@ impl EntityStorage {
         self.entities.read().is_empty()
     }
 
+    /// Returns the number of entities counted against the chunk's entity cap.
+    ///
+    /// Players and removed entities are not counted.
+    #[must_use]
+    pub fn capped_len(&self) -> usize {
+        self.entities
+            .read()
+            .values()
+            .filter(|e| !e.is_removed() && (*e).clone().as_player().is_none())
+            .count()
+    }
+
+    /// Returns up to `count` item entities, oldest first.
+    #[must_use]
+    pub fn oldest_items(&self, count: usize) -> Vec<Arc<ItemEntity>> {
+        let mut items: Vec<_> = self
+            .entities
+            .read()
+            .values()
+            .filter(|e| !e.is_removed())
+            .filter_map(|e| e.clone().as_item_entity())
+            .collect();
+        items.sort_unstable_by_key(|item| Reverse(item.get_tick_count()));
+        items.truncate(count);
+        items
+    }
+
     /// Returns entities that should be saved when the chunk is persisted.
     ///
     /// Excludes:
@@ -95,8 +126,15 @@ impl EntityStorage {
     ///
     /// Uses `tick_count` to prevent double-ticking: if an entity moves to a
     /// different chunk during its tick and that chunk is ticked later in the
-    /// same server tick, the entity will be skipped.
-    pub fn tick(&self, world: &Arc<World>, chunk_pos: ChunkPos, tick_count: i32) -> bool {
+    /// same server tick, the entity will be skipped. Entities `activation`
+    /// finds out of range are skipped too.
+    pub fn tick(
+        &self,
+        world: &Arc<World>,
+        chunk_pos: ChunkPos,
+        tick_count: i32,
+        activation: &mut EntityActivation,
+    ) -> bool {
         // Clone to avoid holding lock during tick
         let entities: Vec<SharedEntity> = self.entities.read().values().cloned().collect();
 
@@ -106,12 +144,22 @@ impl EntityStorage {
                 continue;
             }
 
+            // Passengers are ticked by their vehicle
+            match entity.vehicle() {
+                Some(vehicle) if !vehicle.is_removed() => continue,
+                Some(_) => riding::stop_riding(entity.as_ref()),
+                None => {}
+            }
+
             // Skip entities that were already ticked this server tick.
             // This happens when an entity moves from a chunk that was already
             // ticked to this chunk within the same server tick.
             if entity.was_ticked_this_tick(tick_count) {
                 continue;
             }
+            if !activation.should_tick(entity.as_ref()) {
+                continue;
+            }
 
             ticked_any = true;
 
@@ -119,17 +167,13 @@ impl EntityStorage {
             // even if the entity moves during its own tick
             entity.mark_ticked(tick_count);
 
+            leash::tick_leash(entity.as_ref());
+
             // Entity-specific tick (entities access world via self.level())
             entity.tick();
+            Self::sync_changes(world, chunk_pos, &entity, tick_count);
 
-            // Send position/velocity changes (mirrors vanilla's ServerEntity.sendChanges())
-            entity.send_changes(tick_count);
-
-            // Broadcast dirty entity data (base tick behavior)
-            if let Some(dirty_data) = entity.pack_dirty_entity_data() {
-                let packet = CSetEntityData::new(entity.id(), dirty_data);
-                world.broadcast_to_nearby(chunk_pos, packet, None);
-            }
+            Self::tick_passengers(world, chunk_pos, &entity, tick_count);
         }
 
         // Cleanup removed entities
@@ -138,6 +182,67 @@ impl EntityStorage {
         ticked_any
     }
 
+    /// Ticks the passengers of `vehicle` and moves them along with it.
+    ///
+    /// Players are ticked by the world and follow their vehicle on their own.
+    ///
+    /// Vanilla equivalent: `ServerLevel.tickPassenger()`.
+    fn tick_passengers(
+        world: &Arc<World>,
+        chunk_pos: ChunkPos,
+        vehicle: &SharedEntity,
+        tick_count: i32,
+    ) {
+        for passenger in vehicle.passengers() {
+            if passenger.is_removed() {
+                riding::stop_riding(passenger.as_ref());
+                continue;
+            }
+            if passenger.clone().as_player().is_some() || passenger.was_ticked_this_tick(tick_count)
+            {
+                continue;
+            }
+            passenger.mark_ticked(tick_count);
+
+            // Vanilla: Entity.rideTick
+            passenger.set_velocity(DVec3::ZERO);
+            passenger.tick();
+            riding::position_rider(vehicle.as_ref(), passenger.as_ref());
+            Self::sync_changes(world, chunk_pos, &passenger, tick_count);
+
+            Self::tick_passengers(world, chunk_pos, &passenger, tick_count);
+        }
+    }
+
+    /// Sends position, velocity and dirty entity data of a ticked entity.
+    fn sync_changes(
+        world: &Arc<World>,
+        chunk_pos: ChunkPos,
+        entity: &SharedEntity,
+        tick_count: i32,
+    ) {
+        // Send position/velocity changes (mirrors vanilla's ServerEntity.sendChanges())
+        entity.send_changes(tick_count);
+
+        // Broadcast dirty entity data (base tick behavior)
+        if let Some(dirty_data) = entity.pack_dirty_entity_data() {
+            let packet = CSetEntityData::new(entity.id(), dirty_data);
+            world.broadcast_to_nearby(chunk_pos, packet, None);
+        }
+
+        // Vanilla: the attribute part of ServerEntity.sendDirtyEntityData()
+        if let Some(mob) = Arc::clone(entity).as_mob() {
+            let attributes = mob.attributes().lock().take_dirty_snapshots();
+            if !attributes.is_empty() {
+                let packet = CUpdateAttributes {
+                    entity_id: entity.id(),
+                    attributes,
+                };
+                world.broadcast_to_nearby(chunk_pos, packet, None);
+            }
+        }
+    }
+
     /// Clears all entities from storage.
     pub fn clear(&self) {
         self.entities.write().clear();
@@ -11,13 +11,15 @@
 use std::sync::Arc;
 
 use rustc_hash::FxHashSet;
-use steel_protocol::packets::game::{CAddEntity, CRemoveEntities, CSetEntityData, to_angle_byte};
+use steel_protocol::packets::game::{
+    CAddEntity, CRemoveEntities, CSetEntityData, CUpdateAttributes, to_angle_byte,
+};
 use steel_registry::RegistryEntry;
 use steel_utils::ChunkPos;
 use steel_utils::locks::SyncRwLock;
 
 use crate::chunk::player_chunk_view::PlayerChunkView;
-use crate::entity::{SharedEntity, WeakEntity};
+use crate::entity::{LivingEntity, SharedEntity, WeakEntity, leash, riding};
 use crate::player::Player;
 
 /// World-level entity tracker using chunk-based spatial indexing.
@@ -383,12 +385,25 @@ fn send_spawn_packets(entity: &SharedEntity, player: &Player) {
         x_rot,
         y_rot,
         head_y_rot: y_rot,
-        data: 0,
+        data: entity.spawn_data(),
     };
 
     // Collect entity data before entering the bundle closure
     let entity_data = entity.pack_all_entity_data();
     let entity_id = entity.id();
+    // TODO: Cover living entities that aren't mobs once there are any besides players
+    let attributes = Arc::clone(entity)
+        .as_mob()
+        .map(|mob| mob.attributes().lock().syncable_snapshots())
+        .filter(|attributes| !attributes.is_empty());
+
+    // Vanilla: ServerEntity.sendPairingData
+    let passengers =
+        (!entity.passengers().is_empty()).then(|| riding::passengers_packet(entity.as_ref()));
+    let vehicle = entity
+        .vehicle()
+        .map(|vehicle| riding::passengers_packet(vehicle.as_ref()));
+    let leash = leash::link_packet(entity.as_ref());
 
     // Send all spawn packets in a bundle so client processes them atomically
     player.send_bundle(|bundle| {
@@ -398,5 +413,20 @@ fn send_spawn_packets(entity: &SharedEntity, player: &Player) {
         if !entity_data.is_empty() {
             bundle.add(CSetEntityData::new(entity_id, entity_data));
         }
+        if let Some(attributes) = attributes {
+            bundle.add(CUpdateAttributes {
+                entity_id,
+                attributes,
+            });
+        }
+        if let Some(passengers) = passengers {
+            bundle.add(passengers);
+        }
+        if let Some(vehicle) = vehicle {
+            bundle.add(vehicle);
+        }
+        if let Some(leash) = leash {
+            bundle.add(leash);
+        }
     });
 }
@@ -0,0 +1,113 @@
+//! Shared behavior for vehicles, the entities that break into an item when hit.
+//!
+//! Mirrors vanilla's `VehicleEntity`, the common parent of boats and minecarts:
+//! the synched hurt time, hurt direction and damage values, and dropping the
+//! vehicle item once it took enough damage.
+
+use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_game_rules::ENTITY_DROPS;
+use steel_registry::{REGISTRY, RegistryExt};
+
+use crate::entity::damage::DamageSource;
+use crate::entity::{Entity, RemovalReason};
+
+/// Damage after which a vehicle breaks. Vanilla: `VehicleEntity.hurtServer()`.
+const MAX_DAMAGE: f32 = 40.0;
+
+/// Ticks the hurt wobble lasts after a hit.
+const HURT_TIME: i32 = 10;
+
+/// An entity that can be broken into its item by hitting it.
+///
+/// Implementors store the values in the `id_hurt`, `id_hurtdir` and `id_damage`
+/// fields of their generated entity data, call [`VehicleEntity::hurt_vehicle`]
+/// from [`Entity::hurt`] and [`VehicleEntity::tick_damage`] from [`Entity::tick`].
+///
+/// Mirrors vanilla's `VehicleEntity`.
+pub trait VehicleEntity: Entity {
+    /// Gets the ticks left of the hurt wobble.
+    fn hurt_time(&self) -> i32;
+
+    /// Sets the ticks left of the hurt wobble.
+    fn set_hurt_time(&self, hurt_time: i32);
+
+    /// Gets the direction of the hurt wobble, either `1` or `-1`.
+    fn hurt_dir(&self) -> i32;
+
+    /// Sets the direction of the hurt wobble.
+    fn set_hurt_dir(&self, hurt_dir: i32);
+
+    /// Gets the accumulated damage.
+    fn damage(&self) -> f32;
+
+    /// Sets the accumulated damage.
+    fn set_damage(&self, damage: f32);
+
+    /// Gets the item this vehicle drops when destroyed.
+    ///
+    /// Vanilla: `VehicleEntity.getDropItem()`. Every vanilla vehicle has an item
+    /// with the same key as its entity type.
+    fn drop_item(&self) -> Option<ItemStack> {
+        REGISTRY
+            .items
+            .by_key(&self.entity_type().key)
+            .map(ItemStack::new)
+    }
+
+    /// Applies a hit to the vehicle, breaking it once it took enough damage.
+    ///
+    /// Creative players break vehicles with a single hit without dropping them.
+    ///
+    /// Vanilla: `VehicleEntity.hurtServer()`.
+    fn hurt_vehicle(&self, source: &DamageSource, amount: f32) -> bool {
+        if self.is_removed() {
+            return true;
+        }
+        // TODO: Check `isInvulnerableToBase` once entities have an invulnerable flag
+
+        self.set_hurt_dir(-self.hurt_dir());
+        self.set_hurt_time(HURT_TIME);
+        self.set_damage(self.damage() + amount * 10.0);
+
+        let creative_attacker = source
+            .causing_entity_id
+            .and_then(|id| self.level()?.get_entity_by_id(id))
+            .and_then(|entity| entity.as_player())
+            .is_some_and(|player| player.has_infinite_materials());
+
+        if creative_attacker {
+            self.set_removed(RemovalReason::Discarded);
+        } else if self.damage() > MAX_DAMAGE {
+            self.destroy();
+        }
+        true
+    }
+
+    /// Kills the vehicle and drops its item if the `entity_drops` rule allows it.
+    ///
+    /// Vanilla: `VehicleEntity.destroy()`.
+    fn destroy(&self) {
+        self.set_removed(RemovalReason::Killed);
+        let drops = self
+            .level()
+            .is_some_and(|world| world.get_game_rule(ENTITY_DROPS).as_bool().unwrap_or(true));
+        if drops && let Some(mut item) = self.drop_item() {
+            item.set_custom_name(self.custom_name());
+            self.spawn_at_location(item, 0.0);
+        }
+    }
+
+    /// Lets the hurt wobble and the accumulated damage wear off.
+    ///
+    /// Vanilla: the start of `AbstractBoat.tick()` and `AbstractMinecart.tick()`.
+    fn tick_damage(&self) {
+        let hurt_time = self.hurt_time();
+        if hurt_time > 0 {
+            self.set_hurt_time(hurt_time - 1);
+        }
+        let damage = self.damage();
+        if damage > 0.0 {
+            self.set_damage(damage - 1.0);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! The events the core posts.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use steel_registry::entity_types::EntityTypeRef;
+use steel_utils::{BlockPos, BlockStateId, ChunkPos};
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use super::{Cancellable, Event};
+use crate::entity::damage::DamageSource;
+use crate::player::Player;
+use crate::world::World;
+
+macro_rules! cancellable {
+    ($event:ty) => {
+        impl Cancellable for $event {
+            fn is_cancelled(&self) -> bool {
+                self.cancelled
+            }
+
+            fn set_cancelled(&mut self, cancelled: bool) {
+                self.cancelled = cancelled;
+            }
+        }
+    };
+}
+
+/// Posted once a joining player was added to the world.
+pub struct PlayerJoinEvent {
+    /// The player that joined.
+    pub player: Arc<Player>,
+}
+
+impl Event for PlayerJoinEvent {}
+
+/// Posted before a player breaks a block. Cancelling it keeps the block and
+/// resends it to the player.
+pub struct BlockBreakEvent {
+    /// The player breaking the block.
+    pub player: Arc<Player>,
+    /// The position of the block.
+    pub pos: BlockPos,
+    /// The block being broken.
+    pub state: BlockStateId,
+    cancelled: bool,
+}
+
+impl BlockBreakEvent {
+    /// Creates an uncancelled event.
+    #[must_use]
+    pub const fn new(player: Arc<Player>, pos: BlockPos, state: BlockStateId) -> Self {
+        Self {
+            player,
+            pos,
+            state,
+            cancelled: false,
+        }
+    }
+}
+
+impl Event for BlockBreakEvent {}
+cancellable!(BlockBreakEvent);
+
+/// Posted before a player's chat message is broadcast. Cancelling it drops the
+/// message.
+pub struct ChatEvent {
+    /// The player sending the message.
+    pub player: Arc<Player>,
+    message: String,
+    /// What clients show instead of the message. Starts as the plain message.
+    ///
+    /// The message itself is signed by the player and can't change, so this is
+    /// sent as the unsigned content, which clients hiding unsigned content ignore.
+    pub content: TextComponent,
+    cancelled: bool,
+}
+
+impl ChatEvent {
+    /// Creates an uncancelled event showing `message` as is.
+    #[must_use]
+    pub fn new(player: Arc<Player>, message: String) -> Self {
+        Self {
+            player,
+            content: TextComponent::plain(message.clone()),
+            message,
+            cancelled: false,
+        }
+    }
+
+    /// Gets the message the player sent.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Event for ChatEvent {}
+cancellable!(ChatEvent);
+
+/// Posted before an entity is damaged. Listeners can change the amount, or
+/// cancel the event so the entity isn't hurt.
+pub struct EntityDamageEvent {
+    /// The network id of the damaged entity.
+    pub entity_id: i32,
+    /// The UUID of the damaged entity.
+    pub uuid: Uuid,
+    /// The type of the damaged entity.
+    pub entity_type: EntityTypeRef,
+    /// What damages the entity.
+    pub source: DamageSource,
+    /// The damage dealt, before armor and effects reduce it.
+    pub amount: f32,
+    cancelled: bool,
+}
+
+impl EntityDamageEvent {
+    /// Creates an uncancelled event.
+    #[must_use]
+    pub const fn new(
+        entity_id: i32,
+        uuid: Uuid,
+        entity_type: EntityTypeRef,
+        source: DamageSource,
+        amount: f32,
+    ) -> Self {
+        Self {
+            entity_id,
+            uuid,
+            entity_type,
+            source,
+            amount,
+            cancelled: false,
+        }
+    }
+}
+
+impl Event for EntityDamageEvent {}
+cancellable!(EntityDamageEvent);
+
+/// Posted once a chunk is loaded or generated and becomes a full chunk.
+///
+/// Posted from a world generation thread.
+pub struct ChunkLoadEvent {
+    /// The world the chunk is in.
+    pub world: Arc<World>,
+    /// The position of the chunk.
+    pub pos: ChunkPos,
+}
+
+impl Event for ChunkLoadEvent {}
+
+/// Posted before a server list ping is answered. Listeners can change what the
+/// server list shows.
+///
+/// Posted from the pinging client's network task.
+pub struct ServerListPingEvent {
+    /// The address of the pinging client.
+    pub address: SocketAddr,
+    /// The message of the day, as text with `§` formatting codes or as JSON text.
+    pub motd: String,
+    /// The online player count shown.
+    pub online: i32,
+    /// The max player count shown.
+    pub max_players: i32,
+    /// The names and UUIDs listed when hovering the player count.
+    pub sample: Vec<(String, String)>,
+    /// Whether the player count and list are hidden, showing `???` instead.
+    pub hide_players: bool,
+    /// The favicon as a base64 PNG data URL.
+    pub favicon: Option<String>,
+}
+
+impl Event for ServerListPingEvent {}
@@ -0,0 +1,249 @@
+//! Events server extensions listen to, to react to or change what the core does.
+//!
+//! Listeners are registered on [`EVENTS`] for one event type and run in
+//! [`EventPriority`] order, then in registration order. Each listener gets the
+//! event mutably, so it can change the event's fields or cancel it; the core reads
+//! the event back once every listener ran.
+//!
+//! Listeners run synchronously on the thread that posted the event, which may be
+//! a network task, the tick thread or a world generation thread. The core may
+//! hold locks on the objects an event is about, so listeners should only read
+//! and change the event itself and defer anything else to a later tick.
+
+mod events;
+
+use std::any::{Any, TypeId};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+
+use rustc_hash::FxHashMap;
+use steel_utils::locks::SyncRwLock;
+
+pub use events::{
+    BlockBreakEvent, ChatEvent, ChunkLoadEvent, EntityDamageEvent, PlayerJoinEvent,
+    ServerListPingEvent,
+};
+
+/// An event listeners can be registered for.
+pub trait Event: Any + Send + Sync {}
+
+/// An event listeners can cancel, stopping the action it announces.
+pub trait Cancellable: Event {
+    /// Returns whether a listener cancelled the event.
+    fn is_cancelled(&self) -> bool;
+
+    /// Cancels the event, or lets it happen again.
+    fn set_cancelled(&mut self, cancelled: bool);
+}
+
+/// When a listener runs relative to the other listeners of the same event.
+///
+/// Listeners with a higher priority run later, so they get the final say.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum EventPriority {
+    /// Runs first.
+    Lowest,
+    /// Runs before [`Self::Normal`].
+    Low,
+    /// The default priority.
+    #[default]
+    Normal,
+    /// Runs after [`Self::Normal`].
+    High,
+    /// Runs last among the listeners that may change the event.
+    Highest,
+    /// Runs after every other listener, to observe the final outcome. Should not
+    /// change the event.
+    Monitor,
+}
+
+/// Identifies a registered listener, to unregister it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+type Handler = dyn Fn(&mut dyn Any) + Send + Sync;
+
+struct Listener {
+    id: ListenerId,
+    priority: EventPriority,
+    handler: Arc<Handler>,
+}
+
+/// Listeners of every event type, sorted by priority.
+#[derive(Default)]
+pub struct EventBus {
+    listeners: SyncRwLock<FxHashMap<TypeId, Vec<Listener>>>,
+    next_id: AtomicU64,
+}
+
+/// The server's event bus.
+pub static EVENTS: LazyLock<EventBus> = LazyLock::new(EventBus::new);
+
+impl EventBus {
+    /// Creates a bus without listeners.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `listener` for events of type `E`.
+    pub fn register<E: Event>(
+        &self,
+        priority: EventPriority,
+        listener: impl Fn(&mut E) + Send + Sync + 'static,
+    ) -> ListenerId {
+        let id = ListenerId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let handler: Arc<Handler> = Arc::new(move |event: &mut dyn Any| {
+            if let Some(event) = event.downcast_mut::<E>() {
+                listener(event);
+            }
+        });
+        let mut listeners = self.listeners.write();
+        let listeners = listeners.entry(TypeId::of::<E>()).or_default();
+        // After every listener of the same priority, so equal priorities keep their order
+        let index = listeners.partition_point(|other| other.priority <= priority);
+        listeners.insert(
+            index,
+            Listener {
+                id,
+                priority,
+                handler,
+            },
+        );
+        id
+    }
+
+    /// Registers `listener` for events of type `E` that no earlier listener
+    /// cancelled.
+    pub fn register_uncancelled<E: Cancellable>(
+        &self,
+        priority: EventPriority,
+        listener: impl Fn(&mut E) + Send + Sync + 'static,
+    ) -> ListenerId {
+        self.register(priority, move |event: &mut E| {
+            if !event.is_cancelled() {
+                listener(event);
+            }
+        })
+    }
+
+    /// Unregisters a listener. Returns whether it was registered.
+    pub fn unregister(&self, id: ListenerId) -> bool {
+        let mut listeners = self.listeners.write();
+        for listeners in listeners.values_mut() {
+            if let Some(index) = listeners.iter().position(|listener| listener.id == id) {
+                listeners.remove(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns whether any listener is registered for events of type `E`.
+    ///
+    /// Lets callers skip building events nobody listens to.
+    #[must_use]
+    pub fn has_listeners<E: Event>(&self) -> bool {
+        self.listeners
+            .read()
+            .get(&TypeId::of::<E>())
+            .is_some_and(|listeners| !listeners.is_empty())
+    }
+
+    /// Runs the listeners of `E` on `event`.
+    pub fn post<E: Event>(&self, event: &mut E) {
+        // Collected first, so listeners can register and unregister listeners
+        let handlers: Vec<Arc<Handler>> = match self.listeners.read().get(&TypeId::of::<E>()) {
+            Some(listeners) => listeners
+                .iter()
+                .map(|listener| Arc::clone(&listener.handler))
+                .collect(),
+            None => return,
+        };
+        for handler in handlers {
+            handler(&mut *event);
+        }
+    }
+
+    /// Runs the listeners of `E` on `event` and returns whether the action it
+    /// announces should happen.
+    #[must_use]
+    pub fn post_cancellable<E: Cancellable>(&self, event: &mut E) -> bool {
+        self.post(event);
+        !event.is_cancelled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_utils::locks::SyncMutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct TestEvent {
+        order: Vec<u8>,
+        cancelled: bool,
+    }
+
+    impl Event for TestEvent {}
+
+    impl Cancellable for TestEvent {
+        fn is_cancelled(&self) -> bool {
+            self.cancelled
+        }
+
+        fn set_cancelled(&mut self, cancelled: bool) {
+            self.cancelled = cancelled;
+        }
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let bus = EventBus::new();
+        bus.register(EventPriority::High, |event: &mut TestEvent| {
+            event.order.push(3);
+        });
+        bus.register(EventPriority::Low, |event: &mut TestEvent| {
+            event.order.push(1);
+        });
+        bus.register(EventPriority::Normal, |event: &mut TestEvent| {
+            event.order.push(2);
+        });
+        bus.register(EventPriority::Normal, |event: &mut TestEvent| {
+            event.order.push(4);
+        });
+        let mut event = TestEvent::default();
+        bus.post(&mut event);
+        assert_eq!(event.order, [1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let bus = EventBus::new();
+        let seen = Arc::new(SyncMutex::new(0));
+        bus.register(EventPriority::Low, |event: &mut TestEvent| {
+            event.set_cancelled(true);
+        });
+        let counter = Arc::clone(&seen);
+        bus.register_uncancelled(EventPriority::Normal, move |_: &mut TestEvent| {
+            *counter.lock() += 1;
+        });
+        assert!(!bus.post_cancellable(&mut TestEvent::default()));
+        assert_eq!(*seen.lock(), 0);
+    }
+
+    #[test]
+    fn test_unregister() {
+        let bus = EventBus::new();
+        let id = bus.register(EventPriority::Normal, |event: &mut TestEvent| {
+            event.order.push(1);
+        });
+        assert!(bus.has_listeners::<TestEvent>());
+        assert!(bus.unregister(id));
+        assert!(!bus.unregister(id));
+        let mut event = TestEvent::default();
+        bus.post(&mut event);
+        assert!(event.order.is_empty());
+    }
+}
@@ -0,0 +1,618 @@
+//! The anvil menu.
+//!
+//! Slot layout (39 total):
+//! - Slot 0: Input item
+//! - Slot 1: Addition (repair material, second item or enchanted book)
+//! - Slot 2: Result
+//! - Slots 3-29: Main inventory (27 slots)
+//! - Slots 30-38: Hotbar (9 slots)
+//!
+//! Data slots: the level cost.
+
+use std::{mem, sync::Arc};
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::data_components::vanilla_components::{
+    ItemEnchantments, REPAIR_COST, STORED_ENCHANTMENTS,
+};
+use steel_registry::enchantment::Enchantment;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_block_tags::ANVIL_TAG;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::{REGISTRY, RegistryExt, TaggedRegistryExt, vanilla_menu_types};
+use steel_utils::BlockPos;
+use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::SimpleContainer,
+    crafting::ResultContainer,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        AnvilResultSlot, NormalSlot, Slot, SlotType, SyncResultContainer,
+        add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+use crate::world::World;
+
+/// Slot index of the input item.
+const INPUT_SLOT: usize = 0;
+/// Slot index of the addition.
+const ADDITION_SLOT: usize = 1;
+/// Slot index of the result.
+const RESULT_SLOT: usize = 2;
+/// Start of main inventory.
+const INV_SLOT_START: usize = 3;
+/// End of main inventory (exclusive), start of the hotbar.
+const HOTBAR_SLOT_START: usize = 30;
+/// End of the hotbar (exclusive).
+const HOTBAR_SLOT_END: usize = 39;
+
+/// Data slot holding the level cost.
+const DATA_COST: usize = 0;
+
+/// The longest name an item can be given.
+const MAX_NAME_LENGTH: usize = 50;
+/// Costs from this level on are "Too Expensive!" outside of creative mode.
+const TOO_EXPENSIVE_COST: i32 = 40;
+
+/// The cost of the current anvil result, shared with its result slot.
+#[derive(Debug, Default)]
+pub struct AnvilCost {
+    /// The level cost of taking the result.
+    pub cost: i32,
+    /// How many repair materials taking the result uses up, or 0 to use up
+    /// the whole addition.
+    pub repair_item_count_cost: i32,
+    /// Whether the result only renames the input, leaving the addition.
+    pub only_renaming: bool,
+}
+
+/// A synchronized anvil cost.
+pub type SharedAnvilCost = Arc<SyncMutex<AnvilCost>>;
+
+/// Doubles a prior work penalty and adds one, so every anvil use gets more
+/// expensive.
+///
+/// Based on Java's `AnvilMenu::calculateIncreasedRepairCost`.
+#[must_use]
+pub fn calculate_increased_repair_cost(cost: i32) -> i32 {
+    (i64::from(cost) * 2 + 1).min(i64::from(i32::MAX)) as i32
+}
+
+/// Removes the characters that aren't allowed in names, returning `None`
+/// if the name is too long.
+///
+/// Based on Java's `AnvilMenu::validateName`.
+fn validate_name(name: &str) -> Option<String> {
+    let filtered: String = name
+        .chars()
+        .filter(|&c| c != '§' && c >= ' ' && c != '\u{7f}')
+        .collect();
+    (filtered.chars().count() <= MAX_NAME_LENGTH).then_some(filtered)
+}
+
+/// The menu of an anvil.
+///
+/// Based on Java's `AnvilMenu`.
+pub struct AnvilMenu {
+    behavior: MenuBehavior,
+    /// The container behind the input and addition slots.
+    input: ContainerRef,
+    /// The container behind the result slot.
+    result: SyncResultContainer,
+    /// The cost of the current result.
+    cost: SharedAnvilCost,
+    /// The name typed by the player, if any.
+    item_name: Option<String>,
+    /// The world the anvil is in.
+    world: Arc<World>,
+    /// The position of the anvil.
+    pos: BlockPos,
+}
+
+impl AnvilMenu {
+    /// Creates a new anvil menu.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `world` - The world the anvil is in
+    /// * `pos` - The position of the anvil
+    #[must_use]
+    pub fn new(
+        inventory: SyncPlayerInv,
+        container_id: u8,
+        world: Arc<World>,
+        pos: BlockPos,
+    ) -> Self {
+        let input = ContainerRef::Other(Arc::new(SyncMutex::new(SimpleContainer::new(2))));
+        let result = Arc::new(SyncMutex::new(ResultContainer::new()));
+        let cost = Arc::new(SyncMutex::new(AnvilCost::default()));
+
+        let mut menu_slots = Vec::with_capacity(HOTBAR_SLOT_END);
+        menu_slots.push(SlotType::Normal(NormalSlot::new(input.clone(), 0)));
+        menu_slots.push(SlotType::Normal(NormalSlot::new(input.clone(), 1)));
+        menu_slots.push(SlotType::AnvilResult(AnvilResultSlot::new(
+            result.clone(),
+            input.clone(),
+            cost.clone(),
+            pos,
+        )));
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut behavior =
+            MenuBehavior::new(menu_slots, container_id, Some(vanilla_menu_types::ANVIL));
+        behavior.add_data_slots(1);
+
+        Self {
+            behavior,
+            input,
+            result,
+            cost,
+            item_name: None,
+            world,
+            pos,
+        }
+    }
+
+    /// Sets the name typed by the player and updates the result.
+    ///
+    /// Returns false if the name is invalid or didn't change.
+    ///
+    /// Based on Java's `AnvilMenu::setItemName`.
+    pub fn set_item_name(&mut self, name: &str, player: &Player) -> bool {
+        let Some(name) = validate_name(name) else {
+            return false;
+        };
+        if self.item_name.as_ref() == Some(&name) {
+            return false;
+        }
+
+        {
+            let result_ref = ContainerRef::ResultContainer(self.result.clone());
+            let mut guard = ContainerLockGuard::lock_all(&[&result_ref]);
+            self.behavior.slots[RESULT_SLOT].modify_item(&mut guard, |result| {
+                if result.is_empty() {
+                    return;
+                }
+                if name.trim().is_empty() {
+                    result.set_custom_name(None);
+                } else {
+                    result.set_custom_name(Some(TextComponent::plain(name.clone())));
+                }
+            });
+        }
+        self.item_name = Some(name);
+        self.create_result(player);
+        true
+    }
+
+    /// Computes the result and its level cost from the inputs.
+    ///
+    /// Based on Java's `AnvilMenu::createResult`.
+    fn create_result(&mut self, player: &Player) {
+        let result_ref = ContainerRef::ResultContainer(self.result.clone());
+        let mut guard = ContainerLockGuard::lock_all(&[&self.input, &result_ref]);
+        let input = self.behavior.slots[INPUT_SLOT].get_item(&guard).clone();
+        let addition = self.behavior.slots[ADDITION_SLOT].get_item(&guard).clone();
+
+        let (result, cost) = {
+            let mut shared = self.cost.lock();
+            let result = Self::compute_result(
+                &input,
+                &addition,
+                self.item_name.as_deref(),
+                player.has_infinite_materials(),
+                &mut shared,
+            );
+            (result, shared.cost)
+        };
+
+        self.behavior.slots[RESULT_SLOT].set_item(&mut guard, result);
+        drop(guard);
+        self.behavior.set_data(DATA_COST, cost as i16);
+    }
+
+    /// Computes the result of combining `input` with `addition`, updating
+    /// the level cost.
+    fn compute_result(
+        input: &ItemStack,
+        addition: &ItemStack,
+        item_name: Option<&str>,
+        has_infinite_materials: bool,
+        shared: &mut AnvilCost,
+    ) -> ItemStack {
+        shared.only_renaming = false;
+        shared.cost = 1;
+        if input.is_empty() || !input.can_store_enchantments() {
+            shared.cost = 0;
+            return ItemStack::empty();
+        }
+
+        let mut result = input.clone();
+        let mut enchantments = result.get_enchantments_for_crafting();
+        let tax = i64::from(input.get_repair_cost()) + i64::from(addition.get_repair_cost());
+        let mut price = 0;
+        let mut naming_cost = 0;
+        shared.repair_item_count_cost = 0;
+
+        if !addition.is_empty() {
+            let using_book = addition.has(STORED_ENCHANTMENTS);
+            if result.is_damageable_item() && input.is_valid_repair_item(addition) {
+                // Each repair material restores a quarter of the durability
+                let mut repair = result.get_damage_value().min(result.get_max_damage() / 4);
+                if repair <= 0 {
+                    shared.cost = 0;
+                    return ItemStack::empty();
+                }
+                let mut count = 0;
+                while repair > 0 && count < addition.count() {
+                    result.set_damage_value(result.get_damage_value() - repair);
+                    price += 1;
+                    repair = result.get_damage_value().min(result.get_max_damage() / 4);
+                    count += 1;
+                }
+                shared.repair_item_count_cost = count;
+            } else {
+                if !using_book
+                    && (!ItemStack::is_same_item(&result, addition) || !result.is_damageable_item())
+                {
+                    shared.cost = 0;
+                    return ItemStack::empty();
+                }
+
+                if result.is_damageable_item() && !using_book {
+                    // Combine the durability of both items with a 12% bonus
+                    let remaining = input.get_max_damage() - input.get_damage_value();
+                    let additional = addition.get_max_damage() - addition.get_damage_value()
+                        + result.get_max_damage() * 12 / 100;
+                    let damage = (result.get_max_damage() - (remaining + additional)).max(0);
+                    if damage < result.get_damage_value() {
+                        result.set_damage_value(damage);
+                        price += 2;
+                    }
+                }
+
+                if !Self::merge_enchantments(
+                    input,
+                    addition,
+                    &mut enchantments,
+                    has_infinite_materials,
+                    using_book,
+                    &mut price,
+                ) {
+                    shared.cost = 0;
+                    return ItemStack::empty();
+                }
+            }
+        }
+
+        // TODO: Compare against the hover name once item names can be
+        // resolved to plain text, so retyping an item's own name is free.
+        match item_name {
+            Some(name) if !name.trim().is_empty() => {
+                let name = TextComponent::plain(name.to_owned());
+                if input.get_custom_name() != Some(&name) {
+                    naming_cost = 1;
+                    price += naming_cost;
+                    result.set_custom_name(Some(name));
+                }
+            }
+            _ => {
+                if input.get_custom_name().is_some() {
+                    naming_cost = 1;
+                    price += naming_cost;
+                    result.set_custom_name(None);
+                }
+            }
+        }
+
+        shared.cost = if price <= 0 {
+            0
+        } else {
+            (tax + i64::from(price)).clamp(0, i64::from(i32::MAX)) as i32
+        };
+        if price <= 0 {
+            result = ItemStack::empty();
+        }
+        if naming_cost == price && naming_cost > 0 {
+            shared.cost = shared.cost.min(TOO_EXPENSIVE_COST - 1);
+            shared.only_renaming = true;
+        }
+        if shared.cost >= TOO_EXPENSIVE_COST && !has_infinite_materials {
+            result = ItemStack::empty();
+        }
+
+        if !result.is_empty() {
+            let mut repair_cost = result.get_repair_cost().max(addition.get_repair_cost());
+            if naming_cost != price || naming_cost == 0 {
+                repair_cost = calculate_increased_repair_cost(repair_cost);
+            }
+            result.set(REPAIR_COST, repair_cost);
+            result.set_enchantments_for_crafting(enchantments);
+        }
+        result
+    }
+
+    /// Merges the addition's enchantments into `enchantments`, adding their
+    /// cost to `price`.
+    ///
+    /// Equal levels combine into the next level. Returns false if the
+    /// addition only has enchantments that can't be merged.
+    ///
+    /// Based on the enchantment part of Java's `AnvilMenu::createResult`.
+    fn merge_enchantments(
+        input: &ItemStack,
+        addition: &ItemStack,
+        enchantments: &mut ItemEnchantments,
+        has_infinite_materials: bool,
+        using_book: bool,
+        price: &mut i32,
+    ) -> bool {
+        let mut any_compatible = false;
+        let mut any_incompatible = false;
+        for (key, &level) in addition.get_enchantments_for_crafting().iter() {
+            let Some(enchantment) = REGISTRY.enchantments.by_key(key) else {
+                continue;
+            };
+            let current = enchantments.get_level(key);
+            let level = if current == level {
+                level + 1
+            } else {
+                level.max(current)
+            };
+
+            let mut compatible = has_infinite_materials
+                || input.is(&ITEMS.enchanted_book)
+                || enchantment.can_enchant(input.item);
+            for (other_key, _) in enchantments.iter() {
+                if other_key == key {
+                    continue;
+                }
+                if let Some(other) = REGISTRY.enchantments.by_key(other_key)
+                    && !Enchantment::are_compatible(enchantment, other)
+                {
+                    compatible = false;
+                    *price += 1;
+                }
+            }
+
+            if compatible {
+                any_compatible = true;
+                let level = level.min(enchantment.max_level);
+                enchantments.set(key.clone(), level);
+                let mut fee = enchantment.anvil_cost;
+                if using_book {
+                    fee = (fee / 2).max(1);
+                }
+                *price += fee * level as i32;
+                if input.count() > 1 {
+                    *price = TOO_EXPENSIVE_COST;
+                }
+            } else {
+                any_incompatible = true;
+            }
+        }
+        any_compatible || !any_incompatible
+    }
+}
+
+impl Menu for AnvilMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `ItemCombinerMenu::quickMoveStack`:
+    /// - Result slot -> player inventory (backwards = true), paying the cost
+    /// - Input slots -> player inventory
+    /// - Player inventory -> input slots, then main inventory <-> hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                INV_SLOT_START,
+                HOTBAR_SLOT_END,
+                true,
+            )
+        } else if slot_index < RESULT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                INV_SLOT_START,
+                HOTBAR_SLOT_END,
+                false,
+            )
+        } else if self.behavior.move_item_stack_to(
+            guard,
+            &mut stack_mut,
+            INPUT_SLOT,
+            RESULT_SLOT,
+            false,
+        ) {
+            true
+        } else if slot_index < HOTBAR_SLOT_START {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                HOTBAR_SLOT_START,
+                HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                INV_SLOT_START,
+                HOTBAR_SLOT_START,
+                false,
+            )
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        if slot_index == RESULT_SLOT {
+            self.behavior.slots[slot_index].on_take(guard, &clicked, player);
+        }
+
+        clicked
+    }
+
+    /// Returns true while the anvil is still there.
+    ///
+    /// Based on Java's `AnvilMenu::isValidBlock`.
+    fn still_valid(&self) -> bool {
+        let state = self.world.get_block_state(self.pos);
+        REGISTRY.blocks.is_in_tag(state.get_block(), &ANVIL_TAG)
+    }
+
+    /// Recomputes the result after the inputs may have changed.
+    ///
+    /// Based on Java's `ItemCombinerMenu::slotsChanged`.
+    fn slots_changed(&mut self, player: &Player) {
+        self.create_result(player);
+    }
+
+    fn can_take_item_for_pick_all(&self, _carried: &ItemStack, slot_index: usize) -> bool {
+        slot_index != RESULT_SLOT
+    }
+
+    /// Gives the inputs back to the player.
+    ///
+    /// Based on Java's `ItemCombinerMenu::removed` which calls `clearContainer`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let inputs = {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.input]);
+            guard.get_mut(self.input.container_id()).map(|container| {
+                [INPUT_SLOT, ADDITION_SLOT].map(|slot| container.remove_item_no_update(slot))
+            })
+        };
+        for item in inputs.into_iter().flatten() {
+            if !item.is_empty() {
+                player.add_item_or_drop(item);
+            }
+        }
+    }
+}
+
+impl MenuInstance for AnvilMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::ANVIL
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+
+    fn as_anvil_menu_mut(&mut self) -> Option<&mut AnvilMenu> {
+        Some(self)
+    }
+}
+
+/// Provider for creating anvil menus.
+pub struct AnvilMenuProvider {
+    inventory: SyncPlayerInv,
+    world: Arc<World>,
+    pos: BlockPos,
+    title: TextComponent,
+}
+
+impl AnvilMenuProvider {
+    /// Creates a new anvil menu provider.
+    #[must_use]
+    pub const fn new(
+        inventory: SyncPlayerInv,
+        world: Arc<World>,
+        pos: BlockPos,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            world,
+            pos,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for AnvilMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(AnvilMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.world.clone(),
+            self.pos,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increased_repair_cost_doubles_and_saturates() {
+        assert_eq!(calculate_increased_repair_cost(0), 1);
+        assert_eq!(calculate_increased_repair_cost(1), 3);
+        assert_eq!(calculate_increased_repair_cost(15), 31);
+        assert_eq!(calculate_increased_repair_cost(i32::MAX), i32::MAX);
+    }
+
+    #[test]
+    fn test_validate_name_filters_disallowed_chars() {
+        assert_eq!(validate_name("§cRed\nName\u{7f}").as_deref(), Some("cRedName"));
+        assert_eq!(validate_name("").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_validate_name_rejects_long_names() {
+        assert!(validate_name(&"a".repeat(MAX_NAME_LENGTH)).is_some());
+        assert!(validate_name(&"a".repeat(MAX_NAME_LENGTH + 1)).is_none());
+    }
+}
@@ -0,0 +1,336 @@
+//! The beacon menu.
+//!
+//! Slot layout (37 total):
+//! - Slot 0: Payment slot
+//! - Slots 1-27: Main inventory (27 slots)
+//! - Slots 28-36: Hotbar (9 slots)
+//!
+//! Data slots: the pyramid levels, then the primary and secondary effect
+//! encoded by [`encode_effect`].
+
+use std::{mem, sync::Arc};
+
+use steel_registry::RegistryEntry;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::mob_effect::MobEffectRef;
+use steel_registry::vanilla_menu_types;
+use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
+
+use crate::block_entity::SharedBlockEntity;
+use crate::block_entity::entities::BeaconBlockEntity;
+use crate::inventory::{
+    SyncPlayerInv,
+    container::Container,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{BeaconPaymentSlot, Slot, SlotType, add_standard_inventory_slots},
+};
+use crate::player::Player;
+
+/// Slot index of the payment slot.
+const PAYMENT_SLOT: usize = 0;
+/// Start of main inventory.
+const INV_SLOT_START: usize = 1;
+/// End of main inventory (exclusive), start of the hotbar.
+const HOTBAR_SLOT_START: usize = 28;
+/// End of the hotbar (exclusive).
+const HOTBAR_SLOT_END: usize = 37;
+
+/// Data slot holding the pyramid levels.
+const DATA_LEVELS: usize = 0;
+/// Data slot holding the primary effect.
+const DATA_PRIMARY: usize = 1;
+/// Data slot holding the secondary effect.
+const DATA_SECONDARY: usize = 2;
+
+/// Encodes an effect for a data slot: its registry id plus one, or 0 for none.
+///
+/// Based on Java's `BeaconMenu::encodeEffect`.
+#[must_use]
+pub fn encode_effect(effect: Option<MobEffectRef>) -> i16 {
+    effect.map_or(0, |effect| effect.id() as i16 + 1)
+}
+
+/// Holds the payment item while the menu is open.
+///
+/// Based on the `SimpleContainer` in Java's `BeaconMenu`.
+struct PaymentContainer {
+    item: ItemStack,
+}
+
+impl Container for PaymentContainer {
+    fn get_container_size(&self) -> usize {
+        1
+    }
+
+    fn get_item(&self, _slot: usize) -> &ItemStack {
+        &self.item
+    }
+
+    fn get_item_mut(&mut self, _slot: usize) -> &mut ItemStack {
+        &mut self.item
+    }
+
+    fn set_item(&mut self, _slot: usize, stack: ItemStack) {
+        self.item = stack;
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        1
+    }
+
+    fn set_changed(&mut self) {
+        // The payment only lives as long as the menu
+    }
+}
+
+/// The menu of a beacon.
+///
+/// Based on Java's `BeaconMenu`.
+pub struct BeaconMenu {
+    behavior: MenuBehavior,
+    /// The container behind the payment slot.
+    payment: ContainerRef,
+    /// The beacon block entity.
+    beacon: SharedBlockEntity,
+}
+
+impl BeaconMenu {
+    /// Creates a new beacon menu.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `beacon` - The beacon block entity
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, beacon: SharedBlockEntity) -> Self {
+        let payment = ContainerRef::Other(Arc::new(SyncMutex::new(PaymentContainer {
+            item: ItemStack::empty(),
+        })));
+
+        let mut menu_slots = Vec::with_capacity(HOTBAR_SLOT_END);
+        menu_slots.push(SlotType::BeaconPayment(BeaconPaymentSlot::new(
+            payment.clone(),
+            0,
+        )));
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut menu = Self {
+            behavior: MenuBehavior::new(menu_slots, container_id, Some(vanilla_menu_types::BEACON)),
+            payment,
+            beacon,
+        };
+        menu.behavior.add_data_slots(3);
+        menu.update_data_slots();
+        menu
+    }
+
+    /// Sets the beacon's effects, using up the payment item.
+    ///
+    /// Does nothing without a payment item.
+    ///
+    /// Based on Java's `BeaconMenu::updateEffects`.
+    pub fn update_effects(
+        &mut self,
+        primary: Option<MobEffectRef>,
+        secondary: Option<MobEffectRef>,
+    ) {
+        {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.payment]);
+            let slot = &self.behavior.slots[PAYMENT_SLOT];
+            if !slot.has_item(&guard) {
+                return;
+            }
+            slot.remove(&mut guard, 1);
+            slot.set_changed(&mut guard);
+        }
+
+        let mut guard = self.beacon.lock();
+        if let Some(beacon) = guard.as_any_mut().downcast_mut::<BeaconBlockEntity>() {
+            beacon.set_primary_power(primary);
+            beacon.set_secondary_power(secondary);
+        }
+    }
+}
+
+impl Menu for BeaconMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `BeaconMenu::quickMoveStack`:
+    /// - Payment slot -> player inventory (backwards = true)
+    /// - A single payment item -> payment slot
+    /// - Main inventory <-> hotbar
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        _player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+        let payment_slot = &self.behavior.slots[PAYMENT_SLOT];
+        let to_payment = !payment_slot.has_item(guard)
+            && payment_slot.may_place(&stack_mut)
+            && stack_mut.count() == 1;
+
+        let moved = if slot_index == PAYMENT_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                INV_SLOT_START,
+                HOTBAR_SLOT_END,
+                true,
+            )
+        } else if to_payment {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                PAYMENT_SLOT,
+                INV_SLOT_START,
+                false,
+            )
+        } else if slot_index < HOTBAR_SLOT_START {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                HOTBAR_SLOT_START,
+                HOTBAR_SLOT_END,
+                false,
+            )
+        } else {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                INV_SLOT_START,
+                HOTBAR_SLOT_START,
+                false,
+            )
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        clicked
+    }
+
+    fn still_valid(&self) -> bool {
+        !self.beacon.lock().is_removed()
+    }
+
+    fn update_data_slots(&mut self) {
+        let values = {
+            let guard = self.beacon.lock();
+            let Some(beacon) = guard.as_any().downcast_ref::<BeaconBlockEntity>() else {
+                return;
+            };
+            [
+                (DATA_LEVELS, beacon.levels() as i16),
+                (DATA_PRIMARY, encode_effect(beacon.primary_power())),
+                (DATA_SECONDARY, encode_effect(beacon.secondary_power())),
+            ]
+        };
+        for (index, value) in values {
+            self.behavior.set_data(index, value);
+        }
+    }
+
+    /// Gives the payment item back to the player.
+    ///
+    /// Based on Java's `BeaconMenu::removed` which calls `clearContainer`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let payment = {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.payment]);
+            guard
+                .get_mut(self.payment.container_id())
+                .map(|container| container.remove_item_no_update(0))
+        };
+        if let Some(payment) = payment
+            && !payment.is_empty()
+        {
+            player.add_item_or_drop(payment);
+        }
+    }
+}
+
+impl MenuInstance for BeaconMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::BEACON
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+
+    fn as_beacon_menu_mut(&mut self) -> Option<&mut BeaconMenu> {
+        Some(self)
+    }
+}
+
+/// Provider for creating beacon menus.
+pub struct BeaconMenuProvider {
+    inventory: SyncPlayerInv,
+    beacon: SharedBlockEntity,
+    title: TextComponent,
+}
+
+impl BeaconMenuProvider {
+    /// Creates a new beacon menu provider.
+    #[must_use]
+    pub const fn new(
+        inventory: SyncPlayerInv,
+        beacon: SharedBlockEntity,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            beacon,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for BeaconMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(BeaconMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.beacon.clone(),
+        ))
+    }
+}
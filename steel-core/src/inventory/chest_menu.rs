@@ -273,10 +273,15 @@ impl Menu for ChestMenu {
 
     /// Called when the menu is closed.
     ///
-    /// Drops the carried item (default behavior).
-    /// Note: Java's `ChestMenu::removed` also calls `container.stopOpen(player)`,
-    /// but we don't have that callback implemented yet.
+    /// Tells the container it was closed and drops the carried item.
     fn removed(&mut self, player: &Player) {
+        {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.container]);
+            if let Some(container) = guard.get_mut(self.container.container_id()) {
+                container.stop_open(player);
+            }
+        }
+
         let carried = mem::take(&mut self.behavior.carried);
         if !carried.is_empty() {
             player.drop_item(carried, false, true);
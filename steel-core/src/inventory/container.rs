@@ -9,6 +9,8 @@ use std::ptr;
 use enum_dispatch::enum_dispatch;
 use steel_registry::item_stack::ItemStack;
 
+use crate::player::Player;
+
 /// Default distance buffer for container interaction range checks.
 pub const DEFAULT_DISTANCE_BUFFER: f32 = 4.0;
 
@@ -95,6 +97,16 @@ pub trait Container {
         true
     }
 
+    /// Called when a player opens a menu on this container.
+    ///
+    /// Based on Java's `Container.startOpen(Player)`.
+    fn start_open(&mut self, _player: &Player) {}
+
+    /// Called when a player closes a menu on this container.
+    ///
+    /// Based on Java's `Container.stopOpen(Player)`.
+    fn stop_open(&mut self, _player: &Player) {}
+
     /// Clears all items from this container.
     fn clear_content(&mut self) -> i32 {
         let mut count = 0;
@@ -153,14 +165,8 @@ pub trait Container {
                     return true;
                 }
                 let existing = self.get_item_mut(slot);
-                if !existing.is_empty() && ItemStack::is_same_item_same_components(existing, stack)
-                {
-                    let space = max_size - existing.count();
-                    if space > 0 {
-                        let to_add = stack.count().min(space);
-                        existing.grow(to_add);
-                        stack.shrink(to_add);
-                    }
+                if existing.can_merge_with(stack) {
+                    existing.merge_from(stack, max_size);
                 }
             }
         }
@@ -180,6 +186,46 @@ pub trait Container {
     }
 }
 
+/// A plain list of items that only lives as long as the menu using it, like
+/// the input slots of an anvil or enchanting table.
+///
+/// Based on Java's `SimpleContainer`.
+pub struct SimpleContainer {
+    items: Vec<ItemStack>,
+}
+
+impl SimpleContainer {
+    /// Creates a container with `size` empty slots.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self {
+            items: (0..size).map(|_| ItemStack::empty()).collect(),
+        }
+    }
+}
+
+impl Container for SimpleContainer {
+    fn get_container_size(&self) -> usize {
+        self.items.len()
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        self.items[slot] = stack;
+    }
+
+    fn set_changed(&mut self) {
+        // Menus react to changes in `Menu::slots_changed` instead
+    }
+}
+
 /// Returns mutable references to `N` disjoint slots in a container.
 ///
 /// # Panics
@@ -0,0 +1,182 @@
+//! The dispenser menu, shared by dispensers and droppers.
+//!
+//! Slot layout (45 total):
+//! - Slots 0-8: 3x3 dispenser grid
+//! - Slots 9-35: Main inventory (27 slots)
+//! - Slots 36-44: Hotbar (9 slots)
+
+use std::mem;
+
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_menu_types;
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{NormalSlot, Slot, SlotType, add_standard_inventory_slots},
+};
+use crate::player::Player;
+
+/// Number of slots in a dispenser.
+pub const CONTAINER_SIZE: usize = 9;
+
+/// The menu of a dispenser or dropper.
+///
+/// Based on Java's `DispenserMenu`.
+pub struct DispenserMenu {
+    behavior: MenuBehavior,
+    /// Reference to the dispenser.
+    container: ContainerRef,
+}
+
+impl DispenserMenu {
+    /// Creates a new dispenser menu.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `container` - Reference to the dispenser
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, container: ContainerRef) -> Self {
+        let mut menu_slots = Vec::with_capacity(CONTAINER_SIZE + 36);
+
+        for i in 0..CONTAINER_SIZE {
+            menu_slots.push(SlotType::Normal(NormalSlot::new(container.clone(), i)));
+        }
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        Self {
+            behavior: MenuBehavior::new(
+                menu_slots,
+                container_id,
+                Some(vanilla_menu_types::GENERIC_3X3),
+            ),
+            container,
+        }
+    }
+}
+
+impl Menu for DispenserMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `DispenserMenu::quickMoveStack`:
+    /// - Dispenser slots -> player inventory (backwards = true)
+    /// - Player inventory slots -> dispenser (backwards = false)
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        _player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+        let total_slots = self.behavior.slots.len();
+
+        let moved = if slot_index < CONTAINER_SIZE {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                CONTAINER_SIZE,
+                total_slots,
+                true,
+            )
+        } else {
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, 0, CONTAINER_SIZE, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        clicked
+    }
+
+    fn still_valid(&self) -> bool {
+        let guard = self.behavior.lock_all_containers();
+        guard
+            .get(self.container.container_id())
+            .is_some_and(super::container::Container::still_valid)
+    }
+
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.drop_item(carried, false, true);
+        }
+    }
+}
+
+impl MenuInstance for DispenserMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::GENERIC_3X3
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating dispenser menus.
+pub struct DispenserMenuProvider {
+    inventory: SyncPlayerInv,
+    container: ContainerRef,
+    title: TextComponent,
+}
+
+impl DispenserMenuProvider {
+    /// Creates a new dispenser menu provider.
+    #[must_use]
+    pub const fn new(
+        inventory: SyncPlayerInv,
+        container: ContainerRef,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            container,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for DispenserMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(DispenserMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.container.clone(),
+        ))
+    }
+}
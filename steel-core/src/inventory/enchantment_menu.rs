@@ -0,0 +1,494 @@
+//! The enchanting table menu.
+//!
+//! Slot layout (38 total):
+//! - Slot 0: Item to enchant
+//! - Slot 1: Lapis lazuli
+//! - Slots 2-28: Main inventory (27 slots)
+//! - Slots 29-37: Hotbar (9 slots)
+//!
+//! Data slots: the level cost of each offer, the enchantment seed, then the
+//! enchantment and level shown as a clue for each offer.
+
+use std::{array, mem, sync::Arc};
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::enchantment::{EnchantmentInstance, get_enchantment_cost, select_enchantment};
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_block_tags::{
+    ENCHANTMENT_POWER_PROVIDER_TAG, ENCHANTMENT_POWER_TRANSMITTER_TAG,
+};
+use steel_registry::vanilla_enchantment_tags::IN_ENCHANTING_TABLE_TAG;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::{
+    REGISTRY, RegistryEntry, TaggedRegistryExt, sound_events, vanilla_blocks, vanilla_menu_types,
+};
+use steel_utils::BlockPos;
+use steel_utils::locks::SyncMutex;
+use steel_utils::random::Random;
+use steel_utils::random::legacy_random::LegacyRandom;
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    container::SimpleContainer,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{
+        EnchantmentItemSlot, EnchantmentLapisSlot, Slot, SlotType, add_standard_inventory_slots,
+    },
+};
+use crate::player::Player;
+use crate::world::World;
+
+/// Slot index of the item to enchant.
+const ITEM_SLOT: usize = 0;
+/// Slot index of the lapis lazuli.
+const LAPIS_SLOT: usize = 1;
+/// Start of main inventory.
+const INV_SLOT_START: usize = 2;
+/// End of main inventory (exclusive), start of the hotbar.
+const HOTBAR_SLOT_START: usize = 29;
+/// End of the hotbar (exclusive).
+const HOTBAR_SLOT_END: usize = 38;
+
+/// First data slot holding the level costs.
+const DATA_COSTS: usize = 0;
+/// Data slot holding the enchantment seed.
+const DATA_SEED: usize = 3;
+/// First data slot holding the enchantment clues.
+const DATA_ENCHANT_CLUES: usize = 4;
+/// First data slot holding the level clues.
+const DATA_LEVEL_CLUES: usize = 7;
+
+/// The number of offers an enchanting table shows.
+const OFFER_COUNT: usize = 3;
+
+/// Returns true if a bookshelf at `offset` from the table powers it.
+///
+/// The block halfway between the table and the bookshelf must let the power
+/// through, which air and other non-solid blocks do.
+///
+/// Based on Java's `EnchantingTableBlock::isValidBookShelf`.
+#[must_use]
+pub fn is_valid_bookshelf(world: &World, pos: BlockPos, offset: (i32, i32, i32)) -> bool {
+    let (x, y, z) = offset;
+    let provider = world.get_block_state(pos.offset(x, y, z)).get_block();
+    let transmitter = world
+        .get_block_state(pos.offset(x / 2, y, z / 2))
+        .get_block();
+    REGISTRY
+        .blocks
+        .is_in_tag(provider, &ENCHANTMENT_POWER_PROVIDER_TAG)
+        && REGISTRY
+            .blocks
+            .is_in_tag(transmitter, &ENCHANTMENT_POWER_TRANSMITTER_TAG)
+}
+
+/// Counts the bookshelves around an enchanting table.
+///
+/// Bookshelves count on the two layers of the ring two blocks away from the
+/// table.
+///
+/// Based on `EnchantingTableBlock.BOOKSHELF_OFFSETS` in Java.
+#[must_use]
+pub fn count_bookshelves(world: &World, pos: BlockPos) -> i32 {
+    let mut count = 0;
+    for y in 0..=1 {
+        for x in -2_i32..=2 {
+            for z in -2_i32..=2 {
+                if x.abs() != 2 && z.abs() != 2 {
+                    continue;
+                }
+                if is_valid_bookshelf(world, pos, (x, y, z)) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+/// The menu of an enchanting table.
+///
+/// Based on Java's `EnchantmentMenu`.
+pub struct EnchantmentMenu {
+    behavior: MenuBehavior,
+    /// The container behind the item and lapis slots.
+    input: ContainerRef,
+    /// Rolls the offers, reseeded from `enchantment_seed` for every roll.
+    random: LegacyRandom,
+    /// The player's enchantment seed.
+    enchantment_seed: i32,
+    /// The level cost of each offer, 0 for no offer.
+    costs: [i32; OFFER_COUNT],
+    /// The world the enchanting table is in.
+    world: Arc<World>,
+    /// The position of the enchanting table.
+    pos: BlockPos,
+}
+
+impl EnchantmentMenu {
+    /// Creates a new enchanting table menu.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `world` - The world the enchanting table is in
+    /// * `pos` - The position of the enchanting table
+    /// * `enchantment_seed` - The player's enchantment seed
+    #[must_use]
+    pub fn new(
+        inventory: SyncPlayerInv,
+        container_id: u8,
+        world: Arc<World>,
+        pos: BlockPos,
+        enchantment_seed: i32,
+    ) -> Self {
+        let input = ContainerRef::Other(Arc::new(SyncMutex::new(SimpleContainer::new(2))));
+
+        let mut menu_slots = Vec::with_capacity(HOTBAR_SLOT_END);
+        menu_slots.push(SlotType::EnchantmentItem(EnchantmentItemSlot::new(
+            input.clone(),
+            0,
+        )));
+        menu_slots.push(SlotType::EnchantmentLapis(EnchantmentLapisSlot::new(
+            input.clone(),
+            1,
+        )));
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        let mut menu = Self {
+            behavior: MenuBehavior::new(
+                menu_slots,
+                container_id,
+                Some(vanilla_menu_types::ENCHANTMENT),
+            ),
+            input,
+            random: LegacyRandom::from_seed(0),
+            enchantment_seed,
+            costs: [0; OFFER_COUNT],
+            world,
+            pos,
+        };
+        menu.behavior.add_data_slots(10);
+        menu.clear_offers();
+        menu
+    }
+
+    /// Removes all offers.
+    fn clear_offers(&mut self) {
+        self.costs = [0; OFFER_COUNT];
+        for i in 0..OFFER_COUNT {
+            self.behavior.set_data(DATA_ENCHANT_CLUES + i, -1);
+            self.behavior.set_data(DATA_LEVEL_CLUES + i, -1);
+        }
+        self.update_data_slots();
+    }
+
+    /// Rolls the enchantments of the offer in `slot` for `cost` levels.
+    ///
+    /// Based on Java's `EnchantmentMenu::getEnchantmentList`.
+    fn get_enchantment_list(
+        &mut self,
+        item: &ItemStack,
+        slot: usize,
+        cost: i32,
+    ) -> Vec<EnchantmentInstance> {
+        self.random = LegacyRandom::from_seed(i64::from(
+            self.enchantment_seed.wrapping_add(slot as i32),
+        ) as u64);
+        let mut list = select_enchantment(
+            &mut self.random,
+            item,
+            cost,
+            REGISTRY.enchantments.iter_tag(&IN_ENCHANTING_TABLE_TAG),
+        );
+        if item.is(&ITEMS.book) && list.len() > 1 {
+            list.remove(self.random.next_i32_bounded(list.len() as i32) as usize);
+        }
+        list
+    }
+}
+
+impl Menu for EnchantmentMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `EnchantmentMenu::quickMoveStack`:
+    /// - Item or lapis slot -> player inventory (backwards = true)
+    /// - Lapis -> lapis slot
+    /// - A single other item -> item slot
+    /// - Otherwise nothing moves
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        _player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+
+        let moved = if slot_index == ITEM_SLOT || slot_index == LAPIS_SLOT {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                INV_SLOT_START,
+                HOTBAR_SLOT_END,
+                true,
+            )
+        } else if stack_mut.is(&ITEMS.lapis_lazuli) {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                LAPIS_SLOT,
+                INV_SLOT_START,
+                true,
+            )
+        } else {
+            let item_slot = &self.behavior.slots[ITEM_SLOT];
+            if item_slot.has_item(guard) || !item_slot.may_place(&stack_mut) {
+                return ItemStack::empty();
+            }
+            item_slot.set_by_player(guard, stack_mut.split(1), &ItemStack::empty());
+            true
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        clicked
+    }
+
+    /// Returns true while the enchanting table is still there.
+    fn still_valid(&self) -> bool {
+        self.world.get_block_state(self.pos).get_block() == vanilla_blocks::ENCHANTING_TABLE
+    }
+
+    fn update_data_slots(&mut self) {
+        for (i, cost) in self.costs.into_iter().enumerate() {
+            self.behavior.set_data(DATA_COSTS + i, cost as i16);
+        }
+        self.behavior
+            .set_data(DATA_SEED, self.enchantment_seed as i16);
+    }
+
+    /// Rolls new offers for the item to enchant.
+    ///
+    /// Based on Java's `EnchantmentMenu::slotsChanged`.
+    fn slots_changed(&mut self, _player: &Player) {
+        let item = {
+            let guard = ContainerLockGuard::lock_all(&[&self.input]);
+            self.behavior.slots[ITEM_SLOT].get_item(&guard).clone()
+        };
+        if item.is_empty() || !item.is_enchantable() {
+            self.clear_offers();
+            return;
+        }
+
+        let bookshelves = count_bookshelves(&self.world, self.pos);
+        self.random = LegacyRandom::from_seed(i64::from(self.enchantment_seed) as u64);
+        self.costs = array::from_fn(|i| {
+            let cost = get_enchantment_cost(&mut self.random, i as i32, bookshelves, &item);
+            if cost < i as i32 + 1 { 0 } else { cost }
+        });
+
+        for (i, cost) in self.costs.into_iter().enumerate() {
+            let mut clue = (-1, -1);
+            if cost > 0 {
+                let list = self.get_enchantment_list(&item, i, cost);
+                if !list.is_empty() {
+                    let instance = list[self.random.next_i32_bounded(list.len() as i32) as usize];
+                    clue = (instance.enchantment.id() as i16, instance.level as i16);
+                }
+            }
+            self.behavior.set_data(DATA_ENCHANT_CLUES + i, clue.0);
+            self.behavior.set_data(DATA_LEVEL_CLUES + i, clue.1);
+        }
+        self.update_data_slots();
+    }
+
+    /// Enchants the item with the offer the player picked.
+    ///
+    /// Based on Java's `EnchantmentMenu::clickMenuButton`.
+    fn click_menu_button(&mut self, player: &Player, id: i32) -> bool {
+        let Ok(index) = usize::try_from(id) else {
+            return false;
+        };
+        if index >= OFFER_COUNT {
+            log::warn!(
+                "Player {} clicked invalid enchanting button {id}",
+                player.gameprofile.name
+            );
+            return false;
+        }
+
+        let creative = player.has_infinite_materials();
+        let lapis_cost = id + 1;
+        let (item, lapis) = {
+            let guard = ContainerLockGuard::lock_all(&[&self.input]);
+            (
+                self.behavior.slots[ITEM_SLOT].get_item(&guard).clone(),
+                self.behavior.slots[LAPIS_SLOT].get_item(&guard).clone(),
+            )
+        };
+        if lapis.count() < lapis_cost && !creative {
+            return false;
+        }
+        let level = player.experience.lock().level();
+        let cost = self.costs[index];
+        if cost <= 0 || item.is_empty() || (level < lapis_cost.max(cost) && !creative) {
+            return false;
+        }
+
+        let enchantments = self.get_enchantment_list(&item, index, cost);
+        if enchantments.is_empty() {
+            return true;
+        }
+
+        let enchantment_seed = {
+            let mut experience = player.experience.lock();
+            experience.on_enchantment_performed(lapis_cost);
+            experience.enchantment_seed
+        };
+
+        {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.input]);
+            let mut enchanted = if item.is(&ITEMS.book) {
+                ItemStack::with_count_and_patch(
+                    &ITEMS.enchanted_book,
+                    item.count(),
+                    item.patch().clone(),
+                )
+            } else {
+                item
+            };
+            let mut applied = enchanted.get_enchantments_for_crafting();
+            for instance in enchantments {
+                applied.upgrade(instance.enchantment.key.clone(), instance.level as u32);
+            }
+            enchanted.set_enchantments_for_crafting(applied);
+            self.behavior.slots[ITEM_SLOT].set_item(&mut guard, enchanted);
+
+            if !creative {
+                self.behavior.slots[LAPIS_SLOT].modify_item(&mut guard, |lapis| {
+                    lapis.shrink(lapis_cost);
+                });
+            }
+            self.behavior.slots[ITEM_SLOT].set_changed(&mut guard);
+        }
+
+        // TODO: Award the enchant item statistic and advancement trigger.
+        self.enchantment_seed = enchantment_seed;
+        self.slots_changed(player);
+        self.world.play_block_sound(
+            sound_events::BLOCK_ENCHANTMENT_TABLE_USE,
+            self.pos,
+            1.0,
+            rand::random::<f32>() * 0.1 + 0.9,
+            None,
+        );
+        true
+    }
+
+    /// Gives the item and lapis back to the player.
+    ///
+    /// Based on Java's `EnchantmentMenu::removed` which calls `clearContainer`.
+    fn removed(&mut self, player: &Player) {
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.add_item_or_drop(carried);
+        }
+
+        let inputs = {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.input]);
+            guard.get_mut(self.input.container_id()).map(|container| {
+                [ITEM_SLOT, LAPIS_SLOT].map(|slot| container.remove_item_no_update(slot))
+            })
+        };
+        for item in inputs.into_iter().flatten() {
+            if !item.is_empty() {
+                player.add_item_or_drop(item);
+            }
+        }
+    }
+}
+
+impl MenuInstance for EnchantmentMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::ENCHANTMENT
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating enchanting table menus.
+pub struct EnchantmentMenuProvider {
+    inventory: SyncPlayerInv,
+    world: Arc<World>,
+    pos: BlockPos,
+    enchantment_seed: i32,
+    title: TextComponent,
+}
+
+impl EnchantmentMenuProvider {
+    /// Creates a new enchanting table menu provider.
+    #[must_use]
+    pub const fn new(
+        inventory: SyncPlayerInv,
+        world: Arc<World>,
+        pos: BlockPos,
+        enchantment_seed: i32,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            world,
+            pos,
+            enchantment_seed,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for EnchantmentMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(EnchantmentMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.world.clone(),
+            self.pos,
+            self.enchantment_seed,
+        ))
+    }
+}
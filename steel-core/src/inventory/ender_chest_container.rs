@@ -0,0 +1,102 @@
+//! The player's personal ender chest inventory.
+//!
+//! Every ender chest shows the same 27 slots to a given player. The container
+//! remembers which ender chest block the player opened last so the lid of
+//! that chest opens and closes with the menu.
+
+use steel_registry::item_stack::ItemStack;
+
+use crate::block_entity::SharedBlockEntity;
+use crate::block_entity::entities::EnderChestBlockEntity;
+use crate::inventory::container::Container;
+use crate::player::Player;
+
+/// Number of slots in the ender chest inventory (3 rows of 9).
+pub const ENDER_CHEST_SLOTS: usize = 27;
+
+/// A player's ender chest inventory.
+///
+/// Based on Java's `PlayerEnderChestContainer`.
+pub struct PlayerEnderChestContainer {
+    /// The 27 item slots.
+    items: Vec<ItemStack>,
+    /// The ender chest the player is looking into, if any.
+    active_chest: Option<SharedBlockEntity>,
+}
+
+impl Default for PlayerEnderChestContainer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlayerEnderChestContainer {
+    /// Creates an empty ender chest inventory.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            items: vec![ItemStack::empty(); ENDER_CHEST_SLOTS],
+            active_chest: None,
+        }
+    }
+
+    /// Sets the ender chest block entity the next menu is opened through.
+    pub fn set_active_chest(&mut self, chest: SharedBlockEntity) {
+        self.active_chest = Some(chest);
+    }
+
+    /// Runs `f` on the active ender chest block entity, if there is one.
+    fn with_active_chest(&self, f: impl FnOnce(&mut EnderChestBlockEntity)) {
+        let Some(chest) = &self.active_chest else {
+            return;
+        };
+        let mut guard = chest.lock();
+        if let Some(chest) = guard.as_any_mut().downcast_mut::<EnderChestBlockEntity>() {
+            f(chest);
+        }
+    }
+}
+
+impl Container for PlayerEnderChestContainer {
+    fn get_container_size(&self) -> usize {
+        ENDER_CHEST_SLOTS
+    }
+
+    fn get_item(&self, slot: usize) -> &ItemStack {
+        &self.items[slot]
+    }
+
+    fn get_item_mut(&mut self, slot: usize) -> &mut ItemStack {
+        &mut self.items[slot]
+    }
+
+    fn set_item(&mut self, slot: usize, stack: ItemStack) {
+        if slot < ENDER_CHEST_SLOTS {
+            self.items[slot] = stack;
+        }
+    }
+
+    fn get_max_stack_size(&self) -> i32 {
+        64
+    }
+
+    fn set_changed(&mut self) {
+        // Saved with the player data
+    }
+
+    /// The menu closes once the ender chest it was opened through is gone.
+    fn still_valid(&self) -> bool {
+        self.active_chest
+            .as_ref()
+            .is_none_or(|chest| !chest.lock().is_removed())
+    }
+
+    fn start_open(&mut self, player: &Player) {
+        self.with_active_chest(|chest| chest.start_open(player));
+    }
+
+    fn stop_open(&mut self, player: &Player) {
+        self.with_active_chest(|chest| chest.stop_open(player));
+        self.active_chest = None;
+    }
+}
@@ -1,5 +1,7 @@
 //! Equipment slot definitions for entities.
 
+use steel_registry::data_components::vanilla_components::EquippableSlot;
+
 /// Equipment slot types for categorization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EquipmentSlotType {
@@ -126,3 +128,18 @@ impl EquipmentSlot {
         }
     }
 }
+
+impl From<EquippableSlot> for EquipmentSlot {
+    fn from(slot: EquippableSlot) -> Self {
+        match slot {
+            EquippableSlot::Head => EquipmentSlot::Head,
+            EquippableSlot::Chest => EquipmentSlot::Chest,
+            EquippableSlot::Legs => EquipmentSlot::Legs,
+            EquippableSlot::Feet => EquipmentSlot::Feet,
+            EquippableSlot::Body => EquipmentSlot::Body,
+            EquippableSlot::Mainhand => EquipmentSlot::MainHand,
+            EquippableSlot::Offhand => EquipmentSlot::OffHand,
+            EquippableSlot::Saddle => EquipmentSlot::Saddle,
+        }
+    }
+}
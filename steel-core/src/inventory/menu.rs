@@ -30,6 +30,7 @@ use steel_registry::{
 };
 
 use crate::{
+    behavior::ITEM_BEHAVIORS,
     inventory::{
         lock::{ContainerId, ContainerLockGuard, ContainerRef},
         slot::{Slot, SlotType},
@@ -439,25 +440,14 @@ impl MenuBehavior {
                 }
 
                 let slot = &self.slots[dest_slot];
-                let target = slot.get_item(guard).clone();
+                let max_stack_size = slot.get_max_stack_size_for_item(guard, item_stack);
+                let target = slot.get_item_mut(guard);
 
-                if !target.is_empty()
-                    && ItemStack::is_same_item_same_components(item_stack, &target)
+                if target.can_merge_with(item_stack)
+                    && target.merge_from(item_stack, max_stack_size) > 0
                 {
-                    let total_stack = target.count + item_stack.count;
-                    let max_stack_size = slot.get_max_stack_size_for_item(guard, &target);
-
-                    if total_stack <= max_stack_size {
-                        item_stack.set_count(0);
-                        slot.get_item_mut(guard).set_count(total_stack);
-                        slot.set_changed(guard);
-                        anything_changed = true;
-                    } else if target.count < max_stack_size {
-                        item_stack.shrink(max_stack_size - target.count);
-                        slot.get_item_mut(guard).set_count(max_stack_size);
-                        slot.set_changed(guard);
-                        anything_changed = true;
-                    }
+                    slot.set_changed(guard);
+                    anything_changed = true;
                 }
 
                 if backwards {
@@ -958,9 +948,35 @@ impl MenuBehavior {
         let slot = &self.slots[slot_index];
 
         // Get the current item in the slot
-        let slot_item = slot.get_item(&guard).clone();
+        let mut slot_item = slot.get_item(&guard).clone();
         let mut carried = mem::take(&mut self.carried);
 
+        // Items like bundles can take over the click
+        if ITEM_BEHAVIORS
+            .get_behavior(carried.item())
+            .override_stacked_on_other(&mut carried, slot, &mut guard, button, player)
+        {
+            self.carried = carried;
+            slot.set_changed(&mut guard);
+            return;
+        }
+        if ITEM_BEHAVIORS
+            .get_behavior(slot_item.item())
+            .override_other_stacked_on_me(
+                &mut slot_item,
+                &mut carried,
+                slot,
+                &mut guard,
+                button,
+                player,
+            )
+        {
+            slot.set_item(&mut guard, slot_item);
+            self.carried = carried;
+            slot.set_changed(&mut guard);
+            return;
+        }
+
         if slot_item.is_empty() {
             // Slot is empty - place carried items (if allowed)
             if !carried.is_empty() && slot.may_place(&carried) {
@@ -1000,38 +1016,14 @@ impl MenuBehavior {
         } else if ItemStack::is_same_item_same_components(&slot_item, &carried) {
             // Same item type - try to stack (if slot allows this item type)
             if slot.may_place(&carried) {
-                if button == 0 {
-                    // Left click - add as many as possible to slot
-                    let max = slot.get_max_stack_size_for_item(&guard, &carried);
-                    let space = max - slot_item.count;
-                    let to_add = space.min(carried.count);
-
-                    if to_add > 0 {
-                        slot.get_item_mut(&mut guard)
-                            .set_count(slot_item.count + to_add);
-                        let remaining = carried.count - to_add;
-                        if remaining > 0 {
-                            let mut new_carried = carried;
-                            new_carried.set_count(remaining);
-                            self.carried = new_carried;
-                        }
-                    } else {
-                        self.carried = carried;
-                    }
-                } else {
-                    // Right click - add one to slot
-                    let max = slot.get_max_stack_size_for_item(&guard, &carried);
-                    if slot_item.count < max {
-                        slot.get_item_mut(&mut guard).set_count(slot_item.count + 1);
-                        let remaining = carried.count - 1;
-                        if remaining > 0 {
-                            let mut new_carried = carried;
-                            new_carried.set_count(remaining);
-                            self.carried = new_carried;
-                        }
-                    } else {
-                        self.carried = carried;
-                    }
+                // Left click adds as many as possible, right click adds one
+                let max = slot.get_max_stack_size_for_item(&guard, &carried);
+                let requested = if button == 0 { carried.count } else { 1 };
+                let mut moving = carried.split(requested);
+                slot.get_item_mut(&mut guard).merge_from(&mut moving, max);
+                carried.grow(moving.count());
+                if !carried.is_empty() {
+                    self.carried = carried;
                 }
             } else {
                 // Can't place this item type in this slot
@@ -1184,6 +1176,27 @@ pub trait Menu {
         true
     }
 
+    /// Copies live values, like a block entity's, into the data slots before
+    /// changes are broadcast.
+    ///
+    /// Java menus read their `ContainerData` on every broadcast instead.
+    fn update_data_slots(&mut self) {}
+
+    /// Called after a click may have changed the menu's slots.
+    /// Override to recompute derived slots, like an anvil's result.
+    ///
+    /// Based on Java's `AbstractContainerMenu::slotsChanged`.
+    fn slots_changed(&mut self, _player: &Player) {}
+
+    /// Handles a menu button click, like picking an enchanting table offer.
+    ///
+    /// Returns true if the click did something.
+    ///
+    /// Based on Java's `AbstractContainerMenu::clickMenuButton`.
+    fn click_menu_button(&mut self, _player: &Player, _id: i32) -> bool {
+        false
+    }
+
     /// Returns true if the item can be taken from the slot during pickup all.
     /// Override to prevent pickup from certain slots (like crafting result).
     fn can_take_item_for_pick_all(&self, _carried: &ItemStack, _slot_index: usize) -> bool {
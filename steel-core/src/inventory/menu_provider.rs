@@ -3,6 +3,8 @@
 use steel_registry::menu_type::MenuTypeRef;
 use text_components::TextComponent;
 
+use crate::inventory::anvil_menu::AnvilMenu;
+use crate::inventory::beacon_menu::BeaconMenu;
 use crate::inventory::menu::Menu;
 
 /// Trait for menu instances that can be opened by players.
@@ -15,6 +17,16 @@ pub trait MenuInstance: Menu + Send + Sync {
 
     /// Returns the container ID for this menu.
     fn container_id(&self) -> u8;
+
+    /// Returns this menu as a beacon menu, if it is one.
+    fn as_beacon_menu_mut(&mut self) -> Option<&mut BeaconMenu> {
+        None
+    }
+
+    /// Returns this menu as an anvil menu, if it is one.
+    fn as_anvil_menu_mut(&mut self) -> Option<&mut AnvilMenu> {
+        None
+    }
 }
 
 /// Trait for types that can create menus.
@@ -0,0 +1,195 @@
+//! The shulker box menu.
+//!
+//! Slot layout (63 total):
+//! - Slots 0-26: Shulker box slots (3 rows of 9)
+//! - Slots 27-53: Main inventory (27 slots)
+//! - Slots 54-62: Hotbar (9 slots)
+
+use std::mem;
+
+use steel_registry::item_stack::ItemStack;
+use steel_registry::menu_type::MenuTypeRef;
+use steel_registry::vanilla_menu_types;
+use text_components::TextComponent;
+
+use crate::inventory::{
+    SyncPlayerInv,
+    lock::{ContainerLockGuard, ContainerRef},
+    menu::{Menu, MenuBehavior},
+    menu_provider::{MenuInstance, MenuProvider},
+    slot::{ShulkerBoxSlot, Slot, SlotType, add_standard_inventory_slots},
+};
+use crate::player::Player;
+
+/// Number of slots in a shulker box.
+pub const CONTAINER_SIZE: usize = 27;
+
+/// The menu of a shulker box.
+///
+/// Based on Java's `ShulkerBoxMenu`.
+pub struct ShulkerBoxMenu {
+    behavior: MenuBehavior,
+    /// Reference to the shulker box.
+    container: ContainerRef,
+}
+
+impl ShulkerBoxMenu {
+    /// Creates a new shulker box menu.
+    ///
+    /// The caller is responsible for calling `start_open` on the container,
+    /// `removed` calls `stop_open` when the menu closes.
+    ///
+    /// # Arguments
+    /// * `inventory` - The player's inventory
+    /// * `container_id` - The container ID for this menu (1-100)
+    /// * `container` - Reference to the shulker box
+    #[must_use]
+    pub fn new(inventory: SyncPlayerInv, container_id: u8, container: ContainerRef) -> Self {
+        let mut menu_slots = Vec::with_capacity(CONTAINER_SIZE + 36);
+
+        for i in 0..CONTAINER_SIZE {
+            menu_slots.push(SlotType::ShulkerBox(ShulkerBoxSlot::new(
+                container.clone(),
+                i,
+            )));
+        }
+        add_standard_inventory_slots(&mut menu_slots, &inventory);
+
+        Self {
+            behavior: MenuBehavior::new(
+                menu_slots,
+                container_id,
+                Some(vanilla_menu_types::SHULKER_BOX),
+            ),
+            container,
+        }
+    }
+}
+
+impl Menu for ShulkerBoxMenu {
+    fn behavior(&self) -> &MenuBehavior {
+        &self.behavior
+    }
+
+    fn behavior_mut(&mut self) -> &mut MenuBehavior {
+        &mut self.behavior
+    }
+
+    /// Handles shift-click (quick move) for a slot.
+    ///
+    /// Based on Java's `ShulkerBoxMenu::quickMoveStack`:
+    /// - Shulker box slots -> player inventory (backwards = true)
+    /// - Player inventory slots -> shulker box (backwards = false)
+    fn quick_move_stack(
+        &mut self,
+        guard: &mut ContainerLockGuard,
+        slot_index: usize,
+        _player: &Player,
+    ) -> ItemStack {
+        if slot_index >= self.behavior.slots.len() {
+            return ItemStack::empty();
+        }
+
+        let stack = self.behavior.slots[slot_index].get_item(guard).clone();
+        if stack.is_empty() {
+            return ItemStack::empty();
+        }
+
+        let clicked = stack.clone();
+        let mut stack_mut = stack;
+        let total_slots = self.behavior.slots.len();
+
+        let moved = if slot_index < CONTAINER_SIZE {
+            self.behavior.move_item_stack_to(
+                guard,
+                &mut stack_mut,
+                CONTAINER_SIZE,
+                total_slots,
+                true,
+            )
+        } else {
+            self.behavior
+                .move_item_stack_to(guard, &mut stack_mut, 0, CONTAINER_SIZE, false)
+        };
+
+        if !moved {
+            return ItemStack::empty();
+        }
+
+        self.behavior.slots[slot_index].set_item(guard, stack_mut.clone());
+        if stack_mut.count == clicked.count {
+            return ItemStack::empty();
+        }
+        self.behavior.slots[slot_index].set_changed(guard);
+
+        clicked
+    }
+
+    fn still_valid(&self) -> bool {
+        let guard = self.behavior.lock_all_containers();
+        guard
+            .get(self.container.container_id())
+            .is_some_and(super::container::Container::still_valid)
+    }
+
+    fn removed(&mut self, player: &Player) {
+        {
+            let mut guard = ContainerLockGuard::lock_all(&[&self.container]);
+            if let Some(container) = guard.get_mut(self.container.container_id()) {
+                container.stop_open(player);
+            }
+        }
+
+        let carried = mem::take(&mut self.behavior.carried);
+        if !carried.is_empty() {
+            player.drop_item(carried, false, true);
+        }
+    }
+}
+
+impl MenuInstance for ShulkerBoxMenu {
+    fn menu_type(&self) -> MenuTypeRef {
+        vanilla_menu_types::SHULKER_BOX
+    }
+
+    fn container_id(&self) -> u8 {
+        self.behavior.container_id
+    }
+}
+
+/// Provider for creating shulker box menus.
+pub struct ShulkerBoxMenuProvider {
+    inventory: SyncPlayerInv,
+    container: ContainerRef,
+    title: TextComponent,
+}
+
+impl ShulkerBoxMenuProvider {
+    /// Creates a new shulker box menu provider.
+    #[must_use]
+    pub const fn new(
+        inventory: SyncPlayerInv,
+        container: ContainerRef,
+        title: TextComponent,
+    ) -> Self {
+        Self {
+            inventory,
+            container,
+            title,
+        }
+    }
+}
+
+impl MenuProvider for ShulkerBoxMenuProvider {
+    fn title(&self) -> TextComponent {
+        self.title.clone()
+    }
+
+    fn create(&self, container_id: u8) -> Box<dyn MenuInstance> {
+        Box::new(ShulkerBoxMenu::new(
+            self.inventory.clone(),
+            container_id,
+            self.container.clone(),
+        ))
+    }
+}
@@ -9,12 +9,21 @@
 use std::{mem, sync::Arc};
 
 use enum_dispatch::enum_dispatch;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, Direction};
 use steel_registry::data_components::vanilla_components::EquippableSlot;
 use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_block_tags::ANVIL_TAG;
 use steel_registry::vanilla_enchantments::BINDING_CURSE;
+use steel_registry::vanilla_item_tags::BEACON_PAYMENT_ITEMS_TAG;
+use steel_registry::vanilla_items::ITEMS;
+use steel_registry::{REGISTRY, TaggedRegistryExt, level_events, vanilla_blocks};
 use steel_utils::locks::SyncMutex;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
 
 use crate::inventory::SyncPlayerInv;
+use crate::inventory::anvil_menu::SharedAnvilCost;
 use crate::inventory::container::Container;
 use crate::inventory::crafting::{CraftingContainer, ResultContainer};
 use crate::inventory::lock::{ContainerId, ContainerLockGuard, ContainerRef};
@@ -180,6 +189,35 @@ pub trait Slot {
         }
     }
 
+    /// Safely inserts up to `amount` items from `stack` into this slot.
+    ///
+    /// Only fills an empty slot or tops up a matching stack, leaving whatever
+    /// doesn't fit in `stack`.
+    ///
+    /// Based on Java's `Slot::safeInsert`.
+    fn safe_insert(&self, guard: &mut ContainerLockGuard, stack: &mut ItemStack, amount: i32) {
+        if stack.is_empty() || !self.may_place(stack) {
+            return;
+        }
+
+        let current = self.get_item(guard).clone();
+        let to_add = amount
+            .min(stack.count())
+            .min(self.get_max_stack_size_for_item(guard, stack) - current.count());
+        if to_add <= 0 {
+            return;
+        }
+
+        if current.is_empty() {
+            self.set_by_player(guard, stack.split(to_add), &current);
+        } else if ItemStack::is_same_item_same_components(&current, stack) {
+            stack.shrink(to_add);
+            let mut grown = current.clone();
+            grown.grow(to_add);
+            self.set_by_player(guard, grown, &current);
+        }
+    }
+
     /// Marks the slot's container as changed.
     fn set_changed(&self, guard: &mut ContainerLockGuard);
 
@@ -698,6 +736,406 @@ impl Slot for CraftingResultSlot {
     }
 }
 
+/// A shulker box slot, which refuses items that can't go inside containers.
+///
+/// Based on Java's `ShulkerBoxSlot`.
+pub struct ShulkerBoxSlot {
+    slot: NormalSlot,
+}
+
+impl ShulkerBoxSlot {
+    /// Creates a new shulker box slot.
+    pub fn new(container: impl Into<ContainerRef>, index: usize) -> Self {
+        Self {
+            slot: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.slot.container_ref()
+    }
+}
+
+impl Slot for ShulkerBoxSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.slot.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.slot.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.slot.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        stack.can_fit_inside_container_items()
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.slot.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.slot.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.slot.get_max_stack_size(guard)
+    }
+}
+
+/// The payment slot of a beacon, which holds a single payment item.
+///
+/// Based on Java's `BeaconMenu.PaymentSlot`.
+pub struct BeaconPaymentSlot {
+    slot: NormalSlot,
+}
+
+impl BeaconPaymentSlot {
+    /// Creates a new beacon payment slot.
+    pub fn new(container: impl Into<ContainerRef>, index: usize) -> Self {
+        Self {
+            slot: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.slot.container_ref()
+    }
+}
+
+impl Slot for BeaconPaymentSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.slot.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.slot.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.slot.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        REGISTRY
+            .items
+            .is_in_tag(stack.item, &BEACON_PAYMENT_ITEMS_TAG)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.slot.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.slot.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, _guard: &ContainerLockGuard) -> i32 {
+        1
+    }
+}
+
+/// The input slot of an enchanting table, which holds a single item.
+///
+/// Based on the item slot in Java's `EnchantmentMenu`.
+pub struct EnchantmentItemSlot {
+    slot: NormalSlot,
+}
+
+impl EnchantmentItemSlot {
+    /// Creates a new enchanting table item slot.
+    pub fn new(container: impl Into<ContainerRef>, index: usize) -> Self {
+        Self {
+            slot: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.slot.container_ref()
+    }
+}
+
+impl Slot for EnchantmentItemSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.slot.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.slot.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.slot.set_item(guard, stack);
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.slot.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.slot.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, _guard: &ContainerLockGuard) -> i32 {
+        1
+    }
+}
+
+/// The lapis slot of an enchanting table, which only accepts lapis lazuli.
+///
+/// Based on the lapis slot in Java's `EnchantmentMenu`.
+pub struct EnchantmentLapisSlot {
+    slot: NormalSlot,
+}
+
+impl EnchantmentLapisSlot {
+    /// Creates a new enchanting table lapis slot.
+    pub fn new(container: impl Into<ContainerRef>, index: usize) -> Self {
+        Self {
+            slot: NormalSlot::new(container, index),
+        }
+    }
+
+    /// Returns a reference to the container.
+    #[must_use]
+    pub fn container_ref(&self) -> ContainerRef {
+        self.slot.container_ref()
+    }
+}
+
+impl Slot for EnchantmentLapisSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        self.slot.get_item(guard)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        self.slot.get_item_mut(guard)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        self.slot.set_item(guard, stack);
+    }
+
+    fn may_place(&self, stack: &ItemStack) -> bool {
+        stack.is(&ITEMS.lapis_lazuli)
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        self.slot.set_changed(guard);
+    }
+
+    fn get_container_slot(&self) -> usize {
+        self.slot.get_container_slot()
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        self.slot.get_max_stack_size(guard)
+    }
+}
+
+/// The result slot of an anvil.
+///
+/// The result can only be taken when the player can pay the level cost.
+/// Taking it pays the cost, uses up the inputs and may damage the anvil.
+///
+/// Based on the result slot of Java's `ItemCombinerMenu` and `AnvilMenu::onTake`.
+pub struct AnvilResultSlot {
+    result_container: SyncResultContainer,
+    input: ContainerRef,
+    cost: SharedAnvilCost,
+    pos: BlockPos,
+}
+
+impl AnvilResultSlot {
+    /// Creates a new anvil result slot.
+    pub const fn new(
+        result_container: SyncResultContainer,
+        input: ContainerRef,
+        cost: SharedAnvilCost,
+        pos: BlockPos,
+    ) -> Self {
+        Self {
+            result_container,
+            input,
+            cost,
+            pos,
+        }
+    }
+
+    /// Returns a reference to the result container.
+    #[must_use]
+    pub fn result_container_ref(&self) -> ContainerRef {
+        ContainerRef::ResultContainer(Arc::clone(&self.result_container))
+    }
+
+    /// Returns a reference to the input container.
+    #[must_use]
+    pub fn input_container_ref(&self) -> ContainerRef {
+        self.input.clone()
+    }
+
+    /// Damages the anvil with a 12% chance, or just plays its use sound.
+    ///
+    /// Based on the block access part of Java's `AnvilMenu::onTake`.
+    fn damage_anvil(&self, player: &Player) {
+        let world = &player.world;
+        let state = world.get_block_state(self.pos);
+        if player.has_infinite_materials()
+            || !REGISTRY.blocks.is_in_tag(state.get_block(), &ANVIL_TAG)
+            || rand::random::<f32>() >= 0.12
+        {
+            world.level_event(level_events::SOUND_ANVIL_USED, self.pos, 0, None);
+            return;
+        }
+
+        if let Some(damaged) = damage_anvil_state(state) {
+            world.set_block(self.pos, damaged, UpdateFlags::UPDATE_CLIENTS);
+            world.level_event(level_events::SOUND_ANVIL_USED, self.pos, 0, None);
+        } else {
+            world.remove_block(self.pos, false);
+            world.level_event(level_events::SOUND_ANVIL_BROKEN, self.pos, 0, None);
+        }
+    }
+}
+
+impl Slot for AnvilResultSlot {
+    fn get_item<'a>(&self, guard: &'a ContainerLockGuard) -> &'a ItemStack {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item(0)
+    }
+
+    fn get_item_mut<'a>(&self, guard: &'a mut ContainerLockGuard) -> &'a mut ItemStack {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_item_mut(0)
+    }
+
+    fn set_item(&self, guard: &mut ContainerLockGuard, stack: ItemStack) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_item(0, stack);
+    }
+
+    /// Cannot place items directly in the result slot.
+    fn may_place(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    /// Based on Java's `AnvilMenu::mayPickup`.
+    fn may_pickup(&self, _guard: &ContainerLockGuard, player: &Player) -> bool {
+        let cost = self.cost.lock().cost;
+        (player.has_infinite_materials() || player.experience.lock().level() >= cost) && cost > 0
+    }
+
+    /// Result slots don't allow partial removal.
+    fn allow_modification(&self, _guard: &ContainerLockGuard, _player: &Player) -> bool {
+        false
+    }
+
+    /// Always takes the entire result stack.
+    fn remove(&self, guard: &mut ContainerLockGuard, _amount: i32) -> ItemStack {
+        mem::take(self.get_item_mut(guard))
+    }
+
+    fn set_changed(&self, guard: &mut ContainerLockGuard) {
+        guard
+            .get_mut(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .set_changed();
+    }
+
+    fn get_container_slot(&self) -> usize {
+        0
+    }
+
+    fn get_max_stack_size(&self, guard: &ContainerLockGuard) -> i32 {
+        guard
+            .get(ContainerId::from_arc(&self.result_container))
+            .expect("container not locked")
+            .get_max_stack_size()
+    }
+
+    /// Pays the level cost and uses up the inputs.
+    ///
+    /// The menu recomputes the (now empty) result and cost afterwards in
+    /// `Menu::slots_changed`.
+    ///
+    /// Based on Java's `AnvilMenu::onTake`.
+    fn on_take(
+        &self,
+        guard: &mut ContainerLockGuard,
+        _stack: &ItemStack,
+        player: &Player,
+    ) -> Option<ItemStack> {
+        {
+            let mut cost = self.cost.lock();
+            if !player.has_infinite_materials() {
+                player.experience.lock().add_levels(-cost.cost);
+            }
+
+            let input = guard
+                .get_mut(self.input.container_id())
+                .expect("container not locked");
+            if cost.repair_item_count_cost > 0 {
+                let addition = input.get_item_mut(1);
+                if !addition.is_empty() && addition.count() > cost.repair_item_count_cost {
+                    addition.shrink(cost.repair_item_count_cost);
+                } else {
+                    input.set_item(1, ItemStack::empty());
+                }
+            } else if !cost.only_renaming {
+                input.set_item(1, ItemStack::empty());
+            }
+            cost.cost = 0;
+            input.set_item(0, ItemStack::empty());
+        }
+
+        self.damage_anvil(player);
+        None
+    }
+
+    /// Anvil result slots are "fake" - they don't persist items.
+    fn is_fake(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the next more damaged anvil, keeping its facing, or `None` if a
+/// damaged anvil breaks.
+///
+/// Based on Java's `AnvilBlock::damage`.
+#[must_use]
+pub fn damage_anvil_state(state: BlockStateId) -> Option<BlockStateId> {
+    let block = state.get_block();
+    let next = if block == vanilla_blocks::ANVIL {
+        vanilla_blocks::CHIPPED_ANVIL
+    } else if block == vanilla_blocks::CHIPPED_ANVIL {
+        vanilla_blocks::DAMAGED_ANVIL
+    } else {
+        return None;
+    };
+    let facing: Direction = state.get_value(&BlockStateProperties::HORIZONTAL_FACING);
+    Some(
+        REGISTRY
+            .blocks
+            .get_base_state_id(next)
+            .set_value(&BlockStateProperties::HORIZONTAL_FACING, facing),
+    )
+}
+
 /// Enum of all slot types that implement the Slot trait.
 #[enum_dispatch(Slot)]
 pub enum SlotType {
@@ -709,6 +1147,16 @@ pub enum SlotType {
     CraftingGrid(CraftingGridSlot),
     /// Crafting result slot (fake, doesn't persist items).
     CraftingResult(CraftingResultSlot),
+    /// Shulker box slot that refuses items which can't go inside containers.
+    ShulkerBox(ShulkerBoxSlot),
+    /// Beacon payment slot that only accepts a single payment item.
+    BeaconPayment(BeaconPaymentSlot),
+    /// Enchanting table slot holding the item to enchant.
+    EnchantmentItem(EnchantmentItemSlot),
+    /// Enchanting table slot that only accepts lapis lazuli.
+    EnchantmentLapis(EnchantmentLapisSlot),
+    /// Anvil result slot (fake, charges the level cost when taken).
+    AnvilResult(AnvilResultSlot),
 }
 
 impl SlotType {
@@ -724,6 +1172,11 @@ impl SlotType {
             SlotType::CraftingResult(s) => {
                 vec![s.result_container_ref(), s.crafting_container_ref()]
             }
+            SlotType::ShulkerBox(s) => vec![s.container_ref()],
+            SlotType::BeaconPayment(s) => vec![s.container_ref()],
+            SlotType::EnchantmentItem(s) => vec![s.container_ref()],
+            SlotType::EnchantmentLapis(s) => vec![s.container_ref()],
+            SlotType::AnvilResult(s) => vec![s.result_container_ref(), s.input_container_ref()],
         }
     }
 
@@ -737,6 +1190,9 @@ impl SlotType {
         match self {
             SlotType::Normal(s) => Some((s.container_ref().container_id(), s.get_container_slot())),
             SlotType::Armor(s) => Some((s.container_ref().container_id(), s.get_container_slot())),
+            SlotType::ShulkerBox(s) => {
+                Some((s.container_ref().container_id(), s.get_container_slot()))
+            }
             _ => None,
         }
     }
@@ -0,0 +1,66 @@
+//! Command storage, the named NBT compounds read and written by `/data storage`.
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+use simdnbt::owned::NbtCompound;
+use steel_utils::Identifier;
+use steel_utils::nbt::snbt::{compound_to_snbt, parse_snbt_compound};
+
+/// Named NBT compounds shared by all commands, mirroring vanilla's `CommandStorage`.
+///
+/// Vanilla saves these to `data/command_storage_<namespace>.dat`. Steel keeps them
+/// in the overworld's `level.json` instead, with every compound written as SNBT.
+#[derive(Debug, Clone, Default)]
+pub struct CommandStorage {
+    entries: FxHashMap<Identifier, NbtCompound>,
+}
+
+impl CommandStorage {
+    /// Returns a copy of the compound stored under `id`, or an empty compound.
+    #[must_use]
+    pub fn get(&self, id: &Identifier) -> NbtCompound {
+        self.entries
+            .get(id)
+            .cloned()
+            .unwrap_or_else(NbtCompound::new)
+    }
+
+    /// Stores `data` under `id`. Storing an empty compound removes the entry.
+    pub fn set(&mut self, id: Identifier, data: NbtCompound) {
+        if data.is_empty() {
+            self.entries.remove(&id);
+        } else {
+            self.entries.insert(id, data);
+        }
+    }
+
+    /// Returns the ids of all non-empty storages.
+    pub fn keys(&self) -> impl Iterator<Item = &Identifier> {
+        self.entries.keys()
+    }
+}
+
+impl Serialize for CommandStorage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries: FxHashMap<&Identifier, String> = self
+            .entries
+            .iter()
+            .map(|(id, data)| (id, compound_to_snbt(data)))
+            .collect();
+        entries.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CommandStorage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = FxHashMap::<Identifier, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(id, snbt)| {
+                parse_snbt_compound(&snbt)
+                    .map(|data| (id.clone(), data))
+                    .map_err(|e| D::Error::custom(format!("invalid command storage {id}: {e}")))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { entries })
+    }
+}
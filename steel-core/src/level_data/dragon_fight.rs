@@ -0,0 +1,42 @@
+//! Saved state of the End dragon fight.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The parts of the dragon fight that survive a restart.
+///
+/// Only the End's `level.json` holds this. Vanilla: `EndDragonFight.Data`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DragonFightData {
+    /// Whether the arena still has to be scanned for an existing portal and dragon.
+    pub needs_state_scanning: bool,
+    /// Whether the current dragon is dead.
+    pub dragon_killed: bool,
+    /// Whether a dragon was ever killed in this world.
+    pub previously_killed: bool,
+    /// Whether the dragon is being respawned with end crystals.
+    pub is_respawning: bool,
+    /// The dragon taking part in the fight.
+    pub dragon: Option<Uuid>,
+    /// Position of the exit portal's center pillar base, as `[x, y, z]`.
+    pub exit_portal_location: Option<[i32; 3]>,
+    /// Indices of the end gateways still to be spawned, the last one is next.
+    ///
+    /// `None` until the fight first shuffles them.
+    pub gateways: Option<Vec<i32>>,
+}
+
+impl Default for DragonFightData {
+    fn default() -> Self {
+        Self {
+            needs_state_scanning: true,
+            dragon_killed: false,
+            previously_killed: false,
+            is_respawning: false,
+            dragon: None,
+            exit_portal_location: None,
+            gateways: None,
+        }
+    }
+}
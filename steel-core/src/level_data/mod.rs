@@ -4,6 +4,14 @@
 //! time, weather, spawn point, and seed. This data is stored in `level.json`
 //! in each world's directory.
 
+mod command_storage;
+mod dragon_fight;
+mod random_sequences;
+
+pub use command_storage::CommandStorage;
+pub use dragon_fight::DragonFightData;
+pub use random_sequences::RandomSequences;
+
 use std::{
     io,
     path::{Path, PathBuf},
@@ -11,20 +19,79 @@ use std::{
 
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use steel_registry::REGISTRY;
 use steel_registry::game_rules::{GameRuleValue, GameRuleValues};
-use steel_utils::BlockPos;
+use steel_registry::vanilla_dimension_types::OVERWORLD;
+use steel_utils::types::Difficulty;
+use steel_utils::{BlockPos, Identifier};
 use tokio::fs;
 
+/// Current level data version. Increment and add an entry to
+/// [`LEVEL_DATA_UPGRADES`] when saved level data needs migrating.
+/// v1: Level data carries a data version.
+pub const LEVEL_DATA_VERSION: i32 = 1;
+
+/// A migration of `level.json` from one data version to the next.
+pub struct LevelDataUpgrade {
+    /// The data version the upgrade starts from.
+    pub from: i32,
+    /// Rewrites the JSON so it matches data version `from + 1`.
+    pub upgrade: fn(&mut Value),
+}
+
+/// The level data upgrades, ordered by the version they start from. Versions
+/// without an entry need no changes to their data.
+pub static LEVEL_DATA_UPGRADES: &[LevelDataUpgrade] = &[];
+
+/// Runs the upgrades `level.json` needs to reach [`LEVEL_DATA_VERSION`].
+///
+/// Returns whether the data changed, so callers can save it again.
+///
+/// # Errors
+/// Returns an error if the data was saved by a newer revision.
+pub fn upgrade_level_data(data: &mut Value) -> io::Result<bool> {
+    let version = data
+        .get("data_version")
+        .and_then(Value::as_i64)
+        .and_then(|version| i32::try_from(version).ok())
+        .unwrap_or(0);
+    if version > LEVEL_DATA_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("level.json has data version {version}, newer than {LEVEL_DATA_VERSION}"),
+        ));
+    }
+    if version == LEVEL_DATA_VERSION {
+        return Ok(false);
+    }
+    for upgrade in LEVEL_DATA_UPGRADES
+        .iter()
+        .filter(|upgrade| upgrade.from >= version)
+    {
+        (upgrade.upgrade)(data);
+    }
+    if let Some(object) = data.as_object_mut() {
+        object.insert("data_version".to_owned(), Value::from(LEVEL_DATA_VERSION));
+    }
+    Ok(true)
+}
+
 /// Persistent level data that gets saved to disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LevelData {
+    /// Version of the saved data, see [`LEVEL_DATA_VERSION`].
+    #[serde(default)]
+    pub data_version: i32,
     /// World seed for terrain generation.
     pub seed: i64,
     /// Total game time in ticks.
     pub game_time: i64,
     /// Time of day in ticks (0-24000).
     pub day_time: i64,
+    /// The world difficulty.
+    #[serde(default)]
+    pub difficulty: Difficulty,
     /// World spawn point.
     pub spawn: SpawnPoint,
     /// Weather state.
@@ -36,6 +103,15 @@ pub struct LevelData {
     pub game_rules_values: GameRuleValues,
     /// Whether the world has been initialized.
     pub initialized: bool,
+    /// Storage for `/data storage`. Only the overworld's copy is used.
+    #[serde(default)]
+    pub command_storage: CommandStorage,
+    /// Random sequences for `/random`. Only the overworld's copy is used.
+    #[serde(default)]
+    pub random_sequences: RandomSequences,
+    /// State of the dragon fight. Only the End's copy is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dragon_fight: Option<DragonFightData>,
 }
 
 /// Spawn point data.
@@ -49,6 +125,18 @@ pub struct SpawnPoint {
     pub z: i32,
     /// Spawn angle (yaw).
     pub angle: f32,
+    /// Spawn pitch.
+    #[serde(default)]
+    pub pitch: f32,
+    /// The dimension the spawn point is in.
+    ///
+    /// Only the overworld's level data holds the server-wide spawn, like vanilla.
+    #[serde(default = "overworld_key")]
+    pub dimension: Identifier,
+}
+
+fn overworld_key() -> Identifier {
+    OVERWORLD.key.clone()
 }
 
 impl Default for SpawnPoint {
@@ -58,6 +146,8 @@ impl Default for SpawnPoint {
             y: 64,
             z: 0,
             angle: 0.0,
+            pitch: 0.0,
+            dimension: overworld_key(),
         }
     }
 }
@@ -88,14 +178,19 @@ impl LevelData {
     #[must_use]
     pub fn new_with_seed(seed: i64) -> Self {
         Self {
+            data_version: LEVEL_DATA_VERSION,
             seed,
             game_time: 0,
             day_time: 0,
+            difficulty: Difficulty::default(),
             spawn: SpawnPoint::default(),
             weather: WeatherState::default(),
             game_rules: FxHashMap::default(),
             game_rules_values: GameRuleValues::new(&REGISTRY.game_rules),
             initialized: false,
+            command_storage: CommandStorage::default(),
+            random_sequences: RandomSequences::default(),
+            dragon_fight: None,
         }
     }
 
@@ -148,6 +243,7 @@ impl LevelDataManager {
     /// If `level.json` exists, it will be loaded (the provided seed is ignored).
     /// Otherwise, new data will be created with the provided seed.
     pub async fn new(world_dir: Option<impl AsRef<Path>>, seed: i64) -> io::Result<Self> {
+        let mut upgraded = false;
         let (data, path) = match &world_dir {
             Some(dir) => {
                 let path = dir.as_ref().join("level.json");
@@ -155,12 +251,15 @@ impl LevelDataManager {
                 let data = if path.exists() {
                     // Load existing level data (seed from file takes precedence)
                     let content = fs::read_to_string(&path).await?;
-                    let mut loaded: LevelData = serde_json::from_str(&content).map_err(|e| {
+                    let invalid = |e: serde_json::Error| {
                         io::Error::new(
                             io::ErrorKind::InvalidData,
                             format!("Invalid level.json: {e}"),
                         )
-                    })?;
+                    };
+                    let mut value: Value = serde_json::from_str(&content).map_err(invalid)?;
+                    upgraded = upgrade_level_data(&mut value)?;
+                    let mut loaded: LevelData = serde_json::from_value(value).map_err(invalid)?;
                     // Initialize runtime game rules from serialized values
                     loaded.load_game_rules();
                     loaded
@@ -176,7 +275,8 @@ impl LevelDataManager {
         Ok(Self {
             path,
             data,
-            dirty: false,
+            // Save upgraded data so the upgrades only run once
+            dirty: upgraded,
         })
     }
 
@@ -265,6 +365,37 @@ impl LevelDataManager {
         self.dirty = true;
     }
 
+    /// Gets the difficulty.
+    #[must_use]
+    pub const fn difficulty(&self) -> Difficulty {
+        self.data.difficulty
+    }
+
+    /// Sets the difficulty.
+    // TODO: Send `CChangeDifficulty` to players once the packet and `/difficulty` exist
+    pub const fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.data.difficulty = difficulty;
+        self.dirty = true;
+    }
+
+    /// Gets the command storage.
+    #[must_use]
+    pub const fn command_storage(&self) -> &CommandStorage {
+        &self.data.command_storage
+    }
+
+    /// Gets a mutable reference to the command storage and marks the data as dirty.
+    pub const fn command_storage_mut(&mut self) -> &mut CommandStorage {
+        self.dirty = true;
+        &mut self.data.command_storage
+    }
+
+    /// Gets a mutable reference to the random sequences and marks the data as dirty.
+    pub const fn random_sequences_mut(&mut self) -> &mut RandomSequences {
+        self.dirty = true;
+        &mut self.data.random_sequences
+    }
+
     /// Gets the clear weather time
     #[must_use]
     pub const fn clear_weather_time(&self) -> i32 {
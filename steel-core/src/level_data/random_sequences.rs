@@ -0,0 +1,164 @@
+//! Random sequences, the named random number generators used by `/random`.
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use steel_utils::Identifier;
+use steel_utils::random::Random;
+use steel_utils::random::xoroshiro::Xoroshiro;
+
+/// Named random number generators, mirroring vanilla's `RandomSequences`.
+///
+/// A sequence rolls the same numbers every time it's reset with the same seed.
+/// Vanilla saves these to `data/random_sequences.dat`. Steel keeps them in the
+/// overworld's `level.json` instead, with every sequence stored as its
+/// generator state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RandomSequences {
+    /// Salt mixed into the seed of new sequences.
+    salt: i32,
+    /// Whether new sequences mix in the world seed.
+    include_world_seed: bool,
+    /// Whether new sequences mix in their own id.
+    include_sequence_id: bool,
+    sequences: FxHashMap<Identifier, (u64, u64)>,
+}
+
+impl Default for RandomSequences {
+    fn default() -> Self {
+        Self {
+            salt: 0,
+            include_world_seed: true,
+            include_sequence_id: true,
+            sequences: FxHashMap::default(),
+        }
+    }
+}
+
+impl RandomSequences {
+    /// Draws a number between `min` and `max` (both inclusive) from the sequence
+    /// `id`, starting the sequence if it doesn't exist yet.
+    ///
+    /// Vanilla: `RandomSequences.get()` followed by `Mth.randomBetweenInclusive()`.
+    pub fn next_i32_between(
+        &mut self,
+        id: &Identifier,
+        world_seed: i64,
+        min: i32,
+        max: i32,
+    ) -> i32 {
+        let mut random = match self.sequences.get(id) {
+            Some(&(seed_lo, seed_hi)) => Xoroshiro::from_state(seed_lo, seed_hi),
+            None => create_sequence(
+                id,
+                world_seed,
+                self.salt,
+                self.include_world_seed,
+                self.include_sequence_id,
+            ),
+        };
+        let value = random.next_i32_between(min, max);
+        self.sequences.insert(id.clone(), random.state());
+        value
+    }
+
+    /// Restarts the sequence `id` from the given seed settings.
+    ///
+    /// Vanilla: `RandomSequences.reset()`.
+    pub fn reset(
+        &mut self,
+        id: Identifier,
+        world_seed: i64,
+        salt: i32,
+        include_world_seed: bool,
+        include_sequence_id: bool,
+    ) {
+        let random = create_sequence(
+            &id,
+            world_seed,
+            salt,
+            include_world_seed,
+            include_sequence_id,
+        );
+        self.sequences.insert(id, random.state());
+    }
+
+    /// Restarts the sequence `id` from the seed settings set by
+    /// [`Self::set_seed_defaults`].
+    ///
+    /// Vanilla: `RandomSequences.reset()` without seed settings.
+    pub fn reset_to_defaults(&mut self, id: Identifier, world_seed: i64) {
+        self.reset(
+            id,
+            world_seed,
+            self.salt,
+            self.include_world_seed,
+            self.include_sequence_id,
+        );
+    }
+
+    /// Removes every sequence, returning how many there were.
+    ///
+    /// Vanilla: `RandomSequences.clear()`.
+    pub fn clear(&mut self) -> usize {
+        let count = self.sequences.len();
+        self.sequences.clear();
+        count
+    }
+
+    /// Sets the seed settings that sequences start with when first used.
+    ///
+    /// Vanilla: `RandomSequences.setSeedDefaults()`.
+    pub const fn set_seed_defaults(
+        &mut self,
+        salt: i32,
+        include_world_seed: bool,
+        include_sequence_id: bool,
+    ) {
+        self.salt = salt;
+        self.include_world_seed = include_world_seed;
+        self.include_sequence_id = include_sequence_id;
+    }
+}
+
+/// Vanilla: `RandomSequences.createSequence()`.
+fn create_sequence(
+    id: &Identifier,
+    world_seed: i64,
+    salt: i32,
+    include_world_seed: bool,
+    include_sequence_id: bool,
+) -> Xoroshiro {
+    let seed = (if include_world_seed { world_seed } else { 0 }) ^ i64::from(salt);
+    let name = include_sequence_id.then(|| id.to_string());
+    Xoroshiro::for_sequence(seed as u64, name.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_to_defaults_uses_the_seed_defaults() {
+        let id = Identifier::vanilla_static("test");
+        let mut defaults = RandomSequences::default();
+        defaults.set_seed_defaults(7, false, false);
+        defaults.reset_to_defaults(id.clone(), 42);
+        let mut explicit = RandomSequences::default();
+        explicit.reset(id.clone(), 42, 7, false, false);
+
+        for _ in 0..4 {
+            assert_eq!(
+                defaults.next_i32_between(&id, 42, 0, 1000),
+                explicit.next_i32_between(&id, 42, 0, 1000)
+            );
+        }
+        let mut unchanged = RandomSequences::default();
+        unchanged.reset_to_defaults(id.clone(), 42);
+        assert_ne!(
+            unchanged.sequences.get(&id),
+            defaults.sequences.get(&id),
+            "the defaults change the sequence"
+        );
+    }
+}
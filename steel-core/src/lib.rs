@@ -11,13 +11,17 @@ pub mod chunk_saver;
 pub mod command;
 pub mod config;
 pub mod entity;
+pub mod event;
 pub mod fluid;
 pub mod inventory;
 pub mod level_data;
+pub mod map;
 pub mod physics;
 pub mod player;
+pub mod plugin;
 pub mod poi;
 pub(crate) mod portal;
+pub mod profiling;
 pub mod server;
 pub mod world;
 pub mod worldgen;
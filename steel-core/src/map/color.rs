@@ -0,0 +1,137 @@
+//! The colors blocks are drawn with on maps.
+
+use std::sync::LazyLock;
+
+use steel_registry::REGISTRY;
+use steel_utils::BlockStateId;
+
+use crate::behavior::map_colors::get_map_color;
+
+/// The base color of a block on a map, in vanilla's id order.
+///
+/// Vanilla equivalent: `MapColor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[expect(missing_docs, reason = "variants are named after vanilla's colors")]
+pub enum MapColor {
+    /// Not drawn; the renderer looks further down.
+    None = 0,
+    Grass,
+    Sand,
+    Wool,
+    Fire,
+    Ice,
+    Metal,
+    Plant,
+    Snow,
+    Clay,
+    Dirt,
+    Stone,
+    Water,
+    Wood,
+    Quartz,
+    ColorOrange,
+    ColorMagenta,
+    ColorLightBlue,
+    ColorYellow,
+    ColorLightGreen,
+    ColorPink,
+    ColorGray,
+    ColorLightGray,
+    ColorCyan,
+    ColorPurple,
+    ColorBlue,
+    ColorBrown,
+    ColorGreen,
+    ColorRed,
+    ColorBlack,
+    Gold,
+    Diamond,
+    Lapis,
+    Emerald,
+    Podzol,
+    Nether,
+    TerracottaWhite,
+    TerracottaOrange,
+    TerracottaMagenta,
+    TerracottaLightBlue,
+    TerracottaYellow,
+    TerracottaLightGreen,
+    TerracottaPink,
+    TerracottaGray,
+    TerracottaLightGray,
+    TerracottaCyan,
+    TerracottaPurple,
+    TerracottaBlue,
+    TerracottaBrown,
+    TerracottaGreen,
+    TerracottaRed,
+    TerracottaBlack,
+    CrimsonNylium,
+    CrimsonStem,
+    CrimsonHyphae,
+    WarpedNylium,
+    WarpedStem,
+    WarpedHyphae,
+    WarpedWartBlock,
+    Deepslate,
+    RawIron,
+    GlowLichen,
+}
+
+/// The shade a map color is drawn with, from the slope of the terrain.
+///
+/// Vanilla equivalent: `MapColor.Brightness`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Brightness {
+    /// Going down to the north.
+    Low = 0,
+    /// Flat.
+    Normal = 1,
+    /// Going up to the north.
+    High = 2,
+    /// Only used by map art, never by the renderer.
+    Lowest = 3,
+}
+
+/// The map color of every block state, indexed by state id.
+static STATE_COLORS: LazyLock<Box<[MapColor]>> = LazyLock::new(|| {
+    (0..REGISTRY.blocks.next_state_id)
+        .map(|id| {
+            let state = BlockStateId(id);
+            REGISTRY
+                .blocks
+                .by_state_id(state)
+                .map_or(MapColor::None, |block| get_map_color(block, state))
+        })
+        .collect()
+});
+
+impl MapColor {
+    /// Returns the color `state` is drawn with.
+    #[must_use]
+    pub fn of(state: BlockStateId) -> Self {
+        STATE_COLORS
+            .get(usize::from(state.0))
+            .copied()
+            .unwrap_or(Self::None)
+    }
+
+    /// The color and brightness as stored in map data and sent to clients.
+    #[must_use]
+    pub const fn packed_id(self, brightness: Brightness) -> u8 {
+        (self as u8) << 2 | brightness as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packed_id() {
+        assert_eq!(MapColor::None.packed_id(Brightness::Normal), 1);
+        assert_eq!(MapColor::Water.packed_id(Brightness::High), 50);
+        assert_eq!(MapColor::GlowLichen.packed_id(Brightness::Lowest), 247);
+    }
+}
@@ -0,0 +1,542 @@
+//! The saved state of one map: its area, colors and decorations.
+
+use std::collections::BTreeMap;
+
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_protocol::packets::game::{CMapItemData, MapDecoration, MapDecorationType, MapPatch};
+use steel_registry::vanilla_dimension_types;
+use steel_utils::Identifier;
+use uuid::Uuid;
+
+/// Maps are 128 by 128 pixels.
+pub const MAP_SIZE: usize = 128;
+/// The highest zoom level a map can be scaled to.
+pub const MAX_SCALE: u8 = 4;
+/// Half the map, in map units; decorations further out are off the map.
+const HALF_MAP_SIZE: f32 = 63.0;
+/// How far off the map, in map units, players are still drawn on its border.
+const MAX_UNLIMITED_DISTANCE: f32 = 320.0;
+
+/// A player carrying the map, and what it hasn't been sent yet.
+///
+/// Vanilla equivalent: `MapItemSavedData.HoldingPlayer`.
+#[derive(Debug)]
+struct HoldingPlayer {
+    uuid: Uuid,
+    /// The name their decoration is stored under.
+    name: String,
+    /// The server tick the player last carried the map.
+    last_seen: u64,
+    /// Which columns the renderer updates next; advanced every render.
+    step: i32,
+    dirty_data: bool,
+    min_dirty_x: usize,
+    min_dirty_y: usize,
+    max_dirty_x: usize,
+    max_dirty_y: usize,
+    dirty_decorations: bool,
+    tick: i32,
+}
+
+impl HoldingPlayer {
+    /// A new holder is sent the whole map.
+    const fn new(uuid: Uuid, name: String, tick: u64) -> Self {
+        Self {
+            uuid,
+            name,
+            last_seen: tick,
+            step: 0,
+            dirty_data: true,
+            min_dirty_x: 0,
+            min_dirty_y: 0,
+            max_dirty_x: MAP_SIZE - 1,
+            max_dirty_y: MAP_SIZE - 1,
+            dirty_decorations: true,
+            tick: 0,
+        }
+    }
+
+    fn mark_colors_dirty(&mut self, x: usize, y: usize) {
+        if self.dirty_data {
+            self.min_dirty_x = self.min_dirty_x.min(x);
+            self.min_dirty_y = self.min_dirty_y.min(y);
+            self.max_dirty_x = self.max_dirty_x.max(x);
+            self.max_dirty_y = self.max_dirty_y.max(y);
+        } else {
+            self.dirty_data = true;
+            self.min_dirty_x = x;
+            self.min_dirty_y = y;
+            self.max_dirty_x = x;
+            self.max_dirty_y = y;
+        }
+    }
+}
+
+/// The data behind a filled map item, shared by every copy of the map.
+///
+/// Vanilla equivalent: `MapItemSavedData`.
+#[derive(Debug)]
+pub struct MapItemSavedData {
+    /// The block x at the middle of the map.
+    pub center_x: i32,
+    /// The block z at the middle of the map.
+    pub center_z: i32,
+    /// The dimension the map shows.
+    pub dimension: Identifier,
+    /// The zoom level; each pixel covers `2^scale` blocks.
+    pub scale: u8,
+    /// Whether players carrying the map are drawn on it.
+    pub tracking_position: bool,
+    /// Whether players far outside the map are still drawn on its border.
+    pub unlimited_tracking: bool,
+    /// Whether the map no longer changes.
+    pub locked: bool,
+    /// The packed color of every pixel, row by row.
+    pub colors: Box<[u8]>,
+    /// The decorations, by the name of what they belong to.
+    decorations: BTreeMap<String, MapDecoration>,
+    carried_by: Vec<HoldingPlayer>,
+    /// Whether the map changed since it was last saved.
+    dirty: bool,
+}
+
+impl MapItemSavedData {
+    /// Creates an empty map covering the grid cell of its scale that `x`, `z` is in.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.createFresh`.
+    #[must_use]
+    pub fn create_fresh(
+        x: f64,
+        z: f64,
+        scale: u8,
+        tracking_position: bool,
+        unlimited_tracking: bool,
+        dimension: Identifier,
+    ) -> Self {
+        let scale = scale.min(MAX_SCALE);
+        let size = (MAP_SIZE << scale) as f64;
+        let cell_x = ((x + 64.0) / size).floor() as i32;
+        let cell_z = ((z + 64.0) / size).floor() as i32;
+        let size = size as i32;
+        Self::new(
+            cell_x * size + size / 2 - 64,
+            cell_z * size + size / 2 - 64,
+            scale,
+            tracking_position,
+            unlimited_tracking,
+            false,
+            dimension,
+        )
+    }
+
+    fn new(
+        center_x: i32,
+        center_z: i32,
+        scale: u8,
+        tracking_position: bool,
+        unlimited_tracking: bool,
+        locked: bool,
+        dimension: Identifier,
+    ) -> Self {
+        Self {
+            center_x,
+            center_z,
+            dimension,
+            scale,
+            tracking_position,
+            unlimited_tracking,
+            locked,
+            colors: vec![0; MAP_SIZE * MAP_SIZE].into_boxed_slice(),
+            decorations: BTreeMap::new(),
+            carried_by: Vec::new(),
+            dirty: true,
+        }
+    }
+
+    /// A copy zoomed out by one level, with nothing drawn yet.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.scaled`.
+    #[must_use]
+    pub fn scaled(&self) -> Self {
+        Self::create_fresh(
+            f64::from(self.center_x),
+            f64::from(self.center_z),
+            (self.scale + 1).min(MAX_SCALE),
+            self.tracking_position,
+            self.unlimited_tracking,
+            self.dimension.clone(),
+        )
+    }
+
+    /// A copy that no longer changes.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.locked`.
+    #[must_use]
+    pub fn locked(&self) -> Self {
+        let mut locked = Self::new(
+            self.center_x,
+            self.center_z,
+            self.scale,
+            self.tracking_position,
+            self.unlimited_tracking,
+            true,
+            self.dimension.clone(),
+        );
+        locked.colors.copy_from_slice(&self.colors);
+        locked
+    }
+
+    /// Whether the map changed since it was last saved.
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Marks the map as saved.
+    pub const fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Marks the map as needing to be saved again.
+    pub const fn set_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Sets the color of a pixel, returning whether it changed.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.updateColor`.
+    pub fn update_color(&mut self, x: usize, y: usize, color: u8) -> bool {
+        let pixel = &mut self.colors[x + y * MAP_SIZE];
+        if *pixel == color {
+            return false;
+        }
+        *pixel = color;
+        self.dirty = true;
+        for holder in &mut self.carried_by {
+            holder.mark_colors_dirty(x, y);
+        }
+        true
+    }
+
+    /// Records that the player `uuid` carries the map this tick, drawing them
+    /// on it if they are in its dimension. Players that stopped carrying it
+    /// are removed along with their decoration.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.tickCarriedBy`.
+    pub fn tick_carried_by(
+        &mut self,
+        uuid: Uuid,
+        name: &str,
+        tick: u64,
+        position: Option<(f64, f64, f32)>,
+        day_time: i64,
+    ) {
+        match self
+            .carried_by
+            .iter_mut()
+            .find(|holder| holder.uuid == uuid)
+        {
+            Some(holder) => holder.last_seen = tick,
+            None => self
+                .carried_by
+                .push(HoldingPlayer::new(uuid, name.to_owned(), tick)),
+        }
+
+        // Players tick one after another, so a holder seen last tick is still holding
+        let gone: Vec<String> = self
+            .carried_by
+            .iter()
+            .filter(|holder| holder.last_seen + 1 < tick)
+            .map(|holder| holder.name.clone())
+            .collect();
+        if !gone.is_empty() {
+            self.carried_by
+                .retain(|holder| holder.last_seen + 1 >= tick);
+            for name in gone {
+                self.remove_decoration(&name);
+            }
+        }
+
+        match position {
+            Some((x, z, y_rot)) if self.tracking_position => {
+                self.add_player_decoration(name, x, z, y_rot, day_time);
+            }
+            _ => self.remove_decoration(name),
+        }
+    }
+
+    /// Removes the decoration of whoever stopped carrying the map.
+    pub fn remove_decoration(&mut self, name: &str) {
+        if self.decorations.remove(name).is_some() {
+            self.set_decorations_dirty();
+        }
+    }
+
+    /// Places the marker of a player carrying the map.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.addDecoration` with the player type.
+    fn add_player_decoration(&mut self, name: &str, x: f64, z: f64, y_rot: f32, day_time: i64) {
+        let scale = f64::from(1 << self.scale);
+        let x_delta = ((x - f64::from(self.center_x)) / scale) as f32;
+        let y_delta = ((z - f64::from(self.center_z)) / scale) as f32;
+
+        let (decoration_type, rotation) = if Self::is_inside_map(x_delta, y_delta) {
+            (MapDecorationType::Player, self.rotation(y_rot, day_time))
+        } else if x_delta.abs() < MAX_UNLIMITED_DISTANCE && y_delta.abs() < MAX_UNLIMITED_DISTANCE {
+            (MapDecorationType::PlayerOffMap, 0)
+        } else if self.unlimited_tracking {
+            (MapDecorationType::PlayerOffLimits, 0)
+        } else {
+            self.remove_decoration(name);
+            return;
+        };
+
+        let decoration = MapDecoration {
+            decoration_type,
+            x: Self::clamp_map_coordinate(x_delta),
+            y: Self::clamp_map_coordinate(y_delta),
+            rotation,
+            name: None,
+        };
+        if self.decorations.get(name) != Some(&decoration) {
+            self.decorations.insert(name.to_owned(), decoration);
+            self.set_decorations_dirty();
+        }
+    }
+
+    /// Which way a marker points, in sixteenths of a turn. Markers spin in the nether.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.calculateRotation`.
+    fn rotation(&self, y_rot: f32, day_time: i64) -> u8 {
+        if self.dimension == vanilla_dimension_types::THE_NETHER.key {
+            let s = (day_time / 10) as i32;
+            return (s
+                .wrapping_mul(s)
+                .wrapping_mul(34_187_121)
+                .wrapping_add(s.wrapping_mul(121))
+                >> 15
+                & 15) as u8;
+        }
+        let adjusted = if y_rot < 0.0 {
+            y_rot - 8.0
+        } else {
+            y_rot + 8.0
+        };
+        (f64::from(adjusted) * 16.0 / 360.0) as i32 as u8
+    }
+
+    fn is_inside_map(x_delta: f32, y_delta: f32) -> bool {
+        (-HALF_MAP_SIZE..=HALF_MAP_SIZE).contains(&x_delta)
+            && (-HALF_MAP_SIZE..=HALF_MAP_SIZE).contains(&y_delta)
+    }
+
+    /// Converts blocks from the center, in map pixels, to decoration units.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.clampMapCoordinate`.
+    fn clamp_map_coordinate(delta: f32) -> i8 {
+        if delta <= -HALF_MAP_SIZE {
+            -128
+        } else if delta >= HALF_MAP_SIZE {
+            127
+        } else {
+            (delta * 2.0 + 0.5) as i8
+        }
+    }
+
+    fn set_decorations_dirty(&mut self) {
+        self.dirty = true;
+        for holder in &mut self.carried_by {
+            holder.dirty_decorations = true;
+        }
+    }
+
+    /// Advances the render step of the player `uuid`, returning the new step.
+    pub(crate) fn next_step(&mut self, uuid: Uuid) -> i32 {
+        self.carried_by
+            .iter_mut()
+            .find(|holder| holder.uuid == uuid)
+            .map_or(0, |holder| {
+                holder.step += 1;
+                holder.step
+            })
+    }
+
+    /// The packet with what the player `uuid` hasn't been sent yet, if anything.
+    /// Decorations are sent at most every 5 ticks.
+    ///
+    /// Vanilla equivalent: `MapItemSavedData.HoldingPlayer.nextUpdatePacket`.
+    #[must_use]
+    pub fn get_update_packet(&mut self, map_id: i32, uuid: Uuid) -> Option<CMapItemData> {
+        let holder = self
+            .carried_by
+            .iter_mut()
+            .find(|holder| holder.uuid == uuid)?;
+
+        let color_patch = holder.dirty_data.then(|| {
+            holder.dirty_data = false;
+            let width = holder.max_dirty_x + 1 - holder.min_dirty_x;
+            let height = holder.max_dirty_y + 1 - holder.min_dirty_y;
+            let mut colors = Vec::with_capacity(width * height);
+            for y in holder.min_dirty_y..=holder.max_dirty_y {
+                let row = y * MAP_SIZE;
+                colors.extend_from_slice(
+                    &self.colors[row + holder.min_dirty_x..=row + holder.max_dirty_x],
+                );
+            }
+            MapPatch {
+                start_x: holder.min_dirty_x as u8,
+                start_y: holder.min_dirty_y as u8,
+                width: width as u8,
+                height: height as u8,
+                colors,
+            }
+        });
+
+        let mut decorations = None;
+        if holder.dirty_decorations {
+            holder.tick += 1;
+            if (holder.tick - 1) % 5 == 0 {
+                holder.dirty_decorations = false;
+                decorations = Some(self.decorations.values().cloned().collect());
+            }
+        }
+
+        if color_patch.is_none() && decorations.is_none() {
+            return None;
+        }
+        Some(CMapItemData {
+            map_id,
+            scale: self.scale as i8,
+            locked: self.locked,
+            decorations,
+            color_patch,
+        })
+    }
+
+    /// Serializes the map to the vanilla `data` compound of `map_<id>.dat`.
+    #[must_use]
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut compound = NbtCompound::new();
+        compound.insert("dimension", self.dimension.to_string());
+        compound.insert("xCenter", self.center_x);
+        compound.insert("zCenter", self.center_z);
+        compound.insert("scale", self.scale as i8);
+        compound.insert("trackingPosition", i8::from(self.tracking_position));
+        compound.insert("unlimitedTracking", i8::from(self.unlimited_tracking));
+        compound.insert("locked", i8::from(self.locked));
+        compound.insert("colors", NbtTag::ByteArray(self.colors.to_vec()));
+        // TODO: Save banner and frame markers once maps track them
+        compound
+    }
+
+    /// Deserializes the map from the vanilla `data` compound.
+    ///
+    /// Returns `None` if the dimension is missing or invalid.
+    #[must_use]
+    pub fn from_nbt(nbt: &NbtCompoundView<'_, '_>) -> Option<Self> {
+        let dimension = nbt.string("dimension")?.to_str().parse().ok()?;
+        let mut data = Self::new(
+            nbt.int("xCenter").unwrap_or(0),
+            nbt.int("zCenter").unwrap_or(0),
+            (nbt.byte("scale").unwrap_or(0).max(0) as u8).min(MAX_SCALE),
+            nbt.byte("trackingPosition") != Some(0),
+            nbt.byte("unlimitedTracking").is_some_and(|b| b != 0),
+            nbt.byte("locked").is_some_and(|b| b != 0),
+            dimension,
+        );
+        if let Some(colors) = nbt.byte_array("colors")
+            && colors.len() == data.colors.len()
+        {
+            data.colors.copy_from_slice(colors);
+        }
+        data.dirty = false;
+        Some(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use simdnbt::borrow::read_compound;
+    use steel_registry::vanilla_dimension_types::OVERWORLD;
+
+    use super::*;
+
+    fn fresh(x: f64, z: f64, scale: u8) -> MapItemSavedData {
+        MapItemSavedData::create_fresh(x, z, scale, true, false, OVERWORLD.key.clone())
+    }
+
+    #[test]
+    fn test_create_fresh_snaps_to_the_grid() {
+        let map = fresh(0.0, 0.0, 0);
+        assert_eq!((map.center_x, map.center_z), (0, 0));
+        let map = fresh(70.0, -70.0, 0);
+        assert_eq!((map.center_x, map.center_z), (128, -128));
+        let map = fresh(70.0, -70.0, 1);
+        assert_eq!((map.center_x, map.center_z), (64, -192));
+    }
+
+    #[test]
+    fn test_new_holder_gets_the_whole_map_then_only_changes() {
+        let mut map = fresh(0.0, 0.0, 0);
+        let uuid = Uuid::from_u64_pair(1, 2);
+        map.tick_carried_by(uuid, "Holder", 1, None, 0);
+
+        let packet = map
+            .get_update_packet(7, uuid)
+            .expect("new holders get the map");
+        let patch = packet.color_patch.expect("the whole map is dirty");
+        assert_eq!((patch.width, patch.height), (128, 128));
+        assert!(map.get_update_packet(7, uuid).is_none());
+
+        assert!(map.update_color(3, 5, 44));
+        assert!(!map.update_color(3, 5, 44));
+        map.update_color(6, 4, 45);
+        let patch = map
+            .get_update_packet(7, uuid)
+            .and_then(|packet| packet.color_patch)
+            .expect("the changed pixels are sent");
+        assert_eq!((patch.start_x, patch.start_y), (3, 4));
+        assert_eq!((patch.width, patch.height), (4, 2));
+        assert_eq!(patch.colors[4], 44);
+        assert_eq!(patch.colors[3], 45);
+    }
+
+    #[test]
+    fn test_player_decoration_clamps_to_the_border() {
+        let mut map = fresh(0.0, 0.0, 0);
+        let uuid = Uuid::from_u64_pair(1, 2);
+        map.tick_carried_by(uuid, "Holder", 1, Some((10.0, -20.0, 90.0)), 0);
+        let inside = &map.decorations["Holder"];
+        assert_eq!(inside.decoration_type, MapDecorationType::Player);
+        assert_eq!((inside.x, inside.y, inside.rotation), (20, -39, 4));
+
+        map.tick_carried_by(uuid, "Holder", 2, Some((200.0, 0.0, 0.0)), 0);
+        let outside = &map.decorations["Holder"];
+        assert_eq!(outside.decoration_type, MapDecorationType::PlayerOffMap);
+        assert_eq!(outside.x, 127);
+
+        map.tick_carried_by(uuid, "Holder", 3, Some((1000.0, 0.0, 0.0)), 0);
+        assert!(!map.decorations.contains_key("Holder"));
+    }
+
+    #[test]
+    fn test_nbt_round_trip() {
+        let mut map = fresh(300.0, 20.0, 2);
+        map.update_color(1, 1, 9);
+        let locked = map.locked();
+
+        let mut bytes = Vec::new();
+        locked.to_nbt().write(&mut bytes);
+        let nbt = read_compound(&mut Cursor::new(&bytes)).expect("the map NBT is valid");
+        let read = MapItemSavedData::from_nbt(&(&nbt).into()).expect("the map reads back");
+
+        assert_eq!((read.center_x, read.center_z), (map.center_x, map.center_z));
+        assert_eq!(read.scale, 2);
+        assert!(read.locked);
+        assert_eq!(read.colors[1 + MAP_SIZE], 9);
+        assert_eq!(map.scaled().scale, 3);
+    }
+}
@@ -0,0 +1,15 @@
+//! Filled maps: the world drawn from above around the players holding them.
+//!
+//! Every filled map item points at a [`MapItemSavedData`] by its `map_id`
+//! component. The data is rendered while a player holds the map and sent to
+//! the players carrying it as [`CMapItemData`](steel_protocol::packets::game::CMapItemData)
+//! patches.
+
+mod color;
+mod data;
+mod render;
+mod storage;
+
+pub use color::{Brightness, MapColor};
+pub use data::{MAP_SIZE, MAX_SCALE, MapItemSavedData};
+pub use storage::{MapStorage, SharedMap};
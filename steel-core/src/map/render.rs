@@ -0,0 +1,244 @@
+//! Drawing the world onto maps around the players holding them.
+
+use glam::DVec3;
+use steel_registry::blocks::BlockRef;
+use steel_registry::fluid::is_lava_fluid;
+use steel_registry::{REGISTRY, vanilla_blocks};
+use steel_utils::{BlockPos, ChunkPos};
+use uuid::Uuid;
+
+use super::{Brightness, MAP_SIZE, MapColor, MapItemSavedData};
+use crate::behavior::BlockStateBehaviorExt;
+use crate::chunk::chunk_access::ChunkAccess;
+use crate::chunk::heightmap::HeightmapType;
+use crate::world::World;
+
+/// The colors of the blocks under one pixel, counted in the order they were found.
+///
+/// Vanilla equivalent: the `LinkedHashMultiset` in `MapItem.update`.
+#[derive(Default)]
+struct ColorCount(Vec<(MapColor, u32)>);
+
+impl ColorCount {
+    fn add(&mut self, color: MapColor, count: u32) {
+        match self.0.iter_mut().find(|(counted, _)| *counted == color) {
+            Some((_, total)) => *total += count,
+            None => self.0.push((color, count)),
+        }
+    }
+
+    /// The most common color; ties go to the one found first.
+    fn most_common(&self) -> MapColor {
+        self.0
+            .iter()
+            .fold(
+                None,
+                |best: Option<(MapColor, u32)>, &(color, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((color, count)),
+                },
+            )
+            .map_or(MapColor::None, |(color, _)| color)
+    }
+}
+
+/// What one pixel covers, sampled from the world.
+struct Sample {
+    color: MapColor,
+    average_height: f64,
+    water_depth: i32,
+}
+
+impl MapItemSavedData {
+    /// Redraws a slice of the map around the player `holder` at `position`.
+    ///
+    /// Each call redraws every 16th column within the holder's view radius, so
+    /// the whole area is refreshed every 16 ticks. A column that changed also
+    /// redraws the next one.
+    ///
+    /// Vanilla equivalent: `MapItem.update`.
+    pub fn update(&mut self, world: &World, holder: Uuid, position: DVec3) {
+        if world.dimension.key != self.dimension {
+            return;
+        }
+        let scale = 1 << self.scale;
+        let center_x = self.center_x;
+        let center_z = self.center_z;
+        let player_x = (position.x - f64::from(center_x)).floor() as i32 / scale + 64;
+        let player_y = (position.z - f64::from(center_z)).floor() as i32 / scale + 64;
+        let mut radius = 128 / scale;
+        if world.dimension.has_ceiling {
+            radius /= 2;
+        }
+        let step = self.next_step(holder);
+
+        let mut found_changed_pixels = false;
+        for img_x in player_x - radius + 1..player_x + radius {
+            if (img_x & 15) != (step & 15) && !found_changed_pixels {
+                continue;
+            }
+            found_changed_pixels = false;
+            let mut previous_height = 0.0;
+            for img_y in player_y - radius - 1..player_y + radius {
+                if !(0..MAP_SIZE as i32).contains(&img_x) || !(-1..MAP_SIZE as i32).contains(&img_y)
+                {
+                    continue;
+                }
+                let distance = (img_x - player_x).pow(2) + (img_y - player_y).pow(2);
+                let dither_black = distance > (radius - 2) * (radius - 2);
+                let world_x = (center_x / scale + img_x - 64) * scale;
+                let world_z = (center_z / scale + img_y - 64) * scale;
+
+                let chunk_pos = ChunkPos::new(world_x >> 4, world_z >> 4);
+                let Some(sample) = world.chunk_map.with_full_chunk(chunk_pos, |chunk| {
+                    Self::sample(world, chunk, world_x, world_z, scale)
+                }) else {
+                    continue;
+                };
+
+                let checker = f64::from((img_x + img_y) & 1);
+                let brightness = if sample.color == MapColor::Water {
+                    let diff = f64::from(sample.water_depth) * 0.1 + checker * 0.2;
+                    if diff < 0.5 {
+                        Brightness::High
+                    } else if diff > 0.9 {
+                        Brightness::Low
+                    } else {
+                        Brightness::Normal
+                    }
+                } else {
+                    let diff = (sample.average_height - previous_height) * 4.0
+                        / f64::from(scale + 4)
+                        + (checker - 0.5) * 0.4;
+                    if diff > 0.6 {
+                        Brightness::High
+                    } else if diff < -0.6 {
+                        Brightness::Low
+                    } else {
+                        Brightness::Normal
+                    }
+                };
+                previous_height = sample.average_height;
+
+                if img_y >= 0
+                    && distance < radius * radius
+                    && (!dither_black || (img_x + img_y) & 1 != 0)
+                {
+                    found_changed_pixels |= self.update_color(
+                        img_x as usize,
+                        img_y as usize,
+                        sample.color.packed_id(brightness),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Finds the color, height and water depth of the `scale` by `scale`
+    /// blocks under one pixel.
+    fn sample(
+        world: &World,
+        chunk: &ChunkAccess,
+        world_x: i32,
+        world_z: i32,
+        scale: i32,
+    ) -> Sample {
+        let mut colors = ColorCount::default();
+        let mut water_depth = 0;
+        let mut average_height = 0.0;
+
+        if world.dimension.has_ceiling {
+            // Roofed dimensions get a noisy dirt and stone pattern instead
+            let mut ss = world_x.wrapping_add(world_z.wrapping_mul(231_871));
+            ss = ss
+                .wrapping_mul(ss)
+                .wrapping_mul(31_287_121)
+                .wrapping_add(ss.wrapping_mul(11));
+            if (ss >> 20) & 1 == 0 {
+                colors.add(Self::block_color(vanilla_blocks::DIRT), 10);
+            } else {
+                colors.add(Self::block_color(vanilla_blocks::STONE), 100);
+            }
+            average_height = 100.0;
+        } else {
+            let min_y = world.get_min_y();
+            let Some(full) = chunk.as_full() else {
+                return Sample {
+                    color: MapColor::None,
+                    average_height,
+                    water_depth,
+                };
+            };
+            for dx in 0..scale {
+                for dz in 0..scale {
+                    let column = BlockPos::new(world_x + dx, 0, world_z + dz);
+                    let mut y = full.get_height(HeightmapType::WorldSurface, column);
+                    let mut color = Self::block_color(vanilla_blocks::BEDROCK);
+                    if y > min_y {
+                        let mut state;
+                        loop {
+                            y -= 1;
+                            state = chunk.get_block_state(BlockPos::new(column.x(), y, column.z()));
+                            if MapColor::of(state) != MapColor::None || y <= min_y {
+                                break;
+                            }
+                        }
+                        color = MapColor::of(state);
+                        let fluid = state.get_fluid_state();
+                        if y > min_y && !fluid.is_empty() {
+                            let mut below_y = y - 1;
+                            loop {
+                                let below = chunk.get_block_state(BlockPos::new(
+                                    column.x(),
+                                    below_y,
+                                    column.z(),
+                                ));
+                                below_y -= 1;
+                                water_depth += 1;
+                                if below_y <= min_y || below.get_fluid_state().is_empty() {
+                                    break;
+                                }
+                            }
+                            // TODO: Keep the block when its top face is sturdy, like vanilla's
+                            // getCorrectStateForFluidBlock
+                            color = if is_lava_fluid(fluid.fluid_id) {
+                                MapColor::Fire
+                            } else {
+                                MapColor::Water
+                            };
+                        }
+                    }
+                    // TODO: Check for banners once maps track banner markers
+                    average_height += f64::from(y) / f64::from(scale * scale);
+                    colors.add(color, 1);
+                }
+            }
+        }
+
+        Sample {
+            color: colors.most_common(),
+            average_height,
+            water_depth: water_depth / (scale * scale),
+        }
+    }
+
+    fn block_color(block: BlockRef) -> MapColor {
+        MapColor::of(REGISTRY.blocks.get_base_state_id(block))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_most_common_color_keeps_the_first_on_ties() {
+        let mut colors = ColorCount::default();
+        colors.add(MapColor::Stone, 1);
+        colors.add(MapColor::Grass, 1);
+        assert_eq!(colors.most_common(), MapColor::Stone);
+        colors.add(MapColor::Grass, 1);
+        assert_eq!(colors.most_common(), MapColor::Grass);
+        assert_eq!(ColorCount::default().most_common(), MapColor::None);
+    }
+}
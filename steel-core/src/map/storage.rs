@@ -0,0 +1,237 @@
+//! Loading and saving map data, and handing out map ids.
+//!
+//! Like vanilla, each map is stored in `data/map_<id>.dat` of the world
+//! folder as gzip-compressed NBT, and the last used id in `data/idcounts.dat`.
+
+use std::fs;
+use std::io::{self, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use rustc_hash::FxHashMap;
+use simdnbt::borrow::{NbtCompound as NbtCompoundView, read_compound};
+use simdnbt::owned::NbtCompound;
+use steel_utils::locks::SyncMutex;
+
+use super::MapItemSavedData;
+
+/// A map loaded from disk or created this session.
+pub type SharedMap = Arc<SyncMutex<MapItemSavedData>>;
+
+/// All maps of the server, loaded on first use.
+///
+/// Vanilla equivalent: the map part of `DimensionDataStorage`.
+pub struct MapStorage {
+    /// The `data` directory, or `None` when worlds aren't saved.
+    dir: Option<PathBuf>,
+    /// Maps by id. `None` caches that a map doesn't exist.
+    maps: SyncMutex<FxHashMap<i32, Option<SharedMap>>>,
+    /// The last id handed out, `-1` before the first map.
+    last_id: SyncMutex<i32>,
+}
+
+impl MapStorage {
+    /// Opens the map storage in `dir`, reading the last used map id.
+    #[must_use]
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        let last_id = dir
+            .as_deref()
+            .and_then(|dir| match Self::read_nbt(&dir.join("idcounts.dat")) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    log::warn!("Failed to read map ids: {e}");
+                    None
+                }
+            })
+            .and_then(|bytes| {
+                let nbt = read_compound(&mut Cursor::new(&bytes)).ok()?;
+                let nbt: NbtCompoundView<'_, '_> = (&nbt).into();
+                nbt.compound("data")?.int("map")
+            })
+            .unwrap_or(-1);
+        Self {
+            dir,
+            maps: SyncMutex::new(FxHashMap::default()),
+            last_id: SyncMutex::new(last_id),
+        }
+    }
+
+    /// Returns the map with `id`, loading it from disk the first time.
+    #[must_use]
+    pub fn get(&self, id: i32) -> Option<SharedMap> {
+        if let Some(map) = self.maps.lock().get(&id) {
+            return map.clone();
+        }
+        let map = self.load(id).map(|data| Arc::new(SyncMutex::new(data)));
+        self.maps.lock().entry(id).or_insert(map).clone()
+    }
+
+    /// Stores a new map and returns its id.
+    ///
+    /// Vanilla equivalent: `ServerLevel.getFreeMapId` followed by `setMapData`.
+    pub fn create(&self, data: MapItemSavedData) -> i32 {
+        let id = {
+            let mut last_id = self.last_id.lock();
+            *last_id += 1;
+            *last_id
+        };
+        self.maps
+            .lock()
+            .insert(id, Some(Arc::new(SyncMutex::new(data))));
+        id
+    }
+
+    /// Creates a copy of map `id` zoomed out by one level, returning the new id.
+    ///
+    /// Vanilla equivalent: `MapItem.scaleMap`.
+    #[must_use]
+    pub fn scaled_copy(&self, id: i32) -> Option<i32> {
+        let scaled = self.get(id)?.lock().scaled();
+        Some(self.create(scaled))
+    }
+
+    /// Creates a locked copy of map `id`, returning the new id.
+    ///
+    /// Vanilla equivalent: `MapItem.lockMap`.
+    #[must_use]
+    pub fn locked_copy(&self, id: i32) -> Option<i32> {
+        let locked = self.get(id)?.lock().locked();
+        Some(self.create(locked))
+    }
+
+    /// Writes every map that changed since it was last saved, and the last used id.
+    ///
+    /// Returns the number of maps written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error; the other maps are still written.
+    pub fn save_all(&self) -> io::Result<usize> {
+        let Some(dir) = &self.dir else {
+            return Ok(0);
+        };
+        fs::create_dir_all(dir)?;
+
+        let maps: Vec<(i32, SharedMap)> = self
+            .maps
+            .lock()
+            .iter()
+            .filter_map(|(id, map)| Some((*id, map.clone()?)))
+            .collect();
+        let mut written = 0;
+        let mut first_error = None;
+        for (id, map) in maps {
+            let data = {
+                let mut map = map.lock();
+                if !map.is_dirty() {
+                    continue;
+                }
+                map.clear_dirty();
+                map.to_nbt()
+            };
+            match Self::write_nbt(&dir.join(format!("map_{id}.dat")), data) {
+                Ok(()) => written += 1,
+                Err(e) => {
+                    log::error!("Failed to save map {id}: {e}");
+                    map.lock().set_dirty();
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        let mut ids = NbtCompound::new();
+        ids.insert("map", *self.last_id.lock());
+        if let Err(e) = Self::write_nbt(&dir.join("idcounts.dat"), ids) {
+            first_error.get_or_insert(e);
+        }
+        first_error.map_or(Ok(written), Err)
+    }
+
+    fn load(&self, id: i32) -> Option<MapItemSavedData> {
+        let path = self.dir.as_ref()?.join(format!("map_{id}.dat"));
+        let bytes = match Self::read_nbt(&path) {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                log::error!("Failed to read map {id}: {e}");
+                return None;
+            }
+        };
+        let data = read_compound(&mut Cursor::new(&bytes))
+            .ok()
+            .and_then(|nbt| {
+                let nbt: NbtCompoundView<'_, '_> = (&nbt).into();
+                MapItemSavedData::from_nbt(&nbt.compound("data")?)
+            });
+        if data.is_none() {
+            log::error!("Map {id} in {} is invalid", path.display());
+        }
+        data
+    }
+
+    /// Reads and decompresses a file, or `None` if it doesn't exist.
+    fn read_nbt(path: &Path) -> io::Result<Option<Vec<u8>>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut bytes = Vec::new();
+        GzDecoder::new(fs::read(path)?.as_slice()).read_to_end(&mut bytes)?;
+        Ok(Some(bytes))
+    }
+
+    /// Writes `data` in the vanilla saved data layout, replacing the file atomically.
+    fn write_nbt(path: &Path, data: NbtCompound) -> io::Result<()> {
+        let mut root = NbtCompound::new();
+        root.insert("data", data);
+        let mut bytes = Vec::new();
+        root.write(&mut bytes);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bytes)?;
+        let temp = path.with_extension("dat.tmp");
+        fs::write(&temp, encoder.finish()?)?;
+        fs::rename(temp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use steel_registry::vanilla_dimension_types::OVERWORLD;
+
+    use super::*;
+
+    #[test]
+    fn test_maps_survive_a_restart() {
+        let dir = env::temp_dir().join(format!("steel-maps-{}", process::id()));
+        let storage = MapStorage::new(Some(dir.clone()));
+        let map = MapItemSavedData::create_fresh(0.0, 0.0, 1, true, false, OVERWORLD.key.clone());
+        let id = storage.create(map);
+        assert_eq!(id, 0);
+        storage
+            .get(id)
+            .expect("the map was created")
+            .lock()
+            .update_color(2, 3, 17);
+        assert_eq!(storage.save_all().expect("maps are writable"), 1);
+        assert_eq!(storage.save_all().expect("maps are writable"), 0);
+
+        let reopened = MapStorage::new(Some(dir.clone()));
+        let map = reopened.get(id).expect("the map was saved");
+        assert_eq!(map.lock().colors[2 + 3 * 128], 17);
+        assert!(reopened.get(id + 1).is_none());
+        assert_eq!(
+            reopened.create(MapItemSavedData::create_fresh(
+                0.0,
+                0.0,
+                0,
+                true,
+                false,
+                OVERWORLD.key.clone()
+            )),
+            1
+        );
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -27,6 +27,8 @@ pub enum MoverType {
     ShulkerBox,
     /// Movement from shulker entity teleportation.
     Shulker,
+    /// Movement sent by a client, e.g. for the vehicle it controls.
+    Player,
 }
 
 /// Result of a movement operation.
@@ -99,7 +101,9 @@ pub fn move_entity(
     let aabb = state.bounding_box;
 
     // Apply sneak-edge prevention if crouching and on ground
-    let movement = if state.is_crouching && state.on_ground && mover_type == MoverType::SelfMovement
+    let movement = if state.is_crouching
+        && state.on_ground
+        && matches!(mover_type, MoverType::SelfMovement | MoverType::Player)
     {
         apply_sneak_edge_prevention(state, delta, &aabb, world)
     } else {
@@ -291,8 +295,8 @@ fn should_try_step_up(
     collision_result: &MoveResult,
     mover_type: MoverType,
 ) -> bool {
-    // Only try step-up for self-movement
-    if mover_type != MoverType::SelfMovement {
+    // Only try step-up for self and player movement
+    if !matches!(mover_type, MoverType::SelfMovement | MoverType::Player) {
         return false;
     }
 
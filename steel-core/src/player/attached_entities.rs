@@ -0,0 +1,196 @@
+//! Entities that leave and come back together with their player.
+//!
+//! When a player disconnects, vanilla saves the vehicle they ride (`RootVehicle`) into
+//! the player data and removes it from the world. It is spawned again when the player
+//! logs back in.
+
+use std::sync::Arc;
+
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_registry::REGISTRY;
+use steel_utils::{ChunkPos, Identifier, UuidExt};
+use uuid::Uuid;
+
+use crate::entity::{
+    ENTITIES, Entity, RemovalReason, SharedEntity, riding, save_entity, saved_entity_position,
+    spawn_saved_entity,
+};
+use crate::world::World;
+
+use super::Player;
+
+/// The vehicle a player was riding when they left.
+///
+/// NBT tag: `RootVehicle` (Compound)
+#[derive(Debug, Clone)]
+pub struct SavedVehicle {
+    /// The UUID of the entity the player sits on, which may be a passenger of `entity`.
+    /// NBT tag: `Attach` (`IntArray`)
+    pub attach: Uuid,
+    /// The lowest vehicle with all its passengers.
+    /// NBT tag: `Entity` (Compound)
+    pub entity: NbtCompound,
+}
+
+impl SavedVehicle {
+    /// Serializes the vehicle to an NBT compound.
+    #[must_use]
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.insert(
+            "Attach",
+            NbtTag::IntArray(self.attach.to_int_array().to_vec()),
+        );
+        nbt.insert("Entity", NbtTag::Compound(self.entity.clone()));
+        nbt
+    }
+
+    /// Deserializes a vehicle saved by [`Self::to_nbt`].
+    #[must_use]
+    pub fn from_nbt(nbt: &NbtCompoundView<'_, '_>) -> Option<Self> {
+        Some(Self {
+            attach: Uuid::from_int_array(&nbt.int_array("Attach")?)?,
+            entity: nbt.compound("Entity")?.to_owned(),
+        })
+    }
+}
+
+/// Tracks the entities saved with a player.
+#[derive(Default)]
+pub struct AttachedEntities {
+    /// Saved vehicle waiting for its chunk to load.
+    pending_vehicle: Option<SavedVehicle>,
+}
+
+impl AttachedEntities {
+    /// Queues the saved vehicle to be spawned once its chunk is loaded.
+    pub fn set_pending(&mut self, vehicle: Option<SavedVehicle>) {
+        self.pending_vehicle = vehicle;
+    }
+
+    /// Returns the vehicle that hasn't been spawned again yet, if any.
+    #[must_use]
+    pub const fn pending_vehicle(&self) -> Option<&SavedVehicle> {
+        self.pending_vehicle.as_ref()
+    }
+
+    /// Spawns the saved vehicle once its chunk has loaded.
+    ///
+    /// The player mounts the vehicle again. A vehicle that can't be loaded yet stays
+    /// pending, so it is saved again instead of being lost.
+    ///
+    /// Vanilla: `ServerPlayer.loadAndSpawnParentVehicle()`, which runs on login because
+    /// vanilla loads the chunks right away.
+    pub fn restore(&mut self, player: &Arc<Player>) {
+        if let Some(vehicle) = self.pending_vehicle.take() {
+            if is_ready(&player.world, &vehicle.entity) {
+                spawn_vehicle(player, &vehicle);
+            } else {
+                self.pending_vehicle = Some(vehicle);
+            }
+        }
+    }
+}
+
+/// Saves the vehicle the player rides, if they are its only player passenger.
+///
+/// Vanilla: the `RootVehicle` part of `ServerPlayer.addAdditionalSaveData()`.
+#[must_use]
+pub fn save_root_vehicle(player: &Player) -> Option<SavedVehicle> {
+    let vehicle = player.vehicle()?;
+    let root = riding::root_vehicle(player)?;
+    if !has_exactly_one_player_passenger(&root) {
+        return None;
+    }
+    Some(SavedVehicle {
+        attach: vehicle.uuid(),
+        entity: save_entity(root.as_ref())?,
+    })
+}
+
+/// Removes the vehicle the player rides from the world, after it was saved.
+///
+/// The vehicle is only removed if the player is the only one riding it.
+///
+/// Vanilla: the vehicle part of `PlayerList.remove()`.
+pub fn remove_root_vehicle(player: &Player) {
+    if let Some(root) = riding::root_vehicle(player)
+        && has_exactly_one_player_passenger(&root)
+    {
+        riding::stop_riding(player);
+        remove_with_passengers(&root);
+    }
+}
+
+/// Returns true once the saved entity can be spawned: its type can be loaded and its
+/// chunk is loaded.
+fn is_ready(world: &World, nbt: &NbtCompound) -> bool {
+    let loadable = nbt
+        .string("id")
+        .and_then(|id| id.to_str().parse::<Identifier>().ok())
+        .and_then(|id| REGISTRY.entity_types.by_key(&id))
+        .is_some_and(|entity_type| ENTITIES.has_load_factory(entity_type));
+    let Some(pos) = saved_entity_position(nbt) else {
+        // Invalid data, let spawning reject it
+        return true;
+    };
+    let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
+    loadable && world.chunk_map.with_full_chunk(chunk_pos, |_| ()).is_some()
+}
+
+/// Spawns the saved vehicle and puts the player back on the entity they were riding.
+fn spawn_vehicle(player: &Arc<Player>, vehicle: &SavedVehicle) {
+    let Some(root) = spawn_saved_entity(&player.world, &vehicle.entity) else {
+        log::warn!("Failed to spawn vehicle of {}", player.gameprofile.name);
+        return;
+    };
+    let passenger: SharedEntity = player.clone();
+    if let Some(seat) = find_with_passengers(&root, vehicle.attach)
+        && riding::start_riding(&passenger, &seat, true)
+    {
+        return;
+    }
+    log::warn!(
+        "Couldn't reattach {} to their vehicle",
+        player.gameprofile.name
+    );
+    remove_with_passengers(&root);
+}
+
+/// Returns true if exactly one player rides `vehicle`, directly or indirectly.
+///
+/// Vanilla: `Entity.hasExactlyOnePlayerPassenger()`.
+fn has_exactly_one_player_passenger(vehicle: &SharedEntity) -> bool {
+    fn count_players(entity: &SharedEntity) -> usize {
+        entity
+            .passengers()
+            .iter()
+            .map(|passenger| {
+                usize::from(passenger.clone().as_player().is_some()) + count_players(passenger)
+            })
+            .sum()
+    }
+    count_players(vehicle) == 1
+}
+
+/// Finds `uuid` among `entity` and everything riding it.
+fn find_with_passengers(entity: &SharedEntity, uuid: Uuid) -> Option<SharedEntity> {
+    if entity.uuid() == uuid {
+        return Some(entity.clone());
+    }
+    entity
+        .passengers()
+        .iter()
+        .find_map(|passenger| find_with_passengers(passenger, uuid))
+}
+
+/// Removes `entity` and every non-player entity riding it from the world.
+fn remove_with_passengers(entity: &SharedEntity) {
+    for passenger in entity.passengers() {
+        if passenger.clone().as_player().is_none() {
+            remove_with_passengers(&passenger);
+        }
+    }
+    entity.set_removed(RemovalReason::UnloadedWithPlayer);
+}
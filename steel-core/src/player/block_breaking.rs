@@ -6,8 +6,10 @@
 use std::sync::Arc;
 
 use steel_protocol::packets::game::CBlockUpdate;
+use steel_registry::block_entity_type::BlockEntityTypeRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
-use steel_registry::loot_table::LootContext;
+use steel_registry::data_components::DataComponentPatch;
+use steel_registry::loot_table::{BlockEntityRef, LootContext};
 use steel_registry::{REGISTRY, RegistryExt, blocks::properties::Direction, vanilla_blocks};
 use steel_utils::Identifier;
 use steel_utils::{
@@ -15,7 +17,8 @@ use steel_utils::{
     types::{GameType, InteractionHand, UpdateFlags},
 };
 
-use crate::behavior::BlockStateBehaviorExt;
+use crate::behavior::{BLOCK_BEHAVIORS, BlockStateBehaviorExt, ITEM_BEHAVIORS};
+use crate::event::{BlockBreakEvent, EVENTS};
 use crate::fluid::fluid_state_to_block;
 use crate::player::Player;
 use crate::world::World;
@@ -270,8 +273,15 @@ impl BlockBreakingManager {
     fn destroy_block(&self, player: &Player, world: &Arc<World>, pos: BlockPos) -> bool {
         let state = world.get_block_state(pos);
 
-        // Check if player's tool can destroy this block
-        // TODO: Implement canDestroyBlock check for adventure mode
+        // Check if player's held item allows destroying this block
+        let can_destroy = player.inventory.lock().with_selected_item_mut(|item| {
+            ITEM_BEHAVIORS
+                .get_behavior(item.item())
+                .can_destroy_block(item, state, world, pos, player)
+        });
+        if !can_destroy {
+            return false;
+        }
 
         // Get block info
         let Some(_block) = REGISTRY.blocks.by_state_id(state) else {
@@ -281,6 +291,22 @@ impl BlockBreakingManager {
         // TODO: Check for GameMasterBlock (command blocks, etc.)
         // TODO: Check blockActionRestricted
 
+        if EVENTS.has_listeners::<BlockBreakEvent>()
+            && let Some(player) = world.players.get_by_entity_id(player.id)
+            && !EVENTS.post_cancellable(&mut BlockBreakEvent::new(player, pos, state))
+        {
+            return false;
+        }
+
+        BLOCK_BEHAVIORS
+            .get_behavior(state.get_block())
+            .player_will_destroy(state, world, pos, player);
+        // The block entity is gone after the block is removed, but its loot may need it
+        let block_entity = world.get_block_entity(pos).map(|block_entity| {
+            let block_entity = block_entity.lock();
+            (block_entity.get_type(), block_entity.collect_components())
+        });
+
         // Vanilla parity: fluidState.createLegacyBlock() — breaking a waterlogged
         // block leaves water behind instead of air.
         let replacement = fluid_state_to_block(state.get_fluid_state());
@@ -336,7 +362,7 @@ impl BlockBreakingManager {
                 && has_correct_tool
             {
                 // TODO: Call playerDestroy to spawn drops
-                drop_block_loot(player, world, pos, state);
+                drop_block_loot(player, pos, state, block_entity.as_ref());
             }
         }
 
@@ -432,7 +458,14 @@ fn get_destroy_progress(player: &Player, block_state: BlockStateId) -> f32 {
 }
 
 /// Drops loot for a destroyed block using its loot table.
-fn drop_block_loot(player: &Player, _world: &Arc<World>, pos: BlockPos, state: BlockStateId) {
+///
+/// `block_entity` is the type and components of the block entity the block had.
+fn drop_block_loot(
+    player: &Player,
+    pos: BlockPos,
+    state: BlockStateId,
+    block_entity: Option<&(BlockEntityTypeRef, DataComponentPatch)>,
+) {
     let block = state.get_block();
 
     // Build the loot table key: "blocks/{block_name}"
@@ -453,6 +486,14 @@ fn drop_block_loot(player: &Player, _world: &Arc<World>, pos: BlockPos, state: B
         .with_block_state(state)
         .with_tool(&tool)
         .with_origin(f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+    if let Some((block_entity_type, components)) = block_entity {
+        ctx = ctx.with_block_entity(BlockEntityRef {
+            block_entity_type: Some(&block_entity_type.key),
+            custom_name: None,
+            inventory: None,
+            components: Some(components),
+        });
+    }
 
     // Generate drops
     let drops = loot_table.get_random_items(&mut ctx);
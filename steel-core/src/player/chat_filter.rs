@@ -0,0 +1,190 @@
+//! Filtering of chat messages before they are broadcast.
+//!
+//! Filters mark which characters of a message to hide, without changing the
+//! message: the signature of a signed message covers the text as typed, so the
+//! mask travels next to it and clients replace the hidden characters with `#`.
+//! Plugins add filters with [`register_chat_filter`]; the built-in one hides the
+//! words from the config.
+//!
+//! Vanilla: `TextFilter`, `FilterMask`.
+
+use std::sync::{Arc, LazyLock};
+
+use steel_protocol::packets::game::FilterType;
+use steel_utils::codec::BitSet;
+use steel_utils::locks::SyncRwLock;
+
+use super::Player;
+use crate::config::STEEL_CONFIG;
+
+/// Decides which parts of chat messages to hide.
+pub trait ChatFilter: Send + Sync {
+    /// Checks a message `sender` typed. Indices of the mask count UTF-16 units,
+    /// as clients do.
+    fn filter(&self, sender: &Player, message: &str) -> FilterType;
+}
+
+static CHAT_FILTERS: SyncRwLock<Vec<Arc<dyn ChatFilter>>> = SyncRwLock::new(Vec::new());
+
+static WORD_FILTER: LazyLock<WordFilter> = LazyLock::new(|| {
+    let config = &STEEL_CONFIG.chat_filter;
+    WordFilter::new(&config.blocked_words, config.block_messages)
+});
+
+/// Adds a filter run on every chat message.
+pub fn register_chat_filter(filter: Arc<dyn ChatFilter>) {
+    CHAT_FILTERS.write().push(filter);
+}
+
+/// Runs the built-in and registered filters on a message, hiding everything
+/// any of them hides.
+#[must_use]
+pub fn filter_message(sender: &Player, message: &str) -> FilterType {
+    let mut mask = WORD_FILTER.filter(sender, message);
+    for filter in CHAT_FILTERS.read().iter() {
+        if matches!(mask, FilterType::FullyFiltered) {
+            break;
+        }
+        mask = merge(mask, filter.filter(sender, message));
+    }
+    mask
+}
+
+/// Combines two masks of the same message.
+fn merge(first: FilterType, second: FilterType) -> FilterType {
+    match (first, second) {
+        (FilterType::FullyFiltered, _) | (_, FilterType::FullyFiltered) => {
+            FilterType::FullyFiltered
+        }
+        (FilterType::PassThrough, other) | (other, FilterType::PassThrough) => other,
+        (FilterType::PartiallyFiltered(first), FilterType::PartiallyFiltered(second)) => {
+            let (mut longer, shorter) = if first.0.len() >= second.0.len() {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            for (word, other) in longer.0.iter_mut().zip(shorter.0.iter()) {
+                *word |= other;
+            }
+            FilterType::PartiallyFiltered(longer)
+        }
+    }
+}
+
+/// Hides words from a list, ignoring case.
+pub struct WordFilter {
+    /// The words, in lowercase.
+    words: Vec<String>,
+    block_messages: bool,
+}
+
+impl WordFilter {
+    /// Creates a filter hiding `words`, or whole messages containing one of them
+    /// if `block_messages` is set.
+    #[must_use]
+    pub fn new(words: &[String], block_messages: bool) -> Self {
+        Self {
+            words: words
+                .iter()
+                .filter(|word| !word.is_empty())
+                .map(|word| word.to_lowercase())
+                .collect(),
+            block_messages,
+        }
+    }
+
+    /// Masks the words in `message`.
+    fn mask(&self, message: &str) -> FilterType {
+        if self.words.is_empty() {
+            return FilterType::PassThrough;
+        }
+        // Characters whose lowercase has another length stay as they are, so
+        // byte offsets match the message
+        let lowercase: String = message
+            .chars()
+            .map(|char| {
+                let mut lower = char.to_lowercase();
+                match (lower.next(), lower.next()) {
+                    (Some(lower), None) if lower.len_utf8() == char.len_utf8() => lower,
+                    _ => char,
+                }
+            })
+            .collect();
+        let mut hidden = Vec::new();
+        for word in &self.words {
+            for (start, _) in lowercase.match_indices(word.as_str()) {
+                hidden.push(start..start + word.len());
+            }
+        }
+        if hidden.is_empty() {
+            return FilterType::PassThrough;
+        }
+        if self.block_messages {
+            return FilterType::FullyFiltered;
+        }
+
+        let utf16_len = message.encode_utf16().count();
+        let mut mask = BitSet(vec![0; utf16_len.div_ceil(64)].into_boxed_slice());
+        let mut utf16_index = 0;
+        for (byte, char) in message.char_indices() {
+            if hidden.iter().any(|range| range.contains(&byte)) {
+                for unit in utf16_index..utf16_index + char.len_utf16() {
+                    mask.set(unit, true);
+                }
+            }
+            utf16_index += char.len_utf16();
+        }
+        FilterType::PartiallyFiltered(mask)
+    }
+}
+
+impl ChatFilter for WordFilter {
+    fn filter(&self, _sender: &Player, message: &str) -> FilterType {
+        self.mask(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(words: &[&str], block_messages: bool) -> WordFilter {
+        let words: Vec<String> = words.iter().map(|word| (*word).to_owned()).collect();
+        WordFilter::new(&words, block_messages)
+    }
+
+    #[test]
+    fn word_filter_masks_words_ignoring_case() {
+        let heck = filter(&["heck"], false);
+        assert!(matches!(heck.mask("hello"), FilterType::PassThrough));
+        let FilterType::PartiallyFiltered(mask) = heck.mask("oh HECK!") else {
+            panic!("expected a partial mask");
+        };
+        assert_eq!(mask.0.as_ref(), &[0b0111_1000]);
+
+        // An emoji takes two UTF-16 units
+        let FilterType::PartiallyFiltered(mask) = heck.mask("\u{1F600}heck") else {
+            panic!("expected a partial mask");
+        };
+        assert_eq!(mask.0.as_ref(), &[0b11_1100]);
+
+        assert!(matches!(
+            filter(&["heck"], true).mask("heck"),
+            FilterType::FullyFiltered
+        ));
+    }
+
+    #[test]
+    fn merge_combines_masks() {
+        let first = FilterType::PartiallyFiltered(BitSet(Box::new([0b01])));
+        let second = FilterType::PartiallyFiltered(BitSet(Box::new([0b10, 0b1])));
+        let FilterType::PartiallyFiltered(mask) = merge(first, second) else {
+            panic!("expected a partial mask");
+        };
+        assert_eq!(mask.0.as_ref(), &[0b11, 0b1]);
+        assert!(matches!(
+            merge(FilterType::PassThrough, FilterType::FullyFiltered),
+            FilterType::FullyFiltered
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 //! Chat and messaging state for a player.
 //!
 //! Groups the fields related to secure chat: message counters, signature cache,
-//! message validator, chat session, and message chain.
+//! message validator, chat session, and message chain, plus the spam throttler.
 
 use super::LastSeenMessagesValidator;
 use super::MessageCache;
@@ -14,8 +14,6 @@ use super::profile_key::RemoteChatSession;
 /// individual atomics/mutexes but are always accessed within short critical
 /// sections per-player, so a single lock is simpler with no real contention cost.
 pub struct ChatState {
-    /// Counter for chat messages sent BY this player.
-    pub messages_sent: i32,
     /// Counter for chat messages received BY this player.
     pub messages_received: i32,
     /// Message signature cache for tracking chat messages.
@@ -26,17 +24,78 @@ pub struct ChatState {
     pub chat_session: Option<RemoteChatSession>,
     /// Message chain state for tracking signed message sequence.
     pub message_chain: Option<SignedMessageChain>,
+    /// Spam counter shared by chat messages and commands.
+    pub spam_throttler: TickThrottler,
 }
 
 impl ChatState {
     pub fn new() -> Self {
         Self {
-            messages_sent: 0,
             messages_received: 0,
             signature_cache: MessageCache::new(),
             message_validator: LastSeenMessagesValidator::new(),
             chat_session: None,
             message_chain: None,
+            spam_throttler: TickThrottler::default(),
+        }
+    }
+}
+
+/// A counter that rises with every action and decays by one each tick.
+///
+/// Matches vanilla's `TickThrottler`: with a cost of 20 and a threshold of 200,
+/// a player can send a burst of 9 messages, then keep up one per second
+/// indefinitely. Only sustained spam faster than that trips it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TickThrottler {
+    count: u32,
+}
+
+impl TickThrottler {
+    /// Adds the cost of one action.
+    pub const fn increment(&mut self, cost: u32) {
+        self.count = self.count.saturating_add(cost);
+    }
+
+    /// Decays the counter, called once per tick.
+    pub const fn tick(&mut self) {
+        self.count = self.count.saturating_sub(1);
+    }
+
+    /// Returns true while the counter is below `threshold`.
+    #[must_use]
+    pub const fn is_under_threshold(&self, threshold: u32) -> bool {
+        self.count < threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COST: u32 = 20;
+    const THRESHOLD: u32 = 200;
+
+    #[test]
+    fn burst_trips_the_throttler() {
+        let mut throttler = TickThrottler::default();
+        for _ in 0..9 {
+            throttler.increment(COST);
+        }
+        assert!(throttler.is_under_threshold(THRESHOLD));
+        throttler.increment(COST);
+        assert!(!throttler.is_under_threshold(THRESHOLD));
+    }
+
+    #[test]
+    fn steady_rate_is_allowed() {
+        let mut throttler = TickThrottler::default();
+        for _ in 0..1000 {
+            throttler.increment(COST);
+            assert!(throttler.is_under_threshold(THRESHOLD));
+            for _ in 0..COST {
+                throttler.tick();
+            }
         }
     }
 }
@@ -17,6 +17,7 @@ use crate::{
     },
     player::PlayerConnection,
     player::connection::NetworkConnection,
+    profiling::{PhaseTimer, TickPhase},
     world::World,
 };
 
@@ -96,6 +97,7 @@ impl ChunkSender {
                         reason = "chunk sending is fire-and-forget; we don't need to await or track the task"
                     )]
                     let _ = spawn_blocking(move || {
+                        let _timer = PhaseTimer::start(TickPhase::ChunkSend);
                         let mut chunks_to_send = Vec::new();
                         for holder in chunks_to_process {
                             if let Some(chunk_guard) = holder.try_chunk(ChunkStatus::Full) {
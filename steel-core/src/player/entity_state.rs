@@ -3,18 +3,25 @@
 //! Groups the boolean/simple state flags that describe what the player is
 //! physically doing: sleeping, gliding, on the ground, sneaking, sprinting.
 
+use steel_protocol::packets::game::SPlayerInput;
+
 /// Physical state flags for a player entity.
 pub struct EntityState {
     /// Whether the player is currently sleeping in a bed.
     pub sleeping: bool,
     /// Whether the player is currently fall flying (elytra gliding).
     pub fall_flying: bool,
+    /// Whether the player is swimming (sprinting under water).
+    pub swimming: bool,
     /// Whether the player is on the ground.
     pub on_ground: bool,
     /// Whether the player is sneaking (shift key down).
     pub crouching: bool,
     /// Whether the player is sprinting.
     pub sprinting: bool,
+    /// The keys the client last reported as held.
+    /// Matches vanilla `ServerPlayer.lastClientInput`.
+    pub last_client_input: SPlayerInput,
 }
 
 impl EntityState {
@@ -23,9 +30,11 @@ impl EntityState {
         Self {
             sleeping: false,
             fall_flying: false,
+            swimming: false,
             on_ground: false,
             crouching: false,
             sprinting: false,
+            last_client_input: SPlayerInput { flags: 0 },
         }
     }
 }
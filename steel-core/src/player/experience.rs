@@ -14,6 +14,8 @@ pub struct Experience {
     pub score: i32,
     /// Whether the `total_points` has changed since the last time the client was updated
     pub dirty: bool,
+    /// Seeds the offers of enchanting tables, rerolled after every enchantment.
+    pub enchantment_seed: i32,
 }
 
 impl Experience {
@@ -24,6 +26,7 @@ impl Experience {
             total_points: total_points.max(0),
             score: 0,
             dirty: true,
+            enchantment_seed: 0,
         }
     }
 
@@ -155,6 +158,14 @@ impl Experience {
         self.dirty = true;
     }
 
+    /// Takes the levels spent on an enchantment and rerolls the enchantment seed.
+    ///
+    /// Vanilla equivalent: `Player::onEnchantmentPerformed`.
+    pub fn on_enchantment_performed(&mut self, levels: i32) {
+        self.add_levels(-levels);
+        self.enchantment_seed = rand::random();
+    }
+
     /// Clears the score, the total experience and sets the dirty flag to true
     pub const fn clear(&mut self) {
         self.score = 0;
@@ -24,7 +24,7 @@ use crate::world::World;
 /// 2. Check if block interaction should be suppressed (sneaking + holding items)
 /// 3. If not suppressed: Call block's `use_item_on` method
 /// 4. If block returns `TryEmptyHandInteraction` and main hand: Call block's `use_without_item`
-/// 5. If item not empty: Call item behavior's `use_on` for placement
+/// 5. If item not empty and not on cooldown: Call item behavior's `use_on` for placement
 /// 6. Handle creative mode infinite materials
 pub fn use_item_on(
     player: &Player,
@@ -89,17 +89,15 @@ pub fn use_item_on(
 
     let inv_id = inv_ref.container_id();
 
-    let is_empty = {
+    let usable = {
         let Some(inv) = guard.get_player_inventory_mut(inv_id) else {
             return InteractionResult::Pass;
         };
-        inv.get_item_in_hand(hand).is_empty()
+        let item = inv.get_item_in_hand(hand);
+        !item.is_empty() && !player.interaction.is_on_cooldown(item)
     };
 
-    if !is_empty {
-        // TODO: Check item cooldowns
-        // if player.getCooldowns().isOnCooldown(item_stack.item) { return Pass }
-
+    if usable {
         let mut context =
             UseOnContext::new(player, hand, hit_result.clone(), world, &mut guard, inv_id);
 
@@ -128,21 +126,19 @@ pub fn use_item(player: &Player, world: &Arc<World>, hand: InteractionHand) -> I
         return InteractionResult::Pass;
     }
 
-    // TODO: Check item cooldowns
-    // if player.getCooldowns().isOnCooldown(item_stack) { return InteractionResult::Pass }
-
     let inv_ref = ContainerRef::PlayerInventory(player.inventory.clone());
     let mut guard = ContainerLockGuard::lock_all(&[&inv_ref]);
     let inv_id = inv_ref.container_id();
 
-    let is_empty = {
+    let usable = {
         let Some(inv) = guard.get_player_inventory_mut(inv_id) else {
             return InteractionResult::Pass;
         };
-        inv.get_item_in_hand(hand).is_empty()
+        let item = inv.get_item_in_hand(hand);
+        !item.is_empty() && !player.interaction.is_on_cooldown(item)
     };
 
-    if !is_empty {
+    if usable {
         let mut context =
             crate::behavior::UseItemContext::new(player, hand, world, &mut guard, inv_id);
 
@@ -154,6 +150,8 @@ pub fn use_item(player: &Player, world: &Arc<World>, hand: InteractionHand) -> I
         let item_behavior = item_behaviors.get_behavior(item_ref);
 
         let result = item_behavior.use_item(&mut context);
+        // TODO: Apply the `use_cooldown` component on success once it is decoded
+        // (vanilla: ItemStack.use)
 
         // Restore count for creative mode (infinite materials)
         if player.has_infinite_materials() && context.inv.item().count < original_count {
@@ -1,8 +1,12 @@
 //! This module contains the `GameProfile` struct, which is used to store information about a player's profile.
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 use steel_protocol::packets::login::GameProfileProperty;
 use uuid::Uuid;
 
+use super::textures::{PlayerTextures, TEXTURES_PROPERTY, parse_cached};
+
 /// An enum representing a profile action.
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -26,3 +30,34 @@ pub struct GameProfile {
     #[serde(rename = "profileActions")]
     pub profile_actions: Option<Vec<GameProfileAction>>,
 }
+
+impl GameProfile {
+    /// Returns the skin, cape and elytra URLs, or `None` if the profile has no
+    /// valid `textures` property.
+    #[must_use]
+    pub fn textures(&self) -> Option<Arc<PlayerTextures>> {
+        let property = self
+            .properties
+            .iter()
+            .find(|property| property.name == TEXTURES_PROPERTY)?;
+        parse_cached(&property.value).ok()
+    }
+
+    /// Removes a `textures` property that can't be parsed or points outside
+    /// Mojang's texture domains, so other clients never download from there.
+    pub fn validate_textures(&mut self) {
+        let name = &self.name;
+        self.properties.retain(|property| {
+            if property.name != TEXTURES_PROPERTY {
+                return true;
+            }
+            match parse_cached(&property.value) {
+                Ok(_) => true,
+                Err(err) => {
+                    log::warn!("Dropped the textures of {name}: {err}");
+                    false
+                }
+            }
+        });
+    }
+}
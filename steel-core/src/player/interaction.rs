@@ -0,0 +1,339 @@
+//! Item use handling for players.
+//!
+//! This module implements the packet side of Java's
+//! `ServerGamePacketListenerImpl.handleUseItemOn()` and `handleUseItem()`, together with
+//! the player's `ItemCooldowns`. The block and item interactions themselves live in
+//! [`game_mode`](super::game_mode).
+
+use std::sync::atomic::Ordering;
+
+use rustc_hash::FxHashMap;
+use steel_protocol::packets::game::{CCooldown, SUseItem, SUseItemOn};
+use steel_registry::item_stack::ItemStack;
+use steel_utils::Identifier;
+use steel_utils::locks::SyncMutex;
+
+use super::chat_state::TickThrottler;
+use crate::behavior::InteractionResult;
+use crate::player::{Player, game_mode};
+
+/// How many use packets can pile up before further ones are dropped.
+///
+/// The counter goes down by one each tick. A vanilla client sends at most one use
+/// packet per hand every 4 ticks, so only modified clients ever reach this.
+const USE_SPAM_THRESHOLD: u32 = 20;
+
+/// How far the clicked location may be from the block's center on each axis.
+const MAX_HIT_OFFSET: f64 = 1.000_000_1;
+
+/// A running cooldown.
+#[derive(Debug, Clone, Copy)]
+struct CooldownInstance {
+    /// The tick the cooldown started on.
+    start_time: i32,
+    /// The tick the cooldown ends on.
+    end_time: i32,
+}
+
+/// Tracks which item cooldown groups are on cooldown.
+///
+/// Vanilla: `ItemCooldowns`.
+#[derive(Debug, Default)]
+pub struct ItemCooldowns {
+    cooldowns: FxHashMap<Identifier, CooldownInstance>,
+    tick_count: i32,
+}
+
+impl ItemCooldowns {
+    /// Creates an empty cooldown tracker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the cooldown group an item stack belongs to.
+    ///
+    /// Vanilla: `ItemCooldowns.getCooldownGroup()`.
+    #[must_use]
+    pub fn cooldown_group(stack: &ItemStack) -> Identifier {
+        // TODO: Use the `use_cooldown` component's cooldown group once it is decoded
+        stack.item.key.clone()
+    }
+
+    /// Returns true if the stack's cooldown group is on cooldown.
+    #[must_use]
+    pub fn is_on_cooldown(&self, stack: &ItemStack) -> bool {
+        self.get_cooldown_percent(stack, 0.0) > 0.0
+    }
+
+    /// Gets how much of the stack's cooldown is left, from 1.0 (just started) to 0.0.
+    ///
+    /// Vanilla: `ItemCooldowns.getCooldownPercent()`.
+    #[must_use]
+    pub fn get_cooldown_percent(&self, stack: &ItemStack, partial_tick: f32) -> f32 {
+        let Some(instance) = self.cooldowns.get(&Self::cooldown_group(stack)) else {
+            return 0.0;
+        };
+        let total = (instance.end_time - instance.start_time) as f32;
+        let remaining = instance.end_time as f32 - (self.tick_count as f32 + partial_tick);
+        (remaining / total).clamp(0.0, 1.0)
+    }
+
+    /// Puts a cooldown group on cooldown for `duration` ticks.
+    pub fn add_cooldown(&mut self, group: Identifier, duration: i32) {
+        self.cooldowns.insert(
+            group,
+            CooldownInstance {
+                start_time: self.tick_count,
+                end_time: self.tick_count + duration,
+            },
+        );
+    }
+
+    /// Clears a group's cooldown. Returns true if it was on cooldown.
+    pub fn remove_cooldown(&mut self, group: &Identifier) -> bool {
+        self.cooldowns.remove(group).is_some()
+    }
+
+    /// Advances the tick counter and returns the groups whose cooldown ran out.
+    ///
+    /// Vanilla: `ItemCooldowns.tick()`.
+    pub fn tick(&mut self) -> Vec<Identifier> {
+        self.tick_count += 1;
+        let tick_count = self.tick_count;
+        let mut ended = Vec::new();
+        self.cooldowns.retain(|group, instance| {
+            let running = instance.end_time > tick_count;
+            if !running {
+                ended.push(group.clone());
+            }
+            running
+        });
+        ended
+    }
+}
+
+/// Handles a player's `SUseItemOn` and `SUseItem` packets.
+///
+/// Validates reach and packet spam, tracks item cooldowns and acknowledges the
+/// client's block change sequence so predicted blocks don't turn into ghost blocks.
+pub struct PlayerInteractionManager {
+    /// Item cooldowns. Vanilla: `Player.cooldowns`.
+    cooldowns: SyncMutex<ItemCooldowns>,
+    /// Counts use packets to drop floods of them.
+    use_throttler: SyncMutex<TickThrottler>,
+}
+
+impl Default for PlayerInteractionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlayerInteractionManager {
+    /// Creates a new interaction manager with no cooldowns.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cooldowns: SyncMutex::new(ItemCooldowns::new()),
+            use_throttler: SyncMutex::new(TickThrottler::default()),
+        }
+    }
+
+    /// Ticks cooldowns and tells the client about the ones that ended.
+    pub fn tick(&self, player: &Player) {
+        self.use_throttler.lock().tick();
+        let ended = self.cooldowns.lock().tick();
+        for cooldown_group in ended {
+            player.send_packet(CCooldown {
+                cooldown_group,
+                duration: 0,
+            });
+        }
+    }
+
+    /// Clears all state, e.g. after respawning.
+    ///
+    /// The client starts with no cooldowns for a new player entity, so nothing is sent.
+    pub fn reset(&self) {
+        *self.cooldowns.lock() = ItemCooldowns::new();
+        *self.use_throttler.lock() = TickThrottler::default();
+    }
+
+    /// Returns true if the stack's cooldown group is on cooldown.
+    #[must_use]
+    pub fn is_on_cooldown(&self, stack: &ItemStack) -> bool {
+        self.cooldowns.lock().is_on_cooldown(stack)
+    }
+
+    /// Puts the stack's cooldown group on cooldown for `duration` ticks.
+    ///
+    /// Vanilla: `ItemCooldowns.addCooldown(ItemStack, int)`.
+    pub fn add_cooldown(&self, player: &Player, stack: &ItemStack, duration: i32) {
+        self.add_group_cooldown(player, ItemCooldowns::cooldown_group(stack), duration);
+    }
+
+    /// Puts a cooldown group on cooldown for `duration` ticks.
+    ///
+    /// Vanilla: `ServerItemCooldowns.onCooldownStarted()`.
+    pub fn add_group_cooldown(&self, player: &Player, group: Identifier, duration: i32) {
+        self.cooldowns.lock().add_cooldown(group.clone(), duration);
+        player.send_packet(CCooldown {
+            cooldown_group: group,
+            duration,
+        });
+    }
+
+    /// Clears a group's cooldown.
+    ///
+    /// Vanilla: `ServerItemCooldowns.onCooldownEnded()`.
+    pub fn remove_cooldown(&self, player: &Player, group: &Identifier) {
+        if self.cooldowns.lock().remove_cooldown(group) {
+            player.send_packet(CCooldown {
+                cooldown_group: group.clone(),
+                duration: 0,
+            });
+        }
+    }
+
+    /// Counts a use packet. Returns false if the player is sending too many.
+    fn accept_use(&self) -> bool {
+        let mut throttler = self.use_throttler.lock();
+        throttler.increment(1);
+        throttler.is_under_threshold(USE_SPAM_THRESHOLD)
+    }
+
+    /// Handles a use item on block packet.
+    ///
+    /// The block gets the first chance to react, then the held item (see
+    /// [`game_mode::use_item_on`]). Rejected packets still get their sequence
+    /// acknowledged and the clicked blocks resent so the client drops its prediction.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.handleUseItemOn()`.
+    pub fn handle_use_item_on(&self, player: &Player, packet: SUseItemOn) {
+        if !player.client_loaded.load(Ordering::Relaxed) {
+            return;
+        }
+
+        player.ack_block_changes_up_to(packet.sequence);
+
+        let pos = packet.block_hit.block_pos;
+        let direction = packet.block_hit.direction;
+
+        if !self.accept_use() {
+            player.send_block_updates(pos, direction);
+            return;
+        }
+
+        if !player.is_within_block_interaction_range(pos) {
+            player.send_block_updates(pos, direction);
+            return;
+        }
+
+        let center_x = f64::from(pos.x()) + 0.5;
+        let center_y = f64::from(pos.y()) + 0.5;
+        let center_z = f64::from(pos.z()) + 0.5;
+        let location = &packet.block_hit.location;
+        if (location.x - center_x).abs() >= MAX_HIT_OFFSET
+            || (location.y - center_y).abs() >= MAX_HIT_OFFSET
+            || (location.z - center_z).abs() >= MAX_HIT_OFFSET
+        {
+            log::warn!(
+                "Rejecting UseItemOnPacket from {}: location {:?} too far from block {:?}",
+                player.gameprofile.name,
+                location,
+                pos
+            );
+            player.send_block_updates(pos, direction);
+            return;
+        }
+
+        if pos.y() >= player.world.max_build_height() {
+            // TODO: Send "build.tooHigh" message to player
+            player.send_block_updates(pos, direction);
+            return;
+        }
+
+        if player.is_awaiting_teleport() || !player.world.may_interact(player, pos) {
+            player.send_block_updates(pos, direction);
+            return;
+        }
+
+        let result = game_mode::use_item_on(player, &player.world, packet.hand, &packet.block_hit);
+        if let InteractionResult::Success = result {
+            player.swing(packet.hand, true);
+        }
+
+        // Always resend both blocks: the client may have predicted a placement on either
+        player.send_block_updates(pos, direction);
+        player.broadcast_inventory_changes();
+    }
+
+    /// Handles a use item packet (right-clicking without targeting a block).
+    ///
+    /// The client sends this for the main hand first and only falls back to the off
+    /// hand if that didn't consume the action, so each hand is handled on its own.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.handleUseItem()`.
+    pub fn handle_use_item(&self, player: &Player, packet: SUseItem) {
+        if !player.client_loaded.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // Without this ack, client-side prediction stays active and overrides server
+        // block updates (e.g. water coming back after picking it up with a bucket).
+        player.ack_block_changes_up_to(packet.sequence);
+
+        if !self.accept_use() {
+            player.broadcast_inventory_changes();
+            return;
+        }
+
+        // Vanilla: absSnapRotationTo, so the item is used in the direction the client saw
+        player.rotation.store((packet.y_rot, packet.x_rot));
+
+        let result = game_mode::use_item(player, &player.world, packet.hand);
+        if let InteractionResult::Success = result {
+            player.swing(packet.hand, true);
+        }
+
+        player.broadcast_inventory_changes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_registry::vanilla_items;
+
+    use super::*;
+
+    fn pearl() -> ItemStack {
+        ItemStack::new(&vanilla_items::ITEMS.ender_pearl)
+    }
+
+    #[test]
+    fn cooldown_runs_out() {
+        let mut cooldowns = ItemCooldowns::new();
+        let group = ItemCooldowns::cooldown_group(&pearl());
+        cooldowns.add_cooldown(group.clone(), 20);
+        assert!(cooldowns.is_on_cooldown(&pearl()));
+
+        for _ in 0..19 {
+            assert!(cooldowns.tick().is_empty());
+        }
+        assert!(cooldowns.is_on_cooldown(&pearl()));
+        assert_eq!(cooldowns.tick(), vec![group]);
+        assert!(!cooldowns.is_on_cooldown(&pearl()));
+    }
+
+    #[test]
+    fn cooldown_percent_goes_down() {
+        let mut cooldowns = ItemCooldowns::new();
+        cooldowns.add_cooldown(ItemCooldowns::cooldown_group(&pearl()), 10);
+        assert!((cooldowns.get_cooldown_percent(&pearl(), 0.0) - 1.0).abs() < f32::EPSILON);
+        for _ in 0..5 {
+            cooldowns.tick();
+        }
+        assert!((cooldowns.get_cooldown_percent(&pearl(), 0.0) - 0.5).abs() < f32::EPSILON);
+    }
+}
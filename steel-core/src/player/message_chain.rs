@@ -6,6 +6,8 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use steel_crypto::{CryptError, SignatureUpdater, signature::SignatureOutput};
+use steel_utils::translations;
+use text_components::TextComponent;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -156,6 +158,48 @@ impl SignedMessageBody {
 
         Ok(())
     }
+
+    /// The timestamp in milliseconds since the epoch, as sent to clients.
+    #[must_use]
+    pub fn timestamp_millis(&self) -> i64 {
+        self.time_stamp
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| {
+                i64::try_from(since.as_millis()).unwrap_or(i64::MAX)
+            })
+    }
+}
+
+/// A chat message from a player, signed or not.
+///
+/// Equivalent to `PlayerChatMessage` in Minecraft.
+#[derive(Clone, Debug)]
+pub struct PlayerChatMessage {
+    /// The link in the sender's chain
+    pub link: SignedMessageLink,
+    /// The sender's signature, if the message is signed
+    pub signature: Option<Box<[u8; 256]>>,
+    /// The signed content
+    pub body: SignedMessageBody,
+}
+
+impl PlayerChatMessage {
+    /// Creates an unsigned message, used when the sender has no chat session
+    /// and secure chat isn't enforced.
+    #[must_use]
+    pub fn unsigned(sender: Uuid, content: String) -> Self {
+        Self {
+            link: SignedMessageLink::unsigned(sender),
+            signature: None,
+            body: SignedMessageBody::unsigned(content),
+        }
+    }
+
+    /// The text of the message.
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.body.content
+    }
 }
 
 /// Helper to update signature with last seen messages
@@ -197,15 +241,37 @@ pub enum ChainError {
     #[error("Message out of order")]
     OutOfOrderChat,
 
-    /// Message has expired
-    #[error("Message has expired")]
-    MessageExpired,
+    /// The signed arguments of a command don't match its message arguments
+    #[error("Unexpected or missing command argument signatures")]
+    InvalidCommandSignature,
 
     /// Cryptographic error
     #[error("Cryptographic error: {0}")]
     CryptoError(#[from] CryptError),
 }
 
+impl ChainError {
+    /// The message shown to the player whose message was rejected.
+    ///
+    /// Vanilla: `SignedMessageChain.DecodeException`.
+    #[must_use]
+    pub fn reason(&self) -> TextComponent {
+        match self {
+            Self::MissingProfileKey => translations::CHAT_DISABLED_MISSING_PROFILE_KEY.msg(),
+            Self::ChainBroken => translations::CHAT_DISABLED_CHAIN_BROKEN.msg(),
+            Self::ExpiredProfileKey => translations::CHAT_DISABLED_EXPIRED_PROFILE_KEY.msg(),
+            Self::InvalidSignature | Self::CryptoError(_) => {
+                translations::CHAT_DISABLED_INVALID_SIGNATURE.msg()
+            }
+            Self::OutOfOrderChat => translations::CHAT_DISABLED_OUT_OF_ORDER_CHAT.msg(),
+            Self::InvalidCommandSignature => {
+                translations::CHAT_DISABLED_INVALID_COMMAND_SIGNATURE.msg()
+            }
+        }
+        .into()
+    }
+}
+
 /// Manages the message chain state for a player.
 ///
 /// Equivalent to `SignedMessageChain` in Minecraft.
@@ -254,6 +320,20 @@ impl SignedMessageChain {
         &mut self,
         body: &SignedMessageBody,
     ) -> Result<SignedMessageLink, ChainError> {
+        let link = self.validate(body)?;
+        self.advance(&link);
+        Ok(link)
+    }
+
+    /// Checks that a message continues the chain, without advancing it, so the
+    /// chain only moves on once the signature is verified. A message older than
+    /// the last one breaks the chain.
+    ///
+    /// Returns the link the message's signature must cover.
+    ///
+    /// # Errors
+    /// Returns `ChainError` if the chain is broken or the message is out of order
+    pub fn validate(&mut self, body: &SignedMessageBody) -> Result<SignedMessageLink, ChainError> {
         // Check chain not broken
         let link = self.next_link.clone().ok_or(ChainError::ChainBroken)?;
 
@@ -262,14 +342,17 @@ impl SignedMessageChain {
             self.break_chain();
             return Err(ChainError::OutOfOrderChat);
         }
-
-        // Update state
         self.last_timestamp = body.time_stamp;
-        self.next_link = link.advance();
 
         Ok(link)
     }
 
+    /// Advances the chain past a verified message. The chain breaks once the
+    /// index can't grow any further.
+    pub fn advance(&mut self, link: &SignedMessageLink) {
+        self.next_link = link.advance();
+    }
+
     /// Resets the chain to a new session
     pub const fn reset(&mut self, sender: Uuid, session_id: Uuid) {
         self.next_link = Some(SignedMessageLink::root(sender, session_id));
@@ -377,4 +460,21 @@ mod tests {
         assert!(matches!(result, Err(ChainError::OutOfOrderChat)));
         assert!(chain.is_broken());
     }
+
+    #[test]
+    fn test_chain_advances_only_after_verification() {
+        let mut chain = SignedMessageChain::new(Uuid::new_v4(), Uuid::new_v4());
+
+        let body = SignedMessageBody::unsigned("Hello".to_string());
+        let link = chain
+            .validate(&body)
+            .expect("First message should validate");
+        assert_eq!(chain.next_link(), Some(&link));
+
+        chain.advance(&link);
+        let next = chain
+            .validate(&body)
+            .expect("Second message should validate");
+        assert_eq!(next.index, 1);
+    }
 }
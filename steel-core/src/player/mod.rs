@@ -1,6 +1,8 @@
 //! This module contains all things player-related.
 mod abilities;
+pub mod attached_entities;
 pub mod block_breaking;
+pub mod chat_filter;
 mod chat_state;
 pub mod chunk_sender;
 /// This module contains the `PlayerConnection` trait that abstracts network connections.
@@ -12,35 +14,49 @@ pub mod experience;
 pub mod game_mode;
 mod game_profile;
 mod health_sync;
+pub mod interaction;
 pub mod message_chain;
 mod message_validator;
 pub mod movement;
 mod movement_state;
 /// This module contains the networking implementation for the player.
 pub mod networking;
+pub mod outbound_queue;
+pub mod packet_recorder;
 pub mod player_data;
 pub mod player_data_storage;
 pub mod player_inventory;
+pub mod profile_cache;
 pub mod profile_key;
+mod respawn;
 mod signature_cache;
 mod teleport_state;
+pub mod textures;
+pub mod title;
 
 pub use abilities::Abilities;
+use attached_entities::AttachedEntities;
 use chat_state::ChatState;
 use entity_state::EntityState;
 use glam::DVec3;
 use health_sync::HealthSyncState;
+use interaction::PlayerInteractionManager;
 pub use message_validator::LastSeenMessagesValidator;
 use movement_state::MovementState;
+pub use respawn::RespawnConfig;
 pub use signature_cache::{LastSeen, MessageCache};
+use simdnbt::owned::NbtCompound;
 use steel_protocol::{packet_traits::CompressionInfo, packets::game::CSetExperience};
 use teleport_state::TeleportState;
+use title::TitleTimes;
 
 use block_breaking::BlockBreakingManager;
 use crossbeam::atomic::AtomicCell;
 use enum_dispatch::enum_dispatch;
 pub use game_profile::{GameProfile, GameProfileAction};
-use message_chain::SignedMessageChain;
+use message_chain::{
+    ChainError, MessageSignatureUpdater, PlayerChatMessage, SignedMessageBody, SignedMessageChain,
+};
 use profile_key::RemoteChatSession;
 use std::{
     sync::{
@@ -53,28 +69,47 @@ use steel_protocol::packet_traits::{ClientPacket, EncodedPacket};
 use steel_protocol::packets::game::CSystemChatMessage;
 use steel_protocol::packets::game::{
     AnimateAction, CAddEntity, CAnimate, CDamageEvent, CEntityEvent, CEntityPositionSync,
-    CHurtAnimation, COpenSignEditor, CPlayerCombatKill, CPlayerPosition, CRemoveEntities, CRespawn,
-    CSetEntityData, CSetHealth, CSetHeldSlot, CSetTime, ClientCommandAction, PlayerAction,
-    SAcceptTeleportation, SPickItemFromBlock, SPlayerAbilities, SPlayerAction, SSetCarriedItem,
-    SUseItem, SUseItemOn,
+    CHurtAnimation, CMoveVehicle, COpenSignEditor, CPlayerCombatKill, CPlayerPosition,
+    CRemoveEntities, CRemoveMobEffect, CRespawn, CSetEntityData, CSetHealth, CSetHeldSlot,
+    CSetTime, CStopSound, CUpdateAttributes, CUpdateMobEffect, ClientCommandAction, PlayerAction,
+    PlayerCommandAction, SAcceptTeleportation, SInteract, SMoveVehicle, SPaddleBoat,
+    SPickItemFromBlock, SPickItemFromEntity, SPlayerAbilities, SPlayerAction, SPlayerCommand,
+    SRenameItem, SSetBeacon, SSetCarriedItem, SUseItem, SUseItemOn, SoundSource,
 };
 use steel_protocol::utils::ConnectionProtocol;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::shapes::AABBd;
+use steel_registry::chat_type::ChatTypeRef;
+use steel_registry::data_components::vanilla_components::{
+    BLOCK_ENTITY_DATA, EQUIPPABLE, GLIDER, LODESTONE_TRACKER, LodestoneTracker, MAP_ID,
+    TypedEntityData,
+};
 use steel_registry::entity_data::EntityPose;
 use steel_registry::entity_types::EntityTypeRef;
 use steel_registry::game_rules::GameRuleValue;
+use steel_registry::vanilla_attributes::{
+    ATTACK_DAMAGE, ATTACK_SPEED, BLOCK_INTERACTION_RANGE, ENTITY_INTERACTION_RANGE,
+    FALL_DAMAGE_MULTIPLIER, GRAVITY, MOVEMENT_SPEED, OXYGEN_BONUS, SAFE_FALL_DISTANCE,
+};
+use steel_registry::vanilla_block_tags::{CLIMBABLE_TAG, FENCE_GATES_TAG, FENCES_TAG, WALLS_TAG};
 use steel_registry::vanilla_entities;
 use steel_registry::vanilla_entity_data::PlayerEntityData;
 use steel_registry::vanilla_game_rules::{
-    ADVANCE_TIME, ELYTRA_MOVEMENT_CHECK, IMMEDIATE_RESPAWN, KEEP_INVENTORY, PLAYER_MOVEMENT_CHECK,
-    SHOW_DEATH_MESSAGES,
+    ADVANCE_TIME, DROWNING_DAMAGE, ELYTRA_MOVEMENT_CHECK, IMMEDIATE_RESPAWN, KEEP_INVENTORY,
+    PLAYER_MOVEMENT_CHECK, PLAYERS_NETHER_PORTAL_CREATIVE_DELAY,
+    PLAYERS_NETHER_PORTAL_DEFAULT_DELAY, SHOW_DEATH_MESSAGES,
+};
+use steel_registry::{
+    REGISTRY, RegistryEntry, RegistryExt, TaggedRegistryExt, sound_events, vanilla_blocks,
+    vanilla_chat_types,
 };
-use steel_registry::{RegistryEntry, vanilla_chat_types};
 use steel_utils::entity_events::EntityStatus;
 
+use rustc_hash::{FxHashMap, FxHashSet};
 use steel_utils::locks::SyncMutex;
+use steel_utils::math;
 use steel_utils::types::GameType;
+use text_components::format::Color;
 use text_components::resolving::TextResolutor;
 use text_components::translation::TranslatedMessage;
 use text_components::{Modifier, TextComponent};
@@ -85,45 +120,66 @@ use text_components::{
 };
 use uuid::Uuid;
 
+use crate::command::context::CommandSigning;
+use crate::command::sender::CommandSender;
+use crate::entity::attribute::{
+    AttributeMap, CREATIVE_BLOCK_INTERACTION_RANGE_MODIFIER,
+    CREATIVE_ENTITY_INTERACTION_RANGE_MODIFIER, SPEED_MODIFIER_SPRINTING, default_attributes,
+};
+use crate::entity::effect::MobEffectInstance;
 use crate::entity::{
     DEATH_DURATION, Entity, EntityLevelCallback, LivingEntityBase, NullEntityCallback,
-    RemovalReason,
+    RemovalReason, RidingState, SharedEntity, WeakEntity, leash, riding,
 };
+use crate::physics::{COLLISION_EPSILON, MoverType};
 use crate::player::player_inventory::PlayerInventory;
+use crate::portal::portal_processor::PortalProcessor;
 use crate::server::Server;
+use crate::server::chunk_distances::CHUNK_DISTANCES;
+use crate::server::languages::LANGUAGES;
+use crate::server::payload_channels::{
+    BRAND_CHANNEL, REGISTER_CHANNEL, UNREGISTER_CHANNEL, decode_brand, decode_channel_list,
+};
 use crate::{command::commands::gamemode::get_gamemode_translation, inventory::SyncPlayerInv};
 use crate::{config::STEEL_CONFIG, player::experience::Experience};
 use crate::{config::WorldGeneratorTypes, entity::damage::DamageSource};
 use steel_registry::vanilla_damage_types;
 
-use steel_crypto::{SignatureValidator, public_key_from_bytes, signature::NoValidation};
+use steel_crypto::signature::{NoValidation, collect_bytes};
+use steel_crypto::{SignatureValidator, public_key_from_bytes};
 use steel_protocol::packets::{
-    common::{SClientInformation, SCustomPayload},
+    common::{CCustomPayload, SClientInformation, SCustomPayload},
     game::{
-        CBlockChangedAck, CBlockUpdate, CContainerClose, CGameEvent, CMoveEntityPosRot,
-        CMoveEntityRot, COpenScreen, CPlayerChat, CPlayerInfoUpdate, CRotateHead,
-        CSetChunkCacheRadius, CSystemChat, ChatTypeBound, FilterType, GameEventType,
-        PreviousMessage, SChat, SChatAck, SChatSessionUpdate, SContainerButtonClick,
-        SContainerClick, SContainerClose, SContainerSlotStateChanged, SMovePlayer, SPlayerInput,
-        SSetCreativeModeSlot, SSignUpdate, calc_delta, to_angle_byte,
+        CBlockChangedAck, CBlockUpdate, CClearTitles, CContainerClose, CGameEvent,
+        CMoveEntityPosRot, CMoveEntityRot, COpenScreen, CPlayerChat, CPlayerInfoUpdate,
+        CRotateHead, CSetActionBarText, CSetCamera, CSetChunkCacheRadius, CSetSubtitleText,
+        CSetTitleText, CSetTitlesAnimation, CSystemChat, ChatTypeBound, FilterType, GameEventType,
+        PreviousMessage, SChat, SChatAck, SChatCommand, SChatCommandSigned, SChatSessionUpdate,
+        SContainerButtonClick, SContainerClick, SContainerClose, SContainerSlotStateChanged,
+        SMovePlayer, SPlayerInput, SSetCreativeModeSlot, SSignUpdate, calc_delta, to_angle_byte,
     },
 };
 use steel_registry::{blocks::properties::Direction, item_stack::ItemStack};
 
-use crate::behavior::{BLOCK_BEHAVIORS, InteractionResult};
+use crate::behavior::blocks::NetherPortalBlock;
+use crate::behavior::{BLOCK_BEHAVIORS, BlockStateBehaviorExt, ITEM_BEHAVIORS, UseItemContext};
 use crate::block_entity::BlockEntity;
 use crate::block_entity::entities::SignBlockEntity;
+use crate::event::{ChatEvent, EVENTS};
 use steel_utils::BlockPos;
+use steel_utils::codec::VarInt;
 
 use steel_utils::types::InteractionHand;
-use steel_utils::{ChunkPos, translations};
+use steel_utils::{ChunkPos, Identifier, translations};
 
 use crate::entity::LivingEntity;
+use crate::fluid::{FluidRef, get_fluid_state, get_height, is_lava_fluid, is_water_fluid};
+use crate::inventory::equipment::EquipmentSlot;
 use crate::inventory::{
-    MenuInstance, MenuProvider,
+    MenuInstance, MenuProvider, PlayerEnderChestContainer,
     container::Container,
     inventory_menu::InventoryMenu,
-    lock::{ContainerId, ContainerLockGuard},
+    lock::{ContainerId, ContainerLockGuard, ContainerRef},
     menu::Menu,
     slot::Slot,
 };
@@ -133,6 +189,73 @@ pub type PreviousMessageEntry = PreviousMessage;
 
 pub use steel_protocol::packets::common::{ChatVisibility, HumanoidArm, ParticleStatus};
 
+/// Bit of the shared flags byte marking the entity as burning (vanilla `Entity.FLAG_ONFIRE`).
+const FLAG_ON_FIRE: u8 = 0;
+
+/// Bit of the shared flags byte marking the entity as swimming
+/// (vanilla `Entity.FLAG_SWIMMING`).
+const FLAG_SWIMMING: u8 = 4;
+
+/// Bit of the shared flags byte marking the entity as gliding
+/// (vanilla `Entity.FLAG_FALL_FLYING`).
+const FLAG_FALL_FLYING: u8 = 7;
+
+/// Bit of the living entity flags byte marking a riptide spin attack
+/// (vanilla `LivingEntity.LIVING_ENTITY_FLAG_SPIN_ATTACK`).
+const LIVING_FLAG_SPIN_ATTACK: i8 = 0x04;
+
+/// Ticks of air a player can hold (vanilla `Entity.TOTAL_AIR_SUPPLY`).
+pub const MAX_AIR_SUPPLY: i32 = 300;
+
+/// Air regained per tick out of water (vanilla `LivingEntity.increaseAirSupply()`).
+const AIR_REFILL_PER_TICK: i32 = 4;
+
+/// Damage taken each time the air supply runs out under water.
+const DROWN_DAMAGE: f32 = 2.0;
+
+/// Ticks before a player can use a portal again (vanilla `Player.getDimensionChangingDelay()`).
+const DIMENSION_CHANGING_DELAY: i32 = 10;
+
+/// Returns true if `stack`, worn in `slot`, lets the player glide.
+///
+/// Vanilla: `LivingEntity.canGlideUsing()`.
+fn can_glide_using(stack: &ItemStack, slot: EquipmentSlot) -> bool {
+    stack.has(GLIDER)
+        && !stack.next_damage_will_break()
+        && stack
+            .get(EQUIPPABLE)
+            .is_some_and(|equippable| EquipmentSlot::from(equippable.slot) == slot)
+}
+
+/// Returns the squared distance from `eye_pos` to the nearest point of `aabb`,
+/// 0 if it's inside.
+///
+/// Vanilla: `AABB.distanceToSqr(Vec3)`.
+fn distance_sq_to_aabb(eye_pos: DVec3, aabb: &AABBd) -> f64 {
+    let dx = (aabb.min_x - eye_pos.x)
+        .max(eye_pos.x - aabb.max_x)
+        .max(0.0);
+    let dy = (aabb.min_y - eye_pos.y)
+        .max(eye_pos.y - aabb.max_y)
+        .max(0.0);
+    let dz = (aabb.min_z - eye_pos.z)
+        .max(eye_pos.z - aabb.max_z)
+        .max(0.0);
+    dx * dx + dy * dy + dz * dz
+}
+
+/// Gets the sound played when a player takes `damage` from falling or flying
+/// into a wall.
+///
+/// Vanilla: `LivingEntity.getFallDamageSound()` with `Player.getFallSounds()`.
+const fn fall_damage_sound(damage: i32) -> i32 {
+    if damage > 4 {
+        sound_events::ENTITY_PLAYER_BIG_FALL
+    } else {
+        sound_events::ENTITY_PLAYER_SMALL_FALL
+    }
+}
+
 /// Client-side settings sent via `SClientInformation` packet.
 /// This is stored separately from the packet struct to allow default initialization.
 #[derive(Debug, Clone)]
@@ -228,6 +351,8 @@ pub struct Player {
     pub last_chunk_pos: SyncMutex<ChunkPos>,
     /// The last chunk tracking view of the player.
     pub last_tracking_view: SyncMutex<Option<PlayerChunkView>>,
+    /// The center and distance of the player's last simulation ticket.
+    pub last_simulation_view: SyncMutex<Option<PlayerChunkView>>,
     /// The chunk sender for the player.
     pub chunk_sender: SyncMutex<ChunkSender>,
 
@@ -235,6 +360,12 @@ pub struct Player {
     /// Updated when the client sends `SClientInformation` during config or play phase.
     client_information: SyncMutex<ClientInformation>,
 
+    /// The brand the client reported, e.g. `vanilla` or `fabric`.
+    client_brand: SyncMutex<Option<String>>,
+
+    /// The channels the client registered with `minecraft:register`.
+    client_channels: SyncMutex<FxHashSet<Identifier>>,
+
     /// Chat state: message counters, signature cache, validator, session, chain.
     pub chat: SyncMutex<ChatState>,
 
@@ -247,6 +378,9 @@ pub struct Player {
     /// The player's inventory container (shared with `inventory_menu`).
     pub inventory: SyncPlayerInv,
 
+    /// The player's personal ender chest inventory, shared by every ender chest.
+    pub ender_chest_inventory: Arc<SyncMutex<PlayerEnderChestContainer>>,
+
     /// The player's inventory menu (always open, even when `container_id` is 0).
     inventory_menu: SyncMutex<InventoryMenu>,
 
@@ -266,6 +400,18 @@ pub struct Player {
     /// Local tick counter (incremented each tick).
     tick_count: AtomicI32,
 
+    /// Ticks since the player last attacked or switched items.
+    /// Vanilla: `LivingEntity.attackStrengthTicker`.
+    attack_strength_ticker: AtomicI32,
+
+    /// Ticks spent gliding since the player last started fall flying.
+    /// Vanilla: `LivingEntity.fallFlyTicks`.
+    fall_fly_ticks: AtomicI32,
+
+    /// Ticks left of a riptide spin attack.
+    /// Vanilla: `LivingEntity.autoSpinAttackTicks`.
+    auto_spin_attack_ticks: AtomicI32,
+
     /// Physical state flags (sleeping, fall flying, on ground).
     pub(crate) entity_state: SyncMutex<EntityState>,
 
@@ -274,11 +420,16 @@ pub struct Player {
 
     /// Block breaking state machine.
     pub block_breaking: SyncMutex<BlockBreakingManager>,
+    /// Item use handling and item cooldowns.
+    pub interaction: PlayerInteractionManager,
 
     /// Shared living-entity fields (`dead`, `invulnerable_time`, `last_hurt`).
     /// Vanilla: `LivingEntity` (L230-232) + `Entity.invulnerableTime` (L256).
     living_base: SyncMutex<LivingEntityBase>,
 
+    /// The player's attributes (max health, movement speed, ...).
+    attributes: SyncMutex<AttributeMap>,
+
     /// Delta-tracking state for `CSetHealth` deduplication.
     health_sync: SyncMutex<HealthSyncState>,
 
@@ -290,6 +441,32 @@ pub struct Player {
 
     /// The Player's Experience
     pub experience: SyncMutex<Experience>,
+
+    /// The player's personal respawn point, `None` to respawn at the world spawn.
+    pub respawn_config: SyncMutex<Option<RespawnConfig>>,
+
+    /// The entity the player is spectating through, `None` for the player's own view.
+    camera: SyncMutex<Option<WeakEntity>>,
+
+    /// Ticks the player keeps burning for; negative while immune to re-ignition.
+    remaining_fire_ticks: AtomicI32,
+
+    /// The vehicle the player rides and the entities riding the player.
+    riding: SyncMutex<RidingState>,
+
+    /// Ticks left before the player can use a portal again.
+    /// Vanilla: `Entity.portalCooldown`.
+    portal_cooldown: AtomicI32,
+
+    /// How long the player has been standing in a nether portal.
+    /// Vanilla: `Entity.portalProcess`.
+    portal_process: SyncMutex<Option<PortalProcessor>>,
+
+    /// Active mob effects by effect key.
+    pub active_effects: SyncMutex<FxHashMap<Identifier, MobEffectInstance>>,
+
+    /// The vehicle saved together with the player.
+    pub attached_entities: SyncMutex<AttachedEntities>,
 }
 
 impl Player {
@@ -304,13 +481,7 @@ impl Player {
         let start_pos = DVec3::new(pos.x, self.get_eye_y(), pos.z);
         let (yaw, pitch) = self.rotation();
         let (yaw_rad, pitch_rad) = (f64::from(yaw.to_radians()), f64::from(pitch.to_radians()));
-        // Vanilla: Attributes.BLOCK_INTERACTION_RANGE defaults to 4.5,
-        // creative mode adds +0.5 via CREATIVE_BLOCK_INTERACTION_RANGE_MODIFIER.
-        let block_interaction_range = if self.has_infinite_materials() {
-            5.0
-        } else {
-            4.5
-        };
+        let block_interaction_range = self.get_attribute_value(BLOCK_INTERACTION_RANGE);
         let direction = DVec3::new(
             -yaw_rad.sin() * pitch_rad.cos() * block_interaction_range,
             -pitch_rad.sin() * block_interaction_range,
@@ -355,26 +526,46 @@ impl Player {
             speed: AtomicCell::new(0.1), // Default walking speed
             last_chunk_pos: SyncMutex::new(ChunkPos::new(0, 0)),
             last_tracking_view: SyncMutex::new(None),
+            last_simulation_view: SyncMutex::new(None),
             chunk_sender: SyncMutex::new(ChunkSender::default()),
             client_information: SyncMutex::new(client_information),
+            client_brand: SyncMutex::new(None),
+            client_channels: SyncMutex::new(FxHashSet::default()),
             chat: SyncMutex::new(ChatState::new()),
             game_mode: AtomicCell::new(GameType::Survival),
             prev_game_mode: AtomicCell::new(GameType::Survival),
             inventory: inventory.clone(),
+            ender_chest_inventory: Arc::new(SyncMutex::new(PlayerEnderChestContainer::new())),
             inventory_menu: SyncMutex::new(InventoryMenu::new(inventory)),
             open_menu: SyncMutex::new(None),
             container_counter: AtomicU8::new(0),
             ack_block_changes_up_to: AtomicI32::new(-1),
             teleport_state: SyncMutex::new(TeleportState::new()),
             tick_count: AtomicI32::new(0),
+            attack_strength_ticker: AtomicI32::new(0),
+            fall_fly_ticks: AtomicI32::new(0),
+            auto_spin_attack_ticks: AtomicI32::new(0),
             entity_state: SyncMutex::new(EntityState::new()),
             abilities: SyncMutex::new(Abilities::default()),
             block_breaking: SyncMutex::new(BlockBreakingManager::new()),
+            interaction: PlayerInteractionManager::new(),
             living_base: SyncMutex::new(LivingEntityBase::new()),
+            attributes: SyncMutex::new(default_attributes(vanilla_entities::PLAYER)),
             health_sync: SyncMutex::new(HealthSyncState::new()),
             removed: AtomicBool::new(false),
             level_callback: SyncMutex::new(Arc::new(NullEntityCallback)),
-            experience: SyncMutex::new(Experience::default()),
+            experience: SyncMutex::new(Experience {
+                enchantment_seed: rand::random(),
+                ..Experience::default()
+            }),
+            respawn_config: SyncMutex::new(None),
+            camera: SyncMutex::new(None),
+            remaining_fire_ticks: AtomicI32::new(0),
+            riding: SyncMutex::new(RidingState::default()),
+            portal_cooldown: AtomicI32::new(0),
+            portal_process: SyncMutex::new(None),
+            active_effects: SyncMutex::new(FxHashMap::default()),
+            attached_entities: SyncMutex::new(AttachedEntities::default()),
         }
     }
 
@@ -418,6 +609,96 @@ impl Player {
         self.connection.disconnect_with_reason(reason.into());
     }
 
+    /// Returns the filter mask of a chat message from this player as sent to
+    /// `recipient`, or `None` if the message is hidden from them entirely.
+    ///
+    /// Senders see their own messages unfiltered. Others get the mask if either
+    /// side has text filtering enabled, or if the config filters for everyone.
+    ///
+    /// Vanilla: `ServerPlayer.shouldFilterMessageTo()`.
+    #[must_use]
+    pub fn filter_mask_for(&self, recipient: &Player, mask: &FilterType) -> Option<FilterType> {
+        let filtered = recipient.gameprofile.id != self.gameprofile.id
+            && (STEEL_CONFIG.chat_filter.filter_everyone
+                || self.client_information.lock().text_filtering_enabled
+                || recipient.client_information.lock().text_filtering_enabled);
+        match mask {
+            _ if !filtered => Some(FilterType::PassThrough),
+            FilterType::FullyFiltered => None,
+            mask => Some(mask.clone()),
+        }
+    }
+
+    /// Counts a chat message or command towards the spam limit.
+    ///
+    /// Kicks the player once they exceed it, like vanilla's `detectRateSpam`.
+    /// `cost` is the configured `message_cost` or `command_cost`.
+    pub fn detect_rate_spam(&self, cost: u32) {
+        let config = &STEEL_CONFIG.chat_spam;
+        if !config.enabled {
+            return;
+        }
+        let is_spamming = {
+            let mut chat = self.chat.lock();
+            chat.spam_throttler.increment(cost);
+            !chat.spam_throttler.is_under_threshold(config.threshold)
+        };
+        // TODO: Exempt operators once permission levels exist
+        if is_spamming {
+            self.disconnect(translations::DISCONNECT_SPAM.msg());
+        }
+    }
+
+    /// Counts the ticks spent in a nether portal and finds where it leads once
+    /// the player stood in it long enough.
+    ///
+    /// Vanilla: `Entity.handlePortal()`.
+    fn handle_portal(&self) {
+        // Vanilla: Entity.processPortalCooldown()
+        if self.portal_cooldown.load(Ordering::Relaxed) > 0 {
+            self.portal_cooldown.fetch_sub(1, Ordering::Relaxed);
+        }
+        let mut portal_process = self.portal_process.lock();
+        let Some(process) = portal_process.as_mut() else {
+            return;
+        };
+        // Vanilla: NetherPortalBlock.getPortalTransitionTime()
+        let delay_rule = if self.abilities.lock().invulnerable {
+            PLAYERS_NETHER_PORTAL_CREATIVE_DELAY
+        } else {
+            PLAYERS_NETHER_PORTAL_DEFAULT_DELAY
+        };
+        let transition_time = self
+            .world
+            .get_game_rule(delay_rule)
+            .as_int()
+            .unwrap_or(0)
+            .max(1);
+        let allowed = !self.is_passenger() && self.is_alive();
+        if !process.process_portal_teleportation(allowed, transition_time) {
+            if process.has_expired() {
+                *portal_process = None;
+            }
+            return;
+        }
+        drop(portal_process);
+        self.portal_cooldown
+            .store(DIMENSION_CHANGING_DELAY, Ordering::Relaxed);
+
+        let Some(server) = self.server.upgrade() else {
+            return;
+        };
+        // TODO: Teleport to the destination once players can change dimension
+        match NetherPortalBlock::portal_destination(&server, &self.world, self.position()) {
+            Some((world, portal)) => log::debug!(
+                "{} would travel to the portal at {portal:?} in {}",
+                self.gameprofile.name,
+                world.dimension.key
+            ),
+            None => log::debug!("{} found no portal to travel to", self.gameprofile.name),
+        }
+    }
+
     /// Ticks the player.
     #[expect(
         clippy::cast_possible_truncation,
@@ -426,15 +707,29 @@ impl Player {
     pub fn tick(&self) {
         // Increment local tick counter
         self.tick_count.fetch_add(1, Ordering::Relaxed);
+        self.attack_strength_ticker.fetch_add(1, Ordering::Relaxed);
+
+        self.chat.lock().spam_throttler.tick();
+        self.interaction.tick(self);
+        self.update_player_attributes();
 
         // Reset first_good_position to current position at start of tick (vanilla: resetPosition)
+        let controlled_vehicle = self.controlled_vehicle();
         {
             let mut mv = self.movement.lock();
             mv.first_good_position = *self.position.lock();
             // Sync packet counts for rate limiting (vanilla: knownMovePacketCount = receivedMovePacketCount)
             mv.known_move_packet_count = mv.received_move_packet_count;
+            // Vanilla: the lastVehicle part of ServerGamePacketListenerImpl.tick()
+            mv.last_vehicle = controlled_vehicle.as_ref().map(|vehicle| vehicle.id());
+            if let Some(vehicle) = controlled_vehicle {
+                mv.vehicle_first_good_position = vehicle.position();
+                mv.vehicle_last_good_position = vehicle.position();
+            }
         }
 
+        self.tick_floating();
+
         // Apply gravity to delta_movement (vanilla: applyGravity in Entity.tick/LivingEntity.travel)
         // This must happen after resetPosition so the speed check has the correct expected velocity
         self.apply_gravity();
@@ -446,6 +741,9 @@ impl Player {
             //return;
         }
 
+        self.tick_camera();
+        self.tick_riding();
+
         let current_pos = *self.position.lock();
         let chunk_x = (current_pos.x as i32) >> 4;
         let chunk_z = (current_pos.z as i32) >> 4;
@@ -471,8 +769,18 @@ impl Player {
         } else {
             self.touch_nearby_items();
             self.block_breaking.lock().tick(self, &self.world);
+            self.handle_portal();
+            self.tick_fire();
+            self.tick_effects();
+            self.tick_fall_flying();
+            self.tick_auto_spin_attack();
+            self.update_swimming();
+            self.tick_air_supply();
             self.check_inside_blocks();
+            self.extinguish_in_water_or_rain();
             self.check_below_world();
+            self.tick_maps();
+            self.tick_lodestone_compasses();
 
             // TODO: Implement remaining player ticking logic here
             // - Handling food/health regeneration
@@ -483,8 +791,11 @@ impl Player {
 
         // --- Post-tick (always runs, vanilla does not gate these behind isAlive) ---
         self.broadcast_inventory_changes();
+        self.send_map_updates();
         self.update_pose();
+        self.set_shared_flag(FLAG_ON_FIRE, self.remaining_fire_ticks() > 0);
         self.sync_entity_data();
+        self.sync_attributes();
 
         // Only send CSetHealth when a value actually changed, matching vanilla's
         // `lastSentHealth` / `lastSentFood` / `lastFoodSaturationZero` pattern.
@@ -521,6 +832,95 @@ impl Player {
         self.connection.tick();
     }
 
+    /// Counts down active effects and removes the ones that ran out.
+    ///
+    /// Vanilla: `LivingEntity.tickEffects()`.
+    // TODO: Update the effect particles
+    fn tick_effects(&self) {
+        let mut expired = Vec::new();
+        self.active_effects.lock().retain(|key, effect| {
+            let active = effect.tick();
+            if !active {
+                expired.push(key.clone());
+            }
+            active
+        });
+
+        for key in expired {
+            if let Some(effect_id) = REGISTRY.mob_effects.id_from_key(&key) {
+                self.send_packet(CRemoveMobEffect {
+                    entity_id: self.id,
+                    effect_id: effect_id as i32,
+                });
+            }
+        }
+    }
+
+    /// Adds an effect, or merges it into the active effect of the same type.
+    ///
+    /// Returns true if the effect was added or the active one changed.
+    ///
+    /// Vanilla: `LivingEntity.addEffect()`.
+    // TODO: Check `canBeAffected` and fire an effect event once plugins can listen for it
+    pub fn add_effect(&self, effect: MobEffectInstance) -> bool {
+        let (effect, added) = {
+            let mut effects = self.active_effects.lock();
+            if let Some(active) = effects.get_mut(&effect.effect) {
+                if !active.update(&effect) {
+                    return false;
+                }
+                (active.clone(), false)
+            } else {
+                effects.insert(effect.effect.clone(), effect.clone());
+                (effect, true)
+            }
+        };
+
+        // Vanilla: ServerPlayer.onEffectAdded() blends new effects in on the client
+        self.send_effect(&effect, added);
+        true
+    }
+
+    /// Sends an active effect to the client.
+    ///
+    /// Vanilla: `ClientboundUpdateMobEffectPacket(int, MobEffectInstance, boolean)`.
+    fn send_effect(&self, effect: &MobEffectInstance, blend: bool) {
+        let Some(effect_id) = REGISTRY.mob_effects.id_from_key(&effect.effect) else {
+            return;
+        };
+
+        let mut flags = 0;
+        if effect.ambient {
+            flags |= CUpdateMobEffect::FLAG_AMBIENT;
+        }
+        if effect.show_particles {
+            flags |= CUpdateMobEffect::FLAG_VISIBLE;
+        }
+        if effect.show_icon {
+            flags |= CUpdateMobEffect::FLAG_SHOW_ICON;
+        }
+        if blend {
+            flags |= CUpdateMobEffect::FLAG_BLEND;
+        }
+
+        self.send_packet(CUpdateMobEffect {
+            entity_id: self.id,
+            effect_id: effect_id as i32,
+            amplifier: i32::from(effect.amplifier),
+            duration: effect.duration,
+            flags,
+        });
+    }
+
+    /// Sends every active effect to the client, like after joining.
+    pub fn send_active_effects(&self) {
+        let effects: Vec<MobEffectInstance> =
+            self.active_effects.lock().values().cloned().collect();
+        for effect in &effects {
+            self.send_effect(effect, false);
+        }
+    }
+
     /// Ticks the death animation timer.
     /// Vanilla: `LivingEntity.tickDeath()` (not overridden by `ServerPlayer`).
     fn tick_death(&self) {
@@ -546,6 +946,24 @@ impl Player {
         }
     }
 
+    /// Sends changed attributes to nearby players, including this one.
+    ///
+    /// Vanilla: the attribute part of `ServerEntity.sendDirtyEntityData()`.
+    fn sync_attributes(&self) {
+        let attributes = self.attributes.lock().take_dirty_snapshots();
+        if !attributes.is_empty() {
+            let chunk_pos = *self.last_chunk_pos.lock();
+            self.world.broadcast_to_nearby(
+                chunk_pos,
+                CUpdateAttributes {
+                    entity_id: self.id,
+                    attributes,
+                },
+                None,
+            );
+        }
+    }
+
     /// Syncs dirty entity data to nearby players.
     fn sync_entity_data(&self) {
         if let Some(dirty_values) = self.entity_data.lock().pack_dirty() {
@@ -597,10 +1015,31 @@ impl Player {
         }
     }
 
-    /// Handles a custom payload packet.
-    #[expect(clippy::unused_self, reason = "this is an api function")]
-    pub fn handle_custom_payload(&self, packet: SCustomPayload) {
-        log::info!("Hello from the other side! {packet:?}");
+    /// Handles a custom payload packet, see
+    /// [`PayloadChannels`](crate::server::payload_channels::PayloadChannels).
+    pub fn handle_custom_payload(self: &Arc<Self>, packet: SCustomPayload, server: &Server) {
+        let channel = packet.identifier;
+        let payload = packet.payload.0.as_slice();
+        if channel == BRAND_CHANNEL {
+            match decode_brand(payload) {
+                Some(brand) => self.set_client_brand(brand),
+                None => log::debug!("{} sent a malformed brand", self.gameprofile.name),
+            }
+        } else if channel == REGISTER_CHANNEL {
+            self.client_channels
+                .lock()
+                .extend(decode_channel_list(payload));
+        } else if channel == UNREGISTER_CHANNEL {
+            let mut channels = self.client_channels.lock();
+            for unregistered in decode_channel_list(payload) {
+                channels.remove(&unregistered);
+            }
+        } else if !server.payload_channels.handle(self, &channel, payload) {
+            log::debug!(
+                "{} sent a payload on unknown channel {channel}",
+                self.gameprofile.name
+            );
+        }
     }
 
     /// Handles the end of a client tick.
@@ -609,238 +1048,460 @@ impl Player {
         //log::info!("Hello from the other side!");
     }
 
-    /// Gets the next `messages_received` counter and increments it
-    pub fn get_and_increment_messages_received(&self) -> i32 {
-        let mut chat = self.chat.lock();
-        let val = chat.messages_received;
-        chat.messages_received += 1;
-        val
+    /// Applies the acknowledgements of a chat message or signed command,
+    /// disconnecting the player if they don't match the messages sent to them.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.unpackAndApplyLastSeen()`.
+    fn apply_last_seen_update(
+        &self,
+        offset: i32,
+        acknowledged: [u8; 3],
+        checksum: u8,
+    ) -> Option<LastSeen> {
+        let result =
+            self.chat
+                .lock()
+                .message_validator
+                .apply_update(acknowledged, offset, checksum);
+        match result {
+            Ok(signatures) => Some(LastSeen::new(signatures)),
+            Err(err) => {
+                log::warn!(
+                    "Failed to validate message acknowledgements from {}: {err}",
+                    self.gameprofile.name
+                );
+                self.disconnect(translations::MULTIPLAYER_DISCONNECT_CHAT_VALIDATION_FAILED.msg());
+                None
+            }
+        }
     }
 
-    fn verify_chat_signature(
+    /// Checks the signature of a chat message or signed command argument and
+    /// moves the message chain past it. Players without a chat session send
+    /// unsigned messages, unless secure chat is enforced.
+    ///
+    /// The signature itself is checked on the server's verification pool, so
+    /// this doesn't block the network task.
+    ///
+    /// Vanilla: `SignedMessageChain.Decoder.unpack()`.
+    async fn unpack_message(
         &self,
-        packet: &SChat,
-    ) -> Result<(message_chain::SignedMessageLink, LastSeen), String> {
+        signature: Option<[u8; 256]>,
+        body: SignedMessageBody,
+    ) -> Result<PlayerChatMessage, ChainError> {
         const MESSAGE_EXPIRES_AFTER: Duration = Duration::from_mins(5);
 
-        let mut chat = self.chat.lock();
-        let session = chat.chat_session.clone().ok_or("No chat session")?;
-        let signature = packet.signature.as_ref().ok_or("No signature present")?;
+        let (link, validator, payload, signature) = {
+            let mut chat = self.chat.lock();
+            let Some(session) = chat.chat_session.clone() else {
+                if STEEL_CONFIG.enforce_secure_chat {
+                    return Err(ChainError::MissingProfileKey);
+                }
+                return Ok(PlayerChatMessage::unsigned(
+                    self.gameprofile.id,
+                    body.content,
+                ));
+            };
+            let Some(signature) = signature else {
+                return Err(ChainError::MissingProfileKey);
+            };
+            if session
+                .profile_public_key
+                .data()
+                .has_expired_with_grace(profile_key::EXPIRY_GRACE_PERIOD)
+            {
+                return Err(ChainError::ExpiredProfileKey);
+            }
 
-        if session
-            .profile_public_key
-            .data()
-            .has_expired_with_grace(profile_key::EXPIRY_GRACE_PERIOD)
-        {
-            return Err("Profile key has expired".to_string());
-        }
+            let chain = chat.message_chain.as_mut().ok_or(ChainError::ChainBroken)?;
+            let link = chain.validate(&body)?;
+            let payload = collect_bytes(&MessageSignatureUpdater::new(&link, &body))?;
+            let validator = session.profile_public_key.create_signature_validator();
+            (link, validator, payload, signature)
+        };
 
-        let chain = chat.message_chain.as_mut().ok_or("No message chain")?;
+        let verification = match self.server.upgrade() {
+            Some(server) => {
+                server
+                    .verification_pool
+                    .run(move || validator.validate(&payload.as_slice(), &signature))
+                    .await
+            }
+            None => validator.validate(&payload.as_slice(), &signature),
+        };
 
-        if chain.is_broken() {
-            return Err("Message chain is broken".to_string());
+        let mut chat = self.chat.lock();
+        let chain = chat.message_chain.as_mut().ok_or(ChainError::ChainBroken)?;
+        if !matches!(verification, Ok(true)) {
+            chain.break_chain();
+            return Err(ChainError::InvalidSignature);
         }
+        chain.advance(&link);
 
-        let timestamp =
-            UNIX_EPOCH + Duration::from_millis(packet.timestamp.try_into().unwrap_or(0));
-
-        let now = SystemTime::now();
-        let message_age = now
-            .duration_since(timestamp)
-            .unwrap_or(Duration::from_secs(0));
-
+        let message_age = SystemTime::now()
+            .duration_since(body.time_stamp)
+            .unwrap_or_default();
         if message_age > MESSAGE_EXPIRES_AFTER {
-            return Err(format!(
-                "Message expired (age: {}s, max: 300s)",
-                message_age.as_secs()
-            ));
+            log::warn!(
+                "Received expired chat: '{}'. Is the client/server system time unsynchronized?",
+                body.content
+            );
         }
 
-        let last_seen_signatures = chat
-            .message_validator
-            .apply_update(packet.acknowledged, packet.offset, packet.checksum)
-            .map_err(|e| {
-                log::error!("Message acknowledgment validation failed: {e}");
-                e
-            })?;
-
-        let last_seen = LastSeen::new(last_seen_signatures);
-
-        let body = message_chain::SignedMessageBody::new(
-            packet.message.clone(),
-            timestamp,
-            packet.salt,
-            last_seen,
-        );
-
-        let chain = chat.message_chain.as_mut().ok_or("No message chain")?;
-        let link = chain
-            .validate_and_advance(&body)
-            .map_err(|e| format!("Chain validation failed: {e}"))?;
-
-        let updater = message_chain::MessageSignatureUpdater::new(&link, &body);
-        let validator = session.profile_public_key.create_signature_validator();
-
-        let is_valid = SignatureValidator::validate(&validator, &updater, signature)
-            .map_err(|e| format!("Signature validation error: {e}"))?;
+        Ok(PlayerChatMessage {
+            link,
+            signature: Some(Box::new(signature)),
+            body,
+        })
+    }
 
-        if is_valid {
-            Ok((link, body.last_seen.clone()))
+    /// Tells the player why their message was rejected. Messages out of order
+    /// can't continue the chain, so they disconnect the player.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.handleMessageDecodeFailure()`.
+    fn handle_message_decode_failure(&self, err: &ChainError) {
+        log::warn!(
+            "Failed to update secure chat state for {}: '{err}'",
+            self.gameprofile.name
+        );
+        if matches!(err, ChainError::OutOfOrderChat) {
+            self.disconnect(translations::MULTIPLAYER_DISCONNECT_OUT_OF_ORDER_CHAT.msg());
         } else {
-            Err("Invalid signature".to_string())
+            self.send_message(&err.reason().color(Color::Red));
         }
     }
 
     /// Handles a chat message from the player.
-    #[expect(
-        clippy::too_many_lines,
-        reason = "chat verification, signing, and broadcast form a single logical flow; splitting would hurt readability"
-    )]
-    pub fn handle_chat(&self, packet: SChat, player: Arc<Player>) {
-        let chat_message = packet.message.clone();
-
-        let verification_result = if let Some(_signature) = &packet.signature {
-            match self.verify_chat_signature(&packet) {
-                Ok((link, last_seen)) => {
-                    // Don't add to cache here - will be added during broadcast
-                    // to avoid cache state mismatch with client
-                    Some(Ok((link, last_seen)))
-                }
-                Err(err) => {
-                    log::warn!(
-                        "Player {} sent message with invalid signature: {err}",
-                        self.gameprofile.name
-                    );
-                    Some(Err(err))
-                }
+    pub async fn handle_chat(&self, packet: SChat, player: Arc<Player>) {
+        let Some(last_seen) =
+            self.apply_last_seen_update(packet.offset, packet.acknowledged, packet.checksum)
+        else {
+            return;
+        };
+        let timestamp =
+            UNIX_EPOCH + Duration::from_millis(packet.timestamp.try_into().unwrap_or(0));
+        let body = SignedMessageBody::new(packet.message, timestamp, packet.salt, last_seen);
+        let message = match self.unpack_message(packet.signature, body).await {
+            Ok(message) => message,
+            Err(err) => {
+                self.handle_message_decode_failure(&err);
+                return;
             }
-        } else {
-            None
         };
 
-        if STEEL_CONFIG.enforce_secure_chat {
-            match &verification_result {
-                Some(Ok(_)) => {}
-                Some(Err(err)) => {
-                    self.disconnect(format!("Chat message validation failed: {err}"));
-                    return;
-                }
-                None => {
-                    self.disconnect(
-                        "Secure chat is enforced on this server, but your message was not signed",
-                    );
-                    return;
-                }
-            }
+        let mut event = ChatEvent::new(Arc::clone(&player), message.content().to_owned());
+        if !EVENTS.post_cancellable(&mut event) {
+            return;
         }
+        // The mask leaves the signed text intact, so filtering keeps signatures valid
+        let filter_mask = chat_filter::filter_message(&player, message.content());
+
+        steel_utils::chat!(player.gameprofile.name.clone(), "{}", message.content());
+        if let Some(server) = self.server.upgrade() {
+            server.broadcast_chat_message(
+                &message,
+                &player,
+                Some(&event.content),
+                &filter_mask,
+                &self.bind_chat_type(vanilla_chat_types::CHAT, None),
+            );
+        }
+    }
 
-        let signature = if matches!(verification_result, Some(Ok(_))) {
-            packet.signature.map(|sig| Box::new(sig) as Box<[u8]>)
-        } else {
-            None
+    /// Handles a command without signed arguments. Commands with message
+    /// arguments must come signed, so sending one unsigned disconnects the player.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.performUnsignedChatCommand()`.
+    pub fn handle_chat_command(&self, packet: SChatCommand, player: Arc<Player>) {
+        let Some(server) = self.server.upgrade() else {
+            return;
         };
+        let sender = CommandSender::Player(player);
+        let dispatcher = server.command_dispatcher.read();
+        if !dispatcher
+            .signable_arguments(sender.clone(), &packet.command, &server)
+            .is_empty()
+        {
+            log::error!(
+                "Received unsigned command packet from {}, but the command requires signable arguments",
+                self.gameprofile.name
+            );
+            self.disconnect(translations::MULTIPLAYER_DISCONNECT_CHAT_VALIDATION_FAILED.msg());
+            return;
+        }
+        dispatcher.handle_command(sender, packet.command, &server);
+    }
 
-        let sender_index = {
-            let mut chat = player.chat.lock();
-            let idx = chat.messages_sent;
-            chat.messages_sent += 1;
-            idx
+    /// Handles a command whose message arguments the player signed, such as
+    /// `/msg`. The signatures are checked before the command runs.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.handleSignedChatCommand()`.
+    pub async fn handle_chat_command_signed(
+        &self,
+        packet: SChatCommandSigned,
+        player: Arc<Player>,
+    ) {
+        let update = &packet.last_seen;
+        let Some(last_seen) =
+            self.apply_last_seen_update(update.offset.0, update.acknowledged, update.checksum)
+        else {
+            return;
+        };
+        let Some(server) = self.server.upgrade() else {
+            return;
         };
 
-        let registry_id = vanilla_chat_types::CHAT.id() as i32;
-
-        let chat_packet = CPlayerChat::new(
-            0,
-            player.gameprofile.id,
-            sender_index,
-            signature.clone(),
-            chat_message.clone(),
-            packet.timestamp,
-            packet.salt,
-            Box::new([]),
-            Some(TextComponent::plain(chat_message.clone())),
-            FilterType::PassThrough,
-            ChatTypeBound {
-                registry_id,
-                sender_name: TextComponent::plain(player.gameprofile.name.clone())
-                    .insertion(player.gameprofile.name.clone())
-                    .click_event(ClickEvent::suggest_command(format!(
-                        "/tell {} ",
-                        player.gameprofile.name
-                    )))
-                    .hover_event(HoverEvent::show_entity(
-                        "minecraft:player",
-                        self.uuid(),
-                        Some(player.gameprofile.name.clone()),
-                    )),
-                target_name: None,
-            },
+        let sender = CommandSender::Player(player);
+        let arguments = server.command_dispatcher.read().signable_arguments(
+            sender.clone(),
+            &packet.command,
+            &server,
         );
+        match self
+            .collect_signed_arguments(&packet, arguments, last_seen)
+            .await
+        {
+            Ok(signed) => server.command_dispatcher.read().handle_signed_command(
+                sender,
+                packet.command,
+                &server,
+                CommandSigning::Signed(signed),
+            ),
+            Err(err) => self.handle_message_decode_failure(&err),
+        }
+    }
 
-        if let Some(sig_box) = &signature {
-            if sig_box.len() == 256 {
-                let mut sig_array = [0u8; 256];
-                sig_array.copy_from_slice(&sig_box[..]);
-
-                let last_seen = if let Some(Ok((_, ref last_seen))) = verification_result {
-                    last_seen.clone()
-                } else {
-                    LastSeen::default()
-                };
+    /// Unpacks the message arguments of a signed command. The client signs
+    /// either all of them or none, and only the arguments the command has.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.collectSignedArguments()`.
+    async fn collect_signed_arguments(
+        &self,
+        packet: &SChatCommandSigned,
+        arguments: Vec<(&'static str, String)>,
+        last_seen: LastSeen,
+    ) -> Result<FxHashMap<&'static str, PlayerChatMessage>, ChainError> {
+        let mut signed = FxHashMap::default();
+        if packet.argument_signatures.is_empty() {
+            for (name, value) in arguments {
+                let message = self
+                    .unpack_message(None, SignedMessageBody::unsigned(value))
+                    .await?;
+                signed.insert(name, message);
+            }
+            return Ok(signed);
+        }
 
-                steel_utils::chat!(player.gameprofile.name.clone(), "{}", chat_message);
-                if let Some(server) = self.server.upgrade() {
-                    for world in server.worlds.values() {
-                        world.broadcast_chat(
-                            chat_packet.clone(),
-                            Arc::clone(&player),
-                            last_seen.clone(),
-                            Some(&sig_array),
-                        );
-                    }
-                }
-            } else if let Some(server) = self.server.upgrade() {
-                for world in server.worlds.values() {
-                    world.broadcast_unsigned_chat(
-                        chat_packet.clone(),
-                        &player.gameprofile.name,
-                        &chat_message,
-                    );
+        let timestamp =
+            UNIX_EPOCH + Duration::from_millis(packet.timestamp.try_into().unwrap_or(0));
+        for argument in &packet.argument_signatures {
+            let Some((name, value)) = arguments.iter().find(|(name, _)| *name == argument.name)
+            else {
+                if let Some(chain) = self.chat.lock().message_chain.as_mut() {
+                    chain.break_chain();
                 }
-            }
-        } else if let Some(server) = self.server.upgrade() {
-            for world in server.worlds.values() {
-                world.broadcast_unsigned_chat(
-                    chat_packet.clone(),
-                    &player.gameprofile.name,
-                    &chat_message,
-                );
-            }
+                return Err(ChainError::InvalidCommandSignature);
+            };
+            let body =
+                SignedMessageBody::new(value.clone(), timestamp, packet.salt, last_seen.clone());
+            let message = self.unpack_message(Some(argument.signature), body).await?;
+            signed.insert(*name, message);
         }
-    }
 
-    /// Sends a system message to the player.
-    pub fn send_message(&self, text: &TextComponent) {
-        self.send_packet(CSystemChatMessage::new(text, self, false));
+        if arguments.iter().any(|(name, _)| !signed.contains_key(name)) {
+            return Err(ChainError::InvalidCommandSignature);
+        }
+        Ok(signed)
     }
 
-    const fn is_invalid_position(x: f64, y: f64, z: f64, rot_x: f32, rot_y: f32) -> bool {
-        if x.is_nan() || y.is_nan() || z.is_nan() {
-            return true;
-        }
+    /// Sends a chat message from a player to this player, keeping track of its
+    /// signature so later messages can refer to it.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.sendPlayerChatMessage()`.
+    pub fn send_chat_message(
+        &self,
+        message: &PlayerChatMessage,
+        unsigned_content: Option<TextComponent>,
+        filter_type: FilterType,
+        chat_type: ChatTypeBound,
+    ) {
+        const MAX_TRACKED_MESSAGES: usize = 4096;
+
+        let (packet, too_many_pending) = {
+            let mut chat = self.chat.lock();
+            let global_index = chat.messages_received;
+            chat.messages_received += 1;
+            // Previous messages are indexed before this one enters the cache,
+            // like vanilla's pack() then push()
+            let previous_messages = chat
+                .signature_cache
+                .index_previous_messages(&message.body.last_seen);
+            let packet = CPlayerChat::new(
+                global_index,
+                message.link.sender,
+                message.link.index,
+                message
+                    .signature
+                    .as_ref()
+                    .map(|signature| Box::new(**signature) as Box<[u8]>),
+                message.body.content.clone(),
+                message.body.timestamp_millis(),
+                message.body.salt,
+                previous_messages,
+                unsigned_content,
+                filter_type,
+                chat_type,
+            );
+            // Clients only track signed messages
+            if let Some(signature) = &message.signature {
+                chat.signature_cache
+                    .push(&message.body.last_seen, Some(&**signature));
+                chat.message_validator
+                    .add_pending(Some(Box::new(**signature) as Box<[u8]>));
+            }
+            let too_many_pending = chat.message_validator.tracked_count() > MAX_TRACKED_MESSAGES;
+            (packet, too_many_pending)
+        };
 
-        if !rot_x.is_finite() || !rot_y.is_finite() {
-            return true;
+        self.send_packet(packet);
+        if too_many_pending {
+            self.disconnect(translations::MULTIPLAYER_DISCONNECT_TOO_MANY_PENDING_CHATS.msg());
         }
-
-        false
     }
 
-    /// Checks if we're awaiting a teleport confirmation and handles timeout/resend.
+    /// Binds a chat type to this player as the sender of a message.
     ///
-    /// Returns `true` if awaiting teleport (movement should be rejected),
-    /// `false` if normal movement processing should continue.
-    fn update_awaiting_teleport(&self) -> bool {
+    /// Vanilla: `ChatType.bind()`.
+    #[must_use]
+    pub fn bind_chat_type(
+        &self,
+        chat_type: ChatTypeRef,
+        target_name: Option<TextComponent>,
+    ) -> ChatTypeBound {
+        ChatTypeBound {
+            registry_id: chat_type.id() as i32,
+            sender_name: self.display_name(),
+            target_name,
+        }
+    }
+
+    /// The name of this player as shown in chat, suggesting a private message
+    /// when clicked.
+    ///
+    /// Vanilla: `Player.getDisplayName()`.
+    #[must_use]
+    pub fn display_name(&self) -> TextComponent {
+        let name = &self.gameprofile.name;
+        TextComponent::plain(name.clone())
+            .insertion(name.clone())
+            .click_event(ClickEvent::suggest_command(format!("/tell {name} ")))
+            .hover_event(HoverEvent::show_entity(
+                "minecraft:player",
+                self.uuid(),
+                Some(name.clone()),
+            ))
+    }
+
+    /// Translates the translatable components in `text` into the player's
+    /// language, as far as the server is set up to.
+    ///
+    /// See [`Languages::localize`](crate::server::languages::Languages::localize).
+    #[must_use]
+    pub fn localize(&self, text: &TextComponent) -> TextComponent {
+        let language = self.client_information.lock().language.clone();
+        LANGUAGES.localize(text, &language, STEEL_CONFIG.translation.players)
+    }
+
+    /// Sends a system message to the player.
+    pub fn send_message(&self, text: &TextComponent) {
+        self.send_packet(CSystemChatMessage::new(&self.localize(text), self, false));
+    }
+
+    /// Sends a message to the player's action bar.
+    pub fn send_overlay_message(&self, text: &TextComponent) {
+        self.send_packet(CSystemChatMessage::new(&self.localize(text), self, true));
+    }
+
+    /// Shows a title to the player, with an optional subtitle below it.
+    ///
+    /// `times` stay in effect for later titles until [`Self::reset_title`].
+    /// Without them the title uses the times the player last got.
+    pub fn send_title(
+        &self,
+        title: &TextComponent,
+        subtitle: Option<&TextComponent>,
+        times: Option<TitleTimes>,
+    ) {
+        if let Some(times) = times {
+            self.send_title_times(times);
+        }
+        // The client only shows the subtitle once the title arrives
+        if let Some(subtitle) = subtitle {
+            self.send_subtitle(subtitle);
+        }
+        self.send_packet(CSetTitleText::new(&self.localize(title), self));
+    }
+
+    /// Sets the subtitle shown with the next title, replacing the one of a
+    /// title that is currently shown.
+    pub fn send_subtitle(&self, subtitle: &TextComponent) {
+        self.send_packet(CSetSubtitleText::new(&self.localize(subtitle), self));
+    }
+
+    /// Sets how long titles fade in, stay and fade out.
+    pub fn send_title_times(&self, times: TitleTimes) {
+        self.send_packet(CSetTitlesAnimation {
+            fade_in: times.fade_in,
+            stay: times.stay,
+            fade_out: times.fade_out,
+        });
+    }
+
+    /// Hides the title currently shown, keeping the title times.
+    pub fn clear_title(&self) {
+        self.send_packet(CClearTitles { reset_times: false });
+    }
+
+    /// Hides the title currently shown and resets the title times to
+    /// [`TitleTimes::DEFAULT`].
+    pub fn reset_title(&self) {
+        self.send_packet(CClearTitles { reset_times: true });
+    }
+
+    /// Shows a message above the player's hotbar.
+    ///
+    /// Unlike [`Self::send_overlay_message`], this uses the dedicated action
+    /// bar packet, like `/title actionbar` does.
+    pub fn send_action_bar(&self, text: &TextComponent) {
+        self.send_packet(CSetActionBarText::new(&self.localize(text), self));
+    }
+
+    /// Stops sounds playing for the player. Only sounds in `source` and with
+    /// the name `sound` stop when given.
+    pub fn stop_sound(&self, source: Option<SoundSource>, sound: Option<Identifier>) {
+        self.send_packet(CStopSound {
+            source,
+            name: sound,
+        });
+    }
+
+    const fn is_invalid_position(x: f64, y: f64, z: f64, rot_x: f32, rot_y: f32) -> bool {
+        if x.is_nan() || y.is_nan() || z.is_nan() {
+            return true;
+        }
+
+        if !rot_x.is_finite() || !rot_y.is_finite() {
+            return true;
+        }
+
+        false
+    }
+
+    /// Checks if we're awaiting a teleport confirmation and handles timeout/resend.
+    ///
+    /// Returns `true` if awaiting teleport (movement should be rejected),
+    /// `false` if normal movement processing should continue.
+    fn update_awaiting_teleport(&self) -> bool {
         let mut tp = self.teleport_state.lock();
         let Some(pos) = tp.awaiting_position else {
             tp.teleport_time = self.tick_count.load(Ordering::Relaxed);
@@ -895,6 +1556,80 @@ impl Player {
         true
     }
 
+    /// Tracks fall distance and lets the block landed on react to the fall.
+    ///
+    /// Matches vanilla `ServerPlayer.doCheckFallDamage()` and `Entity.checkFallDamage()`.
+    fn check_fall_damage(&self, dy: f64, on_ground: bool) {
+        // TODO: Also reset in cobwebs, powder snow and other blocks that slow falling
+        if self.resets_fall_distance() {
+            self.movement.lock().fall_distance = 0.0;
+            return;
+        }
+        if on_ground {
+            let fall_distance = {
+                let mut mv = self.movement.lock();
+                let fall_distance = mv.fall_distance;
+                mv.fall_distance = 0.0;
+                fall_distance
+            };
+            if fall_distance > 0.0 {
+                let pos = self.get_on_pos_legacy();
+                let state = self.world.get_block_state(pos);
+                BLOCK_BEHAVIORS.get_behavior(state.get_block()).fall_on(
+                    state,
+                    &self.world,
+                    pos,
+                    self,
+                    fall_distance,
+                );
+            }
+        } else if dy < 0.0 {
+            self.movement.lock().fall_distance -= dy;
+        }
+    }
+
+    /// Returns true if the player's current surroundings stop a fall.
+    ///
+    /// Vanilla resets the fall distance in several places: `Entity.updateInWaterStateAndDoFluidPushing()`,
+    /// `LivingEntity.handleOnClimbable()` and the slow falling check in `LivingEntity.travel()`.
+    fn resets_fall_distance(&self) -> bool {
+        if self.is_in_water() {
+            return true;
+        }
+        let feet = self.world.get_block_state(BlockPos::from(self.position()));
+        if REGISTRY.blocks.is_in_tag(feet.get_block(), &CLIMBABLE_TAG) {
+            return true;
+        }
+        self.get_delta_movement().y <= 0.0
+            && self
+                .active_effects
+                .lock()
+                .contains_key(&Identifier::vanilla_static("slow_falling"))
+    }
+
+    /// Gets the block the player is standing on, looking 0.2 blocks below the feet.
+    ///
+    /// Fences, walls and gates stick out above their block space, so when the
+    /// feet are in air above one of them, that block is used instead.
+    /// Matches vanilla `Entity.getOnPosLegacy()`.
+    fn get_on_pos_legacy(&self) -> BlockPos {
+        let position = *self.position.lock();
+        let pos = BlockPos::new(
+            position.x.floor() as i32,
+            (position.y - 0.2).floor() as i32,
+            position.z.floor() as i32,
+        );
+        if !self.world.get_block_state(pos).is_air() {
+            return pos;
+        }
+        let below = pos.below();
+        let below_block = self.world.get_block_state(below).get_block();
+        let is_tall = REGISTRY.blocks.is_in_tag(below_block, &FENCES_TAG)
+            || REGISTRY.blocks.is_in_tag(below_block, &WALLS_TAG)
+            || REGISTRY.blocks.is_in_tag(below_block, &FENCE_GATES_TAG);
+        if is_tall { below } else { pos }
+    }
+
     /// Handles a move player packet.
     ///
     /// Matches vanilla `ServerGamePacketListenerImpl.handleMovePlayer()`.
@@ -923,6 +1658,14 @@ impl Player {
             return;
         }
 
+        // Passengers move with their vehicle, only the rotation comes from the client
+        if self.is_passenger() {
+            if packet.has_rot {
+                self.rotation.store((packet.y_rot, packet.x_rot));
+            }
+            return;
+        }
+
         if !self.client_loaded.load(Ordering::Relaxed) {
             return;
         }
@@ -939,6 +1682,7 @@ impl Player {
             let es = self.entity_state.lock();
             (es.sleeping, es.fall_flying, es.on_ground, es.crouching)
         };
+        let is_low_pose = self.has_low_pose();
         // Skip movement checks when tick rate is frozen (vanilla: tickRateManager().runsNormally())
         let tick_frozen = !self.world.tick_runs_normally();
 
@@ -997,6 +1741,7 @@ impl Player {
                 };
 
                 // Validate movement using physics simulation
+                let config = &STEEL_CONFIG.movement_checks;
                 let mut validation = movement::validate_movement(
                     &self.world,
                     &movement::MovementInput {
@@ -1006,13 +1751,32 @@ impl Player {
                         expected_velocity_sq,
                         delta_packets,
                         is_fall_flying,
+                        is_low_pose,
                         skip_checks,
+                        no_physics: is_spectator,
                         in_impulse_grace,
                         is_crouching,
                         on_ground: was_on_ground,
                     },
+                    config,
                 );
 
+                if validation.failure_reason == Some(movement::MovementFailure::TooFast) {
+                    log::warn!(
+                        "{} moved too quickly! {},{},{}",
+                        self.gameprofile.name,
+                        target_pos.x - first_good.x,
+                        target_pos.y - first_good.y,
+                        target_pos.z - first_good.z
+                    );
+                } else if validation.moved_wrongly {
+                    log::warn!(
+                        "{} moved wrongly! {}",
+                        self.gameprofile.name,
+                        validation.error_dist_sq.sqrt()
+                    );
+                }
+
                 if !validation.is_valid {
                     // Teleport back to start position
                     let (yaw, pitch) = prev_rot;
@@ -1020,14 +1784,38 @@ impl Player {
                     return;
                 }
 
+                // Vanilla: clientIsFloating, checked every tick in tick_floating()
+                let client_is_floating = target_pos.y - last_good.y >= -0.03125
+                    && !is_fall_flying
+                    && !self.is_auto_spin_attack()
+                    && !is_spectator
+                    && !config.allow_flight
+                    && !self.abilities.lock().may_fly
+                    && !self
+                        .active_effects
+                        .lock()
+                        .contains_key(&Identifier::vanilla_static("levitation"))
+                    && movement::no_blocks_around(&self.world, target_pos);
+
                 // Movement accepted - update last good position
-                self.movement.lock().last_good_position = target_pos;
+                {
+                    let mut mv = self.movement.lock();
+                    mv.last_good_position = target_pos;
+                    mv.client_is_floating = client_is_floating;
+                }
 
                 // Zero Y velocity when landing (vanilla: Block.updateEntityMovementAfterFallOn)
                 // This prevents gravity from accumulating while on the ground
                 if !was_on_ground && packet.on_ground {
                     validation.move_delta.y = 0.0;
                 }
+                if is_fall_flying && !is_spectator {
+                    self.check_fly_into_wall(
+                        target_pos,
+                        self.get_delta_movement(),
+                        validation.move_delta,
+                    );
+                }
                 // Update velocity based on actual movement (vanilla: handlePlayerKnownMovement)
                 self.set_delta_movement(validation.move_delta);
 
@@ -1055,6 +1843,15 @@ impl Player {
             self.rotation.store((packet.y_rot, packet.x_rot));
         }
 
+        if !is_spectator {
+            let dy = if packet.has_pos {
+                packet.position.y - start_pos.y
+            } else {
+                0.0
+            };
+            self.check_fall_damage(dy, packet.on_ground);
+        }
+
         // Broadcast movement to other players
         let pos = if packet.has_pos {
             packet.position
@@ -1229,7 +2026,7 @@ impl Player {
     /// Handles a chat session update packet from the client.
     ///
     /// This validates the player's profile key and initializes signed chat if valid.
-    pub fn handle_chat_session_update(&self, packet: SChatSessionUpdate) {
+    pub async fn handle_chat_session_update(&self, packet: SChatSessionUpdate) {
         log::info!("Player {} sent chat session update", self.gameprofile.name);
 
         // Convert the packet data to profile key data
@@ -1258,14 +2055,27 @@ impl Player {
         let profile_key_data =
             profile_key::ProfilePublicKeyData::new(expires_at, public_key, packet.key_signature);
 
-        let validator = Box::new(NoValidation) as Box<dyn SignatureValidator>;
+        let validator = Box::new(NoValidation) as Box<dyn SignatureValidator + Send>;
 
         let session_data = profile_key::RemoteChatSessionData {
             session_id: packet.session_id,
             profile_public_key: profile_key_data,
         };
 
-        match session_data.validate(self.gameprofile.id, &*validator) {
+        let Some(server) = self.server.upgrade() else {
+            return;
+        };
+        let profile_id = self.gameprofile.id;
+        let result = match server
+            .verification_pool
+            .run(move || Ok(session_data.validate(profile_id, &*validator)))
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => Err(err.into()),
+        };
+
+        match result {
             Ok(session) => {
                 self.set_chat_session(session);
             }
@@ -1336,6 +2146,10 @@ impl Player {
         // Update abilities based on new game mode (mirrors vanilla GameType.updatePlayerAbilities)
         self.abilities.lock().update_for_game_mode(gamemode);
 
+        if gamemode != GameType::Spectator {
+            self.set_camera(None);
+        }
+
         // Send abilities first (vanilla sends this before game event)
         self.send_abilities();
 
@@ -1391,19 +2205,77 @@ impl Player {
     }
 
     /// Handles a container button click packet (e.g., enchanting table buttons).
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleContainerButtonClick`.
     pub fn handle_container_button_click(&self, packet: SContainerButtonClick) {
-        log::debug!(
-            "Player {} clicked button {} in container {}",
-            self.gameprofile.name,
-            packet.button_id,
-            packet.container_id
-        );
-        // TODO: Implement container button click handling
-        // This is used for things like:
-        // - Enchanting table level selection
-        // - Stonecutter recipe selection
-        // - Loom pattern selection
-        // - Lectern page turning
+        if self.game_mode.load() == GameType::Spectator {
+            return;
+        }
+
+        let mut open_menu = self.open_menu.lock();
+        let Some(menu) = open_menu.as_mut() else {
+            return;
+        };
+        if i32::from(menu.container_id()) != packet.container_id {
+            return;
+        }
+        if !menu.still_valid() {
+            log::debug!(
+                "Player {} interacted with invalid menu {}",
+                self.gameprofile.name,
+                packet.container_id
+            );
+            return;
+        }
+
+        if menu.click_menu_button(self, packet.button_id) {
+            menu.behavior_mut().broadcast_changes(&self.connection);
+        }
+    }
+
+    /// Handles the name a player typed into an anvil menu.
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleRenameItem`.
+    pub fn handle_rename_item(&self, packet: SRenameItem) {
+        let mut open_menu = self.open_menu.lock();
+        let Some(menu) = open_menu.as_mut().and_then(|menu| menu.as_anvil_menu_mut()) else {
+            return;
+        };
+        if !menu.still_valid() {
+            log::debug!(
+                "Player {} interacted with invalid anvil menu",
+                self.gameprofile.name
+            );
+            return;
+        }
+
+        if menu.set_item_name(&packet.name, self) {
+            menu.behavior_mut().broadcast_changes(&self.connection);
+        }
+    }
+
+    /// Handles the effects a player confirmed in a beacon menu.
+    ///
+    /// Based on Java's `ServerGamePacketListenerImpl::handleSetBeaconPacket`.
+    pub fn handle_set_beacon(&self, packet: SSetBeacon) {
+        let mut open_menu = self.open_menu.lock();
+        let Some(menu) = open_menu
+            .as_mut()
+            .and_then(|menu| menu.as_beacon_menu_mut())
+        else {
+            return;
+        };
+        if !menu.still_valid() {
+            log::debug!(
+                "Player {} interacted with invalid beacon menu",
+                self.gameprofile.name
+            );
+            return;
+        }
+
+        let effect =
+            |id: Option<VarInt>| id.and_then(|id| REGISTRY.mob_effects.by_id(id.0 as usize));
+        menu.update_effects(effect(packet.primary), effect(packet.secondary));
     }
 
     /// Handles a container click packet (slot interaction).
@@ -1471,6 +2343,7 @@ impl Player {
             has_infinite_materials,
             self,
         );
+        menu.slots_changed(self);
 
         // Update remote slots from the client's perception
         for (slot, hash) in packet.changed_slots {
@@ -1596,32 +2469,54 @@ impl Player {
         }
     }
 
+    /// Adds or removes the creative mode interaction range modifiers.
+    ///
+    /// Vanilla: `Player.updatePlayerAttributes()`.
+    fn update_player_attributes(&self) {
+        let creative = self.has_infinite_materials();
+        let mut attributes = self.attributes.lock();
+        for (attribute, modifier) in [
+            (
+                BLOCK_INTERACTION_RANGE,
+                CREATIVE_BLOCK_INTERACTION_RANGE_MODIFIER,
+            ),
+            (
+                ENTITY_INTERACTION_RANGE,
+                CREATIVE_ENTITY_INTERACTION_RANGE_MODIFIER,
+            ),
+        ] {
+            if let Some(instance) = attributes.get_instance_mut(attribute) {
+                if creative {
+                    instance.add_or_update_transient_modifier(modifier);
+                } else {
+                    instance.remove_modifier(&modifier.id);
+                }
+            }
+        }
+    }
+
     /// Returns true if player is within block interaction range.
     ///
-    /// Uses eye position and AABB distance (nearest point on block surface),
-    /// matching vanilla's `Player.isWithinBlockInteractionRange(pos, 1.0)`.
+    /// Matches vanilla's `Player.isWithinBlockInteractionRange(pos, 1.0)`.
     #[must_use]
     pub fn is_within_block_interaction_range(&self, pos: BlockPos) -> bool {
-        let player_pos = *self.position.lock();
-        let eye_y = player_pos.y + self.get_eye_height();
-
-        // Block AABB is [x, y, z] to [x+1, y+1, z+1]
-        let min_x = f64::from(pos.x());
-        let min_y = f64::from(pos.y());
-        let min_z = f64::from(pos.z());
-        let max_x = min_x + 1.0;
-        let max_y = min_y + 1.0;
-        let max_z = min_z + 1.0;
-
-        // Distance from eye to nearest point on block AABB (0 if inside on that axis)
-        let dx = f64::max(f64::max(min_x - player_pos.x, player_pos.x - max_x), 0.0);
-        let dy = f64::max(f64::max(min_y - eye_y, eye_y - max_y), 0.0);
-        let dz = f64::max(f64::max(min_z - player_pos.z, player_pos.z - max_z), 0.0);
-        let dist_sq = dx * dx + dy * dy + dz * dz;
+        let (x, y, z) = (f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+        let block = AABBd::new(x, y, z, x + 1.0, y + 1.0, z + 1.0);
+        let pos = self.position();
+        let eye_pos = DVec3::new(pos.x, self.get_eye_y(), pos.z);
+        let max_range = self.get_attribute_value(BLOCK_INTERACTION_RANGE) + 1.0;
+        distance_sq_to_aabb(eye_pos, &block) < max_range * max_range
+    }
 
-        // Base range is 4.5 blocks + 1.0 buffer
-        let max_range = 4.5 + 1.0;
-        dist_sq < max_range * max_range
+    /// Returns true if player is within entity interaction range of `bounding_box`.
+    ///
+    /// Matches vanilla's `Player.isWithinEntityInteractionRange(aabb, buffer)`.
+    #[must_use]
+    pub fn is_within_entity_interaction_range(&self, bounding_box: &AABBd, buffer: f64) -> bool {
+        let pos = self.position();
+        let eye_pos = DVec3::new(pos.x, self.get_eye_y(), pos.z);
+        let max_range = self.get_attribute_value(ENTITY_INTERACTION_RANGE) + buffer;
+        distance_sq_to_aabb(eye_pos, bounding_box) < max_range * max_range
     }
 
     /// Returns true if player is sneaking (secondary use active).
@@ -1636,6 +2531,16 @@ impl Player {
         self.game_mode.load() == GameType::Creative
     }
 
+    /// Returns true if the player may use operator-only blocks and items
+    /// (command blocks, structure blocks, the debug stick).
+    ///
+    /// TODO: also require permission level 2 (gamemasters) once player permission
+    /// levels exist. Every player is currently sent the owner permission level.
+    #[must_use]
+    pub fn can_use_game_master_blocks(&self) -> bool {
+        self.abilities.lock().instabuild
+    }
+
     /// Returns true if the player is currently sleeping.
     #[must_use]
     pub fn is_sleeping(&self) -> bool {
@@ -1656,6 +2561,296 @@ impl Player {
     /// Sets the player's fall flying state.
     pub fn set_fall_flying(&self, fall_flying: bool) {
         self.entity_state.lock().fall_flying = fall_flying;
+        self.set_shared_flag(FLAG_FALL_FLYING, fall_flying);
+    }
+
+    /// Starts gliding if the player is in mid-air with a usable glider equipped.
+    ///
+    /// Returns true if the player started gliding.
+    /// Vanilla: `Player.tryToStartFallFlying()`.
+    pub fn try_to_start_fall_flying(&self) -> bool {
+        if self.is_fall_flying() || !self.can_glide() || self.is_in_water() {
+            return false;
+        }
+        self.set_fall_flying(true);
+        true
+    }
+
+    /// Stops gliding and makes the client stop too.
+    ///
+    /// Vanilla: `Player.stopFallFlying()`, which toggles the flag so it's resent
+    /// even if the server never saw the player glide.
+    pub fn stop_fall_flying(&self) {
+        self.set_fall_flying(true);
+        self.set_fall_flying(false);
+    }
+
+    /// Returns true if the player is swimming.
+    #[must_use]
+    pub fn is_swimming(&self) -> bool {
+        self.entity_state.lock().swimming
+    }
+
+    /// Sets the player's swimming state.
+    pub fn set_swimming(&self, swimming: bool) {
+        self.entity_state.lock().swimming = swimming;
+        self.set_shared_flag(FLAG_SWIMMING, swimming);
+    }
+
+    /// Starts or stops swimming depending on sprinting and the water around the player.
+    ///
+    /// Vanilla: `Player.updateSwimming()`.
+    fn update_swimming(&self) {
+        if self.is_flying() {
+            if self.is_swimming() {
+                self.set_swimming(false);
+            }
+            return;
+        }
+        let sprinting = self.is_sprinting() && !self.is_passenger();
+        let swimming = if self.is_swimming() {
+            sprinting && self.is_in_water()
+        } else {
+            let feet = BlockPos::from(*self.position.lock());
+            sprinting
+                && self.is_eye_in_water()
+                && is_water_fluid(get_fluid_state(&self.world, feet).fluid_id)
+        };
+        if swimming != self.is_swimming() {
+            self.set_swimming(swimming);
+        }
+    }
+
+    /// Returns true if the player is spinning from a riptide trident.
+    #[must_use]
+    pub fn is_auto_spin_attack(&self) -> bool {
+        self.auto_spin_attack_ticks.load(Ordering::Relaxed) > 0
+    }
+
+    /// Puts the player into a riptide spin for `ticks` ticks.
+    ///
+    /// The client launches itself, so this only tracks the pose and lets the
+    /// movement checks tolerate the burst of speed.
+    /// Vanilla: `LivingEntity.startAutoSpinAttack()`.
+    // TODO: Call this from a trident item once items can be charged (use duration)
+    pub fn start_auto_spin_attack(&self, ticks: i32) {
+        self.auto_spin_attack_ticks.store(ticks, Ordering::Relaxed);
+        self.set_living_flag(LIVING_FLAG_SPIN_ATTACK, true);
+        self.apply_impulse();
+    }
+
+    /// Counts down the riptide spin and ends it when the time runs out.
+    ///
+    /// Vanilla: the `autoSpinAttackTicks` part of `LivingEntity.aiStep()`.
+    fn tick_auto_spin_attack(&self) {
+        if self.auto_spin_attack_ticks.load(Ordering::Relaxed) <= 0 {
+            return;
+        }
+        let ticks = self.auto_spin_attack_ticks.fetch_sub(1, Ordering::Relaxed) - 1;
+        if ticks <= 0 {
+            self.set_living_flag(LIVING_FLAG_SPIN_ATTACK, false);
+        }
+        // TODO: Damage entities the player spins into (checkAutoSpinAttack)
+    }
+
+    /// Returns the player's remaining air in ticks.
+    #[must_use]
+    pub fn get_air_supply(&self) -> i32 {
+        *self.entity_data.lock().air_supply.get()
+    }
+
+    /// Sets the player's remaining air in ticks.
+    pub fn set_air_supply(&self, air: i32) {
+        self.entity_data.lock().air_supply.set(air);
+    }
+
+    /// Uses up air while the player's head is under water and refills it otherwise,
+    /// dealing drowning damage when it runs out.
+    ///
+    /// Vanilla: the air supply part of `LivingEntity.baseTick()`.
+    fn tick_air_supply(&self) {
+        let air = self.get_air_supply();
+        if self.is_eye_in_water() && !self.is_in_bubble_column() && !self.can_breathe_underwater() {
+            let air = self.decrease_air_supply(air);
+            if air > -20 {
+                self.set_air_supply(air);
+                return;
+            }
+            self.set_air_supply(0);
+            let chunk_pos = *self.last_chunk_pos.lock();
+            self.world.broadcast_to_nearby(
+                chunk_pos,
+                CEntityEvent {
+                    entity_id: self.id,
+                    event: EntityStatus::DrownParticles,
+                },
+                None,
+            );
+            if self.world.get_game_rule(DROWNING_DAMAGE) == GameRuleValue::Bool(true) {
+                self.take_damage(
+                    &DamageSource::environment(vanilla_damage_types::DROWN),
+                    DROWN_DAMAGE,
+                );
+            }
+        } else if air < MAX_AIR_SUPPLY {
+            self.set_air_supply((air + AIR_REFILL_PER_TICK).min(MAX_AIR_SUPPLY));
+        }
+    }
+
+    /// Vanilla: `LivingEntity.decreaseAirSupply()`.
+    fn decrease_air_supply(&self, air: i32) -> i32 {
+        let oxygen_bonus = self.get_attribute_value(OXYGEN_BONUS);
+        if oxygen_bonus > 0.0 && rand::random::<f64>() >= 1.0 / (oxygen_bonus + 1.0) {
+            air
+        } else {
+            air - 1
+        }
+    }
+
+    /// Returns true if nothing uses up the player's air under water.
+    ///
+    /// Vanilla: `LivingEntity.canBreatheUnderwater()` and the invulnerability
+    /// check of `LivingEntity.baseTick()`.
+    fn can_breathe_underwater(&self) -> bool {
+        if self.abilities.lock().invulnerable {
+            return true;
+        }
+        let effects = self.active_effects.lock();
+        effects.contains_key(&Identifier::vanilla_static("water_breathing"))
+            || effects.contains_key(&Identifier::vanilla_static("conduit_power"))
+    }
+
+    /// Sets or clears one bit of the synched living entity flags byte.
+    ///
+    /// Vanilla: `LivingEntity.setLivingEntityFlag()`.
+    fn set_living_flag(&self, flag: i8, value: bool) {
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.living_entity_flags.get();
+        entity_data
+            .living_entity_flags
+            .set(if value { flags | flag } else { flags & !flag });
+    }
+
+    /// Returns true if the player could glide right now.
+    ///
+    /// Vanilla: `Player.canGlide()` and `LivingEntity.canGlide()`.
+    #[must_use]
+    pub fn can_glide(&self) -> bool {
+        if self.on_ground() || self.is_passenger() || self.is_flying() {
+            return false;
+        }
+        if self
+            .active_effects
+            .lock()
+            .contains_key(&Identifier::vanilla_static("levitation"))
+        {
+            return false;
+        }
+        let inventory = self.inventory.lock();
+        EquipmentSlot::ALL
+            .into_iter()
+            .any(|slot| can_glide_using(inventory.equipment().get_ref(slot), slot))
+    }
+
+    /// Caps the fall distance while gliding and wears down the glider.
+    ///
+    /// Vanilla: the `fallFlyTicks` part of `LivingEntity.tick()` and
+    /// `LivingEntity.updateFallFlying()`.
+    fn tick_fall_flying(&self) {
+        if !self.is_fall_flying() {
+            self.fall_fly_ticks.store(0, Ordering::Relaxed);
+            return;
+        }
+        let ticks = self.fall_fly_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+
+        // Gliding down slowly doesn't count as falling
+        {
+            let mut mv = self.movement.lock();
+            if mv.delta_movement.y > -0.5 && mv.fall_distance > 1.0 {
+                mv.fall_distance = 1.0;
+            }
+        }
+
+        if !self.can_glide() {
+            self.set_fall_flying(false);
+            return;
+        }
+
+        // TODO: Emit GameEvent::ELYTRA_GLIDE every 10 ticks
+        if (ticks + 1) % 20 == 0 {
+            self.damage_glider();
+        }
+    }
+
+    /// Takes one point of durability from a random equipped glider.
+    fn damage_glider(&self) {
+        let has_infinite_materials = self.has_infinite_materials();
+        let mut inventory = self.inventory.lock();
+        let slots: Vec<EquipmentSlot> = EquipmentSlot::ALL
+            .into_iter()
+            .filter(|&slot| can_glide_using(inventory.equipment().get_ref(slot), slot))
+            .collect();
+        if slots.is_empty() {
+            return;
+        }
+        let slot = slots[rand::random_range(0..slots.len())];
+        inventory
+            .equipment_mut()
+            .get_mut(slot)
+            .hurt_and_break(1, has_infinite_materials);
+        inventory.set_changed();
+    }
+
+    /// Hurts a gliding player that flew into a wall, based on how much speed the
+    /// impact took away.
+    ///
+    /// The client already stopped at the wall before sending `pos`, so the wall is
+    /// found by continuing the previous horizontal motion from there.
+    /// Vanilla: `LivingEntity.handleFallFlyingCollisions()`.
+    fn check_fly_into_wall(&self, pos: DVec3, old_delta: DVec3, new_delta: DVec3) {
+        let old_speed = old_delta.x.hypot(old_delta.z);
+        let new_speed = new_delta.x.hypot(new_delta.z);
+        let damage = ((old_speed - new_speed) * 10.0 - 3.0) as f32;
+        if damage <= 0.0 {
+            return;
+        }
+        let horizontal = DVec3::new(old_delta.x, 0.0, old_delta.z);
+        if !movement::simulate_move(&self.world, pos, horizontal, false, false, true).collision_x {
+            return;
+        }
+        self.play_sound(fall_damage_sound(damage as i32), 1.0, 1.0);
+        self.take_damage(
+            &DamageSource::environment(vanilla_damage_types::FLY_INTO_WALL),
+            damage,
+        );
+    }
+
+    /// Handles the player command packet sent for sprinting, leaving a bed,
+    /// gliding and vehicle controls.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.handlePlayerCommand()`.
+    pub fn handle_player_command(&self, packet: SPlayerCommand) {
+        // TODO: Reset the idle timer (resetLastActionTime)
+        match packet.action {
+            PlayerCommandAction::StopSleeping => {
+                // TODO: Wake up through stopSleepInBed and await the position from the client
+                if self.is_sleeping() {
+                    self.set_sleeping(false);
+                }
+            }
+            PlayerCommandAction::StartSprinting => self.set_sprinting(true),
+            PlayerCommandAction::StopSprinting => self.set_sprinting(false),
+            PlayerCommandAction::StartRidingJump
+            | PlayerCommandAction::StopRidingJump
+            | PlayerCommandAction::OpenInventory => {
+                // TODO: Horse jumps and vehicle inventories once rideable animals exist
+            }
+            PlayerCommandAction::StartFallFlying => {
+                if !self.try_to_start_fall_flying() {
+                    self.stop_fall_flying();
+                }
+            }
+        }
     }
 
     /// Returns true if the player is flying (creative/spectator flight).
@@ -1707,16 +2902,19 @@ impl Player {
     }
 
     /// Determines the desired pose based on current player state.
-    /// Priority: `Sleeping` > `FallFlying` > `Sneaking` > `Standing`
-    // TODO: Add Swimming pose (requires water detection)
-    // TODO: Add SpinAttack pose (requires riptide trident)
+    /// Priority: `Sleeping` > `FallFlying` > `Swimming` > `SpinAttack` > `Sneaking` > `Standing`
     // TODO: Add pose collision checks (force crouch in low ceilings)
     fn get_desired_pose(&self) -> EntityPose {
+        let spinning = self.is_auto_spin_attack();
         let es = self.entity_state.lock();
         if es.sleeping {
             EntityPose::Sleeping
         } else if es.fall_flying {
             EntityPose::FallFlying
+        } else if es.swimming {
+            EntityPose::Swimming
+        } else if spinning {
+            EntityPose::SpinAttack
         } else if es.crouching && !self.abilities.lock().flying {
             EntityPose::Sneaking
         } else {
@@ -1724,6 +2922,15 @@ impl Player {
         }
     }
 
+    /// Returns true if the player uses the 0.6 tall hitbox of gliding, swimming
+    /// or spinning with riptide.
+    fn has_low_pose(&self) -> bool {
+        matches!(
+            self.get_desired_pose(),
+            EntityPose::FallFlying | EntityPose::Swimming | EntityPose::SpinAttack
+        )
+    }
+
     /// Updates the player's pose in entity data based on current state.
     fn update_pose(&self) {
         let desired_pose = self.get_desired_pose();
@@ -1741,14 +2948,49 @@ impl Player {
         *self.client_information.lock() = info;
     }
 
+    /// Returns the brand the client reported, if it sent one.
+    #[must_use]
+    pub fn client_brand(&self) -> Option<String> {
+        self.client_brand.lock().clone()
+    }
+
+    /// Sets the brand the client reported.
+    pub fn set_client_brand(&self, brand: String) {
+        *self.client_brand.lock() = Some(brand);
+    }
+
+    /// Returns whether the client registered `channel` with `minecraft:register`.
+    ///
+    /// Vanilla clients never register channels, so this is only useful for
+    /// payloads meant for mods or proxies.
+    #[must_use]
+    pub fn listens_on_channel(&self, channel: &Identifier) -> bool {
+        self.client_channels.lock().contains(channel)
+    }
+
+    /// Sends a custom payload on `channel`, see
+    /// [`PayloadChannels`](crate::server::payload_channels::PayloadChannels).
+    pub fn send_custom_payload(&self, channel: Identifier, payload: impl Into<Box<[u8]>>) {
+        self.send_packet(CCustomPayload::new(channel, payload.into()));
+    }
+
     /// Returns the effective view distance for this player.
     ///
     /// This is the minimum of the client's requested view distance and
-    /// the server's configured maximum view distance.
+    /// the server's current maximum view distance.
     #[must_use]
     pub fn view_distance(&self) -> u8 {
         let client_view_distance = self.client_information.lock().view_distance;
-        client_view_distance.min(STEEL_CONFIG.view_distance)
+        client_view_distance.min(CHUNK_DISTANCES.view_distance())
+    }
+
+    /// Returns the distance around this player in which chunks are ticked.
+    ///
+    /// This is the simulation distance of the player's world, but never more
+    /// than the player's view distance, since chunks past it aren't loaded.
+    #[must_use]
+    pub fn simulation_distance(&self) -> u8 {
+        self.world.simulation_distance().min(self.view_distance())
     }
 
     /// Returns the player's current velocity.
@@ -1762,14 +3004,52 @@ impl Player {
         self.movement.lock().delta_movement = velocity;
     }
 
+    /// Kicks the player once they floated in the air for too long without being
+    /// allowed to fly.
+    ///
+    /// Vanilla: the `clientIsFloating` part of `ServerGamePacketListenerImpl.tick()`.
+    fn tick_floating(&self) {
+        let exempt = self.entity_state.lock().sleeping
+            || self.is_passenger()
+            || *self.entity_data.lock().health.get() <= 0.0;
+        let above_ground_ticks = {
+            let mut mv = self.movement.lock();
+            if !mv.client_is_floating || exempt {
+                mv.client_is_floating = false;
+                mv.above_ground_tick_count = 0;
+                return;
+            }
+            mv.above_ground_tick_count += 1;
+            mv.above_ground_tick_count
+        };
+
+        if f64::from(above_ground_ticks) > self.max_floating_ticks() {
+            log::warn!(
+                "{} was kicked for floating too long!",
+                self.gameprofile.name
+            );
+            self.disconnect(translations::MULTIPLAYER_DISCONNECT_FLYING.msg());
+        }
+    }
+
+    /// How many ticks the player may float, longer with lower gravity.
+    ///
+    /// Vanilla: `ServerGamePacketListenerImpl.getMaximumFlyingTicks()`.
+    fn max_floating_ticks(&self) -> f64 {
+        let gravity = self.get_gravity();
+        if gravity < 1.0E-5 {
+            return f64::INFINITY;
+        }
+        let max_ticks = f64::from(STEEL_CONFIG.movement_checks.max_floating_ticks);
+        (max_ticks * (movement::DEFAULT_GRAVITY / gravity).max(1.0)).ceil()
+    }
+
     /// Returns the player's current gravity value.
     ///
     /// Matches vanilla `LivingEntity.getGravity()` which reads from `Attributes.GRAVITY`.
     /// Default is 0.08 blocks/tick².
-    const fn get_gravity(&self) -> f64 {
-        // TODO: Read from attribute system when implemented
-        let _ = self; // Silence unused warning until attributes are implemented
-        movement::DEFAULT_GRAVITY
+    fn get_gravity(&self) -> f64 {
+        self.get_attribute_value(GRAVITY)
     }
 
     /// Applies gravity to the player's velocity.
@@ -1780,6 +3060,9 @@ impl Player {
     /// - Player is in spectator mode (no physics)
     /// - Player is in creative mode and flying
     /// - Player is fall flying (elytra - uses different physics)
+    ///
+    /// In water and lava the fluid drag of `LivingEntity.travelInFluid()` is
+    /// applied instead of the full gravity.
     fn apply_gravity(&self) {
         let (on_ground, is_fall_flying) = {
             let es = self.entity_state.lock();
@@ -1795,7 +3078,19 @@ impl Player {
         }
 
         let gravity = self.get_gravity();
-        if gravity != 0.0 {
+        if self.is_in_water() {
+            let sprinting = self.is_sprinting();
+            let drag = if sprinting { 0.9 } else { 0.8 };
+            let mut mv = self.movement.lock();
+            mv.delta_movement = mv.delta_movement * DVec3::new(drag, 0.8, drag);
+            if !sprinting {
+                mv.delta_movement.y -= gravity / 16.0;
+            }
+        } else if self.is_in_lava() {
+            let mut mv = self.movement.lock();
+            mv.delta_movement *= 0.5;
+            mv.delta_movement.y -= gravity / 4.0;
+        } else if gravity != 0.0 {
             self.movement.lock().delta_movement.y -= gravity;
         }
     }
@@ -1831,6 +3126,114 @@ impl Player {
         self.send_packet(CPlayerPosition::absolute(new_id, x, y, z, yaw, pitch));
     }
 
+    /// Returns the entity the player is spectating through, if any.
+    #[must_use]
+    pub fn camera(&self) -> Option<SharedEntity> {
+        self.camera.lock().as_ref().and_then(WeakEntity::upgrade)
+    }
+
+    /// Makes the player view the world from `entity`, or from their own eyes for `None`.
+    ///
+    /// The camera is released again when the entity is removed or leaves the world,
+    /// when the player sneaks, and when the player leaves spectator mode. Returns
+    /// `false` if the entity is in another world.
+    ///
+    /// Vanilla equivalent: `ServerPlayer.setCamera()`.
+    pub fn set_camera(&self, entity: Option<SharedEntity>) -> bool {
+        // Viewing through yourself is the same as having no camera
+        let entity = entity.filter(|entity| entity.id() != self.id);
+        // TODO: Move the player to the entity's world once players can change worlds
+        if let Some(entity) = &entity
+            && !entity
+                .level()
+                .is_some_and(|world| Arc::ptr_eq(&world, &self.world))
+        {
+            return false;
+        }
+
+        let old = {
+            let mut camera = self.camera.lock();
+            let old = camera.take();
+            *camera = entity.as_ref().map(Arc::downgrade);
+            old
+        };
+        let unchanged = match (&old, &entity) {
+            (None, None) => true,
+            (Some(old), Some(new)) => old.upgrade().is_some_and(|old| old.id() == new.id()),
+            _ => false,
+        };
+        if unchanged {
+            return true;
+        }
+
+        if let Some(entity) = &entity {
+            let pos = entity.position();
+            let (yaw, pitch) = self.rotation.load();
+            self.teleport(pos.x, pos.y, pos.z, yaw, pitch);
+        }
+        self.send_packet(CSetCamera {
+            camera_id: entity.as_ref().map_or(self.id, |entity| entity.id()),
+        });
+        self.movement.lock().last_good_position = *self.position.lock();
+        true
+    }
+
+    /// Keeps the player at their camera entity, releasing it once it's gone.
+    fn tick_camera(&self) {
+        let Some(camera) = self.camera.lock().clone() else {
+            return;
+        };
+        match camera.upgrade() {
+            Some(entity)
+                if !entity.is_removed()
+                    && !self.is_crouching()
+                    && entity
+                        .level()
+                        .is_some_and(|world| Arc::ptr_eq(&world, &self.world)) =>
+            {
+                // Vanilla: absSnapTo, the client already moves with its camera
+                let pos = entity.position();
+                *self.position.lock() = pos;
+                self.rotation.store(entity.rotation());
+                let mut mv = self.movement.lock();
+                mv.prev_position = pos;
+                mv.last_good_position = pos;
+                mv.first_good_position = pos;
+            }
+            _ => {
+                self.set_camera(None);
+            }
+        }
+    }
+
+    /// Keeps the player on their vehicle, dismounting once it's gone or the player sneaks.
+    ///
+    /// Vanilla equivalent: `ServerPlayer.rideTick()`.
+    fn tick_riding(&self) {
+        let Some(vehicle) = self.vehicle() else {
+            return;
+        };
+        if vehicle.is_removed()
+            || self.is_crouching()
+            || !vehicle
+                .level()
+                .is_some_and(|world| Arc::ptr_eq(&world, &self.world))
+        {
+            riding::stop_riding(self);
+            self.entity_state.lock().crouching = false;
+            return;
+        }
+
+        // The client already moves with its vehicle, so this doesn't need a teleport
+        let pos = riding::rider_position(vehicle.as_ref(), self);
+        *self.position.lock() = pos;
+        let mut mv = self.movement.lock();
+        mv.delta_movement = DVec3::ZERO;
+        mv.prev_position = pos;
+        mv.last_good_position = pos;
+        mv.first_good_position = pos;
+    }
+
     /// Handles a teleport acknowledgment from the client.
     ///
     /// Matches vanilla `ServerGamePacketListenerImpl.handleAcceptTeleportPacket()`.
@@ -1882,85 +3285,50 @@ impl Player {
 
     /// Handles a player input packet (movement keys, sneaking, sprinting).
     pub fn handle_player_input(&self, packet: SPlayerInput) {
-        self.entity_state.lock().crouching = packet.shift();
-        // Note: sprinting is handled via SPlayerCommand packet
+        let mut entity_state = self.entity_state.lock();
+        entity_state.crouching = packet.shift();
+        entity_state.last_client_input = packet;
+        // Note: sprinting is handled via SPlayerCommand packet (see `handle_player_command`)
     }
 
-    /// Handles the use of an item on a block.
+    /// Gets the direction the player wants to move in, from the keys they hold.
     ///
-    /// Implements the logic from Java's `ServerGamePacketListenerImpl.handleUseItemOn()`.
-    pub fn handle_use_item_on(&self, packet: SUseItemOn) {
-        // 1. Check client loaded
-        if !self.client_loaded.load(Ordering::Relaxed) {
-            return;
-        }
-
-        // 2. Ack block changes
-        self.ack_block_changes_up_to(packet.sequence);
-
-        let pos = packet.block_hit.block_pos;
-        let direction = packet.block_hit.direction;
-
-        // 3. Validate interaction range
-        if !self.is_within_block_interaction_range(pos) {
-            self.send_block_updates(pos, direction);
-            return;
-        }
-
-        // 4. Validate hit location precision (must be within 1.0000001 of block center)
-        let center_x = f64::from(pos.x()) + 0.5;
-        let center_y = f64::from(pos.y()) + 0.5;
-        let center_z = f64::from(pos.z()) + 0.5;
-        let location = &packet.block_hit.location;
-        let limit = 1.000_000_1;
-
-        if (location.x - center_x).abs() >= limit
-            || (location.y - center_y).abs() >= limit
-            || (location.z - center_z).abs() >= limit
-        {
-            log::warn!(
-                "Rejecting UseItemOnPacket from {}: location {:?} too far from block {:?}",
-                self.gameprofile.name,
-                location,
-                pos
-            );
-            self.send_block_updates(pos, direction);
-            return;
-        }
-
-        // 5. Validate Y height
-        if pos.y() >= self.world.max_build_height() {
-            // TODO: Send "build.tooHigh" message to player
-            self.send_block_updates(pos, direction);
-            return;
-        }
-
-        // 6. Check awaiting teleport
-        if self.is_awaiting_teleport() {
-            self.send_block_updates(pos, direction);
-            return;
-        }
-
-        // 7. Check may_interact permission
-        if !self.world.may_interact(self, pos) {
-            self.send_block_updates(pos, direction);
-            return;
-        }
-
-        // 8. Call use_item_on
-        let result = game_mode::use_item_on(self, &self.world, packet.hand, &packet.block_hit);
-
-        // 9. Handle result
-        if let InteractionResult::Success = result {
-            // TODO: Trigger arm swing animation if needed
-            self.swing(packet.hand, true);
+    /// Vanilla: `ServerPlayer.getLastClientMoveIntent()`.
+    #[must_use]
+    pub fn last_client_move_intent(&self) -> DVec3 {
+        let input = self.entity_state.lock().last_client_input.clone();
+        let x = match (input.left(), input.right()) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        let z = match (input.forward(), input.backward()) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        let relative = DVec3::new(x, 0.0, z);
+        if relative.length_squared() < 1.0E-7 {
+            return DVec3::ZERO;
         }
+        // Vanilla: Entity.getInputVector() with a speed of 1
+        let relative = if relative.length_squared() > 1.0 {
+            relative.normalize()
+        } else {
+            relative
+        };
+        let yaw = self.rotation.load().0.to_radians();
+        let (sin, cos) = (f64::from(yaw.sin()), f64::from(yaw.cos()));
+        DVec3::new(
+            relative.x * cos - relative.z * sin,
+            0.0,
+            relative.z * cos + relative.x * sin,
+        )
+    }
 
-        // 10. Always send block updates to resync client
-        self.send_block_updates(pos, direction);
-
-        // 11. Broadcast inventory changes
-        self.broadcast_inventory_changes();
+    /// Handles the use of an item on a block. See [`PlayerInteractionManager`].
+    pub fn handle_use_item_on(&self, packet: SUseItemOn) {
+        self.interaction.handle_use_item_on(self, packet);
     }
 
     /// Handles a player action packet (block breaking, item dropping, etc.).
@@ -2014,42 +3382,17 @@ impl Player {
             PlayerAction::SwapItemWithOffhand => {
                 // TODO: Implement swap item with offhand (F key)
                 log::debug!("Player {} wants to swap items", self.gameprofile.name);
-            }
-            PlayerAction::Stab => {
-                // Stab action for new combat system
-                log::debug!("Player {} performed stab action", self.gameprofile.name);
-            }
-        }
-    }
-
-    /// Handles the use of an item.
-    pub fn handle_use_item(&self, packet: SUseItem) {
-        log::info!(
-            "Player {} used {:?} (sequence: {}, yaw: {}, pitch: {})",
-            self.gameprofile.name,
-            packet.hand,
-            packet.sequence,
-            packet.y_rot,
-            packet.x_rot
-        );
-
-        // Ack block changes up to this sequence number.
-        // Vanilla: handleUseItem calls ackBlockChangesUpTo(packet.getSequence()) first.
-        // Without this, client-side prediction stays active and overrides server block updates
-        // (e.g. water regeneration after picking it up with a bucket is invisible to the actor).
-        self.ack_block_changes_up_to(packet.sequence);
-
-        // Call use_item
-        let result = game_mode::use_item(self, &self.world, packet.hand);
-
-        // Handle result
-        if let InteractionResult::Success = result {
-            // Trigger arm swing animation
-            self.swing(packet.hand, true);
+            }
+            PlayerAction::Stab => {
+                // Stab action for new combat system
+                log::debug!("Player {} performed stab action", self.gameprofile.name);
+            }
         }
+    }
 
-        // Broadcast inventory changes as item might have changed
-        self.broadcast_inventory_changes();
+    /// Handles the use of an item. See [`PlayerInteractionManager`].
+    pub fn handle_use_item(&self, packet: SUseItem) {
+        self.interaction.handle_use_item(self, packet);
     }
 
     /// Handles the pick block action (middle click on a block).
@@ -2078,7 +3421,7 @@ impl Player {
         let include_data = self.has_infinite_materials() && packet.include_data;
 
         // Get clone item stack from behavior (handles blocks with different item keys)
-        let Some(item_stack) = behavior.get_clone_item_stack(block, state, include_data) else {
+        let Some(mut item_stack) = behavior.get_clone_item_stack(block, state, include_data) else {
             // No corresponding item for this block (e.g., fire, portal)
             return;
         };
@@ -2087,9 +3430,255 @@ impl Player {
             return;
         }
 
-        // TODO: If include_data, add block entity NBT data to the item stack
-        // This requires block entity support which isn't implemented yet
+        if include_data {
+            self.add_block_data_to_item(packet.pos, &mut item_stack);
+        }
+
+        self.try_pick_item(item_stack);
+    }
+
+    /// Handles the pick block action (middle click) on an entity.
+    pub fn handle_pick_item_from_entity(&self, packet: SPickItemFromEntity) {
+        let Some(entity) = self.world.get_entity_by_id(packet.id) else {
+            return;
+        };
+
+        if !self.is_within_entity_interaction_range(&entity.bounding_box(), 3.0) {
+            return;
+        }
+
+        if let Some(item_stack) = entity.get_pick_result()
+            && !item_stack.is_empty()
+        {
+            self.try_pick_item(item_stack);
+        }
+    }
+
+    /// Gets the vehicle this player steers, if it rides one.
+    ///
+    /// Vanilla: `getRootVehicle()` whose `getControllingPassenger()` is this player.
+    fn controlled_vehicle(&self) -> Option<SharedEntity> {
+        riding::root_vehicle(self).filter(|vehicle| {
+            vehicle
+                .controlling_passenger()
+                .is_some_and(|controller| controller.id() == self.id)
+        })
+    }
+
+    /// Handles a right-click on an entity.
+    ///
+    /// Mirrors vanilla's `ServerGamePacketListenerImpl.handleInteract` and `Player.interactOn`.
+    pub fn handle_interact(self: &Arc<Self>, packet: SInteract) {
+        if !self.client_loaded.load(Ordering::Relaxed) {
+            return;
+        }
+        self.entity_state.lock().crouching = packet.using_secondary_action;
+
+        // TODO: Ignore entities outside of the world border
+        let Some(entity) = self.world.get_entity_by_id(packet.entity_id) else {
+            return;
+        };
+        if !self.is_within_entity_interaction_range(&entity.bounding_box(), 3.0) {
+            return;
+        }
+
+        // TODO: Spectators open the menu of the clicked entity instead
+        if self.game_mode.load() == GameType::Spectator {
+            return;
+        }
+
+        let hand = packet.hand;
+        let original_count = self.inventory.lock().get_item_in_hand(hand).count;
+        let mut result = leash::interact(&entity, self, hand);
+        if !result.consumes_action() {
+            result = entity.clone().interact(self, hand);
+        }
+        if !result.consumes_action()
+            && (entity.clone().as_mob().is_some() || entity.clone().as_player().is_some())
+        {
+            // Vanilla: ItemStack.interactLivingEntity()
+            let inv_ref = ContainerRef::PlayerInventory(self.inventory.clone());
+            let mut guard = ContainerLockGuard::lock_all(&[&inv_ref]);
+            let inv_id = inv_ref.container_id();
+            let mut context = UseItemContext::new(self, hand, &self.world, &mut guard, inv_id);
+            let item = context.inv.item().item;
+            if !context.inv.item().is_empty() {
+                let _ = ITEM_BEHAVIORS
+                    .get_behavior(item)
+                    .interact_living_entity(&mut context, &entity);
+            }
+        }
+
+        // Restore count for creative mode (infinite materials)
+        if self.has_infinite_materials() {
+            let mut inventory = self.inventory.lock();
+            let item = inventory.get_item_in_hand_mut(hand);
+            if item.count < original_count {
+                item.count = original_count;
+            }
+        }
+    }
+
+    /// Handles the client moving the vehicle it controls.
+    ///
+    /// Mirrors vanilla's `ServerGamePacketListenerImpl.handleMoveVehicle`.
+    pub fn handle_move_vehicle(&self, packet: SMoveVehicle) {
+        if Self::is_invalid_position(
+            packet.position.x,
+            packet.position.y,
+            packet.position.z,
+            packet.x_rot,
+            packet.y_rot,
+        ) {
+            self.disconnect(translations::MULTIPLAYER_DISCONNECT_INVALID_VEHICLE_MOVEMENT.msg());
+            return;
+        }
+
+        if self.update_awaiting_teleport() || !self.client_loaded.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let Some(vehicle) = self.controlled_vehicle() else {
+            return;
+        };
+        let (last_vehicle, first_good, last_good) = {
+            let mv = self.movement.lock();
+            (
+                mv.last_vehicle,
+                mv.vehicle_first_good_position,
+                mv.vehicle_last_good_position,
+            )
+        };
+        if last_vehicle != Some(vehicle.id()) {
+            return;
+        }
+
+        let start_pos = vehicle.position();
+        let target_pos = DVec3::new(
+            movement::clamp_horizontal(packet.position.x),
+            movement::clamp_vertical(packet.position.y),
+            movement::clamp_horizontal(packet.position.z),
+        );
+        let rotation = (
+            math::wrap_degrees(packet.y_rot),
+            math::wrap_degrees(packet.x_rot),
+        );
+
+        let moved_dist_sq = (target_pos - first_good).length_squared();
+        let config = &STEEL_CONFIG.movement_checks;
+        if moved_dist_sq - vehicle.velocity().length_squared() > config.max_vehicle_speed {
+            log::warn!(
+                "{} (vehicle of {}) moved too quickly! {},{},{}",
+                vehicle.entity_type().key,
+                self.gameprofile.name,
+                target_pos.x - first_good.x,
+                target_pos.y - first_good.y,
+                target_pos.z - first_good.z
+            );
+            self.send_vehicle_position(vehicle.as_ref());
+            return;
+        }
+
+        let old_aabb = vehicle.bounding_box();
+        vehicle.move_by(MoverType::Player, target_pos - last_good);
+
+        // Vanilla's Y tolerance check is always true, so only the horizontal error counts
+        let error = target_pos - vehicle.position();
+        let error_dist_sq = error.x * error.x + error.z * error.z;
+        let moved_wrongly = error_dist_sq > config.max_error;
+        if moved_wrongly {
+            log::warn!(
+                "{} (vehicle of {}) moved wrongly! {}",
+                vehicle.entity_type().key,
+                self.gameprofile.name,
+                error_dist_sq.sqrt()
+            );
+        }
+
+        let dimensions = vehicle.entity_type().dimensions;
+        let new_aabb = AABBd::entity_box(
+            target_pos.x,
+            target_pos.y,
+            target_pos.z,
+            f64::from(dimensions.width) / 2.0,
+            f64::from(dimensions.height),
+        );
+        if (moved_wrongly && !movement::has_block_collision(&self.world, &old_aabb))
+            || movement::is_box_colliding_with_new_blocks(
+                &self.world,
+                &old_aabb.deflate(COLLISION_EPSILON),
+                &new_aabb.deflate(COLLISION_EPSILON),
+            )
+        {
+            vehicle.set_position(start_pos);
+            vehicle.set_rotation(rotation);
+            self.send_vehicle_position(vehicle.as_ref());
+            return;
+        }
+
+        vehicle.set_position(target_pos);
+        vehicle.set_rotation(rotation);
+        vehicle.set_on_ground(packet.on_ground);
+        self.movement.lock().vehicle_last_good_position = target_pos;
+        // TODO: Vehicle fall damage, movement statistics and the floating vehicle kick
+    }
+
+    /// Syncs the position of `vehicle` back to the client controlling it.
+    fn send_vehicle_position(&self, vehicle: &dyn Entity) {
+        let (y_rot, x_rot) = vehicle.rotation();
+        self.send_packet(CMoveVehicle {
+            position: vehicle.position(),
+            y_rot,
+            x_rot,
+        });
+    }
+
+    /// Handles the paddle state of the boat this player steers.
+    ///
+    /// Mirrors vanilla's `ServerGamePacketListenerImpl.handlePaddleBoat`.
+    pub fn handle_paddle_boat(&self, packet: SPaddleBoat) {
+        let Some(vehicle) = self.vehicle() else {
+            return;
+        };
+        if vehicle
+            .controlling_passenger()
+            .is_none_or(|controller| controller.id() != self.id)
+        {
+            return;
+        }
+        if let Some(boat) = vehicle.as_boat() {
+            boat.set_paddle_state(packet.left, packet.right);
+        }
+    }
+
+    /// Copies the block entity at `pos` into the item's `block_entity_data` component.
+    ///
+    /// Mirrors vanilla's `ServerGamePacketListenerImpl.addBlockDataToItem`.
+    fn add_block_data_to_item(&self, pos: BlockPos, item_stack: &mut ItemStack) {
+        let Some(block_entity) = self.world.get_block_entity(pos) else {
+            return;
+        };
+
+        let guard = block_entity.lock();
+        let mut tag = NbtCompound::new();
+        guard.save_additional(&mut tag);
+        guard.remove_components_from_tag(&mut tag);
+        let block_entity_type = guard.get_type().key.clone();
+        let components = guard.collect_components();
+        drop(guard);
+
+        item_stack.set(
+            BLOCK_ENTITY_DATA,
+            TypedEntityData::new(block_entity_type, tag),
+        );
+        item_stack.apply_components(&components);
+    }
 
+    /// Selects or adds a picked item in the hotbar.
+    ///
+    /// Mirrors vanilla's `ServerGamePacketListenerImpl.tryPickItem`: an existing matching stack
+    /// is selected (or swapped into the hotbar), otherwise creative players get a new copy.
+    fn try_pick_item(&self, item_stack: ItemStack) {
         let mut inventory = self.inventory.lock();
 
         // Find existing slot with this item
@@ -2127,7 +3716,36 @@ impl Player {
 
     /// Sets selected slot
     pub fn handle_set_carried_item(&self, packet: SSetCarriedItem) {
-        self.inventory.lock().set_selected_slot(packet.slot as u8);
+        let mut inventory = self.inventory.lock();
+        if inventory.get_selected_slot() != packet.slot as u8 {
+            // Vanilla: switching the held item restarts the attack cooldown
+            self.reset_attack_strength_ticker();
+        }
+        inventory.set_selected_slot(packet.slot as u8);
+    }
+
+    /// Gets the ticks it takes for an attack to fully recharge.
+    ///
+    /// Vanilla: `Player.getCurrentItemAttackStrengthDelay()`.
+    #[must_use]
+    pub fn get_attack_strength_delay(&self) -> f32 {
+        (20.0 / self.get_attribute_value(ATTACK_SPEED)) as f32
+    }
+
+    /// Gets how charged the player's next attack is, from 0.0 to 1.0.
+    ///
+    /// Vanilla: `Player.getAttackStrengthScale()`.
+    #[must_use]
+    pub fn get_attack_strength_scale(&self, adjust_ticks: f32) -> f32 {
+        let ticks = self.attack_strength_ticker.load(Ordering::Relaxed) as f32;
+        ((ticks + adjust_ticks) / self.get_attack_strength_delay()).clamp(0.0, 1.0)
+    }
+
+    /// Restarts the attack cooldown.
+    ///
+    /// Vanilla: `LivingEntity.resetAttackStrengthTicker()`.
+    pub fn reset_attack_strength_ticker(&self) {
+        self.attack_strength_ticker.store(0, Ordering::Relaxed);
     }
 
     /// Handles a sign update packet from the client.
@@ -2170,29 +3788,21 @@ impl Player {
                 // Create a plain text component from the line
                 // Strip formatting codes (like vanilla does with ChatFormatting.stripFormatting)
                 let stripped = strip_formatting_codes(line);
-                text.set_message(i, TextComponent::plain(stripped));
+                let mut message = TextComponent::plain(stripped);
+                // Keep the line's style (color, click event), vanilla only replaces the text
+                if let Some(old) = text.get_message(i) {
+                    message.format = old.format.clone();
+                    message.interactions = old.interactions.clone();
+                }
+                text.set_message(i, message);
             }
         }
 
         // Clear the edit lock now that we're done editing
         sign.set_player_who_may_edit(None);
 
-        // Mark as changed (for persistence)
-        sign.set_changed();
-
-        // Get the update tag for broadcasting
-        let update_tag = sign.get_update_tag();
-        let block_entity_type = sign.get_type();
-        let pos = packet.pos;
-
-        // Release the lock before broadcasting
-        drop(guard);
-
-        // Broadcast block entity update to nearby players
-        if let Some(nbt) = update_tag {
-            self.world
-                .broadcast_block_entity_update(pos, block_entity_type, nbt);
-        }
+        // Save the text and send it to nearby players
+        sign.mark_updated();
     }
 
     /// Opens the sign editor for the player.
@@ -2316,6 +3926,7 @@ impl Player {
         // First, broadcast changes for any open external menu
         let mut open_menu = self.open_menu.lock();
         if let Some(ref mut menu) = *open_menu {
+            menu.update_data_slots();
             menu.behavior_mut().broadcast_changes(&self.connection);
         } else {
             drop(open_menu);
@@ -2501,16 +4112,228 @@ impl Player {
         }
     }
 
+    /// Puts the player out when standing in water or rain.
+    ///
+    /// Vanilla: the end of `Entity.applyEffectsFromBlocks()`, which leaves the player
+    /// immune to re-ignition for `Player.getFireImmuneTicks()` (20) ticks.
+    fn extinguish_in_water_or_rain(&self) {
+        if self.remaining_fire_ticks() <= 0 {
+            return;
+        }
+
+        let pos = self.position();
+        let feet = BlockPos::from(pos);
+        let head = BlockPos::from(DVec3::new(pos.x, self.bounding_box().max_y, pos.z));
+        let in_rain = [feet, head]
+            .into_iter()
+            .any(|block_pos| self.world.is_raining_at(block_pos));
+
+        if in_rain || self.is_in_water() {
+            self.set_remaining_fire_ticks(-20);
+        }
+    }
+
+    /// Returns true if water touches the player's bounding box.
+    ///
+    /// Vanilla: `Entity.isInWater()`.
+    #[must_use]
+    pub fn is_in_water(&self) -> bool {
+        self.is_in_fluid(is_water_fluid)
+    }
+
+    /// Returns true if lava touches the player's bounding box.
+    ///
+    /// Vanilla: `Entity.isInLava()`.
+    #[must_use]
+    pub fn is_in_lava(&self) -> bool {
+        self.is_in_fluid(is_lava_fluid)
+    }
+
+    /// Returns true if the player's eyes are below the water surface.
+    ///
+    /// Vanilla: `Entity.isEyeInFluid(FluidTags.WATER)`, sampled like
+    /// `Entity.updateFluidOnEyes()` slightly below the actual eye height.
+    #[must_use]
+    pub fn is_eye_in_water(&self) -> bool {
+        let eye_y = self.get_eye_y() - 0.111_111_11;
+        let mut eye = *self.position.lock();
+        eye.y = eye_y;
+        let pos = BlockPos::from(eye);
+        let fluid = get_fluid_state(&self.world, pos);
+        is_water_fluid(fluid.fluid_id)
+            && f64::from(pos.y()) + f64::from(get_height(&self.world, pos, fluid)) > eye_y
+    }
+
+    /// Returns true if the block at the player's eyes is a bubble column.
+    fn is_in_bubble_column(&self) -> bool {
+        let mut eye = *self.position.lock();
+        eye.y = self.get_eye_y();
+        self.world.get_block_state(BlockPos::from(eye)).get_block() == vanilla_blocks::BUBBLE_COLUMN
+    }
+
+    /// Returns true if a fluid matching `is_fluid` touches the player's bounding box.
+    ///
+    /// Vanilla: `Entity.updateFluidHeightAndDoFluidPushing()` without the pushing.
+    fn is_in_fluid(&self, is_fluid: fn(FluidRef) -> bool) -> bool {
+        let aabb = self.bounding_box().deflate(0.001);
+        for x in aabb.min_x.floor() as i32..aabb.max_x.ceil() as i32 {
+            for y in aabb.min_y.floor() as i32..aabb.max_y.ceil() as i32 {
+                for z in aabb.min_z.floor() as i32..aabb.max_z.ceil() as i32 {
+                    let pos = BlockPos::new(x, y, z);
+                    let fluid = get_fluid_state(&self.world, pos);
+                    if is_fluid(fluid.fluid_id)
+                        && f64::from(y) + f64::from(get_height(&self.world, pos, fluid))
+                            >= aabb.min_y
+                    {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Sets or clears one bit of the synched shared flags byte.
+    ///
+    /// Vanilla: `Entity.setSharedFlag()`.
+    fn set_shared_flag(&self, flag: u8, value: bool) {
+        let mut entity_data = self.entity_data.lock();
+        let flags = *entity_data.shared_flags.get();
+        let mask = 1i8 << flag;
+        entity_data
+            .shared_flags
+            .set(if value { flags | mask } else { flags & !mask });
+    }
+
     fn check_below_world(&self) {
         let pos = *self.position.lock();
         if pos.y < f64::from(self.world.get_min_y() - 64) {
-            self.hurt(
+            self.take_damage(
                 &DamageSource::environment(vanilla_damage_types::OUT_OF_WORLD),
                 4.0,
             );
         }
     }
 
+    /// Marks the player on every map they carry and draws the world onto the
+    /// ones in their hands.
+    ///
+    /// Vanilla: `MapItem.inventoryTick()`.
+    fn tick_maps(&self) {
+        let Some(server) = self.server.upgrade() else {
+            return;
+        };
+        let maps = self.carried_maps();
+        if maps.is_empty() {
+            return;
+        }
+        let (tick, day_time) = {
+            let level_data = self.world.level_data.read();
+            (level_data.game_time() as u64, level_data.day_time())
+        };
+        let position = *self.position.lock();
+        let (yaw, _) = self.rotation.load();
+        for (id, in_hand) in maps {
+            let Some(map) = server.maps.get(id) else {
+                continue;
+            };
+            let mut map = map.lock();
+            let marker = (map.dimension == self.world.dimension.key)
+                .then_some((position.x, position.z, yaw));
+            map.tick_carried_by(
+                self.gameprofile.id,
+                &self.gameprofile.name,
+                tick,
+                marker,
+                day_time,
+            );
+            if in_hand && !map.locked {
+                map.update(&self.world, self.gameprofile.id, position);
+            }
+        }
+    }
+
+    /// Unbinds carried compasses whose lodestone in this dimension was destroyed.
+    ///
+    /// Vanilla: `CompassItem.inventoryTick()` and `LodestoneTracker.tick()`.
+    fn tick_lodestone_compasses(&self) {
+        let mut inventory = self.inventory.lock();
+        let mut changed = false;
+        for slot in 0..inventory.get_container_size() {
+            let item = inventory.get_item_mut(slot);
+            let lost = item.get(LODESTONE_TRACKER).is_some_and(|tracker| {
+                tracker.tracked
+                    && tracker.target.as_ref().is_some_and(|target| {
+                        target.dimension == self.world.dimension.key
+                            && self.is_lodestone_missing(target.pos)
+                    })
+            });
+            if lost {
+                item.set(
+                    LODESTONE_TRACKER,
+                    LodestoneTracker {
+                        target: None,
+                        tracked: true,
+                    },
+                );
+                changed = true;
+            }
+        }
+        if changed {
+            inventory.set_changed();
+        }
+    }
+
+    /// Returns true if there's certainly no lodestone at `pos`.
+    ///
+    /// Unloaded chunks count as unknown, so compasses stay bound to lodestones
+    /// in chunks that aren't loaded.
+    fn is_lodestone_missing(&self, pos: BlockPos) -> bool {
+        if !self.world.is_in_valid_bounds(pos) {
+            return true;
+        }
+        let chunk_pos = ChunkPos::new(pos.x() >> 4, pos.z() >> 4);
+        self.world
+            .chunk_map
+            .with_full_chunk(chunk_pos, |chunk| {
+                chunk.get_block_state(pos).get_block() != vanilla_blocks::LODESTONE
+            })
+            .unwrap_or(false)
+    }
+
+    /// Sends the changed pixels and markers of every map the player carries.
+    ///
+    /// Vanilla: `ServerPlayer.synchronizeSpecialItemUpdates()`.
+    fn send_map_updates(&self) {
+        let Some(server) = self.server.upgrade() else {
+            return;
+        };
+        for (id, _) in self.carried_maps() {
+            let packet = server
+                .maps
+                .get(id)
+                .and_then(|map| map.lock().get_update_packet(id, self.gameprofile.id));
+            if let Some(packet) = packet {
+                self.send_packet(packet);
+            }
+        }
+    }
+
+    /// The ids of the maps in the inventory, and whether each is in a hand.
+    fn carried_maps(&self) -> Vec<(i32, bool)> {
+        let inventory = self.inventory.lock();
+        let selected = usize::from(inventory.get_selected_slot());
+        (0..inventory.get_container_size())
+            .filter_map(|slot| {
+                let id = *inventory.get_item(slot).get(MAP_ID)?;
+                Some((
+                    id,
+                    slot == selected || slot == PlayerInventory::SLOT_OFFHAND,
+                ))
+            })
+            .collect()
+    }
+
     /// Main entry point for dealing damage. Returns `true` if damage was applied.
     ///
     /// Vanilla: `LivingEntity.hurtServer()` (with `ServerPlayer` override adding
@@ -2734,21 +4557,44 @@ impl Player {
 
         // Reset transient state. Vanilla creates a fresh ServerPlayer so all state
         // is naturally zeroed; we reuse the same Player, so we must reset manually.
-        // TODO: as new transient fields are added (effects, fire ticks, frozen
-        // ticks, etc.), they must be reset here too.
-        self.movement.lock().delta_movement = DVec3::default();
+        // TODO: as new transient fields are added (frozen ticks, etc.), they must
+        // be reset here too.
+        {
+            let mut mv = self.movement.lock();
+            mv.delta_movement = DVec3::default();
+            mv.fall_distance = 0.0;
+        }
+        self.fall_fly_ticks.store(0, Ordering::Relaxed);
+        self.auto_spin_attack_ticks.store(0, Ordering::Relaxed);
+        self.set_shared_flag(FLAG_FALL_FLYING, false);
+        self.set_shared_flag(FLAG_SWIMMING, false);
+        self.set_living_flag(LIVING_FLAG_SPIN_ATTACK, false);
         {
             let mut es = self.entity_state.lock();
             es.on_ground = false;
             es.fall_flying = false;
+            es.swimming = false;
             es.sleeping = false;
             es.crouching = false;
         }
         *self.block_breaking.lock() = BlockBreakingManager::new();
+        self.interaction.reset();
+        self.set_remaining_fire_ticks(0);
+        self.active_effects.lock().clear();
+        riding::stop_riding(self);
+        {
+            // Vanilla: ServerPlayer.restoreFrom() only keeps the base values after death
+            let mut attributes = self.attributes.lock();
+            let mut fresh = default_attributes(vanilla_entities::PLAYER);
+            fresh.assign_base_values(&attributes);
+            *attributes = fresh;
+        }
 
         {
+            let max_health = self.get_max_health();
             let mut entity_data = self.entity_data.lock();
-            entity_data.health.set(20.0);
+            entity_data.health.set(max_health);
+            entity_data.air_supply.set(MAX_AIR_SUPPLY);
             entity_data.pose.set(EntityPose::Standing);
         }
 
@@ -2773,7 +4619,22 @@ impl Player {
             data_kept: 0,
         });
 
-        let spawn_pos = world.level_data.read().data().spawn_pos();
+        let world_spawn = self.server.upgrade().map_or_else(
+            || world.level_data.read().data().spawn.clone(),
+            |server| server.respawn_data(),
+        );
+        // TODO: respawn in the config's dimension, and check the bed or respawn anchor
+        // of non-forced points (sending NO_RESPAWN_BLOCK_AVAILABLE when it's gone)
+        let (spawn_pos, yaw, pitch) = match self.respawn_config.lock().clone() {
+            Some(config) if config.dimension == world.dimension.key => {
+                (config.pos, config.yaw, config.pitch)
+            }
+            _ => (
+                BlockPos::new(world_spawn.x, world_spawn.y, world_spawn.z),
+                world_spawn.angle,
+                world_spawn.pitch,
+            ),
+        };
         let spawn = DVec3::new(
             f64::from(spawn_pos.x()) + 0.5,
             f64::from(spawn_pos.y()),
@@ -2786,10 +4647,10 @@ impl Player {
             mv.last_good_position = spawn;
             mv.first_good_position = spawn;
         }
-        self.rotation.store((0.0, 0.0));
-        self.teleport(spawn.x, spawn.y, spawn.z, 0.0, 0.0);
+        self.rotation.store((yaw, pitch));
+        self.teleport(spawn.x, spawn.y, spawn.z, yaw, pitch);
 
-        // TODO: send CSetDefaultSpawnPosition (dimension, pos, yaw, pitch)
+        self.send_packet(Server::default_spawn_packet(&world_spawn));
 
         // TODO: send CChangeDifficulty (difficulty, locked)
 
@@ -2810,7 +4671,7 @@ impl Player {
         // TODO: send CInitializeBorder once world border is implemented
 
         // Vanilla: ChunkMap.addEntity -> addPairing -> sendPairingData
-        // TODO: also send SetEquipment + UpdateAttributes in the bundle
+        // TODO: also send SetEquipment in the bundle
         let player_type_id = vanilla_entities::PLAYER.id() as i32;
         let spawn_packet = CAddEntity::player(
             self.id,
@@ -2819,10 +4680,11 @@ impl Player {
             spawn.x,
             spawn.y,
             spawn.z,
-            0.0,
-            0.0,
+            yaw,
+            pitch,
         );
         let entity_data = self.entity_data.lock().pack_all();
+        let attributes = self.attributes.lock().syncable_snapshots();
         let entity_id = self.id;
         world.players.iter_players(|_, p| {
             if p.id != entity_id {
@@ -2831,6 +4693,10 @@ impl Player {
                     if !entity_data.is_empty() {
                         bundle.add(CSetEntityData::new(entity_id, entity_data.clone()));
                     }
+                    bundle.add(CUpdateAttributes {
+                        entity_id,
+                        attributes: attributes.clone(),
+                    });
                 });
             }
             true
@@ -2924,12 +4790,56 @@ impl Entity for Player {
         *self.position.lock()
     }
 
+    fn sound_source(&self) -> SoundSource {
+        SoundSource::Players
+    }
+
+    /// The player already hears their own sounds client-side.
+    fn play_sound(&self, sound_id: i32, volume: f32, pitch: f32) {
+        self.world.play_sound_at(
+            sound_id,
+            SoundSource::Players,
+            self.position(),
+            volume,
+            pitch,
+            Some(self.id),
+        );
+    }
+
+    /// Vanilla: `Player.causeFallDamage()` and `LivingEntity.causeFallDamage()`.
+    fn cause_fall_damage(
+        &self,
+        fall_distance: f64,
+        multiplier: f32,
+        source: &DamageSource,
+    ) -> bool {
+        if self.abilities.lock().may_fly {
+            return false;
+        }
+        // TODO: Lower the fall power by the jump boost amplifier
+        let fall_power = fall_distance + 1.0E-6 - self.get_attribute_value(SAFE_FALL_DISTANCE);
+        let damage =
+            (fall_power * f64::from(multiplier) * self.get_attribute_value(FALL_DAMAGE_MULTIPLIER))
+                .floor() as i32;
+        if damage <= 0 {
+            return false;
+        }
+        self.play_sound(fall_damage_sound(damage), 1.0, 1.0);
+        // TODO: Play the fall sound of the block landed on (playBlockFallSound)
+        self.take_damage(source, damage as f32)
+    }
+
     fn bounding_box(&self) -> AABBd {
         let pos = self.position();
-        // Player hitbox: 0.6 wide, 1.8 tall (standing)
-        // TODO: Adjust for pose (crouching, swimming, etc.)
+        // Player hitbox: 0.6 wide, 1.8 tall (standing), 0.6 tall while gliding,
+        // swimming or spinning
+        // TODO: Adjust for the other poses (crouching, sleeping)
         let half_width = 0.3;
-        let height = 1.8;
+        let height = if self.has_low_pose() {
+            movement::LOW_POSE_HEIGHT
+        } else {
+            1.8
+        };
         AABBd {
             min_x: pos.x - half_width,
             min_y: pos.y,
@@ -3001,6 +4911,45 @@ impl Entity for Player {
         // invulnerability, armor, death, and network packets.
         Player::hurt(self, source, amount)
     }
+
+    fn remaining_fire_ticks(&self) -> i32 {
+        self.remaining_fire_ticks.load(Ordering::Relaxed)
+    }
+
+    fn set_remaining_fire_ticks(&self, ticks: i32) {
+        self.remaining_fire_ticks.store(ticks, Ordering::Relaxed);
+    }
+
+    fn riding(&self) -> Option<&SyncMutex<RidingState>> {
+        Some(&self.riding)
+    }
+
+    fn set_as_inside_portal(&self, pos: BlockPos) {
+        if self.portal_cooldown.load(Ordering::Relaxed) > 0 {
+            self.portal_cooldown
+                .store(DIMENSION_CHANGING_DELAY, Ordering::Relaxed);
+            return;
+        }
+        let mut portal_process = self.portal_process.lock();
+        match portal_process.as_mut() {
+            Some(process) => process.set_inside_portal(pos),
+            None => *portal_process = Some(PortalProcessor::new(pos)),
+        }
+    }
+
+    fn can_ride(&self, _vehicle: &dyn Entity) -> bool {
+        !self.is_crouching()
+    }
+
+    /// Vanilla: `Player.DEFAULT_VEHICLE_ATTACHMENT`.
+    fn vehicle_attachment_y(&self) -> f64 {
+        0.6
+    }
+
+    fn teleport_to(&self, pos: DVec3) {
+        let (yaw, pitch) = self.rotation.load();
+        self.teleport(pos.x, pos.y, pos.z, yaw, pitch);
+    }
 }
 
 impl LivingEntity for Player {
@@ -3014,9 +4963,30 @@ impl LivingEntity for Player {
         self.entity_data.lock().health.set(clamped);
     }
 
-    fn get_max_health(&self) -> f32 {
-        // TODO: Get from attributes system when implemented
-        20.0
+    fn attributes(&self) -> &SyncMutex<AttributeMap> {
+        &self.attributes
+    }
+
+    /// Vanilla: `Player.attack()`, scaled by how charged the attack is.
+    fn do_hurt_target(&self, target: &dyn Entity) -> bool {
+        let scale = self.get_attack_strength_scale(0.5);
+        let mut damage =
+            self.get_attribute_value(ATTACK_DAMAGE) as f32 * (0.2 + scale * scale * 0.8);
+        self.reset_attack_strength_ticker();
+
+        // TODO: Also require a fall distance and no climbing, water or blindness
+        let critical = scale > 0.9
+            && !self.on_ground()
+            && self.velocity().y < 0.0
+            && !self.is_passenger()
+            && !self.is_sprinting();
+        if critical {
+            damage *= 1.5;
+        }
+
+        // TODO: Sweeping, knockback, weapon enchantments and the crit/attack sounds
+        let source = DamageSource::entity(vanilla_damage_types::PLAYER_ATTACK, self.id);
+        target.take_damage(&source, damage)
     }
 
     fn living_base(&self) -> &SyncMutex<LivingEntityBase> {
@@ -3035,10 +5005,8 @@ impl LivingEntity for Player {
         // Dirty flag set automatically, will sync on next tick
     }
 
-    fn get_armor_value(&self) -> i32 {
-        // TODO: Calculate from equipped items when data components are implemented
-        // Will iterate over ARMOR_SLOTS and sum armor values from each piece
-        0
+    fn is_fall_flying(&self) -> bool {
+        self.entity_state.lock().fall_flying
     }
 
     fn is_sprinting(&self) -> bool {
@@ -3047,11 +5015,19 @@ impl LivingEntity for Player {
 
     fn set_sprinting(&self, sprinting: bool) {
         self.entity_state.lock().sprinting = sprinting;
-        // TODO: Apply speed modifiers when attribute system is implemented
+        // Vanilla: LivingEntity.setSprinting()
+        let mut attributes = self.attributes.lock();
+        if let Some(speed) = attributes.get_instance_mut(MOVEMENT_SPEED) {
+            speed.remove_modifier(&SPEED_MODIFIER_SPRINTING.id);
+            if sprinting {
+                speed.add_transient_modifier(SPEED_MODIFIER_SPRINTING);
+            }
+        }
     }
 
+    /// Vanilla: `Player.getSpeed()` reads the attribute instead of the speed field.
     fn get_speed(&self) -> f32 {
-        self.speed.load()
+        self.get_attribute_value(MOVEMENT_SPEED) as f32
     }
 
     fn set_speed(&self, speed: f32) {
@@ -8,9 +8,11 @@ use std::sync::Arc;
 use glam::DVec3;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::shapes::AABBd;
+use steel_registry::entity_types::EntityDimensions;
 use steel_registry::vanilla_entities;
 use steel_utils::BlockPos;
 
+use crate::config::MovementCheckConfig;
 use crate::physics::{
     CollisionWorld, EntityPhysicsState, MoverType, WorldCollisionProvider, join_is_not_empty,
     move_entity,
@@ -22,21 +24,18 @@ pub const PLAYER_WIDTH: f64 = vanilla_entities::PLAYER.dimensions.width as f64;
 /// Player bounding box height (from entity type registry).
 pub const PLAYER_HEIGHT: f64 = vanilla_entities::PLAYER.dimensions.height as f64;
 
+/// Player dimensions while gliding, swimming or spinning with riptide
+/// (vanilla `Avatar.POSES` for `FALL_FLYING`, `SWIMMING` and `SPIN_ATTACK`).
+pub const LOW_POSE_DIMENSIONS: EntityDimensions = EntityDimensions::new(0.6, 0.6, 0.4);
+/// Player bounding box height while gliding, swimming or spinning.
+pub const LOW_POSE_HEIGHT: f64 = LOW_POSE_DIMENSIONS.height as f64;
+
 /// Small epsilon for AABB deflation (matches vanilla 1.0E-5).
 pub const COLLISION_EPSILON: f64 = 1.0E-5;
 
 /// Default gravity for players (blocks/tick²). Vanilla uses 0.08.
 pub const DEFAULT_GRAVITY: f64 = 0.08;
 
-/// Maximum movement speed threshold for normal movement (meters per tick squared).
-pub const SPEED_THRESHOLD_NORMAL: f64 = 100.0;
-/// Maximum movement speed threshold for elytra flight (meters per tick squared).
-pub const SPEED_THRESHOLD_FLYING: f64 = 300.0;
-
-/// Movement error threshold - if player ends up more than this far from target, reject.
-/// Matches vanilla's 0.0625 (1/16 of a block squared).
-pub const MOVEMENT_ERROR_THRESHOLD: f64 = 0.0625;
-
 /// Horizontal position clamping limit (matches vanilla).
 pub const CLAMP_HORIZONTAL: f64 = 3.0E7;
 /// Vertical position clamping limit (matches vanilla).
@@ -56,9 +55,16 @@ pub fn make_player_aabb(pos: DVec3) -> AABBd {
 }
 
 /// Creates a player bounding box at the given position, deflated by the collision epsilon.
+///
+/// Gliding, swimming and spinning players use the shorter box.
 #[must_use]
-pub fn make_player_aabb_deflated(pos: DVec3) -> AABBd {
-    make_player_aabb(pos).deflate(COLLISION_EPSILON)
+pub fn make_player_aabb_deflated(pos: DVec3, is_low_pose: bool) -> AABBd {
+    let height = if is_low_pose {
+        LOW_POSE_HEIGHT
+    } else {
+        PLAYER_HEIGHT
+    };
+    AABBd::entity_box(pos.x, pos.y, pos.z, PLAYER_WIDTH / 2.0, height).deflate(COLLISION_EPSILON)
 }
 
 /// Clamps a horizontal coordinate to vanilla limits.
@@ -108,6 +114,7 @@ pub struct MoveResult {
 /// * `delta` - The desired movement vector
 /// * `is_crouching` - Whether the player is sneaking (for edge prevention)
 /// * `on_ground` - Whether the player is currently on ground (affects step-up)
+/// * `is_low_pose` - Whether the player is gliding, swimming or spinning (uses the 0.6 tall box)
 ///
 /// # Returns
 /// A `MoveResult` containing the resolved movement and collision info.
@@ -118,11 +125,15 @@ pub fn simulate_move(
     delta: DVec3,
     is_crouching: bool,
     on_ground: bool,
+    is_low_pose: bool,
 ) -> MoveResult {
     // Create physics state for the player
     let mut state = EntityPhysicsState::new(start_pos, vanilla_entities::PLAYER);
     state.is_crouching = is_crouching;
     state.on_ground = on_ground;
+    if is_low_pose {
+        state.set_dimensions(LOW_POSE_DIMENSIONS);
+    }
 
     // Create collision provider
     let collision_world = WorldCollisionProvider::new(world);
@@ -145,8 +156,8 @@ pub fn simulate_move(
 ///
 /// Used to allow movement when already stuck in blocks.
 #[must_use]
-pub fn is_in_collision(world: &Arc<World>, pos: DVec3) -> bool {
-    let aabb = make_player_aabb_deflated(pos);
+pub fn is_in_collision(world: &Arc<World>, pos: DVec3, is_low_pose: bool) -> bool {
+    let aabb = make_player_aabb_deflated(pos, is_low_pose);
 
     let min_x = aabb.min_x.floor() as i32;
     let max_x = aabb.max_x.ceil() as i32;
@@ -185,25 +196,70 @@ pub fn is_in_collision(world: &Arc<World>, pos: DVec3) -> bool {
 ///
 /// Matches vanilla `ServerGamePacketListenerImpl.isEntityCollidingWithAnythingNew()`.
 #[must_use]
-pub fn is_colliding_with_new_blocks(world: &Arc<World>, old_pos: DVec3, new_pos: DVec3) -> bool {
-    let old_aabb = make_player_aabb_deflated(old_pos);
-    let new_aabb = make_player_aabb_deflated(new_pos);
+pub fn is_colliding_with_new_blocks(
+    world: &Arc<World>,
+    old_pos: DVec3,
+    new_pos: DVec3,
+    is_low_pose: bool,
+) -> bool {
+    is_box_colliding_with_new_blocks(
+        world,
+        &make_player_aabb_deflated(old_pos, is_low_pose),
+        &make_player_aabb_deflated(new_pos, is_low_pose),
+    )
+}
 
+/// Checks if `new_aabb` collides with blocks that `old_aabb` did not collide with.
+///
+/// Both boxes are expected to be deflated by [`COLLISION_EPSILON`] already. This is the
+/// size-independent part of [`is_colliding_with_new_blocks`], also used for vehicles.
+#[must_use]
+pub fn is_box_colliding_with_new_blocks(
+    world: &Arc<World>,
+    old_aabb: &AABBd,
+    new_aabb: &AABBd,
+) -> bool {
     // Use physics collision provider for consistency
     let collision_world = WorldCollisionProvider::new(world);
-    let collisions = collision_world.get_block_collisions(&new_aabb);
-
-    // Check if any collision is NEW (not present at old position)
-    for collision_aabb in &collisions {
-        // If new position collides but old didn't, this is a NEW collision
-        if join_is_not_empty(&new_aabb, collision_aabb)
-            && !join_is_not_empty(&old_aabb, collision_aabb)
-        {
-            return true;
-        }
-    }
+    let collisions = collision_world.get_block_collisions(new_aabb);
 
-    false
+    // If new position collides but old didn't, this is a NEW collision
+    collisions.iter().any(|collision_aabb| {
+        join_is_not_empty(new_aabb, collision_aabb) && !join_is_not_empty(old_aabb, collision_aabb)
+    })
+}
+
+/// Checks if any block collision shape intersects `aabb`.
+///
+/// Vanilla equivalent: the block part of `!Level.noCollision(entity, aabb)`.
+#[must_use]
+pub fn has_block_collision(world: &Arc<World>, aabb: &AABBd) -> bool {
+    !WorldCollisionProvider::new(world)
+        .get_block_collisions(aabb)
+        .is_empty()
+}
+
+/// Checks if there are only air blocks around a player at `pos`, including just
+/// below their feet, so they can't be standing, climbing or swimming.
+///
+/// Vanilla equivalent: `ServerGamePacketListenerImpl.noBlocksAround()`.
+#[must_use]
+pub fn no_blocks_around(world: &Arc<World>, pos: DVec3) -> bool {
+    let aabb = make_player_aabb(pos).inflate(0.0625);
+
+    let min_x = aabb.min_x.floor() as i32;
+    let max_x = aabb.max_x.floor() as i32;
+    // Vanilla: expandTowards(0.0, -0.55, 0.0)
+    let min_y = (aabb.min_y - 0.55).floor() as i32;
+    let max_y = aabb.max_y.floor() as i32;
+    let min_z = aabb.min_z.floor() as i32;
+    let max_z = aabb.max_z.floor() as i32;
+
+    (min_x..=max_x).all(|bx| {
+        (min_y..=max_y).all(|by| {
+            (min_z..=max_z).all(|bz| world.get_block_state(BlockPos::new(bx, by, bz)).is_air())
+        })
+    })
 }
 
 /// Input parameters for movement validation.
@@ -221,9 +277,14 @@ pub struct MovementInput {
     pub delta_packets: i32,
     /// Whether the player is using elytra.
     pub is_fall_flying: bool,
-    /// Whether to skip anti-cheat checks (spectator, creative, tick frozen, gamerules).
-    /// When true, all validation checks are bypassed.
+    /// Whether the player is gliding, swimming or spinning, using the 0.6 tall box.
+    pub is_low_pose: bool,
+    /// Whether to skip the speed and position error checks (spectator, creative,
+    /// tick frozen, gamerules).
     pub skip_checks: bool,
+    /// Whether the player moves through blocks (spectators), skipping the
+    /// collision check too.
+    pub no_physics: bool,
     /// Whether the player is in post-impulse grace period.
     pub in_impulse_grace: bool,
     /// Whether the player is crouching (for sneak-edge prevention).
@@ -243,6 +304,11 @@ pub struct MovementValidation {
     pub move_result: MoveResult,
     /// Why the movement failed (if invalid).
     pub failure_reason: Option<MovementFailure>,
+    /// How far the client's position is from the simulated one, squared.
+    pub error_dist_sq: f64,
+    /// Whether the client's position is too far from the simulated one. Only
+    /// rejects the move if the player wasn't stuck in blocks.
+    pub moved_wrongly: bool,
 }
 
 /// Reason for movement validation failure.
@@ -259,9 +325,14 @@ pub enum MovementFailure {
 /// Validates a player's movement.
 ///
 /// This encapsulates the movement validation logic from vanilla's `handleMovePlayer`.
-/// It runs physics simulation and checks for speed hacks, position errors, and collisions.
+/// It runs physics simulation and checks for speed hacks, position errors, and collisions
+/// with blocks the player wasn't already in (noclip).
 #[must_use]
-pub fn validate_movement(world: &Arc<World>, input: &MovementInput) -> MovementValidation {
+pub fn validate_movement(
+    world: &Arc<World>,
+    input: &MovementInput,
+    config: &MovementCheckConfig,
+) -> MovementValidation {
     let target_pos = input.target_pos;
     let first_good = input.first_good_pos;
     let last_good = input.last_good_pos;
@@ -275,9 +346,9 @@ pub fn validate_movement(world: &Arc<World>, input: &MovementInput) -> MovementV
     // Speed check
     if !input.skip_checks {
         let threshold = if input.is_fall_flying {
-            SPEED_THRESHOLD_FLYING
+            config.max_elytra_speed
         } else {
-            SPEED_THRESHOLD_NORMAL
+            config.max_speed
         } * f64::from(input.delta_packets);
 
         if moved_dist_sq - input.expected_velocity_sq > threshold {
@@ -293,6 +364,8 @@ pub fn validate_movement(world: &Arc<World>, input: &MovementInput) -> MovementV
                     on_ground: false,
                 },
                 failure_reason: Some(MovementFailure::TooFast),
+                error_dist_sq: 0.0,
+                moved_wrongly: false,
             };
         }
     }
@@ -311,6 +384,7 @@ pub fn validate_movement(world: &Arc<World>, input: &MovementInput) -> MovementV
         move_delta,
         input.is_crouching,
         input.on_ground,
+        input.is_low_pose,
     );
 
     // Calculate error between client position and server-simulated position
@@ -326,43 +400,27 @@ pub fn validate_movement(world: &Arc<World>, input: &MovementInput) -> MovementV
     let error_dist_sq = error_x * error_x + error_y * error_y + error_z * error_z;
 
     // Movement error check
-    let error_check_failed = !input.in_impulse_grace && error_dist_sq > MOVEMENT_ERROR_THRESHOLD;
-
-    // Collision checks
-    let was_in_collision = is_in_collision(world, last_good);
-    let collision_check_failed = error_check_failed
-        && was_in_collision
-        && is_colliding_with_new_blocks(world, last_good, target_pos);
-
-    let new_collision_without_error =
-        !error_check_failed && is_colliding_with_new_blocks(world, last_good, target_pos);
-
-    // Determine if movement failed
-    let movement_failed = !input.skip_checks
-        && ((error_check_failed && !was_in_collision)
-            || collision_check_failed
-            || new_collision_without_error);
-
-    if movement_failed {
-        let reason = if error_check_failed && !was_in_collision {
-            MovementFailure::PositionError
-        } else {
-            MovementFailure::Collision
-        };
-
-        return MovementValidation {
-            is_valid: false,
-            move_delta,
-            move_result,
-            failure_reason: Some(reason),
-        };
-    }
+    let moved_wrongly =
+        !input.skip_checks && !input.in_impulse_grace && error_dist_sq > config.max_error;
+
+    // Moving wrongly is fine while stuck in blocks, moving into new blocks never is
+    let failure_reason = if input.no_physics {
+        None
+    } else if moved_wrongly && !is_in_collision(world, last_good, input.is_low_pose) {
+        Some(MovementFailure::PositionError)
+    } else if is_colliding_with_new_blocks(world, last_good, target_pos, input.is_low_pose) {
+        Some(MovementFailure::Collision)
+    } else {
+        None
+    };
 
     MovementValidation {
-        is_valid: true,
+        is_valid: failure_reason.is_none(),
         move_delta,
         move_result,
-        failure_reason: None,
+        failure_reason,
+        error_dist_sq,
+        moved_wrongly,
     }
 }
 
@@ -397,4 +455,15 @@ mod tests {
         assert!((aabb.min_z - (-0.3)).abs() < 0.001);
         assert!((aabb.max_z - 0.3).abs() < 0.001);
     }
+
+    #[test]
+    fn test_make_player_aabb_low_pose() {
+        let pos = DVec3::new(0.0, 64.0, 0.0);
+        let standing = make_player_aabb_deflated(pos, false);
+        let swimming = make_player_aabb_deflated(pos, true);
+
+        assert!((standing.max_y - 65.8).abs() < 0.001);
+        assert!((swimming.max_y - 64.6).abs() < 0.001);
+        assert!((swimming.max_x - standing.max_x).abs() < 1.0e-9);
+    }
 }
@@ -32,6 +32,25 @@ pub struct MovementState {
 
     /// Last `on_ground` state sent to tracking players (for detecting changes).
     pub last_sent_on_ground: bool,
+
+    /// Distance fallen since the player last stood on the ground.
+    pub fall_distance: f64,
+
+    /// Whether the last move left the player in the air without being able to fly.
+    /// Matches vanilla `clientIsFloating`.
+    pub client_is_floating: bool,
+    /// Ticks the player has been floating for.
+    /// Matches vanilla `aboveGroundTickCount`.
+    pub above_ground_tick_count: i32,
+
+    /// The id of the vehicle the player controlled at the start of the tick.
+    /// Matches vanilla `lastVehicle`.
+    pub last_vehicle: Option<i32>,
+    /// Vehicle position at start of tick (for speed validation).
+    /// Matches vanilla `vehicleFirstGoodX/Y/Z`.
+    pub vehicle_first_good_position: DVec3,
+    /// Last known good vehicle position (for collision rollback).
+    pub vehicle_last_good_position: DVec3,
 }
 
 impl MovementState {
@@ -48,6 +67,12 @@ impl MovementState {
             last_impulse_tick: 0,
             position_sync_delay: 0,
             last_sent_on_ground: false,
+            fall_distance: 0.0,
+            client_is_floating: false,
+            above_ground_tick_count: 0,
+            last_vehicle: None,
+            vehicle_first_good_position: DVec3::new(0.0, 0.0, 0.0),
+            vehicle_last_good_position: DVec3::new(0.0, 0.0, 0.0),
         }
     }
 
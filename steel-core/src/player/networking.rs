@@ -5,19 +5,22 @@ use std::sync::{Arc, Weak};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use steel_protocol::packet_reader::TCPNetworkDecoder;
-use steel_protocol::packet_traits::{ClientPacket, CompressionInfo, EncodedPacket, ServerPacket};
+use steel_protocol::packet_traits::{
+    ClientPacket, CompressionInfo, EncodedPacket, SerializedPacket, ServerPacket,
+};
 use steel_protocol::packet_writer::TCPNetworkEncoder;
 use steel_protocol::packets::common::{
-    CDisconnect, CKeepAlive, CPongResponse, SClientInformation, SCustomPayload, SKeepAlive,
-    SPingRequest,
+    CDisconnect, CKeepAlive, CPongResponse, SClientInformation, SCustomClickAction, SCustomPayload,
+    SKeepAlive, SPingRequest,
 };
 use steel_protocol::packets::game::{
     CBundleDelimiter, SAcceptTeleportation, SChangeGameMode, SChat, SChatAck, SChatCommand,
-    SChatSessionUpdate, SChunkBatchReceived, SClientCommand, SClientTickEnd, SCommandSuggestion,
-    SContainerButtonClick, SContainerClick, SContainerClose, SContainerSlotStateChanged,
-    SMovePlayerPos, SMovePlayerPosRot, SMovePlayerRot, SMovePlayerStatusOnly, SPickItemFromBlock,
-    SPlayerAbilities, SPlayerAction, SPlayerInput, SPlayerLoad, SSetCarriedItem,
-    SSetCreativeModeSlot, SSignUpdate, SSwing, SUseItem, SUseItemOn,
+    SChatCommandSigned, SChatSessionUpdate, SChunkBatchReceived, SClientCommand, SClientTickEnd,
+    SCommandSuggestion, SContainerButtonClick, SContainerClick, SContainerClose,
+    SContainerSlotStateChanged, SInteract, SMovePlayerPos, SMovePlayerPosRot, SMovePlayerRot,
+    SMovePlayerStatusOnly, SMoveVehicle, SPaddleBoat, SPickItemFromBlock, SPickItemFromEntity,
+    SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerLoad, SRenameItem,
+    SSetBeacon, SSetCarriedItem, SSetCreativeModeSlot, SSignUpdate, SSwing, SUseItem, SUseItemOn,
 };
 
 use steel_protocol::utils::{ConnectionProtocol, PacketError, RawPacket};
@@ -31,13 +34,18 @@ use text_components::resolving::TextResolutor;
 use tokio::io::{BufReader, BufWriter};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::select;
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio_util::sync::CancellationToken;
 
-use crate::command::sender::CommandSender;
+use crate::config::STEEL_CONFIG;
 use crate::player::Player;
 use crate::player::connection::NetworkConnection;
+use crate::player::outbound_queue::{OutboundPacket, OutboundQueue, OutboundReceiver, QueueError};
+use crate::player::packet_recorder::PacketRecorder;
 use crate::server::Server;
+use crate::server::watchdog::WATCHDOG;
+
+/// The most queued packets written to the socket before flushing it.
+const SEND_BATCH_SIZE: usize = 256;
 
 /// Builder for creating packet bundles.
 ///
@@ -86,7 +94,7 @@ struct KeepAliveTracker {
 
 /// A connection to a Java client.
 pub struct JavaConnection {
-    outgoing_packets: UnboundedSender<EncodedPacket>,
+    outgoing_packets: OutboundQueue,
     cancel_token: CancellationToken,
     compression: Option<CompressionInfo>,
     network_writer: Arc<AsyncMutex<TCPNetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
@@ -100,7 +108,7 @@ pub struct JavaConnection {
 impl JavaConnection {
     /// Creates a new `JavaConnection`.
     pub const fn new(
-        outgoing_packets: UnboundedSender<EncodedPacket>,
+        outgoing_packets: OutboundQueue,
         cancel_token: CancellationToken,
         compression: Option<CompressionInfo>,
         network_writer: Arc<AsyncMutex<TCPNetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
@@ -179,30 +187,43 @@ impl JavaConnection {
 
     /// Disconnects the client.
     pub fn disconnect(&self, reason: impl Into<TextComponent>) {
-        self.send_packet(CDisconnect::new(&reason.into(), self));
+        let mut reason = reason.into();
+        if let Some(player) = self.player.upgrade() {
+            reason = player.localize(&reason);
+        }
+        self.send_packet(CDisconnect::new(&reason, self));
         self.close();
     }
 
     /// Sends a packet to the client.
     ///
+    /// Only the packet's bytes are written here, compressing them is left to
+    /// the connection's sending task.
+    ///
     /// # Panics
     /// - If the packet fails to be encoded.
-    /// - If the packet fails to be sent through the channel.
     pub fn send_packet<P: ClientPacket>(&self, packet: P) {
-        let packet = EncodedPacket::from_bare(packet, self.compression, ConnectionProtocol::Play)
+        let packet = SerializedPacket::from_bare(packet, ConnectionProtocol::Play)
             .expect("Failed to encode packet");
-        if self.outgoing_packets.send(packet).is_err() {
-            self.close();
-        }
+        self.queue_packet(OutboundPacket::Serialized(packet, self.compression));
     }
 
     /// Sends an encoded packet to the client.
-    ///
-    /// # Panics
-    /// - If the packet fails to be sent through the channel.
     pub fn send_encoded_packet(&self, packet: EncodedPacket) {
-        if self.outgoing_packets.send(packet).is_err() {
-            self.close();
+        self.queue_packet(OutboundPacket::Encoded(packet));
+    }
+
+    fn queue_packet(&self, packet: OutboundPacket) {
+        WATCHDOG.packet_sent();
+        match self.outgoing_packets.push(packet) {
+            Ok(_) => {}
+            Err(QueueError::Closed) => self.close(),
+            Err(err @ QueueError::OverBudget { .. }) => {
+                if !self.closed() {
+                    log::warn!("Client {} can't keep up with its packets: {err}", self.id);
+                }
+                self.close();
+            }
         }
     }
 
@@ -227,26 +248,32 @@ impl JavaConnection {
         clippy::too_many_lines,
         reason = "single match dispatch over all play packets; splitting would hurt readability"
     )]
-    pub fn process_packet(
+    pub async fn process_packet(
         &self,
         packet: RawPacket,
         player: Arc<Player>,
         server: Arc<Server>,
     ) -> Result<(), PacketError> {
+        WATCHDOG.packet_received();
         let data = &mut Cursor::new(packet.payload.as_slice());
 
         match packet.id {
             play::S_ACCEPT_TELEPORTATION => {
                 player.handle_accept_teleportation(SAcceptTeleportation::read_packet(data)?);
             }
-            play::C_CUSTOM_PAYLOAD => {
-                player.handle_custom_payload(SCustomPayload::read_packet(data)?);
+            play::S_CUSTOM_PAYLOAD => {
+                player.handle_custom_payload(SCustomPayload::read_packet(data)?, &server);
             }
             play::S_CHAT => {
-                player.handle_chat(SChat::read_packet(data)?, Arc::clone(&player));
+                player
+                    .handle_chat(SChat::read_packet(data)?, Arc::clone(&player))
+                    .await;
+                player.detect_rate_spam(STEEL_CONFIG.chat_spam.message_cost);
             }
             play::S_CHAT_SESSION_UPDATE => {
-                player.handle_chat_session_update(SChatSessionUpdate::read_packet(data)?);
+                player
+                    .handle_chat_session_update(SChatSessionUpdate::read_packet(data)?)
+                    .await;
             }
             play::S_CHAT_ACK => {
                 player.handle_chat_ack(SChatAck::read_packet(data)?);
@@ -287,11 +314,17 @@ impl JavaConnection {
                 player.send_inventory_to_remote();
             }
             play::S_CHAT_COMMAND => {
-                server.command_dispatcher.read().handle_command(
-                    CommandSender::Player(player),
-                    SChatCommand::read_packet(data)?.command,
-                    &server,
-                );
+                player.handle_chat_command(SChatCommand::read_packet(data)?, Arc::clone(&player));
+                player.detect_rate_spam(STEEL_CONFIG.chat_spam.command_cost);
+            }
+            play::S_CHAT_COMMAND_SIGNED => {
+                player
+                    .handle_chat_command_signed(
+                        SChatCommandSigned::read_packet(data)?,
+                        Arc::clone(&player),
+                    )
+                    .await;
+                player.detect_rate_spam(STEEL_CONFIG.chat_spam.command_cost);
             }
             play::S_COMMAND_SUGGESTION => {
                 let packet = SCommandSuggestion::read_packet(data)?;
@@ -342,10 +375,32 @@ impl JavaConnection {
                 let packet = SPlayerAction::read_packet(data)?;
                 player.handle_player_action(packet);
             }
+            play::S_PLAYER_COMMAND => {
+                player.handle_player_command(SPlayerCommand::read_packet(data)?);
+            }
             play::S_PICK_ITEM_FROM_BLOCK => {
                 let packet = SPickItemFromBlock::read_packet(data)?;
                 player.handle_pick_item_from_block(packet);
             }
+            play::S_PICK_ITEM_FROM_ENTITY => {
+                let packet = SPickItemFromEntity::read_packet(data)?;
+                player.handle_pick_item_from_entity(packet);
+            }
+            play::S_INTERACT => {
+                player.handle_interact(SInteract::read_packet(data)?);
+            }
+            play::S_MOVE_VEHICLE => {
+                player.handle_move_vehicle(SMoveVehicle::read_packet(data)?);
+            }
+            play::S_PADDLE_BOAT => {
+                player.handle_paddle_boat(SPaddleBoat::read_packet(data)?);
+            }
+            play::S_SET_BEACON => {
+                player.handle_set_beacon(SSetBeacon::read_packet(data)?);
+            }
+            play::S_RENAME_ITEM => {
+                player.handle_rename_item(SRenameItem::read_packet(data)?);
+            }
             play::S_SIGN_UPDATE => {
                 let packet = SSignUpdate::read_packet(data)?;
                 player.handle_sign_update(packet);
@@ -358,6 +413,20 @@ impl JavaConnection {
                 let packet = SPingRequest::read_packet(data)?;
                 player.send_packet(CPongResponse::new(packet.time));
             }
+            play::S_CUSTOM_CLICK_ACTION => {
+                let packet = SCustomClickAction::read_packet(data)?;
+                if !server.click_callbacks.handle_click(
+                    &player,
+                    &packet.id,
+                    packet.payload.as_ref(),
+                ) {
+                    log::debug!(
+                        "{} clicked unknown custom action {}",
+                        player.gameprofile.name,
+                        packet.id
+                    );
+                }
+            }
             play::S_CHANGE_GAME_MODE => {
                 // TODO: Check player permission level (Or gamemode permission)
                 let packet = SChangeGameMode::read_packet(data)?;
@@ -374,6 +443,10 @@ impl JavaConnection {
         mut reader: TCPNetworkDecoder<BufReader<OwnedReadHalf>>,
         server: Arc<Server>,
     ) {
+        let mut recorder = self
+            .player
+            .upgrade()
+            .and_then(|player| PacketRecorder::for_player(&player.gameprofile.name));
         loop {
             select! {
                 () = self.wait_for_close() => {
@@ -382,12 +455,23 @@ impl JavaConnection {
                 packet = reader.get_raw_packet() => {
                     match packet {
                         Ok(packet) => {
+                            let packet_id = packet.id;
+                            if let Some(active) = &mut recorder
+                                && let Err(err) = active.record(&packet)
+                            {
+                                log::warn!(
+                                    "Stopped recording {}: {err}",
+                                    active.path().display()
+                                );
+                                recorder = None;
+                            }
                             if let Some(player) = self.player.upgrade()
-                                && let Err(err) = self.process_packet(packet, player, server.clone()) {
+                                && let Err(err) = self.process_packet(packet, player, server.clone()).await {
                                 log::warn!(
-                                    "Failed to get packet from client {}: {err}",
+                                    "Failed to decode packet {packet_id:#04x} from client {}: {err}",
                                     self.id
                                 );
+                                self.disconnect(translations::DISCONNECT_PACKET_ERROR.msg());
                             }
                         }
                         Err(err) => {
@@ -404,24 +488,20 @@ impl JavaConnection {
     ///
     /// # Panics
     /// - If the player is not available.
-    pub async fn sender(&self, mut sender_recv: UnboundedReceiver<EncodedPacket>) {
+    pub async fn sender(&self, mut sender_recv: OutboundReceiver) {
+        let mut batch = Vec::with_capacity(SEND_BATCH_SIZE);
         loop {
             select! {
                 () = self.wait_for_close() => {
                     break;
                 }
-                packet = sender_recv.recv() => {
-                    if let Some(packet) = packet {
-                        if let Err(err) = self.network_writer.lock().await.write_packet(&packet).await
-                        {
-                            log::warn!("Failed to send packet to client {}: {err}", self.id);
-                            self.close();
-                        }
-                    } else {
-                        //log::warn!(
-                        //    "Internal packet_sender_recv channel closed for client {}",
-                        //    self.id
-                        //);
+                received = sender_recv.recv_many(&mut batch, SEND_BATCH_SIZE) => {
+                    if received == 0 {
+                        self.close();
+                        continue;
+                    }
+                    if let Err(err) = self.write_batch(&mut batch, &sender_recv).await {
+                        log::warn!("Failed to send packet to client {}: {err}", self.id);
                         self.close();
                     }
                 }
@@ -432,6 +512,30 @@ impl JavaConnection {
         let world = player.world.clone();
         world.remove_player(player).await;
     }
+
+    /// Compresses and writes a batch of queued packets, flushing the socket
+    /// once for all of them.
+    async fn write_batch(
+        &self,
+        batch: &mut Vec<OutboundPacket>,
+        sender_recv: &OutboundReceiver,
+    ) -> Result<(), PacketError> {
+        let mut writer = self.network_writer.lock().await;
+        let mut bytes = 0;
+        let mut result = Ok(());
+        for packet in batch.drain(..) {
+            bytes += packet.len();
+            if result.is_ok() {
+                result = match packet.encode() {
+                    Ok(packet) => writer.write_packet_unflushed(&packet).await,
+                    Err(err) => Err(err),
+                };
+            }
+        }
+        sender_recv.mark_sent(bytes);
+        result?;
+        writer.flush().await
+    }
 }
 
 impl TextResolutor for JavaConnection {
@@ -0,0 +1,250 @@
+//! The queue of packets waiting to be sent to a client, with backpressure for
+//! clients that can't keep up.
+//!
+//! Every connection has its own queue, so a slow client only ever holds up
+//! itself: its low priority packets are dropped once too much is queued, and
+//! it is disconnected once the queue exceeds its byte budget.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use steel_protocol::packet_traits::{
+    CompressionInfo, EncodedPacket, PacketPriority, SerializedPacket,
+};
+use steel_protocol::utils::PacketError;
+use thiserror::Error;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// A packet waiting to be sent.
+pub enum OutboundPacket {
+    /// A packet ready to send, often shared between players.
+    Encoded(EncodedPacket),
+    /// A packet still to be compressed, with the compression in effect when it
+    /// was queued.
+    Serialized(SerializedPacket, Option<CompressionInfo>),
+}
+
+impl OutboundPacket {
+    /// The number of bytes the packet counts for in the queue, before
+    /// compression if it isn't compressed yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Encoded(packet) => packet.len(),
+            Self::Serialized(packet, _) => packet.len(),
+        }
+    }
+
+    /// Returns true if the packet has no bytes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How important the packet is to deliver.
+    #[must_use]
+    pub const fn priority(&self) -> PacketPriority {
+        match self {
+            Self::Encoded(packet) => packet.priority,
+            Self::Serialized(packet, _) => packet.priority(),
+        }
+    }
+
+    /// Compresses and frames the packet, if it isn't already.
+    ///
+    /// # Errors
+    /// - If the packet is too long.
+    /// - If the packet fails to compress.
+    pub fn encode(self) -> Result<EncodedPacket, PacketError> {
+        match self {
+            Self::Encoded(packet) => Ok(packet),
+            Self::Serialized(packet, compression) => packet.encode(compression),
+        }
+    }
+}
+
+/// Why a packet couldn't be queued.
+#[derive(Debug, Error)]
+pub enum QueueError {
+    /// The connection's sending task has stopped.
+    #[error("The connection is closed")]
+    Closed,
+    /// The client fell too far behind.
+    #[error("{queued} bytes would be queued, more than the budget of {budget}")]
+    OverBudget {
+        /// The bytes that would be queued with the packet.
+        queued: usize,
+        /// The most bytes that may be queued.
+        budget: usize,
+    },
+}
+
+/// How much may be queued for a single connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueLimits {
+    /// Low priority packets are dropped while more bytes than this are queued.
+    pub low_priority_bytes: usize,
+    /// Packets that would take the queue past this many bytes are refused.
+    pub max_bytes: usize,
+}
+
+/// The sending half of a connection's queue.
+#[derive(Clone)]
+pub struct OutboundQueue {
+    sender: UnboundedSender<OutboundPacket>,
+    queued_bytes: Arc<AtomicUsize>,
+    limits: QueueLimits,
+}
+
+/// The receiving half of a connection's queue, read by the task writing to
+/// the socket.
+pub struct OutboundReceiver {
+    receiver: UnboundedReceiver<OutboundPacket>,
+    queued_bytes: Arc<AtomicUsize>,
+}
+
+/// Creates the queue for a new connection.
+#[must_use]
+pub fn channel(limits: QueueLimits) -> (OutboundQueue, OutboundReceiver) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let queued_bytes = Arc::new(AtomicUsize::new(0));
+    (
+        OutboundQueue {
+            sender,
+            queued_bytes: Arc::clone(&queued_bytes),
+            limits,
+        },
+        OutboundReceiver {
+            receiver,
+            queued_bytes,
+        },
+    )
+}
+
+impl OutboundQueue {
+    /// Queues a packet. Returns `false` if it was low priority and dropped
+    /// because the client is behind.
+    ///
+    /// # Errors
+    /// - [`QueueError::OverBudget`] if the packet would take the queue past its budget.
+    /// - [`QueueError::Closed`] if the connection's sending task has stopped.
+    pub fn push(&self, packet: OutboundPacket) -> Result<bool, QueueError> {
+        let len = packet.len();
+        let queued = self.queued_bytes.load(Ordering::Relaxed);
+        if packet.priority() == PacketPriority::Low && queued > self.limits.low_priority_bytes {
+            return Ok(false);
+        }
+        if queued + len > self.limits.max_bytes {
+            return Err(QueueError::OverBudget {
+                queued: queued + len,
+                budget: self.limits.max_bytes,
+            });
+        }
+
+        self.queued_bytes.fetch_add(len, Ordering::Relaxed);
+        if self.sender.send(packet).is_err() {
+            self.queued_bytes.fetch_sub(len, Ordering::Relaxed);
+            return Err(QueueError::Closed);
+        }
+        Ok(true)
+    }
+
+    /// The number of bytes queued but not yet sent.
+    #[must_use]
+    pub fn queued_bytes(&self) -> usize {
+        self.queued_bytes.load(Ordering::Relaxed)
+    }
+}
+
+impl OutboundReceiver {
+    /// Waits for the next packet. Returns `None` once every sender is gone.
+    pub async fn recv(&mut self) -> Option<OutboundPacket> {
+        self.receiver.recv().await
+    }
+
+    /// Waits for packets and moves up to `limit` of them into `buffer`, so they
+    /// can be written together. Returns 0 once every sender is gone.
+    pub async fn recv_many(&mut self, buffer: &mut Vec<OutboundPacket>, limit: usize) -> usize {
+        self.receiver.recv_many(buffer, limit).await
+    }
+
+    /// Releases `bytes` from the budget, once the packets they belong to are
+    /// written.
+    pub fn mark_sent(&self, bytes: usize) {
+        self.queued_bytes.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_protocol::packets::game::{CBundleDelimiter, CSound, SoundSource};
+    use steel_protocol::utils::ConnectionProtocol;
+
+    use super::*;
+
+    const LIMITS: QueueLimits = QueueLimits {
+        low_priority_bytes: 4,
+        max_bytes: 64,
+    };
+
+    fn serialized<P: steel_protocol::packet_traits::ClientPacket>(packet: P) -> OutboundPacket {
+        OutboundPacket::Serialized(
+            SerializedPacket::from_bare(packet, ConnectionProtocol::Play).expect("packet encodes"),
+            None,
+        )
+    }
+
+    fn sound() -> OutboundPacket {
+        serialized(CSound::new(
+            0,
+            SoundSource::Master,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            1.0,
+            0,
+        ))
+    }
+
+    #[test]
+    fn test_low_priority_dropped_when_behind() {
+        let (queue, _receiver) = channel(LIMITS);
+        assert!(queue.push(sound()).expect("queue is open"));
+        // The sound alone is over the low priority limit
+        assert!(!queue.push(sound()).expect("queue is open"));
+        assert!(
+            queue
+                .push(serialized(CBundleDelimiter))
+                .expect("queue is open")
+        );
+    }
+
+    #[test]
+    fn test_over_budget_refused() {
+        let (queue, receiver) = channel(LIMITS);
+        while queue.queued_bytes() < LIMITS.max_bytes {
+            queue
+                .push(serialized(CBundleDelimiter))
+                .expect("queue has room");
+        }
+        assert!(matches!(
+            queue.push(serialized(CBundleDelimiter)),
+            Err(QueueError::OverBudget { .. })
+        ));
+
+        receiver.mark_sent(queue.queued_bytes());
+        assert!(queue.push(serialized(CBundleDelimiter)).is_ok());
+    }
+
+    #[test]
+    fn test_closed_queue() {
+        let (queue, receiver) = channel(LIMITS);
+        drop(receiver);
+        assert!(matches!(
+            queue.push(serialized(CBundleDelimiter)),
+            Err(QueueError::Closed)
+        ));
+        assert_eq!(queue.queued_bytes(), 0);
+    }
+}
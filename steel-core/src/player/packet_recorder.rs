@@ -0,0 +1,255 @@
+//! Recording of the packets a player sends, so a session can be replayed
+//! against a test server to reproduce desyncs and movement check false
+//! positives.
+//!
+//! A recording is a JSON lines file with one [`PacketRecord`] per packet, in
+//! the order they arrived. Only play packets are recorded: whoever replays a
+//! recording logs in and configures the connection on its own.
+
+use std::fs::{self, File};
+use std::io::{self, Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use steel_protocol::packet_traits::ServerPacket;
+use steel_protocol::packets::common::{
+    SClientInformation, SCustomClickAction, SCustomPayload, SKeepAlive, SPingRequest,
+};
+use steel_protocol::packets::game::{
+    SAcceptTeleportation, SChangeGameMode, SChat, SChatAck, SChatCommand, SChatCommandSigned,
+    SChatSessionUpdate, SChunkBatchReceived, SClientCommand, SClientTickEnd, SCommandSuggestion,
+    SContainerButtonClick, SContainerClick, SContainerClose, SContainerSlotStateChanged, SInteract,
+    SMovePlayerPos, SMovePlayerPosRot, SMovePlayerRot, SMovePlayerStatusOnly, SMoveVehicle,
+    SPaddleBoat, SPickItemFromBlock, SPickItemFromEntity, SPlayerAbilities, SPlayerAction,
+    SPlayerCommand, SPlayerInput, SPlayerLoad, SRenameItem, SSetBeacon, SSetCarriedItem,
+    SSetCreativeModeSlot, SSignUpdate, SSwing, SUseItem, SUseItemOn,
+};
+use steel_protocol::utils::RawPacket;
+use steel_registry::packets::play;
+
+use crate::config::STEEL_CONFIG;
+
+/// A packet in a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PacketRecord {
+    /// Milliseconds since the recording started.
+    pub millis: u64,
+    /// The play packet ID.
+    pub id: i32,
+    /// The payload after the packet ID, base64 encoded.
+    pub payload: String,
+    /// The decoded fields, for reading the recording. Missing for packets that
+    /// failed to decode, which are still recorded since they may be the bug.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<String>,
+}
+
+impl PacketRecord {
+    /// The packet as it arrived.
+    ///
+    /// # Errors
+    /// If the payload isn't valid base64.
+    pub fn raw(&self) -> Result<RawPacket, base64::DecodeError> {
+        Ok(RawPacket {
+            id: self.id,
+            payload: BASE64_STANDARD.decode(&self.payload)?,
+        })
+    }
+}
+
+/// Writes the packets of one connection to a recording.
+pub struct PacketRecorder {
+    file: File,
+    path: PathBuf,
+    started: Instant,
+}
+
+impl PacketRecorder {
+    /// Starts recording the packets of the player `name`, if the config asks
+    /// for it. Failing to create the recording only logs a warning.
+    #[must_use]
+    pub fn for_player(name: &str) -> Option<Self> {
+        let config = &STEEL_CONFIG.packet_recording;
+        let recorded = config.players.is_empty()
+            || config
+                .players
+                .iter()
+                .any(|player| player.eq_ignore_ascii_case(name));
+        if !config.enabled || !recorded {
+            return None;
+        }
+
+        match Self::create(Path::new(&config.directory), name) {
+            Ok(recorder) => {
+                log::info!(
+                    "Recording the packets of {name} to {}",
+                    recorder.path.display()
+                );
+                Some(recorder)
+            }
+            Err(err) => {
+                log::warn!("Failed to start recording the packets of {name}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Creates a recording in `directory`, named after the player and the
+    /// time it started.
+    ///
+    /// # Errors
+    /// If the directory or file can't be created.
+    pub fn create(directory: &Path, name: &str) -> io::Result<Self> {
+        fs::create_dir_all(directory)?;
+        let started = Local::now().format("%Y-%m-%d_%H-%M-%S%.3f");
+        let path = directory.join(format!("{name}_{started}.jsonl"));
+        Ok(Self {
+            file: File::create_new(&path)?,
+            path,
+            started: Instant::now(),
+        })
+    }
+
+    /// Where the recording is written.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a packet to the recording. Every packet is written right away,
+    /// so a recording survives the server crashing.
+    ///
+    /// # Errors
+    /// If the recording can't be written to.
+    pub fn record(&mut self, packet: &RawPacket) -> io::Result<()> {
+        let record = PacketRecord {
+            millis: self.started.elapsed().as_millis() as u64,
+            id: packet.id,
+            payload: BASE64_STANDARD.encode(&packet.payload),
+            decoded: decode(packet),
+        };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        self.file.write_all(&line)
+    }
+}
+
+/// Reads a recording.
+///
+/// # Errors
+/// If the file can't be read or a line isn't a [`PacketRecord`].
+pub fn read_recording(path: &Path) -> io::Result<Vec<PacketRecord>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str(line).map_err(io::Error::from))
+        .collect()
+}
+
+/// Formats the fields of a play packet the server handles.
+fn decode(packet: &RawPacket) -> Option<String> {
+    macro_rules! decode {
+        ($($id:path => $packet:ty),* $(,)?) => {
+            match packet.id {
+                $($id => <$packet>::read_packet(&mut Cursor::new(packet.payload.as_slice()))
+                    .ok()
+                    .map(|decoded| format!("{decoded:?}")),)*
+                _ => None,
+            }
+        };
+    }
+
+    decode! {
+        play::S_ACCEPT_TELEPORTATION => SAcceptTeleportation,
+        play::S_CUSTOM_PAYLOAD => SCustomPayload,
+        play::S_CHAT => SChat,
+        play::S_CHAT_SESSION_UPDATE => SChatSessionUpdate,
+        play::S_CHAT_ACK => SChatAck,
+        play::S_CLIENT_INFORMATION => SClientInformation,
+        play::S_CLIENT_TICK_END => SClientTickEnd,
+        play::S_CHUNK_BATCH_RECEIVED => SChunkBatchReceived,
+        play::S_KEEP_ALIVE => SKeepAlive,
+        play::S_MOVE_PLAYER_POS => SMovePlayerPos,
+        play::S_MOVE_PLAYER_POS_ROT => SMovePlayerPosRot,
+        play::S_MOVE_PLAYER_ROT => SMovePlayerRot,
+        play::S_MOVE_PLAYER_STATUS_ONLY => SMovePlayerStatusOnly,
+        play::S_PLAYER_LOADED => SPlayerLoad,
+        play::S_CHAT_COMMAND => SChatCommand,
+        play::S_CHAT_COMMAND_SIGNED => SChatCommandSigned,
+        play::S_COMMAND_SUGGESTION => SCommandSuggestion,
+        play::S_CONTAINER_BUTTON_CLICK => SContainerButtonClick,
+        play::S_CONTAINER_CLICK => SContainerClick,
+        play::S_CONTAINER_CLOSE => SContainerClose,
+        play::S_CONTAINER_SLOT_STATE_CHANGED => SContainerSlotStateChanged,
+        play::S_SET_CREATIVE_MODE_SLOT => SSetCreativeModeSlot,
+        play::S_PLAYER_INPUT => SPlayerInput,
+        play::S_PLAYER_ABILITIES => SPlayerAbilities,
+        play::S_USE_ITEM_ON => SUseItemOn,
+        play::S_USE_ITEM => SUseItem,
+        play::S_SET_CARRIED_ITEM => SSetCarriedItem,
+        play::S_SWING => SSwing,
+        play::S_PLAYER_ACTION => SPlayerAction,
+        play::S_PLAYER_COMMAND => SPlayerCommand,
+        play::S_PICK_ITEM_FROM_BLOCK => SPickItemFromBlock,
+        play::S_PICK_ITEM_FROM_ENTITY => SPickItemFromEntity,
+        play::S_INTERACT => SInteract,
+        play::S_MOVE_VEHICLE => SMoveVehicle,
+        play::S_PADDLE_BOAT => SPaddleBoat,
+        play::S_SET_BEACON => SSetBeacon,
+        play::S_RENAME_ITEM => SRenameItem,
+        play::S_SIGN_UPDATE => SSignUpdate,
+        play::S_CLIENT_COMMAND => SClientCommand,
+        play::S_PING_REQUEST => SPingRequest,
+        play::S_CUSTOM_CLICK_ACTION => SCustomClickAction,
+        play::S_CHANGE_GAME_MODE => SChangeGameMode,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use glam::DVec3;
+    use steel_utils::serial::WriteTo;
+
+    use super::*;
+
+    #[test]
+    fn test_recording_round_trips() {
+        let directory = env::temp_dir().join(format!("steel-recording-{}", process::id()));
+        let mut recorder = PacketRecorder::create(&directory, "Recorder").expect("recording opens");
+
+        let mut payload = Vec::new();
+        DVec3::new(1.5, 64.0, -2.0)
+            .write(&mut payload)
+            .expect("writing to a Vec can't fail");
+        payload.push(1);
+        let packet = RawPacket {
+            id: play::S_MOVE_PLAYER_POS,
+            payload,
+        };
+        recorder.record(&packet).expect("recording is writable");
+        // Garbage is kept, just not decoded
+        let garbage = RawPacket {
+            id: play::S_MOVE_PLAYER_POS,
+            payload: vec![1, 2],
+        };
+        recorder.record(&garbage).expect("recording is writable");
+
+        let records = read_recording(recorder.path()).expect("recording reads back");
+        fs::remove_dir_all(&directory).ok();
+
+        assert_eq!(records.len(), 2);
+        let raw = records[0].raw().expect("payload is base64");
+        assert_eq!(raw.id, packet.id);
+        assert_eq!(raw.payload, packet.payload);
+        let decoded = records[0].decoded.as_deref().expect("packet decodes");
+        assert!(decoded.contains("SMovePlayerPos"), "{decoded}");
+        assert!(records[1].decoded.is_none());
+        assert_eq!(records[1].raw().expect("payload is base64").payload, [1, 2]);
+    }
+}
@@ -9,10 +9,15 @@ use simdnbt::{
     owned::{NbtCompound, NbtList, NbtTag},
 };
 use steel_registry::item_stack::ItemStack;
+use steel_registry::vanilla_entities;
 
+use crate::entity::Entity;
+use crate::entity::attribute::{AttributeMap, default_attributes};
+use crate::entity::effect::MobEffectInstance;
 use crate::inventory::container::Container;
 
-use super::{Player, abilities::Abilities};
+use super::attached_entities::{SavedVehicle, save_root_vehicle};
+use super::{MAX_AIR_SUPPLY, Player, RespawnConfig, abilities::Abilities};
 
 /// Current data version for player saves.
 /// Increment when making breaking changes to the format.
@@ -26,12 +31,8 @@ pub const PLAYER_DATA_VERSION: i32 = 1;
 /// # TODO: Missing vanilla fields
 /// The following fields should be added once their systems are implemented:
 /// - Food data: `foodLevel`, `foodSaturationLevel`, `foodExhaustionLevel`, `foodTickTimer`
-/// - Experience: `XpSeed`
-/// - Active potion effects: `active_effects` (List)
 /// - Score: `Score` (Int)
-/// - Ender chest inventory: `EnderItems` (List)
 /// - Last death location: `LastDeathLocation` (`GlobalPos`)
-/// - Respawn position: `SpawnX`, `SpawnY`, `SpawnZ`, `SpawnDimension`, `SpawnForced`, `SpawnAngle`
 #[derive(Debug, Clone)]
 pub struct PersistentPlayerData {
     /// Position (x, y, z) in absolute world coordinates.
@@ -58,6 +59,14 @@ pub struct PersistentPlayerData {
     /// NBT tag: `Health` (Float)
     pub health: f32,
 
+    /// Ticks the player keeps burning for; negative while immune to re-ignition.
+    /// NBT tag: `Fire` (Short)
+    pub fire: i16,
+
+    /// Ticks of air left, refilled out of water.
+    /// NBT tag: `Air` (Short)
+    pub air: i16,
+
     /// Current game mode (0=survival, 1=creative, 2=adventure, 3=spectator).
     /// NBT tag: `playerGameType` (Int)
     pub game_mode: i32,
@@ -78,6 +87,10 @@ pub struct PersistentPlayerData {
     /// NBT tag: `SelectedItemSlot` (Int)
     pub selected_slot: i32,
 
+    /// Ender chest items with slot indices.
+    /// NBT tag: `EnderItems` (List of Compounds)
+    pub ender_items: Vec<PersistentSlot>,
+
     /// Dimension identifier (e.g., "minecraft:overworld").
     /// NBT tag: `Dimension` (String)
     pub dimension: String,
@@ -99,10 +112,32 @@ pub struct PersistentPlayerData {
     /// NBT tag: `XpTotal` (Int)
     pub experience_total: i32,
 
+    /// Seed of the enchanting table offers
+    /// NBT tag: `XpSeed` (Int)
+    pub enchantment_seed: i32,
+
     /// A non decreasing value of the experience orbs added (/xp add, picking up orbs and advancements)
     /// this value can be negative by using (/xp add ... -x)
     /// NBT tag: `Score` (Int)
     pub score: i32,
+
+    /// The player's respawn point, if set.
+    /// NBT tag: `respawn` (Compound)
+    pub respawn: Option<RespawnConfig>,
+
+    /// Attribute base values and permanent modifiers.
+    /// NBT tag: `attributes` (List of Compounds)
+    pub attributes: AttributeMap,
+
+    /// Active mob effects with their remaining durations.
+    /// NBT tag: `active_effects` (List of Compounds)
+    pub active_effects: Vec<MobEffectInstance>,
+
+    /// The vehicle the player rides, with everything else riding it.
+    /// NBT tag: `RootVehicle` (Compound)
+    pub root_vehicle: Option<SavedVehicle>,
+    // TODO: Save the ender pearls the player threw (`ender_pearls`) once thrown
+    // ender pearls exist
 }
 
 /// Persistent abilities data.
@@ -161,12 +196,30 @@ impl PersistentPlayerData {
             }
         }
 
-        let (experience_level, experience_progress, experience_total, score) = {
+        let ender_items = {
+            let ender_chest = player.ender_chest_inventory.lock();
+            (0..ender_chest.get_container_size())
+                .filter_map(|slot| {
+                    let item = ender_chest.get_item(slot);
+                    (!item.is_empty()).then(|| PersistentSlot {
+                        slot: slot as i8,
+                        item: item.clone(),
+                    })
+                })
+                .collect()
+        };
+
+        // A vehicle that hasn't been spawned again since the last login is saved as loaded
+        let root_vehicle = save_root_vehicle(player)
+            .or_else(|| player.attached_entities.lock().pending_vehicle().cloned());
+
+        let (experience_level, experience_progress, experience_total, enchantment_seed, score) = {
             let lock = player.experience.lock();
             (
                 lock.level(),
                 lock.progress() as f32,
                 lock.total_points(),
+                lock.enchantment_seed,
                 lock.score,
             )
         };
@@ -178,6 +231,8 @@ impl PersistentPlayerData {
             on_ground,
             fall_flying,
             health: *entity_data.health.get(),
+            fire: i16::try_from(player.remaining_fire_ticks()).unwrap_or(i16::MAX),
+            air: *entity_data.air_supply.get() as i16,
             game_mode: player.game_mode.load() as i32,
             prev_game_mode: player.prev_game_mode.load() as i32,
             abilities: PersistentAbilities {
@@ -191,12 +246,18 @@ impl PersistentPlayerData {
             },
             inventory: slots,
             selected_slot: i32::from(inventory.get_selected_slot()),
+            ender_items,
             dimension: player.world.dimension.key.to_string(),
             data_version: PLAYER_DATA_VERSION,
             experience_level,
             experience_progress,
             experience_total,
+            enchantment_seed,
             score,
+            respawn: player.respawn_config.lock().clone(),
+            attributes: player.attributes.lock().clone(),
+            active_effects: player.active_effects.lock().values().cloned().collect(),
+            root_vehicle,
         }
     }
 
@@ -232,6 +293,8 @@ impl PersistentPlayerData {
         compound.insert("OnGround", i8::from(self.on_ground));
         compound.insert("FallFlying", i8::from(self.fall_flying));
         compound.insert("Health", self.health);
+        compound.insert("Fire", self.fire);
+        compound.insert("Air", self.air);
         compound.insert("playerGameType", self.game_mode);
         compound.insert("SelectedItemSlot", self.selected_slot);
         compound.insert("Dimension", self.dimension.clone());
@@ -240,27 +303,35 @@ impl PersistentPlayerData {
         // Abilities compound
         compound.insert("abilities", self.abilities.to_nbt());
 
-        // Inventory list
-        let inventory_list: Vec<NbtTag> = self
-            .inventory
-            .iter()
-            .map(|slot| {
-                let mut item_compound = match slot.item.clone().to_nbt_tag() {
-                    NbtTag::Compound(c) => c,
-                    _ => NbtCompound::new(),
-                };
-                item_compound.insert("Slot", slot.slot);
-                NbtTag::Compound(item_compound)
-            })
-            .collect();
-        compound.insert("Inventory", NbtList::from(inventory_list));
+        // Inventory lists
+        compound.insert("Inventory", slots_to_nbt(&self.inventory));
+        compound.insert("EnderItems", slots_to_nbt(&self.ender_items));
 
         // Experience
         compound.insert("XpLevel", self.experience_level);
         compound.insert("XpP", self.experience_progress);
         compound.insert("XpTotal", self.experience_total);
+        compound.insert("XpSeed", self.enchantment_seed);
         compound.insert("Score", self.score);
 
+        if let Some(respawn) = &self.respawn {
+            compound.insert("respawn", respawn.to_nbt());
+        }
+
+        compound.insert("attributes", self.attributes.save());
+
+        if !self.active_effects.is_empty() {
+            let effects = self
+                .active_effects
+                .iter()
+                .map(MobEffectInstance::save)
+                .collect();
+            compound.insert("active_effects", NbtList::Compound(effects));
+        }
+        if let Some(root_vehicle) = &self.root_vehicle {
+            compound.insert("RootVehicle", root_vehicle.to_nbt());
+        }
+
         compound
     }
 
@@ -306,6 +377,8 @@ impl PersistentPlayerData {
         let on_ground = nbt.byte("OnGround") != Some(0);
         let fall_flying = nbt.byte("FallFlying").is_some_and(|b| b != 0);
         let health = nbt.float("Health").unwrap_or(20.0);
+        let fire = nbt.short("Fire").unwrap_or(0);
+        let air = nbt.short("Air").unwrap_or(MAX_AIR_SUPPLY as i16);
         let game_mode = nbt.int("playerGameType").unwrap_or(0);
         let prev_game_mode = nbt.int("previousPlayerGameType").unwrap_or(0);
         let selected_slot = nbt.int("SelectedItemSlot").unwrap_or(0);
@@ -321,24 +394,37 @@ impl PersistentPlayerData {
             .map(|c| PersistentAbilities::from_nbt(&c))
             .unwrap_or_default();
 
-        // Inventory
-        let mut inventory = Vec::new();
-        if let Some(inv_list) = nbt.list("Inventory")
-            && let Some(compounds) = inv_list.compounds()
-        {
-            for item_compound in compounds {
-                let slot = item_compound.byte("Slot").unwrap_or(0);
-                if let Some(item) = ItemStack::from_borrowed_compound(&item_compound) {
-                    inventory.push(PersistentSlot { slot, item });
-                }
-            }
-        }
+        // Inventories
+        let inventory = slots_from_nbt(&nbt, "Inventory");
+        let ender_items = slots_from_nbt(&nbt, "EnderItems");
 
         let experience_level = nbt.int("XpLevel").unwrap_or(0);
         let experience_progress = nbt.float("XpP").unwrap_or(0.0);
         let experience_total = nbt.int("XpTotal").unwrap_or(0);
+        let enchantment_seed = nbt.int("XpSeed").unwrap_or(0);
         let score = nbt.int("Score").unwrap_or(0);
 
+        let respawn = nbt
+            .compound("respawn")
+            .and_then(|c| RespawnConfig::from_nbt(&c));
+
+        let mut attributes = default_attributes(vanilla_entities::PLAYER);
+        attributes.load(&nbt);
+
+        let active_effects = nbt
+            .list("active_effects")
+            .and_then(|list| list.compounds())
+            .map(|effects| {
+                effects
+                    .into_iter()
+                    .filter_map(|e| MobEffectInstance::load(&e))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let root_vehicle = nbt
+            .compound("RootVehicle")
+            .and_then(|c| SavedVehicle::from_nbt(&c));
+
         Some(Self {
             pos,
             motion,
@@ -346,19 +432,59 @@ impl PersistentPlayerData {
             on_ground,
             fall_flying,
             health,
+            fire,
+            air,
             game_mode,
             prev_game_mode,
             abilities,
             inventory,
             selected_slot,
+            ender_items,
             dimension,
             data_version,
             experience_level,
             experience_progress,
             experience_total,
+            enchantment_seed,
             score,
+            respawn,
+            attributes,
+            active_effects,
+            root_vehicle,
+        })
+    }
+}
+
+/// Serializes item slots to a list of item compounds with a `Slot` byte.
+fn slots_to_nbt(slots: &[PersistentSlot]) -> NbtList {
+    let list: Vec<NbtTag> = slots
+        .iter()
+        .map(|slot| {
+            let mut item_compound = match slot.item.clone().to_nbt_tag() {
+                NbtTag::Compound(c) => c,
+                _ => NbtCompound::new(),
+            };
+            item_compound.insert("Slot", slot.slot);
+            NbtTag::Compound(item_compound)
         })
+        .collect();
+    NbtList::from(list)
+}
+
+/// Reads the item slots stored in the list `key`, skipping invalid items.
+fn slots_from_nbt(nbt: &NbtCompoundView<'_, '_>, key: &str) -> Vec<PersistentSlot> {
+    let mut slots = Vec::new();
+    if let Some(list) = nbt.list(key)
+        && let Some(compounds) = list.compounds()
+    {
+        for item_compound in compounds {
+            let slot = item_compound.byte("Slot").unwrap_or(0);
+            if let Some(item) = ItemStack::from_borrowed_compound(&item_compound) {
+                slots.push(PersistentSlot { slot, item });
+            }
+        }
     }
+    slots
 }
 
 impl PersistentAbilities {
@@ -457,8 +583,13 @@ impl PersistentPlayerData {
             es.fall_flying = self.fall_flying;
         }
 
+        // Attributes
+        player.attributes.lock().assign_all_values(&self.attributes);
+
         // Health
         player.entity_data.lock().health.set(self.health);
+        player.set_remaining_fire_ticks(i32::from(self.fire));
+        player.set_air_supply(i32::from(self.air));
 
         // Game mode
         let game_mode = self.game_mode.into();
@@ -490,11 +621,40 @@ impl PersistentPlayerData {
             inventory.set_selected_slot(selected);
         }
 
+        // Ender chest
+        {
+            let mut ender_chest = player.ender_chest_inventory.lock();
+            ender_chest.clear_content();
+            for slot_data in &self.ender_items {
+                let slot_index = slot_data.slot as usize;
+                if slot_index < ender_chest.get_container_size() {
+                    ender_chest.set_item(slot_index, slot_data.item.clone());
+                }
+            }
+        }
+
         {
             let mut experience = player.experience.lock();
             experience.set_levels(self.experience_level);
             experience.set_progress(f64::from(self.experience_progress));
             experience.score = self.score;
+            experience.enchantment_seed = self.enchantment_seed;
         }
+
+        // Respawn point
+        player.respawn_config.lock().clone_from(&self.respawn);
+
+        // Active effects
+        *player.active_effects.lock() = self
+            .active_effects
+            .iter()
+            .map(|effect| (effect.effect.clone(), effect.clone()))
+            .collect();
+
+        // The vehicle, spawned once its chunk is loaded
+        player
+            .attached_entities
+            .lock()
+            .set_pending(self.root_vehicle.clone());
     }
 }
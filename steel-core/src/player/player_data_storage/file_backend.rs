@@ -0,0 +1,136 @@
+//! Flat file player data backend.
+//!
+//! Saves player data to `<dir>/<uuid>.dat` as gzip-compressed NBT, like vanilla.
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use uuid::Uuid;
+
+use super::PlayerDataBackend;
+
+/// Stores each player in its own gzip-compressed `<uuid>.dat` file.
+pub struct FileBackend {
+    /// Path to the players directory.
+    players_dir: PathBuf,
+}
+
+impl FileBackend {
+    /// Opens the file backend, creating the players directory if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn open(players_dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let players_dir = players_dir.into();
+        fs::create_dir_all(&players_dir)?;
+        Ok(Self { players_dir })
+    }
+
+    /// Returns the path to a player's data file.
+    fn get_player_file(&self, uuid: Uuid) -> PathBuf {
+        self.players_dir.join(format!("{uuid}.dat"))
+    }
+
+    /// Returns the path to a player's temporary data file.
+    fn get_temp_file(&self, uuid: Uuid) -> PathBuf {
+        self.players_dir.join(format!("{uuid}.dat.tmp"))
+    }
+
+    /// Returns the path to a player's backup data file.
+    fn get_backup_file(&self, uuid: Uuid) -> PathBuf {
+        self.players_dir.join(format!("{uuid}.dat_old"))
+    }
+
+    /// Writes a single player's data.
+    ///
+    /// Uses atomic write pattern:
+    /// 1. Write to `<uuid>.dat.tmp`
+    /// 2. Rename existing `<uuid>.dat` to `<uuid>.dat_old` (backup)
+    /// 3. Rename `.tmp` to `.dat`
+    fn save(&self, uuid: Uuid, nbt_bytes: &[u8]) -> io::Result<()> {
+        let temp_path = self.get_temp_file(uuid);
+        let final_path = self.get_player_file(uuid);
+        let backup_path = self.get_backup_file(uuid);
+
+        let mut compressed = Vec::new();
+        {
+            let mut encoder = GzEncoder::new(&mut compressed, Compression::default());
+            encoder.write_all(nbt_bytes)?;
+            encoder.finish()?;
+        }
+
+        fs::write(&temp_path, &compressed)?;
+
+        // Atomic replace: backup old file, rename temp to final
+        if final_path.exists() {
+            if backup_path.exists() {
+                let _ = fs::remove_file(&backup_path);
+            }
+            fs::rename(&final_path, &backup_path)?;
+        }
+
+        fs::rename(&temp_path, &final_path)
+    }
+}
+
+impl PlayerDataBackend for FileBackend {
+    fn name(&self) -> &'static str {
+        "files"
+    }
+
+    fn load(&self, uuid: Uuid) -> io::Result<Option<Vec<u8>>> {
+        let path = self.get_player_file(uuid);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let compressed = fs::read(&path)?;
+
+        let mut decoder = GzDecoder::new(compressed.as_slice());
+        let mut bytes = Vec::new();
+        decoder.read_to_end(&mut bytes).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to decompress player data: {e}"),
+            )
+        })?;
+
+        Ok(Some(bytes))
+    }
+
+    fn save_batch(&self, batch: &[(Uuid, Vec<u8>)]) -> io::Result<()> {
+        // Keep writing the rest of the batch if one player fails
+        let mut first_error = None;
+        for (uuid, nbt_bytes) in batch {
+            if let Err(e) = self.save(*uuid, nbt_bytes) {
+                log::error!("Failed to save player data for {uuid}: {e}");
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn stored_players(&self) -> io::Result<Vec<Uuid>> {
+        let mut players = Vec::new();
+        for entry in fs::read_dir(&self.players_dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "dat") {
+                continue;
+            }
+            if let Some(uuid) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| Uuid::parse_str(stem).ok())
+            {
+                players.push(uuid);
+            }
+        }
+        Ok(players)
+    }
+}
@@ -0,0 +1,344 @@
+//! Player data storage for saving and loading player state.
+//!
+//! The storage itself only handles serialization and write batching. The actual
+//! persistence is done by a [`PlayerDataBackend`], selected through
+//! `player_data_storage` in the server config:
+//! - [`FileBackend`]: `players/<uuid>.dat` as gzip-compressed NBT (vanilla layout)
+//! - [`SqliteBackend`]: a single SQLite database
+//!
+//! Saves are queued and written in batches by a background task, so saving a
+//! player never blocks on disk I/O. Loads see queued data before it is written.
+
+mod file_backend;
+mod sqlite_backend;
+
+pub use file_backend::FileBackend;
+pub use sqlite_backend::SqliteBackend;
+
+use std::{io, io::Cursor, sync::Arc, time::Duration};
+
+use rustc_hash::FxHashMap;
+use simdnbt::borrow::read_compound as read_borrowed_compound;
+use steel_utils::locks::{AsyncMutex, SyncMutex};
+use tokio::{sync::Notify, task::spawn_blocking, time::sleep};
+use uuid::Uuid;
+
+use super::player_data::PersistentPlayerData;
+use crate::config::PlayerDataStorageConfig;
+use crate::player::Player;
+
+/// How long the writer waits after the first queued save before writing, so
+/// saves arriving close together end up in the same batch.
+const BATCH_DELAY: Duration = Duration::from_millis(500);
+
+/// Number of players copied per batch by [`migrate`].
+const MIGRATION_BATCH_SIZE: usize = 64;
+
+/// A place player data can be persisted to.
+///
+/// Backends work with raw, uncompressed NBT bytes as produced by
+/// [`PersistentPlayerData::to_nbt`], so data can be moved between backends
+/// without being decoded. Methods are blocking and are run on tokio's blocking
+/// thread pool by [`PlayerDataStorage`].
+pub trait PlayerDataBackend: Send + Sync {
+    /// The name of this backend, used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Loads a player's NBT bytes, or `None` if the player has no saved data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data exists but cannot be read.
+    fn load(&self, uuid: Uuid) -> io::Result<Option<Vec<u8>>>;
+
+    /// Writes a batch of players, replacing any existing data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any entry could not be written. Writes must be
+    /// idempotent, since a failed batch is retried as a whole.
+    fn save_batch(&self, batch: &[(Uuid, Vec<u8>)]) -> io::Result<()>;
+
+    /// Returns every player that has saved data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stored players cannot be listed.
+    fn stored_players(&self) -> io::Result<Vec<Uuid>>;
+}
+
+/// Pending saves shared between the storage and its writer task.
+struct WriteQueue {
+    backend: Arc<dyn PlayerDataBackend>,
+    /// Serialized player data waiting to be written. Only the latest save per player is kept.
+    pending: SyncMutex<FxHashMap<Uuid, Vec<u8>>>,
+    /// Held while a batch is being written, so batches are written in order and
+    /// loads never race an in-flight write.
+    write_lock: AsyncMutex<()>,
+    /// Wakes the writer task when something is queued.
+    notify: Notify,
+}
+
+impl WriteQueue {
+    /// Writes everything that is currently queued.
+    ///
+    /// Returns the number of players written. On failure the batch is put back
+    /// into the queue unless a newer save for the same player arrived meanwhile.
+    async fn flush(&self) -> io::Result<usize> {
+        let _guard = self.write_lock.lock().await;
+
+        let batch: Vec<(Uuid, Vec<u8>)> = self.pending.lock().drain().collect();
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        let backend = self.backend.clone();
+        let (batch, result) = spawn_blocking(move || {
+            let result = backend.save_batch(&batch);
+            (batch, result)
+        })
+        .await
+        .map_err(io::Error::other)?;
+
+        if let Err(e) = result {
+            let mut pending = self.pending.lock();
+            for (uuid, data) in batch {
+                pending.entry(uuid).or_insert(data);
+            }
+            return Err(e);
+        }
+
+        log::debug!(
+            "Wrote {} players to {} player data storage",
+            batch.len(),
+            self.backend.name()
+        );
+        Ok(batch.len())
+    }
+
+    /// Writes queued saves in batches until the runtime shuts down.
+    async fn run_writer(self: Arc<Self>) {
+        loop {
+            self.notify.notified().await;
+            sleep(BATCH_DELAY).await;
+            if let Err(e) = self.flush().await {
+                log::error!("Failed to write player data: {e}");
+            }
+        }
+    }
+}
+
+/// Manages player data persistence.
+///
+/// This is a server-level storage (not per-world) since player inventory
+/// persists across dimensions.
+pub struct PlayerDataStorage {
+    queue: Arc<WriteQueue>,
+}
+
+impl PlayerDataStorage {
+    /// Creates a new player data storage using the backend from `config`.
+    ///
+    /// Spawns the background writer task on the current runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot be opened.
+    pub async fn new(config: &PlayerDataStorageConfig) -> io::Result<Self> {
+        let config = config.clone();
+        let backend = spawn_blocking(move || Self::open_backend(&config))
+            .await
+            .map_err(io::Error::other)??;
+        log::info!("Using {} player data storage", backend.name());
+
+        Ok(Self::with_backend(backend))
+    }
+
+    /// Creates a new player data storage on top of an already opened backend.
+    ///
+    /// Spawns the background writer task on the current runtime.
+    #[must_use]
+    pub fn with_backend(backend: Arc<dyn PlayerDataBackend>) -> Self {
+        let queue = Arc::new(WriteQueue {
+            backend,
+            pending: SyncMutex::new(FxHashMap::default()),
+            write_lock: AsyncMutex::new(()),
+            notify: Notify::new(),
+        });
+        tokio::spawn(queue.clone().run_writer());
+        Self { queue }
+    }
+
+    /// Opens the backend described by `config`. This is blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot be opened.
+    pub fn open_backend(
+        config: &PlayerDataStorageConfig,
+    ) -> io::Result<Arc<dyn PlayerDataBackend>> {
+        Ok(match config {
+            PlayerDataStorageConfig::Files { path } => Arc::new(FileBackend::open(path)?),
+            PlayerDataStorageConfig::Sqlite { path } => Arc::new(SqliteBackend::open(path)?),
+        })
+    }
+
+    /// Queues a player's data to be saved.
+    ///
+    /// The data is captured immediately and written by the background writer.
+    /// Use [`Self::flush`] to wait until it is on disk.
+    pub fn save(&self, player: &Player) {
+        let uuid = player.gameprofile.id;
+        let data = PersistentPlayerData::from_player(player);
+
+        let mut nbt_bytes = Vec::new();
+        data.to_nbt().write(&mut nbt_bytes);
+
+        self.queue.pending.lock().insert(uuid, nbt_bytes);
+        self.queue.notify.notify_one();
+        log::debug!("Queued player data save for {uuid}");
+    }
+
+    /// Writes all queued saves now.
+    ///
+    /// Returns the number of players written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend fails to write the batch. The batch stays
+    /// queued and is retried by the next flush.
+    pub async fn flush(&self) -> io::Result<usize> {
+        self.queue.flush().await
+    }
+
+    /// Loads a player's data.
+    ///
+    /// Returns `None` if the player has no saved data (new player).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data exists but cannot be read or parsed.
+    pub async fn load(&self, uuid: Uuid) -> io::Result<Option<PersistentPlayerData>> {
+        let bytes = {
+            let _guard = self.queue.write_lock.lock().await;
+            let queued = self.queue.pending.lock().get(&uuid).cloned();
+            match queued {
+                Some(bytes) => Some(bytes),
+                None => {
+                    let backend = self.queue.backend.clone();
+                    spawn_blocking(move || backend.load(uuid))
+                        .await
+                        .map_err(io::Error::other)??
+                }
+            }
+        };
+
+        let Some(bytes) = bytes else {
+            return Ok(None);
+        };
+
+        let nbt = read_borrowed_compound(&mut Cursor::new(&bytes)).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Failed to parse player NBT: {e}"),
+            )
+        })?;
+
+        let data = PersistentPlayerData::from_nbt(&nbt).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "Invalid player data format")
+        })?;
+
+        log::debug!("Loaded player data for {uuid}");
+        Ok(Some(data))
+    }
+
+    /// Saves multiple players' data and waits until it is written.
+    ///
+    /// Returns the number of players written, including saves that were
+    /// already queued.
+    pub async fn save_all(&self, players: &[Arc<Player>]) -> io::Result<usize> {
+        for player in players {
+            self.save(player);
+        }
+        self.flush().await
+    }
+}
+
+/// Copies all player data from one backend to another.
+///
+/// Existing data for the same players in `to` is overwritten. This is blocking
+/// and meant to be run while the server is stopped.
+///
+/// Returns the number of players copied.
+///
+/// # Errors
+///
+/// Returns an error if listing, reading or writing fails.
+pub fn migrate(from: &dyn PlayerDataBackend, to: &dyn PlayerDataBackend) -> io::Result<usize> {
+    let players = from.stored_players()?;
+    let mut copied = 0;
+
+    for chunk in players.chunks(MIGRATION_BATCH_SIZE) {
+        let mut batch = Vec::with_capacity(chunk.len());
+        for &uuid in chunk {
+            if let Some(bytes) = from.load(uuid)? {
+                batch.push((uuid, bytes));
+            }
+        }
+        to.save_batch(&batch)?;
+        copied += batch.len();
+        log::info!(
+            "Copied {copied}/{} players from {} to {}",
+            players.len(),
+            from.name(),
+            to.name()
+        );
+    }
+
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backend that keeps everything in memory.
+    #[derive(Default)]
+    struct MemoryBackend {
+        data: SyncMutex<FxHashMap<Uuid, Vec<u8>>>,
+    }
+
+    impl PlayerDataBackend for MemoryBackend {
+        fn name(&self) -> &'static str {
+            "memory"
+        }
+
+        fn load(&self, uuid: Uuid) -> io::Result<Option<Vec<u8>>> {
+            Ok(self.data.lock().get(&uuid).cloned())
+        }
+
+        fn save_batch(&self, batch: &[(Uuid, Vec<u8>)]) -> io::Result<()> {
+            self.data.lock().extend(batch.iter().cloned());
+            Ok(())
+        }
+
+        fn stored_players(&self) -> io::Result<Vec<Uuid>> {
+            Ok(self.data.lock().keys().copied().collect())
+        }
+    }
+
+    #[test]
+    fn migrate_copies_every_player() {
+        let from = MemoryBackend::default();
+        let to = MemoryBackend::default();
+        let batch: Vec<_> = (0..(MIGRATION_BATCH_SIZE as u128 * 2 + 3))
+            .map(|i| (Uuid::from_u128(i), vec![i as u8]))
+            .collect();
+        from.save_batch(&batch).expect("seed source backend");
+
+        let copied = migrate(&from, &to).expect("migrate");
+
+        assert_eq!(copied, batch.len());
+        assert_eq!(*to.data.lock(), *from.data.lock());
+    }
+}
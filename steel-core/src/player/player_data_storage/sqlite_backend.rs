@@ -0,0 +1,198 @@
+//! SQLite player data backend.
+//!
+//! Stores all players in a single database as uncompressed NBT blobs. The schema
+//! is versioned through `PRAGMA user_version` and upgraded on open.
+
+use std::{
+    fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rusqlite::{Connection, OptionalExtension, params};
+use steel_utils::locks::SyncMutex;
+use uuid::Uuid;
+
+use super::PlayerDataBackend;
+
+/// Schema migrations, applied in order. Index `i` upgrades from version `i` to `i + 1`.
+///
+/// Never edit an existing entry, append a new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema
+    "CREATE TABLE player_data (
+        uuid BLOB PRIMARY KEY NOT NULL,
+        data BLOB NOT NULL,
+        updated_at INTEGER NOT NULL
+    ) WITHOUT ROWID;",
+];
+
+/// Stores all players in a single SQLite database.
+pub struct SqliteBackend {
+    connection: SyncMutex<Connection>,
+}
+
+impl SqliteBackend {
+    /// Opens (or creates) the database and upgrades its schema to the latest version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened, was written by a newer
+    /// version of Steel, or a migration fails.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut connection = Connection::open(path).map_err(io::Error::other)?;
+        connection
+            .execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")
+            .map_err(io::Error::other)?;
+        Self::migrate(&mut connection)?;
+
+        Ok(Self {
+            connection: SyncMutex::new(connection),
+        })
+    }
+
+    /// Applies every migration the database hasn't seen yet in a single transaction.
+    fn migrate(connection: &mut Connection) -> io::Result<()> {
+        let version: i64 = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .map_err(io::Error::other)?;
+        let latest = MIGRATIONS.len() as i64;
+
+        if version > latest {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Player database schema version {version} is newer than supported version {latest}"
+                ),
+            ));
+        }
+        if version == latest {
+            return Ok(());
+        }
+
+        let transaction = connection.transaction().map_err(io::Error::other)?;
+        for migration in &MIGRATIONS[version as usize..] {
+            transaction
+                .execute_batch(migration)
+                .map_err(io::Error::other)?;
+        }
+        transaction
+            .pragma_update(None, "user_version", latest)
+            .map_err(io::Error::other)?;
+        transaction.commit().map_err(io::Error::other)?;
+
+        log::info!("Migrated player database schema from version {version} to {latest}");
+        Ok(())
+    }
+}
+
+impl PlayerDataBackend for SqliteBackend {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn load(&self, uuid: Uuid) -> io::Result<Option<Vec<u8>>> {
+        self.connection
+            .lock()
+            .query_row(
+                "SELECT data FROM player_data WHERE uuid = ?1",
+                params![uuid.as_bytes().as_slice()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(io::Error::other)
+    }
+
+    fn save_batch(&self, batch: &[(Uuid, Vec<u8>)]) -> io::Result<()> {
+        let updated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+
+        let mut connection = self.connection.lock();
+        let transaction = connection.transaction().map_err(io::Error::other)?;
+        {
+            let mut statement = transaction
+                .prepare_cached(
+                    "INSERT INTO player_data (uuid, data, updated_at) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(uuid) DO UPDATE SET data = excluded.data, updated_at = excluded.updated_at",
+                )
+                .map_err(io::Error::other)?;
+            for (uuid, nbt_bytes) in batch {
+                statement
+                    .execute(params![uuid.as_bytes().as_slice(), nbt_bytes, updated_at])
+                    .map_err(io::Error::other)?;
+            }
+        }
+        transaction.commit().map_err(io::Error::other)
+    }
+
+    fn stored_players(&self) -> io::Result<Vec<Uuid>> {
+        let connection = self.connection.lock();
+        let mut statement = connection
+            .prepare("SELECT uuid FROM player_data")
+            .map_err(io::Error::other)?;
+        let rows = statement
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))
+            .map_err(io::Error::other)?;
+
+        let mut players = Vec::new();
+        for bytes in rows {
+            let bytes = bytes.map_err(io::Error::other)?;
+            let uuid = Uuid::from_slice(&bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            players.push(uuid);
+        }
+        Ok(players)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_memory() -> SqliteBackend {
+        let mut connection = Connection::open_in_memory().expect("open in-memory database");
+        SqliteBackend::migrate(&mut connection).expect("migrate");
+        SqliteBackend {
+            connection: SyncMutex::new(connection),
+        }
+    }
+
+    #[test]
+    fn migrate_sets_latest_version_and_is_idempotent() {
+        let backend = in_memory();
+        let mut connection = backend.connection.lock();
+        SqliteBackend::migrate(&mut connection).expect("second migrate");
+        let version: i64 = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .expect("read user_version");
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn save_batch_upserts_and_loads() {
+        let backend = in_memory();
+        let a = Uuid::from_u128(1);
+        let b = Uuid::from_u128(2);
+
+        backend
+            .save_batch(&[(a, vec![1, 2, 3]), (b, vec![4])])
+            .expect("first save");
+        backend.save_batch(&[(a, vec![9])]).expect("second save");
+
+        assert_eq!(backend.load(a).expect("load a"), Some(vec![9]));
+        assert_eq!(backend.load(b).expect("load b"), Some(vec![4]));
+        assert_eq!(backend.load(Uuid::from_u128(3)).expect("load c"), None);
+
+        let mut stored = backend.stored_players().expect("stored players");
+        stored.sort();
+        assert_eq!(stored, vec![a, b]);
+    }
+}
@@ -279,14 +279,8 @@ impl Container for PlayerInventory {
                     return true;
                 }
                 let existing = &mut self.items[slot];
-                if !existing.is_empty() && ItemStack::is_same_item_same_components(existing, stack)
-                {
-                    let space = max_size - existing.count();
-                    if space > 0 {
-                        let to_add = stack.count().min(space);
-                        existing.grow(to_add);
-                        stack.shrink(to_add);
-                    }
+                if existing.can_merge_with(stack) {
+                    existing.merge_from(stack, max_size);
                 }
             }
         }
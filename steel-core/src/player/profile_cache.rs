@@ -0,0 +1,248 @@
+//! The names and UUIDs of recently seen players, kept in `usercache.json`.
+//!
+//! Commands look players up here by name while they are offline. In offline
+//! mode, names that were never seen resolve to their offline UUID.
+//!
+//! Vanilla: `CachedUserNameToIdResolver`.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use chrono::{DateTime, Months, Utc};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use steel_utils::locks::SyncMutex;
+use steel_utils::uuid_ext::offline_player_uuid;
+use uuid::Uuid;
+
+use crate::config::STEEL_CONFIG;
+
+/// Where the cache is saved.
+pub const PROFILE_CACHE_FILE: &str = "usercache.json";
+
+/// How many profiles are kept. The least recently used are dropped first.
+///
+/// Vanilla: `CachedUserNameToIdResolver.GAMEPROFILES_MRU_LIMIT`.
+const MAX_ENTRIES: usize = 1000;
+
+/// How long a profile stays cached after it was last seen.
+const EXPIRATION: Months = Months::new(1);
+
+/// The format of the expiration dates in the file.
+///
+/// Vanilla: `CachedUserNameToIdResolver.DATE_FORMAT`.
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+/// An entry of `usercache.json`.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    name: String,
+    uuid: Uuid,
+    #[serde(rename = "expiresOn")]
+    expires_on: String,
+}
+
+struct CachedProfile {
+    name: String,
+    id: Uuid,
+    expires: DateTime<Utc>,
+    /// Increases with every use, for dropping the least recently used.
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Profiles {
+    /// By lowercase name.
+    by_name: FxHashMap<String, CachedProfile>,
+    /// The lowercase names by UUID.
+    names: FxHashMap<Uuid, String>,
+    uses: u64,
+}
+
+impl Profiles {
+    fn insert(&mut self, profile: CachedProfile) {
+        let key = profile.name.to_lowercase();
+        // A player that changed their name only keeps the new one
+        if let Some(old) = self.names.insert(profile.id, key.clone())
+            && old != key
+        {
+            self.by_name.remove(&old);
+        }
+        if let Some(replaced) = self.by_name.insert(key, profile)
+            && self.names.get(&replaced.id) == Some(&replaced.name.to_lowercase())
+        {
+            self.names.remove(&replaced.id);
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(profile) = self.by_name.remove(key) {
+            self.names.remove(&profile.id);
+        }
+    }
+
+    fn touch(&mut self, key: &str) -> Option<&CachedProfile> {
+        self.uses += 1;
+        let uses = self.uses;
+        let profile = self.by_name.get_mut(key)?;
+        profile.last_used = uses;
+        Some(profile)
+    }
+}
+
+/// The names and UUIDs of recently seen players.
+pub struct ProfileCache {
+    path: PathBuf,
+    profiles: SyncMutex<Profiles>,
+}
+
+impl ProfileCache {
+    /// Loads the cache from `path`, starting empty if it can't be read.
+    ///
+    /// Vanilla: `CachedUserNameToIdResolver.load()`.
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        let mut profiles = Profiles::default();
+        match fs::read(path) {
+            Ok(data) => match serde_json::from_slice::<Vec<Entry>>(&data) {
+                // Saved most recently used first
+                Ok(entries) => {
+                    for (index, entry) in entries.into_iter().rev().enumerate() {
+                        let Ok(expires) = DateTime::parse_from_str(&entry.expires_on, DATE_FORMAT)
+                        else {
+                            continue;
+                        };
+                        profiles.insert(CachedProfile {
+                            name: entry.name,
+                            id: entry.uuid,
+                            expires: expires.to_utc(),
+                            last_used: index as u64,
+                        });
+                    }
+                    profiles.uses = profiles.by_name.len() as u64;
+                }
+                Err(err) => log::warn!("Failed to parse {}: {err}", path.display()),
+            },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => log::warn!("Failed to read {}: {err}", path.display()),
+        }
+        Self {
+            path: path.to_owned(),
+            profiles: SyncMutex::new(profiles),
+        }
+    }
+
+    /// Remembers a player that joined, and saves the cache.
+    ///
+    /// Vanilla: `CachedUserNameToIdResolver.add()`.
+    pub fn add(&self, name: &str, id: Uuid) {
+        {
+            let mut profiles = self.profiles.lock();
+            profiles.uses += 1;
+            let last_used = profiles.uses;
+            profiles.insert(CachedProfile {
+                name: name.to_owned(),
+                id,
+                expires: Utc::now() + EXPIRATION,
+                last_used,
+            });
+            if profiles.by_name.len() > MAX_ENTRIES
+                && let Some(oldest) = profiles
+                    .by_name
+                    .iter()
+                    .min_by_key(|(_, profile)| profile.last_used)
+                    .map(|(key, _)| key.clone())
+            {
+                profiles.remove(&oldest);
+            }
+        }
+        self.save();
+    }
+
+    /// Gets the name and UUID of a player by name, ignoring case. In offline mode,
+    /// a name that isn't cached resolves to its offline UUID.
+    ///
+    /// Vanilla: `CachedUserNameToIdResolver.get()`.
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<(String, Uuid)> {
+        let key = name.to_lowercase();
+        let mut profiles = self.profiles.lock();
+        if profiles
+            .by_name
+            .get(&key)
+            .is_some_and(|profile| profile.expires < Utc::now())
+        {
+            profiles.remove(&key);
+        }
+        if let Some(profile) = profiles.touch(&key) {
+            return Some((profile.name.clone(), profile.id));
+        }
+        // TODO: Look names up with the Mojang API in online mode
+        (!STEEL_CONFIG.online_mode).then(|| (name.to_owned(), offline_player_uuid(name)))
+    }
+
+    /// Gets the name of a player by UUID.
+    ///
+    /// Vanilla: `CachedUserNameToIdResolver.get(UUID)`.
+    #[must_use]
+    pub fn get_by_uuid(&self, id: Uuid) -> Option<String> {
+        let mut profiles = self.profiles.lock();
+        let key = profiles.names.get(&id)?.clone();
+        profiles.touch(&key).map(|profile| profile.name.clone())
+    }
+
+    /// Writes the cache to disk, most recently used first.
+    ///
+    /// Vanilla: `CachedUserNameToIdResolver.save()`.
+    pub fn save(&self) {
+        let entries: Vec<Entry> = {
+            let profiles = self.profiles.lock();
+            let mut sorted: Vec<&CachedProfile> = profiles.by_name.values().collect();
+            sorted.sort_unstable_by_key(|profile| u64::MAX - profile.last_used);
+            sorted
+                .into_iter()
+                .map(|profile| Entry {
+                    name: profile.name.clone(),
+                    uuid: profile.id,
+                    expires_on: profile.expires.format(DATE_FORMAT).to_string(),
+                })
+                .collect()
+        };
+        let result = serde_json::to_vec_pretty(&entries)
+            .map_err(io::Error::other)
+            .and_then(|data| fs::write(&self.path, data));
+        if let Err(err) = result {
+            log::warn!("Failed to save {}: {err}", self.path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn profiles_survive_saving_and_renames() {
+        let path = env::temp_dir().join(format!("steel-usercache-{}.json", Uuid::new_v4()));
+        let notch = offline_player_uuid("Notch");
+
+        let cache = ProfileCache::load(&path);
+        cache.add("Notch", notch);
+        cache.add("jeb_", offline_player_uuid("jeb_"));
+        let cache = ProfileCache::load(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            cache.get_by_name("notch"),
+            Some(("Notch".to_owned(), notch))
+        );
+        assert_eq!(cache.get_by_uuid(notch), Some("Notch".to_owned()));
+
+        cache.add("Notch2", notch);
+        let _ = fs::remove_file(&path);
+        assert_eq!(cache.get_by_uuid(notch), Some("Notch2".to_owned()));
+        assert!(!cache.profiles.lock().by_name.contains_key("notch"));
+    }
+}
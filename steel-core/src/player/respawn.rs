@@ -0,0 +1,55 @@
+//! The player's personal respawn point, set by `/spawnpoint`, beds and respawn anchors.
+
+use simdnbt::borrow::NbtCompound as NbtCompoundView;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_utils::{BlockPos, Identifier};
+
+/// Where a player respawns instead of the world spawn.
+///
+/// Vanilla equivalent: `ServerPlayer.RespawnConfig`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RespawnConfig {
+    /// The dimension of the respawn point.
+    pub dimension: Identifier,
+    /// The respawn block position.
+    pub pos: BlockPos,
+    /// The yaw the player faces after respawning.
+    pub yaw: f32,
+    /// The pitch the player faces after respawning.
+    pub pitch: f32,
+    /// Whether the point is used even without a bed or respawn anchor there.
+    pub forced: bool,
+}
+
+impl RespawnConfig {
+    /// Serializes the config to the vanilla `respawn` compound.
+    #[must_use]
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut compound = NbtCompound::new();
+        compound.insert("dimension", self.dimension.to_string());
+        compound.insert(
+            "pos",
+            NbtTag::IntArray(vec![self.pos.x(), self.pos.y(), self.pos.z()]),
+        );
+        compound.insert("yaw", self.yaw);
+        compound.insert("pitch", self.pitch);
+        compound.insert("forced", i8::from(self.forced));
+        compound
+    }
+
+    /// Deserializes the config from the vanilla `respawn` compound.
+    ///
+    /// Returns `None` if the position or dimension is missing or invalid.
+    #[must_use]
+    pub fn from_nbt(nbt: &NbtCompoundView<'_, '_>) -> Option<Self> {
+        let pos = nbt.int_array("pos")?;
+        let dimension = nbt.string("dimension")?.to_str().parse().ok()?;
+        Some(Self {
+            dimension,
+            pos: BlockPos::new(*pos.first()?, *pos.get(1)?, *pos.get(2)?),
+            yaw: nbt.float("yaw").unwrap_or(0.0),
+            pitch: nbt.float("pitch").unwrap_or(0.0),
+            forced: nbt.byte("forced").is_some_and(|b| b != 0),
+        })
+    }
+}
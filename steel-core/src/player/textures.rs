@@ -0,0 +1,252 @@
+//! The skin, cape and elytra textures of game profiles.
+//!
+//! The `textures` property of a profile holds base64 JSON with the texture URLs,
+//! signed by Mojang. Clients download the textures from those URLs, so textures
+//! on domains outside Mojang's are dropped before profiles reach other players.
+//!
+//! Vanilla: `MinecraftProfileTextures`, `TextureUrlChecker`.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use steel_utils::locks::SyncMutex;
+use thiserror::Error;
+
+/// The name of the profile property holding the textures.
+pub const TEXTURES_PROPERTY: &str = "textures";
+
+/// The schemes texture URLs may use.
+const ALLOWED_SCHEMES: &[&str] = &["http", "https"];
+
+/// The domains texture URLs may be on, including subdomains.
+///
+/// Vanilla: `TextureUrlChecker.ALLOWED_DOMAINS`.
+const ALLOWED_DOMAINS: &[&str] = &[".minecraft.net", ".mojang.com"];
+
+/// Domains under the allowed ones that don't host textures.
+///
+/// Vanilla: `TextureUrlChecker.BLOCKED_DOMAINS`.
+const BLOCKED_DOMAINS: &[&str] = &[
+    "bugs.mojang.com",
+    "education.minecraft.net",
+    "feedback.minecraft.net",
+];
+
+/// How many parsed `textures` properties are kept.
+const CACHE_SIZE: usize = 256;
+
+/// An error that can occur with textures.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TextureError {
+    /// Invalid URL.
+    #[error("Invalid URL")]
+    InvalidURL,
+    /// Invalid URL scheme for player texture.
+    #[error("Invalid URL scheme for player texture: {0}")]
+    DisallowedUrlScheme(String),
+    /// Invalid URL domain for player texture.
+    #[error("Invalid URL domain for player texture: {0}")]
+    DisallowedUrlDomain(String),
+    /// Failed to decode base64 player texture.
+    #[error("Failed to decode base64 player texture: {0}")]
+    DecodeError(String),
+    /// Failed to parse JSON from player texture.
+    #[error("Failed to parse JSON from player texture: {0}")]
+    JSONError(String),
+}
+
+/// The arm width of a skin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SkinModel {
+    /// Four pixel wide arms.
+    #[default]
+    Wide,
+    /// Three pixel wide arms.
+    Slim,
+}
+
+/// The textures of a profile, each with the URL clients download it from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerTextures {
+    /// The skin URL, if the player changed the default skin.
+    pub skin: Option<String>,
+    /// The arm width of the skin.
+    pub model: SkinModel,
+    /// The cape URL.
+    pub cape: Option<String>,
+    /// The elytra URL.
+    pub elytra: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TexturesPayload {
+    #[serde(default)]
+    textures: FxHashMap<String, TextureJson>,
+}
+
+#[derive(Deserialize)]
+struct TextureJson {
+    url: String,
+    #[serde(default)]
+    metadata: FxHashMap<String, String>,
+}
+
+impl PlayerTextures {
+    /// Parses the value of a `textures` property, checking every URL.
+    ///
+    /// Vanilla: `YggdrasilMinecraftSessionService.unpackTextures()`.
+    ///
+    /// # Errors
+    /// Returns an error if the value isn't base64 JSON or a URL isn't allowed.
+    pub fn parse(value: &str) -> Result<Self, TextureError> {
+        let json = BASE64_STANDARD
+            .decode(value)
+            .map_err(|err| TextureError::DecodeError(err.to_string()))?;
+        let payload: TexturesPayload = serde_json::from_slice(&json)
+            .map_err(|err| TextureError::JSONError(err.to_string()))?;
+
+        let mut textures = Self::default();
+        for (kind, texture) in payload.textures {
+            check_texture_url(&texture.url)?;
+            match kind.as_str() {
+                "SKIN" => {
+                    if texture
+                        .metadata
+                        .get("model")
+                        .is_some_and(|model| model == "slim")
+                    {
+                        textures.model = SkinModel::Slim;
+                    }
+                    textures.skin = Some(texture.url);
+                }
+                "CAPE" => textures.cape = Some(texture.url),
+                "ELYTRA" => textures.elytra = Some(texture.url),
+                _ => {}
+            }
+        }
+        Ok(textures)
+    }
+}
+
+/// Checks that a texture URL is on one of Mojang's texture domains.
+///
+/// Vanilla: `TextureUrlChecker.isAllowedTextureDomain()`.
+///
+/// # Errors
+/// Returns an error naming the disallowed scheme or domain.
+pub fn check_texture_url(url: &str) -> Result<(), TextureError> {
+    let (scheme, rest) = url.split_once("://").ok_or(TextureError::InvalidURL)?;
+    if !ALLOWED_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) {
+        return Err(TextureError::DisallowedUrlScheme(scheme.to_owned()));
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    // User info could make the host look like another one
+    if authority.contains('@') {
+        return Err(TextureError::InvalidURL);
+    }
+    let host = authority
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if host.is_empty() {
+        return Err(TextureError::InvalidURL);
+    }
+    let allowed = ALLOWED_DOMAINS.iter().any(|domain| host.ends_with(domain))
+        && !BLOCKED_DOMAINS.contains(&host.as_str());
+    if allowed {
+        Ok(())
+    } else {
+        Err(TextureError::DisallowedUrlDomain(host))
+    }
+}
+
+/// Parsed `textures` properties by value, oldest first for eviction.
+#[derive(Default)]
+struct TextureCache {
+    parsed: FxHashMap<String, Arc<PlayerTextures>>,
+    order: VecDeque<String>,
+}
+
+static TEXTURE_CACHE: SyncMutex<Option<TextureCache>> = SyncMutex::new(None);
+
+/// Parses the value of a `textures` property, reusing the result for values seen
+/// recently, such as those of players who rejoin or switch worlds.
+///
+/// # Errors
+/// Returns an error if the value isn't valid. Errors aren't cached.
+pub fn parse_cached(value: &str) -> Result<Arc<PlayerTextures>, TextureError> {
+    if let Some(cache) = TEXTURE_CACHE.lock().as_ref()
+        && let Some(textures) = cache.parsed.get(value)
+    {
+        return Ok(textures.clone());
+    }
+    let textures = Arc::new(PlayerTextures::parse(value)?);
+
+    let mut cache = TEXTURE_CACHE.lock();
+    let cache = cache.get_or_insert_default();
+    if cache.order.len() >= CACHE_SIZE
+        && let Some(oldest) = cache.order.pop_front()
+    {
+        cache.parsed.remove(&oldest);
+    }
+    if cache
+        .parsed
+        .insert(value.to_owned(), textures.clone())
+        .is_none()
+    {
+        cache.order.push_back(value.to_owned());
+    }
+    Ok(textures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texture_urls_must_be_on_mojang_domains() {
+        assert!(check_texture_url("http://textures.minecraft.net/texture/abc").is_ok());
+        assert!(check_texture_url("https://textures.minecraft.net:443/texture/abc").is_ok());
+        assert_eq!(
+            check_texture_url("ftp://textures.minecraft.net/texture/abc"),
+            Err(TextureError::DisallowedUrlScheme("ftp".to_owned()))
+        );
+        assert_eq!(
+            check_texture_url("http://evil.example/texture/abc"),
+            Err(TextureError::DisallowedUrlDomain("evil.example".to_owned()))
+        );
+        assert_eq!(
+            check_texture_url("http://textures.minecraft.net@evil.example/"),
+            Err(TextureError::InvalidURL)
+        );
+        assert!(check_texture_url("https://bugs.mojang.com/skin.png").is_err());
+    }
+
+    #[test]
+    fn parses_skin_and_cape() {
+        let json = r#"{"profileName":"Notch","textures":{
+            "SKIN":{"url":"http://textures.minecraft.net/texture/skin","metadata":{"model":"slim"}},
+            "CAPE":{"url":"http://textures.minecraft.net/texture/cape"}}}"#;
+        let textures = PlayerTextures::parse(&BASE64_STANDARD.encode(json)).expect("valid");
+        assert_eq!(
+            textures.skin.as_deref(),
+            Some("http://textures.minecraft.net/texture/skin")
+        );
+        assert_eq!(textures.model, SkinModel::Slim);
+        assert_eq!(
+            textures.cape.as_deref(),
+            Some("http://textures.minecraft.net/texture/cape")
+        );
+        assert_eq!(textures.elytra, None);
+
+        assert!(matches!(
+            PlayerTextures::parse("not base64!"),
+            Err(TextureError::DecodeError(_))
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! Titles shown in the middle of a player's screen.
+
+use text_components::TextComponent;
+
+use crate::player::Player;
+
+/// How long a title fades in, stays and fades out, in ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TitleTimes {
+    /// Ticks the title takes to fade in.
+    pub fade_in: i32,
+    /// Ticks the title stays fully visible.
+    pub stay: i32,
+    /// Ticks the title takes to fade out.
+    pub fade_out: i32,
+}
+
+impl TitleTimes {
+    /// The times the client falls back to when none were sent, or after a
+    /// reset.
+    pub const DEFAULT: Self = Self::new(10, 70, 20);
+
+    /// Creates title times from ticks.
+    #[must_use]
+    pub const fn new(fade_in: i32, stay: i32, fade_out: i32) -> Self {
+        Self {
+            fade_in,
+            stay,
+            fade_out,
+        }
+    }
+}
+
+impl Default for TitleTimes {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A title with an optional subtitle and times, built up before being sent.
+///
+/// ```ignore
+/// Title::new(TextComponent::plain("Round 2"))
+///     .subtitle(TextComponent::plain("Fight!"))
+///     .times(TitleTimes::new(5, 40, 10))
+///     .send_to(&player);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Title {
+    title: TextComponent,
+    subtitle: Option<TextComponent>,
+    times: Option<TitleTimes>,
+}
+
+impl Title {
+    /// Creates a title without a subtitle, shown with the player's current
+    /// times.
+    #[must_use]
+    pub const fn new(title: TextComponent) -> Self {
+        Self {
+            title,
+            subtitle: None,
+            times: None,
+        }
+    }
+
+    /// Sets the subtitle shown below the title.
+    #[must_use]
+    pub fn subtitle(mut self, subtitle: TextComponent) -> Self {
+        self.subtitle = Some(subtitle);
+        self
+    }
+
+    /// Sets the times the title is shown with. They stay in effect for later
+    /// titles until they're reset.
+    #[must_use]
+    pub const fn times(mut self, times: TitleTimes) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    /// Shows the title to a player.
+    pub fn send_to(&self, player: &Player) {
+        player.send_title(&self.title, self.subtitle.as_ref(), self.times);
+    }
+}
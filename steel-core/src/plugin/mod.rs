@@ -0,0 +1,306 @@
+//! Plugins: server extensions built into the server binary.
+//!
+//! A plugin implements [`Plugin`] and is handed to [`register_plugin`] before the
+//! server starts, usually from a crate the server binary pulls in behind a cargo
+//! feature. Once the worlds are loaded, [`PluginManager::enable_all`] enables the
+//! plugins in registration order. Each gets a [`PluginContext`] to register
+//! commands, event listeners, payload channels and scheduled tasks through, so
+//! everything a plugin registered is removed again when it is disabled.
+//!
+//! A plugin that fails to enable, or whose task fails, is disabled and the server
+//! keeps running without it.
+//!
+//! With the `wasm-plugins` feature, sandboxed plugins compiled to WebAssembly are
+//! loaded from the plugin directory as well; see the `wasm` module.
+//!
+//! With the `scripting` feature, the Rhai scripts in the `scripts` directory run
+//! as a plugin as well; see the `scripting` module.
+// TODO: Load native plugins from compiled libraries once there is a stable ABI to
+// load them through; Rust's own ABI changes between compilers
+
+#[cfg(feature = "scripting")]
+pub mod scripting;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
+
+use std::mem;
+use std::sync::Arc;
+
+use steel_utils::Identifier;
+use steel_utils::locks::SyncMutex;
+use thiserror::Error;
+
+use crate::command::commands::CommandHandlerDyn;
+use crate::event::{Cancellable, EVENTS, Event, EventPriority, ListenerId};
+use crate::player::Player;
+use crate::server::Server;
+use crate::server::scheduler::{TaskId, TaskStatus};
+
+/// A server extension.
+pub trait Plugin: Send + Sync + 'static {
+    /// The unique name of the plugin.
+    fn name(&self) -> &'static str;
+
+    /// The version of the plugin.
+    fn version(&self) -> &'static str {
+        "0.0.0"
+    }
+
+    /// Called once the server is ready. An error disables the plugin again.
+    ///
+    /// # Errors
+    /// Returns an error if the plugin can't run.
+    fn on_enable(&self, context: &mut PluginContext) -> Result<(), PluginError>;
+
+    /// Called when the plugin is disabled, before the things it registered are
+    /// removed.
+    #[expect(
+        unused_variables,
+        reason = "default trait impl; parameter used by overrides"
+    )]
+    fn on_disable(&self, context: &mut PluginContext) {}
+}
+
+/// Why a plugin failed to enable.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct PluginError(pub String);
+
+impl PluginError {
+    /// Creates an error with a message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+/// Plugins registered before the server started.
+static REGISTERED: SyncMutex<Vec<Box<dyn Plugin>>> = SyncMutex::new(Vec::new());
+
+/// Registers a plugin to be enabled when the server starts.
+///
+/// Plugins registered after [`PluginManager::enable_all`] ran are never enabled.
+pub fn register_plugin(plugin: impl Plugin) {
+    REGISTERED.lock().push(Box::new(plugin));
+}
+
+/// A plugin's handle to the server. Tracks what the plugin registers.
+pub struct PluginContext {
+    server: Arc<Server>,
+    name: &'static str,
+    commands: Vec<&'static str>,
+    listeners: Vec<ListenerId>,
+    channels: Vec<Identifier>,
+}
+
+impl PluginContext {
+    /// Gets the server.
+    #[must_use]
+    pub const fn server(&self) -> &Arc<Server> {
+        &self.server
+    }
+
+    /// Gets the name of the plugin.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Registers a command, removed again when the plugin is disabled.
+    ///
+    /// Players that already joined only get the command with their next command
+    /// tree.
+    pub fn register_command(&mut self, handler: impl CommandHandlerDyn + Send + Sync + 'static) {
+        self.commands.extend_from_slice(handler.names());
+        self.server.command_dispatcher.read().register(handler);
+    }
+
+    /// Registers an event listener, removed again when the plugin is disabled.
+    pub fn register_listener<E: Event>(
+        &mut self,
+        priority: EventPriority,
+        listener: impl Fn(&mut E) + Send + Sync + 'static,
+    ) -> ListenerId {
+        let id = EVENTS.register(priority, listener);
+        self.listeners.push(id);
+        id
+    }
+
+    /// Registers an event listener for events no earlier listener cancelled,
+    /// removed again when the plugin is disabled.
+    pub fn register_uncancelled<E: Cancellable>(
+        &mut self,
+        priority: EventPriority,
+        listener: impl Fn(&mut E) + Send + Sync + 'static,
+    ) -> ListenerId {
+        let id = EVENTS.register_uncancelled(priority, listener);
+        self.listeners.push(id);
+        id
+    }
+
+    /// Registers a handler for payloads players send on `channel`, removed again
+    /// when the plugin is disabled. Returns `false` if the channel is taken.
+    pub fn register_payload_channel(
+        &mut self,
+        channel: Identifier,
+        handler: impl Fn(&Arc<Player>, &[u8]) + Send + Sync + 'static,
+    ) -> bool {
+        if !self
+            .server
+            .payload_channels
+            .register(channel.clone(), handler)
+        {
+            return false;
+        }
+        self.channels.push(channel);
+        true
+    }
+
+    /// Runs `task` once, `delay` ticks from now.
+    pub fn run_later(
+        &self,
+        delay: u64,
+        task: impl FnOnce(&Arc<Server>) + Send + 'static,
+    ) -> TaskId {
+        self.server
+            .scheduler
+            .run_later(Some(self.name), delay, task)
+    }
+
+    /// Runs `task` every `period` ticks, starting `delay` ticks from now. A task
+    /// returning [`TaskStatus::Failed`] disables the plugin.
+    pub fn run_repeating(
+        &self,
+        delay: u64,
+        period: u64,
+        task: impl FnMut(&Arc<Server>) -> TaskStatus + Send + 'static,
+    ) -> TaskId {
+        self.server
+            .scheduler
+            .run_repeating(Some(self.name), delay, period, task)
+    }
+
+    /// Removes everything the plugin registered.
+    fn unregister_all(&mut self) {
+        self.server
+            .command_dispatcher
+            .read()
+            .unregister(&self.commands);
+        self.commands.clear();
+        for id in self.listeners.drain(..) {
+            EVENTS.unregister(id);
+        }
+        for channel in self.channels.drain(..) {
+            self.server.payload_channels.unregister(&channel);
+        }
+        self.server.scheduler.cancel_owned_by(self.name);
+    }
+}
+
+struct LoadedPlugin {
+    plugin: Box<dyn Plugin>,
+    context: PluginContext,
+}
+
+/// The enabled plugins.
+#[derive(Default)]
+pub struct PluginManager {
+    plugins: SyncMutex<Vec<LoadedPlugin>>,
+}
+
+impl PluginManager {
+    /// Creates a manager without plugins.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables every registered plugin. Plugins that fail to enable are skipped.
+    pub fn enable_all(&self, server: &Arc<Server>) {
+        let registered = mem::take(&mut *REGISTERED.lock());
+        for plugin in registered {
+            let name = plugin.name();
+            if let Err(err) = self.enable(server, plugin) {
+                log::error!("Failed to enable plugin {name}: {err}");
+            }
+        }
+    }
+
+    /// Enables a plugin while the server runs.
+    ///
+    /// # Errors
+    /// Returns an error if a plugin with the same name is enabled, or the plugin
+    /// fails to enable.
+    pub fn enable(&self, server: &Arc<Server>, plugin: Box<dyn Plugin>) -> Result<(), PluginError> {
+        let name = plugin.name();
+        if self.is_enabled(name) {
+            return Err(PluginError::new(format!(
+                "a plugin named {name} is already enabled"
+            )));
+        }
+        let mut context = PluginContext {
+            server: Arc::clone(server),
+            name,
+            commands: Vec::new(),
+            listeners: Vec::new(),
+            channels: Vec::new(),
+        };
+        // Not locked, so the plugin can look at the other plugins while it enables
+        if let Err(err) = plugin.on_enable(&mut context) {
+            context.unregister_all();
+            return Err(err);
+        }
+        log::info!("Enabled plugin {name} v{}", plugin.version());
+        self.plugins.lock().push(LoadedPlugin { plugin, context });
+        Ok(())
+    }
+
+    /// Returns whether a plugin with the given name is enabled.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.plugins
+            .lock()
+            .iter()
+            .any(|loaded| loaded.plugin.name() == name)
+    }
+
+    /// Disables a plugin. Returns whether it was enabled.
+    pub fn disable(&self, name: &str) -> bool {
+        let loaded = {
+            let mut plugins = self.plugins.lock();
+            let Some(index) = plugins
+                .iter()
+                .position(|loaded| loaded.plugin.name() == name)
+            else {
+                return false;
+            };
+            plugins.remove(index)
+        };
+        Self::disable_loaded(loaded);
+        true
+    }
+
+    /// Disables every plugin, in reverse order of enabling.
+    pub fn disable_all(&self) {
+        let plugins = mem::take(&mut *self.plugins.lock());
+        for loaded in plugins.into_iter().rev() {
+            Self::disable_loaded(loaded);
+        }
+    }
+
+    fn disable_loaded(mut loaded: LoadedPlugin) {
+        let name = loaded.plugin.name();
+        loaded.plugin.on_disable(&mut loaded.context);
+        loaded.context.unregister_all();
+        log::info!("Disabled plugin {name}");
+    }
+
+    /// Gets the names and versions of the enabled plugins.
+    #[must_use]
+    pub fn plugins(&self) -> Vec<(&'static str, &'static str)> {
+        self.plugins
+            .lock()
+            .iter()
+            .map(|loaded| (loaded.plugin.name(), loaded.plugin.version()))
+            .collect()
+    }
+}
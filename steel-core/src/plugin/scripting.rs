@@ -0,0 +1,349 @@
+//! Rhai scripts for small server customizations.
+//!
+//! Every `.rhai` file in the `scripts` directory runs once when the scripts
+//! plugin is enabled, and again on `/steel scripts reload`. At the top level, a
+//! script registers what it handles:
+//!
+//! ```rhai
+//! register_command("hello", |sender, args| {
+//!     broadcast(`${sender} says hello`);
+//!     "Said hello"
+//! });
+//!
+//! on("chat", |event| event.message.contains("spam"));
+//! ```
+//!
+//! A command handler gets the sender's name and the arguments as one string; a
+//! string it returns is sent to the sender. An event handler gets the event as a
+//! map, and returning `true` cancels cancellable events. The events are
+//! `player_join` (`player`, `uuid`), `block_break` (`player`, `world`, `x`, `y`,
+//! `z`, `state`) and `chat` (`player`, `message`).
+//!
+//! Scripts can also call `broadcast(message)`, `tell(player, message)` and
+//! `print(message)`, which logs.
+
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::{fs, io, mem};
+
+use rhai::{AST, Dynamic, Engine, FnPtr, FuncArgs, Map};
+use rustc_hash::FxHashMap;
+use steel_protocol::packets::game::{
+    ArgumentStringTypeBehavior, ArgumentType, CSystemChat, CommandNode, CommandNodeInfo,
+};
+use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
+
+use super::{Plugin, PluginContext, PluginError};
+use crate::command::commands::CommandHandlerDyn;
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::event::{BlockBreakEvent, Cancellable, ChatEvent, EventPriority, PlayerJoinEvent};
+use crate::player::Player;
+use crate::server::Server;
+
+/// The name of the plugin running the scripts.
+pub const SCRIPTS_PLUGIN: &str = "scripts";
+
+/// The directory scripts are loaded from.
+const SCRIPT_DIRECTORY: &str = "scripts";
+
+/// How many operations one call into a script may run, so a stuck script can't
+/// hang the thread it runs on.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Reloads every script, replacing the commands and listeners they registered.
+///
+/// # Errors
+/// Returns an error if the script directory can't be read.
+pub fn reload_scripts(server: &Arc<Server>) -> Result<(), PluginError> {
+    server.plugins.disable(SCRIPTS_PLUGIN);
+    let result = server.plugins.enable(server, Box::new(ScriptPlugin));
+    server.resend_commands();
+    result
+}
+
+/// Runs the scripts in the `scripts` directory.
+pub struct ScriptPlugin;
+
+impl Plugin for ScriptPlugin {
+    fn name(&self) -> &'static str {
+        SCRIPTS_PLUGIN
+    }
+
+    fn on_enable(&self, context: &mut PluginContext) -> Result<(), PluginError> {
+        let paths = script_paths()
+            .map_err(|err| PluginError::new(format!("failed to read {SCRIPT_DIRECTORY}: {err}")))?;
+        let registrations = Arc::new(SyncMutex::new(Registrations::default()));
+        let engine = Arc::new(create_engine(context.server(), &registrations));
+
+        for path in paths {
+            let name = path.display().to_string();
+            let ast = match engine
+                .compile_file(path)
+                .and_then(|ast| engine.run_ast(&ast).map(|()| ast))
+            {
+                Ok(ast) => ast,
+                Err(err) => {
+                    log::error!("Failed to run script {name}: {err}");
+                    // Drops what the script registered before it failed
+                    *registrations.lock() = Registrations::default();
+                    continue;
+                }
+            };
+            let Registrations {
+                commands,
+                listeners,
+            } = mem::take(&mut *registrations.lock());
+            let script = Arc::new(Script {
+                name,
+                engine: Arc::clone(&engine),
+                ast,
+            });
+            for (name, function) in commands {
+                context.register_command(ScriptCommand::new(&name, Arc::clone(&script), function));
+            }
+            for (event, function) in listeners {
+                register_listener(context, &event, Arc::clone(&script), function);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `.rhai` files in the script directory, in name order.
+fn script_paths() -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(SCRIPT_DIRECTORY) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension() == Some(OsStr::new("rhai")))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// What the script running right now registered.
+#[derive(Default)]
+struct Registrations {
+    commands: Vec<(String, FnPtr)>,
+    listeners: Vec<(String, FnPtr)>,
+}
+
+fn create_engine(server: &Arc<Server>, registrations: &Arc<SyncMutex<Registrations>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| log::info!("[script] {text}"));
+
+    let sink = Arc::clone(registrations);
+    engine.register_fn("register_command", move |name: &str, function: FnPtr| {
+        sink.lock().commands.push((name.to_owned(), function));
+    });
+    let sink = Arc::clone(registrations);
+    engine.register_fn("on", move |event: &str, function: FnPtr| {
+        sink.lock().listeners.push((event.to_owned(), function));
+    });
+
+    let target = Arc::clone(server);
+    engine.register_fn("broadcast", move |message: &str| {
+        let message = TextComponent::plain(message.to_owned());
+        for world in target.worlds.values() {
+            world.broadcast_to_all_with(|player| CSystemChat::new(&message, false, player));
+        }
+    });
+    let target = Arc::clone(server);
+    engine.register_fn("tell", move |name: &str, message: &str| {
+        let player = target
+            .get_players()
+            .into_iter()
+            .find(|player| player.gameprofile.name == name);
+        if let Some(player) = &player {
+            player.send_message(&TextComponent::plain(message.to_owned()));
+        }
+        player.is_some()
+    });
+
+    engine
+}
+
+/// A script that ran successfully.
+struct Script {
+    name: String,
+    engine: Arc<Engine>,
+    ast: AST,
+}
+
+impl Script {
+    /// Calls a function of the script. Returns `None` if it failed.
+    fn call(&self, function: &FnPtr, args: impl FuncArgs) -> Option<Dynamic> {
+        match function.call::<Dynamic>(&self.engine, &self.ast, args) {
+            Ok(result) => Some(result),
+            Err(err) => {
+                log::error!("Script {} failed: {err}", self.name);
+                None
+            }
+        }
+    }
+
+    /// Calls an event handler. Returns whether it cancels the event.
+    fn handle_event(&self, function: &FnPtr, event: Map) -> bool {
+        self.call(function, (event,))
+            .is_some_and(|result| result.as_bool() == Ok(true))
+    }
+}
+
+fn player_map(player: &Player) -> Map {
+    let mut map = Map::new();
+    map.insert("player".into(), player.gameprofile.name.clone().into());
+    map
+}
+
+fn register_listener(
+    context: &mut PluginContext,
+    event: &str,
+    script: Arc<Script>,
+    function: FnPtr,
+) {
+    match event {
+        "player_join" => {
+            context.register_listener(EventPriority::Normal, move |event: &mut PlayerJoinEvent| {
+                let mut map = player_map(&event.player);
+                map.insert(
+                    "uuid".into(),
+                    event.player.gameprofile.id.to_string().into(),
+                );
+                script.handle_event(&function, map);
+            });
+        }
+        "block_break" => {
+            context.register_uncancelled(
+                EventPriority::Normal,
+                move |event: &mut BlockBreakEvent| {
+                    let mut map = player_map(&event.player);
+                    let world = event.player.world.dimension.key.to_string();
+                    map.insert("world".into(), world.into());
+                    map.insert("x".into(), i64::from(event.pos.x()).into());
+                    map.insert("y".into(), i64::from(event.pos.y()).into());
+                    map.insert("z".into(), i64::from(event.pos.z()).into());
+                    map.insert("state".into(), i64::from(event.state.0).into());
+                    if script.handle_event(&function, map) {
+                        event.set_cancelled(true);
+                    }
+                },
+            );
+        }
+        "chat" => {
+            context.register_uncancelled(EventPriority::Normal, move |event: &mut ChatEvent| {
+                let mut map = player_map(&event.player);
+                map.insert("message".into(), event.message().to_owned().into());
+                if script.handle_event(&function, map) {
+                    event.set_cancelled(true);
+                }
+            });
+        }
+        other => log::warn!("Script {} listens to unknown event {other}", script.name),
+    }
+}
+
+/// The names and permission of each command a script registered, kept across
+/// reloads so reloading doesn't leak them again.
+static COMMAND_STRINGS: SyncMutex<Option<FxHashMap<String, CommandStrings>>> = SyncMutex::new(None);
+
+type CommandStrings = (&'static [&'static str], &'static str);
+
+fn command_strings(name: &str) -> CommandStrings {
+    let mut strings = COMMAND_STRINGS.lock();
+    let strings = strings.get_or_insert_with(FxHashMap::default);
+    if let Some(&known) = strings.get(name) {
+        return known;
+    }
+    let leaked: &'static str = Box::leak(name.into());
+    let permission = format!("steel:command.script.{name}");
+    let known = (
+        &*Box::leak(Box::new([leaked])) as &'static [&'static str],
+        &*Box::leak(permission.into_boxed_str()),
+    );
+    strings.insert(name.to_owned(), known);
+    known
+}
+
+/// A command registered by a script, taking any text as arguments.
+struct ScriptCommand {
+    names: &'static [&'static str],
+    permission: &'static str,
+    script: Arc<Script>,
+    function: FnPtr,
+}
+
+impl ScriptCommand {
+    fn new(name: &str, script: Arc<Script>, function: FnPtr) -> Self {
+        let (names, permission) = command_strings(name);
+        Self {
+            names,
+            permission,
+            script,
+            function,
+        }
+    }
+}
+
+impl CommandHandlerDyn for ScriptCommand {
+    fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+
+    fn description(&self) -> &'static str {
+        "A command of a script."
+    }
+
+    fn permission(&self) -> &'static str {
+        self.permission
+    }
+
+    fn execute(
+        &self,
+        command_args: &[&str],
+        context: &mut CommandContext,
+        _server: &Arc<Server>,
+    ) -> Result<(), CommandError> {
+        let sender = context.sender.to_string();
+        let Some(result) = self
+            .script
+            .call(&self.function, (sender, command_args.join(" ")))
+        else {
+            return Err(CommandError::CommandFailed(Box::new(
+                "The script failed, see the server log.".into(),
+            )));
+        };
+        if let Ok(message) = result.into_string() {
+            context.sender.send_message(&TextComponent::plain(message));
+        }
+        Ok(())
+    }
+
+    fn usage(&self, buffer: &mut Vec<CommandNode>, root_children: &mut Vec<i32>) {
+        let node_index = buffer.len();
+        buffer.push(CommandNode::new_root()); // Reserve spot
+        root_children.push(node_index as i32);
+
+        let args_index = buffer.len();
+        buffer.push(CommandNode::new_argument(
+            CommandNodeInfo::new_executable(),
+            "args",
+            (
+                ArgumentType::String {
+                    behavior: ArgumentStringTypeBehavior::GreedyPhrase,
+                },
+                None,
+            ),
+        ));
+        buffer[node_index] = CommandNode::new_literal(
+            CommandNodeInfo::new(vec![args_index as i32]).chain(CommandNodeInfo::new_executable()),
+            self.names[0],
+        );
+    }
+}
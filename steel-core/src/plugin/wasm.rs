@@ -0,0 +1,614 @@
+//! Sandboxed plugins compiled to WebAssembly.
+//!
+//! Every `.wasm` file in the configured plugin directory is loaded as a plugin
+//! named after the file. Plugins only reach the server through the host API
+//! below, can't touch files or the network, and are stopped and disabled once a
+//! call into them uses up its fuel or they outgrow their memory limit.
+//!
+//! # Plugin interface
+//!
+//! Strings are passed as a pointer and a byte length into the plugin's memory,
+//! encoded as UTF-8. A plugin exports:
+//! - `memory`: its linear memory.
+//! - `steel_alloc(len) -> ptr`: reserves `len` bytes for the server to write
+//!   arguments into.
+//! - `steel_enable() -> i32`: called once the server is ready. Anything but 0
+//!   fails the plugin.
+//! - `steel_disable()`: optional, called when the plugin is disabled.
+//! - `steel_tick()`: optional, called at the end of every tick.
+//! - `steel_command(id, sender_ptr, sender_len, args_ptr, args_len) -> i32`: runs
+//!   a command from `register_command`. Anything but 0 tells the sender the
+//!   command failed.
+//! - `steel_event(kind, ptr, len) -> i32`: handles an event from `listen`, given
+//!   as JSON. Returning 1 cancels the event.
+//!
+//! The server provides, in the `steel` module:
+//! - `log(level, ptr, len)`: logs a message; 0 is error up to 4 for trace.
+//! - `broadcast(ptr, len)`: sends a message to every player.
+//! - `send_message(player_ptr, player_len, ptr, len) -> i32`: sends a message to
+//!   the named player; -1 if they aren't online.
+//! - `get_block(world_ptr, world_len, x, y, z) -> i32`: the block state id; -1
+//!   for an unknown world.
+//! - `set_block(world_ptr, world_len, x, y, z, state) -> i32`: sets a block at
+//!   the end of the tick; -1 for an unknown world or block state.
+//! - `register_command(name_ptr, name_len) -> i32`: registers a command taking
+//!   any text as arguments and returns its id. Only during `steel_enable`.
+//! - `listen(kind) -> i32`: subscribes to an event kind. Only during
+//!   `steel_enable`.
+//!
+//! The event kinds are:
+//! - 0, player join: `{"player", "uuid"}`.
+//! - 1, block break: `{"player", "world", "x", "y", "z", "state"}`. Cancellable.
+//! - 2, chat: `{"player", "message"}`. Cancellable.
+
+use std::ffi::OsStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{fs, io, mem};
+
+use log::Level;
+use serde_json::json;
+use steel_protocol::packets::game::{
+    ArgumentStringTypeBehavior, ArgumentType, CSystemChat, CommandNode, CommandNodeInfo,
+};
+use steel_registry::REGISTRY;
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+use text_components::TextComponent;
+use wasmtime::{
+    Caller, Config, Engine, Error, Extern, Instance, Linker, Module, Store, StoreLimits,
+    StoreLimitsBuilder,
+};
+
+use super::{Plugin, PluginContext, PluginError, register_plugin};
+use crate::command::commands::CommandHandlerDyn;
+use crate::command::context::CommandContext;
+use crate::command::error::CommandError;
+use crate::config::STEEL_CONFIG;
+use crate::event::{BlockBreakEvent, Cancellable, ChatEvent, EventPriority, PlayerJoinEvent};
+use crate::server::Server;
+use crate::server::scheduler::TaskStatus;
+use crate::world::World;
+
+const EVENT_PLAYER_JOIN: i32 = 0;
+const EVENT_BLOCK_BREAK: i32 = 1;
+const EVENT_CHAT: i32 = 2;
+
+/// Registers every `.wasm` plugin in the configured plugin directory.
+pub fn register_wasm_plugins() {
+    let directory = &STEEL_CONFIG.wasm_plugins.directory;
+    let mut paths: Vec<_> = match fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension() == Some(OsStr::new("wasm")))
+            .collect(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+        Err(err) => {
+            log::error!("Failed to read plugin directory {directory}: {err}");
+            return;
+        }
+    };
+    paths.sort();
+
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    let engine = match Engine::new(&config) {
+        Ok(engine) => engine,
+        Err(err) => {
+            log::error!("Failed to create the WASM engine: {err}");
+            return;
+        }
+    };
+
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+            continue;
+        };
+        match Module::from_file(&engine, &path) {
+            Ok(module) => register_plugin(WasmPlugin {
+                name: Box::leak(name.into()),
+                module,
+                instance: SyncMutex::new(None),
+            }),
+            Err(err) => log::error!("Failed to load WASM plugin {}: {err}", path.display()),
+        }
+    }
+}
+
+/// A plugin compiled to WebAssembly.
+pub struct WasmPlugin {
+    name: &'static str,
+    module: Module,
+    instance: SyncMutex<Option<Arc<WasmInstance>>>,
+}
+
+impl Plugin for WasmPlugin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn on_enable(&self, context: &mut PluginContext) -> Result<(), PluginError> {
+        let instance = WasmInstance::new(self.name, &self.module, context.server())
+            .map_err(|err| PluginError::new(err.to_string()))?;
+        let instance = Arc::new(instance);
+
+        let enabled = instance.call(|store, wasm| {
+            store.data_mut().enabling = true;
+            let result = wasm
+                .get_typed_func::<(), i32>(&mut *store, "steel_enable")
+                .and_then(|enable| enable.call(&mut *store, ()));
+            store.data_mut().enabling = false;
+            result
+        });
+        match enabled {
+            Some(0) => {}
+            Some(code) => return Err(PluginError::new(format!("steel_enable returned {code}"))),
+            None => return Err(PluginError::new("steel_enable failed")),
+        }
+
+        let (commands, events) = {
+            let mut store = instance.store.lock();
+            let state = store.data_mut();
+            (mem::take(&mut state.commands), mem::take(&mut state.events))
+        };
+        for (id, name) in commands.into_iter().enumerate() {
+            context.register_command(WasmCommand::new(
+                self.name,
+                name,
+                id as i32,
+                Arc::clone(&instance),
+            ));
+        }
+        for kind in events {
+            register_listener(context, kind, &instance);
+        }
+
+        let ticking = Arc::clone(&instance);
+        context.run_repeating(0, 1, move |_| ticking.tick());
+        *self.instance.lock() = Some(instance);
+        Ok(())
+    }
+
+    fn on_disable(&self, _context: &mut PluginContext) {
+        let Some(instance) = self.instance.lock().take() else {
+            return;
+        };
+        instance.call(
+            |store, wasm| match wasm.get_func(&mut *store, "steel_disable") {
+                Some(disable) => disable.typed::<(), ()>(&*store)?.call(&mut *store, ()),
+                None => Ok(()),
+            },
+        );
+        // Listeners and commands holding the instance may outlive the plugin briefly
+        instance.failed.store(true, Ordering::Relaxed);
+        instance.store.lock().data_mut().server = None;
+    }
+}
+
+fn register_listener(context: &mut PluginContext, kind: i32, instance: &Arc<WasmInstance>) {
+    let instance = Arc::clone(instance);
+    match kind {
+        EVENT_PLAYER_JOIN => {
+            context.register_listener(EventPriority::Normal, move |event: &mut PlayerJoinEvent| {
+                let payload = json!({
+                    "player": event.player.gameprofile.name,
+                    "uuid": event.player.gameprofile.id.to_string(),
+                });
+                instance.handle_event(EVENT_PLAYER_JOIN, &payload.to_string());
+            });
+        }
+        EVENT_BLOCK_BREAK => {
+            context.register_uncancelled(
+                EventPriority::Normal,
+                move |event: &mut BlockBreakEvent| {
+                    let payload = json!({
+                        "player": event.player.gameprofile.name,
+                        "world": event.player.world.dimension.key.to_string(),
+                        "x": event.pos.x(),
+                        "y": event.pos.y(),
+                        "z": event.pos.z(),
+                        "state": event.state.0,
+                    });
+                    if instance.handle_event(EVENT_BLOCK_BREAK, &payload.to_string()) {
+                        event.set_cancelled(true);
+                    }
+                },
+            );
+        }
+        EVENT_CHAT => {
+            context.register_uncancelled(EventPriority::Normal, move |event: &mut ChatEvent| {
+                let payload = json!({
+                    "player": event.player.gameprofile.name,
+                    "message": event.message(),
+                });
+                if instance.handle_event(EVENT_CHAT, &payload.to_string()) {
+                    event.set_cancelled(true);
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+/// What the host functions can reach.
+struct HostState {
+    name: &'static str,
+    limits: StoreLimits,
+    /// Cleared when the plugin is disabled.
+    server: Option<Arc<Server>>,
+    /// Whether `steel_enable` is running, the only time commands and listeners
+    /// can be registered.
+    enabling: bool,
+    commands: Vec<String>,
+    events: Vec<i32>,
+    /// Applied at the end of the tick, as the caller may be holding chunk locks.
+    block_changes: Vec<(Arc<World>, BlockPos, BlockStateId)>,
+}
+
+/// A running plugin.
+struct WasmInstance {
+    name: &'static str,
+    store: SyncMutex<Store<HostState>>,
+    instance: Instance,
+    /// Set once a call failed. The plugin isn't called again and is disabled on
+    /// the next tick.
+    failed: AtomicBool,
+}
+
+impl WasmInstance {
+    fn new(name: &'static str, module: &Module, server: &Arc<Server>) -> Result<Self, Error> {
+        let config = &STEEL_CONFIG.wasm_plugins;
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(config.max_memory)
+            .instances(1)
+            .build();
+        let mut store = Store::new(
+            module.engine(),
+            HostState {
+                name,
+                limits,
+                server: Some(Arc::clone(server)),
+                enabling: false,
+                commands: Vec::new(),
+                events: Vec::new(),
+                block_changes: Vec::new(),
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        // For the module's start function
+        store.set_fuel(config.fuel_per_call)?;
+        let instance = host_functions(module.engine())?.instantiate(&mut store, module)?;
+        Ok(Self {
+            name,
+            store: SyncMutex::new(store),
+            instance,
+            failed: AtomicBool::new(false),
+        })
+    }
+
+    /// Calls into the plugin with a fresh fuel budget. Returns `None` if the
+    /// plugin already failed or fails now.
+    fn call<R>(
+        &self,
+        call: impl FnOnce(&mut Store<HostState>, Instance) -> Result<R, Error>,
+    ) -> Option<R> {
+        if self.failed.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut store = self.store.lock();
+        let result = store
+            .set_fuel(STEEL_CONFIG.wasm_plugins.fuel_per_call)
+            .and_then(|()| call(&mut store, self.instance));
+        match result {
+            Ok(result) => Some(result),
+            Err(err) => {
+                log::error!("WASM plugin {} failed: {err:#}", self.name);
+                self.failed.store(true, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Passes an event to the plugin. Returns whether the plugin cancels it.
+    fn handle_event(&self, kind: i32, payload: &str) -> bool {
+        self.call(|store, wasm| {
+            let (ptr, len) = write_str(store, wasm, payload)?;
+            wasm.get_typed_func::<(i32, i32, i32), i32>(&mut *store, "steel_event")?
+                .call(&mut *store, (kind, ptr, len))
+        }) == Some(1)
+    }
+
+    /// Runs a command of the plugin. Returns whether it succeeded.
+    fn run_command(&self, id: i32, sender: &str, args: &str) -> bool {
+        self.call(|store, wasm| {
+            let (sender_ptr, sender_len) = write_str(store, wasm, sender)?;
+            let (args_ptr, args_len) = write_str(store, wasm, args)?;
+            wasm.get_typed_func::<(i32, i32, i32, i32, i32), i32>(&mut *store, "steel_command")?
+                .call(
+                    &mut *store,
+                    (id, sender_ptr, sender_len, args_ptr, args_len),
+                )
+        }) == Some(0)
+    }
+
+    /// Ticks the plugin and applies the block changes it queued.
+    fn tick(&self) -> TaskStatus {
+        self.call(
+            |store, wasm| match wasm.get_func(&mut *store, "steel_tick") {
+                Some(tick) => tick.typed::<(), ()>(&*store)?.call(&mut *store, ()),
+                None => Ok(()),
+            },
+        );
+        let block_changes = mem::take(&mut self.store.lock().data_mut().block_changes);
+        for (world, pos, state) in block_changes {
+            world.set_block(pos, state, UpdateFlags::UPDATE_ALL);
+        }
+        if self.failed.load(Ordering::Relaxed) {
+            TaskStatus::Failed
+        } else {
+            TaskStatus::Continue
+        }
+    }
+}
+
+/// Copies a string into the plugin's memory.
+fn write_str(
+    store: &mut Store<HostState>,
+    wasm: Instance,
+    data: &str,
+) -> Result<(i32, i32), Error> {
+    let len = i32::try_from(data.len())?;
+    let ptr = wasm
+        .get_typed_func::<i32, i32>(&mut *store, "steel_alloc")?
+        .call(&mut *store, len)?;
+    let memory = wasm
+        .get_memory(&mut *store, "memory")
+        .ok_or_else(|| Error::msg("plugin exports no memory"))?;
+    memory.write(&mut *store, usize::try_from(ptr)?, data.as_bytes())?;
+    Ok((ptr, len))
+}
+
+/// Copies a string out of the plugin's memory.
+fn read_str(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Result<String, Error> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| Error::msg("plugin exports no memory"))?;
+    let mut buffer = vec![0; usize::try_from(len)?];
+    memory.read(&*caller, usize::try_from(ptr)?, &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+fn find_world(server: &Server, key: &str) -> Option<Arc<World>> {
+    let key: Identifier = key.parse().ok()?;
+    server.worlds.get(&key).cloned()
+}
+
+/// Creates the linker providing the host API.
+fn host_functions(engine: &Engine) -> Result<Linker<HostState>, Error> {
+    let mut linker = Linker::new(engine);
+
+    linker.func_wrap(
+        "steel",
+        "log",
+        |mut caller: Caller<'_, HostState>, level: i32, ptr: i32, len: i32| -> Result<(), Error> {
+            let message = read_str(&mut caller, ptr, len)?;
+            let level = match level {
+                0 => Level::Error,
+                1 => Level::Warn,
+                2 => Level::Info,
+                3 => Level::Debug,
+                _ => Level::Trace,
+            };
+            log::log!(level, "[{}] {message}", caller.data().name);
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        "steel",
+        "broadcast",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<(), Error> {
+            let message = TextComponent::plain(read_str(&mut caller, ptr, len)?);
+            if let Some(server) = &caller.data().server {
+                for world in server.worlds.values() {
+                    world.broadcast_to_all_with(|player| CSystemChat::new(&message, false, player));
+                }
+            }
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        "steel",
+        "send_message",
+        |mut caller: Caller<'_, HostState>,
+         player_ptr: i32,
+         player_len: i32,
+         ptr: i32,
+         len: i32|
+         -> Result<i32, Error> {
+            let name = read_str(&mut caller, player_ptr, player_len)?;
+            let message = TextComponent::plain(read_str(&mut caller, ptr, len)?);
+            let player = caller.data().server.as_ref().and_then(|server| {
+                server
+                    .get_players()
+                    .into_iter()
+                    .find(|player| player.gameprofile.name == name)
+            });
+            Ok(match player {
+                Some(player) => {
+                    player.send_message(&message);
+                    0
+                }
+                None => -1,
+            })
+        },
+    )?;
+
+    linker.func_wrap(
+        "steel",
+        "get_block",
+        |mut caller: Caller<'_, HostState>,
+         world_ptr: i32,
+         world_len: i32,
+         x: i32,
+         y: i32,
+         z: i32|
+         -> Result<i32, Error> {
+            let key = read_str(&mut caller, world_ptr, world_len)?;
+            let world = caller
+                .data()
+                .server
+                .as_ref()
+                .and_then(|server| find_world(server, &key));
+            Ok(world.map_or(-1, |world| {
+                i32::from(world.get_block_state(BlockPos::new(x, y, z)).0)
+            }))
+        },
+    )?;
+
+    linker.func_wrap(
+        "steel",
+        "set_block",
+        |mut caller: Caller<'_, HostState>,
+         world_ptr: i32,
+         world_len: i32,
+         x: i32,
+         y: i32,
+         z: i32,
+         state: i32|
+         -> Result<i32, Error> {
+            let key = read_str(&mut caller, world_ptr, world_len)?;
+            let state = u16::try_from(state)
+                .ok()
+                .map(BlockStateId)
+                .filter(|&state| REGISTRY.blocks.by_state_id(state).is_some());
+            let host = caller.data_mut();
+            let world = host
+                .server
+                .as_ref()
+                .and_then(|server| find_world(server, &key));
+            Ok(match (world, state) {
+                (Some(world), Some(state)) => {
+                    host.block_changes
+                        .push((world, BlockPos::new(x, y, z), state));
+                    0
+                }
+                _ => -1,
+            })
+        },
+    )?;
+
+    linker.func_wrap(
+        "steel",
+        "register_command",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<i32, Error> {
+            let name = read_str(&mut caller, ptr, len)?;
+            let valid = !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_');
+            let host = caller.data_mut();
+            if !host.enabling || !valid {
+                return Ok(-1);
+            }
+            host.commands.push(name);
+            Ok(host.commands.len() as i32 - 1)
+        },
+    )?;
+
+    linker.func_wrap(
+        "steel",
+        "listen",
+        |mut caller: Caller<'_, HostState>, kind: i32| {
+            let host = caller.data_mut();
+            if !host.enabling || !(EVENT_PLAYER_JOIN..=EVENT_CHAT).contains(&kind) {
+                return -1;
+            }
+            if !host.events.contains(&kind) {
+                host.events.push(kind);
+            }
+            0
+        },
+    )?;
+
+    Ok(linker)
+}
+
+/// A command registered by a plugin, taking any text as arguments.
+struct WasmCommand {
+    names: &'static [&'static str],
+    permission: &'static str,
+    id: i32,
+    instance: Arc<WasmInstance>,
+}
+
+impl WasmCommand {
+    fn new(plugin: &'static str, name: String, id: i32, instance: Arc<WasmInstance>) -> Self {
+        let permission = format!("{plugin}:command.{name}");
+        let name: &'static str = Box::leak(name.into_boxed_str());
+        Self {
+            names: Box::leak(Box::new([name])),
+            permission: Box::leak(permission.into_boxed_str()),
+            id,
+            instance,
+        }
+    }
+}
+
+impl CommandHandlerDyn for WasmCommand {
+    fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+
+    fn description(&self) -> &'static str {
+        "A command of a WASM plugin."
+    }
+
+    fn permission(&self) -> &'static str {
+        self.permission
+    }
+
+    fn execute(
+        &self,
+        command_args: &[&str],
+        context: &mut CommandContext,
+        _server: &Arc<Server>,
+    ) -> Result<(), CommandError> {
+        let sender = context.sender.to_string();
+        if self
+            .instance
+            .run_command(self.id, &sender, &command_args.join(" "))
+        {
+            Ok(())
+        } else {
+            Err(CommandError::CommandFailed(Box::new(
+                "The command failed.".into(),
+            )))
+        }
+    }
+
+    fn usage(&self, buffer: &mut Vec<CommandNode>, root_children: &mut Vec<i32>) {
+        let node_index = buffer.len();
+        buffer.push(CommandNode::new_root()); // Reserve spot
+        root_children.push(node_index as i32);
+
+        let args_index = buffer.len();
+        buffer.push(CommandNode::new_argument(
+            CommandNodeInfo::new_executable(),
+            "args",
+            (
+                ArgumentType::String {
+                    behavior: ArgumentStringTypeBehavior::GreedyPhrase,
+                },
+                None,
+            ),
+        ));
+        buffer[node_index] = CommandNode::new_literal(
+            CommandNodeInfo::new(vec![args_index as i32]).chain(CommandNodeInfo::new_executable()),
+            self.names[0],
+        );
+    }
+}
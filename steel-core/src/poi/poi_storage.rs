@@ -73,7 +73,10 @@ fn max_tickets_for(type_id: usize) -> u32 {
         .map_or(0, |t| t.ticket_count)
 }
 
-fn distance_sq(a: BlockPos, b: BlockPos) -> i64 {
+/// Returns the squared distance between two block positions.
+///
+/// Vanilla: `Vec3i.distSqr()`.
+pub(crate) fn distance_sq(a: BlockPos, b: BlockPos) -> i64 {
     let dx = i64::from(a.0.x - b.0.x);
     let dy = i64::from(a.0.y - b.0.y);
     let dz = i64::from(a.0.z - b.0.z);
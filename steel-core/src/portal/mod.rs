@@ -1,2 +1,4 @@
 //! Dimension portal system for nether/end portals and future portal types.
+pub mod portal_forcer;
+pub mod portal_processor;
 pub mod portal_shape;
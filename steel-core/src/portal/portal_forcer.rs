@@ -0,0 +1,134 @@
+//! Finding the portal an entity comes out of after changing dimension.
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::{RegistryEntry, vanilla_poi_types};
+use steel_utils::BlockPos;
+
+use crate::poi::poi_storage::distance_sq;
+use crate::poi::{OccupationStatus, PointOfInterestStorage};
+use crate::world::World;
+
+/// How far to search for an existing portal when arriving in the nether.
+pub const NETHER_SEARCH_RADIUS: i32 = 16;
+/// How far to search for an existing portal when arriving anywhere else.
+pub const OVERWORLD_SEARCH_RADIUS: i32 = 128;
+
+/// Finds the nether portal block closest to `pos` in `world`.
+///
+/// Only loaded chunks are searched. Ties are broken by the lower portal block.
+/// Vanilla: `PortalForcer.findClosestPortalPosition()`.
+// TODO: Load the POIs of unloaded chunks in range (PoiManager.ensureLoadedAndValid)
+// TODO: Skip portals outside the world border once it exists
+#[must_use]
+pub fn find_closest_portal_position(
+    world: &World,
+    pos: BlockPos,
+    is_nether: bool,
+) -> Option<BlockPos> {
+    let candidates = portals_in_range(
+        &world.poi_storage.lock(),
+        vanilla_poi_types::NETHER_PORTAL.id(),
+        pos,
+        is_nether,
+    );
+    closest_portal(
+        candidates.into_iter().filter(|&portal| {
+            world
+                .get_block_state(portal)
+                .try_get_value(&BlockStateProperties::HORIZONTAL_AXIS)
+                .is_some()
+        }),
+        pos,
+    )
+}
+
+/// Returns the portal POIs in the square searched around `pos`.
+fn portals_in_range(
+    storage: &PointOfInterestStorage,
+    portal_type: usize,
+    pos: BlockPos,
+    is_nether: bool,
+) -> Vec<BlockPos> {
+    let radius = if is_nether {
+        NETHER_SEARCH_RADIUS
+    } else {
+        OVERWORLD_SEARCH_RADIUS
+    };
+    storage
+        .get_in_square(
+            &|poi_type| poi_type == portal_type,
+            pos,
+            radius,
+            OccupationStatus::Any,
+        )
+        .into_iter()
+        .map(|(portal, _)| portal)
+        .collect()
+}
+
+/// Returns the portal closest to `pos`, the lower one on ties.
+fn closest_portal(portals: impl Iterator<Item = BlockPos>, pos: BlockPos) -> Option<BlockPos> {
+    portals.min_by_key(|&portal| (distance_sq(portal, pos), portal.y()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Once;
+
+    use steel_registry::{REGISTRY, Registry};
+
+    use super::*;
+
+    static INIT: Once = Once::new();
+
+    /// Initializes the vanilla registry, which the POI storage reads ticket counts from.
+    fn ensure_registry() {
+        INIT.call_once(|| {
+            let mut registry = Registry::new_vanilla();
+            registry.freeze();
+            let _ = REGISTRY.init(registry);
+        });
+    }
+
+    #[test]
+    fn finds_the_nearest_portal_in_range() {
+        ensure_registry();
+        let (portal, bed) = (
+            vanilla_poi_types::NETHER_PORTAL.id(),
+            vanilla_poi_types::HOME.id(),
+        );
+        let mut storage = PointOfInterestStorage::new();
+        let center = BlockPos::new(0, 64, 0);
+        storage.add(BlockPos::new(10, 64, 0), portal, 0);
+        storage.add(BlockPos::new(0, 70, 6), portal, 0);
+        storage.add(BlockPos::new(0, 58, 6), portal, 0);
+        storage.add(BlockPos::new(2, 64, 0), bed, 1);
+        storage.add(BlockPos::new(40, 64, 0), portal, 0);
+
+        let nether = portals_in_range(&storage, portal, center, true);
+        assert_eq!(nether.len(), 3);
+        assert_eq!(
+            closest_portal(nether.into_iter(), center),
+            Some(BlockPos::new(0, 58, 6))
+        );
+
+        let far = BlockPos::new(36, 64, 0);
+        let overworld = portals_in_range(&storage, portal, far, false);
+        assert_eq!(overworld.len(), 4);
+        assert_eq!(
+            closest_portal(overworld.into_iter(), far),
+            Some(BlockPos::new(40, 64, 0))
+        );
+    }
+
+    #[test]
+    fn nothing_found_outside_the_radius() {
+        ensure_registry();
+        let portal = vanilla_poi_types::NETHER_PORTAL.id();
+        let mut storage = PointOfInterestStorage::new();
+        storage.add(BlockPos::new(0, 64, NETHER_SEARCH_RADIUS + 1), portal, 0);
+        let nether = portals_in_range(&storage, portal, BlockPos::new(0, 64, 0), true);
+        assert!(nether.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! Tracks how long an entity has been standing in a portal.
+
+use steel_utils::BlockPos;
+
+/// How long an entity has been inside a portal, and where it entered it.
+///
+/// Vanilla: `PortalProcessor`.
+// TODO: Track the portal kind once end portals and end gateways teleport
+#[derive(Debug, Clone)]
+pub struct PortalProcessor {
+    entry_pos: BlockPos,
+    portal_time: i32,
+    inside_portal_this_tick: bool,
+}
+
+impl PortalProcessor {
+    /// Starts tracking an entity that just entered the portal at `entry_pos`.
+    #[must_use]
+    pub const fn new(entry_pos: BlockPos) -> Self {
+        Self {
+            entry_pos,
+            portal_time: 0,
+            inside_portal_this_tick: true,
+        }
+    }
+
+    /// Marks the entity as inside the portal at `pos` for this tick.
+    ///
+    /// Vanilla: the same-portal branch of `Entity.setAsInsidePortal()`.
+    pub fn set_inside_portal(&mut self, pos: BlockPos) {
+        if !self.inside_portal_this_tick {
+            self.entry_pos = pos;
+            self.inside_portal_this_tick = true;
+        }
+    }
+
+    /// Counts a tick and returns true once the entity stood in the portal for
+    /// `transition_time` ticks. Time spent outside the portal slowly decays.
+    ///
+    /// Vanilla: `PortalProcessor.processPortalTeleportation()`.
+    pub fn process_portal_teleportation(
+        &mut self,
+        allowed_to_teleport: bool,
+        transition_time: i32,
+    ) -> bool {
+        if !self.inside_portal_this_tick {
+            self.portal_time = (self.portal_time - 4).max(0);
+            return false;
+        }
+        self.inside_portal_this_tick = false;
+        if !allowed_to_teleport {
+            return false;
+        }
+        let ready = self.portal_time >= transition_time;
+        self.portal_time += 1;
+        ready
+    }
+
+    /// Returns true once the time spent in the portal decayed back to zero.
+    ///
+    /// Vanilla: `PortalProcessor.hasExpired()`.
+    #[must_use]
+    pub const fn has_expired(&self) -> bool {
+        self.portal_time <= 0
+    }
+
+    /// Returns the portal block the entity last entered.
+    #[must_use]
+    pub const fn entry_pos(&self) -> BlockPos {
+        self.entry_pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teleports_after_the_transition_time() {
+        let mut portal = PortalProcessor::new(BlockPos::new(0, 64, 0));
+        for _ in 0..3 {
+            assert!(!portal.process_portal_teleportation(true, 3));
+            portal.set_inside_portal(BlockPos::new(0, 64, 0));
+        }
+        assert!(portal.process_portal_teleportation(true, 3));
+    }
+
+    #[test]
+    fn time_decays_outside_the_portal() {
+        let mut portal = PortalProcessor::new(BlockPos::new(0, 64, 0));
+        for _ in 0..6 {
+            portal.process_portal_teleportation(true, 80);
+            portal.set_inside_portal(BlockPos::new(1, 64, 0));
+        }
+        assert_eq!(portal.entry_pos(), BlockPos::new(1, 64, 0));
+        portal.process_portal_teleportation(true, 80);
+        assert!(!portal.has_expired());
+        portal.process_portal_teleportation(true, 80);
+        assert!(!portal.has_expired());
+        portal.process_portal_teleportation(true, 80);
+        assert!(portal.has_expired());
+    }
+}
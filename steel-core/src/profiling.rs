@@ -0,0 +1,427 @@
+//! Per-phase tick timers and chunk allocation counters, compiled in with the
+//! `profiling` feature.
+//!
+//! Without the feature [`PhaseTimer`] is zero-sized, never reads the clock, and
+//! the `record_*` functions are empty, so the call sites cost nothing. Packet
+//! encoding counters live in [`steel_protocol::profiling`] and are included in
+//! [`ProfilingSnapshot`].
+
+use std::fmt::Write;
+#[cfg(feature = "profiling")]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+pub use steel_protocol::profiling::PacketCounters;
+
+/// Whether the timers and counters are compiled in.
+pub const ENABLED: bool = cfg!(feature = "profiling");
+
+/// A timed part of the server tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TickPhase {
+    /// A whole world tick.
+    WorldTick,
+    /// Weather and time of day.
+    WeatherAndTime,
+    /// Neighbor updates carried over from the last tick.
+    NeighborUpdates,
+    /// Chunk loading, generation scheduling and chunk ticking.
+    ChunkMap,
+    /// Block events (pistons, note blocks, ...).
+    BlockEvents,
+    /// Ticking players.
+    Players,
+    /// Waypoint tracking.
+    Waypoints,
+    /// Player latency broadcasts.
+    LatencyBroadcast,
+    /// Serializing and encoding chunk batches, off the tick thread.
+    ChunkSend,
+    /// Compacting chunk sections after many block changes.
+    ChunkCompaction,
+}
+
+impl TickPhase {
+    /// All phases.
+    pub const ALL: [Self; 10] = [
+        Self::WorldTick,
+        Self::WeatherAndTime,
+        Self::NeighborUpdates,
+        Self::ChunkMap,
+        Self::BlockEvents,
+        Self::Players,
+        Self::Waypoints,
+        Self::LatencyBroadcast,
+        Self::ChunkSend,
+        Self::ChunkCompaction,
+    ];
+
+    /// Gets the phase's name in metrics.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::WorldTick => "world_tick",
+            Self::WeatherAndTime => "weather_and_time",
+            Self::NeighborUpdates => "neighbor_updates",
+            Self::ChunkMap => "chunk_map",
+            Self::BlockEvents => "block_events",
+            Self::Players => "players",
+            Self::Waypoints => "waypoints",
+            Self::LatencyBroadcast => "latency_broadcast",
+            Self::ChunkSend => "chunk_send",
+            Self::ChunkCompaction => "chunk_compaction",
+        }
+    }
+
+    #[cfg(feature = "profiling")]
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Times a phase until dropped.
+///
+/// ```ignore
+/// let _timer = PhaseTimer::start(TickPhase::BlockEvents);
+/// world.run_block_events();
+/// ```
+#[must_use = "the phase is only timed until the timer is dropped"]
+pub struct PhaseTimer {
+    #[cfg(feature = "profiling")]
+    phase: TickPhase,
+    #[cfg(feature = "profiling")]
+    start: std::time::Instant,
+}
+
+impl PhaseTimer {
+    /// Starts timing `phase`.
+    #[cfg(feature = "profiling")]
+    #[inline]
+    pub fn start(phase: TickPhase) -> Self {
+        Self {
+            phase,
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Starts timing `phase`.
+    #[cfg(not(feature = "profiling"))]
+    #[inline]
+    pub const fn start(_phase: TickPhase) -> Self {
+        Self {}
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        record_phase(self.phase, self.start.elapsed());
+    }
+}
+
+/// Accumulated timings of one phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseStats {
+    /// The phase.
+    pub phase: TickPhase,
+    /// Time spent in the phase in total.
+    pub total: Duration,
+    /// How many times the phase ran.
+    pub samples: u64,
+    /// The longest single run.
+    pub max: Duration,
+}
+
+/// Totals of the chunk serialization path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkCounters {
+    /// Chunks serialized for sending.
+    pub chunks_serialized: u64,
+    /// Bytes of serialized section data.
+    pub section_bytes: u64,
+    /// Buffers allocated for section data.
+    pub buffer_allocations: u64,
+    /// Bytes allocated for those buffers, including growth.
+    pub buffer_allocated_bytes: u64,
+}
+
+#[cfg(feature = "profiling")]
+struct PhaseCounters {
+    total_nanos: AtomicU64,
+    samples: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+#[cfg(feature = "profiling")]
+#[expect(
+    clippy::declare_interior_mutable_const,
+    reason = "only used to initialize the PHASES array"
+)]
+const PHASE_COUNTERS_INIT: PhaseCounters = PhaseCounters {
+    total_nanos: AtomicU64::new(0),
+    samples: AtomicU64::new(0),
+    max_nanos: AtomicU64::new(0),
+};
+
+#[cfg(feature = "profiling")]
+static PHASES: [PhaseCounters; TickPhase::ALL.len()] = [PHASE_COUNTERS_INIT; TickPhase::ALL.len()];
+
+#[cfg(feature = "profiling")]
+static CHUNKS_SERIALIZED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static SECTION_BYTES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static CHUNK_BUFFER_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static CHUNK_BUFFER_ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Adds a run of `phase` that took `elapsed`.
+#[cfg(feature = "profiling")]
+pub fn record_phase(phase: TickPhase, elapsed: Duration) {
+    let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    let counters = &PHASES[phase.index()];
+    counters.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+    counters.samples.fetch_add(1, Ordering::Relaxed);
+    counters.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+}
+
+/// Adds a run of `phase` that took `elapsed`.
+#[cfg(not(feature = "profiling"))]
+#[inline]
+pub const fn record_phase(_phase: TickPhase, _elapsed: Duration) {}
+
+/// Records a serialized chunk with `len` bytes of section data in a buffer of
+/// `allocated` bytes.
+#[cfg(feature = "profiling")]
+#[inline]
+pub fn record_chunk_serialized(len: usize, allocated: usize) {
+    CHUNKS_SERIALIZED.fetch_add(1, Ordering::Relaxed);
+    SECTION_BYTES.fetch_add(len as u64, Ordering::Relaxed);
+    CHUNK_BUFFER_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    CHUNK_BUFFER_ALLOCATED_BYTES.fetch_add(allocated as u64, Ordering::Relaxed);
+}
+
+/// Records a serialized chunk with `len` bytes of section data in a buffer of
+/// `allocated` bytes.
+#[cfg(not(feature = "profiling"))]
+#[inline]
+pub const fn record_chunk_serialized(_len: usize, _allocated: usize) {}
+
+/// Sets all timers and counters, including the packet counters, back to zero.
+#[cfg(feature = "profiling")]
+pub fn reset() {
+    for counters in &PHASES {
+        counters.total_nanos.store(0, Ordering::Relaxed);
+        counters.samples.store(0, Ordering::Relaxed);
+        counters.max_nanos.store(0, Ordering::Relaxed);
+    }
+    for counter in [
+        &CHUNKS_SERIALIZED,
+        &SECTION_BYTES,
+        &CHUNK_BUFFER_ALLOCATIONS,
+        &CHUNK_BUFFER_ALLOCATED_BYTES,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+    steel_protocol::profiling::reset();
+}
+
+/// Sets all timers and counters, including the packet counters, back to zero.
+#[cfg(not(feature = "profiling"))]
+pub const fn reset() {}
+
+/// All timers and counters at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfilingSnapshot {
+    /// Timings per phase. Empty without the `profiling` feature.
+    pub phases: Vec<PhaseStats>,
+    /// Chunk serialization counters.
+    pub chunks: ChunkCounters,
+    /// Packet encoding counters.
+    pub packets: PacketCounters,
+}
+
+impl ProfilingSnapshot {
+    /// Reads the current values of all timers and counters.
+    #[cfg(feature = "profiling")]
+    #[must_use]
+    pub fn capture() -> Self {
+        let phases = TickPhase::ALL
+            .iter()
+            .map(|&phase| {
+                let counters = &PHASES[phase.index()];
+                PhaseStats {
+                    phase,
+                    total: Duration::from_nanos(counters.total_nanos.load(Ordering::Relaxed)),
+                    samples: counters.samples.load(Ordering::Relaxed),
+                    max: Duration::from_nanos(counters.max_nanos.load(Ordering::Relaxed)),
+                }
+            })
+            .collect();
+        Self {
+            phases,
+            chunks: ChunkCounters {
+                chunks_serialized: CHUNKS_SERIALIZED.load(Ordering::Relaxed),
+                section_bytes: SECTION_BYTES.load(Ordering::Relaxed),
+                buffer_allocations: CHUNK_BUFFER_ALLOCATIONS.load(Ordering::Relaxed),
+                buffer_allocated_bytes: CHUNK_BUFFER_ALLOCATED_BYTES.load(Ordering::Relaxed),
+            },
+            packets: steel_protocol::profiling::snapshot(),
+        }
+    }
+
+    /// Reads the current values of all timers and counters.
+    #[cfg(not(feature = "profiling"))]
+    #[must_use]
+    pub fn capture() -> Self {
+        Self {
+            phases: Vec::new(),
+            chunks: ChunkCounters::default(),
+            packets: steel_protocol::profiling::snapshot(),
+        }
+    }
+
+    /// Renders the snapshot in the Prometheus text exposition format.
+    // TODO: Serve this from an HTTP metrics listener once the server has one
+    #[must_use]
+    pub fn render_metrics(&self) -> String {
+        let mut out = String::new();
+        metric(
+            &mut out,
+            "steel_profiling_enabled",
+            "gauge",
+            "Whether profiling counters are compiled in.",
+            &[("", u64::from(ENABLED).to_string())],
+        );
+
+        if !self.phases.is_empty() {
+            let series = |value: fn(&PhaseStats) -> String| {
+                self.phases
+                    .iter()
+                    .map(|stats| {
+                        (
+                            format!("{{phase=\"{}\"}}", stats.phase.name()),
+                            value(stats),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
+            metric(
+                &mut out,
+                "steel_tick_phase_seconds_total",
+                "counter",
+                "Time spent in each tick phase.",
+                &series(|stats| stats.total.as_secs_f64().to_string()),
+            );
+            metric(
+                &mut out,
+                "steel_tick_phase_runs_total",
+                "counter",
+                "How many times each tick phase ran.",
+                &series(|stats| stats.samples.to_string()),
+            );
+            metric(
+                &mut out,
+                "steel_tick_phase_max_seconds",
+                "gauge",
+                "Longest single run of each tick phase.",
+                &series(|stats| stats.max.as_secs_f64().to_string()),
+            );
+        }
+
+        let chunks = &self.chunks;
+        let packets = &self.packets;
+        for (name, help, value) in [
+            (
+                "steel_chunks_serialized_total",
+                "Chunks serialized for sending.",
+                chunks.chunks_serialized,
+            ),
+            (
+                "steel_chunk_section_bytes_total",
+                "Bytes of serialized chunk section data.",
+                chunks.section_bytes,
+            ),
+            (
+                "steel_chunk_buffer_allocations_total",
+                "Buffers allocated for chunk section data.",
+                chunks.buffer_allocations,
+            ),
+            (
+                "steel_chunk_buffer_allocated_bytes_total",
+                "Bytes allocated for chunk section data.",
+                chunks.buffer_allocated_bytes,
+            ),
+            (
+                "steel_packets_encoded_total",
+                "Packets encoded.",
+                packets.packets_encoded,
+            ),
+            (
+                "steel_packet_bytes_total",
+                "Bytes of encoded packets after compression.",
+                packets.bytes_encoded,
+            ),
+            (
+                "steel_packets_compressed_total",
+                "Packets that were compressed.",
+                packets.packets_compressed,
+            ),
+            (
+                "steel_packet_buffer_allocations_total",
+                "Buffers allocated while encoding packets.",
+                packets.buffer_allocations,
+            ),
+            (
+                "steel_packet_buffer_allocated_bytes_total",
+                "Bytes allocated while encoding packets.",
+                packets.buffer_allocated_bytes,
+            ),
+        ] {
+            metric(&mut out, name, "counter", help, &[("", value.to_string())]);
+        }
+        out
+    }
+}
+
+/// Writes one metric family with its series, given as (labels, value).
+fn metric<L: AsRef<str>>(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    series: &[(L, String)],
+) {
+    // Writing to a String can't fail
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (labels, value) in series {
+        let _ = writeln!(out, "{name}{} {value}", labels.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_list_every_counter() {
+        let metrics = ProfilingSnapshot::capture().render_metrics();
+        assert!(metrics.contains(&format!("steel_profiling_enabled {}", u8::from(ENABLED))));
+        assert!(metrics.contains("# TYPE steel_packets_encoded_total counter"));
+        assert!(metrics.contains("steel_chunks_serialized_total "));
+        assert_eq!(
+            metrics.contains("steel_tick_phase_seconds_total{phase=\"chunk_map\"}"),
+            ENABLED
+        );
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn phase_timer_records_runs() {
+        let before = ProfilingSnapshot::capture().phases[TickPhase::Waypoints.index()].samples;
+        drop(PhaseTimer::start(TickPhase::Waypoints));
+        let after = ProfilingSnapshot::capture().phases[TickPhase::Waypoints.index()].samples;
+        assert!(after > before);
+    }
+}
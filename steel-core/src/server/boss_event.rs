@@ -0,0 +1,196 @@
+//! Server-side boss bars shown to a set of players.
+
+use std::sync::{Arc, Weak};
+
+use rustc_hash::FxHashMap;
+use steel_protocol::packet_traits::EncodedPacket;
+use steel_protocol::packets::game::{BossBarColor, BossBarOverlay, BossEventOperation, CBossEvent};
+use steel_protocol::utils::ConnectionProtocol;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::config::STEEL_CONFIG;
+use crate::player::Player;
+use crate::player::connection::NetworkConnection;
+
+/// A boss bar and the players who see it.
+///
+/// Every change is sent to the players right away. Players are held weakly, a
+/// player who disconnected simply stops receiving updates.
+///
+/// Vanilla equivalent: `ServerBossEvent`.
+pub struct ServerBossEvent {
+    id: Uuid,
+    name: TextComponent,
+    progress: f32,
+    color: BossBarColor,
+    overlay: BossBarOverlay,
+    flags: u8,
+    visible: bool,
+    players: FxHashMap<Uuid, Weak<Player>>,
+}
+
+impl ServerBossEvent {
+    /// Creates a visible, full boss bar without any players.
+    #[must_use]
+    pub fn new(name: TextComponent, color: BossBarColor, overlay: BossBarOverlay) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            progress: 1.0,
+            color,
+            overlay,
+            flags: 0,
+            visible: true,
+            players: FxHashMap::default(),
+        }
+    }
+
+    /// Gets the id the client knows the bar by.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Gets how full the bar is, from 0 to 1.
+    #[must_use]
+    pub const fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Returns true if the players see the bar.
+    #[must_use]
+    pub const fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Sets how full the bar is, from 0 to 1.
+    pub fn set_progress(&mut self, progress: f32) {
+        if progress != self.progress {
+            self.progress = progress;
+            self.broadcast(BossEventOperation::UpdateProgress(progress));
+        }
+    }
+
+    /// Sets the title above the bar.
+    pub fn set_name(&mut self, name: TextComponent) {
+        self.name = name.clone();
+        self.broadcast(BossEventOperation::UpdateName(name));
+    }
+
+    /// Sets the bar's color.
+    pub fn set_color(&mut self, color: BossBarColor) {
+        if color != self.color {
+            self.color = color;
+            self.broadcast(BossEventOperation::UpdateStyle(color, self.overlay));
+        }
+    }
+
+    /// Sets how the bar is divided.
+    pub fn set_overlay(&mut self, overlay: BossBarOverlay) {
+        if overlay != self.overlay {
+            self.overlay = overlay;
+            self.broadcast(BossEventOperation::UpdateStyle(self.color, overlay));
+        }
+    }
+
+    /// Turns `flag` on or off. See [`CBossEvent::DARKEN_SCREEN`] and the other flags.
+    pub fn set_flag(&mut self, flag: u8, value: bool) {
+        let flags = if value {
+            self.flags | flag
+        } else {
+            self.flags & !flag
+        };
+        if flags != self.flags {
+            self.flags = flags;
+            self.broadcast(BossEventOperation::UpdateProperties(flags));
+        }
+    }
+
+    /// Shows or hides the bar for all of its players.
+    pub fn set_visible(&mut self, visible: bool) {
+        if visible == self.visible {
+            return;
+        }
+        self.visible = visible;
+        for player in self.players.values().filter_map(Weak::upgrade) {
+            let operation = if visible {
+                self.add_operation()
+            } else {
+                BossEventOperation::Remove
+            };
+            self.send(&player, operation);
+        }
+    }
+
+    /// Adds a player, showing them the bar if it's visible.
+    pub fn add_player(&mut self, player: &Arc<Player>) {
+        if self
+            .players
+            .insert(player.uuid(), Arc::downgrade(player))
+            .is_none()
+            && self.visible
+        {
+            self.send(player, self.add_operation());
+        }
+    }
+
+    /// Removes a player, hiding the bar from them.
+    pub fn remove_player(&mut self, player: &Arc<Player>) {
+        if self.players.remove(&player.uuid()).is_some() && self.visible {
+            self.send(player, BossEventOperation::Remove);
+        }
+    }
+
+    /// Removes every player, hiding the bar from all of them.
+    pub fn remove_all_players(&mut self) {
+        for player in self.players.values().filter_map(Weak::upgrade) {
+            if self.visible {
+                self.send(&player, BossEventOperation::Remove);
+            }
+        }
+        self.players.clear();
+    }
+
+    /// Gets the players who are still online, dropping the ones who left.
+    pub fn players(&mut self) -> Vec<Arc<Player>> {
+        self.players.retain(|_, player| player.strong_count() > 0);
+        self.players.values().filter_map(Weak::upgrade).collect()
+    }
+
+    fn add_operation(&self) -> BossEventOperation {
+        BossEventOperation::Add {
+            name: self.name.clone(),
+            progress: self.progress,
+            color: self.color,
+            overlay: self.overlay,
+            flags: self.flags,
+        }
+    }
+
+    fn send(&self, player: &Player, operation: BossEventOperation) {
+        player.send_packet(CBossEvent {
+            id: self.id,
+            operation,
+        });
+    }
+
+    fn broadcast(&self, operation: BossEventOperation) {
+        if !self.visible {
+            return;
+        }
+        let Ok(encoded) = EncodedPacket::from_bare(
+            CBossEvent {
+                id: self.id,
+                operation,
+            },
+            STEEL_CONFIG.compression,
+            ConnectionProtocol::Play,
+        ) else {
+            return;
+        };
+        for player in self.players.values().filter_map(Weak::upgrade) {
+            player.connection.send_encoded(encoded.clone());
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Runtime view and simulation distance settings.
+//!
+//! The values start out from `STEEL_CONFIG` but can be changed while the server
+//! is running (see `Server::set_view_distance` and `Server::set_simulation_distance`).
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::chunk::chunk_ticket_manager::MAX_VIEW_DISTANCE;
+use crate::config::STEEL_CONFIG;
+
+/// The smallest view distance the server can be set to.
+pub const MIN_VIEW_DISTANCE: u8 = 1;
+
+/// The server-wide view and simulation distance caps.
+pub struct ChunkDistances {
+    view_distance: AtomicU8,
+    simulation_distance: AtomicU8,
+}
+
+/// The global chunk distance settings.
+pub static CHUNK_DISTANCES: ChunkDistances = ChunkDistances {
+    view_distance: AtomicU8::new(10),
+    simulation_distance: AtomicU8::new(10),
+};
+
+/// Error returned when a distance change is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceError {
+    /// The view distance is outside of `MIN_VIEW_DISTANCE..=MAX_VIEW_DISTANCE`.
+    ViewDistanceOutOfRange,
+    /// The simulation distance is larger than the view distance.
+    SimulationExceedsView,
+}
+
+impl ChunkDistances {
+    /// Resets both distances to the values from the server config.
+    pub fn init_from_config(&self) {
+        self.view_distance
+            .store(STEEL_CONFIG.view_distance, Ordering::Relaxed);
+        self.simulation_distance
+            .store(STEEL_CONFIG.simulation_distance, Ordering::Relaxed);
+    }
+
+    /// Returns the maximum view distance a player can use.
+    #[must_use]
+    pub fn view_distance(&self) -> u8 {
+        self.view_distance.load(Ordering::Relaxed)
+    }
+
+    /// Returns the distance around players in which chunks are ticked.
+    #[must_use]
+    pub fn simulation_distance(&self) -> u8 {
+        self.simulation_distance.load(Ordering::Relaxed)
+    }
+
+    /// Sets the view distance cap.
+    ///
+    /// If the simulation distance is larger than the new view distance it is
+    /// lowered to match. Returns the simulation distance after the change.
+    ///
+    /// # Errors
+    /// - If `distance` is outside of `MIN_VIEW_DISTANCE..=MAX_VIEW_DISTANCE`.
+    pub fn set_view_distance(&self, distance: u8) -> Result<u8, DistanceError> {
+        if !(MIN_VIEW_DISTANCE..=MAX_VIEW_DISTANCE).contains(&distance) {
+            return Err(DistanceError::ViewDistanceOutOfRange);
+        }
+        self.view_distance.store(distance, Ordering::Relaxed);
+        let simulation_distance = self.simulation_distance().min(distance);
+        self.simulation_distance
+            .store(simulation_distance, Ordering::Relaxed);
+        Ok(simulation_distance)
+    }
+
+    /// Sets the simulation distance.
+    ///
+    /// # Errors
+    /// - If `distance` is larger than the current view distance.
+    pub fn set_simulation_distance(&self, distance: u8) -> Result<(), DistanceError> {
+        if distance > self.view_distance() {
+            return Err(DistanceError::SimulationExceedsView);
+        }
+        self.simulation_distance.store(distance, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distances(view: u8, simulation: u8) -> ChunkDistances {
+        ChunkDistances {
+            view_distance: AtomicU8::new(view),
+            simulation_distance: AtomicU8::new(simulation),
+        }
+    }
+
+    #[test]
+    fn lowering_view_distance_clamps_simulation_distance() {
+        let distances = distances(12, 10);
+        assert_eq!(distances.set_view_distance(6), Ok(6));
+        assert_eq!(distances.view_distance(), 6);
+        assert_eq!(distances.simulation_distance(), 6);
+    }
+
+    #[test]
+    fn view_distance_must_be_in_range() {
+        let distances = distances(10, 10);
+        assert_eq!(
+            distances.set_view_distance(0),
+            Err(DistanceError::ViewDistanceOutOfRange)
+        );
+        assert_eq!(
+            distances.set_view_distance(MAX_VIEW_DISTANCE + 1),
+            Err(DistanceError::ViewDistanceOutOfRange)
+        );
+        assert_eq!(distances.view_distance(), 10);
+    }
+
+    #[test]
+    fn simulation_distance_cannot_exceed_view_distance() {
+        let distances = distances(8, 4);
+        assert_eq!(
+            distances.set_simulation_distance(9),
+            Err(DistanceError::SimulationExceedsView)
+        );
+        assert_eq!(distances.set_simulation_distance(8), Ok(()));
+        assert_eq!(distances.simulation_distance(), 8);
+    }
+}
@@ -0,0 +1,235 @@
+//! Server-side callbacks for clickable text.
+//!
+//! A callback is registered under a random, unguessable id and attached to a
+//! component as a `custom` click event. The client sends the id back in
+//! `SCustomClickAction` when the text is clicked, so plugins can react to clicks
+//! without exposing a command the player could also type.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use steel_utils::Identifier;
+use steel_utils::locks::SyncMutex;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+use crate::player::Player;
+
+/// Namespace of the ids handed out for click callbacks.
+pub const CALLBACK_NAMESPACE: &str = "steel";
+
+/// Path prefix of the ids handed out for click callbacks.
+const CALLBACK_PATH_PREFIX: &str = "callback/";
+
+/// A function run when a player clicks text carrying its click event.
+///
+/// Receives the clicking player and the payload sent along with the click, if any.
+pub type ClickCallback = dyn Fn(&Arc<Player>, Option<&NbtTag>) + Send + Sync;
+
+/// Limits on when a click callback may run.
+#[derive(Debug, Clone, Copy)]
+pub struct CallbackOptions {
+    /// How long the callback stays registered.
+    pub lifetime: Duration,
+    /// How many times it may run, or `None` for no limit.
+    pub max_uses: Option<u32>,
+    /// The only player allowed to trigger it, or `None` for anyone.
+    pub owner: Option<Uuid>,
+}
+
+impl Default for CallbackOptions {
+    fn default() -> Self {
+        Self {
+            lifetime: Duration::from_secs(10 * 60),
+            max_uses: None,
+            owner: None,
+        }
+    }
+}
+
+impl CallbackOptions {
+    /// Options for a callback that runs once, for `owner` only.
+    #[must_use]
+    pub fn once_for(owner: Uuid) -> Self {
+        Self {
+            max_uses: Some(1),
+            owner: Some(owner),
+            ..Self::default()
+        }
+    }
+}
+
+struct CallbackEntry {
+    callback: Arc<ClickCallback>,
+    expires_at: Instant,
+    remaining_uses: Option<u32>,
+    owner: Option<Uuid>,
+}
+
+/// Registry of click callbacks, keyed by the id sent in the click event.
+#[derive(Default)]
+pub struct ClickCallbacks {
+    entries: SyncMutex<FxHashMap<Identifier, CallbackEntry>>,
+}
+
+impl ClickCallbacks {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `callback` and returns the id to put in a `custom` click event.
+    pub fn register(
+        &self,
+        options: CallbackOptions,
+        callback: impl Fn(&Arc<Player>, Option<&NbtTag>) + Send + Sync + 'static,
+    ) -> Identifier {
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        entries.retain(|_, entry| entry.expires_at > now);
+
+        let id = loop {
+            let id = Identifier::new(
+                CALLBACK_NAMESPACE,
+                format!("{CALLBACK_PATH_PREFIX}{:032x}", rand::random::<u128>()),
+            );
+            if !entries.contains_key(&id) {
+                break id;
+            }
+        };
+        entries.insert(
+            id.clone(),
+            CallbackEntry {
+                callback: Arc::new(callback),
+                expires_at: now + options.lifetime,
+                remaining_uses: options.max_uses,
+                owner: options.owner,
+            },
+        );
+        id
+    }
+
+    /// Registers `callback` and returns `component` with a click event that runs it.
+    pub fn attach(
+        &self,
+        component: TextComponent,
+        options: CallbackOptions,
+        callback: impl Fn(&Arc<Player>, Option<&NbtTag>) + Send + Sync + 'static,
+    ) -> TextComponent {
+        let id = self.register(options, callback);
+        with_custom_click(component, &id, None)
+    }
+
+    /// Removes a callback before it expires.
+    pub fn unregister(&self, id: &Identifier) -> bool {
+        self.entries.lock().remove(id).is_some()
+    }
+
+    /// Runs the callback registered under `id` for a click by `player`.
+    ///
+    /// Returns `false` if `id` is not a live callback the player may trigger.
+    pub fn handle_click(
+        &self,
+        player: &Arc<Player>,
+        id: &Identifier,
+        payload: Option<&NbtTag>,
+    ) -> bool {
+        let callback = {
+            let mut entries = self.entries.lock();
+            let Some(entry) = entries.get_mut(id) else {
+                return false;
+            };
+            if entry.expires_at <= Instant::now() {
+                entries.remove(id);
+                return false;
+            }
+            if entry
+                .owner
+                .is_some_and(|owner| owner != player.gameprofile.id)
+            {
+                return false;
+            }
+            let callback = entry.callback.clone();
+            if let Some(remaining) = &mut entry.remaining_uses {
+                *remaining = remaining.saturating_sub(1);
+                if *remaining == 0 {
+                    entries.remove(id);
+                }
+            }
+            callback
+        };
+        // Run outside the lock so the callback can register new callbacks
+        callback(player, payload);
+        true
+    }
+
+    /// Returns true if `id` has the shape of a callback id handed out by this registry.
+    #[must_use]
+    pub fn is_callback_id(id: &Identifier) -> bool {
+        id.namespace == CALLBACK_NAMESPACE && id.path.starts_with(CALLBACK_PATH_PREFIX)
+    }
+}
+
+/// Returns `component` with a `custom` click event sending `id` (and `payload`) to the server.
+#[must_use]
+pub fn with_custom_click(
+    mut component: TextComponent,
+    id: &Identifier,
+    payload: Option<NbtTag>,
+) -> TextComponent {
+    let mut click_event = NbtCompound::new();
+    click_event.insert("action", "custom");
+    click_event.insert("id", id.to_string());
+    if let Some(payload) = payload {
+        click_event.insert("payload", payload);
+    }
+    let mut template = NbtCompound::new();
+    template.insert("text", "");
+    template.insert("click_event", NbtTag::Compound(click_event));
+
+    // Decode through the component codec rather than building the event by hand,
+    // so the payload ends up in the same representation the client echoes back
+    if let Some(template) = TextComponent::from_nbt(&NbtTag::Compound(template)) {
+        component.interactions.click = template.interactions.click;
+    }
+    component
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_ids_are_unique_callback_ids() {
+        let callbacks = ClickCallbacks::new();
+        let a = callbacks.register(CallbackOptions::default(), |_, _| {});
+        let b = callbacks.register(CallbackOptions::default(), |_, _| {});
+
+        assert_ne!(a, b);
+        assert!(ClickCallbacks::is_callback_id(&a));
+        assert!(!ClickCallbacks::is_callback_id(
+            &Identifier::vanilla_static("callback/0")
+        ));
+    }
+
+    #[test]
+    fn expired_and_removed_callbacks_are_dropped() {
+        let callbacks = ClickCallbacks::new();
+        let id = callbacks.register(CallbackOptions::default(), |_, _| {});
+        assert!(callbacks.unregister(&id));
+        assert!(!callbacks.unregister(&id));
+
+        let expired = callbacks.register(
+            CallbackOptions {
+                lifetime: Duration::ZERO,
+                ..CallbackOptions::default()
+            },
+            |_, _| {},
+        );
+        callbacks.register(CallbackOptions::default(), |_, _| {});
+        assert!(!callbacks.unregister(&expired));
+    }
+}
@@ -0,0 +1,148 @@
+//! Crash reports written to the `crash-reports` directory.
+//!
+//! A report has a description and named sections, followed by details about the
+//! system, in the layout of vanilla crash reports. [`install_panic_hook`] writes
+//! one for every panic.
+
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs, io, panic, thread};
+
+use super::watchdog::WATCHDOG;
+
+/// The directory crash reports are written to.
+pub const CRASH_REPORT_DIRECTORY: &str = "crash-reports";
+
+/// A crash report.
+///
+/// Vanilla: `CrashReport`.
+pub struct CrashReport {
+    title: String,
+    sections: Vec<(String, String)>,
+}
+
+impl CrashReport {
+    /// Creates a report with a description of what happened.
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            sections: Vec::new(),
+        }
+    }
+
+    /// Adds a section.
+    #[must_use]
+    pub fn section(mut self, name: impl Into<String>, body: impl Into<String>) -> Self {
+        self.sections.push((name.into(), body.into()));
+        self
+    }
+
+    /// Formats the report.
+    ///
+    /// Vanilla: `CrashReport.getFriendlyReport()`.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("---- Steel Crash Report ----\n\n");
+        let _ = writeln!(text, "Time: {} (unix seconds)", unix_seconds());
+        let _ = writeln!(text, "Description: {}", self.title);
+        for (name, body) in &self.sections {
+            let _ = write!(text, "\n-- {name} --\n{}\n", body.trim_end());
+        }
+        let _ = write!(text, "\n-- System Details --\n{}\n", system_details());
+        text
+    }
+
+    /// Writes the report to a new file in `directory`.
+    ///
+    /// Vanilla: `CrashReport.saveToFile()`.
+    ///
+    /// # Errors
+    /// Returns an error if the directory or the file can't be written.
+    pub fn write_to(&self, directory: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(directory)?;
+        let path = directory.join(format!("crash-{}-server.txt", unix_seconds()));
+        fs::write(&path, self.to_text())?;
+        Ok(path)
+    }
+
+    /// Writes the report to the crash report directory and logs where it went.
+    pub fn save(&self) {
+        match self.write_to(Path::new(CRASH_REPORT_DIRECTORY)) {
+            Ok(path) => log::error!("Crash report saved to {}", path.display()),
+            Err(err) => log::error!("Failed to save crash report: {err}\n{}", self.to_text()),
+        }
+    }
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+fn system_details() -> String {
+    let threads = thread::available_parallelism().map_or(0, Into::into);
+    format!(
+        "Steel Version: {}\nOperating System: {} ({})\nAvailable Threads: {threads}",
+        env!("CARGO_PKG_VERSION"),
+        env::consts::OS,
+        env::consts::ARCH,
+    )
+}
+
+/// Writes a crash report for every panic, then runs the previous panic hook.
+///
+/// Panics in tick tasks abort the server in release builds, so the report is the
+/// only record of what the tick was doing.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let thread = thread::current();
+        let location = info
+            .location()
+            .map_or_else(|| "unknown".to_owned(), ToString::to_string);
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_owned());
+        CrashReport::new(format!(
+            "Panic in thread {}",
+            thread.name().unwrap_or("<unnamed>")
+        ))
+        .section(
+            "Panic",
+            format!(
+                "Message: {message}\nLocation: {location}\n\n{}",
+                Backtrace::force_capture()
+            ),
+        )
+        .section("Server Tick", WATCHDOG.tick_details())
+        .save();
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_sections_in_order() {
+        let text = CrashReport::new("Testing")
+            .section("First", "one\n")
+            .section("Second", "two")
+            .to_text();
+
+        assert!(text.starts_with("---- Steel Crash Report ----"));
+        assert!(text.contains("Description: Testing\n"));
+        let first = text.find("-- First --\none\n").expect("first section");
+        let second = text.find("-- Second --\ntwo\n").expect("second section");
+        let system = text.find("-- System Details --").expect("system details");
+        assert!(first < second && second < system);
+    }
+}
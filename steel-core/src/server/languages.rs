@@ -0,0 +1,235 @@
+//! Translation of messages into the languages of players and the console.
+//!
+//! Messages are normally sent as translatable components that the client
+//! translates into its own language. That doesn't work for keys the client
+//! doesn't know, such as ones added by plugins, nor for the console. Those are
+//! translated here, as are all messages to players if the server is set up to
+//! translate them (see [`TranslationMode`]).
+//!
+//! `en_us` is bundled with the server. More languages are loaded from
+//! `<language>.json` files in the configured directory, in vanilla's format. A
+//! file for `en_us` adds to and overrides the bundled translations.
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use rustc_hash::FxHashMap;
+use steel_utils::locks::SyncRwLock;
+use steel_utils::text::translation::{resolve_translations, resolve_translations_except};
+use steel_utils::translations_registry::TRANSLATIONS;
+use text_components::TextComponent;
+
+use crate::config::{STEEL_CONFIG, TranslationMode};
+
+/// The language used for keys missing from a player's language, like vanilla.
+pub const DEFAULT_LANGUAGE: &str = "en_us";
+
+/// Translations by language, loaded from language files.
+#[derive(Default)]
+pub struct Languages {
+    languages: SyncRwLock<FxHashMap<String, FxHashMap<String, String>>>,
+}
+
+/// The global languages.
+pub static LANGUAGES: LazyLock<Languages> = LazyLock::new(Languages::new);
+
+impl Languages {
+    /// Creates languages with only the bundled `en_us` translations.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the language files from the directory in the server config.
+    pub fn init_from_config(&self) {
+        let directory = Path::new(&STEEL_CONFIG.translation.directory);
+        match self.load(directory) {
+            Ok(0) => {}
+            Ok(count) => log::info!("Loaded {count} language files"),
+            Err(err) => log::warn!(
+                "Failed to load the language files in {}: {err}",
+                directory.display()
+            ),
+        }
+    }
+
+    /// Replaces the loaded languages with the `.json` files in `directory`.
+    /// Returns how many were loaded.
+    ///
+    /// A missing directory loads no languages. Files that can't be parsed are
+    /// skipped with a warning.
+    ///
+    /// # Errors
+    /// Returns an error if the directory can't be read.
+    pub fn load(&self, directory: &Path) -> io::Result<usize> {
+        let mut languages = FxHashMap::default();
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.languages.write().clear();
+                return Ok(0);
+            }
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match read_language_file(&path) {
+                Ok(translations) => {
+                    languages.insert(language.to_lowercase(), translations);
+                }
+                Err(err) => log::warn!("Skipping language file {}: {err}", path.display()),
+            }
+        }
+        let count = languages.len();
+        *self.languages.write() = languages;
+        Ok(count)
+    }
+
+    /// Adds translations to `language`, replacing ones with the same key.
+    ///
+    /// Lets plugins bring translations for their own keys.
+    pub fn add_translations(
+        &self,
+        language: &str,
+        translations: impl IntoIterator<Item = (String, String)>,
+    ) {
+        self.languages
+            .write()
+            .entry(language.to_lowercase())
+            .or_default()
+            .extend(translations);
+    }
+
+    /// Returns whether translations for `language` were loaded. `en_us` is
+    /// always available.
+    #[must_use]
+    pub fn has_language(&self, language: &str) -> bool {
+        language == DEFAULT_LANGUAGE || self.languages.read().contains_key(language)
+    }
+
+    /// Translates `key` into `language`, falling back to `en_us`.
+    #[must_use]
+    pub fn translate(&self, language: &str, key: &str) -> Option<String> {
+        let languages = self.languages.read();
+        lookup(&languages, language, key).map(ToOwned::to_owned)
+    }
+
+    /// Translates the translatable components in `component` into `language`.
+    ///
+    /// With [`TranslationMode::Client`], keys the client knows stay
+    /// translatable, so it translates them itself.
+    #[must_use]
+    pub fn localize(
+        &self,
+        component: &TextComponent,
+        language: &str,
+        mode: TranslationMode,
+    ) -> TextComponent {
+        let languages = self.languages.read();
+        let translate = |key: &str| lookup(&languages, language, key);
+        match mode {
+            TranslationMode::Client => resolve_translations_except(component, &translate, &|key| {
+                TRANSLATIONS.contains_key(key)
+            }),
+            TranslationMode::Server => resolve_translations(component, &translate),
+        }
+    }
+
+    /// Translates `component` for the console, into the language from the
+    /// server config.
+    #[must_use]
+    pub fn localize_for_console(&self, component: &TextComponent) -> TextComponent {
+        self.localize(
+            component,
+            &STEEL_CONFIG.translation.console_language,
+            TranslationMode::Server,
+        )
+    }
+}
+
+/// Reads a language file: a JSON object of translations by key.
+fn read_language_file(path: &Path) -> Result<FxHashMap<String, String>, String> {
+    let json = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&json).map_err(|err| err.to_string())
+}
+
+/// Looks `key` up in `language`, then in the `en_us` file and the bundled
+/// `en_us` translations.
+///
+/// Vanilla: `ClientLanguage.loadFrom()`, which layers the selected language on
+/// top of `en_us`.
+fn lookup<'a>(
+    languages: &'a FxHashMap<String, FxHashMap<String, String>>,
+    language: &str,
+    key: &str,
+) -> Option<&'a str> {
+    languages
+        .get(language)
+        .and_then(|translations| translations.get(key))
+        .or_else(|| {
+            languages
+                .get(DEFAULT_LANGUAGE)
+                .and_then(|translations| translations.get(key))
+        })
+        .map(String::as_str)
+        .or_else(|| TRANSLATIONS.get(key).copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn languages() -> Languages {
+        let languages = Languages::new();
+        languages.add_translations(
+            "de_de",
+            [
+                ("block.minecraft.stone".to_owned(), "Stein".to_owned()),
+                ("example.greeting".to_owned(), "Hallo %s".to_owned()),
+            ],
+        );
+        languages.add_translations(
+            "en_us",
+            [("example.greeting".to_owned(), "Hello %s".to_owned())],
+        );
+        languages
+    }
+
+    #[test]
+    fn translations_fall_back_to_en_us() {
+        let languages = languages();
+        assert_eq!(
+            languages
+                .translate("de_de", "block.minecraft.stone")
+                .as_deref(),
+            Some("Stein")
+        );
+        assert_eq!(
+            languages
+                .translate("de_de", "block.minecraft.dirt")
+                .as_deref(),
+            Some("Dirt")
+        );
+        assert_eq!(
+            languages.translate("fr_fr", "example.greeting").as_deref(),
+            Some("Hello %s")
+        );
+        assert_eq!(languages.translate("de_de", "example.missing"), None);
+    }
+
+    #[test]
+    fn languages_are_case_insensitive_when_added() {
+        let languages = languages();
+        languages.add_translations("PT_BR", [("example.a".to_owned(), "a".to_owned())]);
+        assert!(languages.has_language("pt_br"));
+        assert!(languages.has_language(DEFAULT_LANGUAGE));
+        assert!(!languages.has_language("fr_fr"));
+    }
+}
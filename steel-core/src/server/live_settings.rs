@@ -0,0 +1,204 @@
+//! Server list settings that can change while the server is running.
+//!
+//! The values start out from `STEEL_CONFIG` and are replaced when the config is
+//! reloaded (see `Server::reload_config`).
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde_json::Value;
+use steel_utils::locks::SyncRwLock;
+
+use crate::config::{STEEL_CONFIG, ServerConfig};
+
+/// The prefix of the favicon in the status response.
+const ICON_PREFIX: &str = "data:image/png;base64,";
+
+/// The size of the favicon, in pixels.
+///
+/// Vanilla: `ServerStatusPinger.FAVICON_SIZE`.
+const FAVICON_SIZE: u32 = 64;
+
+/// The message of the day, the player limit and the favicon.
+pub struct LiveSettings {
+    motd: SyncRwLock<String>,
+    max_players: AtomicU32,
+    favicon: SyncRwLock<Option<String>>,
+}
+
+/// The global live settings.
+pub static LIVE_SETTINGS: LiveSettings = LiveSettings {
+    motd: SyncRwLock::new(String::new()),
+    max_players: AtomicU32::new(20),
+    favicon: SyncRwLock::new(None),
+};
+
+impl LiveSettings {
+    /// Resets the settings to the values from the server config.
+    pub fn init_from_config(&self) {
+        self.set_motd(STEEL_CONFIG.motd.clone());
+        self.set_max_players(STEEL_CONFIG.max_players);
+        self.load_favicon(&STEEL_CONFIG);
+    }
+
+    /// Returns the message of the day shown in the server list, as text with `§`
+    /// formatting codes or as JSON text.
+    #[must_use]
+    pub fn motd(&self) -> String {
+        self.motd.read().clone()
+    }
+
+    /// Sets the message of the day.
+    pub fn set_motd(&self, motd: String) {
+        *self.motd.write() = motd;
+    }
+
+    /// Returns the maximum number of players shown in the server list.
+    #[must_use]
+    pub fn max_players(&self) -> u32 {
+        self.max_players.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of players.
+    pub fn set_max_players(&self, max_players: u32) {
+        self.max_players.store(max_players, Ordering::Relaxed);
+    }
+
+    /// Returns the favicon as a base64 PNG data URL.
+    #[must_use]
+    pub fn favicon(&self) -> Option<String> {
+        self.favicon.read().clone()
+    }
+
+    /// Loads the favicon the config points to. Returns whether it changed.
+    pub fn load_favicon(&self, config: &ServerConfig) -> bool {
+        let favicon = if config.use_favicon {
+            encode_favicon(Path::new(&config.favicon))
+        } else {
+            None
+        };
+        let mut current = self.favicon.write();
+        let changed = *current != favicon;
+        *current = favicon;
+        changed
+    }
+}
+
+/// Reads a favicon and encodes it as a data URL. Returns `None` if it can't be
+/// read or isn't a 64x64 PNG.
+///
+/// Vanilla: `MinecraftServer.loadStatusIcon()`.
+fn encode_favicon(path: &Path) -> Option<String> {
+    let icon = match fs::read(path) {
+        Ok(icon) => icon,
+        Err(err) => {
+            log::warn!("Failed to read the favicon {}: {err}", path.display());
+            return None;
+        }
+    };
+    if png_size(&icon) != Some((FAVICON_SIZE, FAVICON_SIZE)) {
+        log::warn!(
+            "The favicon {} must be a {FAVICON_SIZE}x{FAVICON_SIZE} PNG",
+            path.display()
+        );
+        return None;
+    }
+    let mut base64 = String::with_capacity(ICON_PREFIX.len() + icon.len().div_ceil(3) * 4);
+    base64 += ICON_PREFIX;
+    BASE64_STANDARD.encode_string(icon, &mut base64);
+    Some(base64)
+}
+
+/// Reads the width and height from the header of a PNG image.
+fn png_size(image: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    // The signature is followed by the IHDR chunk's length and type, then the size
+    let header = image.strip_prefix(SIGNATURE)?.get(4..16)?;
+    if &header[..4] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(header[4..8].try_into().ok()?);
+    let height = u32::from_be_bytes(header[8..12].try_into().ok()?);
+    Some((width, height))
+}
+
+/// Turns a message of the day into the JSON text of the status response.
+///
+/// A MOTD that is a JSON object or array is sent as is; anything else is sent as
+/// a string, which clients show with its `§` formatting codes and line breaks.
+#[must_use]
+pub fn motd_json(motd: &str) -> Value {
+    let trimmed = motd.trim_start();
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && let Ok(json) = serde_json::from_str::<Value>(motd)
+    {
+        return json;
+    }
+    Value::String(motd.to_owned())
+}
+
+/// Turns a message of the day into plain text with `§` formatting codes, for
+/// clients that can't read JSON text. Only the text of JSON text is kept.
+#[must_use]
+pub fn motd_plain(motd: &str) -> String {
+    fn collect(json: &Value, text: &mut String) {
+        match json {
+            Value::String(string) => text.push_str(string),
+            Value::Array(parts) => parts.iter().for_each(|part| collect(part, text)),
+            Value::Object(component) => {
+                if let Some(Value::String(string)) = component.get("text") {
+                    text.push_str(string);
+                }
+                if let Some(extra) = component.get("extra") {
+                    collect(extra, text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut text = String::new();
+    collect(&motd_json(motd), &mut text);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn motd_json_keeps_text_and_parses_components() {
+        assert_eq!(
+            motd_json("§aA Steel Server\n§7Line two"),
+            Value::String("§aA Steel Server\n§7Line two".to_owned())
+        );
+        assert_eq!(
+            motd_json(r#"{"text":"Steel","color":"gray"}"#)["color"],
+            Value::String("gray".to_owned())
+        );
+        assert_eq!(
+            motd_json("{not json"),
+            Value::String("{not json".to_owned())
+        );
+    }
+
+    #[test]
+    fn motd_plain_keeps_only_text() {
+        assert_eq!(motd_plain("§aSteel"), "§aSteel");
+        assert_eq!(
+            motd_plain(r#"{"text":"Steel","extra":[" Server",{"text":"!"}]}"#),
+            "Steel Server!"
+        );
+    }
+
+    #[test]
+    fn png_size_reads_the_header() {
+        let mut image = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        image.extend_from_slice(&64u32.to_be_bytes());
+        image.extend_from_slice(&32u32.to_be_bytes());
+        assert_eq!(png_size(&image), Some((64, 32)));
+        assert_eq!(png_size(b"GIF89a"), None);
+    }
+}
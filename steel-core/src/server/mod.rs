@@ -1,8 +1,28 @@
 //! This module contains the `Server` struct, which is the main entry point for the server.
+/// Boss bars shown to a set of players.
+pub mod boss_event;
+/// Runtime view and simulation distance settings.
+pub mod chunk_distances;
+/// Server-side callbacks for clickable text.
+pub mod click_callbacks;
+/// Crash reports.
+pub mod crash_report;
+/// Translation of messages into the languages of players and the console.
+pub mod languages;
+/// Server list settings that can change at runtime.
+pub mod live_settings;
+/// Custom payload channels for plugin messages.
+pub mod payload_channels;
 /// The registry cache for the server.
 pub mod registry_cache;
+/// Tasks run on the server tick after a delay.
+pub mod scheduler;
+/// The in-game tick profiler.
+pub mod tick_profiler;
 /// The tick rate manager for the server.
 pub mod tick_rate_manager;
+/// Detection of stalled ticks.
+pub mod watchdog;
 
 use crate::behavior::init_behaviors;
 use crate::block_entity::init_block_entities;
@@ -13,28 +33,49 @@ use crate::chunk::world_gen_context::ChunkGeneratorType;
 use crate::command::CommandDispatcher;
 use crate::config::{STEEL_CONFIG, WorldGeneratorTypes, WorldStorageConfig};
 use crate::entity::init_entities;
+use crate::event::{EVENTS, PlayerJoinEvent};
+use crate::level_data::SpawnPoint;
+use crate::map::MapStorage;
 use crate::player::Player;
+use crate::player::message_chain::PlayerChatMessage;
 use crate::player::player_data_storage::PlayerDataStorage;
+use crate::player::profile_cache::{PROFILE_CACHE_FILE, ProfileCache};
+use crate::plugin::PluginManager;
+use crate::server::chunk_distances::{CHUNK_DISTANCES, DistanceError};
+use crate::server::click_callbacks::ClickCallbacks;
+use crate::server::languages::LANGUAGES;
+use crate::server::live_settings::LIVE_SETTINGS;
+use crate::server::payload_channels::{PayloadChannels, REGISTER_CHANNEL, encode_channel_list};
 use crate::server::registry_cache::RegistryCache;
+use crate::server::scheduler::Scheduler;
+use crate::server::tick_profiler::TICK_PROFILER;
+use crate::server::watchdog::{TickPhase, WATCHDOG};
 use crate::world::{World, WorldConfig, WorldTickTimings};
-use crate::worldgen::BiomeSourceKind;
+use crate::worldgen::{BiomeSourceKind, STRUCTURE_TEMPLATES, StructureTemplateManager};
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use small_map::FxSmallMap;
 use std::{
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
+use steel_crypto::VerificationPool;
 use steel_crypto::key_store::KeyStore;
+use steel_protocol::packet_traits::{ClientPacket, EncodedPacket};
 use steel_protocol::packets::game::{
-    CEntityEvent, CGameEvent, CLogin, CSetHeldSlot, CSystemChat, CTabList, CTickingState,
-    CTickingStep, CommonPlayerSpawnInfo, GameEventType,
+    CEntityEvent, CGameEvent, CLogin, CSetChunkCacheRadius, CSetDefaultSpawnPosition, CSetHeldSlot,
+    CSystemChat, CTabList, CTickingState, CTickingStep, ChatTypeBound, CommonPlayerSpawnInfo,
+    FilterType, GameEventType,
 };
+use steel_protocol::utils::ConnectionProtocol;
 use steel_registry::dimension_type::DimensionTypeRef;
 use steel_registry::game_rules::GameRuleValue;
 use steel_registry::vanilla_dimension_types::{OVERWORLD, THE_END, THE_NETHER};
 use steel_registry::vanilla_game_rules::{IMMEDIATE_RESPAWN, LIMITED_CRAFTING, REDUCED_DEBUG_INFO};
 use steel_registry::{REGISTRY, Registry, RegistryEntry, RegistryExt, vanilla_blocks};
-use steel_utils::{Identifier, entity_events::EntityStatus, locks::SyncRwLock};
+use steel_utils::{
+    BlockPos, Identifier, entity_events::EntityStatus, locks::SyncRwLock, translations,
+};
 use text_components::{Modifier, TextComponent, format::Color};
 use tick_rate_manager::{SprintReport, TickRateManager};
 use tokio::{runtime::Runtime, task::spawn_blocking, time::sleep};
@@ -49,6 +90,8 @@ pub struct Server {
     pub cancel_token: CancellationToken,
     /// The key store for the server.
     pub key_store: KeyStore,
+    /// Runs chat signature verification off the network tasks.
+    pub verification_pool: VerificationPool,
     /// The registry cache for the server.
     pub registry_cache: RegistryCache,
     /// A list of all the worlds on the server.
@@ -59,6 +102,18 @@ pub struct Server {
     pub command_dispatcher: SyncRwLock<CommandDispatcher>,
     /// Player data storage for saving/loading player state.
     pub player_data_storage: PlayerDataStorage,
+    /// The names and UUIDs of recently seen players.
+    pub profile_cache: ProfileCache,
+    /// The data of every filled map.
+    pub maps: MapStorage,
+    /// Callbacks run when players click text with a `custom` click event.
+    pub click_callbacks: ClickCallbacks,
+    /// Handlers of custom payload channels.
+    pub payload_channels: PayloadChannels,
+    /// Tasks run on the server tick.
+    pub scheduler: Scheduler,
+    /// The enabled plugins.
+    pub plugins: PluginManager,
 }
 
 impl Server {
@@ -83,7 +138,21 @@ impl Server {
         init_entities();
         log::info!("Behavior registries initialized");
 
+        let generated_dir = match &STEEL_CONFIG.world_storage_config {
+            WorldStorageConfig::Disk { path } => Some(Path::new(path).join("generated")),
+            WorldStorageConfig::RamOnly => None,
+        };
+        if STRUCTURE_TEMPLATES
+            .set(StructureTemplateManager::new(generated_dir))
+            .is_err()
+        {
+            log::warn!("Structure template manager was already initialized");
+        }
+
         let registry_cache = RegistryCache::new();
+        CHUNK_DISTANCES.init_from_config();
+        LIVE_SETTINGS.init_from_config();
+        LANGUAGES.init_from_config();
 
         let seed: i64 = if STEEL_CONFIG.seed.is_empty() {
             rand::random()
@@ -138,7 +207,7 @@ impl Server {
         .await
         .expect("Failed to create end");
 
-        let player_data_storage = PlayerDataStorage::new()
+        let player_data_storage = PlayerDataStorage::new(&STEEL_CONFIG.player_data_storage)
             .await
             .expect("Failed to create player data storage");
         let mut worlds: FxSmallMap<8, Identifier, Arc<World>> = FxSmallMap::default();
@@ -148,12 +217,35 @@ impl Server {
 
         Server {
             cancel_token,
-            key_store: KeyStore::create(),
+            key_store: Self::create_key_store(),
+            verification_pool: VerificationPool::new(
+                STEEL_CONFIG.crypto.max_concurrent_verifications,
+            ),
             worlds,
             registry_cache,
             tick_rate_manager: SyncRwLock::new(TickRateManager::new()),
             command_dispatcher: SyncRwLock::new(CommandDispatcher::new()),
             player_data_storage,
+            profile_cache: ProfileCache::load(Path::new(PROFILE_CACHE_FILE)),
+            maps: MapStorage::new(match &STEEL_CONFIG.world_storage_config {
+                WorldStorageConfig::Disk { path } => Some(Path::new(path).join("data")),
+                WorldStorageConfig::RamOnly => None,
+            }),
+            click_callbacks: ClickCallbacks::new(),
+            payload_channels: PayloadChannels::new(),
+            scheduler: Scheduler::new(),
+            plugins: PluginManager::new(),
+        }
+    }
+
+    /// Creates the key store, reusing the cached key pair if caching is enabled.
+    fn create_key_store() -> KeyStore {
+        match &STEEL_CONFIG.crypto.key_cache_path {
+            Some(path) => KeyStore::load_or_create(
+                Path::new(path),
+                Duration::from_secs(STEEL_CONFIG.crypto.key_cache_max_age),
+            ),
+            None => KeyStore::create(),
         }
     }
 
@@ -200,9 +292,9 @@ impl Server {
             player_id: player.id,
             hardcore: false,
             levels: REGISTRY.dimension_types.get_ids(),
-            max_players: STEEL_CONFIG.max_players as i32,
+            max_players: LIVE_SETTINGS.max_players() as i32,
             chunk_radius: player.view_distance().into(),
-            simulation_distance: STEEL_CONFIG.simulation_distance.into(),
+            simulation_distance: world.simulation_distance().into(),
             reduced_debug_info,
             show_death_screen: !immediate_respawn,
             do_limited_crafting,
@@ -232,6 +324,8 @@ impl Server {
             slot: i32::from(player.inventory.lock().get_selected_slot()),
         });
 
+        player.send_packet(Self::default_spawn_packet(&self.respawn_data()));
+
         if world.can_have_weather() {
             let (rain_level, thunder_level) = {
                 let weather = world.weather.lock();
@@ -259,6 +353,12 @@ impl Server {
         let commands = self.command_dispatcher.read().get_commands();
         player.send_packet(commands);
 
+        // Let mods and proxies know which channels the server listens on
+        let channels = self.payload_channels.channels();
+        if !channels.is_empty() {
+            player.send_custom_payload(REGISTER_CHANNEL, encode_channel_list(&channels));
+        }
+
         // TODO: Set permissions level to match player's level
         player.send_packet(CEntityEvent {
             entity_id: player.id,
@@ -279,6 +379,7 @@ impl Server {
         player.reset_sent_info();
 
         world.add_player(player.clone());
+        EVENTS.post(&mut PlayerJoinEvent { player });
     }
 
     /// Gets all the players on the server
@@ -293,23 +394,45 @@ impl Server {
         players
     }
 
+    /// Resends the command tree to every player, after commands were registered
+    /// or unregistered.
+    ///
+    /// Vanilla: `Commands.sendCommands()`.
+    pub fn resend_commands(&self) {
+        let commands = self.command_dispatcher.read().get_commands();
+        self.broadcast_to_all(commands);
+    }
+
+    /// Broadcasts a packet to every player in every world.
+    ///
+    /// The packet is encoded once and the bytes are shared between all players.
+    /// If encoding fails, the broadcast is silently skipped.
+    pub fn broadcast_to_all<P: ClientPacket>(&self, packet: P) {
+        let Ok(encoded) =
+            EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
+        else {
+            return;
+        };
+        for world in self.worlds.values() {
+            world.broadcast_to_all_encoded(encoded.clone());
+        }
+    }
+
     /// Returns the total number of players currently online across all worlds.
     #[must_use]
     pub fn player_count(&self) -> usize {
         self.worlds.iter().map(|w| w.1.players.len()).sum()
     }
 
-    /// Returns a sample of up to 12 online players for the server list ping.
+    /// Returns a sample of up to `max` online players for the server list ping.
     #[must_use]
-    pub fn player_sample(&self) -> Vec<(String, String)> {
-        const MAX_SAMPLE: usize = 12;
-
+    pub fn player_sample(&self, max: usize) -> Vec<(String, String)> {
         let players = self.get_players();
-        if players.is_empty() {
+        if players.is_empty() || max == 0 {
             return vec![];
         }
 
-        let sample_size = players.len().min(MAX_SAMPLE);
+        let sample_size = players.len().min(max);
         // Random starting offset into the player list
         let offset = if players.len() > sample_size {
             (rand::random::<u64>() as usize) % (players.len() - sample_size + 1)
@@ -358,6 +481,32 @@ impl Server {
         self.worlds.get(&THE_END.key)
     }
 
+    /// Returns the world spawn point, which is stored in the overworld's level data.
+    #[must_use]
+    pub fn respawn_data(&self) -> SpawnPoint {
+        self.overworld().level_data.read().data().spawn.clone()
+    }
+
+    /// Sets the world spawn point and tells every player so their compasses update.
+    ///
+    /// Vanilla equivalent: `MinecraftServer.setRespawnData()`.
+    pub fn set_respawn_data(&self, spawn: SpawnPoint) {
+        let packet = Self::default_spawn_packet(&spawn);
+        self.overworld().level_data.write().data_mut().spawn = spawn;
+        self.broadcast_to_all(packet);
+    }
+
+    /// Builds the packet that tells a client where the world spawn is.
+    #[must_use]
+    pub fn default_spawn_packet(spawn: &SpawnPoint) -> CSetDefaultSpawnPosition {
+        CSetDefaultSpawnPosition {
+            dimension: spawn.dimension.clone(),
+            pos: BlockPos::new(spawn.x, spawn.y, spawn.z),
+            yaw: spawn.angle,
+            pitch: spawn.pitch,
+        }
+    }
+
     /// Runs the server tick loop.
     pub async fn run(self: Arc<Self>, cancel_token: CancellationToken) {
         let mut next_tick_time = Instant::now();
@@ -415,14 +564,26 @@ impl Server {
                 }
                 (tick_manager.tick_count, runs_normally)
             };
+            WATCHDOG.begin_tick(tick_count);
 
             // Always tick worlds (for chunk loading/gen), but pass runs_normally
             // so game elements like random ticks only run when not frozen
             self.tick_worlds(tick_count, runs_normally).await;
 
+            WATCHDOG.set_phase(TickPhase::Scheduler);
+            for plugin in self.scheduler.tick(&self) {
+                log::error!("A task of plugin {plugin} failed, disabling it");
+                self.plugins.disable(plugin);
+            }
+
             // Record tick duration for TPS/MSPT tracking
+            WATCHDOG.set_phase(TickPhase::Bookkeeping);
             let (tps, mspt) = {
-                let tick_duration_nanos = tick_start.elapsed().as_nanos() as u64;
+                let tick_duration = tick_start.elapsed();
+                if TICK_PROFILER.is_running() {
+                    TICK_PROFILER.record_tick(tick_count, tick_duration);
+                }
+                let tick_duration_nanos = tick_duration.as_nanos() as u64;
                 let mut tick_manager = self.tick_rate_manager.write();
                 tick_manager.record_tick_time(tick_duration_nanos);
                 (tick_manager.get_tps(), tick_manager.get_average_mspt())
@@ -437,6 +598,7 @@ impl Server {
                 let mut tick_manager = self.tick_rate_manager.write();
                 tick_manager.end_tick_work();
             }
+            WATCHDOG.end_tick();
         }
     }
 
@@ -445,24 +607,32 @@ impl Server {
         let mut tasks = Vec::with_capacity(self.worlds.len());
         for world in self.worlds.values() {
             let world_clone = world.clone();
-            tasks.push(spawn_blocking(move || {
-                world_clone.tick_b(tick_count, runs_normally)
-            }));
+            tasks.push((
+                world,
+                spawn_blocking(move || world_clone.tick_b(tick_count, runs_normally)),
+            ));
         }
         let start = Instant::now();
-        let mut all_timings: Vec<WorldTickTimings> = Vec::with_capacity(tasks.len());
-        for task in tasks {
-            if let Ok(timings) = task.await {
-                all_timings.push(timings);
+        let mut all_timings: Vec<(Identifier, WorldTickTimings)> = Vec::with_capacity(tasks.len());
+        for (world, task) in tasks {
+            match task.await {
+                Ok(timings) => {
+                    if TICK_PROFILER.is_running() {
+                        TICK_PROFILER.record_world(&world.dimension.key, &timings);
+                    }
+                    all_timings.push((world.dimension.key.clone(), timings));
+                }
+                // The panic hook already wrote a crash report
+                Err(err) => log::error!("Ticking world {} failed: {err}", world.dimension.key),
             }
         }
         let elapsed = start.elapsed();
         if elapsed.as_millis() >= 30 {
             // Log detailed breakdown when tick is slow
-            for (i, timings) in all_timings.iter().enumerate() {
+            for (world, timings) in &all_timings {
                 let cm = &timings.chunk_map;
                 tracing::warn!(
-                    world = i,
+                    %world,
                     ?elapsed,
                     tick_count,
                     player_tick = ?timings.player_tick,
@@ -473,6 +643,7 @@ impl Server {
                     run_generation = ?cm.run_generation,
                     broadcast_changes = ?cm.broadcast_changes,
                     process_unloads = ?cm.process_unloads,
+                    autosave = ?cm.autosave,
                     collect_tickable = ?cm.collect_tickable,
                     tick_chunks = ?cm.tick_chunks,
                     tickable_count = cm.tickable_count,
@@ -481,6 +652,7 @@ impl Server {
                 );
             }
         }
+        WATCHDOG.record_timings(all_timings);
     }
 
     /// Broadcasts the tab list header/footer with current TPS and MSPT values.
@@ -521,9 +693,7 @@ impl Server {
     }
 
     /// Broadcasts a sprint completion report to all players.
-    fn broadcast_sprint_report(&self, report: &SprintReport) {
-        use steel_utils::translations;
-
+    pub fn broadcast_sprint_report(&self, report: &SprintReport) {
         let message: TextComponent = translations::COMMANDS_TICK_SPRINT_REPORT
             .message([
                 TextComponent::from(format!("{}", report.ticks_per_second)),
@@ -531,8 +701,151 @@ impl Server {
             ])
             .into();
 
+        self.broadcast_system_message(&message);
+    }
+
+    /// Sends a system message to every player on the server.
+    ///
+    /// Vanilla equivalent: `PlayerList.broadcastSystemMessage()`.
+    pub fn broadcast_system_message(&self, message: &TextComponent) {
+        log::info!("{:p}", LANGUAGES.localize_for_console(message));
+        for world in self.worlds.values() {
+            world.broadcast_to_all_with(|player| {
+                CSystemChat::new(&player.localize(message), false, player)
+            });
+        }
+    }
+
+    /// Changes the server view distance cap at runtime.
+    ///
+    /// Players whose effective view distance changes are sent a new chunk cache radius;
+    /// their loaded chunks are adjusted on the next tick. If a simulation distance
+    /// exceeds the new view distance it is lowered and broadcast as well.
+    ///
+    /// # Errors
+    /// - If `distance` is not a valid view distance.
+    pub fn set_view_distance(&self, distance: u8) -> Result<(), DistanceError> {
+        let players = self.get_players();
+        let old_distances: Vec<u8> = players.iter().map(|p| p.view_distance()).collect();
+        let old_simulation_distances = self.simulation_distances();
+
+        CHUNK_DISTANCES.set_view_distance(distance)?;
+
+        for (player, old_distance) in players.iter().zip(old_distances) {
+            let new_distance = player.view_distance();
+            if new_distance != old_distance {
+                player.send_packet(CSetChunkCacheRadius {
+                    radius: i32::from(new_distance),
+                });
+            }
+        }
+        self.broadcast_changed_simulation_distances(&old_simulation_distances);
+        Ok(())
+    }
+
+    /// Changes the server simulation distance at runtime and notifies the clients
+    /// in worlds without a simulation distance of their own.
+    ///
+    /// # Errors
+    /// - If `distance` is larger than the current view distance.
+    pub fn set_simulation_distance(&self, distance: u8) -> Result<(), DistanceError> {
+        let old_simulation_distances = self.simulation_distances();
+        CHUNK_DISTANCES.set_simulation_distance(distance)?;
+        self.broadcast_changed_simulation_distances(&old_simulation_distances);
+        Ok(())
+    }
+
+    /// Loads the config from disk again and applies the settings that can change
+    /// while the server runs: the MOTD, the max player count, the favicon and the
+    /// view and simulation distances, including those of single worlds. The other
+    /// settings need a restart. Returns the names
+    /// of the settings that changed.
+    ///
+    /// # Errors
+    /// Returns a description of why the config couldn't be loaded. Nothing is
+    /// applied then.
+    pub fn reload_config(&self) -> Result<Vec<&'static str>, String> {
+        let config = STEEL_CONFIG.reload()?;
+        let mut changed = Vec::new();
+        if LIVE_SETTINGS.motd() != config.motd {
+            LIVE_SETTINGS.set_motd(config.motd);
+            changed.push("motd");
+        }
+        if LIVE_SETTINGS.max_players() != config.max_players {
+            LIVE_SETTINGS.set_max_players(config.max_players);
+            changed.push("max_players");
+        }
+        if LIVE_SETTINGS.load_favicon(&config) {
+            changed.push("favicon");
+        }
+        // Validated when loading, so neither change can be rejected
+        if CHUNK_DISTANCES.view_distance() != config.view_distance
+            && self.set_view_distance(config.view_distance).is_ok()
+        {
+            changed.push("view_distance");
+        }
+        if CHUNK_DISTANCES.simulation_distance() != config.simulation_distance
+            && self
+                .set_simulation_distance(config.simulation_distance)
+                .is_ok()
+        {
+            changed.push("simulation_distance");
+        }
+        let mut worlds_changed = false;
+        for world in self.worlds.values() {
+            let distance = config
+                .worlds
+                .get(world.dimension.key.path.as_ref())
+                .and_then(|settings| settings.simulation_distance);
+            if world.own_simulation_distance() != distance
+                && world.set_simulation_distance(distance).is_ok()
+            {
+                worlds_changed = true;
+            }
+        }
+        if worlds_changed {
+            changed.push("worlds");
+        }
+        Ok(changed)
+    }
+
+    /// Returns the simulation distance of every world.
+    fn simulation_distances(&self) -> Vec<u8> {
+        self.worlds
+            .values()
+            .map(|world| world.simulation_distance())
+            .collect()
+    }
+
+    /// Tells the players of every world whose simulation distance differs from
+    /// `old_distances` about the new one.
+    fn broadcast_changed_simulation_distances(&self, old_distances: &[u8]) {
+        for (world, &old_distance) in self.worlds.values().zip(old_distances) {
+            if world.simulation_distance() != old_distance {
+                world.broadcast_simulation_distance();
+            }
+        }
+    }
+
+    /// Sends a player's chat message to every player. Tells the sender if the
+    /// chat filter hid the message from anyone entirely.
+    ///
+    /// Vanilla: `PlayerList.broadcastChatMessage()`.
+    pub fn broadcast_chat_message(
+        &self,
+        message: &PlayerChatMessage,
+        sender: &Player,
+        unsigned_content: Option<&TextComponent>,
+        filter_mask: &FilterType,
+        chat_type: &ChatTypeBound,
+    ) {
+        let mut fully_filtered = false;
         for world in self.worlds.values() {
-            world.broadcast_to_all_with(|player| CSystemChat::new(&message, false, player));
+            fully_filtered |=
+                world.broadcast_chat(message, sender, unsigned_content, filter_mask, chat_type);
+        }
+        if fully_filtered {
+            sender.send_message(&translations::CHAT_FILTERED_FULL.msg().into());
         }
     }
 
@@ -543,9 +856,7 @@ impl Server {
         let packet = CTickingState::new(tick_manager.tick_rate(), tick_manager.is_frozen());
         drop(tick_manager);
 
-        for world in self.worlds.values() {
-            world.broadcast_to_all(packet.clone());
-        }
+        self.broadcast_to_all(packet);
     }
 
     /// Broadcasts the current step tick count to all clients.
@@ -555,9 +866,7 @@ impl Server {
         let packet = CTickingStep::new(tick_manager.frozen_ticks_to_run());
         drop(tick_manager);
 
-        for world in self.worlds.values() {
-            world.broadcast_to_all(packet.clone());
-        }
+        self.broadcast_to_all(packet);
     }
 
     /// Sends the current ticking state and step packets to a joining player.
@@ -637,6 +946,10 @@ impl Server {
                 WorldStorageConfig::RamOnly => WorldStorageConfig::RamOnly,
             },
             generator: Arc::new(Self::make_generator_for_dimension(dimension, seed)),
+            simulation_distance: STEEL_CONFIG
+                .worlds
+                .get(dimension.key.path.as_ref())
+                .and_then(|settings| settings.simulation_distance),
         }
     }
 }
@@ -0,0 +1,185 @@
+//! Custom payload channels, also known as plugin messages.
+//!
+//! Clients, mods and proxies exchange data with the server in custom payload
+//! packets tagged with a channel id. Handlers registered here get the payloads
+//! players send on their channel, and [`Player::send_custom_payload`] sends
+//! payloads the other way.
+//!
+//! The server handles a few channels itself:
+//! - `minecraft:brand`, the name of the client or server software.
+//! - `minecraft:register` and `minecraft:unregister`, listing the channels the
+//!   other side listens on. These aren't used by vanilla, but mod loaders and
+//!   proxies use them to decide which payloads to forward. Joining players are
+//!   told about every channel registered here.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::locks::SyncRwLock;
+use steel_utils::serial::{PrefixedRead, PrefixedWrite};
+
+use crate::player::Player;
+
+/// The channel the client and server send their brand on.
+pub const BRAND_CHANNEL: Identifier = Identifier::vanilla_static("brand");
+
+/// The channel listing channels the sender starts listening on.
+pub const REGISTER_CHANNEL: Identifier = Identifier::vanilla_static("register");
+
+/// The channel listing channels the sender stops listening on.
+pub const UNREGISTER_CHANNEL: Identifier = Identifier::vanilla_static("unregister");
+
+/// The brand this server reports to clients.
+pub const SERVER_BRAND: &str = "Steel";
+
+/// The longest brand accepted, matching vanilla's string limit.
+const MAX_BRAND_LENGTH: usize = 32767;
+
+/// A function run when a player sends a payload on its channel.
+///
+/// Receives the sending player and the raw payload.
+pub type PayloadHandler = dyn Fn(&Arc<Player>, &[u8]) + Send + Sync;
+
+/// Registry of custom payload channels and their handlers.
+#[derive(Default)]
+pub struct PayloadChannels {
+    handlers: SyncRwLock<FxHashMap<Identifier, Arc<PayloadHandler>>>,
+}
+
+impl PayloadChannels {
+    /// Creates a registry without channels.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` for payloads sent on `channel`.
+    ///
+    /// Returns `false` without registering if the channel already has a handler
+    /// or is one the server handles itself. Players that already joined only
+    /// learn about the channel when they rejoin.
+    pub fn register(
+        &self,
+        channel: Identifier,
+        handler: impl Fn(&Arc<Player>, &[u8]) + Send + Sync + 'static,
+    ) -> bool {
+        if Self::is_builtin(&channel) {
+            return false;
+        }
+        let mut handlers = self.handlers.write();
+        if handlers.contains_key(&channel) {
+            return false;
+        }
+        handlers.insert(channel, Arc::new(handler));
+        true
+    }
+
+    /// Removes the handler of `channel`. Returns whether there was one.
+    pub fn unregister(&self, channel: &Identifier) -> bool {
+        self.handlers.write().remove(channel).is_some()
+    }
+
+    /// Returns whether `channel` has a handler.
+    #[must_use]
+    pub fn is_registered(&self, channel: &Identifier) -> bool {
+        self.handlers.read().contains_key(channel)
+    }
+
+    /// Gets every channel with a handler.
+    #[must_use]
+    pub fn channels(&self) -> Vec<Identifier> {
+        self.handlers.read().keys().cloned().collect()
+    }
+
+    /// Runs the handler of `channel` for a payload sent by `player`.
+    ///
+    /// Returns `false` if the channel has no handler.
+    pub fn handle(&self, player: &Arc<Player>, channel: &Identifier, payload: &[u8]) -> bool {
+        let Some(handler) = self.handlers.read().get(channel).cloned() else {
+            return false;
+        };
+        // Run outside the lock so the handler can register channels itself
+        handler(player, payload);
+        true
+    }
+
+    /// Returns whether the server handles `channel` itself.
+    #[must_use]
+    pub fn is_builtin(channel: &Identifier) -> bool {
+        *channel == BRAND_CHANNEL || *channel == REGISTER_CHANNEL || *channel == UNREGISTER_CHANNEL
+    }
+}
+
+/// Encodes a `minecraft:brand` payload.
+///
+/// Vanilla: `BrandPayload.write()`.
+#[must_use]
+pub fn encode_brand(brand: &str) -> Box<[u8]> {
+    let mut payload = Vec::new();
+    brand
+        .write_prefixed::<VarInt>(&mut payload)
+        .expect("writing to a Vec can't fail");
+    payload.into_boxed_slice()
+}
+
+/// Decodes a `minecraft:brand` payload, or returns `None` if it is malformed.
+///
+/// Vanilla: `BrandPayload(FriendlyByteBuf)`.
+#[must_use]
+pub fn decode_brand(payload: &[u8]) -> Option<String> {
+    String::read_prefixed_bound::<VarInt>(&mut Cursor::new(payload), MAX_BRAND_LENGTH).ok()
+}
+
+/// Encodes a `minecraft:register` or `minecraft:unregister` payload: the channel
+/// names separated by null bytes.
+#[must_use]
+pub fn encode_channel_list(channels: &[Identifier]) -> Box<[u8]> {
+    channels
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\0")
+        .into_bytes()
+        .into_boxed_slice()
+}
+
+/// Decodes a `minecraft:register` or `minecraft:unregister` payload, skipping
+/// names that aren't valid channel ids.
+#[must_use]
+pub fn decode_channel_list(payload: &[u8]) -> Vec<Identifier> {
+    payload
+        .split(|&byte| byte == 0)
+        .filter_map(|name| std::str::from_utf8(name).ok()?.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brand_round_trips_as_a_prefixed_string() {
+        let payload = encode_brand(SERVER_BRAND);
+        assert_eq!(&payload[..], b"\x05Steel");
+        assert_eq!(decode_brand(&payload).as_deref(), Some(SERVER_BRAND));
+        assert_eq!(decode_brand(b"\x09Steel"), None);
+    }
+
+    #[test]
+    fn channel_lists_skip_invalid_names() {
+        let channels = vec![
+            Identifier::new("bungeecord", "main"),
+            Identifier::new("example", "sync"),
+        ];
+        let payload = encode_channel_list(&channels);
+        assert_eq!(&payload[..], b"bungeecord:main\0example:sync");
+        assert_eq!(decode_channel_list(&payload), channels);
+        assert_eq!(
+            decode_channel_list(b"example:sync\0Not Valid\0"),
+            vec![Identifier::new("example", "sync")]
+        );
+    }
+}
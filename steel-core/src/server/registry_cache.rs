@@ -129,6 +129,7 @@ impl RegistryCache {
 
         add_tags!(BLOCKS_REGISTRY, blocks);
         add_tags!(ITEMS_REGISTRY, items);
+        add_tags!(BIOMES_REGISTRY, biomes);
         add_tags!(TIMELINE_REGISTRY, timelines);
         add_tags!(DIALOG_REGISTRY, dialogs);
         add_tags!(DAMAGE_TYPE_REGISTRY, damage_types);
@@ -0,0 +1,171 @@
+//! Tasks run on the server tick after a delay, once or repeatedly.
+//!
+//! Tasks run at the end of a tick, after the worlds ticked, on the tick task.
+
+use std::mem;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use steel_utils::locks::SyncMutex;
+
+use super::Server;
+
+/// Identifies a scheduled task, to cancel it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+/// What a repeating task wants after a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    /// Run again after the period.
+    Continue,
+    /// Don't run again.
+    Stop,
+    /// The task failed. It doesn't run again, and its owner is reported by
+    /// [`Scheduler::tick`].
+    Failed,
+}
+
+type Task = dyn FnMut(&Arc<Server>) -> TaskStatus + Send;
+
+struct ScheduledTask {
+    id: TaskId,
+    owner: Option<&'static str>,
+    /// Ticks left until the next run.
+    remaining: u64,
+    /// Ticks between runs, or `None` to run once.
+    period: Option<u64>,
+    task: Box<Task>,
+}
+
+/// Runs tasks on the server tick.
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: SyncMutex<Vec<ScheduledTask>>,
+    /// Tasks cancelled while [`Self::tick`] had them taken out.
+    cancelled: SyncMutex<Vec<TaskId>>,
+    /// Owners whose tasks were cancelled while [`Self::tick`] had them taken out.
+    cancelled_owners: SyncMutex<Vec<&'static str>>,
+    /// Whether [`Self::tick`] has the tasks taken out. Only changed while
+    /// `tasks` is locked.
+    ticking: AtomicBool,
+    next_id: AtomicU64,
+}
+
+impl Scheduler {
+    /// Creates a scheduler without tasks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `task` once, `delay` ticks from now. A delay of 0 runs it at the end
+    /// of the current tick.
+    pub fn run_later(
+        &self,
+        owner: Option<&'static str>,
+        delay: u64,
+        task: impl FnOnce(&Arc<Server>) + Send + 'static,
+    ) -> TaskId {
+        let mut task = Some(task);
+        self.schedule(owner, delay, None, move |server| {
+            if let Some(task) = task.take() {
+                task(server);
+            }
+            TaskStatus::Stop
+        })
+    }
+
+    /// Runs `task` every `period` ticks, starting `delay` ticks from now, until it
+    /// returns something other than [`TaskStatus::Continue`].
+    pub fn run_repeating(
+        &self,
+        owner: Option<&'static str>,
+        delay: u64,
+        period: u64,
+        task: impl FnMut(&Arc<Server>) -> TaskStatus + Send + 'static,
+    ) -> TaskId {
+        self.schedule(owner, delay, Some(period.max(1)), task)
+    }
+
+    fn schedule(
+        &self,
+        owner: Option<&'static str>,
+        delay: u64,
+        period: Option<u64>,
+        task: impl FnMut(&Arc<Server>) -> TaskStatus + Send + 'static,
+    ) -> TaskId {
+        let id = TaskId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.tasks.lock().push(ScheduledTask {
+            id,
+            owner,
+            remaining: delay,
+            period,
+            task: Box::new(task),
+        });
+        id
+    }
+
+    /// Cancels a task.
+    pub fn cancel(&self, id: TaskId) {
+        let mut tasks = self.tasks.lock();
+        tasks.retain(|task| task.id != id);
+        if self.ticking.load(Ordering::Relaxed) {
+            self.cancelled.lock().push(id);
+        }
+    }
+
+    /// Cancels every task of `owner`.
+    pub fn cancel_owned_by(&self, owner: &'static str) {
+        let mut tasks = self.tasks.lock();
+        tasks.retain(|task| task.owner != Some(owner));
+        if self.ticking.load(Ordering::Relaxed) {
+            self.cancelled_owners.lock().push(owner);
+        }
+    }
+
+    /// Runs the tasks that are due. Returns the owners of tasks that failed.
+    pub fn tick(&self, server: &Arc<Server>) -> Vec<&'static str> {
+        // Taken out, so tasks can schedule and cancel tasks while they run
+        let mut tasks = {
+            let mut scheduled = self.tasks.lock();
+            self.ticking.store(true, Ordering::Relaxed);
+            mem::take(&mut *scheduled)
+        };
+        let mut failed = Vec::new();
+        tasks.retain_mut(|task| {
+            if task.remaining > 0 {
+                task.remaining -= 1;
+                return true;
+            }
+            match (task.task)(server) {
+                TaskStatus::Continue => match task.period {
+                    Some(period) => {
+                        task.remaining = period - 1;
+                        true
+                    }
+                    None => false,
+                },
+                TaskStatus::Stop => false,
+                TaskStatus::Failed => {
+                    failed.extend(task.owner);
+                    false
+                }
+            }
+        });
+        let mut scheduled = self.tasks.lock();
+        self.ticking.store(false, Ordering::Relaxed);
+        let cancelled = mem::take(&mut *self.cancelled.lock());
+        let cancelled_owners = mem::take(&mut *self.cancelled_owners.lock());
+        tasks.retain(|task| {
+            !cancelled.contains(&task.id)
+                && !task
+                    .owner
+                    .is_some_and(|owner| cancelled_owners.contains(&owner))
+        });
+        // Tasks scheduled while running go after the ones that were already there
+        tasks.append(&mut scheduled);
+        *scheduled = tasks;
+        failed
+    }
+}
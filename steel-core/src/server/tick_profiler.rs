@@ -0,0 +1,490 @@
+//! An in-game tick profiler, started and stopped with `/steel profile`.
+//!
+//! While it runs, it sums up where each world's tick time goes and which chunks
+//! take the longest to tick. Stopping it writes a report file and returns a
+//! summary for chat. Unlike the `profiling` feature it is always compiled in; while
+//! stopped it costs one atomic load per world tick.
+
+use std::cmp::Reverse;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+use rustc_hash::FxHashMap;
+use steel_utils::locks::SyncMutex;
+use steel_utils::{ChunkPos, Identifier};
+
+use crate::world::WorldTickTimings;
+
+/// The directory profile reports are written to.
+pub const PROFILE_DIRECTORY: &str = "profiles";
+
+/// How many of the slowest chunks each world lists in a report.
+const HOT_CHUNKS_REPORTED: usize = 10;
+
+/// The server's tick profiler.
+pub static TICK_PROFILER: TickProfiler = TickProfiler::new();
+
+/// A timed part of a world tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorldPhase {
+    /// Applying chunk ticket changes.
+    TicketUpdates,
+    /// Creating and updating chunk holders.
+    HolderUpdates,
+    /// Scheduling and spawning chunk generation and loading.
+    Generation,
+    /// Sending changed blocks to the players tracking them.
+    PacketFlush,
+    /// Saving and unloading chunks.
+    Unloads,
+    /// Ticking block entities and random block ticks.
+    ChunkTick,
+    /// Ticking entities.
+    EntityTick,
+    /// Running scheduled block and fluid ticks.
+    BlockTicks,
+    /// Ticking players.
+    Players,
+}
+
+impl WorldPhase {
+    /// All phases.
+    pub const ALL: [Self; 9] = [
+        Self::TicketUpdates,
+        Self::HolderUpdates,
+        Self::Generation,
+        Self::PacketFlush,
+        Self::Unloads,
+        Self::ChunkTick,
+        Self::EntityTick,
+        Self::BlockTicks,
+        Self::Players,
+    ];
+
+    /// Gets the phase's name in reports.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::TicketUpdates => "ticket_updates",
+            Self::HolderUpdates => "holder_updates",
+            Self::Generation => "generation",
+            Self::PacketFlush => "packet_flush",
+            Self::Unloads => "unloads",
+            Self::ChunkTick => "chunk_tick",
+            Self::EntityTick => "entity_tick",
+            Self::BlockTicks => "block_ticks",
+            Self::Players => "players",
+        }
+    }
+
+    fn duration(self, timings: &WorldTickTimings) -> Duration {
+        let chunk_map = &timings.chunk_map;
+        match self {
+            Self::TicketUpdates => chunk_map.ticket_updates,
+            Self::HolderUpdates => chunk_map.holder_creation,
+            Self::Generation => chunk_map.schedule_generation + chunk_map.run_generation,
+            Self::PacketFlush => chunk_map.broadcast_changes,
+            Self::Unloads => chunk_map.process_unloads + chunk_map.autosave,
+            Self::ChunkTick => (chunk_map.collect_tickable + chunk_map.tick_chunks)
+                .saturating_sub(chunk_map.entity_tick),
+            Self::EntityTick => chunk_map.entity_tick,
+            Self::BlockTicks => chunk_map.scheduled_ticks,
+            Self::Players => timings.player_tick,
+        }
+    }
+}
+
+/// Time spent in one phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTotal {
+    /// Time spent in the phase in total.
+    pub total: Duration,
+    /// The longest tick spent in the phase.
+    pub max: Duration,
+}
+
+impl PhaseTotal {
+    fn add(&mut self, duration: Duration) {
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+}
+
+struct WorldProfile {
+    world: Identifier,
+    phases: [PhaseTotal; WorldPhase::ALL.len()],
+    chunks: FxHashMap<ChunkPos, Duration>,
+    active_entities: u64,
+    inactive_entities: u64,
+}
+
+struct Session {
+    started: Instant,
+    ticks: u64,
+    tick_total: Duration,
+    slowest_tick: Option<(u64, Duration)>,
+    worlds: Vec<WorldProfile>,
+}
+
+/// Records tick timings between [`Self::start`] and [`Self::stop`].
+pub struct TickProfiler {
+    running: AtomicBool,
+    session: SyncMutex<Option<Session>>,
+}
+
+impl Default for TickProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickProfiler {
+    /// Creates a stopped profiler.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            running: AtomicBool::new(false),
+            session: SyncMutex::new(None),
+        }
+    }
+
+    /// Returns whether the profiler is recording.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+
+    /// Starts recording. Returns `false` if the profiler was already running.
+    pub fn start(&self) -> bool {
+        let mut session = self.session.lock();
+        if session.is_some() {
+            return false;
+        }
+        *session = Some(Session {
+            started: Instant::now(),
+            ticks: 0,
+            tick_total: Duration::ZERO,
+            slowest_tick: None,
+            worlds: Vec::new(),
+        });
+        self.running.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// Stops recording and returns what was recorded, or `None` if the profiler
+    /// wasn't running.
+    pub fn stop(&self) -> Option<ProfileReport> {
+        let session = self.session.lock().take()?;
+        self.running.store(false, Ordering::Relaxed);
+        Some(ProfileReport::new(session))
+    }
+
+    /// Records the timings of one tick of a world.
+    pub fn record_world(&self, world: &Identifier, timings: &WorldTickTimings) {
+        let mut session = self.session.lock();
+        let Some(session) = session.as_mut() else {
+            return;
+        };
+        let index = match session.worlds.iter().position(|p| &p.world == world) {
+            Some(index) => index,
+            None => {
+                session.worlds.push(WorldProfile {
+                    world: world.clone(),
+                    phases: Default::default(),
+                    chunks: FxHashMap::default(),
+                    active_entities: 0,
+                    inactive_entities: 0,
+                });
+                session.worlds.len() - 1
+            }
+        };
+        let profile = &mut session.worlds[index];
+        for (phase, total) in WorldPhase::ALL.iter().zip(&mut profile.phases) {
+            total.add(phase.duration(timings));
+        }
+        for &(pos, duration) in &timings.chunk_map.chunk_times {
+            *profile.chunks.entry(pos).or_default() += duration;
+        }
+        profile.active_entities += timings.chunk_map.active_entities as u64;
+        profile.inactive_entities += timings.chunk_map.inactive_entities as u64;
+    }
+
+    /// Records how long a whole server tick took.
+    pub fn record_tick(&self, tick: u64, duration: Duration) {
+        let mut session = self.session.lock();
+        let Some(session) = session.as_mut() else {
+            return;
+        };
+        session.ticks += 1;
+        session.tick_total += duration;
+        if session
+            .slowest_tick
+            .is_none_or(|(_, slowest)| duration > slowest)
+        {
+            session.slowest_tick = Some((tick, duration));
+        }
+    }
+}
+
+/// The recorded timings of one world.
+#[derive(Debug, Clone)]
+pub struct WorldReport {
+    /// The world.
+    pub world: Identifier,
+    /// Time spent per phase, slowest first.
+    pub phases: Vec<(WorldPhase, PhaseTotal)>,
+    /// The chunks that took the longest to tick in total, slowest first.
+    pub hot_chunks: Vec<(ChunkPos, Duration)>,
+    /// How many entity ticks ran in total.
+    pub active_entities: u64,
+    /// How many entity ticks were skipped by activation ranges in total.
+    pub inactive_entities: u64,
+}
+
+/// What a profiler run recorded.
+#[derive(Debug, Clone)]
+pub struct ProfileReport {
+    /// How long the profiler ran.
+    pub duration: Duration,
+    /// How many server ticks ran.
+    pub ticks: u64,
+    /// Time spent in server ticks in total.
+    pub tick_total: Duration,
+    /// The number and duration of the slowest tick.
+    pub slowest_tick: Option<(u64, Duration)>,
+    /// The timings of each world.
+    pub worlds: Vec<WorldReport>,
+}
+
+impl ProfileReport {
+    fn new(session: Session) -> Self {
+        let worlds = session
+            .worlds
+            .into_iter()
+            .map(|profile| {
+                let mut phases: Vec<_> = WorldPhase::ALL.into_iter().zip(profile.phases).collect();
+                phases.sort_by_key(|(_, total)| Reverse(total.total));
+                let mut hot_chunks: Vec<_> = profile.chunks.into_iter().collect();
+                hot_chunks.sort_by_key(|&(_, duration)| Reverse(duration));
+                hot_chunks.truncate(HOT_CHUNKS_REPORTED);
+                WorldReport {
+                    world: profile.world,
+                    phases,
+                    hot_chunks,
+                    active_entities: profile.active_entities,
+                    inactive_entities: profile.inactive_entities,
+                }
+            })
+            .collect();
+        Self {
+            duration: session.started.elapsed(),
+            ticks: session.ticks,
+            tick_total: session.tick_total,
+            slowest_tick: session.slowest_tick,
+            worlds,
+        }
+    }
+
+    /// Milliseconds per tick on average.
+    fn per_tick(&self, duration: Duration) -> f64 {
+        duration.as_secs_f64() * 1000.0 / self.ticks.max(1) as f64
+    }
+
+    /// How many of something there were per tick on average.
+    fn per_tick_count(&self, count: u64) -> f64 {
+        count as f64 / self.ticks.max(1) as f64
+    }
+
+    /// A few lines for chat: tick times, the slowest phases and the hottest chunk.
+    #[must_use]
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Profiled {} ticks over {:.1}s, {:.2} ms per tick on average{}",
+            self.ticks,
+            self.duration.as_secs_f64(),
+            self.per_tick(self.tick_total),
+            self.slowest_tick
+                .map_or_else(String::new, |(tick, duration)| format!(
+                    ", {:.2} ms at most (tick {tick})",
+                    duration.as_secs_f64() * 1000.0
+                )),
+        )];
+
+        let mut phases: FxHashMap<WorldPhase, Duration> = FxHashMap::default();
+        for world in &self.worlds {
+            for (phase, total) in &world.phases {
+                *phases.entry(*phase).or_default() += total.total;
+            }
+        }
+        let mut phases: Vec<_> = phases.into_iter().collect();
+        phases.sort_by_key(|&(_, total)| Reverse(total));
+        let slowest: Vec<_> = phases
+            .iter()
+            .take(4)
+            .map(|&(phase, total)| format!("{} {:.2} ms", phase.name(), self.per_tick(total)))
+            .collect();
+        if !slowest.is_empty() {
+            lines.push(format!("Slowest phases per tick: {}", slowest.join(", ")));
+        }
+
+        let (active, inactive) = self
+            .worlds
+            .iter()
+            .fold((0, 0), |(active, inactive), world| {
+                (
+                    active + world.active_entities,
+                    inactive + world.inactive_entities,
+                )
+            });
+        if active + inactive > 0 {
+            lines.push(format!(
+                "Entities per tick: {:.1} ticked, {:.1} skipped by activation range",
+                self.per_tick_count(active),
+                self.per_tick_count(inactive)
+            ));
+        }
+
+        let hottest = self
+            .worlds
+            .iter()
+            .flat_map(|world| {
+                world
+                    .hot_chunks
+                    .first()
+                    .map(|&(pos, duration)| (&world.world, pos, duration))
+            })
+            .max_by_key(|&(_, _, duration)| duration);
+        if let Some((world, pos, duration)) = hottest {
+            lines.push(format!(
+                "Hottest chunk: [{}, {}] in {world}, {:.3} ms per tick",
+                pos.0.x,
+                pos.0.y,
+                self.per_tick(duration)
+            ));
+        }
+        lines
+    }
+
+    /// Formats the full report.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for line in self.summary() {
+            let _ = writeln!(text, "{line}");
+        }
+        for world in &self.worlds {
+            let _ = writeln!(text, "\n== {} ==", world.world);
+            let _ = writeln!(
+                text,
+                "{:<16} {:>12} {:>12} {:>12}",
+                "phase", "total ms", "ms/tick", "max ms"
+            );
+            for (phase, total) in &world.phases {
+                let _ = writeln!(
+                    text,
+                    "{:<16} {:>12.2} {:>12.3} {:>12.2}",
+                    phase.name(),
+                    total.total.as_secs_f64() * 1000.0,
+                    self.per_tick(total.total),
+                    total.max.as_secs_f64() * 1000.0,
+                );
+            }
+            let _ = writeln!(
+                text,
+                "\nEntities per tick: {:.1} ticked, {:.1} skipped by activation range",
+                self.per_tick_count(world.active_entities),
+                self.per_tick_count(world.inactive_entities)
+            );
+            if !world.hot_chunks.is_empty() {
+                let _ = writeln!(text, "\nSlowest chunks:");
+                for (pos, duration) in &world.hot_chunks {
+                    let _ = writeln!(
+                        text,
+                        "[{}, {}] {:.3} ms/tick",
+                        pos.0.x,
+                        pos.0.y,
+                        self.per_tick(*duration)
+                    );
+                }
+            }
+        }
+        text
+    }
+
+    /// Writes the report to a new file in `directory`. Returns the file's path.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be written.
+    pub fn write_to(&self, directory: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(directory)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let path = directory.join(format!("profile-{timestamp}.txt"));
+        fs::write(&path, self.to_text())?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::chunk_map::ChunkMapTickTimings;
+
+    fn timings(entity_ms: u64, chunk_ms: u64, hot: ChunkPos) -> WorldTickTimings {
+        WorldTickTimings {
+            chunk_map: ChunkMapTickTimings {
+                tick_chunks: Duration::from_millis(chunk_ms),
+                entity_tick: Duration::from_millis(entity_ms),
+                chunk_times: vec![(hot, Duration::from_millis(chunk_ms))],
+                active_entities: 3,
+                inactive_entities: 5,
+                ..Default::default()
+            },
+            player_tick: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn test_records_only_while_running() {
+        let profiler = TickProfiler::new();
+        let world = Identifier::vanilla_static("overworld");
+        profiler.record_world(&world, &timings(1, 2, ChunkPos::new(0, 0)));
+        assert!(profiler.stop().is_none());
+
+        assert!(profiler.start());
+        assert!(!profiler.start());
+        assert!(profiler.is_running());
+        let hot = ChunkPos::new(3, -2);
+        profiler.record_world(&world, &timings(4, 6, hot));
+        profiler.record_world(&world, &timings(2, 6, hot));
+        profiler.record_tick(7, Duration::from_millis(12));
+        profiler.record_tick(8, Duration::from_millis(20));
+
+        let report = profiler.stop().expect("profiler was running");
+        assert!(!profiler.is_running());
+        assert_eq!(report.ticks, 2);
+        assert_eq!(report.slowest_tick, Some((8, Duration::from_millis(20))));
+        let world = &report.worlds[0];
+        let phase = |phase| {
+            world
+                .phases
+                .iter()
+                .find(|(p, _)| *p == phase)
+                .map(|(_, total)| *total)
+                .expect("every phase is reported")
+        };
+        assert_eq!(
+            phase(WorldPhase::EntityTick).total,
+            Duration::from_millis(6)
+        );
+        assert_eq!(phase(WorldPhase::ChunkTick).total, Duration::from_millis(6));
+        assert_eq!(phase(WorldPhase::ChunkTick).max, Duration::from_millis(4));
+        assert_eq!(world.hot_chunks, [(hot, Duration::from_millis(12))]);
+        assert_eq!(world.active_entities, 6);
+        assert_eq!(world.inactive_entities, 10);
+    }
+}
@@ -15,6 +15,12 @@ const MS_PER_SEC: f64 = 1000.0;
 /// Smoothing factor for exponential moving average (matches vanilla's 0.8).
 const TICK_TIME_SMOOTHING: f32 = 0.8;
 
+/// The lowest tick rate, in ticks per second (matches vanilla's `/tick rate`).
+pub const MIN_TICK_RATE: f32 = 1.0;
+
+/// The highest tick rate, in ticks per second (matches vanilla's `/tick rate`).
+pub const MAX_TICK_RATE: f32 = 10_000.0;
+
 /// Report data returned when a sprint finishes.
 #[derive(Debug, Clone)]
 pub struct SprintReport {
@@ -82,9 +88,14 @@ impl TickRateManager {
         }
     }
 
-    /// Sets the tick rate.
+    /// Sets the tick rate, clamped to [`MIN_TICK_RATE`]..=[`MAX_TICK_RATE`] so
+    /// a plugin can't stall the tick loop or spin it without sleeping.
     pub fn set_tick_rate(&mut self, rate: f32) {
-        self.tick_rate = rate.max(1.0);
+        self.tick_rate = if rate.is_nan() {
+            MIN_TICK_RATE
+        } else {
+            rate.clamp(MIN_TICK_RATE, MAX_TICK_RATE)
+        };
         #[expect(
             clippy::cast_possible_truncation,
             clippy::cast_sign_loss,
@@ -359,3 +370,39 @@ impl Default for TickRateManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_rate_is_clamped() {
+        let mut manager = TickRateManager::new();
+        manager.set_tick_rate(f32::NAN);
+        assert_eq!(manager.tick_rate().to_bits(), MIN_TICK_RATE.to_bits());
+        manager.set_tick_rate(f32::INFINITY);
+        assert_eq!(manager.tick_rate().to_bits(), MAX_TICK_RATE.to_bits());
+        assert_eq!(manager.nanoseconds_per_tick, 100_000);
+        manager.set_tick_rate(0.0);
+        assert_eq!(manager.nanoseconds_per_tick, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_sprint_restores_frozen_state() {
+        let mut manager = TickRateManager::new();
+        manager.set_frozen(true);
+        assert!(!manager.request_game_to_sprint(2));
+        assert!(!manager.is_frozen());
+
+        for _ in 0..2 {
+            manager.tick();
+            let (sprinting, report) = manager.check_should_sprint_this_tick();
+            assert!(sprinting && report.is_none());
+        }
+        manager.tick();
+        let (sprinting, report) = manager.check_should_sprint_this_tick();
+        assert!(!sprinting && report.is_some());
+        assert!(manager.is_frozen());
+        assert!(!manager.is_sprinting());
+    }
+}
@@ -0,0 +1,304 @@
+//! Detects when the server tick stalls.
+//!
+//! The tick loop reports when each tick starts and ends, and the watchdog thread
+//! checks every second whether the running tick exceeds the configured stall
+//! threshold. A stalled tick gets a crash report with the tick's phase, the last
+//! tick's timing breakdown, recent packet activity and the server's threads. If
+//! configured, the server then shuts down, saving what it can, and exits with a
+//! failure code so a process supervisor restarts it.
+//!
+//! Vanilla: `ServerWatchdog`.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use std::{fs, process, thread};
+
+use steel_utils::Identifier;
+use steel_utils::locks::SyncMutex;
+use tokio_util::sync::CancellationToken;
+
+use super::crash_report::CrashReport;
+use crate::config::WatchdogConfig;
+use crate::world::WorldTickTimings;
+
+/// The watchdog of the server tick.
+pub static WATCHDOG: LazyLock<Watchdog> = LazyLock::new(Watchdog::new);
+
+/// How often the watchdog checks the tick.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many seconds of packet activity crash reports include.
+const PACKET_HISTORY: usize = 30;
+
+/// How long a shutdown after a stall may take before the process exits anyway.
+const FORCE_EXIT_DELAY: Duration = Duration::from_secs(30);
+
+/// What the tick loop is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TickPhase {
+    /// Waiting for the next tick.
+    Idle,
+    /// Ticking the worlds.
+    Worlds,
+    /// Running scheduled tasks.
+    Scheduler,
+    /// Updating the tick rate and tab list.
+    Bookkeeping,
+}
+
+impl TickPhase {
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Worlds,
+            2 => Self::Scheduler,
+            3 => Self::Bookkeeping,
+            _ => Self::Idle,
+        }
+    }
+}
+
+/// Packets handled in one second.
+#[derive(Clone, Copy)]
+struct PacketSample {
+    received: u64,
+    sent: u64,
+}
+
+/// Tracks the server tick for stall detection and crash reports.
+pub struct Watchdog {
+    epoch: Instant,
+    /// When the running tick started, in milliseconds since `epoch` plus one, or
+    /// 0 between ticks.
+    tick_started: AtomicU64,
+    tick: AtomicU64,
+    phase: AtomicU8,
+    /// The timings of the last tick, per world.
+    last_timings: SyncMutex<Vec<(Identifier, WorldTickTimings)>>,
+    packets_received: AtomicU64,
+    packets_sent: AtomicU64,
+    /// Packets handled per second, oldest first.
+    packet_history: SyncMutex<VecDeque<PacketSample>>,
+    stalled: AtomicBool,
+}
+
+impl Watchdog {
+    fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            tick_started: AtomicU64::new(0),
+            tick: AtomicU64::new(0),
+            phase: AtomicU8::new(TickPhase::Idle as u8),
+            last_timings: SyncMutex::new(Vec::new()),
+            packets_received: AtomicU64::new(0),
+            packets_sent: AtomicU64::new(0),
+            packet_history: SyncMutex::new(VecDeque::with_capacity(PACKET_HISTORY)),
+            stalled: AtomicBool::new(false),
+        }
+    }
+
+    fn now_millis(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+
+    /// Marks the start of a tick.
+    pub fn begin_tick(&self, tick: u64) {
+        self.tick.store(tick, Ordering::Relaxed);
+        self.phase.store(TickPhase::Worlds as u8, Ordering::Relaxed);
+        self.tick_started
+            .store(self.now_millis() + 1, Ordering::Release);
+    }
+
+    /// Marks what the running tick moves on to.
+    pub fn set_phase(&self, phase: TickPhase) {
+        self.phase.store(phase as u8, Ordering::Relaxed);
+    }
+
+    /// Marks the end of a tick.
+    pub fn end_tick(&self) {
+        self.tick_started.store(0, Ordering::Release);
+        self.phase.store(TickPhase::Idle as u8, Ordering::Relaxed);
+    }
+
+    /// Keeps the timings of the last world tick for crash reports.
+    pub fn record_timings(&self, timings: Vec<(Identifier, WorldTickTimings)>) {
+        *self.last_timings.lock() = timings;
+    }
+
+    /// Counts a packet received from a client.
+    pub fn packet_received(&self) {
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a packet sent to a client.
+    pub fn packet_sent(&self) {
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns whether the watchdog shut the server down after a stall.
+    #[must_use]
+    pub fn has_stalled(&self) -> bool {
+        self.stalled.load(Ordering::Relaxed)
+    }
+
+    /// How long the running tick has been running, or `None` between ticks.
+    fn tick_running_for(&self) -> Option<Duration> {
+        let started = self.tick_started.load(Ordering::Acquire);
+        (started != 0).then(|| Duration::from_millis(self.now_millis() + 1 - started))
+    }
+
+    /// Describes the running tick and the timings of the last world tick.
+    #[must_use]
+    pub fn tick_details(&self) -> String {
+        let mut text = format!(
+            "Tick: {}\nPhase: {:?}\n",
+            self.tick.load(Ordering::Relaxed),
+            TickPhase::from_u8(self.phase.load(Ordering::Relaxed)),
+        );
+        if let Some(running) = self.tick_running_for() {
+            let _ = writeln!(text, "Running for: {running:?}");
+        }
+        // Not blocking: the thread holding the lock may be the one that crashed
+        let Some(timings) = self.last_timings.try_lock() else {
+            text.push_str("Last world tick: unavailable\n");
+            return text;
+        };
+        for (world, timings) in timings.iter() {
+            let cm = &timings.chunk_map;
+            let _ = write!(
+                text,
+                "Last tick of {world}:\n  ticket updates: {:?}\n  holder creation: {:?}\n  \
+                 schedule generation: {:?} ({} scheduled)\n  run generation: {:?}\n  \
+                 broadcast changes: {:?}\n  process unloads: {:?}\n  autosave: {:?}\n  collect tickable: {:?}\n  \
+                 tick chunks: {:?} ({} of {} chunks)\n  players: {:?}\n",
+                cm.ticket_updates,
+                cm.holder_creation,
+                cm.schedule_generation,
+                cm.scheduled_count,
+                cm.run_generation,
+                cm.broadcast_changes,
+                cm.process_unloads,
+                cm.autosave,
+                cm.collect_tickable,
+                cm.tick_chunks,
+                cm.tickable_count,
+                cm.total_chunks,
+                timings.player_tick,
+            );
+        }
+        text
+    }
+
+    /// Describes the packets handled in the last seconds.
+    fn packet_details(&self) -> String {
+        let history = self.packet_history.lock();
+        let mut text = format!("Packets per second, the last {} seconds:\n", history.len());
+        for (seconds_ago, sample) in history.iter().rev().enumerate() {
+            let _ = writeln!(
+                text,
+                "  -{}s: {} received, {} sent",
+                seconds_ago + 1,
+                sample.received,
+                sample.sent
+            );
+        }
+        text
+    }
+
+    fn sample_packets(&self) {
+        let sample = PacketSample {
+            received: self.packets_received.swap(0, Ordering::Relaxed),
+            sent: self.packets_sent.swap(0, Ordering::Relaxed),
+        };
+        let mut history = self.packet_history.lock();
+        if history.len() == PACKET_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    /// Starts the watchdog thread. Stalls cancel `cancel_token` if configured.
+    pub fn spawn(&'static self, config: WatchdogConfig, cancel_token: CancellationToken) {
+        let spawned = thread::Builder::new()
+            .name("Server Watchdog".to_owned())
+            .spawn(move || self.watch(&config, &cancel_token));
+        if let Err(err) = spawned {
+            log::error!("Failed to start the watchdog: {err}");
+        }
+    }
+
+    fn watch(&self, config: &WatchdogConfig, cancel_token: &CancellationToken) {
+        let threshold = Duration::from_secs(config.stall_threshold);
+        let mut reported_tick = None;
+        loop {
+            thread::sleep(CHECK_INTERVAL);
+            self.sample_packets();
+
+            let Some(running) = self.tick_running_for() else {
+                continue;
+            };
+            let tick = self.tick.load(Ordering::Relaxed);
+            if running < threshold || reported_tick == Some(tick) {
+                continue;
+            }
+            reported_tick = Some(tick);
+
+            let stall = format!(
+                "A single server tick took {:.2} seconds (should be max 0.05)",
+                running.as_secs_f64()
+            );
+            log::error!("{stall}");
+            CrashReport::new("Watching Server")
+                .section("Stall", stall)
+                .section("Server Tick", self.tick_details())
+                .section("Recent Packets", self.packet_details())
+                .section("Threads", thread_dump())
+                .save();
+
+            if config.shutdown_on_stall {
+                log::error!("Considering it to be crashed, server will forcibly shutdown.");
+                self.stalled.store(true, Ordering::Relaxed);
+                cancel_token.cancel();
+                // The stalled tick may hold locks the shutdown needs
+                thread::sleep(FORCE_EXIT_DELAY);
+                log::error!("The server didn't shut down in time, exiting");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Lists the threads of the server with their state.
+// TODO: Include the stack of every thread. Rust can only capture the backtrace of
+// the current thread; the others need a platform specific sampler
+fn thread_dump() -> String {
+    let Ok(tasks) = fs::read_dir("/proc/self/task") else {
+        return "Thread list unavailable on this platform".to_owned();
+    };
+    let mut text = String::new();
+    for task in tasks.filter_map(Result::ok) {
+        let path = task.path();
+        let read = |file: &str| {
+            fs::read_to_string(path.join(file))
+                .map(|content| content.trim().to_owned())
+                .unwrap_or_default()
+        };
+        let name = read("comm");
+        // The state follows the parenthesized name, which may contain spaces
+        let stat = read("stat");
+        let state = stat
+            .rsplit_once(") ")
+            .and_then(|(_, rest)| rest.split(' ').next())
+            .unwrap_or("?");
+        let _ = writeln!(
+            text,
+            "\"{name}\" tid={} state={state} waiting in {}",
+            task.file_name().to_string_lossy(),
+            read("wchan"),
+        );
+    }
+    text
+}
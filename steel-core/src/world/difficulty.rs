@@ -0,0 +1,123 @@
+//! Local difficulty, mirroring vanilla's `DifficultyInstance`.
+
+use steel_utils::types::Difficulty;
+
+/// Moon brightness per moon phase, mirroring vanilla's `DimensionType.MOON_BRIGHTNESS_PER_PHASE`.
+pub const MOON_BRIGHTNESS_PER_PHASE: [f32; 8] = [1.0, 0.75, 0.5, 0.25, 0.0, 0.25, 0.5, 0.75];
+
+/// The difficulty at a specific position, scaled by world age, chunk inhabited
+/// time and moon phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyInstance {
+    base: Difficulty,
+    effective_difficulty: f32,
+}
+
+impl DifficultyInstance {
+    /// Computes the local difficulty.
+    ///
+    /// * `total_game_time` - Ticks the world has existed for
+    /// * `local_game_time` - Inhabited time of the chunk containing the position
+    /// * `moon_brightness` - Brightness of the current moon phase (see [`MOON_BRIGHTNESS_PER_PHASE`])
+    #[must_use]
+    pub fn new(
+        base: Difficulty,
+        total_game_time: i64,
+        local_game_time: i64,
+        moon_brightness: f32,
+    ) -> Self {
+        Self {
+            base,
+            effective_difficulty: Self::calculate_difficulty(
+                base,
+                total_game_time,
+                local_game_time,
+                moon_brightness,
+            ),
+        }
+    }
+
+    fn calculate_difficulty(
+        base: Difficulty,
+        total_game_time: i64,
+        local_game_time: i64,
+        moon_brightness: f32,
+    ) -> f32 {
+        if base == Difficulty::Peaceful {
+            return 0.0;
+        }
+
+        let is_hard = base == Difficulty::Hard;
+        let mut scale = 0.75;
+        let global_scale =
+            ((total_game_time as f32 - 72_000.0) / 1_440_000.0).clamp(0.0, 1.0) * 0.25;
+        scale += global_scale;
+
+        let mut local_scale = (local_game_time as f32 / 3_600_000.0).clamp(0.0, 1.0)
+            * if is_hard { 1.0 } else { 0.75 };
+        local_scale += (moon_brightness * 0.25).clamp(0.0, global_scale);
+        if base == Difficulty::Easy {
+            local_scale *= 0.5;
+        }
+        scale += local_scale;
+
+        f32::from(base.id()) * scale
+    }
+
+    /// Returns the world difficulty this instance was computed from.
+    #[must_use]
+    pub const fn difficulty(&self) -> Difficulty {
+        self.base
+    }
+
+    /// Returns the local difficulty, ranging from 0.0 up to 6.75 on hard.
+    #[must_use]
+    pub const fn effective_difficulty(&self) -> f32 {
+        self.effective_difficulty
+    }
+
+    /// Returns whether the local difficulty exceeds `difficulty`.
+    #[must_use]
+    pub const fn is_harder_than(&self, difficulty: f32) -> bool {
+        self.effective_difficulty > difficulty
+    }
+
+    /// Returns the clamped regional difficulty shown in the debug screen (0.0 to 1.0).
+    ///
+    /// Used to scale equipment chances, enchantments and similar special effects.
+    #[must_use]
+    pub const fn special_multiplier(&self) -> f32 {
+        if self.effective_difficulty < 2.0 {
+            0.0
+        } else if self.effective_difficulty > 4.0 {
+            1.0
+        } else {
+            (self.effective_difficulty - 2.0) / 2.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peaceful_is_always_zero() {
+        let instance = DifficultyInstance::new(Difficulty::Peaceful, 10_000_000, 10_000_000, 1.0);
+        assert!(instance.effective_difficulty().abs() < f32::EPSILON);
+        assert!(instance.special_multiplier().abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn fresh_world_uses_base_scale() {
+        let instance = DifficultyInstance::new(Difficulty::Normal, 0, 0, 1.0);
+        assert!((instance.effective_difficulty() - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fully_inhabited_hard_chunk_reaches_maximum() {
+        let instance = DifficultyInstance::new(Difficulty::Hard, 10_000_000, 10_000_000, 1.0);
+        assert!((instance.effective_difficulty() - 6.75).abs() < 1e-5);
+        assert!((instance.special_multiplier() - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,576 @@
+//! The End's dragon fight.
+//!
+//! Tracks the dragon, shows its boss bar to nearby players, counts the end
+//! crystals on the spikes and builds the exit portal and gateways once the
+//! dragon dies.
+
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use glam::DVec3;
+use rustc_hash::FxHashSet;
+use steel_protocol::packets::game::{BossBarColor, BossBarOverlay, CBossEvent};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::{REGISTRY, level_events, vanilla_blocks, vanilla_entities};
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::random::shuffle;
+use steel_utils::translations;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, ChunkPos, Direction};
+use uuid::Uuid;
+
+use crate::chunk::chunk_ticket_manager::MAX_VIEW_DISTANCE;
+use crate::chunk::heightmap::HeightmapType;
+use crate::entity::entities::{DragonPhase, EnderDragonEntity};
+use crate::entity::{Entity, LivingEntity, RemovalReason, SharedEntity};
+use crate::level_data::DragonFightData;
+use crate::server::boss_event::ServerBossEvent;
+use crate::world::World;
+use crate::worldgen::end_spikes;
+
+/// Radius in chunks around the origin kept loaded while players are nearby.
+const ARENA_RADIUS: i32 = 9;
+/// Radius in chunks around the origin that has to be loaded for the fight to run.
+const ARENA_CHECK_RADIUS: i32 = 8;
+/// Distance from the dragon's spawn within which players see the boss bar.
+const PLAYER_RANGE: f64 = 192.0;
+/// Number of end gateways around the island.
+const GATEWAY_COUNT: i32 = 20;
+/// Distance of the end gateways from the island's center.
+const GATEWAY_DISTANCE: f64 = 96.0;
+/// Height the end gateways spawn at.
+const GATEWAY_Y: i32 = 75;
+/// Height above the origin the dragon spawns at.
+const DRAGON_SPAWN_HEIGHT: i32 = 128;
+
+/// The dragon fight of a world with `has_ender_dragon_fight`.
+///
+/// Vanilla: `EndDragonFight`.
+pub struct EndDragonFight {
+    boss_event: ServerBossEvent,
+    /// Gateway indices still to be spawned, the last one is next.
+    gateways: Vec<i32>,
+    ticks_since_dragon_seen: i32,
+    crystals_alive: i32,
+    ticks_since_crystals_scanned: i32,
+    ticks_since_last_player_scan: i32,
+    dragon_killed: bool,
+    previously_killed: bool,
+    needs_state_scanning: bool,
+    dragon_uuid: Option<Uuid>,
+    portal_location: Option<BlockPos>,
+    /// Whether the arena's chunk ticket is held.
+    ticket_added: bool,
+}
+
+impl EndDragonFight {
+    /// Center of the arena, where the exit portal stands.
+    pub const FIGHT_ORIGIN: BlockPos = BlockPos::new(0, 0, 0);
+
+    /// Restores a fight from its saved `data`.
+    ///
+    /// The gateway order is shuffled with the world `seed` the first time.
+    #[must_use]
+    pub fn new(seed: i64, data: DragonFightData) -> Self {
+        let mut boss_event = ServerBossEvent::new(
+            translations::ENTITY_MINECRAFT_ENDER_DRAGON.msg().into(),
+            BossBarColor::Pink,
+            BossBarOverlay::Progress,
+        );
+        boss_event.set_flag(CBossEvent::PLAY_BOSS_MUSIC, true);
+        boss_event.set_flag(CBossEvent::CREATE_WORLD_FOG, true);
+
+        let gateways = data.gateways.unwrap_or_else(|| {
+            let mut gateways: Vec<i32> = (0..GATEWAY_COUNT).collect();
+            shuffle(&mut gateways, &mut LegacyRandom::from_seed(seed as u64));
+            gateways
+        });
+        // TODO: Resume the respawn animation when `is_respawning` is set
+        Self {
+            boss_event,
+            gateways,
+            ticks_since_dragon_seen: 0,
+            crystals_alive: 0,
+            ticks_since_crystals_scanned: 0,
+            ticks_since_last_player_scan: 21,
+            dragon_killed: data.dragon_killed,
+            previously_killed: data.previously_killed,
+            needs_state_scanning: data.needs_state_scanning,
+            dragon_uuid: data.dragon,
+            portal_location: data
+                .exit_portal_location
+                .map(|[x, y, z]| BlockPos::new(x, y, z)),
+            ticket_added: false,
+        }
+    }
+
+    /// Gets the state to save.
+    ///
+    /// Vanilla: `EndDragonFight.saveData()`.
+    #[must_use]
+    pub fn save_data(&self) -> DragonFightData {
+        DragonFightData {
+            needs_state_scanning: self.needs_state_scanning,
+            dragon_killed: self.dragon_killed,
+            previously_killed: self.previously_killed,
+            is_respawning: false,
+            dragon: self.dragon_uuid,
+            exit_portal_location: self.portal_location.map(|pos| [pos.x(), pos.y(), pos.z()]),
+            gateways: Some(self.gateways.clone()),
+        }
+    }
+
+    /// Gets the UUID of the fight's dragon.
+    #[must_use]
+    pub const fn dragon_uuid(&self) -> Option<Uuid> {
+        self.dragon_uuid
+    }
+
+    /// Gets the number of end crystals on the spikes, as of the last count.
+    #[must_use]
+    pub const fn crystals_alive(&self) -> i32 {
+        self.crystals_alive
+    }
+
+    /// Returns true if a dragon was ever killed in this world.
+    #[must_use]
+    pub const fn has_previously_killed_dragon(&self) -> bool {
+        self.previously_killed
+    }
+
+    /// Runs the fight for one tick.
+    ///
+    /// Vanilla: `EndDragonFight.tick()`.
+    pub fn tick(&mut self, world: &Arc<World>) {
+        self.boss_event.set_visible(!self.dragon_killed);
+        self.ticks_since_last_player_scan += 1;
+        if self.ticks_since_last_player_scan >= 20 {
+            self.update_players(world);
+            self.ticks_since_last_player_scan = 0;
+        }
+
+        if self.boss_event.players().is_empty() {
+            self.set_arena_ticket(world, false);
+            return;
+        }
+        self.set_arena_ticket(world, true);
+        let arena_loaded = Self::is_arena_loaded(world);
+        if self.needs_state_scanning && arena_loaded {
+            self.scan_state(world);
+            self.needs_state_scanning = false;
+        }
+        // TODO: Tick the respawn animation once dragons can be respawned
+
+        if self.dragon_killed {
+            return;
+        }
+        self.ticks_since_dragon_seen += 1;
+        if (self.dragon_uuid.is_none() || self.ticks_since_dragon_seen >= 1200) && arena_loaded {
+            if let Some(dragon) = world
+                .get_entities_of_type(vanilla_entities::ENDER_DRAGON)
+                .first()
+            {
+                log::debug!("Haven't seen our dragon, but found another one to use.");
+                self.dragon_uuid = Some(dragon.uuid());
+            } else {
+                log::debug!("Haven't seen the dragon, respawning it");
+                self.create_new_dragon(world);
+            }
+            self.ticks_since_dragon_seen = 0;
+        }
+        self.ticks_since_crystals_scanned += 1;
+        if self.ticks_since_crystals_scanned >= 100 && arena_loaded {
+            self.update_crystal_count(world);
+        }
+    }
+
+    /// Shows the boss bar to the living players near the arena and hides it
+    /// from everyone else.
+    ///
+    /// Vanilla: `EndDragonFight.updatePlayers()`.
+    fn update_players(&mut self, world: &World) {
+        let center = DVec3::new(
+            f64::from(Self::FIGHT_ORIGIN.x()),
+            f64::from(Self::FIGHT_ORIGIN.y() + DRAGON_SPAWN_HEIGHT),
+            f64::from(Self::FIGHT_ORIGIN.z()),
+        );
+        let mut in_range = FxHashSet::default();
+        world.players.iter_players(|uuid, player| {
+            if player.is_alive()
+                && player.position().distance_squared(center) <= PLAYER_RANGE * PLAYER_RANGE
+            {
+                self.boss_event.add_player(player);
+                in_range.insert(*uuid);
+            }
+            true
+        });
+        for player in self.boss_event.players() {
+            if !in_range.contains(&player.uuid()) {
+                self.boss_event.remove_player(&player);
+            }
+        }
+    }
+
+    /// Keeps the arena loaded and ticking while players are around.
+    fn set_arena_ticket(&mut self, world: &World, add: bool) {
+        if self.ticket_added == add {
+            return;
+        }
+        self.ticket_added = add;
+        let pos = ChunkPos::new(0, 0);
+        let level = MAX_VIEW_DISTANCE - ARENA_RADIUS as u8;
+        if add {
+            world.chunk_map.add_simulating_ticket(pos, level);
+        } else {
+            world.chunk_map.remove_simulating_ticket(pos, level);
+        }
+    }
+
+    /// Returns true if every chunk of the arena is loaded.
+    ///
+    /// Vanilla: `EndDragonFight.isArenaLoaded()`.
+    fn is_arena_loaded(world: &World) -> bool {
+        let origin = ChunkPos::new(Self::FIGHT_ORIGIN.x() >> 4, Self::FIGHT_ORIGIN.z() >> 4);
+        (-ARENA_CHECK_RADIUS..=ARENA_CHECK_RADIUS).all(|x| {
+            (-ARENA_CHECK_RADIUS..=ARENA_CHECK_RADIUS).all(|z| {
+                let pos = ChunkPos::new(origin.0.x + x, origin.0.y + z);
+                world.chunk_map.with_full_chunk(pos, |_| ()).is_some()
+            })
+        })
+    }
+
+    /// Works out the fight's state from the world, for worlds that didn't save it.
+    ///
+    /// Vanilla: `EndDragonFight.scanState()`.
+    fn scan_state(&mut self, world: &Arc<World>) {
+        log::info!("Scanning for legacy world dragon fight...");
+        let active_portal = self.has_active_exit_portal(world);
+        if active_portal {
+            log::info!("Found that the dragon has been killed in this world already.");
+            self.previously_killed = true;
+        } else {
+            log::info!("Found that the dragon has not yet been killed in this world.");
+            self.previously_killed = false;
+            if self.find_exit_portal(world).is_none() {
+                self.spawn_exit_portal(world, false);
+            }
+        }
+
+        let dragons = world.get_entities_of_type(vanilla_entities::ENDER_DRAGON);
+        if let Some(dragon) = dragons.first() {
+            self.dragon_uuid = Some(dragon.uuid());
+            log::info!(
+                "Found that there's a dragon still alive ({})",
+                dragon.uuid()
+            );
+            self.dragon_killed = false;
+            if !active_portal {
+                log::info!("But we didn't have a portal, let's remove it.");
+                dragon.set_removed(RemovalReason::Discarded);
+                self.dragon_uuid = None;
+            }
+        } else {
+            self.dragon_killed = true;
+        }
+
+        if !self.previously_killed && self.dragon_killed {
+            self.dragon_killed = false;
+        }
+    }
+
+    /// Returns true if the exit portal is lit.
+    ///
+    /// Vanilla: `EndDragonFight.hasActiveExitPortal()`, which looks for end
+    /// portal block entities in the whole arena.
+    fn has_active_exit_portal(&mut self, world: &World) -> bool {
+        self.find_exit_portal(world).is_some_and(|pos| {
+            Direction::HORIZONTAL.iter().any(|&direction| {
+                world.get_block_state(pos.relative(direction)).get_block()
+                    == vanilla_blocks::END_PORTAL
+            })
+        })
+    }
+
+    /// Finds the exit portal's bedrock frame, remembering where it is.
+    ///
+    /// Vanilla: `EndDragonFight.findExitPortal()`.
+    fn find_exit_portal(&mut self, world: &World) -> Option<BlockPos> {
+        if let Some(pos) = self.portal_location
+            && is_exit_portal(world, pos)
+        {
+            return Some(pos);
+        }
+        let surface = world.get_height(HeightmapType::MotionBlocking, Self::FIGHT_ORIGIN);
+        let found = (world.get_min_y()..=surface)
+            .rev()
+            .map(|y| Self::FIGHT_ORIGIN.at_y(y))
+            .find(|&pos| is_exit_portal(world, pos));
+        if let Some(pos) = found {
+            log::debug!("Found the exit portal & saved its location for next time.");
+            self.portal_location = Some(pos);
+        }
+        found
+    }
+
+    /// Builds the exit portal, lit if `active`.
+    ///
+    /// Vanilla: `EndDragonFight.spawnExitPortal()`.
+    fn spawn_exit_portal(&mut self, world: &Arc<World>, active: bool) {
+        let origin = *self.portal_location.get_or_insert_with(|| {
+            let mut pos =
+                EnderDragonEntity::egg_pos(world, HeightmapType::MotionBlockingNoLeaves).below();
+            while world.get_block_state(pos).get_block() == vanilla_blocks::BEDROCK && pos.y() > 63
+            {
+                pos = pos.below();
+            }
+            pos.at_y(pos.y().max(world.get_min_y() + 1))
+        });
+        place_end_podium(world, origin, active);
+    }
+
+    /// Spawns the next end gateway.
+    ///
+    /// Vanilla: `EndDragonFight.spawnNewGateway()`.
+    fn spawn_new_gateway(&mut self, world: &Arc<World>) {
+        let Some(index) = self.gateways.pop() else {
+            return;
+        };
+        let angle = 2.0 * (-PI + PI / 20.0 * f64::from(index));
+        let pos = BlockPos::new(
+            (GATEWAY_DISTANCE * angle.cos()).floor() as i32,
+            GATEWAY_Y,
+            (GATEWAY_DISTANCE * angle.sin()).floor() as i32,
+        );
+        world.level_event(level_events::ANIMATION_END_GATEWAY_SPAWN, pos, 0, None);
+        place_end_gateway(world, pos);
+    }
+
+    /// Spawns a new dragon above the exit portal.
+    ///
+    /// Vanilla: `EndDragonFight.createNewDragon()`.
+    fn create_new_dragon(&mut self, world: &Arc<World>) {
+        let position = DVec3::new(
+            f64::from(Self::FIGHT_ORIGIN.x()),
+            f64::from(DRAGON_SPAWN_HEIGHT + Self::FIGHT_ORIGIN.y()),
+            f64::from(Self::FIGHT_ORIGIN.z()),
+        );
+        let dragon = Arc::new(EnderDragonEntity::new(0, position, Arc::downgrade(world)));
+        dragon.set_dragon_phase(DragonPhase::HoldingPattern);
+        dragon.set_rotation((rand::random::<f32>() * 360.0, 0.0));
+        let uuid = dragon.uuid();
+        let entity: SharedEntity = dragon;
+        if world.add_entity(entity) {
+            self.dragon_uuid = Some(uuid);
+        }
+    }
+
+    /// Counts the end crystals on top of the spikes.
+    ///
+    /// Vanilla: `EndDragonFight.updateCrystalCount()`.
+    fn update_crystal_count(&mut self, world: &World) {
+        self.ticks_since_crystals_scanned = 0;
+        self.crystals_alive = end_spikes(world.seed())
+            .iter()
+            .map(|spike| {
+                let area = spike.top_bounding_box(world.get_min_y(), world.get_max_y());
+                world
+                    .get_entities_in_aabb(&area)
+                    .iter()
+                    .filter(|entity| entity.entity_type().key == vanilla_entities::END_CRYSTAL.key)
+                    .count() as i32
+            })
+            .sum();
+        log::debug!("Found {} end crystals still alive", self.crystals_alive);
+    }
+
+    /// Called when the fight's dragon finished dying.
+    ///
+    /// Lights the exit portal, opens a gateway and places the dragon egg after
+    /// the first kill.
+    /// Vanilla: `EndDragonFight.setDragonKilled()`.
+    pub fn set_dragon_killed(&mut self, world: &Arc<World>, dragon: Uuid) {
+        if self.dragon_uuid != Some(dragon) {
+            return;
+        }
+        self.boss_event.set_progress(0.0);
+        self.boss_event.set_visible(false);
+        self.spawn_exit_portal(world, true);
+        self.spawn_new_gateway(world);
+        if !self.previously_killed {
+            world.set_block(
+                EnderDragonEntity::egg_pos(world, HeightmapType::MotionBlocking),
+                REGISTRY
+                    .blocks
+                    .get_default_state_id(vanilla_blocks::DRAGON_EGG),
+                UpdateFlags::UPDATE_ALL,
+            );
+        }
+        self.previously_killed = true;
+        self.dragon_killed = true;
+    }
+
+    /// Updates the boss bar from the dragon's health and name.
+    ///
+    /// Vanilla: `EndDragonFight.updateDragon()`.
+    pub fn update_dragon(&mut self, dragon: &EnderDragonEntity) {
+        if self.dragon_uuid != Some(dragon.uuid()) {
+            return;
+        }
+        self.boss_event
+            .set_progress(dragon.get_health() / dragon.get_max_health());
+        self.ticks_since_dragon_seen = 0;
+        if let Some(name) = dragon.custom_name() {
+            self.boss_event.set_name(name);
+        }
+    }
+
+    /// Called when an end crystal was destroyed, recounting the crystals.
+    ///
+    /// Returns the dragon to tell about it, since it must not be called while
+    /// the fight is locked.
+    /// Vanilla: `EndDragonFight.onCrystalDestroyed()`.
+    pub fn on_crystal_destroyed(&mut self, world: &World) -> Option<Uuid> {
+        // TODO: Restart the respawn animation if one of its crystals was destroyed
+        self.update_crystal_count(world);
+        self.dragon_uuid
+    }
+}
+
+/// Returns true if the bedrock frame of an exit portal is at `pos`, the top of
+/// its base.
+///
+/// Vanilla: `EndDragonFight.exitPortalPattern`.
+fn is_exit_portal(world: &World, pos: BlockPos) -> bool {
+    let is_bedrock =
+        |pos: BlockPos| world.get_block_state(pos).get_block() == vanilla_blocks::BEDROCK;
+    (0..=3).all(|dy| is_bedrock(pos.above_n(dy)))
+        && (-3..=3).all(|dx: i32| {
+            (-3..=3).all(|dz: i32| {
+                let dist = dx * dx + dz * dz;
+                (!(8..=10).contains(&dist) || is_bedrock(pos.offset(dx, 0, dz)))
+                    && (dist > 5 || is_bedrock(pos.offset(dx, -1, dz)))
+            })
+        })
+}
+
+/// Builds the exit portal with its bedrock pillar and torches at `origin`.
+///
+/// Vanilla: `EndPodiumFeature.place()`.
+fn place_end_podium(world: &Arc<World>, origin: BlockPos, active: bool) {
+    let state = |block| REGISTRY.blocks.get_default_state_id(block);
+    for dx in -4..=4 {
+        for dz in -4..=4 {
+            let dist = dx * dx + dz * dz;
+            // Vanilla: BlockPos.closerThan(origin, 2.5) and (origin, 3.5)
+            let inside = dist < 7;
+            if !inside && dist >= 13 {
+                continue;
+            }
+            for dy in -1..=32 {
+                let pos = origin.offset(dx, dy, dz);
+                let block = if dy < 0 {
+                    if inside {
+                        vanilla_blocks::BEDROCK
+                    } else {
+                        vanilla_blocks::END_STONE
+                    }
+                } else if dy > 0 {
+                    vanilla_blocks::AIR
+                } else if !inside {
+                    vanilla_blocks::BEDROCK
+                } else if active {
+                    vanilla_blocks::END_PORTAL
+                } else {
+                    vanilla_blocks::AIR
+                };
+                world.set_block(pos, state(block), UpdateFlags::UPDATE_ALL);
+            }
+        }
+    }
+
+    for dy in 0..4 {
+        world.set_block(
+            origin.above_n(dy),
+            state(vanilla_blocks::BEDROCK),
+            UpdateFlags::UPDATE_ALL,
+        );
+    }
+    let torch_center = origin.above_n(2);
+    for direction in Direction::HORIZONTAL {
+        let torch = state(vanilla_blocks::WALL_TORCH)
+            .set_value(&BlockStateProperties::HORIZONTAL_FACING, direction);
+        world.set_block(
+            torch_center.relative(direction),
+            torch,
+            UpdateFlags::UPDATE_ALL,
+        );
+    }
+}
+
+/// Builds an end gateway in its bedrock frame at `origin`.
+///
+/// Vanilla: `EndGatewayFeature.place()`.
+// TODO: Search for an exit once end gateways teleport
+fn place_end_gateway(world: &Arc<World>, origin: BlockPos) {
+    let state = |block| REGISTRY.blocks.get_default_state_id(block);
+    for dx in -1..=1 {
+        for dy in -2..=2 {
+            for dz in -1..=1 {
+                let same_x = dx == 0;
+                let same_y = dy == 0;
+                let same_z = dz == 0;
+                let edge_y = dy.abs() == 2;
+                let block = if same_x && same_y && same_z {
+                    vanilla_blocks::END_GATEWAY
+                } else if same_y {
+                    vanilla_blocks::AIR
+                } else if (edge_y && same_x && same_z) || ((same_x || same_z) && !edge_y) {
+                    vanilla_blocks::BEDROCK
+                } else {
+                    vanilla_blocks::AIR
+                };
+                world.set_block(
+                    origin.offset(dx, dy, dz),
+                    state(block),
+                    UpdateFlags::UPDATE_ALL,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_fight_shuffles_every_gateway() {
+        let fight = EndDragonFight::new(42, DragonFightData::default());
+        let mut gateways = fight.gateways.clone();
+        gateways.sort_unstable();
+        assert_eq!(gateways, (0..GATEWAY_COUNT).collect::<Vec<_>>());
+        assert_eq!(
+            fight.gateways,
+            EndDragonFight::new(42, DragonFightData::default()).gateways
+        );
+    }
+
+    #[test]
+    fn save_data_round_trips() {
+        let data = DragonFightData {
+            needs_state_scanning: false,
+            dragon_killed: true,
+            previously_killed: true,
+            is_respawning: false,
+            dragon: Some(Uuid::from_u128(7)),
+            exit_portal_location: Some([0, 64, 0]),
+            gateways: Some(vec![3, 1, 2]),
+        };
+        let saved = EndDragonFight::new(0, data.clone()).save_data();
+        assert_eq!(saved.dragon, data.dragon);
+        assert_eq!(saved.exit_portal_location, data.exit_portal_location);
+        assert_eq!(saved.gateways, data.gateways);
+        assert!(saved.dragon_killed && saved.previously_killed);
+    }
+}
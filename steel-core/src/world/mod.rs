@@ -1,5 +1,6 @@
 //! This module contains the `World` struct, which represents a world.
 
+use std::collections::VecDeque;
 use std::path::Path;
 use std::{
     io,
@@ -10,12 +11,25 @@ use std::{
     time::Duration,
 };
 
-use crate::{chunk::chunk_map::ChunkMapTickTimings, world::weather::Weather};
+use crate::{
+    chunk::{
+        chunk_access::{ChunkAccess, ChunkStatus},
+        chunk_limits::ChunkLimitSnapshot,
+        chunk_map::ChunkMapTickTimings,
+        heightmap::HeightmapType,
+        section::ChunkMemory,
+    },
+    world::{
+        difficulty::{DifficultyInstance, MOON_BRIGHTNESS_PER_PHASE},
+        weather::Weather,
+    },
+};
 
 use sha2::{Digest, Sha256};
 use steel_protocol::packets::game::{
-    CBlockDestruction, CBlockEvent, CGameEvent, CLevelEvent, CPlayerChat, CPlayerInfoUpdate,
-    CRemoveEntities, CSound, CSystemChat, GameEventType, SoundSource,
+    CBlockDestruction, CBlockEvent, CGameEvent, CLevelEvent, CPlayerInfoUpdate, CRemoveEntities,
+    CSetSimulationDistance, CSound, CSoundEntity, CSystemChat, ChatTypeBound, FilterType,
+    GameEventType, SoundSource,
 };
 use steel_protocol::utils::ConnectionProtocol;
 use steel_protocol::{
@@ -24,16 +38,20 @@ use steel_protocol::{
 };
 
 use simdnbt::owned::NbtCompound;
+use steel_registry::biome::BiomeRef;
 use steel_registry::blocks::block_state_ext::BlockStateExt;
 use steel_registry::blocks::properties::Direction;
 use steel_registry::blocks::shapes::{AABBd, VoxelShape};
+use steel_registry::entity_types::EntityTypeRef;
 use steel_registry::fluid::FluidRef;
 use steel_registry::game_rules::{GameRuleRef, GameRuleValue};
 use steel_registry::item_stack::ItemStack;
 use steel_registry::level_events;
-use steel_registry::loot_table::LootContext;
+use steel_registry::loot_table::{BlockEntityRef, LootContext};
 use steel_registry::vanilla_blocks;
-use steel_registry::vanilla_game_rules::{BLOCK_DROPS, RANDOM_TICK_SPEED};
+use steel_registry::vanilla_game_rules::{
+    BLOCK_DROPS, FIRE_SPREAD_RADIUS_AROUND_PLAYER, RANDOM_TICK_SPEED,
+};
 use steel_registry::{REGISTRY, RegistryEntry, RegistryExt, dimension_type::DimensionTypeRef};
 use steel_registry::{block_entity_type::BlockEntityTypeRef, vanilla_dimension_types};
 use steel_registry::{
@@ -56,7 +74,11 @@ pub enum RaytraceAction {
 }
 
 use glam::DVec3;
-use steel_utils::{BlockPos, BlockStateId, ChunkPos, SectionPos, types::UpdateFlags};
+use steel_utils::{
+    BlockPos, BlockStateId, ChunkPos, SectionPos,
+    types::{GameType, UpdateFlags},
+};
+use text_components::TextComponent;
 use tokio::{runtime::Runtime, time::Instant};
 
 use crate::{
@@ -66,31 +88,43 @@ use crate::{
     block_entity::SharedBlockEntity,
     chunk_saver::{ChunkStorage, RamOnlyStorage, RegionManager},
     config::STEEL_CONFIG,
-    entity::{EntityCache, EntityTracker, RemovalReason, SharedEntity, entities::ItemEntity},
+    entity::{
+        Entity, EntityCache, EntityTracker, RemovalReason, SharedEntity, entities::ItemEntity,
+    },
     fluid::fluid_state_to_block,
     level_data::LevelDataManager,
-    player::{LastSeen, Player, connection::NetworkConnection},
+    player::{Player, connection::NetworkConnection, message_chain::PlayerChatMessage},
     poi::PointOfInterestStorage,
+    profiling::{PhaseTimer, TickPhase},
+    server::chunk_distances::{CHUNK_DISTANCES, DistanceError},
 };
 
+pub mod difficulty;
+pub mod dragon_fight;
+pub mod neighbor_updater;
 mod player_area_map;
 mod player_map;
+pub mod redstone;
 pub mod structure;
 pub mod tick_scheduler;
+mod waypoints;
 mod weather;
 mod world_entities;
 
 use crate::chunk::world_gen_context::ChunkGeneratorType;
 pub use crate::config::WorldStorageConfig;
+pub use dragon_fight::EndDragonFight;
+pub use neighbor_updater::{NeighborUpdateStats, NeighborUpdater};
 pub use player_area_map::PlayerAreaMap;
 pub use player_map::PlayerMap;
 pub use tick_scheduler::ScheduledTick;
+pub use waypoints::{CustomWaypoint, PLAYER_WAYPOINT_RANGE, WaypointManager};
 
 /// Generates a random value using triangle distribution.
 ///
 /// Mirrors vanilla's `RandomSource.triangle(mode, deviation)`.
 /// Produces values centered around `mode` with a spread of `deviation`.
-fn triangle_random(mode: f64, deviation: f64) -> f64 {
+pub(crate) fn triangle_random(mode: f64, deviation: f64) -> f64 {
     mode + deviation * (rand::random::<f64>() - rand::random::<f64>())
 }
 
@@ -107,6 +141,22 @@ pub struct WorldTickTimings {
 /// Matches vanilla `PlayerList.SEND_PLAYER_INFO_INTERVAL`.
 const SEND_PLAYER_INFO_INTERVAL: u64 = 600;
 
+/// How often loaded chunks are checked for sections to compact, in ticks.
+const SECTION_COMPACTION_INTERVAL: u64 = 20 * 30;
+
+/// Gets how far away a sound played at `volume` can be heard.
+///
+/// Vanilla: `SoundEvent.getRange()`.
+// TODO: Sound events with a fixed range (none are sent by the server yet)
+#[must_use]
+pub fn sound_range(volume: f32) -> f64 {
+    if volume > 1.0 {
+        f64::from(16.0 * volume)
+    } else {
+        16.0
+    }
+}
+
 /// Configuration for creating a new world.
 #[derive(Clone)]
 pub struct WorldConfig {
@@ -114,6 +164,8 @@ pub struct WorldConfig {
     pub storage: WorldStorageConfig,
     /// World generator.
     pub generator: Arc<ChunkGeneratorType>,
+    /// The simulation distance of this world, if it differs from the server's.
+    pub simulation_distance: Option<u8>,
 }
 
 /// A struct that represents a world.
@@ -131,6 +183,8 @@ pub struct World {
     /// Whether the tick rate is running normally (not frozen/paused).
     /// When false, movement validation checks are skipped.
     tick_runs_normally: AtomicBool,
+    /// The simulation distance of this world, or `None` to use the server's.
+    simulation_distance: SyncMutex<Option<u8>>,
     /// Entity cache for fast entity lookups by ID, UUID, or spatial position.
     /// Uses `Weak` references - entities are owned by chunks.
     entity_cache: EntityCache,
@@ -144,6 +198,25 @@ pub struct World {
     sub_tick_count: AtomicI64,
     /// Point of interest storage for efficient spatial queries of special blocks.
     pub poi_storage: SyncMutex<PointOfInterestStorage>,
+    /// Locator bar waypoints and which players see them.
+    pub waypoints: WaypointManager,
+    /// Block events waiting to be run at the end of the tick.
+    block_events: SyncMutex<VecDeque<BlockEventData>>,
+    /// Queue for neighbor and shape updates.
+    neighbor_updater: NeighborUpdater,
+    /// The dragon fight, only in dimensions with `has_ender_dragon_fight`.
+    dragon_fight: Option<SyncMutex<EndDragonFight>>,
+}
+
+/// A block event queued by [`World::block_event`].
+///
+/// Vanilla equivalent: `BlockEventData`.
+#[derive(Clone, Copy, PartialEq)]
+struct BlockEventData {
+    pos: BlockPos,
+    block: BlockRef,
+    action_id: u8,
+    action_param: u8,
 }
 
 impl World {
@@ -192,6 +265,11 @@ impl World {
         //         .get_default_state_id(vanilla_blocks::GRASS_BLOCK), // Grass Block
         // )));
 
+        let dragon_fight = dimension.has_ender_dragon_fight.then(|| {
+            let data = level_data.data().dragon_fight.clone().unwrap_or_default();
+            SyncMutex::new(EndDragonFight::new(seed, data))
+        });
+
         let mut weather = Weather::default();
         if level_data.is_raining() {
             weather.rain_level = 1.0;
@@ -214,11 +292,16 @@ impl World {
             dimension,
             level_data: SyncRwLock::new(level_data),
             tick_runs_normally: AtomicBool::new(true),
+            simulation_distance: SyncMutex::new(config.simulation_distance),
             entity_cache: EntityCache::new(),
             entity_tracker: EntityTracker::new(),
             weather: SyncMutex::new(weather),
             sub_tick_count: AtomicI64::new(0),
             poi_storage: SyncMutex::new(PointOfInterestStorage::new()),
+            waypoints: WaypointManager::new(),
+            block_events: SyncMutex::new(VecDeque::new()),
+            neighbor_updater: NeighborUpdater::new(&STEEL_CONFIG.neighbor_updates),
+            dragon_fight,
         }))
     }
 
@@ -228,6 +311,9 @@ impl World {
         reason = "holding the write lock across await is safe here because it only happens during shutdown"
     )]
     pub async fn cleanup(&self, total_saved: &mut usize) {
+        if let Some(fight) = &self.dragon_fight {
+            self.level_data.write().data_mut().dragon_fight = Some(fight.lock().save_data());
+        }
         match self.level_data.write().save().await {
             Ok(()) => log::info!(
                 "World {} level data saved successfully",
@@ -290,50 +376,41 @@ impl World {
         self.is_in_valid_bounds(pos)
     }
 
-    /// Player dimensions matching vanilla Minecraft.
-    const PLAYER_WIDTH: f64 = 0.6;
-    const PLAYER_HEIGHT: f64 = 1.8;
-
     /// Checks if a block's collision shape at the given position is unobstructed by entities.
     ///
     /// This is the Rust equivalent of vanilla's `Level.isUnobstructed(BlockState, BlockPos, CollisionContext)`.
-    /// In vanilla, this checks all entities with `blocksBuilding=true` (players, mobs, boats, etc.).
-    /// Currently only checks players since other entities aren't fully implemented.
+    /// Vanilla only counts entities with `blocksBuilding=true` (players, mobs, boats, etc.);
+    /// pickable entities are used as the closest match, so items, orbs and arrows don't count.
+    /// Spectators never obstruct placement.
     ///
     /// Returns `true` if the position is clear, `false` if an entity would obstruct placement.
     #[must_use]
     pub fn is_unobstructed(&self, collision_shape: VoxelShape, pos: BlockPos) -> bool {
-        if collision_shape.is_empty() {
-            return true;
-        }
-
-        // TODO: Check other entities with blocksBuilding=true (mobs, boats, minecarts, etc.)
-        let mut obstructed = false;
-        self.players.iter_players(|_uuid, player| {
-            let player_pos = player.position.lock();
-            let half_width = Self::PLAYER_WIDTH / 2.0;
-            let player_aabb = AABBd::new(
-                player_pos.x - half_width,
-                player_pos.y,
-                player_pos.z - half_width,
-                player_pos.x + half_width,
-                player_pos.y + Self::PLAYER_HEIGHT,
-                player_pos.z + half_width,
+        collision_shape.iter().all(|block_aabb| {
+            let world_aabb = block_aabb.at_block(pos.x(), pos.y(), pos.z());
+            let world_aabb = AABBd::new(
+                f64::from(world_aabb.min_x),
+                f64::from(world_aabb.min_y),
+                f64::from(world_aabb.min_z),
+                f64::from(world_aabb.max_x),
+                f64::from(world_aabb.max_y),
+                f64::from(world_aabb.max_z),
             );
+            self.get_entities_in_aabb(&world_aabb)
+                .into_iter()
+                .all(|entity| !Self::blocks_building(entity))
+        })
+    }
 
-            // Check if any block AABB intersects with the player
-            for block_aabb in collision_shape {
-                let world_aabb = block_aabb.at_block(pos.x(), pos.y(), pos.z());
-                if player_aabb.intersects_block_aabb(&world_aabb) {
-                    obstructed = true;
-                    return false; // stop iteration
-                }
-            }
-
-            true // continue iteration
-        });
-
-        !obstructed
+    /// Returns true if `entity` keeps blocks from being placed where it stands.
+    // TODO: Replace with a real blocksBuilding flag once entity types carry one
+    fn blocks_building(entity: SharedEntity) -> bool {
+        if entity.is_removed() || !entity.entity_type().flags.is_pickable {
+            return false;
+        }
+        entity
+            .as_player()
+            .is_none_or(|player| player.game_mode.load() != GameType::Spectator)
     }
 
     /// Returns whether the tick rate is running normally.
@@ -353,6 +430,50 @@ impl World {
             .store(runs_normally, Ordering::Relaxed);
     }
 
+    /// Returns the distance around players in which this world ticks chunks.
+    ///
+    /// This is the world's own simulation distance if it has one, otherwise the
+    /// server's, and never more than the server's view distance.
+    #[must_use]
+    pub fn simulation_distance(&self) -> u8 {
+        self.simulation_distance
+            .lock()
+            .unwrap_or_else(|| CHUNK_DISTANCES.simulation_distance())
+            .min(CHUNK_DISTANCES.view_distance())
+    }
+
+    /// Returns the simulation distance of this world, or `None` if it uses the
+    /// server's.
+    #[must_use]
+    pub fn own_simulation_distance(&self) -> Option<u8> {
+        *self.simulation_distance.lock()
+    }
+
+    /// Gives this world its own simulation distance, or makes it use the
+    /// server's again with `None`. Players are told about the new distance and
+    /// their simulation tickets move on their next tick.
+    ///
+    /// # Errors
+    /// - If `distance` is larger than the server's view distance.
+    pub fn set_simulation_distance(&self, distance: Option<u8>) -> Result<(), DistanceError> {
+        if distance.is_some_and(|distance| distance > CHUNK_DISTANCES.view_distance()) {
+            return Err(DistanceError::SimulationExceedsView);
+        }
+        let old_distance = self.simulation_distance();
+        *self.simulation_distance.lock() = distance;
+        if self.simulation_distance() != old_distance {
+            self.broadcast_simulation_distance();
+        }
+        Ok(())
+    }
+
+    /// Sends the simulation distance of this world to all its players.
+    pub fn broadcast_simulation_distance(&self) {
+        self.broadcast_to_all(CSetSimulationDistance {
+            simulation_distance: i32::from(self.simulation_distance()),
+        });
+    }
+
     /// Gets the value of a game rule.
     /// WARNING: this function acquires a read lock on the level data.
     /// if you already have a write lock on level data, this will DEADLOCK
@@ -398,6 +519,12 @@ impl World {
             .set(rule, value, &REGISTRY.game_rules)
     }
 
+    /// Gets the dragon fight, if this world has one.
+    #[must_use]
+    pub const fn dragon_fight(&self) -> Option<&SyncMutex<EndDragonFight>> {
+        self.dragon_fight.as_ref()
+    }
+
     /// Gets the world seed.
     #[must_use]
     pub fn seed(&self) -> i64 {
@@ -440,15 +567,19 @@ impl World {
 
     /// Sets a block at the given position.
     ///
+    /// `flags` pick which side effects run; see [`UpdateFlags`]. Bulk edits can use
+    /// `UPDATE_CLIENTS | UPDATE_SKIP_ALL_SIDEEFFECTS` to avoid neighbor and shape
+    /// update cascades and item drops.
+    ///
     /// Returns `true` if the block was successfully set, `false` otherwise.
-    /// Uses the default update limit of 512 (matching vanilla).
+    /// Uses the default update limit of [`Self::UPDATE_LIMIT`].
     pub fn set_block(
         self: &Arc<Self>,
         pos: BlockPos,
         block_state: BlockStateId,
         flags: UpdateFlags,
     ) -> bool {
-        self.set_block_with_limit(pos, block_state, flags, 512)
+        self.set_block_with_limit(pos, block_state, flags, Self::UPDATE_LIMIT)
     }
 
     /// Sets a block at the given position with a custom update limit.
@@ -483,9 +614,11 @@ impl World {
             return false;
         };
 
-        // Record the block change for broadcasting to clients
         log::debug!("Block changed at {pos:?}: {old_state:?} -> {block_state:?}");
-        self.chunk_map.block_changed(pos);
+        // Record the block change for broadcasting to clients (when UPDATE_CLIENTS is set)
+        if flags.contains(UpdateFlags::UPDATE_CLIENTS) {
+            self.chunk_map.block_changed(pos);
+        }
 
         // Neighbor updates (when UPDATE_NEIGHBORS is set)
         if flags.contains(UpdateFlags::UPDATE_NEIGHBORS) {
@@ -495,31 +628,52 @@ impl World {
         }
 
         // Shape updates (unless UPDATE_KNOWN_SHAPE is set)
-        if !flags.contains(UpdateFlags::UPDATE_KNOWN_SHAPE) && update_limit > 0 {
-            // Clear UPDATE_NEIGHBORS and UPDATE_SUPPRESS_DROPS for propagation
-            let neighbor_flags =
-                flags & !(UpdateFlags::UPDATE_NEIGHBORS | UpdateFlags::UPDATE_SUPPRESS_DROPS);
-
-            // Notify all 6 neighbors about our shape change
-            for direction in Direction::UPDATE_SHAPE_ORDER {
-                let neighbor_pos = pos.relative(direction);
-
-                // Tell the neighbor that we (at pos) changed
-                self.neighbor_shape_changed(
-                    direction.opposite(), // Direction from us to neighbor
-                    neighbor_pos,         // Neighbor's position
-                    pos,                  // Our position (the one that changed)
-                    block_state,          // Our new state
-                    neighbor_flags,
-                    update_limit - 1,
-                );
-            }
+        if !flags.contains(UpdateFlags::UPDATE_KNOWN_SHAPE) {
+            self.update_neighbor_shapes(pos, block_state, flags, update_limit);
         }
         true
     }
 
+    /// Lets all 6 neighbors of `pos` react to the shape of `block_state`.
+    ///
+    /// Runs automatically in [`Self::set_block`] unless `UPDATE_KNOWN_SHAPE` is set.
+    /// Vanilla: `BlockState.updateNeighbourShapes()`.
+    pub fn update_neighbor_shapes(
+        self: &Arc<Self>,
+        pos: BlockPos,
+        block_state: BlockStateId,
+        flags: UpdateFlags,
+        update_limit: i32,
+    ) {
+        if update_limit <= 0 {
+            return;
+        }
+
+        // Clear UPDATE_NEIGHBORS and UPDATE_SUPPRESS_DROPS for propagation
+        let neighbor_flags =
+            flags & !(UpdateFlags::UPDATE_NEIGHBORS | UpdateFlags::UPDATE_SUPPRESS_DROPS);
+
+        // Notify all 6 neighbors about our shape change
+        for direction in Direction::UPDATE_SHAPE_ORDER {
+            let neighbor_pos = pos.relative(direction);
+
+            // Tell the neighbor that we (at pos) changed
+            self.neighbor_shape_changed(
+                direction.opposite(), // Direction from us to neighbor
+                neighbor_pos,         // Neighbor's position
+                pos,                  // Our position (the one that changed)
+                block_state,          // Our new state
+                neighbor_flags,
+                update_limit - 1,
+            );
+        }
+    }
+
+    /// How deep shape updates may recurse (matches vanilla's `Block.UPDATE_LIMIT`).
+    pub const UPDATE_LIMIT: i32 = 512;
+
     /// Order in which neighbors are updated (matches vanilla's `NeighborUpdater.UPDATE_ORDER`).
-    const NEIGHBOR_UPDATE_ORDER: [Direction; 6] = [
+    pub(crate) const NEIGHBOR_UPDATE_ORDER: [Direction; 6] = [
         Direction::West,
         Direction::East,
         Direction::Down,
@@ -528,20 +682,10 @@ impl World {
         Direction::South,
     ];
 
-    /// Updates all neighbors of the given position about a block change.
-    ///
-    /// This is the Rust equivalent of vanilla's `Level.updateNeighborsAt()`.
-    pub fn update_neighbors_at(self: &Arc<Self>, pos: BlockPos, source_block: BlockRef) {
-        for direction in Self::NEIGHBOR_UPDATE_ORDER {
-            let neighbor_pos = pos.relative(direction);
-            self.neighbor_changed(neighbor_pos, source_block, false);
-        }
-    }
-
     /// Called when a neighbor's shape changes, to update this block's state.
     ///
     /// This is the Rust equivalent of vanilla's `NeighborUpdater.executeShapeUpdate()`.
-    fn neighbor_shape_changed(
+    fn execute_shape_update(
         self: &Arc<Self>,
         direction: Direction,
         pos: BlockPos,
@@ -601,29 +745,46 @@ impl World {
         }
 
         if new_state.is_air() {
-            self.destroy_block(pos, !flags.contains(UpdateFlags::UPDATE_SUPPRESS_DROPS));
+            self.destroy_block_with_limit(
+                pos,
+                !flags.contains(UpdateFlags::UPDATE_SUPPRESS_DROPS),
+                recursion_left,
+            );
         } else {
             self.set_block_with_limit(pos, new_state, flags, recursion_left);
         }
     }
 
-    /// Notifies a block that one of its neighbors changed.
+    /// Returns `state` updated to fit the blocks currently around `pos`.
     ///
-    /// This is the Rust equivalent of vanilla's `Level.neighborChanged()`.
-    pub(crate) fn neighbor_changed(
+    /// This is the Rust equivalent of vanilla's `Block.updateFromNeighbourShapes()`.
+    #[must_use]
+    pub fn update_from_neighbor_shapes(
         self: &Arc<Self>,
+        state: BlockStateId,
         pos: BlockPos,
-        source_block: BlockRef,
-        moved_by_piston: bool,
-    ) {
-        if !self.is_in_valid_bounds(pos) {
-            return;
+    ) -> BlockStateId {
+        let mut result = state;
+        for direction in Direction::UPDATE_SHAPE_ORDER {
+            let neighbor_pos = pos.relative(direction);
+            let neighbor_state = self.get_block_state(neighbor_pos);
+            result = BLOCK_BEHAVIORS
+                .get_behavior(result.get_block())
+                .update_shape(result, self, pos, direction, neighbor_pos, neighbor_state);
         }
+        result
+    }
 
-        let state = self.get_block_state(pos);
-        let block_behaviors = &*BLOCK_BEHAVIORS;
-        let behavior = block_behaviors.get_behavior(state.get_block());
-        behavior.handle_neighbor_changed(state, self, pos, source_block, moved_by_piston);
+    /// Removes the block at the given position, leaving its fluid behind.
+    ///
+    /// This is the Rust equivalent of vanilla's `Level.removeBlock()`.
+    pub fn remove_block(self: &Arc<Self>, pos: BlockPos, moved_by_piston: bool) -> bool {
+        let replacement = fluid_state_to_block(self.get_block_state(pos).get_fluid_state());
+        let mut flags = UpdateFlags::UPDATE_ALL;
+        if moved_by_piston {
+            flags |= UpdateFlags::UPDATE_MOVE_BY_PISTON;
+        }
+        self.set_block(pos, replacement, flags)
     }
 
     const fn chunk_pos_for_block(pos: BlockPos) -> ChunkPos {
@@ -646,6 +807,28 @@ impl World {
             .flatten()
     }
 
+    /// Adds a block entity to the world, replacing any block entity at its position.
+    ///
+    /// Does nothing if the chunk is not loaded.
+    pub fn set_block_entity(&self, block_entity: SharedBlockEntity) {
+        let chunk_pos = Self::chunk_pos_for_block(block_entity.lock().get_block_pos());
+        self.chunk_map.with_full_chunk(chunk_pos, |chunk| {
+            if let Some(lc) = chunk.as_full() {
+                lc.add_and_register_block_entity(block_entity);
+            }
+        });
+    }
+
+    /// Removes the block entity at the given position without touching the block.
+    pub fn remove_block_entity(&self, pos: BlockPos) {
+        let chunk_pos = Self::chunk_pos_for_block(pos);
+        self.chunk_map.with_full_chunk(chunk_pos, |chunk| {
+            if let Some(lc) = chunk.as_full() {
+                lc.remove_block_entity(pos);
+            }
+        });
+    }
+
     /// Called when a block entity's data changes.
     ///
     /// Marks the containing chunk as unsaved so it will be persisted to disk.
@@ -654,6 +837,14 @@ impl World {
         self.mark_chunk_dirty(chunk_pos);
     }
 
+    /// Queues the block at `pos` for the next chunk broadcast, which sends its
+    /// state and block entity data to every player tracking the chunk.
+    ///
+    /// Vanilla: `ServerLevel.sendBlockUpdated()`.
+    pub fn send_block_updated(&self, pos: BlockPos) {
+        self.chunk_map.block_changed(pos);
+    }
+
     /// Marks a chunk as dirty (unsaved) so it will be persisted to disk.
     ///
     /// Called when entities move, are added/removed, or when block entities change.
@@ -665,6 +856,57 @@ impl World {
         });
     }
 
+    /// Returns the inhabited time of a loaded chunk, or `None` if it is not loaded.
+    #[must_use]
+    pub fn inhabited_time(&self, chunk_pos: ChunkPos) -> Option<i64> {
+        self.chunk_map
+            .with_full_chunk(chunk_pos, ChunkAccess::inhabited_time)
+    }
+
+    /// Returns whether a non-spectator player is within 128 blocks horizontally
+    /// of the chunk's center.
+    ///
+    /// Mirrors vanilla's `ChunkMap.anyPlayerCloseEnoughForSpawning`.
+    #[must_use]
+    pub fn is_player_close_enough_for_spawning(&self, chunk_pos: ChunkPos) -> bool {
+        const MAX_DISTANCE_SQR: f64 = 128.0 * 128.0;
+
+        let center_x = f64::from(chunk_pos.0.x * 16 + 8);
+        let center_z = f64::from(chunk_pos.0.y * 16 + 8);
+        self.player_area_map
+            .get_tracking_players(chunk_pos)
+            .into_iter()
+            .filter_map(|entity_id| self.players.get_by_entity_id(entity_id))
+            .any(|player| {
+                if player.game_mode.load() == GameType::Spectator {
+                    return false;
+                }
+                let pos = *player.position.lock();
+                let dx = pos.x - center_x;
+                let dz = pos.z - center_z;
+                dx * dx + dz * dz < MAX_DISTANCE_SQR
+            })
+    }
+
+    /// Returns the local difficulty at a position.
+    ///
+    /// Mirrors vanilla's `Level.getCurrentDifficultyAt`: unloaded chunks count as
+    /// never inhabited.
+    #[must_use]
+    pub fn get_current_difficulty_at(&self, pos: BlockPos) -> DifficultyInstance {
+        let inhabited_time = self
+            .inhabited_time(Self::chunk_pos_for_block(pos))
+            .unwrap_or(0);
+        let level_data = self.level_data.read();
+        let moon_phase = usize::try_from(level_data.day().rem_euclid(8)).unwrap_or_default();
+        DifficultyInstance::new(
+            level_data.difficulty(),
+            level_data.game_time(),
+            inhabited_time,
+            MOON_BRIGHTNESS_PER_PHASE[moon_phase],
+        )
+    }
+
     /// Ticks the world.
     ///
     /// * `tick_count` - The current tick number
@@ -674,41 +916,77 @@ impl World {
     /// Returns timing information for the world tick.
     #[tracing::instrument(level = "trace", skip(self), name = "world_tick")]
     pub fn tick_b(self: &Arc<Self>, tick_count: u64, runs_normally: bool) -> WorldTickTimings {
+        let _timer = PhaseTimer::start(TickPhase::WorldTick);
         // Update the world's stored game time so components (like fluids) can access it
         {
             let mut level_data = self.level_data.write();
             level_data.data_mut().game_time = tick_count as i64;
         }
         if runs_normally {
+            let _timer = PhaseTimer::start(TickPhase::WeatherAndTime);
             self.tick_weather();
             self.tick_time();
         }
+        if runs_normally && let Some(fight) = &self.dragon_fight {
+            fight.lock().tick(self);
+        }
+
+        {
+            let _timer = PhaseTimer::start(TickPhase::NeighborUpdates);
+            self.run_carried_over_neighbor_updates();
+        }
 
         let random_tick_speed = self.get_game_rule(RANDOM_TICK_SPEED).as_int().unwrap_or(3) as u32;
 
-        let chunk_map_timings =
+        let chunk_map_timings = {
+            let _timer = PhaseTimer::start(TickPhase::ChunkMap);
             self.chunk_map
-                .tick_b(self, tick_count, random_tick_speed, runs_normally);
+                .tick_b(self, tick_count, random_tick_speed, runs_normally)
+        };
 
         // Scheduled ticks are now processed per-chunk in ChunkMap::execute_scheduled_ticks()
 
+        if runs_normally {
+            let _span = tracing::trace_span!("block_events").entered();
+            let _timer = PhaseTimer::start(TickPhase::BlockEvents);
+            self.run_block_events();
+        }
+
         // Tick players (always tick players - they can move when frozen)
         let player_tick = {
             let _span = tracing::trace_span!("player_tick").entered();
+            let _timer = PhaseTimer::start(TickPhase::Players);
             let start = Instant::now();
             self.players.iter_players(|_uuid, player| {
                 player.tick();
+                player.attached_entities.lock().restore(player);
                 true
             });
             start.elapsed()
         };
 
+        {
+            let _span = tracing::trace_span!("waypoints").entered();
+            let _timer = PhaseTimer::start(TickPhase::Waypoints);
+            self.waypoints.tick(self);
+        }
+
         // Broadcast player latency updates periodically
         if tick_count.is_multiple_of(SEND_PLAYER_INFO_INTERVAL) {
             let _span = tracing::trace_span!("broadcast_latency").entered();
+            let _timer = PhaseTimer::start(TickPhase::LatencyBroadcast);
             self.broadcast_player_latency_updates();
         }
 
+        if tick_count.is_multiple_of(SECTION_COMPACTION_INTERVAL) {
+            let _span = tracing::trace_span!("section_compaction").entered();
+            let _timer = PhaseTimer::start(TickPhase::ChunkCompaction);
+            let compacted = self.compact_sections();
+            if compacted > 0 {
+                log::debug!("Compacted {compacted} chunk sections");
+            }
+        }
+
         WorldTickTimings {
             chunk_map: chunk_map_timings,
             player_tick,
@@ -887,6 +1165,83 @@ impl World {
             && self.dimension.key != vanilla_dimension_types::THE_END.key
     }
 
+    /// Gets the first free Y coordinate above the given heightmap at the column of `pos`.
+    ///
+    /// Returns the world's minimum Y if the chunk is not loaded.
+    #[must_use]
+    pub fn get_height(&self, heightmap_type: HeightmapType, pos: BlockPos) -> i32 {
+        let chunk_pos = Self::chunk_pos_for_block(pos);
+        self.chunk_map
+            .with_full_chunk(chunk_pos, |chunk_access| {
+                chunk_access
+                    .as_full()
+                    .map(|chunk| chunk.get_height(heightmap_type, pos))
+            })
+            .flatten()
+            .unwrap_or_else(|| self.get_min_y())
+    }
+
+    /// Gets the biome at the given position, or `None` if the chunk is not loaded.
+    ///
+    /// TODO: Apply vanilla's `BiomeManager` fuzzy zoom instead of reading the raw 4x4x4 cell.
+    #[must_use]
+    pub fn get_biome(&self, pos: BlockPos) -> Option<BiomeRef> {
+        let chunk_pos = Self::chunk_pos_for_block(pos);
+        let biome_id = self
+            .chunk_map
+            .with_full_chunk(chunk_pos, |chunk_access| {
+                chunk_access
+                    .as_full()
+                    .map(|chunk| chunk.get_noise_biome(pos))
+            })
+            .flatten()?;
+        REGISTRY.biomes.by_id(usize::from(biome_id))
+    }
+
+    /// Checks whether rain falls on the given position, matching vanilla's `Level.isRainingAt`.
+    ///
+    /// It must be raining, nothing motion blocking may be above the position, and the biome
+    /// must have precipitation while being too warm for snow.
+    ///
+    /// TODO: Apply the height and `frozen` temperature modifiers of `Biome.getTemperature`.
+    #[must_use]
+    pub fn is_raining_at(&self, pos: BlockPos) -> bool {
+        if !self.is_raining() {
+            return false;
+        }
+        if self.get_height(HeightmapType::MotionBlocking, pos) > pos.y() {
+            return false;
+        }
+        self.get_biome(pos)
+            .is_some_and(|biome| biome.has_precipitation && biome.temperature >= 0.15)
+    }
+
+    /// Checks whether fire at `pos` may tick, matching vanilla's `ServerLevel.canSpreadFireAround`.
+    ///
+    /// A `fire_spread_radius_around_player` of -1 allows fire everywhere, otherwise a
+    /// non-spectator player has to be within that many blocks.
+    #[must_use]
+    pub fn can_spread_fire_around(&self, pos: BlockPos) -> bool {
+        let radius = self
+            .get_game_rule(FIRE_SPREAD_RADIUS_AROUND_PLAYER)
+            .as_int()
+            .unwrap_or(128);
+        if radius == -1 {
+            return true;
+        }
+
+        let (x, y, z) = pos.get_center();
+        let center = DVec3::new(x, y, z);
+        let max_distance_sq = f64::from(radius) * f64::from(radius);
+        let mut found = false;
+        self.players.iter_players(|_, player| {
+            found = player.game_mode.load() != GameType::Spectator
+                && player.position().distance_squared(center) < max_distance_sq;
+            !found
+        });
+        found
+    }
+
     /// Schedules a block tick at the given position.
     ///
     /// The tick will fire after `delay` game ticks with the given priority.
@@ -1022,74 +1377,33 @@ impl World {
         }
     }
 
-    /// Broadcasts a signed chat message to all players in the world.
+    /// Sends a player's chat message to all players in the world.
     ///
-    /// # Panics
-    /// Panics if `message_signature` is `None` after checking `is_some()` (should never happen).
+    /// `filter_mask` hides parts of the message from players it is filtered for
+    /// (see [`Player::filter_mask_for`]). Returns whether the message was hidden
+    /// from any of them entirely.
     pub fn broadcast_chat(
         &self,
-        mut packet: CPlayerChat,
-        _sender: Arc<Player>,
-        sender_last_seen: LastSeen,
-        message_signature: Option<&[u8; 256]>,
-    ) {
-        log::debug!(
-            "broadcast_chat: sender_last_seen has {} signatures, message_signature present: {}",
-            sender_last_seen.len(),
-            message_signature.is_some()
-        );
-
+        message: &PlayerChatMessage,
+        sender: &Player,
+        unsigned_content: Option<&TextComponent>,
+        filter_mask: &FilterType,
+        chat_type: &ChatTypeBound,
+    ) -> bool {
+        let mut fully_filtered = false;
         self.players.iter_players(|_, recipient| {
-            let messages_received = recipient.get_and_increment_messages_received();
-            packet.global_index = messages_received;
-
-            log::debug!(
-                "Broadcasting to player {} (UUID: {}), global_index={}",
-                recipient.gameprofile.name,
-                recipient.gameprofile.id,
-                messages_received
-            );
-
-            // IMPORTANT: Index previous messages BEFORE updating the cache
-            // This matches vanilla's order: pack() then push()
-            let previous_messages = {
-                let chat = recipient.chat.lock();
-                chat.signature_cache
-                    .index_previous_messages(&sender_last_seen)
-            };
-
-            log::debug!(
-                "  Indexed {} previous messages for recipient",
-                previous_messages.len()
-            );
-
-            packet.previous_messages.clone_from(&previous_messages);
-
-            // Send the packet
-            recipient.send_packet(packet.clone());
-
-            // AFTER sending, update the recipient's cache using vanilla's push algorithm
-            // This adds all lastSeen signatures + current signature to the cache
-            {
-                let mut chat = recipient.chat.lock();
-                if let Some(signature) = message_signature {
-                    chat.signature_cache
-                        .push(&sender_last_seen, Some(signature));
-
-                    log::debug!("  Added signature to recipient's cache and pending list");
-
-                    // Add to pending messages for acknowledgment tracking
-                    chat.message_validator
-                        .add_pending(Some(Box::new(*signature) as Box<[u8]>));
-                } else {
-                    // Even unsigned messages update the pending tracker
-                    chat.message_validator.add_pending(None);
-                    log::debug!("  Added unsigned message to pending list");
-                }
+            match sender.filter_mask_for(recipient, filter_mask) {
+                Some(filter_type) => recipient.send_chat_message(
+                    message,
+                    unsigned_content.cloned(),
+                    filter_type,
+                    chat_type.clone(),
+                ),
+                None => fully_filtered = true,
             }
-
             true
         });
+        fully_filtered
     }
 
     /// Broadcasts a system chat message to all players.
@@ -1137,24 +1451,6 @@ impl World {
         });
     }
 
-    /// Broadcasts an unsigned player chat message to all players.
-    pub fn broadcast_unsigned_chat(
-        &self,
-        mut packet: CPlayerChat,
-        sender_name: &str,
-        message: &str,
-    ) {
-        log::info!("<{sender_name}> {message}");
-
-        self.players.iter_players(|_, recipient| {
-            let messages_received = recipient.get_and_increment_messages_received();
-            packet.global_index = messages_received;
-
-            recipient.send_packet(packet.clone());
-            true
-        });
-    }
-
     /// Broadcasts a packet to all players tracking the given chunk.
     ///
     /// This method handles encoding the packet internally, avoiding boilerplate at call sites.
@@ -1328,13 +1624,26 @@ impl World {
         from: DVec3,
         to: DVec3,
     ) -> (bool, Option<Direction>) {
+        match self.ray_outline_clip(block_pos, from, to) {
+            Some((_, dir)) => (true, Some(dir)),
+            None => (false, None),
+        }
+    }
+
+    /// Clips a ray against a block's selection box.
+    ///
+    /// Returns the ray parameter of the closest hit (0 at `from`, 1 at `to`)
+    /// and the face that was hit, or `None` if the ray misses the block.
+    #[must_use]
+    pub fn ray_outline_clip(
+        &self,
+        block_pos: BlockPos,
+        from: DVec3,
+        to: DVec3,
+    ) -> Option<(f64, Direction)> {
         let state = self.get_block_state(block_pos);
         let bounding_boxes = state.get_outline_shape();
 
-        if bounding_boxes.is_empty() {
-            return (false, None);
-        }
-
         // Vanilla parity: pick the *closest* AABB hit across all boxes in the shape,
         // matching VoxelShape.clip() which finds the minimum entry t-parameter.
         let mut closest: Option<(f64, Direction)> = None;
@@ -1363,10 +1672,7 @@ impl World {
             }
         }
 
-        match closest {
-            Some((_, dir)) => (true, Some(dir)),
-            None => (false, None),
-        }
+        closest
     }
 
     /// Ray-AABB intersection returning the entry t-parameter and the hit face.
@@ -1375,9 +1681,9 @@ impl World {
     /// and `direction` is the face normal pointing away from the hit surface.
     /// Returns `None` if the AABB is missed or entirely behind the ray origin.
     ///
-    /// Used internally by [`ray_outline_check`] to pick the *closest* hit across
+    /// Used internally by [`ray_outline_clip`] to pick the *closest* hit across
     /// a multi-box voxel shape, matching vanilla's `VoxelShape.clip()` behavior.
-    fn intersects_aabb_with_t(
+    pub(crate) fn intersects_aabb_with_t(
         start: DVec3,
         end: DVec3,
         min: DVec3,
@@ -1633,11 +1939,7 @@ impl World {
     /// * `pos` - The position where the event occurs
     /// * `data` - Event-specific data
     pub fn global_level_event(&self, event_type: i32, pos: BlockPos, data: i32) {
-        let packet = CLevelEvent::new(event_type, pos, data, true);
-        self.players.iter_players(|_, player| {
-            player.send_packet(packet.clone());
-            true
-        });
+        self.broadcast_to_all(CLevelEvent::new(event_type, pos, data, true));
     }
 
     /// Broadcasts block destruction particles and sound for a destroyed block.
@@ -1662,9 +1964,9 @@ impl World {
     /// Sends destruction particles (skipping fire blocks), optionally drops
     /// resources via loot table, then replaces with air.
     ///
-    /// Defaults to recursion limit of 512
+    /// Defaults to recursion limit of [`Self::UPDATE_LIMIT`]
     pub fn destroy_block(self: &Arc<Self>, pos: BlockPos, drop_items: bool) -> bool {
-        self.destroy_block_with_limit(pos, drop_items, 512)
+        self.destroy_block_with_limit(pos, drop_items, Self::UPDATE_LIMIT)
     }
 
     /// Destroys a block at the given position, optionally dropping its loot.
@@ -1690,7 +1992,7 @@ impl World {
 
         if drop_items {
             self.drop_resources(state, pos);
-            // TODO: block entity and entity drops
+            // TODO: entity drops
         }
 
         // Vanilla parity: fluidState.createLegacyBlock() — breaking a waterlogged
@@ -1705,9 +2007,10 @@ impl World {
     ///
     /// This is the no-tool/no-entity overload. Player block breaking uses
     /// `block_breaking::drop_block_loot` which includes tool context for
-    /// fortune/silk touch.
+    /// fortune/silk touch. Call it before the block is removed, so the loot
+    /// can copy from the block entity.
     // TODO: `spawnAfterBreak` (XP orbs for ores) not called yet.
-    // TODO: block entity and entity drops
+    // TODO: entity drops
     pub fn drop_resources(self: &Arc<Self>, state: BlockStateId, pos: BlockPos) {
         let block = state.get_block();
         let loot_key = steel_utils::Identifier::vanilla(format!("blocks/{}", block.key.path));
@@ -1716,10 +2019,23 @@ impl World {
             return;
         };
 
+        let block_entity = self.get_block_entity(pos).map(|block_entity| {
+            let block_entity = block_entity.lock();
+            (block_entity.get_type(), block_entity.collect_components())
+        });
+
         let mut rng = rand::rng();
         let mut ctx = LootContext::new(&mut rng)
             .with_block_state(state)
             .with_origin(f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+        if let Some((block_entity_type, components)) = &block_entity {
+            ctx = ctx.with_block_entity(BlockEntityRef {
+                block_entity_type: Some(&block_entity_type.key),
+                custom_name: None,
+                inventory: None,
+                components: Some(components),
+            });
+        }
 
         let drops = loot_table.get_random_items(&mut ctx);
         for item in drops {
@@ -1729,10 +2045,15 @@ impl World {
         }
     }
 
-    /// Broadcasts a block event to nearby players within 64 blocks.
+    /// Queues a block event to run at the end of the current tick.
     ///
     /// Block events are used for special block behaviors like pistons, note blocks,
     /// chests, and bells. Each block type interprets the parameters differently.
+    /// When the event runs, [`BlockBehavior::trigger_event`] is called if the block
+    /// is still there, and the event is sent to nearby players if it returns `true`.
+    /// Queuing the same event twice before it runs has no effect.
+    ///
+    /// [`BlockBehavior::trigger_event`]: crate::behavior::BlockBehavior::trigger_event
     ///
     /// # Arguments
     /// * `pos` - The position of the block
@@ -1740,8 +2061,51 @@ impl World {
     /// * `action_id` - The action ID (block-specific meaning)
     /// * `action_param` - The action parameter (block-specific meaning)
     pub fn block_event(&self, pos: BlockPos, block: BlockRef, action_id: u8, action_param: u8) {
+        let event = BlockEventData {
+            pos,
+            block,
+            action_id,
+            action_param,
+        };
+        let mut block_events = self.block_events.lock();
+        if !block_events.contains(&event) {
+            block_events.push_back(event);
+        }
+    }
+
+    /// Runs all queued block events, including the ones queued while running them.
+    ///
+    /// Vanilla equivalent: `ServerLevel.runBlockEvents()`.
+    fn run_block_events(self: &Arc<Self>) {
+        // TODO: Keep events for unloaded chunks queued until the chunk ticks again
+        loop {
+            // The lock must not be held while the event runs, it may queue new events
+            let Some(event) = self.block_events.lock().pop_front() else {
+                break;
+            };
+
+            let state = self.get_block_state(event.pos);
+            if state.get_block() != event.block {
+                continue;
+            }
+            let behavior = BLOCK_BEHAVIORS.get_behavior(event.block);
+            if behavior.trigger_event(state, self, event.pos, event.action_id, event.action_param) {
+                self.broadcast_block_event(event);
+            }
+        }
+    }
+
+    /// Sends a block event to nearby players within 64 blocks.
+    fn broadcast_block_event(&self, event: BlockEventData) {
         const MAX_DISTANCE_SQ: f64 = 64.0 * 64.0;
 
+        let BlockEventData {
+            pos,
+            block,
+            action_id,
+            action_param,
+        } = event;
+
         let block_id = block.id() as i32;
 
         let chunk = ChunkPos::new(
@@ -1778,11 +2142,9 @@ impl World {
         }
     }
 
-    /// Plays a sound at a specific position, broadcasting to nearby players.
+    /// Plays a sound at the center of a block, broadcasting to nearby players.
     ///
-    /// The sound is sent to all players within 64 blocks of the position,
-    /// except for the excluded player (if any). The excluded player is typically
-    /// the one who triggered the sound, as they hear it client-side.
+    /// See [`Self::play_sound_at`] for who hears it.
     ///
     /// # Arguments
     /// * `sound_id` - The sound event registry ID (from `steel_registry::sound_events`)
@@ -1800,26 +2162,63 @@ impl World {
         pitch: f32,
         exclude: Option<i32>,
     ) {
-        const MAX_DISTANCE_SQ: f64 = 64.0 * 64.0;
-
-        let chunk = ChunkPos::new(
-            SectionPos::block_to_section_coord(pos.x()),
-            SectionPos::block_to_section_coord(pos.z()),
+        let center = DVec3::new(
+            f64::from(pos.x()) + 0.5,
+            f64::from(pos.y()) + 0.5,
+            f64::from(pos.z()) + 0.5,
         );
+        self.play_sound_at(sound_id, source, center, volume, pitch, exclude);
+    }
 
+    /// Plays a sound at an exact position, broadcasting to nearby players.
+    ///
+    /// The sound is sent to every player within [`sound_range`] of the position,
+    /// except for the excluded player (if any). The excluded player is typically
+    /// the one who triggered the sound, as they hear it client-side.
+    ///
+    /// Vanilla: `ServerLevel.playSeededSound()`.
+    pub fn play_sound_at(
+        &self,
+        sound_id: i32,
+        source: SoundSource,
+        pos: DVec3,
+        volume: f32,
+        pitch: f32,
+        exclude: Option<i32>,
+    ) {
         // Generate a random seed for sound variations
         let seed = rand::random::<i64>();
+        let packet = CSound::new(sound_id, source, pos.x, pos.y, pos.z, volume, pitch, seed);
+        self.broadcast_sound(packet, pos, sound_range(volume), exclude);
+    }
 
-        let packet = CSound::new(
-            sound_id,
-            source,
-            f64::from(pos.x()) + 0.5,
-            f64::from(pos.y()) + 0.5,
-            f64::from(pos.z()) + 0.5,
-            volume,
-            pitch,
-            seed,
-        );
+    /// Plays a sound that follows an entity, broadcasting to players near it.
+    ///
+    /// Vanilla: `ServerLevel.playSeededSound()` with an entity.
+    pub fn play_entity_sound(
+        &self,
+        entity: &dyn Entity,
+        sound_id: i32,
+        source: SoundSource,
+        volume: f32,
+        pitch: f32,
+        exclude: Option<i32>,
+    ) {
+        let seed = rand::random::<i64>();
+        let packet = CSoundEntity::new(sound_id, source, entity.id(), volume, pitch, seed);
+        self.broadcast_sound(packet, entity.position(), sound_range(volume), exclude);
+    }
+
+    /// Sends a sound packet to every player closer than `range` to `pos`.
+    ///
+    /// Vanilla: `PlayerList.broadcast()`.
+    fn broadcast_sound<P: ClientPacket>(
+        &self,
+        packet: P,
+        pos: DVec3,
+        range: f64,
+        exclude: Option<i32>,
+    ) {
         let Ok(encoded) =
             EncodedPacket::from_bare(packet, STEEL_CONFIG.compression, ConnectionProtocol::Play)
         else {
@@ -1827,30 +2226,15 @@ impl World {
             return;
         };
 
-        // Get players tracking this chunk, then filter by 64-block distance
-        let sound_pos = (
-            f64::from(pos.x()) + 0.5,
-            f64::from(pos.y()) + 0.5,
-            f64::from(pos.z()) + 0.5,
-        );
-
-        for entity_id in self.player_area_map.get_tracking_players(chunk) {
+        self.players.iter_players(|_uuid, player| {
             // Skip excluded player (they hear the sound client-side)
-            if exclude == Some(entity_id) {
-                continue;
-            }
-            if let Some(player) = self.players.get_by_entity_id(entity_id) {
-                let player_pos = *player.position.lock();
-                let dx = player_pos.x - sound_pos.0;
-                let dy = player_pos.y - sound_pos.1;
-                let dz = player_pos.z - sound_pos.2;
-                let dist_sq = dx * dx + dy * dy + dz * dz;
-
-                if dist_sq <= MAX_DISTANCE_SQ {
-                    player.connection.send_encoded(encoded.clone());
-                }
+            if exclude != Some(player.id)
+                && player.position.lock().distance_squared(pos) < range * range
+            {
+                player.connection.send_encoded(encoded.clone());
             }
-        }
+            true
+        });
     }
 
     /// Plays a block sound at a specific position.
@@ -1891,21 +2275,72 @@ impl World {
 
     /// Adds an entity to the world.
     ///
-    /// This delegates to the chunk's `add_and_register_entity` method which handles:
+    /// This delegates to the chunk's `spawn_entity` method which handles:
+    /// - Applying the chunk's entity cap
     /// - Adding to chunk storage
     /// - Setting up level callback
     /// - Registering in entity cache
     /// - Adding to entity tracker and sending spawn packets
     /// - Marking the chunk dirty
-    pub fn add_entity(self: &Arc<Self>, entity: SharedEntity) {
+    ///
+    /// Returns `false` if the chunk is not loaded or refused the entity.
+    pub fn add_entity(self: &Arc<Self>, entity: SharedEntity) -> bool {
         let pos = entity.position();
         let chunk_pos = ChunkPos::new((pos.x as i32) >> 4, (pos.z as i32) >> 4);
 
-        self.chunk_map.with_full_chunk(chunk_pos, |chunk| {
-            if let Some(c) = chunk.as_full() {
-                c.add_and_register_entity(entity.clone());
+        self.chunk_map
+            .with_full_chunk(chunk_pos, |chunk| {
+                chunk
+                    .as_full()
+                    .is_some_and(|c| c.spawn_entity(entity.clone()))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Returns the entity counts and cap counters of every loaded chunk.
+    #[must_use]
+    pub fn chunk_limit_snapshots(&self) -> Vec<ChunkLimitSnapshot> {
+        let mut snapshots = Vec::new();
+        self.chunk_map.chunks.iter_sync(|_, holder| {
+            if let Some(chunk) = holder.try_chunk(ChunkStatus::Full)
+                && let Some(level_chunk) = chunk.as_full()
+            {
+                snapshots.push(level_chunk.limit_snapshot());
             }
+            true
         });
+        snapshots
+    }
+
+    /// Compacts the sections of loaded chunks that changed a lot since they
+    /// were last compacted. Returns how many sections changed.
+    pub fn compact_sections(&self) -> usize {
+        let mut compacted = 0;
+        self.chunk_map.chunks.iter_sync(|_, holder| {
+            if let Some(chunk) = holder.try_chunk(ChunkStatus::Full)
+                && let Some(level_chunk) = chunk.as_full()
+            {
+                compacted += level_chunk.sections.compact();
+            }
+            true
+        });
+        compacted
+    }
+
+    /// Measures the memory used by the sections of the loaded chunks.
+    #[must_use]
+    pub fn chunk_memory(&self) -> ChunkMemory {
+        let mut memory = ChunkMemory::default();
+        self.chunk_map.chunks.iter_sync(|_, holder| {
+            if let Some(chunk) = holder.try_chunk(ChunkStatus::Full)
+                && let Some(level_chunk) = chunk.as_full()
+            {
+                memory.chunks += 1;
+                level_chunk.sections.memory_usage(&mut memory);
+            }
+            true
+        });
+        memory
     }
 
     /// Spawns an item entity at the given position.
@@ -1947,8 +2382,7 @@ impl World {
         ));
         entity.set_default_pickup_delay();
 
-        self.add_entity(entity.clone());
-        Some(entity)
+        self.add_entity(entity.clone()).then_some(entity)
     }
 
     /// Drops an item at a block position with random offset and velocity.
@@ -2076,6 +2510,14 @@ impl World {
         self.entity_cache.get_entities_in_aabb(aabb)
     }
 
+    /// Gets all loaded entities of the given type.
+    ///
+    /// Vanilla: `ServerLevel.getEntities(EntityTypeTest, Predicate)`.
+    #[must_use]
+    pub fn get_entities_of_type(&self, entity_type: EntityTypeRef) -> Vec<SharedEntity> {
+        self.entity_cache.get_entities_of_type(entity_type)
+    }
+
     /// Moves an entity's Arc between chunks when it crosses a chunk boundary.
     ///
     /// Called by `EntityChunkCallback` when an entity moves between chunks.
@@ -0,0 +1,445 @@
+//! Queue for neighbor and shape updates.
+//!
+//! Mirrors vanilla's `CollectingNeighborUpdater`: instead of recursing, updates
+//! triggered while another one runs are collected and run depth-first afterwards,
+//! and a chain is cut off after [`NeighborUpdateConfig::max_chained`] updates.
+//! With a [`NeighborUpdateConfig::tick_budget`] set, a cascade that runs out of
+//! budget is paused and carried over to the next tick instead of stalling this one.
+
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::Arc;
+
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_utils::locks::SyncMutex;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::config::NeighborUpdateConfig;
+use crate::world::World;
+
+/// A queued update.
+///
+/// Vanilla: `CollectingNeighborUpdater.NeighborUpdates`.
+#[derive(Debug, Clone, Copy)]
+pub enum NeighborUpdate {
+    /// `pos` reacts to the new shape of the block at `neighbor_pos`.
+    Shape {
+        /// Direction from `pos` to `neighbor_pos`.
+        direction: Direction,
+        /// The block to update.
+        pos: BlockPos,
+        /// The block that changed.
+        neighbor_pos: BlockPos,
+        /// The new state of the block that changed.
+        neighbor_state: BlockStateId,
+        /// Flags for the resulting block change.
+        flags: UpdateFlags,
+        /// How much deeper the shape updates may recurse.
+        update_limit: i32,
+    },
+    /// `pos` reacts to a change of one of its neighbors.
+    Neighbor {
+        /// The block to update.
+        pos: BlockPos,
+        /// The block that changed.
+        source_block: BlockRef,
+        /// Whether a piston moved the changed block.
+        moved_by_piston: bool,
+    },
+    /// All 6 neighbors of `source_pos` react to its change, one per step.
+    Neighbors {
+        /// The position that changed.
+        source_pos: BlockPos,
+        /// The block that changed.
+        source_block: BlockRef,
+        /// Index of the next direction in `World::NEIGHBOR_UPDATE_ORDER`.
+        next: usize,
+    },
+}
+
+impl NeighborUpdate {
+    /// Runs the next step of this update.
+    ///
+    /// Returns `true` if there are more steps left.
+    fn run_next(&mut self, world: &Arc<World>) -> bool {
+        match *self {
+            Self::Shape {
+                direction,
+                pos,
+                neighbor_pos,
+                neighbor_state,
+                flags,
+                update_limit,
+            } => {
+                world.execute_shape_update(
+                    direction,
+                    pos,
+                    neighbor_pos,
+                    neighbor_state,
+                    flags,
+                    update_limit,
+                );
+                false
+            }
+            Self::Neighbor {
+                pos,
+                source_block,
+                moved_by_piston,
+            } => {
+                world.execute_neighbor_update(pos, source_block, moved_by_piston);
+                false
+            }
+            Self::Neighbors {
+                source_pos,
+                source_block,
+                ref mut next,
+            } => {
+                let direction = World::NEIGHBOR_UPDATE_ORDER[*next];
+                *next += 1;
+                world.execute_neighbor_update(source_pos.relative(direction), source_block, false);
+                *next < World::NEIGHBOR_UPDATE_ORDER.len()
+            }
+        }
+    }
+
+    /// Gets the position the update starts at, for logging.
+    const fn pos(&self) -> BlockPos {
+        match *self {
+            Self::Shape { pos, .. } | Self::Neighbor { pos, .. } => pos,
+            Self::Neighbors { source_pos, .. } => source_pos,
+        }
+    }
+}
+
+/// Counters of one neighbor update queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NeighborUpdateStats {
+    /// Updates waiting for the next tick.
+    pub pending: usize,
+    /// Updates run this tick.
+    pub run_this_tick: u32,
+}
+
+struct QueueState<T> {
+    /// Updates to run, the next one at the back.
+    stack: VecDeque<T>,
+    /// Updates queued by the update that is running right now.
+    added_this_layer: Vec<T>,
+    /// Updates queued since the chain started, including skipped ones.
+    count: u32,
+    /// Steps run since the tick started.
+    run_this_tick: u32,
+    /// Whether a thread is working through the queue.
+    running: bool,
+}
+
+/// Collects updates and runs them depth-first within a per-tick budget.
+///
+/// Generic over the update so the ordering can be tested without a world.
+pub struct NeighborUpdater<T = NeighborUpdate> {
+    state: SyncMutex<QueueState<T>>,
+    max_chained: Option<u32>,
+    tick_budget: Option<u32>,
+}
+
+impl<T> NeighborUpdater<T> {
+    /// Creates an empty queue with the limits from `config`.
+    #[must_use]
+    pub fn new(config: &NeighborUpdateConfig) -> Self {
+        Self {
+            state: SyncMutex::new(QueueState {
+                stack: VecDeque::new(),
+                added_this_layer: Vec::new(),
+                count: 0,
+                run_this_tick: 0,
+                running: false,
+            }),
+            max_chained: config.max_chained,
+            tick_budget: config.tick_budget,
+        }
+    }
+
+    /// Queues `update` and, unless a chain is already in progress, runs it and
+    /// everything it triggers.
+    ///
+    /// `run_next` runs one step of an update and returns `true` if it has more.
+    /// It is called without the queue locked, so it may queue further updates.
+    ///
+    /// Vanilla: `CollectingNeighborUpdater.addAndRun()`.
+    pub fn add_and_run(
+        &self,
+        update: T,
+        pos: impl FnOnce(&T) -> BlockPos,
+        run_next: impl FnMut(&mut T) -> bool,
+    ) {
+        let chain_in_progress = {
+            let mut state = self.state.lock();
+            let chain_in_progress = state.count > 0;
+            let too_many = self.max_chained.is_some_and(|max| state.count >= max);
+            state.count += 1;
+            if !too_many {
+                if !chain_in_progress {
+                    state.stack.push_back(update);
+                } else if state.running {
+                    state.added_this_layer.push(update);
+                } else {
+                    // The chain is paused until next tick, queue behind it
+                    state.stack.push_front(update);
+                }
+            } else if Some(state.count - 1) == self.max_chained {
+                log::error!(
+                    "Too many chained neighbor updates. Skipping the rest. \
+                     First skipped position: {:?}",
+                    pos(&update)
+                );
+            }
+            chain_in_progress
+        };
+        if !chain_in_progress {
+            self.run_updates(run_next);
+        }
+    }
+
+    /// Resets the tick budget and resumes a chain carried over from the last tick.
+    pub fn start_tick(&self, run_next: impl FnMut(&mut T) -> bool) {
+        let resume = {
+            let mut state = self.state.lock();
+            state.run_this_tick = 0;
+            !state.running && state.count > 0
+        };
+        if resume {
+            self.run_updates(run_next);
+        }
+    }
+
+    /// Gets the queue's counters.
+    #[must_use]
+    pub fn stats(&self) -> NeighborUpdateStats {
+        let state = self.state.lock();
+        NeighborUpdateStats {
+            pending: state.stack.len() + state.added_this_layer.len(),
+            run_this_tick: state.run_this_tick,
+        }
+    }
+
+    /// Vanilla: `CollectingNeighborUpdater.runUpdates()`.
+    fn run_updates(&self, mut run_next: impl FnMut(&mut T) -> bool) {
+        let mut state = self.state.lock();
+        if state.running {
+            return;
+        }
+        state.running = true;
+        loop {
+            // The first update queued by the last step runs first
+            let layer = mem::take(&mut state.added_this_layer);
+            state.stack.extend(layer.into_iter().rev());
+            let Some(mut update) = state.stack.pop_back() else {
+                state.count = 0;
+                state.running = false;
+                return;
+            };
+            if self
+                .tick_budget
+                .is_some_and(|budget| state.run_this_tick >= budget)
+            {
+                // Out of budget, the chain continues next tick
+                state.stack.push_back(update);
+                state.running = false;
+                return;
+            }
+            state.run_this_tick += 1;
+            drop(state);
+
+            let has_more = run_next(&mut update);
+
+            state = self.state.lock();
+            if has_more {
+                // Run the rest after the updates it just queued
+                state.stack.push_back(update);
+            }
+        }
+    }
+}
+
+impl World {
+    /// Queues an update, running it right away unless a chain is in progress.
+    fn queue_neighbor_update(self: &Arc<Self>, update: NeighborUpdate) {
+        self.neighbor_updater
+            .add_and_run(update, NeighborUpdate::pos, |update| update.run_next(self));
+    }
+
+    /// Resumes neighbor updates carried over from the last tick.
+    pub(crate) fn run_carried_over_neighbor_updates(self: &Arc<Self>) {
+        self.neighbor_updater
+            .start_tick(|update| update.run_next(self));
+    }
+
+    /// Gets the counters of this world's neighbor update queue.
+    #[must_use]
+    pub fn neighbor_update_stats(&self) -> NeighborUpdateStats {
+        self.neighbor_updater.stats()
+    }
+
+    /// Queues an update for `pos` about the change of `source_block`.
+    ///
+    /// This is the Rust equivalent of vanilla's `Level.neighborChanged()`.
+    pub(crate) fn neighbor_changed(
+        self: &Arc<Self>,
+        pos: BlockPos,
+        source_block: BlockRef,
+        moved_by_piston: bool,
+    ) {
+        self.queue_neighbor_update(NeighborUpdate::Neighbor {
+            pos,
+            source_block,
+            moved_by_piston,
+        });
+    }
+
+    /// Queues updates for all neighbors of `pos` about the change of `source_block`.
+    ///
+    /// This is the Rust equivalent of vanilla's `Level.updateNeighborsAt()`.
+    pub fn update_neighbors_at(self: &Arc<Self>, pos: BlockPos, source_block: BlockRef) {
+        self.queue_neighbor_update(NeighborUpdate::Neighbors {
+            source_pos: pos,
+            source_block,
+            next: 0,
+        });
+    }
+
+    /// Queues an update for `pos` about the new shape of the block at `neighbor_pos`.
+    ///
+    /// This is the Rust equivalent of vanilla's `Level.neighborShapeChanged()`.
+    pub(crate) fn neighbor_shape_changed(
+        self: &Arc<Self>,
+        direction: Direction,
+        pos: BlockPos,
+        neighbor_pos: BlockPos,
+        neighbor_state: BlockStateId,
+        flags: UpdateFlags,
+        update_limit: i32,
+    ) {
+        self.queue_neighbor_update(NeighborUpdate::Shape {
+            direction,
+            pos,
+            neighbor_pos,
+            neighbor_state,
+            flags,
+            update_limit,
+        });
+    }
+
+    /// Notifies a block that one of its neighbors changed.
+    ///
+    /// This is the Rust equivalent of vanilla's `NeighborUpdater.executeUpdate()`.
+    fn execute_neighbor_update(
+        self: &Arc<Self>,
+        pos: BlockPos,
+        source_block: BlockRef,
+        moved_by_piston: bool,
+    ) {
+        if !self.is_in_valid_bounds(pos) {
+            return;
+        }
+        let state = self.get_block_state(pos);
+        BLOCK_BEHAVIORS
+            .get_behavior(state.get_block())
+            .handle_neighbor_changed(state, self, pos, source_block, moved_by_piston);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fake update: `(id, children, steps left)`.
+    type Fake = (u32, Vec<u32>, u32);
+
+    fn updater(max_chained: Option<u32>, tick_budget: Option<u32>) -> NeighborUpdater<Fake> {
+        NeighborUpdater::new(&NeighborUpdateConfig {
+            max_chained,
+            tick_budget,
+        })
+    }
+
+    /// Runs `root`, where update `n` queues updates `children(n)` on its first step.
+    fn run(
+        updater: &NeighborUpdater<Fake>,
+        root: Fake,
+        children: &dyn Fn(u32) -> Vec<u32>,
+        log: &SyncMutex<Vec<u32>>,
+    ) {
+        updater.add_and_run(
+            root,
+            |_| BlockPos::ZERO,
+            |update| step(updater, update, children, log),
+        );
+    }
+
+    fn step(
+        updater: &NeighborUpdater<Fake>,
+        update: &mut Fake,
+        children: &dyn Fn(u32) -> Vec<u32>,
+        log: &SyncMutex<Vec<u32>>,
+    ) -> bool {
+        log.lock().push(update.0);
+        for child in mem::take(&mut update.1) {
+            run(updater, (child, children(child), 1), children, log);
+        }
+        update.2 -= 1;
+        update.2 > 0
+    }
+
+    fn tree(id: u32) -> Vec<u32> {
+        match id {
+            1 => vec![2, 3],
+            2 => vec![4],
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_depth_first_order() {
+        let updater = updater(None, None);
+        let log = SyncMutex::new(Vec::new());
+        run(&updater, (1, tree(1), 2), &tree, &log);
+        // The root's second step runs after everything its first step queued
+        assert_eq!(*log.lock(), vec![1, 2, 4, 3, 1]);
+        assert_eq!(updater.stats().pending, 0);
+    }
+
+    #[test]
+    fn test_max_chained_skips_rest() {
+        let updater = updater(Some(3), None);
+        let log = SyncMutex::new(Vec::new());
+        run(&updater, (1, tree(1), 1), &tree, &log);
+        assert_eq!(*log.lock(), vec![1, 2, 3]);
+
+        // The count resets once the chain is done
+        run(&updater, (5, Vec::new(), 1), &tree, &log);
+        assert_eq!(*log.lock(), vec![1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn test_tick_budget_carries_over() {
+        let updater = updater(None, Some(2));
+        let log = SyncMutex::new(Vec::new());
+        run(&updater, (1, tree(1), 1), &tree, &log);
+        assert_eq!(*log.lock(), vec![1, 2]);
+        assert_eq!(updater.stats().pending, 2);
+
+        // Updates queued while paused wait behind the carried over chain
+        run(&updater, (5, Vec::new(), 1), &tree, &log);
+        assert_eq!(*log.lock(), vec![1, 2]);
+
+        updater.start_tick(|update| step(&updater, update, &tree, &log));
+        assert_eq!(*log.lock(), vec![1, 2, 4, 3]);
+        updater.start_tick(|update| step(&updater, update, &tree, &log));
+        assert_eq!(*log.lock(), vec![1, 2, 4, 3, 5]);
+        assert_eq!(updater.stats().pending, 0);
+    }
+}
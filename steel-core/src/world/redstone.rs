@@ -0,0 +1,95 @@
+//! Redstone signal queries.
+//!
+//! Mirrors vanilla's `SignalGetter`: blocks emit signals through
+//! `BlockBehavior::get_signal` / `get_direct_signal`, and full blocks pass on the
+//! strong (direct) signals they receive to their other neighbors.
+
+use std::sync::Arc;
+
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::Direction;
+use steel_registry::blocks::shapes::is_shape_full_block;
+use steel_utils::{BlockPos, BlockStateId};
+
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::world::World;
+
+/// The strongest redstone signal.
+pub const MAX_SIGNAL: i32 = 15;
+
+/// Order in which neighbors are checked (matches vanilla's `SignalGetter.DIRECTIONS`).
+const SIGNAL_DIRECTIONS: [Direction; 6] = [
+    Direction::Down,
+    Direction::Up,
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+/// Checks if a block state passes strong signals on to its neighbors.
+///
+/// Vanilla's default `isRedstoneConductor` is a full-block collision shape.
+// TODO: Glass, leaves, ice and a few others override this to never conduct.
+pub(crate) fn is_redstone_conductor(state: BlockStateId) -> bool {
+    is_shape_full_block(state.get_collision_shape())
+}
+
+impl World {
+    /// Gets the strong signal the block at `pos` emits.
+    ///
+    /// `direction` points from the receiving block towards `pos`.
+    pub fn get_direct_signal(self: &Arc<Self>, pos: BlockPos, direction: Direction) -> i32 {
+        let state = self.get_block_state(pos);
+        BLOCK_BEHAVIORS
+            .get_behavior(state.get_block())
+            .get_direct_signal(state, self, pos, direction)
+    }
+
+    /// Gets the strongest strong signal any neighbor sends into `pos`.
+    pub fn get_direct_signal_to(self: &Arc<Self>, pos: BlockPos) -> i32 {
+        let mut signal = 0;
+        for direction in SIGNAL_DIRECTIONS {
+            signal = signal.max(self.get_direct_signal(pos.relative(direction), direction));
+            if signal >= MAX_SIGNAL {
+                break;
+            }
+        }
+        signal
+    }
+
+    /// Gets the signal the block at `pos` emits, including strong signals a
+    /// conductor passes on.
+    ///
+    /// `direction` points from the receiving block towards `pos`.
+    pub fn get_signal(self: &Arc<Self>, pos: BlockPos, direction: Direction) -> i32 {
+        let state = self.get_block_state(pos);
+        let signal = BLOCK_BEHAVIORS
+            .get_behavior(state.get_block())
+            .get_signal(state, self, pos, direction);
+        if is_redstone_conductor(state) {
+            signal.max(self.get_direct_signal_to(pos))
+        } else {
+            signal
+        }
+    }
+
+    /// Checks if any neighbor powers the block at `pos`.
+    pub fn has_neighbor_signal(self: &Arc<Self>, pos: BlockPos) -> bool {
+        SIGNAL_DIRECTIONS
+            .into_iter()
+            .any(|direction| self.get_signal(pos.relative(direction), direction) > 0)
+    }
+
+    /// Gets the strongest signal any neighbor sends into `pos`.
+    pub fn get_best_neighbor_signal(self: &Arc<Self>, pos: BlockPos) -> i32 {
+        let mut signal = 0;
+        for direction in SIGNAL_DIRECTIONS {
+            signal = signal.max(self.get_signal(pos.relative(direction), direction));
+            if signal >= MAX_SIGNAL {
+                break;
+            }
+        }
+        signal
+    }
+}
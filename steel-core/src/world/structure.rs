@@ -4,13 +4,16 @@
 //! - `structureStarts`: structures originating in this chunk
 //! - `structuresReferences`: references to structures from nearby chunks
 //!
-//! Structure generation is not yet implemented. These types support
-//! persistence, the proto-chunk → level-chunk data flow, and future generation.
-//! The structure key is `Identifier` until a structure registry is added.
+//! Starts are created by [`crate::worldgen::Structure::generate`]; pieces keep
+//! their type-specific data as NBT so every piece type shares one layout.
 
 use rustc_hash::FxHashMap;
 
-use steel_utils::{BoundingBox, ChunkPos, Direction, Identifier};
+use steel_utils::random::Random;
+use steel_utils::{BlockPos, BoundingBox, ChunkPos, Direction, Identifier};
+
+use crate::chunk::beardifier::TerrainAdjustment;
+use crate::worldgen::{JigsawPiece, Structure, StructureLevel};
 
 /// A structure start placed in a chunk.
 ///
@@ -29,6 +32,55 @@ pub struct StructureStart {
     pub pieces: Vec<StructurePiece>,
 }
 
+impl StructureStart {
+    /// Gets the box around all pieces, grown by 12 blocks if the structure adapts
+    /// the terrain around it.
+    ///
+    /// Vanilla: `StructureStart.getBoundingBox()`.
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let first = self.pieces.first()?;
+        let bounding_box = self
+            .pieces
+            .iter()
+            .fold(first.bounding_box, |bounding_box, piece| {
+                BoundingBox::encapsulating(&bounding_box, &piece.bounding_box)
+            });
+        let adapts_terrain = Structure::by_id(&self.structure)
+            .is_some_and(|structure| structure.terrain_adaptation != TerrainAdjustment::None);
+        Some(if adapts_terrain {
+            bounding_box.inflated_by(12, 12, 12)
+        } else {
+            bounding_box
+        })
+    }
+
+    /// Places the parts of the pieces inside `chunk_box`.
+    ///
+    /// Vanilla: `StructureStart.placeInChunk()`.
+    pub fn place_in_chunk(
+        &self,
+        level: &impl StructureLevel,
+        random: &mut impl Random,
+        chunk_box: BoundingBox,
+    ) {
+        let Some(first) = self.pieces.first() else {
+            return;
+        };
+        let center = first.bounding_box.get_center();
+        let reference_pos = BlockPos::new(center.x(), first.bounding_box.min_y, center.z());
+        for piece in &self.pieces {
+            if !piece.bounding_box.intersects(&chunk_box) {
+                continue;
+            }
+            // TODO: Place the other piece types once their structures generate
+            if let Some(jigsaw) = JigsawPiece::from_piece(piece) {
+                jigsaw.place(level, random, chunk_box, reference_pos);
+            }
+        }
+    }
+}
+
 /// A single piece of a structure.
 ///
 /// Corresponds to vanilla's `StructurePiece`. Type-specific data is stored
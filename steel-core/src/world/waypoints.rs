@@ -0,0 +1,278 @@
+//! Server-side locator bar waypoints.
+//!
+//! Each tick every player in the world is matched against every waypoint source
+//! (other players and custom waypoints), and the client is told to track, update
+//! or untrack a waypoint whenever what it's allowed to see changes.
+//!
+//! Vanilla equivalent: `ServerWaypointManager` and `WaypointTransmitter`.
+
+use glam::DVec3;
+use rustc_hash::{FxHashMap, FxHashSet};
+use steel_protocol::packets::game::{
+    CWaypoint, WaypointIcon, WaypointId, WaypointOperation, WaypointPosition,
+};
+use steel_registry::vanilla_game_rules::LOCATOR_BAR;
+use steel_utils::locks::SyncMutex;
+use steel_utils::{ChunkPos, types::GameType};
+use uuid::Uuid;
+
+use crate::chunk::player_chunk_view::PlayerChunkView;
+use crate::config::STEEL_CONFIG;
+use crate::player::Player;
+use crate::world::World;
+
+/// Default transmit and receive range of players, in blocks.
+// TODO: Read `waypoint_transmit_range` / `waypoint_receive_range` once attributes exist
+pub const PLAYER_WAYPOINT_RANGE: f64 = 6.0E7;
+
+/// Sources outside the view distance but within this many blocks are sent as a
+/// chunk position; farther ones only as a direction.
+const CHUNK_CONNECTION_DISTANCE: f64 = 332.0;
+
+/// Minimum change of the azimuth (half a degree) before an update is sent.
+const AZIMUTH_UPDATE_THRESHOLD: f32 = 0.008_726_646;
+
+/// A waypoint that isn't attached to an entity, e.g. one added by a plugin.
+#[derive(Debug, Clone)]
+pub struct CustomWaypoint {
+    /// Where the waypoint is.
+    pub position: DVec3,
+    /// How the waypoint is drawn.
+    pub icon: WaypointIcon,
+    /// How far away, in blocks, players can see the waypoint.
+    pub range: f64,
+}
+
+/// Something that shows up on other players' locator bars this tick.
+struct WaypointSource {
+    id: WaypointId,
+    position: DVec3,
+    icon: WaypointIcon,
+    range: f64,
+}
+
+/// Tracks which waypoints each player in a world currently sees.
+pub struct WaypointManager {
+    /// Custom waypoints by name.
+    custom: SyncMutex<FxHashMap<String, CustomWaypoint>>,
+    /// For every receiving player, the waypoints they see and the last position sent.
+    connections: SyncMutex<FxHashMap<Uuid, FxHashMap<WaypointId, WaypointPosition>>>,
+}
+
+impl Default for WaypointManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaypointManager {
+    /// Creates a manager with no custom waypoints.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            custom: SyncMutex::new(FxHashMap::default()),
+            connections: SyncMutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Adds a custom waypoint, replacing any waypoint with the same name.
+    ///
+    /// Players receive it on the next tick.
+    pub fn add_waypoint(&self, name: impl Into<String>, waypoint: CustomWaypoint) {
+        self.custom.lock().insert(name.into(), waypoint);
+    }
+
+    /// Removes a custom waypoint. Players stop seeing it on the next tick.
+    pub fn remove_waypoint(&self, name: &str) -> Option<CustomWaypoint> {
+        self.custom.lock().remove(name)
+    }
+
+    /// Returns a copy of the custom waypoint with the given name.
+    #[must_use]
+    pub fn get_waypoint(&self, name: &str) -> Option<CustomWaypoint> {
+        self.custom.lock().get(name).cloned()
+    }
+
+    /// Sends the waypoint changes of this tick to every player in the world.
+    pub fn tick(&self, world: &World) {
+        let mut connections = self.connections.lock();
+
+        let enabled = STEEL_CONFIG.waypoints.enabled
+            && world.get_game_rule(LOCATOR_BAR).as_bool().unwrap_or(true);
+        if !enabled {
+            for (receiver, seen) in connections.drain() {
+                if let Some(player) = world.players.get_by_uuid(&receiver) {
+                    for id in seen.into_keys() {
+                        player.send_packet(CWaypoint::untrack(id));
+                    }
+                }
+            }
+            return;
+        }
+
+        let mut sources = Vec::new();
+        world.players.iter_players(|uuid, player| {
+            if is_transmitting(player) {
+                sources.push(WaypointSource {
+                    id: WaypointId::Uuid(*uuid),
+                    position: *player.position.lock(),
+                    icon: WaypointIcon::default(),
+                    range: PLAYER_WAYPOINT_RANGE,
+                });
+            }
+            true
+        });
+        for (name, waypoint) in self.custom.lock().iter() {
+            sources.push(WaypointSource {
+                id: WaypointId::Name(name.clone()),
+                position: waypoint.position,
+                icon: waypoint.icon.clone(),
+                range: waypoint.range,
+            });
+        }
+        let source_ids: FxHashSet<&WaypointId> = sources.iter().map(|source| &source.id).collect();
+
+        let mut receivers = FxHashSet::default();
+        world.players.iter_players(|uuid, player| {
+            receivers.insert(*uuid);
+            let seen = connections.entry(*uuid).or_default();
+            update_receiver(player, &sources, seen);
+
+            // Sources that left the world or were removed
+            seen.retain(|id, _| {
+                let keep = source_ids.contains(id);
+                if !keep {
+                    player.send_packet(CWaypoint::untrack(id.clone()));
+                }
+                keep
+            });
+            true
+        });
+        // The client drops its waypoints when it leaves the world
+        connections.retain(|uuid, _| receivers.contains(uuid));
+    }
+}
+
+/// Returns whether other players can see this player on their locator bar.
+///
+/// Vanilla equivalent: `WaypointTransmitter.isTransmittingWaypoint()`.
+// TODO: Hide invisible players and players wearing a pumpkin or mob head
+fn is_transmitting(player: &Player) -> bool {
+    player.game_mode.load() != GameType::Spectator && !player.is_crouching()
+}
+
+/// Diffs what `receiver` should see against what it has been sent and sends the difference.
+fn update_receiver(
+    receiver: &Player,
+    sources: &[WaypointSource],
+    seen: &mut FxHashMap<WaypointId, WaypointPosition>,
+) {
+    let receiver_uuid = receiver.gameprofile.id;
+    let receiver_pos = *receiver.position.lock();
+    let is_spectator = receiver.game_mode.load() == GameType::Spectator;
+    let view = receiver.last_tracking_view.lock().clone();
+
+    for source in sources {
+        if source.id == WaypointId::Uuid(receiver_uuid) {
+            continue;
+        }
+        let wanted = connection_position(source, receiver_pos, is_spectator, view.as_ref());
+        let previous = seen.get(&source.id).copied();
+
+        match (previous, wanted) {
+            (None, None) => {}
+            (None, Some(position)) => {
+                receiver.send_packet(track(source, WaypointOperation::Track, position));
+                seen.insert(source.id.clone(), position);
+            }
+            (Some(_), None) => {
+                receiver.send_packet(CWaypoint::untrack(source.id.clone()));
+                seen.remove(&source.id);
+            }
+            (Some(old), Some(new)) => {
+                if !same_kind(old, new) {
+                    // Vanilla breaks the old connection and starts a new one
+                    receiver.send_packet(CWaypoint::untrack(source.id.clone()));
+                    receiver.send_packet(track(source, WaypointOperation::Track, new));
+                } else if needs_update(old, new) {
+                    receiver.send_packet(track(source, WaypointOperation::Update, new));
+                } else {
+                    continue;
+                }
+                seen.insert(source.id.clone(), new);
+            }
+        }
+    }
+}
+
+/// Picks how precisely `source` is shown to a receiver, or `None` if it's out of range.
+///
+/// Vanilla equivalent: `WaypointTransmitter.createWaypointConnection()`.
+fn connection_position(
+    source: &WaypointSource,
+    receiver_pos: DVec3,
+    is_spectator: bool,
+    view: Option<&PlayerChunkView>,
+) -> Option<WaypointPosition> {
+    let distance = source.position.distance(receiver_pos);
+    // Spectators see every waypoint regardless of range
+    if !is_spectator && distance >= source.range.min(PLAYER_WAYPOINT_RANGE) {
+        return None;
+    }
+
+    let block_x = source.position.x.floor() as i32;
+    let block_z = source.position.z.floor() as i32;
+    let chunk = ChunkPos::new(block_x >> 4, block_z >> 4);
+    let in_view = view.is_some_and(|view| {
+        PlayerChunkView::is_within_distance(
+            view.center.0.x,
+            view.center.0.y,
+            i32::from(view.view_distance),
+            chunk.0.x,
+            chunk.0.y,
+            false,
+        )
+    });
+
+    Some(if in_view {
+        WaypointPosition::Block(block_x, source.position.y.floor() as i32, block_z)
+    } else if distance <= CHUNK_CONNECTION_DISTANCE {
+        WaypointPosition::Chunk(chunk)
+    } else {
+        // Direction from the source to the receiver, rotated clockwise by 90 degrees
+        let delta = receiver_pos - source.position;
+        WaypointPosition::Azimuth(delta.x.atan2(-delta.z) as f32)
+    })
+}
+
+const fn same_kind(a: WaypointPosition, b: WaypointPosition) -> bool {
+    matches!(
+        (a, b),
+        (WaypointPosition::Empty, WaypointPosition::Empty)
+            | (WaypointPosition::Block(..), WaypointPosition::Block(..))
+            | (WaypointPosition::Chunk(_), WaypointPosition::Chunk(_))
+            | (WaypointPosition::Azimuth(_), WaypointPosition::Azimuth(_))
+    )
+}
+
+fn needs_update(old: WaypointPosition, new: WaypointPosition) -> bool {
+    match (old, new) {
+        (WaypointPosition::Azimuth(old), WaypointPosition::Azimuth(new)) => {
+            (old - new).abs() > AZIMUTH_UPDATE_THRESHOLD
+        }
+        _ => old != new,
+    }
+}
+
+fn track(
+    source: &WaypointSource,
+    operation: WaypointOperation,
+    position: WaypointPosition,
+) -> CWaypoint {
+    CWaypoint {
+        operation,
+        id: source.id.clone(),
+        icon: source.icon.clone(),
+        position,
+    }
+}
@@ -1,16 +1,22 @@
 //! This module contains the implementation of the world's entity-related methods.
 use std::sync::Arc;
 
+use steel_protocol::packet_traits::EncodedPacket;
 use steel_protocol::packets::game::{
-    CAddEntity, CGameEvent, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo, GameEventType,
+    CAddEntity, CGameEvent, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
+    CUpdateAttributes, GameEventType,
 };
+use steel_protocol::utils::ConnectionProtocol;
 use steel_registry::{RegistryEntry, vanilla_entities};
 use tokio::time::Instant;
 
 use crate::{
-    entity::{Entity, PlayerEntityCallback, SharedEntity},
+    config::STEEL_CONFIG,
+    entity::{Entity, LivingEntity, PlayerEntityCallback, SharedEntity, riding},
     player::Player,
+    player::attached_entities::remove_root_vehicle,
     player::connection::NetworkConnection,
+    player::networking::BundleBuilder,
     world::World,
 };
 
@@ -23,12 +29,13 @@ impl World {
         if self.players.remove(&uuid).await.is_some() {
             let start = Instant::now();
 
-            // Save player data before removal
-            if let Some(server) = player.server.upgrade()
-                && let Err(e) = server.player_data_storage.save(&player).await
-            {
-                log::error!("Failed to save player data for {uuid}: {e}");
+            // Save player data before removal, while the vehicle is still around to
+            // be saved with it
+            if let Some(server) = player.server.upgrade() {
+                server.player_data_storage.save(&player);
             }
+            remove_root_vehicle(&player);
+            riding::stop_riding(player.as_ref());
 
             // Unregister from entity cache
             let pos = player.position();
@@ -53,6 +60,9 @@ impl World {
     }
 
     /// Adds a player to the world.
+    ///
+    /// # Panics
+    /// - If the packets announcing the player fail to encode.
     pub fn add_player(self: &Arc<Self>, player: Arc<Player>) {
         if !self.players.insert(player.clone()) {
             player.connection.close();
@@ -108,6 +118,10 @@ impl World {
                 let existing_pos = *existing_player.position.lock();
                 let (existing_yaw, existing_pitch) = existing_player.rotation.load();
                 let player_type_id = vanilla_entities::PLAYER.id() as i32;
+                let vehicle = existing_player
+                    .vehicle()
+                    .map(|vehicle| riding::passengers_packet(vehicle.as_ref()));
+                let attributes = existing_player.attributes().lock().syncable_snapshots();
                 player.send_bundle(|bundle| {
                     bundle.add(CAddEntity::player(
                         existing_player.id,
@@ -119,6 +133,13 @@ impl World {
                         existing_yaw,
                         existing_pitch,
                     ));
+                    bundle.add(CUpdateAttributes {
+                        entity_id: existing_player.id,
+                        attributes,
+                    });
+                    if let Some(vehicle) = vehicle {
+                        bundle.add(vehicle);
+                    }
                     // TODO: Add entity metadata and equipment packets here when implemented
                 });
             }
@@ -146,20 +167,37 @@ impl World {
             yaw,
             pitch,
         );
+        let attributes_packet = CUpdateAttributes {
+            entity_id: player.id,
+            attributes: player.attributes().lock().syncable_snapshots(),
+        };
+
+        // Encoded once and shared, every existing player gets the same bytes
+        let player_info_packet = EncodedPacket::from_bare(
+            player_info_packet,
+            STEEL_CONFIG.compression,
+            ConnectionProtocol::Play,
+        )
+        .expect("Failed to encode packet");
+        // Bundle spawn packet for atomic processing
+        let mut spawn_bundle = BundleBuilder::new(STEEL_CONFIG.compression);
+        spawn_bundle.add(spawn_packet);
+        spawn_bundle.add(attributes_packet);
+        // TODO: Add entity metadata and equipment packets here when implemented
+        let spawn_bundle = spawn_bundle.into_packets();
 
         self.players.iter_players(|_, p| {
-            p.send_packet(player_info_packet.clone());
+            p.connection.send_encoded(player_info_packet.clone());
             // Don't send spawn packet to self
             if p.gameprofile.id != player.gameprofile.id {
-                // Bundle spawn packet for atomic processing
-                p.send_bundle(|bundle| {
-                    bundle.add(spawn_packet.clone());
-                    // TODO: Add entity metadata and equipment packets here when implemented
-                });
+                p.connection.send_encoded_bundle(spawn_bundle.clone());
             }
             true
         });
 
+        // Vanilla: PlayerList.placeNewPlayer() sends the effects loaded from disk
+        player.send_active_effects();
+
         player.send_packet(CGameEvent {
             event: GameEventType::LevelChunksLoadStart,
             data: 0.0,
@@ -0,0 +1,165 @@
+//! Closest-biome search, used by `/locate biome`.
+//!
+//! Mirrors vanilla's `BiomeSource.findClosestBiome3d`: columns are visited in a
+//! square spiral around the origin, and each column is sampled at heights moving
+//! outwards from the origin's Y. The first match in that order wins.
+//!
+//! Vanilla walks the spiral on the main thread. We sample it in parallel on the
+//! generation pool instead, using an order-preserving search so the result is
+//! the same position vanilla finds; workers stop as soon as an earlier match is known.
+
+use std::ops::RangeInclusive;
+
+use rayon::prelude::*;
+use steel_registry::biome::BiomeRef;
+use steel_utils::BlockPos;
+
+use super::BiomeSourceKind;
+
+/// Search radius in blocks used by `/locate biome`.
+pub const LOCATE_BIOME_RADIUS: i32 = 6400;
+/// Horizontal distance in blocks between sampled columns.
+pub const LOCATE_BIOME_HORIZONTAL_STEP: i32 = 32;
+/// Vertical distance in blocks between samples in a column.
+pub const LOCATE_BIOME_VERTICAL_STEP: i32 = 64;
+
+/// Returns the offsets of a square spiral of the given radius, starting at the center.
+///
+/// Vanilla equivalent: `BlockPos.spiralAround(BlockPos.ZERO, radius, EAST, SOUTH)`.
+#[must_use]
+pub fn spiral_around(radius: i32) -> Vec<(i32, i32)> {
+    // East, south, west, north
+    const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+    let side = radius.max(0) as usize * 2 + 1;
+    let mut offsets = Vec::with_capacity(side * side);
+    let legs = 4 * radius.max(0);
+    let (mut x, mut z) = (0, 1);
+    let mut leg = -1;
+    let mut leg_size = 0;
+    let mut leg_index = 0;
+    loop {
+        let (dx, dz) = DIRECTIONS[((leg + 4) % 4) as usize];
+        x += dx;
+        z += dz;
+        if leg_index >= leg_size {
+            if leg >= legs {
+                break;
+            }
+            leg += 1;
+            leg_index = 0;
+            leg_size = leg / 2 + 1;
+        }
+        leg_index += 1;
+        offsets.push((x, z));
+    }
+    offsets
+}
+
+/// Returns values from `min` (inclusive) to `max` (exclusive) in steps of `step`,
+/// starting at `origin` and alternating above and below it.
+///
+/// Vanilla equivalent: `Mth.outFromOrigin()`.
+#[must_use]
+pub fn out_from_origin(origin: i32, min: i32, max: i32, step: i32) -> Vec<i32> {
+    if min >= max || step < 1 {
+        return Vec::new();
+    }
+    let origin = origin.clamp(min, max - 1);
+    let mut values = vec![origin];
+    let mut distance = step;
+    loop {
+        let above = origin + distance;
+        let below = origin - distance;
+        if above >= max && below < min {
+            break;
+        }
+        if above < max {
+            values.push(above);
+        }
+        if below >= min {
+            values.push(below);
+        }
+        distance += step;
+    }
+    values
+}
+
+impl BiomeSourceKind {
+    /// Finds the closest position whose biome matches `predicate`.
+    ///
+    /// Samples every `horizontal_step` blocks within `radius` of `origin`, and every
+    /// `vertical_step` blocks in `y_range` (the world's build height). Runs on the
+    /// current rayon pool, so call it inside `ThreadPool::install` to pick the pool.
+    ///
+    /// Vanilla equivalent: `BiomeSource.findClosestBiome3d()`.
+    pub fn find_closest_biome_3d(
+        &self,
+        origin: BlockPos,
+        radius: i32,
+        horizontal_step: i32,
+        vertical_step: i32,
+        y_range: RangeInclusive<i32>,
+        predicate: impl Fn(BiomeRef) -> bool + Sync,
+    ) -> Option<(BlockPos, BiomeRef)> {
+        let y_coords = out_from_origin(
+            origin.y(),
+            y_range.start() + 1,
+            y_range.end() + 1,
+            vertical_step,
+        );
+        let steps = radius.div_euclid(horizontal_step);
+
+        spiral_around(steps)
+            .into_par_iter()
+            .map_init(
+                || self.chunk_sampler(),
+                |sampler, (step_x, step_z)| {
+                    let block_x = origin.x() + step_x * horizontal_step;
+                    let block_z = origin.z() + step_z * horizontal_step;
+                    y_coords.iter().find_map(|&block_y| {
+                        let biome = sampler.sample(block_x >> 2, block_y >> 2, block_z >> 2);
+                        predicate(biome)
+                            .then_some((BlockPos::new(block_x, block_y, block_z), biome))
+                    })
+                },
+            )
+            .find_first(Option::is_some)
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spiral_visits_rings_in_vanilla_order() {
+        let offsets = spiral_around(1);
+        assert_eq!(
+            offsets,
+            vec![
+                (0, 0),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+            ]
+        );
+        assert_eq!(spiral_around(3).len(), 49);
+    }
+
+    #[test]
+    fn heights_alternate_around_origin() {
+        assert_eq!(
+            out_from_origin(64, -63, 321, 64),
+            vec![64, 128, 0, 192, 256, 320]
+        );
+        assert_eq!(out_from_origin(64, -127, 321, 64).len(), 7);
+        assert_eq!(out_from_origin(1000, 0, 100, 50), vec![99, 49]);
+    }
+}
@@ -19,10 +19,14 @@
 use steel_registry::biome::BiomeRef;
 use steel_registry::density_functions::nether::NetherColumnCache;
 use steel_registry::density_functions::overworld::OverworldColumnCache;
-use steel_registry::multi_noise::{get_nether_biome_cached, get_overworld_biome_cached};
+use steel_registry::multi_noise::{
+    NETHER_BIOME_PARAMETERS, OVERWORLD_BIOME_PARAMETERS, get_nether_biome_cached,
+    get_overworld_biome_cached,
+};
 use steel_registry::vanilla_biomes;
 
 use super::{NetherClimateSampler, OverworldClimateSampler};
+use steel_utils::climate::TargetPoint;
 use steel_utils::noise::EndIslands;
 
 /// Dimension-specific biome source.
@@ -73,6 +77,32 @@ impl BiomeSourceKind {
             Self::End(source) => source.chunk_sampler(),
         }
     }
+
+    /// Checks if this source can produce `biome` anywhere in the world.
+    ///
+    /// Vanilla equivalent: `BiomeSource.possibleBiomes().contains()`.
+    #[must_use]
+    pub fn can_generate(&self, biome: BiomeRef) -> bool {
+        match self {
+            Self::Overworld(_) => OVERWORLD_BIOME_PARAMETERS
+                .values()
+                .iter()
+                .any(|(_, candidate)| candidate.key == biome.key),
+            Self::Nether(_) => NETHER_BIOME_PARAMETERS
+                .values()
+                .iter()
+                .any(|(_, candidate)| candidate.key == biome.key),
+            Self::End(_) => [
+                &vanilla_biomes::THE_END,
+                &vanilla_biomes::END_HIGHLANDS,
+                &vanilla_biomes::END_MIDLANDS,
+                &vanilla_biomes::SMALL_END_ISLANDS,
+                &vanilla_biomes::END_BARRENS,
+            ]
+            .iter()
+            .any(|candidate| candidate.key == biome.key),
+        }
+    }
 }
 
 /// Per-chunk biome sampler with internal caches.
@@ -102,6 +132,58 @@ impl ChunkBiomeSampler<'_> {
             Self::End(s) => s.sample(quart_x, quart_y, quart_z),
         }
     }
+
+    /// Evaluate the climate of the 4×4×4 section whose lowest quart corner is
+    /// at the given position, a whole column at a time.
+    ///
+    /// Following [`sample`](Self::sample) calls inside the section only do the
+    /// biome lookup, in whatever order the caller visits them, so the R-tree
+    /// warm start still sees vanilla's lookup order.
+    pub fn prepare_section(&mut self, quart_x: i32, quart_y: i32, quart_z: i32) {
+        match self {
+            Self::Overworld(s) => s.prepare_section(quart_x, quart_y, quart_z),
+            Self::Nether(s) => s.prepare_section(quart_x, quart_y, quart_z),
+            // End biomes don't depend on climate, there's nothing to prepare
+            Self::End(_) => {}
+        }
+    }
+}
+
+/// Climate targets of one chunk section, evaluated ahead of its biome lookups.
+struct SectionTargets {
+    /// Quart position of the section's lowest corner.
+    origin: (i32, i32, i32),
+    /// One column of four targets after another, indexed by `(x * 4 + z) * 4 + y`.
+    targets: [TargetPoint; 64],
+}
+
+impl SectionTargets {
+    fn evaluate(
+        quart_x: i32,
+        quart_y: i32,
+        quart_z: i32,
+        mut sample_column: impl FnMut(i32, i32, &mut [TargetPoint]),
+    ) -> Self {
+        let mut targets = [TargetPoint::new(0, 0, 0, 0, 0, 0); 64];
+        for (column, out) in targets.chunks_exact_mut(4).enumerate() {
+            let column = column as i32;
+            sample_column(quart_x + column / 4, quart_z + column % 4, out);
+        }
+        Self {
+            origin: (quart_x, quart_y, quart_z),
+            targets,
+        }
+    }
+
+    fn get(&self, quart_x: i32, quart_y: i32, quart_z: i32) -> Option<&TargetPoint> {
+        let x = quart_x - self.origin.0;
+        let y = quart_y - self.origin.1;
+        let z = quart_z - self.origin.2;
+        if !(0..4).contains(&x) || !(0..4).contains(&y) || !(0..4).contains(&z) {
+            return None;
+        }
+        Some(&self.targets[((x * 4 + z) * 4 + y) as usize])
+    }
 }
 
 /// Multi-noise biome source for the overworld.
@@ -134,6 +216,7 @@ impl OverworldBiomeSource {
             source: self,
             column_cache: OverworldColumnCache::new(),
             biome_cache: None,
+            section: None,
         }))
     }
 }
@@ -142,16 +225,37 @@ pub struct OverworldChunkBiomeSampler<'a> {
     source: &'a OverworldBiomeSource,
     column_cache: OverworldColumnCache,
     biome_cache: Option<usize>,
+    section: Option<SectionTargets>,
 }
 
 impl OverworldChunkBiomeSampler<'_> {
     fn sample(&mut self, quart_x: i32, quart_y: i32, quart_z: i32) -> BiomeRef {
-        let target =
+        let target = if let Some(target) = self
+            .section
+            .as_ref()
+            .and_then(|section| section.get(quart_x, quart_y, quart_z))
+        {
+            *target
+        } else {
             self.source
                 .climate_sampler
-                .sample(quart_x, quart_y, quart_z, &mut self.column_cache);
+                .sample(quart_x, quart_y, quart_z, &mut self.column_cache)
+        };
         get_overworld_biome_cached(&target, &mut self.biome_cache)
     }
+
+    fn prepare_section(&mut self, quart_x: i32, quart_y: i32, quart_z: i32) {
+        let climate_sampler = &self.source.climate_sampler;
+        let column_cache = &mut self.column_cache;
+        self.section = Some(SectionTargets::evaluate(
+            quart_x,
+            quart_y,
+            quart_z,
+            |column_x, column_z, out| {
+                climate_sampler.sample_column(column_x, quart_y, column_z, column_cache, out);
+            },
+        ));
+    }
 }
 
 // ── Nether ──────────────────────────────────────────────────────────────────
@@ -180,6 +284,7 @@ impl NetherBiomeSource {
             source: self,
             column_cache: NetherColumnCache::new(),
             biome_cache: None,
+            section: None,
         }))
     }
 }
@@ -188,16 +293,37 @@ pub struct NetherChunkBiomeSampler<'a> {
     source: &'a NetherBiomeSource,
     column_cache: NetherColumnCache,
     biome_cache: Option<usize>,
+    section: Option<SectionTargets>,
 }
 
 impl NetherChunkBiomeSampler<'_> {
     fn sample(&mut self, quart_x: i32, quart_y: i32, quart_z: i32) -> BiomeRef {
-        let target =
+        let target = if let Some(target) = self
+            .section
+            .as_ref()
+            .and_then(|section| section.get(quart_x, quart_y, quart_z))
+        {
+            *target
+        } else {
             self.source
                 .climate_sampler
-                .sample(quart_x, quart_y, quart_z, &mut self.column_cache);
+                .sample(quart_x, quart_y, quart_z, &mut self.column_cache)
+        };
         get_nether_biome_cached(&target, &mut self.biome_cache)
     }
+
+    fn prepare_section(&mut self, quart_x: i32, quart_y: i32, quart_z: i32) {
+        let climate_sampler = &self.source.climate_sampler;
+        let column_cache = &mut self.column_cache;
+        self.section = Some(SectionTargets::evaluate(
+            quart_x,
+            quart_y,
+            quart_z,
+            |column_x, column_z, out| {
+                climate_sampler.sample_column(column_x, quart_y, column_z, column_cache, out);
+            },
+        ));
+    }
 }
 
 // ── The End ───────────────────────────────────────────────────────────────────
@@ -58,7 +58,41 @@ impl OverworldClimateSampler {
 
         // Ensure column cache is populated for this (x, z)
         cache.ensure(block_x, block_z, &self.noises);
+        self.sample_ensured(block_x, block_y, block_z, cache)
+    }
+
+    /// Sample climate for a run of quart positions stacked in one column,
+    /// filling `out` upwards from `first_quart_y`.
+    ///
+    /// The column's xz-only values are evaluated once for the whole run, so
+    /// callers that visit positions out of column order (like chunk biome
+    /// filling, which walks x → y → z) don't recompute them per position.
+    pub fn sample_column(
+        &self,
+        quart_x: i32,
+        first_quart_y: i32,
+        quart_z: i32,
+        cache: &mut OverworldColumnCache,
+        out: &mut [TargetPoint],
+    ) {
+        let block_x = quart_x << 2;
+        let block_z = quart_z << 2;
+        cache.ensure(block_x, block_z, &self.noises);
+
+        for (quart_y, target) in (first_quart_y..).zip(out) {
+            *target = self.sample_ensured(block_x, quart_y << 2, block_z, cache);
+        }
+    }
 
+    /// Evaluates the router at a block position whose column is already in `cache`.
+    #[inline]
+    fn sample_ensured(
+        &self,
+        block_x: i32,
+        block_y: i32,
+        block_z: i32,
+        cache: &mut OverworldColumnCache,
+    ) -> TargetPoint {
         // Density functions return f64 but vanilla truncates to float before quantizing.
         // The f64→f32→f64 round-trip through quantize_coord is intentional for parity.
         let temp =
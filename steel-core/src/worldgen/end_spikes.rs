@@ -0,0 +1,202 @@
+//! The obsidian spikes around the main End island.
+//!
+//! The spikes' positions are fixed, but which spike gets which size depends on
+//! the world seed. The dragon fight counts end crystals on top of them.
+
+use std::array;
+use std::f64::consts::PI;
+
+use glam::DVec3;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::BlockStateProperties;
+use steel_registry::blocks::shapes::AABBd;
+use steel_registry::{REGISTRY, vanilla_blocks, vanilla_entities};
+use steel_utils::BlockPos;
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::random::{Random, shuffle};
+use steel_utils::types::UpdateFlags;
+
+use crate::chunk::chunk_access::ChunkAccess;
+use crate::chunk::proto_chunk::ProtoEntity;
+
+/// Number of spikes around the island.
+pub const SPIKE_COUNT: usize = 10;
+
+/// Distance of the spikes from the island's center.
+const SPIKE_DISTANCE: f64 = 42.0;
+
+/// One obsidian spike.
+///
+/// Vanilla: `SpikeFeature.EndSpike`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndSpike {
+    /// X coordinate of the spike's center.
+    pub center_x: i32,
+    /// Z coordinate of the spike's center.
+    pub center_z: i32,
+    /// Radius of the spike.
+    pub radius: i32,
+    /// Y coordinate of the spike's top.
+    pub height: i32,
+    /// Whether the crystal on top is caged in iron bars.
+    pub guarded: bool,
+}
+
+impl EndSpike {
+    /// Gets the box crystals on top of this spike are searched in.
+    ///
+    /// Vanilla: `EndSpike.getTopBoundingBox()`, which spans the whole possible
+    /// build height; callers clamp it to the world.
+    #[must_use]
+    pub fn top_bounding_box(&self, min_y: i32, max_y: i32) -> AABBd {
+        AABBd::new(
+            f64::from(self.center_x - self.radius),
+            f64::from(min_y),
+            f64::from(self.center_z - self.radius),
+            f64::from(self.center_x + self.radius),
+            f64::from(max_y),
+            f64::from(self.center_z + self.radius),
+        )
+    }
+}
+
+/// Places the part of `spike` that lies in `chunk`.
+///
+/// The spike's blocks don't depend on the random, so every chunk it overlaps
+/// builds its own part. Only the chunk holding the center places the crystal.
+/// Vanilla: `SpikeFeature.placeSpike()`.
+pub fn place_end_spike(chunk: &ChunkAccess, spike: &EndSpike, random: &mut impl Random) {
+    let state = |block| REGISTRY.blocks.get_default_state_id(block);
+    let pos = chunk.pos();
+    let min_x = pos.0.x * 16;
+    let min_z = pos.0.y * 16;
+    let in_chunk =
+        |x: i32, z: i32| (min_x..min_x + 16).contains(&x) && (min_z..min_z + 16).contains(&z);
+
+    let radius = spike.radius;
+    for x in (spike.center_x - radius).max(min_x)..=(spike.center_x + radius).min(min_x + 15) {
+        for z in (spike.center_z - radius).max(min_z)..=(spike.center_z + radius).min(min_z + 15) {
+            let dx = x - spike.center_x;
+            let dz = z - spike.center_z;
+            let in_spike = dx * dx + dz * dz <= radius * radius + 1;
+            for y in chunk.min_y()..=spike.height + 10 {
+                let block = if in_spike && y < spike.height {
+                    vanilla_blocks::OBSIDIAN
+                } else if y > 65 {
+                    vanilla_blocks::AIR
+                } else {
+                    continue;
+                };
+                chunk.set_block_state(
+                    BlockPos::new(x, y, z),
+                    state(block),
+                    UpdateFlags::UPDATE_ALL,
+                );
+            }
+        }
+    }
+
+    if spike.guarded {
+        for dx in -2..=2_i32 {
+            for dz in -2..=2_i32 {
+                for dy in 0..=3 {
+                    let max_x = dx.abs() == 2;
+                    let max_z = dz.abs() == 2;
+                    let max_y = dy == 3;
+                    let (x, z) = (spike.center_x + dx, spike.center_z + dz);
+                    if !(max_x || max_z || max_y) || !in_chunk(x, z) {
+                        continue;
+                    }
+                    let north_or_south = max_x || max_y;
+                    let east_or_west = max_z || max_y;
+                    let bars = state(vanilla_blocks::IRON_BARS)
+                        .set_value(&BlockStateProperties::NORTH, north_or_south && dz != -2)
+                        .set_value(&BlockStateProperties::SOUTH, north_or_south && dz != 2)
+                        .set_value(&BlockStateProperties::WEST, east_or_west && dx != -2)
+                        .set_value(&BlockStateProperties::EAST, east_or_west && dx != 2);
+                    chunk.set_block_state(
+                        BlockPos::new(x, spike.height + dy, z),
+                        bars,
+                        UpdateFlags::UPDATE_ALL,
+                    );
+                }
+            }
+        }
+    }
+
+    if !in_chunk(spike.center_x, spike.center_z) {
+        return;
+    }
+    // TODO: Make the crystal invulnerable and aim its beam when respawning the dragon
+    if let ChunkAccess::Proto(proto) = chunk {
+        proto.add_entity(ProtoEntity {
+            entity_type: vanilla_entities::END_CRYSTAL,
+            position: DVec3::new(
+                f64::from(spike.center_x) + 0.5,
+                f64::from(spike.height + 1),
+                f64::from(spike.center_z) + 0.5,
+            ),
+            rotation: (random.next_f32() * 360.0, 0.0),
+        });
+    }
+    let crystal_pos = BlockPos::new(spike.center_x, spike.height + 1, spike.center_z);
+    chunk.set_block_state(
+        crystal_pos.below(),
+        state(vanilla_blocks::BEDROCK),
+        UpdateFlags::UPDATE_ALL,
+    );
+    // Bedrock never burns, so this is what `FireBlock.getState()` gives
+    chunk.set_block_state(
+        crystal_pos,
+        state(vanilla_blocks::FIRE),
+        UpdateFlags::UPDATE_ALL,
+    );
+}
+
+/// Gets the spikes of a world with the given seed.
+///
+/// Vanilla: `SpikeFeature.getSpikesForLevel()`.
+#[must_use]
+pub fn end_spikes(seed: i64) -> [EndSpike; SPIKE_COUNT] {
+    let key = LegacyRandom::from_seed(seed as u64).next_i64() & 0xFFFF;
+    let mut sizes: [i32; SPIKE_COUNT] = array::from_fn(|size| size as i32);
+    shuffle(&mut sizes, &mut LegacyRandom::from_seed(key as u64));
+
+    array::from_fn(|i| {
+        let angle = 2.0 * (-PI + PI / 10.0 * i as f64);
+        let size = sizes[i];
+        EndSpike {
+            center_x: (SPIKE_DISTANCE * angle.cos()).floor() as i32,
+            center_z: (SPIKE_DISTANCE * angle.sin()).floor() as i32,
+            radius: 2 + size / 3,
+            height: 76 + size * 3,
+            guarded: size == 1 || size == 2,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spikes_use_every_size_once() {
+        let spikes = end_spikes(12345);
+        let mut heights: Vec<i32> = spikes.iter().map(|spike| spike.height).collect();
+        heights.sort_unstable();
+        let expected: Vec<i32> = (0..10).map(|size| 76 + size * 3).collect();
+        assert_eq!(heights, expected);
+        assert_eq!(spikes.iter().filter(|spike| spike.guarded).count(), 2);
+    }
+
+    #[test]
+    fn spikes_circle_the_island() {
+        let spikes = end_spikes(0);
+        assert_eq!((spikes[0].center_x, spikes[0].center_z), (42, 0));
+        assert_eq!((spikes[5].center_x, spikes[5].center_z), (-42, -1));
+        for spike in spikes {
+            let distance = f64::from(spike.center_x).hypot(f64::from(spike.center_z));
+            assert!((distance - SPIKE_DISTANCE).abs() < 2.0);
+        }
+    }
+}
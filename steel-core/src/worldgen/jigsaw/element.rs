@@ -0,0 +1,468 @@
+//! The elements of template pools: the templates, lists and features a jigsaw
+//! structure is built from.
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, FrontAndTop};
+use steel_registry::{REGISTRY, vanilla_blocks};
+use steel_utils::random::{Random, shuffle};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BoundingBox, Identifier, Rotation};
+
+use crate::chunk::heightmap::HeightmapType;
+use crate::worldgen::structure_level::StructureLevel;
+use crate::worldgen::structure_processor::{ProcessorList, StructureProcessor};
+use crate::worldgen::structure_template::{
+    JigsawBlockInfo, StructurePlaceSettings, StructureTemplate,
+};
+use crate::worldgen::template_manager::STRUCTURE_TEMPLATES;
+
+/// How a piece follows the terrain.
+///
+/// Vanilla: `StructureTemplatePool.Projection`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Projection {
+    /// The piece keeps its shape.
+    Rigid,
+    /// Each block column of the piece is moved onto the terrain.
+    TerrainMatching,
+}
+
+impl Projection {
+    /// Gets the name used in saved data.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Rigid => "rigid",
+            Self::TerrainMatching => "terrain_matching",
+        }
+    }
+
+    /// Gets the projection with the given saved name.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "rigid" => Some(Self::Rigid),
+            "terrain_matching" => Some(Self::TerrainMatching),
+            _ => None,
+        }
+    }
+
+    /// Vanilla: `StructureTemplatePool.Projection.getProcessors()`.
+    fn processors(self) -> Vec<StructureProcessor> {
+        match self {
+            Self::Rigid => Vec::new(),
+            Self::TerrainMatching => vec![StructureProcessor::Gravity {
+                heightmap: HeightmapType::WorldSurfaceWg,
+                offset: -1,
+            }],
+        }
+    }
+}
+
+/// Whether placed blocks are waterlogged when placed into water.
+///
+/// Vanilla: `LiquidSettings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiquidSettings {
+    /// Blocks placed into water are waterlogged.
+    #[default]
+    ApplyWaterlogging,
+    /// Blocks keep their own waterlogged state.
+    IgnoreWaterlogging,
+}
+
+impl LiquidSettings {
+    /// Gets the name used in saved data.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ApplyWaterlogging => "apply_waterlogging",
+            Self::IgnoreWaterlogging => "ignore_waterlogging",
+        }
+    }
+
+    /// Gets the settings with the given saved name.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "apply_waterlogging" => Some(Self::ApplyWaterlogging),
+            "ignore_waterlogging" => Some(Self::IgnoreWaterlogging),
+            _ => None,
+        }
+    }
+}
+
+/// A template placed as one piece.
+///
+/// Vanilla: `SinglePoolElement`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SinglePoolElement {
+    /// The id of the template.
+    pub location: Identifier,
+    /// Run on each block after the element's own processors.
+    pub processors: ProcessorList,
+    /// How the piece follows the terrain.
+    pub projection: Projection,
+    /// Replaces the liquid settings of the structure.
+    #[serde(default)]
+    pub override_liquid_settings: Option<LiquidSettings>,
+}
+
+impl SinglePoolElement {
+    fn template(&self) -> Arc<StructureTemplate> {
+        STRUCTURE_TEMPLATES.get(&self.location).unwrap_or_else(|| {
+            log::warn!("Missing structure template {}", self.location);
+            Arc::default()
+        })
+    }
+
+    /// Vanilla: `SinglePoolElement.getSettings()`.
+    fn settings(
+        &self,
+        rotation: Rotation,
+        bounding_box: BoundingBox,
+        liquid_settings: LiquidSettings,
+        legacy: bool,
+    ) -> StructurePlaceSettings {
+        let mut processors = Vec::new();
+        if !legacy {
+            processors.push(ignore(&[vanilla_blocks::STRUCTURE_BLOCK]));
+        }
+        processors.push(StructureProcessor::JigsawReplacement);
+        processors.extend(self.processors.processors.iter().cloned());
+        processors.extend(self.projection.processors());
+        // Vanilla: `LegacySinglePoolElement.getSettings()`
+        if legacy {
+            processors.push(ignore(&[
+                vanilla_blocks::STRUCTURE_BLOCK,
+                vanilla_blocks::AIR,
+            ]));
+        }
+        StructurePlaceSettings {
+            rotation,
+            bounding_box: Some(bounding_box),
+            known_shape: true,
+            ignore_entities: false,
+            keep_liquids: self.override_liquid_settings.unwrap_or(liquid_settings)
+                == LiquidSettings::ApplyWaterlogging,
+            processors,
+            ..StructurePlaceSettings::default()
+        }
+    }
+}
+
+fn ignore(blocks: &[BlockRef]) -> StructureProcessor {
+    StructureProcessor::BlockIgnore {
+        blocks: blocks.to_vec(),
+    }
+}
+
+/// Several elements placed on top of each other as one piece.
+///
+/// Vanilla: `ListPoolElement`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "ListPoolElementJson")]
+pub struct ListPoolElement {
+    /// The elements, placed in order. The first one holds the jigsaws.
+    pub elements: Vec<PoolElement>,
+    /// How the piece follows the terrain, shared by all elements.
+    pub projection: Projection,
+}
+
+#[derive(Deserialize)]
+struct ListPoolElementJson {
+    elements: Vec<PoolElement>,
+    projection: Projection,
+}
+
+impl From<ListPoolElementJson> for ListPoolElement {
+    fn from(json: ListPoolElementJson) -> Self {
+        let mut element = Self {
+            elements: json.elements,
+            projection: json.projection,
+        };
+        element.set_projection(json.projection);
+        element
+    }
+}
+
+impl ListPoolElement {
+    /// Vanilla: `ListPoolElement.setProjectionOnEachElement()`.
+    fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+        for element in &mut self.elements {
+            element.set_projection(projection);
+        }
+    }
+}
+
+/// A configured feature placed as a piece.
+///
+/// Vanilla: `FeaturePoolElement`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeaturePoolElement {
+    /// The id of the configured feature.
+    pub feature: Identifier,
+    /// How the piece follows the terrain.
+    pub projection: Projection,
+}
+
+/// One entry of a template pool.
+///
+/// Vanilla: `StructurePoolElement`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "element_type")]
+pub enum PoolElement {
+    /// Vanilla: `SinglePoolElement`.
+    #[serde(rename = "minecraft:single_pool_element")]
+    Single(SinglePoolElement),
+    /// Like [`Self::Single`], but also skips the template's air.
+    /// Vanilla: `LegacySinglePoolElement`.
+    #[serde(rename = "minecraft:legacy_single_pool_element")]
+    LegacySingle(SinglePoolElement),
+    /// Vanilla: `ListPoolElement`.
+    #[serde(rename = "minecraft:list_pool_element")]
+    List(ListPoolElement),
+    /// Vanilla: `FeaturePoolElement`.
+    #[serde(rename = "minecraft:feature_pool_element")]
+    Feature(FeaturePoolElement),
+    /// Stops a jigsaw from growing. Vanilla: `EmptyPoolElement`.
+    #[serde(rename = "minecraft:empty_pool_element")]
+    Empty,
+}
+
+impl PoolElement {
+    /// Gets the projection of the element. A list's projection applies to its elements.
+    #[must_use]
+    pub const fn projection(&self) -> Projection {
+        match self {
+            Self::Single(element) | Self::LegacySingle(element) => element.projection,
+            Self::List(element) => element.projection,
+            Self::Feature(element) => element.projection,
+            Self::Empty => Projection::TerrainMatching,
+        }
+    }
+
+    /// Vanilla: `StructurePoolElement.setProjection()`.
+    fn set_projection(&mut self, projection: Projection) {
+        match self {
+            Self::Single(element) | Self::LegacySingle(element) => element.projection = projection,
+            Self::List(element) => element.set_projection(projection),
+            Self::Feature(element) => element.projection = projection,
+            Self::Empty => {}
+        }
+    }
+
+    /// Vanilla: `StructurePoolElement.getGroundLevelDelta()`.
+    #[must_use]
+    pub const fn ground_level_delta(&self) -> i32 {
+        1
+    }
+
+    /// Gets the jigsaws of the element placed at `pos`, in the order they are tried.
+    ///
+    /// Vanilla: `StructurePoolElement.getShuffledJigsawBlocks()`.
+    pub fn shuffled_jigsaws(
+        &self,
+        pos: BlockPos,
+        rotation: Rotation,
+        random: &mut impl Random,
+    ) -> Vec<JigsawBlockInfo> {
+        match self {
+            Self::Single(element) | Self::LegacySingle(element) => {
+                let mut jigsaws = element.template().jigsaws(pos, rotation);
+                shuffle(&mut jigsaws, random);
+                // Stable, so jigsaws of the same priority keep their shuffled order
+                jigsaws.sort_by_key(|jigsaw| -jigsaw.selection_priority);
+                jigsaws
+            }
+            Self::List(element) => element
+                .elements
+                .first()
+                .map(|first| first.shuffled_jigsaws(pos, rotation, random))
+                .unwrap_or_default(),
+            Self::Feature(_) => {
+                // Vanilla: `FeaturePoolElement.fillDefaultJigsawNBT()`
+                let mut nbt = NbtCompound::new();
+                nbt.insert("name", "minecraft:bottom");
+                nbt.insert("pool", "minecraft:empty");
+                nbt.insert("target", "minecraft:empty");
+                nbt.insert("joint", "rollable");
+                let state = REGISTRY
+                    .blocks
+                    .get_default_state_id(vanilla_blocks::JIGSAW)
+                    .set_value(&BlockStateProperties::ORIENTATION, FrontAndTop::DownSouth);
+                vec![JigsawBlockInfo::of(pos, state, Some(&nbt))]
+            }
+            Self::Empty => Vec::new(),
+        }
+    }
+
+    /// Gets the box the element covers when placed at `pos`.
+    ///
+    /// Vanilla: `StructurePoolElement.getBoundingBox()`.
+    #[must_use]
+    pub fn bounding_box(&self, pos: BlockPos, rotation: Rotation) -> BoundingBox {
+        match self {
+            Self::Single(element) | Self::LegacySingle(element) => {
+                let settings = StructurePlaceSettings {
+                    rotation,
+                    ..StructurePlaceSettings::default()
+                };
+                element.template().bounding_box(&settings, pos)
+            }
+            Self::List(element) => element
+                .elements
+                .iter()
+                .filter(|element| !matches!(element, Self::Empty))
+                .map(|element| element.bounding_box(pos, rotation))
+                .reduce(|a, b| BoundingBox::encapsulating(&a, &b))
+                .unwrap_or_else(|| block_box(pos)),
+            Self::Feature(_) | Self::Empty => block_box(pos),
+        }
+    }
+
+    /// Places the element at `pos`, only touching blocks inside `bounding_box`.
+    ///
+    /// Vanilla: `StructurePoolElement.place()`.
+    #[expect(clippy::too_many_arguments, reason = "mirrors the vanilla signature")]
+    pub fn place(
+        &self,
+        level: &impl StructureLevel,
+        pos: BlockPos,
+        reference_pos: BlockPos,
+        rotation: Rotation,
+        bounding_box: BoundingBox,
+        random: &mut impl Random,
+        liquid_settings: LiquidSettings,
+    ) -> bool {
+        let flags = UpdateFlags::UPDATE_CLIENTS | UpdateFlags::UPDATE_KNOWN_SHAPE;
+        match self {
+            Self::Single(element) | Self::LegacySingle(element) => {
+                let legacy = matches!(self, Self::LegacySingle(_));
+                let settings = element.settings(rotation, bounding_box, liquid_settings, legacy);
+                element.template().place_in_world(
+                    level,
+                    pos,
+                    reference_pos,
+                    &settings,
+                    random,
+                    flags,
+                )
+            }
+            Self::List(element) => element.elements.iter().all(|child| {
+                child.place(
+                    level,
+                    pos,
+                    reference_pos,
+                    rotation,
+                    bounding_box,
+                    random,
+                    liquid_settings,
+                )
+            }),
+            // TODO: Place the feature once configured features exist
+            Self::Feature(_) | Self::Empty => true,
+        }
+    }
+
+    /// Saves the element like vanilla's `StructurePoolElement.CODEC`.
+    // TODO: Save inline processor lists; they are saved empty for now
+    #[must_use]
+    pub fn save(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        match self {
+            Self::Single(element) | Self::LegacySingle(element) => {
+                let element_type = if matches!(self, Self::LegacySingle(_)) {
+                    "minecraft:legacy_single_pool_element"
+                } else {
+                    "minecraft:single_pool_element"
+                };
+                nbt.insert("element_type", element_type);
+                nbt.insert("location", element.location.to_string());
+                match &element.processors.id {
+                    Some(id) => nbt.insert("processors", id.to_string()),
+                    None => {
+                        let mut inline = NbtCompound::new();
+                        inline.insert("processors", NbtList::Empty);
+                        nbt.insert("processors", inline);
+                    }
+                }
+                nbt.insert("projection", element.projection.name());
+                if let Some(liquid_settings) = element.override_liquid_settings {
+                    nbt.insert("override_liquid_settings", liquid_settings.name());
+                }
+            }
+            Self::List(element) => {
+                nbt.insert("element_type", "minecraft:list_pool_element");
+                let elements = element.elements.iter().map(Self::save).collect();
+                nbt.insert("elements", NbtList::Compound(elements));
+                nbt.insert("projection", element.projection.name());
+            }
+            Self::Feature(element) => {
+                nbt.insert("element_type", "minecraft:feature_pool_element");
+                nbt.insert("feature", element.feature.to_string());
+                nbt.insert("projection", element.projection.name());
+            }
+            Self::Empty => nbt.insert("element_type", "minecraft:empty_pool_element"),
+        }
+        nbt
+    }
+
+    /// Loads an element saved by [`Self::save`].
+    #[must_use]
+    pub fn load(nbt: &NbtCompound) -> Option<Self> {
+        let id = |key: &str| nbt.string(key)?.to_str().parse::<Identifier>().ok();
+        let projection = || Projection::by_name(&nbt.string("projection")?.to_str());
+        let element_type = nbt.string("element_type")?.to_str();
+        let single = || {
+            let processors = match id("processors") {
+                Some(list) => ProcessorList::by_id(&list)?,
+                None => ProcessorList {
+                    id: None,
+                    processors: Arc::new([]),
+                },
+            };
+            Some(SinglePoolElement {
+                location: id("location")?,
+                processors,
+                projection: projection()?,
+                override_liquid_settings: nbt
+                    .string("override_liquid_settings")
+                    .and_then(|name| LiquidSettings::by_name(&name.to_str())),
+            })
+        };
+        match &*element_type {
+            "minecraft:single_pool_element" => Some(Self::Single(single()?)),
+            "minecraft:legacy_single_pool_element" => Some(Self::LegacySingle(single()?)),
+            "minecraft:list_pool_element" => Some(Self::List(ListPoolElement {
+                elements: nbt
+                    .list("elements")
+                    .and_then(NbtList::compounds)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(Self::load)
+                    .collect::<Option<_>>()?,
+                projection: projection()?,
+            })),
+            "minecraft:feature_pool_element" => Some(Self::Feature(FeaturePoolElement {
+                feature: id("feature")?,
+                projection: projection()?,
+            })),
+            "minecraft:empty_pool_element" => Some(Self::Empty),
+            _ => None,
+        }
+    }
+}
+
+/// The box of a single block at `pos`.
+const fn block_box(pos: BlockPos) -> BoundingBox {
+    BoundingBox::new(pos.x(), pos.y(), pos.z(), pos.x(), pos.y(), pos.z())
+}
@@ -0,0 +1,180 @@
+//! Jigsaw structures, assembled from template pools by connecting jigsaw blocks.
+//!
+//! A start piece is picked from the start pool, then each jigsaw of a placed piece
+//! picks a piece from its pool whose jigsaw faces it, until the depth limit or the
+//! free space around the start runs out.
+
+mod element;
+mod piece;
+mod placement;
+mod pool;
+mod pool_alias;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use steel_utils::{BlockPos, Identifier};
+
+pub use element::{LiquidSettings, PoolElement, Projection};
+pub use piece::JigsawPiece;
+pub use pool::TemplatePool;
+pub use pool_alias::PoolAliasBinding;
+
+use placement::add_pieces;
+use pool_alias::PoolAliasLookup;
+
+use super::structures::{GenerationContext, HeightProvider};
+use crate::chunk::heightmap::HeightmapType;
+
+/// The settings of a jigsaw structure.
+///
+/// Vanilla: `JigsawStructure`.
+#[derive(Debug, Deserialize)]
+pub struct JigsawStructure {
+    /// The pool the start piece is picked from.
+    pub start_pool: Identifier,
+    /// The jigsaw of the start piece placed at the start position, if any.
+    #[serde(default)]
+    pub start_jigsaw_name: Option<Identifier>,
+    /// How many jigsaws deep pieces grow from the start piece.
+    pub size: i32,
+    /// The height of the start position.
+    pub start_height: HeightProvider,
+    /// Reserves space above pieces so pieces attached inside them later fit.
+    pub use_expansion_hack: bool,
+    /// Moves the start onto the terrain, offset by the start height.
+    #[serde(default, deserialize_with = "deserialize_optional_heightmap")]
+    pub project_start_to_heightmap: Option<HeightmapType>,
+    /// How far pieces can be from the start.
+    pub max_distance_from_center: MaxDistance,
+    /// Swaps the pools jigsaws name, picked once per start.
+    #[serde(default)]
+    pub pool_aliases: Vec<PoolAliasBinding>,
+    /// How far pieces must stay from the bottom and top of the world.
+    #[serde(default)]
+    pub dimension_padding: DimensionPadding,
+    /// Whether blocks placed into water are waterlogged.
+    #[serde(default)]
+    pub liquid_settings: LiquidSettings,
+}
+
+impl JigsawStructure {
+    /// Assembles the structure starting in the context's chunk. `valid_biome` is
+    /// asked about the biome at the start before the pieces grow.
+    ///
+    /// Vanilla: `JigsawStructure.findGenerationPoint()` and `Structure.generate()`.
+    pub fn generate(
+        &self,
+        context: &mut GenerationContext<'_>,
+        valid_biome: impl FnOnce(BlockPos) -> bool,
+    ) -> Vec<JigsawPiece> {
+        let height = self
+            .start_height
+            .sample(&mut context.random, context.min_y, context.height);
+        let chunk_pos = context.chunk_pos;
+        let start = BlockPos::new(chunk_pos.0.x * 16, height, chunk_pos.0.y * 16);
+        let aliases = PoolAliasLookup::create(&self.pool_aliases, start, context.seed);
+        let Some(stub) = add_pieces(self, context, start, &aliases) else {
+            return Vec::new();
+        };
+        if !valid_biome(stub.position) {
+            return Vec::new();
+        }
+        stub.pieces(self, context, &aliases)
+    }
+}
+
+/// How far pieces can be from the start, written as one distance for both or as
+/// `{horizontal, vertical}`.
+///
+/// Vanilla: `JigsawStructure.MaxDistance`.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxDistance {
+    /// The distance along X and Z.
+    pub horizontal: i32,
+    /// The distance along Y.
+    pub vertical: i32,
+}
+
+impl<'de> Deserialize<'de> for MaxDistance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Both(i32),
+            Split {
+                horizontal: i32,
+                #[serde(default = "default_vertical")]
+                vertical: i32,
+            },
+        }
+
+        const fn default_vertical() -> i32 {
+            // Vanilla: `DimensionType.Y_SIZE`
+            4064
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Both(distance) => Self {
+                horizontal: distance,
+                vertical: distance,
+            },
+            Raw::Split {
+                horizontal,
+                vertical,
+            } => Self {
+                horizontal,
+                vertical,
+            },
+        })
+    }
+}
+
+/// How far pieces must stay from the bottom and top of the world, written as
+/// one distance for both or as `{bottom, top}`.
+///
+/// Vanilla: `DimensionPadding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DimensionPadding {
+    /// The distance from the bottom.
+    pub bottom: i32,
+    /// The distance from the top.
+    pub top: i32,
+}
+
+impl DimensionPadding {
+    /// No padding.
+    pub const ZERO: Self = Self { bottom: 0, top: 0 };
+}
+
+impl<'de> Deserialize<'de> for DimensionPadding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Both(i32),
+            Split {
+                #[serde(default)]
+                bottom: i32,
+                #[serde(default)]
+                top: i32,
+            },
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Both(padding) => Self {
+                bottom: padding,
+                top: padding,
+            },
+            Raw::Split { bottom, top } => Self { bottom, top },
+        })
+    }
+}
+
+fn deserialize_optional_heightmap<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<HeightmapType>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    HeightmapType::by_name(&name)
+        .map(Some)
+        .ok_or_else(|| D::Error::custom(format!("unknown heightmap {name}")))
+}
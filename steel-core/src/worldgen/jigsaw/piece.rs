@@ -0,0 +1,167 @@
+//! The pieces jigsaw structures are made of.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use simdnbt::borrow::read_compound as read_borrowed_compound;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_utils::random::Random;
+use steel_utils::{BlockPos, BoundingBox, Identifier, Rotation};
+
+use super::element::{LiquidSettings, PoolElement, Projection};
+use crate::chunk::beardifier::JigsawJunction;
+use crate::world::structure::StructurePiece;
+use crate::worldgen::structure_level::StructureLevel;
+
+/// A placed pool element.
+///
+/// Vanilla: `PoolElementStructurePiece`.
+#[derive(Debug, Clone)]
+pub struct JigsawPiece {
+    /// The element the piece places.
+    pub element: Arc<PoolElement>,
+    /// Where the element's origin is placed.
+    pub position: BlockPos,
+    /// How far above the bottom of the piece the ground is.
+    pub ground_level_delta: i32,
+    /// The rotation of the element.
+    pub rotation: Rotation,
+    /// The box the piece covers.
+    pub bounding_box: BoundingBox,
+    /// Where other pieces attach, for terrain adaptation.
+    pub junctions: Vec<JigsawJunction>,
+    /// Whether blocks placed into water are waterlogged.
+    pub liquid_settings: LiquidSettings,
+}
+
+impl JigsawPiece {
+    /// The piece type jigsaw pieces are saved with.
+    pub const TYPE: Identifier = Identifier::vanilla_static("jigsaw");
+
+    /// Moves the piece.
+    ///
+    /// Vanilla: `PoolElementStructurePiece.move()`.
+    pub const fn move_by(&mut self, dx: i32, dy: i32, dz: i32) {
+        self.position = self.position.offset(dx, dy, dz);
+        self.bounding_box = self.bounding_box.moved(dx, dy, dz);
+    }
+
+    /// Places the part of the piece inside `bounding_box`.
+    ///
+    /// Vanilla: `PoolElementStructurePiece.place()`.
+    pub fn place(
+        &self,
+        level: &impl StructureLevel,
+        random: &mut impl Random,
+        bounding_box: BoundingBox,
+        reference_pos: BlockPos,
+    ) {
+        self.element.place(
+            level,
+            self.position,
+            reference_pos,
+            self.rotation,
+            bounding_box,
+            random,
+            self.liquid_settings,
+        );
+    }
+
+    /// Saves the piece.
+    ///
+    /// Vanilla: `PoolElementStructurePiece.addAdditionalSaveData()`.
+    #[must_use]
+    pub fn to_piece(&self) -> StructurePiece {
+        let mut nbt = NbtCompound::new();
+        nbt.insert("PosX", self.position.x());
+        nbt.insert("PosY", self.position.y());
+        nbt.insert("PosZ", self.position.z());
+        nbt.insert("ground_level_delta", self.ground_level_delta);
+        nbt.insert("pool_element", self.element.save());
+        nbt.insert("rotation", rotation_name(self.rotation));
+        let junctions = self
+            .junctions
+            .iter()
+            .map(|junction| {
+                let mut nbt = NbtCompound::new();
+                nbt.insert("source_x", junction.source_x);
+                nbt.insert("source_ground_y", junction.source_ground_y);
+                nbt.insert("source_z", junction.source_z);
+                nbt.insert("delta_y", junction.delta_y);
+                nbt.insert("dest_proj", junction.dest_proj.name());
+                nbt
+            })
+            .collect();
+        nbt.insert("junctions", NbtList::Compound(junctions));
+        nbt.insert("liquid_settings", self.liquid_settings.name());
+
+        let mut nbt_data = Vec::new();
+        nbt.write(&mut nbt_data);
+        StructurePiece {
+            piece_type: Self::TYPE,
+            bounding_box: self.bounding_box,
+            gen_depth: 0,
+            orientation: None,
+            nbt_data,
+        }
+    }
+
+    /// Loads a piece saved by [`Self::to_piece`]. Returns `None` for other pieces.
+    ///
+    /// Vanilla: `PoolElementStructurePiece(StructurePieceSerializationContext, CompoundTag)`.
+    #[must_use]
+    pub fn from_piece(piece: &StructurePiece) -> Option<Self> {
+        if piece.piece_type != Self::TYPE {
+            return None;
+        }
+        let nbt = read_borrowed_compound(&mut Cursor::new(&piece.nbt_data))
+            .ok()?
+            .to_owned();
+        let junctions = nbt
+            .list("junctions")
+            .and_then(NbtList::compounds)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|junction| {
+                Some(JigsawJunction {
+                    source_x: junction.int("source_x")?,
+                    source_ground_y: junction.int("source_ground_y")?,
+                    source_z: junction.int("source_z")?,
+                    delta_y: junction.int("delta_y")?,
+                    dest_proj: Projection::by_name(&junction.string("dest_proj")?.to_str())?,
+                })
+            })
+            .collect();
+        Some(Self {
+            element: Arc::new(PoolElement::load(nbt.compound("pool_element")?)?),
+            position: BlockPos::new(nbt.int("PosX")?, nbt.int("PosY")?, nbt.int("PosZ")?),
+            ground_level_delta: nbt.int("ground_level_delta")?,
+            rotation: nbt
+                .string("rotation")
+                .and_then(|name| rotation_by_name(&name.to_str()))
+                .unwrap_or(Rotation::None),
+            bounding_box: piece.bounding_box,
+            junctions,
+            liquid_settings: nbt
+                .string("liquid_settings")
+                .and_then(|name| LiquidSettings::by_name(&name.to_str()))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Vanilla: `Rotation.LEGACY_CODEC`.
+const fn rotation_name(rotation: Rotation) -> &'static str {
+    match rotation {
+        Rotation::None => "NONE",
+        Rotation::Clockwise90 => "CLOCKWISE_90",
+        Rotation::Clockwise180 => "CLOCKWISE_180",
+        Rotation::Counterclockwise90 => "COUNTERCLOCKWISE_90",
+    }
+}
+
+fn rotation_by_name(name: &str) -> Option<Rotation> {
+    Rotation::VALUES
+        .into_iter()
+        .find(|rotation| rotation_name(*rotation) == name)
+}
@@ -0,0 +1,447 @@
+//! Assembling jigsaw structures: picking the start piece, then growing pieces out of
+//! its jigsaws until the depth limit or the free space runs out.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use steel_utils::random::{Random, shuffle};
+use steel_utils::{BlockPos, BoundingBox, Rotation};
+
+use super::element::{PoolElement, Projection};
+use super::piece::JigsawPiece;
+use super::pool::TemplatePool;
+use super::pool_alias::PoolAliasLookup;
+use super::{DimensionPadding, JigsawStructure};
+use crate::chunk::beardifier::JigsawJunction;
+use crate::chunk::heightmap::HeightmapType;
+use crate::worldgen::structure_template::JigsawBlockInfo;
+use crate::worldgen::structures::GenerationContext;
+
+/// Where a jigsaw structure starts, with its start piece placed. The rest of the
+/// pieces are only assembled once the biome at `position` is known to fit.
+///
+/// Vanilla: `Structure.GenerationStub`.
+pub struct JigsawStub {
+    /// The position the structure's biome is checked at.
+    pub position: BlockPos,
+    center: JigsawPiece,
+}
+
+impl JigsawStub {
+    /// Assembles the pieces of the structure.
+    ///
+    /// Vanilla: the pieces builder of `JigsawPlacement.addPieces()`.
+    #[must_use]
+    pub fn pieces(
+        self,
+        structure: &JigsawStructure,
+        context: &mut GenerationContext<'_>,
+        aliases: &PoolAliasLookup,
+    ) -> Vec<JigsawPiece> {
+        if structure.size <= 0 {
+            return Vec::new();
+        }
+        let JigsawStub { position, center } = self;
+        let horizontal = structure.max_distance_from_center.horizontal;
+        let vertical = structure.max_distance_from_center.vertical;
+        let padding = structure.dimension_padding;
+        // The top is exclusive in vanilla's AABB, so one less here
+        let container = BoundingBox {
+            min_x: position.x() - horizontal,
+            min_y: (position.y() - vertical).max(context.min_y + padding.bottom),
+            min_z: position.z() - horizontal,
+            max_x: position.x() + horizontal,
+            max_y: (position.y() + vertical + 1).min(context.max_y() + 1 - padding.top) - 1,
+            max_z: position.z() + horizontal,
+        };
+        let mut placer = Placer {
+            structure,
+            context,
+            aliases,
+            pieces: vec![center],
+            free_spaces: vec![FreeSpace::new(container)],
+            placing: SequencedPriorityQueue::default(),
+        };
+        let center_box = placer.pieces[0].bounding_box;
+        placer.free_spaces[0].take(center_box);
+        placer.try_placing_children(0, 0, 0);
+        while let Some(state) = placer.placing.pop() {
+            placer.try_placing_children(state.piece, state.free_space, state.depth);
+        }
+        placer.pieces
+    }
+}
+
+/// Picks and places the start piece of a structure.
+///
+/// Vanilla: `JigsawPlacement.addPieces()`.
+pub fn add_pieces(
+    structure: &JigsawStructure,
+    context: &mut GenerationContext<'_>,
+    position: BlockPos,
+    aliases: &PoolAliasLookup,
+) -> Option<JigsawStub> {
+    let rotation = Rotation::VALUES[context.random.next_i32_bounded(4) as usize];
+    let pool = TemplatePool::by_id(aliases.lookup(&structure.start_pool))
+        .or_else(|| TemplatePool::by_id(&structure.start_pool))?;
+    let element = pool.random_template(&mut context.random);
+    if matches!(*element, PoolElement::Empty) {
+        return None;
+    }
+
+    let anchor = match &structure.start_jigsaw_name {
+        Some(name) => {
+            let anchor = element
+                .shuffled_jigsaws(position, rotation, &mut context.random)
+                .into_iter()
+                .find(|jigsaw| jigsaw.name == *name);
+            let Some(anchor) = anchor else {
+                log::error!(
+                    "No starting jigsaw {name} found in start pool {}",
+                    structure.start_pool
+                );
+                return None;
+            };
+            anchor.pos
+        }
+        None => position,
+    };
+    let local_anchor = BlockPos::new(
+        anchor.x() - position.x(),
+        anchor.y() - position.y(),
+        anchor.z() - position.z(),
+    );
+    let start = position.offset(-local_anchor.x(), -local_anchor.y(), -local_anchor.z());
+
+    let mut center = JigsawPiece {
+        ground_level_delta: element.ground_level_delta(),
+        bounding_box: element.bounding_box(start, rotation),
+        element,
+        position: start,
+        rotation,
+        junctions: Vec::new(),
+        liquid_settings: structure.liquid_settings,
+    };
+    let center_box = center.bounding_box;
+    let center_x = (center_box.max_x + center_box.min_x) / 2;
+    let center_z = (center_box.max_z + center_box.min_z) / 2;
+    let bottom_y = match structure.project_start_to_heightmap {
+        Some(heightmap) => position.y() + context.base_height(center_x, center_z, heightmap),
+        None => start.y(),
+    };
+    let ground_y = center_box.min_y + center.ground_level_delta;
+    center.move_by(0, bottom_y - ground_y, 0);
+
+    if is_start_too_close_to_height_limits(context, structure.dimension_padding, &center) {
+        log::debug!(
+            "Center piece of {} would be outside the world's height limits",
+            structure.start_pool
+        );
+        return None;
+    }
+    Some(JigsawStub {
+        position: BlockPos::new(center_x, bottom_y + local_anchor.y(), center_z),
+        center,
+    })
+}
+
+/// Vanilla: `JigsawPlacement.isStartTooCloseToWorldHeightLimits()`.
+fn is_start_too_close_to_height_limits(
+    context: &GenerationContext<'_>,
+    padding: DimensionPadding,
+    center: &JigsawPiece,
+) -> bool {
+    if padding == DimensionPadding::ZERO {
+        return false;
+    }
+    let bottom_limit = context.min_y + padding.bottom;
+    let top_limit = context.max_y() - padding.top;
+    center.bounding_box.min_y < bottom_limit || center.bounding_box.max_y > top_limit
+}
+
+/// Space pieces can still grow into. Vanilla tracks it as a voxel shape; every
+/// box here covers whole blocks, so a container minus the boxes taken is exact.
+struct FreeSpace {
+    container: BoundingBox,
+    taken: Vec<BoundingBox>,
+}
+
+impl FreeSpace {
+    const fn new(container: BoundingBox) -> Self {
+        Self {
+            container,
+            taken: Vec::new(),
+        }
+    }
+
+    fn fits(&self, bounding_box: &BoundingBox) -> bool {
+        let container = &self.container;
+        bounding_box.min_x >= container.min_x
+            && bounding_box.min_y >= container.min_y
+            && bounding_box.min_z >= container.min_z
+            && bounding_box.max_x <= container.max_x
+            && bounding_box.max_y <= container.max_y
+            && bounding_box.max_z <= container.max_z
+            && !self
+                .taken
+                .iter()
+                .any(|taken| taken.intersects(bounding_box))
+    }
+
+    fn take(&mut self, bounding_box: BoundingBox) {
+        self.taken.push(bounding_box);
+    }
+}
+
+/// A piece waiting to grow its jigsaws.
+///
+/// Vanilla: `JigsawPlacement.PieceState`.
+struct PieceState {
+    piece: usize,
+    free_space: usize,
+    depth: i32,
+}
+
+/// Hands out the highest priority first, and equal priorities in insertion order.
+///
+/// Vanilla: `SequencedPriorityIterator`.
+#[derive(Default)]
+struct SequencedPriorityQueue {
+    by_priority: BTreeMap<i32, VecDeque<PieceState>>,
+}
+
+impl SequencedPriorityQueue {
+    fn push(&mut self, state: PieceState, priority: i32) {
+        self.by_priority
+            .entry(priority)
+            .or_default()
+            .push_back(state);
+    }
+
+    fn pop(&mut self) -> Option<PieceState> {
+        let mut entry = self.by_priority.last_entry()?;
+        let state = entry.get_mut().pop_front();
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+        state
+    }
+}
+
+/// Vanilla: `JigsawPlacement.Placer`.
+struct Placer<'a, 'c> {
+    structure: &'a JigsawStructure,
+    context: &'a mut GenerationContext<'c>,
+    aliases: &'a PoolAliasLookup,
+    pieces: Vec<JigsawPiece>,
+    /// Free spaces, referenced by index so pieces can share them.
+    free_spaces: Vec<FreeSpace>,
+    placing: SequencedPriorityQueue,
+}
+
+impl Placer<'_, '_> {
+    /// Vanilla: `JigsawPlacement.Placer.tryPlacingChildren()`.
+    #[expect(
+        clippy::too_many_lines,
+        reason = "follows the vanilla method step by step"
+    )]
+    fn try_placing_children(&mut self, source_index: usize, context_free: usize, depth: i32) {
+        let source = &self.pieces[source_index];
+        let source_element = source.element.clone();
+        let source_rotation = source.rotation;
+        let source_position = source.position;
+        let source_box = source.bounding_box;
+        let source_projection = source_element.projection();
+        let source_rigid = source_projection == Projection::Rigid;
+        let source_min_y = source_box.min_y;
+        // The source piece's own free space, created when a jigsaw points into it
+        let mut source_free = None;
+
+        let source_jigsaws = source_element.shuffled_jigsaws(
+            source_position,
+            source_rotation,
+            &mut self.context.random,
+        );
+        'jigsaws: for source_jigsaw in source_jigsaws {
+            let source_pos = source_jigsaw.pos;
+            let target_pos = source_jigsaw.front.relative(source_pos);
+            let source_jigsaw_y_offset = source_pos.y() - source_min_y;
+            let mut source_ground_y = None;
+
+            let pool_id = self.aliases.lookup(&source_jigsaw.pool);
+            let Some(target_pool) = TemplatePool::by_id(pool_id)
+                .filter(|pool| pool.size() != 0 || *pool_id == TemplatePool::EMPTY)
+            else {
+                log::warn!("Empty or non-existent pool: {pool_id}");
+                continue;
+            };
+            let Some(fallback_pool) = TemplatePool::by_id(&target_pool.fallback)
+                .filter(|pool| pool.size() != 0 || target_pool.fallback == TemplatePool::EMPTY)
+            else {
+                log::warn!(
+                    "Empty or non-existent fallback pool: {}",
+                    target_pool.fallback
+                );
+                continue;
+            };
+
+            let free = if source_box.is_inside(target_pos) {
+                *source_free.get_or_insert_with(|| {
+                    self.free_spaces.push(FreeSpace::new(source_box));
+                    self.free_spaces.len() - 1
+                })
+            } else {
+                context_free
+            };
+
+            let mut target_elements = Vec::new();
+            if depth != self.structure.size {
+                target_elements = target_pool.shuffled_templates(&mut self.context.random);
+            }
+            target_elements.extend(fallback_pool.shuffled_templates(&mut self.context.random));
+            let placement_priority = source_jigsaw.placement_priority;
+
+            for target_element in target_elements {
+                if matches!(*target_element, PoolElement::Empty) {
+                    break;
+                }
+                let mut rotations = Rotation::VALUES;
+                shuffle(&mut rotations, &mut self.context.random);
+                for target_rotation in rotations {
+                    let target_jigsaws = target_element.shuffled_jigsaws(
+                        BlockPos::ZERO,
+                        target_rotation,
+                        &mut self.context.random,
+                    );
+                    let zero_box = target_element.bounding_box(BlockPos::ZERO, target_rotation);
+                    let expand_to =
+                        if self.structure.use_expansion_hack && zero_box.get_y_span() <= 16 {
+                            self.expansion_height(&target_jigsaws, &zero_box)
+                        } else {
+                            0
+                        };
+
+                    for target_jigsaw in &target_jigsaws {
+                        if !source_jigsaw.can_attach(target_jigsaw) {
+                            continue;
+                        }
+                        let target_jigsaw_pos = target_jigsaw.pos;
+                        let target_position_without_offset = target_pos.offset(
+                            -target_jigsaw_pos.x(),
+                            -target_jigsaw_pos.y(),
+                            -target_jigsaw_pos.z(),
+                        );
+                        let target_box_without_offset = target_element
+                            .bounding_box(target_position_without_offset, target_rotation);
+                        let target_min_y = target_box_without_offset.min_y;
+                        let target_projection = target_element.projection();
+                        let target_rigid = target_projection == Projection::Rigid;
+                        let target_jigsaw_y_offset = target_jigsaw_pos.y();
+                        let delta_y = source_jigsaw_y_offset - target_jigsaw_y_offset
+                            + source_jigsaw.front.offset().1;
+                        let target_y = if source_rigid && target_rigid {
+                            source_min_y + delta_y
+                        } else {
+                            self.ground_y(&mut source_ground_y, source_pos) - target_jigsaw_y_offset
+                        };
+
+                        let target_y_offset = target_y - target_min_y;
+                        let mut target_box = target_box_without_offset.moved(0, target_y_offset, 0);
+                        let target_position =
+                            target_position_without_offset.offset(0, target_y_offset, 0);
+                        if expand_to > 0 {
+                            let max_target_y =
+                                (expand_to + 1).max(target_box.max_y - target_box.min_y);
+                            target_box.max_y =
+                                target_box.max_y.max(target_box.min_y + max_target_y);
+                        }
+
+                        if !self.free_spaces[free].fits(&target_box) {
+                            continue;
+                        }
+                        self.free_spaces[free].take(target_box);
+
+                        let source_ground_level_delta =
+                            self.pieces[source_index].ground_level_delta;
+                        let target_ground_level_delta = if target_rigid {
+                            source_ground_level_delta - delta_y
+                        } else {
+                            target_element.ground_level_delta()
+                        };
+                        let junction_y = if source_rigid {
+                            source_min_y + source_jigsaw_y_offset
+                        } else if target_rigid {
+                            target_y + target_jigsaw_y_offset
+                        } else {
+                            self.ground_y(&mut source_ground_y, source_pos) + delta_y / 2
+                        };
+
+                        self.pieces[source_index].junctions.push(JigsawJunction {
+                            source_x: target_pos.x(),
+                            source_ground_y: junction_y - source_jigsaw_y_offset
+                                + source_ground_level_delta,
+                            source_z: target_pos.z(),
+                            delta_y,
+                            dest_proj: target_projection,
+                        });
+                        self.pieces.push(JigsawPiece {
+                            element: target_element.clone(),
+                            position: target_position,
+                            ground_level_delta: target_ground_level_delta,
+                            rotation: target_rotation,
+                            bounding_box: target_box,
+                            junctions: vec![JigsawJunction {
+                                source_x: source_pos.x(),
+                                source_ground_y: junction_y - target_jigsaw_y_offset
+                                    + target_ground_level_delta,
+                                source_z: source_pos.z(),
+                                delta_y: -delta_y,
+                                dest_proj: source_projection,
+                            }],
+                            liquid_settings: self.structure.liquid_settings,
+                        });
+                        if depth < self.structure.size {
+                            let state = PieceState {
+                                piece: self.pieces.len() - 1,
+                                free_space: free,
+                                depth: depth + 1,
+                            };
+                            self.placing.push(state, placement_priority);
+                        }
+                        continue 'jigsaws;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gets how far up a target piece must reserve space, so pieces attached inside
+    /// it later still fit.
+    fn expansion_height(&self, target_jigsaws: &[JigsawBlockInfo], zero_box: &BoundingBox) -> i32 {
+        target_jigsaws
+            .iter()
+            .map(|jigsaw| {
+                if !zero_box.is_inside(jigsaw.front.relative(jigsaw.pos)) {
+                    return 0;
+                }
+                let Some(pool) = TemplatePool::by_id(self.aliases.lookup(&jigsaw.pool)) else {
+                    return 0;
+                };
+                let fallback_size =
+                    TemplatePool::by_id(&pool.fallback).map_or(0, TemplatePool::max_size);
+                pool.max_size().max(fallback_size)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Gets the ground height at the source jigsaw, sampling it only once.
+    fn ground_y(&self, cached: &mut Option<i32>, source_pos: BlockPos) -> i32 {
+        *cached.get_or_insert_with(|| {
+            self.context.base_height(
+                source_pos.x(),
+                source_pos.z(),
+                HeightmapType::WorldSurfaceWg,
+            )
+        })
+    }
+}
@@ -0,0 +1,115 @@
+//! Template pools, the weighted lists jigsaws pick their pieces from.
+
+use std::sync::{Arc, LazyLock, OnceLock};
+
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use steel_registry::vanilla_structure_data::VANILLA_TEMPLATE_POOLS;
+use steel_utils::random::{Random, shuffle};
+use steel_utils::{BlockPos, Identifier, Rotation};
+
+use super::element::PoolElement;
+
+/// A weighted list of pool elements.
+///
+/// Vanilla: `StructureTemplatePool`.
+#[derive(Debug)]
+pub struct TemplatePool {
+    /// The pool used after the last jigsaw depth, or when no element of this pool fits.
+    pub fallback: Identifier,
+    /// Each element repeated by its weight.
+    templates: Vec<Arc<PoolElement>>,
+    /// The tallest element, computed on first use.
+    max_size: OnceLock<i32>,
+}
+
+#[derive(Deserialize)]
+struct TemplatePoolJson {
+    fallback: Identifier,
+    elements: Vec<WeightedElement>,
+}
+
+#[derive(Deserialize)]
+struct WeightedElement {
+    element: PoolElement,
+    weight: usize,
+}
+
+impl TemplatePool {
+    /// The id of the pool that holds nothing.
+    ///
+    /// Vanilla: `Pools.EMPTY`.
+    pub const EMPTY: Identifier = Identifier::vanilla_static("empty");
+
+    /// Gets the pool with the given id from the data pack.
+    #[must_use]
+    pub fn by_id(id: &Identifier) -> Option<&'static Self> {
+        TEMPLATE_POOLS.get(id)
+    }
+
+    /// Gets the number of entries, counting each element once per weight.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Vanilla: `StructureTemplatePool.getRandomTemplate()`.
+    pub fn random_template(&self, random: &mut impl Random) -> Arc<PoolElement> {
+        if self.templates.is_empty() {
+            return Arc::new(PoolElement::Empty);
+        }
+        let index = random.next_i32_bounded(self.templates.len() as i32) as usize;
+        self.templates[index].clone()
+    }
+
+    /// Vanilla: `StructureTemplatePool.getShuffledTemplates()`.
+    pub fn shuffled_templates(&self, random: &mut impl Random) -> Vec<Arc<PoolElement>> {
+        let mut templates = self.templates.clone();
+        shuffle(&mut templates, random);
+        templates
+    }
+
+    /// Gets the height of the tallest element.
+    ///
+    /// Vanilla: `StructureTemplatePool.getMaxSize()`.
+    pub fn max_size(&self) -> i32 {
+        *self.max_size.get_or_init(|| {
+            self.templates
+                .iter()
+                .filter(|element| !matches!(***element, PoolElement::Empty))
+                .map(|element| {
+                    element
+                        .bounding_box(BlockPos::ZERO, Rotation::None)
+                        .get_y_span()
+                })
+                .max()
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// The template pools of the vanilla data pack.
+static TEMPLATE_POOLS: LazyLock<FxHashMap<Identifier, TemplatePool>> = LazyLock::new(|| {
+    VANILLA_TEMPLATE_POOLS
+        .iter()
+        .filter_map(|(path, json)| {
+            let pool: TemplatePoolJson = serde_json::from_str(json)
+                .inspect_err(|err| log::error!("Couldn't load template pool {path}: {err}"))
+                .ok()?;
+            let templates = pool
+                .elements
+                .into_iter()
+                .flat_map(|entry| {
+                    let element = Arc::new(entry.element);
+                    (0..entry.weight).map(move |_| element.clone())
+                })
+                .collect();
+            let pool = TemplatePool {
+                fallback: pool.fallback,
+                templates,
+                max_size: OnceLock::new(),
+            };
+            Some((Identifier::vanilla((*path).to_owned()), pool))
+        })
+        .collect()
+});
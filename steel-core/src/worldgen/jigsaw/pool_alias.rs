@@ -0,0 +1,117 @@
+//! Pool aliases, which let one structure start swap the pools its jigsaws name.
+
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::random::{PositionalRandom, Random};
+use steel_utils::{BlockPos, Identifier};
+
+/// An entry of a weighted list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Weighted<T> {
+    /// The entry.
+    pub data: T,
+    /// How likely the entry is picked, relative to the others.
+    pub weight: i32,
+}
+
+/// Picks an entry with a chance proportional to its weight.
+///
+/// Vanilla: `WeightedList.getRandom()`.
+pub fn pick_weighted<'a, T>(entries: &'a [Weighted<T>], random: &mut impl Random) -> Option<&'a T> {
+    let total: i32 = entries.iter().map(|entry| entry.weight).sum();
+    if total <= 0 {
+        return None;
+    }
+    let mut choice = random.next_i32_bounded(total);
+    entries.iter().find_map(|entry| {
+        choice -= entry.weight;
+        (choice < 0).then_some(&entry.data)
+    })
+}
+
+/// Binds a pool alias to the pool it stands for.
+///
+/// Vanilla: `PoolAliasBinding`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum PoolAliasBinding {
+    /// Always the same pool. Vanilla: `DirectPoolAlias`.
+    #[serde(rename = "minecraft:direct")]
+    Direct {
+        /// The pool jigsaws name.
+        alias: Identifier,
+        /// The pool used instead.
+        target: Identifier,
+    },
+    /// One pool picked at random. Vanilla: `RandomPoolAlias`.
+    #[serde(rename = "minecraft:random")]
+    Random {
+        /// The pool jigsaws name.
+        alias: Identifier,
+        /// The pools to pick from.
+        targets: Vec<Weighted<Identifier>>,
+    },
+    /// One group of bindings picked at random. Vanilla: `RandomGroupPoolAlias`.
+    #[serde(rename = "minecraft:random_group")]
+    RandomGroup {
+        /// The groups to pick from.
+        groups: Vec<Weighted<Vec<PoolAliasBinding>>>,
+    },
+}
+
+impl PoolAliasBinding {
+    /// Vanilla: `PoolAliasBinding.forEachResolved()`.
+    fn resolve(&self, random: &mut impl Random, resolved: &mut FxHashMap<Identifier, Identifier>) {
+        match self {
+            Self::Direct { alias, target } => {
+                resolved.insert(alias.clone(), target.clone());
+            }
+            Self::Random { alias, targets } => {
+                if let Some(target) = pick_weighted(targets, random) {
+                    resolved.insert(alias.clone(), target.clone());
+                }
+            }
+            Self::RandomGroup { groups } => {
+                if let Some(group) = pick_weighted(groups, random) {
+                    for binding in group {
+                        binding.resolve(random, resolved);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The resolved pool aliases of one structure start.
+///
+/// Vanilla: `PoolAliasLookup`.
+#[derive(Debug, Default)]
+pub struct PoolAliasLookup(FxHashMap<Identifier, Identifier>);
+
+impl PoolAliasLookup {
+    /// Resolves the bindings for a start at `pos`.
+    ///
+    /// Vanilla: `PoolAliasLookup.create()`.
+    #[must_use]
+    pub fn create(bindings: &[PoolAliasBinding], pos: BlockPos, seed: i64) -> Self {
+        if bindings.is_empty() {
+            return Self::default();
+        }
+        let mut random =
+            LegacyRandom::from_seed(seed as u64)
+                .next_positional()
+                .at(pos.x(), pos.y(), pos.z());
+        let mut resolved = FxHashMap::default();
+        for binding in bindings {
+            binding.resolve(&mut random, &mut resolved);
+        }
+        Self(resolved)
+    }
+
+    /// Gets the pool `pool` stands for.
+    #[must_use]
+    pub fn lookup<'a>(&'a self, pool: &'a Identifier) -> &'a Identifier {
+        self.0.get(pool).unwrap_or(pool)
+    }
+}
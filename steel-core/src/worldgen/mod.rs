@@ -3,14 +3,42 @@
 //! This module provides the integration between extracted vanilla worldgen data
 //! and the world generation pipeline.
 
+mod biome_search;
 mod biome_source;
 mod climate_sampler;
+mod end_spikes;
+mod jigsaw;
 mod nether_climate_sampler;
+mod structure_level;
+mod structure_processor;
+mod structure_template;
+mod structures;
+mod template_manager;
 
+pub use biome_search::{
+    LOCATE_BIOME_HORIZONTAL_STEP, LOCATE_BIOME_RADIUS, LOCATE_BIOME_VERTICAL_STEP,
+};
 pub use biome_source::{
     BiomeSourceKind, ChunkBiomeSampler, EndBiomeSource, NetherBiomeSource, OverworldBiomeSource,
 };
 pub use climate_sampler::OverworldClimateSampler;
+pub use end_spikes::{EndSpike, SPIKE_COUNT, end_spikes, place_end_spike};
+pub use jigsaw::{
+    JigsawPiece, JigsawStructure, LiquidSettings, PoolElement, Projection, TemplatePool,
+};
 pub use nether_climate_sampler::NetherClimateSampler;
 pub use steel_registry::density_functions::overworld::OverworldColumnCache;
 pub use steel_utils::noise::EndIslands;
+pub use structure_level::StructureLevel;
+pub use structure_processor::{BlockSet, ProcessorList, StructureProcessor};
+pub use structure_template::{
+    JigsawBlockInfo, JointType, StructureBlockInfo, StructureEntityInfo, StructurePlaceSettings,
+    StructureTemplate, read_block_state, transform, transform_vec,
+};
+pub use structures::{
+    DECORATION_STEPS, GenerationContext, HeightProvider, Structure, StructureKind,
+    StructurePlacement, StructureSelectionEntry, StructureSet, VerticalAnchor,
+};
+pub use template_manager::{
+    STRUCTURE_TEMPLATES, StructureTemplateManager, StructureTemplateManagerLock,
+};
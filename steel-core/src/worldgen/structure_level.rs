@@ -0,0 +1,169 @@
+//! Where structure templates are placed: the live world, or a chunk that is
+//! still being generated.
+
+use std::sync::{Arc, Weak};
+
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::{REGISTRY, RegistryExt, vanilla_blocks};
+use steel_utils::nbt::with_borrowed;
+use steel_utils::random::Random;
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use crate::behavior::BLOCK_BEHAVIORS;
+use crate::chunk::chunk_access::ChunkAccess;
+use crate::chunk::heightmap::HeightmapType;
+use crate::chunk::proto_chunk::ProtoEntity;
+use crate::entity::{saved_entity_position, spawn_saved_entity};
+use crate::world::World;
+
+/// A level structure templates can be placed into.
+///
+/// Vanilla: `ServerLevelAccessor`.
+pub trait StructureLevel {
+    /// Gets the block state at `pos`.
+    fn get_block_state(&self, pos: BlockPos) -> BlockStateId;
+
+    /// Sets the block state at `pos`. Returns whether the block could be set.
+    fn set_block(&self, pos: BlockPos, state: BlockStateId, flags: UpdateFlags) -> bool;
+
+    /// Gets the first free Y above the heightmap's blocks at `x`, `z`.
+    ///
+    /// Vanilla: `LevelReader.getHeight()`.
+    fn get_height(&self, heightmap: HeightmapType, x: i32, z: i32) -> i32;
+
+    /// Loads the saved data of a placed block into its block entity. Containers get
+    /// a loot seed from `random`.
+    fn load_block_entity(&self, pos: BlockPos, nbt: NbtCompound, random: &mut impl Random);
+
+    /// Adds a saved entity of a template.
+    fn add_entity(&self, nbt: &NbtCompound);
+}
+
+impl StructureLevel for Arc<World> {
+    fn get_block_state(&self, pos: BlockPos) -> BlockStateId {
+        World::get_block_state(self, pos)
+    }
+
+    fn set_block(&self, pos: BlockPos, state: BlockStateId, flags: UpdateFlags) -> bool {
+        World::set_block(self, pos, state, flags)
+    }
+
+    fn get_height(&self, heightmap: HeightmapType, x: i32, z: i32) -> i32 {
+        World::get_height(self, final_heightmap(heightmap), BlockPos::new(x, 0, z))
+    }
+
+    fn load_block_entity(&self, pos: BlockPos, mut nbt: NbtCompound, random: &mut impl Random) {
+        let Some(block_entity) = self.get_block_entity(pos) else {
+            return;
+        };
+        let mut block_entity = block_entity.lock();
+        // Vanilla reseeds every randomizable container, which are the containers Steel has
+        if block_entity.as_container().is_some() {
+            nbt.insert("LootTableSeed", random.next_i64());
+        }
+        if with_borrowed(&nbt, |base| block_entity.load_additional(base)).is_err() {
+            return;
+        }
+        block_entity.set_changed();
+        if let Some(update_tag) = block_entity.get_update_tag() {
+            self.broadcast_block_entity_update(pos, block_entity.get_type(), update_tag);
+        }
+    }
+
+    fn add_entity(&self, nbt: &NbtCompound) {
+        spawn_saved_entity(self, nbt);
+    }
+}
+
+/// Placing into a generating chunk only touches blocks inside it. Block entities and
+/// entities wait in the proto chunk until it becomes full.
+///
+/// Vanilla: `WorldGenRegion`.
+impl StructureLevel for ChunkAccess {
+    fn get_block_state(&self, pos: BlockPos) -> BlockStateId {
+        if !contains(self, pos) {
+            return REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+        }
+        ChunkAccess::get_block_state(self, pos)
+    }
+
+    fn set_block(&self, pos: BlockPos, state: BlockStateId, flags: UpdateFlags) -> bool {
+        if !contains(self, pos) {
+            return false;
+        }
+        self.set_block_state(pos, state, flags);
+        true
+    }
+
+    fn get_height(&self, heightmap: HeightmapType, x: i32, z: i32) -> i32 {
+        match self {
+            Self::Proto(proto_chunk) => proto_chunk.get_height(heightmap, x, z),
+            Self::Full(chunk) => {
+                chunk.get_height(final_heightmap(heightmap), BlockPos::new(x, 0, z))
+            }
+            Self::Unloaded => unreachable!(),
+        }
+    }
+
+    fn load_block_entity(&self, pos: BlockPos, mut nbt: NbtCompound, random: &mut impl Random) {
+        let Self::Proto(proto_chunk) = self else {
+            return;
+        };
+        let state = proto_chunk.get_block_state(pos);
+        let behavior = BLOCK_BEHAVIORS.get_behavior(state.get_block());
+        if !behavior.has_block_entity() {
+            return;
+        }
+        // Vanilla creates the block entity right away, so the loot seed is rolled now
+        let is_container = behavior
+            .new_block_entity(Weak::new(), pos, state)
+            .is_some_and(|block_entity| block_entity.lock().as_container().is_some());
+        if is_container {
+            nbt.insert("LootTableSeed", random.next_i64());
+        }
+        proto_chunk.set_block_entity_nbt(pos, nbt);
+    }
+
+    // TODO: Keep the rest of the entity's data once proto entities can hold it
+    fn add_entity(&self, nbt: &NbtCompound) {
+        let Self::Proto(proto_chunk) = self else {
+            return;
+        };
+        let Some(entity_type) = nbt
+            .string("id")
+            .and_then(|id| id.to_str().parse::<Identifier>().ok())
+            .and_then(|id| REGISTRY.entity_types.by_key(&id))
+        else {
+            return;
+        };
+        let Some(position) = saved_entity_position(nbt) else {
+            return;
+        };
+        let rotation = nbt
+            .list("Rotation")
+            .and_then(NbtList::floats)
+            .and_then(|rotation| Some((*rotation.first()?, *rotation.get(1)?)))
+            .unwrap_or((0.0, 0.0));
+        proto_chunk.add_entity(ProtoEntity {
+            entity_type,
+            position,
+            rotation,
+        });
+    }
+}
+
+fn contains(chunk: &ChunkAccess, pos: BlockPos) -> bool {
+    let chunk_pos = chunk.pos();
+    pos.x() >> 4 == chunk_pos.0.x && pos.z() >> 4 == chunk_pos.0.y
+}
+
+/// Full chunks only keep the final heightmaps, so worldgen types read their final twin.
+const fn final_heightmap(heightmap: HeightmapType) -> HeightmapType {
+    match heightmap {
+        HeightmapType::WorldSurfaceWg => HeightmapType::WorldSurface,
+        HeightmapType::OceanFloorWg => HeightmapType::OceanFloor,
+        other => other,
+    }
+}
@@ -0,0 +1,577 @@
+//! Structure processors, which change or drop blocks while a template is placed,
+//! and the processor lists of the data pack.
+
+use std::sync::{Arc, LazyLock};
+
+use rustc_hash::FxHashMap;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use simdnbt::owned::NbtCompound;
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::vanilla_structure_data::VANILLA_PROCESSOR_LISTS;
+use steel_registry::{REGISTRY, RegistryExt, TaggedRegistryExt, vanilla_blocks};
+use steel_utils::math::Axis;
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::random::{Random, get_seed};
+use steel_utils::{BlockPos, BlockStateId, Identifier};
+
+use super::structure_level::StructureLevel;
+use super::structure_template::{SettingsRandom, StructureBlockInfo, state_with_properties};
+use crate::chunk::heightmap::HeightmapType;
+
+/// Changes or drops blocks while a template is placed.
+///
+/// Vanilla: `StructureProcessor`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "processor_type")]
+pub enum StructureProcessor {
+    /// Keeps each block with a chance of `integrity`. Vanilla: `BlockRotProcessor`.
+    #[serde(rename = "minecraft:block_rot")]
+    BlockRot {
+        /// The chance to keep a block, from 0 to 1.
+        integrity: f32,
+        /// Only these blocks can rot. `None` means all of them.
+        #[serde(default)]
+        rottable_blocks: Option<BlockSet>,
+    },
+    /// Skips the given blocks. Vanilla: `BlockIgnoreProcessor`.
+    #[serde(rename = "minecraft:block_ignore")]
+    BlockIgnore {
+        /// The blocks to skip.
+        #[serde(deserialize_with = "deserialize_blocks")]
+        blocks: Vec<BlockRef>,
+    },
+    /// Replaces blocks by the first matching rule. Vanilla: `RuleProcessor`.
+    #[serde(rename = "minecraft:rule")]
+    Rule {
+        /// The rules, tried in order.
+        rules: Vec<ProcessorRule>,
+    },
+    /// Moves blocks onto the terrain. Vanilla: `GravityProcessor`.
+    #[serde(rename = "minecraft:gravity")]
+    Gravity {
+        /// The heightmap blocks are moved onto.
+        #[serde(
+            default = "default_gravity_heightmap",
+            deserialize_with = "deserialize_heightmap"
+        )]
+        heightmap: HeightmapType,
+        /// Added to the height of the heightmap.
+        #[serde(default)]
+        offset: i32,
+    },
+    /// Turns jigsaw blocks into their final state. Vanilla: `JigsawReplacementProcessor`.
+    #[serde(rename = "minecraft:jigsaw_replacement")]
+    JigsawReplacement,
+    /// Skips blocks that would replace one of the given blocks.
+    /// Vanilla: `ProtectedBlockProcessor`.
+    #[serde(rename = "minecraft:protected_blocks")]
+    ProtectedBlocks {
+        /// The blocks that can't be replaced.
+        #[serde(rename = "value")]
+        cannot_replace: BlockSet,
+    },
+    /// Leaves blocks as they are. Vanilla: `NopProcessor`.
+    #[serde(rename = "minecraft:nop")]
+    Nop,
+    /// A processor Steel can't run yet, which leaves blocks as they are.
+    // TODO: Support `capped`, `lava_submerged_block` and `black_stone_replace`
+    #[serde(other)]
+    Unsupported,
+}
+
+impl StructureProcessor {
+    /// Processes one block. `original` is the block as stored in the template and
+    /// `info` the block after the earlier processors.
+    ///
+    /// Vanilla: `StructureProcessor.processBlock()`.
+    pub(super) fn process(
+        &self,
+        level: &impl StructureLevel,
+        reference_pos: BlockPos,
+        original: &StructureBlockInfo,
+        info: StructureBlockInfo,
+        random: &mut SettingsRandom,
+    ) -> Option<StructureBlockInfo> {
+        match self {
+            Self::BlockRot {
+                integrity,
+                rottable_blocks,
+            } => {
+                let can_rot = rottable_blocks
+                    .as_ref()
+                    .is_none_or(|blocks| blocks.contains(original.state.get_block()));
+                if !can_rot {
+                    return Some(info);
+                }
+                let roll = random.with(info.pos, Random::next_f32);
+                (roll <= *integrity).then_some(info)
+            }
+            Self::BlockIgnore { blocks } => {
+                (!blocks.contains(&info.state.get_block())).then_some(info)
+            }
+            Self::Rule { rules } => {
+                let pos = info.pos;
+                let mut random =
+                    LegacyRandom::from_seed(get_seed(pos.x(), pos.y(), pos.z()) as u64);
+                let location_state = level.get_block_state(pos);
+                let Some(rule) = rules.iter().find(|rule| {
+                    rule.test(info.state, location_state, pos, reference_pos, &mut random)
+                }) else {
+                    return Some(info);
+                };
+                Some(StructureBlockInfo {
+                    pos,
+                    state: rule.output_state,
+                    nbt: rule.block_entity_modifier.apply(&mut random, info.nbt),
+                })
+            }
+            Self::Gravity { heightmap, offset } => {
+                let pos = info.pos;
+                let height = level.get_height(*heightmap, pos.x(), pos.z()) + offset;
+                Some(StructureBlockInfo {
+                    pos: BlockPos::new(pos.x(), height + original.pos.y(), pos.z()),
+                    ..info
+                })
+            }
+            Self::JigsawReplacement => {
+                if info.state.get_block() != vanilla_blocks::JIGSAW {
+                    return Some(info);
+                }
+                let Some(nbt) = &info.nbt else {
+                    log::warn!("Jigsaw block at {:?} is missing its data", info.pos);
+                    return None;
+                };
+                let final_state = nbt
+                    .string("final_state")
+                    .map_or_else(|| "minecraft:air".to_owned(), |s| s.to_str().into_owned());
+                let Some(state) = parse_block_state(&final_state) else {
+                    log::error!("Couldn't parse jigsaw final state {final_state}");
+                    return None;
+                };
+                (state.get_block() != vanilla_blocks::STRUCTURE_VOID).then_some(
+                    StructureBlockInfo {
+                        pos: info.pos,
+                        state,
+                        nbt: None,
+                    },
+                )
+            }
+            Self::ProtectedBlocks { cannot_replace } => {
+                let replaced = level.get_block_state(info.pos);
+                (!cannot_replace.contains(replaced.get_block())).then_some(info)
+            }
+            Self::Nop | Self::Unsupported => Some(info),
+        }
+    }
+}
+
+/// A set of blocks, written as a `#tag`, one block id or a list of block ids.
+///
+/// Vanilla: `HolderSet<Block>`.
+#[derive(Debug, Clone)]
+pub enum BlockSet {
+    /// The blocks of a tag.
+    Tag(Identifier),
+    /// The listed blocks.
+    Blocks(Vec<BlockRef>),
+}
+
+impl BlockSet {
+    /// Returns whether `block` is in the set.
+    #[must_use]
+    pub fn contains(&self, block: BlockRef) -> bool {
+        match self {
+            Self::Tag(tag) => REGISTRY.blocks.is_in_tag(block, tag),
+            Self::Blocks(blocks) => blocks.contains(&block),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            One(String),
+            List(Vec<Identifier>),
+        }
+
+        let ids = match Raw::deserialize(deserializer)? {
+            Raw::One(id) => {
+                if let Some(tag) = id.strip_prefix('#') {
+                    return tag.parse().map(Self::Tag).map_err(D::Error::custom);
+                }
+                vec![id.parse().map_err(D::Error::custom)?]
+            }
+            Raw::List(ids) => ids,
+        };
+        ids.iter()
+            .map(|id| {
+                REGISTRY
+                    .blocks
+                    .by_key(id)
+                    .ok_or_else(|| D::Error::custom(format!("unknown block {id}")))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::Blocks)
+    }
+}
+
+/// One rule of a rule processor.
+///
+/// Vanilla: `ProcessorRule`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProcessorRule {
+    input_predicate: RuleTest,
+    location_predicate: RuleTest,
+    #[serde(default)]
+    position_predicate: PosRuleTest,
+    #[serde(deserialize_with = "deserialize_block_state")]
+    output_state: BlockStateId,
+    #[serde(default)]
+    block_entity_modifier: BlockEntityModifier,
+}
+
+impl ProcessorRule {
+    /// Vanilla: `ProcessorRule.test()`.
+    fn test(
+        &self,
+        input_state: BlockStateId,
+        location_state: BlockStateId,
+        world_pos: BlockPos,
+        reference_pos: BlockPos,
+        random: &mut LegacyRandom,
+    ) -> bool {
+        self.input_predicate.test(input_state, random)
+            && self.location_predicate.test(location_state, random)
+            && self
+                .position_predicate
+                .test(world_pos, reference_pos, random)
+    }
+}
+
+/// Tests a block state. Vanilla: `RuleTest`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "predicate_type")]
+enum RuleTest {
+    #[serde(rename = "minecraft:always_true")]
+    AlwaysTrue,
+    #[serde(rename = "minecraft:block_match")]
+    BlockMatch {
+        #[serde(deserialize_with = "deserialize_block")]
+        block: BlockRef,
+    },
+    #[serde(rename = "minecraft:blockstate_match")]
+    BlockStateMatch {
+        #[serde(deserialize_with = "deserialize_block_state")]
+        block_state: BlockStateId,
+    },
+    #[serde(rename = "minecraft:random_block_match")]
+    RandomBlockMatch {
+        #[serde(deserialize_with = "deserialize_block")]
+        block: BlockRef,
+        probability: f32,
+    },
+    #[serde(rename = "minecraft:random_blockstate_match")]
+    RandomBlockStateMatch {
+        #[serde(deserialize_with = "deserialize_block_state")]
+        block_state: BlockStateId,
+        probability: f32,
+    },
+    #[serde(rename = "minecraft:tag_match")]
+    TagMatch { tag: Identifier },
+}
+
+impl RuleTest {
+    /// Vanilla: `RuleTest.test()`.
+    fn test(&self, state: BlockStateId, random: &mut LegacyRandom) -> bool {
+        match self {
+            Self::AlwaysTrue => true,
+            Self::BlockMatch { block } => state.get_block() == *block,
+            Self::BlockStateMatch { block_state } => state == *block_state,
+            Self::RandomBlockMatch { block, probability } => {
+                state.get_block() == *block && random.next_f32() < *probability
+            }
+            Self::RandomBlockStateMatch {
+                block_state,
+                probability,
+            } => state == *block_state && random.next_f32() < *probability,
+            Self::TagMatch { tag } => REGISTRY.blocks.is_in_tag(state.get_block(), tag),
+        }
+    }
+}
+
+/// Tests where a block is placed. Vanilla: `PosRuleTest`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "predicate_type")]
+enum PosRuleTest {
+    #[default]
+    #[serde(rename = "minecraft:always_true")]
+    AlwaysTrue,
+    #[serde(rename = "minecraft:linear_pos")]
+    LinearPos {
+        #[serde(default)]
+        min_chance: f32,
+        #[serde(default)]
+        max_chance: f32,
+        #[serde(default)]
+        min_dist: i32,
+        #[serde(default)]
+        max_dist: i32,
+    },
+    #[serde(rename = "minecraft:axis_aligned_linear_pos")]
+    AxisAlignedLinearPos {
+        #[serde(default = "default_axis", deserialize_with = "deserialize_axis")]
+        axis: Axis,
+        #[serde(default)]
+        min_chance: f32,
+        #[serde(default)]
+        max_chance: f32,
+        #[serde(default)]
+        min_dist: i32,
+        #[serde(default)]
+        max_dist: i32,
+    },
+}
+
+impl PosRuleTest {
+    /// Vanilla: `PosRuleTest.test()`.
+    fn test(
+        &self,
+        world_pos: BlockPos,
+        reference_pos: BlockPos,
+        random: &mut LegacyRandom,
+    ) -> bool {
+        let (distance, min_chance, max_chance, min_dist, max_dist) = match self {
+            Self::AlwaysTrue => return true,
+            Self::LinearPos {
+                min_chance,
+                max_chance,
+                min_dist,
+                max_dist,
+            } => {
+                let distance = (world_pos.x() - reference_pos.x()).abs()
+                    + (world_pos.y() - reference_pos.y()).abs()
+                    + (world_pos.z() - reference_pos.z()).abs();
+                (distance, min_chance, max_chance, min_dist, max_dist)
+            }
+            Self::AxisAlignedLinearPos {
+                axis,
+                min_chance,
+                max_chance,
+                min_dist,
+                max_dist,
+            } => {
+                let distance = match axis {
+                    Axis::X => world_pos.x() - reference_pos.x(),
+                    Axis::Y => world_pos.y() - reference_pos.y(),
+                    Axis::Z => world_pos.z() - reference_pos.z(),
+                };
+                (distance.abs(), min_chance, max_chance, min_dist, max_dist)
+            }
+        };
+        let chance = random.next_f32();
+        let factor = (distance - min_dist) as f32 / (max_dist - min_dist) as f32;
+        chance <= clamped_lerp(factor, *min_chance, *max_chance)
+    }
+}
+
+/// Vanilla: `Mth.clampedLerp()`.
+fn clamped_lerp(factor: f32, min: f32, max: f32) -> f32 {
+    if factor < 0.0 {
+        min
+    } else if factor > 1.0 {
+        max
+    } else {
+        min + factor * (max - min)
+    }
+}
+
+/// Changes the block entity data of a rule's output.
+///
+/// Vanilla: `RuleBlockEntityModifier`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type")]
+enum BlockEntityModifier {
+    #[default]
+    #[serde(rename = "minecraft:passthrough")]
+    Passthrough,
+    #[serde(rename = "minecraft:clear")]
+    Clear,
+    #[serde(rename = "minecraft:append_loot")]
+    AppendLoot { loot_table: Identifier },
+    // TODO: Support `append_static`
+    #[serde(other)]
+    Unsupported,
+}
+
+impl BlockEntityModifier {
+    /// Vanilla: `RuleBlockEntityModifier.apply()`.
+    fn apply(&self, random: &mut LegacyRandom, nbt: Option<NbtCompound>) -> Option<NbtCompound> {
+        match self {
+            Self::Passthrough | Self::Unsupported => nbt,
+            Self::Clear => Some(NbtCompound::new()),
+            Self::AppendLoot { loot_table } => {
+                let mut nbt = nbt.unwrap_or_default();
+                nbt.insert("LootTable", loot_table.to_string());
+                nbt.insert("LootTableSeed", random.next_i64());
+                Some(nbt)
+            }
+        }
+    }
+}
+
+/// A list of processors, named by its id or written inline.
+///
+/// Vanilla: `StructureProcessorList`.
+#[derive(Debug, Clone)]
+pub struct ProcessorList {
+    /// The id of the list, or `None` if it was written inline.
+    pub id: Option<Identifier>,
+    /// The processors, run in order.
+    pub processors: Arc<[StructureProcessor]>,
+}
+
+impl ProcessorList {
+    /// Gets the list with the given id from the data pack.
+    #[must_use]
+    pub fn by_id(id: &Identifier) -> Option<Self> {
+        Some(Self {
+            id: Some(id.clone()),
+            processors: PROCESSOR_LISTS.get(id)?.clone(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ProcessorList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Id(Identifier),
+            Inline(ProcessorListJson),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Id(id) => Self::by_id(&id)
+                .ok_or_else(|| D::Error::custom(format!("unknown processor list {id}"))),
+            Raw::Inline(list) => Ok(Self {
+                id: None,
+                processors: list.processors.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProcessorListJson {
+    processors: Vec<StructureProcessor>,
+}
+
+/// The processor lists of the vanilla data pack.
+static PROCESSOR_LISTS: LazyLock<FxHashMap<Identifier, Arc<[StructureProcessor]>>> =
+    LazyLock::new(|| {
+        VANILLA_PROCESSOR_LISTS
+            .iter()
+            .filter_map(|(path, json)| {
+                let list: ProcessorListJson = serde_json::from_str(json)
+                    .inspect_err(|err| log::error!("Couldn't load processor list {path}: {err}"))
+                    .ok()?;
+                Some((
+                    Identifier::vanilla((*path).to_owned()),
+                    list.processors.into(),
+                ))
+            })
+            .collect()
+    });
+
+/// Parses a block state written like `minecraft:chest[facing=north]`.
+///
+/// Vanilla: `BlockStateParser.parseForBlock()`.
+fn parse_block_state(input: &str) -> Option<BlockStateId> {
+    let (name, properties) = match input.split_once('[') {
+        Some((name, rest)) => (name, rest.strip_suffix(']')?),
+        None => (input, ""),
+    };
+    let key = if name.contains(':') {
+        name.parse().ok()?
+    } else {
+        Identifier::vanilla(name.to_owned())
+    };
+    let block = REGISTRY.blocks.by_key(&key)?;
+    let properties = properties
+        .split(',')
+        .filter(|property| !property.is_empty())
+        .map(|property| property.split_once('='))
+        .collect::<Option<Vec<_>>>()?;
+    Some(state_with_properties(block, properties))
+}
+
+/// A block state written as `{Name, Properties}`.
+#[derive(Deserialize)]
+struct BlockStateJson {
+    #[serde(rename = "Name")]
+    name: Identifier,
+    #[serde(rename = "Properties", default)]
+    properties: FxHashMap<String, String>,
+}
+
+fn deserialize_block_state<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BlockStateId, D::Error> {
+    let json = BlockStateJson::deserialize(deserializer)?;
+    let block = REGISTRY
+        .blocks
+        .by_key(&json.name)
+        .ok_or_else(|| D::Error::custom(format!("unknown block {}", json.name)))?;
+    Ok(state_with_properties(block, &json.properties))
+}
+
+fn deserialize_blocks<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<BlockRef>, D::Error> {
+    Vec::<BlockStateJson>::deserialize(deserializer)?
+        .iter()
+        .map(|json| {
+            REGISTRY
+                .blocks
+                .by_key(&json.name)
+                .ok_or_else(|| D::Error::custom(format!("unknown block {}", json.name)))
+        })
+        .collect()
+}
+
+fn deserialize_block<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BlockRef, D::Error> {
+    let id = Identifier::deserialize(deserializer)?;
+    REGISTRY
+        .blocks
+        .by_key(&id)
+        .ok_or_else(|| D::Error::custom(format!("unknown block {id}")))
+}
+
+fn deserialize_heightmap<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HeightmapType, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    HeightmapType::by_name(&name)
+        .ok_or_else(|| D::Error::custom(format!("unknown heightmap {name}")))
+}
+
+const fn default_gravity_heightmap() -> HeightmapType {
+    HeightmapType::WorldSurfaceWg
+}
+
+fn deserialize_axis<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Axis, D::Error> {
+    match String::deserialize(deserializer)?.as_str() {
+        "x" => Ok(Axis::X),
+        "y" => Ok(Axis::Y),
+        "z" => Ok(Axis::Z),
+        other => Err(D::Error::custom(format!("unknown axis {other}"))),
+    }
+}
+
+const fn default_axis() -> Axis {
+    Axis::Y
+}
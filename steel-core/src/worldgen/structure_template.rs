@@ -0,0 +1,748 @@
+//! Structure templates, the `.nbt` files structure blocks save and load.
+//!
+//! A template holds one or more palettes of blocks plus the entities inside it.
+//! Placing it applies a mirror, a rotation and the settings' processors.
+
+use std::array;
+use std::io::{self, Cursor, Read};
+
+use flate2::read::GzDecoder;
+use glam::DVec3;
+use simdnbt::owned::{NbtCompound, NbtList};
+use steel_registry::blocks::BlockRef;
+use steel_registry::blocks::block_state_ext::BlockStateExt;
+use steel_registry::blocks::properties::{BlockStateProperties, FrontAndTop};
+use steel_registry::blocks::shapes::is_shape_full_block;
+use steel_registry::{REGISTRY, RegistryExt, vanilla_blocks};
+use steel_utils::math::wrap_degrees;
+use steel_utils::nbt::NbtLimits;
+use steel_utils::nbt::binary::read_named;
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::random::{Random, get_seed};
+use steel_utils::types::UpdateFlags;
+use steel_utils::{BlockPos, BlockStateId, BoundingBox, Direction, Identifier, Mirror, Rotation};
+
+use super::structure_level::StructureLevel;
+use super::structure_processor::StructureProcessor;
+
+/// A block of a structure template.
+#[derive(Debug, Clone)]
+pub struct StructureBlockInfo {
+    /// The position, relative to the template before processing and absolute after.
+    pub pos: BlockPos,
+    /// The block state, before mirroring and rotating.
+    pub state: BlockStateId,
+    /// The block entity data, without the position.
+    pub nbt: Option<NbtCompound>,
+}
+
+/// An entity of a structure template.
+#[derive(Debug, Clone)]
+pub struct StructureEntityInfo {
+    /// The exact position, relative to the template.
+    pub pos: DVec3,
+    /// The block the entity is in, relative to the template.
+    pub block_pos: BlockPos,
+    /// The saved entity.
+    pub nbt: NbtCompound,
+}
+
+/// How a template is placed.
+///
+/// Vanilla: `StructurePlaceSettings`.
+#[derive(Debug, Clone)]
+pub struct StructurePlaceSettings {
+    /// Applied before the rotation.
+    pub mirror: Mirror,
+    /// The rotation around `rotation_pivot`.
+    pub rotation: Rotation,
+    /// The point the template rotates around, relative to the template.
+    pub rotation_pivot: BlockPos,
+    /// Skips placing the template's entities.
+    pub ignore_entities: bool,
+    /// Only blocks and entities inside this box are placed.
+    pub bounding_box: Option<BoundingBox>,
+    /// Skips fixing up the shapes of blocks at the template's edges.
+    pub known_shape: bool,
+    /// Waterlogs blocks placed into water.
+    pub keep_liquids: bool,
+    /// Seeds one random shared by all processors. Without it, each block
+    /// gets a random seeded from its position.
+    pub random_seed: Option<i64>,
+    /// Run in order on each block.
+    pub processors: Vec<StructureProcessor>,
+}
+
+impl Default for StructurePlaceSettings {
+    fn default() -> Self {
+        Self {
+            mirror: Mirror::None,
+            rotation: Rotation::None,
+            rotation_pivot: BlockPos::ZERO,
+            ignore_entities: false,
+            bounding_box: None,
+            known_shape: false,
+            keep_liquids: true,
+            random_seed: None,
+            processors: Vec::new(),
+        }
+    }
+}
+
+/// The random of one placement. Vanilla: `StructurePlaceSettings.getRandom()`.
+pub(super) struct SettingsRandom(Option<LegacyRandom>);
+
+impl SettingsRandom {
+    pub(super) fn new(settings: &StructurePlaceSettings) -> Self {
+        Self(
+            settings
+                .random_seed
+                .map(|seed| LegacyRandom::from_seed(seed as u64)),
+        )
+    }
+
+    pub(super) fn with<R>(&mut self, pos: BlockPos, f: impl FnOnce(&mut LegacyRandom) -> R) -> R {
+        match &mut self.0 {
+            Some(random) => f(random),
+            None => f(&mut LegacyRandom::from_seed(
+                get_seed(pos.x(), pos.y(), pos.z()) as u64,
+            )),
+        }
+    }
+}
+
+/// How a jigsaw connects to the jigsaw it faces.
+///
+/// Vanilla: `JigsawBlockEntity.JointType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JointType {
+    /// The attached piece can turn around the connection.
+    Rollable,
+    /// The tops of both jigsaws must point the same way.
+    Aligned,
+}
+
+/// A jigsaw block of a template with the data of its block entity.
+///
+/// Vanilla: `StructureTemplate.JigsawBlockInfo`.
+#[derive(Debug, Clone)]
+pub struct JigsawBlockInfo {
+    /// Where the jigsaw is.
+    pub pos: BlockPos,
+    /// The side the jigsaw connects through.
+    pub front: Direction,
+    /// The side the top of the jigsaw points to.
+    pub top: Direction,
+    /// How the jigsaw connects.
+    pub joint: JointType,
+    /// The name jigsaws of other pieces target.
+    pub name: Identifier,
+    /// The pool the attached piece is picked from.
+    pub pool: Identifier,
+    /// The name of the jigsaw the attached piece connects through.
+    pub target: Identifier,
+    /// Jigsaws of pieces attached here with a higher priority grow first.
+    pub placement_priority: i32,
+    /// Jigsaws with a higher priority are tried first.
+    pub selection_priority: i32,
+}
+
+impl JigsawBlockInfo {
+    /// Reads the jigsaw at `pos`. Missing data falls back to vanilla's defaults.
+    ///
+    /// Vanilla: `StructureTemplate.JigsawBlockInfo.of()`.
+    #[must_use]
+    pub fn of(pos: BlockPos, state: BlockStateId, nbt: Option<&NbtCompound>) -> Self {
+        let (front, top) = state
+            .try_get_value(&BlockStateProperties::ORIENTATION)
+            .map_or((Direction::North, Direction::Up), front_and_top);
+        let id = |key: &str| {
+            nbt.and_then(|nbt| nbt.string(key))
+                .and_then(|id| id.to_str().parse::<Identifier>().ok())
+                .unwrap_or_else(|| Identifier::vanilla_static("empty"))
+        };
+        let joint = match nbt.and_then(|nbt| nbt.string("joint")) {
+            Some(joint) if joint.to_str() == "rollable" => JointType::Rollable,
+            Some(joint) if joint.to_str() == "aligned" => JointType::Aligned,
+            _ if front.is_horizontal() => JointType::Aligned,
+            _ => JointType::Rollable,
+        };
+        let priority = |key: &str| nbt.and_then(|nbt| nbt.int(key)).unwrap_or(0);
+        Self {
+            pos,
+            front,
+            top,
+            joint,
+            name: id("name"),
+            pool: id("pool"),
+            target: id("target"),
+            placement_priority: priority("placement_priority"),
+            selection_priority: priority("selection_priority"),
+        }
+    }
+
+    /// Returns whether a piece can attach its `target` jigsaw to this one.
+    ///
+    /// Vanilla: `JigsawBlock.canAttach()`.
+    #[must_use]
+    pub fn can_attach(&self, target: &Self) -> bool {
+        self.front == target.front.opposite()
+            && (self.joint == JointType::Rollable || self.top == target.top)
+            && self.target == target.name
+    }
+}
+
+/// Vanilla: `FrontAndTop.front()` and `FrontAndTop.top()`.
+const fn front_and_top(orientation: FrontAndTop) -> (Direction, Direction) {
+    match orientation {
+        FrontAndTop::DownEast => (Direction::Down, Direction::East),
+        FrontAndTop::DownNorth => (Direction::Down, Direction::North),
+        FrontAndTop::DownSouth => (Direction::Down, Direction::South),
+        FrontAndTop::DownWest => (Direction::Down, Direction::West),
+        FrontAndTop::UpEast => (Direction::Up, Direction::East),
+        FrontAndTop::UpNorth => (Direction::Up, Direction::North),
+        FrontAndTop::UpSouth => (Direction::Up, Direction::South),
+        FrontAndTop::UpWest => (Direction::Up, Direction::West),
+        FrontAndTop::WestUp => (Direction::West, Direction::Up),
+        FrontAndTop::EastUp => (Direction::East, Direction::Up),
+        FrontAndTop::NorthUp => (Direction::North, Direction::Up),
+        FrontAndTop::SouthUp => (Direction::South, Direction::Up),
+    }
+}
+
+/// A structure loaded from a `.nbt` file.
+///
+/// Vanilla: `StructureTemplate`.
+#[derive(Debug, Default)]
+pub struct StructureTemplate {
+    size: BlockPos,
+    palettes: Vec<Vec<StructureBlockInfo>>,
+    entities: Vec<StructureEntityInfo>,
+}
+
+impl StructureTemplate {
+    /// Reads a template from a gzipped `.nbt` file.
+    ///
+    /// # Errors
+    /// Returns an error if the data isn't gzipped NBT with a compound root.
+    pub fn from_gzipped(bytes: &[u8]) -> io::Result<Self> {
+        let mut decoded = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut decoded)?;
+        let (_, nbt) = read_named(&mut Cursor::new(&decoded), NbtLimits::UNLIMITED)?;
+        Ok(Self::load(&nbt))
+    }
+
+    /// Reads a template. Missing fields are left empty, like vanilla.
+    ///
+    /// Vanilla: `StructureTemplate.load()`.
+    // TODO: Upgrade templates saved with an older `DataVersion`
+    #[must_use]
+    pub fn load(nbt: &NbtCompound) -> Self {
+        let [x, y, z] = ints(nbt.list("size"));
+        let blocks = nbt
+            .list("blocks")
+            .and_then(NbtList::compounds)
+            .unwrap_or_default();
+
+        let palettes = match nbt.list("palettes") {
+            Some(NbtList::List(palettes)) => palettes
+                .iter()
+                .map(|palette| load_palette(palette.compounds().unwrap_or_default(), blocks))
+                .collect(),
+            _ => {
+                let palette = nbt
+                    .list("palette")
+                    .and_then(NbtList::compounds)
+                    .unwrap_or_default();
+                vec![load_palette(palette, blocks)]
+            }
+        };
+
+        let entities = nbt
+            .list("entities")
+            .and_then(NbtList::compounds)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entity| {
+                let [x, y, z] = doubles(entity.list("pos"));
+                let [block_x, block_y, block_z] = ints(entity.list("blockPos"));
+                Some(StructureEntityInfo {
+                    pos: DVec3::new(x, y, z),
+                    block_pos: BlockPos::new(block_x, block_y, block_z),
+                    nbt: entity.compound("nbt")?.clone(),
+                })
+            })
+            .collect();
+
+        Self {
+            size: BlockPos::new(x, y, z),
+            palettes,
+            entities,
+        }
+    }
+
+    /// Gets the size of the template.
+    #[must_use]
+    pub const fn size(&self) -> BlockPos {
+        self.size
+    }
+
+    /// Gets the size after rotating. Quarter turns swap the X and Z sizes.
+    ///
+    /// Vanilla: `StructureTemplate.getSize(Rotation)`.
+    #[must_use]
+    pub const fn rotated_size(&self, rotation: Rotation) -> BlockPos {
+        match rotation {
+            Rotation::Clockwise90 | Rotation::Counterclockwise90 => {
+                BlockPos::new(self.size.z(), self.size.y(), self.size.x())
+            }
+            Rotation::None | Rotation::Clockwise180 => self.size,
+        }
+    }
+
+    /// Gets the block palettes. Most templates have one; shipwrecks pick one at random.
+    #[must_use]
+    pub fn palettes(&self) -> &[Vec<StructureBlockInfo>] {
+        &self.palettes
+    }
+
+    /// Gets the entities.
+    #[must_use]
+    pub fn entities(&self) -> &[StructureEntityInfo] {
+        &self.entities
+    }
+
+    /// Gets the jigsaw blocks of the palette picked for `pos`, rotated and moved
+    /// to `pos`.
+    ///
+    /// Vanilla: `StructureTemplate.getJigsaws()`.
+    #[must_use]
+    pub fn jigsaws(&self, pos: BlockPos, rotation: Rotation) -> Vec<JigsawBlockInfo> {
+        if self.palettes.is_empty() {
+            return Vec::new();
+        }
+        let mut random = LegacyRandom::from_seed(get_seed(pos.x(), pos.y(), pos.z()) as u64);
+        let palette_index = random.next_i32_bounded(self.palettes.len() as i32) as usize;
+        self.palettes[palette_index]
+            .iter()
+            .filter(|info| info.state.get_block() == vanilla_blocks::JIGSAW)
+            .map(|info| {
+                let moved = transform(info.pos, Mirror::None, rotation, BlockPos::ZERO).offset(
+                    pos.x(),
+                    pos.y(),
+                    pos.z(),
+                );
+                JigsawBlockInfo::of(moved, info.state.rotate(rotation), info.nbt.as_ref())
+            })
+            .collect()
+    }
+
+    /// Gets the box the template covers when placed at `pos`.
+    ///
+    /// Vanilla: `StructureTemplate.getBoundingBox()`.
+    #[must_use]
+    pub const fn bounding_box(
+        &self,
+        settings: &StructurePlaceSettings,
+        pos: BlockPos,
+    ) -> BoundingBox {
+        let far = BlockPos::new(self.size.x() - 1, self.size.y() - 1, self.size.z() - 1);
+        let a = transform(
+            BlockPos::ZERO,
+            settings.mirror,
+            settings.rotation,
+            settings.rotation_pivot,
+        );
+        let b = transform(
+            far,
+            settings.mirror,
+            settings.rotation,
+            settings.rotation_pivot,
+        );
+        BoundingBox::from_corners(
+            a.offset(pos.x(), pos.y(), pos.z()),
+            b.offset(pos.x(), pos.y(), pos.z()),
+        )
+    }
+
+    /// Places the template with its origin at `pos`.
+    ///
+    /// Processors measure distances from `reference_pos` and `random` seeds the
+    /// loot of containers. Returns `false` if the template is empty, so nothing
+    /// was placed.
+    /// Vanilla: `StructureTemplate.placeInWorld()`.
+    // TODO: Update the shapes of blocks at the edges unless `known_shape` is set
+    // TODO: Spread water into waterloggable blocks next to placed water
+    pub fn place_in_world(
+        &self,
+        level: &impl StructureLevel,
+        pos: BlockPos,
+        reference_pos: BlockPos,
+        settings: &StructurePlaceSettings,
+        random: &mut impl Random,
+        flags: UpdateFlags,
+    ) -> bool {
+        if self.palettes.is_empty() {
+            return false;
+        }
+        let mut settings_random = SettingsRandom::new(settings);
+        let palette_index =
+            settings_random.with(pos, |r| r.next_i32_bounded(self.palettes.len() as i32));
+        let blocks = &self.palettes[palette_index as usize];
+        let has_content =
+            !blocks.is_empty() || (!settings.ignore_entities && !self.entities.is_empty());
+        if !has_content || self.size.x() < 1 || self.size.y() < 1 || self.size.z() < 1 {
+            return false;
+        }
+
+        let barrier = REGISTRY
+            .blocks
+            .get_default_state_id(vanilla_blocks::BARRIER);
+        let infos = process_block_infos(
+            level,
+            pos,
+            reference_pos,
+            settings,
+            blocks,
+            &mut settings_random,
+        );
+        for info in infos {
+            if settings
+                .bounding_box
+                .is_some_and(|bounding_box| !bounding_box.is_inside(info.pos))
+            {
+                continue;
+            }
+            let mut state = info.state.mirror(settings.mirror).rotate(settings.rotation);
+            if settings.keep_liquids
+                && is_water_source(level.get_block_state(info.pos))
+                && state.try_get_value(&BlockStateProperties::WATERLOGGED) == Some(false)
+            {
+                state = state.set_value(&BlockStateProperties::WATERLOGGED, true);
+            }
+            if info.nbt.is_some() {
+                // Swap the old block out first, so a container there doesn't spill its items
+                level.set_block(
+                    info.pos,
+                    barrier,
+                    UpdateFlags::UPDATE_INVISIBLE | UpdateFlags::UPDATE_SKIP_ALL_SIDEEFFECTS,
+                );
+            }
+            if level.set_block(info.pos, state, flags)
+                && let Some(nbt) = info.nbt
+            {
+                level.load_block_entity(info.pos, nbt, random);
+            }
+        }
+
+        if !settings.ignore_entities {
+            self.place_entities(level, pos, settings);
+        }
+        true
+    }
+
+    /// Vanilla: `StructureTemplate.addEntitiesToWorld()`.
+    // TODO: Rotate the facing of hanging entities and finalize mob spawns
+    fn place_entities(
+        &self,
+        level: &impl StructureLevel,
+        pos: BlockPos,
+        settings: &StructurePlaceSettings,
+    ) {
+        let origin = DVec3::new(f64::from(pos.x()), f64::from(pos.y()), f64::from(pos.z()));
+        for info in &self.entities {
+            let block_pos = transform(
+                info.block_pos,
+                settings.mirror,
+                settings.rotation,
+                settings.rotation_pivot,
+            )
+            .offset(pos.x(), pos.y(), pos.z());
+            if settings
+                .bounding_box
+                .is_some_and(|bounding_box| !bounding_box.is_inside(block_pos))
+            {
+                continue;
+            }
+            let entity_pos = transform_vec(
+                info.pos,
+                settings.mirror,
+                settings.rotation,
+                settings.rotation_pivot,
+            ) + origin;
+
+            let mut nbt = info.nbt.clone();
+            nbt.insert(
+                "Pos",
+                NbtList::Double(vec![entity_pos.x, entity_pos.y, entity_pos.z]),
+            );
+            nbt.remove("UUID");
+            let (yaw, pitch) = nbt
+                .list("Rotation")
+                .and_then(NbtList::floats)
+                .and_then(|rotation| Some((*rotation.first()?, *rotation.get(1)?)))
+                .unwrap_or((0.0, 0.0));
+            let yaw = rotate_yaw(yaw, settings.rotation) + mirror_yaw(yaw, settings.mirror) - yaw;
+            nbt.insert("Rotation", NbtList::Float(vec![yaw, pitch]));
+            level.add_entity(&nbt);
+        }
+    }
+}
+
+/// Moves the blocks of a palette to where they are placed and runs the processors.
+///
+/// Vanilla: `StructureTemplate.processBlockInfos()`.
+fn process_block_infos(
+    level: &impl StructureLevel,
+    pos: BlockPos,
+    reference_pos: BlockPos,
+    settings: &StructurePlaceSettings,
+    blocks: &[StructureBlockInfo],
+    random: &mut SettingsRandom,
+) -> Vec<StructureBlockInfo> {
+    blocks
+        .iter()
+        .filter_map(|info| {
+            let placed = StructureBlockInfo {
+                pos: transform(
+                    info.pos,
+                    settings.mirror,
+                    settings.rotation,
+                    settings.rotation_pivot,
+                )
+                .offset(pos.x(), pos.y(), pos.z()),
+                state: info.state,
+                nbt: info.nbt.clone(),
+            };
+            settings
+                .processors
+                .iter()
+                .try_fold(placed, |placed, processor| {
+                    processor.process(level, reference_pos, info, placed, random)
+                })
+        })
+        .collect()
+}
+
+/// Mirrors and then rotates a position inside a template around `pivot`.
+///
+/// Vanilla: `StructureTemplate.transform(BlockPos, ...)`.
+#[must_use]
+pub const fn transform(
+    pos: BlockPos,
+    mirror: Mirror,
+    rotation: Rotation,
+    pivot: BlockPos,
+) -> BlockPos {
+    let y = pos.y();
+    let (x, z) = match mirror {
+        Mirror::None => (pos.x(), pos.z()),
+        Mirror::LeftRight => (pos.x(), -pos.z()),
+        Mirror::FrontBack => (-pos.x(), pos.z()),
+    };
+    let (pivot_x, pivot_z) = (pivot.x(), pivot.z());
+    match rotation {
+        Rotation::None => BlockPos::new(x, y, z),
+        Rotation::Counterclockwise90 => {
+            BlockPos::new(pivot_x - pivot_z + z, y, pivot_x + pivot_z - x)
+        }
+        Rotation::Clockwise90 => BlockPos::new(pivot_x + pivot_z - z, y, pivot_z - pivot_x + x),
+        Rotation::Clockwise180 => BlockPos::new(2 * pivot_x - x, y, 2 * pivot_z - z),
+    }
+}
+
+/// Mirrors and then rotates an exact position inside a template around `pivot`.
+///
+/// Vanilla: `StructureTemplate.transform(Vec3, ...)`.
+#[must_use]
+pub fn transform_vec(pos: DVec3, mirror: Mirror, rotation: Rotation, pivot: BlockPos) -> DVec3 {
+    let y = pos.y;
+    let (x, z) = match mirror {
+        Mirror::None => (pos.x, pos.z),
+        Mirror::LeftRight => (pos.x, 1.0 - pos.z),
+        Mirror::FrontBack => (1.0 - pos.x, pos.z),
+    };
+    let pivot_x = f64::from(pivot.x());
+    let pivot_z = f64::from(pivot.z());
+    match rotation {
+        Rotation::None => DVec3::new(x, y, z),
+        Rotation::Counterclockwise90 => {
+            DVec3::new(pivot_x - pivot_z + z, y, pivot_x + pivot_z + 1.0 - x)
+        }
+        Rotation::Clockwise90 => DVec3::new(pivot_x + pivot_z + 1.0 - z, y, pivot_z - pivot_x + x),
+        Rotation::Clockwise180 => DVec3::new(2.0 * pivot_x + 1.0 - x, y, 2.0 * pivot_z + 1.0 - z),
+    }
+}
+
+/// Vanilla: `Entity.rotate()`.
+fn rotate_yaw(yaw: f32, rotation: Rotation) -> f32 {
+    wrap_degrees(yaw) + 90.0 * rotation.quarter_turns() as f32
+}
+
+/// Vanilla: `Entity.mirror()`.
+fn mirror_yaw(yaw: f32, mirror: Mirror) -> f32 {
+    let yaw = wrap_degrees(yaw);
+    match mirror {
+        Mirror::None => yaw,
+        Mirror::LeftRight => -yaw,
+        Mirror::FrontBack => 180.0 - yaw,
+    }
+}
+
+/// Reads a block state saved as `{Name, Properties}`, like a palette entry.
+///
+/// Unknown blocks become air and unknown property values are skipped.
+/// Vanilla: `NbtUtils.readBlockState()`.
+#[must_use]
+pub fn read_block_state(nbt: &NbtCompound) -> BlockStateId {
+    let Some(block) = nbt
+        .string("Name")
+        .and_then(|name| name.to_str().parse::<Identifier>().ok())
+        .and_then(|key| REGISTRY.blocks.by_key(&key))
+    else {
+        return REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+    };
+    let Some(saved) = nbt.compound("Properties") else {
+        return block.default_state();
+    };
+    let properties: Vec<(String, String)> = saved
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((
+                name.to_str().into_owned(),
+                value.string()?.to_str().into_owned(),
+            ))
+        })
+        .collect();
+    state_with_properties(block, properties)
+}
+
+/// Gets the state of `block` with the given properties. Unknown properties and
+/// values are skipped, leaving the default value.
+pub(super) fn state_with_properties<K, V>(
+    block: BlockRef,
+    properties: impl IntoIterator<Item = (K, V)>,
+) -> BlockStateId
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let state = block.default_state();
+    let mut merged: Vec<(&str, String)> = REGISTRY
+        .blocks
+        .get_properties(state)
+        .into_iter()
+        .map(|(name, value)| (name, value.to_owned()))
+        .collect();
+    for (name, value) in properties {
+        let (name, value) = (name.as_ref(), value.as_ref());
+        let valid = block.properties.iter().any(|property| {
+            property.get_name() == name && property.get_possible_values().contains(&value)
+        });
+        if let Some(entry) = merged.iter_mut().find(|(known, _)| *known == name)
+            && valid
+        {
+            entry.1 = value.to_owned();
+        }
+    }
+    let merged: Vec<(&str, &str)> = merged
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    REGISTRY
+        .blocks
+        .state_id_from_properties(&block.key, &merged)
+        .unwrap_or(state)
+}
+
+/// Sorts the blocks of a palette so full blocks are placed first and blocks with
+/// a block entity last, each bottom to top.
+///
+/// Vanilla: `StructureTemplate.buildInfoList()`.
+fn load_palette(palette: &[NbtCompound], blocks: &[NbtCompound]) -> Vec<StructureBlockInfo> {
+    let states: Vec<BlockStateId> = palette.iter().map(read_block_state).collect();
+    let air = REGISTRY.blocks.get_default_state_id(vanilla_blocks::AIR);
+
+    let mut full_blocks = Vec::new();
+    let mut other_blocks = Vec::new();
+    let mut blocks_with_nbt = Vec::new();
+    for block in blocks {
+        let [x, y, z] = ints(block.list("pos"));
+        let state = block
+            .int("state")
+            .and_then(|index| states.get(usize::try_from(index).ok()?))
+            .copied()
+            .unwrap_or(air);
+        let info = StructureBlockInfo {
+            pos: BlockPos::new(x, y, z),
+            state,
+            nbt: block.compound("nbt").cloned(),
+        };
+        if info.nbt.is_some() {
+            blocks_with_nbt.push(info);
+        } else if !state.get_block().config.dynamic_shape
+            && is_shape_full_block(state.get_collision_shape())
+        {
+            full_blocks.push(info);
+        } else {
+            other_blocks.push(info);
+        }
+    }
+
+    let mut sorted = Vec::with_capacity(blocks.len());
+    for mut list in [full_blocks, other_blocks, blocks_with_nbt] {
+        list.sort_by_key(|info| (info.pos.y(), info.pos.x(), info.pos.z()));
+        sorted.append(&mut list);
+    }
+    sorted
+}
+
+fn is_water_source(state: BlockStateId) -> bool {
+    if state.get_block() == vanilla_blocks::WATER {
+        return state.get_value(&BlockStateProperties::LEVEL) == 0;
+    }
+    state.try_get_value(&BlockStateProperties::WATERLOGGED) == Some(true)
+}
+
+fn ints(list: Option<&NbtList>) -> [i32; 3] {
+    let values = list.and_then(NbtList::ints).unwrap_or_default();
+    array::from_fn(|i| values.get(i).copied().unwrap_or(0))
+}
+
+fn doubles(list: Option<&NbtList>) -> [f64; 3] {
+    let values = list.and_then(NbtList::doubles).unwrap_or_default();
+    array::from_fn(|i| values.get(i).copied().unwrap_or(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use steel_utils::{BlockPos, Mirror, Rotation};
+
+    use super::transform;
+
+    #[test]
+    fn transform_matches_java() {
+        let pos = BlockPos::new(3, 5, 1);
+        let pivot = BlockPos::new(1, 0, 2);
+        assert_eq!(
+            transform(pos, Mirror::None, Rotation::Clockwise90, pivot),
+            BlockPos::new(2, 5, 4)
+        );
+        assert_eq!(
+            transform(pos, Mirror::None, Rotation::Counterclockwise90, pivot),
+            BlockPos::new(0, 5, 0)
+        );
+        assert_eq!(
+            transform(pos, Mirror::None, Rotation::Clockwise180, pivot),
+            BlockPos::new(-1, 5, 3)
+        );
+        assert_eq!(
+            transform(pos, Mirror::LeftRight, Rotation::None, pivot),
+            BlockPos::new(3, 5, -1)
+        );
+        assert_eq!(
+            transform(pos, Mirror::FrontBack, Rotation::Clockwise90, pivot),
+            BlockPos::new(2, 5, -2)
+        );
+    }
+}
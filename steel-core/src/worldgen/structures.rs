@@ -0,0 +1,618 @@
+//! Structures and structure sets from the data pack, and where they start.
+//!
+//! A structure set decides which chunks may start one of its structures; the
+//! structure then decides whether it fits there and which pieces it places.
+//! Only jigsaw structures generate so far.
+
+use std::sync::LazyLock;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use steel_registry::biome::BiomeRef;
+use steel_registry::vanilla_structure_data::{VANILLA_STRUCTURE_SETS, VANILLA_STRUCTURES};
+use steel_registry::{REGISTRY, RegistryExt, TaggedRegistryExt};
+use steel_utils::random::legacy_random::LegacyRandom;
+use steel_utils::random::worldgen_random::WorldgenRandom;
+use steel_utils::random::{Random, RandomSource};
+use steel_utils::{ChunkPos, Identifier};
+
+use super::BiomeSourceKind;
+use super::jigsaw::{JigsawPiece, JigsawStructure};
+use crate::chunk::beardifier::TerrainAdjustment;
+use crate::chunk::heightmap::HeightmapType;
+use crate::world::structure::StructureStart;
+
+/// The names of the feature decoration steps, in order.
+///
+/// Vanilla: `GenerationStep.Decoration`.
+pub const DECORATION_STEPS: [&str; 11] = [
+    "raw_generation",
+    "lakes",
+    "local_modifications",
+    "underground_structures",
+    "surface_structures",
+    "strongholds",
+    "underground_ores",
+    "underground_decoration",
+    "fluid_springs",
+    "vegetal_decoration",
+    "top_layer_modification",
+];
+
+/// What a structure sees of the world while it picks where it starts.
+///
+/// Vanilla: `Structure.GenerationContext`.
+pub struct GenerationContext<'a> {
+    /// The chunk the structure starts in.
+    pub chunk_pos: ChunkPos,
+    /// The world seed.
+    pub seed: i64,
+    /// The lowest Y of the dimension.
+    pub min_y: i32,
+    /// The height of the dimension.
+    pub height: i32,
+    /// The random the structure is assembled with.
+    pub random: WorldgenRandom,
+    base_height: &'a dyn Fn(i32, i32, HeightmapType) -> i32,
+}
+
+impl<'a> GenerationContext<'a> {
+    /// Creates the context for a structure starting in `chunk_pos`.
+    /// `base_height` gets the height of the noise terrain before surfaces.
+    #[must_use]
+    pub fn new(
+        chunk_pos: ChunkPos,
+        seed: i64,
+        min_y: i32,
+        height: i32,
+        base_height: &'a dyn Fn(i32, i32, HeightmapType) -> i32,
+    ) -> Self {
+        let mut random = WorldgenRandom::new(RandomSource::Legacy(LegacyRandom::from_seed(0)));
+        random.set_large_feature_seed(seed, chunk_pos.0.x, chunk_pos.0.y);
+        Self {
+            chunk_pos,
+            seed,
+            min_y,
+            height,
+            random,
+            base_height,
+        }
+    }
+
+    /// Gets the height of the noise terrain at `x`, `z`.
+    ///
+    /// Vanilla: `ChunkGenerator.getFirstFreeHeight()`.
+    #[must_use]
+    pub fn base_height(&self, x: i32, z: i32, heightmap: HeightmapType) -> i32 {
+        (self.base_height)(x, z, heightmap)
+    }
+
+    /// Gets the highest Y of the dimension.
+    #[must_use]
+    pub const fn max_y(&self) -> i32 {
+        self.min_y + self.height - 1
+    }
+}
+
+/// A Y position relative to the bottom or top of the world.
+///
+/// Vanilla: `VerticalAnchor`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerticalAnchor {
+    /// An absolute Y.
+    Absolute(i32),
+    /// A distance above the lowest Y.
+    AboveBottom(i32),
+    /// A distance below the highest Y.
+    BelowTop(i32),
+}
+
+impl VerticalAnchor {
+    /// Resolves the anchor in a world with the given bounds.
+    #[must_use]
+    pub const fn resolve_y(self, min_y: i32, height: i32) -> i32 {
+        match self {
+            Self::Absolute(y) => y,
+            Self::AboveBottom(offset) => min_y + offset,
+            Self::BelowTop(offset) => min_y + height - 1 - offset,
+        }
+    }
+}
+
+/// Picks a Y, written as a plain anchor or as a typed provider.
+///
+/// Vanilla: `HeightProvider`.
+#[derive(Debug, Clone, Copy)]
+pub enum HeightProvider {
+    /// Always the same Y. Vanilla: `ConstantHeight`.
+    Constant(VerticalAnchor),
+    /// Any Y between two anchors. Vanilla: `UniformHeight`.
+    Uniform {
+        /// The lowest Y picked.
+        min_inclusive: VerticalAnchor,
+        /// The highest Y picked.
+        max_inclusive: VerticalAnchor,
+    },
+}
+
+impl HeightProvider {
+    /// Picks a Y in a world with the given bounds.
+    ///
+    /// Vanilla: `HeightProvider.sample()`.
+    pub fn sample(&self, random: &mut impl Random, min_y: i32, height: i32) -> i32 {
+        match *self {
+            Self::Constant(anchor) => anchor.resolve_y(min_y, height),
+            Self::Uniform {
+                min_inclusive,
+                max_inclusive,
+            } => {
+                let min = min_inclusive.resolve_y(min_y, height);
+                let max = max_inclusive.resolve_y(min_y, height);
+                if min > max {
+                    log::warn!("Empty height range: {min}..={max}");
+                    return min;
+                }
+                random.next_i32_bounded(max - min + 1) + min
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HeightProvider {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(tag = "type")]
+        enum Typed {
+            #[serde(rename = "minecraft:constant")]
+            Constant { value: VerticalAnchor },
+            #[serde(rename = "minecraft:uniform")]
+            Uniform {
+                min_inclusive: VerticalAnchor,
+                max_inclusive: VerticalAnchor,
+            },
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Typed(Typed),
+            Anchor(VerticalAnchor),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Typed(Typed::Constant { value }) | Raw::Anchor(value) => Self::Constant(value),
+            Raw::Typed(Typed::Uniform {
+                min_inclusive,
+                max_inclusive,
+            }) => Self::Uniform {
+                min_inclusive,
+                max_inclusive,
+            },
+        })
+    }
+}
+
+/// The biomes a structure starts in, written as a tag, one biome or a list.
+#[derive(Debug, Clone)]
+pub enum BiomeSet {
+    /// Every biome in the tag.
+    Tag(Identifier),
+    /// The listed biomes.
+    Biomes(Vec<Identifier>),
+}
+
+impl BiomeSet {
+    /// Checks if the set holds `biome`.
+    #[must_use]
+    pub fn contains(&self, biome: BiomeRef) -> bool {
+        match self {
+            Self::Tag(tag) => REGISTRY.biomes.is_in_tag(biome, tag),
+            Self::Biomes(biomes) => biomes.contains(&biome.key),
+        }
+    }
+
+    /// Checks if `biome_source` generates any biome of the set.
+    fn any_possible(&self, biome_source: &BiomeSourceKind) -> bool {
+        match self {
+            Self::Tag(tag) => REGISTRY
+                .biomes
+                .iter_tag(tag)
+                .any(|biome| biome_source.can_generate(biome)),
+            Self::Biomes(biomes) => biomes
+                .iter()
+                .filter_map(|key| REGISTRY.biomes.by_key(key))
+                .any(|biome| biome_source.can_generate(biome)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BiomeSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            One(String),
+            List(Vec<Identifier>),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::One(name) => match name.strip_prefix('#') {
+                Some(tag) => tag.parse().map(Self::Tag).map_err(D::Error::custom),
+                None => name
+                    .parse()
+                    .map(|biome| Self::Biomes(vec![biome]))
+                    .map_err(D::Error::custom),
+            },
+            Raw::List(biomes) => Ok(Self::Biomes(biomes)),
+        }
+    }
+}
+
+/// The kind of a structure, with its own settings.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum StructureKind {
+    /// Vanilla: `JigsawStructure`.
+    #[serde(rename = "minecraft:jigsaw")]
+    Jigsaw(JigsawStructure),
+    /// A structure that doesn't generate yet.
+    // TODO: Port the remaining structure types (mineshafts, strongholds, temples, ...)
+    #[serde(other)]
+    Unsupported,
+}
+
+/// A structure from the data pack.
+///
+/// Vanilla: `Structure`.
+#[derive(Debug)]
+pub struct Structure {
+    /// The structure's id.
+    pub id: Identifier,
+    /// The biomes the structure starts in.
+    pub biomes: BiomeSet,
+    /// The decoration step the structure is placed in, see [`DECORATION_STEPS`].
+    pub step: usize,
+    /// How the structure shapes the terrain around it.
+    pub terrain_adaptation: TerrainAdjustment,
+    /// The kind of the structure.
+    pub kind: StructureKind,
+}
+
+#[derive(Deserialize)]
+struct StructureJson {
+    biomes: BiomeSet,
+    step: String,
+    #[serde(default)]
+    terrain_adaptation: TerrainAdjustment,
+    #[serde(flatten)]
+    kind: StructureKind,
+}
+
+impl Structure {
+    /// Gets the structure with the given id from the data pack.
+    #[must_use]
+    pub fn by_id(id: &Identifier) -> Option<&'static Self> {
+        STRUCTURES.iter().find(|structure| structure.id == *id)
+    }
+
+    /// Gets every structure of the data pack, in registry order.
+    #[must_use]
+    pub fn all() -> &'static [Self] {
+        &STRUCTURES
+    }
+
+    /// Tries to start the structure in the context's chunk. Returns `None` if it
+    /// doesn't fit there.
+    ///
+    /// Vanilla: `Structure.generate()`.
+    // TODO: Add regression hashes of plains village pieces against vanilla output
+    #[must_use]
+    pub fn generate(
+        &self,
+        context: &mut GenerationContext<'_>,
+        biome_source: &BiomeSourceKind,
+    ) -> Option<StructureStart> {
+        let pieces = match &self.kind {
+            StructureKind::Jigsaw(jigsaw) => jigsaw.generate(context, |pos| {
+                // Vanilla: `Structure.isValidBiome()`
+                let biome =
+                    biome_source
+                        .chunk_sampler()
+                        .sample(pos.x() >> 2, pos.y() >> 2, pos.z() >> 2);
+                self.biomes.contains(biome)
+            }),
+            StructureKind::Unsupported => return None,
+        };
+        if pieces.is_empty() {
+            return None;
+        }
+        Some(StructureStart {
+            structure: self.id.clone(),
+            chunk_pos: context.chunk_pos,
+            references: 0,
+            pieces: pieces.iter().map(JigsawPiece::to_piece).collect(),
+        })
+    }
+}
+
+/// The structures of the vanilla data pack, sorted by id.
+static STRUCTURES: LazyLock<Vec<Structure>> = LazyLock::new(|| {
+    VANILLA_STRUCTURES
+        .iter()
+        .filter_map(|(path, json)| {
+            let structure: StructureJson = serde_json::from_str(json)
+                .inspect_err(|err| log::error!("Couldn't load structure {path}: {err}"))
+                .ok()?;
+            let Some(step) = DECORATION_STEPS
+                .iter()
+                .position(|step| *step == structure.step)
+            else {
+                log::error!("Structure {path} has unknown step {}", structure.step);
+                return None;
+            };
+            Some(Structure {
+                id: Identifier::vanilla((*path).to_owned()),
+                biomes: structure.biomes,
+                step,
+                terrain_adaptation: structure.terrain_adaptation,
+                kind: structure.kind,
+            })
+        })
+        .collect()
+});
+
+/// How a random spread offsets structures inside their region.
+///
+/// Vanilla: `RandomSpreadType`.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpreadType {
+    /// Any offset is as likely.
+    #[default]
+    Linear,
+    /// Offsets near the middle are more likely.
+    Triangular,
+}
+
+impl SpreadType {
+    /// Vanilla: `RandomSpreadType.evaluate()`.
+    fn evaluate(self, random: &mut impl Random, limit: i32) -> i32 {
+        match self {
+            Self::Linear => random.next_i32_bounded(limit),
+            Self::Triangular => {
+                (random.next_i32_bounded(limit) + random.next_i32_bounded(limit)) / 2
+            }
+        }
+    }
+}
+
+/// How a placement thins out the chunks its structures start in.
+///
+/// Vanilla: `StructurePlacement.FrequencyReductionMethod`.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrequencyReductionMethod {
+    /// Vanilla: `StructurePlacement.probabilityReducer()`.
+    #[default]
+    Default,
+    /// Vanilla: `StructurePlacement.legacyPillagerOutpostReducer()`.
+    LegacyType1,
+    /// Vanilla: `StructurePlacement.legacyArbitrarySaltProbabilityReducer()`.
+    LegacyType2,
+    /// Vanilla: `StructurePlacement.legacyProbabilityReducerWithDouble()`.
+    LegacyType3,
+}
+
+impl FrequencyReductionMethod {
+    /// Checks if a structure may start in chunk `x`, `z`.
+    fn should_generate(self, seed: i64, salt: i32, x: i32, z: i32, probability: f32) -> bool {
+        let mut random = WorldgenRandom::new(RandomSource::Legacy(LegacyRandom::from_seed(0)));
+        match self {
+            Self::Default => {
+                // Vanilla passes the salt and coordinates in this order
+                random.set_large_feature_with_salt(seed, salt, x, z);
+                random.next_f32() < probability
+            }
+            Self::LegacyType1 => {
+                let region_x = x >> 4;
+                let region_z = z >> 4;
+                random.set_seed(i64::from(region_x ^ (region_z << 4)) ^ seed);
+                random.next_i32();
+                random.next_i32_bounded((1.0 / probability) as i32) == 0
+            }
+            Self::LegacyType2 => {
+                random.set_large_feature_with_salt(seed, x, z, 10_387_320);
+                random.next_f32() < probability
+            }
+            Self::LegacyType3 => {
+                random.set_large_feature_seed(seed, x, z);
+                random.next_f64() < f64::from(probability)
+            }
+        }
+    }
+}
+
+/// Keeps a placement's structures away from another set's structures.
+///
+/// Vanilla: `StructurePlacement.ExclusionZone`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExclusionZone {
+    /// The set to stay away from.
+    pub other_set: Identifier,
+    /// How many chunks to stay away.
+    pub chunk_count: i32,
+}
+
+/// Spreads structures over a grid of regions, one per region.
+///
+/// Vanilla: `RandomSpreadStructurePlacement`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RandomSpreadPlacement {
+    /// The side of a region, in chunks.
+    pub spacing: i32,
+    /// The least distance between structures of neighbouring regions, in chunks.
+    pub separation: i32,
+    /// How structures are offset inside their region.
+    #[serde(default)]
+    pub spread_type: SpreadType,
+    /// Makes this placement's regions differ from other placements'.
+    pub salt: i32,
+    /// The chance a structure starts in its chunk.
+    #[serde(default = "default_frequency")]
+    pub frequency: f32,
+    /// How the frequency thins out chunks.
+    #[serde(default)]
+    pub frequency_reduction_method: FrequencyReductionMethod,
+    /// Another set this placement's structures stay away from.
+    #[serde(default)]
+    pub exclusion_zone: Option<ExclusionZone>,
+}
+
+const fn default_frequency() -> f32 {
+    1.0
+}
+
+impl RandomSpreadPlacement {
+    /// Gets the chunk the structure of the region holding chunk `x`, `z` starts in.
+    ///
+    /// Vanilla: `RandomSpreadStructurePlacement.getPotentialStructureChunk()`.
+    #[must_use]
+    pub fn potential_structure_chunk(&self, seed: i64, x: i32, z: i32) -> ChunkPos {
+        let region_x = x.div_euclid(self.spacing);
+        let region_z = z.div_euclid(self.spacing);
+        let mut random = WorldgenRandom::new(RandomSource::Legacy(LegacyRandom::from_seed(0)));
+        random.set_large_feature_with_salt(seed, region_x, region_z, self.salt);
+        let limit = self.spacing - self.separation;
+        let offset_x = self.spread_type.evaluate(&mut random, limit);
+        let offset_z = self.spread_type.evaluate(&mut random, limit);
+        ChunkPos::new(
+            region_x * self.spacing + offset_x,
+            region_z * self.spacing + offset_z,
+        )
+    }
+}
+
+/// Decides which chunks a structure set's structures start in.
+///
+/// Vanilla: `StructurePlacement`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum StructurePlacement {
+    /// Vanilla: `RandomSpreadStructurePlacement`.
+    #[serde(rename = "minecraft:random_spread")]
+    RandomSpread(RandomSpreadPlacement),
+    /// A placement that doesn't place anything yet.
+    // TODO: Port concentric rings placement for strongholds
+    #[serde(other)]
+    Unsupported,
+}
+
+impl StructurePlacement {
+    /// Checks if a structure of the set starts in chunk `x`, `z`.
+    ///
+    /// Vanilla: `StructurePlacement.isStructureChunk()`.
+    #[must_use]
+    pub fn is_structure_chunk(&self, seed: i64, x: i32, z: i32) -> bool {
+        let Self::RandomSpread(placement) = self else {
+            return false;
+        };
+        if placement.potential_structure_chunk(seed, x, z) != ChunkPos::new(x, z) {
+            return false;
+        }
+        if placement.frequency < 1.0
+            && !placement.frequency_reduction_method.should_generate(
+                seed,
+                placement.salt,
+                x,
+                z,
+                placement.frequency,
+            )
+        {
+            return false;
+        }
+        // Vanilla: `StructurePlacement.ExclusionZone.isPlacementForbidden()`
+        let Some(zone) = &placement.exclusion_zone else {
+            return true;
+        };
+        let Some(other) = StructureSet::by_id(&zone.other_set) else {
+            return true;
+        };
+        let range = zone.chunk_count;
+        !((x - range)..=(x + range)).any(|other_x| {
+            ((z - range)..=(z + range))
+                .any(|other_z| other.placement.is_structure_chunk(seed, other_x, other_z))
+        })
+    }
+}
+
+/// A structure of a set, with how likely it is picked.
+///
+/// Vanilla: `StructureSet.StructureSelectionEntry`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StructureSelectionEntry {
+    /// The structure's id.
+    pub structure: Identifier,
+    /// How likely the structure is picked, relative to the others.
+    pub weight: i32,
+}
+
+/// Structures that share where they start.
+///
+/// Vanilla: `StructureSet`.
+#[derive(Debug)]
+pub struct StructureSet {
+    /// The set's id.
+    pub id: Identifier,
+    /// The structures of the set.
+    pub structures: Vec<StructureSelectionEntry>,
+    /// Where the structures start.
+    pub placement: StructurePlacement,
+}
+
+#[derive(Deserialize)]
+struct StructureSetJson {
+    structures: Vec<StructureSelectionEntry>,
+    placement: StructurePlacement,
+}
+
+impl StructureSet {
+    /// Gets the structure set with the given id from the data pack.
+    #[must_use]
+    pub fn by_id(id: &Identifier) -> Option<&'static Self> {
+        STRUCTURE_SETS.iter().find(|set| set.id == *id)
+    }
+
+    /// Gets the sets with a structure that can start in a biome of `biome_source`.
+    ///
+    /// Vanilla: `ChunkGeneratorStructureState.possibleStructureSets()`.
+    #[must_use]
+    pub fn possible(biome_source: &BiomeSourceKind) -> Vec<&'static Self> {
+        STRUCTURE_SETS
+            .iter()
+            .filter(|set| {
+                set.structures.iter().any(|entry| {
+                    Structure::by_id(&entry.structure)
+                        .is_some_and(|structure| structure.biomes.any_possible(biome_source))
+                })
+            })
+            .collect()
+    }
+}
+
+/// The structure sets of the vanilla data pack, sorted by id.
+static STRUCTURE_SETS: LazyLock<Vec<StructureSet>> = LazyLock::new(|| {
+    VANILLA_STRUCTURE_SETS
+        .iter()
+        .filter_map(|(path, json)| {
+            let set: StructureSetJson = serde_json::from_str(json)
+                .inspect_err(|err| log::error!("Couldn't load structure set {path}: {err}"))
+                .ok()?;
+            Some(StructureSet {
+                id: Identifier::vanilla((*path).to_owned()),
+                structures: set.structures,
+                placement: set.placement,
+            })
+        })
+        .collect()
+});
@@ -0,0 +1,125 @@
+//! Loading and caching structure templates by id.
+
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use rustc_hash::FxHashMap;
+use steel_registry::vanilla_structure_templates::vanilla_structure_template;
+use steel_utils::Identifier;
+use steel_utils::locks::SyncMutex;
+
+use super::structure_template::StructureTemplate;
+
+/// Finds structure templates, first in the world's `generated` directory and
+/// then in the vanilla data pack.
+///
+/// Vanilla: `StructureTemplateManager`.
+pub struct StructureTemplateManager {
+    /// Where saved templates live, or `None` for worlds kept in memory.
+    generated_dir: Option<PathBuf>,
+    /// Loaded templates. Ids that weren't found are cached as `None`.
+    templates: SyncMutex<FxHashMap<Identifier, Option<Arc<StructureTemplate>>>>,
+}
+
+impl StructureTemplateManager {
+    /// Creates a manager that also looks for templates in `generated_dir`.
+    #[must_use]
+    pub fn new(generated_dir: Option<PathBuf>) -> Self {
+        Self {
+            generated_dir,
+            templates: SyncMutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Gets the template with the given id, loading it on first use.
+    ///
+    /// Vanilla: `StructureTemplateManager.get()`.
+    #[must_use]
+    pub fn get(&self, id: &Identifier) -> Option<Arc<StructureTemplate>> {
+        if let Some(template) = self.templates.lock().get(id) {
+            return template.clone();
+        }
+        let template = self.load(id).map(Arc::new);
+        self.templates.lock().insert(id.clone(), template.clone());
+        template
+    }
+
+    /// Forgets a loaded template, so the next [`Self::get`] reads it again.
+    pub fn remove(&self, id: &Identifier) {
+        self.templates.lock().remove(id);
+    }
+
+    /// Gets the path a template is saved to, like
+    /// `generated/minecraft/structures/igloo/top.nbt`.
+    ///
+    /// Vanilla: `StructureTemplateManager.createAndValidatePathToGeneratedStructure()`.
+    #[must_use]
+    pub fn generated_path(&self, id: &Identifier) -> Option<PathBuf> {
+        // Keep ids like `../level` from escaping the generated directory
+        if id
+            .path
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+        {
+            return None;
+        }
+        let dir = self.generated_dir.as_ref()?;
+        Some(
+            dir.join(&*id.namespace)
+                .join("structures")
+                .join(format!("{}.nbt", id.path)),
+        )
+    }
+
+    fn load(&self, id: &Identifier) -> Option<StructureTemplate> {
+        if let Some(path) = self.generated_path(id) {
+            match fs::read(&path) {
+                Ok(bytes) => return parse(id, &bytes),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    log::error!("Couldn't read structure template {id}: {err}");
+                    return None;
+                }
+            }
+        }
+        if id.namespace != Identifier::VANILLA_NAMESPACE {
+            return None;
+        }
+        parse(id, vanilla_structure_template(&id.path)?)
+    }
+}
+
+fn parse(id: &Identifier, bytes: &[u8]) -> Option<StructureTemplate> {
+    StructureTemplate::from_gzipped(bytes)
+        .inspect_err(|err| log::error!("Couldn't load structure template {id}: {err}"))
+        .ok()
+}
+
+/// Wrapper for the global template manager that implements `Deref`.
+pub struct StructureTemplateManagerLock(OnceLock<StructureTemplateManager>);
+
+impl Deref for StructureTemplateManagerLock {
+    type Target = StructureTemplateManager;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+            .get()
+            .expect("Structure template manager not initialized")
+    }
+}
+
+impl StructureTemplateManagerLock {
+    /// Sets the manager. Returns `Err` if already initialized.
+    pub fn set(&self, manager: StructureTemplateManager) -> Result<(), StructureTemplateManager> {
+        self.0.set(manager)
+    }
+}
+
+/// Global structure template manager, set up when the server starts.
+///
+/// Access via deref: `STRUCTURE_TEMPLATES.get(&id)`
+pub static STRUCTURE_TEMPLATES: StructureTemplateManagerLock =
+    StructureTemplateManagerLock(OnceLock::new());
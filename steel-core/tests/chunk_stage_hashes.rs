@@ -5,7 +5,11 @@
 //! reference data is available, shows exact block-level diffs.
 //!
 //! Tests all dimensions (overworld, nether, end) using the new JSON format
-//! with a `dimensions` wrapper.
+//! with a `dimensions` wrapper. Every `chunk_stage_hashes*.json` fixture in
+//! `test_assets` is checked, so more seeds are covered by adding a fixture
+//! extracted for that seed. New fixtures hash block state names (see
+//! [`BlockHash`]), so they survive changes of the state IDs, and
+//! `migrate_fixtures_to_state_names` moves older fixtures over.
 
 use std::fmt::Write;
 use std::fs;
@@ -31,16 +35,41 @@ struct DimensionData {
 #[derive(Deserialize, Debug)]
 struct ChunkStageHashesJson {
     seed: u64,
+    #[serde(default)]
+    hash: BlockHash,
     dimensions: FxHashMap<String, DimensionData>,
 }
 
-/// Stages to verify. Uncomment as each stage is implemented.
-const STAGES: &[&str] = &[
-    "minecraft:noise",
-    "minecraft:surface",
-    // "minecraft:carvers",
-    // "minecraft:features",
-];
+/// What the chunk hashes of a fixture are computed from.
+///
+/// All-air sections are hashed as a single `0` byte either way.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum BlockHash {
+    /// Every block's state ID as a big-endian `u32`, in YZX order. Used by fixtures
+    /// extracted before names were hashed, which
+    /// [`migrate_fixtures_to_state_names`] rewrites.
+    #[default]
+    StateIds,
+    /// Every block's state as `name[key=value,...]` (the path of the block key,
+    /// properties in vanilla's order, no brackets without properties), in YZX order.
+    StateNames,
+}
+
+/// Stages to verify.
+///
+/// Each stage builds on the previous one, and a fixture without hashes for a
+/// stage skips it.
+// TODO: Add the carvers and features stages once they generate blocks
+const STAGES: &[&str] = &["minecraft:noise", "minecraft:surface"];
+
+/// Chunks per dimension checked by the quick test that runs with every
+/// `cargo test`, spread evenly over the fixture. The full fixture runs in the
+/// ignored test.
+const QUICK_CHUNKS: usize = 8;
+
+/// State name hashes of checked chunks, by dimension, chunk and stage.
+type NameHashes = FxHashMap<(String, i32, i32, String), String>;
 
 /// Max block-level diffs to show per chunk before truncating.
 const MAX_DIFFS_PER_CHUNK: usize = 30;
@@ -50,13 +79,48 @@ const MAX_DIFFS_PER_CHUNK: usize = 30;
 /// Example: &[(24, 35)] to debug a single failing chunk.
 const DEBUG_CHUNKS: &[(i32, i32)] = &[];
 
-fn load_expected_hashes() -> ChunkStageHashesJson {
-    let json_str = include_str!("../test_assets/chunk_stage_hashes.json");
-    serde_json::from_str(json_str).expect("Failed to parse chunk_stage_hashes.json")
+/// A fixture file and the prefix of its binary reference files.
+///
+/// `chunk_stage_hashes.json` pairs with `chunk_stage_<dim>_<stage>_blocks.bin.gz`,
+/// `chunk_stage_hashes_<name>.json` with `chunk_stage_<name>_<dim>_<stage>_blocks.bin.gz`.
+struct Fixture {
+    name: String,
+    reference_prefix: String,
+    hashes: ChunkStageHashesJson,
+}
+
+fn load_fixtures() -> Vec<Fixture> {
+    let dir = format!("{}/test_assets", env!("CARGO_MANIFEST_DIR"));
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .expect("Failed to read test_assets")
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("chunk_stage_hashes") && name.ends_with(".json"))
+        .collect();
+    names.sort_unstable();
+    assert!(!names.is_empty(), "No chunk_stage_hashes*.json fixtures");
+
+    names
+        .into_iter()
+        .map(|name| {
+            let json_str = fs::read_to_string(format!("{dir}/{name}"))
+                .unwrap_or_else(|e| panic!("Failed to read {name}: {e}"));
+            let hashes = serde_json::from_str(&json_str)
+                .unwrap_or_else(|e| panic!("Failed to parse {name}: {e}"));
+            let reference_prefix =
+                name.trim_end_matches(".json")
+                    .replacen("chunk_stage_hashes", "chunk_stage", 1);
+            Fixture {
+                name,
+                reference_prefix,
+                hashes,
+            }
+        })
+        .collect()
 }
 
-fn compute_block_hash(sections: &Sections) -> String {
+fn compute_block_hash(sections: &Sections, hash: BlockHash) -> String {
     let mut ctx = md5::Context::new();
+    let mut names: FxHashMap<u16, String> = FxHashMap::default();
 
     for section_holder in &sections.sections {
         let section = section_holder.read();
@@ -67,11 +131,19 @@ fn compute_block_hash(sections: &Sections) -> String {
                 for z in 0..16 {
                     for x in 0..16 {
                         let state = section.states.get(x, y, z);
-                        let state_id = u32::from(state.0);
-                        ctx.consume([(state_id >> 24) as u8]);
-                        ctx.consume([(state_id >> 16) as u8]);
-                        ctx.consume([(state_id >> 8) as u8]);
-                        ctx.consume([state_id as u8]);
+                        match hash {
+                            BlockHash::StateIds => {
+                                ctx.consume(u32::from(state.0).to_be_bytes());
+                            }
+                            BlockHash::StateNames => {
+                                let name = names.entry(state.0).or_insert_with(|| {
+                                    state_name(state).unwrap_or_else(|| {
+                                        panic!("Unknown block state {}", state.0)
+                                    })
+                                });
+                                ctx.consume(name.as_bytes());
+                            }
+                        }
                     }
                 }
             }
@@ -81,6 +153,20 @@ fn compute_block_hash(sections: &Sections) -> String {
     format!("{:x}", ctx.finalize())
 }
 
+/// Formats a block state as `name[key=value,...]`, `None` if it isn't registered.
+fn state_name(state: steel_utils::types::BlockStateId) -> Option<String> {
+    use steel_registry::REGISTRY;
+
+    let block = REGISTRY.blocks.by_state_id(state)?;
+    let props = REGISTRY.blocks.get_properties(state);
+    if props.is_empty() {
+        Some(block.key.path.to_string())
+    } else {
+        let prop_str: Vec<_> = props.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        Some(format!("{}[{}]", block.key.path, prop_str.join(",")))
+    }
+}
+
 /// Per-chunk reference block data from the extractor binary.
 struct ChunkBlockData {
     /// Sections, each None (all air) or Some(4096 state IDs in YZX order).
@@ -99,12 +185,13 @@ struct ChunkBlockData {
 ///       `has_data`: u8
 ///       if `has_data` == 1: `state_ids`: [i32; 4096]
 fn load_reference_blocks(
+    reference_prefix: &str,
     stage: &str,
     dim_short: &str,
 ) -> Option<FxHashMap<(i32, i32), ChunkBlockData>> {
     let short_name = stage.strip_prefix("minecraft:").unwrap_or(stage);
     let path = format!(
-        "{}/test_assets/chunk_stage_{dim_short}_{short_name}_blocks.bin.gz",
+        "{}/test_assets/{reference_prefix}_{dim_short}_{short_name}_blocks.bin.gz",
         env!("CARGO_MANIFEST_DIR"),
     );
     let compressed = fs::read(&path).ok()?;
@@ -156,19 +243,11 @@ fn load_reference_blocks(
 
 /// Format a state ID as "id (`block_name`[props])" for human-readable output.
 fn describe_state(state_id: i32) -> String {
-    use steel_registry::REGISTRY;
     use steel_utils::types::BlockStateId;
 
-    let bsid = BlockStateId(state_id as u16);
-    let Some(block) = REGISTRY.blocks.by_state_id(bsid) else {
-        return format!("{state_id} (unknown)");
-    };
-    let props = REGISTRY.blocks.get_properties(bsid);
-    if props.is_empty() {
-        format!("{state_id} ({})", block.key)
-    } else {
-        let prop_str: Vec<_> = props.iter().map(|(k, v)| format!("{k}={v}")).collect();
-        format!("{state_id} ({}[{}])", block.key, prop_str.join(","))
+    match state_name(BlockStateId(state_id as u16)) {
+        Some(name) => format!("{state_id} ({name})"),
+        None => format!("{state_id} (unknown)"),
     }
 }
 
@@ -310,6 +389,76 @@ fn format_chunk_diffs(diffs: &[BlockDiff], chunk_x: i32, chunk_z: i32, min_y: i3
 #[test]
 #[ignore = "This test takes too long to run for normal testing"]
 fn chunk_stage_hashes() {
+    run_on_large_stack(|| {
+        for fixture in load_fixtures() {
+            chunk_stage_hashes_inner(&fixture, None, None);
+        }
+    });
+}
+
+/// Checks chunks spread over every fixture, so terrain regressions fail a plain
+/// `cargo test` like biome regressions do.
+#[test]
+fn chunk_stage_hashes_quick() {
+    run_on_large_stack(|| {
+        for fixture in load_fixtures() {
+            chunk_stage_hashes_inner(&fixture, Some(QUICK_CHUNKS), None);
+        }
+    });
+}
+
+/// Rewrites the fixtures that hash state IDs to hash state names. Every chunk is
+/// checked against the state ID hashes first, so the name hashes are of the same
+/// blocks vanilla generated.
+#[test]
+#[ignore = "Rewrites the fixtures in test_assets, run by hand"]
+fn migrate_fixtures_to_state_names() {
+    run_on_large_stack(|| {
+        for fixture in load_fixtures() {
+            if fixture.hashes.hash != BlockHash::StateIds {
+                continue;
+            }
+            let mut name_hashes = NameHashes::default();
+            chunk_stage_hashes_inner(&fixture, None, Some(&mut name_hashes));
+
+            let path = format!(
+                "{}/test_assets/{}",
+                env!("CARGO_MANIFEST_DIR"),
+                fixture.name
+            );
+            let json_str = fs::read_to_string(&path).expect("Failed to read the fixture");
+            let mut json: serde_json::Value =
+                serde_json::from_str(&json_str).expect("Failed to parse the fixture");
+            json["hash"] = "state_names".into();
+            let dimensions = json["dimensions"]
+                .as_object_mut()
+                .expect("Fixture has dimensions");
+            for (dim_key, dim_data) in dimensions {
+                let chunks = dim_data["chunks"]
+                    .as_array_mut()
+                    .expect("Dimension has chunks");
+                for chunk in chunks {
+                    let x = chunk["x"].as_i64().expect("Chunk has an x") as i32;
+                    let z = chunk["z"].as_i64().expect("Chunk has a z") as i32;
+                    let stages = chunk["stages"].as_object_mut().expect("Chunk has stages");
+                    for (stage, hash) in stages {
+                        let key = (dim_key.clone(), x, z, stage.clone());
+                        let name_hash = name_hashes
+                            .get(&key)
+                            .unwrap_or_else(|| panic!("No hash for {dim_key} ({x},{z}) {stage}"));
+                        *hash = name_hash.clone().into();
+                    }
+                }
+            }
+
+            let json_str = serde_json::to_string_pretty(&json).expect("Fixture serializes");
+            fs::write(&path, json_str + "\n").expect("Failed to write the fixture");
+            eprintln!("Migrated {} to state name hashes", fixture.name);
+        }
+    });
+}
+
+fn run_on_large_stack(f: impl FnOnce() + Send + 'static) {
     use std::panic;
     use std::thread;
 
@@ -317,7 +466,7 @@ fn chunk_stage_hashes() {
     // since pre-generating biome data for neighbor lookups increases stack usage.
     let result = thread::Builder::new()
         .stack_size(16 * 1024 * 1024)
-        .spawn(chunk_stage_hashes_inner)
+        .spawn(f)
         .expect("Failed to spawn test thread")
         .join();
 
@@ -338,7 +487,11 @@ const DIMENSION_ORDER: &[&str] = &[
     clippy::similar_names,
     reason = "large test with many hash assertions"
 )]
-fn chunk_stage_hashes_inner() {
+fn chunk_stage_hashes_inner(
+    fixture: &Fixture,
+    chunk_limit: Option<usize>,
+    mut name_hashes: Option<&mut NameHashes>,
+) {
     use steel_core::chunk::chunk_access::ChunkAccess;
     use steel_core::chunk::chunk_generator::ChunkGenerator;
     use steel_core::chunk::proto_chunk::ProtoChunk;
@@ -354,9 +507,8 @@ fn chunk_stage_hashes_inner() {
     registry.freeze();
     let _ = REGISTRY.init(registry);
 
-    let expected = load_expected_hashes();
+    let expected = &fixture.hashes;
     let seed = expected.seed;
-    assert_eq!(seed, 13579, "Expected seed 13579");
 
     for &dim_key in DIMENSION_ORDER {
         let Some(dim_data) = expected.dimensions.get(dim_key) else {
@@ -393,11 +545,12 @@ fn chunk_stage_hashes_inner() {
             _ => unreachable!(),
         };
 
-        eprintln!("=== {dim_key} ===");
+        eprintln!("=== {} (seed {seed}): {dim_key} ===", fixture.name);
 
         // Filter entries by DEBUG_CHUNKS if set
         let test_entries: Vec<&ChunkStageEntry> = if DEBUG_CHUNKS.is_empty() {
-            dim_data.chunks.iter().collect()
+            let step = chunk_limit.map_or(1, |limit| dim_data.chunks.len().div_ceil(limit));
+            dim_data.chunks.iter().step_by(step.max(1)).collect()
         } else {
             dim_data
                 .chunks
@@ -414,7 +567,8 @@ fn chunk_stage_hashes_inner() {
         let mut neighbors_built = false;
 
         for &stage in STAGES {
-            let reference_blocks = load_reference_blocks(stage, dim_short);
+            let reference_blocks =
+                load_reference_blocks(&fixture.reference_prefix, stage, dim_short);
             let has_reference = reference_blocks.is_some();
             let needs_neighbors = stage != "minecraft:noise";
 
@@ -439,7 +593,7 @@ fn chunk_stage_hashes_inner() {
                                 height,
                             );
                             let chunk = ChunkAccess::Proto(proto);
-                            generator.create_biomes(&chunk);
+                            generator.create_biomes(&chunk, None);
                             biome_neighbors.insert(pos, chunk);
                         }
                     }
@@ -472,8 +626,8 @@ fn chunk_stage_hashes_inner() {
                         height,
                     );
                     let chunk = ChunkAccess::Proto(proto);
-                    generator.create_biomes(&chunk);
-                    generator.fill_from_noise(&chunk);
+                    generator.create_biomes(&chunk, None);
+                    generator.fill_from_noise(&chunk, &[], None);
                     chunk
                 });
 
@@ -503,14 +657,22 @@ fn chunk_stage_hashes_inner() {
                     };
 
                     match stage {
-                        "minecraft:surface" => generator.build_surface(chunk, &neighbor_biomes),
+                        "minecraft:surface" => {
+                            generator.build_surface(chunk, &neighbor_biomes, None);
+                        }
                         _ => panic!("Stage {stage} not yet implemented in test harness"),
                     }
                 }
 
-                let actual_hash = compute_block_hash(chunk.sections());
+                let actual_hash = compute_block_hash(chunk.sections(), expected.hash);
 
                 let ok = actual_hash == expected_hash;
+                if ok && let Some(name_hashes) = name_hashes.as_deref_mut() {
+                    name_hashes.insert(
+                        (dim_key.to_owned(), chunk_x, chunk_z, stage.to_owned()),
+                        compute_block_hash(chunk.sections(), BlockHash::StateNames),
+                    );
+                }
                 if (i + 1) % 10 == 0 || i + 1 == total || !ok {
                     let status = if ok { "OK" } else { "MISMATCH" };
                     eprintln!(
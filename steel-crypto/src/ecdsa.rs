@@ -0,0 +1,103 @@
+//! ECDSA (P-256) signature verification for chat session keys.
+//!
+//! Vanilla only issues RSA profile keys, but proxies and alternative
+//! authentication services may hand out EC keys instead. Keys use the same
+//! X.509 encoding as RSA keys, and signatures are DER-encoded like Java's
+//! `SHA256withECDSA` produces them.
+
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey;
+
+use crate::rsa_utils::CryptError;
+use crate::signature::{SignatureUpdater, SignatureValidator, collect_bytes};
+
+/// Signing algorithm used for ECDSA chat session keys.
+pub const ECDSA_SIGNING_ALGORITHM: &str = "SHA256withECDSA";
+
+/// Parses a P-256 public key from DER-encoded X.509 format bytes.
+pub fn ecdsa_public_key_from_bytes(bytes: &[u8]) -> Result<VerifyingKey, CryptError> {
+    VerifyingKey::from_public_key_der(bytes).map_err(|_| CryptError::InvalidKeyFormat)
+}
+
+/// Creates a signature validator from a P-256 public key using SHA256withECDSA.
+pub struct EcdsaPublicKeyValidator {
+    verifying_key: VerifyingKey,
+}
+
+impl EcdsaPublicKeyValidator {
+    pub fn new(verifying_key: VerifyingKey) -> Self {
+        Self { verifying_key }
+    }
+
+    /// Creates a validator from DER-encoded X.509 public key bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptError> {
+        ecdsa_public_key_from_bytes(bytes).map(Self::new)
+    }
+}
+
+impl SignatureValidator for EcdsaPublicKeyValidator {
+    fn validate(
+        &self,
+        updater: &dyn SignatureUpdater,
+        signature_bytes: &[u8],
+    ) -> Result<bool, CryptError> {
+        let data = collect_bytes(updater)?;
+
+        let Ok(signature) = Signature::from_der(signature_bytes) else {
+            return Ok(false);
+        };
+
+        Ok(self.verifying_key.verify(&data, &signature).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p256::ecdsa::SigningKey;
+    use p256::ecdsa::signature::Signer;
+    use p256::pkcs8::EncodePublicKey;
+
+    use super::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[7u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_verify_der_signature() {
+        let signing_key = signing_key();
+        let key_bytes = signing_key
+            .verifying_key()
+            .to_public_key_der()
+            .unwrap()
+            .to_vec();
+        let validator = EcdsaPublicKeyValidator::from_bytes(&key_bytes).unwrap();
+
+        let data: &[u8] = b"Hello, signed chat!";
+        let signature: Signature = signing_key.sign(data);
+        let der = signature.to_der();
+
+        assert!(validator.validate(&data, der.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn test_reject_invalid_signature() {
+        let validator = EcdsaPublicKeyValidator::new(*signing_key().verifying_key());
+
+        let data: &[u8] = b"Hello, signed chat!";
+        let other: Signature = SigningKey::from_slice(&[9u8; 32]).unwrap().sign(data);
+
+        assert!(
+            !validator
+                .validate(&data, other.to_der().as_bytes())
+                .unwrap()
+        );
+        assert!(!validator.validate(&data, &[0u8; 16]).unwrap());
+    }
+
+    #[test]
+    fn test_reject_invalid_key() {
+        assert!(ecdsa_public_key_from_bytes(&[1, 2, 3]).is_err());
+    }
+}
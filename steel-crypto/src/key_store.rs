@@ -1,6 +1,13 @@
 //! This module contains the `KeyStore` struct, which is used to store the server's encryption keys.
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::time::Duration;
+
 use rsa::{RsaPrivateKey, traits::PublicKeyParts};
 
+use crate::rsa_utils::{CryptError, private_key_from_pem, private_key_to_pem};
+
 /// A struct that stores the server's encryption keys.
 pub struct KeyStore {
     /// The server's private key.
@@ -14,8 +21,71 @@ impl KeyStore {
     #[must_use]
     pub fn create() -> Self {
         log::debug!("Creating encryption keys...");
-        let private_key = Self::generate_private_key();
+        Self::from_private_key(Self::generate_private_key())
+    }
+
+    /// Loads the key pair cached at `path`, or creates and caches a new one.
+    ///
+    /// A cached key pair is only reused while it is younger than `max_age`, so a
+    /// quick restart keeps the same keys while long-lived keys still rotate.
+    #[must_use]
+    pub fn load_or_create(path: &Path, max_age: Duration) -> Self {
+        match Self::load_cached(path, max_age) {
+            Ok(Some(key_store)) => {
+                log::debug!("Loaded cached encryption keys from {}", path.display());
+                return key_store;
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!(
+                "Failed to load cached encryption keys from {}: {err}",
+                path.display()
+            ),
+        }
+
+        let key_store = Self::create();
+        if let Err(err) = key_store.save(path) {
+            log::warn!(
+                "Failed to cache encryption keys at {}: {err}",
+                path.display()
+            );
+        }
+        key_store
+    }
+
+    /// Reads the key pair at `path`, returning `None` if there is none or it is too old.
+    fn load_cached(path: &Path, max_age: Duration) -> Result<Option<Self>, CryptError> {
+        let modified = match fs::metadata(path) {
+            Ok(metadata) => metadata.modified()?,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        if modified.elapsed().unwrap_or_default() >= max_age {
+            return Ok(None);
+        }
 
+        let pem = fs::read_to_string(path)?;
+        Ok(Some(Self::from_private_key(private_key_from_pem(&pem)?)))
+    }
+
+    /// Writes the private key to `path` as PKCS8 PEM, readable only by the owner.
+    fn save(&self, path: &Path) -> Result<(), CryptError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let pem = private_key_to_pem(&self.private_key)?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(path)?.write_all(pem.as_bytes())?;
+        Ok(())
+    }
+
+    fn from_private_key(private_key: RsaPrivateKey) -> Self {
         let public_key_der = rsa_der::public_key_to_der(
             &private_key.n().to_be_bytes(),
             &private_key.e().to_be_bytes(),
@@ -35,3 +105,34 @@ impl KeyStore {
         RsaPrivateKey::new(&mut rng, 1024).expect("Failed to generate a key")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn cache_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("steel-key-store-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_cached_keys_are_reused() {
+        let path = cache_path("reused.pem");
+        let first = KeyStore::load_or_create(&path, Duration::from_secs(60));
+        let second = KeyStore::load_or_create(&path, Duration::from_secs(60));
+        assert_eq!(first.public_key_der, second.public_key_der);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_expired_keys_are_replaced() {
+        let path = cache_path("expired.pem");
+        let first = KeyStore::load_or_create(&path, Duration::ZERO);
+        let second = KeyStore::load_or_create(&path, Duration::ZERO);
+        assert_ne!(first.public_key_der, second.public_key_der);
+        fs::remove_file(&path).unwrap();
+    }
+}
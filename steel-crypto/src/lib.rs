@@ -5,13 +5,16 @@
 //! signed chat system, including RSA key pair generation, SHA256withRSA signing,
 //! and signature verification.
 
+pub mod ecdsa;
 pub mod key_store;
 pub mod mojang_api;
 pub mod rsa_utils;
 pub mod signature;
+pub mod verification_pool;
 
 pub use rsa_utils::{CryptError, generate_key_pair, public_key_from_bytes, public_key_to_bytes};
 pub use signature::{SignatureUpdater, SignatureValidator, Signer};
+pub use verification_pool::VerificationPool;
 
 /// Signing algorithm used for chat messages (SHA256withRSA)
 pub const SIGNING_ALGORITHM: &str = "SHA256withRSA";
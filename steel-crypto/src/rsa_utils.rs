@@ -17,6 +17,9 @@ pub enum CryptError {
     #[error("SPKI error: {0}")]
     SpkiError(#[from] rsa::pkcs8::spki::Error),
 
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     #[error("Invalid key format")]
     InvalidKeyFormat,
 
@@ -69,12 +69,8 @@ impl RsaPrivateKeySigner {
 
 impl Signer for RsaPrivateKeySigner {
     fn sign(&self, updater: &dyn SignatureUpdater) -> Result<Vec<u8>, CryptError> {
-        // Collect all bytes to sign
-        let mut collector = ByteCollector::new();
-        updater.update(&mut collector)?;
-
         // Sign the collected data
-        let signature = self.signing_key.sign(&collector.bytes);
+        let signature = self.signing_key.sign(&collect_bytes(updater)?);
         Ok(signature.to_bytes().as_ref().to_vec())
     }
 }
@@ -101,8 +97,7 @@ impl SignatureValidator for RsaPublicKeyValidator {
         signature_bytes: &[u8],
     ) -> Result<bool, CryptError> {
         // Collect all bytes to verify
-        let mut collector = ByteCollector::new();
-        updater.update(&mut collector)?;
+        let data = collect_bytes(updater)?;
 
         // Parse signature
         let signature = match rsa::pkcs1v15::Signature::try_from(signature_bytes) {
@@ -111,10 +106,7 @@ impl SignatureValidator for RsaPublicKeyValidator {
         };
 
         // Verify the signature
-        Ok(self
-            .verifying_key
-            .verify(&collector.bytes, &signature)
-            .is_ok())
+        Ok(self.verifying_key.verify(&data, &signature).is_ok())
     }
 }
 
@@ -168,6 +160,16 @@ impl SignatureValidator for NoValidation {
     }
 }
 
+/// Collects all bytes written by `updater`.
+///
+/// Useful to move signature data into another thread, since updaters usually
+/// borrow the message they sign.
+pub fn collect_bytes(updater: &dyn SignatureUpdater) -> Result<Vec<u8>, CryptError> {
+    let mut collector = ByteCollector::new();
+    updater.update(&mut collector)?;
+    Ok(collector.bytes)
+}
+
 /// Helper struct to collect bytes during signature operations.
 struct ByteCollector {
     bytes: Vec<u8>,
@@ -0,0 +1,96 @@
+//! A bounded pool for running signature verification off the async runtime.
+//!
+//! RSA and ECDSA verification take long enough to stall a network task that
+//! handles many packets, so it runs on tokio's blocking threads instead. The
+//! number of concurrent verifications is capped, so a flood of signed packets
+//! cannot take over the whole blocking pool.
+
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::spawn_blocking;
+
+use crate::rsa_utils::CryptError;
+
+/// Runs verification jobs on the blocking thread pool, at most `max_concurrent` at a time.
+pub struct VerificationPool {
+    permits: Arc<Semaphore>,
+}
+
+impl VerificationPool {
+    /// Creates a pool that runs at most `max_concurrent` jobs at once.
+    ///
+    /// A limit of 0 is raised to 1.
+    #[must_use]
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    /// Runs `job` on a blocking thread once a slot is free and returns its result.
+    pub async fn run<T, F>(&self, job: F) -> Result<T, CryptError>
+    where
+        F: FnOnce() -> Result<T, CryptError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|err| CryptError::OperationFailed(err.to_string()))?;
+
+        spawn_blocking(move || {
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|err| CryptError::OperationFailed(err.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_returns_job_result() {
+        let pool = VerificationPool::new(1);
+        assert_eq!(pool.run(|| Ok(42)).await.unwrap(), 42);
+        assert!(
+            pool.run(|| Err::<(), _>(CryptError::InvalidKeyFormat))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limits_concurrency() {
+        let pool = Arc::new(VerificationPool::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let jobs = (0..8).map(|_| {
+            let pool = Arc::clone(&pool);
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            tokio::spawn(async move {
+                pool.run(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(10));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                })
+                .await
+            })
+        });
+        for job in jobs.collect::<Vec<_>>() {
+            job.await.unwrap().unwrap();
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -2,14 +2,42 @@
 //!
 //! Handles authentication with Mojang's session servers for online mode.
 
-use reqwest::StatusCode;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, StatusCode};
+use steel_core::config::STEEL_CONFIG;
 use steel_core::player::GameProfile;
+pub use steel_core::player::textures::TextureError;
 use thiserror::Error;
+use tokio::time::sleep;
 
-const MOJANG_AUTH_URL: &str =
-    "https://sessionserver.mojang.com/session/minecraft/hasJoined?username=";
+const MOJANG_SESSION_SERVER: &str = "https://sessionserver.mojang.com";
+const HAS_JOINED_PATH: &str = "/session/minecraft/hasJoined?username=";
 const SERVER_ID_ARG: &str = "&serverId=";
 
+/// The delay before the second attempt, doubled for every attempt after it.
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// The HTTP client shared by all requests to Mojang, so connections are reused.
+static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    let config = &STEEL_CONFIG.authentication;
+    Client::builder()
+        .connect_timeout(Duration::from_secs(config.connect_timeout))
+        .timeout(Duration::from_secs(config.request_timeout))
+        .build()
+        .unwrap_or_else(|err| {
+            log::error!("Failed to build the HTTP client, using the defaults: {err}");
+            Client::new()
+        })
+});
+
+/// Returns the HTTP client for requests to Mojang's servers.
+pub(crate) fn http_client() -> &'static Client {
+    &HTTP_CLIENT
+}
+
 /// An error that can occur during Mojang authentication.
 #[derive(Error, Debug)]
 pub enum AuthError {
@@ -36,58 +64,113 @@ pub enum AuthError {
     UnknownStatusCode(StatusCode),
 }
 
-/// An error that can occur with textures.
-#[derive(Error, Debug)]
-pub enum TextureError {
-    /// Invalid URL.
-    #[error("Invalid URL")]
-    InvalidURL,
-    /// Invalid URL scheme for player texture.
-    #[error("Invalid URL scheme for player texture: {0}")]
-    DisallowedUrlScheme(String),
-    /// Invalid URL domain for player texture.
-    #[error("Invalid URL domain for player texture: {0}")]
-    DisallowedUrlDomain(String),
-    /// Failed to decode base64 player texture.
-    #[error("Failed to decode base64 player texture: {0}")]
-    DecodeError(String),
-    /// Failed to parse JSON from player texture.
-    #[error("Failed to parse JSON from player texture: {0}")]
-    JSONError(String),
+/// Fails logins right away while the authentication servers seem down, instead
+/// of making every joining player wait for the timeouts.
+struct CircuitBreaker {
+    epoch: LazyLock<Instant>,
+    failures: AtomicU32,
+    /// Until when logins fail right away, in milliseconds since `epoch`.
+    open_until: AtomicU64,
+}
+
+static BREAKER: CircuitBreaker = CircuitBreaker {
+    epoch: LazyLock::new(Instant::now),
+    failures: AtomicU32::new(0),
+    open_until: AtomicU64::new(0),
+};
+
+impl CircuitBreaker {
+    fn now_millis(&self) -> u64 {
+        u64::try_from(self.epoch.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+
+    fn is_open(&self) -> bool {
+        self.now_millis() < self.open_until.load(Ordering::Relaxed)
+    }
+
+    fn record_success(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+    }
+
+    fn record_failure(&self) {
+        let config = &STEEL_CONFIG.authentication;
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= config.breaker_threshold {
+            log::warn!(
+                "{failures} logins failed in a row, failing logins for the next {}s",
+                config.breaker_cooldown
+            );
+            self.failures.store(0, Ordering::Relaxed);
+            self.open_until.store(
+                self.now_millis() + config.breaker_cooldown * 1000,
+                Ordering::Relaxed,
+            );
+        }
+    }
 }
 
-const MAX_RETRIES: u32 = 3;
+/// The delay before retrying after `attempt` failed attempts: exponential, with
+/// up to half of it added at random so clients don't retry in lockstep.
+fn retry_delay(attempt: u32) -> Duration {
+    let delay = BASE_RETRY_DELAY * 2u32.saturating_pow(attempt.saturating_sub(1));
+    delay + delay.mul_f64(rand::random_range(0.0..0.5))
+}
 
 /// Authenticates a player with Mojang's servers.
+///
+/// Failed requests are retried with backoff. While the servers seem down, this
+/// fails right away with [`AuthError::FailedResponse`].
 pub async fn mojang_authenticate(
     username: &str,
     server_hash: &str,
 ) -> Result<GameProfile, AuthError> {
-    let cap = MOJANG_AUTH_URL.len() + SERVER_ID_ARG.len() + username.len() + server_hash.len();
-    let mut auth_url = String::with_capacity(cap);
-    auth_url += MOJANG_AUTH_URL;
-    auth_url += username;
-    auth_url += SERVER_ID_ARG;
-    auth_url += server_hash;
+    if BREAKER.is_open() {
+        return Err(AuthError::FailedResponse);
+    }
+
+    let config = &STEEL_CONFIG.authentication;
+    let session_server = config
+        .session_server
+        .as_deref()
+        .unwrap_or(MOJANG_SESSION_SERVER)
+        .trim_end_matches('/');
+    let auth_url =
+        format!("{session_server}{HAS_JOINED_PATH}{username}{SERVER_ID_ARG}{server_hash}");
 
     let mut last_error = AuthError::FailedResponse;
 
-    for _ in 0..MAX_RETRIES {
-        let Ok(response) = reqwest::get(&auth_url).await else {
-            last_error = AuthError::FailedResponse;
-            continue;
+    for attempt in 0..config.max_attempts {
+        if attempt > 0 {
+            sleep(retry_delay(attempt)).await;
+        }
+        let response = match http_client().get(&auth_url).send().await {
+            Ok(response) => response,
+            Err(err) => {
+                log::debug!("Session server request failed: {err}");
+                last_error = AuthError::FailedResponse;
+                continue;
+            }
         };
 
         match response.status() {
             StatusCode::OK => {
-                return response.json().await.map_err(|_| AuthError::FailedParse);
+                BREAKER.record_success();
+                let mut profile: GameProfile =
+                    response.json().await.map_err(|_| AuthError::FailedParse)?;
+                profile.validate_textures();
+                return Ok(profile);
+            }
+            // The servers answered, so they aren't down
+            StatusCode::NO_CONTENT => {
+                BREAKER.record_success();
+                return Err(AuthError::UnverifiedUsername);
             }
-            StatusCode::NO_CONTENT => last_error = AuthError::UnverifiedUsername,
             other => last_error = AuthError::UnknownStatusCode(other),
         }
     }
 
     log::warn!("Player {username} auth failed");
+    BREAKER.record_failure();
 
     Err(last_error)
 }
@@ -181,6 +264,15 @@ mod tests {
         assert_eq!(signed_bytes_be_to_hex(&[0x00, 0x00, 0x00]), "0");
     }
 
+    #[test]
+    fn test_retry_delay_backs_off() {
+        for attempt in 1..5 {
+            let base = BASE_RETRY_DELAY * 2u32.pow(attempt - 1);
+            let delay = retry_delay(attempt);
+            assert!(delay >= base && delay <= base.mul_f64(1.5));
+        }
+    }
+
     #[test]
     fn test_empty() {
         assert_eq!(signed_bytes_be_to_hex(&[]), "0");
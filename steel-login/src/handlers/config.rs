@@ -7,6 +7,9 @@ use steel_core::entity::next_entity_id;
 use steel_core::player::PlayerConnection;
 use steel_core::player::networking::JavaConnection;
 use steel_core::player::{ClientInformation, Player};
+use steel_core::server::payload_channels::{
+    BRAND_CHANNEL, SERVER_BRAND, decode_brand, encode_brand,
+};
 use steel_protocol::packets::common::CCustomPayload;
 use steel_protocol::packets::common::{SClientInformation, SCustomPayload};
 use steel_protocol::packets::config::CFinishConfiguration;
@@ -14,17 +17,22 @@ use steel_protocol::packets::config::CSelectKnownPacks;
 use steel_protocol::packets::config::SSelectKnownPacks;
 use steel_protocol::packets::shared_implementation::KnownPack;
 use steel_protocol::utils::ConnectionProtocol;
-use steel_utils::Identifier;
 
 use crate::tcp_client::{ConnectionUpdate, JavaTcpClient};
 
-const BRAND_PAYLOAD: [u8; 5] = *b"Steel";
-
 impl JavaTcpClient {
     /// Handles a custom payload packet during the configuration state.
-    #[expect(clippy::unused_self, reason = "this is an api function")]
-    pub fn handle_config_custom_payload(&self, packet: SCustomPayload) {
-        log::debug!("Custom payload packet: {packet:?}");
+    ///
+    /// Only the brand is kept, it's handed to the player once they join.
+    pub async fn handle_config_custom_payload(&self, packet: SCustomPayload) {
+        if packet.identifier != BRAND_CHANNEL {
+            log::debug!("Custom payload packet: {packet:?}");
+            return;
+        }
+        match decode_brand(&packet.payload.0) {
+            Some(brand) => *self.client_brand.lock().await = Some(brand),
+            None => log::debug!("Malformed brand payload: {packet:?}"),
+        }
     }
 
     /// Handles the client information packet during the configuration state.
@@ -50,8 +58,8 @@ impl JavaTcpClient {
     /// Starts the configuration process by sending initial packets.
     pub async fn start_configuration(&self) {
         self.send_bare_packet_now(CCustomPayload::new(
-            Identifier::vanilla_static("brand"),
-            Box::new(BRAND_PAYLOAD),
+            BRAND_CHANNEL,
+            encode_brand(SERVER_BRAND),
         ))
         .await;
 
@@ -100,6 +108,7 @@ impl JavaTcpClient {
             .expect("Game profile is empty");
 
         let client_info = self.client_information.lock().await.clone();
+        let client_brand = self.client_brand.lock().await.take();
 
         let world = self.server.overworld().clone();
         let entity_id = next_entity_id();
@@ -126,6 +135,10 @@ impl JavaTcpClient {
             )
         });
 
+        if let Some(brand) = client_brand {
+            player.set_client_brand(brand);
+        }
+
         self.connection_updates
             .send(ConnectionUpdate::Upgrade(player.connection.clone()))
             .expect("Failed to send connection update");
@@ -9,28 +9,30 @@ use steel_protocol::{
     utils::ConnectionProtocol,
 };
 use steel_utils::translations;
+use steel_utils::uuid_ext::offline_player_uuid;
 use text_components::TextComponent;
 
 use crate::{
-    AuthError, is_valid_player_name, mojang_authenticate, offline_uuid, signed_bytes_be_to_hex,
+    AuthError, is_valid_player_name, mojang_authenticate, offline_skin, signed_bytes_be_to_hex,
     tcp_client::{ConnectionUpdate, JavaTcpClient},
 };
 
 impl JavaTcpClient {
     /// Handles the hello packet during the login state.
-    ///
-    /// # Panics
-    /// This function will panic if the player name converted to a UUID fails.
     pub async fn handle_hello(&self, packet: SHello) {
         if !is_valid_player_name(&packet.name) {
             self.kick("Invalid player name".into()).await;
             return;
         }
 
-        let id = if STEEL_CONFIG.online_mode {
-            packet.profile_id
+        let (id, properties) = if STEEL_CONFIG.online_mode {
+            // The session server fills in the properties
+            (packet.profile_id, vec![])
         } else {
-            offline_uuid(&packet.name).expect("Failed to generate offline UUID")
+            (
+                offline_player_uuid(&packet.name),
+                offline_skin(&packet.name).await,
+            )
         };
 
         {
@@ -38,7 +40,7 @@ impl JavaTcpClient {
             *gameprofile = Some(GameProfile {
                 id,
                 name: packet.name.clone(),
-                properties: vec![],
+                properties: properties.clone(),
                 profile_actions: None,
             });
         }
@@ -58,7 +60,7 @@ impl JavaTcpClient {
             self.finish_login(&GameProfile {
                 id,
                 name: packet.name,
-                properties: vec![],
+                properties,
                 profile_actions: None,
             })
             .await;
@@ -154,6 +156,8 @@ impl JavaTcpClient {
     /// # Panics
     /// This function will panic if the compression threshold cannot be converted to an i32.
     pub async fn finish_login(&self, profile: &GameProfile) {
+        self.server.profile_cache.add(&profile.name, profile.id);
+
         if let Some(compression) = STEEL_CONFIG.compression {
             self.send_bare_packet_now(CLoginCompression::new(
                 compression
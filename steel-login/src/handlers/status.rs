@@ -1,6 +1,9 @@
 //! Status state packet handlers (server list ping).
 
 use steel_core::config::STEEL_CONFIG;
+use steel_core::event::{EVENTS, ServerListPingEvent};
+use steel_core::server::live_settings::{LIVE_SETTINGS, motd_json, motd_plain};
+use steel_protocol::legacy_ping::LegacyPing;
 use steel_protocol::packets::{
     common::{CPongResponse, SPingRequest},
     status::{CStatusResponse, Players, Sample, Status, Version},
@@ -9,23 +12,30 @@ use steel_registry::packets::CURRENT_MC_PROTOCOL;
 
 use crate::tcp_client::JavaTcpClient;
 
+/// The UUID of the lines of a custom player list.
+const CUSTOM_SAMPLE_ID: &str = "00000000-0000-0000-0000-000000000000";
+
 impl JavaTcpClient {
     /// Handles a status request from the client.
+    ///
+    /// Vanilla: `ServerStatusPacketListenerImpl.handleStatusRequest()`.
     pub async fn handle_status_request(&self) {
+        let mut event = self.server_list_ping();
+        EVENTS.post(&mut event);
+
         let res_packet = CStatusResponse::new(Status {
-            description: &STEEL_CONFIG.motd,
-            players: Some(Players {
-                max: STEEL_CONFIG.max_players.cast_signed(),
-                online: self.server.player_count() as i32,
-                sample: self
-                    .server
-                    .player_sample()
+            description: motd_json(&event.motd),
+            players: (!event.hide_players).then(|| Players {
+                max: event.max_players,
+                online: event.online,
+                sample: event
+                    .sample
                     .into_iter()
                     .map(|(name, id)| Sample { name, id })
                     .collect(),
             }),
             enforce_secure_chat: STEEL_CONFIG.enforce_secure_chat,
-            favicon: load_favicon(),
+            favicon: event.favicon,
             version: Some(Version {
                 name: STEEL_CONFIG.mc_version,
                 protocol: CURRENT_MC_PROTOCOL,
@@ -34,6 +44,60 @@ impl JavaTcpClient {
         self.send_bare_packet_now(res_packet).await;
     }
 
+    /// Answers the server list ping of a client before 1.7, then closes the
+    /// connection.
+    ///
+    /// Vanilla: `LegacyQueryHandler.channelRead()`.
+    pub async fn handle_legacy_ping(&self, ping: LegacyPing) {
+        log::debug!("Legacy ping ({ping:?}) from {}", self.address);
+        let mut event = self.server_list_ping();
+        EVENTS.post(&mut event);
+
+        let response = ping.response(
+            STEEL_CONFIG.mc_version,
+            &motd_plain(&event.motd),
+            event.online,
+            event.max_players,
+        );
+        if let Err(err) = self.network_writer.lock().await.write_raw(&response).await {
+            log::debug!(
+                "Failed to answer the legacy ping of {}: {err}",
+                self.address
+            );
+        }
+        self.close();
+    }
+
+    /// Builds what the server list shows from the config, before listeners change it.
+    fn server_list_ping(&self) -> ServerListPingEvent {
+        let status = &STEEL_CONFIG.status;
+        let online = (self.server.player_count() as i32).saturating_add(status.online_offset);
+        let max_players = match status.max_above_online {
+            Some(above) => online.saturating_add(above.cast_signed()),
+            None => LIVE_SETTINGS.max_players().cast_signed(),
+        };
+        let sample = if !status.show_players {
+            Vec::new()
+        } else if status.custom_sample.is_empty() {
+            self.server.player_sample(status.sample_size)
+        } else {
+            status
+                .custom_sample
+                .iter()
+                .map(|line| (line.clone(), CUSTOM_SAMPLE_ID.to_owned()))
+                .collect()
+        };
+        ServerListPingEvent {
+            address: self.address,
+            motd: LIVE_SETTINGS.motd(),
+            online,
+            max_players,
+            sample,
+            hide_players: false,
+            favicon: LIVE_SETTINGS.favicon(),
+        }
+    }
+
     /// Handles a ping request from the client.
     pub async fn handle_ping_request(&self, packet: SPingRequest) {
         self.send_bare_packet_now(CPongResponse::new(packet.time))
@@ -41,27 +105,3 @@ impl JavaTcpClient {
         self.close();
     }
 }
-
-/// Loads the favicon from config.
-fn load_favicon() -> Option<String> {
-    use base64::{Engine, prelude::BASE64_STANDARD};
-    use std::fs;
-    use std::path::Path;
-
-    const ICON_PREFIX: &str = "data:image/png;base64,";
-
-    if !STEEL_CONFIG.use_favicon {
-        return None;
-    }
-
-    let path = Path::new(&STEEL_CONFIG.favicon);
-    let Ok(icon) = fs::read(path) else {
-        return None;
-    };
-
-    let cap = ICON_PREFIX.len() + icon.len().div_ceil(3) * 4;
-    let mut base64 = String::with_capacity(cap);
-    base64 += ICON_PREFIX;
-    BASE64_STANDARD.encode_string(icon, &mut base64);
-    Some(base64)
-}
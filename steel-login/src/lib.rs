@@ -10,13 +10,15 @@ mod authentication;
 mod connection;
 mod handlers;
 mod login;
+mod offline_skins;
 mod tcp_client;
 
 // Authentication
 pub use authentication::{AuthError, TextureError, mojang_authenticate, signed_bytes_be_to_hex};
 
 // Login helpers
-pub use login::{is_valid_player_name, offline_uuid};
+pub use login::is_valid_player_name;
+pub use offline_skins::offline_skin;
 
 // Type re-exports from steel-core
 pub use steel_core::player::{ClientInformation, GameProfile, GameProfileAction};
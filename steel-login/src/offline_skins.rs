@@ -0,0 +1,126 @@
+//! Skins for players on a server in offline mode.
+//!
+//! Offline players have no session, so the server looks up the premium account
+//! with the same name and gives them its signed textures. Mojang rate limits the
+//! API per IP address, so lookups are limited to a configured number per minute
+//! and the results are kept for a while.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use steel_core::config::STEEL_CONFIG;
+use steel_core::player::GameProfile;
+use steel_core::player::textures::TEXTURES_PROPERTY;
+use steel_protocol::packets::login::GameProfileProperty;
+use steel_utils::locks::SyncMutex;
+use uuid::Uuid;
+
+use crate::authentication::http_client;
+
+const PROFILE_LOOKUP_URL: &str = "https://api.mojang.com/users/profiles/minecraft/";
+const PROFILE_URL: &str = "https://sessionserver.mojang.com/session/minecraft/profile/";
+const UNSIGNED_ARG: &str = "?unsigned=false";
+
+/// The window `lookups_per_minute` counts lookups in.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How long looked up textures are kept, including names without an account.
+const CACHE_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// A response of the profile lookup API.
+#[derive(Deserialize)]
+struct ProfileLookup {
+    id: Uuid,
+}
+
+#[derive(Default)]
+struct SkinLookups {
+    /// When the lookups of the last minute started, oldest first.
+    recent: VecDeque<Instant>,
+    /// Textures by lowercase name, with when they were looked up.
+    cache: FxHashMap<String, (Instant, Vec<GameProfileProperty>)>,
+}
+
+static SKIN_LOOKUPS: SyncMutex<Option<SkinLookups>> = SyncMutex::new(None);
+
+/// Gets the textures of the premium account named `name`, for an offline player.
+/// Returns no properties if skins for offline players are disabled, the name
+/// has no account, or the lookup limit is reached.
+pub async fn offline_skin(name: &str) -> Vec<GameProfileProperty> {
+    let config = &STEEL_CONFIG.offline_skins;
+    if !config.enabled {
+        return Vec::new();
+    }
+    let key = name.to_lowercase();
+    {
+        let mut lookups = SKIN_LOOKUPS.lock();
+        let lookups = lookups.get_or_insert_default();
+        let now = Instant::now();
+        lookups
+            .cache
+            .retain(|_, (looked_up, _)| now.duration_since(*looked_up) < CACHE_DURATION);
+        if let Some((_, properties)) = lookups.cache.get(&key) {
+            return properties.clone();
+        }
+        while lookups
+            .recent
+            .front()
+            .is_some_and(|started| now.duration_since(*started) >= RATE_WINDOW)
+        {
+            lookups.recent.pop_front();
+        }
+        if lookups.recent.len() >= config.lookups_per_minute as usize {
+            log::debug!("Skipped the skin lookup of {name}, too many lookups");
+            return Vec::new();
+        }
+        lookups.recent.push_back(now);
+    }
+
+    let properties = match fetch_textures(name).await {
+        Ok(properties) => properties,
+        Err(err) => {
+            // Not cached, so the next join tries again
+            log::warn!("Failed to look up the skin of {name}: {err}");
+            return Vec::new();
+        }
+    };
+    SKIN_LOOKUPS
+        .lock()
+        .get_or_insert_default()
+        .cache
+        .insert(key, (Instant::now(), properties.clone()));
+    properties
+}
+
+/// Looks up the account named `name`, then its signed textures.
+async fn fetch_textures(name: &str) -> Result<Vec<GameProfileProperty>, reqwest::Error> {
+    let response = http_client()
+        .get(format!("{PROFILE_LOOKUP_URL}{name}"))
+        .send()
+        .await?;
+    // Names without an account get no content or not found
+    if matches!(
+        response.status(),
+        StatusCode::NO_CONTENT | StatusCode::NOT_FOUND
+    ) {
+        return Ok(Vec::new());
+    }
+    let ProfileLookup { id } = response.error_for_status()?.json().await?;
+
+    let mut profile: GameProfile = http_client()
+        .get(format!("{PROFILE_URL}{}{UNSIGNED_ARG}", id.simple()))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    profile.validate_textures();
+    Ok(profile
+        .properties
+        .into_iter()
+        .filter(|property| property.name == TEXTURES_PROPERTY)
+        .collect())
+}
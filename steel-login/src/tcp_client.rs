@@ -11,9 +11,12 @@ use std::{
 };
 
 use crossbeam::atomic::AtomicCell;
+use steel_core::config::STEEL_CONFIG;
+use steel_core::player::outbound_queue::{self, OutboundPacket, OutboundQueue, OutboundReceiver};
 use steel_core::player::{ClientInformation, GameProfile, PlayerConnection};
 use steel_core::server::Server;
 use steel_protocol::{
+    legacy_ping::LegacyPing,
     packet_reader::TCPNetworkDecoder,
     packet_traits::{ClientPacket, CompressionInfo, EncodedPacket, ServerPacket},
     packet_writer::TCPNetworkEncoder,
@@ -26,7 +29,7 @@ use steel_protocol::{
     utils::{ConnectionProtocol, PacketError, RawPacket},
 };
 use steel_registry::packets::{config, handshake, login as login_packets, status};
-use steel_utils::locks::AsyncMutex;
+use steel_utils::{locks::AsyncMutex, translations};
 use text_components::{
     TextComponent, content::Resolvable, custom::CustomData, resolving::TextResolutor,
 };
@@ -40,7 +43,6 @@ use tokio::{
     sync::{
         Notify,
         broadcast::{self, Sender, error::RecvError},
-        mpsc::{self, UnboundedReceiver, UnboundedSender},
     },
 };
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
@@ -78,6 +80,8 @@ pub struct JavaTcpClient {
     pub gameprofile: AsyncMutex<Option<GameProfile>>,
     /// The client's settings (view distance, language, etc.) received during config.
     pub client_information: AsyncMutex<ClientInformation>,
+    /// The brand the client reported during config, e.g. `vanilla`.
+    pub client_brand: AsyncMutex<Option<String>>,
     /// The current connection state of the client (e.g., Handshaking, Status, Play).
     pub protocol: Arc<AtomicCell<ConnectionProtocol>>,
     /// The client's IP address.
@@ -85,8 +89,8 @@ pub struct JavaTcpClient {
     /// A token to cancel the client's operations. Called when the connection is closed.
     pub cancel_token: CancellationToken,
 
-    /// A queue of encoded packets to send to the network.
-    pub outgoing_queue: UnboundedSender<EncodedPacket>,
+    /// A queue of packets to send to the network.
+    pub outgoing_queue: OutboundQueue,
     /// The packet encoder for outgoing packets.
     pub network_writer: Arc<AsyncMutex<TCPNetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
     /// Current compression settings.
@@ -117,17 +121,18 @@ impl JavaTcpClient {
         task_tracker: TaskTracker,
     ) -> (
         Self,
-        UnboundedReceiver<EncodedPacket>,
+        OutboundReceiver,
         TCPNetworkDecoder<BufReader<OwnedReadHalf>>,
     ) {
         let (read, write) = tcp_stream.into_split();
-        let (outgoing_queue, recv) = mpsc::unbounded_channel();
+        let (outgoing_queue, recv) = outbound_queue::channel(STEEL_CONFIG.outbound_queue.limits());
         let (connection_updates, _) = broadcast::channel(128);
 
         let client = Self {
             id,
             gameprofile: AsyncMutex::new(None),
             client_information: AsyncMutex::new(ClientInformation::default()),
+            client_brand: AsyncMutex::new(None),
             address,
             protocol: Arc::new(AtomicCell::new(ConnectionProtocol::Handshake)),
             cancel_token,
@@ -185,32 +190,25 @@ impl JavaTcpClient {
         let compression = self.compression.load();
         let protocol = self.protocol.load();
         let packet = EncodedPacket::from_bare(packet, compression, protocol)?;
-        self.outgoing_queue.send(packet).map_err(|e| {
-            PacketError::SendError(format!(
-                "Failed to send packet to client {}: {}",
-                self.id, e
-            ))
-        })?;
-        Ok(())
+        self.send_packet(packet)
     }
 
     /// Queues an already encoded packet to be sent.
     pub fn send_packet(&self, packet: EncodedPacket) -> Result<(), PacketError> {
-        self.outgoing_queue.send(packet).map_err(|e| {
-            PacketError::SendError(format!(
-                "Failed to send packet to client {}: {}",
-                self.id, e
-            ))
-        })?;
+        self.outgoing_queue
+            .push(OutboundPacket::Encoded(packet))
+            .map_err(|e| {
+                PacketError::SendError(format!(
+                    "Failed to send packet to client {}: {}",
+                    self.id, e
+                ))
+            })?;
         Ok(())
     }
 
     /// Starts a task that will send packets to the client from the outgoing packet queue.
     /// This task will run until the client is closed or the cancellation token is cancelled.
-    pub fn start_outgoing_packet_task(
-        self: &Arc<Self>,
-        mut sender_recv: UnboundedReceiver<EncodedPacket>,
-    ) {
+    pub fn start_outgoing_packet_task(self: &Arc<Self>, mut sender_recv: OutboundReceiver) {
         let cancel_token = self.cancel_token.clone();
         let network_writer = self.network_writer.clone();
         let id = self.id;
@@ -218,6 +216,16 @@ impl JavaTcpClient {
         let connection_updated = self.connection_updated.clone();
 
         self.task_tracker.spawn(async move {
+            // Clients before 1.7 ping without packet framing
+            let legacy_ping = select! {
+                () = cancel_token.cancelled() => None,
+                data = reader.peek() => data.ok().and_then(LegacyPing::read),
+            };
+            if let Some(ping) = legacy_ping {
+                self_clone.handle_legacy_ping(ping).await;
+                return;
+            }
+
             let mut connection = None;
             loop {
                 select! {
@@ -226,8 +234,13 @@ impl JavaTcpClient {
                     }
                     packet = sender_recv.recv() => {
                         if let Some(packet) = packet {
-                            if let Err(err) = network_writer.lock().await.write_packet(&packet).await
-                            {
+                            let len = packet.len();
+                            let result = match packet.encode() {
+                                Ok(packet) => network_writer.lock().await.write_packet(&packet).await,
+                                Err(err) => Err(err),
+                            };
+                            sender_recv.mark_sent(len);
+                            if let Err(err) = result {
                                 log::warn!("Failed to send packet to client {id}: {err}");
                                 cancel_token.cancel();
                             }
@@ -298,10 +311,16 @@ impl JavaTcpClient {
                     packet = reader.get_raw_packet() => {
                         match packet {
                             Ok(packet) => {
+                                let packet_id = packet.id;
                                 if let Err(err) = self_clone.process_packet(packet).await {
                                     log::warn!(
-                                        "Failed to get packet from client {id}: {err}",
+                                        "Failed to decode packet {packet_id:#04x} from client {id}: {err}",
                                     );
+                                    self_clone
+                                        .kick(TextComponent::translated(
+                                            translations::DISCONNECT_PACKET_ERROR.msg(),
+                                        ))
+                                        .await;
                                 }
                             }
                             Err(err) => {
@@ -422,7 +441,8 @@ impl JavaTcpClient {
 
         match packet.id {
             config::S_CUSTOM_PAYLOAD => {
-                self.handle_config_custom_payload(SCustomPayload::read_packet(data)?);
+                self.handle_config_custom_payload(SCustomPayload::read_packet(data)?)
+                    .await;
             }
             config::S_CLIENT_INFORMATION => {
                 self.handle_client_information(SClientInformation::read_packet(data)?)
@@ -211,6 +211,9 @@ fn parse_read_attributes(f: &syn::Field) -> FieldReadAttributes {
 }
 
 /// Generates read code for a field based on the given strategy.
+///
+/// Every length prefix is checked against `bound` (or the default bound) before anything
+/// is allocated, so malformed input can't make us reserve arbitrary amounts of memory.
 fn generate_read_code(
     strategy: &Strategy,
     field_type: &syn::Type,
@@ -231,11 +234,17 @@ fn generate_read_code(
             if let Some(inner) = &strategy.inner {
                 // Custom inner read strategy - read length then iterate
                 let inner_read = generate_read_code(inner, field_type, None);
+                let bound = bound.map_or_else(
+                    || quote! { steel_utils::serial::DEFAULT_BOUND },
+                    |b| quote! { #b },
+                );
                 quote! {
                     {
                         use steel_utils::serial::PrefixedRead;
-                        let len = #prefix::read(data)?.0 as usize;
-                        let mut items = Vec::with_capacity(len);
+                        let len = usize::try_from(#prefix::read(data)?.0)
+                            .map_err(|_| steel_utils::serial::ReadError::InvalidLength)?;
+                        let len = steel_utils::serial::ReadError::check_len(len, #bound)?;
+                        let mut items = Vec::with_capacity(len.min(data.get_ref().len()));
                         for _ in 0..len {
                             items.push(#inner_read);
                         }
@@ -314,21 +323,23 @@ fn parse_struct_read_attributes(attrs: &[syn::Attribute]) -> FieldReadAttributes
 fn read_from_struct(s: syn::DataStruct, name: Ident, attrs: &[syn::Attribute]) -> TokenStream {
     match s.fields {
         Fields::Named(fields) => {
-            // Create read calls for every field
+            // Create read calls for every field, tagging errors with the field they came from
             let readers = fields.named.iter().map(|f| {
                 let field_name = f.ident.as_ref().expect("should have a named field");
                 let field_type = &f.ty;
                 let FieldReadAttributes { strategy, bound } = parse_read_attributes(f);
+                let field_path = format!("{name}.{field_name}");
 
-                if let Some(strat) = strategy {
-                    let read_code = generate_read_code(&strat, field_type, bound.as_ref());
-                    quote! {
-                        let #field_name = #read_code;
-                    }
+                let read_code = if let Some(strat) = strategy {
+                    generate_read_code(&strat, field_type, bound.as_ref())
                 } else {
-                    quote! {
-                        let #field_name = <#field_type>::read(data)?;
-                    }
+                    quote! { <#field_type>::read(data)? }
+                };
+                quote! {
+                    let #field_name = steel_utils::serial::ReadError::read_field(
+                        #field_path,
+                        || Ok(#read_code),
+                    )?;
                 }
             });
 
@@ -340,7 +351,9 @@ fn read_from_struct(s: syn::DataStruct, name: Ident, attrs: &[syn::Attribute]) -
             let expanded = quote! {
                 #[automatically_derived]
                 impl steel_utils::serial::ReadFrom for #name {
-                    fn read(data: &mut std::io::Cursor<&[u8]>) -> std::io::Result<Self>{
+                    fn read(
+                        data: &mut std::io::Cursor<&[u8]>,
+                    ) -> std::result::Result<Self, steel_utils::serial::ReadError> {
                         use steel_utils::serial::PrefixedRead;
 
                         #(#readers)*
@@ -368,7 +381,9 @@ fn read_from_struct(s: syn::DataStruct, name: Ident, attrs: &[syn::Attribute]) -
             let expanded = quote! {
                 #[automatically_derived]
                 impl steel_utils::serial::ReadFrom for #name {
-                    fn read(data: &mut std::io::Cursor<&[u8]>) -> std::io::Result<Self> {
+                    fn read(
+                        data: &mut std::io::Cursor<&[u8]>,
+                    ) -> std::result::Result<Self, steel_utils::serial::ReadError> {
                         use steel_utils::serial::PrefixedRead;
 
                         Ok(Self(#read_code))
@@ -386,7 +401,9 @@ fn read_from_struct(s: syn::DataStruct, name: Ident, attrs: &[syn::Attribute]) -
             let expanded = quote! {
                 #[automatically_derived]
                 impl steel_utils::serial::ReadFrom for #name {
-                    fn read(_data: &mut std::io::Cursor<&[u8]>) -> std::io::Result<Self> {
+                    fn read(
+                        _data: &mut std::io::Cursor<&[u8]>,
+                    ) -> std::result::Result<Self, steel_utils::serial::ReadError> {
                         Ok(Self)
                     }
                 }
@@ -445,21 +462,24 @@ fn read_from_enum(e: syn::DataEnum, name: Ident, attrs: Vec<syn::Attribute>) ->
     let read_discriminant = match &strategy.as_ref().map(Strategy::name_str) {
         // Default: read a VarInt (i32)
         None => {
-            quote! { steel_utils::codec::VarInt::read(data)?.into() }
+            quote! { let discriminant: i32 = steel_utils::codec::VarInt::read(data)?.0; }
         }
         // Explicit VarInt
         Some(s) if s == "VarInt" => {
-            quote! { steel_utils::codec::VarInt::read(data)?.into() }
+            quote! { let discriminant: i32 = steel_utils::codec::VarInt::read(data)?.0; }
         }
         // VarLong
         Some(s) if s == "VarLong" => {
-            quote! { steel_utils::codec::VarLong::read(data)?.into() }
+            quote! { let discriminant: i64 = steel_utils::codec::VarLong::read(data)?.0; }
         }
         // Primitive numeric type (u8, i32, etc.)
         Some(s) if ALLOWED_TYPES.contains(&s.as_str()) => {
             let enum_type = Ident::new(s, Span::call_site());
             let _ = bound; // `bound` currently unused for primitive reads
-            quote! { <#enum_type as steel_utils::serial::ReadFrom>::read(data)? }
+            quote! {
+                let discriminant: #enum_type =
+                    <#enum_type as steel_utils::serial::ReadFrom>::read(data)?;
+            }
         }
         Some(s) => panic!(
             "Unknown read strategy for enum: `{s}`. \
@@ -467,18 +487,22 @@ fn read_from_enum(e: syn::DataEnum, name: Ident, attrs: Vec<syn::Attribute>) ->
         ),
     };
 
-    let error_msg = format!("Invalid {name}");
+    let kind = name.to_string();
 
     TokenStream::from(quote! {
         #[automatically_derived]
         impl steel_utils::serial::ReadFrom for #name {
-            fn read(data: &mut std::io::Cursor<&[u8]>) -> std::io::Result<Self> {
-                Ok(match { #read_discriminant } {
+            fn read(
+                data: &mut std::io::Cursor<&[u8]>,
+            ) -> std::result::Result<Self, steel_utils::serial::ReadError> {
+                #read_discriminant
+                Ok(match discriminant {
                     #(#readers)*
                     _ => {
-                        return Err(
-                            std::io::Error::other(#error_msg)
-                        );
+                        return Err(steel_utils::serial::ReadError::InvalidDiscriminant {
+                            kind: #kind,
+                            value: i128::try_from(discriminant).unwrap_or(i128::MAX),
+                        });
                     }
                 })
             }
@@ -861,9 +885,12 @@ fn write_to_enum(name: Ident, attrs: Vec<syn::Attribute>) -> TokenStream {
 
 /// Derives the `ClientPacket` trait for a struct.
 ///
+/// Cosmetic packets that may be dropped for slow clients are marked with
+/// `#[low_priority]`.
+///
 /// # Panics
 /// - If the `packet_id` attribute is missing or malformed.
-#[proc_macro_derive(ClientPacket, attributes(packet_id))]
+#[proc_macro_derive(ClientPacket, attributes(packet_id, low_priority))]
 pub fn client_packet_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
@@ -906,11 +933,24 @@ pub fn client_packet_derive(input: TokenStream) -> TokenStream {
         }
     }
 
+    let priority = input
+        .attrs
+        .iter()
+        .any(|a| a.path().is_ident("low_priority"))
+        .then(|| {
+            quote! {
+                const PRIORITY: crate::packet_traits::PacketPriority =
+                    crate::packet_traits::PacketPriority::Low;
+            }
+        });
+
     let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
 
     let expanded = quote! {
         #[automatically_derived]
         impl #impl_generics crate::packet_traits::ClientPacket for #name #ty_generics {
+            #priority
+
             fn get_id(&self, protocol: crate::utils::ConnectionProtocol) -> Option<i32> {
                 match protocol {
                     #(#match_arms)*
@@ -942,10 +982,13 @@ pub fn server_packet_derive(input: TokenStream) -> TokenStream {
     //    panic!("`packet_id` must be used as #[packet_id(...)]");
     //};
 
+    let packet_name = name.to_string();
+
     let expanded = quote! {
         #[automatically_derived]
         impl crate::packet_traits::ServerPacket for #name {
     //        const ID: i32 = #id_expr as i32;
+            const NAME: &'static str = #packet_name;
         }
     };
 
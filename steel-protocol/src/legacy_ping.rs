@@ -0,0 +1,189 @@
+//! # Steel Protocol Legacy Ping
+//!
+//! The server list ping of clients before 1.7, which comes before packet framing.
+//! A connection starting with `0xFE` is answered with a disconnect packet holding
+//! the server list entry, then closed.
+//!
+//! Vanilla: `LegacyQueryHandler`.
+
+/// The first byte of a legacy ping.
+const PING: u8 = 0xFE;
+/// The second byte of a ping from 1.4 on.
+const PING_PAYLOAD: u8 = 0x01;
+/// The id of the plugin message 1.6 clients append.
+const CUSTOM_PAYLOAD: u8 = 0xFA;
+/// The id of the disconnect packet carrying the response.
+const DISCONNECT: u8 = 0xFF;
+/// The channel of the plugin message 1.6 clients append.
+const PING_HOST_CHANNEL: &str = "MC|PingHost";
+/// The oldest protocol version sending `MC|PingHost`.
+const PING_HOST_MIN_PROTOCOL: u8 = 73;
+/// The protocol version reported to 1.4+ clients. It matches none of them, so
+/// they show the server as incompatible.
+const RESPONSE_PROTOCOL: i32 = 127;
+
+/// A legacy server list ping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LegacyPing {
+    /// Beta 1.8 to 1.3, which only send `0xFE`.
+    V1_3,
+    /// 1.4 and 1.5, which send `0xFE 0x01`.
+    V1_4,
+    /// 1.6, which appends a `MC|PingHost` plugin message.
+    V1_6 {
+        /// The client's protocol version.
+        protocol: u8,
+        /// The host name the client connected to.
+        host: String,
+        /// The port the client connected to.
+        port: i32,
+    },
+}
+
+impl LegacyPing {
+    /// Reads a legacy ping from the first bytes a client sent. Returns `None` if
+    /// they aren't one, so the connection uses the current protocol.
+    ///
+    /// Vanilla: `LegacyQueryHandler.channelRead()`.
+    #[must_use]
+    pub fn read(data: &[u8]) -> Option<Self> {
+        match data {
+            [PING] => Some(Self::V1_3),
+            [PING, PING_PAYLOAD] => Some(Self::V1_4),
+            [PING, PING_PAYLOAD, rest @ ..] => read_ping_host(rest),
+            _ => None,
+        }
+    }
+
+    /// Encodes the answer: a disconnect packet with the server list entry.
+    ///
+    /// Vanilla: `LegacyQueryHandler.createVersion0Response()` and
+    /// `LegacyQueryHandler.createVersion1Response()`.
+    #[must_use]
+    pub fn response(&self, version: &str, motd: &str, online: i32, max: i32) -> Vec<u8> {
+        let text = match self {
+            Self::V1_3 => format!("{motd}§{online}§{max}"),
+            Self::V1_4 | Self::V1_6 { .. } => {
+                format!("§1\0{RESPONSE_PROTOCOL}\0{version}\0{motd}\0{online}\0{max}")
+            }
+        };
+        let mut packet = vec![DISCONNECT];
+        write_string(&mut packet, &text);
+        packet
+    }
+}
+
+/// Reads the `MC|PingHost` plugin message of a 1.6 ping.
+///
+/// Vanilla: `LegacyQueryHandler.readCustomPayloadPacket()`.
+fn read_ping_host(mut data: &[u8]) -> Option<LegacyPing> {
+    if read_u8(&mut data)? != CUSTOM_PAYLOAD || read_string(&mut data)? != PING_HOST_CHANNEL {
+        return None;
+    }
+    let length = u16::from_be_bytes([read_u8(&mut data)?, read_u8(&mut data)?]);
+    if data.len() != usize::from(length) {
+        return None;
+    }
+    let protocol = read_u8(&mut data)?;
+    if protocol < PING_HOST_MIN_PROTOCOL {
+        return None;
+    }
+    let host = read_string(&mut data)?;
+    let port = i32::from_be_bytes(data.get(..4)?.try_into().ok()?);
+    if port > i32::from(u16::MAX) {
+        return None;
+    }
+    Some(LegacyPing::V1_6 {
+        protocol,
+        host,
+        port,
+    })
+}
+
+fn read_u8(data: &mut &[u8]) -> Option<u8> {
+    let (&byte, rest) = data.split_first()?;
+    *data = rest;
+    Some(byte)
+}
+
+/// Reads a string prefixed with its length in UTF-16 units.
+///
+/// Vanilla: `LegacyProtocolUtils.readLegacyString()`.
+fn read_string(data: &mut &[u8]) -> Option<String> {
+    let units = usize::from(u16::from_be_bytes([read_u8(data)?, read_u8(data)?]));
+    let bytes = data.get(..units * 2)?;
+    *data = &data[units * 2..];
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+/// Writes a string prefixed with its length in UTF-16 units, cut off at the
+/// longest length the prefix can hold.
+///
+/// Vanilla: `LegacyProtocolUtils.writeLegacyString()`.
+fn write_string(packet: &mut Vec<u8>, text: &str) {
+    let units: Vec<u16> = text.encode_utf16().take(usize::from(u16::MAX)).collect();
+    let length = u16::try_from(units.len()).unwrap_or(u16::MAX);
+    packet.extend_from_slice(&length.to_be_bytes());
+    for unit in units {
+        packet.extend_from_slice(&unit.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(text: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        write_string(&mut data, text);
+        data
+    }
+
+    #[test]
+    fn reads_each_version() {
+        assert_eq!(LegacyPing::read(&[0xFE]), Some(LegacyPing::V1_3));
+        assert_eq!(LegacyPing::read(&[0xFE, 0x01]), Some(LegacyPing::V1_4));
+
+        let host = string("localhost");
+        let mut data = vec![0xFE, 0x01, 0xFA];
+        data.extend(string("MC|PingHost"));
+        let length = u16::try_from(1 + host.len() + 4).expect("short payload");
+        data.extend_from_slice(&length.to_be_bytes());
+        data.push(78);
+        data.extend(host);
+        data.extend_from_slice(&25565i32.to_be_bytes());
+        assert_eq!(
+            LegacyPing::read(&data),
+            Some(LegacyPing::V1_6 {
+                protocol: 78,
+                host: "localhost".to_owned(),
+                port: 25565,
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_current_protocol() {
+        // The length and id of a handshake
+        assert_eq!(LegacyPing::read(&[0x10, 0x00]), None);
+        assert_eq!(LegacyPing::read(&[0xFE, 0x02]), None);
+        assert_eq!(LegacyPing::read(&[0xFE, 0x01, 0xFA, 0x00]), None);
+    }
+
+    #[test]
+    fn encodes_responses() {
+        let response = LegacyPing::V1_3.response("26.1", "Steel", 1, 20);
+        assert_eq!(response[0], 0xFF);
+        assert_eq!(response[1..], string("Steel§1§20"));
+
+        let response = LegacyPing::V1_4.response("26.1", "Steel", 1, 20);
+        assert_eq!(
+            response[1..],
+            string("§1\u{0}127\u{0}26.1\u{0}Steel\u{0}1\u{0}20")
+        );
+    }
+}
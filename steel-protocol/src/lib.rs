@@ -2,8 +2,10 @@
 //!
 //! The core library for the Steel Minecraft server. Handles everything related to the PLAY state.
 
+pub mod legacy_ping;
 pub mod packet_reader;
 pub mod packet_traits;
 pub mod packet_writer;
 pub mod packets;
+pub mod profiling;
 pub mod utils;
@@ -16,7 +16,7 @@ use aes::cipher::KeyIvInit;
 use flate2::read::ZlibDecoder;
 use steel_utils::codec::VarInt;
 use steel_utils::serial::ReadFrom;
-use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, ReadBuf};
 
 use crate::utils::{
     Aes128Cfb8Dec, MAX_PACKET_DATA_SIZE, MAX_PACKET_SIZE, PacketError, RawPacket, StreamDecryptor,
@@ -99,13 +99,28 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
         replace_with::replace_with_or_abort(&mut self.reader, |decoder| decoder.upgrade(cipher));
     }
 
+    /// Waits for data from the stream and returns what arrived, without consuming
+    /// it. Returns nothing once the stream is encrypted.
+    ///
+    /// # Errors
+    /// - If reading from the stream fails.
+    pub async fn peek(&mut self) -> io::Result<&[u8]>
+    where
+        R: AsyncBufRead,
+    {
+        match &mut self.reader {
+            DecryptionReader::None(reader) => reader.fill_buf().await,
+            DecryptionReader::Decrypt(_) => Ok(&[]),
+        }
+    }
+
     /// Gets a raw packet from the stream.
     ///
     /// # Errors
     /// - If the packet length is invalid.
     /// - If the packet is too long.
     /// - If the packet is not compressed when it should be.
-    /// - If the packet fails to decompress.
+    /// - If the packet fails to decompress or its decompressed size doesn't match the header.
     #[expect(clippy::cast_sign_loss)]
     pub async fn get_raw_packet(&mut self) -> Result<RawPacket, PacketError> {
         let packet_len = VarInt::read_async(&mut self.reader).await? as usize;
@@ -125,18 +140,31 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
 
         let decompressed_data = if let Some(threshold) = self.compression {
             let decompressed_len = VarInt::read(&mut cursor)?.0 as usize;
-            let raw_packet_len = packet_len - VarInt::written_size(decompressed_len as i32);
+            let raw_packet_len = packet_len - cursor.position() as usize;
 
             if decompressed_len > MAX_PACKET_DATA_SIZE {
                 Err(PacketError::TooLong(decompressed_len))?;
             }
 
             if decompressed_len > 0 {
-                // Decompress the remaining data
+                if decompressed_len < threshold.get() as usize {
+                    Err(PacketError::DecompressionFailed(format!(
+                        "size of {decompressed_len} is below server threshold of {threshold}"
+                    )))?;
+                }
+
+                // Decompress the remaining data, never inflating past the declared size
                 let mut decompressed = Vec::with_capacity(decompressed_len);
                 ZlibDecoder::new(&mut cursor)
+                    .take(decompressed_len as u64)
                     .read_to_end(&mut decompressed)
                     .map_err(|e| PacketError::DecompressionFailed(e.to_string()))?;
+                if decompressed.len() != decompressed_len {
+                    Err(PacketError::DecompressionFailed(format!(
+                        "size of {} does not match declared size of {decompressed_len}",
+                        decompressed.len()
+                    )))?;
+                }
                 decompressed
             } else {
                 // Validate that we are not less than the compression threshold
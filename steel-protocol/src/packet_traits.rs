@@ -15,14 +15,24 @@ use steel_utils::{
     serial::{ReadFrom, WriteTo},
 };
 
+use crate::profiling;
 use crate::utils::{ConnectionProtocol, MAX_PACKET_DATA_SIZE, MAX_PACKET_SIZE, PacketError};
 
 // These are the network read/write traits
 /// A trait for packets sent from the server to the client.
 pub trait ServerPacket: ReadFrom {
+    /// The name of the packet, used to give decode errors context.
+    const NAME: &'static str;
+
     /// Reads a packet from the given data.
+    ///
+    /// # Errors
+    /// - [`PacketError::Decode`] if the payload is malformed for this packet.
     fn read_packet(data: &mut Cursor<&[u8]>) -> Result<Self, PacketError> {
-        Self::read(data).map_err(PacketError::from)
+        Self::read(data).map_err(|source| PacketError::Decode {
+            packet: Self::NAME,
+            source,
+        })
     }
 }
 
@@ -47,10 +57,23 @@ pub trait ClientPacket: WriteTo {
         self.write(writer).map_err(PacketError::from)
     }
 
+    /// How important the packet is to deliver to a client that falls behind.
+    const PRIORITY: PacketPriority = PacketPriority::Normal;
+
     /// Gets the ID of the packet for the given protocol.
     fn get_id(&self, protocol: ConnectionProtocol) -> Option<i32>;
 }
 
+/// How important a packet is to deliver to a client that can't keep up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PacketPriority {
+    /// The packet is always sent.
+    Normal,
+    /// Cosmetic packets, such as sounds, that are dropped once a slow client's
+    /// queue grows too large.
+    Low,
+}
+
 /// Information about compression.
 #[derive(Copy, Clone, Debug, Deserialize)]
 pub struct CompressionInfo {
@@ -112,6 +135,8 @@ pub struct EncodedPacket {
     // This is optimized for reduces allocation
     /// The encoded data.
     pub encoded_data: Arc<FrontVec>,
+    /// The priority of the packet this was encoded from.
+    pub priority: PacketPriority,
 }
 
 impl EncodedPacket {
@@ -125,9 +150,11 @@ impl EncodedPacket {
         }
 
         VarInt(data_len as i32).set_in_front(&mut packet_data, varint_size);
+        profiling::record_packet(packet_data.len(), false);
 
         Ok(Self {
             encoded_data: Arc::new(packet_data),
+            priority: PacketPriority::Normal,
         })
     }
 
@@ -160,12 +187,15 @@ impl EncodedPacket {
 
             VarInt(data_len as i32).set_in_front(&mut buf, varint_size);
             VarInt(full_len as i32).set_in_front(&mut buf, full_varint_size);
+            profiling::record_allocation(buf.allocated());
+            profiling::record_packet(buf.len(), true);
             log::trace!(
                 "data length: {data_len}, full length: {full_len}, varint size: {varint_size}, full varint size: {full_varint_size}"
             );
 
             Ok(Self {
                 encoded_data: Arc::new(buf),
+                priority: PacketPriority::Normal,
             })
         } else {
             // Pushed before data:
@@ -177,9 +207,11 @@ impl EncodedPacket {
 
             VarInt(0).set_in_front(&mut packet_data, 1);
             VarInt(data_len_with_header as i32).set_in_front(&mut packet_data, varint_size);
+            profiling::record_packet(packet_data.len(), false);
 
             Ok(Self {
                 encoded_data: Arc::new(packet_data),
+                priority: PacketPriority::Normal,
             })
         }
     }
@@ -194,17 +226,20 @@ impl EncodedPacket {
         compression: Option<CompressionInfo>,
         protocol: ConnectionProtocol,
     ) -> Result<Self, PacketError> {
-        let buf = Self::write_vec(packet, protocol)?;
-        Self::from_data(buf, compression)
+        SerializedPacket::from_bare(packet, protocol)?.encode(compression)
     }
 
-    fn write_vec<P: ClientPacket>(
-        packet: P,
-        protocol: ConnectionProtocol,
-    ) -> Result<FrontVec, PacketError> {
-        let mut buf = FrontVec::new(6);
-        packet.write_packet(&mut buf, protocol)?;
-        Ok(buf)
+    /// The number of bytes sent for this packet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.encoded_data.len()
+    }
+
+    /// Returns true if the packet has no bytes, which never happens for a
+    /// packet that was encoded successfully.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.encoded_data.is_empty()
     }
 
     fn from_data(buf: FrontVec, compression: Option<CompressionInfo>) -> Result<Self, PacketError> {
@@ -215,3 +250,64 @@ impl EncodedPacket {
         }
     }
 }
+
+/// A packet written to bytes, but not yet compressed and framed.
+///
+/// Writing a packet is cheap, compressing it is not, so packets sent to a
+/// single player are written on the calling thread and compressed on the
+/// connection's task.
+pub struct SerializedPacket {
+    data: FrontVec,
+    priority: PacketPriority,
+}
+
+impl SerializedPacket {
+    /// Writes a packet to bytes.
+    ///
+    /// # Errors
+    /// - If the packet fails to write.
+    pub fn from_bare<P: ClientPacket>(
+        packet: P,
+        protocol: ConnectionProtocol,
+    ) -> Result<Self, PacketError> {
+        let mut data = FrontVec::new(6);
+        packet.write_packet(&mut data, protocol)?;
+        profiling::record_allocation(data.allocated());
+        Ok(Self {
+            data,
+            priority: P::PRIORITY,
+        })
+    }
+
+    /// The number of bytes written, before compression.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if nothing was written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The priority of the packet.
+    #[must_use]
+    pub const fn priority(&self) -> PacketPriority {
+        self.priority
+    }
+
+    /// Compresses and frames the packet for sending.
+    ///
+    /// # Errors
+    /// - If the packet is too long.
+    /// - If the packet fails to compress.
+    pub fn encode(
+        self,
+        compression: Option<CompressionInfo>,
+    ) -> Result<EncodedPacket, PacketError> {
+        let mut encoded = EncodedPacket::from_data(self.data, compression)?;
+        encoded.priority = self.priority;
+        Ok(encoded)
+    }
+}
@@ -116,17 +116,53 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
         replace_with::replace_with_or_abort(&mut self.writer, |encoder| encoder.upgrade(cipher));
     }
 
+    /// Writes bytes without packet framing, for answering a legacy ping.
+    ///
+    /// # Errors
+    /// - If the bytes fail to write.
+    /// - If the stream fails to flush.
+    pub async fn write_raw(&mut self, data: &[u8]) -> Result<(), PacketError> {
+        self.writer
+            .write_all(data)
+            .await
+            .map_err(|e| PacketError::Other(e.to_string()))?;
+
+        self.writer
+            .flush()
+            .await
+            .map_err(|e| PacketError::Other(e.to_string()))
+    }
+
     /// Writes a packet to the stream.
     ///
     /// # Errors
     /// - If the packet fails to write.
     /// - If the stream fails to flush.
     pub async fn write_packet(&mut self, packet: &EncodedPacket) -> Result<(), PacketError> {
+        self.write_packet_unflushed(packet).await?;
+        self.flush().await
+    }
+
+    /// Writes a packet to the stream without flushing it, so several packets
+    /// can go out together. Call [`Self::flush`] after the last one.
+    ///
+    /// # Errors
+    /// - If the packet fails to write.
+    pub async fn write_packet_unflushed(
+        &mut self,
+        packet: &EncodedPacket,
+    ) -> Result<(), PacketError> {
         self.writer
             .write_all(&packet.encoded_data)
             .await
-            .map_err(|e| PacketError::EncryptionFailed(e.to_string()))?;
+            .map_err(|e| PacketError::EncryptionFailed(e.to_string()))
+    }
 
+    /// Flushes the packets written so far.
+    ///
+    /// # Errors
+    /// - If the stream fails to flush.
+    pub async fn flush(&mut self) -> Result<(), PacketError> {
         self.writer
             .flush()
             .await
@@ -4,6 +4,7 @@ mod c_keep_alive;
 mod c_pong_response;
 mod c_update_tags;
 mod s_client_information;
+mod s_custom_click_action;
 mod s_custom_payload;
 mod s_keep_alive;
 mod s_ping_request;
@@ -15,6 +16,7 @@ pub use c_pong_response::CPongResponse;
 pub use c_update_tags::CUpdateTags;
 pub use c_update_tags::TagCollection;
 pub use s_client_information::{ChatVisibility, HumanoidArm, ParticleStatus, SClientInformation};
+pub use s_custom_click_action::{MAX_CLICK_PAYLOAD_SIZE, SCustomClickAction};
 pub use s_custom_payload::SCustomPayload;
 pub use s_keep_alive::SKeepAlive;
 pub use s_ping_request::SPingRequest;
@@ -0,0 +1,38 @@
+use std::io::{Cursor, Read};
+
+use simdnbt::owned::{NbtTag, read_tag};
+use steel_macros::ServerPacket;
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{ReadError, ReadFrom};
+
+/// Maximum encoded size of the payload tag (`ServerboundCustomClickActionPacket.UNTRUSTED_TAG_CODEC`).
+pub const MAX_CLICK_PAYLOAD_SIZE: usize = 65536;
+
+/// Sent when the player clicks a text component with a `custom` click event.
+#[derive(ServerPacket, Clone, Debug)]
+pub struct SCustomClickAction {
+    /// The id of the click event.
+    pub id: Identifier,
+    /// The optional payload attached to the click event.
+    pub payload: Option<NbtTag>,
+}
+
+impl ReadFrom for SCustomClickAction {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let id = Identifier::read(data)?;
+        let payload = if bool::read(data)? {
+            let len =
+                usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+            let len = ReadError::check_len(len, MAX_CLICK_PAYLOAD_SIZE)?;
+            let mut bytes = vec![0; len];
+            data.read_exact(&mut bytes)?;
+            let tag = read_tag(&mut Cursor::new(&bytes[..]))
+                .map_err(|e| ReadError::malformed(format!("Failed to read NBT: {e:?}")))?;
+            Some(tag)
+        } else {
+            None
+        };
+        Ok(Self { id, payload })
+    }
+}
@@ -3,7 +3,7 @@ use std::io::{Cursor, Read};
 use steel_macros::{ReadFrom, ServerPacket};
 use steel_utils::Identifier;
 
-use steel_utils::serial::ReadFrom;
+use steel_utils::serial::{ReadError, ReadFrom};
 
 #[derive(ReadFrom, ServerPacket, Clone, Debug)]
 pub struct SCustomPayload {
@@ -12,13 +12,17 @@ pub struct SCustomPayload {
     pub payload: Payload,
 }
 
+/// Maximum size of a serverbound custom payload (`ServerboundCustomPayloadPacket.MAX_PAYLOAD_SIZE`).
+pub const MAX_PAYLOAD_SIZE: usize = 32767;
+
 #[derive(Clone, Debug)]
 pub struct Payload(pub Vec<u8>);
 
 impl ReadFrom for Payload {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, std::io::Error> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = vec![];
         data.read_to_end(&mut buf)?;
+        ReadError::check_len(buf.len(), MAX_PAYLOAD_SIZE)?;
         Ok(Self(buf))
     }
 }
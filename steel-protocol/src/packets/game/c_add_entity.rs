@@ -1,9 +1,12 @@
 //! Packet sent to spawn an entity (including players) for the client.
 
+use std::io::Cursor;
+
+use glam::DVec3;
 use steel_macros::ClientPacket;
 use steel_registry::packets::play::C_ADD_ENTITY;
 use steel_utils::codec::VarInt;
-use steel_utils::serial::WriteTo;
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
 use uuid::Uuid;
 
 /// Spawns an entity on the client.
@@ -143,6 +146,35 @@ pub fn write_lp_vec3(
     }
 }
 
+/// Reads a vector written in LpVec3 format.
+///
+/// Mirrors vanilla's `LpVec3.read()`.
+///
+/// # Errors
+/// - If the input is truncated.
+pub fn read_lp_vec3(reader: &mut Cursor<&[u8]>) -> Result<DVec3, ReadError> {
+    let lowest = u8::read(reader)?;
+    if lowest == 0 {
+        return Ok(DVec3::ZERO);
+    }
+    let middle = u8::read(reader)?;
+    let highest = u32::read(reader)?;
+    let buffer = (i64::from(highest) << 16) | (i64::from(middle) << 8) | i64::from(lowest);
+
+    let mut scale = i64::from(lowest & 3);
+    if i64::from(lowest) & CONTINUATION_FLAG != 0 {
+        // Vanilla reads the VarInt as an unsigned int
+        scale |= i64::from(VarInt::read(reader)?.0 as u32) << 2;
+    }
+    let scale = scale as f64;
+
+    Ok(DVec3::new(
+        unpack_component(buffer >> 3) * scale,
+        unpack_component(buffer >> 18) * scale,
+        unpack_component(buffer >> 33) * scale,
+    ))
+}
+
 /// Sanitizes a velocity component.
 /// Mirrors vanilla's `LpVec3.sanitize()`.
 #[inline]
@@ -164,6 +196,13 @@ fn pack_component(value: f64) -> i64 {
     (normalized * MAX_QUANTIZED_VALUE + 0.5).floor() as i64
 }
 
+/// Unpacks a 15-bit quantized value to a normalized [-1, 1] value.
+/// Mirrors vanilla's `LpVec3.unpack()`.
+#[inline]
+fn unpack_component(value: i64) -> f64 {
+    ((value & 32767) as f64).min(MAX_QUANTIZED_VALUE) * 2.0 / MAX_QUANTIZED_VALUE - 1.0
+}
+
 impl CAddEntity {
     /// Creates a new CAddEntity packet for spawning a player.
     #[must_use]
@@ -256,4 +295,14 @@ mod tests {
         // Should be 6 bytes + VarInt for scale
         assert!(buf.len() > 6, "Should have continuation VarInt");
     }
+
+    #[test]
+    fn test_read_round_trip() {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (0.5, -0.25, 1.0), (5.0, -3.0, 0.125)] {
+            let mut buf = Vec::new();
+            write_lp_vec3(&mut buf, x, y, z).unwrap();
+            let read = read_lp_vec3(&mut Cursor::new(&buf[..])).unwrap();
+            assert!((read - DVec3::new(x, y, z)).abs().max_element() < 1e-3 * 5.0);
+        }
+    }
 }
@@ -0,0 +1,171 @@
+//! Clientbound packet that adds, updates or removes a boss bar.
+
+use std::io::{Result, Write};
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_BOSS_EVENT;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::WriteTo;
+use text_components::TextComponent;
+use uuid::Uuid;
+
+/// The color of a boss bar (matches vanilla `BossEvent.BossBarColor` order).
+#[derive(WriteTo, Copy, Clone, Debug, PartialEq, Eq)]
+#[write(as = VarInt)]
+pub enum BossBarColor {
+    /// Pink.
+    Pink = 0,
+    /// Blue.
+    Blue = 1,
+    /// Red.
+    Red = 2,
+    /// Green.
+    Green = 3,
+    /// Yellow.
+    Yellow = 4,
+    /// Purple.
+    Purple = 5,
+    /// White.
+    White = 6,
+}
+
+/// How a boss bar is divided (matches vanilla `BossEvent.BossBarOverlay` order).
+#[derive(WriteTo, Copy, Clone, Debug, PartialEq, Eq)]
+#[write(as = VarInt)]
+pub enum BossBarOverlay {
+    /// A solid bar.
+    Progress = 0,
+    /// 6 segments.
+    Notched6 = 1,
+    /// 10 segments.
+    Notched10 = 2,
+    /// 12 segments.
+    Notched12 = 3,
+    /// 20 segments.
+    Notched20 = 4,
+}
+
+/// What the packet does with the boss bar.
+///
+/// Vanilla equivalent: `ClientboundBossEventPacket.Operation`.
+#[derive(Clone, Debug)]
+pub enum BossEventOperation {
+    /// Shows a new boss bar.
+    Add {
+        /// The title above the bar.
+        name: TextComponent,
+        /// How full the bar is, from 0 to 1.
+        progress: f32,
+        /// The bar's color.
+        color: BossBarColor,
+        /// How the bar is divided.
+        overlay: BossBarOverlay,
+        /// See [`CBossEvent::DARKEN_SCREEN`] and the other flags.
+        flags: u8,
+    },
+    /// Hides the boss bar.
+    Remove,
+    /// Changes how full the bar is.
+    UpdateProgress(f32),
+    /// Changes the title.
+    UpdateName(TextComponent),
+    /// Changes the color and division.
+    UpdateStyle(BossBarColor, BossBarOverlay),
+    /// Changes the flags.
+    UpdateProperties(u8),
+}
+
+/// Adds, updates or removes a boss bar.
+///
+/// Vanilla equivalent: `ClientboundBossEventPacket`.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_BOSS_EVENT)]
+pub struct CBossEvent {
+    /// The id of the boss bar.
+    pub id: Uuid,
+    /// What to do with it.
+    pub operation: BossEventOperation,
+}
+
+impl CBossEvent {
+    /// Darkens the sky while the bar is shown.
+    pub const DARKEN_SCREEN: u8 = 1;
+    /// Plays the boss music while the bar is shown.
+    pub const PLAY_BOSS_MUSIC: u8 = 2;
+    /// Adds fog while the bar is shown.
+    pub const CREATE_WORLD_FOG: u8 = 4;
+}
+
+impl WriteTo for CBossEvent {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.id.write(writer)?;
+        match &self.operation {
+            BossEventOperation::Add {
+                name,
+                progress,
+                color,
+                overlay,
+                flags,
+            } => {
+                VarInt(0).write(writer)?;
+                name.write(writer)?;
+                progress.write(writer)?;
+                color.write(writer)?;
+                overlay.write(writer)?;
+                flags.write(writer)
+            }
+            BossEventOperation::Remove => VarInt(1).write(writer),
+            BossEventOperation::UpdateProgress(progress) => {
+                VarInt(2).write(writer)?;
+                progress.write(writer)
+            }
+            BossEventOperation::UpdateName(name) => {
+                VarInt(3).write(writer)?;
+                name.write(writer)
+            }
+            BossEventOperation::UpdateStyle(color, overlay) => {
+                VarInt(4).write(writer)?;
+                color.write(writer)?;
+                overlay.write(writer)
+            }
+            BossEventOperation::UpdateProperties(flags) => {
+                VarInt(5).write(writer)?;
+                flags.write(writer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_update_style() {
+        let id = Uuid::from_u64_pair(1, 2);
+        let packet = CBossEvent {
+            id,
+            operation: BossEventOperation::UpdateStyle(
+                BossBarColor::Pink,
+                BossBarOverlay::Notched10,
+            ),
+        };
+        let mut buf = Vec::new();
+        packet.write(&mut buf).unwrap();
+        assert_eq!(&buf[..16], id.as_bytes());
+        assert_eq!(&buf[16..], &[4, 0, 2]);
+    }
+
+    #[test]
+    fn test_encode_remove() {
+        let mut buf = Vec::new();
+        CBossEvent {
+            id: Uuid::nil(),
+            operation: BossEventOperation::Remove,
+        }
+        .write(&mut buf)
+        .unwrap();
+        assert_eq!(buf.len(), 17);
+        assert_eq!(buf[16], 1);
+    }
+}
@@ -0,0 +1,9 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_CLEAR_TITLES;
+
+/// Hides the current title, optionally also resetting the title times.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_CLEAR_TITLES)]
+pub struct CClearTitles {
+    pub reset_times: bool,
+}
@@ -0,0 +1,18 @@
+//! Clientbound packet that starts or clears an item cooldown.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_COOLDOWN;
+use steel_utils::Identifier;
+
+/// Puts every item in a cooldown group on cooldown, or clears it when `duration` is 0.
+///
+/// Vanilla equivalent: `ClientboundCooldownPacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_COOLDOWN)]
+pub struct CCooldown {
+    /// The cooldown group, usually the item's key.
+    pub cooldown_group: Identifier,
+    /// The cooldown length in ticks.
+    #[write(as = VarInt)]
+    pub duration: i32,
+}
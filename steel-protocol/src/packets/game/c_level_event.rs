@@ -11,6 +11,7 @@ use steel_utils::BlockPos;
 /// See `steel_registry::level_events` for all available event type constants.
 #[derive(WriteTo, ClientPacket, Clone, Debug)]
 #[packet_id(Play = C_LEVEL_EVENT)]
+#[low_priority]
 pub struct CLevelEvent {
     /// The event type ID. Use constants from `steel_registry::level_events`.
     pub event_type: i32,
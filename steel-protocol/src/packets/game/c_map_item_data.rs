@@ -0,0 +1,161 @@
+//! Clientbound packet that updates the colors and decorations of a map.
+
+use std::io::{Result, Write};
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_MAP_ITEM_DATA;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{PrefixedWrite, WriteTo};
+use text_components::TextComponent;
+
+/// The icon of a map decoration (matches vanilla `MapDecorationTypes` registration order).
+#[derive(WriteTo, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[write(as = VarInt)]
+pub enum MapDecorationType {
+    /// A player on the map.
+    Player = 0,
+    /// The item frame the map is in.
+    Frame = 1,
+    /// A red marker.
+    RedMarker = 2,
+    /// A blue marker.
+    BlueMarker = 3,
+    /// A target cross.
+    TargetX = 4,
+    /// A target point.
+    TargetPoint = 5,
+    /// A player just outside the map, drawn on its border.
+    PlayerOffMap = 6,
+    /// A player far outside the map, drawn small on its border.
+    PlayerOffLimits = 7,
+}
+
+/// An icon drawn on top of a map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapDecoration {
+    /// The icon.
+    pub decoration_type: MapDecorationType,
+    /// The x position, from -128 to 127 across the map.
+    pub x: i8,
+    /// The y position, from -128 to 127 across the map.
+    pub y: i8,
+    /// The rotation in sixteenths of a full turn.
+    pub rotation: u8,
+    /// The name shown under the icon.
+    pub name: Option<TextComponent>,
+}
+
+impl WriteTo for MapDecoration {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.decoration_type.write(writer)?;
+        self.x.write(writer)?;
+        self.y.write(writer)?;
+        (self.rotation & 15).write(writer)?;
+        self.name.is_some().write(writer)?;
+        if let Some(name) = &self.name {
+            name.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// A rectangle of map colors that changed.
+///
+/// Vanilla equivalent: `MapItemSavedData.MapPatch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapPatch {
+    /// The left edge of the rectangle.
+    pub start_x: u8,
+    /// The top edge of the rectangle.
+    pub start_y: u8,
+    /// The width of the rectangle, at least 1.
+    pub width: u8,
+    /// The height of the rectangle.
+    pub height: u8,
+    /// The packed colors of the rectangle, row by row.
+    pub colors: Vec<u8>,
+}
+
+/// Sends the colors and decorations of a map that changed.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_MAP_ITEM_DATA)]
+pub struct CMapItemData {
+    /// The id of the map.
+    pub map_id: i32,
+    /// The zoom level, from 0 to 4.
+    pub scale: i8,
+    /// Whether the map can no longer change.
+    pub locked: bool,
+    /// All decorations of the map, or `None` if they didn't change.
+    pub decorations: Option<Vec<MapDecoration>>,
+    /// The colors that changed, if any.
+    pub color_patch: Option<MapPatch>,
+}
+
+impl WriteTo for CMapItemData {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        VarInt(self.map_id).write(writer)?;
+        self.scale.write(writer)?;
+        self.locked.write(writer)?;
+        self.decorations.is_some().write(writer)?;
+        if let Some(decorations) = &self.decorations {
+            decorations.write_prefixed::<VarInt>(writer)?;
+        }
+        // A width of zero means there is no patch
+        match &self.color_patch {
+            Some(patch) => {
+                patch.width.write(writer)?;
+                patch.height.write(writer)?;
+                patch.start_x.write(writer)?;
+                patch.start_y.write(writer)?;
+                patch.colors.write_prefixed::<VarInt>(writer)
+            }
+            None => 0u8.write(writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_patch_without_decorations() {
+        let packet = CMapItemData {
+            map_id: 3,
+            scale: 1,
+            locked: false,
+            decorations: None,
+            color_patch: Some(MapPatch {
+                start_x: 4,
+                start_y: 5,
+                width: 2,
+                height: 1,
+                colors: vec![9, 10],
+            }),
+        };
+        let mut buf = Vec::new();
+        packet.write(&mut buf).unwrap();
+        assert_eq!(buf, [3, 1, 0, 0, 2, 1, 4, 5, 2, 9, 10]);
+    }
+
+    #[test]
+    fn test_encode_decorations_without_patch() {
+        let packet = CMapItemData {
+            map_id: 0,
+            scale: 0,
+            locked: true,
+            decorations: Some(vec![MapDecoration {
+                decoration_type: MapDecorationType::PlayerOffMap,
+                x: -128,
+                y: 127,
+                rotation: 18,
+                name: None,
+            }]),
+            color_patch: None,
+        };
+        let mut buf = Vec::new();
+        packet.write(&mut buf).unwrap();
+        assert_eq!(buf, [0, 0, 1, 1, 1, 6, 0x80, 127, 2, 0, 0]);
+    }
+}
@@ -0,0 +1,19 @@
+//! Clientbound packet that moves the vehicle a player controls.
+
+use glam::DVec3;
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_MOVE_VEHICLE;
+
+/// Moves the vehicle of the controlling player, e.g. to undo an invalid move.
+///
+/// Vanilla equivalent: `ClientboundMoveVehiclePacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_MOVE_VEHICLE)]
+pub struct CMoveVehicle {
+    /// The new position of the vehicle.
+    pub position: DVec3,
+    /// The yaw of the vehicle in degrees.
+    pub y_rot: f32,
+    /// The pitch of the vehicle in degrees.
+    pub x_rot: f32,
+}
@@ -0,0 +1,18 @@
+//! Clientbound packet that removes an effect from an entity.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_REMOVE_MOB_EFFECT;
+
+/// Sent when an effect runs out or is removed from an entity.
+///
+/// Vanilla equivalent: `ClientboundRemoveMobEffectPacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_REMOVE_MOB_EFFECT)]
+pub struct CRemoveMobEffect {
+    /// The id of the affected entity.
+    #[write(as = VarInt)]
+    pub entity_id: i32,
+    /// The mob effect registry id.
+    #[write(as = VarInt)]
+    pub effect_id: i32,
+}
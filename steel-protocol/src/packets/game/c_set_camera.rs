@@ -0,0 +1,15 @@
+//! Clientbound packet that moves the client's camera to another entity.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_CAMERA;
+
+/// Makes the client view the world from another entity, as spectators do.
+///
+/// Sending the player's own entity id puts the camera back on the player.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_CAMERA)]
+pub struct CSetCamera {
+    /// The id of the entity to view from.
+    #[write(as = VarInt)]
+    pub camera_id: i32,
+}
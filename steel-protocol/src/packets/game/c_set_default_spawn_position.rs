@@ -0,0 +1,22 @@
+//! Clientbound packet that sets the world spawn point.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_DEFAULT_SPAWN_POSITION;
+use steel_utils::{BlockPos, Identifier};
+
+/// Tells the client where the world spawn is.
+///
+/// Compasses point at this position and the spawn chunks are shown around it.
+/// Vanilla equivalent: `ClientboundSetDefaultSpawnPositionPacket` (a `RespawnData`).
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_DEFAULT_SPAWN_POSITION)]
+pub struct CSetDefaultSpawnPosition {
+    /// The dimension the spawn point is in.
+    pub dimension: Identifier,
+    /// The spawn block position.
+    pub pos: BlockPos,
+    /// The yaw players face when spawning.
+    pub yaw: f32,
+    /// The pitch players face when spawning.
+    pub pitch: f32,
+}
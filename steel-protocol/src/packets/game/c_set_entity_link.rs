@@ -0,0 +1,16 @@
+//! Clientbound packet that attaches a leash between two entities.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_ENTITY_LINK;
+
+/// Attaches the leash of an entity to its holder, or removes it.
+///
+/// Vanilla equivalent: `ClientboundSetEntityLinkPacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_ENTITY_LINK)]
+pub struct CSetEntityLink {
+    /// The id of the leashed entity.
+    pub source_id: i32,
+    /// The id of the leash holder, `0` to remove the leash.
+    pub dest_id: i32,
+}
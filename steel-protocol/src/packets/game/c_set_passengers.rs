@@ -0,0 +1,18 @@
+//! Clientbound packet that sets the passengers of a vehicle.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_PASSENGERS;
+
+/// Replaces the full passenger list of a vehicle on the client.
+///
+/// An empty list dismounts everyone. Vanilla equivalent: `ClientboundSetPassengersPacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_PASSENGERS)]
+pub struct CSetPassengers {
+    /// The id of the vehicle entity.
+    #[write(as = VarInt)]
+    pub vehicle_id: i32,
+    /// The ids of the passengers, the first one being the controlling passenger.
+    #[write(as = Prefixed(VarInt, inner = VarInt))]
+    pub passenger_ids: Vec<i32>,
+}
@@ -0,0 +1,17 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_SUBTITLE_TEXT;
+use text_components::{TextComponent, resolving::TextResolutor};
+
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_SUBTITLE_TEXT)]
+pub struct CSetSubtitleText {
+    pub text: TextComponent,
+}
+
+impl CSetSubtitleText {
+    pub fn new<T: TextResolutor>(text: &TextComponent, player: &T) -> Self {
+        Self {
+            text: text.resolve(player),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SET_TITLES_ANIMATION;
+
+/// Sets how long titles fade in, stay and fade out, in ticks.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_SET_TITLES_ANIMATION)]
+pub struct CSetTitlesAnimation {
+    pub fade_in: i32,
+    pub stay: i32,
+    pub fade_out: i32,
+}
@@ -1,5 +1,10 @@
+use std::io::{Result, Write};
+
 use steel_macros::{ClientPacket, WriteTo};
 use steel_registry::packets::play::C_SOUND;
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::WriteTo;
 
 /// Sound source categories (matches vanilla SoundSource enum order).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,6 +29,64 @@ impl SoundSource {
     pub fn as_varint(self) -> i32 {
         self as i32
     }
+
+    /// Returns the name used in commands and the sound options, e.g. `record`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Master => "master",
+            Self::Music => "music",
+            Self::Records => "record",
+            Self::Weather => "weather",
+            Self::Blocks => "block",
+            Self::Hostile => "hostile",
+            Self::Neutral => "neutral",
+            Self::Players => "player",
+            Self::Ambient => "ambient",
+            Self::Voice => "voice",
+            Self::Ui => "ui",
+        }
+    }
+}
+
+/// The sound a [`CSound`] plays.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoundEvent {
+    /// A sound event registry ID. Use `steel_registry::sound_events` for sound
+    /// constants.
+    Registered(i32),
+    /// A sound event by name, which can also be a sound added by a resource
+    /// pack.
+    Direct {
+        /// The name of the sound event.
+        location: Identifier,
+        /// How far the sound is heard, or `None` to scale with the volume.
+        fixed_range: Option<f32>,
+    },
+}
+
+impl From<i32> for SoundEvent {
+    fn from(sound_id: i32) -> Self {
+        Self::Registered(sound_id)
+    }
+}
+
+/// Written as a registry holder: the registry ID + 1, or 0 followed by the
+/// inline sound event.
+impl WriteTo for SoundEvent {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        match self {
+            Self::Registered(sound_id) => VarInt(sound_id + 1).write(writer),
+            Self::Direct {
+                location,
+                fixed_range,
+            } => {
+                VarInt(0).write(writer)?;
+                location.write(writer)?;
+                fixed_range.write(writer)
+            }
+        }
+    }
 }
 
 /// Sent to play a sound effect at a specific position.
@@ -32,11 +95,10 @@ impl SoundSource {
 /// This allows sub-block positioning for more accurate sound placement.
 #[derive(WriteTo, ClientPacket, Clone, Debug)]
 #[packet_id(Play = C_SOUND)]
+#[low_priority]
 pub struct CSound {
-    /// The sound event registry ID (VarInt).
-    /// Use `steel_registry::sound_events` for sound constants.
-    #[write(as = VarInt)]
-    pub sound_id: i32,
+    /// The sound to play.
+    pub sound: SoundEvent,
     /// The sound source category (VarInt).
     #[write(as = VarInt)]
     pub source: i32,
@@ -58,7 +120,7 @@ impl CSound {
     /// Creates a new sound packet.
     ///
     /// # Arguments
-    /// * `sound_id` - Sound event registry ID
+    /// * `sound` - Sound event registry ID or [`SoundEvent`]
     /// * `source` - Sound source category
     /// * `x`, `y`, `z` - Position in block coordinates (will be scaled by 8)
     /// * `volume` - Volume multiplier (1.0 = normal)
@@ -67,7 +129,7 @@ impl CSound {
     #[must_use]
     #[expect(clippy::too_many_arguments)]
     pub fn new(
-        sound_id: i32,
+        sound: impl Into<SoundEvent>,
         source: SoundSource,
         x: f64,
         y: f64,
@@ -77,7 +139,7 @@ impl CSound {
         seed: i64,
     ) -> Self {
         Self {
-            sound_id,
+            sound: sound.into(),
             source: source.as_varint(),
             x: (x * 8.0) as i32,
             y: (y * 8.0) as i32,
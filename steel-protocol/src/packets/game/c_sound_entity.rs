@@ -0,0 +1,52 @@
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_SOUND_ENTITY;
+
+use super::SoundSource;
+
+/// Sent to play a sound effect that follows an entity.
+///
+/// Unlike [`super::CSound`], the client keeps the sound at the entity's position
+/// while it plays.
+#[derive(WriteTo, ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_SOUND_ENTITY)]
+#[low_priority]
+pub struct CSoundEntity {
+    /// The sound event registry ID, written as a registry holder (id + 1).
+    /// Use `steel_registry::sound_events` for sound constants.
+    #[write(as = RegistryHolder)]
+    pub sound_id: i32,
+    /// The sound source category (VarInt).
+    #[write(as = VarInt)]
+    pub source: i32,
+    /// The network ID of the entity the sound follows.
+    #[write(as = VarInt)]
+    pub entity_id: i32,
+    /// Volume (1.0 = normal).
+    pub volume: f32,
+    /// Pitch (1.0 = normal).
+    pub pitch: f32,
+    /// Random seed for sound variations.
+    pub seed: i64,
+}
+
+impl CSoundEntity {
+    /// Creates a new entity sound packet.
+    #[must_use]
+    pub fn new(
+        sound_id: i32,
+        source: SoundSource,
+        entity_id: i32,
+        volume: f32,
+        pitch: f32,
+        seed: i64,
+    ) -> Self {
+        Self {
+            sound_id,
+            source: source.as_varint(),
+            entity_id,
+            volume,
+            pitch,
+            seed,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::io::{Result, Write};
+
+use steel_macros::ClientPacket;
+use steel_registry::packets::play::C_STOP_SOUND;
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::WriteTo;
+
+use super::SoundSource;
+
+/// Stops sounds playing on the client.
+///
+/// Without a source or name every sound stops, otherwise only the sounds
+/// matching both.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_STOP_SOUND)]
+pub struct CStopSound {
+    /// Only stop sounds playing in this source.
+    pub source: Option<SoundSource>,
+    /// Only stop the sound event with this name.
+    pub name: Option<Identifier>,
+}
+
+impl WriteTo for CStopSound {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        let mut flags = 0u8;
+        if self.source.is_some() {
+            flags |= 1;
+        }
+        if self.name.is_some() {
+            flags |= 2;
+        }
+        flags.write(writer)?;
+        if let Some(source) = self.source {
+            VarInt(source.as_varint()).write(writer)?;
+        }
+        if let Some(name) = &self.name {
+            name.write(writer)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! Clientbound packet that updates the attributes of an entity.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_UPDATE_ATTRIBUTES;
+use steel_utils::Identifier;
+
+/// Sends the base value and modifiers of some of an entity's attributes.
+///
+/// Only syncable attributes are sent. Vanilla equivalent: `ClientboundUpdateAttributesPacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_UPDATE_ATTRIBUTES)]
+pub struct CUpdateAttributes {
+    /// The id of the entity.
+    #[write(as = VarInt)]
+    pub entity_id: i32,
+    /// The attributes that changed, each replacing the client's copy.
+    #[write(as = Prefixed(VarInt))]
+    pub attributes: Vec<AttributeSnapshot>,
+}
+
+/// The full state of one attribute.
+#[derive(WriteTo, Clone, Debug)]
+pub struct AttributeSnapshot {
+    /// The attribute's registry id.
+    #[write(as = VarInt)]
+    pub attribute_id: i32,
+    /// The base value before modifiers.
+    pub base: f64,
+    /// The modifiers applied on top of the base value.
+    #[write(as = Prefixed(VarInt))]
+    pub modifiers: Vec<AttributeModifierSnapshot>,
+}
+
+/// One modifier of an attribute.
+#[derive(WriteTo, Clone, Debug)]
+pub struct AttributeModifierSnapshot {
+    /// The modifier's id, unique per attribute.
+    pub id: Identifier,
+    /// The amount the modifier changes the value by.
+    pub amount: f64,
+    /// The operation id: 0 = add value, 1 = add multiplied base, 2 = add multiplied total.
+    #[write(as = VarInt)]
+    pub operation: i32,
+}
@@ -0,0 +1,37 @@
+//! Clientbound packet that adds or updates an effect on an entity.
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_UPDATE_MOB_EFFECT;
+
+/// Sent when an entity gains an effect or an active effect changes.
+///
+/// Vanilla equivalent: `ClientboundUpdateMobEffectPacket`.
+#[derive(ClientPacket, WriteTo, Clone, Debug)]
+#[packet_id(Play = C_UPDATE_MOB_EFFECT)]
+pub struct CUpdateMobEffect {
+    /// The id of the affected entity.
+    #[write(as = VarInt)]
+    pub entity_id: i32,
+    /// The mob effect registry id.
+    #[write(as = VarInt)]
+    pub effect_id: i32,
+    /// The effect level minus one.
+    #[write(as = VarInt)]
+    pub amplifier: i32,
+    /// Remaining ticks, `-1` for infinite effects.
+    #[write(as = VarInt)]
+    pub duration: i32,
+    /// Bit field of the `FLAG_*` constants.
+    pub flags: u8,
+}
+
+impl CUpdateMobEffect {
+    /// The effect comes from a beacon or conduit.
+    pub const FLAG_AMBIENT: u8 = 0x01;
+    /// The effect shows particles.
+    pub const FLAG_VISIBLE: u8 = 0x02;
+    /// The effect shows an icon in the HUD.
+    pub const FLAG_SHOW_ICON: u8 = 0x04;
+    /// The client should blend the effect in (used by darkness).
+    pub const FLAG_BLEND: u8 = 0x08;
+}
@@ -0,0 +1,188 @@
+//! Packet sent to add, remove or move a waypoint on the client's locator bar.
+
+use std::io::{Result, Write};
+
+use steel_macros::{ClientPacket, WriteTo};
+use steel_registry::packets::play::C_WAYPOINT;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{PrefixedWrite, WriteTo};
+use steel_utils::{ChunkPos, Identifier};
+use uuid::Uuid;
+
+/// What the client should do with the waypoint (matches vanilla `Operation` order).
+#[derive(WriteTo, Copy, Clone, Debug, PartialEq, Eq)]
+#[write(as = VarInt)]
+pub enum WaypointOperation {
+    /// Starts showing a new waypoint.
+    Track = 0,
+    /// Stops showing a waypoint.
+    Untrack = 1,
+    /// Moves a waypoint that is already shown.
+    Update = 2,
+}
+
+/// Identifies a waypoint; entity waypoints use the entity's UUID.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WaypointId {
+    /// The UUID of the entity the waypoint follows.
+    Uuid(Uuid),
+    /// A custom name, for waypoints that don't belong to an entity.
+    Name(String),
+}
+
+/// How the waypoint is drawn on the locator bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaypointIcon {
+    /// The waypoint style asset, e.g. `minecraft:default` or `minecraft:bowtie`.
+    pub style: Identifier,
+    /// RGB color. The client picks a color from the id when absent.
+    pub color: Option<u32>,
+}
+
+impl Default for WaypointIcon {
+    fn default() -> Self {
+        Self {
+            style: Identifier::vanilla_static("default"),
+            color: None,
+        }
+    }
+}
+
+/// Where the waypoint is, at the precision the receiver is allowed to know.
+///
+/// Vanilla equivalent: `TrackedWaypoint.Type`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WaypointPosition {
+    /// No position, used when untracking.
+    Empty,
+    /// An exact block position, used while the source is in view distance.
+    Block(i32, i32, i32),
+    /// Only the chunk, used for sources a bit further away.
+    Chunk(ChunkPos),
+    /// Only the direction (yaw angle in radians), used for far away sources.
+    Azimuth(f32),
+}
+
+impl WaypointPosition {
+    const fn type_id(self) -> i32 {
+        match self {
+            Self::Empty => 0,
+            Self::Block(..) => 1,
+            Self::Chunk(_) => 2,
+            Self::Azimuth(_) => 3,
+        }
+    }
+}
+
+/// Adds, removes or moves a waypoint on the locator bar.
+#[derive(ClientPacket, Clone, Debug)]
+#[packet_id(Play = C_WAYPOINT)]
+pub struct CWaypoint {
+    /// What to do with the waypoint.
+    pub operation: WaypointOperation,
+    /// Which waypoint this is.
+    pub id: WaypointId,
+    /// How the waypoint is drawn.
+    pub icon: WaypointIcon,
+    /// Where the waypoint is.
+    pub position: WaypointPosition,
+}
+
+impl CWaypoint {
+    /// Creates a packet that removes the waypoint with the given id.
+    #[must_use]
+    pub fn untrack(id: WaypointId) -> Self {
+        Self {
+            operation: WaypointOperation::Untrack,
+            id,
+            icon: WaypointIcon::default(),
+            position: WaypointPosition::Empty,
+        }
+    }
+}
+
+impl WriteTo for CWaypoint {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        self.operation.write(writer)?;
+
+        // Either<UUID, String>: a boolean that is true for the UUID side
+        match &self.id {
+            WaypointId::Uuid(uuid) => {
+                true.write(writer)?;
+                uuid.write(writer)?;
+            }
+            WaypointId::Name(name) => {
+                false.write(writer)?;
+                name.write_prefixed::<VarInt>(writer)?;
+            }
+        }
+
+        self.icon.style.write(writer)?;
+        self.icon.color.is_some().write(writer)?;
+        if let Some(color) = self.icon.color {
+            // RGB_COLOR: three unsigned bytes
+            ((color >> 16) as u8).write(writer)?;
+            ((color >> 8) as u8).write(writer)?;
+            (color as u8).write(writer)?;
+        }
+
+        VarInt(self.position.type_id()).write(writer)?;
+        match self.position {
+            WaypointPosition::Empty => {}
+            WaypointPosition::Block(x, y, z) => {
+                VarInt(x).write(writer)?;
+                VarInt(y).write(writer)?;
+                VarInt(z).write(writer)?;
+            }
+            WaypointPosition::Chunk(pos) => {
+                VarInt(pos.0.x).write(writer)?;
+                VarInt(pos.0.y).write(writer)?;
+            }
+            WaypointPosition::Azimuth(angle) => angle.write(writer)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_named_chunk_waypoint() {
+        let packet = CWaypoint {
+            operation: WaypointOperation::Track,
+            id: WaypointId::Name("home".to_owned()),
+            icon: WaypointIcon {
+                style: Identifier::vanilla_static("bowtie"),
+                color: Some(0x12_34_56),
+            },
+            position: WaypointPosition::Chunk(ChunkPos::new(-1, 2)),
+        };
+        let mut buf = Vec::new();
+        packet.write(&mut buf).unwrap();
+
+        let mut expected = vec![0, 0, 4];
+        expected.extend_from_slice(b"home");
+        expected.push(16);
+        expected.extend_from_slice(b"minecraft:bowtie");
+        expected.extend_from_slice(&[1, 0x12, 0x34, 0x56, 2]);
+        // VarInt(-1) is five bytes
+        expected.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 2]);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_encode_untrack() {
+        let uuid = Uuid::from_u64_pair(1, 2);
+        let mut buf = Vec::new();
+        CWaypoint::untrack(WaypointId::Uuid(uuid))
+            .write(&mut buf)
+            .unwrap();
+        assert_eq!(buf[0], 1);
+        assert_eq!(buf[1], 1);
+        assert_eq!(&buf[2..18], uuid.as_bytes());
+        // Default style, no color, empty type
+        assert_eq!(&buf[buf.len() - 2..], &[0, 0]);
+    }
+}
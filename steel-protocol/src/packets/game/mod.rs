@@ -5,15 +5,18 @@ mod c_block_destruction;
 mod c_block_entity_data;
 mod c_block_event;
 mod c_block_update;
+mod c_boss_event;
 mod c_bundle_delimiter;
 mod c_chunk_batch_finished;
 mod c_chunk_batch_start;
+mod c_clear_titles;
 mod c_command_suggestions;
 mod c_commands;
 mod c_container_close;
 mod c_container_set_content;
 mod c_container_set_data;
 mod c_container_set_slot;
+mod c_cooldown;
 mod c_damage_event;
 mod c_disguised_chat;
 mod c_entity_event;
@@ -24,7 +27,9 @@ mod c_hurt_animation;
 mod c_level_chunk_with_light;
 mod c_level_event;
 mod c_login;
+mod c_map_item_data;
 mod c_move_entity;
+mod c_move_vehicle;
 mod c_open_screen;
 mod c_open_sign_editor;
 mod c_player_abilities;
@@ -33,26 +38,41 @@ mod c_player_combat_kill;
 mod c_player_info_update;
 mod c_player_position;
 mod c_remove_entities;
+mod c_remove_mob_effect;
 mod c_remove_player_info;
 mod c_respawn;
 mod c_rotate_head;
 mod c_section_blocks_update;
+mod c_set_action_bar_text;
+mod c_set_camera;
 mod c_set_chunk_cache_radius;
 mod c_set_chunk_center;
 mod c_set_cursor_item;
+mod c_set_default_spawn_position;
 mod c_set_entity_data;
+mod c_set_entity_link;
 mod c_set_entity_motion;
 mod c_set_experience;
 mod c_set_health;
 mod c_set_held_slot;
+mod c_set_passengers;
+mod c_set_simulation_distance;
+mod c_set_subtitle_text;
 mod c_set_time;
+mod c_set_title_text;
+mod c_set_titles_animation;
 mod c_sound;
+mod c_sound_entity;
+mod c_stop_sound;
 mod c_system_chat;
 mod c_system_chat_message;
 mod c_tab_list;
 mod c_take_item_entity;
 mod c_ticking_state;
 mod c_ticking_step;
+mod c_update_attributes;
+mod c_update_mob_effect;
+mod c_waypoint;
 mod chat_session_data;
 mod s_accept_teleportation;
 mod s_change_game_mode;
@@ -69,12 +89,19 @@ mod s_container_button_click;
 mod s_container_click;
 mod s_container_close;
 mod s_container_slot_state_changed;
+mod s_interact;
 mod s_move_player;
+mod s_move_vehicle;
+mod s_paddle_boat;
 mod s_pick_item_from_block;
+mod s_pick_item_from_entity;
 mod s_player_abilities;
 mod s_player_action;
+mod s_player_command;
 mod s_player_input;
 mod s_player_load;
+mod s_rename_item;
+mod s_set_beacon;
 mod s_set_carried_item;
 mod s_set_creative_mode_slot;
 mod s_set_held_item;
@@ -83,22 +110,28 @@ mod s_swing;
 mod s_use_item;
 mod s_use_item_on;
 
-pub use c_add_entity::{CAddEntity, write_lp_vec3};
+pub use c_add_entity::{CAddEntity, read_lp_vec3, write_lp_vec3};
 pub use c_animate::{AnimateAction, CAnimate};
 pub use c_block_changed_ack::CBlockChangedAck;
 pub use c_block_destruction::CBlockDestruction;
 pub use c_block_entity_data::CBlockEntityData;
 pub use c_block_event::CBlockEvent;
 pub use c_block_update::CBlockUpdate;
+pub use c_boss_event::{BossBarColor, BossBarOverlay, BossEventOperation, CBossEvent};
 pub use c_bundle_delimiter::CBundleDelimiter;
 pub use c_chunk_batch_finished::CChunkBatchFinished;
 pub use c_chunk_batch_start::CChunkBatchStart;
+pub use c_clear_titles::CClearTitles;
 pub use c_command_suggestions::{CCommandSuggestions, SuggestionEntry};
-pub use c_commands::{ArgumentType, CCommands, CommandNode, CommandNodeInfo, SuggestionType};
+pub use c_commands::{
+    ArgumentStringTypeBehavior, ArgumentType, CCommands, CommandNode, CommandNodeInfo,
+    SuggestionType,
+};
 pub use c_container_close::CContainerClose;
 pub use c_container_set_content::CContainerSetContent;
 pub use c_container_set_data::CContainerSetData;
 pub use c_container_set_slot::CContainerSetSlot;
+pub use c_cooldown::CCooldown;
 pub use c_damage_event::CDamageEvent;
 pub use c_disguised_chat::CDisguisedChat;
 pub use c_entity_event::CEntityEvent;
@@ -114,9 +147,11 @@ pub use c_level_chunk_with_light::{
 pub use c_level_event::CLevelEvent;
 pub use c_login::CLogin;
 pub use c_login::CommonPlayerSpawnInfo;
+pub use c_map_item_data::{CMapItemData, MapDecoration, MapDecorationType, MapPatch};
 pub use c_move_entity::{
     CMoveEntityPos, CMoveEntityPosRot, CMoveEntityRot, calc_delta, to_angle_byte,
 };
+pub use c_move_vehicle::CMoveVehicle;
 pub use c_open_screen::COpenScreen;
 pub use c_open_sign_editor::COpenSignEditor;
 pub use c_player_abilities::{CPlayerAbilities, ability_flags};
@@ -127,26 +162,41 @@ pub use c_player_info_update::{
 };
 pub use c_player_position::{CPlayerPosition, RelativeMovement};
 pub use c_remove_entities::CRemoveEntities;
+pub use c_remove_mob_effect::CRemoveMobEffect;
 pub use c_remove_player_info::CRemovePlayerInfo;
 pub use c_respawn::CRespawn;
 pub use c_rotate_head::CRotateHead;
 pub use c_section_blocks_update::{BlockChange, CSectionBlocksUpdate};
+pub use c_set_action_bar_text::CSetActionBarText;
+pub use c_set_camera::CSetCamera;
 pub use c_set_chunk_cache_radius::CSetChunkCacheRadius;
 pub use c_set_chunk_center::CSetChunkCenter;
 pub use c_set_cursor_item::CSetCursorItem;
+pub use c_set_default_spawn_position::CSetDefaultSpawnPosition;
 pub use c_set_entity_data::CSetEntityData;
+pub use c_set_entity_link::CSetEntityLink;
 pub use c_set_entity_motion::CSetEntityMotion;
 pub use c_set_experience::CSetExperience;
 pub use c_set_health::CSetHealth;
 pub use c_set_held_slot::CSetHeldSlot;
+pub use c_set_passengers::CSetPassengers;
+pub use c_set_simulation_distance::CSetSimulationDistance;
+pub use c_set_subtitle_text::CSetSubtitleText;
 pub use c_set_time::CSetTime;
-pub use c_sound::{CSound, SoundSource};
+pub use c_set_title_text::CSetTitleText;
+pub use c_set_titles_animation::CSetTitlesAnimation;
+pub use c_sound::{CSound, SoundEvent, SoundSource};
+pub use c_sound_entity::CSoundEntity;
+pub use c_stop_sound::CStopSound;
 pub use c_system_chat::CSystemChat;
 pub use c_system_chat_message::CSystemChatMessage;
 pub use c_tab_list::CTabList;
 pub use c_take_item_entity::CTakeItemEntity;
 pub use c_ticking_state::CTickingState;
 pub use c_ticking_step::CTickingStep;
+pub use c_update_attributes::{AttributeModifierSnapshot, AttributeSnapshot, CUpdateAttributes};
+pub use c_update_mob_effect::CUpdateMobEffect;
+pub use c_waypoint::{CWaypoint, WaypointIcon, WaypointId, WaypointOperation, WaypointPosition};
 pub use chat_session_data::ProtocolRemoteChatSessionData;
 pub use s_accept_teleportation::SAcceptTeleportation;
 pub use s_change_game_mode::SChangeGameMode;
@@ -163,14 +213,21 @@ pub use s_container_button_click::SContainerButtonClick;
 pub use s_container_click::{ClickType, HashedPatchMap, HashedStack, SContainerClick};
 pub use s_container_close::SContainerClose;
 pub use s_container_slot_state_changed::SContainerSlotStateChanged;
+pub use s_interact::SInteract;
 pub use s_move_player::{
     SMovePlayer, SMovePlayerPos, SMovePlayerPosRot, SMovePlayerRot, SMovePlayerStatusOnly,
 };
+pub use s_move_vehicle::SMoveVehicle;
+pub use s_paddle_boat::SPaddleBoat;
 pub use s_pick_item_from_block::SPickItemFromBlock;
+pub use s_pick_item_from_entity::SPickItemFromEntity;
 pub use s_player_abilities::SPlayerAbilities;
 pub use s_player_action::{PlayerAction, SPlayerAction};
+pub use s_player_command::{PlayerCommandAction, SPlayerCommand};
 pub use s_player_input::SPlayerInput;
 pub use s_player_load::SPlayerLoad;
+pub use s_rename_item::SRenameItem;
+pub use s_set_beacon::SSetBeacon;
 pub use s_set_carried_item::SSetCarriedItem;
 pub use s_set_creative_mode_slot::SSetCreativeModeSlot;
 pub use s_set_held_item::SSetHeldItem;
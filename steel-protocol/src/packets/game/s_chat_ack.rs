@@ -2,7 +2,7 @@ use std::io::Cursor;
 
 use steel_macros::ServerPacket;
 use steel_utils::codec::VarInt;
-use steel_utils::serial::ReadFrom;
+use steel_utils::serial::{ReadError, ReadFrom};
 
 /// Client -> Server: Acknowledges messages received from the server.
 ///
@@ -17,7 +17,7 @@ pub struct SChatAck {
 }
 
 impl ReadFrom for SChatAck {
-    fn read(reader: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(reader: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self {
             offset: VarInt::read(reader)?,
         })
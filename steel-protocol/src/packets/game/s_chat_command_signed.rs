@@ -2,7 +2,7 @@ use std::io::{Cursor, Read};
 
 use steel_macros::ServerPacket;
 use steel_utils::codec::VarInt;
-use steel_utils::serial::{PrefixedRead, ReadFrom};
+use steel_utils::serial::{PrefixedRead, ReadError, ReadFrom};
 
 /// Client -> Server: Executes a signed command.
 ///
@@ -29,22 +29,12 @@ pub struct SChatCommandSigned {
 }
 
 impl ReadFrom for SChatCommandSigned {
-    fn read(reader: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(reader: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let command = String::read_prefixed_bound::<VarInt>(reader, 256)?;
         let timestamp = i64::read(reader)?;
         let salt = i64::read(reader)?;
 
-        let arg_count = VarInt::read(reader)?.0 as usize;
-        if arg_count > 8 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Too many argument signatures",
-            ));
-        }
-        let mut argument_signatures = Vec::with_capacity(arg_count);
-        for _ in 0..arg_count {
-            argument_signatures.push(ArgumentSignature::read(reader)?);
-        }
+        let argument_signatures = Vec::read_prefixed_bound::<VarInt>(reader, 8)?;
 
         let last_seen = LastSeenMessagesUpdate::read(reader)?;
 
@@ -69,7 +59,7 @@ pub struct ArgumentSignature {
 }
 
 impl ReadFrom for ArgumentSignature {
-    fn read(reader: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(reader: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         // Read argument name (max 16 chars)
         let name = String::read_prefixed_bound::<VarInt>(reader, 16)?;
 
@@ -90,17 +80,22 @@ pub struct LastSeenMessagesUpdate {
     /// BitSet indicating which of the last 20 messages were acknowledged
     /// 3 bytes = 24 bits (using 20)
     pub acknowledged: [u8; 3],
+
+    /// Checksum of the acknowledged signatures (0 = not checked)
+    pub checksum: u8,
 }
 
 impl ReadFrom for LastSeenMessagesUpdate {
-    fn read(reader: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(reader: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let offset = VarInt::read(reader)?;
         let mut acknowledged = [0u8; 3];
         reader.read_exact(&mut acknowledged)?;
+        let checksum = u8::read(reader)?;
 
         Ok(Self {
             offset,
             acknowledged,
+            checksum,
         })
     }
 }
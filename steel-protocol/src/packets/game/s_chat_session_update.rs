@@ -1,9 +1,15 @@
-use std::io::{Cursor, Read};
+use std::io::Cursor;
 
 use steel_macros::ServerPacket;
-use steel_utils::serial::ReadFrom;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{PrefixedRead, ReadError, ReadFrom};
 use uuid::Uuid;
 
+/// Maximum size of the encoded public key (`ProfilePublicKey.Data.MAX_KEY_BYTES`).
+const MAX_KEY_BYTES: usize = 512;
+/// Maximum size of Mojang's key signature (`ProfilePublicKey.Data.MAX_KEY_SIGNATURE_BYTES`).
+const MAX_KEY_SIGNATURE_BYTES: usize = 4096;
+
 /// Client -> Server: Updates the player's chat session with their public key.
 ///
 /// Sent when the player first joins or when their key needs to be updated.
@@ -26,17 +32,12 @@ pub struct SChatSessionUpdate {
 }
 
 impl ReadFrom for SChatSessionUpdate {
-    fn read(reader: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(reader: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let session_id = Uuid::read(reader)?;
         let expires_at = i64::read(reader)?;
 
-        let key_len = steel_utils::codec::VarInt::read(reader)?.0 as usize;
-        let mut public_key = vec![0u8; key_len];
-        reader.read_exact(&mut public_key)?;
-
-        let sig_len = steel_utils::codec::VarInt::read(reader)?.0 as usize;
-        let mut key_signature = vec![0u8; sig_len];
-        reader.read_exact(&mut key_signature)?;
+        let public_key = Vec::read_prefixed_bound::<VarInt>(reader, MAX_KEY_BYTES)?;
+        let key_signature = Vec::read_prefixed_bound::<VarInt>(reader, MAX_KEY_SIGNATURE_BYTES)?;
 
         Ok(Self {
             session_id,
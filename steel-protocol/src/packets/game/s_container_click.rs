@@ -1,8 +1,22 @@
-use std::io::{Cursor, Result};
+use std::io::Cursor;
 
 use rustc_hash::FxHashMap;
 use steel_macros::ServerPacket;
-use steel_utils::{codec::VarInt, serial::ReadFrom};
+use steel_utils::{
+    codec::VarInt,
+    serial::{ReadError, ReadFrom},
+};
+
+/// Maximum number of entries in each half of a [`HashedPatchMap`].
+const MAX_HASHED_COMPONENTS: usize = 256;
+/// Maximum number of changed slots in a single [`SContainerClick`].
+const MAX_CHANGED_SLOTS: usize = 128;
+
+/// Reads a `VarInt` collection size, rejecting negative or oversized counts like vanilla does.
+fn read_count(data: &mut Cursor<&[u8]>, bound: usize) -> Result<usize, ReadError> {
+    let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+    ReadError::check_len(count, bound)
+}
 
 /// The type of click action performed on a container slot.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,7 +32,7 @@ pub enum ClickType {
 }
 
 impl ReadFrom for ClickType {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let id = VarInt::read(data)?.0;
         Ok(match id {
             0 => ClickType::Pickup,
@@ -42,20 +56,20 @@ pub struct HashedPatchMap {
 }
 
 impl ReadFrom for HashedPatchMap {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         // Read added components map: Map<VarInt, Int>
-        let added_count = VarInt::read(data)?.0 as usize;
+        let added_count = read_count(data, MAX_HASHED_COMPONENTS)?;
         let mut added_components = FxHashMap::default();
-        for _ in 0..added_count.min(256) {
+        for _ in 0..added_count {
             let type_id = VarInt::read(data)?.0;
             let hash = i32::read(data)?;
             added_components.insert(type_id, hash);
         }
 
         // Read removed components set: Collection<VarInt>
-        let removed_count = VarInt::read(data)?.0 as usize;
-        let mut removed_components = Vec::with_capacity(removed_count.min(256));
-        for _ in 0..removed_count.min(256) {
+        let removed_count = read_count(data, MAX_HASHED_COMPONENTS)?;
+        let mut removed_components = Vec::with_capacity(removed_count);
+        for _ in 0..removed_count {
             let type_id = VarInt::read(data)?.0;
             removed_components.push(type_id);
         }
@@ -80,7 +94,7 @@ pub enum HashedStack {
 }
 
 impl ReadFrom for HashedStack {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         // Optional<ActualItem> - bool prefix
         let present = bool::read(data)?;
         if !present {
@@ -113,17 +127,16 @@ pub struct SContainerClick {
 }
 
 impl ReadFrom for SContainerClick {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let container_id = VarInt::read(data)?.0;
         let state_id = VarInt::read(data)?.0;
         let slot_num = i16::read(data)?;
         let button_num = i8::read(data)?;
         let click_type = ClickType::read(data)?;
 
-        // Read changed slots map with max 128 entries
-        let slot_count = VarInt::read(data)?.0 as usize;
+        let slot_count = read_count(data, MAX_CHANGED_SLOTS)?;
         let mut changed_slots = FxHashMap::default();
-        for _ in 0..slot_count.min(128) {
+        for _ in 0..slot_count {
             let slot = i16::read(data)?;
             let stack = HashedStack::read(data)?;
             changed_slots.insert(slot, stack);
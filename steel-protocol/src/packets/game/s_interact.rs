@@ -0,0 +1,37 @@
+//! Serverbound packet sent when a player right-clicks an entity.
+
+use std::io::Cursor;
+
+use glam::DVec3;
+use steel_macros::ServerPacket;
+use steel_utils::codec::VarInt;
+use steel_utils::serial::{ReadError, ReadFrom};
+use steel_utils::types::InteractionHand;
+
+use super::read_lp_vec3;
+
+/// Sent when a player uses an entity (right-click).
+///
+/// Attacks are sent separately. Vanilla equivalent: `ServerboundInteractPacket`.
+#[derive(ServerPacket, Clone, Debug)]
+pub struct SInteract {
+    /// The id of the clicked entity.
+    pub entity_id: i32,
+    /// The hand used to interact.
+    pub hand: InteractionHand,
+    /// Where the entity was clicked, relative to its position.
+    pub location: DVec3,
+    /// Whether the player was sneaking.
+    pub using_secondary_action: bool,
+}
+
+impl ReadFrom for SInteract {
+    fn read(reader: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        Ok(Self {
+            entity_id: VarInt::read(reader)?.0,
+            hand: InteractionHand::read(reader)?,
+            location: read_lp_vec3(reader)?,
+            using_secondary_action: bool::read(reader)?,
+        })
+    }
+}
@@ -0,0 +1,13 @@
+use glam::DVec3;
+use steel_macros::{ReadFrom, ServerPacket};
+
+/// Serverbound packet sent by the client controlling a vehicle with its new position.
+///
+/// Vanilla equivalent: `ServerboundMoveVehiclePacket`.
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SMoveVehicle {
+    pub position: DVec3,
+    pub y_rot: f32,
+    pub x_rot: f32,
+    pub on_ground: bool,
+}
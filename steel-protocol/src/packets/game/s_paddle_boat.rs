@@ -0,0 +1,10 @@
+use steel_macros::{ReadFrom, ServerPacket};
+
+/// Serverbound packet sent when the paddles of a ridden boat start or stop moving.
+///
+/// Vanilla equivalent: `ServerboundPaddleBoatPacket`.
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SPaddleBoat {
+    pub left: bool,
+    pub right: bool,
+}
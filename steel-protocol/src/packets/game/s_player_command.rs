@@ -0,0 +1,39 @@
+//! Serverbound player command packet - sent when the player starts or stops sprinting,
+//! leaves a bed, starts gliding and similar.
+
+use steel_macros::{ReadFrom, ServerPacket};
+
+/// The action the player performed.
+#[derive(ReadFrom, Clone, Copy, Debug, PartialEq, Eq)]
+#[read(as = VarInt)]
+pub enum PlayerCommandAction {
+    /// The player clicked "Leave Bed".
+    StopSleeping = 0,
+    /// The player started sprinting.
+    StartSprinting = 1,
+    /// The player stopped sprinting.
+    StopSprinting = 2,
+    /// The player started charging a jump on a horse-like vehicle.
+    StartRidingJump = 3,
+    /// The player released the jump key while charging a vehicle jump.
+    StopRidingJump = 4,
+    /// The player opened the inventory of the vehicle they're riding.
+    OpenInventory = 5,
+    /// The player pressed jump in mid-air while wearing an elytra.
+    StartFallFlying = 6,
+}
+
+/// Sent by the client when the player performs one of the actions in [`PlayerCommandAction`].
+///
+/// Vanilla equivalent: `ServerboundPlayerCommandPacket`.
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SPlayerCommand {
+    /// The id of the player's entity.
+    #[read(as = VarInt)]
+    pub entity_id: i32,
+    /// The action the player performed.
+    pub action: PlayerCommandAction,
+    /// The jump strength (0-100) for `StopRidingJump`, zero otherwise.
+    #[read(as = VarInt)]
+    pub data: i32,
+}
@@ -0,0 +1,12 @@
+use steel_macros::{ReadFrom, ServerPacket};
+use steel_utils::codec::VarInt;
+
+/// Serverbound packet sent when a player types a new name into an anvil menu.
+///
+/// Vanilla equivalent: `ServerboundRenameItemPacket`.
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SRenameItem {
+    /// The name typed by the player.
+    #[read(as = Prefixed(VarInt))]
+    pub name: String,
+}
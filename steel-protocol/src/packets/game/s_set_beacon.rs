@@ -0,0 +1,13 @@
+use steel_macros::{ReadFrom, ServerPacket};
+use steel_utils::codec::VarInt;
+
+/// Serverbound packet sent when a player confirms the effects in a beacon menu.
+///
+/// Vanilla equivalent: `ServerboundSetBeaconPacket`.
+#[derive(ReadFrom, ServerPacket, Clone, Debug)]
+pub struct SSetBeacon {
+    /// The mob effect registry id of the primary effect.
+    pub primary: Option<VarInt>,
+    /// The mob effect registry id of the secondary effect.
+    pub secondary: Option<VarInt>,
+}
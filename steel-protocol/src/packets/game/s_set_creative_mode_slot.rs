@@ -1,6 +1,6 @@
 use steel_macros::ServerPacket;
 use steel_registry::item_stack::ItemStack;
-use steel_utils::serial::ReadFrom;
+use steel_utils::serial::{ReadError, ReadFrom};
 
 /// Creative mode slot packet uses the delimited (untrusted) item format
 /// where each component value is prefixed with a VarInt byte length.
@@ -11,7 +11,7 @@ pub struct SSetCreativeModeSlot {
 }
 
 impl ReadFrom for SSetCreativeModeSlot {
-    fn read(data: &mut std::io::Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(data: &mut std::io::Cursor<&[u8]>) -> Result<Self, ReadError> {
         let slot_num = i16::read(data)?;
         let item_stack = ItemStack::read_untrusted(data)?;
         Ok(Self {
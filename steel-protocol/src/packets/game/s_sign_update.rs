@@ -2,7 +2,7 @@ use std::io::Cursor;
 
 use steel_macros::ServerPacket;
 use steel_utils::BlockPos;
-use steel_utils::serial::{PrefixedRead, ReadFrom};
+use steel_utils::serial::{PrefixedRead, ReadError, ReadFrom};
 
 /// Maximum characters per sign line.
 pub const MAX_SIGN_LINE_LENGTH: usize = 384;
@@ -19,7 +19,7 @@ pub struct SSignUpdate {
 }
 
 impl ReadFrom for SSignUpdate {
-    fn read(data: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         use steel_utils::codec::VarInt;
 
         let pos = BlockPos::read(data)?;
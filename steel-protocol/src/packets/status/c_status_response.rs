@@ -1,4 +1,5 @@
 use serde::Serialize;
+use serde_json::Value;
 use steel_macros::{ClientPacket, WriteTo};
 use steel_registry::packets::status::C_STATUS_RESPONSE;
 
@@ -25,7 +26,7 @@ pub struct Version {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Status {
-    pub description: &'static str,
+    pub description: Value,
     pub players: Option<Players>,
     pub version: Option<Version>,
     pub favicon: Option<String>,
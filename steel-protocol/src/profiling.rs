@@ -0,0 +1,111 @@
+//! # Steel Protocol Profiling
+//!
+//! Counters for the packet encoding path, compiled in with the `profiling` feature.
+//!
+//! Without the feature every `record_*` function is an empty inline function and
+//! [`snapshot`] returns zeroes, so call sites don't need their own `cfg` attributes.
+
+#[cfg(feature = "profiling")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Whether the counters are compiled in.
+pub const ENABLED: bool = cfg!(feature = "profiling");
+
+/// Totals of the packet encoding path since startup (or the last [`reset`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PacketCounters {
+    /// Packets encoded.
+    pub packets_encoded: u64,
+    /// Bytes of encoded packets, after compression.
+    pub bytes_encoded: u64,
+    /// Packets that were large enough to get compressed.
+    pub packets_compressed: u64,
+    /// Buffers allocated while encoding.
+    pub buffer_allocations: u64,
+    /// Bytes allocated for those buffers, including growth.
+    pub buffer_allocated_bytes: u64,
+}
+
+#[cfg(feature = "profiling")]
+static PACKETS_ENCODED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static BYTES_ENCODED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static PACKETS_COMPRESSED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static BUFFER_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "profiling")]
+static BUFFER_ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Records an encoded packet of `len` bytes.
+#[cfg(feature = "profiling")]
+#[inline]
+pub fn record_packet(len: usize, compressed: bool) {
+    PACKETS_ENCODED.fetch_add(1, Ordering::Relaxed);
+    BYTES_ENCODED.fetch_add(len as u64, Ordering::Relaxed);
+    if compressed {
+        PACKETS_COMPRESSED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Records an encoded packet of `len` bytes.
+#[cfg(not(feature = "profiling"))]
+#[inline]
+pub const fn record_packet(_len: usize, _compressed: bool) {}
+
+/// Records a buffer allocated while encoding, `bytes` being its final capacity.
+#[cfg(feature = "profiling")]
+#[inline]
+pub fn record_allocation(bytes: usize) {
+    BUFFER_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BUFFER_ALLOCATED_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Records a buffer allocated while encoding, `bytes` being its final capacity.
+#[cfg(not(feature = "profiling"))]
+#[inline]
+pub const fn record_allocation(_bytes: usize) {}
+
+/// Returns the current counter values.
+#[cfg(feature = "profiling")]
+#[must_use]
+pub fn snapshot() -> PacketCounters {
+    PacketCounters {
+        packets_encoded: PACKETS_ENCODED.load(Ordering::Relaxed),
+        bytes_encoded: BYTES_ENCODED.load(Ordering::Relaxed),
+        packets_compressed: PACKETS_COMPRESSED.load(Ordering::Relaxed),
+        buffer_allocations: BUFFER_ALLOCATIONS.load(Ordering::Relaxed),
+        buffer_allocated_bytes: BUFFER_ALLOCATED_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Returns the current counter values, all zero without the `profiling` feature.
+#[cfg(not(feature = "profiling"))]
+#[must_use]
+pub const fn snapshot() -> PacketCounters {
+    PacketCounters {
+        packets_encoded: 0,
+        bytes_encoded: 0,
+        packets_compressed: 0,
+        buffer_allocations: 0,
+        buffer_allocated_bytes: 0,
+    }
+}
+
+/// Sets all counters back to zero.
+#[cfg(feature = "profiling")]
+pub fn reset() {
+    for counter in [
+        &PACKETS_ENCODED,
+        &BYTES_ENCODED,
+        &PACKETS_COMPRESSED,
+        &BUFFER_ALLOCATIONS,
+        &BUFFER_ALLOCATED_BYTES,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Sets all counters back to zero.
+#[cfg(not(feature = "profiling"))]
+pub const fn reset() {}
@@ -8,6 +8,7 @@ use std::{
 };
 
 use aes::cipher::{Array, BlockModeDecrypt, BlockModeEncrypt, BlockSizeUser};
+use steel_utils::serial::ReadError;
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
@@ -101,6 +102,17 @@ pub enum PacketError {
     #[error("Invalid protocol: {0}")]
     /// The protocol is invalid.
     InvalidProtocol(String),
+    #[error("failed to read packet frame: {0}")]
+    /// The packet frame (length, id or compression header) could not be read.
+    Read(#[from] ReadError),
+    #[error("failed to decode {packet}: {source}")]
+    /// A serverbound packet's payload could not be decoded.
+    Decode {
+        /// The name of the packet being decoded.
+        packet: &'static str,
+        /// The error that occurred, including the path to the offending field.
+        source: ReadError,
+    },
 }
 
 impl From<io::Error> for PacketError {
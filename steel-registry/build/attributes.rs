@@ -0,0 +1,69 @@
+use std::fs;
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct AttributeJson {
+    id: u32,
+    name: String,
+    translation_key: String,
+    default_value: f64,
+    syncable: bool,
+    min_value: f64,
+    max_value: f64,
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=build_assets/attributes.json");
+
+    let content =
+        fs::read_to_string("build_assets/attributes.json").expect("Failed to read attributes.json");
+    let mut attributes: Vec<AttributeJson> =
+        serde_json::from_str(&content).expect("Failed to parse attributes.json");
+    // Registration order must match vanilla IDs, they are used on the network
+    attributes.sort_by_key(|attribute| attribute.id);
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::attribute::{Attribute, AttributeRegistry};
+        use steel_utils::Identifier;
+    });
+
+    let mut register_stream = TokenStream::new();
+    for attribute in &attributes {
+        let ident = Ident::new(&attribute.name.to_shouty_snake_case(), Span::call_site());
+        let name = &attribute.name;
+        let translation_key = &attribute.translation_key;
+        let default_value = attribute.default_value;
+        let min_value = attribute.min_value;
+        let max_value = attribute.max_value;
+        let syncable = attribute.syncable;
+
+        stream.extend(quote! {
+            pub static #ident: &Attribute = &Attribute {
+                key: Identifier::vanilla_static(#name),
+                translation_key: #translation_key,
+                default_value: #default_value,
+                min_value: #min_value,
+                max_value: #max_value,
+                syncable: #syncable,
+            };
+        });
+
+        register_stream.extend(quote! {
+            registry.register(#ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_attributes(registry: &mut AttributeRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
@@ -1,6 +1,8 @@
 use std::{env, fs, path::Path, process::Command};
 
+mod attributes;
 mod banner_patterns;
+mod biome_tags;
 mod biomes;
 mod block_entity_types;
 mod block_tags;
@@ -30,6 +32,7 @@ mod jukebox_songs;
 mod level_events;
 mod loot_tables;
 mod menu_types;
+mod mob_effects;
 mod packets;
 mod painting_variants;
 mod pig_sound_variants;
@@ -38,6 +41,8 @@ mod poi_types;
 mod recipes;
 mod sound_events;
 mod sound_types;
+mod structure_data;
+mod structure_templates;
 mod timeline_tags;
 mod timelines;
 mod trim_materials;
@@ -72,6 +77,7 @@ const ITEM_TAGS: &str = "item_tags";
 const PACKETS: &str = "packets";
 const BANNER_PATTERNS: &str = "banner_patterns";
 const BIOMES: &str = "biomes";
+const BIOME_TAGS: &str = "biome_tags";
 const CHAT_TYPES: &str = "chat_types";
 const TRIM_PATTERNS: &str = "trim_patterns";
 const TRIM_MATERIALS: &str = "trim_materials";
@@ -109,6 +115,8 @@ const ENTITY_DATA: &str = "entity_data";
 const FLUIDS: &str = "fluids";
 const FLUID_TAGS: &str = "fluid_tags";
 const POI_TYPES: &str = "poi_types";
+const STRUCTURE_TEMPLATES: &str = "structure_templates";
+const STRUCTURE_DATA: &str = "structure_data";
 
 const ENCHANTMENT_TAGS: &str = "enchantment_tags";
 const ENCHANTMENTS: &str = "enchantments";
@@ -121,6 +129,8 @@ const SOUND_TYPES: &str = "sound_types";
 const MULTI_NOISE: &str = "multi_noise";
 const NOISE_PARAMETERS: &str = "noise_parameters";
 const WORLD_CLOCKS: &str = "world_clocks";
+const ATTRIBUTES: &str = "attributes";
+const MOB_EFFECTS: &str = "mob_effects";
 
 pub fn main() {
     // Rerun build script when any file in the build/ directory changes
@@ -143,6 +153,7 @@ pub fn main() {
         (packets::build(), PACKETS),
         (banner_patterns::build(), BANNER_PATTERNS),
         (biomes::build(), BIOMES),
+        (biome_tags::build(), BIOME_TAGS),
         (chat_types::build(), CHAT_TYPES),
         (trim_patterns::build(), TRIM_PATTERNS),
         (trim_materials::build(), TRIM_MATERIALS),
@@ -181,9 +192,13 @@ pub fn main() {
         (sound_events::build(), SOUND_EVENTS),
         (sound_types::build(), SOUND_TYPES),
         (world_clocks::build(), WORLD_CLOCKS),
+        (attributes::build(), ATTRIBUTES),
+        (mob_effects::build(), MOB_EFFECTS),
         (multi_noise::build(), MULTI_NOISE),
         (noise_parameters::build(), NOISE_PARAMETERS),
         (poi_types::build(), POI_TYPES),
+        (structure_templates::build(), STRUCTURE_TEMPLATES),
+        (structure_data::build(), STRUCTURE_DATA),
         (banner_pattern_tags::build(), BANNER_PATTERN_TAGS),
         (entity_type_tags::build(), ENTITY_TYPE_TAGS),
         (instrument_tags::build(), INSTRUMENT_TAGS),
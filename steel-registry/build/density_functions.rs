@@ -780,6 +780,11 @@ fn generate_noise_settings(dimension: &str, prefix: &str) -> TokenStream {
             fn init_grid(&mut self, chunk_block_x: i32, chunk_block_z: i32, noises: &Self::Noises) {
                 #cache_struct::init_grid(self, chunk_block_x, chunk_block_z, noises)
             }
+
+            #[inline]
+            fn set_blender(&mut self, blender: Option<std::sync::Arc<steel_utils::density::Blender>>) {
+                self.blender = blender;
+            }
         }
 
         impl steel_utils::density::DimensionNoises for #noises_struct {
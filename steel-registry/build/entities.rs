@@ -1,4 +1,4 @@
-use std::fs;
+use std::{collections::BTreeMap, fs};
 
 use heck::ToShoutySnakeCase;
 use proc_macro2::{Ident, Literal, Span, TokenStream};
@@ -22,6 +22,24 @@ struct EntityTypeEntry {
     can_serialize: bool,
     #[serde(default)]
     flags: Option<FlagsEntry>,
+    /// Base attribute values, only present for living entities.
+    #[serde(default)]
+    attributes: BTreeMap<String, f64>,
+    #[serde(default)]
+    attachments: AttachmentsEntry,
+}
+
+#[derive(Deserialize, Default)]
+struct AttachmentsEntry {
+    #[serde(default)]
+    passenger: Vec<AttachmentPoint>,
+}
+
+#[derive(Deserialize)]
+struct AttachmentPoint {
+    x: f32,
+    y: f32,
+    z: f32,
 }
 
 fn default_can_serialize() -> bool {
@@ -71,6 +89,8 @@ pub(crate) fn build() -> TokenStream {
 
     stream.extend(quote! {
         use crate::entity_types::{EntityDimensions, EntityFlags, EntityType, EntityTypeRegistry, MobCategory};
+        use crate::vanilla_attributes;
+        use glam::Vec3;
         use steel_utils::Identifier;
     });
 
@@ -108,6 +128,19 @@ pub(crate) fn build() -> TokenStream {
         let can_breathe_underwater = flags.is_some_and(|f| f.can_breathe_underwater);
         let can_be_seen_as_enemy = flags.is_some_and(|f| f.can_be_seen_as_enemy);
 
+        let default_attributes = entity_type.attributes.iter().map(|(name, base)| {
+            let attribute = Ident::new(&name.to_shouty_snake_case(), Span::call_site());
+            let base = Literal::f64_suffixed(*base);
+            quote! { (vanilla_attributes::#attribute, #base) }
+        });
+
+        let passenger_attachments = entity_type.attachments.passenger.iter().map(|point| {
+            let x = Literal::f32_suffixed(point.x);
+            let y = Literal::f32_suffixed(point.y);
+            let z = Literal::f32_suffixed(point.z);
+            quote! { Vec3::new(#x, #y, #z) }
+        });
+
         stream.extend(quote! {
             pub static #entity_type_ident: &EntityType = &EntityType {
                 key: Identifier::vanilla_static(#entity_type_key),
@@ -132,6 +165,8 @@ pub(crate) fn build() -> TokenStream {
                     can_breathe_underwater: #can_breathe_underwater,
                     can_be_seen_as_enemy: #can_be_seen_as_enemy,
                 },
+                default_attributes: &[#(#default_attributes),*],
+                passenger_attachments: &[#(#passenger_attachments),*],
             };
         });
         register_stream.extend(quote! {
@@ -96,6 +96,30 @@ fn parse_block_or_tag(s: &str) -> TokenStream {
     }
 }
 
+/// Generates the TokenStream for a Repairable component from JSON data.
+/// `items` is either an item tag ("#minecraft:iron_tool_materials"), a single item, or a list of items.
+fn generate_repairable_component(value: &Value) -> TokenStream {
+    let item_tokens = |items: Vec<&str>| {
+        let items = items.into_iter().map(|item| {
+            let path = item.strip_prefix("minecraft:").unwrap_or(item);
+            quote! { Identifier::vanilla_static(#path) }
+        });
+        quote! { vanilla_components::Repairable::Items(vec![#(#items),*]) }
+    };
+
+    match value.get("items") {
+        Some(Value::String(s)) => match s.strip_prefix('#') {
+            Some(tag) => {
+                let path = tag.strip_prefix("minecraft:").unwrap_or(tag);
+                quote! { vanilla_components::Repairable::Tag(Identifier::vanilla_static(#path)) }
+            }
+            None => item_tokens(vec![s]),
+        },
+        Some(Value::Array(arr)) => item_tokens(arr.iter().filter_map(|v| v.as_str()).collect()),
+        _ => item_tokens(Vec::new()),
+    }
+}
+
 /// Generates the TokenStream for a single ToolRule from JSON data.
 fn generate_tool_rule(rule: &Value) -> TokenStream {
     // Parse blocks - can be a string (single block or tag), or an array of strings
@@ -232,6 +256,73 @@ fn generate_builder_calls(item: &Item) -> Vec<TokenStream> {
                 builder_calls
                     .push(quote! { .builder_set(vanilla_components::TOOL, Some(#tool_token)) });
             }
+            "minecraft:debug_stick_state" => {
+                // Only the empty default state ever appears on item prototypes
+                builder_calls.push(quote! {
+                    .builder_set(
+                        vanilla_components::#component_ident,
+                        Some(vanilla_components::DebugStickState::default()),
+                    )
+                });
+            }
+            "minecraft:container" => {
+                // Shulker boxes start out empty
+                builder_calls.push(quote! {
+                    .builder_set(
+                        vanilla_components::#component_ident,
+                        Some(vanilla_components::ItemContainerContents::default()),
+                    )
+                });
+            }
+            "minecraft:bundle_contents" => {
+                // Bundles start out empty
+                builder_calls.push(quote! {
+                    .builder_set(
+                        vanilla_components::#component_ident,
+                        Some(vanilla_components::BundleContents::default()),
+                    )
+                });
+            }
+            "minecraft:enchantments" | "minecraft:stored_enchantments" => {
+                // Items start out without (stored) enchantments
+                builder_calls.push(quote! {
+                    .builder_set(
+                        vanilla_components::#component_ident,
+                        Some(vanilla_components::ItemEnchantments::empty()),
+                    )
+                });
+            }
+            "minecraft:enchantable" => {
+                let val = value.get("value").and_then(|v| v.as_i64()).unwrap() as i32;
+                builder_calls.push(quote! {
+                    .builder_set(
+                        vanilla_components::#component_ident,
+                        Some(vanilla_components::Enchantable { value: #val }),
+                    )
+                });
+            }
+            "minecraft:fireworks" => {
+                // Item prototypes never carry explosions
+                let flight_duration = value
+                    .get("flight_duration")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0) as i32;
+                builder_calls.push(quote! {
+                    .builder_set(
+                        vanilla_components::#component_ident,
+                        Some(vanilla_components::Fireworks {
+                            flight_duration: #flight_duration,
+                            explosions: Vec::new(),
+                        }),
+                    )
+                });
+            }
+            "minecraft:repairable" => {
+                let repairable_token = generate_repairable_component(value);
+                builder_calls.push(quote! {
+                    .builder_set(vanilla_components::#component_ident, Some(#repairable_token))
+                });
+            }
             _ => {
                 // TODO: Implement more
             }
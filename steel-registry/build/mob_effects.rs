@@ -0,0 +1,65 @@
+use std::fs;
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct MobEffectJson {
+    id: u32,
+    name: String,
+    category: String,
+    color: i32,
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=build_assets/mob_effects.json");
+
+    let content = fs::read_to_string("build_assets/mob_effects.json")
+        .expect("Failed to read mob_effects.json");
+    let mut mob_effects: Vec<MobEffectJson> =
+        serde_json::from_str(&content).expect("Failed to parse mob_effects.json");
+    // Registration order must match vanilla IDs, they are used on the network
+    mob_effects.sort_by_key(|mob_effect| mob_effect.id);
+
+    let mut stream = TokenStream::new();
+
+    stream.extend(quote! {
+        use crate::mob_effect::{MobEffect, MobEffectCategory, MobEffectRegistry};
+        use steel_utils::Identifier;
+    });
+
+    let mut register_stream = TokenStream::new();
+    for mob_effect in &mob_effects {
+        let ident = Ident::new(&mob_effect.name.to_shouty_snake_case(), Span::call_site());
+        let name = &mob_effect.name;
+        let category = match mob_effect.category.as_str() {
+            "BENEFICIAL" => quote! { MobEffectCategory::Beneficial },
+            "HARMFUL" => quote! { MobEffectCategory::Harmful },
+            "NEUTRAL" => quote! { MobEffectCategory::Neutral },
+            other => panic!("Unknown mob effect category: {other}"),
+        };
+        let color = mob_effect.color;
+
+        stream.extend(quote! {
+            pub static #ident: &MobEffect = &MobEffect {
+                key: Identifier::vanilla_static(#name),
+                category: #category,
+                color: #color,
+            };
+        });
+
+        register_stream.extend(quote! {
+            registry.register(#ident);
+        });
+    }
+
+    stream.extend(quote! {
+        pub fn register_mob_effects(registry: &mut MobEffectRegistry) {
+            #register_stream
+        }
+    });
+
+    stream
+}
@@ -0,0 +1,97 @@
+use std::{fs, path::Path};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+const WORLDGEN_DIR: &str = "build_assets/builtin_datapacks/minecraft/worldgen";
+
+fn collect_files(dir: &Path, base_dir: &Path, files: &mut Vec<(String, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, base_dir, files);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let key = path
+                .strip_prefix(base_dir)
+                .unwrap_or(&path)
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/");
+            let file = path.to_string_lossy().replace('\\', "/");
+            files.push((key, file));
+        }
+    }
+}
+
+/// Builds a sorted table of the JSON files in one registry directory, plus a lookup function.
+fn build_table(registry: &str, table: &str, lookup: &str, description: &str) -> TokenStream {
+    let dir = format!("{WORLDGEN_DIR}/{registry}");
+    println!("cargo:rerun-if-changed={dir}/");
+
+    let mut files = Vec::new();
+    collect_files(Path::new(&dir), Path::new(&dir), &mut files);
+    // Sorted so lookups can binary search
+    files.sort();
+
+    let entries = files.iter().map(|(key, file)| {
+        quote! {
+            (#key, include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #file))),
+        }
+    });
+    let table = format_ident!("{table}");
+    let lookup = format_ident!("{lookup}");
+    let table_doc = format!("The {description} of the vanilla data pack as JSON, sorted by path.");
+    let lookup_doc = format!("Gets the JSON of the vanilla {description} at `path`.");
+
+    quote! {
+        #[doc = #table_doc]
+        pub static #table: &[(&str, &str)] = &[
+            #(#entries)*
+        ];
+
+        #[doc = #lookup_doc]
+        #[must_use]
+        pub fn #lookup(path: &str) -> Option<&'static str> {
+            #table
+                .binary_search_by(|(key, _)| (*key).cmp(path))
+                .ok()
+                .map(|index| #table[index].1)
+        }
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    let tables = [
+        build_table(
+            "template_pool",
+            "VANILLA_TEMPLATE_POOLS",
+            "vanilla_template_pool",
+            "template pools",
+        ),
+        build_table(
+            "processor_list",
+            "VANILLA_PROCESSOR_LISTS",
+            "vanilla_processor_list",
+            "structure processor lists",
+        ),
+        build_table(
+            "structure",
+            "VANILLA_STRUCTURES",
+            "vanilla_structure",
+            "structures",
+        ),
+        build_table(
+            "structure_set",
+            "VANILLA_STRUCTURE_SETS",
+            "vanilla_structure_set",
+            "structure sets",
+        ),
+    ];
+
+    quote! {
+        #(#tables)*
+    }
+}
@@ -0,0 +1,62 @@
+use std::{fs, path::Path};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+const STRUCTURE_DIR: &str = "build_assets/builtin_datapacks/minecraft/structure";
+
+fn collect_templates(dir: &Path, base_dir: &Path, templates: &mut Vec<(String, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_templates(&path, base_dir, templates);
+        } else if path.extension().and_then(|s| s.to_str()) == Some("nbt") {
+            let key = path
+                .strip_prefix(base_dir)
+                .unwrap_or(&path)
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/");
+            let file = path.to_string_lossy().replace('\\', "/");
+            templates.push((key, file));
+        }
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed={STRUCTURE_DIR}/");
+
+    let mut templates = Vec::new();
+    collect_templates(
+        Path::new(STRUCTURE_DIR),
+        Path::new(STRUCTURE_DIR),
+        &mut templates,
+    );
+    // Sorted so lookups can binary search
+    templates.sort();
+
+    let entries = templates.iter().map(|(key, file)| {
+        quote! {
+            (#key, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #file))),
+        }
+    });
+
+    quote! {
+        /// The gzipped `.nbt` structure templates of the vanilla data pack, sorted by path.
+        pub static VANILLA_STRUCTURE_TEMPLATES: &[(&str, &[u8])] = &[
+            #(#entries)*
+        ];
+
+        /// Gets the gzipped `.nbt` data of the vanilla template at `path`, like `igloo/top`.
+        #[must_use]
+        pub fn vanilla_structure_template(path: &str) -> Option<&'static [u8]> {
+            VANILLA_STRUCTURE_TEMPLATES
+                .binary_search_by(|(key, _)| (*key).cmp(path))
+                .ok()
+                .map(|index| VANILLA_STRUCTURE_TEMPLATES[index].1)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Entity attribute registry.
+//!
+//! Attributes are numeric entity stats (max health, movement speed, armor, ...)
+//! that can be changed by modifiers from equipment, effects and commands.
+
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+
+/// An entity attribute with its default value and valid range.
+///
+/// Vanilla equivalent: `RangedAttribute`.
+#[derive(Debug)]
+pub struct Attribute {
+    pub key: Identifier,
+    pub translation_key: &'static str,
+    pub default_value: f64,
+    pub min_value: f64,
+    pub max_value: f64,
+    /// Whether changes to this attribute are sent to clients.
+    pub syncable: bool,
+}
+
+impl Attribute {
+    /// Clamps `value` into the attribute's range, mapping NaN to the minimum.
+    ///
+    /// Vanilla equivalent: `RangedAttribute.sanitizeValue()`.
+    #[must_use]
+    pub fn sanitize_value(&self, value: f64) -> f64 {
+        if value.is_nan() {
+            self.min_value
+        } else {
+            value.clamp(self.min_value, self.max_value)
+        }
+    }
+}
+
+pub type AttributeRef = &'static Attribute;
+
+pub struct AttributeRegistry {
+    attributes_by_id: Vec<AttributeRef>,
+    attributes_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl AttributeRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            attributes_by_id: Vec::new(),
+            attributes_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, attribute: AttributeRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register attributes after the registry has been frozen"
+        );
+
+        let id = self.attributes_by_id.len();
+        self.attributes_by_key.insert(attribute.key.clone(), id);
+        self.attributes_by_id.push(attribute);
+        id
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, AttributeRef)> + '_ {
+        self.attributes_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &attribute)| (id, attribute))
+    }
+}
+
+impl Default for AttributeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+crate::impl_registry!(
+    AttributeRegistry,
+    Attribute,
+    attributes_by_id,
+    attributes_by_key,
+    attributes
+);
@@ -1,7 +1,19 @@
+use std::sync::LazyLock;
+
 use rustc_hash::FxHashMap;
 use simdnbt::ToNbtTag;
 use simdnbt::owned::NbtTag;
 use steel_utils::Identifier;
+use steel_utils::colormap::{ColorMap, colormap_index, dark_forest_grass_color, swamp_grass_color};
+use steel_utils::noise::PerlinSimplexNoise;
+use steel_utils::random::RandomSource;
+use steel_utils::random::legacy_random::LegacyRandom;
+
+/// Noise that varies the swamp grass color. Vanilla: `Biome.BIOME_INFO_NOISE`.
+static BIOME_INFO_NOISE: LazyLock<PerlinSimplexNoise> = LazyLock::new(|| {
+    let mut random = RandomSource::Legacy(LegacyRandom::from_seed(2345));
+    PerlinSimplexNoise::new(&mut random, &[0])
+});
 
 #[derive(Debug)]
 pub struct Biome {
@@ -18,6 +30,55 @@ pub struct Biome {
     pub features: Vec<Vec<Identifier>>,
 }
 
+impl Biome {
+    /// Returns the colormap pixel index for this biome's climate.
+    ///
+    /// Grass and foliage colors that the biome doesn't set are read from this
+    /// index of the client's colormap textures.
+    #[must_use]
+    pub fn colormap_index(&self) -> usize {
+        colormap_index(f64::from(self.temperature), f64::from(self.downfall))
+    }
+
+    /// Gets the grass color at the given block coordinates.
+    ///
+    /// Vanilla: `Biome.getGrassColor()`.
+    #[must_use]
+    pub fn grass_color(&self, x: f64, z: f64, grass_colormap: &ColorMap) -> i32 {
+        let color = self
+            .effects
+            .grass_color
+            .unwrap_or_else(|| grass_colormap.get_by_index(self.colormap_index()));
+        self.effects.grass_color_modifier.modify_color(x, z, color)
+    }
+
+    /// Gets the color of leaves that take their color from the biome.
+    ///
+    /// Vanilla: `Biome.getFoliageColor()`.
+    #[must_use]
+    pub fn foliage_color(&self, foliage_colormap: &ColorMap) -> i32 {
+        self.effects
+            .foliage_color
+            .unwrap_or_else(|| foliage_colormap.get_by_index(self.colormap_index()))
+    }
+
+    /// Gets the color of dry foliage like leaf litter.
+    ///
+    /// Vanilla: `Biome.getDryFoliageColor()`.
+    #[must_use]
+    pub fn dry_foliage_color(&self, dry_foliage_colormap: &ColorMap) -> i32 {
+        self.effects
+            .dry_foliage_color
+            .unwrap_or_else(|| dry_foliage_colormap.get_by_index(self.colormap_index()))
+    }
+
+    /// Gets the water color.
+    #[must_use]
+    pub const fn water_color(&self) -> i32 {
+        self.effects.water_color
+    }
+}
+
 #[derive(Debug)]
 pub struct BiomeEffects {
     pub fog_color: i32,
@@ -63,6 +124,20 @@ pub enum GrassColorModifier {
     Swamp,
 }
 
+impl GrassColorModifier {
+    /// Applies the modifier to a grass color at the given block coordinates.
+    ///
+    /// Vanilla: `GrassColorModifier.modifyColor()`.
+    #[must_use]
+    pub fn modify_color(&self, x: f64, z: f64, color: i32) -> i32 {
+        match self {
+            Self::None => color,
+            Self::DarkForest => dark_forest_grass_color(color),
+            Self::Swamp => swamp_grass_color(BIOME_INFO_NOISE.get_value(x * 0.0225, z * 0.0225)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct WeightedMusic {
     pub data: Music,
@@ -253,6 +328,7 @@ pub type BiomeRef = &'static Biome;
 pub struct BiomeRegistry {
     biomes_by_id: Vec<BiomeRef>,
     biomes_by_key: FxHashMap<Identifier, usize>,
+    tags: FxHashMap<Identifier, Vec<Identifier>>,
     allows_registering: bool,
 }
 
@@ -262,6 +338,7 @@ impl BiomeRegistry {
         Self {
             biomes_by_id: Vec::new(),
             biomes_by_key: FxHashMap::default(),
+            tags: FxHashMap::default(),
             allows_registering: true,
         }
     }
@@ -304,3 +381,4 @@ impl Default for BiomeRegistry {
 }
 
 crate::impl_registry!(BiomeRegistry, Biome, biomes_by_id, biomes_by_key, biomes);
+crate::impl_tagged_registry!(BiomeRegistry, biomes_by_key, "biome");
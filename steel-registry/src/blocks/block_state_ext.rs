@@ -7,7 +7,7 @@ use crate::{
         shapes::SupportType,
     },
 };
-use steel_utils::BlockStateId;
+use steel_utils::{BlockStateId, Mirror, Rotation};
 
 pub trait BlockStateExt {
     fn get_block(&self) -> BlockRef;
@@ -33,6 +33,16 @@ pub trait BlockStateExt {
     fn is_solid(&self) -> bool;
     /// Returns if a block can be replaced extracted from the minecraft data
     fn is_replaceable(&self) -> bool;
+    /// Rotates the state around the Y axis, e.g. when placing a structure.
+    ///
+    /// Vanilla: `BlockState.rotate()`.
+    #[must_use]
+    fn rotate(&self, rotation: Rotation) -> BlockStateId;
+    /// Mirrors the state, e.g. when placing a structure.
+    ///
+    /// Vanilla: `BlockState.mirror()`.
+    #[must_use]
+    fn mirror(&self, mirror: Mirror) -> BlockStateId;
 }
 
 impl BlockStateExt for BlockStateId {
@@ -115,6 +125,96 @@ impl BlockStateExt for BlockStateId {
     fn is_replaceable(&self) -> bool {
         self.get_block().config.replaceable
     }
+
+    // TODO: Rotate rail shapes
+    fn rotate(&self, rotation: Rotation) -> BlockStateId {
+        if rotation == Rotation::None {
+            return *self;
+        }
+        let inverse = Rotation::from_quarter_turns(-rotation.quarter_turns());
+        transform_properties(*self, |name, value, properties| match name {
+            "facing" => {
+                Direction::by_name(value).map(|dir| rotation.rotate(dir).as_str().to_owned())
+            }
+            "orientation" => map_orientation(value, |dir| rotation.rotate(dir)),
+            "rotation" => value
+                .parse()
+                .ok()
+                .map(|segment| rotation.rotate_segment(segment, 16).to_string()),
+            "axis" if rotation != Rotation::Clockwise180 => match value {
+                "x" => Some("z".to_owned()),
+                "z" => Some("x".to_owned()),
+                _ => None,
+            },
+            _ => side_value(name, properties, |dir| inverse.rotate(dir)),
+        })
+    }
+
+    // TODO: Mirror stair and rail shapes, door hinges and chest halves
+    fn mirror(&self, mirror: Mirror) -> BlockStateId {
+        if mirror == Mirror::None {
+            return *self;
+        }
+        transform_properties(*self, |name, value, properties| match name {
+            "facing" => Direction::by_name(value).map(|dir| mirror.mirror(dir).as_str().to_owned()),
+            "orientation" => map_orientation(value, |dir| mirror.mirror(dir)),
+            "rotation" => value
+                .parse()
+                .ok()
+                .map(|segment| mirror.mirror_segment(segment, 16).to_string()),
+            _ => side_value(name, properties, |dir| mirror.mirror(dir)),
+        })
+    }
+}
+
+/// Rebuilds `state` with each property value replaced by what `transform` returns.
+///
+/// `transform` gets the property name, its value and all of the state's properties,
+/// and returns `None` to keep the value.
+fn transform_properties(
+    state: BlockStateId,
+    transform: impl Fn(&str, &str, &[(&'static str, &'static str)]) -> Option<String>,
+) -> BlockStateId {
+    let properties = REGISTRY.blocks.get_properties(state);
+    if properties.is_empty() {
+        return state;
+    }
+    let values: Vec<Option<String>> = properties
+        .iter()
+        .map(|&(name, value)| transform(name, value, &properties))
+        .collect();
+    let properties: Vec<(&str, &str)> = properties
+        .iter()
+        .zip(&values)
+        .map(|(&(name, value), new)| (name, new.as_deref().unwrap_or(value)))
+        .collect();
+    REGISTRY
+        .blocks
+        .state_id_from_properties(&state.get_block().key, &properties)
+        .unwrap_or(state)
+}
+
+/// Gets the new value of a per-side property like the `north` of a fence, which
+/// takes the old value of the side `source` maps it to.
+fn side_value(
+    name: &str,
+    properties: &[(&'static str, &'static str)],
+    source: impl Fn(Direction) -> Direction,
+) -> Option<String> {
+    let side = Direction::by_name(name).filter(|dir| dir.is_horizontal())?;
+    let source = source(side);
+    properties
+        .iter()
+        .find(|(name, _)| *name == source.as_str())
+        .map(|(_, value)| (*value).to_owned())
+}
+
+/// Transforms both directions of a jigsaw or crafter `orientation`, like `north_up`.
+fn map_orientation(value: &str, map: impl Fn(Direction) -> Direction) -> Option<String> {
+    let (front, top) = value.split_once('_')?;
+    let front = map(Direction::by_name(front)?);
+    let top = map(Direction::by_name(top)?);
+    Some(format!("{}_{}", front.as_str(), top.as_str()))
 }
 
 pub trait FluidReplaceableExt {
@@ -412,6 +412,41 @@ impl BlockRegistry {
         BlockStateId(base_state_id + new_relative_index)
     }
 
+    /// Moves the property named `name` to its next possible value, or the previous
+    /// one if `backwards` is set, wrapping around at either end.
+    ///
+    /// Matches vanilla's `BlockState.cycle` / `Util.findPreviousInIterable` ordering.
+    /// Returns `None` if the block doesn't have this property.
+    #[must_use]
+    pub fn cycle_property(
+        &self,
+        id: BlockStateId,
+        name: &str,
+        backwards: bool,
+    ) -> Option<BlockStateId> {
+        let block = self.by_state_id(id)?;
+        let property_index = block.properties.iter().position(|p| p.get_name() == name)?;
+
+        // Multiplier of this property in the state encoding: the product of the
+        // value counts of all properties after it (last property = inner loop).
+        let stride: u16 = block.properties[property_index + 1..]
+            .iter()
+            .map(|p| p.get_possible_values().len() as u16)
+            .product();
+        let count = block.properties[property_index].get_possible_values().len() as u16;
+
+        let block_id = self.state_to_block_id[id.0 as usize];
+        let relative_index = id.0 - self.block_to_base_state[block_id];
+        let current = (relative_index / stride) % count;
+        let next = if backwards {
+            (current + count - 1) % count
+        } else {
+            (current + 1) % count
+        };
+
+        Some(BlockStateId(id.0 - current * stride + next * stride))
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (usize, BlockRef)> + '_ {
         self.blocks_by_id
             .iter()
@@ -711,6 +746,35 @@ mod tests {
         assert!(result.is_none(), "Should return None for invalid value");
     }
 
+    #[test]
+    fn test_cycle_property_wraps_and_keeps_other_properties() {
+        let registry = create_test_registry();
+        let key = Identifier::vanilla_static("redstone_wire");
+        let state_id = registry
+            .state_id_from_properties(&key, &[("east", "side"), ("power", "15")])
+            .expect("Should find state");
+
+        let cycled = registry
+            .cycle_property(state_id, "power", false)
+            .expect("redstone_wire has power");
+        let retrieved = registry.get_properties(cycled);
+        assert!(retrieved.contains(&("power", "0")));
+        assert!(retrieved.contains(&("east", "side")));
+
+        let back = registry
+            .cycle_property(cycled, "power", true)
+            .expect("redstone_wire has power");
+        assert_eq!(back, state_id);
+
+        let east = registry
+            .cycle_property(state_id, "east", false)
+            .expect("redstone_wire has east");
+        assert!(registry.get_properties(east).contains(&("east", "none")));
+        assert!(registry.get_properties(east).contains(&("power", "15")));
+
+        assert!(registry.cycle_property(state_id, "facing", false).is_none());
+    }
+
     #[test]
     fn test_stone_no_properties() {
         let registry = create_test_registry();
@@ -3,7 +3,11 @@
 //! This module provides the core types for storing component values in an ABI-stable way.
 //! Vanilla components get dedicated enum variants for zero-cost access, while plugin
 //! components use the `Other` variant with opaque bytes.
-use super::components::{Equippable, ItemEnchantments, Tool};
+use super::components::{
+    BundleContents, CustomData, DebugStickState, Enchantable, Equippable, Fireworks,
+    ItemContainerContents, ItemEnchantments, ItemLore, LodestoneTracker, Repairable, Tool,
+    TypedEntityData,
+};
 use text_components::TextComponent;
 
 /// Discriminant for [`ComponentData`] variants.
@@ -20,6 +24,16 @@ pub enum ComponentDataDiscriminant {
     Equippable,
     Enchantments,
     TextComponent,
+    TypedEntityData,
+    DebugStickState,
+    Lore,
+    CustomData,
+    Container,
+    BundleContents,
+    Enchantable,
+    Repairable,
+    Fireworks,
+    LodestoneTracker,
     Todo,
     Other,
 }
@@ -74,6 +88,26 @@ pub enum ComponentData {
     Enchantments(ItemEnchantments),
     /// TextComponent component (e.g., CustomName, ItemName)
     TextComponent(Box<TextComponent>),
+    /// minecraft:block_entity_data / minecraft:entity_data
+    TypedEntityData(Box<TypedEntityData>),
+    /// minecraft:debug_stick_state
+    DebugStickState(DebugStickState),
+    /// minecraft:lore
+    Lore(ItemLore),
+    /// minecraft:custom_data
+    CustomData(Box<CustomData>),
+    /// minecraft:container
+    Container(ItemContainerContents),
+    /// minecraft:bundle_contents
+    BundleContents(BundleContents),
+    /// minecraft:enchantable
+    Enchantable(Enchantable),
+    /// minecraft:repairable
+    Repairable(Repairable),
+    /// minecraft:fireworks
+    Fireworks(Fireworks),
+    /// minecraft:lodestone_tracker
+    LodestoneTracker(LodestoneTracker),
 
     // ==================== Not yet implemented ====================
     /// Placeholder for components that aren't implemented yet.
@@ -114,6 +148,16 @@ impl ComponentData {
             Self::Equippable(_) => ComponentDataDiscriminant::Equippable,
             Self::Enchantments(_) => ComponentDataDiscriminant::Enchantments,
             Self::TextComponent(_) => ComponentDataDiscriminant::TextComponent,
+            Self::TypedEntityData(_) => ComponentDataDiscriminant::TypedEntityData,
+            Self::DebugStickState(_) => ComponentDataDiscriminant::DebugStickState,
+            Self::Lore(_) => ComponentDataDiscriminant::Lore,
+            Self::CustomData(_) => ComponentDataDiscriminant::CustomData,
+            Self::Container(_) => ComponentDataDiscriminant::Container,
+            Self::BundleContents(_) => ComponentDataDiscriminant::BundleContents,
+            Self::Enchantable(_) => ComponentDataDiscriminant::Enchantable,
+            Self::Repairable(_) => ComponentDataDiscriminant::Repairable,
+            Self::Fireworks(_) => ComponentDataDiscriminant::Fireworks,
+            Self::LodestoneTracker(_) => ComponentDataDiscriminant::LodestoneTracker,
             Self::Todo => ComponentDataDiscriminant::Todo,
             Self::Other(_) => ComponentDataDiscriminant::Other,
         }
@@ -140,6 +184,16 @@ impl ComponentData {
             Self::Equippable(v) => v.hash_component(&mut hasher),
             Self::Enchantments(v) => v.hash_component(&mut hasher),
             Self::TextComponent(v) => v.hash_component(&mut hasher),
+            Self::TypedEntityData(v) => v.hash_component(&mut hasher),
+            Self::DebugStickState(v) => v.hash_component(&mut hasher),
+            Self::Lore(v) => v.hash_component(&mut hasher),
+            Self::CustomData(v) => v.hash_component(&mut hasher),
+            Self::Container(v) => v.hash_component(&mut hasher),
+            Self::BundleContents(v) => v.hash_component(&mut hasher),
+            Self::Enchantable(v) => v.hash_component(&mut hasher),
+            Self::Repairable(v) => v.hash_component(&mut hasher),
+            Self::Fireworks(v) => v.hash_component(&mut hasher),
+            Self::LodestoneTracker(v) => v.hash_component(&mut hasher),
 
             // Stub/plugin types - hash as empty map for now
             // TODO: Implement proper hashing when these types are implemented
@@ -350,6 +404,206 @@ impl Component for TextComponent {
     }
 }
 
+impl Component for TypedEntityData {
+    fn into_data(self) -> ComponentData {
+        ComponentData::TypedEntityData(Box::new(self))
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::TypedEntityData(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::TypedEntityData(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for DebugStickState {
+    fn into_data(self) -> ComponentData {
+        ComponentData::DebugStickState(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::DebugStickState(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::DebugStickState(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for ItemLore {
+    fn into_data(self) -> ComponentData {
+        ComponentData::Lore(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::Lore(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::Lore(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for CustomData {
+    fn into_data(self) -> ComponentData {
+        ComponentData::CustomData(Box::new(self))
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::CustomData(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::CustomData(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
 // TextComponent and Identifier need special handling since they're used
 // for multiple component types. We'll handle these through the DataComponentType
 // registration rather than a blanket Component impl.
+
+impl Component for ItemContainerContents {
+    fn into_data(self) -> ComponentData {
+        ComponentData::Container(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::Container(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::Container(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for BundleContents {
+    fn into_data(self) -> ComponentData {
+        ComponentData::BundleContents(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::BundleContents(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::BundleContents(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for Enchantable {
+    fn into_data(self) -> ComponentData {
+        ComponentData::Enchantable(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::Enchantable(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::Enchantable(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for Repairable {
+    fn into_data(self) -> ComponentData {
+        ComponentData::Repairable(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::Repairable(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::Repairable(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for Fireworks {
+    fn into_data(self) -> ComponentData {
+        ComponentData::Fireworks(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::Fireworks(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::Fireworks(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Component for LodestoneTracker {
+    fn into_data(self) -> ComponentData {
+        ComponentData::LodestoneTracker(self)
+    }
+
+    fn from_data(data: ComponentData) -> Option<Self> {
+        match data {
+            ComponentData::LodestoneTracker(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_data_ref(data: &ComponentData) -> Option<&Self> {
+        match data {
+            ComponentData::LodestoneTracker(v) => Some(v),
+            _ => None,
+        }
+    }
+}
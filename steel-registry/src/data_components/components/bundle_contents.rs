@@ -0,0 +1,402 @@
+use std::cmp::Ordering;
+use std::io::{Cursor, Write};
+use std::ops::{Add, Mul, Sub};
+
+use simdnbt::owned::{NbtList, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::codec::VarInt;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+use crate::data_components::vanilla_components::BUNDLE_CONTENTS;
+use crate::item_stack::ItemStack;
+
+/// An exact fraction, used for how full a bundle is.
+///
+/// Vanilla uses Apache Commons' `Fraction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fraction {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Fraction {
+    /// Nothing.
+    pub const ZERO: Self = Self::new_reduced(0, 1);
+    /// A whole.
+    pub const ONE: Self = Self::new_reduced(1, 1);
+
+    const fn new_reduced(numerator: i64, denominator: i64) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Creates `numerator / denominator` in lowest terms.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is zero.
+    #[must_use]
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        assert!(denominator != 0, "fraction with a zero denominator");
+        let sign = denominator.signum();
+        let gcd = gcd(numerator, denominator).max(1);
+        Self::new_reduced(sign * numerator / gcd, sign * denominator / gcd)
+    }
+
+    /// Returns how many whole times `other` fits in this fraction, rounded down.
+    #[must_use]
+    pub const fn div_floor(self, other: Self) -> i64 {
+        (self.numerator * other.denominator).div_euclid(self.denominator * other.numerator)
+    }
+}
+
+impl Add for Fraction {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Sub for Fraction {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator - other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Mul<i64> for Fraction {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Self::new(self.numerator * factor, self.denominator)
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+const fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// The items inside a bundle (`minecraft:bundle_contents`), newest first.
+///
+/// A bundle holds one stack's worth of items: each item weighs `1 / max stack
+/// size`, and the total can't go past one.
+#[derive(Debug, Clone)]
+pub struct BundleContents {
+    items: Vec<ItemStack>,
+    /// The item picked by scrolling over the bundle, `-1` for none.
+    ///
+    /// Only lives on the server, it isn't saved or sent.
+    selected_item: i32,
+}
+
+/// The selection isn't part of the contents, like vanilla.
+impl PartialEq for BundleContents {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl Default for BundleContents {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl BundleContents {
+    /// How much a bundle weighs inside another bundle, on top of its contents.
+    const BUNDLE_IN_BUNDLE_WEIGHT: Fraction = Fraction::new_reduced(1, 16);
+
+    /// Upper bound on how many stacks the network format may contain.
+    const MAX_NETWORK_ITEMS: usize = 256;
+
+    /// Creates contents holding `items`, newest first.
+    #[must_use]
+    pub const fn new(items: Vec<ItemStack>) -> Self {
+        Self {
+            items,
+            selected_item: -1,
+        }
+    }
+
+    /// Returns the stacks in the bundle, newest first.
+    #[must_use]
+    pub fn items(&self) -> &[ItemStack] {
+        &self.items
+    }
+
+    /// Returns whether the bundle is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the index of the selected stack, or `-1`.
+    #[must_use]
+    pub const fn selected_item(&self) -> i32 {
+        self.selected_item
+    }
+
+    /// Selects the stack at `index`, or clears the selection if it was
+    /// already selected or doesn't exist.
+    ///
+    /// Vanilla equivalent: `BundleContents.Mutable.toggleSelectedItem`.
+    pub fn toggle_selected_item(&mut self, index: i32) {
+        let exists = usize::try_from(index).is_ok_and(|index| index < self.items.len());
+        self.selected_item = if self.selected_item != index && exists {
+            index
+        } else {
+            -1
+        };
+    }
+
+    /// Returns how full the bundle is, from zero to one.
+    #[must_use]
+    pub fn weight(&self) -> Fraction {
+        self.items.iter().fold(Fraction::ZERO, |total, item| {
+            total + Self::item_weight(item) * i64::from(item.count())
+        })
+    }
+
+    /// Returns how much one of `item` weighs in a bundle.
+    ///
+    /// Vanilla equivalent: `BundleContents.getWeight`.
+    #[must_use]
+    pub fn item_weight(item: &ItemStack) -> Fraction {
+        // TODO: Occupied beehives weigh a whole bundle once bees are a component
+        match item.get(BUNDLE_CONTENTS) {
+            Some(contents) => Self::BUNDLE_IN_BUNDLE_WEIGHT + contents.weight(),
+            None => Fraction::new(1, i64::from(item.max_stack_size())),
+        }
+    }
+
+    /// Returns whether `item` may be put in a bundle at all.
+    ///
+    /// Vanilla equivalent: `BundleContents.canItemBeInBundle`.
+    #[must_use]
+    pub fn can_item_be_in_bundle(item: &ItemStack) -> bool {
+        !item.is_empty() && item.can_fit_inside_container_items()
+    }
+
+    /// Returns how many of `item` still fit.
+    ///
+    /// Vanilla equivalent: `BundleContents.Mutable.getMaxAmountToAdd`.
+    #[must_use]
+    pub fn max_amount_to_add(&self, item: &ItemStack) -> i32 {
+        let remaining = Fraction::ONE - self.weight();
+        remaining.div_floor(Self::item_weight(item)).max(0) as i32
+    }
+
+    /// Moves as many of `item` into the bundle as fit, returning how many moved.
+    ///
+    /// The new items go to the front, merged with an equal stack if there is one.
+    ///
+    /// Vanilla equivalent: `BundleContents.Mutable.tryInsert`.
+    pub fn try_insert(&mut self, item: &mut ItemStack) -> i32 {
+        if !Self::can_item_be_in_bundle(item) {
+            return 0;
+        }
+        let amount = item.count().min(self.max_amount_to_add(item));
+        if amount == 0 {
+            return 0;
+        }
+
+        let existing = if item.is_stackable() {
+            self.items
+                .iter()
+                .position(|stack| ItemStack::is_same_item_same_components(stack, item))
+        } else {
+            None
+        };
+        let inserted = match existing {
+            Some(index) => {
+                let stack = self.items.remove(index);
+                item.shrink(amount);
+                stack.copy_with_count(stack.count() + amount)
+            }
+            None => item.split(amount),
+        };
+        self.items.insert(0, inserted);
+        amount
+    }
+
+    /// Takes out the selected stack, or the newest one when nothing is selected.
+    ///
+    /// Vanilla equivalent: `BundleContents.Mutable.removeOne`.
+    pub fn remove_one(&mut self) -> Option<ItemStack> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let index = usize::try_from(self.selected_item)
+            .ok()
+            .filter(|&index| index < self.items.len())
+            .unwrap_or(0);
+        let removed = self.items.remove(index);
+        self.toggle_selected_item(-1);
+        Some(removed)
+    }
+}
+
+/// Network format: VarInt count, then each stack.
+impl WriteTo for BundleContents {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        VarInt(self.items.len() as i32).write(writer)?;
+        for item in &self.items {
+            item.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFrom for BundleContents {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+        let count = ReadError::check_len(count, Self::MAX_NETWORK_ITEMS)?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            let item = ItemStack::read(data)?;
+            if item.is_empty() {
+                return Err(ReadError::malformed("Empty item stack in bundle"));
+            }
+            items.push(item);
+        }
+        Ok(Self::new(items))
+    }
+}
+
+/// NBT format: list of item stacks.
+impl ToNbtTag for BundleContents {
+    fn to_nbt_tag(self) -> NbtTag {
+        let items = self
+            .items
+            .into_iter()
+            .filter_map(|item| match item.to_nbt_tag() {
+                NbtTag::Compound(compound) => Some(compound),
+                _ => None,
+            })
+            .collect();
+        NbtTag::List(NbtList::Compound(items))
+    }
+}
+
+impl FromNbtTag for BundleContents {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let list = tag.list()?;
+        let items = list
+            .compounds()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|compound| ItemStack::from_borrowed_compound(&compound))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::new(items))
+    }
+}
+
+impl HashComponent for BundleContents {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.start_list();
+        for item in &self.items {
+            hasher.put_raw_bytes(&item.compute_hash().to_le_bytes());
+        }
+        hasher.end_list();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vanilla_items::{self, ITEMS};
+    use crate::{REGISTRY, Registry, vanilla_item_tags};
+
+    fn init_test_registries() {
+        REGISTRY.get_or_init(|| {
+            let mut registry = Registry::new_empty();
+            vanilla_items::register_items(&mut registry.items);
+            vanilla_item_tags::register_item_tags(&mut registry.items);
+            registry.freeze();
+            registry
+        });
+    }
+
+    #[test]
+    fn fractions_stay_reduced() {
+        let third = Fraction::new(1, 3);
+        assert_eq!(third + third + third, Fraction::ONE);
+        assert_eq!(Fraction::new(2, -4), Fraction::new(-1, 2));
+        assert_eq!((Fraction::ONE - third).div_floor(Fraction::new(1, 16)), 10);
+        assert!(third < Fraction::new(1, 2));
+    }
+
+    #[test]
+    fn bundle_holds_one_stack_worth() {
+        init_test_registries();
+        let mut contents = BundleContents::default();
+        let mut pearls = ItemStack::with_count(&ITEMS.ender_pearl, 16);
+        let mut stone = ItemStack::with_count(&ITEMS.stone, 64);
+
+        assert_eq!(contents.try_insert(&mut pearls), 16);
+        assert!(pearls.is_empty());
+        assert_eq!(contents.weight(), Fraction::ONE);
+        assert_eq!(contents.try_insert(&mut stone), 0);
+        assert_eq!(stone.count(), 64);
+
+        let removed = contents.remove_one().expect("the bundle has pearls");
+        assert_eq!(removed.count(), 16);
+        assert!(contents.is_empty());
+        assert_eq!(contents.try_insert(&mut stone), 64);
+    }
+
+    #[test]
+    fn inserting_merges_and_moves_to_front() {
+        init_test_registries();
+        let mut contents = BundleContents::default();
+        contents.try_insert(&mut ItemStack::with_count(&ITEMS.stone, 8));
+        contents.try_insert(&mut ItemStack::with_count(&ITEMS.dirt, 8));
+        contents.try_insert(&mut ItemStack::with_count(&ITEMS.stone, 8));
+
+        assert_eq!(contents.items().len(), 2);
+        assert!(contents.items()[0].is(&ITEMS.stone));
+        assert_eq!(contents.items()[0].count(), 16);
+    }
+
+    #[test]
+    fn nested_bundles_weigh_their_contents() {
+        init_test_registries();
+        let mut inner = BundleContents::default();
+        inner.try_insert(&mut ItemStack::with_count(&ITEMS.stone, 32));
+        let mut bundle = ItemStack::new(&ITEMS.bundle);
+        bundle.set(BUNDLE_CONTENTS, inner);
+
+        assert_eq!(
+            BundleContents::item_weight(&bundle),
+            Fraction::new(1, 16) + Fraction::new(1, 2)
+        );
+        let mut outer = BundleContents::default();
+        assert_eq!(outer.max_amount_to_add(&bundle), 1);
+        assert_eq!(outer.try_insert(&mut bundle), 1);
+    }
+}
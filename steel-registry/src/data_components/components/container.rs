@@ -0,0 +1,139 @@
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::codec::VarInt;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+use crate::item_stack::ItemStack;
+
+/// The items of a container kept on its item (`minecraft:container`), like a
+/// broken shulker box's contents.
+///
+/// Trailing empty slots are left out, so there can be fewer items than the
+/// container has slots.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ItemContainerContents {
+    items: Vec<ItemStack>,
+}
+
+impl ItemContainerContents {
+    /// Maximum number of slots, matching vanilla's `ItemContainerContents.MAX_SIZE`.
+    pub const MAX_SIZE: usize = 256;
+
+    /// Creates the contents from a container's slots.
+    ///
+    /// Vanilla equivalent: `ItemContainerContents.fromItems`.
+    #[must_use]
+    pub fn from_items(items: &[ItemStack]) -> Self {
+        let len = items
+            .iter()
+            .rposition(|item| !item.is_empty())
+            .map_or(0, |last| last + 1)
+            .min(Self::MAX_SIZE);
+        Self {
+            items: items[..len].to_vec(),
+        }
+    }
+
+    /// Copies the contents into `slots`, emptying the slots past the end.
+    ///
+    /// Vanilla equivalent: `ItemContainerContents.copyInto`.
+    pub fn copy_into(&self, slots: &mut [ItemStack]) {
+        for (i, slot) in slots.iter_mut().enumerate() {
+            *slot = self.items.get(i).cloned().unwrap_or_else(ItemStack::empty);
+        }
+    }
+
+    /// Returns the items that aren't empty.
+    pub fn non_empty_items(&self) -> impl Iterator<Item = &ItemStack> {
+        self.items.iter().filter(|item| !item.is_empty())
+    }
+
+    /// Returns whether there are no items at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.non_empty_items().next().is_none()
+    }
+}
+
+/// Network format: VarInt count, then each slot as an optional item stack.
+impl WriteTo for ItemContainerContents {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        VarInt(self.items.len() as i32).write(writer)?;
+        for item in &self.items {
+            item.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFrom for ItemContainerContents {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+        let count = ReadError::check_len(count, Self::MAX_SIZE)?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            items.push(ItemStack::read(data)?);
+        }
+        Ok(Self { items })
+    }
+}
+
+/// NBT format: list of `{slot: int, item: {...}}` for the slots that aren't empty.
+impl ToNbtTag for ItemContainerContents {
+    fn to_nbt_tag(self) -> NbtTag {
+        let slots = self
+            .items
+            .into_iter()
+            .enumerate()
+            .filter(|(_, item)| !item.is_empty())
+            .map(|(slot, item)| {
+                let mut compound = NbtCompound::new();
+                compound.insert("slot", slot as i32);
+                compound.insert("item", item.to_nbt_tag());
+                compound
+            })
+            .collect();
+        NbtTag::List(NbtList::Compound(slots))
+    }
+}
+
+impl FromNbtTag for ItemContainerContents {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let list = tag.list()?;
+        let mut items = Vec::new();
+        for compound in list.compounds().unwrap_or_default() {
+            let slot = usize::try_from(compound.int("slot")?).ok()?;
+            if slot >= Self::MAX_SIZE {
+                return None;
+            }
+            let item = ItemStack::from_borrowed_compound(&compound.compound("item")?)?;
+            if items.len() <= slot {
+                items.resize(slot + 1, ItemStack::empty());
+            }
+            items[slot] = item;
+        }
+        Some(Self { items })
+    }
+}
+
+/// Hashed like the NBT format, as a list of `{slot, item}` maps.
+impl HashComponent for ItemContainerContents {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.start_list();
+        for (slot, item) in self.items.iter().enumerate() {
+            if item.is_empty() {
+                continue;
+            }
+            let mut entry = ComponentHasher::new();
+            entry.put_fields(&[
+                ("slot", (slot as i32).compute_hash()),
+                ("item", item.compute_hash()),
+            ]);
+            hasher.put_raw_bytes(&entry.finish().to_le_bytes());
+        }
+        hasher.end_list();
+    }
+}
@@ -0,0 +1,69 @@
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::{NbtCompound, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::nbt::merge_compound;
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+/// Arbitrary NBT attached to an item (`minecraft:custom_data`).
+///
+/// The game never interprets it; commands, loot functions and plugins use it
+/// to tag items with their own data.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CustomData {
+    pub tag: NbtCompound,
+}
+
+impl CustomData {
+    #[must_use]
+    pub const fn new(tag: NbtCompound) -> Self {
+        Self { tag }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_empty()
+    }
+
+    /// Returns a copy with `other` merged in, like vanilla's `CustomData.update`.
+    #[must_use]
+    pub fn merged(&self, other: &NbtCompound) -> Self {
+        let mut tag = self.tag.clone();
+        merge_compound(&mut tag, other);
+        Self { tag }
+    }
+}
+
+/// Network format: the compound as a nameless tag.
+impl WriteTo for CustomData {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(&[0x0A])?;
+        WriteTo::write(&self.tag, writer)
+    }
+}
+
+impl ReadFrom for CustomData {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        Ok(Self::new(NbtCompound::read(data)?))
+    }
+}
+
+/// NBT format: the compound itself.
+impl ToNbtTag for CustomData {
+    fn to_nbt_tag(self) -> NbtTag {
+        NbtTag::Compound(self.tag)
+    }
+}
+
+impl FromNbtTag for CustomData {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        Some(Self::new(tag.compound()?.to_owned()))
+    }
+}
+
+impl HashComponent for CustomData {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        self.tag.hash_component(hasher);
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::{Cursor, Write};
+
+use rustc_hash::FxHashMap;
+use simdnbt::owned::{NbtCompound, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::Identifier;
+use steel_utils::hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+use crate::{REGISTRY, RegistryExt};
+
+/// The property the debug stick has selected for each block.
+///
+/// Maps block key to the name of one of that block's properties. Entries naming
+/// unknown blocks or properties are dropped when decoding, like vanilla's codec.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DebugStickState {
+    pub properties: FxHashMap<Identifier, &'static str>,
+}
+
+impl DebugStickState {
+    /// Returns the selected property name for `block`, if any.
+    #[must_use]
+    pub fn get(&self, block: &Identifier) -> Option<&'static str> {
+        self.properties.get(block).copied()
+    }
+
+    /// Selects `property` for `block`.
+    pub fn set(&mut self, block: Identifier, property: &'static str) {
+        self.properties.insert(block, property);
+    }
+
+    fn to_compound(&self) -> NbtCompound {
+        let mut compound = NbtCompound::new();
+        for (block, property) in &self.properties {
+            compound.insert(block.to_string(), NbtTag::String((*property).into()));
+        }
+        compound
+    }
+
+    fn from_compound(compound: &NbtCompound) -> Self {
+        let mut properties = FxHashMap::default();
+        for (key, value) in compound.iter() {
+            let Ok(block_key) = key.to_str().parse::<Identifier>() else {
+                continue;
+            };
+            let Some(block) = REGISTRY.blocks.by_key(&block_key) else {
+                continue;
+            };
+            let Some(name) = value.string().map(|s| s.to_str()) else {
+                continue;
+            };
+            if let Some(property) = block.properties.iter().find(|p| p.get_name() == name) {
+                properties.insert(block_key, property.get_name());
+            }
+        }
+        Self { properties }
+    }
+}
+
+/// Network format: the NBT compound as a nameless tag (the component has no dedicated stream codec).
+impl WriteTo for DebugStickState {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(&[0x0A])?;
+        WriteTo::write(&self.to_compound(), writer)
+    }
+}
+
+impl ReadFrom for DebugStickState {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        Ok(Self::from_compound(&NbtCompound::read(data)?))
+    }
+}
+
+/// NBT format: compound with block identifiers as keys and property names as values.
+impl ToNbtTag for DebugStickState {
+    fn to_nbt_tag(self) -> NbtTag {
+        NbtTag::Compound(self.to_compound())
+    }
+}
+
+impl FromNbtTag for DebugStickState {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        Some(Self::from_compound(&tag.compound()?.to_owned()))
+    }
+}
+
+impl HashComponent for DebugStickState {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.start_map();
+        let mut entries: Vec<_> = self
+            .properties
+            .iter()
+            .map(|(block, property)| {
+                let mut key_hasher = ComponentHasher::new();
+                key_hasher.put_string(&block.to_string());
+                let mut value_hasher = ComponentHasher::new();
+                value_hasher.put_string(property);
+                HashEntry::new(key_hasher, value_hasher)
+            })
+            .collect();
+        sort_map_entries(&mut entries);
+        for entry in &entries {
+            hasher.put_raw_bytes(&entry.key_bytes);
+            hasher.put_raw_bytes(&entry.value_bytes);
+        }
+        hasher.end_map();
+    }
+}
@@ -0,0 +1,61 @@
+//! Enchantable component for items that can be enchanted at an enchanting table.
+
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::{NbtCompound, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::codec::VarInt;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+/// How well an item takes enchantments at an enchanting table.
+///
+/// Higher values give more and better enchantments for the same level cost.
+///
+/// Vanilla equivalent: `Enchantable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Enchantable {
+    /// The enchantment value, always positive.
+    pub value: i32,
+}
+
+/// Network format: VarInt value.
+impl WriteTo for Enchantable {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        VarInt(self.value).write(writer)
+    }
+}
+
+impl ReadFrom for Enchantable {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let value = VarInt::read(data)?.0;
+        if value <= 0 {
+            return Err(ReadError::malformed(format!(
+                "Enchantment value must be positive, but was {value}"
+            )));
+        }
+        Ok(Self { value })
+    }
+}
+
+/// NBT format: compound with an int `value`.
+impl ToNbtTag for Enchantable {
+    fn to_nbt_tag(self) -> NbtTag {
+        let mut compound = NbtCompound::new();
+        compound.insert("value", self.value);
+        NbtTag::Compound(compound)
+    }
+}
+
+impl FromNbtTag for Enchantable {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let value = tag.compound()?.get("value")?.int()?;
+        (value > 0).then_some(Self { value })
+    }
+}
+
+impl HashComponent for Enchantable {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.put_fields(&[("value", self.value.compute_hash())]);
+    }
+}
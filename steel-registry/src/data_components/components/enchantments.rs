@@ -4,7 +4,7 @@ use simdnbt::{FromNbtTag, ToNbtTag};
 use steel_utils::Identifier;
 use steel_utils::codec::VarInt;
 use steel_utils::hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries};
-use steel_utils::serial::{ReadFrom, WriteTo};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
 
 use crate::{REGISTRY, RegistryExt};
 
@@ -87,14 +87,9 @@ impl WriteTo for ItemEnchantments {
 }
 
 impl ReadFrom for ItemEnchantments {
-    fn read(data: &mut std::io::Cursor<&[u8]>) -> std::io::Result<Self> {
-        let count = VarInt::read(data)?.0;
-        if !(0..=256).contains(&count) {
-            return Err(std::io::Error::other(format!(
-                "Enchantment count out of range: {count}"
-            )));
-        }
-        let count = count as usize;
+    fn read(data: &mut std::io::Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+        let count = ReadError::check_len(count, 256)?;
         let mut levels = FxHashMap::default();
         for _ in 0..count {
             let id = VarInt::read(data)?.0 as usize;
@@ -102,7 +97,7 @@ impl ReadFrom for ItemEnchantments {
             let enchantment = REGISTRY
                 .enchantments
                 .by_id(id)
-                .ok_or_else(|| std::io::Error::other(format!("Unknown enchantment id: {id}")))?;
+                .ok_or_else(|| ReadError::malformed(format!("Unknown enchantment id: {id}")))?;
             levels.insert(enchantment.key.clone(), level);
         }
         Ok(Self { levels })
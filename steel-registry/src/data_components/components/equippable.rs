@@ -1,10 +1,10 @@
 //! Equippable component for armor and equipment items.
 
-use std::io::{Result, Write};
+use std::io::{self, Write};
 
 use steel_utils::{
     hash::{ComponentHasher, HashComponent},
-    serial::{ReadFrom, WriteTo},
+    serial::{ReadError, ReadFrom, WriteTo},
 };
 
 /// Equipment slot for the equippable component.
@@ -66,7 +66,7 @@ pub struct Equippable {
 }
 
 impl WriteTo for Equippable {
-    fn write(&self, _writer: &mut impl Write) -> Result<()> {
+    fn write(&self, _writer: &mut impl Write) -> io::Result<()> {
         // TODO: Implement proper Equippable serialization
         // Format: slot (VarInt), equip_sound (SoundEvent), model (Optional), camera_overlay (Optional),
         //         allowed_entities (Optional HolderSet), dispensable (bool), swappable (bool),
@@ -76,7 +76,7 @@ impl WriteTo for Equippable {
 }
 
 impl ReadFrom for Equippable {
-    fn read(_data: &mut std::io::Cursor<&[u8]>) -> Result<Self> {
+    fn read(_data: &mut std::io::Cursor<&[u8]>) -> Result<Self, ReadError> {
         // TODO: Implement proper Equippable deserialization
         Ok(Self {
             slot: EquippableSlot::Chest,
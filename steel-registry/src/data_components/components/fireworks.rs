@@ -0,0 +1,313 @@
+//! Fireworks component for firework rockets.
+
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::codec::VarInt;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+/// The shape of a firework explosion.
+///
+/// Vanilla equivalent: `FireworkExplosion.Shape`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireworkExplosionShape {
+    SmallBall,
+    LargeBall,
+    Star,
+    Creeper,
+    Burst,
+}
+
+impl FireworkExplosionShape {
+    const ALL: [Self; 5] = [
+        Self::SmallBall,
+        Self::LargeBall,
+        Self::Star,
+        Self::Creeper,
+        Self::Burst,
+    ];
+
+    /// Returns the serialized name of this shape.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::SmallBall => "small_ball",
+            Self::LargeBall => "large_ball",
+            Self::Star => "star",
+            Self::Creeper => "creeper",
+            Self::Burst => "burst",
+        }
+    }
+
+    /// Parses a shape from its serialized name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|shape| shape.name() == name)
+    }
+}
+
+/// A single explosion of a firework rocket or firework star.
+///
+/// Vanilla equivalent: `FireworkExplosion`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FireworkExplosion {
+    pub shape: FireworkExplosionShape,
+    /// RGB colors of the initial burst.
+    pub colors: Vec<i32>,
+    /// RGB colors the particles fade into.
+    pub fade_colors: Vec<i32>,
+    pub has_trail: bool,
+    pub has_twinkle: bool,
+}
+
+fn write_int_list(values: &[i32], writer: &mut impl Write) -> std::io::Result<()> {
+    VarInt(values.len() as i32).write(writer)?;
+    for value in values {
+        value.write(writer)?;
+    }
+    Ok(())
+}
+
+fn read_int_list(data: &mut Cursor<&[u8]>) -> Result<Vec<i32>, ReadError> {
+    let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+    // Every color takes four bytes, so a longer list can't be valid
+    let remaining = data
+        .get_ref()
+        .len()
+        .saturating_sub(data.position() as usize);
+    let count = ReadError::check_len(count, remaining / 4)?;
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        values.push(i32::read(data)?);
+    }
+    Ok(values)
+}
+
+fn hash_int_list(values: &[i32]) -> i32 {
+    let mut hasher = ComponentHasher::new();
+    hasher.start_list();
+    for value in values {
+        hasher.put_raw_bytes(&value.compute_hash().to_le_bytes());
+    }
+    hasher.end_list();
+    hasher.finish()
+}
+
+/// Network format: VarInt shape, VarInt-counted lists of fixed-width int
+/// colors and fade colors, then the trail and twinkle flags.
+impl WriteTo for FireworkExplosion {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        VarInt(self.shape as i32).write(writer)?;
+        write_int_list(&self.colors, writer)?;
+        write_int_list(&self.fade_colors, writer)?;
+        self.has_trail.write(writer)?;
+        self.has_twinkle.write(writer)
+    }
+}
+
+impl ReadFrom for FireworkExplosion {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let shape_id = VarInt::read(data)?.0;
+        let shape = usize::try_from(shape_id)
+            .ok()
+            .and_then(|id| FireworkExplosionShape::ALL.get(id).copied())
+            .ok_or_else(|| ReadError::malformed(format!("Unknown firework shape {shape_id}")))?;
+        Ok(Self {
+            shape,
+            colors: read_int_list(data)?,
+            fade_colors: read_int_list(data)?,
+            has_trail: bool::read(data)?,
+            has_twinkle: bool::read(data)?,
+        })
+    }
+}
+
+impl From<&crate::loot_table::FireworkExplosion> for FireworkExplosion {
+    fn from(explosion: &crate::loot_table::FireworkExplosion) -> Self {
+        use crate::loot_table::FireworkShape;
+
+        let shape = match explosion.shape {
+            FireworkShape::SmallBall => FireworkExplosionShape::SmallBall,
+            FireworkShape::LargeBall => FireworkExplosionShape::LargeBall,
+            FireworkShape::Star => FireworkExplosionShape::Star,
+            FireworkShape::Creeper => FireworkExplosionShape::Creeper,
+            FireworkShape::Burst => FireworkExplosionShape::Burst,
+        };
+        Self {
+            shape,
+            colors: explosion.colors.to_vec(),
+            fade_colors: explosion.fade_colors.to_vec(),
+            has_trail: explosion.has_trail,
+            has_twinkle: explosion.has_twinkle,
+        }
+    }
+}
+
+impl FireworkExplosion {
+    fn to_nbt(&self) -> NbtCompound {
+        let mut compound = NbtCompound::new();
+        compound.insert("shape", self.shape.name());
+        if !self.colors.is_empty() {
+            compound.insert("colors", NbtTag::IntArray(self.colors.clone()));
+        }
+        if !self.fade_colors.is_empty() {
+            compound.insert("fade_colors", NbtTag::IntArray(self.fade_colors.clone()));
+        }
+        if self.has_trail {
+            compound.insert("has_trail", true);
+        }
+        if self.has_twinkle {
+            compound.insert("has_twinkle", true);
+        }
+        compound
+    }
+
+    fn from_nbt(compound: &NbtCompound) -> Option<Self> {
+        let shape = FireworkExplosionShape::from_name(&compound.string("shape")?.to_str())?;
+        Some(Self {
+            shape,
+            colors: compound.int_array("colors").unwrap_or_default().to_vec(),
+            fade_colors: compound
+                .int_array("fade_colors")
+                .unwrap_or_default()
+                .to_vec(),
+            has_trail: compound.byte("has_trail").is_some_and(|b| b != 0),
+            has_twinkle: compound.byte("has_twinkle").is_some_and(|b| b != 0),
+        })
+    }
+}
+
+impl HashComponent for FireworkExplosion {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // Fields at their default value are left out, like vanilla's optional fields
+        let mut fields = vec![("shape", self.shape.name().compute_hash())];
+        if !self.colors.is_empty() {
+            fields.push(("colors", hash_int_list(&self.colors)));
+        }
+        if !self.fade_colors.is_empty() {
+            fields.push(("fade_colors", hash_int_list(&self.fade_colors)));
+        }
+        if self.has_trail {
+            fields.push(("has_trail", true.compute_hash()));
+        }
+        if self.has_twinkle {
+            fields.push(("has_twinkle", true.compute_hash()));
+        }
+        hasher.put_fields(&fields);
+    }
+}
+
+/// The flight duration and explosions of a firework rocket (`minecraft:fireworks`).
+///
+/// Vanilla equivalent: `Fireworks`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Fireworks {
+    /// How long the rocket flies, in gunpowder used to craft it (0-255).
+    pub flight_duration: i32,
+    pub explosions: Vec<FireworkExplosion>,
+}
+
+impl Fireworks {
+    /// Maximum number of explosions, matching vanilla's `Fireworks.MAX_EXPLOSIONS`.
+    pub const MAX_EXPLOSIONS: usize = 256;
+}
+
+/// Network format: VarInt flight duration, then a VarInt-counted list of explosions.
+impl WriteTo for Fireworks {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        VarInt(self.flight_duration).write(writer)?;
+        VarInt(self.explosions.len() as i32).write(writer)?;
+        for explosion in &self.explosions {
+            explosion.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFrom for Fireworks {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let flight_duration = VarInt::read(data)?.0;
+        let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+        let count = ReadError::check_len(count, Self::MAX_EXPLOSIONS)?;
+        let mut explosions = Vec::with_capacity(count);
+        for _ in 0..count {
+            explosions.push(FireworkExplosion::read(data)?);
+        }
+        Ok(Self {
+            flight_duration,
+            explosions,
+        })
+    }
+}
+
+/// NBT format: compound with a byte `flight_duration` and an `explosions` list.
+impl ToNbtTag for Fireworks {
+    fn to_nbt_tag(self) -> NbtTag {
+        let mut compound = NbtCompound::new();
+        if self.flight_duration != 0 {
+            compound.insert("flight_duration", self.flight_duration as u8 as i8);
+        }
+        if !self.explosions.is_empty() {
+            compound.insert(
+                "explosions",
+                NbtList::Compound(
+                    self.explosions
+                        .iter()
+                        .map(FireworkExplosion::to_nbt)
+                        .collect(),
+                ),
+            );
+        }
+        NbtTag::Compound(compound)
+    }
+}
+
+impl FromNbtTag for Fireworks {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let NbtTag::Compound(compound) = tag.to_owned() else {
+            return None;
+        };
+        let flight_duration = compound
+            .byte("flight_duration")
+            .map_or(0, |duration| i32::from(duration as u8));
+        let explosions = match compound.list("explosions") {
+            Some(list) => list
+                .compounds()?
+                .iter()
+                .map(FireworkExplosion::from_nbt)
+                .collect::<Option<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        if explosions.len() > Self::MAX_EXPLOSIONS {
+            return None;
+        }
+        Some(Self {
+            flight_duration,
+            explosions,
+        })
+    }
+}
+
+impl HashComponent for Fireworks {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        let mut fields = Vec::new();
+        if self.flight_duration != 0 {
+            fields.push((
+                "flight_duration",
+                (self.flight_duration as u8).compute_hash(),
+            ));
+        }
+        if !self.explosions.is_empty() {
+            let mut explosions = ComponentHasher::new();
+            explosions.start_list();
+            for explosion in &self.explosions {
+                explosions.put_raw_bytes(&explosion.compute_hash().to_le_bytes());
+            }
+            explosions.end_list();
+            fields.push(("explosions", explosions.finish()));
+        }
+        hasher.put_fields(&fields);
+    }
+}
@@ -0,0 +1,136 @@
+//! Lodestone tracker component for lodestone compasses.
+
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::{NbtCompound, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+use steel_utils::{BlockPos, Identifier};
+
+use crate::entity_data::GlobalPos;
+
+/// The lodestone a compass points at (`minecraft:lodestone_tracker`).
+///
+/// Vanilla equivalent: `LodestoneTracker`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LodestoneTracker {
+    /// The tracked lodestone, or `None` once it was destroyed. A compass without
+    /// a target spins randomly.
+    pub target: Option<GlobalPos>,
+    /// Whether the target is cleared when the lodestone disappears. Compasses
+    /// bound with commands can set this to false to point anywhere.
+    pub tracked: bool,
+}
+
+impl LodestoneTracker {
+    /// Creates a tracker pointing at the lodestone at `target`.
+    #[must_use]
+    pub const fn new(target: GlobalPos) -> Self {
+        Self {
+            target: Some(target),
+            tracked: true,
+        }
+    }
+}
+
+fn global_pos_to_nbt(target: &GlobalPos) -> NbtCompound {
+    let mut compound = NbtCompound::new();
+    compound.insert("dimension", target.dimension.to_string());
+    compound.insert(
+        "pos",
+        NbtTag::IntArray(vec![target.pos.x(), target.pos.y(), target.pos.z()]),
+    );
+    compound
+}
+
+fn global_pos_from_nbt(compound: &NbtCompound) -> Option<GlobalPos> {
+    let dimension = compound.string("dimension")?.to_str().parse().ok()?;
+    let pos = compound.int_array("pos")?;
+    let [x, y, z] = pos[..] else {
+        return None;
+    };
+    Some(GlobalPos::new(dimension, BlockPos::new(x, y, z)))
+}
+
+/// Network format: optional target (dimension identifier and packed block
+/// position), then the tracked flag.
+impl WriteTo for LodestoneTracker {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        match &self.target {
+            Some(target) => {
+                true.write(writer)?;
+                target.dimension.write(writer)?;
+                target.pos.write(writer)?;
+            }
+            None => false.write(writer)?,
+        }
+        self.tracked.write(writer)
+    }
+}
+
+impl ReadFrom for LodestoneTracker {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let target = if bool::read(data)? {
+            let dimension = Identifier::read(data)?;
+            Some(GlobalPos::new(dimension, BlockPos::read(data)?))
+        } else {
+            None
+        };
+        Ok(Self {
+            target,
+            tracked: bool::read(data)?,
+        })
+    }
+}
+
+/// NBT format: compound with an optional `target` (`dimension` and `pos`) and
+/// a `tracked` byte that is left out while true.
+impl ToNbtTag for LodestoneTracker {
+    fn to_nbt_tag(self) -> NbtTag {
+        let mut compound = NbtCompound::new();
+        if let Some(target) = &self.target {
+            compound.insert("target", global_pos_to_nbt(target));
+        }
+        if !self.tracked {
+            compound.insert("tracked", false);
+        }
+        NbtTag::Compound(compound)
+    }
+}
+
+impl FromNbtTag for LodestoneTracker {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let NbtTag::Compound(compound) = tag.to_owned() else {
+            return None;
+        };
+        let target = match compound.compound("target") {
+            Some(target) => Some(global_pos_from_nbt(target)?),
+            None => None,
+        };
+        Some(Self {
+            target,
+            tracked: compound.byte("tracked").is_none_or(|b| b != 0),
+        })
+    }
+}
+
+impl HashComponent for LodestoneTracker {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        let mut fields = Vec::new();
+        if let Some(target) = &self.target {
+            let mut pos = ComponentHasher::new();
+            pos.put_int_array(&[target.pos.x(), target.pos.y(), target.pos.z()]);
+            let mut global_pos = ComponentHasher::new();
+            global_pos.put_fields(&[
+                ("dimension", target.dimension.to_string().compute_hash()),
+                ("pos", pos.finish()),
+            ]);
+            fields.push(("target", global_pos.finish()));
+        }
+        if !self.tracked {
+            fields.push(("tracked", false.compute_hash()));
+        }
+        hasher.put_fields(&fields);
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::NbtTag;
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::codec::VarInt;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::nbt::{list_from_tags, list_to_tags};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+use text_components::TextComponent;
+
+/// Tooltip lines shown below an item's name (`minecraft:lore`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ItemLore {
+    pub lines: Vec<TextComponent>,
+}
+
+impl ItemLore {
+    /// Maximum number of lines, matching vanilla's `ItemLore.MAX_LINES`.
+    pub const MAX_LINES: usize = 256;
+
+    /// Creates lore from the given lines, dropping any past [`Self::MAX_LINES`].
+    #[must_use]
+    pub fn new(mut lines: Vec<TextComponent>) -> Self {
+        lines.truncate(Self::MAX_LINES);
+        Self { lines }
+    }
+
+    /// Returns a copy with `line` appended, unless the lore is already full.
+    #[must_use]
+    pub fn with_line_added(&self, line: TextComponent) -> Self {
+        let mut lines = self.lines.clone();
+        if lines.len() < Self::MAX_LINES {
+            lines.push(line);
+        }
+        Self { lines }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+/// Network format: VarInt count, then each line as a text component.
+impl WriteTo for ItemLore {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        VarInt(self.lines.len() as i32).write(writer)?;
+        for line in &self.lines {
+            line.write(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFrom for ItemLore {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let count = usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)?;
+        let count = ReadError::check_len(count, Self::MAX_LINES)?;
+        let mut lines = Vec::with_capacity(count);
+        for _ in 0..count {
+            lines.push(TextComponent::read(data)?);
+        }
+        Ok(Self { lines })
+    }
+}
+
+/// NBT format: list of text components.
+impl ToNbtTag for ItemLore {
+    fn to_nbt_tag(self) -> NbtTag {
+        NbtTag::List(list_from_tags(
+            self.lines.into_iter().map(ToNbtTag::to_nbt_tag).collect(),
+        ))
+    }
+}
+
+impl FromNbtTag for ItemLore {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let NbtTag::List(list) = tag.to_owned() else {
+            return None;
+        };
+        let tags = list_to_tags(&list);
+        if tags.len() > Self::MAX_LINES {
+            return None;
+        }
+        let lines = tags
+            .iter()
+            .map(TextComponent::from_nbt)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { lines })
+    }
+}
+
+impl HashComponent for ItemLore {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        // List elements are hashed individually and written as their 4-byte hashes
+        hasher.start_list();
+        for line in &self.lines {
+            hasher.put_raw_bytes(&line.compute_hash().to_le_bytes());
+        }
+        hasher.end_list();
+    }
+}
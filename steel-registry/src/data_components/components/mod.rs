@@ -1,9 +1,29 @@
 //! Individual component type definitions.
 
+mod bundle_contents;
+mod container;
+mod custom_data;
+mod debug_stick_state;
+mod enchantable;
 mod enchantments;
 mod equippable;
+mod fireworks;
+mod lodestone_tracker;
+mod lore;
+mod repairable;
 mod tool;
+mod typed_entity_data;
 
+pub use bundle_contents::{BundleContents, Fraction};
+pub use container::ItemContainerContents;
+pub use custom_data::CustomData;
+pub use debug_stick_state::DebugStickState;
+pub use enchantable::Enchantable;
 pub use enchantments::ItemEnchantments;
 pub use equippable::{Equippable, EquippableSlot};
+pub use fireworks::{FireworkExplosion, FireworkExplosionShape, Fireworks};
+pub use lodestone_tracker::LodestoneTracker;
+pub use lore::ItemLore;
+pub use repairable::Repairable;
 pub use tool::{Tool, ToolRule};
+pub use typed_entity_data::TypedEntityData;
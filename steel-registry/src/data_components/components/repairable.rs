@@ -0,0 +1,143 @@
+//! Repairable component naming the items that repair an item in an anvil.
+
+use std::io::{Cursor, Write};
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::Identifier;
+use steel_utils::codec::VarInt;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+use steel_utils::serial::{ReadError, ReadFrom, WriteTo};
+
+use crate::items::ItemRef;
+use crate::{REGISTRY, RegistryExt, TaggedRegistryExt};
+
+/// The items that repair an item when combined with it in an anvil.
+///
+/// Vanilla stores a `HolderSet<Item>`, which is either an item tag or a
+/// direct list of items.
+///
+/// Vanilla equivalent: `Repairable`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Repairable {
+    /// Every item in this item tag repairs the item.
+    Tag(Identifier),
+    /// Exactly these items repair the item.
+    Items(Vec<Identifier>),
+}
+
+impl Repairable {
+    /// Returns whether `item` can be used to repair the item.
+    ///
+    /// Vanilla equivalent: `Repairable.isValidRepairItem`.
+    #[must_use]
+    pub fn is_valid_repair_item(&self, item: ItemRef) -> bool {
+        match self {
+            Self::Tag(tag) => REGISTRY.items.is_in_tag(item, tag),
+            Self::Items(items) => items.contains(&item.key),
+        }
+    }
+
+    /// Parses the codec form: `#tag`, a single item, or a list of items.
+    fn from_items_tag(tag: &simdnbt::borrow::NbtTag) -> Option<Self> {
+        if let Some(value) = tag.string() {
+            let value = value.to_str();
+            return Some(match value.strip_prefix('#') {
+                Some(tag) => Self::Tag(tag.parse().ok()?),
+                None => Self::Items(vec![value.parse().ok()?]),
+            });
+        }
+        let items = tag
+            .list()?
+            .strings()?
+            .iter()
+            .map(|item| item.to_str().parse().ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::Items(items))
+    }
+}
+
+/// Network format: VarInt 0 followed by the tag identifier, or VarInt
+/// `count + 1` followed by that many item registry ids.
+impl WriteTo for Repairable {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            Self::Tag(tag) => {
+                VarInt(0).write(writer)?;
+                tag.write(writer)
+            }
+            Self::Items(items) => {
+                VarInt(items.len() as i32 + 1).write(writer)?;
+                for key in items {
+                    let id = REGISTRY
+                        .items
+                        .id_from_key(key)
+                        .ok_or_else(|| std::io::Error::other(format!("Unknown item: {key}")))?;
+                    VarInt(id as i32).write(writer)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ReadFrom for Repairable {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let count = VarInt::read(data)?.0;
+        if count == 0 {
+            return Ok(Self::Tag(Identifier::read(data)?));
+        }
+        let count = usize::try_from(count - 1).map_err(|_| ReadError::InvalidLength)?;
+        let count = ReadError::check_len(count, 4096)?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            let id = VarInt::read(data)?.0 as usize;
+            let item = REGISTRY
+                .items
+                .by_id(id)
+                .ok_or_else(|| ReadError::malformed(format!("Unknown item id: {id}")))?;
+            items.push(item.key.clone());
+        }
+        Ok(Self::Items(items))
+    }
+}
+
+/// NBT format: compound whose `items` is `#tag`, a single item, or a list of items.
+impl ToNbtTag for Repairable {
+    fn to_nbt_tag(self) -> NbtTag {
+        let items = match self {
+            Self::Tag(tag) => NbtTag::String(format!("#{tag}").into()),
+            Self::Items(items) if items.len() == 1 => NbtTag::String(items[0].to_string().into()),
+            Self::Items(items) => NbtTag::List(NbtList::String(
+                items.iter().map(|item| item.to_string().into()).collect(),
+            )),
+        };
+        let mut compound = NbtCompound::new();
+        compound.insert("items", items);
+        NbtTag::Compound(compound)
+    }
+}
+
+impl FromNbtTag for Repairable {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        Self::from_items_tag(&tag.compound()?.get("items")?)
+    }
+}
+
+impl HashComponent for Repairable {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        let mut items = ComponentHasher::new();
+        match self {
+            Self::Tag(tag) => items.put_string(&format!("#{tag}")),
+            Self::Items(keys) if keys.len() == 1 => keys[0].hash_component(&mut items),
+            Self::Items(keys) => {
+                items.start_list();
+                for key in keys {
+                    items.put_raw_bytes(&key.compute_hash().to_le_bytes());
+                }
+                items.end_list();
+            }
+        }
+        hasher.put_fields(&[("items", items.finish())]);
+    }
+}
@@ -1,12 +1,12 @@
 //! Tool component for mining speed and drop behavior.
 
-use std::io::{Result, Write};
+use std::io::{self, Write};
 use std::str::FromStr;
 
 use steel_utils::{
     BlockStateId, Identifier,
     hash::{ComponentHasher, HashComponent},
-    serial::{ReadFrom, WriteTo},
+    serial::{ReadError, ReadFrom, WriteTo},
 };
 
 use crate::{REGISTRY, TaggedRegistryExt};
@@ -143,7 +143,7 @@ impl Tool {
 }
 
 impl WriteTo for Tool {
-    fn write(&self, _writer: &mut impl Write) -> Result<()> {
+    fn write(&self, _writer: &mut impl Write) -> io::Result<()> {
         // TODO: Implement proper Tool serialization
         // Format: rules (list), default_mining_speed (float), damage_per_block (VarInt)
         Ok(())
@@ -151,7 +151,7 @@ impl WriteTo for Tool {
 }
 
 impl ReadFrom for Tool {
-    fn read(_data: &mut std::io::Cursor<&[u8]>) -> Result<Self> {
+    fn read(_data: &mut std::io::Cursor<&[u8]>) -> Result<Self, ReadError> {
         // TODO: Implement proper Tool deserialization
         Ok(Self::default())
     }
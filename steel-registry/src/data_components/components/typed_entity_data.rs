@@ -0,0 +1,59 @@
+use simdnbt::owned::{NbtCompound, NbtTag};
+use simdnbt::{FromNbtTag, ToNbtTag};
+use steel_utils::Identifier;
+use steel_utils::hash::{ComponentHasher, HashComponent};
+
+/// Entity or block entity data stored on an item, tagged with the type it belongs to.
+///
+/// Used by `minecraft:block_entity_data` (e.g. creative pick-block with data)
+/// and `minecraft:entity_data`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedEntityData {
+    /// The block entity or entity type the data belongs to.
+    pub id: Identifier,
+    /// The saved data, without the `id` field.
+    pub tag: NbtCompound,
+}
+
+impl TypedEntityData {
+    /// Creates new typed data for the given type id.
+    #[must_use]
+    pub const fn new(id: Identifier, tag: NbtCompound) -> Self {
+        Self { id, tag }
+    }
+
+    /// Returns the stored data merged with the `id` field, matching vanilla's codec.
+    fn to_compound(&self) -> NbtCompound {
+        let mut compound = self.tag.clone();
+        compound.insert("id", NbtTag::String(self.id.to_string().into()));
+        compound
+    }
+}
+
+/// NBT format: the data compound with an added `id` string field.
+impl ToNbtTag for TypedEntityData {
+    fn to_nbt_tag(self) -> NbtTag {
+        NbtTag::Compound(self.to_compound())
+    }
+}
+
+impl FromNbtTag for TypedEntityData {
+    fn from_nbt_tag(tag: simdnbt::borrow::NbtTag) -> Option<Self> {
+        let compound = tag.compound()?;
+        let id = compound.string("id")?.to_str().parse::<Identifier>().ok()?;
+        let mut tag = NbtCompound::new();
+        for (key, value) in compound.iter() {
+            let key = key.to_str();
+            if key != "id" {
+                tag.insert(key.to_string(), value.to_owned());
+            }
+        }
+        Some(Self { id, tag })
+    }
+}
+
+impl HashComponent for TypedEntityData {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        self.to_compound().hash_component(hasher);
+    }
+}
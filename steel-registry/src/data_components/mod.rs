@@ -32,7 +32,11 @@ pub mod vanilla_components;
 
 // Re-export core types
 pub use component_data::{Component, ComponentData, ComponentDataDiscriminant};
-pub use components::{Equippable, EquippableSlot, Tool, ToolRule};
+pub use components::{
+    BundleContents, CustomData, DebugStickState, Enchantable, Equippable, EquippableSlot,
+    FireworkExplosion, FireworkExplosionShape, Fireworks, ItemContainerContents, ItemLore,
+    LodestoneTracker, Repairable, Tool, ToolRule, TypedEntityData,
+};
 pub use registry::{
     ComponentEntry,
     ComponentEntryRef,
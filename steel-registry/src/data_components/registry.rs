@@ -14,18 +14,18 @@ use simdnbt::{
 };
 use std::{
     fmt::Debug,
-    io::{Cursor, Result, Write},
+    io::{self, Cursor, Write},
     marker::PhantomData,
 };
 
 use steel_utils::{
     Identifier,
     codec::VarInt,
-    serial::{ReadFrom, WriteTo},
+    serial::{ReadError, ReadFrom, WriteTo},
 };
 
 use super::component_data::{Component, ComponentData, ComponentDataDiscriminant};
-use super::components::ItemEnchantments;
+use super::components::{ItemEnchantments, ItemLore};
 use super::vanilla_components::{
     ATTRIBUTE_MODIFIERS, BREAK_SOUND, ENCHANTMENTS, LORE, MAX_STACK_SIZE, RARITY, REPAIR_COST,
     TOOLTIP_DISPLAY,
@@ -74,10 +74,10 @@ impl<T> DataComponentType<T> {
 // ==================== ComponentEntry ====================
 
 /// Reader function for deserializing a component from network format.
-pub type NetworkReader = fn(&mut Cursor<&[u8]>) -> Result<ComponentData>;
+pub type NetworkReader = fn(&mut Cursor<&[u8]>) -> Result<ComponentData, ReadError>;
 
 /// Writer function for serializing a component to network format.
-pub type NetworkWriter = fn(&ComponentData, &mut Vec<u8>) -> Result<()>;
+pub type NetworkWriter = fn(&ComponentData, &mut Vec<u8>) -> io::Result<()>;
 
 /// Reader function for deserializing a component from NBT format.
 pub type NbtReader = fn(BorrowedNbtTag) -> Option<ComponentData>;
@@ -385,7 +385,7 @@ impl DataComponentMap {
     pub fn common_item_components() -> Self {
         let mut map = FxHashMap::default();
         map.insert(MAX_STACK_SIZE.key.clone(), ComponentData::I32(64));
-        map.insert(LORE.key.clone(), ComponentData::Todo);
+        map.insert(LORE.key.clone(), ComponentData::Lore(ItemLore::default()));
         map.insert(
             ENCHANTMENTS.key.clone(),
             ComponentData::Enchantments(ItemEnchantments::empty()),
@@ -575,6 +575,16 @@ impl DataComponentPatch {
         self.entries.remove(&component.key);
     }
 
+    /// Marks a component as removed by its key.
+    pub fn remove_raw(&mut self, key: Identifier) {
+        self.entries.insert(key, ComponentPatchEntry::Removed);
+    }
+
+    /// Clears any patch entry for a component by its key.
+    pub fn clear_raw(&mut self, key: &Identifier) {
+        self.entries.remove(key);
+    }
+
     /// Gets the patch entry for a key.
     #[must_use]
     pub fn get_entry(&self, key: &Identifier) -> Option<&ComponentPatchEntry> {
@@ -625,7 +635,7 @@ impl DataComponentPatch {
 // ==================== Network Serialization ====================
 
 impl WriteTo for DataComponentPatch {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         use crate::{REGISTRY, RegistryExt};
 
         let mut added: Vec<(&Identifier, &ComponentData)> = Vec::new();
@@ -674,11 +684,11 @@ impl WriteTo for DataComponentPatch {
 }
 
 impl ReadFrom for DataComponentPatch {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         use crate::{REGISTRY, RegistryExt};
 
-        let added_count = VarInt::read(data)?.0 as usize;
-        let removed_count = VarInt::read(data)?.0 as usize;
+        let added_count = read_count(data)?;
+        let removed_count = read_count(data)?;
 
         log::info!("Reading DataComponentPatch: added={added_count}, removed={removed_count}");
 
@@ -693,7 +703,7 @@ impl ReadFrom for DataComponentPatch {
                 .data_components
                 .get_key_by_id(type_id)
                 .ok_or_else(|| {
-                    std::io::Error::other(format!("Unknown component type ID: {type_id}"))
+                    ReadError::malformed(format!("Unknown component type ID: {type_id}"))
                 })?
                 .clone();
 
@@ -702,7 +712,7 @@ impl ReadFrom for DataComponentPatch {
             let entry = REGISTRY
                 .data_components
                 .by_id(type_id)
-                .ok_or_else(|| std::io::Error::other(format!("No entry for component: {key}")))?;
+                .ok_or_else(|| ReadError::malformed(format!("No entry for component: {key}")))?;
 
             let component_data = (entry.network_reader)(data).map_err(|e| {
                 log::error!("    Failed to read component {key}: {e}");
@@ -725,7 +735,7 @@ impl ReadFrom for DataComponentPatch {
                 .data_components
                 .get_key_by_id(type_id)
                 .ok_or_else(|| {
-                    std::io::Error::other(format!("Unknown component type ID: {type_id}"))
+                    ReadError::malformed(format!("Unknown component type ID: {type_id}"))
                 })?
                 .clone();
 
@@ -736,44 +746,39 @@ impl ReadFrom for DataComponentPatch {
     }
 }
 
+/// Reads a `VarInt` element count, rejecting negative values.
+fn read_count(data: &mut Cursor<&[u8]>) -> Result<usize, ReadError> {
+    usize::try_from(VarInt::read(data)?.0).map_err(|_| ReadError::InvalidLength)
+}
+
 impl DataComponentPatch {
     /// Reads a patch where each component value is prefixed with a VarInt byte length.
     ///
     /// Vanilla uses this for untrusted client packets (e.g., creative mode slot)
     /// via `DataComponentPatch.DELIMITED_STREAM_CODEC`.
-    pub fn read_delimited(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    pub fn read_delimited(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         use crate::{REGISTRY, RegistryExt};
         use std::io::Read;
 
-        let added_count = VarInt::read(data)?.0 as usize;
-        let removed_count = VarInt::read(data)?.0 as usize;
+        let added_count = read_count(data)?;
+        let removed_count = read_count(data)?;
 
         const MAX_COMPONENTS: usize = 65_536;
         const MAX_COMPONENT_BYTES: usize = 2 * 1024 * 1024;
 
-        if added_count.saturating_add(removed_count) > MAX_COMPONENTS {
-            return Err(std::io::Error::other(format!(
-                "Component patch too large: {added_count} added + {removed_count} removed > {MAX_COMPONENTS}"
-            )));
-        }
+        ReadError::check_len(added_count.saturating_add(removed_count), MAX_COMPONENTS)?;
 
         let mut patch = Self::new();
 
         for _ in 0..added_count {
             let type_id = VarInt::read(data)?.0 as usize;
-            let byte_len = VarInt::read(data)?.0 as usize;
-
-            if byte_len > MAX_COMPONENT_BYTES {
-                return Err(std::io::Error::other(format!(
-                    "Component data too large: {byte_len} bytes > {MAX_COMPONENT_BYTES}"
-                )));
-            }
+            let byte_len = ReadError::check_len(read_count(data)?, MAX_COMPONENT_BYTES)?;
 
             let key = REGISTRY
                 .data_components
                 .get_key_by_id(type_id)
                 .ok_or_else(|| {
-                    std::io::Error::other(format!("Unknown component type ID: {type_id}"))
+                    ReadError::malformed(format!("Unknown component type ID: {type_id}"))
                 })?
                 .clone();
 
@@ -804,7 +809,7 @@ impl DataComponentPatch {
                 .data_components
                 .get_key_by_id(type_id)
                 .ok_or_else(|| {
-                    std::io::Error::other(format!("Unknown component type ID: {type_id}"))
+                    ReadError::malformed(format!("Unknown component type ID: {type_id}"))
                 })?
                 .clone();
             patch.entries.insert(key, ComponentPatchEntry::Removed);
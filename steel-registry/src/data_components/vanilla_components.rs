@@ -5,12 +5,16 @@
 use steel_utils::Identifier;
 use text_components::TextComponent;
 
-use super::component_data::ComponentData;
+use super::component_data::{Component, ComponentData};
 use super::registry::DataComponentRegistry;
 pub use super::registry::DataComponentType;
 
 // Re-export component types for convenience
-pub use super::components::{Equippable, EquippableSlot, ItemEnchantments, Tool, ToolRule};
+pub use super::components::{
+    BundleContents, CustomData, DebugStickState, Enchantable, Equippable, EquippableSlot,
+    FireworkExplosion, FireworkExplosionShape, Fireworks, ItemContainerContents, ItemEnchantments,
+    ItemLore, LodestoneTracker, Repairable, Tool, ToolRule, TypedEntityData,
+};
 
 // ==================== Fully Implemented Components ====================
 
@@ -56,13 +60,22 @@ pub const ENCHANTMENT_GLINT_OVERRIDE: DataComponentType<bool> =
 pub const POTION_DURATION_SCALE: DataComponentType<f32> =
     DataComponentType::new(Identifier::vanilla_static("potion_duration_scale"));
 
+pub const BLOCK_ENTITY_DATA: DataComponentType<TypedEntityData> =
+    DataComponentType::new(Identifier::vanilla_static("block_entity_data"));
+
+pub const DEBUG_STICK_STATE: DataComponentType<DebugStickState> =
+    DataComponentType::new(Identifier::vanilla_static("debug_stick_state"));
+
+pub const CUSTOM_DATA: DataComponentType<CustomData> =
+    DataComponentType::new(Identifier::vanilla_static("custom_data"));
+
+pub const LORE: DataComponentType<ItemLore> =
+    DataComponentType::new(Identifier::vanilla_static("lore"));
+
 // ==================== Stub Component Keys ====================
 // These components are registered but use placeholder serialization.
 // They use the Todo ComponentData variant.
 
-pub const CUSTOM_DATA: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("custom_data"));
-
 pub const USE_EFFECTS: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("use_effects"));
 
@@ -75,8 +88,6 @@ pub const DAMAGE_TYPE: DataComponentType<()> =
 pub const ITEM_MODEL: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("item_model"));
 
-pub const LORE: DataComponentType<()> = DataComponentType::new(Identifier::vanilla_static("lore"));
-
 pub const RARITY: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("rarity"));
 
@@ -124,10 +135,10 @@ pub const WEAPON: DataComponentType<()> =
 pub const ATTACK_RANGE: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("attack_range"));
 
-pub const ENCHANTABLE: DataComponentType<()> =
+pub const ENCHANTABLE: DataComponentType<Enchantable> =
     DataComponentType::new(Identifier::vanilla_static("enchantable"));
 
-pub const REPAIRABLE: DataComponentType<()> =
+pub const REPAIRABLE: DataComponentType<Repairable> =
     DataComponentType::new(Identifier::vanilla_static("repairable"));
 
 pub const DEATH_PROTECTION: DataComponentType<()> =
@@ -159,7 +170,7 @@ pub const DYED_COLOR: DataComponentType<()> =
 pub const MAP_COLOR: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("map_color"));
 
-pub const MAP_ID: DataComponentType<()> =
+pub const MAP_ID: DataComponentType<i32> =
     DataComponentType::new(Identifier::vanilla_static("map_id"));
 
 pub const MAP_DECORATIONS: DataComponentType<()> =
@@ -171,7 +182,7 @@ pub const MAP_POST_PROCESSING: DataComponentType<()> =
 pub const CHARGED_PROJECTILES: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("charged_projectiles"));
 
-pub const BUNDLE_CONTENTS: DataComponentType<()> =
+pub const BUNDLE_CONTENTS: DataComponentType<BundleContents> =
     DataComponentType::new(Identifier::vanilla_static("bundle_contents"));
 
 pub const POTION_CONTENTS: DataComponentType<()> =
@@ -188,18 +199,12 @@ pub const WRITTEN_BOOK_CONTENT: DataComponentType<()> =
 
 pub const TRIM: DataComponentType<()> = DataComponentType::new(Identifier::vanilla_static("trim"));
 
-pub const DEBUG_STICK_STATE: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("debug_stick_state"));
-
 pub const ENTITY_DATA: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("entity_data"));
 
 pub const BUCKET_ENTITY_DATA: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("bucket_entity_data"));
 
-pub const BLOCK_ENTITY_DATA: DataComponentType<()> =
-    DataComponentType::new(Identifier::vanilla_static("block_entity_data"));
-
 pub const INSTRUMENT: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("instrument"));
 
@@ -218,13 +223,13 @@ pub const PROVIDES_BANNER_PATTERNS: DataComponentType<()> =
 pub const RECIPES: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("recipes"));
 
-pub const LODESTONE_TRACKER: DataComponentType<()> =
+pub const LODESTONE_TRACKER: DataComponentType<LodestoneTracker> =
     DataComponentType::new(Identifier::vanilla_static("lodestone_tracker"));
 
 pub const FIREWORK_EXPLOSION: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("firework_explosion"));
 
-pub const FIREWORKS: DataComponentType<()> =
+pub const FIREWORKS: DataComponentType<Fireworks> =
     DataComponentType::new(Identifier::vanilla_static("fireworks"));
 
 pub const PROFILE: DataComponentType<()> =
@@ -239,7 +244,7 @@ pub const BASE_COLOR: DataComponentType<()> =
 pub const POT_DECORATIONS: DataComponentType<()> =
     DataComponentType::new(Identifier::vanilla_static("pot_decorations"));
 
-pub const CONTAINER: DataComponentType<()> =
+pub const CONTAINER: DataComponentType<ItemContainerContents> =
     DataComponentType::new(Identifier::vanilla_static("container"));
 
 pub const BLOCK_STATE: DataComponentType<()> =
@@ -349,7 +354,9 @@ pub const SHULKER_COLOR: DataComponentType<()> =
 /// These components use the Todo variant as a placeholder.
 macro_rules! register_stub {
     ($registry:expr, $key:expr) => {{
-        fn network_reader(cursor: &mut std::io::Cursor<&[u8]>) -> std::io::Result<ComponentData> {
+        fn network_reader(
+            cursor: &mut std::io::Cursor<&[u8]>,
+        ) -> Result<ComponentData, steel_utils::serial::ReadError> {
             // Stub: read nothing, return Todo
             let _ = cursor;
             Ok(ComponentData::Todo)
@@ -383,7 +390,9 @@ macro_rules! register_stub {
 // ==================== Custom Network Readers/Writers ====================
 
 /// Network reader for VarInt-encoded i32 components.
-fn varint_reader(cursor: &mut std::io::Cursor<&[u8]>) -> std::io::Result<ComponentData> {
+fn varint_reader(
+    cursor: &mut std::io::Cursor<&[u8]>,
+) -> Result<ComponentData, steel_utils::serial::ReadError> {
     use steel_utils::{codec::VarInt, serial::ReadFrom};
     let value = VarInt::read(cursor)?;
     Ok(ComponentData::I32(value.0))
@@ -399,6 +408,44 @@ fn varint_writer(data: &ComponentData, writer: &mut Vec<u8>) -> std::io::Result<
     }
 }
 
+/// Network reader for `block_entity_data`: VarInt block entity type id, then a compound tag.
+fn block_entity_data_reader(
+    cursor: &mut std::io::Cursor<&[u8]>,
+) -> Result<ComponentData, steel_utils::serial::ReadError> {
+    use crate::{REGISTRY, RegistryExt};
+    use simdnbt::owned::NbtCompound;
+    use steel_utils::{
+        codec::VarInt,
+        serial::{ReadError, ReadFrom},
+    };
+
+    let id = VarInt::read(cursor)?.0;
+    let block_entity_type = usize::try_from(id)
+        .ok()
+        .and_then(|id| REGISTRY.block_entity_types.by_id(id))
+        .ok_or_else(|| ReadError::malformed(format!("Unknown block entity type id: {id}")))?;
+    let tag = NbtCompound::read(cursor)?;
+    Ok(TypedEntityData::new(block_entity_type.key.clone(), tag).into_data())
+}
+
+/// Network writer for `block_entity_data`.
+fn block_entity_data_writer(data: &ComponentData, writer: &mut Vec<u8>) -> std::io::Result<()> {
+    use crate::{REGISTRY, RegistryExt};
+    use steel_utils::{codec::VarInt, serial::WriteTo};
+
+    let Some(data) = TypedEntityData::from_data_ref(data) else {
+        return Err(std::io::Error::other("Component type mismatch"));
+    };
+    let id = REGISTRY
+        .block_entity_types
+        .id_from_key(&data.id)
+        .ok_or_else(|| std::io::Error::other(format!("Unknown block entity type: {}", data.id)))?;
+    VarInt(id as i32).write(writer)?;
+    // Nameless compound tag: type byte followed by the payload
+    writer.push(0x0A);
+    WriteTo::write(&data.tag, writer)
+}
+
 /// Registers all vanilla data components.
 ///
 /// IMPORTANT: The registration order MUST match vanilla's DataComponents.java exactly,
@@ -408,7 +455,7 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
 
     // Order must match vanilla's DataComponents.java exactly!
     // 0: custom_data
-    register_stub!(registry, CUSTOM_DATA.key.clone());
+    registry.register(CUSTOM_DATA, ComponentDataDiscriminant::CustomData);
     // 1: max_stack_size
     registry.register_custom_network(
         MAX_STACK_SIZE,
@@ -445,7 +492,7 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 10: item_model
     register_stub!(registry, ITEM_MODEL.key.clone());
     // 11: lore
-    register_stub!(registry, LORE.key.clone());
+    registry.register(LORE, ComponentDataDiscriminant::Lore);
     // 12: rarity
     register_stub!(registry, RARITY.key.clone());
     // 13: enchantments
@@ -490,11 +537,11 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 30: attack_range
     register_stub!(registry, ATTACK_RANGE.key.clone());
     // 31: enchantable
-    register_stub!(registry, ENCHANTABLE.key.clone());
+    registry.register(ENCHANTABLE, ComponentDataDiscriminant::Enchantable);
     // 32: equippable
     registry.register(EQUIPPABLE, ComponentDataDiscriminant::Equippable);
     // 33: repairable
-    register_stub!(registry, REPAIRABLE.key.clone());
+    registry.register(REPAIRABLE, ComponentDataDiscriminant::Repairable);
     // 34: glider
     registry.register(GLIDER, ComponentDataDiscriminant::Empty);
     // 35: tooltip_style
@@ -520,7 +567,12 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 45: map_color
     register_stub!(registry, MAP_COLOR.key.clone());
     // 46: map_id
-    register_stub!(registry, MAP_ID.key.clone());
+    registry.register_custom_network(
+        MAP_ID,
+        ComponentDataDiscriminant::I32,
+        varint_reader,
+        varint_writer,
+    );
     // 47: map_decorations
     register_stub!(registry, MAP_DECORATIONS.key.clone());
     // 48: map_post_processing
@@ -528,7 +580,7 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 49: charged_projectiles
     register_stub!(registry, CHARGED_PROJECTILES.key.clone());
     // 50: bundle_contents
-    register_stub!(registry, BUNDLE_CONTENTS.key.clone());
+    registry.register(BUNDLE_CONTENTS, ComponentDataDiscriminant::BundleContents);
     // 51: potion_contents
     register_stub!(registry, POTION_CONTENTS.key.clone());
     // 52: potion_duration_scale
@@ -542,13 +594,21 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 56: trim
     register_stub!(registry, TRIM.key.clone());
     // 57: debug_stick_state
-    register_stub!(registry, DEBUG_STICK_STATE.key.clone());
+    registry.register(
+        DEBUG_STICK_STATE,
+        ComponentDataDiscriminant::DebugStickState,
+    );
     // 58: entity_data
     register_stub!(registry, ENTITY_DATA.key.clone());
     // 59: bucket_entity_data
     register_stub!(registry, BUCKET_ENTITY_DATA.key.clone());
     // 60: block_entity_data
-    register_stub!(registry, BLOCK_ENTITY_DATA.key.clone());
+    registry.register_custom_network(
+        BLOCK_ENTITY_DATA,
+        ComponentDataDiscriminant::TypedEntityData,
+        block_entity_data_reader,
+        block_entity_data_writer,
+    );
     // 61: instrument
     register_stub!(registry, INSTRUMENT.key.clone());
     // 62: provides_trim_material
@@ -562,11 +622,14 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 66: recipes
     register_stub!(registry, RECIPES.key.clone());
     // 67: lodestone_tracker
-    register_stub!(registry, LODESTONE_TRACKER.key.clone());
+    registry.register(
+        LODESTONE_TRACKER,
+        ComponentDataDiscriminant::LodestoneTracker,
+    );
     // 68: firework_explosion
     register_stub!(registry, FIREWORK_EXPLOSION.key.clone());
     // 69: fireworks
-    register_stub!(registry, FIREWORKS.key.clone());
+    registry.register(FIREWORKS, ComponentDataDiscriminant::Fireworks);
     // 70: profile
     register_stub!(registry, PROFILE.key.clone());
     // 71: note_block_sound
@@ -578,7 +641,7 @@ pub fn register_vanilla_data_components(registry: &mut DataComponentRegistry) {
     // 74: pot_decorations
     register_stub!(registry, POT_DECORATIONS.key.clone());
     // 75: container
-    register_stub!(registry, CONTAINER.key.clone());
+    registry.register(CONTAINER, ComponentDataDiscriminant::Container);
     // 76: block_state
     register_stub!(registry, BLOCK_STATE.key.clone());
     // 77: bees
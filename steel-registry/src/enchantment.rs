@@ -1,10 +1,13 @@
+use crate::item_stack::ItemStack;
 use crate::items::ItemRef;
 pub use crate::loot_table::EquipmentSlotGroup;
+use crate::vanilla_items::ITEMS;
 use crate::{REGISTRY, RegistryEntry, RegistryExt, TaggedRegistryExt};
 use rustc_hash::FxHashMap;
 use simdnbt::ToNbtTag;
 use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
 use steel_utils::Identifier;
+use steel_utils::random::Random;
 
 /// Enchanting cost formula: `base + per_level_above_first * (level - 1)`.
 #[derive(Debug, Clone, Copy)]
@@ -13,6 +16,14 @@ pub struct EnchantmentCost {
     pub per_level_above_first: i32,
 }
 
+impl EnchantmentCost {
+    /// Returns the cost at `level`.
+    #[must_use]
+    pub const fn calculate(self, level: i32) -> i32 {
+        self.base + self.per_level_above_first * (level - 1)
+    }
+}
+
 #[derive(Debug)]
 pub struct Enchantment {
     pub key: Identifier,
@@ -94,6 +105,31 @@ fn parse_tag_ref(tag_ref: &str) -> Option<Identifier> {
 }
 
 impl Enchantment {
+    /// Returns the lowest enchanting power that can roll this enchantment at `level`.
+    #[must_use]
+    pub const fn get_min_cost(&self, level: i32) -> i32 {
+        self.min_cost.calculate(level)
+    }
+
+    /// Returns the highest enchanting power that can roll this enchantment at `level`.
+    #[must_use]
+    pub const fn get_max_cost(&self, level: i32) -> i32 {
+        self.max_cost.calculate(level)
+    }
+
+    /// Checks if an enchanting table can offer this enchantment for the given
+    /// item: it must be supported and, if set, in `primary_items`.
+    ///
+    /// Vanilla equivalent: `Enchantment.isPrimaryItem`.
+    #[must_use]
+    pub fn is_primary_item(&self, item: ItemRef) -> bool {
+        self.can_enchant(item)
+            && self
+                .primary_items
+                .and_then(parse_tag_ref)
+                .is_none_or(|tag| REGISTRY.items.is_in_tag(item, &tag))
+    }
+
     /// Checks if this enchantment can be applied to the given item via `supported_items` tag.
     pub fn can_enchant(&self, item: ItemRef) -> bool {
         let Some(tag) = parse_tag_ref(self.supported_items) else {
@@ -147,6 +183,124 @@ impl Enchantment {
 
 pub type EnchantmentRef = &'static Enchantment;
 
+/// An enchantment together with the level it was rolled at.
+///
+/// Vanilla equivalent: `EnchantmentInstance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnchantmentInstance {
+    pub enchantment: EnchantmentRef,
+    pub level: i32,
+}
+
+/// Rolls the level cost an enchanting table shows in `slot` (0 to 2).
+///
+/// Returns 0 for items that aren't enchantable.
+///
+/// Vanilla equivalent: `EnchantmentHelper.getEnchantmentCost`.
+pub fn get_enchantment_cost(
+    random: &mut impl Random,
+    slot: i32,
+    bookcases: i32,
+    item: &ItemStack,
+) -> i32 {
+    if item.get_enchantment_value() == 0 {
+        return 0;
+    }
+    let bookcases = bookcases.min(15);
+    let selected =
+        random.next_i32_bounded(8) + 1 + (bookcases >> 1) + random.next_i32_bounded(bookcases + 1);
+    match slot {
+        0 => (selected / 3).max(1),
+        1 => selected * 2 / 3 + 1,
+        _ => selected.max(bookcases * 2),
+    }
+}
+
+/// Rolls the enchantments an enchanting table applies for the given level cost.
+///
+/// Vanilla equivalent: `EnchantmentHelper.selectEnchantment`.
+pub fn select_enchantment(
+    random: &mut impl Random,
+    item: &ItemStack,
+    cost: i32,
+    source: impl IntoIterator<Item = EnchantmentRef>,
+) -> Vec<EnchantmentInstance> {
+    let mut results = Vec::new();
+    let value = item.get_enchantment_value();
+    if value == 0 {
+        return results;
+    }
+
+    let mut cost =
+        cost + 1 + random.next_i32_bounded(value / 4 + 1) + random.next_i32_bounded(value / 4 + 1);
+    let deviation = (random.next_f32() + random.next_f32() - 1.0) * 0.15;
+    cost = ((cost as f32 + cost as f32 * deviation + 0.5).floor() as i32).max(1);
+
+    let mut available = get_available_enchantment_results(cost, item, source);
+    if available.is_empty() {
+        return results;
+    }
+
+    results.extend(get_random_instance(random, &available));
+    while random.next_i32_bounded(50) <= cost {
+        if let Some(last) = results.last() {
+            available.retain(|instance| {
+                Enchantment::are_compatible(last.enchantment, instance.enchantment)
+            });
+        }
+        if available.is_empty() {
+            break;
+        }
+        results.extend(get_random_instance(random, &available));
+        cost /= 2;
+    }
+    results
+}
+
+/// Returns the highest level of every enchantment in `source` that `cost`
+/// can roll on `item`. Books take any enchantment.
+///
+/// Vanilla equivalent: `EnchantmentHelper.getAvailableEnchantmentResults`.
+#[must_use]
+pub fn get_available_enchantment_results(
+    cost: i32,
+    item: &ItemStack,
+    source: impl IntoIterator<Item = EnchantmentRef>,
+) -> Vec<EnchantmentInstance> {
+    let is_book = item.is(&ITEMS.book);
+    source
+        .into_iter()
+        .filter(|enchantment| is_book || enchantment.is_primary_item(item.item))
+        .filter_map(|enchantment| {
+            (1..=enchantment.max_level as i32)
+                .rev()
+                .find(|&level| {
+                    cost >= enchantment.get_min_cost(level)
+                        && cost <= enchantment.get_max_cost(level)
+                })
+                .map(|level| EnchantmentInstance { enchantment, level })
+        })
+        .collect()
+}
+
+/// Picks an instance with a chance proportional to its enchantment's weight.
+///
+/// Vanilla equivalent: `WeightedRandom.getRandomItem`.
+fn get_random_instance(
+    random: &mut impl Random,
+    instances: &[EnchantmentInstance],
+) -> Option<EnchantmentInstance> {
+    let total: i32 = instances.iter().map(|i| i.enchantment.weight as i32).sum();
+    if total <= 0 {
+        return None;
+    }
+    let mut selection = random.next_i32_bounded(total);
+    instances.iter().copied().find(|instance| {
+        selection -= instance.enchantment.weight as i32;
+        selection < 0
+    })
+}
+
 impl PartialEq for EnchantmentRef {
     #[expect(clippy::disallowed_methods)] // This IS the PartialEq impl; ptr::eq is correct here
     fn eq(&self, other: &Self) -> bool {
@@ -1,6 +1,9 @@
+use glam::Vec3;
 use rustc_hash::FxHashMap;
 use steel_utils::Identifier;
 
+use crate::attribute::AttributeRef;
+
 /// Mob category for spawn classification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MobCategory {
@@ -91,6 +94,45 @@ pub struct EntityType {
 
     /// Behavioral flags for collision and interaction.
     pub flags: EntityFlags,
+
+    /// Base values of the attributes this type has, empty for non-living entities.
+    ///
+    /// Vanilla: `DefaultAttributes.SUPPLIERS`.
+    pub default_attributes: &'static [(AttributeRef, f64)],
+
+    /// Where passengers sit relative to the entity's feet, by passenger index.
+    ///
+    /// Vanilla: the `PASSENGER` points of `EntityType.getDefaultAttachments()`.
+    pub passenger_attachments: &'static [Vec3],
+}
+
+impl EntityType {
+    /// Returns true if entities of this type have attributes, which all living entities do.
+    #[must_use]
+    pub const fn has_attributes(&self) -> bool {
+        !self.default_attributes.is_empty()
+    }
+
+    /// Gets the base value this type gives `attribute`, if it has it.
+    #[must_use]
+    pub fn default_attribute(&self, attribute: AttributeRef) -> Option<f64> {
+        self.default_attributes
+            .iter()
+            .find(|(candidate, _)| candidate.key == attribute.key)
+            .map(|&(_, base)| base)
+    }
+
+    /// Gets where the passenger at `index` sits, before rotating by the entity's yaw.
+    ///
+    /// Passengers past the last point share it. Vanilla: `EntityAttachments.getClamped()`.
+    #[must_use]
+    pub fn passenger_attachment(&self, index: usize) -> Vec3 {
+        self.passenger_attachments
+            .get(index)
+            .or(self.passenger_attachments.last())
+            .copied()
+            .unwrap_or(Vec3::new(0.0, self.dimensions.height, 0.0))
+    }
 }
 
 pub type EntityTypeRef = &'static EntityType;
@@ -159,3 +201,100 @@ crate::impl_registry!(
 );
 
 crate::impl_tagged_registry!(EntityTypeRegistry, types_by_key, "entity type");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::AttributeRegistry;
+    use crate::{RegistryExt, vanilla_attributes, vanilla_entities};
+
+    fn create_test_registry() -> EntityTypeRegistry {
+        let mut registry = EntityTypeRegistry::new();
+        vanilla_entities::register_entity_types(&mut registry);
+        registry
+    }
+
+    #[test]
+    fn every_living_entity_type_has_attributes() {
+        // Living entities that spawn as MISC, so their category doesn't give them away
+        const MISC_LIVING: [&str; 7] = [
+            "armor_stand",
+            "copper_golem",
+            "iron_golem",
+            "mannequin",
+            "snow_golem",
+            "villager",
+            "player",
+        ];
+        for (_, entity_type) in create_test_registry().iter() {
+            let living = entity_type.mob_category != MobCategory::Misc
+                || MISC_LIVING.contains(&&*entity_type.key.path);
+            assert_eq!(
+                entity_type.has_attributes(),
+                living,
+                "{} attributes",
+                entity_type.key
+            );
+            if living {
+                assert!(
+                    entity_type
+                        .default_attribute(vanilla_attributes::MAX_HEALTH)
+                        .is_some(),
+                    "{} has no max health",
+                    entity_type.key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn default_attributes_are_registered() {
+        let mut attributes = AttributeRegistry::new();
+        vanilla_attributes::register_attributes(&mut attributes);
+        for (_, entity_type) in create_test_registry().iter() {
+            for (attribute, base) in entity_type.default_attributes {
+                assert!(
+                    attributes.by_key(&attribute.key).is_some(),
+                    "{} uses unregistered attribute {}",
+                    entity_type.key,
+                    attribute.key
+                );
+                assert_eq!(attribute.sanitize_value(*base), *base);
+            }
+        }
+    }
+
+    #[test]
+    fn zombie_matches_vanilla() {
+        let zombie = vanilla_entities::ZOMBIE;
+        assert_eq!(
+            zombie.default_attribute(vanilla_attributes::MAX_HEALTH),
+            Some(20.0)
+        );
+        assert_eq!(
+            zombie.default_attribute(vanilla_attributes::MOVEMENT_SPEED),
+            Some(f64::from(0.23_f32))
+        );
+        assert_eq!(
+            zombie.default_attribute(vanilla_attributes::ATTACK_DAMAGE),
+            Some(3.0)
+        );
+        assert_eq!(
+            zombie.default_attribute(vanilla_attributes::TEMPT_RANGE),
+            None
+        );
+    }
+
+    #[test]
+    fn passenger_attachments_are_clamped() {
+        let minecart = vanilla_entities::MINECART;
+        assert_eq!(
+            minecart.passenger_attachment(0),
+            Vec3::new(0.0, 0.1875, 0.0)
+        );
+        assert_eq!(
+            minecart.passenger_attachment(3),
+            minecart.passenger_attachment(0)
+        );
+    }
+}
@@ -1,26 +1,32 @@
 //! Item stack implementation.
 
-use std::io::{Cursor, Result, Write};
+use std::io::{self, Cursor, Write};
 
 use rand::RngExt;
 
 use steel_utils::{
     Identifier,
     codec::VarInt,
-    serial::{ReadFrom, WriteTo},
+    hash::{ComponentHasher, HashComponent},
+    nbt::snbt::{parse_snbt, parse_snbt_compound},
+    serial::{ReadError, ReadFrom, WriteTo},
 };
+use text_components::TextComponent;
 
 use crate::{
-    REGISTRY, RegistryEntry, RegistryExt,
+    REGISTRY, RegistryEntry, RegistryExt, TaggedRegistryExt,
     data_components::{
         Component, ComponentData, ComponentPatchEntry, DataComponentMap, DataComponentPatch,
         DataComponentType,
         vanilla_components::{
-            DAMAGE, ENCHANTMENTS, EQUIPPABLE, Equippable, EquippableSlot, ItemEnchantments,
-            MAX_DAMAGE, MAX_STACK_SIZE, TOOL, Tool, UNBREAKABLE,
+            CUSTOM_DATA, CUSTOM_NAME, CustomData, DAMAGE, ENCHANTABLE, ENCHANTMENTS, EQUIPPABLE,
+            Equippable, EquippableSlot, FIREWORKS, ITEM_NAME, ItemEnchantments, ItemLore, LORE,
+            MAX_DAMAGE, MAX_STACK_SIZE, REPAIR_COST, REPAIRABLE, STORED_ENCHANTMENTS, TOOL, Tool,
+            UNBREAKABLE,
         },
     },
     items::ItemRef,
+    vanilla_item_tags::SHULKER_BOXES_TAG,
     vanilla_items::ITEMS,
 };
 
@@ -42,6 +48,9 @@ impl Default for ItemStack {
 }
 
 impl ItemStack {
+    /// The largest value the `max_stack_size` component may have.
+    pub const ABSOLUTE_MAX_STACK_SIZE: i32 = 99;
+
     /// Creates an empty item stack (using AIR).
     #[must_use]
     pub fn empty() -> Self {
@@ -117,16 +126,19 @@ impl ItemStack {
     /// If the amount is greater than or equal to the current count, this stack becomes
     /// empty and the entire contents are returned.
     pub fn split(&mut self, amount: i32) -> Self {
-        let take = amount.min(self.count);
-        let result = Self {
-            item: self.item,
-            count: take,
-            patch: self.patch.clone(),
-        };
+        let take = amount.clamp(0, self.count());
+        let result = self.copy_with_count(take);
         self.shrink(take);
         result
     }
 
+    /// Caps the count of this stack at `max_count`.
+    pub fn limit_size(&mut self, max_count: i32) {
+        if !self.is_empty() && self.count > max_count {
+            self.count = max_count;
+        }
+    }
+
     /// Copies the identity (item type and patch) from another stack.
     ///
     /// Used when splitting stacks to preserve components.
@@ -189,6 +201,14 @@ impl ItemStack {
         self.is_damageable_item() && self.get_damage_value() >= self.get_max_damage()
     }
 
+    /// Returns true if one more point of damage would break the item.
+    ///
+    /// Vanilla: `ItemStack.nextDamageWillBreak()`.
+    #[must_use]
+    pub fn next_damage_will_break(&self) -> bool {
+        self.is_damageable_item() && self.get_damage_value() >= self.get_max_damage() - 1
+    }
+
     /// Damages the item and breaks it if durability reaches zero.
     ///
     /// Returns `true` if the item broke and should be removed/replaced.
@@ -261,9 +281,48 @@ impl ItemStack {
         if a.is_empty() && b.is_empty() {
             return true;
         }
+        // Same prototype and identical patch is by far the most common case
+        if a.patch == b.patch {
+            return true;
+        }
         a.components_equal(b)
     }
 
+    /// Returns true if `other` could be merged into this stack, ignoring counts.
+    ///
+    /// Both stacks must be non-empty, stackable, and have the same item and components.
+    #[must_use]
+    pub fn can_merge_with(&self, other: &Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.is_stackable()
+            && Self::is_same_item_same_components(self, other)
+    }
+
+    /// Moves as many items as possible from `other` into this stack.
+    ///
+    /// The resulting count never exceeds `max_count` nor this item's own max stack size.
+    /// If this stack is empty it takes on the identity of `other`.
+    /// Returns the number of items moved; `other` is shrunk by the same amount.
+    pub fn merge_from(&mut self, other: &mut Self, max_count: i32) -> i32 {
+        if other.is_empty() {
+            return 0;
+        }
+        if self.is_empty() {
+            let limit = max_count.min(other.max_stack_size());
+            *self = other.split(limit);
+            return self.count();
+        }
+        if !self.can_merge_with(other) {
+            return 0;
+        }
+        let limit = max_count.min(self.max_stack_size());
+        let moved = (limit - self.count).clamp(0, other.count());
+        self.grow(moved);
+        other.shrink(moved);
+        moved
+    }
+
     #[must_use]
     pub fn matches(a: &Self, b: &Self) -> bool {
         a.count() == b.count() && Self::is_same_item_same_components(a, b)
@@ -274,8 +333,25 @@ impl ItemStack {
         self.item().key == item.key
     }
 
+    /// Returns the maximum number of items this stack can hold.
+    ///
+    /// Reads the `max_stack_size` component, clamped to `1..=ABSOLUTE_MAX_STACK_SIZE`.
+    /// Stacks whose component was removed can only hold a single item.
+    #[must_use]
     pub fn max_stack_size(&self) -> i32 {
-        self.get(MAX_STACK_SIZE).copied().unwrap_or(64)
+        self.get(MAX_STACK_SIZE)
+            .copied()
+            .unwrap_or(1)
+            .clamp(1, Self::ABSOLUTE_MAX_STACK_SIZE)
+    }
+
+    /// Returns whether this item may be stored inside container items, which
+    /// shulker boxes may not.
+    ///
+    /// Vanilla equivalent: `Item.canFitInsideContainerItems`.
+    #[must_use]
+    pub fn can_fit_inside_container_items(&self) -> bool {
+        !REGISTRY.items.is_in_tag(self.item, &SHULKER_BOXES_TAG)
     }
 
     /// Returns the equippable component if this item has one.
@@ -321,8 +397,17 @@ impl ItemStack {
     }
 
     /// Sets a component value in this item's patch, overriding the prototype.
+    ///
+    /// Like vanilla, setting the prototype's own value clears the patch entry
+    /// instead, so the stack still matches a fresh one.
     pub fn set<T: Component>(&mut self, component: DataComponentType<T>, value: T) {
-        self.patch.set(component, value);
+        let key = component.key.clone();
+        self.patch.set(component.clone(), value);
+        if let Some(ComponentPatchEntry::Set(data)) = self.patch.get_entry(&key)
+            && self.prototype().get_raw(&key) == Some(data)
+        {
+            self.patch.clear(component);
+        }
     }
 
     /// Removes a component from this item (marks it as removed in the patch).
@@ -343,6 +428,25 @@ impl ItemStack {
         &self.patch
     }
 
+    /// Applies every entry of `patch` on top of this item's components.
+    ///
+    /// Entries that match the prototype clear the item's own entry instead.
+    /// Vanilla equivalent: `ItemStack.applyComponents(DataComponentPatch)`.
+    pub fn apply_components(&mut self, patch: &DataComponentPatch) {
+        for (key, entry) in patch.iter() {
+            let prototype = self.prototype().get_raw(key);
+            match entry {
+                ComponentPatchEntry::Set(data) if prototype != Some(data) => {
+                    self.patch.set_raw(key.clone(), data.clone());
+                }
+                ComponentPatchEntry::Removed if prototype.is_some() => {
+                    self.patch.remove_raw(key.clone());
+                }
+                _ => self.patch.clear_raw(key),
+            }
+        }
+    }
+
     /// Gets the Tool component if present.
     #[must_use]
     pub fn get_tool(&self) -> Option<&Tool> {
@@ -396,6 +500,163 @@ impl ItemStack {
         self.get(ENCHANTMENTS)
     }
 
+    /// Returns the component this item keeps its enchantments in:
+    /// `stored_enchantments` for enchanted books, `enchantments` otherwise.
+    ///
+    /// Vanilla equivalent: `EnchantmentHelper.getComponentType`.
+    #[must_use]
+    pub fn enchantments_component(&self) -> DataComponentType<ItemEnchantments> {
+        if self.is(&ITEMS.enchanted_book) {
+            STORED_ENCHANTMENTS
+        } else {
+            ENCHANTMENTS
+        }
+    }
+
+    /// Returns the enchantments an anvil or grindstone works with, which are
+    /// the stored ones for enchanted books.
+    ///
+    /// Vanilla equivalent: `EnchantmentHelper.getEnchantmentsForCrafting`.
+    #[must_use]
+    pub fn get_enchantments_for_crafting(&self) -> ItemEnchantments {
+        self.get(self.enchantments_component())
+            .cloned()
+            .unwrap_or_else(ItemEnchantments::empty)
+    }
+
+    /// Replaces the enchantments in the item's enchantments component.
+    ///
+    /// Vanilla equivalent: `EnchantmentHelper.setEnchantments`.
+    pub fn set_enchantments_for_crafting(&mut self, enchantments: ItemEnchantments) {
+        self.set(self.enchantments_component(), enchantments);
+    }
+
+    /// Returns true if an enchanting table can enchant this item: it is
+    /// enchantable and not enchanted yet.
+    ///
+    /// Vanilla equivalent: `ItemStack.isEnchantable`.
+    #[must_use]
+    pub fn is_enchantable(&self) -> bool {
+        self.has(ENCHANTABLE)
+            && self
+                .get_enchantments()
+                .is_none_or(ItemEnchantments::is_empty)
+    }
+
+    /// Returns the enchantment value from the `enchantable` component, or 0.
+    #[must_use]
+    pub fn get_enchantment_value(&self) -> i32 {
+        self.get(ENCHANTABLE)
+            .map_or(0, |enchantable| enchantable.value)
+    }
+
+    /// Returns true if `repair_item` repairs this item in an anvil.
+    ///
+    /// Vanilla equivalent: `ItemStack.isValidRepairItem`.
+    #[must_use]
+    pub fn is_valid_repair_item(&self, repair_item: &Self) -> bool {
+        self.get(REPAIRABLE)
+            .is_some_and(|repairable| repairable.is_valid_repair_item(repair_item.item))
+    }
+
+    /// Returns the extra level cost this item adds in an anvil.
+    #[must_use]
+    pub fn get_repair_cost(&self) -> i32 {
+        self.get(REPAIR_COST).copied().unwrap_or(0)
+    }
+
+    /// Returns true if this item can hold enchantments, which enchanted
+    /// books do in their stored enchantments.
+    ///
+    /// Vanilla equivalent: `EnchantmentHelper.canStoreEnchantments`.
+    #[must_use]
+    pub fn can_store_enchantments(&self) -> bool {
+        self.has(self.enchantments_component())
+    }
+
+    /// Returns the custom name given to this item (e.g. by an anvil), if any.
+    #[must_use]
+    pub fn get_custom_name(&self) -> Option<&TextComponent> {
+        self.get(CUSTOM_NAME)
+    }
+
+    /// Returns the lore lines of this item, if any.
+    #[must_use]
+    pub fn get_lore(&self) -> Option<&ItemLore> {
+        self.get(LORE)
+    }
+
+    /// Returns the custom NBT data attached to this item, if any.
+    #[must_use]
+    pub fn get_custom_data(&self) -> Option<&NbtCompound> {
+        self.get(CUSTOM_DATA).map(|data| &data.tag)
+    }
+
+    /// Sets the custom name, or removes it if `None`.
+    pub fn set_custom_name(&mut self, name: Option<TextComponent>) {
+        match name {
+            Some(name) => self.set(CUSTOM_NAME, name),
+            None => self.remove(CUSTOM_NAME),
+        }
+    }
+
+    /// Modifies the custom data in place, like vanilla's `CustomData.update`.
+    ///
+    /// The component is removed if the data is empty afterwards.
+    pub fn update_custom_data(&mut self, update: impl FnOnce(&mut NbtCompound)) {
+        let mut tag = self.get_custom_data().cloned().unwrap_or_default();
+        update(&mut tag);
+        if tag.is_empty() {
+            self.remove(CUSTOM_DATA);
+        } else {
+            self.set(CUSTOM_DATA, CustomData::new(tag));
+        }
+    }
+
+    // ==================== Builders ====================
+
+    /// Sets the custom name (builder pattern).
+    #[must_use]
+    pub fn with_custom_name(mut self, name: TextComponent) -> Self {
+        self.set(CUSTOM_NAME, name);
+        self
+    }
+
+    /// Replaces the lore lines (builder pattern).
+    #[must_use]
+    pub fn with_lore(mut self, lines: Vec<TextComponent>) -> Self {
+        self.set(LORE, ItemLore::new(lines));
+        self
+    }
+
+    /// Sets the damage value, clamped to the item's max damage (builder pattern).
+    #[must_use]
+    pub fn with_damage(mut self, damage: i32) -> Self {
+        self.set_damage_value(damage);
+        self
+    }
+
+    /// Adds an enchantment, replacing any existing level (builder pattern).
+    #[must_use]
+    pub fn with_enchantment(mut self, enchantment: Identifier, level: u32) -> Self {
+        self.set_enchantments(&[(enchantment, level)], false);
+        self
+    }
+
+    /// Marks the item as unbreakable (builder pattern).
+    #[must_use]
+    pub fn with_unbreakable(mut self) -> Self {
+        self.set(UNBREAKABLE, ());
+        self
+    }
+
+    /// Merges `tag` into the item's custom data (builder pattern).
+    #[must_use]
+    pub fn with_custom_data(mut self, tag: &NbtCompound) -> Self {
+        self.update_custom_data(|data| steel_utils::nbt::merge_compound(data, tag));
+        self
+    }
+
     /// Sets the damage/durability as a fraction (0.0 = broken, 1.0 = full).
     /// If `add` is true, adds to current damage instead of setting.
     pub fn set_damage_fraction(&mut self, _fraction: f32, _add: bool) {
@@ -437,13 +698,27 @@ impl ItemStack {
     /// Copies components from a source (block entity, attacker, etc.) to this item.
     pub fn copy_components<R: rand::Rng>(
         &mut self,
-        _source: crate::loot_table::CopySource,
-        _include: &[Identifier],
-        _ctx: &crate::loot_table::LootContext<'_, R>,
+        source: crate::loot_table::CopySource,
+        include: &[Identifier],
+        ctx: &crate::loot_table::LootContext<'_, R>,
     ) {
-        // TODO: Implement when block entity system is ready
-        // 1. Get the source entity/block entity from context
-        // 2. For each component in `include`, copy it to this item's patch
+        // TODO: Copy from entities once they expose their components
+        if !matches!(source, crate::loot_table::CopySource::BlockEntity) {
+            return;
+        }
+        let Some(components) = ctx
+            .block_entity
+            .and_then(|block_entity| block_entity.components)
+        else {
+            return;
+        };
+        for key in include {
+            if let Some(ComponentPatchEntry::Set(data)) = components.get_entry(key)
+                && self.prototype().get_raw(key) != Some(data)
+            {
+                self.patch.set_raw(key.clone(), data.clone());
+            }
+        }
     }
 
     /// Copies block state properties to this item (for blocks like note_block).
@@ -465,11 +740,11 @@ impl ItemStack {
     }
 
     /// Sets custom NBT data on this item (merges with existing custom_data).
-    pub fn set_custom_data(&mut self, _tag: &str) {
-        // TODO: Implement when NBT/SNBT parsing is available
-        // 1. Parse the tag string as SNBT (Stringified NBT)
-        // 2. Merge it with existing CUSTOM_DATA component
-        // 3. Set the merged result as the new CUSTOM_DATA
+    pub fn set_custom_data(&mut self, tag: &str) {
+        match parse_snbt_compound(tag) {
+            Ok(tag) => self.update_custom_data(|data| steel_utils::nbt::merge_compound(data, &tag)),
+            Err(err) => log::warn!("Invalid custom data tag {tag:?}: {err}"),
+        }
     }
 
     /// Applies furnace smelting to convert this item (e.g., raw iron -> iron ingot).
@@ -496,9 +771,16 @@ impl ItemStack {
     }
 
     /// Sets the custom name or item name of this item.
-    pub fn set_name(&mut self, _name: &str, _target: crate::loot_table::NameTarget) {
-        // TODO: Implement name setting
-        // Parse the name as a text component and set CUSTOM_NAME or ITEM_NAME
+    pub fn set_name(&mut self, name: &str, target: crate::loot_table::NameTarget) {
+        // TODO: Resolve entity-dependent content once loot contexts carry an entity
+        let Some(name) = parse_text_component(name) else {
+            log::warn!("Invalid item name {name:?}");
+            return;
+        };
+        match target {
+            crate::loot_table::NameTarget::CustomName => self.set(CUSTOM_NAME, name),
+            crate::loot_table::NameTarget::ItemName => self.set(ITEM_NAME, name),
+        }
     }
 
     /// Sets the ominous bottle amplifier.
@@ -578,10 +860,54 @@ impl ItemStack {
     }
 
     /// Sets lore lines on this item.
-    pub fn set_lore(&mut self, _lore: &[&str], _mode: crate::loot_table::ListOperation) {
-        // TODO: Implement lore setting
-        // Parse lore strings as text components and set LORE component
-        // Apply mode (replace, append, insert, etc.)
+    pub fn set_lore(&mut self, lore: &[&str], mode: crate::loot_table::ListOperation) {
+        use crate::loot_table::ListOperation;
+
+        let Some(lines) = lore
+            .iter()
+            .map(|line| parse_text_component(line))
+            .collect::<Option<Vec<_>>>()
+        else {
+            log::warn!("Invalid lore lines {lore:?}");
+            return;
+        };
+        let current = self.get_lore().map(|l| l.lines.clone()).unwrap_or_default();
+
+        let insert_at = |offset: i32| {
+            let offset = usize::try_from(offset).ok()?;
+            (offset <= current.len()).then_some(offset)
+        };
+        let (start, end) = match mode {
+            ListOperation::ReplaceAll => (0, current.len()),
+            ListOperation::Append => (current.len(), current.len()),
+            ListOperation::InsertBefore { offset } => match insert_at(offset) {
+                Some(start) => (start, start),
+                None => return,
+            },
+            ListOperation::InsertAfter { offset } => match insert_at(offset) {
+                Some(start) => {
+                    let start = (start + 1).min(current.len());
+                    (start, start)
+                }
+                None => return,
+            },
+            ListOperation::ReplaceSection { offset, size } => match insert_at(offset) {
+                Some(start) => {
+                    let size = size.map_or(lines.len(), |size| size.max(0) as usize);
+                    (start, (start + size).min(current.len()))
+                }
+                None => return,
+            },
+        };
+
+        let mut result = current[..start].to_vec();
+        result.extend(lines);
+        result.extend_from_slice(&current[end..]);
+        if result.len() > ItemLore::MAX_LINES {
+            log::warn!("Lore would exceed {} lines", ItemLore::MAX_LINES);
+            return;
+        }
+        self.set(LORE, ItemLore::new(result));
     }
 
     /// Sets container inventory contents.
@@ -657,11 +983,17 @@ impl ItemStack {
     /// Sets firework rocket properties.
     pub fn set_fireworks(
         &mut self,
-        _explosions: Option<&[crate::loot_table::FireworkExplosion]>,
-        _flight_duration: Option<i32>,
+        explosions: Option<&[crate::loot_table::FireworkExplosion]>,
+        flight_duration: Option<i32>,
     ) {
-        // TODO: Implement firework setting
-        // Set FIREWORKS component
+        let mut fireworks = self.get(FIREWORKS).cloned().unwrap_or_default();
+        if let Some(explosions) = explosions {
+            fireworks.explosions = explosions.iter().map(Into::into).collect();
+        }
+        if let Some(flight_duration) = flight_duration {
+            fireworks.flight_duration = flight_duration;
+        }
+        self.set(FIREWORKS, fireworks);
     }
 
     /// Sets firework star explosion properties.
@@ -755,6 +1087,11 @@ impl ItemStack {
     }
 }
 
+/// Parses a text component from its JSON/SNBT string form, as loot tables store them.
+fn parse_text_component(text: &str) -> Option<TextComponent> {
+    TextComponent::from_nbt(&parse_snbt(text).ok()?)
+}
+
 /// Vanilla unbreaking formula: `1 / (unbreaking_level + 1)` chance to consume durability.
 fn should_consume_durability(unbreaking_level: i32) -> bool {
     if unbreaking_level <= 0 {
@@ -775,7 +1112,7 @@ impl std::fmt::Display for ItemStack {
 }
 
 impl WriteTo for ItemStack {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         if self.is_empty() {
             VarInt(0).write(writer)?;
         } else {
@@ -789,15 +1126,53 @@ impl WriteTo for ItemStack {
     }
 }
 
+/// Hashed like the NBT format, as a map of `id`, `count` and `components`.
+impl HashComponent for ItemStack {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        let mut id = ComponentHasher::new();
+        id.put_string(&self.item.key.to_string());
+        let mut fields = vec![("id", id.finish()), ("count", self.count.compute_hash())];
+
+        let components = if self.patch.is_empty() {
+            None
+        } else {
+            // Removed components are written as `!key` with an empty value
+            let entries: Vec<(String, i32)> = self
+                .patch
+                .iter()
+                .map(|(key, entry)| match entry {
+                    ComponentPatchEntry::Set(data) => (key.to_string(), data.compute_hash()),
+                    ComponentPatchEntry::Removed => {
+                        let mut empty = ComponentHasher::new();
+                        empty.start_map();
+                        empty.end_map();
+                        (format!("!{key}"), empty.finish())
+                    }
+                })
+                .collect();
+            let entries: Vec<(&str, i32)> = entries
+                .iter()
+                .map(|(key, hash)| (key.as_str(), *hash))
+                .collect();
+            let mut hasher = ComponentHasher::new();
+            hasher.put_fields(&entries);
+            Some(hasher.finish())
+        };
+        if let Some(components) = components {
+            fields.push(("components", components));
+        }
+        hasher.put_fields(&fields);
+    }
+}
+
 impl ReadFrom for ItemStack {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let count = VarInt::read(data)?.0;
         if count <= 0 {
             return Ok(Self::empty());
         }
 
-        let item_id = VarInt::read(data)?.0 as usize;
-        let item = REGISTRY.items.by_id(item_id).unwrap_or(&ITEMS.air);
+        let item = Self::read_item(data)?;
 
         // Read DataComponentPatch
         let patch = DataComponentPatch::read(data)?;
@@ -811,18 +1186,26 @@ impl ItemStack {
     ///
     /// Vanilla uses this for serverbound packets where component data is
     /// length-prefixed (e.g., `ServerboundSetCreativeModeSlotPacket`).
-    pub fn read_untrusted(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    pub fn read_untrusted(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let count = VarInt::read(data)?.0;
         if count <= 0 {
             return Ok(Self::empty());
         }
 
-        let item_id = VarInt::read(data)?.0 as usize;
-        let item = REGISTRY.items.by_id(item_id).unwrap_or(&ITEMS.air);
+        let item = Self::read_item(data)?;
         let patch = DataComponentPatch::read_delimited(data)?;
 
         Ok(Self { item, count, patch })
     }
+
+    /// Reads an item registry id, rejecting ids that don't exist like vanilla's `byIdOrThrow`.
+    fn read_item(data: &mut Cursor<&[u8]>) -> Result<ItemRef, ReadError> {
+        let item_id = VarInt::read(data)?.0;
+        usize::try_from(item_id)
+            .ok()
+            .and_then(|id| REGISTRY.items.by_id(id))
+            .ok_or_else(|| ReadError::malformed(format!("Unknown item id {item_id}")))
+    }
 }
 
 // ==================== NBT Serialization ====================
@@ -927,3 +1310,147 @@ impl ItemStack {
         Some(Self::with_count_and_patch(item, count, patch))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_from_respects_max_stack_size() {
+        let mut target = ItemStack::with_count(&ITEMS.stone, 40);
+        let mut source = ItemStack::with_count(&ITEMS.stone, 40);
+
+        assert_eq!(target.merge_from(&mut source, i32::MAX), 24);
+        assert_eq!(target.count(), 64);
+        assert_eq!(source.count(), 16);
+    }
+
+    #[test]
+    fn merge_from_respects_slot_limit() {
+        let mut target = ItemStack::with_count(&ITEMS.stone, 10);
+        let mut source = ItemStack::with_count(&ITEMS.stone, 10);
+
+        assert_eq!(target.merge_from(&mut source, 16), 6);
+        assert_eq!(target.count(), 16);
+        assert_eq!(source.count(), 4);
+    }
+
+    #[test]
+    fn merge_from_into_empty_takes_identity() {
+        let mut target = ItemStack::empty();
+        let mut source = ItemStack::with_count(&ITEMS.stone, 80);
+
+        assert_eq!(target.merge_from(&mut source, i32::MAX), 64);
+        assert!(target.is(&ITEMS.stone));
+        assert_eq!(source.count(), 16);
+    }
+
+    #[test]
+    fn merge_from_rejects_different_components() {
+        let mut target = ItemStack::with_count(&ITEMS.stone, 1);
+        let mut source = ItemStack::with_count(&ITEMS.stone, 1);
+        source.set(MAX_STACK_SIZE, 16);
+
+        assert!(!ItemStack::is_same_item_same_components(&target, &source));
+        assert_eq!(target.merge_from(&mut source, i32::MAX), 0);
+        assert_eq!(source.count(), 1);
+    }
+
+    #[test]
+    fn patch_matching_prototype_is_same_components() {
+        let a = ItemStack::new(&ITEMS.stone);
+        let mut b = ItemStack::new(&ITEMS.stone);
+        b.set(MAX_STACK_SIZE, 64);
+
+        assert!(ItemStack::is_same_item_same_components(&a, &b));
+    }
+
+    #[test]
+    fn max_stack_size_is_clamped() {
+        let mut stack = ItemStack::new(&ITEMS.stone);
+        stack.set(MAX_STACK_SIZE, 1000);
+        assert_eq!(stack.max_stack_size(), ItemStack::ABSOLUTE_MAX_STACK_SIZE);
+
+        stack.remove(MAX_STACK_SIZE);
+        assert_eq!(stack.max_stack_size(), 1);
+        assert!(!stack.is_stackable());
+    }
+
+    #[test]
+    fn split_never_goes_negative() {
+        let mut stack = ItemStack::with_count(&ITEMS.stone, 3);
+        let taken = stack.split(-5);
+        assert!(taken.is_empty());
+        assert_eq!(stack.count(), 3);
+
+        let taken = stack.split(10);
+        assert_eq!(taken.count(), 3);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn builders_set_components() {
+        let stack = ItemStack::new(&ITEMS.diamond_sword)
+            .with_custom_name(TextComponent::plain("Excalibur"))
+            .with_lore(vec![TextComponent::plain("Sharp")])
+            .with_damage(10)
+            .with_unbreakable();
+
+        assert_eq!(
+            stack.get_custom_name(),
+            Some(&TextComponent::plain("Excalibur"))
+        );
+        assert_eq!(stack.get_lore().map(ItemLore::len), Some(1));
+        assert_eq!(stack.get_damage_value(), 10);
+        assert!(!stack.is_damageable_item());
+        assert!(!ItemStack::is_same_item_same_components(
+            &stack,
+            &ItemStack::new(&ITEMS.diamond_sword)
+        ));
+    }
+
+    #[test]
+    fn custom_data_merges_and_clears() {
+        let mut stack = ItemStack::new(&ITEMS.stone);
+        stack.set_custom_data("{a:1,nested:{x:1}}");
+        stack.set_custom_data("{b:2,nested:{y:2}}");
+
+        let data = stack.get_custom_data().expect("custom data should be set");
+        assert!(data.get("a").is_some());
+        assert!(data.get("b").is_some());
+        let nested = data.compound("nested").expect("nested compound");
+        assert!(nested.get("x").is_some() && nested.get("y").is_some());
+
+        stack.update_custom_data(|data| *data = NbtCompound::new());
+        assert!(stack.get_custom_data().is_none());
+    }
+
+    #[test]
+    fn set_lore_applies_list_operation() {
+        use crate::loot_table::ListOperation;
+
+        let mut stack = ItemStack::new(&ITEMS.stone);
+        stack.set_lore(&["\"b\""], ListOperation::ReplaceAll);
+        stack.set_lore(&["\"c\""], ListOperation::Append);
+        stack.set_lore(&["\"a\""], ListOperation::InsertBefore { offset: 0 });
+
+        let lines = &stack.get_lore().expect("lore should be set").lines;
+        assert_eq!(
+            lines,
+            &vec![
+                TextComponent::plain("a"),
+                TextComponent::plain("b"),
+                TextComponent::plain("c"),
+            ]
+        );
+
+        stack.set_lore(
+            &["\"x\""],
+            ListOperation::ReplaceSection {
+                offset: 1,
+                size: Some(2),
+            },
+        );
+        assert_eq!(stack.get_lore().map(ItemLore::len), Some(2));
+    }
+}
@@ -3,10 +3,10 @@
 //! Dynamic item behavior has been moved to `steel-core::behavior`.
 //! This file contains data structures that are needed by other crates.
 
-use std::io::{self, Cursor};
+use std::io::Cursor;
 
 use steel_utils::BlockPos;
-use steel_utils::serial::ReadFrom;
+use steel_utils::serial::{ReadError, ReadFrom};
 
 use crate::blocks::properties::Direction;
 
@@ -33,7 +33,7 @@ pub struct BlockHitResult {
 }
 
 impl ReadFrom for BlockHitResult {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let block_pos = BlockPos::read(data)?;
         let direction = Direction::read(data)?;
         // Click coordinates are relative to the block position (0.0 to 1.0 range)
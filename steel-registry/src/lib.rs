@@ -2,6 +2,7 @@
 
 use crate::world_clock::WorldClockRegistry;
 use crate::{
+    attribute::AttributeRegistry,
     banner_pattern::BannerPatternRegistry,
     biome::BiomeRegistry,
     block_entity_type::BlockEntityTypeRegistry,
@@ -28,6 +29,7 @@ use crate::{
     jukebox_song::JukeboxSongRegistry,
     loot_table::LootTableRegistry,
     menu_type::MenuTypeRegistry,
+    mob_effect::MobEffectRegistry,
     painting_variant::PaintingVariantRegistry,
     pig_sound_variant::PigSoundVariantRegistry,
     pig_variant::PigVariantRegistry,
@@ -43,6 +45,7 @@ use crate::{
 use std::{fmt::Debug, ops::Deref, sync::OnceLock};
 use steel_utils::Identifier;
 
+pub mod attribute;
 pub mod banner_pattern;
 pub mod biome;
 pub mod block_entity_type;
@@ -70,6 +73,7 @@ pub mod items;
 pub mod jukebox_song;
 pub mod loot_table;
 pub mod menu_type;
+pub mod mob_effect;
 pub mod painting_variant;
 pub mod pig_sound_variant;
 pub mod pig_variant;
@@ -113,6 +117,11 @@ pub mod vanilla_item_tags;
 #[path = "generated/vanilla_biomes.rs"]
 pub mod vanilla_biomes;
 
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_biome_tags.rs"]
+pub mod vanilla_biome_tags;
+
 #[expect(warnings)]
 #[rustfmt::skip]
 #[path = "generated/vanilla_chat_types.rs"]
@@ -342,6 +351,18 @@ pub mod sound_types;
 #[path = "generated/vanilla_packets.rs"]
 pub mod packets;
 
+/// The structure templates of the vanilla data pack.
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_structure_templates.rs"]
+pub mod vanilla_structure_templates;
+
+/// The template pools, processor lists, structures and structure sets of the vanilla data pack.
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_structure_data.rs"]
+pub mod vanilla_structure_data;
+
 /// Multi-noise biome parameters for climate-based biome selection.
 #[expect(warnings)]
 #[rustfmt::skip]
@@ -365,6 +386,16 @@ pub mod density_functions;
 #[path = "generated/vanilla_world_clocks.rs"]
 pub mod vanilla_world_clocks;
 
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_attributes.rs"]
+pub mod vanilla_attributes;
+
+#[expect(warnings)]
+#[rustfmt::skip]
+#[path = "generated/vanilla_mob_effects.rs"]
+pub mod vanilla_mob_effects;
+
 pub struct RegistryLock(OnceLock<Registry>);
 
 impl RegistryLock {
@@ -655,6 +686,8 @@ pub struct Registry {
     pub poi_types: PoiTypeRegistry,
     pub enchantments: EnchantmentRegistry,
     pub world_clocks: WorldClockRegistry,
+    pub attributes: AttributeRegistry,
+    pub mob_effects: MobEffectRegistry,
 }
 
 impl Debug for Registry {
@@ -681,6 +714,7 @@ impl Registry {
         vanilla_item_tags::register_item_tags(&mut registry.items);
 
         vanilla_biomes::register_biomes(&mut registry.biomes);
+        vanilla_biome_tags::register_biome_tags(&mut registry.biomes);
         vanilla_chat_types::register_chat_types(&mut registry.chat_types);
         vanilla_trim_patterns::register_trim_patterns(&mut registry.trim_patterns);
         vanilla_trim_materials::register_trim_materials(&mut registry.trim_materials);
@@ -737,6 +771,10 @@ impl Registry {
 
         vanilla_world_clocks::register_world_clocks(&mut registry.world_clocks);
 
+        vanilla_attributes::register_attributes(&mut registry.attributes);
+
+        vanilla_mob_effects::register_mob_effects(&mut registry.mob_effects);
+
         registry
     }
 
@@ -779,6 +817,8 @@ impl Registry {
         self.poi_types.freeze();
         self.enchantments.freeze();
         self.world_clocks.freeze();
+        self.attributes.freeze();
+        self.mob_effects.freeze();
     }
 
     #[must_use]
@@ -822,6 +862,8 @@ impl Registry {
             world_clocks: WorldClockRegistry::new(),
             poi_types: PoiTypeRegistry::new(),
             enchantments: EnchantmentRegistry::new(),
+            attributes: AttributeRegistry::new(),
+            mob_effects: MobEffectRegistry::new(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     REGISTRY, RegistryExt, TaggedRegistryExt, blocks::block_state_ext::BlockStateExt,
-    item_stack::ItemStack,
+    data_components::DataComponentPatch, item_stack::ItemStack,
 };
 use rand::RngExt;
 use rustc_hash::FxHashMap;
@@ -379,6 +379,8 @@ pub struct BlockEntityRef<'a> {
     pub custom_name: Option<&'a str>,
     /// Inventory contents (for dynamic/slots entries).
     pub inventory: Option<&'a [ItemStack]>,
+    /// Components collected from the block entity (for copy_components).
+    pub components: Option<&'a DataComponentPatch>,
 }
 
 impl<'a, R: rand::Rng> LootContext<'a, R> {
@@ -1670,7 +1672,6 @@ impl LootFunction {
                 item.enchant_with_levels(level, options, ctx.rng);
             }
             LootFunction::CopyComponents { source, include } => {
-                // TODO: Implement when block entity system is ready
                 item.copy_components(*source, include, ctx);
             }
             LootFunction::CopyState { block, properties } => {
@@ -1698,7 +1699,6 @@ impl LootFunction {
                 item.create_exploration_map(destination, decoration, *zoom, *skip_existing_chunks);
             }
             LootFunction::SetName { name, target } => {
-                // TODO: Implement name setting
                 item.set_name(name, *target);
             }
             LootFunction::SetOminousBottleAmplifier { amplifier } => {
@@ -0,0 +1,88 @@
+//! Mob effect registry.
+//!
+//! Mob effects are the status effects (speed, poison, regeneration, ...) that
+//! potions, beacons and other sources give to living entities.
+
+use rustc_hash::FxHashMap;
+use steel_utils::Identifier;
+
+/// Whether an effect helps or hurts the entity it's applied to.
+///
+/// Vanilla equivalent: `MobEffectCategory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobEffectCategory {
+    Beneficial,
+    Harmful,
+    Neutral,
+}
+
+/// A status effect type.
+///
+/// Vanilla equivalent: `MobEffect`.
+#[derive(Debug)]
+pub struct MobEffect {
+    pub key: Identifier,
+    pub category: MobEffectCategory,
+    /// The particle and potion color as RGB.
+    pub color: i32,
+}
+
+impl MobEffect {
+    /// Returns true if the effect is beneficial.
+    #[must_use]
+    pub fn is_beneficial(&self) -> bool {
+        self.category == MobEffectCategory::Beneficial
+    }
+}
+
+pub type MobEffectRef = &'static MobEffect;
+
+pub struct MobEffectRegistry {
+    mob_effects_by_id: Vec<MobEffectRef>,
+    mob_effects_by_key: FxHashMap<Identifier, usize>,
+    allows_registering: bool,
+}
+
+impl MobEffectRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            mob_effects_by_id: Vec::new(),
+            mob_effects_by_key: FxHashMap::default(),
+            allows_registering: true,
+        }
+    }
+
+    pub fn register(&mut self, mob_effect: MobEffectRef) -> usize {
+        assert!(
+            self.allows_registering,
+            "Cannot register mob effects after the registry has been frozen"
+        );
+
+        let id = self.mob_effects_by_id.len();
+        self.mob_effects_by_key.insert(mob_effect.key.clone(), id);
+        self.mob_effects_by_id.push(mob_effect);
+        id
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, MobEffectRef)> + '_ {
+        self.mob_effects_by_id
+            .iter()
+            .enumerate()
+            .map(|(id, &mob_effect)| (id, mob_effect))
+    }
+}
+
+impl Default for MobEffectRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+crate::impl_registry!(
+    MobEffectRegistry,
+    MobEffect,
+    mob_effects_by_id,
+    mob_effects_by_key,
+    mob_effects
+);
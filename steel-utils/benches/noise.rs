@@ -0,0 +1,48 @@
+#![expect(missing_docs, reason = "benchmarks")]
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use steel_utils::noise::NormalNoise;
+use steel_utils::random::{Random, xoroshiro::Xoroshiro};
+
+/// One chunk's worth of quart columns over the overworld height.
+const POSITIONS: usize = 16 * 96;
+
+fn positions() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let x = (0..POSITIONS).map(|i| ((i / 96) % 4 * 4) as f64).collect();
+    let y = (0..POSITIONS)
+        .map(|i| ((i % 96) * 4) as f64 - 64.0)
+        .collect();
+    let z = (0..POSITIONS).map(|i| ((i / 384) * 4) as f64).collect();
+    (x, y, z)
+}
+
+fn bench_normal_noise(c: &mut Criterion) {
+    let mut rng = Xoroshiro::from_seed(0);
+    let splitter = rng.next_positional();
+    let noise = NormalNoise::create(
+        &splitter,
+        "minecraft:temperature",
+        -10,
+        &[1.5, 0.0, 1.0, 0.0, 0.0, 0.0],
+    );
+    let (x, y, z) = positions();
+    let mut out = vec![0.0; POSITIONS];
+
+    c.bench_function("normal_noise get_value", |b| {
+        b.iter(|| {
+            for (i, out) in out.iter_mut().enumerate() {
+                *out = noise.get_value(x[i], y[i], z[i]);
+            }
+            black_box(&out);
+        });
+    });
+    c.bench_function("normal_noise get_values", |b| {
+        b.iter(|| {
+            noise.get_values(&x, &y, &z, &mut out);
+            black_box(&out);
+        });
+    });
+}
+
+criterion_group!(benches, bench_normal_noise);
+criterion_main!(benches);
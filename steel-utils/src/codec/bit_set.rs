@@ -1,6 +1,6 @@
-use std::io::{Cursor, Result, Write};
+use std::io::{self, Cursor, Write};
 
-use crate::serial::{PrefixedRead, PrefixedWrite, ReadFrom, WriteTo};
+use crate::serial::{PrefixedRead, PrefixedWrite, ReadError, ReadFrom, WriteTo};
 
 use super::VarInt;
 
@@ -27,13 +27,13 @@ impl BitSet {
 }
 
 impl ReadFrom for BitSet {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self(Vec::read_prefixed::<VarInt>(data)?.into_boxed_slice()))
     }
 }
 
 impl WriteTo for BitSet {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         self.0.write_prefixed::<VarInt>(writer)
     }
 }
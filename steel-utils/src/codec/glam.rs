@@ -1,16 +1,16 @@
-use crate::serial::{ReadFrom, WriteTo};
+use crate::serial::{ReadError, ReadFrom, WriteTo};
 use glam::{DVec3, IVec2, IVec3, Vec3};
-use std::io::{Cursor, Result, Write};
+use std::io::{self, Cursor, Write};
 
 impl WriteTo for IVec2 {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         self.x.write(writer)?;
         self.y.write(writer)
     }
 }
 
 impl ReadFrom for IVec2 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self {
             x: i32::read(data)?,
             y: i32::read(data)?,
@@ -19,7 +19,7 @@ impl ReadFrom for IVec2 {
 }
 
 impl WriteTo for IVec3 {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         self.x.write(writer)?;
         self.y.write(writer)?;
         self.z.write(writer)
@@ -27,7 +27,7 @@ impl WriteTo for IVec3 {
 }
 
 impl ReadFrom for IVec3 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self {
             x: i32::read(data)?,
             y: i32::read(data)?,
@@ -37,7 +37,7 @@ impl ReadFrom for IVec3 {
 }
 
 impl WriteTo for DVec3 {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         self.x.write(writer)?;
         self.y.write(writer)?;
         self.z.write(writer)
@@ -45,7 +45,7 @@ impl WriteTo for DVec3 {
 }
 
 impl ReadFrom for DVec3 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self {
             x: f64::read(data)?,
             y: f64::read(data)?,
@@ -55,7 +55,7 @@ impl ReadFrom for DVec3 {
 }
 
 impl WriteTo for Vec3 {
-    fn write(&self, writer: &mut impl Write) -> Result<()> {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
         self.x.write(writer)?;
         self.y.write(writer)?;
         self.z.write(writer)
@@ -63,7 +63,7 @@ impl WriteTo for Vec3 {
 }
 
 impl ReadFrom for Vec3 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self {
             x: f32::read(data)?,
             y: f32::read(data)?,
@@ -1,6 +1,6 @@
 use std::io::{Cursor, Error, Write};
 
-use crate::serial::{ReadFrom, WriteTo};
+use crate::serial::{ReadError, ReadFrom, WriteTo};
 
 /// An enum that represents one of two possible types (Left or Right).
 /// When serialized, it writes only the inner value without any discriminant.
@@ -61,7 +61,7 @@ impl WriteTo for () {
 }
 
 impl ReadFrom for () {
-    fn read(_data: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+    fn read(_data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(())
     }
 }
@@ -4,7 +4,7 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use crate::{
     FrontVec,
-    serial::{ReadFrom, WriteTo},
+    serial::{ReadError, ReadFrom, WriteTo},
 };
 
 /// A variable-length integer.
@@ -79,7 +79,7 @@ impl VarInt {
 }
 
 impl ReadFrom for VarInt {
-    fn read(read: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+    fn read(read: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut val = 0;
         for i in 0..Self::MAX_SIZE {
             let byte = u8::read(read)?;
@@ -88,7 +88,7 @@ impl ReadFrom for VarInt {
                 return Ok(Self(val));
             }
         }
-        Err(Error::other("VarInt to long"))
+        Err(ReadError::VarIntTooLong("VarInt"))
     }
 }
 
@@ -1,6 +1,6 @@
 use std::io::{Cursor, Error, Write};
 
-use crate::serial::{ReadFrom, WriteTo};
+use crate::serial::{ReadError, ReadFrom, WriteTo};
 
 /// A variable-length long integer.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -12,7 +12,7 @@ impl VarLong {
 }
 
 impl ReadFrom for VarLong {
-    fn read(read: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+    fn read(read: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut val = 0i64;
         for i in 0..Self::MAX_SIZE {
             let byte = u8::read(read)?;
@@ -21,7 +21,7 @@ impl ReadFrom for VarLong {
                 return Ok(Self(val));
             }
         }
-        Err(Error::other("VarLong too long"))
+        Err(ReadError::VarIntTooLong("VarLong"))
     }
 }
 
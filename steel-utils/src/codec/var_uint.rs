@@ -1,6 +1,6 @@
 use std::io::{Cursor, Error, Write};
 
-use crate::serial::{ReadFrom, WriteTo};
+use crate::serial::{ReadError, ReadFrom, WriteTo};
 
 /// A variable-length unsigned integer.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -40,7 +40,7 @@ impl VarUint {
     ///
     /// # Errors
     /// - If the `VarUint` is too long.
-    pub fn read(read: &mut Cursor<&[u8]>) -> Result<u32, Error> {
+    pub fn read(read: &mut Cursor<&[u8]>) -> Result<u32, ReadError> {
         let mut val = 0;
         for i in 0..Self::MAX_SIZE {
             let byte = u8::read(read)?;
@@ -49,6 +49,6 @@ impl VarUint {
                 return Ok(val);
             }
         }
-        Err(Error::other("Malformed VarUint"))
+        Err(ReadError::VarIntTooLong("VarUint"))
     }
 }
@@ -0,0 +1,123 @@
+//! Biome colormap sampling.
+//!
+//! Grass, foliage and dry foliage colors that aren't set explicitly by a biome are
+//! looked up in a 256x256 colormap texture (`textures/colormap/*.png` in the client
+//! resources), indexed by the biome's temperature and downfall.
+
+/// Width and height of a colormap texture.
+pub const COLORMAP_SIZE: usize = 256;
+
+/// Color returned when a colormap lookup falls outside of the texture.
+///
+/// Vanilla: the default of `GrassColor.get()`.
+pub const COLORMAP_MISSING_COLOR: i32 = 0xFF_00FF;
+
+/// Foliage color of spruce leaves. Vanilla: `FoliageColor.FOLIAGE_EVERGREEN`.
+pub const FOLIAGE_EVERGREEN: i32 = 0x61_9961;
+/// Foliage color of birch leaves. Vanilla: `FoliageColor.FOLIAGE_BIRCH`.
+pub const FOLIAGE_BIRCH: i32 = 0x80_A755;
+/// Foliage color used without a biome. Vanilla: `FoliageColor.FOLIAGE_DEFAULT`.
+pub const FOLIAGE_DEFAULT: i32 = 0x48_B518;
+/// Foliage color of mangrove leaves. Vanilla: `FoliageColor.FOLIAGE_MANGROVE`.
+pub const FOLIAGE_MANGROVE: i32 = 0x92_C648;
+
+/// Returns the pixel index in a colormap for the given climate.
+///
+/// Temperature and downfall are clamped to `0.0..=1.0`, and downfall is scaled by
+/// temperature, so only the lower-left triangle of the texture is ever used.
+///
+/// Vanilla: `ColorMapColorUtil.get()`.
+#[must_use]
+pub fn colormap_index(temperature: f64, downfall: f64) -> usize {
+    let temperature = temperature.clamp(0.0, 1.0);
+    let downfall = downfall.clamp(0.0, 1.0) * temperature;
+    let x = ((1.0 - temperature) * 255.0) as usize;
+    let y = ((1.0 - downfall) * 255.0) as usize;
+    (y << 8) | x
+}
+
+/// A 256x256 colormap texture as packed `0xRRGGBB` pixels, row by row.
+#[derive(Debug, Clone)]
+pub struct ColorMap {
+    pixels: Box<[i32]>,
+}
+
+impl ColorMap {
+    /// Creates a colormap from its pixels.
+    ///
+    /// Returns `None` if there aren't exactly 256x256 pixels.
+    #[must_use]
+    pub fn from_pixels(pixels: Vec<i32>) -> Option<Self> {
+        (pixels.len() == COLORMAP_SIZE * COLORMAP_SIZE).then(|| Self {
+            pixels: pixels.into_boxed_slice(),
+        })
+    }
+
+    /// Gets the color for the given climate.
+    #[must_use]
+    pub fn get(&self, temperature: f64, downfall: f64) -> i32 {
+        self.get_by_index(colormap_index(temperature, downfall))
+    }
+
+    /// Gets the color at a pixel index from [`colormap_index`].
+    #[must_use]
+    pub fn get_by_index(&self, index: usize) -> i32 {
+        self.pixels
+            .get(index)
+            .copied()
+            .unwrap_or(COLORMAP_MISSING_COLOR)
+    }
+}
+
+/// Darkens a grass color the way dark forests do.
+///
+/// Vanilla: `BiomeSpecialEffects.GrassColorModifier.DARK_FOREST`.
+#[must_use]
+pub const fn dark_forest_grass_color(color: i32) -> i32 {
+    ((color & 0xFE_FEFE) + 0x28_340A) >> 1
+}
+
+/// Picks a swamp grass color from the biome info noise value at a position.
+///
+/// Vanilla: `BiomeSpecialEffects.GrassColorModifier.SWAMP`.
+#[must_use]
+pub const fn swamp_grass_color(biome_info_noise: f64) -> i32 {
+    if biome_info_noise < -0.1 {
+        0x4C_763C
+    } else {
+        0x6A_7039
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_corners() {
+        // Hot and wet is the bottom-left corner, cold is the top-right
+        assert_eq!(colormap_index(1.0, 1.0), 0);
+        assert_eq!(colormap_index(0.0, 0.0), (255 << 8) | 255);
+        assert_eq!(colormap_index(1.0, 0.0), 255 << 8);
+    }
+
+    #[test]
+    fn index_clamps_and_scales_downfall() {
+        assert_eq!(colormap_index(2.0, 5.0), colormap_index(1.0, 1.0));
+        // Plains: temperature 0.8, downfall 0.4 -> downfall 0.32
+        assert_eq!(colormap_index(0.8, 0.4), (173 << 8) | 50);
+    }
+
+    #[test]
+    fn colormap_requires_full_texture() {
+        assert!(ColorMap::from_pixels(vec![0; 16]).is_none());
+        let map = ColorMap::from_pixels(vec![0x12_3456; COLORMAP_SIZE * COLORMAP_SIZE])
+            .expect("full texture");
+        assert_eq!(map.get(0.5, 0.5), 0x12_3456);
+    }
+
+    #[test]
+    fn dark_forest_modifier() {
+        assert_eq!(dark_forest_grass_color(0x79_C05A), 0x50_7A32);
+    }
+}
@@ -0,0 +1,697 @@
+//! Blending of newly generated terrain into chunks generated by an older version.
+//!
+//! Old chunks keep a small amount of sampled data along their borders
+//! ([`BlendingData`]). When new chunks are generated next to them, a
+//! [`Blender`] pulls the new terrain's height, density and biomes towards the
+//! old values so the two meet without cliffs.
+//!
+//! Matches vanilla's `Blender` and `BlendingData`.
+
+use std::sync::{Arc, LazyLock};
+
+use rustc_hash::FxHashMap;
+
+use crate::noise::NormalNoise;
+use crate::random::{RandomSource, xoroshiro::Xoroshiro};
+
+/// Marks a cell column without a sampled value.
+pub const NO_VALUE: f64 = f64::MAX;
+
+/// Chunk radius around a new chunk searched for old chunks to blend heights and biomes with.
+pub const HEIGHT_BLENDING_RANGE_CHUNKS: i32 = 7;
+/// Chunk radius around a new chunk searched for old chunks to blend densities with.
+pub const DENSITY_BLENDING_RANGE_CHUNKS: i32 = 1;
+
+/// Cell radius (in quarts) over which heights and biomes are blended.
+const HEIGHT_BLENDING_RANGE_CELLS: i32 = 27;
+/// Cell radius over which densities are blended.
+const DENSITY_BLENDING_RANGE_CELLS: f64 = 2.0;
+
+const BLENDING_DENSITY_FACTOR: f64 = 0.1;
+const CELL_HEIGHT: i32 = 8;
+const QUARTS_PER_SECTION: i32 = 4;
+const CELL_HORIZONTAL_MAX_INDEX_INSIDE: i32 = QUARTS_PER_SECTION - 1;
+const CELL_HORIZONTAL_MAX_INDEX_OUTSIDE: i32 = QUARTS_PER_SECTION;
+const CELL_COLUMN_INSIDE_COUNT: i32 = 2 * CELL_HORIZONTAL_MAX_INDEX_INSIDE + 1;
+const CELL_COLUMN_OUTSIDE_COUNT: i32 = 2 * CELL_HORIZONTAL_MAX_INDEX_OUTSIDE + 1;
+
+/// Number of cell columns sampled along the borders of an old chunk.
+pub const CELL_COLUMN_COUNT: usize =
+    (CELL_COLUMN_INSIDE_COUNT + CELL_COLUMN_OUTSIDE_COUNT) as usize;
+
+/// Noise that roughens the edge between blended and generated biomes.
+static SHIFT_NOISE: LazyLock<NormalNoise> = LazyLock::new(|| {
+    let mut random = RandomSource::Xoroshiro(Xoroshiro::from_seed(42));
+    NormalNoise::create_from_random(&mut random, -3, &[1.0, 1.0, 1.0, 0.0])
+});
+
+/// Read access to an old chunk's blocks and biomes.
+///
+/// Horizontal coordinates are local to the chunk (`0..16`), `y` is absolute.
+pub trait BlendingSource {
+    /// Whether the block is one of the natural surface blocks old terrain is
+    /// measured by (grass, dirt, stone, sand, ...).
+    fn is_surface_block(&self, x: i32, y: i32, z: i32) -> bool;
+
+    /// Whether the block counts as solid ground.
+    ///
+    /// Vanilla: `BlendingData.isGround()`.
+    fn is_ground(&self, x: i32, y: i32, z: i32) -> bool;
+
+    /// Gets the biome id at the given quart position.
+    fn noise_biome(&self, quart_x: i32, quart_y: i32, quart_z: i32) -> u16;
+}
+
+/// The blended height of a column and how strongly it replaces the generated one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlendingOutput {
+    /// `0.0` uses only `blending_offset`, `1.0` only the generated terrain.
+    pub alpha: f64,
+    /// The terrain offset matching the old terrain's height.
+    pub blending_offset: f64,
+}
+
+/// Heights, densities and biomes sampled along the borders of an old chunk.
+///
+/// The inside columns run along the chunk's north and west edges, the outside
+/// columns along its south and east edges including the corner of the next chunk.
+#[derive(Debug, Clone)]
+pub struct BlendingData {
+    min_section: i32,
+    max_section: i32,
+    has_calculated_data: bool,
+    heights: [f64; CELL_COLUMN_COUNT],
+    densities: [Option<Box<[f64]>>; CELL_COLUMN_COUNT],
+    biomes: [Option<Box<[u16]>>; CELL_COLUMN_COUNT],
+}
+
+impl BlendingData {
+    /// Creates blending data for an old chunk spanning sections
+    /// `min_section..max_section`, with the heights saved for it, if any.
+    #[must_use]
+    pub fn new(
+        min_section: i32,
+        max_section: i32,
+        heights: Option<[f64; CELL_COLUMN_COUNT]>,
+    ) -> Self {
+        Self {
+            min_section,
+            max_section,
+            has_calculated_data: false,
+            heights: heights.unwrap_or([NO_VALUE; CELL_COLUMN_COUNT]),
+            densities: Default::default(),
+            biomes: Default::default(),
+        }
+    }
+
+    /// Gets the lowest section of the old generation.
+    #[must_use]
+    pub const fn min_section(&self) -> i32 {
+        self.min_section
+    }
+
+    /// Gets the section above the highest section of the old generation.
+    #[must_use]
+    pub const fn max_section(&self) -> i32 {
+        self.max_section
+    }
+
+    /// Gets the sampled heights, or `None` if no column has been sampled yet.
+    #[must_use]
+    pub fn heights(&self) -> Option<&[f64; CELL_COLUMN_COUNT]> {
+        self.heights
+            .iter()
+            .any(|&height| height != NO_VALUE)
+            .then_some(&self.heights)
+    }
+
+    /// Whether the border columns have already been sampled.
+    #[must_use]
+    pub const fn has_calculated_data(&self) -> bool {
+        self.has_calculated_data
+    }
+
+    /// Samples the border columns facing newly generated chunks.
+    ///
+    /// `is_new_side` is called with the offset of each neighbouring chunk and
+    /// returns whether that chunk uses new generation. Only runs once.
+    /// Vanilla: `BlendingData.calculateData()`.
+    pub fn calculate_data(
+        &mut self,
+        chunk: &impl BlendingSource,
+        is_new_side: impl Fn(i32, i32) -> bool,
+    ) {
+        if self.has_calculated_data {
+            return;
+        }
+        let north = is_new_side(0, -1);
+        let east = is_new_side(1, 0);
+        let south = is_new_side(0, 1);
+        let west = is_new_side(-1, 0);
+
+        if north || west || is_new_side(-1, -1) {
+            self.add_values_for_column(inside_index(0, 0), chunk, 0, 0);
+        }
+        if north {
+            for i in 1..QUARTS_PER_SECTION {
+                self.add_values_for_column(inside_index(i, 0), chunk, 4 * i, 0);
+            }
+        }
+        if west {
+            for i in 1..QUARTS_PER_SECTION {
+                self.add_values_for_column(inside_index(0, i), chunk, 0, 4 * i);
+            }
+        }
+        if east {
+            for i in 1..QUARTS_PER_SECTION {
+                let index = outside_index(CELL_HORIZONTAL_MAX_INDEX_OUTSIDE, i);
+                self.add_values_for_column(index, chunk, 15, 4 * i);
+            }
+        }
+        if south {
+            for i in 0..QUARTS_PER_SECTION {
+                let index = outside_index(i, CELL_HORIZONTAL_MAX_INDEX_OUTSIDE);
+                self.add_values_for_column(index, chunk, 4 * i, 15);
+            }
+        }
+        if east && is_new_side(1, -1) {
+            let index = outside_index(CELL_HORIZONTAL_MAX_INDEX_OUTSIDE, 0);
+            self.add_values_for_column(index, chunk, 15, 0);
+        }
+        if east && south && is_new_side(1, 1) {
+            let index = outside_index(
+                CELL_HORIZONTAL_MAX_INDEX_OUTSIDE,
+                CELL_HORIZONTAL_MAX_INDEX_OUTSIDE,
+            );
+            self.add_values_for_column(index, chunk, 15, 15);
+        }
+        self.has_calculated_data = true;
+    }
+
+    fn add_values_for_column(&mut self, index: usize, chunk: &impl BlendingSource, x: i32, z: i32) {
+        if self.heights[index] == NO_VALUE {
+            self.heights[index] = f64::from(self.height_at(chunk, x, z));
+        }
+        let height = self.heights[index].floor() as i32;
+        self.densities[index] = Some(self.density_column(chunk, x, z, height));
+        self.biomes[index] = Some(self.biome_column(chunk, x, z));
+    }
+
+    // TODO: Start at the WORLD_SURFACE_WG heightmap when the old chunk has one primed
+    fn height_at(&self, chunk: &impl BlendingSource, x: i32, z: i32) -> i32 {
+        let min_y = self.min_y();
+        let mut y = self.max_y();
+        while y > min_y {
+            if chunk.is_surface_block(x, y, z) {
+                return y;
+            }
+            y -= 1;
+        }
+        min_y
+    }
+
+    fn density_column(
+        &self,
+        chunk: &impl BlendingSource,
+        x: i32,
+        z: i32,
+        height: i32,
+    ) -> Box<[f64]> {
+        let mut densities = vec![-1.0; self.cell_count_per_column()].into_boxed_slice();
+        let mut y = self.max_y() + 1;
+        let mut read1 = || {
+            y -= 1;
+            if chunk.is_ground(x, y, z) { 1.0 } else { -1.0 }
+        };
+
+        let mut sum: f64 = (0..7).map(|_| read1()).sum();
+        for i in (0..densities.len() - 1).rev() {
+            let bottom = read1();
+            let middle: f64 = (0..7).map(|_| read1()).sum();
+            densities[i] = (sum + bottom + middle) / 15.0;
+            sum = middle;
+        }
+
+        let top_index = self.cell_y_index(height.div_euclid(CELL_HEIGHT));
+        if top_index >= 0 && (top_index as usize) < densities.len() - 1 {
+            let top_index = top_index as usize;
+            let height_in_cell = (f64::from(height) + 0.5) % 8.0 / 8.0;
+            let air_factor = (1.0 - height_in_cell) / height_in_cell;
+            let max = air_factor.max(1.0) * 0.25;
+            densities[top_index + 1] = -air_factor / max;
+            densities[top_index] = 1.0 / max;
+        }
+        densities
+    }
+
+    fn biome_column(&self, chunk: &impl BlendingSource, x: i32, z: i32) -> Box<[u16]> {
+        let min_quart_y = self.min_y() >> 2;
+        (0..self.quart_count_per_column())
+            .map(|i| chunk.noise_biome(x >> 2, min_quart_y + i as i32, z >> 2))
+            .collect()
+    }
+
+    /// Gets the old terrain height at a cell of this chunk, or [`NO_VALUE`].
+    #[must_use]
+    pub fn height(&self, cell_x: i32, _cell_y: i32, cell_z: i32) -> f64 {
+        if cell_x == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+            || cell_z == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+        {
+            self.heights[outside_index(cell_x, cell_z)]
+        } else if cell_x != 0 && cell_z != 0 {
+            NO_VALUE
+        } else {
+            self.heights[inside_index(cell_x, cell_z)]
+        }
+    }
+
+    /// Gets the old terrain density at a cell of this chunk, or [`NO_VALUE`].
+    #[must_use]
+    pub fn density(&self, cell_x: i32, cell_y: i32, cell_z: i32) -> f64 {
+        if cell_y == self.min_cell_y() {
+            BLENDING_DENSITY_FACTOR
+        } else if cell_x == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+            || cell_z == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+        {
+            self.blending_data_value(outside_index(cell_x, cell_z), cell_y)
+        } else if cell_x != 0 && cell_z != 0 {
+            NO_VALUE
+        } else {
+            self.blending_data_value(inside_index(cell_x, cell_z), cell_y)
+        }
+    }
+
+    fn blending_data_value(&self, column: usize, cell_y: i32) -> f64 {
+        let Some(densities) = &self.densities[column] else {
+            return NO_VALUE;
+        };
+        let index = self.cell_y_index(cell_y);
+        if index >= 0 && (index as usize) < densities.len() {
+            densities[index as usize] * BLENDING_DENSITY_FACTOR
+        } else {
+            NO_VALUE
+        }
+    }
+
+    fn for_each_biome(
+        &self,
+        min_cell_x: i32,
+        quart_y: i32,
+        min_cell_z: i32,
+        mut consumer: impl FnMut(i32, i32, u16),
+    ) {
+        let min_quart_y = self.min_y() >> 2;
+        if quart_y < min_quart_y || quart_y > self.max_y() >> 2 {
+            return;
+        }
+        let quart_index = (quart_y - min_quart_y) as usize;
+        for (i, column) in self.biomes.iter().enumerate() {
+            if let Some(biome) = column.as_ref().and_then(|c| c.get(quart_index)) {
+                consumer(min_cell_x + column_x(i), min_cell_z + column_z(i), *biome);
+            }
+        }
+    }
+
+    fn for_each_height(
+        &self,
+        min_cell_x: i32,
+        min_cell_z: i32,
+        mut consumer: impl FnMut(i32, i32, f64),
+    ) {
+        for (i, &height) in self.heights.iter().enumerate() {
+            if height != NO_VALUE {
+                consumer(min_cell_x + column_x(i), min_cell_z + column_z(i), height);
+            }
+        }
+    }
+
+    fn for_each_density(
+        &self,
+        min_cell_x: i32,
+        min_cell_z: i32,
+        from_cell_y: i32,
+        to_cell_y: i32,
+        mut consumer: impl FnMut(i32, i32, i32, f64),
+    ) {
+        let column_min_y = self.column_min_cell_y();
+        let from_y = (from_cell_y - column_min_y).max(0);
+        let to_y = (to_cell_y - column_min_y).min(self.cell_count_per_column() as i32);
+        for (i, column) in self.densities.iter().enumerate() {
+            let Some(column) = column else {
+                continue;
+            };
+            let x = min_cell_x + column_x(i);
+            let z = min_cell_z + column_z(i);
+            for y_index in from_y..to_y {
+                let density = column[y_index as usize] * BLENDING_DENSITY_FACTOR;
+                consumer(x, y_index + column_min_y, z, density);
+            }
+        }
+    }
+
+    const fn min_y(&self) -> i32 {
+        self.min_section * 16
+    }
+
+    const fn max_y(&self) -> i32 {
+        self.max_section * 16 - 1
+    }
+
+    const fn cell_count_per_column(&self) -> usize {
+        ((self.max_section - self.min_section) * 2) as usize
+    }
+
+    const fn quart_count_per_column(&self) -> usize {
+        ((self.max_section - self.min_section) * QUARTS_PER_SECTION) as usize
+    }
+
+    const fn min_cell_y(&self) -> i32 {
+        self.min_section * 2
+    }
+
+    const fn column_min_cell_y(&self) -> i32 {
+        self.min_cell_y() + 1
+    }
+
+    const fn cell_y_index(&self, cell_y: i32) -> i32 {
+        cell_y - self.column_min_cell_y()
+    }
+}
+
+const fn inside_index(x: i32, z: i32) -> usize {
+    (CELL_HORIZONTAL_MAX_INDEX_INSIDE - x + z) as usize
+}
+
+const fn outside_index(x: i32, z: i32) -> usize {
+    (CELL_COLUMN_INSIDE_COUNT + x + CELL_HORIZONTAL_MAX_INDEX_OUTSIDE - z) as usize
+}
+
+const fn column_x(index: usize) -> i32 {
+    let index = index as i32;
+    if index < CELL_COLUMN_INSIDE_COUNT {
+        zero_if_negative(CELL_HORIZONTAL_MAX_INDEX_INSIDE - index)
+    } else {
+        let offset = index - CELL_COLUMN_INSIDE_COUNT;
+        CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+            - zero_if_negative(CELL_HORIZONTAL_MAX_INDEX_OUTSIDE - offset)
+    }
+}
+
+const fn column_z(index: usize) -> i32 {
+    let index = index as i32;
+    if index < CELL_COLUMN_INSIDE_COUNT {
+        zero_if_negative(index - CELL_HORIZONTAL_MAX_INDEX_INSIDE)
+    } else {
+        let offset = index - CELL_COLUMN_INSIDE_COUNT;
+        CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+            - zero_if_negative(offset - CELL_HORIZONTAL_MAX_INDEX_OUTSIDE)
+    }
+}
+
+const fn zero_if_negative(value: i32) -> i32 {
+    value & !(value >> 31)
+}
+
+/// Blends new terrain into the old chunks around the chunk being generated.
+///
+/// Vanilla uses an empty blender when there are no old chunks nearby; here
+/// that is `None` wherever a blender is optional.
+#[derive(Debug, Default)]
+pub struct Blender {
+    height_and_biome_data: FxHashMap<(i32, i32), Arc<BlendingData>>,
+    density_data: FxHashMap<(i32, i32), Arc<BlendingData>>,
+}
+
+impl Blender {
+    /// Creates a blender from the old chunks within [`HEIGHT_BLENDING_RANGE_CHUNKS`]
+    /// and [`DENSITY_BLENDING_RANGE_CHUNKS`] of the generated chunk, keyed by chunk position.
+    #[must_use]
+    pub const fn new(
+        height_and_biome_data: FxHashMap<(i32, i32), Arc<BlendingData>>,
+        density_data: FxHashMap<(i32, i32), Arc<BlendingData>>,
+    ) -> Self {
+        Self {
+            height_and_biome_data,
+            density_data,
+        }
+    }
+
+    /// Whether there is no old chunk to blend with.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.height_and_biome_data.is_empty() && self.density_data.is_empty()
+    }
+
+    /// Gets the blended terrain offset at a block column.
+    ///
+    /// Vanilla: `Blender.blendOffsetAndFactor()`.
+    #[must_use]
+    pub fn blend_offset_and_factor(&self, block_x: i32, block_z: i32) -> BlendingOutput {
+        let cell_x = block_x >> 2;
+        let cell_z = block_z >> 2;
+        let fixed_height = self.cell_value(cell_x, 0, cell_z, BlendingData::height);
+        if fixed_height != NO_VALUE {
+            return BlendingOutput {
+                alpha: 0.0,
+                blending_offset: height_to_offset(fixed_height),
+            };
+        }
+
+        let mut total_weight = 0.0;
+        let mut weighted_heights = 0.0;
+        let mut closest_distance = f64::INFINITY;
+        for (&(chunk_x, chunk_z), data) in &self.height_and_biome_data {
+            data.for_each_height(chunk_x * 4, chunk_z * 4, |test_x, test_z, height| {
+                let distance = length2(cell_x - test_x, cell_z - test_z);
+                if distance > f64::from(HEIGHT_BLENDING_RANGE_CELLS) {
+                    return;
+                }
+                if distance < closest_distance {
+                    closest_distance = distance;
+                }
+                let weight = 1.0 / (distance * distance * distance * distance);
+                weighted_heights += height * weight;
+                total_weight += weight;
+            });
+        }
+
+        if closest_distance == f64::INFINITY {
+            return BlendingOutput {
+                alpha: 1.0,
+                blending_offset: 0.0,
+            };
+        }
+        let average_height = weighted_heights / total_weight;
+        let alpha = (closest_distance / f64::from(HEIGHT_BLENDING_RANGE_CELLS + 1)).clamp(0.0, 1.0);
+        let alpha = 3.0 * alpha * alpha - 2.0 * alpha * alpha * alpha;
+        BlendingOutput {
+            alpha,
+            blending_offset: height_to_offset(average_height),
+        }
+    }
+
+    /// Blends the generated `noise_value` at a block with the old densities around it.
+    ///
+    /// Vanilla: `Blender.blendDensity()`.
+    #[must_use]
+    pub fn blend_density(&self, block_x: i32, block_y: i32, block_z: i32, noise_value: f64) -> f64 {
+        let cell_x = block_x >> 2;
+        let cell_y = block_y / CELL_HEIGHT;
+        let cell_z = block_z >> 2;
+        let fixed_density = self.cell_value(cell_x, cell_y, cell_z, BlendingData::density);
+        if fixed_density != NO_VALUE {
+            return fixed_density;
+        }
+
+        let mut total_weight = 0.0;
+        let mut weighted_densities = 0.0;
+        let mut closest_distance = f64::INFINITY;
+        for (&(chunk_x, chunk_z), data) in &self.density_data {
+            data.for_each_density(
+                chunk_x * 4,
+                chunk_z * 4,
+                cell_y - 1,
+                cell_y + 1,
+                |test_x, test_y, test_z, density| {
+                    let dx = f64::from(cell_x - test_x);
+                    let dy = f64::from((cell_y - test_y) * 2);
+                    let dz = f64::from(cell_z - test_z);
+                    let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+                    if distance > DENSITY_BLENDING_RANGE_CELLS {
+                        return;
+                    }
+                    if distance < closest_distance {
+                        closest_distance = distance;
+                    }
+                    let weight = 1.0 / (distance * distance * distance * distance);
+                    weighted_densities += density * weight;
+                    total_weight += weight;
+                },
+            );
+        }
+
+        if closest_distance == f64::INFINITY {
+            return noise_value;
+        }
+        let average_density = weighted_densities / total_weight;
+        let alpha = (closest_distance / 3.0).clamp(0.0, 1.0);
+        average_density + alpha * (noise_value - average_density)
+    }
+
+    /// Gets the old biome that replaces the generated one at a quart position, if any.
+    ///
+    /// Vanilla: `Blender.blendBiome()`.
+    #[must_use]
+    pub fn blend_biome(&self, quart_x: i32, quart_y: i32, quart_z: i32) -> Option<u16> {
+        let mut closest_distance = f64::INFINITY;
+        let mut closest_biome = None;
+        for (&(chunk_x, chunk_z), data) in &self.height_and_biome_data {
+            data.for_each_biome(
+                chunk_x * 4,
+                quart_y,
+                chunk_z * 4,
+                |test_x, test_z, biome| {
+                    let distance = length2(quart_x - test_x, quart_z - test_z);
+                    if distance <= f64::from(HEIGHT_BLENDING_RANGE_CELLS)
+                        && distance < closest_distance
+                    {
+                        closest_biome = Some(biome);
+                        closest_distance = distance;
+                    }
+                },
+            );
+        }
+
+        let closest_biome = closest_biome?;
+        let shift = SHIFT_NOISE.get_value(f64::from(quart_x), 0.0, f64::from(quart_z)) * 12.0;
+        let alpha = ((closest_distance + shift) / f64::from(HEIGHT_BLENDING_RANGE_CELLS + 1))
+            .clamp(0.0, 1.0);
+        (alpha <= 0.5).then_some(closest_biome)
+    }
+
+    /// Looks a cell up in the old chunk it belongs to, falling back to the
+    /// chunks to the north and west for cells on their shared border.
+    fn cell_value(
+        &self,
+        cell_x: i32,
+        cell_y: i32,
+        cell_z: i32,
+        getter: fn(&BlendingData, i32, i32, i32) -> f64,
+    ) -> f64 {
+        let chunk_x = cell_x >> 2;
+        let chunk_z = cell_z >> 2;
+        let min_x = cell_x & 3 == 0;
+        let min_z = cell_z & 3 == 0;
+        let lookup = |chunk_x: i32, chunk_z: i32| {
+            self.height_and_biome_data
+                .get(&(chunk_x, chunk_z))
+                .map_or(NO_VALUE, |data| {
+                    getter(data, cell_x - chunk_x * 4, cell_y, cell_z - chunk_z * 4)
+                })
+        };
+
+        let mut value = lookup(chunk_x, chunk_z);
+        if value == NO_VALUE {
+            if min_x && min_z {
+                value = lookup(chunk_x - 1, chunk_z - 1);
+            }
+            if value == NO_VALUE {
+                if min_x {
+                    value = lookup(chunk_x - 1, chunk_z);
+                }
+                if value == NO_VALUE && min_z {
+                    value = lookup(chunk_x, chunk_z - 1);
+                }
+            }
+        }
+        value
+    }
+}
+
+fn length2(dx: i32, dz: i32) -> f64 {
+    let dx = f64::from(dx);
+    let dz = f64::from(dz);
+    (dx * dx + dz * dz).sqrt()
+}
+
+/// Converts an old terrain height to the overworld's terrain offset.
+fn height_to_offset(height: f64) -> f64 {
+    let target_y = height + 0.5;
+    let target_y_mod = ((target_y % 8.0) + 8.0) % 8.0;
+    (32.0 * (target_y - 128.0) - 3.0 * (target_y - 120.0) * target_y_mod
+        + 3.0 * target_y_mod * target_y_mod)
+        / (128.0 * (32.0 - 3.0 * target_y_mod))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A flat old chunk: stone up to and including y = 63, air above.
+    struct Flat;
+
+    impl BlendingSource for Flat {
+        fn is_surface_block(&self, _x: i32, y: i32, _z: i32) -> bool {
+            y <= 63
+        }
+
+        fn is_ground(&self, _x: i32, y: i32, _z: i32) -> bool {
+            y <= 63
+        }
+
+        fn noise_biome(&self, _quart_x: i32, _quart_y: i32, _quart_z: i32) -> u16 {
+            7
+        }
+    }
+
+    #[test]
+    fn column_indices_round_trip() {
+        for i in 0..CELL_COLUMN_COUNT {
+            let (x, z) = (column_x(i), column_z(i));
+            let index = if x == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+                || z == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE
+            {
+                outside_index(x, z)
+            } else {
+                inside_index(x, z)
+            };
+            assert_eq!(index, i);
+        }
+    }
+
+    #[test]
+    fn height_offset_is_zero_at_y_128() {
+        assert!(height_to_offset(127.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn blends_towards_old_chunk() {
+        let mut data = BlendingData::new(-4, 20, None);
+        data.calculate_data(&Flat, |_, _| true);
+        assert!(data.has_calculated_data());
+        assert!((data.height(0, 0, 0) - 63.0).abs() < 1e-12);
+        assert!(data.height(2, 0, 2) >= NO_VALUE);
+
+        let mut chunks = FxHashMap::default();
+        chunks.insert((0, 0), Arc::new(data));
+        let blender = Blender::new(chunks.clone(), chunks);
+
+        // On the old border the height is taken as is.
+        let at_border = blender.blend_offset_and_factor(0, 8);
+        assert!(at_border.alpha.abs() < 1e-12);
+        assert!((at_border.blending_offset - height_to_offset(63.0)).abs() < 1e-12);
+
+        // Alpha grows with the distance to the border.
+        let near = blender.blend_offset_and_factor(-8, 8);
+        let far = blender.blend_offset_and_factor(-80, 8);
+        assert!(near.alpha > 0.0 && near.alpha < far.alpha);
+        assert!((blender.blend_offset_and_factor(-200, 8).alpha - 1.0).abs() < 1e-12);
+
+        // Biomes next to the old chunk are taken from it.
+        assert_eq!(blender.blend_biome(-1, 16, 1), Some(7));
+        assert_eq!(blender.blend_biome(-40, 16, 1), None);
+
+        // Far from the old chunk densities are left alone.
+        assert!((blender.blend_density(-64, 0, 0, 0.25) - 0.25).abs() < 1e-12);
+    }
+}
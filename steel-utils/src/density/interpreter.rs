@@ -0,0 +1,410 @@
+//! Runtime interpreter for density function trees.
+//!
+//! The vanilla dimensions are transpiled to native code at build time, but a
+//! datapack can define noise settings and density functions the server has
+//! never seen. Those trees are parsed, [resolved](DensityFunction::resolve)
+//! against the noises from [`bake_noises`] and then evaluated here.
+//!
+//! Evaluation follows the transpiled code expression for expression, so a
+//! vanilla tree gives the same values either way. It is a plain tree walk:
+//! `Interpolated`, `CacheOnce` and `CacheAllInCell` markers are transparent,
+//! and `FlatCache` evaluates its input at the quart corner at `y = 0`, which
+//! is the value vanilla's flat cache holds for that column.
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+
+use crate::density::{
+    Blender, CubicSpline, DensityFunction, MappedType, MarkerType, NoiseParameters, SplineValue,
+    TwoArgType, spline_eval,
+};
+use crate::math::{clamp, map_clamped};
+use crate::noise::{BlendedNoise, EndIslands, NormalNoise};
+use crate::random::legacy_random::LegacyRandom;
+use crate::random::name_hash::NameHash;
+use crate::random::{PositionalRandom, RandomSource, RandomSplitter};
+
+/// Creates a noise generator for every entry in `params`, ready to pass to
+/// [`DensityFunction::resolve`].
+///
+/// Matches the transpiled `Noises::create`: with `legacy` set, temperature and
+/// vegetation come from `LegacyRandom` like vanilla's `RandomState` does.
+#[must_use]
+pub fn bake_noises(
+    seed: u64,
+    splitter: &RandomSplitter,
+    params: &FxHashMap<String, NoiseParameters>,
+    legacy: bool,
+) -> FxHashMap<String, NormalNoise> {
+    params
+        .iter()
+        .map(|(id, p)| {
+            let noise = match id.as_str() {
+                "minecraft:temperature" if legacy => {
+                    let mut rng = RandomSource::Legacy(LegacyRandom::from_seed(seed));
+                    NormalNoise::create_legacy_nether_biome(&mut rng, -7, &[1.0, 1.0])
+                }
+                "minecraft:vegetation" if legacy => {
+                    let mut rng =
+                        RandomSource::Legacy(LegacyRandom::from_seed(seed.wrapping_add(1)));
+                    NormalNoise::create_legacy_nether_biome(&mut rng, -7, &[1.0, 1.0])
+                }
+                _ => NormalNoise::create(splitter, id, p.first_octave, &p.amplitudes),
+            };
+            (id.clone(), noise)
+        })
+        .collect()
+}
+
+/// The samplers an interpreted tree needs besides the noises baked into it.
+///
+/// Fills the role of the transpiled `Noises` struct and column cache.
+#[derive(Default)]
+pub struct InterpreterContext {
+    /// Sampler for `old_blended_noise`, if the tree uses it.
+    pub blended_noise: Option<BlendedNoise>,
+    /// Sampler for `end_islands`, if the tree uses it.
+    pub end_islands: Option<EndIslands>,
+    /// Blender for `blend_alpha`, `blend_offset` and `blend_density`.
+    pub blender: Option<Arc<Blender>>,
+}
+
+impl InterpreterContext {
+    /// Creates the samplers used anywhere in the resolved `roots`.
+    ///
+    /// Seeds them like the transpiled `Noises::create` does.
+    #[must_use]
+    pub fn create<'a>(
+        seed: u64,
+        splitter: &RandomSplitter,
+        roots: impl IntoIterator<Item = &'a DensityFunction>,
+        legacy: bool,
+    ) -> Self {
+        let mut ctx = Self::default();
+        let mut stack: Vec<&DensityFunction> = roots.into_iter().collect();
+        while let Some(df) = stack.pop() {
+            match df {
+                DensityFunction::BlendedNoise(bn) if ctx.blended_noise.is_none() => {
+                    let mut random = if legacy {
+                        RandomSource::Legacy(LegacyRandom::from_seed(seed))
+                    } else {
+                        splitter.with_hash_of(&NameHash::new("minecraft:terrain"))
+                    };
+                    ctx.blended_noise = Some(BlendedNoise::new(
+                        &mut random,
+                        bn.xz_scale,
+                        bn.y_scale,
+                        bn.xz_factor,
+                        bn.y_factor,
+                        bn.smear_scale_multiplier,
+                    ));
+                }
+                DensityFunction::EndIslands if ctx.end_islands.is_none() => {
+                    ctx.end_islands = Some(EndIslands::new(seed));
+                }
+                _ => push_children(df, &mut stack),
+            }
+        }
+        ctx
+    }
+}
+
+/// Pushes the direct inputs of `df` onto `stack`.
+fn push_children<'a>(df: &'a DensityFunction, stack: &mut Vec<&'a DensityFunction>) {
+    match df {
+        DensityFunction::Constant(_)
+        | DensityFunction::YClampedGradient(_)
+        | DensityFunction::Noise(_)
+        | DensityFunction::ShiftA(_)
+        | DensityFunction::ShiftB(_)
+        | DensityFunction::Shift(_)
+        | DensityFunction::BlendedNoise(_)
+        | DensityFunction::EndIslands
+        | DensityFunction::BlendAlpha(_)
+        | DensityFunction::BlendOffset(_) => {}
+        DensityFunction::Reference(r) => stack.extend(r.resolved.as_deref()),
+        DensityFunction::ShiftedNoise(sn) => {
+            stack.extend([&*sn.shift_x, &*sn.shift_y, &*sn.shift_z]);
+        }
+        DensityFunction::TwoArgumentSimple(t) => stack.extend([&*t.argument1, &*t.argument2]),
+        DensityFunction::Mapped(m) => stack.push(&m.input),
+        DensityFunction::Clamp(c) => stack.push(&c.input),
+        DensityFunction::RangeChoice(rc) => {
+            stack.extend([&*rc.input, &*rc.when_in_range, &*rc.when_out_of_range]);
+        }
+        DensityFunction::Spline(s) => push_spline(&s.spline, stack),
+        DensityFunction::WeirdScaledSampler(ws) => stack.push(&ws.input),
+        DensityFunction::BlendDensity(bd) => stack.push(&bd.input),
+        DensityFunction::Marker(m) => stack.push(&m.wrapped),
+        DensityFunction::FindTopSurface(fts) => stack.extend([&*fts.upper_bound, &*fts.density]),
+    }
+}
+
+fn push_spline<'a>(spline: &'a CubicSpline, stack: &mut Vec<&'a DensityFunction>) {
+    stack.push(&spline.coordinate);
+    for point in &spline.points {
+        if let SplineValue::Spline(nested) = &point.value {
+            push_spline(nested, stack);
+        }
+    }
+}
+
+impl DensityFunction {
+    /// Evaluates a [resolved](Self::resolve) tree at a block position.
+    ///
+    /// Unresolved references and missing noises evaluate to `0.0`, as do
+    /// `old_blended_noise` and `end_islands` when `ctx` has no sampler for them.
+    #[must_use]
+    pub fn compute(&self, ctx: &InterpreterContext, x: i32, y: i32, z: i32) -> f64 {
+        match self {
+            Self::Constant(c) => c.value,
+
+            Self::Reference(r) => r
+                .resolved
+                .as_ref()
+                .map_or(0.0, |df| df.compute(ctx, x, y, z)),
+
+            Self::YClampedGradient(g) => map_clamped(
+                f64::from(y),
+                f64::from(g.from_y),
+                f64::from(g.to_y),
+                g.from_value,
+                g.to_value,
+            ),
+
+            Self::Noise(n) => n.noise.as_ref().map_or(0.0, |noise| {
+                let y = if n.y_scale == 0.0 {
+                    0.0
+                } else {
+                    f64::from(y) * n.y_scale
+                };
+                noise.get_value(f64::from(x) * n.xz_scale, y, f64::from(z) * n.xz_scale)
+            }),
+
+            Self::ShiftedNoise(sn) => sn.noise.as_ref().map_or(0.0, |noise| {
+                // Vanilla formula: x * xz_scale + dx (multiply THEN add shift)
+                let dx = sn.shift_x.compute(ctx, x, y, z);
+                let dz = sn.shift_z.compute(ctx, x, y, z);
+                let y = if sn.y_scale == 0.0 {
+                    0.0
+                } else {
+                    f64::from(y) * sn.y_scale + sn.shift_y.compute(ctx, x, y, z)
+                };
+                noise.get_value(
+                    f64::from(x) * sn.xz_scale + dx,
+                    y,
+                    f64::from(z) * sn.xz_scale + dz,
+                )
+            }),
+
+            Self::ShiftA(s) => s.noise.as_ref().map_or(0.0, |noise| {
+                noise.get_value(f64::from(x) * 0.25, 0.0, f64::from(z) * 0.25) * 4.0
+            }),
+
+            Self::ShiftB(s) => s.noise.as_ref().map_or(0.0, |noise| {
+                noise.get_value(f64::from(z) * 0.25, f64::from(x) * 0.25, 0.0) * 4.0
+            }),
+
+            Self::Shift(s) => s.noise.as_ref().map_or(0.0, |noise| {
+                noise.get_value(
+                    f64::from(x) * 0.25,
+                    f64::from(y) * 0.25,
+                    f64::from(z) * 0.25,
+                ) * 4.0
+            }),
+
+            Self::TwoArgumentSimple(t) => {
+                let a = t.argument1.compute(ctx, x, y, z);
+                match t.op {
+                    TwoArgType::Add => a + t.argument2.compute(ctx, x, y, z),
+                    // Vanilla skips the second argument when the first is zero
+                    TwoArgType::Mul if a == 0.0 => 0.0,
+                    TwoArgType::Mul => a * t.argument2.compute(ctx, x, y, z),
+                    TwoArgType::Min => f64::min(a, t.argument2.compute(ctx, x, y, z)),
+                    TwoArgType::Max => f64::max(a, t.argument2.compute(ctx, x, y, z)),
+                }
+            }
+
+            Self::Mapped(m) => {
+                let v = m.input.compute(ctx, x, y, z);
+                match m.op {
+                    MappedType::Abs => v.abs(),
+                    MappedType::Square => v * v,
+                    MappedType::Cube => v * v * v,
+                    MappedType::HalfNegative => {
+                        if v > 0.0 {
+                            v
+                        } else {
+                            v * 0.5
+                        }
+                    }
+                    MappedType::QuarterNegative => {
+                        if v > 0.0 {
+                            v
+                        } else {
+                            v * 0.25
+                        }
+                    }
+                    MappedType::Invert => 1.0 / v,
+                    MappedType::Squeeze => {
+                        let c = clamp(v, -1.0, 1.0);
+                        c / 2.0 - c * c * c / 24.0
+                    }
+                }
+            }
+
+            Self::Clamp(c) => clamp(c.input.compute(ctx, x, y, z), c.min, c.max),
+
+            Self::RangeChoice(rc) => {
+                let v = rc.input.compute(ctx, x, y, z);
+                if v >= rc.min_inclusive && v < rc.max_exclusive {
+                    rc.when_in_range.compute(ctx, x, y, z)
+                } else {
+                    rc.when_out_of_range.compute(ctx, x, y, z)
+                }
+            }
+
+            Self::Spline(s) => f64::from(compute_spline(&s.spline, ctx, x, y, z)),
+
+            Self::BlendedNoise(_) => ctx
+                .blended_noise
+                .as_ref()
+                .map_or(0.0, |noise| noise.compute(x, y, z)),
+
+            Self::WeirdScaledSampler(ws) => ws.noise.as_ref().map_or(0.0, |noise| {
+                let rarity = ws.input.compute(ctx, x, y, z);
+                let scale = ws.rarity_value_mapper.get_values(rarity);
+                scale
+                    * noise
+                        .get_value(
+                            f64::from(x) / scale,
+                            f64::from(y) / scale,
+                            f64::from(z) / scale,
+                        )
+                        .abs()
+            }),
+
+            Self::EndIslands => ctx
+                .end_islands
+                .as_ref()
+                .map_or(0.0, |islands| islands.sample(x, y, z)),
+
+            Self::BlendAlpha(_) => ctx
+                .blender
+                .as_ref()
+                .map_or(1.0, |b| b.blend_offset_and_factor(x, z).alpha),
+
+            Self::BlendOffset(_) => ctx
+                .blender
+                .as_ref()
+                .map_or(0.0, |b| b.blend_offset_and_factor(x, z).blending_offset),
+
+            Self::BlendDensity(bd) => {
+                let v = bd.input.compute(ctx, x, y, z);
+                ctx.blender
+                    .as_ref()
+                    .map_or(v, |b| b.blend_density(x, y, z, v))
+            }
+
+            Self::Marker(m) => {
+                if m.kind == MarkerType::FlatCache {
+                    m.wrapped.compute(ctx, x & !3, 0, z & !3)
+                } else {
+                    m.wrapped.compute(ctx, x, y, z)
+                }
+            }
+
+            Self::FindTopSurface(fts) => {
+                let upper = fts.upper_bound.compute(ctx, x, y, z);
+                let top_y = ((upper / f64::from(fts.cell_height)).floor() as i32) * fts.cell_height;
+                let mut y = top_y;
+                while y >= fts.lower_bound {
+                    if fts.density.compute(ctx, x, y, z) > 0.0 {
+                        return f64::from(y);
+                    }
+                    y -= fts.cell_height;
+                }
+                f64::from(fts.lower_bound)
+            }
+        }
+    }
+}
+
+/// Evaluates a cubic spline, recursing into nested splines.
+fn compute_spline(spline: &CubicSpline, ctx: &InterpreterContext, x: i32, y: i32, z: i32) -> f32 {
+    let coord = spline.coordinate.compute(ctx, x, y, z) as f32;
+    spline_eval::evaluate_spline(
+        &spline.locations,
+        &spline.derivatives,
+        coord,
+        |i| match &spline.points[i].value {
+            SplineValue::Constant(v) => *v,
+            SplineValue::Spline(nested) => compute_spline(nested, ctx, x, y, z),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::density::{
+        Constant, Marker, Noise, RangeChoice, TwoArgumentSimple, YClampedGradient,
+    };
+    use crate::random::{Random, xoroshiro::Xoroshiro};
+
+    fn constant(value: f64) -> Arc<DensityFunction> {
+        Arc::new(DensityFunction::Constant(Constant { value }))
+    }
+
+    #[test]
+    fn test_arithmetic_and_range_choice() {
+        let gradient = Arc::new(DensityFunction::YClampedGradient(YClampedGradient {
+            from_y: 0,
+            to_y: 100,
+            from_value: 1.0,
+            to_value: -1.0,
+        }));
+        let df = DensityFunction::RangeChoice(RangeChoice {
+            input: gradient,
+            min_inclusive: 0.0,
+            max_exclusive: 10.0,
+            when_in_range: Arc::new(DensityFunction::TwoArgumentSimple(TwoArgumentSimple {
+                op: TwoArgType::Mul,
+                argument1: constant(2.0),
+                argument2: constant(3.0),
+            })),
+            when_out_of_range: constant(-5.0),
+        });
+
+        let ctx = InterpreterContext::default();
+        assert!((df.compute(&ctx, 0, 25, 0) - 6.0).abs() < f64::EPSILON);
+        assert!((df.compute(&ctx, 0, 75, 0) + 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_noise_matches_sampler() {
+        let splitter = Xoroshiro::from_seed(42).next_positional();
+        let mut params = FxHashMap::default();
+        params.insert(
+            "minecraft:test".to_owned(),
+            NoiseParameters::new(-4, vec![1.0, 1.0]),
+        );
+        let noises = bake_noises(42, &splitter, &params, false);
+
+        let df = DensityFunction::Marker(Marker {
+            kind: MarkerType::CacheOnce,
+            wrapped: Arc::new(DensityFunction::Noise(Noise {
+                noise_id: "minecraft:test".to_owned(),
+                xz_scale: 0.5,
+                y_scale: 2.0,
+                noise: None,
+            })),
+        })
+        .resolve(&FxHashMap::default(), &noises);
+
+        let expected = noises["minecraft:test"].get_value(5.0, 128.0, -3.5);
+        let ctx = InterpreterContext::default();
+        assert_eq!(df.compute(&ctx, 10, 64, -7).to_bits(), expected.to_bits());
+    }
+}
@@ -1,8 +1,8 @@
 //! Density function types and transpiler for world generation.
 //!
 //! Density functions form a tree structure parsed from JSON at build time.
-//! The transpiler compiles these trees into native Rust code — runtime evaluation
-//! is done by the transpiled output, not by interpreting this tree.
+//! The transpiler compiles these trees into native Rust code. Trees that only
+//! exist at runtime, like ones from a datapack, go through the [`interpreter`].
 //!
 //! # Key Types
 //!
@@ -12,7 +12,11 @@
 //! - [`RarityValueMapper`] - Used at runtime by transpiled cave generation code
 //! - [`DimensionNoises`] - Trait for dimension-specific noise generators
 //! - [`NoiseSettings`] - Trait for dimension-specific settings from datapack
+//! - [`Blender`] - Blends new terrain into chunks from older generation
+//! - [`InterpreterContext`] - Samplers for evaluating a tree with [`DensityFunction::compute`]
 
+pub mod blending;
+pub mod interpreter;
 pub mod spline_eval;
 mod traits;
 mod types;
@@ -20,6 +24,8 @@ mod types;
 #[cfg(feature = "codegen")]
 pub mod transpiler;
 
+pub use blending::{Blender, BlendingData, BlendingOutput, BlendingSource};
+pub use interpreter::{InterpreterContext, bake_noises};
 pub use traits::{ColumnCache, DimensionNoises, NoiseSettings};
 pub use types::{
     BlendAlpha, BlendDensity, BlendOffset, BlendedNoise, Clamp, Constant, CubicSpline,
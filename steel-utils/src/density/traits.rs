@@ -4,12 +4,14 @@
 //! allowing generic chunk generation code to work with any dimension's transpiled
 //! density functions.
 
+use std::sync::Arc;
+
 use crate::BlockStateId;
 use crate::random::RandomSplitter;
 use crate::surface::SurfaceRuleContext;
 use rustc_hash::FxHashMap;
 
-use super::NoiseParameters;
+use super::{Blender, NoiseParameters};
 
 /// Noise settings for a dimension, parsed from the datapack.
 ///
@@ -59,6 +61,12 @@ pub trait ColumnCache: Clone + Default + Send + Sync {
     /// lookup. Out-of-bounds positions fall back to on-the-fly evaluation at
     /// raw (non-quantized) coordinates.
     fn init_grid(&mut self, chunk_block_x: i32, chunk_block_z: i32, noises: &Self::Noises);
+
+    /// Sets the blender used by `blend_alpha`, `blend_offset` and `blend_density`.
+    ///
+    /// Without one they return `1.0`, `0.0` and their input, like vanilla's
+    /// empty blender. Call before `init_grid()` so the grid sees the blender.
+    fn set_blender(&mut self, blender: Option<Arc<Blender>>);
 }
 
 /// All noise generators and density functions for a dimension.
@@ -15,6 +15,16 @@
 //! let tokens: TokenStream = transpile(&input);
 //! ```
 //!
+//! # Markers
+//!
+//! - `Interpolated`: the wrapped function is only evaluated at cell corners
+//!   (`fill_cell_corner_densities`), and the per-block `combine_*` functions
+//!   read the trilinearly interpolated values, matching vanilla's `NoiseChunk`.
+//! - `FlatCache` / `Cache2D`: evaluated once per column into the column cache.
+//! - `CacheOnce` / `CacheAllInCell`: evaluated once per position. Repeated
+//!   subtrees in a generated function body share one local, so the wrapped
+//!   function runs once per block however often it's referenced.
+//!
 //! Gated behind the `codegen` feature flag.
 
 use std::collections::{BTreeMap, BTreeSet};
@@ -125,6 +135,29 @@ struct TranspileContext {
     /// Named functions that (transitively) contain `Interpolated` markers.
     /// In param mode, these are inlined instead of called as functions.
     interpolated_refs: BTreeSet<String>,
+    /// Subtrees shared within the function body being generated, `None`
+    /// outside [`Self::gen_body`].
+    cse: Option<CseScope>,
+    /// Depth of conditionally evaluated branches being generated. Shared
+    /// values can be reused there, but not hoisted, since hoisting would
+    /// evaluate them unconditionally.
+    cse_conditional: usize,
+}
+
+/// Subtrees evaluated more than once in one generated function body, hoisted
+/// into `let`s so each is computed once per evaluation.
+///
+/// Subtrees are compared by [`subtree_key`], so identical trees share a value
+/// even when they come from different places in the JSON (e.g. the same noise
+/// sampled from two router arguments).
+#[derive(Default)]
+struct CseScope {
+    /// How often each shareable subtree is evaluated unconditionally.
+    counts: BTreeMap<String, usize>,
+    /// Locals already holding a subtree's value.
+    locals: BTreeMap<String, Ident>,
+    /// The `let`s binding those locals, dependencies first.
+    lets: Vec<TokenStream>,
 }
 
 impl TranspileContext {
@@ -145,6 +178,8 @@ impl TranspileContext {
             interpolated_param_mode: false,
             interpolated_param_counter: 0,
             interpolated_refs: BTreeSet::new(),
+            cse: None,
+            cse_conditional: 0,
         }
     }
 
@@ -648,6 +683,8 @@ impl TranspileContext {
                 grid_first_quart_x: i32,
                 grid_first_quart_z: i32,
                 has_grid: bool,
+                /// Blender for chunks next to old generation (`None` blends nothing).
+                pub blender: Option<std::sync::Arc<steel_utils::density::Blender>>,
                 // Active value fields (read by compute functions)
                 #(#cache_fields,)*
                 #(#router_fields,)*
@@ -672,6 +709,7 @@ impl TranspileContext {
                         grid_first_quart_x: 0,
                         grid_first_quart_z: 0,
                         has_grid: false,
+                        blender: None,
                         #(#default_fields,)*
                         #(#router_default_fields,)*
                         #(#grid_default_fields,)*
@@ -809,7 +847,7 @@ impl TranspileContext {
             let fn_name = named_fn_ident(&name);
             let is_flat = self.flat_cached.contains(&name);
 
-            let body = self.gen_expr(&inner, input, is_flat);
+            let body = self.gen_single_body(&inner, input, is_flat);
 
             let params = self.fn_params(is_flat);
 
@@ -848,7 +886,7 @@ impl TranspileContext {
                 // Generate private compute function used by ensure().
                 let compute_fn_name = router_compute_fn_ident(name);
                 let inner = unwrap_markers(df);
-                let compute_body = self.gen_expr(inner, input, true);
+                let compute_body = self.gen_single_body(inner, input, true);
                 let compute_params = self.fn_params(true);
 
                 fns.push(quote! {
@@ -872,7 +910,7 @@ impl TranspileContext {
             } else {
                 let inner = unwrap_markers(df);
                 let is_flat = is_flat_cached(df);
-                let body = self.gen_expr(inner, input, is_flat);
+                let body = self.gen_single_body(inner, input, is_flat);
                 let params = self.fn_params_router(is_flat);
 
                 fns.push(quote! {
@@ -946,21 +984,25 @@ impl TranspileContext {
         let total_count = all_inners.len();
         let total_count_lit = Literal::usize_unsuffixed(total_count);
 
-        // Phase 2: Generate fill_cell_corner_densities with ALL channels
-        let mut inner_stmts = Vec::with_capacity(total_count);
-        for (i, inner_df) in all_inners.iter().enumerate() {
-            let idx = Literal::usize_unsuffixed(i);
-            let inner = unwrap_markers(inner_df);
-            let expr = self.gen_expr(inner, input, false);
-            inner_stmts.push(quote! { out[#idx] = #expr; });
-        }
+        // Phase 2: Generate fill_cell_corner_densities with ALL channels,
+        // sharing subtrees between channels
+        let roots: Vec<&DensityFunction> = all_inners.iter().map(unwrap_markers).collect();
+        let (inner_lets, inner_exprs) = self.gen_body(&roots, input, false);
+        let inner_stmts: Vec<TokenStream> = inner_exprs
+            .iter()
+            .enumerate()
+            .map(|(i, expr)| {
+                let idx = Literal::usize_unsuffixed(i);
+                quote! { out[#idx] = #expr; }
+            })
+            .collect();
         let fill_spline_fns = mem::take(&mut self.spline_fns);
 
         // Phase 3: Generate combine_interpolated for final_density
         let combine_fd_body = if let Some(info) = entries.get("final_density") {
             self.interpolated_param_mode = true;
             self.interpolated_param_counter = info.start;
-            let body = self.gen_expr(&info.df, input, false);
+            let body = self.gen_single_body(&info.df, input, false);
             self.interpolated_param_mode = false;
             body
         } else {
@@ -972,7 +1014,7 @@ impl TranspileContext {
         let combine_vein_toggle_body = if let Some(info) = entries.get("vein_toggle") {
             self.interpolated_param_mode = true;
             self.interpolated_param_counter = info.start;
-            let body = self.gen_expr(&info.df, input, false);
+            let body = self.gen_single_body(&info.df, input, false);
             self.interpolated_param_mode = false;
             body
         } else {
@@ -984,7 +1026,7 @@ impl TranspileContext {
         let combine_vein_ridged_body = if let Some(info) = entries.get("vein_ridged") {
             self.interpolated_param_mode = true;
             self.interpolated_param_counter = info.start;
-            let body = self.gen_expr(&info.df, input, false);
+            let body = self.gen_single_body(&info.df, input, false);
             self.interpolated_param_mode = false;
             body
         } else {
@@ -1022,6 +1064,7 @@ impl TranspileContext {
             ) {
                 let x = cache.x;
                 let z = cache.z;
+                #(#inner_lets)*
                 #(#inner_stmts)*
             }
 
@@ -1079,15 +1122,181 @@ impl TranspileContext {
 
     // ── Expression generation ───────────────────────────────────────────
 
-    /// Generate a `TokenStream` expression that computes a density function value.
+    /// Generate the expressions evaluated together in one function body.
+    ///
+    /// Returns the `let`s for the subtrees the expressions share, which must
+    /// come before them, and the expressions themselves.
+    fn gen_body(
+        &mut self,
+        roots: &[&DensityFunction],
+        input: &TranspilerInput,
+        is_flat: bool,
+    ) -> (Vec<TokenStream>, Vec<TokenStream>) {
+        let mut counts = BTreeMap::new();
+        for root in roots {
+            self.count_shared(root, input, is_flat, &mut counts);
+        }
+
+        let outer = self.cse.replace(CseScope {
+            counts,
+            ..CseScope::default()
+        });
+        let exprs = roots
+            .iter()
+            .map(|root| self.gen_expr(root, input, is_flat))
+            .collect();
+        let scope = mem::replace(&mut self.cse, outer).expect("scope was set above");
+        (scope.lets, exprs)
+    }
+
+    /// Generate a function body evaluating a single density function.
+    fn gen_single_body(
+        &mut self,
+        df: &DensityFunction,
+        input: &TranspilerInput,
+        is_flat: bool,
+    ) -> TokenStream {
+        let (lets, exprs) = self.gen_body(&[df], input, is_flat);
+        let expr = &exprs[0];
+        quote! {
+            #(#lets)*
+            #expr
+        }
+    }
+
+    /// Count the unconditional evaluations of each shareable subtree of `df`.
+    ///
+    /// Only the first occurrence of a subtree is descended into, so the
+    /// children of a shared subtree aren't shared separately unless they
+    /// also appear elsewhere.
+    fn count_shared(
+        &self,
+        df: &DensityFunction,
+        input: &TranspilerInput,
+        is_flat: bool,
+        counts: &mut BTreeMap<String, usize>,
+    ) {
+        if let Some(key) = self.shareable_key(df, input, is_flat) {
+            let count = counts.entry(key).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                return;
+            }
+        }
+
+        match unwrap_markers(df) {
+            DensityFunction::ShiftedNoise(sn) => {
+                self.count_shared(&sn.shift_x, input, is_flat, counts);
+                self.count_shared(&sn.shift_y, input, is_flat, counts);
+                self.count_shared(&sn.shift_z, input, is_flat, counts);
+            }
+            DensityFunction::TwoArgumentSimple(t) => {
+                self.count_shared(&t.argument1, input, is_flat, counts);
+                self.count_shared(&t.argument2, input, is_flat, counts);
+            }
+            DensityFunction::Mapped(m) => self.count_shared(&m.input, input, is_flat, counts),
+            DensityFunction::Clamp(c) => self.count_shared(&c.input, input, is_flat, counts),
+            DensityFunction::WeirdScaledSampler(ws) => {
+                self.count_shared(&ws.input, input, is_flat, counts);
+            }
+            DensityFunction::BlendDensity(bd) => {
+                self.count_shared(&bd.input, input, is_flat, counts);
+            }
+            // Branches are evaluated conditionally and the surface search
+            // rebinds y, so only the parts always evaluated count
+            DensityFunction::RangeChoice(rc) => {
+                self.count_shared(&rc.input, input, is_flat, counts);
+            }
+            DensityFunction::FindTopSurface(fts) => {
+                self.count_shared(&fts.upper_bound, input, is_flat, counts);
+            }
+            // Nested spline values live in their own helper functions
+            DensityFunction::Spline(s) => {
+                self.count_shared(&s.spline.coordinate, input, is_flat, counts);
+            }
+            _ => {}
+        }
+    }
+
+    /// The key `df` is shared under in the current function body, or `None`
+    /// if it's too cheap to be worth a local or can't be shared.
+    ///
+    /// Cheap leaves are only shared when wrapped in `CacheOnce` or
+    /// `CacheAllInCell`, which ask for one evaluation per position.
+    fn shareable_key(
+        &self,
+        df: &DensityFunction,
+        input: &TranspilerInput,
+        is_flat: bool,
+    ) -> Option<String> {
+        // Interpolated markers are numbered as they're generated in param
+        // mode, so subtrees containing them must each be generated in place
+        if self.interpolated_param_mode
+            && has_interpolated_markers(df, &input.registry, &mut BTreeSet::new())
+        {
+            return None;
+        }
+
+        let worth_sharing = match unwrap_markers(df) {
+            DensityFunction::Constant(_) => false,
+            DensityFunction::YClampedGradient(_)
+            | DensityFunction::BlendAlpha(_)
+            | DensityFunction::BlendOffset(_) => {
+                has_marker(df, MarkerType::CacheOnce) || has_marker(df, MarkerType::CacheAllInCell)
+            }
+            // Flat-cached references are already a field read
+            DensityFunction::Reference(r) => !self.flat_cached.contains(&r.id),
+            _ => true,
+        };
+        worth_sharing.then(|| format!("{is_flat}:{}", subtree_key(df)))
+    }
+
+    /// Generate a `TokenStream` expression that computes a density function value,
+    /// reading it from a shared local when the subtree is evaluated more than
+    /// once in the current function body.
     ///
     /// `is_flat` indicates this expression tree is xz-only (no y available).
-    #[expect(clippy::too_many_lines, reason = "splitting would hurt readability")]
     fn gen_expr(
         &mut self,
         df: &DensityFunction,
         input: &TranspilerInput,
         is_flat: bool,
+    ) -> TokenStream {
+        let key = match &self.cse {
+            Some(_) => self.shareable_key(df, input, is_flat),
+            None => None,
+        };
+        let Some((key, scope)) = key.zip(self.cse.as_ref()) else {
+            return self.gen_node(df, input, is_flat);
+        };
+
+        if let Some(local) = scope.locals.get(&key) {
+            return quote! { #local };
+        }
+        if self.cse_conditional > 0 || scope.counts.get(&key).is_none_or(|&count| count < 2) {
+            return self.gen_node(df, input, is_flat);
+        }
+
+        // Markers are transparent here; unwrapping them keeps the wrapped
+        // node from being hoisted a second time under the same key
+        let expr = self.gen_node(unwrap_markers(df), input, is_flat);
+        let scope = self
+            .cse
+            .as_mut()
+            .expect("scope is restored after nested bodies");
+        let local = format_ident!("__cse_{}", scope.lets.len());
+        scope.lets.push(quote! { let #local = #expr; });
+        scope.locals.insert(key, local.clone());
+        quote! { #local }
+    }
+
+    /// Generate the expression for a single density function node.
+    #[expect(clippy::too_many_lines, reason = "splitting would hurt readability")]
+    fn gen_node(
+        &mut self,
+        df: &DensityFunction,
+        input: &TranspilerInput,
+        is_flat: bool,
     ) -> TokenStream {
         match df {
             DensityFunction::Constant(c) => {
@@ -1204,8 +1413,10 @@ impl TranspileContext {
 
             DensityFunction::RangeChoice(rc) => {
                 let input_expr = self.gen_expr(&rc.input, input, is_flat);
+                self.cse_conditional += 1;
                 let in_range = self.gen_expr(&rc.when_in_range, input, is_flat);
                 let out_range = self.gen_expr(&rc.when_out_of_range, input, is_flat);
+                self.cse_conditional -= 1;
                 let min = Literal::f64_unsuffixed(rc.min_inclusive);
                 let max = Literal::f64_unsuffixed(rc.max_exclusive);
                 quote! {{
@@ -1236,8 +1447,14 @@ impl TranspileContext {
                 }}
             }
 
-            DensityFunction::BlendAlpha(_) => quote! { 1.0 },
-            DensityFunction::BlendOffset(_) => quote! { 0.0 },
+            DensityFunction::BlendAlpha(_) => quote! {
+                cache.blender.as_ref().map_or(1.0, |b| b.blend_offset_and_factor(x, z).alpha)
+            },
+            DensityFunction::BlendOffset(_) => quote! {
+                cache.blender.as_ref().map_or(0.0, |b| {
+                    b.blend_offset_and_factor(x, z).blending_offset
+                })
+            },
             // EndIslands ignores y internally, so we can pass 0 in flat contexts
             DensityFunction::EndIslands => {
                 if is_flat {
@@ -1246,7 +1463,13 @@ impl TranspileContext {
                     quote! { noises.end_islands.sample(x, y, z) }
                 }
             }
-            DensityFunction::BlendDensity(bd) => self.gen_expr(&bd.input, input, is_flat),
+            DensityFunction::BlendDensity(bd) => {
+                let inner = self.gen_expr(&bd.input, input, is_flat);
+                quote! {{
+                    let v = #inner;
+                    cache.blender.as_ref().map_or(v, |b| b.blend_density(x, y, z, v))
+                }}
+            }
             DensityFunction::Marker(m) => {
                 if self.interpolated_param_mode && m.kind == MarkerType::Interpolated {
                     let idx = Literal::usize_unsuffixed(self.interpolated_param_counter);
@@ -1260,8 +1483,11 @@ impl TranspileContext {
             DensityFunction::FindTopSurface(fts) => {
                 // upper_bound is flat (xz-only)
                 let upper_expr = self.gen_expr(&fts.upper_bound, input, is_flat);
-                // density uses y — generate with is_flat=false so it references our loop var
+                // density uses y — generate with is_flat=false so it references our loop var.
+                // The loop rebinds y, so nothing shared outside it applies inside.
+                let outer = self.cse.take();
                 let density_expr = self.gen_expr(&fts.density, input, false);
+                self.cse = outer;
                 let cell_height = Literal::i32_unsuffixed(fts.cell_height);
                 let lower_bound = Literal::i32_unsuffixed(fts.lower_bound);
                 quote! {{
@@ -1378,7 +1604,10 @@ impl TranspileContext {
         self.spline_counter += 1;
         let fn_name = format_ident!("spline_helper_{}", id);
 
+        // The helper is a separate function, locals of the caller aren't in scope
+        let outer = self.cse.take();
         let body = self.gen_spline_expr(spline, input, is_flat);
+        self.cse = outer;
 
         let params = self.fn_params(is_flat);
 
@@ -1402,7 +1631,9 @@ fn uses_y(df: &DensityFunction) -> bool {
         // uses y * 0.25
         DensityFunction::YClampedGradient(_)
         | DensityFunction::Shift(_)
-        | DensityFunction::BlendedNoise(_) => true,
+        | DensityFunction::BlendedNoise(_)
+        // blends against old densities per 8-block cell
+        | DensityFunction::BlendDensity(_) => true,
         DensityFunction::Noise(n) => n.y_scale != 0.0,
         DensityFunction::ShiftedNoise(sn) => sn.y_scale != 0.0 || uses_y(&sn.shift_y),
         DensityFunction::WeirdScaledSampler(ws) => uses_y(&ws.input),
@@ -1412,13 +1643,12 @@ fn uses_y(df: &DensityFunction) -> bool {
         DensityFunction::RangeChoice(rc) => {
             uses_y(&rc.input) || uses_y(&rc.when_in_range) || uses_y(&rc.when_out_of_range)
         }
-        DensityFunction::BlendDensity(bd) => uses_y(&bd.input),
         DensityFunction::Marker(m) => uses_y(&m.wrapped),
         DensityFunction::Spline(s) => uses_y_spline(&s.spline),
         // These don't use Y:
         // - FindTopSurface scans Y internally but result only depends on (x, z)
         // - References are handled at the analysis level
-        // - Constants, shifts, blend, and end-islands are Y-independent
+        // - Constants, shifts, blend alpha/offset, and end-islands are Y-independent
         DensityFunction::FindTopSurface(_)
         | DensityFunction::Reference(_)
         | DensityFunction::Constant(_)
@@ -1457,6 +1687,103 @@ fn unwrap_markers(df: &DensityFunction) -> &DensityFunction {
     }
 }
 
+/// Check if any of the markers wrapping `df` is of the given kind.
+fn has_marker(df: &DensityFunction, kind: MarkerType) -> bool {
+    match df {
+        DensityFunction::Marker(m) => m.kind == kind || has_marker(&m.wrapped, kind),
+        _ => false,
+    }
+}
+
+/// A structural key for a density function subtree: two subtrees with the same
+/// key compute the same value at the same position.
+///
+/// Markers are transparent, and references are keyed by ID rather than by
+/// their resolved tree.
+fn subtree_key(df: &DensityFunction) -> String {
+    match unwrap_markers(df) {
+        DensityFunction::Constant(c) => format!("{:?}", c.value),
+        DensityFunction::Reference(r) => format!("ref({})", r.id),
+        DensityFunction::YClampedGradient(g) => format!(
+            "y_gradient({},{},{:?},{:?})",
+            g.from_y, g.to_y, g.from_value, g.to_value
+        ),
+        DensityFunction::Noise(n) => {
+            format!("noise({},{:?},{:?})", n.noise_id, n.xz_scale, n.y_scale)
+        }
+        DensityFunction::ShiftedNoise(sn) => format!(
+            "shifted_noise({},{:?},{:?},{},{},{})",
+            sn.noise_id,
+            sn.xz_scale,
+            sn.y_scale,
+            subtree_key(&sn.shift_x),
+            subtree_key(&sn.shift_y),
+            subtree_key(&sn.shift_z)
+        ),
+        DensityFunction::ShiftA(s) => format!("shift_a({})", s.noise_id),
+        DensityFunction::ShiftB(s) => format!("shift_b({})", s.noise_id),
+        DensityFunction::Shift(s) => format!("shift({})", s.noise_id),
+        DensityFunction::TwoArgumentSimple(t) => format!(
+            "{:?}({},{})",
+            t.op,
+            subtree_key(&t.argument1),
+            subtree_key(&t.argument2)
+        ),
+        DensityFunction::Mapped(m) => format!("{:?}({})", m.op, subtree_key(&m.input)),
+        DensityFunction::Clamp(c) => {
+            format!("clamp({},{:?},{:?})", subtree_key(&c.input), c.min, c.max)
+        }
+        DensityFunction::RangeChoice(rc) => format!(
+            "range_choice({},{:?},{:?},{},{})",
+            subtree_key(&rc.input),
+            rc.min_inclusive,
+            rc.max_exclusive,
+            subtree_key(&rc.when_in_range),
+            subtree_key(&rc.when_out_of_range)
+        ),
+        DensityFunction::Spline(s) => spline_key(&s.spline),
+        // A dimension has a single blended noise
+        DensityFunction::BlendedNoise(_) => "blended_noise".to_owned(),
+        DensityFunction::WeirdScaledSampler(ws) => format!(
+            "weird_scaled_sampler({},{:?},{})",
+            ws.noise_id,
+            ws.rarity_value_mapper,
+            subtree_key(&ws.input)
+        ),
+        DensityFunction::EndIslands => "end_islands".to_owned(),
+        DensityFunction::BlendAlpha(_) => "blend_alpha".to_owned(),
+        DensityFunction::BlendOffset(_) => "blend_offset".to_owned(),
+        DensityFunction::BlendDensity(bd) => format!("blend_density({})", subtree_key(&bd.input)),
+        DensityFunction::FindTopSurface(fts) => format!(
+            "find_top_surface({},{},{},{})",
+            subtree_key(&fts.density),
+            subtree_key(&fts.upper_bound),
+            fts.lower_bound,
+            fts.cell_height
+        ),
+        DensityFunction::Marker(_) => unreachable!("markers are unwrapped"),
+    }
+}
+
+fn spline_key(spline: &CubicSpline) -> String {
+    let points: Vec<String> = spline
+        .points
+        .iter()
+        .map(|p| {
+            let value = match &p.value {
+                SplineValue::Constant(c) => format!("{c:?}"),
+                SplineValue::Spline(nested) => spline_key(nested),
+            };
+            format!("{:?}:{value}:{:?}", p.location, p.derivative)
+        })
+        .collect();
+    format!(
+        "spline({},[{}])",
+        subtree_key(&spline.coordinate),
+        points.join(";")
+    )
+}
+
 fn collect_references(df: &DensityFunction) -> Vec<String> {
     let mut refs = Vec::new();
     collect_refs_inner(df, &mut refs);
@@ -1555,9 +1882,11 @@ fn collect_interpolated_walk(
             collect_interpolated_walk(&sn.shift_y, registry, inners);
             collect_interpolated_walk(&sn.shift_z, registry, inners);
         }
+        // Same order as `gen_node` generates them, so the channels line up
+        // with the `interpolated[i]` reads in param mode
         DensityFunction::FindTopSurface(fts) => {
-            collect_interpolated_walk(&fts.density, registry, inners);
             collect_interpolated_walk(&fts.upper_bound, registry, inners);
+            collect_interpolated_walk(&fts.density, registry, inners);
         }
         DensityFunction::Reference(r) => {
             if let Some(ref_df) = registry.get(&r.id) {
@@ -1686,3 +2015,87 @@ fn sanitize_name(id: &str) -> String {
     };
     path.replace('/', "__").replace('-', "_")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::density::{Marker, Noise, RangeChoice, TwoArgumentSimple};
+
+    fn noise(noise_id: &str) -> Arc<DensityFunction> {
+        Arc::new(DensityFunction::Noise(Noise {
+            noise_id: noise_id.to_owned(),
+            xz_scale: 1.0,
+            y_scale: 1.0,
+            noise: None,
+        }))
+    }
+
+    fn transpile_router(name: &str, df: DensityFunction) -> String {
+        let input = TranspilerInput {
+            registry: BTreeMap::new(),
+            router_entries: BTreeMap::from([(name.to_owned(), df)]),
+            prefix: "Test".to_owned(),
+            cell_width: 4,
+            legacy_random_source: false,
+        };
+        transpile(&input).to_string()
+    }
+
+    #[test]
+    fn test_repeated_subtree_evaluated_once() {
+        let code = transpile_router(
+            "test",
+            DensityFunction::TwoArgumentSimple(TwoArgumentSimple {
+                op: TwoArgType::Add,
+                argument1: noise("minecraft:a"),
+                argument2: noise("minecraft:a"),
+            }),
+        );
+
+        assert_eq!(code.matches("get_value").count(), 1);
+        assert!(code.contains("let __cse_0"));
+    }
+
+    #[test]
+    fn test_conditional_branches_not_hoisted() {
+        let code = transpile_router(
+            "test",
+            DensityFunction::RangeChoice(RangeChoice {
+                input: noise("minecraft:a"),
+                min_inclusive: 0.0,
+                max_exclusive: 1.0,
+                when_in_range: noise("minecraft:b"),
+                when_out_of_range: noise("minecraft:b"),
+            }),
+        );
+
+        assert_eq!(code.matches("get_value").count(), 3);
+        assert!(!code.contains("__cse"));
+    }
+
+    #[test]
+    fn test_combine_shares_subtrees_outside_interpolation() {
+        let interpolated = Arc::new(DensityFunction::Marker(Marker {
+            kind: MarkerType::Interpolated,
+            wrapped: noise("minecraft:a"),
+        }));
+        let squared = Arc::new(DensityFunction::TwoArgumentSimple(TwoArgumentSimple {
+            op: TwoArgType::Mul,
+            argument1: noise("minecraft:b"),
+            argument2: noise("minecraft:b"),
+        }));
+        let code = transpile_router(
+            "final_density",
+            DensityFunction::TwoArgumentSimple(TwoArgumentSimple {
+                op: TwoArgType::Add,
+                argument1: interpolated,
+                argument2: squared,
+            }),
+        );
+
+        // `a` in the direct router and at cell corners, `b` once each in the
+        // direct router and in `combine_interpolated`
+        assert_eq!(code.matches("get_value").count(), 4);
+        assert!(code.contains("INTERPOLATED_COUNT : usize = 1"));
+    }
+}
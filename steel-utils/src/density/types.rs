@@ -4,8 +4,8 @@
 //! record/class pattern. The [`DensityFunction`] enum wraps them for dispatch.
 //!
 //! These types are used at build time by the density function transpiler to parse
-//! JSON density function trees and generate native Rust code. Runtime evaluation
-//! is done by the transpiled code, not by interpreting this tree.
+//! JSON density function trees and generate native Rust code. Trees that aren't
+//! known at build time are evaluated by the [interpreter](super::interpreter).
 
 use std::sync::Arc;
 
@@ -313,13 +313,13 @@ pub struct Spline {
     pub spline: Arc<CubicSpline>,
 }
 
-/// Blend alpha density function (returns 1.0, placeholder for blending).
+/// Blend alpha density function (1.0 away from old chunks, see [`Blender`](super::Blender)).
 ///
 /// Matches vanilla's `DensityFunctions.BlendAlpha`.
 #[derive(Debug, Clone, Copy)]
 pub struct BlendAlpha;
 
-/// Blend offset density function (returns 0.0, placeholder for blending).
+/// Blend offset density function (0.0 away from old chunks, see [`Blender`](super::Blender)).
 ///
 /// Matches vanilla's `DensityFunctions.BlendOffset`.
 #[derive(Debug, Clone, Copy)]
@@ -334,7 +334,7 @@ pub struct BlendOffset;
 /// per-type class/record pattern.
 ///
 /// This tree is used at build time by the transpiler to generate native Rust code.
-/// Runtime evaluation is done by the transpiled output, not by interpreting this tree.
+/// Trees only known at runtime are evaluated with [`compute`](Self::compute).
 #[derive(Debug, Clone)]
 pub enum DensityFunction {
     /// A constant value.
@@ -386,10 +386,10 @@ pub enum DensityFunction {
     /// lives in `steel-core::worldgen::end_islands::EndIslands`).
     EndIslands,
 
-    /// Blend alpha (returns 1.0, placeholder for blending).
+    /// Blend alpha (1.0 away from old chunks).
     BlendAlpha(BlendAlpha),
 
-    /// Blend offset (returns 0.0, placeholder for blending).
+    /// Blend offset (0.0 away from old chunks).
     BlendOffset(BlendOffset),
 
     /// Blend density (for chunk blending).
@@ -653,6 +653,8 @@ pub struct CubicSpline {
     pub points: Vec<SplinePoint>,
     /// Pre-extracted point locations for binary search (avoids allocation per eval).
     pub locations: Vec<f32>,
+    /// Pre-extracted point derivatives, parallel to `locations`.
+    pub derivatives: Vec<f32>,
 }
 
 /// A point in a cubic spline.
@@ -680,10 +682,12 @@ impl CubicSpline {
     #[must_use]
     pub fn new(coordinate: Arc<DensityFunction>, points: Vec<SplinePoint>) -> Self {
         let locations = points.iter().map(|p| p.location).collect();
+        let derivatives = points.iter().map(|p| p.derivative).collect();
         Self {
             coordinate,
             points,
             locations,
+            derivatives,
         }
     }
 }
@@ -2,9 +2,14 @@
 //!
 //! This is moved from `steel-registry::blocks::properties::Direction`.
 
-use std::io::{self, Cursor};
+use std::io::Cursor;
 
-use crate::{codec::VarInt, math::Axis, serial::ReadFrom, types::BlockPos};
+use crate::{
+    codec::VarInt,
+    math::Axis,
+    serial::{ReadError, ReadFrom},
+    types::BlockPos,
+};
 
 /// The six cardinal directions in Minecraft.
 #[derive(Clone, Copy, Debug)]
@@ -25,7 +30,7 @@ pub enum Direction {
 }
 
 impl ReadFrom for Direction {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let id = VarInt::read(data)?.0;
         match id {
             0 => Ok(Direction::Down),
@@ -34,7 +39,10 @@ impl ReadFrom for Direction {
             3 => Ok(Direction::South),
             4 => Ok(Direction::West),
             5 => Ok(Direction::East),
-            _ => Err(io::Error::other("Invalid Direction id")),
+            _ => Err(ReadError::InvalidDiscriminant {
+                kind: "Direction",
+                value: i128::from(id),
+            }),
         }
     }
 }
@@ -83,6 +91,27 @@ impl Direction {
         }
     }
 
+    /// Returns the direction with the given 3D data value (down, up, north, south, west, east).
+    ///
+    /// Values outside `0..6` wrap around. Vanilla: `Direction.from3DDataValue()`.
+    #[must_use]
+    pub const fn from_3d_data_value(value: i32) -> Direction {
+        match (value % 6).abs() {
+            0 => Direction::Down,
+            1 => Direction::Up,
+            2 => Direction::North,
+            3 => Direction::South,
+            4 => Direction::West,
+            _ => Direction::East,
+        }
+    }
+
+    /// Returns the 3D data value of this direction. Vanilla: `Direction.get3DDataValue()`.
+    #[must_use]
+    pub const fn get_3d_data_value(self) -> i32 {
+        self as i32
+    }
+
     /// Returns the horizontal direction from a yaw rotation.
     ///
     /// Yaw values follow Minecraft's convention:
@@ -187,6 +216,17 @@ impl Direction {
         Direction::East,
     ];
 
+    /// The 6 directions in 3D data value order.
+    /// This matches vanilla's `Direction.values()`.
+    pub const VALUES: [Direction; 6] = [
+        Direction::Down,
+        Direction::Up,
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+
     /// The 6 directions.
     pub const ALL: [Direction; 6] = [
         Direction::North,
@@ -279,6 +319,14 @@ impl Direction {
         ]
     }
 
+    /// Returns the direction with the given name, like `north`.
+    ///
+    /// Vanilla: `Direction.byName()`.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Direction> {
+        Self::VALUES.into_iter().find(|dir| dir.as_str() == name)
+    }
+
     /// Returns the direction name as a string (for `PropertyEnum` compatibility).
     #[must_use]
     pub const fn as_str(&self) -> &str {
@@ -48,6 +48,13 @@ impl FrontVec {
         (self.buf.len() - self.front_space) == 0
     }
 
+    /// Returns how many bytes the underlying buffer has allocated, including the
+    /// reserved front space.
+    #[must_use]
+    pub const fn allocated(&self) -> usize {
+        self.buf.capacity()
+    }
+
     /// Pushes a value to the back of the `FrontVec`.
     pub fn push(&mut self, value: u8) {
         self.buf.push(value);
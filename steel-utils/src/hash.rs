@@ -14,6 +14,8 @@
 //!
 //! All numeric values are little-endian (matching Guava's Hasher).
 
+use simdnbt::owned::{NbtCompound, NbtTag};
+
 /// Type tags matching Minecraft's `HashOps` implementation.
 #[repr(u8)]
 #[derive(Clone, Copy)]
@@ -184,6 +186,33 @@ impl ComponentHasher {
         self.put_tag(HashTag::MapEnd);
     }
 
+    /// Writes a map with string keys whose values are already hashed, like
+    /// the fields of a record.
+    pub fn put_fields(&mut self, fields: &[(&str, i32)]) {
+        let mut entries: Vec<_> = fields
+            .iter()
+            .map(|&(key, value)| {
+                let mut key_hasher = Self::new();
+                key_hasher.put_string(key);
+                let key_hash = crc32c::crc32c(&key_hasher.data);
+                HashEntry {
+                    key_hash: i64::from(key_hash),
+                    value_hash: i64::from(value as u32),
+                    key_bytes: key_hash.to_le_bytes(),
+                    value_bytes: value.to_le_bytes(),
+                }
+            })
+            .collect();
+        sort_map_entries(&mut entries);
+
+        self.start_map();
+        for entry in &entries {
+            self.put_raw_bytes(&entry.key_bytes);
+            self.put_raw_bytes(&entry.value_bytes);
+        }
+        self.end_map();
+    }
+
     /// Starts a list. Call `end_list()` when done adding elements.
     pub fn start_list(&mut self) {
         self.put_tag(HashTag::ListStart);
@@ -405,6 +434,54 @@ impl HashComponent for () {
     }
 }
 
+impl HashComponent for NbtTag {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        match self {
+            Self::Byte(v) => hasher.put_byte(*v),
+            Self::Short(v) => hasher.put_short(*v),
+            Self::Int(v) => hasher.put_int(*v),
+            Self::Long(v) => hasher.put_long(*v),
+            Self::Float(v) => hasher.put_float(*v),
+            Self::Double(v) => hasher.put_double(*v),
+            Self::ByteArray(v) => hasher.put_byte_array(v),
+            Self::String(v) => hasher.put_string(&v.to_str()),
+            Self::List(list) => {
+                hasher.start_list();
+                for tag in list.as_nbt_tags() {
+                    tag.hash_component(hasher);
+                }
+                hasher.end_list();
+            }
+            Self::Compound(compound) => compound.hash_component(hasher),
+            Self::IntArray(v) => hasher.put_int_array(v),
+            Self::LongArray(v) => hasher.put_long_array(v),
+        }
+    }
+}
+
+/// Compounds hash as maps, matching `NbtOps` converted through `HashOps`.
+impl HashComponent for NbtCompound {
+    fn hash_component(&self, hasher: &mut ComponentHasher) {
+        hasher.start_map();
+        let mut entries: Vec<_> = self
+            .iter()
+            .map(|(key, value)| {
+                let mut key_hasher = ComponentHasher::new();
+                key_hasher.put_string(&key.to_str());
+                let mut value_hasher = ComponentHasher::new();
+                value.hash_component(&mut value_hasher);
+                HashEntry::new(key_hasher, value_hasher)
+            })
+            .collect();
+        sort_map_entries(&mut entries);
+        for entry in &entries {
+            hasher.put_raw_bytes(&entry.key_bytes);
+            hasher.put_raw_bytes(&entry.value_bytes);
+        }
+        hasher.end_map();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
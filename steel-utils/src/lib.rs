@@ -7,6 +7,8 @@
 /// Climate system for biome selection.
 pub mod climate;
 pub mod codec;
+/// Biome colormap sampling for grass and foliage colors.
+pub mod colormap;
 /// Density function system for world generation.
 pub mod density;
 /// Direction enum for the six cardinal directions.
@@ -19,11 +21,15 @@ pub mod locks;
 /// Utilities for Steel logging.
 pub mod logger;
 pub mod math;
+/// NBT binary and SNBT codecs.
+pub mod nbt;
 /// Noise generation utilities for world generation.
 pub mod noise;
 pub mod random;
 /// helpful tools for registry
 pub mod registry;
+/// Rotation and mirror transforms around the Y axis.
+pub mod rotation;
 pub mod serial;
 /// Surface rule context types for generated code.
 pub mod surface;
@@ -48,6 +54,7 @@ pub mod entity_events;
 
 pub use direction::Direction;
 pub use front_vec::FrontVec;
+pub use rotation::{Mirror, Rotation};
 pub use types::BlockPos;
 pub use types::BlockStateId;
 pub use types::BoundingBox;
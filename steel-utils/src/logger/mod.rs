@@ -3,11 +3,42 @@ use std::{
     sync::{Arc, OnceLock},
 };
 use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
 
 /// A reference to the Steel Logger\
 /// Use the log macros instead: `log::info`!, `logger::chat`!, etc...
 pub static STEEL_LOGGER: OnceLock<Arc<dyn SteelLogger>> = OnceLock::new();
 
+/// Changes the level a log target logs from, set by the server binary that owns
+/// the log filter.
+pub type LevelSetter = fn(&str, LevelFilter) -> Result<(), String>;
+
+static LEVEL_SETTER: OnceLock<LevelSetter> = OnceLock::new();
+
+/// Sets how log levels are changed while the server runs.
+///
+/// # Panics
+/// Panics if called more than once.
+pub fn set_level_setter(setter: LevelSetter) {
+    assert!(
+        LEVEL_SETTER.set(setter).is_ok(),
+        "Log level setter already set"
+    );
+}
+
+/// Makes `target` log from `level` until the server stops. The target is a
+/// module path like `steel_core::chunk`, or empty for every target without a
+/// level of its own.
+///
+/// # Errors
+/// Returns a description of why the level couldn't be changed.
+pub fn set_level(target: &str, level: LevelFilter) -> Result<(), String> {
+    let setter = LEVEL_SETTER
+        .get()
+        .ok_or_else(|| "Log levels can't be changed".to_owned())?;
+    setter(target, level)
+}
+
 /// Levels of logging in Steel
 pub enum Level {
     /// Standard levels from tracing
@@ -200,6 +200,22 @@ pub fn bias_towards_extreme(noise: f64, factor: f64) -> f64 {
     noise + (PI * noise).sin() * factor / PI
 }
 
+/// Wraps an angle in degrees to the range `[-180, 180)`.
+///
+/// Java reference: `Mth.wrapDegrees(float)`
+#[inline]
+#[must_use]
+pub fn wrap_degrees(degrees: f32) -> f32 {
+    let mut wrapped = degrees % 360.0;
+    if wrapped >= 180.0 {
+        wrapped -= 360.0;
+    }
+    if wrapped < -180.0 {
+        wrapped += 360.0;
+    }
+    wrapped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +246,12 @@ mod tests {
         assert!((lerp(1.0, 10.0, 20.0) - 20.0).abs() < 1e-10);
         assert!((lerp(0.5, 10.0, 20.0) - 15.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_wrap_degrees() {
+        assert!((wrap_degrees(190.0) - -170.0).abs() < 1e-6);
+        assert!((wrap_degrees(-190.0) - 170.0).abs() < 1e-6);
+        assert!((wrap_degrees(180.0) - -180.0).abs() < 1e-6);
+        assert!((wrap_degrees(720.5) - 0.5).abs() < 1e-6);
+    }
 }
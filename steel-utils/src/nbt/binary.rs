@@ -0,0 +1,520 @@
+//! Binary NBT with depth and size limits.
+//!
+//! Two layouts are supported:
+//! - named: tag id, root name and payload, as used on disk (`level.dat`, region files)
+//! - nameless: tag id and payload, as used by the network protocol since 1.20.2
+//!
+//! Strings are encoded as Java's modified UTF-8.
+
+use std::io::Cursor;
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+use super::{
+    NbtError, NbtLimits, TAG_BYTE, TAG_BYTE_ARRAY, TAG_COMPOUND, TAG_DOUBLE, TAG_END, TAG_FLOAT,
+    TAG_INT, TAG_INT_ARRAY, TAG_LIST, TAG_LONG, TAG_LONG_ARRAY, TAG_SHORT, TAG_STRING,
+    list_element_id, tag_id,
+};
+
+/// Reads a named root compound, returning the root name and the compound.
+///
+/// # Errors
+/// - If the input is truncated or malformed, the root is not a compound, or a limit is exceeded.
+pub fn read_named(
+    data: &mut Cursor<&[u8]>,
+    limits: NbtLimits,
+) -> Result<(String, NbtCompound), NbtError> {
+    let mut reader = Reader::new(data, limits);
+    let id = reader.u8()?;
+    if id != TAG_COMPOUND {
+        return Err(NbtError::InvalidRoot(id));
+    }
+    let name = reader.string()?;
+    let compound = reader.compound(0)?;
+    Ok((name, compound))
+}
+
+/// Reads a nameless tag, returning `None` for a lone `TAG_End`.
+///
+/// # Errors
+/// - If the input is truncated or malformed, or a limit is exceeded.
+pub fn read_nameless(
+    data: &mut Cursor<&[u8]>,
+    limits: NbtLimits,
+) -> Result<Option<NbtTag>, NbtError> {
+    let mut reader = Reader::new(data, limits);
+    let id = reader.u8()?;
+    if id == TAG_END {
+        return Ok(None);
+    }
+    reader.payload(id, 0).map(Some)
+}
+
+/// Writes `compound` as a named root compound.
+///
+/// # Errors
+/// - If a string is too long to be encoded.
+pub fn write_named(name: &str, compound: &NbtCompound, out: &mut Vec<u8>) -> Result<(), NbtError> {
+    out.push(TAG_COMPOUND);
+    write_string(name, out)?;
+    write_compound(compound, out)
+}
+
+/// Writes `tag` as a nameless tag.
+///
+/// # Errors
+/// - If a string is too long to be encoded.
+pub fn write_nameless(tag: &NbtTag, out: &mut Vec<u8>) -> Result<(), NbtError> {
+    out.push(tag_id(tag));
+    write_payload(tag, out)
+}
+
+/// Reads tags from a cursor while keeping track of the limits.
+struct Reader<'a, 'b> {
+    data: &'a mut Cursor<&'b [u8]>,
+    limits: NbtLimits,
+    read_bytes: usize,
+}
+
+impl<'a, 'b> Reader<'a, 'b> {
+    const fn new(data: &'a mut Cursor<&'b [u8]>, limits: NbtLimits) -> Self {
+        Self {
+            data,
+            limits,
+            read_bytes: 0,
+        }
+    }
+
+    /// Takes `len` bytes from the input, charging them against the size limit first.
+    fn take(&mut self, len: usize) -> Result<&'b [u8], NbtError> {
+        self.read_bytes = self
+            .read_bytes
+            .checked_add(len)
+            .filter(|&total| total <= self.limits.max_bytes)
+            .ok_or(NbtError::SizeLimit(self.limits.max_bytes))?;
+
+        let buf: &'b [u8] = *self.data.get_ref();
+        let start = usize::try_from(self.data.position()).map_err(|_| NbtError::UnexpectedEof)?;
+        let end = start
+            .checked_add(len)
+            .filter(|&end| end <= buf.len())
+            .ok_or(NbtError::UnexpectedEof)?;
+        self.data.set_position(end as u64);
+        Ok(&buf[start..end])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], NbtError> {
+        let bytes = self.take(N)?;
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, NbtError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn i8(&mut self) -> Result<i8, NbtError> {
+        Ok(i8::from_be_bytes(self.array()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, NbtError> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, NbtError> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, NbtError> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, NbtError> {
+        Ok(f32::from_be_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, NbtError> {
+        Ok(f64::from_be_bytes(self.array()?))
+    }
+
+    /// Reads an `i32` length prefix.
+    fn len(&mut self) -> Result<usize, NbtError> {
+        let len = self.i32()?;
+        usize::try_from(len).map_err(|_| NbtError::NegativeLength(len))
+    }
+
+    /// Charges `len` elements of `size` bytes each before anything is allocated.
+    fn reserve(&self, len: usize, size: usize) -> Result<(), NbtError> {
+        len.checked_mul(size)
+            .and_then(|bytes| bytes.checked_add(self.read_bytes))
+            .filter(|&total| total <= self.limits.max_bytes)
+            .map(|_| ())
+            .ok_or(NbtError::SizeLimit(self.limits.max_bytes))
+    }
+
+    fn string(&mut self) -> Result<String, NbtError> {
+        let len = u16::from_be_bytes(self.array()?);
+        let bytes = self.take(usize::from(len))?;
+        decode_mutf8(bytes).ok_or(NbtError::InvalidString)
+    }
+
+    fn vec<T>(
+        &mut self,
+        size: usize,
+        mut read: impl FnMut(&mut Self) -> Result<T, NbtError>,
+    ) -> Result<Vec<T>, NbtError> {
+        let len = self.len()?;
+        self.reserve(len, size)?;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push(read(self)?);
+        }
+        Ok(values)
+    }
+
+    fn byte_array(&mut self) -> Result<Vec<u8>, NbtError> {
+        let len = self.len()?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn int_array(&mut self) -> Result<Vec<i32>, NbtError> {
+        self.vec(4, Self::i32)
+    }
+
+    fn long_array(&mut self) -> Result<Vec<i64>, NbtError> {
+        self.vec(8, Self::i64)
+    }
+
+    const fn check_depth(&self, depth: usize) -> Result<(), NbtError> {
+        if depth > self.limits.max_depth {
+            return Err(NbtError::DepthLimit(self.limits.max_depth));
+        }
+        Ok(())
+    }
+
+    fn compound(&mut self, depth: usize) -> Result<NbtCompound, NbtError> {
+        self.check_depth(depth)?;
+        let mut compound = NbtCompound::new();
+        loop {
+            let id = self.u8()?;
+            if id == TAG_END {
+                return Ok(compound);
+            }
+            let name = self.string()?;
+            let value = self.payload(id, depth + 1)?;
+            compound.insert(name, value);
+        }
+    }
+
+    fn list(&mut self, depth: usize) -> Result<NbtList, NbtError> {
+        self.check_depth(depth)?;
+        let id = self.u8()?;
+        Ok(match id {
+            TAG_END => {
+                let len = self.len()?;
+                if len > 0 {
+                    return Err(NbtError::InvalidTagType(TAG_END));
+                }
+                NbtList::Empty
+            }
+            TAG_BYTE => NbtList::Byte(self.vec(1, Self::i8)?),
+            TAG_SHORT => NbtList::Short(self.vec(2, Self::i16)?),
+            TAG_INT => NbtList::Int(self.vec(4, Self::i32)?),
+            TAG_LONG => NbtList::Long(self.vec(8, Self::i64)?),
+            TAG_FLOAT => NbtList::Float(self.vec(4, Self::f32)?),
+            TAG_DOUBLE => NbtList::Double(self.vec(8, Self::f64)?),
+            TAG_BYTE_ARRAY => NbtList::ByteArray(self.vec(4, Self::byte_array)?),
+            TAG_STRING => NbtList::String(self.vec(2, |reader| reader.string().map(Into::into))?),
+            TAG_LIST => NbtList::List(self.vec(5, |reader| reader.list(depth + 1))?),
+            TAG_COMPOUND => NbtList::Compound(self.vec(1, |reader| reader.compound(depth + 1))?),
+            TAG_INT_ARRAY => NbtList::IntArray(self.vec(4, Self::int_array)?),
+            TAG_LONG_ARRAY => NbtList::LongArray(self.vec(4, Self::long_array)?),
+            other => return Err(NbtError::InvalidTagType(other)),
+        })
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> Result<NbtTag, NbtError> {
+        Ok(match id {
+            TAG_BYTE => NbtTag::Byte(self.i8()?),
+            TAG_SHORT => NbtTag::Short(self.i16()?),
+            TAG_INT => NbtTag::Int(self.i32()?),
+            TAG_LONG => NbtTag::Long(self.i64()?),
+            TAG_FLOAT => NbtTag::Float(self.f32()?),
+            TAG_DOUBLE => NbtTag::Double(self.f64()?),
+            TAG_BYTE_ARRAY => NbtTag::ByteArray(self.byte_array()?),
+            TAG_STRING => NbtTag::String(self.string()?.into()),
+            TAG_LIST => NbtTag::List(self.list(depth)?),
+            TAG_COMPOUND => NbtTag::Compound(self.compound(depth)?),
+            TAG_INT_ARRAY => NbtTag::IntArray(self.int_array()?),
+            TAG_LONG_ARRAY => NbtTag::LongArray(self.long_array()?),
+            other => return Err(NbtError::InvalidTagType(other)),
+        })
+    }
+}
+
+fn write_len(len: usize, out: &mut Vec<u8>) {
+    // Lengths above i32::MAX cannot exist in memory for any real tag
+    out.extend_from_slice(&i32::try_from(len).unwrap_or(i32::MAX).to_be_bytes());
+}
+
+fn write_string(value: &str, out: &mut Vec<u8>) -> Result<(), NbtError> {
+    let mut encoded = Vec::with_capacity(value.len());
+    encode_mutf8(value, &mut encoded);
+    let len = u16::try_from(encoded.len()).map_err(|_| NbtError::StringTooLong(encoded.len()))?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(&encoded);
+    Ok(())
+}
+
+fn write_compound(compound: &NbtCompound, out: &mut Vec<u8>) -> Result<(), NbtError> {
+    for (name, value) in compound.iter() {
+        out.push(tag_id(value));
+        write_string(&name.to_str(), out)?;
+        write_payload(value, out)?;
+    }
+    out.push(TAG_END);
+    Ok(())
+}
+
+fn write_list(list: &NbtList, out: &mut Vec<u8>) -> Result<(), NbtError> {
+    out.push(list_element_id(list));
+    match list {
+        NbtList::Empty => write_len(0, out),
+        NbtList::Byte(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtList::Short(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtList::Int(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtList::Long(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtList::Float(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtList::Double(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtList::ByteArray(v) => {
+            write_len(v.len(), out);
+            for array in v {
+                write_len(array.len(), out);
+                out.extend_from_slice(array);
+            }
+        }
+        NbtList::String(v) => {
+            write_len(v.len(), out);
+            for string in v {
+                write_string(&string.to_str(), out)?;
+            }
+        }
+        NbtList::List(v) => {
+            write_len(v.len(), out);
+            for inner in v {
+                write_list(inner, out)?;
+            }
+        }
+        NbtList::Compound(v) => {
+            write_len(v.len(), out);
+            for compound in v {
+                write_compound(compound, out)?;
+            }
+        }
+        NbtList::IntArray(v) => {
+            write_len(v.len(), out);
+            for array in v {
+                write_len(array.len(), out);
+                out.extend(array.iter().flat_map(|x| x.to_be_bytes()));
+            }
+        }
+        NbtList::LongArray(v) => {
+            write_len(v.len(), out);
+            for array in v {
+                write_len(array.len(), out);
+                out.extend(array.iter().flat_map(|x| x.to_be_bytes()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_payload(tag: &NbtTag, out: &mut Vec<u8>) -> Result<(), NbtError> {
+    match tag {
+        NbtTag::Byte(v) => out.extend_from_slice(&v.to_be_bytes()),
+        NbtTag::Short(v) => out.extend_from_slice(&v.to_be_bytes()),
+        NbtTag::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+        NbtTag::Long(v) => out.extend_from_slice(&v.to_be_bytes()),
+        NbtTag::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+        NbtTag::Double(v) => out.extend_from_slice(&v.to_be_bytes()),
+        NbtTag::ByteArray(v) => {
+            write_len(v.len(), out);
+            out.extend_from_slice(v);
+        }
+        NbtTag::String(v) => write_string(&v.to_str(), out)?,
+        NbtTag::List(v) => write_list(v, out)?,
+        NbtTag::Compound(v) => write_compound(v, out)?,
+        NbtTag::IntArray(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+        NbtTag::LongArray(v) => {
+            write_len(v.len(), out);
+            out.extend(v.iter().flat_map(|x| x.to_be_bytes()));
+        }
+    }
+    Ok(())
+}
+
+/// Decodes Java's modified UTF-8: `\0` is two bytes and supplementary characters
+/// are encoded as surrogate pairs of three bytes each.
+fn decode_mutf8(bytes: &[u8]) -> Option<String> {
+    if bytes.iter().all(|&b| b != 0 && b.is_ascii()) {
+        return String::from_utf8(bytes.to_vec()).ok();
+    }
+
+    let continuation = |index: usize| {
+        bytes
+            .get(index)
+            .filter(|&&b| b & 0xC0 == 0x80)
+            .map(|&b| u16::from(b & 0x3F))
+    };
+
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while let Some(&b) = bytes.get(i) {
+        if b & 0x80 == 0 {
+            units.push(u16::from(b));
+            i += 1;
+        } else if b & 0xE0 == 0xC0 {
+            units.push((u16::from(b & 0x1F) << 6) | continuation(i + 1)?);
+            i += 2;
+        } else if b & 0xF0 == 0xE0 {
+            units.push(
+                (u16::from(b & 0x0F) << 12) | (continuation(i + 1)? << 6) | continuation(i + 2)?,
+            );
+            i += 3;
+        } else {
+            return None;
+        }
+    }
+    String::from_utf16(&units).ok()
+}
+
+/// Encodes `value` as Java's modified UTF-8.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "every branch masks the unit to the bits it encodes"
+)]
+fn encode_mutf8(value: &str, out: &mut Vec<u8>) {
+    for unit in value.encode_utf16() {
+        match unit {
+            0x01..=0x7F => out.push(unit as u8),
+            0x00 | 0x80..=0x7FF => {
+                out.push(0xC0 | (unit >> 6) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                out.push(0xE0 | (unit >> 12) as u8);
+                out.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                out.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> NbtCompound {
+        let mut inner = NbtCompound::new();
+        inner.insert(
+            "name",
+            NbtTag::String("snow\u{0}man \u{2603} \u{1F600}".into()),
+        );
+        inner.insert("longs", NbtTag::LongArray(vec![i64::MIN, 0, i64::MAX]));
+
+        let mut root = NbtCompound::new();
+        root.insert("byte", NbtTag::Byte(-1));
+        root.insert("double", NbtTag::Double(0.5));
+        root.insert("bytes", NbtTag::ByteArray(vec![1, 2, 3]));
+        root.insert("list", NbtTag::List(NbtList::Int(vec![1, 2, 3])));
+        root.insert("empty", NbtTag::List(NbtList::Empty));
+        root.insert("inner", NbtTag::Compound(inner));
+        root
+    }
+
+    #[test]
+    fn named_round_trip() {
+        let mut bytes = Vec::new();
+        write_named("root", &sample(), &mut bytes).expect("write");
+
+        let (name, compound) =
+            read_named(&mut Cursor::new(&bytes), NbtLimits::UNLIMITED).expect("read");
+
+        assert_eq!(name, "root");
+        assert_eq!(compound, sample());
+    }
+
+    #[test]
+    fn nameless_round_trip() {
+        let tag = NbtTag::Compound(sample());
+        let mut bytes = Vec::new();
+        write_nameless(&tag, &mut bytes).expect("write");
+
+        let read = read_nameless(&mut Cursor::new(&bytes), NbtLimits::NETWORK).expect("read");
+
+        assert_eq!(read, Some(tag));
+        assert_eq!(
+            read_nameless(&mut Cursor::new(&[TAG_END][..]), NbtLimits::NETWORK).expect("read"),
+            None
+        );
+    }
+
+    #[test]
+    fn depth_limit_is_enforced() {
+        let mut bytes = vec![TAG_COMPOUND];
+        for _ in 0..=NbtLimits::MAX_DEPTH {
+            bytes.extend_from_slice(&[TAG_COMPOUND, 0, 0]);
+        }
+
+        let result = read_nameless(&mut Cursor::new(&bytes), NbtLimits::NETWORK);
+
+        assert!(matches!(result, Err(NbtError::DepthLimit(_))));
+    }
+
+    #[test]
+    fn size_limit_is_checked_before_allocating() {
+        let mut bytes = vec![TAG_LONG_ARRAY];
+        bytes.extend_from_slice(&i32::MAX.to_be_bytes());
+        let limits = NbtLimits {
+            max_depth: NbtLimits::MAX_DEPTH,
+            max_bytes: 1024,
+        };
+
+        let result = read_nameless(&mut Cursor::new(&bytes), limits);
+
+        assert!(matches!(result, Err(NbtError::SizeLimit(1024))));
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let mut bytes = Vec::new();
+        write_named("", &sample(), &mut bytes).expect("write");
+        bytes.truncate(bytes.len() / 2);
+
+        let result = read_named(&mut Cursor::new(&bytes), NbtLimits::UNLIMITED);
+
+        assert!(matches!(result, Err(NbtError::UnexpectedEof)));
+    }
+}
@@ -0,0 +1,332 @@
+//! NBT codecs on top of simdnbt's owned tag model.
+//!
+//! simdnbt is used as the tag model everywhere in Steel. This module adds the
+//! pieces it does not cover:
+//! - [`binary`]: binary read/write with vanilla's depth and size limits, for
+//!   untrusted input (network, region files)
+//! - [`snbt`]: parsing and printing of stringified NBT, as used by commands
+//! - [`path`]: NBT paths like `foo.bar[0]`, as used by `/data`
+//! - [`to_nbt`] / [`from_nbt`]: converting any serde type to and from tags
+
+pub mod binary;
+pub mod path;
+mod serde_bridge;
+pub mod snbt;
+
+use std::io::{self, Cursor};
+
+use simdnbt::borrow::{BaseNbtCompound, read_compound as read_borrowed_compound};
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+use thiserror::Error;
+
+pub use path::{NbtPath, NbtPathError, merge_compound};
+pub use serde_bridge::{NbtSerdeError, from_nbt, to_nbt};
+pub use snbt::{SnbtError, parse_snbt, to_snbt};
+
+/// Tag id of `TAG_End`.
+pub const TAG_END: u8 = 0;
+/// Tag id of `TAG_Byte`.
+pub const TAG_BYTE: u8 = 1;
+/// Tag id of `TAG_Short`.
+pub const TAG_SHORT: u8 = 2;
+/// Tag id of `TAG_Int`.
+pub const TAG_INT: u8 = 3;
+/// Tag id of `TAG_Long`.
+pub const TAG_LONG: u8 = 4;
+/// Tag id of `TAG_Float`.
+pub const TAG_FLOAT: u8 = 5;
+/// Tag id of `TAG_Double`.
+pub const TAG_DOUBLE: u8 = 6;
+/// Tag id of `TAG_Byte_Array`.
+pub const TAG_BYTE_ARRAY: u8 = 7;
+/// Tag id of `TAG_String`.
+pub const TAG_STRING: u8 = 8;
+/// Tag id of `TAG_List`.
+pub const TAG_LIST: u8 = 9;
+/// Tag id of `TAG_Compound`.
+pub const TAG_COMPOUND: u8 = 10;
+/// Tag id of `TAG_Int_Array`.
+pub const TAG_INT_ARRAY: u8 = 11;
+/// Tag id of `TAG_Long_Array`.
+pub const TAG_LONG_ARRAY: u8 = 12;
+
+/// Limits applied while reading binary NBT, mirroring vanilla's `NbtAccounter`.
+///
+/// Unlike vanilla, the size quota counts encoded bytes rather than estimated
+/// heap usage, which is stricter for small tags and simpler to reason about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NbtLimits {
+    /// Maximum nesting of lists and compounds.
+    pub max_depth: usize,
+    /// Maximum number of bytes a single tag may take up.
+    pub max_bytes: usize,
+}
+
+impl NbtLimits {
+    /// Vanilla's maximum nesting depth.
+    pub const MAX_DEPTH: usize = 512;
+
+    /// Limits for NBT received from clients (2 MiB, like vanilla's packet quota).
+    pub const NETWORK: Self = Self {
+        max_depth: Self::MAX_DEPTH,
+        max_bytes: 2 * 1024 * 1024,
+    };
+
+    /// Limits for trusted data such as chunks and player data: only the depth is bounded.
+    pub const UNLIMITED: Self = Self {
+        max_depth: Self::MAX_DEPTH,
+        max_bytes: usize::MAX,
+    };
+}
+
+impl Default for NbtLimits {
+    fn default() -> Self {
+        Self::NETWORK
+    }
+}
+
+/// An error that can occur while reading or writing binary NBT.
+#[derive(Error, Debug)]
+pub enum NbtError {
+    /// The input ended before the tag was fully read.
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    /// A tag id outside of `0..=12` was found, or `TAG_End` where it is not allowed.
+    #[error("invalid tag type {0}")]
+    InvalidTagType(u8),
+    /// The root of a named tag was not a compound.
+    #[error("root tag must be a compound, found type {0}")]
+    InvalidRoot(u8),
+    /// Lists and compounds were nested deeper than allowed.
+    #[error("tried to read NBT tag with too high complexity, depth > {0}")]
+    DepthLimit(usize),
+    /// The tag was larger than allowed.
+    #[error("tried to read NBT tag that was too big; tried to allocate more than {0} bytes")]
+    SizeLimit(usize),
+    /// A length prefix was negative.
+    #[error("negative length {0}")]
+    NegativeLength(i32),
+    /// A string was not valid modified UTF-8, or contained unpaired surrogates.
+    #[error("invalid modified utf-8 string")]
+    InvalidString,
+    /// A string was too long to be written (more than 65535 encoded bytes).
+    #[error("string of {0} bytes is too long to encode")]
+    StringTooLong(usize),
+    /// An underlying I/O error.
+    #[error("io error: {0}")]
+    Io(io::Error),
+}
+
+impl From<io::Error> for NbtError {
+    fn from(value: io::Error) -> Self {
+        if value.kind() == io::ErrorKind::UnexpectedEof {
+            Self::UnexpectedEof
+        } else {
+            Self::Io(value)
+        }
+    }
+}
+
+impl From<NbtError> for io::Error {
+    fn from(value: NbtError) -> Self {
+        match value {
+            NbtError::UnexpectedEof => Self::from(io::ErrorKind::UnexpectedEof),
+            NbtError::Io(err) => err,
+            other => Self::new(io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+/// Runs `f` with a borrowed copy of `nbt`, for APIs taking simdnbt's borrowed
+/// compounds such as `load_additional`.
+///
+/// # Errors
+/// Returns an error if the written compound can't be read back.
+pub fn with_borrowed<R>(
+    nbt: &NbtCompound,
+    f: impl FnOnce(&BaseNbtCompound<'_>) -> R,
+) -> Result<R, simdnbt::Error> {
+    let mut bytes = Vec::new();
+    nbt.write(&mut bytes);
+    let base = read_borrowed_compound(&mut Cursor::new(&bytes))?;
+    Ok(f(&base))
+}
+
+/// Returns the tag id of `tag`.
+#[must_use]
+pub const fn tag_id(tag: &NbtTag) -> u8 {
+    match tag {
+        NbtTag::Byte(_) => TAG_BYTE,
+        NbtTag::Short(_) => TAG_SHORT,
+        NbtTag::Int(_) => TAG_INT,
+        NbtTag::Long(_) => TAG_LONG,
+        NbtTag::Float(_) => TAG_FLOAT,
+        NbtTag::Double(_) => TAG_DOUBLE,
+        NbtTag::ByteArray(_) => TAG_BYTE_ARRAY,
+        NbtTag::String(_) => TAG_STRING,
+        NbtTag::List(_) => TAG_LIST,
+        NbtTag::Compound(_) => TAG_COMPOUND,
+        NbtTag::IntArray(_) => TAG_INT_ARRAY,
+        NbtTag::LongArray(_) => TAG_LONG_ARRAY,
+    }
+}
+
+/// Returns the tag id of the elements of `list`, `TAG_End` for an empty list.
+#[must_use]
+pub const fn list_element_id(list: &NbtList) -> u8 {
+    match list {
+        NbtList::Empty => TAG_END,
+        NbtList::Byte(_) => TAG_BYTE,
+        NbtList::Short(_) => TAG_SHORT,
+        NbtList::Int(_) => TAG_INT,
+        NbtList::Long(_) => TAG_LONG,
+        NbtList::Float(_) => TAG_FLOAT,
+        NbtList::Double(_) => TAG_DOUBLE,
+        NbtList::ByteArray(_) => TAG_BYTE_ARRAY,
+        NbtList::String(_) => TAG_STRING,
+        NbtList::List(_) => TAG_LIST,
+        NbtList::Compound(_) => TAG_COMPOUND,
+        NbtList::IntArray(_) => TAG_INT_ARRAY,
+        NbtList::LongArray(_) => TAG_LONG_ARRAY,
+    }
+}
+
+/// Returns the elements of `list` as individual tags.
+///
+/// Compounds that only wrap a single element under the empty key (see
+/// [`list_from_tags`]) are unwrapped again.
+#[must_use]
+pub fn list_to_tags(list: &NbtList) -> Vec<NbtTag> {
+    match list {
+        NbtList::Empty => Vec::new(),
+        NbtList::Byte(v) => v.iter().map(|&x| NbtTag::Byte(x)).collect(),
+        NbtList::Short(v) => v.iter().map(|&x| NbtTag::Short(x)).collect(),
+        NbtList::Int(v) => v.iter().map(|&x| NbtTag::Int(x)).collect(),
+        NbtList::Long(v) => v.iter().map(|&x| NbtTag::Long(x)).collect(),
+        NbtList::Float(v) => v.iter().map(|&x| NbtTag::Float(x)).collect(),
+        NbtList::Double(v) => v.iter().map(|&x| NbtTag::Double(x)).collect(),
+        NbtList::ByteArray(v) => v.iter().cloned().map(NbtTag::ByteArray).collect(),
+        NbtList::String(v) => v.iter().cloned().map(NbtTag::String).collect(),
+        NbtList::List(v) => v.iter().cloned().map(NbtTag::List).collect(),
+        NbtList::Compound(v) => v
+            .iter()
+            .map(|compound| match unwrap_list_element(compound) {
+                Some(inner) => inner.clone(),
+                None => NbtTag::Compound(compound.clone()),
+            })
+            .collect(),
+        NbtList::IntArray(v) => v.iter().cloned().map(NbtTag::IntArray).collect(),
+        NbtList::LongArray(v) => v.iter().cloned().map(NbtTag::LongArray).collect(),
+    }
+}
+
+/// Builds a list from individual tags.
+///
+/// Lists of mixed types are stored like vanilla does since 1.21.5: as a list of
+/// compounds, where every element that is not already a compound is wrapped as
+/// `{"": element}`.
+#[must_use]
+pub fn list_from_tags(tags: Vec<NbtTag>) -> NbtList {
+    let Some(first) = tags.first() else {
+        return NbtList::Empty;
+    };
+    let first_id = tag_id(first);
+    if tags.iter().any(|tag| tag_id(tag) != first_id) {
+        return NbtList::Compound(tags.into_iter().map(wrap_list_element).collect());
+    }
+
+    macro_rules! collect {
+        ($variant:ident) => {
+            NbtList::$variant(
+                tags.into_iter()
+                    .filter_map(|tag| match tag {
+                        NbtTag::$variant(value) => Some(value),
+                        _ => None,
+                    })
+                    .collect(),
+            )
+        };
+    }
+
+    match first_id {
+        TAG_BYTE => collect!(Byte),
+        TAG_SHORT => collect!(Short),
+        TAG_INT => collect!(Int),
+        TAG_LONG => collect!(Long),
+        TAG_FLOAT => collect!(Float),
+        TAG_DOUBLE => collect!(Double),
+        TAG_BYTE_ARRAY => collect!(ByteArray),
+        TAG_STRING => collect!(String),
+        TAG_LIST => collect!(List),
+        TAG_INT_ARRAY => collect!(IntArray),
+        TAG_LONG_ARRAY => collect!(LongArray),
+        // Wrapper-shaped compounds have to be wrapped again to survive a round trip
+        _ => NbtList::Compound(tags.into_iter().map(wrap_list_element).collect()),
+    }
+}
+
+/// Wraps an element of a mixed list in a compound, unless it already is a compound
+/// that cannot be mistaken for a wrapper.
+fn wrap_list_element(tag: NbtTag) -> NbtCompound {
+    match tag {
+        NbtTag::Compound(compound) if unwrap_list_element(&compound).is_none() => compound,
+        other => {
+            let mut wrapper = NbtCompound::new();
+            wrapper.insert("", other);
+            wrapper
+        }
+    }
+}
+
+/// Returns the wrapped element if `compound` is a `{"": element}` wrapper.
+fn unwrap_list_element(compound: &NbtCompound) -> Option<&NbtTag> {
+    let mut entries = compound.iter();
+    let (key, value) = entries.next()?;
+    (entries.next().is_none() && key.to_str().is_empty()).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_lists_are_wrapped_and_unwrapped() {
+        let mut compound = NbtCompound::new();
+        compound.insert("a", NbtTag::Int(1));
+        let tags = vec![
+            NbtTag::Int(1),
+            NbtTag::String("two".into()),
+            NbtTag::Compound(compound),
+        ];
+
+        let list = list_from_tags(tags.clone());
+
+        assert!(matches!(&list, NbtList::Compound(elements) if elements.len() == 3));
+        assert_eq!(list_to_tags(&list), tags);
+    }
+
+    #[test]
+    fn borrowed_copy_has_the_same_tags() {
+        let mut compound = NbtCompound::new();
+        compound.insert("id", NbtTag::String("minecraft:chest".into()));
+        compound.insert("x", NbtTag::Int(3));
+
+        let (id, x) = with_borrowed(&compound, |base| {
+            (
+                base.string("id").map(|id| id.to_str().into_owned()),
+                base.int("x"),
+            )
+        })
+        .expect("written compound reads back");
+
+        assert_eq!(id.as_deref(), Some("minecraft:chest"));
+        assert_eq!(x, Some(3));
+    }
+
+    #[test]
+    fn homogeneous_lists_keep_their_type() {
+        let list = list_from_tags(vec![NbtTag::Short(1), NbtTag::Short(2)]);
+        assert_eq!(list, NbtList::Short(vec![1, 2]));
+        assert_eq!(list_from_tags(Vec::new()), NbtList::Empty);
+    }
+}
@@ -0,0 +1,806 @@
+//! NBT paths such as `foo.bar[0].baz`, mirroring vanilla's `NbtPathArgument.NbtPath`.
+//!
+//! Supported nodes:
+//! - `{...}` at the start: the root, if it matches the compound pattern
+//! - `name` or `"quoted name"`: a child of a compound
+//! - `name{...}`: a child of a compound, if it matches the compound pattern
+//! - `[]`: every element of a list or array
+//! - `[i]`: the element at index `i`, counting from the end if negative
+//! - `[{...}]`: every element of a list matching the compound pattern
+//!
+//! Lists are stored as typed vectors by simdnbt, so operations that change
+//! elements unpack a list into individual tags and pack it again afterwards.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+use super::snbt::{parse_snbt_prefix, to_snbt};
+use super::{list_from_tags, list_to_tags};
+
+/// An error that occurred while parsing or applying an NBT path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NbtPathError {
+    /// The path could not be parsed.
+    Invalid {
+        /// The byte offset into the input at which the error occurred.
+        position: usize,
+        /// What went wrong.
+        message: String,
+    },
+    /// The path did not match any element.
+    NothingFound(String),
+    /// A list was expected at the target, but something else was found.
+    ExpectedList(String),
+    /// A compound was expected at the target, but something else was found.
+    ExpectedObject(String),
+    /// The index to insert at is outside of the target list.
+    InvalidIndex(i32),
+}
+
+impl Display for NbtPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { position, message } => write!(f, "{message} at position {position}"),
+            Self::NothingFound(path) => write!(f, "Found no elements matching {path}"),
+            Self::ExpectedList(found) => write!(f, "Expected a list: got {found}"),
+            Self::ExpectedObject(found) => write!(f, "Expected an object: got {found}"),
+            Self::InvalidIndex(index) => write!(f, "Invalid list index: {index}"),
+        }
+    }
+}
+
+impl Error for NbtPathError {}
+
+/// A single step of an [`NbtPath`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtPathNode {
+    /// `{...}` at the start of a path.
+    MatchRootObject(NbtCompound),
+    /// `name`
+    CompoundChild(String),
+    /// `name{...}`
+    MatchChild(String, NbtCompound),
+    /// `[]`
+    AllElements,
+    /// `[i]`
+    IndexedElement(i32),
+    /// `[{...}]`
+    MatchElement(NbtCompound),
+}
+
+/// A parsed NBT path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NbtPath {
+    original: String,
+    nodes: Vec<NbtPathNode>,
+}
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+impl NbtPath {
+    /// Parses a complete path.
+    ///
+    /// # Errors
+    /// - If the input is not a valid path or has trailing data.
+    pub fn parse(input: &str) -> Result<Self, NbtPathError> {
+        let (path, end) = Self::parse_prefix(input)?;
+        if end != input.len() {
+            return Err(invalid(end, "Unexpected trailing data"));
+        }
+        Ok(path)
+    }
+
+    /// Parses a path from the start of `input`, stopping at the first space.
+    ///
+    /// Returns the path and the byte offset right after it.
+    ///
+    /// # Errors
+    /// - If the input does not start with a valid path.
+    pub fn parse_prefix(input: &str) -> Result<(Self, usize), NbtPathError> {
+        let bytes = input.as_bytes();
+        let mut nodes = Vec::new();
+        let mut pos = 0;
+        let mut root = true;
+
+        while pos < bytes.len() && bytes[pos] != b' ' {
+            let node = match bytes[pos] {
+                b'{' if root => {
+                    let (pattern, len) = parse_pattern(&input[pos..], pos)?;
+                    pos += len;
+                    NbtPathNode::MatchRootObject(pattern)
+                }
+                b'[' => {
+                    pos += 1;
+                    let node = match bytes.get(pos) {
+                        Some(b']') => NbtPathNode::AllElements,
+                        Some(b'{') => {
+                            let (pattern, len) = parse_pattern(&input[pos..], pos)?;
+                            pos += len;
+                            NbtPathNode::MatchElement(pattern)
+                        }
+                        _ => {
+                            let start = pos;
+                            while bytes
+                                .get(pos)
+                                .is_some_and(|c| c.is_ascii_digit() || *c == b'-')
+                            {
+                                pos += 1;
+                            }
+                            let index = input[start..pos]
+                                .parse()
+                                .map_err(|_| invalid(start, "Invalid NBT path element"))?;
+                            NbtPathNode::IndexedElement(index)
+                        }
+                    };
+                    if bytes.get(pos) != Some(&b']') {
+                        return Err(invalid(pos, "Expected ']'"));
+                    }
+                    pos += 1;
+                    node
+                }
+                _ => {
+                    let (name, len) = parse_key(&input[pos..], pos)?;
+                    pos += len;
+                    if bytes.get(pos) == Some(&b'{') {
+                        let (pattern, len) = parse_pattern(&input[pos..], pos)?;
+                        pos += len;
+                        NbtPathNode::MatchChild(name, pattern)
+                    } else {
+                        NbtPathNode::CompoundChild(name)
+                    }
+                }
+            };
+            nodes.push(node);
+            root = false;
+
+            // Nodes are separated by dots, except before an index
+            match bytes.get(pos) {
+                Some(b'.') => {
+                    pos += 1;
+                    if matches!(bytes.get(pos), None | Some(b' ')) {
+                        return Err(invalid(pos, "Expected path element"));
+                    }
+                }
+                None | Some(b' ' | b'[' | b'{') => {}
+                Some(_) => return Err(invalid(pos, "Invalid NBT path element")),
+            }
+        }
+
+        if nodes.is_empty() {
+            return Err(invalid(0, "Invalid NBT path element"));
+        }
+        Ok((
+            Self {
+                original: input[..pos].to_owned(),
+                nodes,
+            },
+            pos,
+        ))
+    }
+
+    /// Returns the nodes of the path.
+    #[must_use]
+    pub const fn nodes(&self) -> &[NbtPathNode] {
+        &self.nodes
+    }
+
+    /// Returns copies of every tag matched by the path.
+    ///
+    /// # Errors
+    /// - [`NbtPathError::NothingFound`] if nothing matched.
+    pub fn get(&self, root: &NbtTag) -> Result<Vec<NbtTag>, NbtPathError> {
+        let mut current = vec![root.clone()];
+        for node in &self.nodes {
+            current = current.iter().flat_map(|tag| node.get(tag)).collect();
+            if current.is_empty() {
+                return Err(self.nothing_found());
+            }
+        }
+        Ok(current)
+    }
+
+    /// Returns the number of tags matched by the path.
+    #[must_use]
+    pub fn count_matching(&self, root: &NbtTag) -> usize {
+        self.get(root).map_or(0, |tags| tags.len())
+    }
+
+    /// Replaces every matched tag with `value`, creating missing parents.
+    ///
+    /// Returns the number of tags that changed.
+    ///
+    /// # Errors
+    /// - [`NbtPathError::NothingFound`] if a parent could not be created.
+    pub fn set(&self, root: &mut NbtTag, value: &NbtTag) -> Result<usize, NbtPathError> {
+        let (last, parents) = self.split_last();
+        let mut found = false;
+        let changed = self.visit_parents(root, parents, true, &mut |parent| {
+            found = true;
+            last.set(parent, value)
+        });
+        if found {
+            Ok(changed)
+        } else {
+            Err(self.nothing_found())
+        }
+    }
+
+    /// Removes every matched tag from its parent.
+    ///
+    /// Returns the number of removed tags.
+    pub fn remove(&self, root: &mut NbtTag) -> usize {
+        let (last, parents) = self.split_last();
+        self.visit_parents(root, parents, false, &mut |parent| last.remove(parent))
+    }
+
+    /// Inserts `values` into every matched list at `index`, creating missing lists.
+    ///
+    /// A negative index counts from the end, so `-1` appends. Returns the number
+    /// of inserted values.
+    ///
+    /// # Errors
+    /// - [`NbtPathError::ExpectedList`] if a matched tag is not a list.
+    /// - [`NbtPathError::InvalidIndex`] if the index is outside of a matched list.
+    pub fn insert(
+        &self,
+        index: i32,
+        root: &mut NbtTag,
+        values: &[NbtTag],
+    ) -> Result<usize, NbtPathError> {
+        self.modify_targets(root, NbtTag::List(NbtList::Empty), |target| {
+            let Some(mut elements) = collection_elements(target) else {
+                return Err(NbtPathError::ExpectedList(to_snbt(target)));
+            };
+            let len = i32::try_from(elements.len()).unwrap_or(i32::MAX);
+            let at = if index < 0 { len + index + 1 } else { index };
+            let Some(at) = usize::try_from(at).ok().filter(|&at| at <= elements.len()) else {
+                return Err(NbtPathError::InvalidIndex(index));
+            };
+            elements.splice(at..at, values.iter().cloned());
+            if set_collection_elements(target, elements) {
+                Ok(values.len())
+            } else {
+                Ok(0)
+            }
+        })
+    }
+
+    /// Merges `source` into every matched compound, creating missing compounds.
+    ///
+    /// Returns the number of compounds that changed.
+    ///
+    /// # Errors
+    /// - [`NbtPathError::ExpectedObject`] if a matched tag is not a compound.
+    pub fn merge(&self, root: &mut NbtTag, source: &NbtCompound) -> Result<usize, NbtPathError> {
+        self.modify_targets(root, NbtTag::Compound(NbtCompound::new()), |target| {
+            let NbtTag::Compound(compound) = target else {
+                return Err(NbtPathError::ExpectedObject(to_snbt(target)));
+            };
+            let before = compound.clone();
+            merge_compound(compound, source);
+            Ok(usize::from(*compound != before))
+        })
+    }
+
+    /// Applies `f` to every matched tag, creating missing tags as `default`.
+    fn modify_targets(
+        &self,
+        root: &mut NbtTag,
+        default: NbtTag,
+        mut f: impl FnMut(&mut NbtTag) -> Result<usize, NbtPathError>,
+    ) -> Result<usize, NbtPathError> {
+        let (last, parents) = self.split_last();
+        let mut result = Ok(0);
+        let mut found = false;
+        self.visit_parents(root, parents, true, &mut |parent| {
+            last.visit(parent, Some(&default), &mut |target| {
+                found = true;
+                if result.is_err() {
+                    return 0;
+                }
+                match f(target) {
+                    Ok(count) => {
+                        if let Ok(total) = &mut result {
+                            *total += count;
+                        }
+                        count
+                    }
+                    Err(err) => {
+                        result = Err(err);
+                        0
+                    }
+                }
+            })
+        });
+        if !found {
+            return Err(self.nothing_found());
+        }
+        result
+    }
+
+    /// Calls `f` on every tag matched by `parents`, creating missing ones if `create` is set.
+    fn visit_parents(
+        &self,
+        root: &mut NbtTag,
+        parents: &[NbtPathNode],
+        create: bool,
+        f: &mut dyn FnMut(&mut NbtTag) -> usize,
+    ) -> usize {
+        let Some((node, rest)) = parents.split_first() else {
+            return f(root);
+        };
+        // Missing tags are created with the shape the following node expects
+        let next = rest.first().unwrap_or_else(|| self.split_last().0);
+        let default = create.then(|| next.preferred_parent());
+        node.visit(root, default.as_ref(), &mut |child| {
+            self.visit_parents(child, rest, create, &mut *f)
+        })
+    }
+
+    fn split_last(&self) -> (&NbtPathNode, &[NbtPathNode]) {
+        // Parsing guarantees at least one node
+        match self.nodes.split_last() {
+            Some(split) => split,
+            None => unreachable!("NBT paths always have at least one node"),
+        }
+    }
+
+    fn nothing_found(&self) -> NbtPathError {
+        NbtPathError::NothingFound(self.original.clone())
+    }
+}
+
+impl NbtPathNode {
+    /// Returns copies of the children of `tag` matched by this node.
+    fn get(&self, tag: &NbtTag) -> Vec<NbtTag> {
+        match (self, tag) {
+            (Self::MatchRootObject(pattern), NbtTag::Compound(compound))
+                if compound_matches(pattern, compound) =>
+            {
+                vec![tag.clone()]
+            }
+            (Self::CompoundChild(name), NbtTag::Compound(compound)) => {
+                compound.get(name).cloned().into_iter().collect()
+            }
+            (Self::MatchChild(name, pattern), NbtTag::Compound(compound)) => compound
+                .get(name)
+                .filter(|child| tag_matches(pattern, child))
+                .cloned()
+                .into_iter()
+                .collect(),
+            (Self::AllElements, _) => collection_elements(tag).unwrap_or_default(),
+            (Self::IndexedElement(index), _) => collection_elements(tag)
+                .and_then(|mut elements| {
+                    resolve_index(*index, elements.len()).map(|i| elements.swap_remove(i))
+                })
+                .into_iter()
+                .collect(),
+            (Self::MatchElement(pattern), NbtTag::List(list)) => list_to_tags(list)
+                .into_iter()
+                .filter(|element| tag_matches(pattern, element))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The tag to create when a parent of this node is missing.
+    fn preferred_parent(&self) -> NbtTag {
+        match self {
+            Self::MatchRootObject(_) | Self::CompoundChild(_) | Self::MatchChild(..) => {
+                NbtTag::Compound(NbtCompound::new())
+            }
+            Self::AllElements | Self::IndexedElement(_) | Self::MatchElement(_) => {
+                NbtTag::List(NbtList::Empty)
+            }
+        }
+    }
+
+    /// Calls `f` on the children of `tag` matched by this node, inserting `default`
+    /// where a missing child can be created. Returns the sum of `f`'s results.
+    fn visit(
+        &self,
+        tag: &mut NbtTag,
+        default: Option<&NbtTag>,
+        f: &mut dyn FnMut(&mut NbtTag) -> usize,
+    ) -> usize {
+        match self {
+            Self::MatchRootObject(pattern) => match tag {
+                NbtTag::Compound(compound) if compound_matches(pattern, compound) => f(tag),
+                _ => 0,
+            },
+            Self::CompoundChild(name) => {
+                let NbtTag::Compound(compound) = tag else {
+                    return 0;
+                };
+                if compound.get(name).is_none()
+                    && let Some(default) = default
+                {
+                    compound.insert(name.as_str(), default.clone());
+                }
+                compound.get_mut(name).map_or(0, f)
+            }
+            Self::MatchChild(name, pattern) => {
+                let NbtTag::Compound(compound) = tag else {
+                    return 0;
+                };
+                if compound.get(name).is_none() && default.is_some() {
+                    compound.insert(name.as_str(), NbtTag::Compound(pattern.clone()));
+                }
+                match compound.get_mut(name) {
+                    Some(child) if tag_matches(pattern, child) => f(child),
+                    _ => 0,
+                }
+            }
+            Self::AllElements => modify_elements(tag, |elements| {
+                if elements.is_empty()
+                    && let Some(default) = default
+                {
+                    elements.push(default.clone());
+                }
+                elements.iter_mut().map(|element| f(element)).sum()
+            }),
+            Self::IndexedElement(index) => modify_elements(tag, |elements| {
+                resolve_index(*index, elements.len()).map_or(0, |i| f(&mut elements[i]))
+            }),
+            Self::MatchElement(pattern) => modify_elements(tag, |elements| {
+                let mut matched = false;
+                let mut total = 0;
+                for element in elements.iter_mut() {
+                    if tag_matches(pattern, element) {
+                        matched = true;
+                        total += f(element);
+                    }
+                }
+                if !matched && default.is_some() {
+                    elements.push(NbtTag::Compound(pattern.clone()));
+                    if let Some(element) = elements.last_mut() {
+                        total += f(element);
+                    }
+                }
+                total
+            }),
+        }
+    }
+
+    /// Replaces the children of `parent` matched by this node with `value`.
+    fn set(&self, parent: &mut NbtTag, value: &NbtTag) -> usize {
+        match self {
+            Self::MatchRootObject(_) => 0,
+            Self::CompoundChild(name) => {
+                let NbtTag::Compound(compound) = parent else {
+                    return 0;
+                };
+                if compound.get(name) == Some(value) {
+                    return 0;
+                }
+                compound.insert(name.as_str(), value.clone());
+                1
+            }
+            Self::MatchChild(..) | Self::IndexedElement(_) | Self::MatchElement(_) => {
+                self.visit(parent, None, &mut |target| replace(target, value))
+            }
+            Self::AllElements => modify_elements(parent, |elements| {
+                if elements.is_empty() {
+                    elements.push(value.clone());
+                    return 1;
+                }
+                elements
+                    .iter_mut()
+                    .map(|element| replace(element, value))
+                    .sum()
+            }),
+        }
+    }
+
+    /// Removes the children of `parent` matched by this node.
+    fn remove(&self, parent: &mut NbtTag) -> usize {
+        match self {
+            Self::MatchRootObject(_) => 0,
+            Self::CompoundChild(name) => match parent {
+                NbtTag::Compound(compound) => usize::from(compound.remove(name).is_some()),
+                _ => 0,
+            },
+            Self::MatchChild(name, pattern) => match parent {
+                NbtTag::Compound(compound)
+                    if compound
+                        .get(name)
+                        .is_some_and(|child| tag_matches(pattern, child)) =>
+                {
+                    usize::from(compound.remove(name).is_some())
+                }
+                _ => 0,
+            },
+            Self::AllElements => modify_elements(parent, |elements| {
+                let removed = elements.len();
+                elements.clear();
+                removed
+            }),
+            Self::IndexedElement(index) => modify_elements(parent, |elements| {
+                resolve_index(*index, elements.len()).map_or(0, |i| {
+                    elements.remove(i);
+                    1
+                })
+            }),
+            Self::MatchElement(pattern) => modify_elements(parent, |elements| {
+                let before = elements.len();
+                elements.retain(|element| !tag_matches(pattern, element));
+                before - elements.len()
+            }),
+        }
+    }
+}
+
+fn invalid(position: usize, message: &str) -> NbtPathError {
+    NbtPathError::Invalid {
+        position,
+        message: message.to_owned(),
+    }
+}
+
+/// Parses a compound pattern at the start of `input`, which starts at `offset` in the full path.
+fn parse_pattern(input: &str, offset: usize) -> Result<(NbtCompound, usize), NbtPathError> {
+    match parse_snbt_prefix(input) {
+        Ok((NbtTag::Compound(compound), len)) => Ok((compound, len)),
+        Ok(_) => Err(invalid(offset, "Expected compound tag")),
+        Err(err) => Err(invalid(offset + err.position, &err.message)),
+    }
+}
+
+/// Parses a quoted or unquoted compound key at the start of `input`.
+fn parse_key(input: &str, offset: usize) -> Result<(String, usize), NbtPathError> {
+    if input.starts_with(['"', '\'']) {
+        return match parse_snbt_prefix(input) {
+            Ok((NbtTag::String(name), len)) => Ok((name.to_str().into_owned(), len)),
+            Ok(_) => Err(invalid(offset, "Expected string")),
+            Err(err) => Err(invalid(offset + err.position, &err.message)),
+        };
+    }
+
+    // Vanilla's `NbtPathArgument.isAllowedInUnquotedName`
+    let len = input
+        .find([' ', '"', '\'', '[', ']', '.', '{', '}'])
+        .unwrap_or(input.len());
+    if len == 0 {
+        return Err(invalid(offset, "Invalid NBT path element"));
+    }
+    Ok((input[..len].to_owned(), len))
+}
+
+fn resolve_index(index: i32, len: usize) -> Option<usize> {
+    let len = i64::try_from(len).ok()?;
+    let index = i64::from(index);
+    let resolved = if index < 0 { len + index } else { index };
+    usize::try_from(resolved).ok().filter(|_| resolved < len)
+}
+
+fn replace(target: &mut NbtTag, value: &NbtTag) -> usize {
+    if target == value {
+        0
+    } else {
+        *target = value.clone();
+        1
+    }
+}
+
+/// Returns the elements of a list or array, or `None` for other tags.
+fn collection_elements(tag: &NbtTag) -> Option<Vec<NbtTag>> {
+    match tag {
+        NbtTag::List(list) => Some(list_to_tags(list)),
+        NbtTag::ByteArray(values) => Some(
+            values
+                .iter()
+                .map(|&v| NbtTag::Byte(v.cast_signed()))
+                .collect(),
+        ),
+        NbtTag::IntArray(values) => Some(values.iter().map(|&v| NbtTag::Int(v)).collect()),
+        NbtTag::LongArray(values) => Some(values.iter().map(|&v| NbtTag::Long(v)).collect()),
+        _ => None,
+    }
+}
+
+/// Stores `elements` back into a list or array.
+///
+/// Returns `false` and leaves `tag` untouched if an array cannot hold one of
+/// the elements.
+fn set_collection_elements(tag: &mut NbtTag, elements: Vec<NbtTag>) -> bool {
+    macro_rules! array {
+        ($variant:ident, $element:ident, $convert:expr) => {{
+            let values: Option<Vec<_>> = elements
+                .into_iter()
+                .map(|element| match element {
+                    NbtTag::$element(value) => Some($convert(value)),
+                    _ => None,
+                })
+                .collect();
+            match values {
+                Some(values) => {
+                    *tag = NbtTag::$variant(values);
+                    true
+                }
+                None => false,
+            }
+        }};
+    }
+
+    match tag {
+        NbtTag::List(list) => {
+            *list = list_from_tags(elements);
+            true
+        }
+        NbtTag::ByteArray(_) => array!(ByteArray, Byte, i8::cast_unsigned),
+        NbtTag::IntArray(_) => array!(IntArray, Int, i32::from),
+        NbtTag::LongArray(_) => array!(LongArray, Long, i64::from),
+        _ => false,
+    }
+}
+
+/// Unpacks the elements of a list or array, lets `f` change them and packs them again.
+fn modify_elements(tag: &mut NbtTag, f: impl FnOnce(&mut Vec<NbtTag>) -> usize) -> usize {
+    let Some(mut elements) = collection_elements(tag) else {
+        return 0;
+    };
+    let changed = f(&mut elements);
+    if changed > 0 && !set_collection_elements(tag, elements) {
+        return 0;
+    }
+    changed
+}
+
+/// Returns whether `tag` matches `pattern`, like vanilla's `NbtUtils.compareNbt` with partial
+/// list matching.
+fn tag_matches(pattern: &NbtCompound, tag: &NbtTag) -> bool {
+    match tag {
+        NbtTag::Compound(compound) => compound_matches(pattern, compound),
+        _ => false,
+    }
+}
+
+fn compound_matches(pattern: &NbtCompound, compound: &NbtCompound) -> bool {
+    pattern.iter().all(|(key, expected)| {
+        compound
+            .get(&key.to_str())
+            .is_some_and(|actual| value_matches(expected, actual))
+    })
+}
+
+fn value_matches(expected: &NbtTag, actual: &NbtTag) -> bool {
+    match (expected, actual) {
+        (NbtTag::Compound(pattern), NbtTag::Compound(compound)) => {
+            compound_matches(pattern, compound)
+        }
+        (NbtTag::List(pattern), NbtTag::List(list)) => {
+            let pattern = list_to_tags(pattern);
+            let list = list_to_tags(list);
+            if pattern.is_empty() {
+                return list.is_empty();
+            }
+            pattern
+                .iter()
+                .all(|expected| list.iter().any(|actual| value_matches(expected, actual)))
+        }
+        _ => expected == actual,
+    }
+}
+
+/// Deeply merges `source` into `target`, like vanilla's `CompoundTag.merge`.
+pub fn merge_compound(target: &mut NbtCompound, source: &NbtCompound) {
+    for (key, value) in source.iter() {
+        let key = key.to_str();
+        match (target.get_mut(&key), value) {
+            (Some(NbtTag::Compound(existing)), NbtTag::Compound(value)) => {
+                merge_compound(existing, value);
+            }
+            _ => target.insert(&*key, value.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nbt::parse_snbt;
+
+    fn tag(snbt: &str) -> NbtTag {
+        parse_snbt(snbt).expect("valid SNBT")
+    }
+
+    #[test]
+    fn parses_all_node_kinds() {
+        let path = NbtPath::parse(r#"{a:1b}.foo.bar[0].baz[].qux[{id:"x"}]."some key"{b:2}"#)
+            .expect("valid path");
+        assert_eq!(path.nodes().len(), 9);
+        assert!(matches!(path.nodes()[0], NbtPathNode::MatchRootObject(_)));
+        assert_eq!(path.nodes()[3], NbtPathNode::IndexedElement(0));
+        assert!(matches!(&path.nodes()[8], NbtPathNode::MatchChild(name, _) if name == "some key"));
+
+        let (path, end) = NbtPath::parse_prefix("Inventory[-1] 2.0").expect("valid path");
+        assert_eq!(end, 13);
+        assert_eq!(path.nodes()[1], NbtPathNode::IndexedElement(-1));
+
+        assert!(NbtPath::parse("foo.").is_err());
+        assert!(NbtPath::parse("foo[bar]").is_err());
+    }
+
+    #[test]
+    fn gets_nested_values() {
+        let root = tag(r#"{foo: {bar: [{baz: 1}, {baz: 2, id: "x"}]}, arr: [I; 5, 6]}"#);
+
+        let path = NbtPath::parse("foo.bar[-1].baz").expect("valid path");
+        assert_eq!(path.get(&root), Ok(vec![NbtTag::Int(2)]));
+
+        let path = NbtPath::parse("foo.bar[].baz").expect("valid path");
+        assert_eq!(path.count_matching(&root), 2);
+
+        let path = NbtPath::parse(r#"foo.bar[{id:"x"}].baz"#).expect("valid path");
+        assert_eq!(path.get(&root), Ok(vec![NbtTag::Int(2)]));
+
+        let path = NbtPath::parse("arr[1]").expect("valid path");
+        assert_eq!(path.get(&root), Ok(vec![NbtTag::Int(6)]));
+
+        let path = NbtPath::parse("missing").expect("valid path");
+        assert!(matches!(
+            path.get(&root),
+            Err(NbtPathError::NothingFound(_))
+        ));
+    }
+
+    #[test]
+    fn set_creates_missing_parents() {
+        let mut root = tag("{}");
+        let path = NbtPath::parse("a.b[].c").expect("valid path");
+        assert_eq!(path.set(&mut root, &NbtTag::Byte(1)), Ok(1));
+        assert_eq!(root, tag("{a: {b: [{c: 1b}]}}"));
+
+        // Setting the same value again changes nothing
+        assert_eq!(path.set(&mut root, &NbtTag::Byte(1)), Ok(0));
+    }
+
+    #[test]
+    fn removes_and_inserts_elements() {
+        let mut root = tag("{list: [1, 2, 3], arr: [I; 1, 2]}");
+
+        let path = NbtPath::parse("list[0]").expect("valid path");
+        assert_eq!(path.remove(&mut root), 1);
+
+        let path = NbtPath::parse("list").expect("valid path");
+        assert_eq!(path.insert(0, &mut root, &[NbtTag::Int(0)]), Ok(1));
+        assert_eq!(path.insert(-1, &mut root, &[NbtTag::Int(9)]), Ok(1));
+        assert_eq!(
+            path.insert(10, &mut root, &[NbtTag::Int(9)]),
+            Err(NbtPathError::InvalidIndex(10))
+        );
+
+        // Arrays only accept elements of their own type
+        let path = NbtPath::parse("arr").expect("valid path");
+        assert_eq!(
+            path.insert(-1, &mut root, &[NbtTag::String("x".into())]),
+            Ok(0)
+        );
+
+        assert_eq!(root, tag("{list: [0, 2, 3, 9], arr: [I; 1, 2]}"));
+    }
+
+    #[test]
+    fn merges_into_compounds() {
+        let mut root = tag("{a: {b: 1, c: {d: 2}}}");
+        let NbtTag::Compound(source) = tag("{c: {e: 3}, f: 4}") else {
+            unreachable!()
+        };
+
+        let path = NbtPath::parse("a").expect("valid path");
+        assert_eq!(path.merge(&mut root, &source), Ok(1));
+        assert_eq!(root, tag("{a: {b: 1, c: {d: 2, e: 3}, f: 4}}"));
+
+        let path = NbtPath::parse("a.b").expect("valid path");
+        assert!(matches!(
+            path.merge(&mut root, &source),
+            Err(NbtPathError::ExpectedObject(_))
+        ));
+    }
+}
@@ -0,0 +1,727 @@
+//! serde support for NBT tags.
+//!
+//! Types are mapped the way vanilla codecs usually encode them: structs and maps
+//! become compounds, sequences become lists, `bool` becomes a byte and unit enum
+//! variants become strings. NBT has no unsigned types, so `u8`, `u16` and `u32`
+//! are widened to the next signed type and `u64` is stored as a long.
+
+use std::fmt::Display;
+use std::vec::IntoIter;
+
+use serde::de::value::StringDeserializer;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::{Deserializer, Serializer, forward_to_deserialize_any};
+use simdnbt::owned::{NbtCompound, NbtTag};
+use thiserror::Error;
+
+use super::{list_from_tags, list_to_tags};
+
+/// An error that can occur while converting between serde types and NBT.
+#[derive(Error, Debug)]
+pub enum NbtSerdeError {
+    /// A message from a `Serialize` or `Deserialize` implementation.
+    #[error("{0}")]
+    Custom(String),
+    /// The value has no NBT representation.
+    #[error("{0} cannot be represented as NBT")]
+    Unsupported(&'static str),
+    /// A map key was not a string or number.
+    #[error("map keys must be strings or numbers")]
+    InvalidKey,
+}
+
+impl ser::Error for NbtSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for NbtSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+/// Converts `value` to an NBT tag.
+///
+/// # Errors
+/// - If `value` serializes to nothing (`None` or `()`), or contains a map with
+///   keys that are not strings or numbers.
+pub fn to_nbt<T: Serialize + ?Sized>(value: &T) -> Result<NbtTag, NbtSerdeError> {
+    value
+        .serialize(TagSerializer)?
+        .ok_or(NbtSerdeError::Unsupported("an empty value"))
+}
+
+/// Converts an NBT tag to `T`.
+///
+/// # Errors
+/// - If the tag does not have the shape `T` expects.
+pub fn from_nbt<T: DeserializeOwned>(tag: NbtTag) -> Result<T, NbtSerdeError> {
+    T::deserialize(TagDeserializer(tag))
+}
+
+/// Serializes values into tags. `None` and `()` produce no tag, and are left
+/// out of compounds and lists.
+struct TagSerializer;
+
+type SerializeResult = Result<Option<NbtTag>, NbtSerdeError>;
+
+impl Serializer for TagSerializer {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = VariantSerializer<ListSerializer>;
+    type SerializeMap = CompoundSerializer;
+    type SerializeStruct = CompoundSerializer;
+    type SerializeStructVariant = VariantSerializer<CompoundSerializer>;
+
+    fn serialize_bool(self, v: bool) -> SerializeResult {
+        Ok(Some(NbtTag::Byte(i8::from(v))))
+    }
+
+    fn serialize_i8(self, v: i8) -> SerializeResult {
+        Ok(Some(NbtTag::Byte(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> SerializeResult {
+        Ok(Some(NbtTag::Short(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> SerializeResult {
+        Ok(Some(NbtTag::Int(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> SerializeResult {
+        Ok(Some(NbtTag::Long(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> SerializeResult {
+        Ok(Some(NbtTag::Short(i16::from(v))))
+    }
+
+    fn serialize_u16(self, v: u16) -> SerializeResult {
+        Ok(Some(NbtTag::Int(i32::from(v))))
+    }
+
+    fn serialize_u32(self, v: u32) -> SerializeResult {
+        Ok(Some(NbtTag::Long(i64::from(v))))
+    }
+
+    fn serialize_u64(self, v: u64) -> SerializeResult {
+        i64::try_from(v)
+            .map(|v| Some(NbtTag::Long(v)))
+            .map_err(|_| NbtSerdeError::Unsupported("a u64 above i64::MAX"))
+    }
+
+    fn serialize_f32(self, v: f32) -> SerializeResult {
+        Ok(Some(NbtTag::Float(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> SerializeResult {
+        Ok(Some(NbtTag::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> SerializeResult {
+        Ok(Some(NbtTag::String(v.to_string().into())))
+    }
+
+    fn serialize_str(self, v: &str) -> SerializeResult {
+        Ok(Some(NbtTag::String(v.into())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> SerializeResult {
+        Ok(Some(NbtTag::ByteArray(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> SerializeResult {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> SerializeResult {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SerializeResult {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerializeResult {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> SerializeResult {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SerializeResult {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> SerializeResult {
+        let mut compound = NbtCompound::new();
+        if let Some(tag) = value.serialize(self)? {
+            compound.insert(variant, tag);
+        }
+        Ok(Some(NbtTag::Compound(compound)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ListSerializer, NbtSerdeError> {
+        Ok(ListSerializer {
+            elements: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer, NbtSerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer, NbtSerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<ListSerializer>, NbtSerdeError> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<CompoundSerializer, NbtSerdeError> {
+        Ok(CompoundSerializer {
+            compound: NbtCompound::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<CompoundSerializer, NbtSerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<CompoundSerializer>, NbtSerdeError> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Collects sequence elements into a list.
+struct ListSerializer {
+    elements: Vec<NbtTag>,
+}
+
+impl ListSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtSerdeError> {
+        if let Some(tag) = value.serialize(TagSerializer)? {
+            self.elements.push(tag);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> NbtTag {
+        NbtTag::List(list_from_tags(self.elements))
+    }
+}
+
+impl SerializeSeq for ListSerializer {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(self.finish()))
+    }
+}
+
+impl SerializeTuple for ListSerializer {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(self.finish()))
+    }
+}
+
+impl SerializeTupleStruct for ListSerializer {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(self.finish()))
+    }
+}
+
+/// Collects map entries and struct fields into a compound.
+struct CompoundSerializer {
+    compound: NbtCompound,
+    key: Option<String>,
+}
+
+impl CompoundSerializer {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), NbtSerdeError> {
+        if let Some(tag) = value.serialize(TagSerializer)? {
+            self.compound.insert(key, tag);
+        }
+        Ok(())
+    }
+}
+
+impl SerializeMap for CompoundSerializer {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        let key = match key.serialize(TagSerializer)? {
+            Some(NbtTag::String(key)) => key.to_str().into_owned(),
+            Some(NbtTag::Byte(v)) => v.to_string(),
+            Some(NbtTag::Short(v)) => v.to_string(),
+            Some(NbtTag::Int(v)) => v.to_string(),
+            Some(NbtTag::Long(v)) => v.to_string(),
+            _ => return Err(NbtSerdeError::InvalidKey),
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self.key.take().ok_or(NbtSerdeError::InvalidKey)?;
+        self.insert(&key, value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(NbtTag::Compound(self.compound)))
+    }
+}
+
+impl SerializeStruct for CompoundSerializer {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(NbtTag::Compound(self.compound)))
+    }
+}
+
+/// Wraps the content of a tuple or struct variant in `{variant: content}`.
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+fn wrap_variant(variant: &'static str, content: NbtTag) -> NbtTag {
+    let mut compound = NbtCompound::new();
+    compound.insert(variant, content);
+    NbtTag::Compound(compound)
+}
+
+impl SerializeTupleVariant for VariantSerializer<ListSerializer> {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(wrap_variant(self.variant, self.inner.finish())))
+    }
+}
+
+impl SerializeStructVariant for VariantSerializer<CompoundSerializer> {
+    type Ok = Option<NbtTag>;
+    type Error = NbtSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.inner.insert(key, value)
+    }
+
+    fn end(self) -> SerializeResult {
+        Ok(Some(wrap_variant(
+            self.variant,
+            NbtTag::Compound(self.inner.compound),
+        )))
+    }
+}
+
+/// Deserializes values from an owned tag.
+struct TagDeserializer(NbtTag);
+
+impl<'de> Deserializer<'de> for TagDeserializer {
+    type Error = NbtSerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            NbtTag::Byte(v) => visitor.visit_i8(v),
+            NbtTag::Short(v) => visitor.visit_i16(v),
+            NbtTag::Int(v) => visitor.visit_i32(v),
+            NbtTag::Long(v) => visitor.visit_i64(v),
+            NbtTag::Float(v) => visitor.visit_f32(v),
+            NbtTag::Double(v) => visitor.visit_f64(v),
+            NbtTag::String(v) => visitor.visit_string(v.to_str().into_owned()),
+            NbtTag::ByteArray(v) => visitor.visit_seq(ListDeserializer(
+                v.into_iter()
+                    .map(|b| NbtTag::Byte(b.cast_signed()))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            )),
+            NbtTag::IntArray(v) => visitor.visit_seq(ListDeserializer(
+                v.into_iter()
+                    .map(NbtTag::Int)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            )),
+            NbtTag::LongArray(v) => visitor.visit_seq(ListDeserializer(
+                v.into_iter()
+                    .map(NbtTag::Long)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            )),
+            NbtTag::List(list) => {
+                visitor.visit_seq(ListDeserializer(list_to_tags(&list).into_iter()))
+            }
+            NbtTag::Compound(compound) => visitor.visit_map(CompoundDeserializer {
+                entries: compound_entries(&compound).into_iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            NbtTag::Byte(v) => visitor.visit_bool(v != 0),
+            other => Self(other).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            NbtTag::ByteArray(v) => visitor.visit_byte_buf(v),
+            other => Self(other).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        // Absent values never reach the deserializer, so anything present is `Some`
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            NbtTag::String(variant) => visitor.visit_enum(VariantDeserializer {
+                variant: variant.to_str().into_owned(),
+                value: None,
+            }),
+            NbtTag::Compound(compound) => {
+                let mut entries = compound_entries(&compound).into_iter();
+                match (entries.next(), entries.next()) {
+                    (Some((variant, value)), None) => visitor.visit_enum(VariantDeserializer {
+                        variant,
+                        value: Some(value),
+                    }),
+                    _ => Err(de::Error::custom(
+                        "expected a compound with a single entry for an enum variant",
+                    )),
+                }
+            }
+            _ => Err(de::Error::custom(
+                "expected a string or compound for an enum",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn compound_entries(compound: &NbtCompound) -> Vec<(String, NbtTag)> {
+    compound
+        .iter()
+        .map(|(key, value)| (key.to_str().into_owned(), value.clone()))
+        .collect()
+}
+
+struct ListDeserializer(IntoIter<NbtTag>);
+
+impl<'de> SeqAccess<'de> for ListDeserializer {
+    type Error = NbtSerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.0
+            .next()
+            .map(|tag| seed.deserialize(TagDeserializer(tag)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct CompoundDeserializer {
+    entries: IntoIter<(String, NbtTag)>,
+    value: Option<NbtTag>,
+}
+
+impl<'de> MapAccess<'de> for CompoundDeserializer {
+    type Error = NbtSerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        let key: StringDeserializer<NbtSerdeError> = key.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| NbtSerdeError::Custom("value requested before key".to_owned()))?;
+        seed.deserialize(TagDeserializer(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Enum access for `"variant"` and `{variant: content}`.
+struct VariantDeserializer {
+    variant: String,
+    value: Option<NbtTag>,
+}
+
+impl<'de> EnumAccess<'de> for VariantDeserializer {
+    type Error = NbtSerdeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), Self::Error> {
+        let variant: StringDeserializer<NbtSerdeError> = self.variant.clone().into_deserializer();
+        Ok((seed.deserialize(variant)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = NbtSerdeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        let value = self
+            .value
+            .ok_or_else(|| NbtSerdeError::Custom("expected newtype variant content".to_owned()))?;
+        seed.deserialize(TagDeserializer(value))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .ok_or_else(|| NbtSerdeError::Custom("expected tuple variant content".to_owned()))?;
+        TagDeserializer(value).deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .ok_or_else(|| NbtSerdeError::Custom("expected struct variant content".to_owned()))?;
+        TagDeserializer(value).deserialize_any(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+    use simdnbt::owned::NbtList;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: i32, height: i32 },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Sample {
+        flag: bool,
+        small: i8,
+        unsigned: u8,
+        name: String,
+        numbers: Vec<i32>,
+        missing: Option<f64>,
+        present: Option<i64>,
+        shapes: Vec<Shape>,
+        map: BTreeMap<String, i16>,
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            flag: true,
+            small: -3,
+            unsigned: 200,
+            name: "steel".to_owned(),
+            numbers: vec![1, 2, 3],
+            missing: None,
+            present: Some(7),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(0.5),
+                Shape::Rect {
+                    width: 2,
+                    height: 3,
+                },
+            ],
+            map: BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]),
+        }
+    }
+
+    #[test]
+    fn struct_round_trip() {
+        let tag = to_nbt(&sample()).expect("serialize");
+        let value: Sample = from_nbt(tag).expect("deserialize");
+        assert_eq!(value, sample());
+    }
+
+    #[test]
+    fn uses_vanilla_shapes() {
+        let NbtTag::Compound(compound) = to_nbt(&sample()).expect("serialize") else {
+            panic!("expected compound");
+        };
+        assert_eq!(compound.get("flag"), Some(&NbtTag::Byte(1)));
+        assert_eq!(compound.get("unsigned"), Some(&NbtTag::Short(200)));
+        assert_eq!(
+            compound.get("numbers"),
+            Some(&NbtTag::List(NbtList::Int(vec![1, 2, 3])))
+        );
+        assert_eq!(compound.get("missing"), None);
+    }
+}
@@ -0,0 +1,716 @@
+//! Stringified NBT (SNBT), following the grammar vanilla uses since 1.21.5.
+//!
+//! Supported syntax:
+//! - compounds `{a: 1, "b c": "d"}` and lists `[1, 2, 3]`, both with optional trailing commas
+//! - typed arrays `[B; 1, 2]`, `[I; 1, 2]` and `[L; 1, 2]`
+//! - integers with optional signedness and type suffix (`1b`, `200ub`, `3s`, `4`, `5L`),
+//!   written in decimal, hexadecimal (`0xFF`) or binary (`0b101`) with `_` separators
+//! - floats with optional exponent and type suffix (`1.5`, `2e3`, `1f`, `0.5d`)
+//! - `true` / `false` as bytes, unquoted strings and single or double quoted strings
+//!   with escapes
+//!
+//! Lists of mixed types are wrapped like vanilla does, see [`super::list_from_tags`].
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::num::IntErrorKind;
+
+use simdnbt::owned::{NbtCompound, NbtList, NbtTag};
+
+use super::{NbtLimits, list_from_tags, list_to_tags};
+
+/// An error that occurred while parsing SNBT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnbtError {
+    /// The byte offset into the input at which the error occurred.
+    pub position: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl Display for SnbtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for SnbtError {}
+
+/// Parses a complete SNBT value. Surrounding whitespace is allowed.
+///
+/// # Errors
+/// - If the input is not valid SNBT or has trailing data.
+pub fn parse_snbt(input: &str) -> Result<NbtTag, SnbtError> {
+    let (tag, end) = parse_snbt_prefix(input)?;
+    let rest = &input[end..];
+    let trailing = rest.len() - rest.trim_start().len();
+    if end + trailing != input.len() {
+        return Err(SnbtError {
+            position: end + trailing,
+            message: "Unexpected trailing data".to_owned(),
+        });
+    }
+    Ok(tag)
+}
+
+/// Parses a complete SNBT compound. Surrounding whitespace is allowed.
+///
+/// # Errors
+/// - If the input is not a valid SNBT compound or has trailing data.
+pub fn parse_snbt_compound(input: &str) -> Result<NbtCompound, SnbtError> {
+    match parse_snbt(input)? {
+        NbtTag::Compound(compound) => Ok(compound),
+        _ => Err(SnbtError {
+            position: 0,
+            message: "Expected compound tag".to_owned(),
+        }),
+    }
+}
+
+/// Parses one SNBT value from the start of `input`.
+///
+/// Returns the value and the byte offset right after it, so callers such as
+/// command argument parsers can continue with the rest of the input.
+///
+/// # Errors
+/// - If the input does not start with valid SNBT.
+pub fn parse_snbt_prefix(input: &str) -> Result<(NbtTag, usize), SnbtError> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+    let tag = parser.value()?;
+    Ok((tag, parser.pos))
+}
+
+/// Formats `tag` as compact SNBT, like vanilla's `StringTagVisitor`.
+///
+/// Compound keys are sorted, so the output is stable.
+#[must_use]
+pub fn to_snbt(tag: &NbtTag) -> String {
+    let mut out = String::new();
+    write_tag(tag, &mut out);
+    out
+}
+
+/// Formats `compound` as compact SNBT, see [`to_snbt`].
+#[must_use]
+pub fn compound_to_snbt(compound: &NbtCompound) -> String {
+    let mut out = String::new();
+    write_compound(compound, &mut out);
+    out
+}
+
+/// Returns whether `c` may appear in an unquoted string or number.
+const fn is_unquoted_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'.' | b'+')
+}
+
+/// The integer types a literal can be suffixed with.
+#[derive(Clone, Copy)]
+enum IntType {
+    Byte,
+    Short,
+    Int,
+    Long,
+}
+
+/// The signedness a literal can be suffixed with.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Signedness {
+    Signed,
+    Unsigned,
+    /// No suffix: decimal literals are signed, hex and binary literals may use
+    /// either range so bit patterns like `0xFFFFFFFF` can be written directly.
+    Default,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, SnbtError> {
+        Err(SnbtError {
+            position: self.pos,
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), SnbtError> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(format!("Expected '{}'", char::from(expected)))
+        }
+    }
+
+    fn push_depth(&mut self) -> Result<(), SnbtError> {
+        self.depth += 1;
+        if self.depth > NbtLimits::MAX_DEPTH {
+            return self.error("Too deeply nested");
+        }
+        Ok(())
+    }
+
+    fn unquoted(&mut self) -> &'a str {
+        let input = self.input;
+        let start = self.pos;
+        while self.peek().is_some_and(is_unquoted_char) {
+            self.pos += 1;
+        }
+        &input[start..self.pos]
+    }
+
+    fn value(&mut self) -> Result<NbtTag, SnbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.compound().map(NbtTag::Compound),
+            Some(b'[') => self.list(),
+            Some(b'"' | b'\'') => self.quoted().map(|s| NbtTag::String(s.into())),
+            _ => {
+                let start = self.pos;
+                let token = self.unquoted();
+                // TODO: Support vanilla's `bool(...)` and `uuid(...)` operations
+                match token.as_bytes().first() {
+                    None => self.error("Expected value"),
+                    Some(c) if c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.') => {
+                        parse_number(token).map_err(|message| SnbtError {
+                            position: start,
+                            message: message.to_owned(),
+                        })
+                    }
+                    _ => Ok(match token {
+                        "true" => NbtTag::Byte(1),
+                        "false" => NbtTag::Byte(0),
+                        _ => NbtTag::String(token.into()),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, SnbtError> {
+        let Some(quote) = self.peek() else {
+            return self.error("Expected quoted string");
+        };
+        self.pos += 1;
+
+        let mut result = String::new();
+        loop {
+            let Some(c) = self.input[self.pos..].chars().next() else {
+                return self.error("Unterminated string");
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '\\' => result.push(self.escape()?),
+                c if c == char::from(quote) => return Ok(result),
+                c => result.push(c),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, SnbtError> {
+        let Some(c) = self.peek() else {
+            return self.error("Unterminated escape sequence");
+        };
+        self.pos += 1;
+        let hex_len = match c {
+            b'\\' | b'"' | b'\'' => return Ok(char::from(c)),
+            b'b' => return Ok('\u{8}'),
+            b'f' => return Ok('\u{c}'),
+            b'n' => return Ok('\n'),
+            b'r' => return Ok('\r'),
+            b's' => return Ok(' '),
+            b't' => return Ok('\t'),
+            b'x' => 2,
+            b'u' => 4,
+            b'U' => 8,
+            // TODO: Support named unicode escapes (`\N{...}`)
+            _ => return self.error(format!("Invalid escape sequence '\\{}'", char::from(c))),
+        };
+
+        let digits = self
+            .input
+            .get(self.pos..self.pos + hex_len)
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()));
+        let Some(c) = digits
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .and_then(char::from_u32)
+        else {
+            return self.error("Invalid unicode escape");
+        };
+        self.pos += hex_len;
+        Ok(c)
+    }
+
+    fn compound(&mut self) -> Result<NbtCompound, SnbtError> {
+        self.expect(b'{')?;
+        self.push_depth()?;
+
+        let mut compound = NbtCompound::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b'}') {
+                break;
+            }
+
+            let key = match self.peek() {
+                Some(b'"' | b'\'') => self.quoted()?,
+                _ => {
+                    let key = self.unquoted();
+                    if key.is_empty() {
+                        return self.error("Expected key");
+                    }
+                    key.to_owned()
+                }
+            };
+            self.expect(b':')?;
+            let value = self.value()?;
+            compound.insert(key, value);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => break,
+                _ => return self.error("Expected ',' or '}'"),
+            }
+        }
+
+        self.pos += 1;
+        self.depth -= 1;
+        Ok(compound)
+    }
+
+    /// Parses comma separated values up to the closing `]`, which is consumed.
+    fn elements(&mut self) -> Result<Vec<NbtTag>, SnbtError> {
+        let mut elements = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b']') {
+                break;
+            }
+            elements.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => break,
+                _ => return self.error("Expected ',' or ']'"),
+            }
+        }
+        self.pos += 1;
+        Ok(elements)
+    }
+
+    fn list(&mut self) -> Result<NbtTag, SnbtError> {
+        self.expect(b'[')?;
+        self.push_depth()?;
+
+        let bytes = self.input.as_bytes();
+        let array_type = match (bytes.get(self.pos), bytes.get(self.pos + 1)) {
+            (Some(&t @ (b'B' | b'I' | b'L')), Some(b';')) => Some(t),
+            _ => None,
+        };
+
+        let tag = if let Some(array_type) = array_type {
+            self.pos += 2;
+            let start = self.pos;
+            let values = self
+                .elements()?
+                .into_iter()
+                .map(|tag| match tag {
+                    NbtTag::Byte(v) => Some(i64::from(v)),
+                    NbtTag::Short(v) => Some(i64::from(v)),
+                    NbtTag::Int(v) => Some(i64::from(v)),
+                    NbtTag::Long(v) => Some(v),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            let array = values.and_then(|values| match array_type {
+                b'B' => values
+                    .into_iter()
+                    .map(|v| i8::try_from(v).ok().map(i8::cast_unsigned))
+                    .collect::<Option<_>>()
+                    .map(NbtTag::ByteArray),
+                b'I' => values
+                    .into_iter()
+                    .map(|v| i32::try_from(v).ok())
+                    .collect::<Option<_>>()
+                    .map(NbtTag::IntArray),
+                _ => Some(NbtTag::LongArray(values)),
+            });
+            match array {
+                Some(array) => array,
+                None => {
+                    return Err(SnbtError {
+                        position: start,
+                        message: format!("Invalid element in [{};] array", char::from(array_type)),
+                    });
+                }
+            }
+        } else {
+            NbtTag::List(list_from_tags(self.elements()?))
+        };
+
+        self.depth -= 1;
+        Ok(tag)
+    }
+}
+
+/// Splits an integer literal (without sign and base prefix) into digits and suffix.
+fn split_int_suffix(body: &str, hex: bool) -> (&str, &str) {
+    let bytes = body.as_bytes();
+    let is_sign = |c: u8| matches!(c.to_ascii_lowercase(), b's' | b'u');
+    let suffix_len = if hex {
+        // `b` is a hex digit, so it only counts as a suffix after a signedness marker
+        match bytes {
+            [.., s, t]
+                if is_sign(*s) && matches!(t.to_ascii_lowercase(), b'b' | b's' | b'i' | b'l') =>
+            {
+                2
+            }
+            [.., t] if matches!(t.to_ascii_lowercase(), b's' | b'i' | b'l') => 1,
+            _ => 0,
+        }
+    } else {
+        bytes
+            .iter()
+            .rev()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .count()
+    };
+    body.split_at(body.len() - suffix_len)
+}
+
+/// Parses a numeric literal such as `-12`, `0xFFub`, `1.5e3f` or `5L`.
+fn parse_number(token: &str) -> Result<NbtTag, &'static str> {
+    let (negative, unsigned_token) = match token.as_bytes().first() {
+        Some(b'-') => (true, &token[1..]),
+        Some(b'+') => (false, &token[1..]),
+        _ => (false, token),
+    };
+
+    let lower = unsigned_token.to_ascii_lowercase();
+    let (radix, body) = if let Some(hex) = lower.strip_prefix("0x") {
+        (16, hex)
+    } else if let Some(binary) = lower
+        .strip_prefix("0b")
+        .filter(|rest| rest.starts_with(['0', '1']))
+    {
+        (2, binary)
+    } else {
+        (10, lower.as_str())
+    };
+
+    let (digits, suffix) = split_int_suffix(body, radix == 16);
+    if radix == 10 && (matches!(suffix, "f" | "d") || digits.contains(['.', 'e'])) {
+        return parse_float(negative, digits, suffix);
+    }
+
+    let (signedness, type_char) = match suffix.as_bytes() {
+        [] => (Signedness::Default, b'i'),
+        [t] => (Signedness::Default, *t),
+        [b's', t] => (Signedness::Signed, *t),
+        [b'u', t] => (Signedness::Unsigned, *t),
+        _ => return Err("Invalid number suffix"),
+    };
+    let int_type = match type_char {
+        b'b' => IntType::Byte,
+        b's' => IntType::Short,
+        b'i' => IntType::Int,
+        b'l' => IntType::Long,
+        _ => return Err("Invalid number suffix"),
+    };
+
+    if digits.is_empty() || digits.starts_with('_') || digits.ends_with('_') {
+        return Err("Invalid number");
+    }
+    let magnitude = u64::from_str_radix(&digits.replace('_', ""), radix).map_err(|e| {
+        if *e.kind() == IntErrorKind::PosOverflow {
+            "Number out of range"
+        } else {
+            "Invalid number"
+        }
+    })?;
+    if negative && signedness == Signedness::Unsigned {
+        return Err("Unsigned numbers cannot be negative");
+    }
+    let value = if negative {
+        -i128::from(magnitude)
+    } else {
+        i128::from(magnitude)
+    };
+
+    let signed_allowed = signedness != Signedness::Unsigned;
+    let unsigned_allowed =
+        signedness == Signedness::Unsigned || (signedness == Signedness::Default && radix != 10);
+
+    macro_rules! fit {
+        ($signed:ty, $unsigned:ty, $variant:ident) => {
+            signed_allowed
+                .then(|| <$signed>::try_from(value).ok())
+                .flatten()
+                .or_else(|| {
+                    unsigned_allowed
+                        .then(|| {
+                            <$unsigned>::try_from(value)
+                                .ok()
+                                .map(<$unsigned>::cast_signed)
+                        })
+                        .flatten()
+                })
+                .map(NbtTag::$variant)
+        };
+    }
+
+    match int_type {
+        IntType::Byte => fit!(i8, u8, Byte),
+        IntType::Short => fit!(i16, u16, Short),
+        IntType::Int => fit!(i32, u32, Int),
+        IntType::Long => fit!(i64, u64, Long),
+    }
+    .ok_or("Number out of range")
+}
+
+/// Parses the digits of a float literal, already split from its suffix.
+fn parse_float(negative: bool, digits: &str, suffix: &str) -> Result<NbtTag, &'static str> {
+    let valid = digits.bytes().any(|c| c.is_ascii_digit())
+        && digits
+            .bytes()
+            .all(|c| c.is_ascii_digit() || matches!(c, b'.' | b'e' | b'_' | b'+' | b'-'));
+    if !valid {
+        return Err("Invalid number");
+    }
+    let value: f64 = digits
+        .replace('_', "")
+        .parse()
+        .map_err(|_| "Invalid number")?;
+    let value = if negative { -value } else { value };
+
+    match suffix {
+        "f" => {
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "float literals are parsed as f64 and narrowed like vanilla"
+            )]
+            let value = value as f32;
+            Ok(NbtTag::Float(value))
+        }
+        "" | "d" => Ok(NbtTag::Double(value)),
+        _ => Err("Invalid number suffix"),
+    }
+}
+
+/// Returns whether `key` can be written without quotes.
+fn is_simple_key(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    bytes
+        .first()
+        .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, b'.' | b'_'))
+        && bytes.iter().copied().all(is_unquoted_char)
+}
+
+/// Quotes and escapes a string like vanilla's `StringTag.quoteAndEscape`.
+///
+/// Double quotes are used unless the string contains a double quote before
+/// any single quote.
+fn write_quoted(value: &str, out: &mut String) {
+    let quote = value
+        .chars()
+        .find(|&c| c == '"' || c == '\'')
+        .map_or('"', |c| if c == '"' { '\'' } else { '"' });
+
+    out.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_ascii_control() => out.push_str(&format!("\\x{:02X}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+}
+
+fn write_compound(compound: &NbtCompound, out: &mut String) {
+    let mut entries: Vec<_> = compound
+        .iter()
+        .map(|(key, value)| (key.to_str(), value))
+        .collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    out.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if is_simple_key(&key) {
+            out.push_str(&key);
+        } else {
+            write_quoted(&key, out);
+        }
+        out.push(':');
+        write_tag(value, out);
+    }
+    out.push('}');
+}
+
+fn write_array<T: Display>(prefix: &str, values: &[T], suffix: &str, out: &mut String) {
+    out.push('[');
+    out.push_str(prefix);
+    out.push(';');
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&value.to_string());
+        out.push_str(suffix);
+    }
+    out.push(']');
+}
+
+fn write_list(list: &NbtList, out: &mut String) {
+    out.push('[');
+    for (i, tag) in list_to_tags(list).iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_tag(tag, out);
+    }
+    out.push(']');
+}
+
+fn write_tag(tag: &NbtTag, out: &mut String) {
+    match tag {
+        NbtTag::Byte(v) => out.push_str(&format!("{v}b")),
+        NbtTag::Short(v) => out.push_str(&format!("{v}s")),
+        NbtTag::Int(v) => out.push_str(&v.to_string()),
+        NbtTag::Long(v) => out.push_str(&format!("{v}L")),
+        NbtTag::Float(v) => out.push_str(&format!("{v:?}f")),
+        NbtTag::Double(v) => out.push_str(&format!("{v:?}d")),
+        NbtTag::ByteArray(v) => {
+            let values: Vec<i8> = v.iter().map(|b| b.cast_signed()).collect();
+            write_array("B", &values, "B", out);
+        }
+        NbtTag::String(v) => write_quoted(&v.to_str(), out),
+        NbtTag::List(v) => write_list(v, out),
+        NbtTag::Compound(v) => write_compound(v, out),
+        NbtTag::IntArray(v) => write_array("I", v, "", out),
+        NbtTag::LongArray(v) => write_array("L", v, "L", out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> NbtTag {
+        parse_snbt(input).unwrap_or_else(|e| panic!("failed to parse {input:?}: {e}"))
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse("1b"), NbtTag::Byte(1));
+        assert_eq!(parse("-128B"), NbtTag::Byte(-128));
+        assert_eq!(parse("255ub"), NbtTag::Byte(-1));
+        assert_eq!(parse("3s"), NbtTag::Short(3));
+        assert_eq!(parse("1_000"), NbtTag::Int(1000));
+        assert_eq!(parse("0xFFFFFFFF"), NbtTag::Int(-1));
+        assert_eq!(parse("0x1F"), NbtTag::Int(31));
+        assert_eq!(parse("0b101"), NbtTag::Int(5));
+        assert_eq!(parse("0x10sb"), NbtTag::Byte(16));
+        assert_eq!(parse("5L"), NbtTag::Long(5));
+        assert_eq!(parse("1.5"), NbtTag::Double(1.5));
+        assert_eq!(parse("2e3"), NbtTag::Double(2000.0));
+        assert_eq!(parse(".5f"), NbtTag::Float(0.5));
+        assert_eq!(parse("1d"), NbtTag::Double(1.0));
+        assert_eq!(parse("true"), NbtTag::Byte(1));
+
+        assert!(parse_snbt("128b").is_err());
+        assert!(parse_snbt("-1ub").is_err());
+        assert!(parse_snbt("2147483648").is_err());
+        assert!(parse_snbt("1x").is_err());
+    }
+
+    #[test]
+    fn parses_strings() {
+        assert_eq!(
+            parse("minecraft.stone"),
+            NbtTag::String("minecraft.stone".into())
+        );
+        assert!(parse_snbt("minecraft:stone").is_err());
+        assert_eq!(parse("hello"), NbtTag::String("hello".into()));
+        assert_eq!(
+            parse(r#""a \"quoted\" ☃ \x41""#),
+            NbtTag::String("a \"quoted\" \u{2603} A".into())
+        );
+        assert_eq!(parse(r"'it\'s'"), NbtTag::String("it's".into()));
+        assert!(parse_snbt("\"unterminated").is_err());
+    }
+
+    #[test]
+    fn parses_collections() {
+        let NbtTag::Compound(compound) = parse(r#"{a: 1, "b c": [1b, 2b,], d: {}, e: [I; 1, 2],}"#)
+        else {
+            panic!("expected compound");
+        };
+        assert_eq!(compound.get("a"), Some(&NbtTag::Int(1)));
+        assert_eq!(
+            compound.get("b c"),
+            Some(&NbtTag::List(NbtList::Byte(vec![1, 2])))
+        );
+        assert_eq!(
+            compound.get("d"),
+            Some(&NbtTag::Compound(NbtCompound::new()))
+        );
+        assert_eq!(compound.get("e"), Some(&NbtTag::IntArray(vec![1, 2])));
+
+        assert_eq!(parse("[B; 1b, -1b]"), NbtTag::ByteArray(vec![1, 255]));
+        assert_eq!(parse("[L;]"), NbtTag::LongArray(Vec::new()));
+        assert!(parse_snbt("[B; 300]").is_err());
+        assert!(parse_snbt("{a 1}").is_err());
+    }
+
+    #[test]
+    fn prefix_reports_consumed_length() {
+        let (tag, end) = parse_snbt_prefix("{a:1} rest").expect("valid prefix");
+        assert!(matches!(tag, NbtTag::Compound(_)));
+        assert_eq!(end, 5);
+        assert!(parse_snbt("{a:1} rest").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let input = r#"{arr:[I;1,2],bytes:[B;1B,-2B],d:1.5d,f:0.5f,"key with space":'say "hi"',l:[L;3L],list:[1,"two",{a:1b}],nested:{x:-1s,y:9L}}"#;
+        let tag = parse(input);
+
+        let printed = to_snbt(&tag);
+
+        assert_eq!(printed, input);
+        assert_eq!(parse(&printed), tag);
+    }
+}
@@ -42,3 +42,12 @@ pub(crate) const GRADIENT: [[f64; 3]; 16] = [
     [-1.0, 1.0, 0.0],
     [0.0, -1.0, -1.0],
 ];
+
+/// Checks that the slices of a batch sampling call line up.
+#[inline]
+pub(crate) fn assert_batch_lengths(x: &[f64], y: &[f64], z: &[f64], out: &[f64]) {
+    assert!(
+        x.len() == out.len() && y.len() == out.len() && z.len() == out.len(),
+        "batch coordinate slices must match the output length"
+    );
+}
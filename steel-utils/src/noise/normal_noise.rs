@@ -3,7 +3,7 @@
 //! This combines two `PerlinNoise` samplers with slightly different coordinate scaling
 //! to create smoother, more natural-looking noise. It's used for biome climate parameters.
 
-use crate::noise::PerlinNoise;
+use crate::noise::{PerlinNoise, assert_batch_lengths};
 use crate::random::{PositionalRandom, RandomSource, RandomSplitter, name_hash::NameHash};
 
 /// Input factor for the second Perlin sampler.
@@ -29,6 +29,10 @@ pub const INPUT_FACTOR: f64 = 1.0181268882175227;
 )]
 const VALUE_FACTOR_NUMERATOR: f64 = 0.16666666666666666;
 
+/// Positions processed per pass of [`NormalNoise::get_values`], sized so the
+/// scratch buffers for the second sampler stay on the stack.
+const BATCH_CHUNK: usize = 64;
+
 /// Normal (Double Perlin) noise generator.
 ///
 /// Combines two `PerlinNoise` samplers with different coordinate scales to create
@@ -149,6 +153,46 @@ impl NormalNoise {
         (self.first.get_value(x, y, z) + self.second.get_value(x2, y2, z2)) * self.value_factor
     }
 
+    /// Sample the noise at many positions at once, writing
+    /// `get_value(x[i], y[i], z[i])` into `out[i]` without allocating.
+    ///
+    /// Both Perlin samplers run over the batch with
+    /// [`PerlinNoise::get_values`], and the results are bit-identical to
+    /// sampling each position on its own.
+    ///
+    /// # Panics
+    /// If the slices don't all have the same length.
+    pub fn get_values(&self, x: &[f64], y: &[f64], z: &[f64], out: &mut [f64]) {
+        assert_batch_lengths(x, y, z, out);
+
+        let mut x2 = [0.0; BATCH_CHUNK];
+        let mut y2 = [0.0; BATCH_CHUNK];
+        let mut z2 = [0.0; BATCH_CHUNK];
+        let mut second = [0.0; BATCH_CHUNK];
+
+        for (chunk, out) in out.chunks_mut(BATCH_CHUNK).enumerate() {
+            let start = chunk * BATCH_CHUNK;
+            let len = out.len();
+            let (x, y, z) = (
+                &x[start..start + len],
+                &y[start..start + len],
+                &z[start..start + len],
+            );
+            for (scaled, coords) in [(&mut x2, x), (&mut y2, y), (&mut z2, z)] {
+                for (scaled, &coord) in scaled.iter_mut().zip(coords) {
+                    *scaled = coord * INPUT_FACTOR;
+                }
+            }
+
+            self.first.get_values(x, y, z, out);
+            self.second
+                .get_values(&x2[..len], &y2[..len], &z2[..len], &mut second[..len]);
+            for (out, &second) in out.iter_mut().zip(&second[..len]) {
+                *out = (*out + second) * self.value_factor;
+            }
+        }
+    }
+
     /// Get the maximum possible output value.
     #[inline]
     #[must_use]
@@ -222,6 +266,26 @@ mod tests {
         assert!((v1 - v2).abs() > 0.0001);
     }
 
+    #[test]
+    fn test_get_values_matches_scalar() {
+        let mut rng = Xoroshiro::from_seed(2024);
+        let splitter = rng.next_positional();
+        let noise =
+            NormalNoise::create(&splitter, "test_noise", -9, &[1.5, 0.0, 1.0, 0.0, 0.0, 0.0]);
+
+        // Longer than one scratch chunk, with a partial chunk at the end
+        let len = BATCH_CHUNK * 2 + 17;
+        let x: Vec<f64> = (0..len).map(|i| i as f64 * 4.0 - 300.0).collect();
+        let y: Vec<f64> = (0..len).map(|i| (i % 7) as f64 * 4.0).collect();
+        let z: Vec<f64> = (0..len).map(|i| i as f64 * -8.0 + 1000.0).collect();
+        let mut out = vec![0.0; len];
+        noise.get_values(&x, &y, &z, &mut out);
+
+        for (i, value) in out.iter().enumerate() {
+            assert_eq!(value.to_bits(), noise.get_value(x[i], y[i], z[i]).to_bits());
+        }
+    }
+
     #[test]
     fn test_expected_deviation() {
         // Check the formula produces expected values
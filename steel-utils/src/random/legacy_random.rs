@@ -28,7 +28,7 @@ impl LegacyRandom {
         }
     }
 
-    const fn next(&mut self, bits: u64) -> i32 {
+    pub(crate) const fn next(&mut self, bits: u64) -> i32 {
         (self.next_random() >> (48 - bits)) as i32
     }
 
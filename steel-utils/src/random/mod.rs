@@ -13,6 +13,8 @@ pub mod gaussian;
 pub mod legacy_random;
 /// Precomputed name hashes for positional random seeding.
 pub mod name_hash;
+/// Positional seeding for decoration, carvers and structure placement.
+pub mod worldgen_random;
 /// This module contains the xoroshiro random number generator.
 pub mod xoroshiro;
 
@@ -82,6 +84,18 @@ pub enum RandomSource {
     Legacy(LegacyRandom),
 }
 
+impl RandomSource {
+    /// Reseeds the random in place, keeping its algorithm.
+    ///
+    /// Vanilla: `RandomSource.setSeed()`.
+    pub const fn set_seed(&mut self, seed: u64) {
+        *self = match self {
+            Self::Xoroshiro(_) => Self::Xoroshiro(Xoroshiro::from_seed(seed)),
+            Self::Legacy(_) => Self::Legacy(LegacyRandom::from_seed(seed)),
+        };
+    }
+}
+
 /// A random number generator that can be split.
 #[derive(Clone)]
 #[enum_dispatch(PositionalRandom)]
@@ -92,6 +106,14 @@ pub enum RandomSplitter {
     Legacy(LegacyRandomSplitter),
 }
 
+/// Shuffles `list` in place, consuming the random exactly like vanilla's `Util.shuffle`.
+pub fn shuffle<T>(list: &mut [T], random: &mut impl Random) {
+    for i in (2..=list.len()).rev() {
+        let swap_to = random.next_i32_bounded(i as i32) as usize;
+        list.swap(i - 1, swap_to);
+    }
+}
+
 /// Gets a seed from a position.
 #[must_use]
 pub fn get_seed(x: i32, y: i32, z: i32) -> i64 {
@@ -0,0 +1,292 @@
+//! The reseedable random used by world generation.
+//!
+//! Decoration, carvers and structure placement all reseed one random per chunk
+//! from the level seed, so the same seed always generates the same world.
+
+use crate::random::{
+    Random, RandomSource, RandomSplitter, gaussian::MarsagliaPolarGaussian,
+    legacy_random::LegacyRandom,
+};
+
+/// Salt mixed into the seed of slime chunks.
+pub const SLIME_CHUNK_SALT: i64 = 987_234_911;
+
+/// A random that can be reseeded from a chunk position.
+///
+/// All values are drawn with the legacy (`java.util.Random`) algorithms on top
+/// of bits from the wrapped source, like vanilla's `WorldgenRandom`.
+pub struct WorldgenRandom {
+    source: RandomSource,
+    count: i32,
+    next_gaussian: Option<f64>,
+}
+
+impl WorldgenRandom {
+    /// Wraps `source`. Its seed is replaced by the first `set_*_seed` call.
+    #[must_use]
+    pub const fn new(source: RandomSource) -> Self {
+        Self {
+            source,
+            count: 0,
+            next_gaussian: None,
+        }
+    }
+
+    /// Gets how many times bits were drawn from the wrapped source.
+    #[must_use]
+    pub const fn count(&self) -> i32 {
+        self.count
+    }
+
+    /// Reseeds the wrapped source.
+    pub const fn set_seed(&mut self, seed: i64) {
+        self.source.set_seed(seed as u64);
+    }
+
+    /// Seeds the random for decorating the chunk whose minimum block corner is
+    /// at `min_x`, `min_z`, and returns the seed for `set_feature_seed()`.
+    ///
+    /// Vanilla: `WorldgenRandom.setDecorationSeed()`.
+    pub fn set_decoration_seed(&mut self, level_seed: i64, min_x: i32, min_z: i32) -> i64 {
+        self.set_seed(level_seed);
+        let x_scale = self.next_i64() | 1;
+        let z_scale = self.next_i64() | 1;
+        let seed = (i64::from(min_x)
+            .wrapping_mul(x_scale)
+            .wrapping_add(i64::from(min_z).wrapping_mul(z_scale)))
+            ^ level_seed;
+        self.set_seed(seed);
+        seed
+    }
+
+    /// Seeds the random for the `index`th feature of generation step `step`.
+    ///
+    /// Vanilla: `WorldgenRandom.setFeatureSeed()`.
+    pub const fn set_feature_seed(&mut self, decoration_seed: i64, index: i32, step: i32) {
+        let seed = decoration_seed
+            .wrapping_add(index as i64)
+            .wrapping_add(10_000_i32.wrapping_mul(step) as i64);
+        self.set_seed(seed);
+    }
+
+    /// Seeds the random for carving or starting structures in a chunk.
+    ///
+    /// Carvers pass the level seed plus the carver's index as `seed`.
+    /// Vanilla: `WorldgenRandom.setLargeFeatureSeed()`.
+    pub fn set_large_feature_seed(&mut self, seed: i64, chunk_x: i32, chunk_z: i32) {
+        self.set_seed(seed);
+        let x_scale = self.next_i64();
+        let z_scale = self.next_i64();
+        let seed = i64::from(chunk_x).wrapping_mul(x_scale)
+            ^ i64::from(chunk_z).wrapping_mul(z_scale)
+            ^ seed;
+        self.set_seed(seed);
+    }
+
+    /// Seeds the random for a structure placement's region at `x`, `z`.
+    ///
+    /// Vanilla: `WorldgenRandom.setLargeFeatureWithSalt()`.
+    pub const fn set_large_feature_with_salt(
+        &mut self,
+        level_seed: i64,
+        x: i32,
+        z: i32,
+        salt: i32,
+    ) {
+        let seed = (x as i64)
+            .wrapping_mul(341_873_128_712)
+            .wrapping_add((z as i64).wrapping_mul(132_897_987_541))
+            .wrapping_add(level_seed)
+            .wrapping_add(salt as i64);
+        self.set_seed(seed);
+    }
+
+    /// Creates the random that decides whether a chunk is a slime chunk.
+    ///
+    /// Vanilla: `WorldgenRandom.seedSlimeChunk()`.
+    #[must_use]
+    pub const fn seed_slime_chunk(
+        chunk_x: i32,
+        chunk_z: i32,
+        level_seed: i64,
+        salt: i64,
+    ) -> LegacyRandom {
+        let seed = level_seed
+            .wrapping_add(chunk_x.wrapping_mul(chunk_x).wrapping_mul(4_987_142) as i64)
+            .wrapping_add(chunk_x.wrapping_mul(5_947_611) as i64)
+            .wrapping_add((chunk_z.wrapping_mul(chunk_z) as i64).wrapping_mul(4_392_871))
+            .wrapping_add(chunk_z.wrapping_mul(389_711) as i64)
+            ^ salt;
+        LegacyRandom::from_seed(seed as u64)
+    }
+
+    /// Forks the wrapped source, as vanilla's `WorldgenRandom.fork()` does.
+    #[must_use]
+    pub fn fork_source(&mut self) -> RandomSource {
+        self.source.fork()
+    }
+
+    fn next(&mut self, bits: u64) -> i32 {
+        self.count += 1;
+        match &mut self.source {
+            RandomSource::Legacy(legacy) => legacy.next(bits),
+            RandomSource::Xoroshiro(xoroshiro) => {
+                ((xoroshiro.next_i64() as u64) >> (64 - bits)) as i32
+            }
+        }
+    }
+}
+
+impl MarsagliaPolarGaussian for WorldgenRandom {
+    fn stored_next_gaussian(&self) -> Option<f64> {
+        self.next_gaussian
+    }
+
+    fn set_stored_next_gaussian(&mut self, value: Option<f64>) {
+        self.next_gaussian = value;
+    }
+}
+
+impl Random for WorldgenRandom {
+    /// Wraps a fork of the source. Use `fork_source()` to match vanilla.
+    fn fork(&mut self) -> Self {
+        Self::new(self.fork_source())
+    }
+
+    fn next_i32(&mut self) -> i32 {
+        self.next(32)
+    }
+
+    fn next_i32_bounded(&mut self, bound: i32) -> i32 {
+        if bound & bound.wrapping_sub(1) == 0 {
+            (i64::from(bound).wrapping_mul(i64::from(self.next(31))) >> 31) as i32
+        } else {
+            loop {
+                let i = self.next(31);
+                let j = i % bound;
+                if i.wrapping_sub(j).wrapping_add(bound.wrapping_sub(1)) >= 0 {
+                    return j;
+                }
+            }
+        }
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        let i = self.next_i32();
+        let j = self.next_i32();
+        (i64::from(i) << 32).wrapping_add(i64::from(j))
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.next(24) as f32 * 5.960_464_5e-8_f32
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        let combined = (i64::from(self.next(26)) << 27) + i64::from(self.next(27));
+        combined as f64 * (1.0 / (1_i64 << 53) as f64)
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next(1) != 0
+    }
+
+    fn next_gaussian(&mut self) -> f64 {
+        self.calculate_gaussian()
+    }
+
+    fn next_positional(&mut self) -> RandomSplitter {
+        self.source.next_positional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SLIME_CHUNK_SALT, WorldgenRandom};
+    use crate::random::{Random, RandomSource, legacy_random::LegacyRandom, xoroshiro::Xoroshiro};
+
+    fn legacy() -> WorldgenRandom {
+        WorldgenRandom::new(RandomSource::Legacy(LegacyRandom::from_seed(0)))
+    }
+
+    fn xoroshiro() -> WorldgenRandom {
+        WorldgenRandom::new(RandomSource::Xoroshiro(Xoroshiro::from_seed(0)))
+    }
+
+    #[test]
+    fn decoration_seed_matches_java() {
+        let mut rand = legacy();
+        assert_eq!(
+            rand.set_decoration_seed(12345, 16, 32),
+            -6_555_216_214_193_597_943
+        );
+        for expected in [135_619_417, -1_235_775_730, -1_750_720_849] {
+            assert_eq!(rand.next_i32(), expected);
+        }
+
+        let mut rand = xoroshiro();
+        assert_eq!(
+            rand.set_decoration_seed(12345, 16, 32),
+            2_704_396_635_230_373_609
+        );
+        for expected in [-442_956_178, 1_633_671_166, 1_832_136_327] {
+            assert_eq!(rand.next_i32(), expected);
+        }
+    }
+
+    #[test]
+    fn feature_seed_offsets_decoration_seed() {
+        let mut rand = xoroshiro();
+        rand.set_feature_seed(1000, 3, 2);
+        let mut expected = xoroshiro();
+        expected.set_seed(21_003);
+        assert_eq!(rand.next_i64(), expected.next_i64());
+    }
+
+    #[test]
+    fn large_feature_seed_matches_java() {
+        let mut rand = legacy();
+        rand.set_large_feature_seed(12345, 3, -7);
+        for expected in [55, 37, 7, 1, 93] {
+            assert_eq!(rand.next_i32_bounded(100), expected);
+        }
+
+        let mut rand = xoroshiro();
+        rand.set_large_feature_seed(12345, 3, -7);
+        for expected in [31, 67, 68, 23, 24] {
+            assert_eq!(rand.next_i32_bounded(100), expected);
+        }
+    }
+
+    #[test]
+    fn large_feature_with_salt_matches_java() {
+        let mut rand = legacy();
+        rand.set_large_feature_with_salt(12345, 10, -4, 14_357_617);
+        for expected in [22, 19, 18, 2] {
+            assert_eq!(rand.next_i32_bounded(24), expected);
+        }
+
+        let mut rand = xoroshiro();
+        rand.set_large_feature_with_salt(12345, 10, -4, 14_357_617);
+        for expected in [11, 16, 2, 10] {
+            assert_eq!(rand.next_i32_bounded(24), expected);
+        }
+    }
+
+    #[test]
+    fn slime_chunks_match_java() {
+        let rolls: Vec<i32> = (-3..12)
+            .map(|x| {
+                WorldgenRandom::seed_slime_chunk(x, 0, 12345, SLIME_CHUNK_SALT).next_i32_bounded(10)
+            })
+            .collect();
+        assert_eq!(rolls, [1, 7, 6, 4, 6, 8, 0, 3, 9, 1, 2, 0, 0, 4, 9]);
+    }
+
+    #[test]
+    fn counts_drawn_bits() {
+        let mut rand = legacy();
+        rand.next_i64();
+        rand.next_i32_bounded(16);
+        assert_eq!(rand.count(), 3);
+    }
+}
@@ -40,6 +40,34 @@ impl Xoroshiro {
         Self::new(lo, hi)
     }
 
+    /// Creates a `Xoroshiro` for a named random sequence, mixing the MD5 hash of
+    /// `name` into the seed when one is given.
+    ///
+    /// Vanilla: `RandomSequence.createSequence()`.
+    #[must_use]
+    pub fn for_sequence(seed: u64, name: Option<&str>) -> Self {
+        let (mut lo, mut hi) = Self::upgrade_seed_to_128_bit(seed);
+        if let Some(name) = name {
+            // Vanilla: RandomSupport.seedFromHashOf()
+            let hash = u128::from_be_bytes(md5::compute(name.as_bytes()).0);
+            lo ^= (hash >> 64) as u64;
+            hi ^= hash as u64;
+        }
+        Self::new(mix_stafford_13(lo), mix_stafford_13(hi))
+    }
+
+    /// Restores a `Xoroshiro` from the state returned by [`Self::state`].
+    #[must_use]
+    pub const fn from_state(seed_lo: u64, seed_hi: u64) -> Self {
+        Self::new(seed_lo, seed_hi)
+    }
+
+    /// Returns the generator's state, for saving it.
+    #[must_use]
+    pub const fn state(&self) -> (u64, u64) {
+        (self.seed_lo, self.seed_hi)
+    }
+
     const fn new(lo: u64, hi: u64) -> Self {
         let (lo, hi) = if (lo | hi) == 0 {
             (GOLDEN_RATIO_64, SILVER_RATIO_64)
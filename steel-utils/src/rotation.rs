@@ -0,0 +1,252 @@
+//! Rotating and mirroring around the Y axis, used to place structures.
+
+use crate::{Direction, math::Axis};
+
+/// A rotation around the Y axis in steps of 90 degrees.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// No rotation.
+    #[default]
+    None,
+    /// 90 degrees clockwise, seen from above.
+    Clockwise90,
+    /// 180 degrees.
+    Clockwise180,
+    /// 90 degrees counter-clockwise, seen from above.
+    Counterclockwise90,
+}
+
+impl Rotation {
+    /// All rotations, in vanilla's order.
+    pub const VALUES: [Rotation; 4] = [
+        Rotation::None,
+        Rotation::Clockwise90,
+        Rotation::Clockwise180,
+        Rotation::Counterclockwise90,
+    ];
+
+    /// Gets the number of clockwise quarter turns.
+    #[must_use]
+    pub const fn quarter_turns(self) -> i32 {
+        match self {
+            Rotation::None => 0,
+            Rotation::Clockwise90 => 1,
+            Rotation::Clockwise180 => 2,
+            Rotation::Counterclockwise90 => 3,
+        }
+    }
+
+    /// Gets the rotation of `turns` clockwise quarter turns.
+    #[must_use]
+    pub const fn from_quarter_turns(turns: i32) -> Rotation {
+        Self::VALUES[turns.rem_euclid(4) as usize]
+    }
+
+    /// Applies `other` after this rotation.
+    ///
+    /// Vanilla: `Rotation.getRotated()`.
+    #[must_use]
+    pub const fn then(self, other: Rotation) -> Rotation {
+        Self::from_quarter_turns(self.quarter_turns() + other.quarter_turns())
+    }
+
+    /// Rotates a direction. Vertical directions are unchanged.
+    ///
+    /// Vanilla: `Rotation.rotate(Direction)`.
+    #[must_use]
+    pub const fn rotate(self, direction: Direction) -> Direction {
+        match self {
+            Rotation::None => direction,
+            Rotation::Clockwise90 => direction.rotate_y_clockwise(),
+            Rotation::Clockwise180 => direction.rotate_y_clockwise().rotate_y_clockwise(),
+            Rotation::Counterclockwise90 => direction.rotate_y_counter_clockwise(),
+        }
+    }
+
+    /// Rotates one of `count` segments of a full turn, like the 16 rotations of a sign.
+    ///
+    /// Vanilla: `Rotation.rotate(int, int)`.
+    #[must_use]
+    pub const fn rotate_segment(self, segment: i32, count: i32) -> i32 {
+        match self {
+            Rotation::None => segment,
+            Rotation::Clockwise90 => (segment + count / 4) % count,
+            Rotation::Clockwise180 => (segment + count / 2) % count,
+            Rotation::Counterclockwise90 => (segment + count * 3 / 4) % count,
+        }
+    }
+
+    /// Gets the name used in commands and NBT.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Rotation::None => "none",
+            Rotation::Clockwise90 => "clockwise_90",
+            Rotation::Clockwise180 => "180",
+            Rotation::Counterclockwise90 => "counterclockwise_90",
+        }
+    }
+
+    /// Gets the rotation with the given name.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Rotation> {
+        Self::VALUES
+            .into_iter()
+            .find(|rotation| rotation.as_str() == name)
+    }
+
+    /// Gets the name vanilla's structure blocks save the rotation with.
+    #[must_use]
+    pub const fn nbt_name(self) -> &'static str {
+        match self {
+            Rotation::None => "NONE",
+            Rotation::Clockwise90 => "CLOCKWISE_90",
+            Rotation::Clockwise180 => "CLOCKWISE_180",
+            Rotation::Counterclockwise90 => "COUNTERCLOCKWISE_90",
+        }
+    }
+
+    /// Gets the rotation with the given structure block NBT name.
+    #[must_use]
+    pub fn by_nbt_name(name: &str) -> Option<Rotation> {
+        Self::VALUES
+            .into_iter()
+            .find(|rotation| rotation.nbt_name() == name)
+    }
+}
+
+/// A mirror across a vertical plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Mirror {
+    /// No mirroring.
+    #[default]
+    None,
+    /// Flips the Z axis.
+    LeftRight,
+    /// Flips the X axis.
+    FrontBack,
+}
+
+impl Mirror {
+    /// All mirrors, in vanilla's order.
+    pub const VALUES: [Mirror; 3] = [Mirror::None, Mirror::LeftRight, Mirror::FrontBack];
+
+    /// Mirrors a direction.
+    ///
+    /// Vanilla: `Mirror.mirror(Direction)`.
+    #[must_use]
+    pub const fn mirror(self, direction: Direction) -> Direction {
+        match (self, direction.get_axis()) {
+            (Mirror::FrontBack, Axis::X) | (Mirror::LeftRight, Axis::Z) => direction.opposite(),
+            _ => direction,
+        }
+    }
+
+    /// Gets the rotation that mirrors a block facing `direction`.
+    ///
+    /// Vanilla: `Mirror.getRotation()`.
+    #[must_use]
+    pub const fn rotation_for(self, direction: Direction) -> Rotation {
+        match (self, direction.get_axis()) {
+            (Mirror::FrontBack, Axis::X) | (Mirror::LeftRight, Axis::Z) => Rotation::Clockwise180,
+            _ => Rotation::None,
+        }
+    }
+
+    /// Mirrors one of `count` segments of a full turn, like the 16 rotations of a sign.
+    ///
+    /// Vanilla: `Mirror.mirror(int, int)`.
+    #[must_use]
+    pub const fn mirror_segment(self, segment: i32, count: i32) -> i32 {
+        let half = count / 2;
+        let relative = if segment > half {
+            segment - count
+        } else {
+            segment
+        };
+        match self {
+            Mirror::None => segment,
+            Mirror::FrontBack => (count - relative) % count,
+            Mirror::LeftRight => (half - relative + count) % count,
+        }
+    }
+
+    /// Gets the name used in commands.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Mirror::None => "none",
+            Mirror::LeftRight => "left_right",
+            Mirror::FrontBack => "front_back",
+        }
+    }
+
+    /// Gets the mirror with the given name.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Mirror> {
+        Self::VALUES
+            .into_iter()
+            .find(|mirror| mirror.as_str() == name)
+    }
+
+    /// Gets the name vanilla's structure blocks save the mirror with.
+    #[must_use]
+    pub const fn nbt_name(self) -> &'static str {
+        match self {
+            Mirror::None => "NONE",
+            Mirror::LeftRight => "LEFT_RIGHT",
+            Mirror::FrontBack => "FRONT_BACK",
+        }
+    }
+
+    /// Gets the mirror with the given structure block NBT name.
+    #[must_use]
+    pub fn by_nbt_name(name: &str) -> Option<Mirror> {
+        Self::VALUES
+            .into_iter()
+            .find(|mirror| mirror.nbt_name() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mirror, Rotation};
+    use crate::Direction;
+
+    #[test]
+    fn rotations_compose() {
+        assert_eq!(
+            Rotation::Clockwise90.then(Rotation::Counterclockwise90),
+            Rotation::None
+        );
+        assert_eq!(
+            Rotation::Clockwise180.then(Rotation::Counterclockwise90),
+            Rotation::Clockwise90
+        );
+        assert_eq!(
+            Rotation::Clockwise180.rotate(Direction::East),
+            Direction::West
+        );
+    }
+
+    #[test]
+    fn segments_match_java() {
+        assert_eq!(Rotation::Clockwise90.rotate_segment(14, 16), 2);
+        assert_eq!(Rotation::Counterclockwise90.rotate_segment(1, 16), 13);
+        assert_eq!(Mirror::FrontBack.mirror_segment(4, 16), 12);
+        assert_eq!(Mirror::LeftRight.mirror_segment(4, 16), 4);
+        assert_eq!(Mirror::LeftRight.mirror_segment(0, 16), 8);
+        assert_eq!(Mirror::LeftRight.mirror_segment(12, 16), 12);
+    }
+
+    #[test]
+    fn mirror_flips_its_axis() {
+        assert_eq!(Mirror::LeftRight.mirror(Direction::North), Direction::South);
+        assert_eq!(Mirror::LeftRight.mirror(Direction::East), Direction::East);
+        assert_eq!(Mirror::FrontBack.mirror(Direction::East), Direction::West);
+        assert_eq!(
+            Mirror::FrontBack.rotation_for(Direction::West),
+            Rotation::Clockwise180
+        );
+    }
+}
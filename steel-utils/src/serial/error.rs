@@ -0,0 +1,122 @@
+use std::{
+    io::{self, ErrorKind},
+    string::FromUtf8Error,
+};
+
+use thiserror::Error;
+
+/// An error that can occur while decoding data with [`super::ReadFrom`] or [`super::PrefixedRead`].
+///
+/// Field context is attached by the `ReadFrom` derive macro, so a failure deep inside
+/// a packet reports the full path to the offending field (e.g. `SSignUpdate.lines`).
+#[derive(Error, Debug)]
+pub enum ReadError {
+    /// The input ended before the value was fully read.
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    /// A `VarInt` or `VarLong` used more bytes than allowed.
+    #[error("{0} is too long")]
+    VarIntTooLong(&'static str),
+    /// A length prefix was negative or did not fit into `usize`.
+    #[error("invalid length prefix")]
+    InvalidLength,
+    /// A length prefix exceeded the maximum allowed for the field.
+    #[error("length {len} exceeds bound {bound}")]
+    TooLong {
+        /// The length read from the input.
+        len: usize,
+        /// The maximum allowed length.
+        bound: usize,
+    },
+    /// A string was not valid UTF-8.
+    #[error("invalid utf-8: {0}")]
+    InvalidUtf8(#[from] FromUtf8Error),
+    /// An enum discriminant did not match any known variant.
+    #[error("invalid {kind} discriminant {value}")]
+    InvalidDiscriminant {
+        /// The name of the enum being read.
+        kind: &'static str,
+        /// The discriminant that was read.
+        value: i128,
+    },
+    /// A value was read successfully but is not valid for its type.
+    #[error("malformed value: {0}")]
+    Malformed(String),
+    /// An underlying I/O error other than running out of input.
+    #[error("io error: {0}")]
+    Io(io::Error),
+    /// Wraps an error that happened while reading a named field.
+    #[error("{field}: {source}")]
+    Field {
+        /// The `Type.field` path of the field being read.
+        field: &'static str,
+        /// The error that occurred while reading the field.
+        source: Box<ReadError>,
+    },
+}
+
+impl ReadError {
+    /// Wraps this error with the name of the field it occurred in.
+    #[must_use]
+    pub fn in_field(self, field: &'static str) -> Self {
+        Self::Field {
+            field,
+            source: Box::new(self),
+        }
+    }
+
+    /// Runs `read` and attaches `field` as context to any error it returns.
+    ///
+    /// Used by the `ReadFrom` derive macro for every named field.
+    pub fn read_field<T>(
+        field: &'static str,
+        read: impl FnOnce() -> Result<T, Self>,
+    ) -> Result<T, Self> {
+        read().map_err(|err| err.in_field(field))
+    }
+
+    /// Creates a [`ReadError::Malformed`] from anything displayable.
+    pub fn malformed(msg: impl ToString) -> Self {
+        Self::Malformed(msg.to_string())
+    }
+
+    /// Checks a decoded length against the maximum allowed for a field.
+    ///
+    /// # Errors
+    /// - If `len` is larger than `bound`.
+    pub const fn check_len(len: usize, bound: usize) -> Result<usize, Self> {
+        if len > bound {
+            return Err(Self::TooLong { len, bound });
+        }
+        Ok(len)
+    }
+
+    /// Returns the innermost error, skipping over any field context.
+    #[must_use]
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            Self::Field { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(value: io::Error) -> Self {
+        if value.kind() == ErrorKind::UnexpectedEof {
+            Self::UnexpectedEof
+        } else {
+            Self::Io(value)
+        }
+    }
+}
+
+impl From<ReadError> for io::Error {
+    fn from(value: ReadError) -> Self {
+        match value {
+            ReadError::UnexpectedEof => Self::from(ErrorKind::UnexpectedEof),
+            ReadError::Io(err) => err,
+            other => Self::new(ErrorKind::InvalidData, other),
+        }
+    }
+}
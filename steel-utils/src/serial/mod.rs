@@ -1,6 +1,8 @@
 //! This module contains traits for serializing and deserializing data.
-use std::io::{Cursor, Result, Write};
+use std::io::{self, Cursor, Write};
 
+/// Typed errors for the read path.
+pub mod error;
 /// A module for reading prefixed data.
 pub mod prefixed_read;
 /// A module for writing prefixed data.
@@ -10,32 +12,39 @@ pub mod read;
 /// A module for writing data.
 pub mod write;
 
+pub use error::ReadError;
 pub use write::OptionalNbt;
 
-const DEFAULT_BOUND: usize = i16::MAX as _;
+/// The bound used by [`PrefixedRead::read_prefixed`] when none is given.
+pub const DEFAULT_BOUND: usize = i16::MAX as _;
 
 /// A trait for reading data from a cursor.
 pub trait ReadFrom: Sized {
     /// Reads data from a cursor.
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self>;
+    ///
+    /// # Errors
+    /// - If the input is truncated or contains a value that is invalid for `Self`.
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError>;
 }
 
 /// A trait for writing data to a writer.
 pub trait WriteTo {
     /// Writes data to a writer.
-    fn write(&self, writer: &mut impl Write) -> Result<()>;
+    fn write(&self, writer: &mut impl Write) -> io::Result<()>;
 }
 
 /// A trait for reading prefixed data from a cursor.
 pub trait PrefixedRead: Sized {
     /// Reads prefixed data from a cursor with a bound.
-    fn read_prefixed_bound<P: TryInto<usize> + ReadFrom>(
+    fn read_prefixed_bound<P: Into<i32> + ReadFrom>(
         data: &mut Cursor<&[u8]>,
         bound: usize,
-    ) -> Result<Self>;
+    ) -> Result<Self, ReadError>;
 
     /// Reads prefixed data from a cursor.
-    fn read_prefixed<P: TryInto<usize> + ReadFrom>(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read_prefixed<P: Into<i32> + ReadFrom>(
+        data: &mut Cursor<&[u8]>,
+    ) -> Result<Self, ReadError> {
         Self::read_prefixed_bound::<P>(data, DEFAULT_BOUND)
     }
 }
@@ -47,10 +56,13 @@ pub trait PrefixedWrite {
         &self,
         writer: &mut impl Write,
         bound: usize,
-    ) -> Result<()>;
+    ) -> io::Result<()>;
 
     /// Writes prefixed data to a writer.
-    fn write_prefixed<P: TryFrom<usize> + WriteTo>(&self, writer: &mut impl Write) -> Result<()> {
+    fn write_prefixed<P: TryFrom<usize> + WriteTo>(
+        &self,
+        writer: &mut impl Write,
+    ) -> io::Result<()> {
         self.write_prefixed_bound::<P>(writer, DEFAULT_BOUND)
     }
 }
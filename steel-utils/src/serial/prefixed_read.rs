@@ -1,39 +1,49 @@
-use std::io::{Cursor, Error, Read, Result};
+use std::io::{Cursor, Read};
 
-use crate::serial::{PrefixedRead, ReadFrom};
+use crate::serial::{PrefixedRead, ReadError, ReadFrom};
+
+/// Reads a length prefix of type `P` and checks it against `bound`.
+fn read_len<P: Into<i32> + ReadFrom>(
+    data: &mut Cursor<&[u8]>,
+    bound: usize,
+) -> Result<usize, ReadError> {
+    let len = usize::try_from(P::read(data)?.into()).map_err(|_| ReadError::InvalidLength)?;
+    ReadError::check_len(len, bound)
+}
+
+/// Returns how many bytes are left to read in `data`.
+///
+/// Used to cap pre-allocations so a forged length prefix can't make us reserve
+/// more memory than the packet could possibly fill.
+fn remaining(data: &Cursor<&[u8]>) -> usize {
+    data.get_ref()
+        .len()
+        .saturating_sub(data.position() as usize)
+}
 
 impl PrefixedRead for String {
-    fn read_prefixed_bound<P: TryInto<usize> + ReadFrom>(
+    fn read_prefixed_bound<P: Into<i32> + ReadFrom>(
         data: &mut Cursor<&[u8]>,
         bound: usize,
-    ) -> Result<Self> {
-        let len: usize = P::read(data)?
-            .try_into()
-            .map_err(|_| Error::other("Invalid Prefix"))?;
-
-        if len > bound {
-            Err(Error::other("To long"))?;
+    ) -> Result<Self, ReadError> {
+        let len = read_len::<P>(data, bound)?;
+        if len > remaining(data) {
+            return Err(ReadError::UnexpectedEof);
         }
 
         let mut buf = vec![0; len];
         data.read_exact(&mut buf)?;
-        String::from_utf8(buf).map_err(Error::other)
+        Ok(String::from_utf8(buf)?)
     }
 }
 
 impl<T: ReadFrom> PrefixedRead for Vec<T> {
-    fn read_prefixed_bound<P: TryInto<usize> + ReadFrom>(
+    fn read_prefixed_bound<P: Into<i32> + ReadFrom>(
         data: &mut Cursor<&[u8]>,
         bound: usize,
-    ) -> Result<Self> {
-        let len: usize = P::read(data)?
-            .try_into()
-            .map_err(|_| Error::other("Invalid Prefix"))?;
-
-        if len > bound {
-            Err(Error::other("To long"))?;
-        }
-        let mut items = Vec::with_capacity(len);
+    ) -> Result<Self, ReadError> {
+        let len = read_len::<P>(data, bound)?;
+        let mut items = Vec::with_capacity(len.min(remaining(data)));
         for _ in 0..len {
             items.push(T::read(data)?);
         }
@@ -42,10 +52,10 @@ impl<T: ReadFrom> PrefixedRead for Vec<T> {
 }
 
 impl<T: PrefixedRead> PrefixedRead for Option<T> {
-    fn read_prefixed_bound<P: TryInto<usize> + ReadFrom>(
+    fn read_prefixed_bound<P: Into<i32> + ReadFrom>(
         data: &mut Cursor<&[u8]>,
         bound: usize,
-    ) -> Result<Self> {
+    ) -> Result<Self, ReadError> {
         if bool::read(data)? {
             Ok(Some(T::read_prefixed_bound::<P>(data, bound)?))
         } else {
@@ -53,3 +63,47 @@ impl<T: PrefixedRead> PrefixedRead for Option<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codec::VarInt, serial::WriteTo};
+
+    fn encode_len(len: i32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        VarInt(len).write(&mut buf).expect("write failed");
+        buf
+    }
+
+    #[test]
+    fn string_over_bound_is_rejected() {
+        let mut buf = encode_len(5);
+        buf.extend_from_slice(b"hello");
+        let result = String::read_prefixed_bound::<VarInt>(&mut Cursor::new(buf.as_slice()), 4);
+        assert!(matches!(
+            result,
+            Err(ReadError::TooLong { len: 5, bound: 4 })
+        ));
+    }
+
+    #[test]
+    fn negative_prefix_is_rejected() {
+        let buf = encode_len(-1);
+        let result = Vec::<u8>::read_prefixed::<VarInt>(&mut Cursor::new(buf.as_slice()));
+        assert!(matches!(result, Err(ReadError::InvalidLength)));
+
+        let buf = encode_len(i32::MIN);
+        let result = String::read_prefixed::<VarInt>(&mut Cursor::new(buf.as_slice()));
+        assert!(matches!(result, Err(ReadError::InvalidLength)));
+    }
+
+    #[test]
+    fn forged_length_fails_without_panicking() {
+        let buf = encode_len(30_000);
+        let result = Vec::<u64>::read_prefixed::<VarInt>(&mut Cursor::new(buf.as_slice()));
+        assert!(matches!(result, Err(ReadError::UnexpectedEof)));
+
+        let result = String::read_prefixed::<VarInt>(&mut Cursor::new(buf.as_slice()));
+        assert!(matches!(result, Err(ReadError::UnexpectedEof)));
+    }
+}
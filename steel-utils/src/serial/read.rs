@@ -1,26 +1,28 @@
 use std::{
     array,
-    io::{Cursor, Error, Read, Result},
+    io::{Cursor, Read},
     str::FromStr,
 };
 
+use simdnbt::owned::{NbtCompound, NbtTag};
 use uuid::Uuid;
 
 use crate::{
     Identifier,
     codec::VarInt,
-    serial::{PrefixedRead, ReadFrom},
+    nbt::{NbtError, NbtLimits, binary::read_nameless},
+    serial::{PrefixedRead, ReadError, ReadFrom},
 };
 
 impl ReadFrom for bool {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let byte = u8::read(data)?;
         Ok(byte == 1)
     }
 }
 
 impl ReadFrom for u8 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -28,7 +30,7 @@ impl ReadFrom for u8 {
 }
 
 impl ReadFrom for u16 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -36,7 +38,7 @@ impl ReadFrom for u16 {
 }
 
 impl ReadFrom for u32 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -44,7 +46,7 @@ impl ReadFrom for u32 {
 }
 
 impl ReadFrom for u64 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -52,7 +54,7 @@ impl ReadFrom for u64 {
 }
 
 impl ReadFrom for i8 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -60,7 +62,7 @@ impl ReadFrom for i8 {
 }
 
 impl ReadFrom for i16 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -68,7 +70,7 @@ impl ReadFrom for i16 {
 }
 
 impl ReadFrom for i32 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -76,7 +78,7 @@ impl ReadFrom for i32 {
 }
 
 impl ReadFrom for i64 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -84,7 +86,7 @@ impl ReadFrom for i64 {
 }
 
 impl ReadFrom for f32 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -92,7 +94,7 @@ impl ReadFrom for f32 {
 }
 
 impl ReadFrom for f64 {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let mut buf = [0; size_of::<Self>()];
         data.read_exact(&mut buf)?;
         Ok(Self::from_be_bytes(buf))
@@ -100,7 +102,7 @@ impl ReadFrom for f64 {
 }
 
 impl<T: ReadFrom> ReadFrom for Option<T> {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         if bool::read(data)? {
             Ok(Some(T::read(data)?))
         } else {
@@ -110,13 +112,13 @@ impl<T: ReadFrom> ReadFrom for Option<T> {
 }
 
 impl<T: ReadFrom, const N: usize> ReadFrom for [T; N] {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         array::try_from_fn(|_| T::read(data))
     }
 }
 
 impl ReadFrom for Uuid {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let most_significant_bits = u64::read(data)?;
         let least_significant_bits = u64::read(data)?;
 
@@ -128,7 +130,21 @@ impl ReadFrom for Uuid {
 }
 
 impl ReadFrom for Identifier {
-    fn read(data: &mut Cursor<&[u8]>) -> Result<Self> {
-        Identifier::from_str(&String::read_prefixed::<VarInt>(data)?).map_err(Error::other)
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        Identifier::from_str(&String::read_prefixed::<VarInt>(data)?).map_err(ReadError::malformed)
+    }
+}
+
+/// Network format: a nameless compound tag (type byte followed by the payload).
+impl ReadFrom for NbtCompound {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
+        let tag = read_nameless(data, NbtLimits::NETWORK).map_err(|e| match e {
+            NbtError::UnexpectedEof => ReadError::UnexpectedEof,
+            e => ReadError::malformed(format!("Failed to read NBT: {e}")),
+        })?;
+        match tag {
+            Some(NbtTag::Compound(compound)) => Ok(compound),
+            _ => Err(ReadError::malformed("Expected an NBT compound")),
+        }
     }
 }
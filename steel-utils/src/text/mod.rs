@@ -1,11 +1,13 @@
 //! This module contains everything related to text components.
+pub mod translation;
+
 use crate::{
     hash::{ComponentHasher, HashComponent, HashEntry, sort_map_entries},
-    serial::ReadFrom,
+    serial::{ReadError, ReadFrom},
     translations_registry::TRANSLATIONS,
 };
 use simdnbt::owned::read_tag;
-use std::io::{self, Cursor};
+use std::io::Cursor;
 use text_components::{
     TextComponent,
     content::{Content, NbtSource, Object, Resolvable},
@@ -35,7 +37,7 @@ impl TextResolutor for DisplayResolutor {
 }
 
 impl ReadFrom for TextComponent {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         use crate::codec::VarInt;
 
         // Minecraft's network format: VarInt length prefix, then NBT tag data
@@ -47,11 +49,11 @@ impl ReadFrom for TextComponent {
         }
 
         // Read exactly one NBT tag using simdnbt
-        let nbt_tag =
-            read_tag(data).map_err(|e| io::Error::other(format!("Failed to read NBT: {e:?}")))?;
+        let nbt_tag = read_tag(data)
+            .map_err(|e| ReadError::malformed(format!("Failed to read NBT: {e:?}")))?;
 
         Self::from_nbt(&nbt_tag)
-            .ok_or_else(|| io::Error::other("Failed to parse TextComponent from NBT"))
+            .ok_or_else(|| ReadError::malformed("Failed to parse TextComponent from NBT"))
     }
 }
 
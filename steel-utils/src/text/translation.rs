@@ -0,0 +1,260 @@
+//! Vanilla-compatible formatting of translatable components.
+//!
+//! Translation strings use a subset of Java's `String.format`: `%s` takes the
+//! next argument, `%1$s` takes an argument by position and `%%` is a literal
+//! percent sign. This mirrors `TranslatableContents.decomposeTemplate`, including
+//! its failure mode: a malformed template (or one referencing a missing argument)
+//! is shown verbatim instead of being partially substituted.
+
+use std::borrow::Cow;
+
+use text_components::{TextComponent, content::Content, translation::TranslatedMessage};
+use thiserror::Error;
+
+use crate::translations_registry::TRANSLATIONS;
+
+/// A piece of a decomposed translation template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePart<'a> {
+    /// Literal text between format specifiers.
+    Text(Cow<'a, str>),
+    /// The argument at this (zero-based) index.
+    Argument(usize),
+}
+
+/// Why a translation template could not be decomposed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TranslationFormatError {
+    /// A specifier other than `%s`, `%n$s` or `%%` was used.
+    #[error("unsupported format: '{0}'")]
+    UnsupportedFormat(String),
+    /// A `%` that does not start a specifier.
+    #[error("stray '%' in template")]
+    StrayPercent,
+    /// A specifier referenced an argument that was not supplied.
+    #[error("invalid argument index {0}")]
+    MissingArgument(usize),
+}
+
+/// Splits `template` into literal text and argument references.
+///
+/// `arg_count` is the number of arguments available; referencing any other
+/// index fails like vanilla's `getArgument`.
+pub fn decompose_template(
+    template: &str,
+    arg_count: usize,
+) -> Result<Vec<TemplatePart<'_>>, TranslationFormatError> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut next_index = 0;
+    let mut rest = template;
+
+    while let Some(start) = rest.find('%') {
+        text.push_str(&rest[..start]);
+        let spec = &rest[start + 1..];
+
+        let digits = spec.bytes().take_while(u8::is_ascii_digit).count();
+        let (position, spec) = if digits > 0 && spec[digits..].starts_with('$') {
+            (Some(&spec[..digits]), &spec[digits + 1..])
+        } else {
+            (None, spec)
+        };
+
+        let Some(kind) = spec.chars().next() else {
+            // A trailing `%` (or `%1$`) matches vanilla's `$` alternative and
+            // is reported as an unsupported format.
+            return Err(TranslationFormatError::UnsupportedFormat(
+                rest[start..].to_string(),
+            ));
+        };
+        if !kind.is_ascii_alphabetic() && kind != '%' {
+            return Err(TranslationFormatError::StrayPercent);
+        }
+        let spec_len = rest.len() - spec.len() + kind.len_utf8() - start;
+
+        match (kind, position) {
+            ('%', None) => text.push('%'),
+            ('s', _) => {
+                let index = match position {
+                    Some(digits) => digits
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|position| position.checked_sub(1))
+                        .ok_or(TranslationFormatError::MissingArgument(0))?,
+                    None => {
+                        next_index += 1;
+                        next_index - 1
+                    }
+                };
+                if index >= arg_count {
+                    return Err(TranslationFormatError::MissingArgument(index));
+                }
+                if !text.is_empty() {
+                    parts.push(TemplatePart::Text(Cow::Owned(std::mem::take(&mut text))));
+                }
+                parts.push(TemplatePart::Argument(index));
+            }
+            _ => {
+                return Err(TranslationFormatError::UnsupportedFormat(
+                    rest[start..start + spec_len].to_string(),
+                ));
+            }
+        }
+        rest = &rest[start + spec_len..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(TemplatePart::Text(Cow::Owned(text)));
+    }
+    Ok(parts)
+}
+
+/// Replaces every translatable component in `component` with its formatted text.
+///
+/// `lookup` returns the translation string for a key. Missing keys use the
+/// component's fallback, then the key itself. Arguments are inserted as child
+/// components, so they keep their own style and inherit the translatable's, and
+/// are resolved recursively. Components that are not translatable are returned
+/// unchanged apart from their children.
+#[must_use]
+pub fn resolve_translations<'a>(
+    component: &TextComponent,
+    lookup: &impl Fn(&str) -> Option<&'a str>,
+) -> TextComponent {
+    resolve_translations_except(component, lookup, &|_| false)
+}
+
+/// Like [`resolve_translations`], but translatable components whose key `keep`
+/// accepts stay translatable. Only their arguments and children are resolved.
+#[must_use]
+pub fn resolve_translations_except<'a>(
+    component: &TextComponent,
+    lookup: &impl Fn(&str) -> Option<&'a str>,
+    keep: &impl Fn(&str) -> bool,
+) -> TextComponent {
+    let mut resolved = component.clone();
+    let children = std::mem::take(&mut resolved.children);
+
+    if let Content::Translate(message) = &component.content {
+        if keep(message.key.as_ref()) {
+            resolved.content = TextComponent::translated(TranslatedMessage {
+                key: message.key.clone(),
+                fallback: message.fallback.clone(),
+                args: message.args.as_ref().map(|args| {
+                    args.iter()
+                        .map(|arg| resolve_translations_except(arg, lookup, keep))
+                        .collect()
+                }),
+            })
+            .content;
+        } else {
+            let template = lookup(message.key.as_ref())
+                .or(message.fallback.as_deref())
+                .unwrap_or(message.key.as_ref());
+            let args = message.args.as_deref().unwrap_or_default();
+
+            resolved.content = Content::Text { text: "".into() };
+            let parts: Vec<TextComponent> = match decompose_template(template, args.len()) {
+                Ok(parts) => parts
+                    .into_iter()
+                    .map(|part| match part {
+                        TemplatePart::Text(text) => TextComponent::plain(text.into_owned()),
+                        TemplatePart::Argument(index) => {
+                            resolve_translations_except(&args[index], lookup, keep)
+                        }
+                    })
+                    .collect(),
+                Err(_) => vec![TextComponent::plain(template.to_string())],
+            };
+            for part in parts {
+                resolved = resolved.add_child(part);
+            }
+        }
+    }
+
+    for child in &children {
+        resolved = resolved.add_child(resolve_translations_except(child, lookup, keep));
+    }
+    resolved
+}
+
+/// Resolves translatable components using the bundled `en_us` translations.
+#[must_use]
+pub fn resolve_vanilla_translations(component: &TextComponent) -> TextComponent {
+    resolve_translations(component, &|key| TRANSLATIONS.get(key).copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> TemplatePart<'_> {
+        TemplatePart::Text(Cow::Borrowed(s))
+    }
+
+    #[test]
+    fn sequential_arguments() {
+        assert_eq!(
+            decompose_template("%s was slain by %s", 2).unwrap(),
+            vec![
+                TemplatePart::Argument(0),
+                text(" was slain by "),
+                TemplatePart::Argument(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn positional_arguments() {
+        assert_eq!(
+            decompose_template("%2$s before %1$s", 2).unwrap(),
+            vec![
+                TemplatePart::Argument(1),
+                text(" before "),
+                TemplatePart::Argument(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn mixed_positional_does_not_advance_sequence() {
+        assert_eq!(
+            decompose_template("%2$s %s", 2).unwrap(),
+            vec![
+                TemplatePart::Argument(1),
+                text(" "),
+                TemplatePart::Argument(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_percent() {
+        assert_eq!(
+            decompose_template("100%% of %s", 1).unwrap(),
+            vec![text("100% of "), TemplatePart::Argument(0)]
+        );
+    }
+
+    #[test]
+    fn invalid_templates() {
+        assert_eq!(
+            decompose_template("%s and %s", 1),
+            Err(TranslationFormatError::MissingArgument(1))
+        );
+        assert_eq!(
+            decompose_template("%0$s", 1),
+            Err(TranslationFormatError::MissingArgument(0))
+        );
+        assert_eq!(
+            decompose_template("%d", 1),
+            Err(TranslationFormatError::UnsupportedFormat("%d".to_string()))
+        );
+        assert_eq!(
+            decompose_template("50% off", 0),
+            Err(TranslationFormatError::StrayPercent)
+        );
+        assert!(decompose_template("trailing %", 0).is_err());
+    }
+}
@@ -20,7 +20,7 @@ use crate::{
     direction::Direction,
     hash::{ComponentHasher, HashComponent},
     math::Axis,
-    serial::{ReadFrom, WriteTo},
+    serial::{ReadError, ReadFrom, WriteTo},
 };
 
 /// A placeholder type for unimplemented component values.
@@ -36,7 +36,7 @@ impl WriteTo for Todo {
 }
 
 impl ReadFrom for Todo {
-    fn read(_data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(_data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         // Placeholder components read nothing
         Ok(Todo)
     }
@@ -94,13 +94,11 @@ impl WriteTo for BlockStateId {
 }
 
 impl ReadFrom for BlockStateId {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let id = VarInt::read(data)?.0;
-        #[expect(
-            clippy::cast_sign_loss,
-            reason = "VarInt is validated upstream; block state IDs are non-negative"
-        )]
-        Ok(Self(id as u16))
+        u16::try_from(id)
+            .map(Self)
+            .map_err(|_| ReadError::malformed(format!("Invalid block state id {id}")))
     }
 }
 
@@ -183,7 +181,7 @@ impl WriteTo for ChunkPos {
 }
 
 impl ReadFrom for ChunkPos {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         Ok(Self(IVec2::read(data)?))
     }
 }
@@ -436,7 +434,7 @@ impl BlockPos {
 }
 
 impl ReadFrom for BlockPos {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let packed = <i64 as ReadFrom>::read(data)?;
         Ok(Self::from_i64(packed))
     }
@@ -578,7 +576,7 @@ impl SectionPos {
 }
 
 impl ReadFrom for SectionPos {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let packed = <i64 as ReadFrom>::read(data)?;
         Ok(Self::from_i64(packed))
     }
@@ -756,17 +754,17 @@ impl GameType {
 }
 
 impl ReadFrom for GameType {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let value = VarInt::read(data)?.0;
         match value {
             0 => Ok(GameType::Survival),
             1 => Ok(GameType::Creative),
             2 => Ok(GameType::Adventure),
             3 => Ok(GameType::Spectator),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid GameType",
-            )),
+            _ => Err(ReadError::InvalidDiscriminant {
+                kind: "GameType",
+                value: i128::from(value),
+            }),
         }
     }
 }
@@ -822,6 +820,37 @@ impl From<f32> for GameType {
     }
 }
 
+/// The difficulty of a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[expect(missing_docs, reason = "variant names are self-explanatory")]
+pub enum Difficulty {
+    Peaceful = 0,
+    Easy = 1,
+    #[default]
+    Normal = 2,
+    Hard = 3,
+}
+
+impl Difficulty {
+    /// Returns the numeric id of the difficulty, as used by the protocol.
+    #[must_use]
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the name of the difficulty.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Peaceful => "peaceful",
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Hard => "hard",
+        }
+    }
+}
+
 /// An identifier used by Minecraft.
 #[derive(Clone, PartialEq, Eq, Hash, Default)]
 pub struct Identifier {
@@ -1003,12 +1032,15 @@ pub enum InteractionHand {
 }
 
 impl ReadFrom for InteractionHand {
-    fn read(data: &mut Cursor<&[u8]>) -> io::Result<Self> {
+    fn read(data: &mut Cursor<&[u8]>) -> Result<Self, ReadError> {
         let id = VarInt::read(data)?.0;
         match id {
             0 => Ok(InteractionHand::MainHand),
             1 => Ok(InteractionHand::OffHand),
-            _ => Err(io::Error::other("Invalid InteractionHand id")),
+            _ => Err(ReadError::InvalidDiscriminant {
+                kind: "InteractionHand",
+                value: i128::from(id),
+            }),
         }
     }
 }
@@ -1046,27 +1078,56 @@ mod tests {
         let decoded = BlockPos::from_i64(encoded);
         assert_eq!(pos, decoded, "Position 0, -61, -2 failed roundtrip");
     }
+
+    #[test]
+    fn test_update_flags_match_vanilla() {
+        assert_eq!(UpdateFlags::UPDATE_ALL.bits(), 3);
+        assert_eq!(UpdateFlags::UPDATE_NONE.bits(), 260);
+        assert_eq!(UpdateFlags::UPDATE_SKIP_ALL_SIDEEFFECTS.bits(), 816);
+    }
 }
 
 /// Flags that control how a block update is processed.
+///
+/// The bits match vanilla's `Block.UPDATE_*` constants. Bulk edits (commands,
+/// structure placement, plugins) can leave bits out to avoid update cascades.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct UpdateFlags(u16);
 
 bitflags! {
     impl UpdateFlags: u16 {
+        /// Notify the six neighbors through `neighbor_changed` (redstone, rails, ...).
         const UPDATE_NEIGHBORS = 1;
+        /// Send the change to clients. Without it, players only see the block after a chunk resend.
         const UPDATE_CLIENTS = 1 << 1;
+        /// Don't re-render on the client. Only meaningful client side.
         const UPDATE_INVISIBLE = 1 << 2;
+        /// Re-render on the client right away instead of off-thread.
         const UPDATE_IMMEDIATE = 1 << 3;
+        /// Skip shape updates of the neighbors. This also keeps observers from firing.
         const UPDATE_KNOWN_SHAPE = 1 << 4;
+        /// Neighbors broken by the resulting shape updates don't drop items.
         const UPDATE_SUPPRESS_DROPS = 1 << 5;
+        /// The change is caused by a piston moving the block.
         const UPDATE_MOVE_BY_PISTON = 1 << 6;
+        /// Shape updates skip redstone wire.
         const UPDATE_SKIP_SHAPE_UPDATE_ON_WIRE = 1 << 7;
+        /// Removing a block entity doesn't run its side effects (e.g. dropping container contents).
         const UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS = 1 << 8;
+        /// Don't call `on_place` of the new block.
         const UPDATE_SKIP_ON_PLACE = 1 << 9;
 
+        /// Change the block without any side effects and without telling clients.
         const UPDATE_NONE = Self::UPDATE_INVISIBLE.bits() | Self::UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS.bits();
+        /// Notify neighbors and clients; the usual flags for gameplay changes.
         const UPDATE_ALL = Self::UPDATE_NEIGHBORS.bits() | Self::UPDATE_CLIENTS.bits();
+        /// Like [`Self::UPDATE_ALL`], re-rendering on the client right away.
         const UPDATE_ALL_IMMEDIATE = Self::UPDATE_ALL.bits() | Self::UPDATE_IMMEDIATE.bits();
+        /// Skips shape updates, drops, `on_place` and block entity side effects.
+        /// Combine with [`Self::UPDATE_CLIENTS`] for bulk edits like a strict `/fill`.
+        const UPDATE_SKIP_ALL_SIDEEFFECTS = Self::UPDATE_KNOWN_SHAPE.bits()
+            | Self::UPDATE_SUPPRESS_DROPS.bits()
+            | Self::UPDATE_SKIP_BLOCK_ENTITY_SIDEEFFECTS.bits()
+            | Self::UPDATE_SKIP_ON_PLACE.bits();
     }
 }
@@ -3,7 +3,17 @@
 //! Vanilla Minecraft stores UUIDs as int arrays with 4 elements,
 //! where each i32 represents 4 bytes of the UUID in big-endian order.
 
-use uuid::Uuid;
+use uuid::{Builder, Uuid};
+
+/// Gets the UUID of a player on a server in offline mode: a version 3 UUID from
+/// the MD5 hash of `OfflinePlayer:<name>`.
+///
+/// Vanilla: `UUIDUtil.createOfflinePlayerUUID()`.
+#[must_use]
+pub fn offline_player_uuid(name: &str) -> Uuid {
+    let hash = md5::compute(format!("OfflinePlayer:{name}"));
+    Builder::from_md5_bytes(hash.0).into_uuid()
+}
 
 /// Extension trait for UUID to support Minecraft's NBT int array format.
 pub trait UuidExt {
@@ -58,6 +68,14 @@ mod tests {
         assert_eq!(uuid, recovered);
     }
 
+    #[test]
+    fn test_offline_player_uuid() {
+        assert_eq!(
+            offline_player_uuid("Notch").to_string(),
+            "b50ad385-829d-3141-a216-7e7d7539ba7f"
+        );
+    }
+
     #[test]
     fn test_uuid_from_invalid_array() {
         assert!(Uuid::from_int_array(&[1, 2, 3]).is_none());
@@ -3,10 +3,21 @@
 //! This module handles loading the server configuration from disk.
 //! The `ServerConfig` struct is defined in `steel-core`, this module
 //! just handles the file I/O and initialization.
+//!
+//! Environment variables starting with `STEEL_` override values of the config
+//! file: `STEEL_VIEW_DISTANCE=12` sets `view_distance`, and `__` separates nested
+//! keys, as in `STEEL_WATCHDOG__STALL_THRESHOLD=30`. Values of string settings
+//! are taken as they are, other values are parsed as JSON5, falling back to a
+//! plain string.
 
 use base64::{Engine, prelude::BASE64_STANDARD};
 use serde::Deserialize;
-use std::{fs, path::Path, sync::LazyLock};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, env, ffi::OsString, fs, io, path::Path, process, sync::LazyLock};
+use steel_core::chunk::chunk_ticket_manager::MAX_VIEW_DISTANCE;
+use steel_core::server::chunk_distances::MIN_VIEW_DISTANCE;
+use thiserror::Error;
+use tracing::level_filters::LevelFilter;
 
 // Re-export types from steel-core for convenience
 pub use steel_core::config::{ConfigLabel, ConfigLink, ServerConfig, ServerConfigRef, ServerLinks};
@@ -20,11 +31,39 @@ const DEFAULT_CONFIG: &str = include_str!("../../package-content/steel_config.js
 /// The Minecraft version this server supports.
 pub const MC_VERSION: &str = "26.1";
 
+/// Where the config file is.
+const CONFIG_PATH: &str = "config/steel_config.json5";
+
+/// The prefix of environment variables overriding config values.
+const ENV_PREFIX: &str = "STEEL_";
+
 /// The server configuration.
 ///
 /// This is loaded from `config/steel_config.json5` or created if it doesn't exist.
-pub static STEEL_CONFIG: LazyLock<ExtendedConfig> =
-    LazyLock::new(|| load_or_create(Path::new("config/steel_config.json5")));
+/// The server exits if the config is invalid.
+pub static STEEL_CONFIG: LazyLock<ExtendedConfig> = LazyLock::new(|| {
+    load_or_create(Path::new(CONFIG_PATH)).unwrap_or_else(|err| {
+        eprintln!("Failed to load {CONFIG_PATH}: {err}");
+        process::exit(1);
+    })
+});
+
+/// Why the config couldn't be loaded.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The config file couldn't be read or created.
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    /// The config file isn't valid JSON5, or a value has the wrong type.
+    #[error("{0}")]
+    Parse(#[from] serde_json5::Error),
+    /// A value has the wrong type after applying the environment overrides.
+    #[error("{0} (after applying the {ENV_PREFIX} environment variables)")]
+    Override(#[from] serde_json::Error),
+    /// Values are out of range or contradict each other.
+    #[error("invalid settings:\n  {}", .0.join("\n  "))]
+    Invalid(Vec<String>),
+}
 
 /// A extended configuration for non-server features
 #[derive(Debug, Clone, Deserialize)]
@@ -46,6 +85,72 @@ pub struct LogConfig {
     pub module_path: bool,
     /// Whether the extra data of the log should be displayed
     pub extra: bool,
+    /// The level logs are shown from, for targets without a level in `targets`
+    #[serde(default)]
+    pub level: LogLevel,
+    /// Levels of single targets, keyed by module path (e.g. `steel_core::chunk`)
+    #[serde(default)]
+    pub targets: BTreeMap<String, LogLevel>,
+    /// Writing the log to files
+    #[serde(default)]
+    pub file: LogFileConfig,
+}
+
+/// The level logs are shown from
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// No logs
+    Off,
+    /// Only errors
+    Error,
+    /// Warnings and errors
+    Warn,
+    /// Info, warnings and errors
+    #[default]
+    Info,
+    /// Everything but trace logs
+    Debug,
+    /// Everything
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => Self::OFF,
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+/// Configuration for writing the log to a file per day
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LogFileConfig {
+    /// Whether the log is written to files
+    pub enabled: bool,
+    /// The directory the log files are written to
+    pub directory: String,
+    /// How many log files are kept, older ones are deleted (0 keeps all)
+    pub max_files: usize,
+    /// Whether entries are written as JSON objects, one per line
+    pub json: bool,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "logs".to_owned(),
+            max_files: 14,
+            json: false,
+        }
+    }
 }
 
 /// Time format for log entries
@@ -61,70 +166,224 @@ pub enum LogTimeFormat {
     Uptime,
 }
 
-/// Loads the server configuration from the given path, or creates it if it doesn't exist.
+/// Loads the server configuration from the given path, or creates it with the
+/// defaults if it doesn't exist, then applies the environment overrides.
 ///
-/// # Panics
-/// This function will panic if the config file does not exist and the directory cannot be created,
-/// or if the config file cannot be read or written.
-#[must_use]
-fn load_or_create(path: &Path) -> ExtendedConfig {
-    let mut config = if path.exists() {
-        let config_str = fs::read_to_string(path).expect("Failed to read config file");
-        let config: ExtendedConfig =
-            serde_json5::from_str(config_str.as_str()).expect("Failed to parse config");
-        validate(&config.server_config).expect("Failed to validate config");
-        config
+/// # Errors
+/// Returns an error if the config file can't be read or created, can't be
+/// parsed, or has invalid settings.
+fn load_or_create(path: &Path) -> Result<ExtendedConfig, ConfigError> {
+    let text = if path.exists() {
+        fs::read_to_string(path)?
     } else {
-        fs::create_dir_all(path.parent().expect("Failed to get config directory"))
-            .expect("Failed to create config directory");
-        fs::write(path, DEFAULT_CONFIG).expect("Failed to write config file");
-        let config: ExtendedConfig =
-            serde_json5::from_str(DEFAULT_CONFIG).expect("Failed to parse config");
-        validate(&config.server_config).expect("Failed to validate config");
-        config
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, DEFAULT_CONFIG)?;
+        DEFAULT_CONFIG.to_owned()
     };
 
+    let overrides = env_overrides(env::vars_os());
+    let mut config: ExtendedConfig = if overrides.is_empty() {
+        // Parsed directly, so errors point at the line in the file
+        serde_json5::from_str(&text)?
+    } else {
+        apply_overrides(&serde_json5::from_str(&text)?, &overrides)?
+    };
+    validate(&config.server_config).map_err(ConfigError::Invalid)?;
+
     // Set the MC version (not loaded from config file)
     config.server_config.mc_version = MC_VERSION;
 
     // If icon file doesnt exist, write it
     #[cfg(feature = "stand-alone")]
     if config.server_config.use_favicon && !Path::new(&config.server_config.favicon).exists() {
-        fs::write(Path::new(&config.server_config.favicon), DEFAULT_FAVICON)
-            .expect("Failed to write favicon file");
+        fs::write(Path::new(&config.server_config.favicon), DEFAULT_FAVICON)?;
     }
 
-    config
+    Ok(config)
+}
+
+/// Loads the server configuration from disk again, for `/steel reload-config`.
+fn reload() -> Result<ServerConfig, String> {
+    load_or_create(Path::new(CONFIG_PATH))
+        .map(|config| config.server_config)
+        .map_err(|err| err.to_string())
+}
+
+/// Collects the `STEEL_` environment variables as config key paths and raw values.
+fn env_overrides(
+    vars: impl IntoIterator<Item = (OsString, OsString)>,
+) -> Vec<(Vec<String>, String)> {
+    vars.into_iter()
+        .filter_map(|(name, raw)| {
+            let (name, raw) = (name.into_string().ok()?, raw.into_string().ok()?);
+            let key = name.strip_prefix(ENV_PREFIX)?;
+            let path = key.split("__").map(str::to_lowercase).collect();
+            Some((path, raw))
+        })
+        .collect()
+}
+
+/// Applies the overrides to the values of the config file and deserializes them.
+///
+/// Overrides of string settings stay strings, so `STEEL_SEED=12345` works. Settings
+/// missing from the file are parsed as JSON5, and taken as strings if the config
+/// doesn't deserialize that way.
+fn apply_overrides(
+    file: &Value,
+    overrides: &[(Vec<String>, String)],
+) -> Result<ExtendedConfig, serde_json::Error> {
+    let deserialize = |missing_as_strings: bool| {
+        let mut config = file.clone();
+        for (path, raw) in overrides {
+            let value = match file.pointer(&format!("/{}", path.join("/"))) {
+                Some(Value::String(_)) => Value::String(raw.clone()),
+                None | Some(Value::Null) if missing_as_strings => Value::String(raw.clone()),
+                _ => serde_json5::from_str(raw).unwrap_or_else(|_| Value::String(raw.clone())),
+            };
+            apply_override(&mut config, path, value);
+        }
+        serde_json::from_value(config)
+    };
+    deserialize(false).or_else(|err| deserialize(true).map_err(|_| err))
+}
+
+/// Sets the value at `path`, creating the objects on the way.
+fn apply_override(config: &mut Value, path: &[String], value: Value) {
+    let mut current = config;
+    for key in path {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(object) = current else {
+            return;
+        };
+        current = object.entry(key.clone()).or_insert(Value::Null);
+    }
+    *current = value;
 }
 
 /// Validates the server configuration.
 ///
 /// # Errors
-/// This function will return an error if the configuration is invalid.
-fn validate(config: &ServerConfig) -> Result<(), &'static str> {
-    if !(1..=32).contains(&config.view_distance) {
-        return Err("View distance must in range 1..32");
+/// Returns every problem found, naming the setting and its value.
+fn validate(config: &ServerConfig) -> Result<(), Vec<String>> {
+    let mut problems = Vec::new();
+    if config.server_port == 0 {
+        problems.push("server_port is 0, must be between 1 and 65535".to_owned());
+    }
+    if !(MIN_VIEW_DISTANCE..=MAX_VIEW_DISTANCE).contains(&config.view_distance) {
+        problems.push(format!(
+            "view_distance is {}, must be between {MIN_VIEW_DISTANCE} and {MAX_VIEW_DISTANCE}",
+            config.view_distance
+        ));
     }
     if config.simulation_distance > config.view_distance {
-        return Err("Simulation distance must be less than or equal to view distance");
+        problems.push(format!(
+            "simulation_distance is {}, must not exceed view_distance ({})",
+            config.simulation_distance, config.view_distance
+        ));
+    }
+    for (world, settings) in &config.worlds {
+        if let Some(simulation_distance) = settings.simulation_distance
+            && simulation_distance > config.view_distance
+        {
+            problems.push(format!(
+                "worlds.{world}.simulation_distance is {simulation_distance}, must not exceed view_distance ({})",
+                config.view_distance
+            ));
+        }
     }
     if let Some(compression) = config.compression {
         if compression.threshold.get() < 256 {
-            return Err("Compression threshold must be greater than or equal to 256");
+            problems.push(format!(
+                "compression.threshold is {}, must be at least 256",
+                compression.threshold
+            ));
         }
         if !(1..=9).contains(&compression.level) {
-            return Err("Compression level must be between 1 and 9");
+            problems.push(format!(
+                "compression.level is {}, must be between 1 and 9",
+                compression.level
+            ));
         }
     }
     if config.enforce_secure_chat {
         if !config.online_mode {
-            return Err("online_mode must be true when enforce_secure_chat is enabled");
+            problems
+                .push("online_mode must be true when enforce_secure_chat is enabled".to_owned());
         }
         if !config.encryption {
-            return Err("encryption must be true when enforce_secure_chat is enabled");
+            problems.push("encryption must be true when enforce_secure_chat is enabled".to_owned());
+        }
+    }
+    if config.crypto.max_concurrent_verifications == 0 {
+        problems.push("crypto.max_concurrent_verifications must be at least 1".to_owned());
+    }
+    let outbound_queue = &config.outbound_queue;
+    if outbound_queue.max_bytes == 0 {
+        problems.push("outbound_queue.max_bytes must be at least 1".to_owned());
+    }
+    if outbound_queue.low_priority_bytes > outbound_queue.max_bytes {
+        problems.push(
+            "outbound_queue.low_priority_bytes must not be more than outbound_queue.max_bytes"
+                .to_owned(),
+        );
+    }
+    let movement_checks = &config.movement_checks;
+    for (name, value) in [
+        ("max_speed", movement_checks.max_speed),
+        ("max_elytra_speed", movement_checks.max_elytra_speed),
+        ("max_vehicle_speed", movement_checks.max_vehicle_speed),
+        ("max_error", movement_checks.max_error),
+    ] {
+        if value.is_nan() || value < 0.0 {
+            problems.push(format!(
+                "movement_checks.{name} is {value}, must be at least 0"
+            ));
         }
     }
-    Ok(())
+    if movement_checks.max_floating_ticks == 0 {
+        problems.push("movement_checks.max_floating_ticks must be at least 1".to_owned());
+    }
+    if config.chunk_limits.max_entities == Some(0) {
+        problems.push("chunk_limits.max_entities must be at least 1".to_owned());
+    }
+    if config.chunk_limits.max_block_entities == Some(0) {
+        problems.push("chunk_limits.max_block_entities must be at least 1".to_owned());
+    }
+    if config.neighbor_updates.tick_budget == Some(0) {
+        problems.push("neighbor_updates.tick_budget must be at least 1".to_owned());
+    }
+    if config.activation_range.wake_up_interval == 0 {
+        problems.push("activation_range.wake_up_interval must be at least 1".to_owned());
+    }
+    if config.autosave.max_in_flight == 0 {
+        problems.push("autosave.max_in_flight must be at least 1".to_owned());
+    }
+    if config.watchdog.stall_threshold == 0 {
+        problems.push("watchdog.stall_threshold must be at least 1".to_owned());
+    }
+    let authentication = &config.authentication;
+    if let Some(url) = &authentication.session_server
+        && !(url.starts_with("https://") || url.starts_with("http://"))
+    {
+        problems.push(format!(
+            "authentication.session_server is \"{url}\", must be an http(s) URL"
+        ));
+    }
+    if authentication.max_attempts == 0 {
+        problems.push("authentication.max_attempts must be at least 1".to_owned());
+    }
+    if authentication.breaker_threshold == 0 {
+        problems.push("authentication.breaker_threshold must be at least 1".to_owned());
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
 }
 
 /// Loads the favicon from the path specified in the config.
@@ -171,4 +430,104 @@ pub fn load_favicon(config: &ServerConfig) -> Option<String> {
 pub fn init_steel_core_config() {
     // Force config to load, then initialize steel-core's reference
     ServerConfigRef::init(&STEEL_CONFIG.server_config);
+    ServerConfigRef::set_reloader(reload);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn default_config() -> ExtendedConfig {
+        serde_json5::from_str(DEFAULT_CONFIG).expect("the default config is valid")
+    }
+
+    fn overrides(vars: &[(&str, &str)]) -> Vec<(Vec<String>, String)> {
+        env_overrides(
+            vars.iter()
+                .map(|(name, value)| (OsString::from(name), OsString::from(value))),
+        )
+    }
+
+    #[test]
+    fn env_overrides_split_nested_keys() {
+        let overrides = overrides(&[
+            ("STEEL_VIEW_DISTANCE", "12"),
+            ("STEEL_WATCHDOG__STALL_THRESHOLD", "30"),
+            ("PATH", "/usr/bin"),
+        ]);
+        assert_eq!(
+            overrides,
+            [
+                (vec!["view_distance".to_owned()], "12".to_owned()),
+                (
+                    vec!["watchdog".to_owned(), "stall_threshold".to_owned()],
+                    "30".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_override_creates_missing_objects() {
+        let mut config = json!({ "view_distance": 10, "watchdog": 5 });
+        apply_override(&mut config, &["view_distance".to_owned()], json!(12));
+        apply_override(
+            &mut config,
+            &["watchdog".to_owned(), "stall_threshold".to_owned()],
+            json!(30),
+        );
+        apply_override(
+            &mut config,
+            &["log".to_owned(), "file".to_owned(), "enabled".to_owned()],
+            json!(true),
+        );
+        assert_eq!(
+            config,
+            json!({
+                "view_distance": 12,
+                "watchdog": { "stall_threshold": 30 },
+                "log": { "file": { "enabled": true } },
+            })
+        );
+    }
+
+    #[test]
+    fn numeric_overrides_of_string_settings_stay_strings() {
+        let file: Value = serde_json5::from_str(DEFAULT_CONFIG).expect("valid JSON5");
+        let config = apply_overrides(
+            &file,
+            &overrides(&[
+                ("STEEL_SEED", "12345"),
+                ("STEEL_MOTD", "1"),
+                ("STEEL_VIEW_DISTANCE", "12"),
+                ("STEEL_ONLINE_MODE", "false"),
+            ]),
+        )
+        .expect("the overrides deserialize");
+        let config = config.server_config;
+        assert_eq!(config.seed, "12345");
+        assert_eq!(config.motd, "1");
+        assert_eq!(config.view_distance, 12);
+        assert!(!config.online_mode);
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(validate(&default_config().server_config), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_every_problem() {
+        let mut config = default_config().server_config;
+        config.server_port = 0;
+        config.simulation_distance = config.view_distance + 1;
+        config.watchdog.stall_threshold = 0;
+        let problems = validate(&config).expect_err("the config is invalid");
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].starts_with("server_port is 0"));
+        assert!(problems[1].starts_with("simulation_distance is"));
+        assert!(problems[2].starts_with("watchdog.stall_threshold"));
+    }
 }
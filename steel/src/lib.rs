@@ -3,7 +3,7 @@
 //! The main library for the Steel Minecraft server.
 
 use std::{
-    net::{Ipv4Addr, SocketAddrV4},
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     sync::{Arc, OnceLock},
 };
 
@@ -37,11 +37,28 @@ pub struct SteelServer {
 }
 
 impl SteelServer {
-    /// Creates a new Steel server.
+    /// Creates a new Steel server listening on the configured port.
     ///
     /// # Panics
     /// This function will panic if the TCP listener fails to bind to the server address.
     pub async fn new(chunk_runtime: Arc<Runtime>, cancel_token: CancellationToken) -> Self {
+        let address = SocketAddrV4::new(
+            Ipv4Addr::UNSPECIFIED,
+            STEEL_CONFIG.server_config.server_port,
+        );
+        Self::bind(chunk_runtime, cancel_token, address.into()).await
+    }
+
+    /// Creates a new Steel server listening on `address`. Port 0 picks a free
+    /// port, which [`TcpListener::local_addr`] then reports.
+    ///
+    /// # Panics
+    /// This function will panic if the TCP listener fails to bind to the address.
+    pub async fn bind(
+        chunk_runtime: Arc<Runtime>,
+        cancel_token: CancellationToken,
+        address: SocketAddr,
+    ) -> Self {
         log::info!("Starting Steel Server");
 
         // Initialize steel-core's config reference before any steel-core code runs
@@ -50,12 +67,9 @@ impl SteelServer {
         let server = Server::new(chunk_runtime, cancel_token.clone()).await;
 
         Self {
-            tcp_listener: TcpListener::bind(SocketAddrV4::new(
-                Ipv4Addr::UNSPECIFIED,
-                STEEL_CONFIG.server_config.server_port,
-            ))
-            .await
-            .expect("Failed to bind to server address"),
+            tcp_listener: TcpListener::bind(address)
+                .await
+                .expect("Failed to bind to server address"),
             cancel_token,
             client_id: 0,
             server: Arc::new(server),
@@ -0,0 +1,211 @@
+//! Writes the log to a file per day, as text or as JSON lines.
+use std::fmt::{Debug, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{NaiveDate, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use steel_utils::locks::SyncMutex;
+use tracing::Subscriber;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+use crate::config::LogFileConfig;
+
+const FILE_PREFIX: &str = "steel-";
+const FILE_EXTENSION: &str = "log";
+
+/// A layer writing every log event to the log file of the day.
+// TODO: Also write chat and command logs, which are sent to the console directly
+pub struct FileLayer {
+    file: SyncMutex<DailyFile>,
+    json: bool,
+}
+
+impl FileLayer {
+    /// Opens today's log file in the configured directory, deleting the oldest
+    /// files past `max_files`.
+    ///
+    /// # Errors
+    /// Returns an error if the directory or the file can't be created.
+    pub fn new(config: &LogFileConfig) -> io::Result<Self> {
+        let directory = PathBuf::from(&config.directory);
+        fs::create_dir_all(&directory)?;
+        let date = Utc::now().date_naive();
+        let file = DailyFile {
+            file: open_file(&directory, date)?,
+            directory,
+            max_files: config.max_files,
+            date,
+        };
+        file.delete_old()?;
+        Ok(Self {
+            file: SyncMutex::new(file),
+            json: config.json,
+        })
+    }
+}
+
+impl<S: Subscriber> Layer<S> for FileLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        // Events from the `log` crate carry their real target as a field
+        let target = fields
+            .target
+            .unwrap_or_else(|| metadata.target().to_owned());
+        let now = Utc::now();
+
+        let line = if self.json {
+            let mut entry = Map::new();
+            entry.insert(
+                "timestamp".to_owned(),
+                Value::String(now.to_rfc3339_opts(SecondsFormat::Millis, true)),
+            );
+            entry.insert(
+                "level".to_owned(),
+                Value::String(metadata.level().as_str().to_owned()),
+            );
+            entry.insert("target".to_owned(), Value::String(target));
+            entry.insert("message".to_owned(), Value::String(fields.message));
+            if !fields.extra.is_empty() {
+                entry.insert("fields".to_owned(), Value::Object(fields.extra));
+            }
+            Value::Object(entry).to_string()
+        } else {
+            let mut line = format!(
+                "{} [{}] {target}: {}",
+                now.format("%F %T%.3f"),
+                metadata.level(),
+                fields.message
+            );
+            for (name, value) in &fields.extra {
+                match value {
+                    Value::String(value) => write!(line, " ({name}: {value})"),
+                    value => write!(line, " ({name}: {value})"),
+                }
+                .ok();
+            }
+            line
+        };
+
+        // Logging the error would end up here again
+        if let Err(err) = self.file.lock().write_line(&line, now.date_naive()) {
+            eprintln!("Failed to write the log file: {err}");
+        }
+    }
+}
+
+/// The log file of the current day.
+struct DailyFile {
+    directory: PathBuf,
+    max_files: usize,
+    date: NaiveDate,
+    file: File,
+}
+
+impl DailyFile {
+    /// Writes a line, to a new file if the day changed.
+    fn write_line(&mut self, line: &str, date: NaiveDate) -> io::Result<()> {
+        if date != self.date {
+            self.file = open_file(&self.directory, date)?;
+            self.date = date;
+            self.delete_old()?;
+        }
+        writeln!(self.file, "{line}")
+    }
+
+    /// Deletes the oldest log files, keeping `max_files`.
+    fn delete_old(&self) -> io::Result<()> {
+        if self.max_files == 0 {
+            return Ok(());
+        }
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.directory)?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| log_file_date(path).is_some())
+            .collect();
+        // The dates in the names sort the files from oldest to newest
+        files.sort();
+        let excess = files.len().saturating_sub(self.max_files);
+        for path in &files[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Opens the log file of `date` for appending, creating it if needed.
+fn open_file(directory: &Path, date: NaiveDate) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(directory.join(format!("{FILE_PREFIX}{date}.{FILE_EXTENSION}")))
+}
+
+/// Gets the date of a log file from its name, `None` if it isn't one.
+fn log_file_date(path: &Path) -> Option<NaiveDate> {
+    let name = path.file_name()?.to_str()?;
+    let date = name
+        .strip_prefix(FILE_PREFIX)?
+        .strip_suffix(FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    date.parse().ok()
+}
+
+/// The fields of a log event.
+#[derive(Default)]
+struct Fields {
+    message: String,
+    target: Option<String>,
+    extra: Map<String, Value>,
+}
+
+impl Fields {
+    fn record(&mut self, field: &Field, value: Value) {
+        match field.name() {
+            "message" => match value {
+                Value::String(message) => self.message = message,
+                value => self.message = value.to_string(),
+            },
+            "log.target" => {
+                if let Value::String(target) = value {
+                    self.target = Some(target);
+                }
+            }
+            // The module path, file and line of events from the `log` crate
+            name if name.starts_with("log.") => {}
+            name => {
+                self.extra.insert(name.to_owned(), value);
+            }
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.record(field, Value::String(format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, Value::String(value.to_owned()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, Value::from(value));
+    }
+}
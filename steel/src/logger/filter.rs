@@ -0,0 +1,143 @@
+//! The log filter, with a level per target that `/steel log level` can change
+//! while the server runs.
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+use steel_utils::locks::SyncMutex;
+use steel_utils::logger::set_level_setter;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+use crate::config::LogConfig;
+
+/// The environment variable with extra filter directives, which take
+/// precedence over the configured levels.
+const ENV_VAR: &str = "RUST_LOG";
+
+struct Levels {
+    default: LevelFilter,
+    targets: BTreeMap<String, LevelFilter>,
+}
+
+impl Levels {
+    fn filter(&self) -> EnvFilter {
+        let mut directives = self.default.to_string();
+        for (target, level) in &self.targets {
+            write!(directives, ",{target}={level}").ok();
+        }
+        if let Ok(env) = env::var(ENV_VAR)
+            && !env.is_empty()
+        {
+            write!(directives, ",{env}").ok();
+        }
+        EnvFilter::builder().parse_lossy(directives)
+    }
+}
+
+struct LogFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    levels: SyncMutex<Levels>,
+}
+
+static FILTER: OnceLock<LogFilter> = OnceLock::new();
+
+/// Creates the log filter from the config. It has to be the first layer on the
+/// registry, so its levels can be changed later.
+pub fn filter_layer(config: Option<&LogConfig>) -> reload::Layer<EnvFilter, Registry> {
+    let levels = Levels {
+        default: config.map_or(LevelFilter::INFO, |config| config.level.into()),
+        targets: config
+            .map(|config| {
+                config
+                    .targets
+                    .iter()
+                    .map(|(target, level)| (target.clone(), (*level).into()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    let (layer, handle) = reload::Layer::new(levels.filter());
+    let filter = LogFilter {
+        handle,
+        levels: SyncMutex::new(levels),
+    };
+    if FILTER.set(filter).is_ok() {
+        set_level_setter(set_level);
+    }
+    layer
+}
+
+/// Makes `target` log from `level`, or every target without a level of its own
+/// if it's empty.
+fn set_level(target: &str, level: LevelFilter) -> Result<(), String> {
+    let filter = FILTER
+        .get()
+        .ok_or_else(|| "The log filter isn't initialized".to_owned())?;
+    let mut levels = filter.levels.lock();
+    if target.is_empty() {
+        levels.default = level;
+    } else if target
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    {
+        levels.targets.insert(target.to_owned(), level);
+    } else {
+        return Err(format!("{target} isn't a module path"));
+    }
+    filter
+        .handle
+        .reload(levels.filter())
+        .map_err(|err| err.to_string())?;
+    // `log` records are dropped before reaching the filter if they're above the
+    // max level `LogTracer` set at init, so it has to follow the reloads.
+    log::set_max_level(LevelFilter::current().as_log());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    use super::*;
+
+    static MESSAGES: SyncMutex<Vec<String>> = SyncMutex::new(Vec::new());
+
+    struct Capture;
+
+    impl Visit for Capture {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            if field.name() == "message" {
+                MESSAGES.lock().push(format!("{value:?}"));
+            }
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Capture {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            event.record(&mut Capture);
+        }
+    }
+
+    #[test]
+    fn raised_target_receives_log_records() {
+        tracing_subscriber::registry()
+            .with(filter_layer(None))
+            .with(Capture)
+            .init();
+
+        log::debug!(target: "filter_test", "before");
+        set_level("filter_test", LevelFilter::DEBUG).expect("the filter is initialized");
+        log::debug!(target: "filter_test", "after");
+        log::debug!(target: "other_target", "other");
+
+        assert_eq!(*MESSAGES.lock(), ["after"]);
+    }
+}
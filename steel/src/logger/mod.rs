@@ -20,6 +20,8 @@ use tracing::Subscriber;
 use tracing_subscriber::Layer;
 use tracing_subscriber::layer::Context;
 
+mod file;
+mod filter;
 mod history;
 mod input;
 mod output;
@@ -34,6 +36,8 @@ fn terminal_width() -> usize {
     terminal::size().map_or(80, |(w, _)| (w as usize).max(1))
 }
 
+pub use file::FileLayer;
+pub use filter::filter_layer;
 pub(crate) use state::LogState;
 
 #[cfg(feature = "spawn_chunk_display")]
@@ -2,20 +2,28 @@
 
 use std::num::NonZero;
 use std::sync::Arc;
-use std::thread;
+use std::{env, process, thread};
 
-use steel::logger::CommandLogger;
+use steel::logger::{CommandLogger, FileLayer, LoggerLayer, filter_layer};
 use steel::spawn_progress::generate_spawn_chunks;
-use steel::{SERVER, SteelServer, logger::LoggerLayer};
+use steel::{SERVER, STEEL_CONFIG, SteelServer};
+use steel_core::config::PlayerDataStorageConfig;
+use steel_core::player::player_data_storage::{self, PlayerDataStorage};
+#[cfg(feature = "wasm-plugins")]
+use steel_core::plugin::wasm::register_wasm_plugins;
+#[cfg(feature = "scripting")]
+use steel_core::plugin::{register_plugin, scripting::ScriptPlugin};
+use steel_core::server::crash_report::install_panic_hook;
+use steel_core::server::watchdog::WATCHDOG;
 use steel_utils::text::DisplayResolutor;
 use text_components::fmt::set_display_resolutor;
 use tokio::runtime::{Builder, Runtime};
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 #[cfg(feature = "jaeger")]
 use tracing::Subscriber;
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 #[cfg(feature = "jaeger")]
-use tracing_subscriber::{Layer, registry::LookupSpan};
+use tracing_subscriber::{EnvFilter, Layer, registry::LookupSpan};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[cfg(feature = "jaeger")]
 fn init_jaeger<S>() -> impl Layer<S> + Send + Sync
@@ -65,19 +73,29 @@ async fn init_tracing(cancel_token: CancellationToken) -> Arc<CommandLogger> {
         .expect("Couldn't initialize the logger");
     let logger = layer.0.clone();
 
-    let tracing = tracing_subscriber::registry().with(layer);
+    let log_config = STEEL_CONFIG.log.as_ref();
+    let file_layer = log_config
+        .filter(|config| config.file.enabled)
+        .map(|config| FileLayer::new(&config.file));
+    let (file_layer, file_error) = match file_layer {
+        Some(Ok(file_layer)) => (Some(file_layer), None),
+        Some(Err(err)) => (None, Some(err)),
+        None => (None, None),
+    };
+
+    let tracing = tracing_subscriber::registry()
+        .with(filter_layer(log_config))
+        .with(layer)
+        .with(file_layer);
 
     #[cfg(feature = "jaeger")]
     let tracing = tracing.with(init_jaeger());
 
-    let tracing = tracing.with(
-        EnvFilter::builder()
-            .with_default_directive(tracing::Level::INFO.into())
-            .from_env_lossy(),
-    );
-
     set_display_resolutor(&DisplayResolutor);
     tracing.init();
+    if let Some(err) = file_error {
+        log::error!("Couldn't open the log file: {err}");
+    }
     logger
 }
 
@@ -107,6 +125,12 @@ fn main() {
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
+    let mut args = env::args().skip(1);
+    if args.next().as_deref() == Some("migrate-player-data") {
+        migrate_player_data(args);
+        return;
+    }
+
     let half_cpus = (thread::available_parallelism().map_or(4, NonZero::get) / 2).max(2);
 
     let chunk_runtime = Arc::new(
@@ -129,11 +153,47 @@ fn main() {
 
     drop(main_runtime);
     drop(chunk_runtime);
+
+    // Tells a process supervisor to restart the server
+    if WATCHDOG.has_stalled() {
+        process::exit(1);
+    }
+}
+
+/// Copies player data between storage backends and exits.
+///
+/// Usage: `steel migrate-player-data <from> <to>`, where each backend is
+/// `files:<dir>` or `sqlite:<path>`. Run this while the server is stopped.
+fn migrate_player_data(mut args: impl Iterator<Item = String>) {
+    let (Some(from), Some(to)) = (args.next(), args.next()) else {
+        eprintln!("Usage: steel migrate-player-data <files|sqlite>:<path> <files|sqlite>:<path>");
+        process::exit(2);
+    };
+
+    let result = (|| {
+        let from: PlayerDataStorageConfig = from.parse()?;
+        let to: PlayerDataStorageConfig = to.parse()?;
+        if from == to {
+            return Err("Source and destination are the same".to_owned());
+        }
+        let from = PlayerDataStorage::open_backend(&from).map_err(|e| e.to_string())?;
+        let to = PlayerDataStorage::open_backend(&to).map_err(|e| e.to_string())?;
+        player_data_storage::migrate(from.as_ref(), to.as_ref()).map_err(|e| e.to_string())
+    })();
+
+    match result {
+        Ok(count) => println!("Migrated {count} players"),
+        Err(e) => {
+            eprintln!("Failed to migrate player data: {e}");
+            process::exit(1);
+        }
+    }
 }
 
 async fn main_async(chunk_runtime: Arc<Runtime>) {
     let cancel_token = CancellationToken::new();
     let logger = init_tracing(cancel_token.clone()).await;
+    install_panic_hook();
 
     run_server(chunk_runtime, cancel_token, &logger).await;
 
@@ -180,6 +240,17 @@ async fn run_server(
     SERVER.set(steel.server.clone()).ok();
     let server = steel.server.clone();
 
+    let watchdog = &STEEL_CONFIG.server_config.watchdog;
+    if watchdog.enabled {
+        WATCHDOG.spawn(watchdog.clone(), cancel_token.clone());
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    register_wasm_plugins();
+    #[cfg(feature = "scripting")]
+    register_plugin(ScriptPlugin);
+    server.plugins.enable_all(&server);
+
     let task_tracker = TaskTracker::new();
 
     steel.start(task_tracker.clone()).await;
@@ -189,6 +260,8 @@ async fn run_server(
     task_tracker.close();
     task_tracker.wait().await;
 
+    server.plugins.disable_all();
+
     for world in server.worlds.values() {
         world.chunk_map.task_tracker.close();
         world.chunk_map.task_tracker.wait().await;
@@ -201,6 +274,10 @@ async fn run_server(
         world.cleanup(&mut total_saved).await;
     }
     log::info!("Saved {total_saved} chunks");
+    match server.maps.save_all() {
+        Ok(count) => log::info!("Saved {count} maps"),
+        Err(e) => log::error!("Failed to save maps: {e}"),
+    }
 
     // Save all player data before shutdown
     log::info!("Saving player data...");
@@ -215,6 +292,7 @@ async fn run_server(
         Ok(count) => log::info!("Saved {count} players"),
         Err(e) => log::error!("Failed to save player data: {e}"),
     }
+    server.profile_cache.save();
 
     log::info!("Server stopped");
 }
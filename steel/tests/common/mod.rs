@@ -0,0 +1,487 @@
+//! A Steel server on an ephemeral port and a minimal headless client to drive
+//! it, for end-to-end tests of the packet handlers.
+//!
+//! The registries can only be initialized once per process, so every test in a
+//! binary shares one server. It runs on its own runtimes in a background thread,
+//! so it outlives the runtime of the test that started it. Tests stay apart by
+//! joining with different player names.
+
+#![allow(
+    dead_code,
+    reason = "each test binary uses a different part of the harness"
+)]
+
+use std::io::{self, Cursor};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, mpsc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, process, thread};
+
+use glam::DVec3;
+use serde_json::{Value, json};
+use steel::SteelServer;
+use steel_core::player::Player;
+use steel_core::player::packet_recorder::PacketRecord;
+use steel_core::server::Server;
+use steel_protocol::packet_reader::TCPNetworkDecoder;
+use steel_protocol::utils::RawPacket;
+use steel_registry::packets::{CURRENT_MC_PROTOCOL, config, handshake, login, play};
+use steel_utils::BlockPos;
+use steel_utils::codec::VarInt;
+use steel_utils::direction::Direction;
+use steel_utils::locks::AsyncMutex;
+use steel_utils::serial::{PrefixedWrite, ReadFrom, WriteTo};
+use steel_utils::text::DisplayResolutor;
+use text_components::fmt::set_display_resolutor;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::runtime::Builder;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::time::{Instant, sleep, sleep_until, timeout};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use uuid::Uuid;
+
+/// How long a client waits for a packet or for the server to catch up.
+pub const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The handshake intent to log in.
+const LOGIN_INTENT: i32 = 2;
+
+/// Where the packets of [`RECORDED_PLAYERS`] are recorded, in the test directory.
+const RECORDINGS: &str = "recordings";
+
+/// The players whose packets the test server records.
+pub const RECORDED_PLAYERS: [&str; 1] = ["RecordTester"];
+
+/// The server shared by the tests of a binary.
+pub struct TestServer {
+    /// The server state, to set up scenarios and check their outcome.
+    pub server: Arc<Server>,
+    /// Where the server accepts connections.
+    pub address: SocketAddr,
+}
+
+static TEST_SERVER: OnceLock<TestServer> = OnceLock::new();
+
+/// Returns the shared server, starting it on first use.
+pub fn server() -> &'static TestServer {
+    TEST_SERVER.get_or_init(start_server)
+}
+
+/// Starts a server in a scratch directory, with a config for offline,
+/// uncompressed connections and a flat world kept in memory.
+fn start_server() -> TestServer {
+    let directory = env::temp_dir().join(format!("steel-tests-{}", process::id()));
+    fs::create_dir_all(directory.join("config")).expect("Failed to create the test directory");
+    fs::write(directory.join("config/steel_config.json5"), test_config())
+        .expect("Failed to write the test config");
+    // The config and player data are resolved relative to the working directory
+    env::set_current_dir(&directory).expect("Failed to enter the test directory");
+    set_display_resolutor(&DisplayResolutor);
+
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("test-server".to_owned())
+        // Same as the chunk stage tests, world generation recurses deeply
+        .stack_size(16 * 1024 * 1024)
+        .spawn(move || {
+            let chunk_runtime = Arc::new(
+                Builder::new_multi_thread()
+                    .worker_threads(2)
+                    .thread_name("chunk-worker")
+                    .enable_all()
+                    .build()
+                    .expect("Failed to build the chunk runtime"),
+            );
+            let main_runtime = Builder::new_multi_thread()
+                .worker_threads(2)
+                .thread_name("main-worker")
+                .enable_all()
+                .build()
+                .expect("Failed to build the main runtime");
+
+            main_runtime.block_on(async move {
+                let address = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+                let mut steel =
+                    SteelServer::bind(chunk_runtime, CancellationToken::new(), address).await;
+                let address = steel
+                    .tcp_listener
+                    .local_addr()
+                    .expect("The listener has an address");
+                sender
+                    .send(TestServer {
+                        server: steel.server.clone(),
+                        address,
+                    })
+                    .expect("The test is waiting for the server");
+                steel.start(TaskTracker::new()).await;
+            });
+        })
+        .expect("Failed to spawn the server thread");
+
+    receiver.recv().expect("The test server failed to start")
+}
+
+/// The default config, changed so a bare client can join and nothing outlives
+/// the test run.
+fn test_config() -> String {
+    let mut config: Value =
+        serde_json5::from_str(include_str!("../../package-content/steel_config.json5"))
+            .expect("The default config is valid");
+    config["seed"] = json!("steel-tests");
+    config["view_distance"] = json!(2);
+    config["simulation_distance"] = json!(2);
+    config["online_mode"] = json!(false);
+    config["encryption"] = json!(false);
+    config["compression"] = Value::Null;
+    config["use_favicon"] = json!(false);
+    config["world_generator"] = json!("flat");
+    config["world_storage_config"] = json!("ram_only");
+    config["watchdog"]["enabled"] = json!(false);
+    config["server_links"]["enable"] = json!(false);
+    config["chat_spam"]["enabled"] = json!(false);
+    // Players join floating above the flat world
+    config["movement_checks"]["allow_flight"] = json!(true);
+    config["packet_recording"] = json!({
+        "enabled": true,
+        "directory": RECORDINGS,
+        "players": RECORDED_PLAYERS,
+    });
+    config.to_string()
+}
+
+/// Waits until `condition` holds, checking every tick.
+///
+/// # Panics
+/// If it doesn't hold within [`TIMEOUT`].
+pub async fn wait_until(what: &str, mut condition: impl FnMut() -> bool) {
+    let result = timeout(TIMEOUT, async {
+        while !condition() {
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await;
+    assert!(result.is_ok(), "Timed out waiting until {what}");
+}
+
+/// Packets the client answers on its own, left out of replays so the server
+/// doesn't get them twice.
+const ANSWERED: [i32; 4] = [
+    play::S_ACCEPT_TELEPORTATION,
+    play::S_KEEP_ALIVE,
+    play::S_CHUNK_BATCH_RECEIVED,
+    play::S_PLAYER_LOADED,
+];
+
+/// A headless client speaking just enough of the protocol to join and play.
+///
+/// Serverbound packets are written by hand, since the protocol crate only
+/// reads them. Once playing, a background task reads the connection and
+/// answers keep alives, teleports and chunk batches right away, like the
+/// vanilla client does.
+pub struct TestClient {
+    /// The player name the client joined with.
+    pub name: String,
+    writer: Arc<AsyncMutex<OwnedWriteHalf>>,
+    packets: UnboundedReceiver<RawPacket>,
+    sequence: i32,
+}
+
+impl TestClient {
+    /// Connects to the shared server and joins as `name`, going through the
+    /// handshake, an offline login and the configuration phase.
+    ///
+    /// # Panics
+    /// If the server doesn't let the client in.
+    pub async fn join(name: &str) -> Self {
+        let address = server().address;
+        let stream = TcpStream::connect(address)
+            .await
+            .expect("Failed to connect to the test server");
+        stream.set_nodelay(true).expect("Failed to set TCP_NODELAY");
+        let (reader, writer) = stream.into_split();
+        let mut reader = TCPNetworkDecoder::new(reader);
+        let writer = Arc::new(AsyncMutex::new(writer));
+
+        write_packet(&writer, handshake::S_INTENTION, |buf| {
+            VarInt(CURRENT_MC_PROTOCOL).write(buf)?;
+            "localhost".write_prefixed::<VarInt>(buf)?;
+            address.port().write(buf)?;
+            VarInt(LOGIN_INTENT).write(buf)
+        })
+        .await;
+        write_packet(&writer, login::S_HELLO, |buf| {
+            name.write_prefixed::<VarInt>(buf)?;
+            Uuid::nil().write(buf)
+        })
+        .await;
+        expect_before_play(&mut reader, login::C_LOGIN_FINISHED).await;
+        write_packet(&writer, login::S_LOGIN_ACKNOWLEDGED, |_| Ok(())).await;
+
+        expect_before_play(&mut reader, config::C_SELECT_KNOWN_PACKS).await;
+        // No known packs, so the server sends the registries in full
+        write_packet(&writer, config::S_SELECT_KNOWN_PACKS, |buf| {
+            VarInt(0).write(buf)
+        })
+        .await;
+        expect_before_play(&mut reader, config::C_FINISH_CONFIGURATION).await;
+        write_packet(&writer, config::S_FINISH_CONFIGURATION, |_| Ok(())).await;
+
+        let (sender, packets) = unbounded_channel();
+        tokio::spawn(read_play_packets(reader, Arc::clone(&writer), sender));
+        let mut client = Self {
+            name: name.to_owned(),
+            writer,
+            packets,
+            sequence: 0,
+        };
+        client.expect_packet(play::C_LOGIN).await;
+        client.expect_packet(play::C_PLAYER_POSITION).await;
+        client.send(play::S_PLAYER_LOADED, |_| Ok(())).await;
+        client
+    }
+
+    /// The server side of this client's player.
+    ///
+    /// # Panics
+    /// If the player isn't on the server.
+    #[must_use]
+    pub fn player(&self) -> Arc<Player> {
+        server()
+            .server
+            .get_players()
+            .into_iter()
+            .find(|player| player.gameprofile.name == self.name)
+            .unwrap_or_else(|| panic!("{} isn't on the server", self.name))
+    }
+
+    /// Sends a play packet, with `write` filling in its fields.
+    ///
+    /// # Panics
+    /// If the connection is closed.
+    pub async fn send(&self, id: i32, write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) {
+        write_packet(&self.writer, id, write).await;
+    }
+
+    /// Sends a chat message, unsigned and acknowledging no messages.
+    pub async fn chat(&self, message: &str) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("The clock is after the epoch")
+            .as_millis() as i64;
+        self.send(play::S_CHAT, |buf| {
+            message.write_prefixed::<VarInt>(buf)?;
+            timestamp.write(buf)?;
+            // Salt
+            0i64.write(buf)?;
+            // No signature
+            false.write(buf)?;
+            // Offset, acknowledged messages and checksum
+            VarInt(0).write(buf)?;
+            [0u8; 3].iter().try_for_each(|byte| byte.write(buf))?;
+            0u8.write(buf)
+        })
+        .await;
+    }
+
+    /// Moves the player to `position`, in the air.
+    pub async fn move_to(&self, position: DVec3) {
+        self.send(play::S_MOVE_PLAYER_POS, |buf| {
+            position.write(buf)?;
+            0u8.write(buf)
+        })
+        .await;
+    }
+
+    /// Right clicks the center of the `face` of the block at `pos` with the
+    /// main hand.
+    pub async fn use_item_on(&mut self, pos: BlockPos, face: Direction) {
+        self.sequence += 1;
+        let sequence = self.sequence;
+        let (x, y, z): (f32, f32, f32) = match face {
+            Direction::Down => (0.5, 0.0, 0.5),
+            Direction::Up => (0.5, 1.0, 0.5),
+            Direction::North => (0.5, 0.5, 0.0),
+            Direction::South => (0.5, 0.5, 1.0),
+            Direction::West => (0.0, 0.5, 0.5),
+            Direction::East => (1.0, 0.5, 0.5),
+        };
+        self.send(play::S_USE_ITEM_ON, |buf| {
+            // Main hand
+            VarInt(0).write(buf)?;
+            pos.write(buf)?;
+            VarInt(face as i32).write(buf)?;
+            x.write(buf)?;
+            y.write(buf)?;
+            z.write(buf)?;
+            // Not inside the block, not hitting the world border
+            false.write(buf)?;
+            false.write(buf)?;
+            VarInt(sequence).write(buf)
+        })
+        .await;
+    }
+
+    /// Sends the packets of a recording again, keeping the time between them.
+    /// The packets this client answers on its own are left out.
+    ///
+    /// # Panics
+    /// If a recorded payload isn't valid or the connection is closed.
+    pub async fn replay(&self, records: &[PacketRecord]) {
+        let first = records.first().map_or(0, |record| record.millis);
+        let started = Instant::now();
+        for record in records
+            .iter()
+            .filter(|record| !ANSWERED.contains(&record.id))
+        {
+            sleep_until(started + Duration::from_millis(record.millis.saturating_sub(first))).await;
+            let packet = record.raw().expect("Recorded payloads are base64");
+            self.send(packet.id, |buf| {
+                buf.extend_from_slice(&packet.payload);
+                Ok(())
+            })
+            .await;
+        }
+    }
+
+    /// Waits for the next packet with the given ID.
+    ///
+    /// # Panics
+    /// If none arrives within [`TIMEOUT`] or the connection is closed.
+    pub async fn expect_packet(&mut self, id: i32) -> RawPacket {
+        self.expect_packet_where(id, |_| true).await
+    }
+
+    /// Waits for the next packet with the given ID whose payload passes
+    /// `matches`.
+    ///
+    /// # Panics
+    /// If none arrives within [`TIMEOUT`] or the connection is closed.
+    pub async fn expect_packet_where(
+        &mut self,
+        id: i32,
+        matches: impl Fn(&[u8]) -> bool,
+    ) -> RawPacket {
+        let result = timeout(TIMEOUT, async {
+            while let Some(packet) = self.packets.recv().await {
+                if packet.id == id && matches(&packet.payload) {
+                    return Some(packet);
+                }
+            }
+            None
+        })
+        .await;
+        match result {
+            Ok(Some(packet)) => packet,
+            Ok(None) => panic!("{} lost the connection", self.name),
+            Err(_) => panic!("{} timed out waiting for packet {id:#04x}", self.name),
+        }
+    }
+}
+
+/// Sends a packet, with `write` filling in its fields.
+async fn write_packet(
+    writer: &AsyncMutex<OwnedWriteHalf>,
+    id: i32,
+    write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>,
+) {
+    let mut body = Vec::new();
+    VarInt(id)
+        .write(&mut body)
+        .and_then(|()| write(&mut body))
+        .expect("Writing to a Vec can't fail");
+    let mut frame = Vec::with_capacity(body.len() + 5);
+    VarInt(body.len() as i32)
+        .write(&mut frame)
+        .expect("Writing to a Vec can't fail");
+    frame.extend(body);
+    writer
+        .lock()
+        .await
+        .write_all(&frame)
+        .await
+        .expect("The server closed the connection");
+}
+
+/// Waits for a login or configuration packet, skipping the others.
+async fn expect_before_play(reader: &mut TCPNetworkDecoder<OwnedReadHalf>, id: i32) {
+    let result = timeout(TIMEOUT, async {
+        loop {
+            let packet = reader
+                .get_raw_packet()
+                .await
+                .expect("The server closed the connection while joining");
+            if packet.id == id {
+                return;
+            }
+        }
+    })
+    .await;
+    assert!(result.is_ok(), "Timed out waiting for packet {id:#04x}");
+}
+
+/// Reads play packets until the connection closes, answering the ones the
+/// vanilla client answers on its own and passing all of them on.
+async fn read_play_packets(
+    mut reader: TCPNetworkDecoder<OwnedReadHalf>,
+    writer: Arc<AsyncMutex<OwnedWriteHalf>>,
+    sender: UnboundedSender<RawPacket>,
+) {
+    while let Ok(packet) = reader.get_raw_packet().await {
+        let data = &mut Cursor::new(packet.payload.as_slice());
+        match packet.id {
+            play::C_KEEP_ALIVE => {
+                let id = i64::read(data).expect("Keep alives have an ID");
+                write_packet(&writer, play::S_KEEP_ALIVE, |buf| id.write(buf)).await;
+            }
+            play::C_PLAYER_POSITION => {
+                let id = VarInt::read(data).expect("Teleports have an ID").0;
+                write_packet(&writer, play::S_ACCEPT_TELEPORTATION, |buf| {
+                    VarInt(id).write(buf)
+                })
+                .await;
+            }
+            play::C_CHUNK_BATCH_FINISHED => {
+                // Chunks per tick the client wants, plenty for a test
+                write_packet(&writer, play::S_CHUNK_BATCH_RECEIVED, |buf| {
+                    64.0f32.write(buf)
+                })
+                .await;
+            }
+            _ => {}
+        }
+        if sender.send(packet).is_err() {
+            break;
+        }
+    }
+}
+
+/// The recording of the player `name`, with the test config recording them.
+///
+/// # Panics
+/// If there is no recording of the player.
+#[must_use]
+pub fn recording_of(name: &str) -> PathBuf {
+    let prefix = format!("{name}_");
+    fs::read_dir(RECORDINGS)
+        .expect("The recordings directory exists")
+        .map(|entry| entry.expect("The recordings directory is readable").path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|file| file.to_str())
+                .is_some_and(|file| file.starts_with(&prefix))
+        })
+        .unwrap_or_else(|| panic!("{name} wasn't recorded"))
+}
+
+/// Returns true if `haystack` contains `needle`, for finding text in payloads
+/// without decoding them.
+#[must_use]
+pub fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
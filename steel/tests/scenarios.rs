@@ -0,0 +1,144 @@
+//! End-to-end scenarios, run by headless clients against a real server
+
+mod common;
+
+use common::{RECORDED_PLAYERS, TestClient, contains_bytes, recording_of, wait_until};
+use glam::DVec3;
+use steel_core::entity::Entity;
+use steel_core::player::packet_recorder::read_recording;
+use steel_registry::item_stack::ItemStack;
+use steel_registry::packets::play;
+use steel_registry::{REGISTRY, vanilla_blocks, vanilla_items};
+use steel_utils::direction::Direction;
+use steel_utils::types::{GameType, UpdateFlags};
+use steel_utils::{BlockPos, BlockStateId};
+
+#[tokio::test]
+async fn join_adds_the_player() {
+    let client = TestClient::join("JoinTester").await;
+
+    let player = client.player();
+    assert_eq!(player.gameprofile.name, "JoinTester");
+}
+
+#[tokio::test]
+async fn movement_updates_the_server_position() {
+    let client = TestClient::join("MoveTester").await;
+    let player = client.player();
+
+    let target = player.position() + DVec3::new(0.5, 0.0, -0.25);
+    client.move_to(target).await;
+
+    wait_until("the player has moved", || {
+        player.position().distance_squared(target) < 1.0e-6
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn chat_reaches_other_players() {
+    let sender = TestClient::join("ChatSender").await;
+    let mut receiver = TestClient::join("ChatReceiver").await;
+
+    let message = "hello from the test harness";
+    sender.chat(message).await;
+
+    receiver
+        .expect_packet_where(play::C_PLAYER_CHAT, |payload| {
+            contains_bytes(payload, message.as_bytes())
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn placing_a_block_changes_the_world() {
+    let stone = REGISTRY.blocks.get_default_state_id(vanilla_blocks::STONE);
+    let mut client = TestClient::join("PlaceTester").await;
+    let player = client.player();
+    player.set_game_mode(GameType::Creative);
+    player
+        .inventory
+        .lock()
+        .set_selected_item(ItemStack::new(&vanilla_items::ITEMS.stone));
+
+    // Players join above the ground, so give them something to build on
+    let feet = BlockPos::from(player.position());
+    let support = BlockPos::new(feet.x() + 2, feet.y() - 1, feet.z());
+    let world = player.world.clone();
+    wait_until("the chunk is loaded", || {
+        world.set_block(support, stone, UpdateFlags::UPDATE_ALL)
+    })
+    .await;
+
+    client.use_item_on(support, Direction::Up).await;
+
+    let placed = BlockPos::new(support.x(), support.y() + 1, support.z());
+    client
+        .expect_packet_where(play::C_BLOCK_UPDATE, |payload| {
+            block_update(payload) == (placed, stone)
+        })
+        .await;
+    assert_eq!(world.get_block_state(placed), stone);
+}
+
+#[tokio::test]
+async fn replaying_a_recording_repeats_the_session() {
+    let name = RECORDED_PLAYERS[0];
+    let recorded = TestClient::join(name).await;
+    let player = recorded.player();
+    let target = player.position() + DVec3::new(-0.5, 0.0, 0.75);
+    recorded.move_to(target).await;
+    wait_until("the recorded player has moved", || {
+        player.position().distance_squared(target) < 1.0e-6
+    })
+    .await;
+    drop(recorded);
+
+    let records = read_recording(&recording_of(name)).expect("The recording is readable");
+    assert!(
+        records
+            .iter()
+            .any(|record| record.id == play::S_MOVE_PLAYER_POS && record.decoded.is_some())
+    );
+
+    let replayer = TestClient::join("ReplayTester").await;
+    replayer.replay(&records).await;
+    let player = replayer.player();
+    wait_until("the replayed movement arrived", || {
+        player.position().distance_squared(target) < 1.0e-6
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn world_simulation_distance_reaches_the_client() {
+    let mut client = TestClient::join("SimulationTester").await;
+    let player = client.player();
+    let world = player.world.clone();
+
+    world
+        .set_simulation_distance(Some(1))
+        .expect("1 is within the view distance");
+    client
+        .expect_packet_where(play::C_SET_SIMULATION_DISTANCE, |payload| payload == [1])
+        .await;
+    assert_eq!(player.simulation_distance(), 1);
+
+    world
+        .set_simulation_distance(None)
+        .expect("The server simulation distance is always valid");
+    assert_eq!(player.simulation_distance(), 2);
+}
+
+/// Reads the position and state of a block update.
+fn block_update(payload: &[u8]) -> (BlockPos, BlockStateId) {
+    use std::io::Cursor;
+
+    use steel_utils::codec::VarInt;
+    use steel_utils::serial::ReadFrom;
+
+    let data = &mut Cursor::new(payload);
+    let pos = BlockPos::read(data).expect("Block updates have a position");
+    let state = VarInt::read(data).expect("Block updates have a state").0;
+    (pos, BlockStateId(state as u16))
+}